@@ -0,0 +1,54 @@
+// Argument lists taken from the VM's pool next to lists allocated per call,
+// filled and emptied the way a call with a few arguments uses them
+//
+// Run with `cargo bench --bench argument_buffers`.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use metorex::object::Object;
+use metorex::vm::ArgumentBuffers;
+use std::hint::black_box;
+
+const CALLS: usize = 10_000;
+
+/// One call's arguments: `count` values, one of them a String
+fn fill(list: &mut Vec<Object>, count: usize, text: &Object) {
+    for i in 0..count {
+        list.push(if i == 0 {
+            text.clone()
+        } else {
+            Object::Int(i as i64)
+        });
+    }
+}
+
+fn bench_argument_buffers(c: &mut Criterion) {
+    let text = Object::string("hello");
+    let mut group = c.benchmark_group("argument_lists_10k");
+    for count in [0, 1, 3] {
+        group.bench_function(format!("pooled, {count} arguments"), |b| {
+            let mut buffers = ArgumentBuffers::default();
+            b.iter(|| {
+                for _ in 0..CALLS {
+                    // Room for a trailing block, as evaluate_arguments asks for
+                    let mut list = buffers.take(count + 1);
+                    fill(&mut list, count, &text);
+                    black_box(&list);
+                    buffers.give_back(list);
+                }
+            })
+        });
+        group.bench_function(format!("allocated, {count} arguments"), |b| {
+            b.iter(|| {
+                for _ in 0..CALLS {
+                    let mut list = Vec::with_capacity(count + 1);
+                    fill(&mut list, count, &text);
+                    black_box(&list);
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_argument_buffers);
+criterion_main!(benches);
@@ -0,0 +1,59 @@
+// Array#sort and Array#sort_by on large arrays mixing Int and Float values
+//
+// Run with `cargo bench --bench array_sort`.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use metorex::ast::Statement;
+use metorex::lexer::Lexer;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+
+const SIZE: usize = 10_000;
+
+fn parse(source: &str) -> Vec<Statement> {
+    Parser::new(Lexer::new(source).tokenize())
+        .parse()
+        .expect("benchmark script should parse")
+}
+
+/// A VM holding `values`, a shuffled array alternating Ints and Floats
+fn vm_with_values() -> VirtualMachine {
+    let setup = format!(
+        "values = []
+i = 0
+while i < {SIZE}
+  if i % 2 == 0
+    values.push((i * 7919) % 10007)
+  else
+    values.push(((i * 104729) % 10007) + 0.5)
+  end
+  i += 1
+end"
+    );
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&parse(&setup))
+        .expect("benchmark setup should run");
+    vm
+}
+
+fn bench_sort(c: &mut Criterion) {
+    let cases = [
+        ("sort natural ordering", "sorted = values.sort"),
+        ("sort with block", "sorted = values.sort { |a, b| a <=> b }"),
+        ("sort_by key", "sorted = values.sort_by { |v| 0 - v }"),
+    ];
+
+    let mut group = c.benchmark_group("array_sort_mixed_10k");
+    group.sample_size(10);
+    for (name, source) in cases {
+        let mut vm = vm_with_values();
+        let program = parse(source);
+        group.bench_function(name, |b| {
+            b.iter(|| vm.execute_program(&program).expect("sort should succeed"))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_sort);
+criterion_main!(benches);
@@ -0,0 +1,52 @@
+// Calls to builtin methods, whose arguments are checked against the catalog
+// signature on every call, next to a loop making no calls
+//
+// Run with `cargo bench --bench native_calls`.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use metorex::ast::Statement;
+use metorex::lexer::Lexer;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+
+const SIZE: usize = 20_000;
+
+fn parse(source: &str) -> Vec<Statement> {
+    Parser::new(Lexer::new(source).tokenize())
+        .parse()
+        .expect("benchmark script should parse")
+}
+
+fn bench_native_calls(c: &mut Criterion) {
+    let cases = [
+        ("plain loop", String::new()),
+        ("no arguments", "n = s.length".to_string()),
+        ("typed argument", "n = s.chomp(\"o\")".to_string()),
+        ("inherited method", "n = s.object_id".to_string()),
+    ];
+
+    let mut group = c.benchmark_group("native_calls_20k");
+    group.sample_size(10);
+    for (name, call) in cases {
+        let source = format!(
+            "s = \"hello\"
+i = 0
+while i < {SIZE}
+  {call}
+  i += 1
+end"
+        );
+        let mut vm = VirtualMachine::new();
+        let program = parse(&source);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                vm.execute_program(&program)
+                    .expect("native calls should succeed")
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_native_calls);
+criterion_main!(benches);
@@ -0,0 +1,74 @@
+// Building a long String by `+` accumulation, Array#join and String.build
+//
+// Run with `cargo bench --bench string_building`.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use metorex::ast::Statement;
+use metorex::lexer::Lexer;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+
+const SIZE: usize = 5_000;
+
+fn parse(source: &str) -> Vec<Statement> {
+    Parser::new(Lexer::new(source).tokenize())
+        .parse()
+        .expect("benchmark script should parse")
+}
+
+fn bench_string_building(c: &mut Criterion) {
+    let cases = [
+        (
+            "plus accumulation",
+            format!(
+                "text = \"\"
+i = 0
+while i < {SIZE}
+  text = text + \"line \" + i.to_s + \"\\n\"
+  i += 1
+end"
+            ),
+        ),
+        (
+            "Array#join",
+            format!(
+                "parts = []
+i = 0
+while i < {SIZE}
+  parts << \"line \" + i.to_s
+  i += 1
+end
+text = parts.join(\"\\n\")"
+            ),
+        ),
+        (
+            "String.build",
+            format!(
+                "text = String.build do |s|
+  i = 0
+  while i < {SIZE}
+    s << \"line \" << i << \"\\n\"
+    i += 1
+  end
+end"
+            ),
+        ),
+    ];
+
+    let mut group = c.benchmark_group("string_building_5k");
+    group.sample_size(10);
+    for (name, source) in cases {
+        let mut vm = VirtualMachine::new();
+        let program = parse(&source);
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                vm.execute_program(&program)
+                    .expect("string building should succeed")
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_string_building);
+criterion_main!(benches);
@@ -0,0 +1,25 @@
+// Build script
+// Hashes the files that define the shape of a cached AST, so the AST cache
+// can tell entries written by a build whose AST types differ from its own.
+
+use std::fs;
+
+/// Files whose types are serialized into AST cache entries
+const AST_SCHEMA_FILES: &[&str] = &[
+    "src/ast/node.rs",
+    "src/lexer/token.rs",
+    "src/lexer/interner.rs",
+];
+
+fn main() {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for file in AST_SCHEMA_FILES {
+        println!("cargo:rerun-if-changed={}", file);
+        let source = fs::read(file).unwrap_or_else(|e| panic!("cannot read {}: {}", file, e));
+        for byte in source {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    println!("cargo:rustc-env=METOREX_AST_SCHEMA_HASH={:016x}", hash);
+}
@@ -1,8 +1,12 @@
 // Abstract Syntax Tree module for Metorex
 
 pub mod node;
+mod positions;
+pub mod visitor;
 
 pub use node::{
-    BinaryOp, ElsifBranch, Expression, InterpolationPart, MatchCase, MatchPattern, Parameter,
-    RescueClause, Statement, UnaryOp,
+    BinaryOp, DestructureTarget, DestructuredParameter, ElsifBranch, Expression,
+    FORWARDING_PARAMETER, InterpolationPart, MatchCase, MatchPattern, Parameter, RescueClause,
+    Statement, TypeAnnotation, UnaryOp,
 };
+pub use visitor::{Visitor, walk_expression, walk_statement, walk_statements};
@@ -1,10 +1,11 @@
 // AST node definitions for Metorex
 
 use crate::lexer::Position;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Binary operators in Metorex
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum BinaryOp {
     // Arithmetic operators
     Add,      // +
@@ -16,10 +17,20 @@ pub enum BinaryOp {
     // Comparison operators
     Equal,        // ==
     NotEqual,     // !=
+    CaseEqual,    // ===
     Less,         // <
     Greater,      // >
     LessEqual,    // <=
     GreaterEqual, // >=
+    Spaceship,    // <=>
+
+    // Appending: `array << item`, `builder << text`
+    ShiftLeft, // <<
+
+    // Logical operators, which only evaluate the right side when the left
+    // does not decide the result; `and` and `or` are looser spellings
+    And, // &&, and
+    Or,  // ||, or
 
     // Assignment operators
     Assign,         // =
@@ -30,14 +41,15 @@ pub enum BinaryOp {
 }
 
 /// Unary operators in Metorex
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum UnaryOp {
     Plus,  // +
     Minus, // -
+    Not,   // !, not
 }
 
 /// Expressions in Metorex - values that can be evaluated
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Expression {
     // Literals
     IntLiteral {
@@ -140,6 +152,8 @@ pub enum Expression {
         parameters: Vec<String>,
         body: Vec<Statement>,
         captured_vars: Option<Vec<String>>, // Variables captured from outer scope
+        #[serde(default)]
+        destructured: Vec<DestructuredParameter>, // Parameters written as `(a, b)`
         position: Position,
     },
 
@@ -160,6 +174,12 @@ pub enum Expression {
         position: Position,
     },
 
+    // Forwarded arguments (`...` inside an argument list), splices in every
+    // argument captured by the enclosing method's `...` parameter
+    ForwardedArgs {
+        position: Position,
+    },
+
     // Range literals
     Range {
         start: Box<Expression>,
@@ -178,17 +198,33 @@ pub enum Expression {
         else_case: Option<Box<Expression>>, // Optional else branch
         position: Position,
     },
+
+    // Rescue modifier (`value rescue fallback`), evaluates the fallback when
+    // the expression raises a StandardError
+    RescueModifier {
+        expression: Box<Expression>,
+        fallback: Box<Expression>,
+        position: Position,
+    },
+
+    // Begin block used as a value (`x = begin ... rescue ... end`), yielding
+    // the last value of the body, rescue clause or else clause that ran.
+    // The block is always a Statement::Begin
+    Begin {
+        block: Box<Statement>,
+        position: Position,
+    },
 }
 
 /// Parts of an interpolated string
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum InterpolationPart {
     Text(String),
     Expression(Box<Expression>),
 }
 
 /// Pattern for match statement cases
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum MatchPattern {
     // Literal patterns
     IntLiteral(i64),
@@ -214,10 +250,13 @@ pub enum MatchPattern {
 
     // Type pattern (for future use)
     Type(String),
+
+    // Shell-style glob pattern, written glob("*.rb"), matching Strings
+    Glob(String),
 }
 
 /// A single case in a match statement
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MatchCase {
     pub pattern: MatchPattern,
     pub guard: Option<Expression>, // Optional guard condition (if ...)
@@ -228,7 +267,7 @@ pub struct MatchCase {
 /// A single case in a case expression (expression context)
 /// Unlike MatchCase which uses Vec<Statement> for body,
 /// ExprMatchCase uses a single Expression for the body.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ExprMatchCase {
     pub pattern: MatchPattern,
     pub guard: Option<Expression>, // Optional guard condition (if ...)
@@ -237,7 +276,7 @@ pub struct ExprMatchCase {
 }
 
 /// A rescue clause in a begin/rescue/ensure block
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RescueClause {
     pub exception_types: Vec<String>, // Exception types to catch (empty means catch all)
     pub variable_name: Option<String>, // Variable to bind the exception to (e.g., "=> e")
@@ -246,21 +285,75 @@ pub struct RescueClause {
 }
 
 /// An elsif branch in an if statement
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ElsifBranch {
     pub condition: Expression,
     pub body: Vec<Statement>,
     pub position: Position,
 }
 
+/// A block parameter written in parentheses, as in `|(key, value), index|`.
+/// Its slot in the parameter list holds the pattern's text, such as
+/// `(key, value)`, and the array passed for it is unpacked into the targets.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DestructuredParameter {
+    pub index: usize, // Position of the parameter in the block's parameter list
+    pub targets: Vec<DestructureTarget>,
+}
+
+/// Where one element of a destructured block parameter is bound
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DestructureTarget {
+    Name(String),
+    Nested(Vec<DestructureTarget>), // `(a, (b, c))` unpacks the element again
+}
+
+impl DestructureTarget {
+    /// Every name bound by these targets, in order
+    pub fn names(targets: &[DestructureTarget]) -> Vec<&str> {
+        targets
+            .iter()
+            .flat_map(|target| match target {
+                DestructureTarget::Name(name) => vec![name.as_str()],
+                DestructureTarget::Nested(inner) => DestructureTarget::names(inner),
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for DestructureTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DestructureTarget::Name(name) => write!(f, "{}", name),
+            DestructureTarget::Nested(targets) => write_target_list(f, targets),
+        }
+    }
+}
+
+impl fmt::Display for DestructuredParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_target_list(f, &self.targets)
+    }
+}
+
+/// Write targets as they appear in source, `(a, (b, c))`
+fn write_target_list(f: &mut fmt::Formatter<'_>, targets: &[DestructureTarget]) -> fmt::Result {
+    let parts: Vec<String> = targets.iter().map(|target| target.to_string()).collect();
+    write!(f, "({})", parts.join(", "))
+}
+
+/// Name under which a `...` parameter is bound in the callee scope
+pub const FORWARDING_PARAMETER: &str = "...";
+
 /// Function parameter definition
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Parameter {
     pub name: String,
     pub default_value: Option<Expression>, // Default value for the parameter
     pub is_variadic: bool,                 // True if this is a *args parameter
     pub is_keyword: bool,                  // True if this is a **kwargs parameter
     pub is_block: bool,                    // True if this is a &block parameter
+    pub type_annotation: Option<TypeAnnotation>, // Declared type (`x: Int`)
     pub position: Position,
 }
 
@@ -273,6 +366,7 @@ impl Parameter {
             is_variadic: false,
             is_keyword: false,
             is_block: false,
+            type_annotation: None,
             position,
         }
     }
@@ -285,6 +379,7 @@ impl Parameter {
             is_variadic: false,
             is_keyword: false,
             is_block: false,
+            type_annotation: None,
             position,
         }
     }
@@ -297,6 +392,7 @@ impl Parameter {
             is_variadic: true,
             is_keyword: false,
             is_block: false,
+            type_annotation: None,
             position,
         }
     }
@@ -309,6 +405,7 @@ impl Parameter {
             is_variadic: false,
             is_keyword: true,
             is_block: false,
+            type_annotation: None,
             position,
         }
     }
@@ -321,10 +418,21 @@ impl Parameter {
             is_variadic: false,
             is_keyword: false,
             is_block: true,
+            type_annotation: None,
             position,
         }
     }
 
+    /// Create a new argument forwarding parameter (...)
+    pub fn forwarding(position: Position) -> Self {
+        Parameter::variadic(FORWARDING_PARAMETER.to_string(), position)
+    }
+
+    /// Check if this is an argument forwarding parameter (...)
+    pub fn is_forwarding(&self) -> bool {
+        self.is_variadic && self.name == FORWARDING_PARAMETER
+    }
+
     /// Check if this is a simple parameter (no default, not variadic/keyword/block)
     pub fn is_simple(&self) -> bool {
         self.default_value.is_none() && !self.is_variadic && !self.is_keyword && !self.is_block
@@ -334,10 +442,49 @@ impl Parameter {
     pub fn has_default(&self) -> bool {
         self.default_value.is_some()
     }
+
+    /// Attach a declared type to this parameter
+    pub fn with_type(mut self, annotation: TypeAnnotation) -> Self {
+        self.type_annotation = Some(annotation);
+        self
+    }
+}
+
+/// An optional type written on a parameter (`x: Int`) or after a method's
+/// parameter list (`-> Int`). A trailing `?` also accepts nil (`String?`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeAnnotation {
+    pub name: String,
+    pub nilable: bool,
+    pub position: Position,
+}
+
+impl TypeAnnotation {
+    /// Create an annotation from its written form, e.g. `Int` or `String?`
+    pub fn parse(written: &str, position: Position) -> Self {
+        match written.strip_suffix('?') {
+            Some(name) => TypeAnnotation {
+                name: name.to_string(),
+                nilable: true,
+                position,
+            },
+            None => TypeAnnotation {
+                name: written.to_string(),
+                nilable: false,
+                position,
+            },
+        }
+    }
+}
+
+impl fmt::Display for TypeAnnotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.name, if self.nilable { "?" } else { "" })
+    }
 }
 
 /// Statements in Metorex - instructions that can be executed
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Statement {
     // Expression statement (an expression used as a statement)
     Expression {
@@ -358,6 +505,7 @@ pub enum Statement {
         parameters: Vec<Parameter>,
         body: Vec<Statement>,
         position: Position,
+        return_type: Option<TypeAnnotation>, // Declared result type (`-> Int`)
     },
 
     // Method definition (function within a class)
@@ -366,6 +514,7 @@ pub enum Statement {
         parameters: Vec<Parameter>,
         body: Vec<Statement>,
         position: Position,
+        return_type: Option<TypeAnnotation>, // Declared result type (`-> Int`)
     },
 
     // Class definition
@@ -376,6 +525,13 @@ pub enum Statement {
         position: Position,
     },
 
+    // Module definition (methods for classes to mix in)
+    ModuleDef {
+        name: String,
+        body: Vec<Statement>,
+        position: Position,
+    },
+
     // Conditional statements
     If {
         condition: Expression,
@@ -449,6 +605,12 @@ pub enum Statement {
     // Raise statement (throw exception)
     Raise {
         exception: Option<Expression>, // None means re-raise current exception
+        cause: Option<Expression>,     // Explicit cause from `raise e2 from e1`
+        position: Position,
+    },
+
+    // Retry statement (re-run the begin body from a rescue clause)
+    Retry {
         position: Position,
     },
 
@@ -482,10 +644,15 @@ impl fmt::Display for BinaryOp {
             BinaryOp::Modulo => write!(f, "%"),
             BinaryOp::Equal => write!(f, "=="),
             BinaryOp::NotEqual => write!(f, "!="),
+            BinaryOp::CaseEqual => write!(f, "==="),
             BinaryOp::Less => write!(f, "<"),
             BinaryOp::Greater => write!(f, ">"),
             BinaryOp::LessEqual => write!(f, "<="),
             BinaryOp::GreaterEqual => write!(f, ">="),
+            BinaryOp::Spaceship => write!(f, "<=>"),
+            BinaryOp::ShiftLeft => write!(f, "<<"),
+            BinaryOp::And => write!(f, "&&"),
+            BinaryOp::Or => write!(f, "||"),
             BinaryOp::Assign => write!(f, "="),
             BinaryOp::AddAssign => write!(f, "+="),
             BinaryOp::SubtractAssign => write!(f, "-="),
@@ -501,6 +668,7 @@ impl fmt::Display for UnaryOp {
         match self {
             UnaryOp::Plus => write!(f, "+"),
             UnaryOp::Minus => write!(f, "-"),
+            UnaryOp::Not => write!(f, "!"),
         }
     }
 }
@@ -531,8 +699,11 @@ impl Expression {
             | Expression::Grouped { position, .. }
             | Expression::SelfExpr { position, .. }
             | Expression::Super { position, .. }
+            | Expression::ForwardedArgs { position, .. }
             | Expression::Range { position, .. }
-            | Expression::Case { position, .. } => *position,
+            | Expression::Case { position, .. }
+            | Expression::RescueModifier { position, .. }
+            | Expression::Begin { position, .. } => *position,
         }
     }
 
@@ -570,6 +741,7 @@ impl Statement {
             | Statement::FunctionDef { position, .. }
             | Statement::MethodDef { position, .. }
             | Statement::ClassDef { position, .. }
+            | Statement::ModuleDef { position, .. }
             | Statement::If { position, .. }
             | Statement::Unless { position, .. }
             | Statement::While { position, .. }
@@ -581,19 +753,21 @@ impl Statement {
             | Statement::Block { position, .. }
             | Statement::Begin { position, .. }
             | Statement::Raise { position, .. }
+            | Statement::Retry { position, .. }
             | Statement::AttrReader { position, .. }
             | Statement::AttrWriter { position, .. }
             | Statement::AttrAccessor { position, .. } => *position,
         }
     }
 
-    /// Check if this statement is a definition (function, method, or class)
+    /// Check if this statement is a definition (function, method, class, or module)
     pub fn is_definition(&self) -> bool {
         matches!(
             self,
             Statement::FunctionDef { .. }
                 | Statement::MethodDef { .. }
                 | Statement::ClassDef { .. }
+                | Statement::ModuleDef { .. }
         )
     }
 
@@ -610,6 +784,7 @@ impl Statement {
                 | Statement::Continue { .. }
                 | Statement::Begin { .. }
                 | Statement::Raise { .. }
+                | Statement::Retry { .. }
         )
     }
 }
@@ -0,0 +1,339 @@
+// Walking every source position in a syntax tree
+// Used to move the nodes of a statement after the text before it was edited,
+// so a document can keep statements it did not need to parse again.
+
+use super::node::{
+    ElsifBranch, ExprMatchCase, Expression, InterpolationPart, MatchCase, Parameter, RescueClause,
+    Statement,
+};
+use crate::lexer::Position;
+
+impl Statement {
+    /// Call `f` with every position in this statement and the nodes inside it
+    pub fn for_each_position_mut(&mut self, f: &mut dyn FnMut(&mut Position)) {
+        match self {
+            Statement::Expression {
+                expression,
+                position,
+            } => {
+                f(position);
+                expression.for_each_position_mut(f);
+            }
+            Statement::Assignment {
+                target,
+                value,
+                position,
+            } => {
+                f(position);
+                target.for_each_position_mut(f);
+                value.for_each_position_mut(f);
+            }
+            Statement::FunctionDef {
+                parameters,
+                body,
+                position,
+                return_type,
+                ..
+            }
+            | Statement::MethodDef {
+                parameters,
+                body,
+                position,
+                return_type,
+                ..
+            } => {
+                f(position);
+                parameters_positions(parameters, f);
+                statements_positions(body, f);
+                if let Some(return_type) = return_type {
+                    f(&mut return_type.position);
+                }
+            }
+            Statement::ClassDef { body, position, .. }
+            | Statement::ModuleDef { body, position, .. }
+            | Statement::Block {
+                statements: body,
+                position,
+            } => {
+                f(position);
+                statements_positions(body, f);
+            }
+            Statement::If {
+                condition,
+                then_branch,
+                elsif_branches,
+                else_branch,
+                position,
+            } => {
+                f(position);
+                condition.for_each_position_mut(f);
+                statements_positions(then_branch, f);
+                for ElsifBranch {
+                    condition,
+                    body,
+                    position,
+                } in elsif_branches
+                {
+                    f(position);
+                    condition.for_each_position_mut(f);
+                    statements_positions(body, f);
+                }
+                if let Some(else_branch) = else_branch {
+                    statements_positions(else_branch, f);
+                }
+            }
+            Statement::Unless {
+                condition,
+                then_branch,
+                else_branch,
+                position,
+            } => {
+                f(position);
+                condition.for_each_position_mut(f);
+                statements_positions(then_branch, f);
+                if let Some(else_branch) = else_branch {
+                    statements_positions(else_branch, f);
+                }
+            }
+            Statement::While {
+                condition,
+                body,
+                position,
+            } => {
+                f(position);
+                condition.for_each_position_mut(f);
+                statements_positions(body, f);
+            }
+            Statement::For {
+                iterable,
+                body,
+                position,
+                ..
+            } => {
+                f(position);
+                iterable.for_each_position_mut(f);
+                statements_positions(body, f);
+            }
+            Statement::Match {
+                expression,
+                cases,
+                position,
+            } => {
+                f(position);
+                expression.for_each_position_mut(f);
+                for MatchCase {
+                    guard,
+                    body,
+                    position,
+                    ..
+                } in cases
+                {
+                    f(position);
+                    if let Some(guard) = guard {
+                        guard.for_each_position_mut(f);
+                    }
+                    statements_positions(body, f);
+                }
+            }
+            Statement::Return { value, position } => {
+                f(position);
+                if let Some(value) = value {
+                    value.for_each_position_mut(f);
+                }
+            }
+            Statement::Begin {
+                body,
+                rescue_clauses,
+                else_clause,
+                ensure_block,
+                position,
+            } => {
+                f(position);
+                statements_positions(body, f);
+                for RescueClause { body, position, .. } in rescue_clauses {
+                    f(position);
+                    statements_positions(body, f);
+                }
+                for block in [else_clause, ensure_block].into_iter().flatten() {
+                    statements_positions(block, f);
+                }
+            }
+            Statement::Raise {
+                exception,
+                cause,
+                position,
+            } => {
+                f(position);
+                for expression in [exception, cause].into_iter().flatten() {
+                    expression.for_each_position_mut(f);
+                }
+            }
+            Statement::Break { position }
+            | Statement::Continue { position }
+            | Statement::Retry { position }
+            | Statement::AttrReader { position, .. }
+            | Statement::AttrWriter { position, .. }
+            | Statement::AttrAccessor { position, .. } => f(position),
+        }
+    }
+}
+
+impl Expression {
+    /// Call `f` with every position in this expression and the nodes inside it
+    pub fn for_each_position_mut(&mut self, f: &mut dyn FnMut(&mut Position)) {
+        match self {
+            Expression::IntLiteral { position, .. }
+            | Expression::FloatLiteral { position, .. }
+            | Expression::StringLiteral { position, .. }
+            | Expression::BoolLiteral { position, .. }
+            | Expression::NilLiteral { position }
+            | Expression::Symbol { position, .. }
+            | Expression::Identifier { position, .. }
+            | Expression::InstanceVariable { position, .. }
+            | Expression::ClassVariable { position, .. }
+            | Expression::SelfExpr { position }
+            | Expression::ForwardedArgs { position } => f(position),
+            Expression::InterpolatedString { parts, position } => {
+                f(position);
+                for part in parts {
+                    if let InterpolationPart::Expression(expression) = part {
+                        expression.for_each_position_mut(f);
+                    }
+                }
+            }
+            Expression::BinaryOp {
+                left,
+                right,
+                position,
+                ..
+            }
+            | Expression::Index {
+                array: left,
+                index: right,
+                position,
+            }
+            | Expression::Range {
+                start: left,
+                end: right,
+                position,
+                ..
+            }
+            | Expression::RescueModifier {
+                expression: left,
+                fallback: right,
+                position,
+            } => {
+                f(position);
+                left.for_each_position_mut(f);
+                right.for_each_position_mut(f);
+            }
+            Expression::UnaryOp {
+                operand: inner,
+                position,
+                ..
+            }
+            | Expression::Grouped {
+                expression: inner,
+                position,
+            } => {
+                f(position);
+                inner.for_each_position_mut(f);
+            }
+            Expression::Call {
+                callee: receiver,
+                arguments,
+                trailing_block,
+                position,
+            }
+            | Expression::MethodCall {
+                receiver,
+                arguments,
+                trailing_block,
+                position,
+                ..
+            } => {
+                f(position);
+                receiver.for_each_position_mut(f);
+                expressions_positions(arguments, f);
+                if let Some(block) = trailing_block {
+                    block.for_each_position_mut(f);
+                }
+            }
+            Expression::Array {
+                elements: expressions,
+                position,
+            }
+            | Expression::Super {
+                arguments: expressions,
+                position,
+            } => {
+                f(position);
+                expressions_positions(expressions, f);
+            }
+            Expression::Dictionary { entries, position } => {
+                f(position);
+                for (key, value) in entries {
+                    key.for_each_position_mut(f);
+                    value.for_each_position_mut(f);
+                }
+            }
+            Expression::Lambda { body, position, .. } => {
+                f(position);
+                statements_positions(body, f);
+            }
+            Expression::Case {
+                expression,
+                cases,
+                else_case,
+                position,
+            } => {
+                f(position);
+                expression.for_each_position_mut(f);
+                for ExprMatchCase {
+                    guard,
+                    body,
+                    position,
+                    ..
+                } in cases
+                {
+                    f(position);
+                    if let Some(guard) = guard {
+                        guard.for_each_position_mut(f);
+                    }
+                    body.for_each_position_mut(f);
+                }
+                if let Some(else_case) = else_case {
+                    else_case.for_each_position_mut(f);
+                }
+            }
+            Expression::Begin { block, position } => {
+                f(position);
+                block.for_each_position_mut(f);
+            }
+        }
+    }
+}
+
+fn statements_positions(statements: &mut [Statement], f: &mut dyn FnMut(&mut Position)) {
+    for statement in statements {
+        statement.for_each_position_mut(f);
+    }
+}
+
+fn expressions_positions(expressions: &mut [Expression], f: &mut dyn FnMut(&mut Position)) {
+    for expression in expressions {
+        expression.for_each_position_mut(f);
+    }
+}
+
+fn parameters_positions(parameters: &mut [Parameter], f: &mut dyn FnMut(&mut Position)) {
+    for parameter in parameters {
+        f(&mut parameter.position);
+        if let Some(default_value) = &mut parameter.default_value {
+            default_value.for_each_position_mut(f);
+        }
+        if let Some(annotation) = &mut parameter.type_annotation {
+            f(&mut annotation.position);
+        }
+    }
+}
@@ -0,0 +1,249 @@
+// Read-only traversal of a syntax tree
+// A `Visitor` overrides the hooks for the nodes it cares about and calls the
+// matching `walk_` function to carry on into their children. The defaults
+// visit everything, so an empty visitor walks the whole tree. Lint rules and
+// other static checks are built on this.
+
+use super::node::{
+    ElsifBranch, ExprMatchCase, Expression, InterpolationPart, MatchCase, RescueClause, Statement,
+};
+
+/// Hooks called for each node while walking a syntax tree
+pub trait Visitor {
+    /// Called for every list of statements: a program, a body or a branch
+    fn visit_statements(&mut self, statements: &[Statement]) {
+        walk_statements(self, statements);
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) {
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        walk_expression(self, expression);
+    }
+}
+
+/// Visit each statement of a list in order
+pub fn walk_statements<V: Visitor + ?Sized>(visitor: &mut V, statements: &[Statement]) {
+    for statement in statements {
+        visitor.visit_statement(statement);
+    }
+}
+
+/// Visit the expressions and statement lists directly inside `statement`
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &Statement) {
+    match statement {
+        Statement::Expression { expression, .. } => visitor.visit_expression(expression),
+        Statement::Assignment { target, value, .. } => {
+            visitor.visit_expression(target);
+            visitor.visit_expression(value);
+        }
+        Statement::FunctionDef {
+            parameters, body, ..
+        }
+        | Statement::MethodDef {
+            parameters, body, ..
+        } => {
+            for default_value in parameters.iter().filter_map(|p| p.default_value.as_ref()) {
+                visitor.visit_expression(default_value);
+            }
+            visitor.visit_statements(body);
+        }
+        Statement::ClassDef { body, .. }
+        | Statement::ModuleDef { body, .. }
+        | Statement::Block {
+            statements: body, ..
+        } => visitor.visit_statements(body),
+        Statement::If {
+            condition,
+            then_branch,
+            elsif_branches,
+            else_branch,
+            ..
+        } => {
+            visitor.visit_expression(condition);
+            visitor.visit_statements(then_branch);
+            for ElsifBranch {
+                condition, body, ..
+            } in elsif_branches
+            {
+                visitor.visit_expression(condition);
+                visitor.visit_statements(body);
+            }
+            if let Some(else_branch) = else_branch {
+                visitor.visit_statements(else_branch);
+            }
+        }
+        Statement::Unless {
+            condition,
+            then_branch,
+            else_branch,
+            ..
+        } => {
+            visitor.visit_expression(condition);
+            visitor.visit_statements(then_branch);
+            if let Some(else_branch) = else_branch {
+                visitor.visit_statements(else_branch);
+            }
+        }
+        Statement::While {
+            condition, body, ..
+        } => {
+            visitor.visit_expression(condition);
+            visitor.visit_statements(body);
+        }
+        Statement::For { iterable, body, .. } => {
+            visitor.visit_expression(iterable);
+            visitor.visit_statements(body);
+        }
+        Statement::Match {
+            expression, cases, ..
+        } => {
+            visitor.visit_expression(expression);
+            for MatchCase { guard, body, .. } in cases {
+                if let Some(guard) = guard {
+                    visitor.visit_expression(guard);
+                }
+                visitor.visit_statements(body);
+            }
+        }
+        Statement::Return { value, .. } => {
+            if let Some(value) = value {
+                visitor.visit_expression(value);
+            }
+        }
+        Statement::Begin {
+            body,
+            rescue_clauses,
+            else_clause,
+            ensure_block,
+            ..
+        } => {
+            visitor.visit_statements(body);
+            for RescueClause { body, .. } in rescue_clauses {
+                visitor.visit_statements(body);
+            }
+            for block in [else_clause, ensure_block].into_iter().flatten() {
+                visitor.visit_statements(block);
+            }
+        }
+        Statement::Raise {
+            exception, cause, ..
+        } => {
+            for expression in [exception, cause].into_iter().flatten() {
+                visitor.visit_expression(expression);
+            }
+        }
+        Statement::Break { .. }
+        | Statement::Continue { .. }
+        | Statement::Retry { .. }
+        | Statement::AttrReader { .. }
+        | Statement::AttrWriter { .. }
+        | Statement::AttrAccessor { .. } => {}
+    }
+}
+
+/// Visit the expressions and statement lists directly inside `expression`
+pub fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, expression: &Expression) {
+    match expression {
+        Expression::IntLiteral { .. }
+        | Expression::FloatLiteral { .. }
+        | Expression::StringLiteral { .. }
+        | Expression::BoolLiteral { .. }
+        | Expression::NilLiteral { .. }
+        | Expression::Symbol { .. }
+        | Expression::Identifier { .. }
+        | Expression::InstanceVariable { .. }
+        | Expression::ClassVariable { .. }
+        | Expression::SelfExpr { .. }
+        | Expression::ForwardedArgs { .. } => {}
+        Expression::InterpolatedString { parts, .. } => {
+            for part in parts {
+                if let InterpolationPart::Expression(expression) = part {
+                    visitor.visit_expression(expression);
+                }
+            }
+        }
+        Expression::BinaryOp { left, right, .. }
+        | Expression::Index {
+            array: left,
+            index: right,
+            ..
+        }
+        | Expression::Range {
+            start: left,
+            end: right,
+            ..
+        }
+        | Expression::RescueModifier {
+            expression: left,
+            fallback: right,
+            ..
+        } => {
+            visitor.visit_expression(left);
+            visitor.visit_expression(right);
+        }
+        Expression::UnaryOp { operand: inner, .. }
+        | Expression::Grouped {
+            expression: inner, ..
+        } => visitor.visit_expression(inner),
+        Expression::Call {
+            callee: receiver,
+            arguments,
+            trailing_block,
+            ..
+        }
+        | Expression::MethodCall {
+            receiver,
+            arguments,
+            trailing_block,
+            ..
+        } => {
+            visitor.visit_expression(receiver);
+            for argument in arguments {
+                visitor.visit_expression(argument);
+            }
+            if let Some(block) = trailing_block {
+                visitor.visit_expression(block);
+            }
+        }
+        Expression::Array {
+            elements: expressions,
+            ..
+        }
+        | Expression::Super {
+            arguments: expressions,
+            ..
+        } => {
+            for expression in expressions {
+                visitor.visit_expression(expression);
+            }
+        }
+        Expression::Dictionary { entries, .. } => {
+            for (key, value) in entries {
+                visitor.visit_expression(key);
+                visitor.visit_expression(value);
+            }
+        }
+        Expression::Lambda { body, .. } => visitor.visit_statements(body),
+        Expression::Case {
+            expression,
+            cases,
+            else_case,
+            ..
+        } => {
+            visitor.visit_expression(expression);
+            for ExprMatchCase { guard, body, .. } in cases {
+                if let Some(guard) = guard {
+                    visitor.visit_expression(guard);
+                }
+                visitor.visit_expression(body);
+            }
+            if let Some(else_case) = else_case {
+                visitor.visit_expression(else_case);
+            }
+        }
+        Expression::Begin { block, .. } => visitor.visit_statement(block),
+    }
+}
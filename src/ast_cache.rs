@@ -0,0 +1,190 @@
+// On-disk cache of parsed ASTs
+// Required files are parsed once and their statements stored as `.mxc` files
+// in a cache directory. Later runs load the stored AST instead of lexing and
+// parsing again, as long as the source is unchanged.
+//
+// Invalidation policy: each entry records a hash and the length of the source
+// it was parsed from, plus the cache format, Metorex version and AST schema
+// that wrote it. The schema is a hash of the files defining the AST and token
+// types, taken at build time, so a build whose AST changed shape never reads
+// entries an older one wrote, even at the same version. An entry is used only
+// when all of these match; otherwise the file is parsed again and the entry
+// rewritten. Unreadable or corrupt entries count as misses.
+
+use crate::ast::Statement;
+use crate::error::MetorexError;
+use crate::file_loader::parse_file;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// File extension of cache entries
+pub const CACHE_EXTENSION: &str = "mxc";
+
+/// Environment variable that overrides the default cache directory
+pub const MX_CACHE_DIR_VAR: &str = "MX_CACHE_DIR";
+
+/// Bumped whenever the entry layout changes; AST changes are caught by
+/// `AST_SCHEMA_HASH`
+pub const CACHE_FORMAT_VERSION: u32 = 5;
+
+/// Hash of the source of the AST and token types this build serializes
+pub const AST_SCHEMA_HASH: &str = env!("METOREX_AST_SCHEMA_HASH");
+
+/// Leading bytes of every cache entry
+const MAGIC: [u8; 4] = *b"MXC\0";
+
+/// Hit and miss counts for a cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    /// Files whose AST was loaded from the cache
+    pub hits: usize,
+    /// Files that had to be parsed
+    pub misses: usize,
+}
+
+/// Everything an entry records besides the AST itself
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct EntryHeader {
+    magic: [u8; 4],
+    format_version: u32,
+    metorex_version: String,
+    ast_schema: String,
+    source_hash: u64,
+    source_len: u64,
+}
+
+impl EntryHeader {
+    fn for_source(source: &str) -> Self {
+        EntryHeader {
+            magic: MAGIC,
+            format_version: CACHE_FORMAT_VERSION,
+            metorex_version: crate::version().to_string(),
+            ast_schema: AST_SCHEMA_HASH.to_string(),
+            source_hash: fnv1a(source.as_bytes()),
+            source_len: source.len() as u64,
+        }
+    }
+}
+
+/// A directory of cached ASTs, keyed by the path of the source file
+#[derive(Debug, Clone)]
+pub struct AstCache {
+    dir: PathBuf,
+    stats: CacheStats,
+}
+
+impl AstCache {
+    /// Use `dir` as the cache directory; it is created on first write
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        AstCache {
+            dir: dir.into(),
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// The directory named by `MX_CACHE_DIR`, or `metorex-cache` in the
+    /// system temporary directory
+    pub fn default_dir() -> PathBuf {
+        env::var_os(MX_CACHE_DIR_VAR)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| env::temp_dir().join("metorex-cache"))
+    }
+
+    /// The cache directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Hits and misses since the cache was created
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Where the entry for the source file at `path` is stored
+    pub fn entry_path(&self, path: &Path) -> PathBuf {
+        let key = fnv1a(path.to_string_lossy().as_bytes());
+        self.dir.join(format!("{:016x}.{}", key, CACHE_EXTENSION))
+    }
+
+    /// Return the AST for `source`, read from `path`, using the cached entry
+    /// when it is still valid and parsing (then caching) otherwise
+    ///
+    /// Failing to write an entry is not an error; the file just stays uncached.
+    pub fn load_or_parse(
+        &mut self,
+        path: &Path,
+        source: &str,
+    ) -> Result<Vec<Statement>, MetorexError> {
+        let entry = self.entry_path(path);
+        let header = EntryHeader::for_source(source);
+
+        if let Some(statements) = read_entry(&entry, &header) {
+            self.stats.hits += 1;
+            return Ok(statements);
+        }
+
+        self.stats.misses += 1;
+        let statements = parse_file(source, &path.to_string_lossy())?;
+        let _ = self.write_entry(&entry, &header, &statements);
+        Ok(statements)
+    }
+
+    /// Delete every entry in the cache directory, returning how many were removed
+    pub fn clear(&self) -> io::Result<usize> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(err) => return Err(err),
+        };
+
+        let mut removed = 0;
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == CACHE_EXTENSION) {
+                fs::remove_file(&path)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Write an entry through a temporary file so readers never see half of one
+    fn write_entry(
+        &self,
+        entry: &Path,
+        header: &EntryHeader,
+        statements: &[Statement],
+    ) -> io::Result<()> {
+        let mut bytes = bincode::serialize(header).map_err(io::Error::other)?;
+        bytes.extend(bincode::serialize(statements).map_err(io::Error::other)?);
+
+        fs::create_dir_all(&self.dir)?;
+        let temp = entry.with_extension(format!("{}.{}", CACHE_EXTENSION, std::process::id()));
+        fs::write(&temp, bytes)?;
+        fs::rename(&temp, entry).inspect_err(|_| {
+            let _ = fs::remove_file(&temp);
+        })
+    }
+}
+
+/// Read an entry, returning its AST only if its header matches `expected`
+fn read_entry(entry: &Path, expected: &EntryHeader) -> Option<Vec<Statement>> {
+    let bytes = fs::read(entry).ok()?;
+    let mut reader = bytes.as_slice();
+    let header: EntryHeader = bincode::deserialize_from(&mut reader).ok()?;
+    if header != *expected {
+        return None;
+    }
+    bincode::deserialize(reader).ok()
+}
+
+/// 64-bit FNV-1a, used because its output never changes between builds
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
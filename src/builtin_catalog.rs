@@ -0,0 +1,2626 @@
+// Catalog of builtin classes, methods and functions
+// Describes every native method the VM dispatches, with its parameters,
+// result type and a one-line doc string. `metorex builtins --json` exports
+// it for editors, the typechecker reads result types from it and the VM checks
+// native calls against its parameters, so the method lists and their arity
+// live here instead of in each tool.
+
+use serde::Serialize;
+
+/// Version of the JSON layout written by [`to_json`]
+pub const CATALOG_FORMAT_VERSION: u32 = 1;
+
+/// Whether a method is called on instances or on the class itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MethodKind {
+    Instance,
+    Class,
+}
+
+/// How a parameter is passed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamKind {
+    Required,
+    Optional,
+    /// Any number of trailing arguments
+    Rest,
+    /// Trailing `key: value` options
+    Keywords,
+    Block,
+    OptionalBlock,
+}
+
+/// A parameter of a builtin method or function
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct BuiltinParam {
+    pub name: &'static str,
+    /// Type annotation the argument must satisfy, if the method checks it
+    #[serde(rename = "type")]
+    pub ty: Option<&'static str>,
+    pub kind: ParamKind,
+}
+
+impl BuiltinParam {
+    /// Require the argument to satisfy the type annotation `ty`
+    const fn of(self, ty: &'static str) -> Self {
+        Self {
+            ty: Some(ty),
+            ..self
+        }
+    }
+
+    /// Make the argument or block optional
+    const fn optional(self) -> Self {
+        let kind = match self.kind {
+            ParamKind::Block => ParamKind::OptionalBlock,
+            _ => ParamKind::Optional,
+        };
+        Self { kind, ..self }
+    }
+}
+
+const fn arg(name: &'static str) -> BuiltinParam {
+    BuiltinParam {
+        name,
+        ty: None,
+        kind: ParamKind::Required,
+    }
+}
+
+const fn rest(name: &'static str) -> BuiltinParam {
+    BuiltinParam {
+        kind: ParamKind::Rest,
+        ..arg(name)
+    }
+}
+
+const fn keywords(name: &'static str) -> BuiltinParam {
+    BuiltinParam {
+        kind: ParamKind::Keywords,
+        ..arg(name)
+    }
+}
+
+const fn block() -> BuiltinParam {
+    BuiltinParam {
+        kind: ParamKind::Block,
+        ..arg("block")
+    }
+}
+
+/// Fewest arguments a call must pass, not counting a block
+pub fn min_arity(parameters: &[BuiltinParam]) -> usize {
+    parameters
+        .iter()
+        .filter(|p| p.kind == ParamKind::Required)
+        .count()
+}
+
+/// Most arguments a call may pass, not counting a block, or `None` if the
+/// parameters end in a rest parameter
+pub fn max_arity(parameters: &[BuiltinParam]) -> Option<usize> {
+    let mut count = 0;
+    for parameter in parameters {
+        match parameter.kind {
+            ParamKind::Rest => return None,
+            ParamKind::Block | ParamKind::OptionalBlock => {}
+            _ => count += 1,
+        }
+    }
+    Some(count)
+}
+
+/// A builtin class and the class it inherits from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuiltinClass {
+    pub name: &'static str,
+    pub superclass: Option<&'static str>,
+    /// Whether the class is bound to its name in every program
+    pub global: bool,
+    pub doc: &'static str,
+}
+
+/// A native method
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuiltinMethod {
+    pub class: &'static str,
+    pub name: &'static str,
+    pub kind: MethodKind,
+    pub parameters: &'static [BuiltinParam],
+    /// Type annotation describing the result
+    pub returns: &'static str,
+    pub doc: &'static str,
+}
+
+/// A native function such as `puts`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuiltinFunction {
+    pub name: &'static str,
+    pub parameters: &'static [BuiltinParam],
+    pub returns: &'static str,
+    pub doc: &'static str,
+}
+
+const fn class(
+    name: &'static str,
+    superclass: Option<&'static str>,
+    doc: &'static str,
+) -> BuiltinClass {
+    BuiltinClass {
+        name,
+        superclass,
+        global: true,
+        doc,
+    }
+}
+
+/// A class whose values exist but whose name is not bound
+const fn hidden_class(name: &'static str, doc: &'static str) -> BuiltinClass {
+    BuiltinClass {
+        global: false,
+        ..class(name, Some("Object"), doc)
+    }
+}
+
+const fn instance(
+    class: &'static str,
+    name: &'static str,
+    parameters: &'static [BuiltinParam],
+    returns: &'static str,
+    doc: &'static str,
+) -> BuiltinMethod {
+    BuiltinMethod {
+        class,
+        name,
+        kind: MethodKind::Instance,
+        parameters,
+        returns,
+        doc,
+    }
+}
+
+const fn singleton(
+    class: &'static str,
+    name: &'static str,
+    parameters: &'static [BuiltinParam],
+    returns: &'static str,
+    doc: &'static str,
+) -> BuiltinMethod {
+    BuiltinMethod {
+        kind: MethodKind::Class,
+        ..instance(class, name, parameters, returns, doc)
+    }
+}
+
+const fn function(
+    name: &'static str,
+    parameters: &'static [BuiltinParam],
+    returns: &'static str,
+    doc: &'static str,
+) -> BuiltinFunction {
+    BuiltinFunction {
+        name,
+        parameters,
+        returns,
+        doc,
+    }
+}
+
+/// Every builtin class, superclasses first
+pub const CLASSES: &[BuiltinClass] = &[
+    class("Object", None, "Root of the class hierarchy"),
+    class("String", Some("Object"), "Immutable UTF-8 text"),
+    class("Integer", Some("Object"), "64-bit signed integers"),
+    class("Float", Some("Object"), "64-bit floating point numbers"),
+    class(
+        "Decimal",
+        Some("Object"),
+        "Exact decimal numbers for money, also named BigDecimal",
+    ),
+    class("BigDecimal", Some("Object"), "Another name for Decimal"),
+    class(
+        "StringBuilder",
+        Some("Object"),
+        "Mutable text buffer that appends in place",
+    ),
+    class(
+        "CodeBuilder",
+        Some("Object"),
+        "Lines of generated source, indented by nesting",
+    ),
+    class("Array", Some("Object"), "Ordered, growable list of values"),
+    class(
+        "Hash",
+        Some("Object"),
+        "Insertion-ordered map from keys to values",
+    ),
+    hidden_class(
+        "IdentityHash",
+        "Hash returned by Hash.identity, comparing keys with equal? instead of ==",
+    ),
+    class(
+        "Enumerator",
+        Some("Object"),
+        "External iteration over what a blockless iterator method would yield",
+    ),
+    class(
+        "Set",
+        Some("Object"),
+        "Unordered collection of unique values",
+    ),
+    hidden_class("Range", "Span of integers written start..end"),
+    class(
+        "Exception",
+        Some("Object"),
+        "Base class of everything that can be raised",
+    ),
+    class(
+        "StandardError",
+        Some("Exception"),
+        "Base class of rescuable errors",
+    ),
+    class(
+        "RuntimeError",
+        Some("StandardError"),
+        "Generic error raised by `raise \"message\"`",
+    ),
+    class(
+        "TypeError",
+        Some("StandardError"),
+        "A value had the wrong type",
+    ),
+    class(
+        "ValueError",
+        Some("StandardError"),
+        "A value had the right type but was invalid",
+    ),
+    class(
+        "ArgumentError",
+        Some("StandardError"),
+        "A builtin was called with the wrong number of arguments",
+    ),
+    class(
+        "NameError",
+        Some("StandardError"),
+        "An undefined or invalid name was used",
+    ),
+    class(
+        "NoMethodError",
+        Some("NameError"),
+        "A method called by name with `send` is not defined for the receiver",
+    ),
+    class(
+        "SyntaxError",
+        Some("StandardError"),
+        "Source passed to Metorex.parse could not be parsed",
+    ),
+    class(
+        "ConcurrentModificationError",
+        Some("RuntimeError"),
+        "A collection was changed while it was being iterated",
+    ),
+    class(
+        "AssertionError",
+        Some("StandardError"),
+        "An `assert` or a method's pre- or postcondition failed",
+    ),
+    class(
+        "StopIteration",
+        Some("StandardError"),
+        "Enumerator#next or #peek went past the last item",
+    ),
+    class(
+        "IOError",
+        Some("StandardError"),
+        "Reading, writing or deleting a file failed",
+    ),
+    class(
+        "Interrupt",
+        Some("Exception"),
+        "The host interrupted the running program",
+    ),
+    class("Timeout", Some("Object"), "Runs a block with a time limit"),
+    BuiltinClass {
+        global: false,
+        ..class(
+            "Timeout::Error",
+            Some("RuntimeError"),
+            "A Timeout.timeout block ran past its time limit",
+        )
+    },
+    class(
+        "GC",
+        Some("Object"),
+        "Heap statistics and garbage collection control",
+    ),
+    class(
+        "File",
+        Some("Object"),
+        "File path helpers, file contents and line-by-line reading",
+    ),
+    class("URI", Some("Object"), "Parsed URIs and percent-encoding"),
+    class("Base64", Some("Object"), "Base64 encoding of Strings"),
+    class("Digest", Some("Object"), "SHA-256, SHA-1 and MD5 checksums"),
+    class(
+        "Inflector",
+        Some("Object"),
+        "snake_case, CamelCase, dashed, titled and plural forms of names",
+    ),
+    class(
+        "SQLite",
+        Some("Object"),
+        "SQLite database connections (needs the `sqlite` feature)",
+    ),
+    hidden_class(
+        "SQLite::Statement",
+        "Prepared statement returned by SQLite#prepare",
+    ),
+    class(
+        "YAML",
+        Some("Object"),
+        "YAML parsing and dumping (needs the `yaml` feature)",
+    ),
+    class(
+        "TOML",
+        Some("Object"),
+        "TOML parsing (needs the `toml` feature)",
+    ),
+    class(
+        "Schema",
+        Some("Object"),
+        "Declarative validation of Hashes and Arrays, such as parsed configuration",
+    ),
+    hidden_class(
+        "Schema::Error",
+        "A problem Schema#validate found, with the path to the value",
+    ),
+    class(
+        "Readline",
+        Some("Object"),
+        "Line editing and history for interactive scripts",
+    ),
+    class(
+        "TracePoint",
+        Some("Object"),
+        "Call, return, line, class and raise events for tracing tools",
+    ),
+    class(
+        "Metorex",
+        Some("Object"),
+        "The interpreter version and how the host configured it",
+    ),
+    hidden_class(
+        "Metorex::Node",
+        "Syntax tree node returned by Metorex.parse",
+    ),
+    hidden_class("Class", "Classes, including the builtin ones"),
+    class(
+        "Module",
+        Some("Object"),
+        "Methods that classes mix in with include or extend",
+    ),
+    hidden_class("Block", "Blocks and lambdas"),
+    hidden_class("Method", "Methods and functions captured with `method`"),
+    hidden_class(
+        "Binding",
+        "Variables captured by a block or visible at a trace event",
+    ),
+];
+
+/// Every native method, grouped by class
+pub const METHODS: &[BuiltinMethod] = &[
+    // Object
+    instance(
+        "Object",
+        "to_s",
+        &[],
+        "String",
+        "Convert the receiver to a String",
+    ),
+    instance("Object", "class", &[], "Class", "The class of the receiver"),
+    instance(
+        "Object",
+        "respond_to?",
+        &[arg("method_name")],
+        "Bool",
+        "Whether the receiver has a public method with this name",
+    ),
+    instance(
+        "Object",
+        "send",
+        &[arg("method_name"), rest("args"), block()],
+        "Object",
+        "Call the receiver's method with this name, private methods included",
+    ),
+    instance(
+        "Object",
+        "__send__",
+        &[arg("method_name"), rest("args"), block()],
+        "Object",
+        "Same as send, for classes that define a send of their own",
+    ),
+    instance(
+        "Object",
+        "to_literal",
+        &[],
+        "String",
+        "Source that evaluates to the receiver, for nil, Bools, numbers, Strings, symbols, Ranges, Arrays and Hashes",
+    ),
+    instance(
+        "Object",
+        "object_id",
+        &[],
+        "Int",
+        "Integer identifying the receiver, never reused while the program runs",
+    ),
+    instance(
+        "Object",
+        "equal?",
+        &[arg("other")],
+        "Bool",
+        "Whether `other` is the same object as the receiver",
+    ),
+    instance(
+        "Object",
+        "instance_variables",
+        &[],
+        "Array",
+        "Names of the receiver's instance variables as Symbols like :@name",
+    ),
+    instance(
+        "Object",
+        "instance_variable_get",
+        &[arg("name")],
+        "Any",
+        "Read an instance variable named like :@name, nil when it is not set",
+    ),
+    instance(
+        "Object",
+        "instance_variable_set",
+        &[arg("name"), arg("value")],
+        "Any",
+        "Assign an instance variable named like :@name",
+    ),
+    instance(
+        "Object",
+        "instance_variable_defined?",
+        &[arg("name")],
+        "Bool",
+        "Whether an instance variable is set",
+    ),
+    // String
+    instance("String", "length", &[], "Int", "Number of characters"),
+    instance(
+        "String",
+        "upcase",
+        &[],
+        "String",
+        "Copy with every letter in upper case",
+    ),
+    instance(
+        "String",
+        "downcase",
+        &[],
+        "String",
+        "Copy with every letter in lower case",
+    ),
+    instance(
+        "String",
+        "+",
+        &[arg("other").of("String")],
+        "String",
+        "Concatenate two Strings",
+    ),
+    instance(
+        "String",
+        "to_source",
+        &[],
+        "String",
+        "The String as a double-quoted literal, escaped to read back exactly",
+    ),
+    instance(
+        "String",
+        "%",
+        &[arg("values")],
+        "String",
+        "Format a value, or an Array of values, like sprintf",
+    ),
+    instance(
+        "String",
+        "trim",
+        &[],
+        "String",
+        "Copy without leading and trailing whitespace",
+    ),
+    instance(
+        "String",
+        "reverse",
+        &[],
+        "String",
+        "Copy with the characters in reverse order",
+    ),
+    instance(
+        "String",
+        "chars",
+        &[],
+        "Array",
+        "The characters as one-character Strings",
+    ),
+    instance(
+        "String",
+        "bytes",
+        &[],
+        "Array",
+        "The UTF-8 bytes as Integers",
+    ),
+    instance(
+        "String",
+        "chomp",
+        &[arg("suffix").of("String").optional()],
+        "String",
+        "Copy without a trailing line ending, or without `suffix` when given",
+    ),
+    instance(
+        "String",
+        "match_glob",
+        &[arg("pattern").of("String")],
+        "Bool",
+        "Whether the String matches a shell-style glob pattern",
+    ),
+    instance(
+        "String",
+        "chop",
+        &[],
+        "String",
+        "Copy without the last character",
+    ),
+    instance(
+        "String",
+        "to_i",
+        &[arg("base").of("Int").optional()],
+        "Int",
+        "Parse a leading integer in the given base (default 10), or 0",
+    ),
+    instance(
+        "String",
+        "to_f",
+        &[],
+        "Float",
+        "Parse a leading decimal number, or 0.0",
+    ),
+    instance(
+        "String",
+        "to_d",
+        &[],
+        "Decimal",
+        "Parse a leading decimal number exactly, or 0",
+    ),
+    instance(
+        "String",
+        "to_number",
+        &[keywords("options")],
+        "Numeric?",
+        "Parse a formatted number using `delimiter:` and `separator:`, or nil",
+    ),
+    instance(
+        "String",
+        "hex_encode",
+        &[],
+        "String",
+        "Lowercase hex of the UTF-8 bytes",
+    ),
+    instance(
+        "String",
+        "hex_decode",
+        &[],
+        "String",
+        "Decode a String of hex digit pairs",
+    ),
+    instance(
+        "String",
+        "each_char",
+        &[block().optional()],
+        "Any",
+        "Yield each character in turn, or return an Enumerator",
+    ),
+    instance(
+        "String",
+        "each_byte",
+        &[block().optional()],
+        "Any",
+        "Yield each UTF-8 byte as an Integer, or return an Enumerator",
+    ),
+    instance(
+        "String",
+        "each_line",
+        &[keywords("options"), block().optional()],
+        "Any",
+        "Yield each line, without its line ending given `chomp: true`, or return an Enumerator",
+    ),
+    instance(
+        "String",
+        "lines",
+        &[keywords("options")],
+        "Array",
+        "The lines, each ending in its newline unless `chomp: true` is given",
+    ),
+    instance(
+        "String",
+        "ljust",
+        &[arg("width").of("Int"), arg("pad").of("String").optional()],
+        "String",
+        "Pad on the right with `pad` (default a space) to `width` characters",
+    ),
+    instance(
+        "String",
+        "rjust",
+        &[arg("width").of("Int"), arg("pad").of("String").optional()],
+        "String",
+        "Pad on the left with `pad` (default a space) to `width` characters",
+    ),
+    instance(
+        "String",
+        "center",
+        &[arg("width").of("Int"), arg("pad").of("String").optional()],
+        "String",
+        "Pad on both sides to `width` characters, the extra one going right",
+    ),
+    instance(
+        "String",
+        "tr",
+        &[arg("from").of("String"), arg("to").of("String")],
+        "String",
+        "Replace characters of the `from` set by those at the same place in `to`",
+    ),
+    instance(
+        "String",
+        "squeeze",
+        &[rest("sets").of("String")],
+        "String",
+        "Collapse runs of a repeated character, limited to the given sets",
+    ),
+    instance(
+        "String",
+        "delete",
+        &[rest("sets").of("String")],
+        "String",
+        "Copy without the characters found in every given set",
+    ),
+    instance(
+        "String",
+        "count",
+        &[rest("sets").of("String")],
+        "Int",
+        "Number of characters found in every given set",
+    ),
+    singleton(
+        "String",
+        "build",
+        &[block()],
+        "String",
+        "Yield a StringBuilder and return the String appended to it",
+    ),
+    // StringBuilder
+    singleton(
+        "StringBuilder",
+        "new",
+        &[arg("initial").of("String").optional()],
+        "StringBuilder",
+        "An empty builder, or one starting with `initial`",
+    ),
+    instance(
+        "StringBuilder",
+        "<<",
+        &[arg("value")],
+        "StringBuilder",
+        "Append a String, or any value as it would be interpolated",
+    ),
+    instance(
+        "StringBuilder",
+        "append",
+        &[arg("value")],
+        "StringBuilder",
+        "Alias for <<",
+    ),
+    instance(
+        "StringBuilder",
+        "to_s",
+        &[],
+        "String",
+        "The text appended so far",
+    ),
+    instance(
+        "StringBuilder",
+        "length",
+        &[],
+        "Int",
+        "Number of characters appended so far",
+    ),
+    instance(
+        "StringBuilder",
+        "empty?",
+        &[],
+        "Bool",
+        "Whether nothing has been appended",
+    ),
+    // CodeBuilder
+    singleton(
+        "CodeBuilder",
+        "new",
+        &[arg("indent").of("String").optional()],
+        "CodeBuilder",
+        "An empty builder indenting each level by `indent`, two spaces by default",
+    ),
+    instance(
+        "CodeBuilder",
+        "line",
+        &[arg("text").optional()],
+        "CodeBuilder",
+        "Write a line, or each line of the text, at the current depth",
+    ),
+    instance(
+        "CodeBuilder",
+        "indent",
+        &[block().optional()],
+        "CodeBuilder",
+        "Nest the lines the block writes one level deeper, or stay deeper without a block",
+    ),
+    instance(
+        "CodeBuilder",
+        "dedent",
+        &[],
+        "CodeBuilder",
+        "Go back one level",
+    ),
+    instance(
+        "CodeBuilder",
+        "block",
+        &[arg("header"), arg("footer").optional(), block()],
+        "CodeBuilder",
+        "Write the header, the block's lines one level deeper, then the footer (\"end\" by default)",
+    ),
+    instance(
+        "CodeBuilder",
+        "depth",
+        &[],
+        "Int",
+        "Number of levels the next line is indented by",
+    ),
+    instance(
+        "CodeBuilder",
+        "to_s",
+        &[],
+        "String",
+        "The source written so far",
+    ),
+    // Array
+    instance("Array", "length", &[], "Int", "Number of elements"),
+    instance(
+        "Array",
+        "push",
+        &[arg("item")],
+        "Array",
+        "Append an element, returning the array",
+    ),
+    instance("Array", "append", &[arg("item")], "Array", "Alias for push"),
+    instance(
+        "Array",
+        "pop",
+        &[],
+        "Any",
+        "Remove and return the last element, or nil",
+    ),
+    instance(
+        "Array",
+        "[]",
+        &[arg("index").of("Int")],
+        "Any",
+        "Element at an index, or nil",
+    ),
+    instance(
+        "Array",
+        "each",
+        &[block().optional()],
+        "Any",
+        "Yield each element of a snapshot of the array, or return an Enumerator",
+    ),
+    instance(
+        "Array",
+        "each_mutating!",
+        &[block().optional()],
+        "Any",
+        "Yield each element, including ones the block appends, or return an Enumerator",
+    ),
+    instance(
+        "Array",
+        "each_with_index",
+        &[block().optional()],
+        "Any",
+        "Yield each element with its index, or return an Enumerator of the pairs",
+    ),
+    instance(
+        "Array",
+        "map",
+        &[block().optional()],
+        "Any",
+        "Array of the block's results, or an Enumerator without a block",
+    ),
+    instance(
+        "Array",
+        "select",
+        &[block().optional()],
+        "Any",
+        "Elements for which the block is truthy, or an Enumerator without a block",
+    ),
+    instance(
+        "Array",
+        "filter",
+        &[block().optional()],
+        "Any",
+        "Alias for select",
+    ),
+    instance(
+        "Array",
+        "grep",
+        &[arg("pattern"), block().optional()],
+        "Array",
+        "Elements matching `pattern` with ===, mapped through the block if given",
+    ),
+    instance(
+        "Array",
+        "reduce",
+        &[arg("initial").optional(), block()],
+        "Any",
+        "Combine the elements with a block taking (accumulator, element)",
+    ),
+    instance(
+        "Array",
+        "zip",
+        &[rest("others").of("Array")],
+        "Array",
+        "Pair each element with the elements at the same index in other arrays",
+    ),
+    instance(
+        "Array",
+        "transpose",
+        &[],
+        "Array",
+        "Swap the rows and columns of an array of arrays",
+    ),
+    instance(
+        "Array",
+        "sort",
+        &[block().optional()],
+        "Array",
+        "Stable sort using the block's (a, b) comparison, `<=>`, or natural ordering",
+    ),
+    instance(
+        "Array",
+        "sort_by",
+        &[block().optional()],
+        "Any",
+        "Stable sort by a key the block computes once per element, or an Enumerator without a block",
+    ),
+    instance(
+        "Array",
+        "join",
+        &[arg("separator").of("String").optional()],
+        "String",
+        "The elements as text, with `separator` between them and nested arrays joined too",
+    ),
+    instance(
+        "Array",
+        "to_table",
+        &[keywords("options")],
+        "String",
+        "Render Hash or Array rows as an aligned table using `headers:` and `markdown:`",
+    ),
+    // Enumerator
+    instance(
+        "Enumerator",
+        "next",
+        &[],
+        "Any",
+        "The item at the cursor, moving past it; raises StopIteration at the end",
+    ),
+    instance(
+        "Enumerator",
+        "peek",
+        &[],
+        "Any",
+        "The item at the cursor, without moving; raises StopIteration at the end",
+    ),
+    instance(
+        "Enumerator",
+        "rewind",
+        &[],
+        "Enumerator",
+        "Move the cursor back to the first item",
+    ),
+    instance("Enumerator", "to_a", &[], "Array", "All of the items"),
+    instance("Enumerator", "size", &[], "Int", "Number of items"),
+    instance(
+        "Enumerator",
+        "each",
+        &[block().optional()],
+        "Enumerator",
+        "Yield every item, leaving the cursor where it is",
+    ),
+    // Hash
+    instance("Hash", "keys", &[], "Array", "The keys in insertion order"),
+    instance(
+        "Hash",
+        "values",
+        &[],
+        "Array",
+        "The values in insertion order",
+    ),
+    instance(
+        "Hash",
+        "has_key?",
+        &[arg("key")],
+        "Bool",
+        "Whether the key is present",
+    ),
+    instance("Hash", "key?", &[arg("key")], "Bool", "Alias for has_key?"),
+    instance(
+        "Hash",
+        "entries",
+        &[],
+        "Array",
+        "The [key, value] pairs in insertion order",
+    ),
+    instance("Hash", "to_a", &[], "Array", "Alias for entries"),
+    instance("Hash", "length", &[], "Int", "Number of entries"),
+    instance("Hash", "size", &[], "Int", "Alias for length"),
+    instance(
+        "Hash",
+        "each",
+        &[block().optional()],
+        "Any",
+        "Yield each [key, value] pair, or the key and value to a two-parameter block; return an Enumerator without a block",
+    ),
+    instance(
+        "Hash",
+        "sort_by",
+        &[block().optional()],
+        "Any",
+        "The [key, value] pairs sorted by the block's result, or an Enumerator without a block",
+    ),
+    instance(
+        "Hash",
+        "min_by",
+        &[block().optional()],
+        "Any",
+        "The [key, value] pair with the smallest block result, or nil; an Enumerator without a block",
+    ),
+    instance(
+        "Hash",
+        "max_by",
+        &[block().optional()],
+        "Any",
+        "The [key, value] pair with the largest block result, or nil; an Enumerator without a block",
+    ),
+    instance(
+        "Hash",
+        "[]",
+        &[arg("key")],
+        "Any",
+        "Value stored under a key, or nil",
+    ),
+    instance(
+        "Hash",
+        "identity?",
+        &[],
+        "Bool",
+        "Whether keys are compared by identity; false for a plain Hash",
+    ),
+    singleton(
+        "Hash",
+        "identity",
+        &[],
+        "IdentityHash",
+        "An empty Hash whose keys are compared by identity",
+    ),
+    // IdentityHash
+    instance(
+        "IdentityHash",
+        "[]",
+        &[arg("key")],
+        "Any",
+        "Value stored under this very key object",
+    ),
+    instance(
+        "IdentityHash",
+        "[]=",
+        &[arg("key"), arg("value")],
+        "Any",
+        "Store a value under a key object and return the value",
+    ),
+    instance(
+        "IdentityHash",
+        "has_key?",
+        &[arg("key")],
+        "Bool",
+        "Whether this very key object is present",
+    ),
+    instance(
+        "IdentityHash",
+        "key?",
+        &[arg("key")],
+        "Bool",
+        "Alias for has_key?",
+    ),
+    instance(
+        "IdentityHash",
+        "delete",
+        &[arg("key")],
+        "Any",
+        "Remove a key and return its value, or nil",
+    ),
+    instance(
+        "IdentityHash",
+        "keys",
+        &[],
+        "Array",
+        "The key objects in insertion order",
+    ),
+    instance(
+        "IdentityHash",
+        "values",
+        &[],
+        "Array",
+        "The values in insertion order",
+    ),
+    instance(
+        "IdentityHash",
+        "entries",
+        &[],
+        "Array",
+        "The [key, value] pairs in insertion order",
+    ),
+    instance("IdentityHash", "to_a", &[], "Array", "Alias for entries"),
+    instance("IdentityHash", "length", &[], "Int", "Number of entries"),
+    instance("IdentityHash", "size", &[], "Int", "Alias for length"),
+    instance("IdentityHash", "identity?", &[], "Bool", "Always true"),
+    // Integer
+    instance(
+        "Integer",
+        "times",
+        &[block().optional()],
+        "Any",
+        "Yield 0 up to the receiver minus one, or return an Enumerator",
+    ),
+    instance(
+        "Integer",
+        "upto",
+        &[arg("limit").of("Int"), block().optional()],
+        "Any",
+        "Yield each integer up to `limit`, or return an Enumerator",
+    ),
+    instance(
+        "Integer",
+        "downto",
+        &[arg("limit").of("Int"), block().optional()],
+        "Any",
+        "Yield each integer down to `limit`, or return an Enumerator",
+    ),
+    instance("Integer", "abs", &[], "Int", "The absolute value"),
+    instance(
+        "Integer",
+        "even?",
+        &[],
+        "Bool",
+        "Whether the receiver is divisible by 2",
+    ),
+    instance(
+        "Integer",
+        "odd?",
+        &[],
+        "Bool",
+        "Whether the receiver is not divisible by 2",
+    ),
+    instance(
+        "Integer",
+        "gcd",
+        &[arg("other").of("Int")],
+        "Int",
+        "The greatest common divisor, which is never negative",
+    ),
+    instance(
+        "Integer",
+        "to_s",
+        &[arg("base").of("Int").optional()],
+        "String",
+        "Write in the given base, 2 to 36 (default 10)",
+    ),
+    instance("Integer", "to_f", &[], "Float", "The same value as a Float"),
+    instance(
+        "Integer",
+        "to_d",
+        &[],
+        "Decimal",
+        "The same value as a Decimal",
+    ),
+    instance("Integer", "to_int", &[], "Int", "The receiver itself"),
+    instance(
+        "Integer",
+        "format",
+        &[keywords("options")],
+        "String",
+        "Format with `delimiter:`, `separator:` and `precision:`",
+    ),
+    instance(
+        "Integer",
+        "humanize",
+        &[keywords("options")],
+        "String",
+        "Scale to a K, M, B or T suffix, like 1.2M, with `precision:` (default 1)",
+    ),
+    instance(
+        "Integer",
+        "to_duration",
+        &[],
+        "String",
+        "Read as seconds and write as days, hours, minutes and seconds, like 1h 2m 5s",
+    ),
+    instance(
+        "Integer",
+        "ago_in_words",
+        &[],
+        "String",
+        "Read as seconds elapsed and describe them, like about 3 hours ago",
+    ),
+    // Float
+    singleton("Float", "INFINITY", &[], "Float", "Positive infinity"),
+    singleton("Float", "NAN", &[], "Float", "Not a number"),
+    singleton(
+        "Float",
+        "EPSILON",
+        &[],
+        "Float",
+        "Difference between 1.0 and the next Float",
+    ),
+    singleton("Float", "MAX", &[], "Float", "Largest finite Float"),
+    singleton(
+        "Float",
+        "MIN",
+        &[],
+        "Float",
+        "Smallest positive normal Float",
+    ),
+    instance(
+        "Float",
+        "round",
+        &[arg("precision").of("Int").optional()],
+        "Numeric",
+        "Nearest Integer, or a Float rounded to `precision` decimal places",
+    ),
+    instance(
+        "Float",
+        "floor",
+        &[],
+        "Int",
+        "Largest Integer not above the value",
+    ),
+    instance(
+        "Float",
+        "ceil",
+        &[],
+        "Int",
+        "Smallest Integer not below the value",
+    ),
+    instance(
+        "Float",
+        "to_i",
+        &[],
+        "Int",
+        "The value truncated toward zero",
+    ),
+    instance(
+        "Float",
+        "to_int",
+        &[],
+        "Int",
+        "The Int the value equals; raises TypeError unless it is a whole number",
+    ),
+    instance("Float", "abs", &[], "Float", "The absolute value"),
+    instance(
+        "Float",
+        "finite?",
+        &[],
+        "Bool",
+        "Whether the value is neither infinite nor NaN",
+    ),
+    instance("Float", "nan?", &[], "Bool", "Whether the value is NaN"),
+    instance(
+        "Float",
+        "infinite?",
+        &[],
+        "Bool",
+        "Whether the value is infinite",
+    ),
+    instance(
+        "Float",
+        "format",
+        &[keywords("options")],
+        "String",
+        "Format with `delimiter:`, `separator:` and `precision:` (default 2)",
+    ),
+    instance(
+        "Float",
+        "humanize",
+        &[keywords("options")],
+        "String",
+        "Scale to a K, M, B or T suffix, like 1.2M, with `precision:` (default 1)",
+    ),
+    instance(
+        "Float",
+        "to_d",
+        &[],
+        "Decimal",
+        "The value as written, like 0.1, as an exact Decimal",
+    ),
+    instance(
+        "Float",
+        "to_duration",
+        &[],
+        "String",
+        "Read as seconds and write as days, hours, minutes and seconds, like 1h 2m 5s",
+    ),
+    instance(
+        "Float",
+        "ago_in_words",
+        &[],
+        "String",
+        "Read as seconds elapsed and describe them, like about 3 hours ago",
+    ),
+    // Decimal
+    singleton(
+        "Decimal",
+        "new",
+        &[arg("value")],
+        "Decimal",
+        "Exact Decimal from a String like \"12.50\", an Int or a Float",
+    ),
+    singleton(
+        "Decimal",
+        "rounding_mode",
+        &[arg("mode").of("Symbol").optional()],
+        "Symbol",
+        "The default rounding mode; given one, set it and return the old mode",
+    ),
+    instance(
+        "Decimal",
+        "round",
+        &[
+            arg("places").of("Int").optional(),
+            arg("mode").of("Symbol").optional(),
+        ],
+        "Decimal",
+        "Round to `places` (default 0) by :half_up, :half_even, :floor... or the default mode",
+    ),
+    instance(
+        "Decimal",
+        "floor",
+        &[arg("places").of("Int").optional()],
+        "Decimal",
+        "Round toward negative infinity to `places` (default 0)",
+    ),
+    instance(
+        "Decimal",
+        "ceil",
+        &[arg("places").of("Int").optional()],
+        "Decimal",
+        "Round toward positive infinity to `places` (default 0)",
+    ),
+    instance(
+        "Decimal",
+        "truncate",
+        &[arg("places").of("Int").optional()],
+        "Decimal",
+        "Drop the digits past `places` (default 0)",
+    ),
+    instance(
+        "Decimal",
+        "to_i",
+        &[],
+        "Int",
+        "The value truncated toward zero",
+    ),
+    instance(
+        "Decimal",
+        "to_int",
+        &[],
+        "Int",
+        "The value truncated toward zero",
+    ),
+    instance(
+        "Decimal",
+        "to_f",
+        &[],
+        "Float",
+        "The nearest Float, which may lose digits",
+    ),
+    instance("Decimal", "to_d", &[], "Decimal", "The receiver itself"),
+    instance("Decimal", "abs", &[], "Decimal", "The absolute value"),
+    instance(
+        "Decimal",
+        "scale",
+        &[],
+        "Int",
+        "Number of digits after the decimal point",
+    ),
+    instance("Decimal", "zero?", &[], "Bool", "Whether the value is zero"),
+    instance(
+        "Decimal",
+        "positive?",
+        &[],
+        "Bool",
+        "Whether the value is above zero",
+    ),
+    instance(
+        "Decimal",
+        "negative?",
+        &[],
+        "Bool",
+        "Whether the value is below zero",
+    ),
+    instance(
+        "Decimal",
+        "format",
+        &[keywords("options")],
+        "String",
+        "Format with `delimiter:`, `separator:` and `precision:` (default 2), rounding by the default mode",
+    ),
+    // Range
+    instance(
+        "Range",
+        "each",
+        &[block().optional()],
+        "Any",
+        "Yield each integer in the range, or return an Enumerator",
+    ),
+    instance("Range", "to_a", &[], "Array", "The integers in the range"),
+    instance(
+        "Range",
+        "include?",
+        &[arg("value")],
+        "Bool",
+        "Whether the range covers a value",
+    ),
+    instance(
+        "Range",
+        "map",
+        &[block().optional()],
+        "Any",
+        "Array of the block's results, or an Enumerator without a block",
+    ),
+    // Exception
+    instance(
+        "Exception",
+        "initialize",
+        &[arg("message").of("String").optional()],
+        "Nil",
+        "Create an exception with a message",
+    ),
+    instance(
+        "Exception",
+        "message",
+        &[],
+        "String",
+        "The message the exception was raised with",
+    ),
+    instance(
+        "Exception",
+        "type",
+        &[],
+        "String",
+        "Name of the exception's class",
+    ),
+    instance(
+        "Exception",
+        "exception_type",
+        &[],
+        "String",
+        "Alias for type",
+    ),
+    instance(
+        "Exception",
+        "cause",
+        &[],
+        "Exception?",
+        "The exception this one wraps, or nil",
+    ),
+    instance(
+        "Exception",
+        "details",
+        &[],
+        "Dict",
+        "Structured context about the failure, each also readable as a method",
+    ),
+    instance(
+        "Exception",
+        "backtrace",
+        &[],
+        "Array?",
+        "The call stack when it was raised",
+    ),
+    instance(
+        "Exception",
+        "to_s",
+        &[],
+        "String",
+        "Message with the location, backtrace and causes",
+    ),
+    // Timeout
+    singleton(
+        "Timeout",
+        "timeout",
+        // Left untyped so any other value is the ArgumentError the VM raises
+        &[arg("seconds"), block()],
+        "Any",
+        "Run the block, raising Timeout::Error if it is still running after seconds (nil for no limit)",
+    ),
+    singleton(
+        "Timeout",
+        "Error",
+        &[],
+        "Class",
+        "The error raised when a timeout expires",
+    ),
+    // GC
+    singleton(
+        "GC",
+        "stats",
+        &[],
+        "Dict",
+        "Allocation, collection and pause statistics, and the next collection threshold",
+    ),
+    singleton("GC", "start", &[], "Nil", "Run a collection now"),
+    singleton(
+        "GC",
+        "enable",
+        &[],
+        "Bool",
+        "Allow collections; true if they were disabled",
+    ),
+    singleton(
+        "GC",
+        "disable",
+        &[],
+        "Bool",
+        "Stop collections; true if they were disabled",
+    ),
+    singleton(
+        "GC",
+        "count",
+        &[],
+        "Int",
+        "Number of collections run so far",
+    ),
+    singleton(
+        "GC",
+        "enabled?",
+        &[],
+        "Bool",
+        "Whether collections are allowed",
+    ),
+    singleton(
+        "GC",
+        "stats_by_class",
+        &[],
+        "Dict",
+        "Allocations and live objects for each class",
+    ),
+    singleton(
+        "GC",
+        "assert_no_leaks",
+        &[block()],
+        "Nil",
+        "Run the block, raising if objects it allocated are still alive",
+    ),
+    // File
+    singleton(
+        "File",
+        "fnmatch",
+        &[arg("pattern").of("String"), arg("path").of("String")],
+        "Bool",
+        "Whether a path matches a shell-style glob pattern",
+    ),
+    singleton(
+        "File",
+        "fnmatch?",
+        &[arg("pattern").of("String"), arg("path").of("String")],
+        "Bool",
+        "Alias for fnmatch",
+    ),
+    singleton(
+        "File",
+        "join",
+        &[rest("parts").of("String")],
+        "String",
+        "The parts joined with single / separators",
+    ),
+    singleton(
+        "File",
+        "basename",
+        &[
+            arg("path").of("String"),
+            arg("suffix").of("String").optional(),
+        ],
+        "String",
+        "The last component of a path, without suffix (\".*\" for any extension)",
+    ),
+    singleton(
+        "File",
+        "dirname",
+        &[arg("path").of("String")],
+        "String",
+        "Everything before the last component of a path",
+    ),
+    singleton(
+        "File",
+        "extname",
+        &[arg("path").of("String")],
+        "String",
+        "The extension of a path with its dot, or \"\"",
+    ),
+    singleton(
+        "File",
+        "read",
+        &[arg("path").of("String")],
+        "String",
+        "The whole contents of a file",
+    ),
+    singleton(
+        "File",
+        "write",
+        &[arg("path").of("String"), arg("content")],
+        "Integer",
+        "Replace a file's contents, returning the number of bytes written",
+    ),
+    singleton(
+        "File",
+        "exists?",
+        &[arg("path").of("String")],
+        "Bool",
+        "Whether a file or directory exists at a path",
+    ),
+    singleton(
+        "File",
+        "exist?",
+        &[arg("path").of("String")],
+        "Bool",
+        "Alias for exists?",
+    ),
+    singleton(
+        "File",
+        "delete",
+        &[arg("path").of("String")],
+        "Nil",
+        "Remove a file",
+    ),
+    singleton(
+        "File",
+        "new",
+        &[arg("path").of("String")],
+        "File",
+        "A File for reading an existing file",
+    ),
+    singleton(
+        "File",
+        "open",
+        &[arg("path").of("String"), block().optional()],
+        "Any",
+        "Pass a File to the block, closing it afterwards, and return the block's result, or return the File",
+    ),
+    instance(
+        "File",
+        "path",
+        &[],
+        "String",
+        "The path the File was opened with",
+    ),
+    instance(
+        "File",
+        "read",
+        &[],
+        "String",
+        "The whole contents of the file",
+    ),
+    instance(
+        "File",
+        "close",
+        &[],
+        "Nil",
+        "Release the File; nothing is held open, so this does nothing",
+    ),
+    instance(
+        "File",
+        "each_line",
+        &[keywords("options"), block().optional()],
+        "Any",
+        "Read the file one line at a time, passing each line to the block, or return an Enumerator",
+    ),
+    // URI
+    singleton(
+        "URI",
+        "parse",
+        &[arg("text").of("String")],
+        "URI",
+        "Split a URI into its components",
+    ),
+    singleton(
+        "URI",
+        "encode_www_form",
+        &[arg("params")],
+        "String",
+        "Build a form query from a Hash or [key, value] pairs",
+    ),
+    singleton(
+        "URI",
+        "decode_www_form",
+        &[arg("query").of("String")],
+        "Array",
+        "Split a form query into [key, value] pairs",
+    ),
+    singleton(
+        "URI",
+        "encode_www_form_component",
+        &[arg("text").of("String")],
+        "String",
+        "Percent-encode for a form query, writing spaces as +",
+    ),
+    singleton(
+        "URI",
+        "encode_uri_component",
+        &[arg("text").of("String")],
+        "String",
+        "Percent-encode everything but unreserved characters",
+    ),
+    singleton(
+        "URI",
+        "decode_www_form_component",
+        &[arg("text").of("String")],
+        "String",
+        "Decode a form query component, reading + as a space",
+    ),
+    singleton(
+        "URI",
+        "decode_uri_component",
+        &[arg("text").of("String")],
+        "String",
+        "Decode percent-escapes",
+    ),
+    instance(
+        "URI",
+        "scheme",
+        &[],
+        "String?",
+        "The scheme, such as \"https\"",
+    ),
+    instance(
+        "URI",
+        "userinfo",
+        &[],
+        "String?",
+        "The user information before the host",
+    ),
+    instance("URI", "host", &[], "String?", "The host name"),
+    instance("URI", "port", &[], "Int?", "The explicit port"),
+    instance("URI", "path", &[], "String?", "The path"),
+    instance("URI", "query", &[], "String?", "The query, without the ?"),
+    instance(
+        "URI",
+        "fragment",
+        &[],
+        "String?",
+        "The fragment, without the #",
+    ),
+    instance(
+        "URI",
+        "query_params",
+        &[],
+        "Dict",
+        "The query decoded into a Hash",
+    ),
+    instance(
+        "URI",
+        "to_s",
+        &[],
+        "String",
+        "The URI reassembled from its components",
+    ),
+    // Base64
+    singleton(
+        "Base64",
+        "encode",
+        &[arg("text").of("String")],
+        "String",
+        "Standard Base64",
+    ),
+    singleton(
+        "Base64",
+        "decode",
+        &[arg("text").of("String")],
+        "String",
+        "Decode standard Base64",
+    ),
+    singleton(
+        "Base64",
+        "urlsafe_encode",
+        &[arg("text").of("String")],
+        "String",
+        "URL-safe Base64",
+    ),
+    singleton(
+        "Base64",
+        "urlsafe_decode",
+        &[arg("text").of("String")],
+        "String",
+        "Decode URL-safe Base64",
+    ),
+    // Digest
+    singleton(
+        "Digest",
+        "sha256",
+        &[arg("data").of("String")],
+        "String",
+        "Hex SHA-256 digest",
+    ),
+    singleton(
+        "Digest",
+        "sha1",
+        &[arg("data").of("String")],
+        "String",
+        "Hex SHA-1 digest",
+    ),
+    singleton(
+        "Digest",
+        "md5",
+        &[arg("data").of("String")],
+        "String",
+        "Hex MD5 digest",
+    ),
+    // Inflector
+    singleton(
+        "Inflector",
+        "underscore",
+        &[arg("name")],
+        "String",
+        "UserName or user-name as user_name; :: becomes /",
+    ),
+    singleton(
+        "Inflector",
+        "camelize",
+        &[arg("name"), arg("first_letter").of("Symbol").optional()],
+        "String",
+        "user_name as UserName, or userName with :lower; / becomes ::",
+    ),
+    singleton(
+        "Inflector",
+        "dasherize",
+        &[arg("name")],
+        "String",
+        "user_name as user-name",
+    ),
+    singleton(
+        "Inflector",
+        "titleize",
+        &[arg("name")],
+        "String",
+        "user_name or UserName as User Name",
+    ),
+    singleton(
+        "Inflector",
+        "pluralize",
+        &[arg("name")],
+        "String",
+        "The plural of the name's last word",
+    ),
+    singleton(
+        "Inflector",
+        "singularize",
+        &[arg("name")],
+        "String",
+        "The singular of the name's last word",
+    ),
+    // SQLite
+    singleton(
+        "SQLite",
+        "open",
+        &[arg("path").of("String"), block().optional()],
+        "Any",
+        "Open a database, closing it after the block when one is given",
+    ),
+    instance(
+        "SQLite",
+        "execute",
+        &[arg("sql").of("String"), arg("params").optional()],
+        "Array",
+        "Run one statement and return its rows as Hashes",
+    ),
+    instance(
+        "SQLite",
+        "execute_batch",
+        &[arg("sql").of("String")],
+        "Nil",
+        "Run several statements, such as a schema",
+    ),
+    instance(
+        "SQLite",
+        "prepare",
+        &[arg("sql").of("String")],
+        "SQLite::Statement",
+        "Compile a statement that can be executed repeatedly",
+    ),
+    instance(
+        "SQLite",
+        "transaction",
+        &[block()],
+        "Any",
+        "Commit if the block succeeds, otherwise roll back",
+    ),
+    instance(
+        "SQLite",
+        "changes",
+        &[],
+        "Int",
+        "Rows changed by the last statement",
+    ),
+    instance(
+        "SQLite",
+        "last_insert_row_id",
+        &[],
+        "Int",
+        "Row id of the last insert",
+    ),
+    instance("SQLite", "close", &[], "Nil", "Close the connection"),
+    instance(
+        "SQLite",
+        "closed?",
+        &[],
+        "Bool",
+        "Whether the connection is closed",
+    ),
+    instance(
+        "SQLite::Statement",
+        "execute",
+        &[arg("params").optional()],
+        "Array",
+        "Run the statement with new parameters",
+    ),
+    instance(
+        "SQLite::Statement",
+        "sql",
+        &[],
+        "String",
+        "The statement's SQL",
+    ),
+    // YAML and TOML
+    singleton(
+        "YAML",
+        "parse",
+        &[arg("text").of("String")],
+        "Any",
+        "Read one YAML document",
+    ),
+    singleton(
+        "YAML",
+        "dump",
+        &[arg("value")],
+        "String",
+        "Write a value as YAML",
+    ),
+    singleton(
+        "TOML",
+        "parse",
+        &[arg("text").of("String")],
+        "Dict",
+        "Read a TOML document",
+    ),
+    // Schema
+    singleton("Schema", "string", &[], "Schema", "Schema for a String"),
+    singleton("Schema", "int", &[], "Schema", "Schema for an Int"),
+    singleton("Schema", "float", &[], "Schema", "Schema for a Float"),
+    singleton(
+        "Schema",
+        "number",
+        &[],
+        "Schema",
+        "Schema for an Int, Float or Decimal",
+    ),
+    singleton("Schema", "decimal", &[], "Schema", "Schema for a Decimal"),
+    singleton("Schema", "bool", &[], "Schema", "Schema for true or false"),
+    singleton("Schema", "symbol", &[], "Schema", "Schema for a Symbol"),
+    singleton(
+        "Schema",
+        "any",
+        &[],
+        "Schema",
+        "Schema for any value that is not nil",
+    ),
+    singleton(
+        "Schema",
+        "array",
+        &[arg("items").of("Schema").optional()],
+        "Schema",
+        "Schema for an Array whose items all match `items`",
+    ),
+    singleton(
+        "Schema",
+        "hash",
+        &[keywords("fields")],
+        "Schema",
+        "Schema for a Hash with the given fields, keyed by String or Symbol",
+    ),
+    singleton(
+        "Schema",
+        "one_of",
+        &[rest("values")],
+        "Schema",
+        "Schema for one of the listed values",
+    ),
+    singleton(
+        "Schema",
+        "of",
+        &[arg("class").of("Class")],
+        "Schema",
+        "Schema for an instance of a class or its subclasses",
+    ),
+    instance(
+        "Schema",
+        "optional",
+        &[],
+        "Schema",
+        "Copy of the schema that also accepts nil or a missing field",
+    ),
+    instance(
+        "Schema",
+        "strict",
+        &[],
+        "Schema",
+        "Copy of a Hash schema that rejects fields it does not list",
+    ),
+    instance(
+        "Schema",
+        "where",
+        &[arg("message").of("String").optional(), block()],
+        "Schema",
+        "Copy of the schema with a check the block must pass",
+    ),
+    instance(
+        "Schema",
+        "validate",
+        &[arg("value")],
+        "Array",
+        "Every problem with the value, as Schema::Error objects",
+    ),
+    instance(
+        "Schema",
+        "valid?",
+        &[arg("value")],
+        "Bool",
+        "Whether the value matches the schema",
+    ),
+    instance(
+        "Schema",
+        "validate!",
+        &[arg("value")],
+        "Any",
+        "The value, or raise ValueError listing its problems",
+    ),
+    instance(
+        "Schema",
+        "optional?",
+        &[],
+        "Bool",
+        "Whether the schema accepts nil",
+    ),
+    instance(
+        "Schema",
+        "type",
+        &[],
+        "Symbol",
+        "The kind of value the schema describes, like :hash",
+    ),
+    instance(
+        "Schema::Error",
+        "path",
+        &[],
+        "String",
+        "Where the value is, like `users[2].age`; empty for the value itself",
+    ),
+    instance("Schema::Error", "message", &[], "String", "What is wrong"),
+    instance(
+        "Schema::Error",
+        "to_s",
+        &[],
+        "String",
+        "The path and message",
+    ),
+    // Readline
+    singleton(
+        "Readline",
+        "readline",
+        &[
+            arg("prompt").of("String").optional(),
+            arg("add_to_history").optional(),
+        ],
+        "String?",
+        "Read an edited line, or nil at end of input",
+    ),
+    singleton(
+        "Readline",
+        "history",
+        &[],
+        "Array",
+        "Lines added to the history, oldest first",
+    ),
+    singleton(
+        "Readline",
+        "clear_history",
+        &[],
+        "Nil",
+        "Forget every history line",
+    ),
+    // TracePoint
+    singleton(
+        "TracePoint",
+        "new",
+        &[rest("events").of("Symbol"), block()],
+        "TracePoint",
+        "Trace the listed events, or every event when none are given",
+    ),
+    instance(
+        "TracePoint",
+        "enable",
+        &[block().optional()],
+        "Any",
+        "Start tracing; with a block, trace only while it runs",
+    ),
+    instance(
+        "TracePoint",
+        "disable",
+        &[],
+        "Bool",
+        "Stop tracing, returning whether it was enabled",
+    ),
+    instance(
+        "TracePoint",
+        "enabled?",
+        &[],
+        "Bool",
+        "Whether events are being delivered",
+    ),
+    instance(
+        "TracePoint",
+        "event",
+        &[],
+        "Symbol",
+        "The event: :call, :return, :line, :class or :raise",
+    ),
+    instance("TracePoint", "lineno", &[], "Int", "Line of the event"),
+    instance(
+        "TracePoint",
+        "path",
+        &[],
+        "String?",
+        "File being executed, or nil outside files",
+    ),
+    instance(
+        "TracePoint",
+        "method_name",
+        &[],
+        "String?",
+        "The method or function running, or nil at top level",
+    ),
+    instance(
+        "TracePoint",
+        "class_name",
+        &[],
+        "String?",
+        "Class owning the method, or the class being defined",
+    ),
+    instance(
+        "TracePoint",
+        "binding",
+        &[],
+        "Binding",
+        "The variables visible where the event fired",
+    ),
+    instance(
+        "TracePoint",
+        "return_value",
+        &[],
+        "Any",
+        "Value being returned, for :return events",
+    ),
+    instance(
+        "TracePoint",
+        "raised_exception",
+        &[],
+        "Exception?",
+        "Exception being raised, for :raise events",
+    ),
+    // Class
+    instance(
+        "Class",
+        "new",
+        &[rest("arguments")],
+        "Any",
+        "Create an instance, calling initialize",
+    ),
+    instance("Class", "name", &[], "String", "The class name"),
+    instance(
+        "Class",
+        "class_variable_get",
+        &[arg("name")],
+        "Any",
+        "Read a class variable named like :@@count",
+    ),
+    instance(
+        "Class",
+        "class_variable_set",
+        &[arg("name"), arg("value")],
+        "Any",
+        "Assign a class variable named like :@@count",
+    ),
+    instance(
+        "Class",
+        "class_variable_defined?",
+        &[arg("name")],
+        "Bool",
+        "Whether a class variable is set",
+    ),
+    instance(
+        "Class",
+        "class_variables",
+        &[],
+        "Array",
+        "Names of the class variables as Symbols",
+    ),
+    instance(
+        "Class",
+        "constants",
+        &[],
+        "Array",
+        "Names of the constants and nested classes defined in the class, as Symbols",
+    ),
+    instance(
+        "Class",
+        "include?",
+        &[arg("module").of("Module")],
+        "Bool",
+        "Whether the class or an ancestor includes the module",
+    ),
+    instance(
+        "Class",
+        "define_method",
+        &[arg("name"), block()],
+        "Symbol",
+        "Add an instance method named `name` whose body is the block",
+    ),
+    instance(
+        "Class",
+        "included_modules",
+        &[],
+        "Array",
+        "The modules included directly in the class, in include order",
+    ),
+    // Module
+    instance("Module", "name", &[], "String", "The module name"),
+    instance(
+        "Module",
+        "include?",
+        &[arg("module").of("Module")],
+        "Bool",
+        "Whether the module includes another, directly or through others",
+    ),
+    instance(
+        "Module",
+        "instance_methods",
+        &[],
+        "Array",
+        "Names of the methods the module defines directly, as Symbols",
+    ),
+    instance(
+        "Module",
+        "constants",
+        &[],
+        "Array",
+        "Names of the constants and nested classes defined in the module, as Symbols",
+    ),
+    singleton(
+        "Module",
+        "nesting",
+        &[],
+        "Array",
+        "The classes and modules the call is lexically inside, innermost first",
+    ),
+    // Block
+    instance(
+        "Block",
+        "call",
+        &[rest("arguments")],
+        "Any",
+        "Run the block with arguments",
+    ),
+    instance(
+        "Block",
+        "binding",
+        &[],
+        "Binding",
+        "The variables the block captured",
+    ),
+    // Method
+    instance("Method", "name", &[], "String", "The method name"),
+    instance(
+        "Method",
+        "owner",
+        &[],
+        "String",
+        "Name of the defining class, or \"main\"",
+    ),
+    instance(
+        "Method",
+        "source_location",
+        &[],
+        "Array?",
+        "[file, line] of the definition, with a nil file outside files",
+    ),
+    instance(
+        "Method",
+        "source",
+        &[],
+        "String?",
+        "Source text of the definition",
+    ),
+    instance(
+        "Method",
+        "ast",
+        &[],
+        "Dict",
+        "The definition as nested Hashes and Symbols",
+    ),
+    instance("Method", "parameters", &[], "Array", "The parameter names"),
+    // Metorex
+    singleton(
+        "Metorex",
+        "VERSION",
+        &[],
+        "String",
+        "Version of the interpreter",
+    ),
+    singleton(
+        "Metorex",
+        "features",
+        &[],
+        "Array",
+        "Names of the optional cargo features this build includes",
+    ),
+    singleton(
+        "Metorex",
+        "feature?",
+        &[arg("name")],
+        "Bool",
+        "Whether this build includes an optional feature such as `sqlite`",
+    ),
+    singleton(
+        "Metorex",
+        "vm_options",
+        &[],
+        "Dict",
+        "VM options by their `MX_OPTS` names",
+    ),
+    singleton(
+        "Metorex",
+        "load_path",
+        &[],
+        "Array",
+        "Directories `require_relative` and `require` resolve against",
+    ),
+    singleton(
+        "Metorex",
+        "pp_width",
+        &[arg("width").of("Integer").optional()],
+        "Integer",
+        "The width `pp` and REPL results wrap at; given a width, set it",
+    ),
+    singleton(
+        "Metorex",
+        "parse",
+        &[arg("source").of("String")],
+        "Metorex::Node",
+        "Parse source into a Program node without running it",
+    ),
+    singleton(
+        "Metorex",
+        "eval_ast",
+        &[arg("ast")],
+        "Any",
+        "Run a Program, statement or expression node, or an Array of statements, in the caller's scope",
+    ),
+    // Metorex::Node
+    instance(
+        "Metorex::Node",
+        "type",
+        &[],
+        "Symbol?",
+        "The node's variant, such as :BinaryOp, or nil for plain records like positions",
+    ),
+    instance(
+        "Metorex::Node",
+        "fields",
+        &[],
+        "Array",
+        "Names of the node's fields as Symbols; each is also a reader method",
+    ),
+    instance(
+        "Metorex::Node",
+        "[]",
+        &[arg("field")],
+        "Any",
+        "Value of a field, for names such as `end` that can't be called",
+    ),
+    instance(
+        "Metorex::Node",
+        "to_h",
+        &[],
+        "Dict",
+        "The node and its children as nested Hashes, as Method#ast returns",
+    ),
+    // Binding
+    instance(
+        "Binding",
+        "local_variables",
+        &[],
+        "Array",
+        "Names of the visible variables as Symbols, sorted",
+    ),
+    instance(
+        "Binding",
+        "local_variable_get",
+        &[arg("name")],
+        "Any",
+        "Value of a visible variable",
+    ),
+    instance(
+        "Binding",
+        "eval",
+        &[arg("source").of("String")],
+        "Any",
+        "Run Metorex source with the binding's variables and return the value of its last statement",
+    ),
+];
+
+/// Every native function
+pub const FUNCTIONS: &[BuiltinFunction] = &[
+    function(
+        "puts",
+        &[rest("values")],
+        "Nil",
+        "Print each value on its own line",
+    ),
+    function(
+        "pp",
+        &[rest("values")],
+        "Any",
+        "Pretty-print values and return the argument",
+    ),
+    function(
+        "method",
+        &[arg("name")],
+        "Method",
+        "Capture a function or method as an object",
+    ),
+    function(
+        "require_relative",
+        &[arg("path").of("String")],
+        "Bool",
+        "Load a file relative to the current one, once",
+    ),
+    function(
+        "require",
+        &[arg("name").of("String")],
+        "Bool",
+        "Load a library from the load path, once",
+    ),
+    function(
+        "reload!",
+        &[arg("path").of("String")],
+        "Bool",
+        "Run a file again, reopening the classes it defines",
+    ),
+    function(
+        "with",
+        &[arg("resource"), block()],
+        "Any",
+        "Yield a resource and close it when the block exits",
+    ),
+    function(
+        "gets",
+        &[arg("prompt").of("String").optional()],
+        "String?",
+        "Print an optional prompt, then read a line from standard input, or nil at end of input",
+    ),
+    function(
+        "rand",
+        &[arg("limit").optional()],
+        "Numeric",
+        "Random Float below 1.0, or a random number below `limit`",
+    ),
+    function(
+        "assert",
+        &[arg("condition"), arg("message").of("String").optional()],
+        "Nil",
+        "Raise AssertionError unless the condition holds; skipped when contracts are off",
+    ),
+    function(
+        "raise_unless",
+        &[
+            arg("condition"),
+            arg("error"),
+            arg("message").of("String").optional(),
+        ],
+        "Nil",
+        "Raise a RuntimeError with a message, or the given error class, unless the condition holds",
+    ),
+    function(
+        "format",
+        &[arg("template").of("String"), rest("values")],
+        "String",
+        "Fill %s, %d and %f directives, with optional flags, width and precision, with values",
+    ),
+    function(
+        "sprintf",
+        &[arg("template").of("String"), rest("values")],
+        "String",
+        "Same as format",
+    ),
+    function(
+        "sleep",
+        &[arg("seconds").of("Numeric")],
+        "Int",
+        "Pause for a number of seconds, which may be fractional",
+    ),
+    function(
+        "retry_on",
+        &[arg("error"), keywords("options"), block()],
+        "Any",
+        "Run the block, running it again after a growing pause when it raises the error",
+    ),
+    function(
+        "eval",
+        &[arg("source").of("String")],
+        "Any",
+        "Run Metorex source in the caller's scope and return the value of its last statement",
+    ),
+];
+
+/// The catalog entry for a class
+pub fn find_class(name: &str) -> Option<&'static BuiltinClass> {
+    CLASSES.iter().find(|class| class.name == name)
+}
+
+/// The methods a class defines itself, in catalog order
+pub fn methods_of(class: &str) -> impl Iterator<Item = &'static BuiltinMethod> + '_ {
+    METHODS.iter().filter(move |method| method.class == class)
+}
+
+/// Look up a method on a class or its superclasses
+pub fn find_method(class: &str, name: &str, kind: MethodKind) -> Option<&'static BuiltinMethod> {
+    let mut current = find_class(class)?;
+    loop {
+        if let Some(method) =
+            methods_of(current.name).find(|method| method.name == name && method.kind == kind)
+        {
+            return Some(method);
+        }
+        current = find_class(current.superclass?)?;
+    }
+}
+
+/// The catalog entry for a native function
+pub fn find_function(name: &str) -> Option<&'static BuiltinFunction> {
+    FUNCTIONS.iter().find(|function| function.name == name)
+}
+
+#[derive(Serialize)]
+struct CatalogJson {
+    version: u32,
+    classes: Vec<ClassJson>,
+    functions: Vec<CallableJson>,
+}
+
+#[derive(Serialize)]
+struct ClassJson {
+    name: &'static str,
+    superclass: Option<&'static str>,
+    global: bool,
+    doc: &'static str,
+    methods: Vec<CallableJson>,
+}
+
+#[derive(Serialize)]
+struct CallableJson {
+    name: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kind: Option<MethodKind>,
+    parameters: &'static [BuiltinParam],
+    min_arity: usize,
+    max_arity: Option<usize>,
+    returns: &'static str,
+    doc: &'static str,
+}
+
+impl CallableJson {
+    fn new(
+        name: &'static str,
+        kind: Option<MethodKind>,
+        parameters: &'static [BuiltinParam],
+        returns: &'static str,
+        doc: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            kind,
+            parameters,
+            min_arity: min_arity(parameters),
+            max_arity: max_arity(parameters),
+            returns,
+            doc,
+        }
+    }
+}
+
+/// The whole catalog as pretty-printed JSON
+pub fn to_json() -> String {
+    let catalog = CatalogJson {
+        version: CATALOG_FORMAT_VERSION,
+        classes: CLASSES
+            .iter()
+            .map(|class| ClassJson {
+                name: class.name,
+                superclass: class.superclass,
+                global: class.global,
+                doc: class.doc,
+                methods: methods_of(class.name)
+                    .map(|m| {
+                        CallableJson::new(m.name, Some(m.kind), m.parameters, m.returns, m.doc)
+                    })
+                    .collect(),
+            })
+            .collect(),
+        functions: FUNCTIONS
+            .iter()
+            .map(|f| CallableJson::new(f.name, None, f.parameters, f.returns, f.doc))
+            .collect(),
+    };
+    serde_json::to_string_pretty(&catalog).expect("the catalog serializes to JSON")
+}
+
+/// Render a parameter list for display, with optional parameters in brackets
+pub fn signature(parameters: &[BuiltinParam]) -> String {
+    parameters
+        .iter()
+        .map(|p| {
+            let typed = match p.ty {
+                Some(ty) => format!("{}: {}", p.name, ty),
+                None => p.name.to_string(),
+            };
+            match p.kind {
+                ParamKind::Required => typed,
+                ParamKind::Optional => format!("[{}]", typed),
+                ParamKind::Rest => format!("*{}", typed),
+                ParamKind::Keywords => format!("**{}", typed),
+                ParamKind::Block => format!("&{}", typed),
+                ParamKind::OptionalBlock => format!("[&{}]", typed),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -16,10 +16,21 @@ pub struct BuiltinClasses {
     pub integer_class: Rc<Class>,
     /// Float class
     pub float_class: Rc<Class>,
+    /// Decimal class (exact decimal numbers), also named BigDecimal
+    pub decimal_class: Rc<Class>,
     /// Array class
     pub array_class: Rc<Class>,
     /// Hash/Dictionary class
     pub hash_class: Rc<Class>,
+    /// Class of the identity-keyed Hashes returned by `Hash.identity`;
+    /// reached only through Hash, so it is not registered as a global
+    pub identity_hash_class: Rc<Class>,
+    /// StringBuilder class (mutable buffer behind `String.build`)
+    pub string_builder_class: Rc<Class>,
+    /// CodeBuilder class (indented lines of generated source)
+    pub code_builder_class: Rc<Class>,
+    /// Enumerator class, returned by iterator methods called without a block
+    pub enumerator_class: Rc<Class>,
     /// Set class
     pub set_class: Rc<Class>,
     /// Range class
@@ -34,6 +45,71 @@ pub struct BuiltinClasses {
     pub type_error_class: Rc<Class>,
     /// ValueError class (inherits from StandardError)
     pub value_error_class: Rc<Class>,
+    /// ArgumentError class (inherits from StandardError)
+    pub argument_error_class: Rc<Class>,
+    /// NameError class (inherits from StandardError)
+    pub name_error_class: Rc<Class>,
+    /// NoMethodError class, raised by `send` for a method the receiver
+    /// lacks (inherits from NameError)
+    pub no_method_error_class: Rc<Class>,
+    /// SyntaxError class, raised by `Metorex.parse` (inherits from StandardError)
+    pub syntax_error_class: Rc<Class>,
+    /// ConcurrentModificationError class (inherits from RuntimeError)
+    pub concurrent_modification_error_class: Rc<Class>,
+    /// AssertionError class, raised by `assert` and failed method contracts
+    /// (inherits from StandardError)
+    pub assertion_error_class: Rc<Class>,
+    /// StopIteration class, raised by `Enumerator#next` past the end
+    /// (inherits from StandardError)
+    pub stop_iteration_class: Rc<Class>,
+    /// IOError class, raised when a File operation fails (inherits from
+    /// StandardError)
+    pub io_error_class: Rc<Class>,
+    /// Interrupt class, raised when a host interrupts the VM (inherits from Exception)
+    pub interrupt_class: Rc<Class>,
+    /// Timeout class (runs a block with a time limit)
+    pub timeout_class: Rc<Class>,
+    /// Class of the error `Timeout.timeout` raises (inherits from
+    /// RuntimeError); reached as `Timeout::Error`, so it is not registered as
+    /// a global
+    pub timeout_error_class: Rc<Class>,
+    /// GC class (heap statistics and collection control)
+    pub gc_class: Rc<Class>,
+    /// Module class (`Module.nesting` reflects on the enclosing classes and
+    /// modules)
+    pub module_class: Rc<Class>,
+    /// File class (path helpers, file contents and line-by-line reading)
+    pub file_class: Rc<Class>,
+    /// URI class (parsed URIs and percent-encoding)
+    pub uri_class: Rc<Class>,
+    /// Base64 class (Base64 encoding of Strings)
+    pub base64_class: Rc<Class>,
+    /// Digest class (SHA-256, SHA-1 and MD5 checksums)
+    pub digest_class: Rc<Class>,
+    /// Inflector class (snake_case, CamelCase and plural forms of names)
+    pub inflector_class: Rc<Class>,
+    /// SQLite class (database connections; needs the `sqlite` feature)
+    pub sqlite_class: Rc<Class>,
+    /// Class of prepared statements returned by `SQLite#prepare`; reached
+    /// only through a database, so it is not registered as a global
+    pub sqlite_statement_class: Rc<Class>,
+    /// YAML class (parsing and dumping; needs the `yaml` feature)
+    pub yaml_class: Rc<Class>,
+    /// TOML class (parsing; needs the `toml` feature)
+    pub toml_class: Rc<Class>,
+    /// Schema class (declarative validation of Hashes and Arrays)
+    pub schema_class: Rc<Class>,
+    /// Class of the errors `Schema#validate` returns; reached only through
+    /// Schema, so it is not registered as a global
+    pub schema_error_class: Rc<Class>,
+    /// Readline class (line editing and history for interactive scripts)
+    pub readline_class: Rc<Class>,
+    pub metorex_class: Rc<Class>,
+    /// Class of the syntax tree nodes `Metorex.parse` returns; reached only
+    /// through Metorex, so it is not registered as a global
+    pub ast_node_class: Rc<Class>,
+    /// TracePoint class (call, return, line, class and raise events)
+    pub trace_point_class: Rc<Class>,
 }
 
 impl BuiltinClasses {
@@ -46,10 +122,17 @@ impl BuiltinClasses {
         let string_class = Rc::new(Class::new("String", Some(Rc::clone(&object_class))));
         let integer_class = Rc::new(Class::new("Integer", Some(Rc::clone(&object_class))));
         let float_class = Rc::new(Class::new("Float", Some(Rc::clone(&object_class))));
+        let decimal_class = Rc::new(Class::new("Decimal", Some(Rc::clone(&object_class))));
+        let string_builder_class =
+            Rc::new(Class::new("StringBuilder", Some(Rc::clone(&object_class))));
+        let code_builder_class = Rc::new(Class::new("CodeBuilder", Some(Rc::clone(&object_class))));
 
         // Create collection classes
         let array_class = Rc::new(Class::new("Array", Some(Rc::clone(&object_class))));
         let hash_class = Rc::new(Class::new("Hash", Some(Rc::clone(&object_class))));
+        let identity_hash_class =
+            Rc::new(Class::new("IdentityHash", Some(Rc::clone(&object_class))));
+        let enumerator_class = Rc::new(Class::new("Enumerator", Some(Rc::clone(&object_class))));
         let set_class = Rc::new(Class::new("Set", Some(Rc::clone(&object_class))));
         let range_class = Rc::new(Class::new("Range", Some(Rc::clone(&object_class))));
 
@@ -71,14 +154,80 @@ impl BuiltinClasses {
             "ValueError",
             Some(Rc::clone(&standard_error_class)),
         ));
+        let argument_error_class = Rc::new(Class::new(
+            "ArgumentError",
+            Some(Rc::clone(&standard_error_class)),
+        ));
+        let name_error_class = Rc::new(Class::new(
+            "NameError",
+            Some(Rc::clone(&standard_error_class)),
+        ));
+        let no_method_error_class = Rc::new(Class::new(
+            "NoMethodError",
+            Some(Rc::clone(&name_error_class)),
+        ));
+        let syntax_error_class = Rc::new(Class::new(
+            "SyntaxError",
+            Some(Rc::clone(&standard_error_class)),
+        ));
+        let concurrent_modification_error_class = Rc::new(Class::new(
+            "ConcurrentModificationError",
+            Some(Rc::clone(&runtime_error_class)),
+        ));
+        let assertion_error_class = Rc::new(Class::new(
+            "AssertionError",
+            Some(Rc::clone(&standard_error_class)),
+        ));
+        let stop_iteration_class = Rc::new(Class::new(
+            "StopIteration",
+            Some(Rc::clone(&standard_error_class)),
+        ));
+        let io_error_class = Rc::new(Class::new(
+            "IOError",
+            Some(Rc::clone(&standard_error_class)),
+        ));
+        let interrupt_class = Rc::new(Class::new("Interrupt", Some(Rc::clone(&exception_class))));
+        let timeout_error_class = Rc::new(Class::new(
+            "Timeout::Error",
+            Some(Rc::clone(&runtime_error_class)),
+        ));
+
+        // Create runtime service classes
+        let gc_class = Rc::new(Class::new("GC", Some(Rc::clone(&object_class))));
+        let module_class = Rc::new(Class::new("Module", Some(Rc::clone(&object_class))));
+        let timeout_class = Rc::new(Class::new("Timeout", Some(Rc::clone(&object_class))));
+        let file_class = Rc::new(Class::new("File", Some(Rc::clone(&object_class))));
+        let uri_class = Rc::new(Class::new("URI", Some(Rc::clone(&object_class))));
+        let base64_class = Rc::new(Class::new("Base64", Some(Rc::clone(&object_class))));
+        let digest_class = Rc::new(Class::new("Digest", Some(Rc::clone(&object_class))));
+        let inflector_class = Rc::new(Class::new("Inflector", Some(Rc::clone(&object_class))));
+        let sqlite_class = Rc::new(Class::new("SQLite", Some(Rc::clone(&object_class))));
+        let sqlite_statement_class = Rc::new(Class::new(
+            "SQLite::Statement",
+            Some(Rc::clone(&object_class)),
+        ));
+        let yaml_class = Rc::new(Class::new("YAML", Some(Rc::clone(&object_class))));
+        let toml_class = Rc::new(Class::new("TOML", Some(Rc::clone(&object_class))));
+        let schema_class = Rc::new(Class::new("Schema", Some(Rc::clone(&object_class))));
+        let schema_error_class =
+            Rc::new(Class::new("Schema::Error", Some(Rc::clone(&object_class))));
+        let readline_class = Rc::new(Class::new("Readline", Some(Rc::clone(&object_class))));
+        let trace_point_class = Rc::new(Class::new("TracePoint", Some(Rc::clone(&object_class))));
+        let metorex_class = Rc::new(Class::new("Metorex", Some(Rc::clone(&object_class))));
+        let ast_node_class = Rc::new(Class::new("Metorex::Node", Some(Rc::clone(&object_class))));
 
         Self {
             object_class,
             string_class,
             integer_class,
             float_class,
+            decimal_class,
             array_class,
             hash_class,
+            identity_hash_class,
+            string_builder_class,
+            code_builder_class,
+            enumerator_class,
             set_class,
             range_class,
             exception_class,
@@ -86,6 +235,34 @@ impl BuiltinClasses {
             runtime_error_class,
             type_error_class,
             value_error_class,
+            argument_error_class,
+            name_error_class,
+            no_method_error_class,
+            syntax_error_class,
+            concurrent_modification_error_class,
+            assertion_error_class,
+            stop_iteration_class,
+            io_error_class,
+            interrupt_class,
+            timeout_class,
+            timeout_error_class,
+            gc_class,
+            module_class,
+            file_class,
+            uri_class,
+            base64_class,
+            digest_class,
+            inflector_class,
+            sqlite_class,
+            sqlite_statement_class,
+            yaml_class,
+            toml_class,
+            schema_class,
+            schema_error_class,
+            readline_class,
+            metorex_class,
+            ast_node_class,
+            trace_point_class,
         }
     }
 
@@ -96,6 +273,7 @@ impl BuiltinClasses {
             Object::Bool(_) => Rc::clone(&self.object_class),
             Object::Int(_) => Rc::clone(&self.integer_class),
             Object::Float(_) => Rc::clone(&self.float_class),
+            Object::Decimal(_) => Rc::clone(&self.decimal_class),
             Object::String(_) => Rc::clone(&self.string_class),
             Object::Symbol(_) => Rc::clone(&self.string_class), // Symbols are like strings
             Object::Array(_) => Rc::clone(&self.array_class),
@@ -103,6 +281,7 @@ impl BuiltinClasses {
             Object::Set(_) => Rc::clone(&self.set_class),
             Object::Instance(inst) => Rc::clone(&inst.borrow().class),
             Object::Class(_) => Rc::clone(&self.object_class),
+            Object::Module(_) => Rc::clone(&self.object_class),
             Object::Method(_) => Rc::clone(&self.object_class),
             Object::Block(_) => Rc::clone(&self.object_class),
             Object::Binding(_) => Rc::clone(&self.object_class),
@@ -144,8 +323,19 @@ impl BuiltinClasses {
         classes.insert("String".to_string(), Rc::clone(&self.string_class));
         classes.insert("Integer".to_string(), Rc::clone(&self.integer_class));
         classes.insert("Float".to_string(), Rc::clone(&self.float_class));
+        classes.insert("Decimal".to_string(), Rc::clone(&self.decimal_class));
+        classes.insert("BigDecimal".to_string(), Rc::clone(&self.decimal_class));
         classes.insert("Array".to_string(), Rc::clone(&self.array_class));
         classes.insert("Hash".to_string(), Rc::clone(&self.hash_class));
+        classes.insert(
+            "StringBuilder".to_string(),
+            Rc::clone(&self.string_builder_class),
+        );
+        classes.insert(
+            "CodeBuilder".to_string(),
+            Rc::clone(&self.code_builder_class),
+        );
+        classes.insert("Enumerator".to_string(), Rc::clone(&self.enumerator_class));
         classes.insert("Set".to_string(), Rc::clone(&self.set_class));
         classes.insert("Exception".to_string(), Rc::clone(&self.exception_class));
         classes.insert(
@@ -158,8 +348,64 @@ impl BuiltinClasses {
         );
         classes.insert("TypeError".to_string(), Rc::clone(&self.type_error_class));
         classes.insert("ValueError".to_string(), Rc::clone(&self.value_error_class));
+        classes.insert(
+            "ArgumentError".to_string(),
+            Rc::clone(&self.argument_error_class),
+        );
+        classes.insert("NameError".to_string(), Rc::clone(&self.name_error_class));
+        classes.insert(
+            "NoMethodError".to_string(),
+            Rc::clone(&self.no_method_error_class),
+        );
+        classes.insert(
+            "SyntaxError".to_string(),
+            Rc::clone(&self.syntax_error_class),
+        );
+        classes.insert(
+            "ConcurrentModificationError".to_string(),
+            Rc::clone(&self.concurrent_modification_error_class),
+        );
+        classes.insert(
+            "AssertionError".to_string(),
+            Rc::clone(&self.assertion_error_class),
+        );
+        classes.insert(
+            "StopIteration".to_string(),
+            Rc::clone(&self.stop_iteration_class),
+        );
+        classes.insert("IOError".to_string(), Rc::clone(&self.io_error_class));
+        classes.insert("Interrupt".to_string(), Rc::clone(&self.interrupt_class));
+        classes.insert("Timeout".to_string(), Rc::clone(&self.timeout_class));
+        classes.insert("GC".to_string(), Rc::clone(&self.gc_class));
+        classes.insert("Module".to_string(), Rc::clone(&self.module_class));
+        classes.insert("File".to_string(), Rc::clone(&self.file_class));
+        classes.insert("URI".to_string(), Rc::clone(&self.uri_class));
+        classes.insert("Base64".to_string(), Rc::clone(&self.base64_class));
+        classes.insert("Digest".to_string(), Rc::clone(&self.digest_class));
+        classes.insert("Inflector".to_string(), Rc::clone(&self.inflector_class));
+        classes.insert("SQLite".to_string(), Rc::clone(&self.sqlite_class));
+        classes.insert("YAML".to_string(), Rc::clone(&self.yaml_class));
+        classes.insert("TOML".to_string(), Rc::clone(&self.toml_class));
+        classes.insert("Schema".to_string(), Rc::clone(&self.schema_class));
+        classes.insert("Readline".to_string(), Rc::clone(&self.readline_class));
+        classes.insert("TracePoint".to_string(), Rc::clone(&self.trace_point_class));
+        classes.insert("Metorex".to_string(), Rc::clone(&self.metorex_class));
         classes
     }
+
+    /// Look up a class reached through another class, such as
+    /// `Timeout::Error`, by its full name
+    pub fn scoped_class(&self, name: &str) -> Option<Rc<Class>> {
+        [
+            &self.timeout_error_class,
+            &self.sqlite_statement_class,
+            &self.schema_error_class,
+            &self.ast_node_class,
+        ]
+        .into_iter()
+        .find(|class| class.name() == name)
+        .cloned()
+    }
 }
 
 impl Default for BuiltinClasses {
@@ -185,6 +431,73 @@ pub fn init_object_methods(object_class: &Class) {
         vec![],
     ));
     object_class.define_method("respond_to?", respond_to_method);
+
+    // Object#send - call a method by name
+    let send_method = Rc::new(Method::new(
+        "send".to_string(),
+        vec!["method_name".to_string()],
+        vec![],
+    ));
+    object_class.define_method("send", send_method);
+
+    // Object#__send__ - send under a name classes won't override
+    let underscore_send_method = Rc::new(Method::new(
+        "__send__".to_string(),
+        vec!["method_name".to_string()],
+        vec![],
+    ));
+    object_class.define_method("__send__", underscore_send_method);
+
+    // Object#to_literal - source that evaluates to the value
+    let to_literal_method = Rc::new(Method::new("to_literal".to_string(), vec![], vec![]));
+    object_class.define_method("to_literal", to_literal_method);
+
+    // Object#object_id - stable integer identifying the object
+    let object_id_method = Rc::new(Method::new("object_id".to_string(), vec![], vec![]));
+    object_class.define_method("object_id", object_id_method);
+
+    // Object#equal? - check if two references are the same object
+    let equal_method = Rc::new(Method::new(
+        "equal?".to_string(),
+        vec!["other".to_string()],
+        vec![],
+    ));
+    object_class.define_method("equal?", equal_method);
+
+    // Object#instance_variables - names of the instance variables
+    let instance_variables_method = Rc::new(Method::new(
+        "instance_variables".to_string(),
+        vec![],
+        vec![],
+    ));
+    object_class.define_method("instance_variables", instance_variables_method);
+
+    // Object#instance_variable_get - read an instance variable by name
+    let instance_variable_get_method = Rc::new(Method::new(
+        "instance_variable_get".to_string(),
+        vec!["name".to_string()],
+        vec![],
+    ));
+    object_class.define_method("instance_variable_get", instance_variable_get_method);
+
+    // Object#instance_variable_set - assign an instance variable by name
+    let instance_variable_set_method = Rc::new(Method::new(
+        "instance_variable_set".to_string(),
+        vec!["name".to_string(), "value".to_string()],
+        vec![],
+    ));
+    object_class.define_method("instance_variable_set", instance_variable_set_method);
+
+    // Object#instance_variable_defined? - check if an instance variable is set
+    let instance_variable_defined_method = Rc::new(Method::new(
+        "instance_variable_defined?".to_string(),
+        vec!["name".to_string()],
+        vec![],
+    ));
+    object_class.define_method(
+        "instance_variable_defined?",
+        instance_variable_defined_method,
+    );
 }
 
 /// Initialize built-in methods for the String class
@@ -209,6 +522,18 @@ pub fn init_string_methods(string_class: &Class) {
     ));
     string_class.define_method("+", concat_method);
 
+    // String#%
+    let format_method = Rc::new(Method::new(
+        "%".to_string(),
+        vec!["values".to_string()],
+        vec![],
+    ));
+    string_class.define_method("%", format_method);
+
+    // String#to_source
+    let to_source_method = Rc::new(Method::new("to_source".to_string(), vec![], vec![]));
+    string_class.define_method("to_source", to_source_method);
+
     // String#trim
     let trim_method = Rc::new(Method::new("trim".to_string(), vec![], vec![]));
     string_class.define_method("trim", trim_method);
@@ -224,6 +549,63 @@ pub fn init_string_methods(string_class: &Class) {
     // String#bytes
     let bytes_method = Rc::new(Method::new("bytes".to_string(), vec![], vec![]));
     string_class.define_method("bytes", bytes_method);
+
+    // String#chop, String#to_f, String#to_d, String#hex_encode and
+    // String#hex_decode, and the iterators String#each_char and String#each_byte
+    for name in [
+        "chop",
+        "to_f",
+        "to_d",
+        "hex_encode",
+        "hex_decode",
+        "each_char",
+        "each_byte",
+    ] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        string_class.define_method(name, method);
+    }
+
+    // String#chomp, String#to_i, String#match_glob and String#to_number take
+    // a suffix, base, pattern or keyword options; String#lines and
+    // String#each_line take keyword options
+    for (name, param) in [
+        ("chomp", "suffix"),
+        ("to_i", "base"),
+        ("match_glob", "pattern"),
+        ("to_number", "options"),
+        ("lines", "options"),
+        ("each_line", "options"),
+    ] {
+        let method = Rc::new(Method::new(
+            name.to_string(),
+            vec![param.to_string()],
+            vec![],
+        ));
+        string_class.define_method(name, method);
+    }
+
+    // String#ljust, String#rjust and String#center pad to a width;
+    // String#tr maps one character set onto another
+    for (name, params) in [
+        ("ljust", ["width", "pad"]),
+        ("rjust", ["width", "pad"]),
+        ("center", ["width", "pad"]),
+        ("tr", ["from", "to"]),
+    ] {
+        let params = params.iter().map(|param| param.to_string()).collect();
+        let method = Rc::new(Method::new(name.to_string(), params, vec![]));
+        string_class.define_method(name, method);
+    }
+
+    // String#squeeze, String#delete and String#count take character sets
+    for name in ["squeeze", "delete", "count"] {
+        let method = Rc::new(Method::new(
+            name.to_string(),
+            vec!["sets".to_string()],
+            vec![],
+        ));
+        string_class.define_method(name, method);
+    }
 }
 
 /// Initialize built-in methods for the Array class
@@ -259,6 +641,63 @@ pub fn init_array_methods(array_class: &Class) {
         vec![],
     ));
     array_class.define_method("[]", index_method);
+
+    // Array#to_table
+    let to_table_method = Rc::new(Method::new(
+        "to_table".to_string(),
+        vec!["options".to_string()],
+        vec![],
+    ));
+    array_class.define_method("to_table", to_table_method);
+}
+
+/// Initialize built-in methods for the StringBuilder class
+pub fn init_string_builder_methods(string_builder_class: &Class) {
+    // StringBuilder#<<(value) and StringBuilder#append(value)
+    for name in ["<<", "append"] {
+        let method = Rc::new(Method::new(
+            name.to_string(),
+            vec!["value".to_string()],
+            vec![],
+        ));
+        string_builder_class.define_method(name, method);
+    }
+
+    // StringBuilder#to_s, #length and #empty?
+    for name in ["to_s", "length", "empty?"] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        string_builder_class.define_method(name, method);
+    }
+}
+
+/// Initialize built-in methods for the CodeBuilder class
+pub fn init_code_builder_methods(code_builder_class: &Class) {
+    // CodeBuilder#line(text), #indent and #block(header, footer)
+    let parameters: [(&str, &[&str]); 3] = [
+        ("line", &["text"]),
+        ("indent", &[]),
+        ("block", &["header", "footer"]),
+    ];
+    for (name, parameters) in parameters {
+        let parameters = parameters.iter().map(|name| name.to_string()).collect();
+        let method = Rc::new(Method::new(name.to_string(), parameters, vec![]));
+        code_builder_class.define_method(name, method);
+    }
+
+    // CodeBuilder#dedent, #depth and #to_s
+    for name in ["dedent", "depth", "to_s"] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        code_builder_class.define_method(name, method);
+    }
+}
+
+/// Initialize built-in methods for the Enumerator class
+pub fn init_enumerator_methods(enumerator_class: &Class) {
+    // Enumerator#next, #peek, #rewind, #to_a, #size and #each
+    for name in ["next", "peek", "rewind", "to_a", "size", "each"] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        enumerator_class.define_method(name, method);
+    }
 }
 
 /// Initialize built-in methods for the Float class
@@ -270,6 +709,277 @@ pub fn init_float_methods(float_class: &Class) {
         vec![],
     ));
     float_class.define_method("round", round_method);
+
+    // Float#floor, Float#ceil, Float#to_i, Float#to_int
+    for name in ["floor", "ceil", "to_i", "to_int"] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        float_class.define_method(name, method);
+    }
+
+    // Float#abs, Float#finite?, Float#nan?, Float#infinite?
+    for name in ["abs", "finite?", "nan?", "infinite?"] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        float_class.define_method(name, method);
+    }
+
+    // Float#format
+    let format_method = Rc::new(Method::new(
+        "format".to_string(),
+        vec!["options".to_string()],
+        vec![],
+    ));
+    float_class.define_method("format", format_method);
+
+    // Float#humanize
+    let humanize_method = Rc::new(Method::new(
+        "humanize".to_string(),
+        vec!["options".to_string()],
+        vec![],
+    ));
+    float_class.define_method("humanize", humanize_method);
+
+    // Float#to_d
+    let to_d_method = Rc::new(Method::new("to_d".to_string(), vec![], vec![]));
+    float_class.define_method("to_d", to_d_method);
+
+    // Float#to_duration and Float#ago_in_words read the value as seconds
+    for name in ["to_duration", "ago_in_words"] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        float_class.define_method(name, method);
+    }
+}
+
+/// Initialize built-in methods for the Decimal class
+pub fn init_decimal_methods(decimal_class: &Class) {
+    // Decimal#round takes the places and rounding mode; floor, ceil and
+    // truncate just the places
+    let round_method = Rc::new(Method::new(
+        "round".to_string(),
+        vec!["places".to_string(), "mode".to_string()],
+        vec![],
+    ));
+    decimal_class.define_method("round", round_method);
+    for name in ["floor", "ceil", "truncate"] {
+        let method = Rc::new(Method::new(
+            name.to_string(),
+            vec!["places".to_string()],
+            vec![],
+        ));
+        decimal_class.define_method(name, method);
+    }
+
+    // Conversions and predicates
+    for name in [
+        "to_i",
+        "to_int",
+        "to_f",
+        "to_d",
+        "abs",
+        "scale",
+        "zero?",
+        "positive?",
+        "negative?",
+    ] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        decimal_class.define_method(name, method);
+    }
+
+    // Decimal#format
+    let format_method = Rc::new(Method::new(
+        "format".to_string(),
+        vec!["options".to_string()],
+        vec![],
+    ));
+    decimal_class.define_method("format", format_method);
+}
+
+/// Initialize built-in methods for the Integer class
+pub fn init_integer_methods(integer_class: &Class) {
+    // Integer#times
+    let times_method = Rc::new(Method::new("times".to_string(), vec![], vec![]));
+    integer_class.define_method("times", times_method);
+
+    // Integer#upto and Integer#downto
+    for name in ["upto", "downto"] {
+        let method = Rc::new(Method::new(
+            name.to_string(),
+            vec!["limit".to_string()],
+            vec![],
+        ));
+        integer_class.define_method(name, method);
+    }
+
+    // Integer#abs, Integer#even? and Integer#odd?
+    for name in ["abs", "even?", "odd?"] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        integer_class.define_method(name, method);
+    }
+
+    // Integer#gcd
+    let gcd_method = Rc::new(Method::new(
+        "gcd".to_string(),
+        vec!["other".to_string()],
+        vec![],
+    ));
+    integer_class.define_method("gcd", gcd_method);
+
+    // Integer#to_s, with an optional base
+    let to_s_method = Rc::new(Method::new(
+        "to_s".to_string(),
+        vec!["base".to_string()],
+        vec![],
+    ));
+    integer_class.define_method("to_s", to_s_method);
+
+    // Integer#to_f, Integer#to_d and Integer#to_int
+    for name in ["to_f", "to_d", "to_int"] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        integer_class.define_method(name, method);
+    }
+
+    // Integer#format
+    let format_method = Rc::new(Method::new(
+        "format".to_string(),
+        vec!["options".to_string()],
+        vec![],
+    ));
+    integer_class.define_method("format", format_method);
+
+    // Integer#humanize
+    let humanize_method = Rc::new(Method::new(
+        "humanize".to_string(),
+        vec!["options".to_string()],
+        vec![],
+    ));
+    integer_class.define_method("humanize", humanize_method);
+
+    // Integer#to_duration and Integer#ago_in_words read the value as seconds
+    for name in ["to_duration", "ago_in_words"] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        integer_class.define_method(name, method);
+    }
+}
+
+/// Initialize built-in methods for File instances
+pub fn init_file_methods(file_class: &Class) {
+    // File#path, File#read and File#close
+    for name in ["path", "read", "close"] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        file_class.define_method(name, method);
+    }
+
+    // File#each_line takes keyword options
+    let each_line_method = Rc::new(Method::new(
+        "each_line".to_string(),
+        vec!["options".to_string()],
+        vec![],
+    ));
+    file_class.define_method("each_line", each_line_method);
+}
+
+/// Initialize built-in methods for URI instances
+pub fn init_uri_methods(uri_class: &Class) {
+    // URI#scheme, URI#userinfo, URI#host, URI#port, URI#path, URI#query,
+    // URI#fragment and URI#query_params
+    for name in [
+        "scheme",
+        "userinfo",
+        "host",
+        "port",
+        "path",
+        "query",
+        "fragment",
+        "query_params",
+    ] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        uri_class.define_method(name, method);
+    }
+}
+
+/// Initialize built-in methods for Schema instances and their errors
+pub fn init_schema_methods(schema_class: &Class, schema_error_class: &Class) {
+    // Schema#optional, Schema#strict, Schema#where, Schema#validate,
+    // Schema#valid?, Schema#validate!, Schema#optional? and Schema#type
+    for name in ["optional", "strict", "optional?", "type"] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        schema_class.define_method(name, method);
+    }
+    let where_method = Rc::new(Method::new(
+        "where".to_string(),
+        vec!["message".to_string()],
+        vec![],
+    ));
+    schema_class.define_method("where", where_method);
+    for name in ["validate", "valid?", "validate!"] {
+        let method = Rc::new(Method::new(
+            name.to_string(),
+            vec!["value".to_string()],
+            vec![],
+        ));
+        schema_class.define_method(name, method);
+    }
+
+    // Schema::Error#path, Schema::Error#message and Schema::Error#to_s
+    for name in ["path", "message", "to_s"] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        schema_error_class.define_method(name, method);
+    }
+}
+
+/// Initialize built-in methods for TracePoint instances
+pub fn init_trace_point_methods(trace_point_class: &Class) {
+    // TracePoint#enable, TracePoint#disable, TracePoint#enabled? and the
+    // readers for the event being delivered
+    for name in [
+        "enable",
+        "disable",
+        "enabled?",
+        "event",
+        "lineno",
+        "path",
+        "method_name",
+        "class_name",
+        "binding",
+        "return_value",
+        "raised_exception",
+    ] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        trace_point_class.define_method(name, method);
+    }
+}
+
+/// Initialize built-in methods for SQLite databases and prepared statements
+pub fn init_sqlite_methods(sqlite_class: &Class, statement_class: &Class) {
+    // SQLite#execute(sql, params), SQLite#execute_batch, SQLite#prepare
+    for (name, params) in [
+        ("execute", vec!["sql", "params"]),
+        ("execute_batch", vec!["sql"]),
+        ("prepare", vec!["sql"]),
+        ("transaction", vec!["block"]),
+    ] {
+        let method = Rc::new(Method::new(
+            name.to_string(),
+            params.into_iter().map(String::from).collect(),
+            vec![],
+        ));
+        sqlite_class.define_method(name, method);
+    }
+
+    // SQLite#changes, SQLite#last_insert_row_id, SQLite#close, SQLite#closed?
+    for name in ["changes", "last_insert_row_id", "close", "closed?"] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        sqlite_class.define_method(name, method);
+    }
+
+    // SQLite::Statement#execute(params) and SQLite::Statement#sql
+    let execute_method = Rc::new(Method::new(
+        "execute".to_string(),
+        vec!["params".to_string()],
+        vec![],
+    ));
+    statement_class.define_method("execute", execute_method);
+    let sql_method = Rc::new(Method::new("sql".to_string(), vec![], vec![]));
+    statement_class.define_method("sql", sql_method);
 }
 
 /// Initialize built-in methods for the Hash class
@@ -298,6 +1008,20 @@ pub fn init_hash_methods(hash_class: &Class) {
     let to_a_method = Rc::new(Method::new("to_a".to_string(), vec![], vec![]));
     hash_class.define_method("to_a", to_a_method);
 
+    // Hash#each
+    let each_method = Rc::new(Method::new("each".to_string(), vec![], vec![]));
+    hash_class.define_method("each", each_method);
+
+    // Hash#sort_by, Hash#min_by and Hash#max_by
+    for name in ["sort_by", "min_by", "max_by"] {
+        let method = Rc::new(Method::new(
+            name.to_string(),
+            vec!["block".to_string()],
+            vec![],
+        ));
+        hash_class.define_method(name, method);
+    }
+
     // Hash#length
     let length_method = Rc::new(Method::new("length".to_string(), vec![], vec![]));
     hash_class.define_method("length", length_method);
@@ -313,6 +1037,63 @@ pub fn init_hash_methods(hash_class: &Class) {
         vec![],
     ));
     hash_class.define_method("[]", index_method);
+
+    // Hash#identity?
+    let identity_method = Rc::new(Method::new("identity?".to_string(), vec![], vec![]));
+    hash_class.define_method("identity?", identity_method);
+}
+
+/// Initialize built-in methods for identity Hashes
+pub fn init_identity_hash_methods(identity_hash_class: &Class) {
+    // IdentityHash#[](key), IdentityHash#has_key?(key), IdentityHash#key?(key)
+    // and IdentityHash#delete(key)
+    for name in ["[]", "has_key?", "key?", "delete"] {
+        let method = Rc::new(Method::new(
+            name.to_string(),
+            vec!["key".to_string()],
+            vec![],
+        ));
+        identity_hash_class.define_method(name, method);
+    }
+
+    // IdentityHash#[]=(key, value)
+    let store_method = Rc::new(Method::new(
+        "[]=".to_string(),
+        vec!["key".to_string(), "value".to_string()],
+        vec![],
+    ));
+    identity_hash_class.define_method("[]=", store_method);
+
+    // IdentityHash#keys, #values, #entries, #to_a, #length, #size, #identity?
+    for name in [
+        "keys",
+        "values",
+        "entries",
+        "to_a",
+        "length",
+        "size",
+        "identity?",
+    ] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        identity_hash_class.define_method(name, method);
+    }
+}
+
+/// Initialize built-in methods for syntax tree nodes
+pub fn init_ast_node_methods(ast_node_class: &Class) {
+    // Metorex::Node#[](field)
+    let index_method = Rc::new(Method::new(
+        "[]".to_string(),
+        vec!["field".to_string()],
+        vec![],
+    ));
+    ast_node_class.define_method("[]", index_method);
+
+    // Metorex::Node#type, Metorex::Node#fields and Metorex::Node#to_h
+    for name in ["type", "fields", "to_h"] {
+        let method = Rc::new(Method::new(name.to_string(), vec![], vec![]));
+        ast_node_class.define_method(name, method);
+    }
 }
 
 /// Initialize built-in methods for the Exception class
@@ -340,4 +1121,8 @@ pub fn init_exception_methods(exception_class: &Class) {
     // Exception#cause
     let cause_method = Rc::new(Method::new("cause".to_string(), vec![], vec![]));
     exception_class.define_method("cause", cause_method);
+
+    // Exception#details
+    let details_method = Rc::new(Method::new("details".to_string(), vec![], vec![]));
+    exception_class.define_method("details", details_method);
 }
@@ -0,0 +1,165 @@
+// Character sets written like the arguments of tr
+// Backs String#tr, String#delete, String#squeeze and String#count.
+//
+// Supported syntax:
+//   abc     the listed characters
+//   a-z     an inclusive range; a '-' at either end is literal
+//   ^abc    every character except the listed ones (only at the start, and
+//           only when something follows the '^')
+//   \x      the character x literally, so "\-" and "\^" escape the syntax
+
+/// A set of characters given by a tr-style specification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharSet {
+    ranges: Vec<(char, char)>,
+    negated: bool,
+}
+
+impl CharSet {
+    /// Parse a specification such as "a-z", "^aeiou" or "\\-+"
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let negated = spec.starts_with('^') && spec.chars().count() > 1;
+        let body = if negated { &spec[1..] } else { spec };
+        Ok(Self {
+            ranges: parse_ranges(body)?,
+            negated,
+        })
+    }
+
+    /// Parse a specification in which a leading '^' is an ordinary character,
+    /// as for the replacement side of tr
+    pub fn parse_sequence(spec: &str) -> Result<Self, String> {
+        Ok(Self {
+            ranges: parse_ranges(spec)?,
+            negated: false,
+        })
+    }
+
+    /// Whether the set holds `ch`
+    pub fn contains(&self, ch: char) -> bool {
+        let listed = self
+            .ranges
+            .iter()
+            .any(|&(start, end)| (start..=end).contains(&ch));
+        listed != self.negated
+    }
+
+    /// Whether the set is written with a leading '^'
+    pub fn is_negated(&self) -> bool {
+        self.negated
+    }
+
+    /// Whether the specification lists no characters
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Position of `ch` in the listed characters, counting ranges expanded
+    fn index_of(&self, ch: char) -> Option<usize> {
+        let mut offset = 0;
+        for &(start, end) in &self.ranges {
+            if (start..=end).contains(&ch) {
+                return Some(offset + (ch as usize - start as usize));
+            }
+            offset += end as usize - start as usize + 1;
+        }
+        None
+    }
+
+    /// The listed character at `index`, or the last one when `index` is past
+    /// the end, which is how tr pads a short replacement
+    fn char_at(&self, index: usize) -> Option<char> {
+        let mut offset = 0;
+        for &(start, end) in &self.ranges {
+            let len = end as usize - start as usize + 1;
+            if index < offset + len {
+                return char::from_u32(start as u32 + (index - offset) as u32);
+            }
+            offset += len;
+        }
+        self.ranges.last().map(|&(_, end)| end)
+    }
+}
+
+/// Whether `ch` belongs to every one of `sets`
+pub fn in_all(sets: &[CharSet], ch: char) -> bool {
+    sets.iter().all(|set| set.contains(ch))
+}
+
+/// Replace each character of `text` found in `from` by the character at the
+/// same position in `to`; with an empty `to` the characters are removed
+pub fn translate(text: &str, from: &CharSet, to: &CharSet) -> String {
+    text.chars()
+        .filter_map(|ch| {
+            if !from.contains(ch) {
+                return Some(ch);
+            }
+            if from.is_negated() {
+                return to.char_at(usize::MAX);
+            }
+            from.index_of(ch).and_then(|index| to.char_at(index))
+        })
+        .collect()
+}
+
+/// Collapse runs of the same character into one, limited to characters in
+/// every one of `sets` when any are given
+pub fn squeeze(text: &str, sets: &[CharSet]) -> String {
+    let mut squeezed = String::with_capacity(text.len());
+    let mut previous = None;
+    for ch in text.chars() {
+        if previous == Some(ch) && in_all(sets, ch) {
+            continue;
+        }
+        squeezed.push(ch);
+        previous = Some(ch);
+    }
+    squeezed
+}
+
+/// Split a specification into inclusive ranges, single characters being
+/// ranges of one
+fn parse_ranges(spec: &str) -> Result<Vec<(char, char)>, String> {
+    let mut chars = Vec::new();
+    let mut escaped = Vec::new();
+    let mut iter = spec.chars();
+    while let Some(ch) = iter.next() {
+        match ch {
+            '\\' => match iter.next() {
+                Some(next) => {
+                    chars.push(next);
+                    escaped.push(true);
+                }
+                None => {
+                    chars.push('\\');
+                    escaped.push(false);
+                }
+            },
+            _ => {
+                chars.push(ch);
+                escaped.push(false);
+            }
+        }
+    }
+
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let is_range = i + 2 < chars.len() && chars[i + 1] == '-' && !escaped[i + 1];
+        if is_range {
+            let (start, end) = (chars[i], chars[i + 2]);
+            if start > end {
+                return Err(format!(
+                    "invalid range \"{}-{}\" in character set",
+                    start, end
+                ));
+            }
+            ranges.push((start, end));
+            i += 3;
+        } else {
+            ranges.push((chars[i], chars[i]));
+            i += 1;
+        }
+    }
+    Ok(ranges)
+}
@@ -1,6 +1,7 @@
 //! Runtime class representation for Metorex
-//! Handles method tables, inheritance, and instance variable declarations.
+//! Handles method tables, inheritance, mixed-in modules, and instance variable declarations.
 
+use crate::module::{self, Module};
 use crate::object::{Method, Object};
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
@@ -12,8 +13,13 @@ pub struct Class {
     name: String,
     superclass: Option<Rc<Class>>,
     methods: RefCell<HashMap<String, Rc<Method>>>,
+    private_methods: RefCell<HashSet<String>>,
     instance_variables: RefCell<HashSet<String>>,
     class_variables: RefCell<HashMap<String, crate::object::Object>>,
+    constants: RefCell<HashMap<String, Object>>,
+    variants: RefCell<Option<Vec<String>>>,
+    included_modules: RefCell<Vec<Rc<Module>>>,
+    extended_modules: RefCell<Vec<Rc<Module>>>,
 }
 
 impl Class {
@@ -23,8 +29,13 @@ impl Class {
             name: name.into(),
             superclass,
             methods: RefCell::new(HashMap::new()),
+            private_methods: RefCell::new(HashSet::new()),
             instance_variables: RefCell::new(HashSet::new()),
             class_variables: RefCell::new(HashMap::new()),
+            constants: RefCell::new(HashMap::new()),
+            variants: RefCell::new(None),
+            included_modules: RefCell::new(Vec::new()),
+            extended_modules: RefCell::new(Vec::new()),
         }
     }
 
@@ -68,7 +79,28 @@ impl Class {
 
     /// Define or replace a method on this class.
     pub fn define_method(&self, name: impl Into<String>, method: Rc<Method>) {
-        self.methods.borrow_mut().insert(name.into(), method);
+        let name = name.into();
+        self.private_methods.borrow_mut().remove(&name);
+        self.methods.borrow_mut().insert(name, method);
+    }
+
+    /// Define or replace a method that can only be called without an
+    /// explicit receiver (or with `self.`).
+    pub fn define_private_method(&self, name: impl Into<String>, method: Rc<Method>) {
+        let name = name.into();
+        self.private_methods.borrow_mut().insert(name.clone());
+        self.methods.borrow_mut().insert(name, method);
+    }
+
+    /// Check if the method `name` resolves to a private definition, looking
+    /// at the class that defines it (this one or the nearest ancestor).
+    pub fn is_private_method(&self, name: &str) -> bool {
+        if self.has_own_method(name) {
+            return self.private_methods.borrow().contains(name);
+        }
+        self.superclass
+            .as_ref()
+            .is_some_and(|superclass| superclass.is_private_method(name))
     }
 
     /// Determine whether this class defines a method (without checking superclasses).
@@ -76,17 +108,70 @@ impl Class {
         self.methods.borrow().contains_key(name)
     }
 
-    /// Look up a method by walking the inheritance chain.
+    /// Look up a method by walking the inheritance chain. A class's own
+    /// methods come first, then those of the modules it includes (the most
+    /// recently included first), then its superclass.
     pub fn find_method(&self, name: &str) -> Option<Rc<Method>> {
         if let Some(method) = self.methods.borrow().get(name) {
             return Some(Rc::clone(method));
         }
+        if let Some(method) = module::find_in_modules(&self.included_modules.borrow(), name) {
+            return Some(method);
+        }
 
         self.superclass
             .as_ref()
             .and_then(|superclass| superclass.find_method(name))
     }
 
+    /// Look up a method called on the class itself: the modules it extends
+    /// (and those its superclasses extend) come before its instance methods.
+    pub fn find_class_method(&self, name: &str) -> Option<Rc<Method>> {
+        self.find_extended_method(name)
+            .or_else(|| self.find_method(name))
+    }
+
+    /// Look up a method in the modules this class or an ancestor extends.
+    fn find_extended_method(&self, name: &str) -> Option<Rc<Method>> {
+        module::find_in_modules(&self.extended_modules.borrow(), name).or_else(|| {
+            self.superclass
+                .as_ref()
+                .and_then(|superclass| superclass.find_extended_method(name))
+        })
+    }
+
+    /// Mix a module's methods into this class's instances. Returns `false`
+    /// if the class already includes it.
+    pub fn include_module(&self, module: &Rc<Module>) -> bool {
+        module::include_into(&self.included_modules, module)
+    }
+
+    /// Make a module's methods callable on this class itself. Returns
+    /// `false` if the class already extends it.
+    pub fn extend_module(&self, module: &Rc<Module>) -> bool {
+        module::include_into(&self.extended_modules, module)
+    }
+
+    /// The modules included directly in this class, in the order they were included.
+    pub fn included_modules(&self) -> Vec<Rc<Module>> {
+        self.included_modules.borrow().clone()
+    }
+
+    /// The modules this class extends, in the order they were added.
+    pub fn extended_modules(&self) -> Vec<Rc<Module>> {
+        self.extended_modules.borrow().clone()
+    }
+
+    /// Check if this class or an ancestor includes `module`, directly or
+    /// through another module.
+    pub fn includes_module(&self, module: &Module) -> bool {
+        module::modules_include(&self.included_modules.borrow(), module)
+            || self
+                .superclass
+                .as_ref()
+                .is_some_and(|superclass| superclass.includes_module(module))
+    }
+
     /// Return a list of method names defined directly on this class.
     pub fn method_names(&self) -> Vec<String> {
         let mut names = self.methods.borrow().keys().cloned().collect::<Vec<_>>();
@@ -103,6 +188,95 @@ impl Class {
     pub fn get_class_var(&self, name: &str) -> Option<Object> {
         self.class_variables.borrow().get(name).cloned()
     }
+
+    /// Look up a class variable on this class or the nearest ancestor defining it.
+    ///
+    /// Class variables are shared with subclasses: a subclass sees (and writes
+    /// to) the variable of the ancestor that defined it rather than a copy.
+    pub fn lookup_class_var(&self, name: &str) -> Option<Object> {
+        self.get_class_var(name).or_else(|| {
+            self.superclass
+                .as_ref()
+                .and_then(|superclass| superclass.lookup_class_var(name))
+        })
+    }
+
+    /// Assign a class variable, updating the ancestor that already defines it
+    /// or defining it on this class otherwise.
+    pub fn assign_class_var(&self, name: impl Into<String>, value: Object) {
+        let name = name.into();
+        match self.class_var_owner(&name) {
+            Some(owner) => owner.set_class_var(name, value),
+            None => self.set_class_var(name, value),
+        }
+    }
+
+    /// Check if a class variable is defined on this class or an ancestor.
+    pub fn class_var_defined(&self, name: &str) -> bool {
+        self.lookup_class_var(name).is_some()
+    }
+
+    /// Names of class variables visible from this class, including inherited ones.
+    pub fn class_var_names(&self) -> Vec<String> {
+        let mut names = self
+            .class_variables
+            .borrow()
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        if let Some(superclass) = &self.superclass {
+            names.extend(superclass.class_var_names());
+        }
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Define a constant, such as a nested class, inside this class.
+    pub fn set_constant(&self, name: impl Into<String>, value: Object) {
+        self.constants.borrow_mut().insert(name.into(), value);
+    }
+
+    /// Retrieve a constant defined directly inside this class.
+    pub fn get_constant(&self, name: &str) -> Option<Object> {
+        self.constants.borrow().get(name).cloned()
+    }
+
+    /// Look up a constant on this class or the nearest ancestor defining it.
+    pub fn lookup_constant(&self, name: &str) -> Option<Object> {
+        self.get_constant(name).or_else(|| {
+            self.superclass
+                .as_ref()
+                .and_then(|superclass| superclass.lookup_constant(name))
+        })
+    }
+
+    /// Names of the constants defined directly inside this class, sorted.
+    pub fn constant_names(&self) -> Vec<String> {
+        let mut names = self.constants.borrow().keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Seal this class so that only the named classes may inherit from it.
+    pub fn seal(&self, variants: Vec<String>) {
+        *self.variants.borrow_mut() = Some(variants);
+    }
+
+    /// The subclasses a sealed class allows, or `None` if it is not sealed.
+    pub fn variants(&self) -> Option<Vec<String>> {
+        self.variants.borrow().clone()
+    }
+
+    /// Find the class (this one or an ancestor) that defines a class variable.
+    fn class_var_owner(&self, name: &str) -> Option<&Class> {
+        if self.class_variables.borrow().contains_key(name) {
+            return Some(self);
+        }
+        self.superclass
+            .as_deref()
+            .and_then(|superclass| superclass.class_var_owner(name))
+    }
 }
 
 impl Clone for Class {
@@ -111,8 +285,13 @@ impl Clone for Class {
             name: self.name.clone(),
             superclass: self.superclass.clone(),
             methods: RefCell::new(self.methods.borrow().clone()),
+            private_methods: RefCell::new(self.private_methods.borrow().clone()),
             instance_variables: RefCell::new(self.instance_variables.borrow().clone()),
             class_variables: RefCell::new(self.class_variables.borrow().clone()),
+            constants: RefCell::new(self.constants.borrow().clone()),
+            variants: RefCell::new(self.variants.borrow().clone()),
+            included_modules: RefCell::new(self.included_modules.borrow().clone()),
+            extended_modules: RefCell::new(self.extended_modules.borrow().clone()),
         }
     }
 }
@@ -137,6 +316,16 @@ impl PartialEq for Class {
             }
         }
 
+        if *self.private_methods.borrow() != *other.private_methods.borrow() {
+            return false;
+        }
+
+        if *self.included_modules.borrow() != *other.included_modules.borrow()
+            || *self.extended_modules.borrow() != *other.extended_modules.borrow()
+        {
+            return false;
+        }
+
         let self_methods = self.methods.borrow();
         let other_methods = other.methods.borrow();
         if self_methods.len() != other_methods.len() {
@@ -145,6 +334,9 @@ impl PartialEq for Class {
         if self.class_variables.borrow().len() != other.class_variables.borrow().len() {
             return false;
         }
+        if *self.constants.borrow() != *other.constants.borrow() {
+            return false;
+        }
 
         self_methods.iter().all(|(name, method)| {
             other_methods.get(name).is_some_and(|other_method| {
@@ -0,0 +1,117 @@
+// Spans of time written for people
+// Backs the numeric to_duration and ago_in_words methods, which read their
+// receiver as a number of seconds.
+//
+//   to_duration     3725   -> "1h 2m 5s"      0.25 -> "250ms"
+//   ago_in_words    300    -> "5 minutes ago" -7200 -> "in about 2 hours"
+
+const SECONDS_PER_MINUTE: f64 = 60.0;
+const SECONDS_PER_HOUR: f64 = 60.0 * SECONDS_PER_MINUTE;
+const SECONDS_PER_DAY: f64 = 24.0 * SECONDS_PER_HOUR;
+const SECONDS_PER_MONTH: f64 = 30.0 * SECONDS_PER_DAY;
+const SECONDS_PER_YEAR: f64 = 365.0 * SECONDS_PER_DAY;
+
+/// Write a number of seconds as days, hours, minutes, seconds and
+/// milliseconds, leaving out the parts that are zero
+///
+/// The value is rounded to the nearest millisecond; a negative span gets a
+/// leading minus sign.
+pub fn format_duration(seconds: f64) -> String {
+    if seconds.is_nan() {
+        return "NaN".to_string();
+    }
+    if seconds.is_infinite() {
+        return if seconds > 0.0 {
+            "Infinity"
+        } else {
+            "-Infinity"
+        }
+        .to_string();
+    }
+
+    let mut millis = (seconds.abs() * 1000.0).round() as u64;
+    if millis == 0 {
+        return "0s".to_string();
+    }
+
+    let mut parts = Vec::new();
+    for (unit, size) in [
+        ("d", 86_400_000),
+        ("h", 3_600_000),
+        ("m", 60_000),
+        ("s", 1000),
+        ("ms", 1),
+    ] {
+        let count = millis / size;
+        millis %= size;
+        if count > 0 {
+            parts.push(format!("{}{}", count, unit));
+        }
+    }
+
+    let text = parts.join(" ");
+    if seconds < 0.0 {
+        format!("-{}", text)
+    } else {
+        text
+    }
+}
+
+/// Describe how long ago something happened, given the seconds elapsed
+///
+/// The wording is approximate, like "about 3 hours ago"; a negative span is
+/// in the future and reads "in about 3 hours".
+pub fn ago_in_words(seconds: f64) -> String {
+    let words = distance_in_words(seconds.abs());
+    if seconds < 0.0 {
+        format!("in {}", words)
+    } else {
+        format!("{} ago", words)
+    }
+}
+
+/// The rough size of a span of `seconds`, which must not be negative
+fn distance_in_words(seconds: f64) -> String {
+    if seconds.is_nan() {
+        return "an unknown time".to_string();
+    }
+    if seconds < 45.0 {
+        return "less than a minute".to_string();
+    }
+    if seconds < 90.0 {
+        return "1 minute".to_string();
+    }
+    let minutes = (seconds / SECONDS_PER_MINUTE).round();
+    if minutes < 45.0 {
+        return format!("{} minutes", minutes);
+    }
+    if minutes < 90.0 {
+        return "about 1 hour".to_string();
+    }
+    let hours = (seconds / SECONDS_PER_HOUR).round();
+    if hours < 24.0 {
+        return format!("about {} hours", hours);
+    }
+    if hours < 42.0 {
+        return "1 day".to_string();
+    }
+    let days = (seconds / SECONDS_PER_DAY).round();
+    if days < 30.0 {
+        return format!("{} days", days);
+    }
+    if days < 45.0 {
+        return "about 1 month".to_string();
+    }
+    let months = (seconds / SECONDS_PER_MONTH).round();
+    if months < 12.0 {
+        return format!("{} months", months);
+    }
+    let years = seconds / SECONDS_PER_YEAR;
+    if years < 1.5 {
+        return "about 1 year".to_string();
+    }
+    if years.is_infinite() {
+        return "forever".to_string();
+    }
+    format!("about {} years", years.round())
+}
@@ -2,10 +2,18 @@
 // This module implements a stack-based scope management system
 
 use crate::object::Object;
-use crate::scope::Scope;
+use crate::scope::{Scope, ScopeSnapshot};
 use std::cell::RefCell;
 use std::rc::Rc;
 
+/// The scope stack and every scope's bindings at one moment, taken by
+/// [`Environment::snapshot`]
+#[derive(Debug, Clone)]
+pub struct EnvironmentSnapshot {
+    scopes: Vec<(Rc<RefCell<Scope>>, ScopeSnapshot)>,
+    depth: usize,
+}
+
 /// Represents the environment with a stack of scopes
 /// The environment manages the scope chain and tracks the current depth
 #[derive(Debug)]
@@ -49,6 +57,20 @@ impl Environment {
         self.scopes.pop()
     }
 
+    /// Makes `scope` the current scope, with the scopes it is nested in
+    /// below it, replacing every scope above the global one
+    pub fn enter_scope(&mut self, scope: Rc<RefCell<Scope>>) {
+        let mut chain = vec![Rc::clone(&scope)];
+        let mut parent = scope.borrow().parent();
+        while let Some(scope) = parent {
+            parent = scope.borrow().parent();
+            chain.push(scope);
+        }
+        chain.reverse();
+        self.depth = chain.len() - 1;
+        self.scopes = chain;
+    }
+
     /// Returns a reference to the current (top) scope
     pub fn current_scope(&self) -> Rc<RefCell<Scope>> {
         self.scopes.last().unwrap().clone()
@@ -70,6 +92,11 @@ impl Environment {
         self.current_scope().borrow_mut().define(name, value);
     }
 
+    /// Removes a variable from the current scope, returning its value
+    pub fn remove(&mut self, name: &str) -> Option<Object> {
+        self.current_scope().borrow_mut().remove(name)
+    }
+
     /// Gets a variable value by traversing the scope chain from the current scope
     pub fn get(&self, name: &str) -> Option<Object> {
         self.current_scope().borrow().get(name)
@@ -115,6 +142,31 @@ impl Environment {
     pub fn get_ref(&self, name: &str) -> Option<std::rc::Rc<std::cell::RefCell<Object>>> {
         self.current_scope().borrow().get_ref(name)
     }
+
+    /// Captures the scope stack and the bindings of every scope on it
+    pub fn snapshot(&self) -> EnvironmentSnapshot {
+        EnvironmentSnapshot {
+            scopes: self
+                .scopes
+                .iter()
+                .map(|scope| (scope.clone(), scope.borrow().snapshot()))
+                .collect(),
+            depth: self.depth,
+        }
+    }
+
+    /// Returns to the state captured by `snapshot`, popping scopes pushed
+    /// since and undoing every definition and assignment made in its scopes
+    /// Objects changed in place, such as an array that was pushed to, keep
+    /// their changes
+    pub fn restore(&mut self, snapshot: EnvironmentSnapshot) {
+        self.scopes.clear();
+        for (scope, bindings) in snapshot.scopes {
+            scope.borrow_mut().restore(bindings);
+            self.scopes.push(scope);
+        }
+        self.depth = snapshot.depth;
+    }
 }
 
 impl Default for Environment {
@@ -4,6 +4,7 @@
 // It provides structured error reporting with source location information and
 // formatting utilities for beautiful error messages.
 
+use crate::messages::Message;
 use std::fmt;
 use thiserror::Error;
 
@@ -17,7 +18,7 @@ pub struct SourceLocation {
     /// Byte offset in the source file
     pub offset: usize,
     /// Optional filename
-    pub filename: Option<String>,
+    pub filename: Option<Box<str>>,
 }
 
 impl SourceLocation {
@@ -37,7 +38,7 @@ impl SourceLocation {
             line,
             column,
             offset,
-            filename: Some(filename),
+            filename: Some(filename.into_boxed_str()),
         }
     }
 }
@@ -85,6 +86,7 @@ pub enum MetorexError {
     SyntaxError {
         message: String,
         location: SourceLocation,
+        catalog_message: Option<&'static Message>,
     },
 
     /// Runtime errors encountered during execution
@@ -93,6 +95,7 @@ pub enum MetorexError {
         message: String,
         location: SourceLocation,
         stack_trace: Vec<StackFrame>,
+        catalog_message: Option<&'static Message>,
     },
 
     /// Type mismatch or type-related errors
@@ -102,6 +105,7 @@ pub enum MetorexError {
         location: SourceLocation,
         expected: Option<String>,
         found: Option<String>,
+        catalog_message: Option<&'static Message>,
     },
 
     /// IO errors (file operations, etc.)
@@ -118,6 +122,7 @@ pub enum MetorexError {
         exception: crate::object::Object,
         location: SourceLocation,
         message: String,
+        catalog_message: Option<&'static Message>,
     },
 }
 
@@ -134,6 +139,7 @@ impl MetorexError {
         Self::SyntaxError {
             message: message.into(),
             location,
+            catalog_message: None,
         }
     }
 
@@ -143,6 +149,7 @@ impl MetorexError {
             message: message.into(),
             location,
             stack_trace: Vec::new(),
+            catalog_message: None,
         }
     }
 
@@ -156,6 +163,7 @@ impl MetorexError {
             message: message.into(),
             location,
             stack_trace,
+            catalog_message: None,
         }
     }
 
@@ -166,6 +174,7 @@ impl MetorexError {
             location,
             expected: None,
             found: None,
+            catalog_message: None,
         }
     }
 
@@ -181,6 +190,7 @@ impl MetorexError {
             location,
             expected: Some(expected.into()),
             found: Some(found.into()),
+            catalog_message: None,
         }
     }
 
@@ -196,18 +206,61 @@ impl MetorexError {
                 message,
                 location,
                 mut stack_trace,
+                catalog_message,
             } => {
                 stack_trace.push(frame);
                 Self::RuntimeError {
                     message,
                     location,
                     stack_trace,
+                    catalog_message,
                 }
             }
             other => other,
         }
     }
 
+    /// Record the catalog message the error's text was built from
+    pub fn with_catalog_message(mut self, message: &'static Message) -> Self {
+        match &mut self {
+            Self::SyntaxError {
+                catalog_message, ..
+            }
+            | Self::RuntimeError {
+                catalog_message, ..
+            }
+            | Self::TypeError {
+                catalog_message, ..
+            }
+            | Self::UncaughtException {
+                catalog_message, ..
+            } => *catalog_message = Some(message),
+            _ => {}
+        }
+        self
+    }
+
+    /// The message catalog code of this error, such as `E0301` for an
+    /// undefined method. Errors built from text that is not in the catalog
+    /// have no code.
+    pub fn code(&self) -> Option<&'static str> {
+        match self {
+            Self::SyntaxError {
+                catalog_message, ..
+            }
+            | Self::RuntimeError {
+                catalog_message, ..
+            }
+            | Self::TypeError {
+                catalog_message, ..
+            }
+            | Self::UncaughtException {
+                catalog_message, ..
+            } => catalog_message.map(|message| message.code),
+            _ => None,
+        }
+    }
+
     /// Get the source location associated with this error, if any
     pub fn location(&self) -> Option<&SourceLocation> {
         match self {
@@ -0,0 +1,219 @@
+// Golden-file testing of example programs
+// `metorex examples` runs each `.mx` file under a directory in a fresh VM and
+// captures what it did: its output, its exit status and the error it stopped
+// on, and the classes, functions and variables it left defined. The snapshot
+// is compared with the one saved next to the example, so `hello.mx` is checked
+// against `hello.golden`, and `--bless` saves the snapshots of the current run
+// instead. A `hello.input` file, if present, is what the example reads from
+// input; otherwise it reads nothing.
+//
+// A golden file is plain text, so differences show up in review:
+//
+//     exit: 0
+//     defined: Greeter, Greeter#greet
+//     assigned: greeter
+//     --- stdout
+//     Hello, Ada!
+
+use crate::path_names::strip_verbatim_prefix;
+use crate::vm::{DefinitionDiff, VirtualMachine, VmOptions};
+use std::cell::RefCell;
+use std::fmt;
+use std::fs;
+use std::io::{self, Cursor, Write};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Extension of the file holding an example's expected snapshot
+pub const GOLDEN_EXTENSION: &str = "golden";
+
+/// Extension of the file holding an example's input
+pub const INPUT_EXTENSION: &str = "input";
+
+/// Seed `rand` uses unless the VM options give one, so examples that use
+/// random numbers print the same thing on every run
+pub const DEFAULT_SEED: u64 = 0;
+
+/// Separates the header of a golden file from the output
+const STDOUT_MARKER: &str = "--- stdout";
+
+/// What running an example did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionSnapshot {
+    /// Everything the example printed
+    pub stdout: String,
+    /// 0 when the example finished, 1 when it raised an error
+    pub exit_status: i32,
+    /// The error the example stopped on
+    pub error: Option<String>,
+    /// The classes, methods, functions and variables it left in the global
+    /// scope
+    pub definitions: DefinitionDiff,
+}
+
+impl ExecutionSnapshot {
+    /// Run the example at `path` in a fresh VM and capture what it did
+    pub fn capture(path: &Path, mut options: VmOptions) -> Self {
+        options.seed.get_or_insert(DEFAULT_SEED);
+        let input = fs::read(path.with_extension(INPUT_EXTENSION)).unwrap_or_default();
+        let output = SharedBuffer::default();
+
+        let mut vm = VirtualMachine::with_options(options);
+        vm.set_input(Cursor::new(input));
+        vm.set_output(output.clone());
+        let checkpoint = vm.definitions_checkpoint();
+        let result = vm.execute_file(path);
+        let definitions = vm.methods_added_since(&checkpoint);
+        // Shutting down runs finalizers, which can still print
+        drop(vm);
+
+        Self {
+            stdout: output.text(),
+            exit_status: if result.is_ok() { 0 } else { 1 },
+            error: result.err().map(|err| relative_paths(&err.to_string())),
+            definitions,
+        }
+    }
+}
+
+impl fmt::Display for ExecutionSnapshot {
+    /// The snapshot in the golden file format
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "exit: {}", self.exit_status)?;
+        if let Some(error) = &self.error {
+            for line in error.lines() {
+                writeln!(f, "error: {}", line)?;
+            }
+        }
+        if self.definitions.defines_anything() {
+            writeln!(f, "defined: {}", self.definitions.definitions().join(", "))?;
+        }
+        if !self.definitions.variables.is_empty() {
+            writeln!(f, "assigned: {}", self.definitions.variables.join(", "))?;
+        }
+        writeln!(f, "{}", STDOUT_MARKER)?;
+        write!(f, "{}", self.stdout)
+    }
+}
+
+/// How an example compared with its golden file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExampleOutcome {
+    /// The run matched the golden file
+    Passed,
+    /// The run differed from the golden file
+    Failed(Difference),
+    /// There is no golden file to compare with
+    Missing,
+    /// The golden file was written from this run
+    Blessed,
+}
+
+/// The first line at which a run stopped matching its golden file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Difference {
+    /// 1-based line of the golden file
+    pub line: usize,
+    /// The golden file's line, or `None` past its end
+    pub expected: Option<String>,
+    /// The run's line, or `None` past its end
+    pub found: Option<String>,
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let describe = |line: &Option<String>| match line {
+            Some(line) => format!("{:?}", line),
+            None => "end of file".to_string(),
+        };
+        write!(
+            f,
+            "line {}: expected {}, found {}",
+            self.line,
+            describe(&self.expected),
+            describe(&self.found)
+        )
+    }
+}
+
+/// The first line at which `found` differs from `expected`, treating a
+/// missing final newline as a difference of its own
+pub fn first_difference(expected: &str, found: &str) -> Option<Difference> {
+    if expected == found {
+        return None;
+    }
+    let expected_lines: Vec<&str> = expected.split('\n').collect();
+    let found_lines: Vec<&str> = found.split('\n').collect();
+    let length = expected_lines.len().max(found_lines.len());
+    (0..length).find_map(|index| {
+        let expected = expected_lines.get(index);
+        let found = found_lines.get(index);
+        (expected != found).then(|| Difference {
+            line: index + 1,
+            expected: expected.map(|line| line.to_string()),
+            found: found.map(|line| line.to_string()),
+        })
+    })
+}
+
+/// The golden file of the example at `path`
+pub fn golden_path(path: &Path) -> PathBuf {
+    path.with_extension(GOLDEN_EXTENSION)
+}
+
+/// Run the example at `path` and compare it with its golden file, or with
+/// `bless` write the golden file when it is missing or different
+pub fn check_example(path: &Path, options: VmOptions, bless: bool) -> io::Result<ExampleOutcome> {
+    let snapshot = ExecutionSnapshot::capture(path, options).to_string();
+    let golden = golden_path(path);
+    let outcome = match fs::read_to_string(&golden) {
+        Ok(expected) => match first_difference(&expected, &snapshot) {
+            None => return Ok(ExampleOutcome::Passed),
+            Some(difference) => ExampleOutcome::Failed(difference),
+        },
+        Err(err) if err.kind() == io::ErrorKind::NotFound => ExampleOutcome::Missing,
+        Err(err) => return Err(err),
+    };
+    if !bless {
+        return Ok(outcome);
+    }
+    fs::write(&golden, snapshot)?;
+    Ok(ExampleOutcome::Blessed)
+}
+
+/// `message` with the paths of files under the current directory made
+/// relative to it, so golden files do not depend on where the project is
+fn relative_paths(message: &str) -> String {
+    let Some(dir) = std::env::current_dir()
+        .and_then(fs::canonicalize)
+        .ok()
+        .map(strip_verbatim_prefix)
+    else {
+        return message.to_string();
+    };
+    message.replace(
+        &format!("{}{}", dir.display(), std::path::MAIN_SEPARATOR),
+        "",
+    )
+}
+
+/// Output captured from a VM, shared with the caller that reads it back
+#[derive(Debug, Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn text(&self) -> String {
+        String::from_utf8_lossy(&self.0.borrow()).into_owned()
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -1,22 +1,29 @@
-// File Loading Infrastructure for require_relative
+// File Loading Infrastructure for require_relative and require
 //
 // This module provides utilities for loading, parsing, and resolving file paths
 // in the Metorex language. It supports Ruby's file loading conventions including
-// automatic file extension detection (.rb, .mx, or no extension).
+// automatic file extension detection (.rb, .mx, or no extension) and paths
+// written with either `/` or `\` separators.
 
 use crate::ast::Statement;
 use crate::error::{MetorexError, SourceLocation};
 use crate::lexer::Lexer;
 use crate::parser::Parser;
+use crate::path_names::{normalize_separators, strip_verbatim_prefix};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Extensions tried, in order, for a required path that names no file
+const SOURCE_EXTENSIONS: [&str; 2] = ["rb", "mx"];
+
 /// Finds the actual file path with extension auto-detection.
 ///
 /// This function supports Ruby's file loading conventions:
-/// - If the path has an extension (.rb, .mx, etc.), it tries that path first
-/// - If no extension or file not found, it tries adding .rb
-/// - If still not found, it tries adding .mx
+/// - If the path names a file, that file is used
+/// - If the path ends in `.rb` or `.mx`, no other path is tried
+/// - Otherwise `.rb` is appended to the whole name, then `.mx`, so
+///   `lib/parser.v2` finds `lib/parser.v2.mx` and a directory `lib`
+///   does not hide `lib.mx`
 /// - Returns the path of the first file that exists
 ///
 /// # Arguments
@@ -27,28 +34,27 @@ use std::path::{Path, PathBuf};
 /// * `Err(MetorexError)` - If the file doesn't exist with any extension
 pub fn find_file_path(path: &Path) -> Result<PathBuf, MetorexError> {
     // Try the path as given first
-    if path.exists() {
+    if path.exists() && !path.is_dir() {
         return Ok(path.to_path_buf());
     }
 
-    // If the path has an extension, don't try alternatives
-    if path.extension().is_some() {
+    // A source extension names the file exactly
+    let extension = path.extension().and_then(|extension| extension.to_str());
+    if extension.is_some_and(|extension| SOURCE_EXTENSIONS.contains(&extension)) {
         return Err(MetorexError::runtime_error(
             format!("File not found: '{}'", path.display()),
             SourceLocation::new(0, 0, 0),
         ));
     }
 
-    // Try with .rb extension
-    let rb_path = path.with_extension("rb");
-    if rb_path.exists() {
-        return Ok(rb_path);
-    }
-
-    // Try with .mx extension
-    let mx_path = path.with_extension("mx");
-    if mx_path.exists() {
-        return Ok(mx_path);
+    for extension in SOURCE_EXTENSIONS {
+        let mut candidate = path.as_os_str().to_owned();
+        candidate.push(".");
+        candidate.push(extension);
+        let candidate = PathBuf::from(candidate);
+        if candidate.is_file() {
+            return Ok(candidate);
+        }
     }
 
     // File not found with any extension
@@ -64,6 +70,67 @@ pub fn find_file_path(path: &Path) -> Result<PathBuf, MetorexError> {
     ))
 }
 
+/// Finds a file like [`find_file_path`] and returns its canonical path.
+///
+/// Symlinks are resolved, so a file reached through several links is
+/// loaded once and requires inside it resolve against its real directory.
+/// On Windows the `\\?\` prefix of canonical paths is removed.
+pub fn canonical_file_path(path: &Path) -> Result<PathBuf, MetorexError> {
+    let actual_path = find_file_path(path)?;
+    actual_path
+        .canonicalize()
+        .map(strip_verbatim_prefix)
+        .map_err(|e| {
+            MetorexError::runtime_error(
+                format!(
+                    "Failed to canonicalize file path '{}': {}",
+                    actual_path.display(),
+                    e
+                ),
+                SourceLocation::new(0, 0, 0),
+            )
+        })
+}
+
+/// Finds the file a `require` of `name` loads, searching `load_path`.
+///
+/// Each directory is tried in order, with the extension auto-detection of
+/// [`find_file_path`]. A name that is absolute or starts with `./` or `../`
+/// is a path rather than a library name, and is used as given.
+///
+/// # Returns
+/// * `Ok(PathBuf)` - The path of the library file
+/// * `Err(MetorexError)` - If no directory holds the library
+pub fn find_in_load_path(load_path: &[PathBuf], name: &str) -> Result<PathBuf, MetorexError> {
+    let name = normalize_separators(name);
+    let path = Path::new(name.as_ref());
+    if path.is_absolute() || name.starts_with("./") || name.starts_with("../") {
+        return find_file_path(path);
+    }
+
+    load_path
+        .iter()
+        .find_map(|dir| find_file_path(&dir.join(path)).ok())
+        .ok_or_else(|| {
+            let searched = load_path
+                .iter()
+                .map(|dir| dir.display().to_string())
+                .collect::<Vec<_>>();
+            MetorexError::runtime_error(
+                format!(
+                    "cannot load such file -- {} (load path: {})",
+                    name,
+                    if searched.is_empty() {
+                        "empty".to_string()
+                    } else {
+                        searched.join(", ")
+                    }
+                ),
+                SourceLocation::new(0, 0, 0),
+            )
+        })
+}
+
 /// Loads the source code from a file, with automatic file extension detection.
 ///
 /// This function supports Ruby's file loading conventions:
@@ -92,7 +159,8 @@ pub fn load_file_source(path: &Path) -> Result<String, MetorexError> {
 ///
 /// This function implements Ruby's `require_relative` path resolution logic:
 /// - Gets the parent directory of the base file
-/// - Joins the relative path to that directory
+/// - Joins the relative path to that directory, reading `\` as a separator
+///   so paths written for Windows work everywhere
 /// - Canonicalizes the result to resolve `..`, `.`, and symlinks
 ///
 /// # Arguments
@@ -127,7 +195,7 @@ pub fn resolve_relative_path(
     // Join the relative path to the base directory
     // Note: We don't canonicalize here because the file may not exist yet (extension auto-detection)
     // Canonicalization will happen in execute_file after load_file_source finds the actual file
-    let target_path = base_dir.join(relative_path);
+    let target_path = base_dir.join(normalize_separators(relative_path).as_ref());
 
     Ok(target_path)
 }
@@ -0,0 +1,165 @@
+// Shell-style glob matching
+// Backs File.fnmatch, String#match_glob and glob(...) patterns in case/when.
+//
+// Supported syntax:
+//   *       any run of characters except '/'
+//   **      any run of characters, including '/'; `**/` also matches no directory
+//   ?       any single character except '/'
+//   [abc]   one of the listed characters; ranges like [a-z]; [!x] or [^x] negates
+//   {a,b}   either alternative; alternatives may themselves contain patterns
+//   \x      the character x literally
+
+/// Whether `text` matches the glob `pattern`
+pub fn fnmatch(pattern: &str, text: &str) -> bool {
+    let text: Vec<char> = text.chars().collect();
+    expand_braces(pattern).iter().any(|alternative| {
+        let pattern: Vec<char> = alternative.chars().collect();
+        match_from(&pattern, &text)
+    })
+}
+
+/// Expand `{a,b}` alternatives into every plain pattern they describe
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let Some((open, close)) = find_brace_group(&chars) else {
+        return vec![pattern.to_string()];
+    };
+
+    let prefix: String = chars[..open].iter().collect();
+    let suffix: String = chars[close + 1..].iter().collect();
+    split_alternatives(&chars[open + 1..close])
+        .into_iter()
+        .flat_map(|alternative| expand_braces(&format!("{}{}{}", prefix, alternative, suffix)))
+        .collect()
+}
+
+/// Positions of the first top-level `{` and its matching `}`
+fn find_brace_group(chars: &[char]) -> Option<(usize, usize)> {
+    let mut open = None;
+    let mut depth = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            '{' => {
+                if depth == 0 {
+                    open = Some(i);
+                }
+                depth += 1;
+            }
+            '}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    return open.map(|start| (start, i));
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Split the inside of a brace group on its top-level commas
+fn split_alternatives(chars: &[char]) -> Vec<String> {
+    let mut alternatives = vec![String::new()];
+    let mut depth = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' if i + 1 < chars.len() => {
+                let current = alternatives.last_mut().unwrap();
+                current.push(c);
+                current.push(chars[i + 1]);
+                i += 2;
+                continue;
+            }
+            ',' if depth == 0 => {
+                alternatives.push(String::new());
+                i += 1;
+                continue;
+            }
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ => {}
+        }
+        alternatives.last_mut().unwrap().push(c);
+        i += 1;
+    }
+    alternatives
+}
+
+/// Backtracking matcher for a brace-free pattern
+fn match_from(pattern: &[char], text: &[char]) -> bool {
+    let Some(&first) = pattern.first() else {
+        return text.is_empty();
+    };
+
+    match first {
+        '*' if pattern.get(1) == Some(&'*') => {
+            let rest = &pattern[2..];
+            // `**/` may stand for no directories at all
+            if rest.first() == Some(&'/') && match_from(&rest[1..], text) {
+                return true;
+            }
+            (0..=text.len()).any(|skip| match_from(rest, &text[skip..]))
+        }
+        '*' => {
+            let rest = &pattern[1..];
+            let segment = text.iter().position(|&c| c == '/').unwrap_or(text.len());
+            (0..=segment).any(|skip| match_from(rest, &text[skip..]))
+        }
+        '?' => {
+            matches!(text.first(), Some(&c) if c != '/') && match_from(&pattern[1..], &text[1..])
+        }
+        '[' => match (text.first(), parse_class(&pattern[1..])) {
+            (Some(&c), Some((matches, length))) => {
+                c != '/' && matches(c) && match_from(&pattern[1 + length..], &text[1..])
+            }
+            // An unterminated class is a literal '['
+            (Some('['), None) => match_from(&pattern[1..], &text[1..]),
+            _ => false,
+        },
+        '\\' if pattern.len() > 1 => {
+            text.first() == Some(&pattern[1]) && match_from(&pattern[2..], &text[1..])
+        }
+        c => text.first() == Some(&c) && match_from(&pattern[1..], &text[1..]),
+    }
+}
+
+/// Parse a character class following `[`, returning a predicate and the
+/// number of pattern characters it used, including the closing `]`
+fn parse_class(pattern: &[char]) -> Option<(impl Fn(char) -> bool, usize)> {
+    let mut i = 0;
+    let negated = matches!(pattern.first(), Some('!' | '^'));
+    if negated {
+        i += 1;
+    }
+
+    let mut ranges = Vec::new();
+    let start = i;
+    while i < pattern.len() {
+        let c = match pattern[i] {
+            ']' if i > start => {
+                let matches = move |c: char| {
+                    ranges.iter().any(|&(low, high)| low <= c && c <= high) != negated
+                };
+                return Some((matches, i + 1));
+            }
+            '\\' if i + 1 < pattern.len() => {
+                i += 1;
+                pattern[i]
+            }
+            c => c,
+        };
+        if pattern.get(i + 1) == Some(&'-') && pattern.get(i + 2).is_some_and(|&end| end != ']') {
+            ranges.push((c, pattern[i + 2]));
+            i += 3;
+        } else {
+            ranges.push((c, c));
+            i += 1;
+        }
+    }
+    None
+}
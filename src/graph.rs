@@ -0,0 +1,452 @@
+// Structural graphs of a Metorex program
+// `metorex graph` parses and resolves a script and every file it requires,
+// without running any of them, and collects the classes and modules they
+// define, what each class inherits from and mixes in, and which file requires
+// which. The result is written as a Graphviz graph, for a quick overview of an
+// unfamiliar codebase.
+
+use crate::ast::{Expression, Statement};
+use crate::error::MetorexError;
+use crate::file_loader::{
+    canonical_file_path, find_in_load_path, load_file_source, parse_file, resolve_relative_path,
+};
+use crate::resolver::Resolver;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// The output formats of `metorex graph`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GraphFormat {
+    /// Graphviz `dot` source
+    #[default]
+    Dot,
+}
+
+impl FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "dot" => Ok(GraphFormat::Dot),
+            other => Err(format!("invalid graph format '{}' (expected dot)", other)),
+        }
+    }
+}
+
+impl fmt::Display for GraphFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphFormat::Dot => write!(f, "dot"),
+        }
+    }
+}
+
+/// Whether a definition is a class or a module
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefinitionKind {
+    Class,
+    Module,
+}
+
+/// A class or module the program defines. A class reopened in several
+/// places is one definition, with the mixins of all of them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Definition {
+    pub name: String,
+    pub kind: DefinitionKind,
+    /// The file of the first definition
+    pub file: PathBuf,
+    pub superclass: Option<String>,
+    /// Modules mixed in with `include`, in order
+    pub includes: Vec<String>,
+    /// Modules mixed in with `extend`, in order
+    pub extends: Vec<String>,
+}
+
+/// A `require` or `require_relative` of a file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Require {
+    /// The file containing the require
+    pub from: PathBuf,
+    /// The required file, or `None` when it could not be found
+    pub to: Option<PathBuf>,
+    /// The name as written in the source
+    pub name: String,
+}
+
+/// Why a graph could not be built
+#[derive(Debug)]
+pub struct GraphError {
+    pub path: PathBuf,
+    pub error: Box<MetorexError>,
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.error)
+    }
+}
+
+/// The classes, modules and requires of a script and the files it loads
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProgramGraph {
+    /// Every file reached, the script first, as canonical paths
+    pub files: Vec<PathBuf>,
+    /// Classes and modules in the order they are first defined
+    pub definitions: Vec<Definition>,
+    pub requires: Vec<Require>,
+}
+
+impl ProgramGraph {
+    /// Parse `entry` and, transitively, the files it requires, resolving
+    /// `require` names against `load_path`
+    pub fn build(entry: &Path, load_path: &[PathBuf]) -> Result<Self, GraphError> {
+        let failed = |path: &Path| {
+            let path = path.to_path_buf();
+            move |error| GraphError {
+                path,
+                error: Box::new(error),
+            }
+        };
+        let mut graph = ProgramGraph::default();
+        let mut queue = VecDeque::from([canonical_file_path(entry).map_err(failed(entry))?]);
+
+        while let Some(file) = queue.pop_front() {
+            if graph.files.contains(&file) {
+                continue;
+            }
+            let source = load_file_source(&file).map_err(failed(&file))?;
+            let program = parse_file(&source, &file.to_string_lossy()).map_err(failed(&file))?;
+            let resolved = Resolver::with_strict_mode(false).resolve(&program);
+            if let Some(error) = resolved.errors.into_iter().next() {
+                return Err(failed(&file)(error));
+            }
+            graph.files.push(file.clone());
+
+            let mut collector = Collector {
+                graph: &mut graph,
+                file: &file,
+                load_path,
+                shadowed: resolved.variables.into_keys().collect(),
+                found: Vec::new(),
+            };
+            collector.statements(&program, None);
+            queue.extend(collector.found);
+        }
+        Ok(graph)
+    }
+
+    /// The definition named `name`, if the program has one
+    pub fn definition(&self, name: &str) -> Option<&Definition> {
+        self.definitions
+            .iter()
+            .find(|definition| definition.name == name)
+    }
+
+    /// The graph in `format`
+    pub fn render(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(),
+        }
+    }
+
+    /// The graph as Graphviz `dot` source: one cluster for classes and
+    /// modules, one for the require graph between files. Paths are shown
+    /// relative to the script's directory.
+    pub fn to_dot(&self) -> String {
+        let base = self
+            .files
+            .first()
+            .and_then(|entry| entry.parent())
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let mut out =
+            String::from("digraph metorex {\n  rankdir=BT;\n  node [fontname=\"Helvetica\"];\n");
+
+        out.push_str("  subgraph cluster_classes {\n    label=\"Classes and modules\";\n");
+        for definition in &self.definitions {
+            let shape = match definition.kind {
+                DefinitionKind::Class => "shape=box",
+                DefinitionKind::Module => "shape=box, style=rounded",
+            };
+            out.push_str(&format!(
+                "    {} [label={}, {}, tooltip={}];\n",
+                quote(&type_id(&definition.name)),
+                quote(&definition.name),
+                shape,
+                quote(&relative(&base, &definition.file))
+            ));
+        }
+        // Superclasses and modules used but not defined, such as builtins
+        let mut external: Vec<&str> = Vec::new();
+        for definition in &self.definitions {
+            let used = definition
+                .superclass
+                .iter()
+                .chain(&definition.includes)
+                .chain(&definition.extends);
+            for name in used {
+                if self.definition(name).is_none() && !external.contains(&name.as_str()) {
+                    external.push(name);
+                    out.push_str(&format!(
+                        "    {} [label={}, shape=box, style=dotted];\n",
+                        quote(&type_id(name)),
+                        quote(name)
+                    ));
+                }
+            }
+        }
+        for definition in &self.definitions {
+            let id = quote(&type_id(&definition.name));
+            if let Some(superclass) = &definition.superclass {
+                out.push_str(&format!(
+                    "    {} -> {} [arrowhead=empty];\n",
+                    id,
+                    quote(&type_id(superclass))
+                ));
+            }
+            for (keyword, modules) in [
+                ("include", &definition.includes),
+                ("extend", &definition.extends),
+            ] {
+                for module in modules {
+                    out.push_str(&format!(
+                        "    {} -> {} [style=dashed, label={}];\n",
+                        id,
+                        quote(&type_id(module)),
+                        quote(keyword)
+                    ));
+                }
+            }
+        }
+        out.push_str("  }\n");
+
+        out.push_str("  subgraph cluster_files {\n    label=\"Requires\";\n");
+        for file in &self.files {
+            out.push_str(&format!(
+                "    {} [label={}, shape=note];\n",
+                quote(&file_id(&relative(&base, file))),
+                quote(&relative(&base, file))
+            ));
+        }
+        for require in &self.requires {
+            let from = quote(&file_id(&relative(&base, &require.from)));
+            match &require.to {
+                Some(to) => out.push_str(&format!(
+                    "    {} -> {};\n",
+                    from,
+                    quote(&file_id(&relative(&base, to)))
+                )),
+                None => {
+                    let missing = quote(&format!("missing:{}", require.name));
+                    out.push_str(&format!(
+                        "    {} [label={}, shape=note, style=dashed, color=red];\n    {} -> {} [style=dashed, color=red];\n",
+                        missing,
+                        quote(&format!("{} (not found)", require.name)),
+                        from,
+                        missing
+                    ));
+                }
+            }
+        }
+        out.push_str("  }\n}\n");
+        out
+    }
+}
+
+/// Walks the statements of one file, recording definitions and requires
+struct Collector<'a> {
+    graph: &'a mut ProgramGraph,
+    file: &'a Path,
+    load_path: &'a [PathBuf],
+    /// Names the file declares itself, so a `require` or `include` among
+    /// them is not the builtin
+    shadowed: HashSet<String>,
+    /// Required files to visit next
+    found: Vec<PathBuf>,
+}
+
+impl Collector<'_> {
+    /// Record what `statements` define and require. `owner` is the class or
+    /// module whose body they are, which `include` and `extend` apply to.
+    fn statements(&mut self, statements: &[Statement], owner: Option<&str>) {
+        for statement in statements {
+            self.statement(statement, owner);
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement, owner: Option<&str>) {
+        match statement {
+            Statement::ClassDef {
+                name,
+                superclass,
+                body,
+                ..
+            } => {
+                self.define(name, DefinitionKind::Class, superclass.as_deref());
+                self.statements(body, Some(name));
+            }
+            Statement::ModuleDef { name, body, .. } => {
+                self.define(name, DefinitionKind::Module, None);
+                self.statements(body, Some(name));
+            }
+            Statement::Expression {
+                expression:
+                    Expression::Call {
+                        callee, arguments, ..
+                    },
+                ..
+            } => {
+                let Expression::Identifier { name: function, .. } = callee.as_ref() else {
+                    return;
+                };
+                if self.shadowed.contains(function) {
+                    return;
+                }
+                match (function.as_str(), owner) {
+                    ("require" | "require_relative", _) => {
+                        if let [Expression::StringLiteral { value, .. }] = arguments.as_slice() {
+                            self.require(function, value);
+                        }
+                    }
+                    ("include" | "extend", Some(owner)) => {
+                        let modules = arguments.iter().filter_map(|argument| match argument {
+                            Expression::Identifier { name, .. } => Some(name.clone()),
+                            _ => None,
+                        });
+                        let definition = self.definition_mut(owner);
+                        if function == "include" {
+                            definition.includes.extend(modules);
+                        } else {
+                            definition.extends.extend(modules);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Statement::FunctionDef { body, .. }
+            | Statement::MethodDef { body, .. }
+            | Statement::While { body, .. }
+            | Statement::For { body, .. } => self.statements(body, None),
+            Statement::Block { statements, .. } => self.statements(statements, owner),
+            Statement::If {
+                then_branch,
+                elsif_branches,
+                else_branch,
+                ..
+            } => {
+                self.statements(then_branch, owner);
+                for branch in elsif_branches {
+                    self.statements(&branch.body, owner);
+                }
+                self.statements(else_branch.as_deref().unwrap_or_default(), owner);
+            }
+            Statement::Unless {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                self.statements(then_branch, owner);
+                self.statements(else_branch.as_deref().unwrap_or_default(), owner);
+            }
+            Statement::Match { cases, .. } => {
+                for case in cases {
+                    self.statements(&case.body, owner);
+                }
+            }
+            Statement::Begin {
+                body,
+                rescue_clauses,
+                else_clause,
+                ensure_block,
+                ..
+            } => {
+                self.statements(body, owner);
+                for clause in rescue_clauses {
+                    self.statements(&clause.body, owner);
+                }
+                self.statements(else_clause.as_deref().unwrap_or_default(), owner);
+                self.statements(ensure_block.as_deref().unwrap_or_default(), owner);
+            }
+            _ => {}
+        }
+    }
+
+    /// Record a class or module definition, merging a reopened one
+    fn define(&mut self, name: &str, kind: DefinitionKind, superclass: Option<&str>) {
+        let file = self.file.to_path_buf();
+        let definition = self.definition_mut(name);
+        definition.kind = kind;
+        if definition.superclass.is_none() {
+            definition.superclass = superclass.map(str::to_string);
+        }
+        if definition.file.as_os_str().is_empty() {
+            definition.file = file;
+        }
+    }
+
+    /// The definition named `name`, added if it is new
+    fn definition_mut(&mut self, name: &str) -> &mut Definition {
+        let definitions = &mut self.graph.definitions;
+        let index = match definitions.iter().position(|d| d.name == name) {
+            Some(index) => index,
+            None => {
+                definitions.push(Definition {
+                    name: name.to_string(),
+                    kind: DefinitionKind::Class,
+                    file: PathBuf::new(),
+                    superclass: None,
+                    includes: Vec::new(),
+                    extends: Vec::new(),
+                });
+                definitions.len() - 1
+            }
+        };
+        &mut definitions[index]
+    }
+
+    /// Record a require of `name`, resolved the way the VM would
+    fn require(&mut self, function: &str, name: &str) {
+        let found = if function == "require" {
+            find_in_load_path(self.load_path, name)
+        } else {
+            resolve_relative_path(self.file, name)
+        };
+        let to = found.and_then(|path| canonical_file_path(&path)).ok();
+        if let Some(to) = &to {
+            self.found.push(to.clone());
+        }
+        let require = Require {
+            from: self.file.to_path_buf(),
+            to,
+            name: name.to_string(),
+        };
+        if !self.graph.requires.contains(&require) {
+            self.graph.requires.push(require);
+        }
+    }
+}
+
+/// The node id of a class or module
+fn type_id(name: &str) -> String {
+    format!("class:{}", name)
+}
+
+/// The node id of a file
+fn file_id(path: &str) -> String {
+    format!("file:{}", path)
+}
+
+/// `path` relative to `base` when it is inside it, with `/` separators
+fn relative(base: &Path, path: &Path) -> String {
+    let shown = path.strip_prefix(base).unwrap_or(path);
+    shown.to_string_lossy().replace('\\', "/")
+}
+
+/// `text` as a quoted `dot` string
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
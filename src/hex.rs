@@ -0,0 +1,38 @@
+// Hexadecimal encoding
+// Backs String#hex_encode and String#hex_decode, and writes out the results
+// of the Digest functions.
+
+/// Write each byte as two lowercase hex digits
+pub fn encode(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut text = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        text.push(DIGITS[usize::from(byte >> 4)] as char);
+        text.push(DIGITS[usize::from(byte & 0x0f)] as char);
+    }
+    text
+}
+
+/// Read pairs of hex digits, in either case, back into bytes
+pub fn decode(text: &str) -> Result<Vec<u8>, String> {
+    if !text.len().is_multiple_of(2) {
+        return Err(format!("odd number of hex digits ({})", text.len()));
+    }
+    text.as_bytes()
+        .chunks(2)
+        .enumerate()
+        .map(
+            |(i, pair)| match (digit_value(pair[0]), digit_value(pair[1])) {
+                (Some(high), Some(low)) => Ok(high << 4 | low),
+                _ => Err(format!(
+                    "invalid hex digit at offset {}",
+                    i * 2 + usize::from(digit_value(pair[0]).is_some())
+                )),
+            },
+        )
+        .collect()
+}
+
+fn digit_value(digit: u8) -> Option<u8> {
+    (digit as char).to_digit(16).map(|value| value as u8)
+}
@@ -0,0 +1,240 @@
+// Word inflections for names in code
+// Backs the Inflector class: converting between snake_case, CamelCase,
+// dashed and titled names, and English plurals.
+//
+// Pluralization covers the regular suffix rules, a short list of irregular
+// words and words without a plural. It handles the names found in code, like
+// "category" or "person", not English at large. Only the last word of a name
+// changes, so "user_category" becomes "user_categories".
+
+/// Words whose plural isn't formed by a suffix rule, as (singular, plural)
+const IRREGULARS: [(&str, &str); 14] = [
+    ("person", "people"),
+    ("man", "men"),
+    ("woman", "women"),
+    ("child", "children"),
+    ("mouse", "mice"),
+    ("goose", "geese"),
+    ("tooth", "teeth"),
+    ("foot", "feet"),
+    ("ox", "oxen"),
+    ("quiz", "quizzes"),
+    ("movie", "movies"),
+    ("knife", "knives"),
+    ("wife", "wives"),
+    ("life", "lives"),
+];
+
+/// Words spelled the same in the singular and the plural
+const UNCOUNTABLES: [&str; 10] = [
+    "data",
+    "equipment",
+    "fish",
+    "information",
+    "metadata",
+    "money",
+    "news",
+    "series",
+    "sheep",
+    "species",
+];
+
+/// Stems of words ending in "sis" whose plural ends in "ses"
+const SIS_STEMS: [&str; 7] = [
+    "analy", "ba", "diagno", "parenthe", "progno", "synop", "the",
+];
+
+/// `UserName`, `userName` or `user-name` as `user_name`. Acronyms stay one
+/// word, so `HTTPServer` becomes `http_server`, and `::` becomes `/`.
+pub fn underscore(name: &str) -> String {
+    let name = name.replace("::", "/");
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::with_capacity(name.len() + 4);
+    for (i, &ch) in chars.iter().enumerate() {
+        if ch.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next_is_lower)
+            {
+                result.push('_');
+            }
+        }
+        match ch {
+            '-' | ' ' => result.push('_'),
+            _ => result.extend(ch.to_lowercase()),
+        }
+    }
+    result
+}
+
+/// `user_name` as `UserName`, or as `userName` when `upper_first` is false.
+/// `/` becomes `::`, so `admin/user` becomes `Admin::User`.
+pub fn camelize(name: &str, upper_first: bool) -> String {
+    let mut result = String::with_capacity(name.len());
+    for (i, part) in name.split('/').enumerate() {
+        if i > 0 {
+            result.push_str("::");
+        }
+        for (j, word) in part.split(['_', '-', ' ']).enumerate() {
+            if i == 0 && j == 0 && !upper_first {
+                result.extend(word.chars().flat_map(char::to_lowercase));
+            } else {
+                result.push_str(&capitalize(word));
+            }
+        }
+    }
+    result
+}
+
+/// `user_name` as `user-name`
+pub fn dasherize(name: &str) -> String {
+    name.replace('_', "-")
+}
+
+/// `user_name` or `UserName` as `User Name`
+pub fn titleize(name: &str) -> String {
+    underscore(name)
+        .split(['_', '/'])
+        .filter(|word| !word.is_empty())
+        .map(capitalize)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The plural of the last word of `name`: `category` gives `categories`,
+/// `Person` gives `People`.
+pub fn pluralize(name: &str) -> String {
+    inflect_last_word(name, plural_of)
+}
+
+/// The singular of the last word of `name`: `categories` gives `category`,
+/// `People` gives `Person`.
+pub fn singularize(name: &str) -> String {
+    inflect_last_word(name, singular_of)
+}
+
+/// `word` with its first letter in upper case and the rest in lower case
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+/// Apply `inflect` to the last word of `name`, given in lower case. The
+/// letters the inflected word shares with the original keep their case, so
+/// `Person` gives `People` and `API` gives `APIs`. A name with no last word,
+/// such as `""`, is returned unchanged.
+fn inflect_last_word(name: &str, inflect: fn(&str) -> String) -> String {
+    let (head, word) = name.split_at(last_word_start(name));
+    if word.is_empty() {
+        return name.to_string();
+    }
+    let inflected = inflect(&word.to_lowercase());
+    let mut result = head.to_string();
+    let mut original = word.chars();
+    let mut shared = true;
+    for ch in inflected.chars() {
+        match original.next() {
+            Some(written) if shared && written.to_lowercase().eq(ch.to_lowercase()) => {
+                result.push(written)
+            }
+            _ => {
+                shared = false;
+                result.push(ch);
+            }
+        }
+    }
+    result
+}
+
+/// Where the last word of a snake_case, dashed, spaced or CamelCase name
+/// starts
+fn last_word_start(name: &str) -> usize {
+    let mut start = 0;
+    let mut previous: Option<char> = None;
+    for (i, ch) in name.char_indices() {
+        if matches!(ch, '_' | '-' | ' ' | '/' | ':') {
+            start = i + ch.len_utf8();
+        } else if ch.is_uppercase() && previous.is_some_and(char::is_lowercase) {
+            start = i;
+        }
+        previous = Some(ch);
+    }
+    start
+}
+
+fn plural_of(word: &str) -> String {
+    if UNCOUNTABLES.contains(&word) {
+        return word.to_string();
+    }
+    for (singular, plural) in IRREGULARS {
+        if word == singular || word == plural {
+            return plural.to_string();
+        }
+    }
+    if let Some(stem) = word.strip_suffix("sis")
+        && SIS_STEMS.contains(&stem)
+    {
+        return format!("{}ses", stem);
+    }
+    if let Some(stem) = word.strip_suffix('y')
+        && !stem.ends_with(is_vowel)
+    {
+        return format!("{}ies", stem);
+    }
+    if let Some(stem) = word.strip_suffix("lf") {
+        return format!("{}lves", stem);
+    }
+    if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|suffix| word.ends_with(suffix))
+    {
+        return format!("{}es", word);
+    }
+    format!("{}s", word)
+}
+
+fn singular_of(word: &str) -> String {
+    if UNCOUNTABLES.contains(&word) {
+        return word.to_string();
+    }
+    for (singular, plural) in IRREGULARS {
+        if word == plural || word == singular {
+            return singular.to_string();
+        }
+    }
+    if let Some(stem) = word.strip_suffix("ses")
+        && SIS_STEMS.contains(&stem)
+    {
+        return format!("{}sis", stem);
+    }
+    if let Some(stem) = word.strip_suffix("ies") {
+        return format!("{}y", stem);
+    }
+    if let Some(stem) = word.strip_suffix("lves") {
+        return format!("{}lf", stem);
+    }
+    // "boxes" and "buses" drop "es", but "cases" only drops the "s"
+    if let Some(stem) = word.strip_suffix("es")
+        && ["x", "z", "ch", "sh", "ss", "us"]
+            .iter()
+            .any(|suffix| stem.ends_with(suffix))
+    {
+        return stem.to_string();
+    }
+    if word.ends_with("ss") || word.ends_with("us") || word.ends_with("is") {
+        return word.to_string();
+    }
+    word.strip_suffix('s').unwrap_or(word).to_string()
+}
+
+fn is_vowel(ch: char) -> bool {
+    matches!(ch, 'a' | 'e' | 'i' | 'o' | 'u')
+}
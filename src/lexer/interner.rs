@@ -0,0 +1,150 @@
+// String interning for identifier tokens
+// Each distinct name is allocated once per lexer and shared by every token
+// that refers to it, so cloning identifier tokens never copies their text
+
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::rc::Rc;
+
+/// An interned name, cheap to clone and compare
+///
+/// Symbols produced by the same interner share one allocation, so equality
+/// checks between them are a pointer comparison in the common case. Symbols
+/// built directly from a `&str` compare by content with interned ones.
+#[derive(Clone)]
+pub struct Symbol(Rc<str>);
+
+impl Symbol {
+    /// The text of this symbol
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether two symbols share the same interned allocation
+    pub fn ptr_eq(&self, other: &Symbol) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Deref for Symbol {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Symbol {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || self.0 == other.0
+    }
+}
+
+impl Eq for Symbol {}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl PartialEq<String> for Symbol {
+    fn eq(&self, other: &String) -> bool {
+        &*self.0 == other.as_str()
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(text: &str) -> Self {
+        Symbol(Rc::from(text))
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(text: String) -> Self {
+        Symbol(Rc::from(text))
+    }
+}
+
+impl From<Symbol> for String {
+    fn from(symbol: Symbol) -> Self {
+        symbol.0.to_string()
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Deduplicates names so each distinct identifier is allocated once
+#[derive(Debug, Default)]
+pub struct Interner {
+    names: HashSet<Rc<str>>,
+}
+
+impl Interner {
+    /// Create an empty interner
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the symbol for `text`, allocating only the first time it is seen
+    pub fn intern(&mut self, text: &str) -> Symbol {
+        if let Some(existing) = self.names.get(text) {
+            return Symbol(Rc::clone(existing));
+        }
+        let name: Rc<str> = Rc::from(text);
+        self.names.insert(Rc::clone(&name));
+        Symbol(name)
+    }
+
+    /// Look up a previously interned name without allocating
+    pub fn get(&self, text: &str) -> Option<Symbol> {
+        self.names.get(text).map(|name| Symbol(Rc::clone(name)))
+    }
+
+    /// Number of distinct names interned so far
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Whether no names have been interned yet
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
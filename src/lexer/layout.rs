@@ -0,0 +1,110 @@
+// Layout facts about source lines
+// Indentation, trailing whitespace and line endings, for formatters and linters
+
+use super::token::Span;
+
+/// The UTF-8 byte order mark some editors write at the start of a file
+const BOM: char = '\u{feff}';
+
+/// `source` without a leading UTF-8 byte order mark
+pub fn strip_bom(source: &str) -> &str {
+    source.strip_prefix(BOM).unwrap_or(source)
+}
+
+/// How a source line ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    CrLf,
+    /// The last line, with no line ending
+    None,
+}
+
+/// The layout of one source line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineLayout<'a> {
+    /// Line number, starting at 1 like `Position::line`
+    pub number: usize,
+    /// Byte offset of the line's first character in the source
+    pub start: usize,
+    /// The line without its line ending
+    pub text: &'a str,
+    /// Spaces and tabs before the first other character; empty on a
+    /// whitespace-only line
+    pub indent: &'a str,
+    /// Spaces and tabs after the last other character; all of the
+    /// whitespace on a whitespace-only line
+    pub trailing: &'a str,
+    /// How the line ends
+    pub ending: LineEnding,
+}
+
+impl LineLayout<'_> {
+    /// Whether the line holds nothing but spaces and tabs
+    pub fn is_blank(&self) -> bool {
+        self.trailing.len() == self.text.len()
+    }
+
+    /// Whether the indentation uses both tabs and spaces
+    pub fn has_mixed_indent(&self) -> bool {
+        self.indent.contains(' ') && self.indent.contains('\t')
+    }
+
+    /// Width of the indentation in columns, with each tab advancing to the
+    /// next multiple of `tab_width`
+    pub fn indent_width(&self, tab_width: usize) -> usize {
+        self.indent.chars().fold(0, |width, ch| match ch {
+            '\t' if tab_width > 0 => (width / tab_width + 1) * tab_width,
+            '\t' => width,
+            _ => width + 1,
+        })
+    }
+
+    /// Byte range of the trailing whitespace in the source
+    pub fn trailing_span(&self) -> Span {
+        let end = self.start + self.text.len();
+        Span::new(end - self.trailing.len(), end)
+    }
+}
+
+/// The layout of every line of `source`, skipping a leading byte order mark
+///
+/// A source ending with a line ending has no empty line after it, as with
+/// `str::lines`.
+pub fn line_layouts(source: &str) -> Vec<LineLayout<'_>> {
+    let mut start = source.len() - strip_bom(source).len();
+    let mut layouts = Vec::new();
+
+    while start < source.len() {
+        let rest = &source[start..];
+        let (line, ending, length) = match rest.find('\n') {
+            Some(end) if rest[..end].ends_with('\r') => {
+                (&rest[..end - 1], LineEnding::CrLf, end + 1)
+            }
+            Some(end) => (&rest[..end], LineEnding::Lf, end + 1),
+            None => (rest, LineEnding::None, rest.len()),
+        };
+        let is_space = |ch: char| ch == ' ' || ch == '\t';
+        let content = line.trim_start_matches(is_space);
+        let body = content.trim_end_matches(is_space);
+        let indent = if body.is_empty() {
+            ""
+        } else {
+            &line[..line.len() - content.len()]
+        };
+
+        layouts.push(LineLayout {
+            number: layouts.len() + 1,
+            start,
+            text: line,
+            indent,
+            trailing: &line[indent.len() + body.len()..],
+            ending,
+        });
+        start += length;
+    }
+
+    layouts
+}
@@ -1,16 +1,26 @@
 // Lexer module for tokenizing Metorex source code
 
+pub mod interner;
+pub mod layout;
 pub mod token;
 
-pub use token::{InterpolationPart, Position, Token, TokenKind};
+pub use interner::{Interner, Symbol};
+pub use layout::{LineEnding, LineLayout, line_layouts, strip_bom};
+pub use token::{InterpolationPart, Position, Span, Token, TokenKind};
 
 use std::iter::Peekable;
 use std::str::Chars;
 
 /// The lexer converts source code into a stream of tokens
 pub struct Lexer<'a> {
+    /// The source code being tokenized
+    source: &'a str,
     /// Peekable iterator over the characters
     chars: Peekable<Chars<'a>>,
+    /// Shared storage for identifier names
+    interner: Interner,
+    /// Problems that do not stop tokenizing, such as unknown escapes
+    warnings: Vec<String>,
     /// Current position in the source
     line: usize,
     column: usize,
@@ -19,22 +29,50 @@ pub struct Lexer<'a> {
 
 impl<'a> Lexer<'a> {
     /// Create a new lexer for the given source code
+    ///
+    /// A leading UTF-8 byte order mark is skipped; offsets still count it,
+    /// so spans slice the source as given.
     pub fn new(source: &'a str) -> Self {
+        let text = strip_bom(source);
         Self {
-            chars: source.chars().peekable(),
+            source,
+            chars: text.chars().peekable(),
+            interner: Interner::new(),
+            warnings: Vec::new(),
             line: 1,
             column: 1,
-            offset: 0,
+            offset: source.len() - text.len(),
         }
     }
 
+    /// The source code being tokenized
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    /// Names interned so far
+    pub fn interner(&self) -> &Interner {
+        &self.interner
+    }
+
+    /// Warnings reported so far
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
     /// Get the current position
     fn current_position(&self) -> Position {
         Position::new(self.line, self.column, self.offset)
     }
 
     /// Advance to the next character and return it
+    ///
+    /// A `\r\n` line ending is consumed whole and returned as `\n`.
     fn advance(&mut self) -> Option<char> {
+        if self.at_crlf() {
+            self.chars.next();
+            self.offset += 1;
+        }
         if let Some(ch) = self.chars.next() {
             self.offset += ch.len_utf8();
             if ch == '\n' {
@@ -49,12 +87,22 @@ impl<'a> Lexer<'a> {
         }
     }
 
-    /// Peek at the next character without consuming it
+    /// Peek at the next character without consuming it, seeing a `\r\n`
+    /// line ending as `\n`
     fn peek(&mut self) -> Option<char> {
+        if self.at_crlf() {
+            return Some('\n');
+        }
         self.chars.peek().copied()
     }
 
-    /// Skip whitespace characters (spaces and tabs, but not newlines)
+    /// Whether the next characters are a `\r\n` line ending
+    fn at_crlf(&self) -> bool {
+        self.source.as_bytes().get(self.offset..self.offset + 2) == Some(b"\r\n")
+    }
+
+    /// Skip whitespace characters (spaces, tabs and stray carriage returns,
+    /// but not newlines)
     fn skip_whitespace(&mut self) {
         while let Some(ch) = self.peek() {
             if ch == ' ' || ch == '\t' || ch == '\r' {
@@ -154,29 +202,33 @@ impl<'a> Lexer<'a> {
         ch.is_ascii_alphanumeric() || ch == '_'
     }
 
-    /// Read an identifier or keyword
-    fn read_identifier(&mut self) -> TokenKind {
-        let mut ident = String::new();
-
-        // Read identifier characters
+    /// Consume identifier characters and return the slice of source they span
+    fn read_name(&mut self) -> &'a str {
+        let start = self.offset;
         while let Some(ch) = self.peek() {
             if Self::is_identifier_continue(ch) {
-                ident.push(ch);
                 self.advance();
             } else {
                 break;
             }
         }
+        &self.source[start..self.offset]
+    }
+
+    /// Read an identifier or keyword
+    fn read_identifier(&mut self) -> TokenKind {
+        let start = self.offset;
+        self.read_name();
 
         // Check for trailing ? or ! (Ruby-style method names)
         if let Some(ch) = self.peek()
             && (ch == '?' || ch == '!')
         {
-            ident.push(ch);
             self.advance();
         }
 
         // Check if it's a keyword
+        let ident = &self.source[start..self.offset];
         self.keyword_or_identifier(ident)
     }
 
@@ -188,37 +240,53 @@ impl<'a> Lexer<'a> {
         // Check if it's a class variable (@@)
         if self.peek() == Some('@') {
             self.advance();
-            // Read the identifier part
-            let mut ident = String::new();
-            while let Some(ch) = self.peek() {
-                if Self::is_identifier_continue(ch) {
-                    ident.push(ch);
-                    self.advance();
-                } else {
-                    break;
-                }
-            }
-            TokenKind::ClassVar(ident)
+            let name = self.read_name();
+            TokenKind::ClassVar(self.interner.intern(name))
         } else {
             // Instance variable (@)
-            let mut ident = String::new();
-            while let Some(ch) = self.peek() {
-                if Self::is_identifier_continue(ch) {
-                    ident.push(ch);
-                    self.advance();
-                } else {
-                    break;
-                }
-            }
-            TokenKind::InstanceVar(ident)
+            let name = self.read_name();
+            TokenKind::InstanceVar(self.interner.intern(name))
         }
     }
 
+    /// Whether the `:` at the current position starts a symbol literal
+    ///
+    /// A name must follow the colon directly, and the colon must not follow
+    /// a value: `x: 1`, `"key":value` and `A::B` keep their plain colons.
+    fn at_symbol(&self) -> bool {
+        let after = self.source[self.offset + 1..].chars().next();
+        let before = self.source[..self.offset].chars().next_back();
+        let starts_name = after.is_some_and(|ch| Self::is_identifier_start(ch) || ch == '@');
+        let follows_value = before.is_some_and(|ch| {
+            Self::is_identifier_continue(ch)
+                || matches!(ch, ')' | ']' | '}' | '"' | '\'' | '?' | '!' | ':')
+        });
+        starts_name && !follows_value
+    }
+
+    /// Read a symbol literal (:name, :name?, :@variable or :@@variable)
+    fn read_symbol(&mut self) -> TokenKind {
+        // Skip the :
+        self.advance();
+        let start = self.offset;
+        while self.peek() == Some('@') && self.offset - start < 2 {
+            self.advance();
+        }
+        self.read_name();
+        if let Some(ch) = self.peek()
+            && (ch == '?' || ch == '!')
+        {
+            self.advance();
+        }
+        TokenKind::Symbol(self.interner.intern(&self.source[start..self.offset]))
+    }
+
     /// Convert a string to a keyword token or identifier
-    fn keyword_or_identifier(&self, ident: String) -> TokenKind {
-        match ident.as_str() {
+    fn keyword_or_identifier(&mut self, ident: &str) -> TokenKind {
+        match ident {
             "def" => TokenKind::Def,
             "class" => TokenKind::Class,
+            "module" => TokenKind::Module,
             "if" => TokenKind::If,
             "elsif" => TokenKind::Elsif,
             "else" => TokenKind::Else,
@@ -234,6 +302,7 @@ impl<'a> Lexer<'a> {
             "raise" => TokenKind::Raise,
             "break" => TokenKind::Break,
             "continue" => TokenKind::Continue,
+            "retry" => TokenKind::Retry,
             "return" => TokenKind::Return,
             "lambda" => TokenKind::Lambda,
             "super" => TokenKind::Super,
@@ -243,10 +312,13 @@ impl<'a> Lexer<'a> {
             "attr_reader" => TokenKind::AttrReader,
             "attr_writer" => TokenKind::AttrWriter,
             "attr_accessor" => TokenKind::AttrAccessor,
+            "and" => TokenKind::And,
+            "or" => TokenKind::Or,
+            "not" => TokenKind::Not,
             "true" => TokenKind::True,
             "false" => TokenKind::False,
             "nil" => TokenKind::Nil,
-            _ => TokenKind::Ident(ident),
+            _ => TokenKind::Ident(self.interner.intern(ident)),
         }
     }
 
@@ -322,6 +394,14 @@ impl<'a> Lexer<'a> {
                         }
                         Some(ch) => {
                             // For unrecognized escape sequences, include the backslash
+                            if quote == '"' {
+                                self.warnings.push(format!(
+                                    "Unknown escape sequence '\\{}' at {}:{} keeps its backslash (write '\\\\' or use a raw string for a literal backslash)",
+                                    ch,
+                                    self.line,
+                                    self.column - 1
+                                ));
+                            }
                             current_text.push('\\');
                             current_text.push(ch);
                             self.advance();
@@ -347,46 +427,7 @@ impl<'a> Lexer<'a> {
                             current_text.clear();
                         }
 
-                        // Read the expression until we find }
-                        let mut expr = String::new();
-                        let mut depth = 1; // Track nested braces
-
-                        loop {
-                            match self.peek() {
-                                None => {
-                                    return Err(format!(
-                                        "Unterminated interpolation starting at line {}",
-                                        self.line
-                                    ));
-                                }
-                                Some('\n') => {
-                                    return Err(format!(
-                                        "Unterminated interpolation starting at line {}",
-                                        self.line
-                                    ));
-                                }
-                                Some('{') => {
-                                    depth += 1;
-                                    expr.push('{');
-                                    self.advance();
-                                }
-                                Some('}') => {
-                                    depth -= 1;
-                                    if depth == 0 {
-                                        self.advance();
-                                        parts.push(InterpolationPart::Expression(expr));
-                                        break;
-                                    } else {
-                                        expr.push('}');
-                                        self.advance();
-                                    }
-                                }
-                                Some(ch) => {
-                                    expr.push(ch);
-                                    self.advance();
-                                }
-                            }
-                        }
+                        parts.push(self.read_interpolation()?);
                     } else {
                         // Not interpolation, just a # character
                         current_text.push('#');
@@ -400,6 +441,69 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Read the expression of an interpolation up to its closing `}`, just
+    /// after the opening `#{`
+    ///
+    /// The expression is tokenized like any other code, so braces in nested
+    /// strings, hash literals and blocks do not end it early. Its tokens keep
+    /// their positions in the enclosing source and end with an EOF token.
+    fn read_interpolation(&mut self) -> Result<InterpolationPart, String> {
+        let start = self.offset;
+        let start_line = self.line;
+        let mut tokens = Vec::new();
+        let mut depth = 0;
+
+        loop {
+            let token = self.next_token();
+            match token.kind {
+                TokenKind::LBrace => depth += 1,
+                TokenKind::RBrace if depth == 0 => {
+                    let end = token.span.start;
+                    tokens.push(Token::new(TokenKind::EOF, token.position));
+                    let source = self.source[start..end].to_string();
+                    return Ok(InterpolationPart::Expression(source, tokens));
+                }
+                TokenKind::RBrace => depth -= 1,
+                TokenKind::Newline | TokenKind::EOF => {
+                    return Err(format!(
+                        "Unterminated interpolation starting at line {}",
+                        start_line
+                    ));
+                }
+                _ => {}
+            }
+            tokens.push(token);
+        }
+    }
+
+    /// Read a raw string literal (`r"..."` or `r'...'`), in which backslashes
+    /// and `#{` are kept as written
+    fn read_raw_string(&mut self) -> Result<TokenKind, String> {
+        // Skip the r prefix and the opening quote
+        self.advance();
+        let quote = self.advance().unwrap_or('"');
+        let start = self.offset;
+
+        loop {
+            match self.peek() {
+                None | Some('\n') => {
+                    return Err(format!(
+                        "Unterminated raw string starting at line {}",
+                        self.line
+                    ));
+                }
+                Some(ch) if ch == quote => {
+                    let text = self.source[start..self.offset].to_string();
+                    self.advance();
+                    return Ok(TokenKind::String(text));
+                }
+                Some(_) => {
+                    self.advance();
+                }
+            }
+        }
+    }
+
     /// Peek at the next token without consuming it
     pub fn peek_token(&mut self) -> Token {
         // Save current state
@@ -407,6 +511,7 @@ impl<'a> Lexer<'a> {
         let saved_line = self.line;
         let saved_column = self.column;
         let saved_offset = self.offset;
+        let saved_warnings = self.warnings.len();
 
         // Get the next token
         let token = self.next_token();
@@ -416,12 +521,18 @@ impl<'a> Lexer<'a> {
         self.line = saved_line;
         self.column = saved_column;
         self.offset = saved_offset;
+        self.warnings.truncate(saved_warnings);
 
         token
     }
 
     /// Collect all tokens from the lexer
-    pub fn tokenize(mut self) -> Vec<Token> {
+    pub fn tokenize(self) -> Vec<Token> {
+        self.tokenize_with_warnings().0
+    }
+
+    /// Collect all tokens along with the warnings reported while tokenizing
+    pub fn tokenize_with_warnings(mut self) -> (Vec<Token>, Vec<String>) {
         let mut tokens = Vec::new();
         loop {
             let token = self.next_token();
@@ -431,7 +542,7 @@ impl<'a> Lexer<'a> {
             }
             tokens.push(token);
         }
-        tokens
+        (tokens, self.warnings)
     }
 
     /// Get the next token from the source code
@@ -440,151 +551,167 @@ impl<'a> Lexer<'a> {
         self.skip_whitespace();
 
         let position = self.current_position();
+        let kind = self.scan_token_kind();
+        Token::with_span(kind, position, Span::new(position.offset, self.offset))
+    }
 
+    /// Scan the next token and return its kind
+    fn scan_token_kind(&mut self) -> TokenKind {
         // Check for end of input
         if let Some(ch) = self.peek() {
             match ch {
                 '\n' => {
                     self.advance();
-                    Token::new(TokenKind::Newline, position)
-                }
-                '#' => {
-                    let comment = self.read_comment();
-                    Token::new(TokenKind::Comment(comment), position)
-                }
-                '0'..='9' => {
-                    let kind = self.read_number();
-                    Token::new(kind, position)
+                    TokenKind::Newline
                 }
+                '#' => TokenKind::Comment(self.read_comment()),
+                '0'..='9' => self.read_number(),
                 '"' | '\'' => match self.read_string(ch) {
-                    Ok(kind) => Token::new(kind, position),
+                    Ok(kind) => kind,
                     Err(_err) => {
                         // For now, return EOF on error
                         // TODO: Proper error handling will be added later
-                        Token::new(TokenKind::EOF, position)
+                        TokenKind::EOF
                     }
                 },
-                '@' => {
-                    let kind = self.read_variable();
-                    Token::new(kind, position)
+                '@' => self.read_variable(),
+                // `$!`, the exception being rescued
+                '$' if self.chars.clone().nth(1) == Some('!') => {
+                    self.advance();
+                    self.advance();
+                    TokenKind::Ident(self.interner.intern("$!"))
                 }
-                ch if Self::is_identifier_start(ch) => {
-                    let kind = self.read_identifier();
-                    Token::new(kind, position)
+                'r' if matches!(self.chars.clone().nth(1), Some('"' | '\'')) => {
+                    match self.read_raw_string() {
+                        Ok(kind) => kind,
+                        Err(_err) => TokenKind::EOF,
+                    }
                 }
+                ch if Self::is_identifier_start(ch) => self.read_identifier(),
                 // Single-character operators and compound operators
                 '+' => {
                     self.advance();
                     if self.peek() == Some('=') {
                         self.advance();
-                        Token::new(TokenKind::PlusEqual, position)
+                        TokenKind::PlusEqual
                     } else {
-                        Token::new(TokenKind::Plus, position)
+                        TokenKind::Plus
                     }
                 }
                 '-' => {
                     self.advance();
                     if self.peek() == Some('=') {
                         self.advance();
-                        Token::new(TokenKind::MinusEqual, position)
+                        TokenKind::MinusEqual
                     } else if self.peek() == Some('>') {
                         self.advance();
-                        Token::new(TokenKind::Arrow, position)
+                        TokenKind::Arrow
                     } else {
-                        Token::new(TokenKind::Minus, position)
+                        TokenKind::Minus
                     }
                 }
                 '*' => {
                     self.advance();
                     if self.peek() == Some('=') {
                         self.advance();
-                        Token::new(TokenKind::StarEqual, position)
+                        TokenKind::StarEqual
                     } else {
-                        Token::new(TokenKind::Star, position)
+                        TokenKind::Star
                     }
                 }
                 '/' => {
                     self.advance();
                     if self.peek() == Some('=') {
                         self.advance();
-                        Token::new(TokenKind::SlashEqual, position)
+                        TokenKind::SlashEqual
                     } else {
-                        Token::new(TokenKind::Slash, position)
+                        TokenKind::Slash
                     }
                 }
                 '%' => {
                     self.advance();
-                    Token::new(TokenKind::Percent, position)
+                    TokenKind::Percent
                 }
                 '=' => {
                     self.advance();
                     if self.peek() == Some('=') {
                         self.advance();
-                        Token::new(TokenKind::EqualEqual, position)
+                        if self.peek() == Some('=') {
+                            self.advance();
+                            TokenKind::CaseEqual
+                        } else {
+                            TokenKind::EqualEqual
+                        }
                     } else if self.peek() == Some('>') {
                         self.advance();
-                        Token::new(TokenKind::FatArrow, position)
+                        TokenKind::FatArrow
                     } else {
-                        Token::new(TokenKind::Equal, position)
+                        TokenKind::Equal
                     }
                 }
                 '!' => {
                     self.advance();
                     if self.peek() == Some('=') {
                         self.advance();
-                        Token::new(TokenKind::BangEqual, position)
+                        TokenKind::BangEqual
                     } else {
-                        // For now, return EOF if ! is not followed by =
-                        // TODO: Add Bang token if needed for unary not operator
-                        Token::new(TokenKind::EOF, position)
+                        TokenKind::Bang
                     }
                 }
                 '<' => {
                     self.advance();
-                    if self.peek() == Some('=') {
+                    if self.peek() == Some('<') {
+                        self.advance();
+                        TokenKind::ShiftLeft
+                    } else if self.peek() == Some('=') {
                         self.advance();
-                        Token::new(TokenKind::LessEqual, position)
+                        if self.peek() == Some('>') {
+                            self.advance();
+                            TokenKind::Spaceship
+                        } else {
+                            TokenKind::LessEqual
+                        }
                     } else {
-                        Token::new(TokenKind::Less, position)
+                        TokenKind::Less
                     }
                 }
                 '>' => {
                     self.advance();
                     if self.peek() == Some('=') {
                         self.advance();
-                        Token::new(TokenKind::GreaterEqual, position)
+                        TokenKind::GreaterEqual
                     } else {
-                        Token::new(TokenKind::Greater, position)
+                        TokenKind::Greater
                     }
                 }
                 // Delimiters
                 '(' => {
                     self.advance();
-                    Token::new(TokenKind::LParen, position)
+                    TokenKind::LParen
                 }
                 ')' => {
                     self.advance();
-                    Token::new(TokenKind::RParen, position)
+                    TokenKind::RParen
                 }
                 '{' => {
                     self.advance();
-                    Token::new(TokenKind::LBrace, position)
+                    TokenKind::LBrace
                 }
                 '}' => {
                     self.advance();
-                    Token::new(TokenKind::RBrace, position)
+                    TokenKind::RBrace
                 }
                 '[' => {
                     self.advance();
-                    Token::new(TokenKind::LBracket, position)
+                    TokenKind::LBracket
                 }
                 ']' => {
                     self.advance();
-                    Token::new(TokenKind::RBracket, position)
+                    TokenKind::RBracket
                 }
                 ',' => {
                     self.advance();
-                    Token::new(TokenKind::Comma, position)
+                    TokenKind::Comma
                 }
                 '.' => {
                     self.advance();
@@ -594,38 +721,54 @@ impl<'a> Lexer<'a> {
                         // Check for third dot
                         if self.peek() == Some('.') {
                             self.advance();
-                            Token::new(TokenKind::DotDotDot, position)
+                            TokenKind::DotDotDot
                         } else {
-                            Token::new(TokenKind::DotDot, position)
+                            TokenKind::DotDot
                         }
                     } else {
-                        Token::new(TokenKind::Dot, position)
+                        TokenKind::Dot
                     }
                 }
+                ':' if self.at_symbol() => self.read_symbol(),
                 ':' => {
                     self.advance();
-                    Token::new(TokenKind::Colon, position)
+                    if self.peek() == Some(':') {
+                        self.advance();
+                        TokenKind::ColonColon
+                    } else {
+                        TokenKind::Colon
+                    }
                 }
                 ';' => {
                     self.advance();
-                    Token::new(TokenKind::Semicolon, position)
+                    TokenKind::Semicolon
                 }
                 '|' => {
                     self.advance();
-                    Token::new(TokenKind::Pipe, position)
+                    if self.peek() == Some('|') {
+                        self.advance();
+                        TokenKind::PipePipe
+                    } else {
+                        TokenKind::Pipe
+                    }
                 }
                 '&' => {
                     self.advance();
-                    Token::new(TokenKind::Ampersand, position)
+                    if self.peek() == Some('&') {
+                        self.advance();
+                        TokenKind::AmpAmp
+                    } else {
+                        TokenKind::Ampersand
+                    }
                 }
                 _ => {
                     // Unknown character, consume and return EOF
                     self.advance();
-                    Token::new(TokenKind::EOF, position)
+                    TokenKind::EOF
                 }
             }
         } else {
-            Token::new(TokenKind::EOF, position)
+            TokenKind::EOF
         }
     }
 }
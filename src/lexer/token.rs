@@ -1,16 +1,19 @@
 // Token types for the Metorex lexer
 
+use super::interner::Symbol;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Represents a part of an interpolated string
 #[derive(Debug, Clone, PartialEq)]
 pub enum InterpolationPart {
     Text(String),
-    Expression(String), // The expression inside {}
+    /// The source text inside `#{}` and its tokens, ending with EOF
+    Expression(String, Vec<Token>),
 }
 
 /// Represents the position of a token in the source code
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct Position {
     pub line: usize,
     pub column: usize,
@@ -27,12 +30,44 @@ impl Position {
     }
 }
 
+/// Byte range of a token in the source code
+///
+/// Spans let tooling recover the exact source text of a token, including
+/// quotes and escape sequences that the token kind no longer carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// Length of the span in bytes
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Whether the span covers no source text
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// The source text covered by this span, or "" if it is out of range
+    pub fn slice<'s>(&self, source: &'s str) -> &'s str {
+        source.get(self.start..self.end).unwrap_or("")
+    }
+}
+
 /// The different kinds of tokens in Metorex
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenKind {
     // Keywords
     Def,
     Class,
+    Module,
     If,
     Elsif,
     Else,
@@ -46,6 +81,7 @@ pub enum TokenKind {
     Rescue,
     Ensure,
     Raise,
+    Retry,
     Break,
     Continue,
     Return,
@@ -57,6 +93,9 @@ pub enum TokenKind {
     AttrReader,
     AttrWriter,
     AttrAccessor,
+    And, // and
+    Or,  // or
+    Not, // not
 
     // Literals
     Int(i64),
@@ -68,9 +107,10 @@ pub enum TokenKind {
     Nil,
 
     // Identifiers
-    Ident(String),
-    InstanceVar(String), // @variable
-    ClassVar(String),    // @@variable
+    Ident(Symbol),
+    InstanceVar(Symbol), // @variable
+    ClassVar(Symbol),    // @@variable
+    Symbol(Symbol),      // :name, :@variable, :@@variable
 
     // Operators
     Plus,         // +
@@ -80,32 +120,39 @@ pub enum TokenKind {
     Percent,      // %
     Equal,        // =
     EqualEqual,   // ==
+    CaseEqual,    // ===
     BangEqual,    // !=
     Less,         // <
     Greater,      // >
     LessEqual,    // <=
     GreaterEqual, // >=
+    Spaceship,    // <=>
+    ShiftLeft,    // <<
     PlusEqual,    // +=
     MinusEqual,   // -=
     StarEqual,    // *=
     SlashEqual,   // /=
+    AmpAmp,       // &&
+    PipePipe,     // ||
+    Bang,         // !
 
     // Delimiters
-    LParen,    // (
-    RParen,    // )
-    LBrace,    // {
-    RBrace,    // }
-    LBracket,  // [
-    RBracket,  // ]
-    Comma,     // ,
-    Dot,       // .
-    DotDot,    // ..
-    DotDotDot, // ...
-    Colon,     // :
-    Arrow,     // ->
-    FatArrow,  // =>
-    Pipe,      // |
-    Ampersand, // &
+    LParen,     // (
+    RParen,     // )
+    LBrace,     // {
+    RBrace,     // }
+    LBracket,   // [
+    RBracket,   // ]
+    Comma,      // ,
+    Dot,        // .
+    DotDot,     // ..
+    DotDotDot,  // ...
+    Colon,      // :
+    ColonColon, // ::
+    Arrow,      // ->
+    FatArrow,   // =>
+    Pipe,       // |
+    Ampersand,  // &
 
     // Special tokens
     Newline,
@@ -114,16 +161,72 @@ pub enum TokenKind {
     EOF,
 }
 
-/// A token with its kind and position in the source code
+impl TokenKind {
+    /// Whether this token is a reserved word
+    pub fn is_keyword(&self) -> bool {
+        matches!(
+            self,
+            TokenKind::Def
+                | TokenKind::Class
+                | TokenKind::Module
+                | TokenKind::If
+                | TokenKind::Elsif
+                | TokenKind::Else
+                | TokenKind::Unless
+                | TokenKind::While
+                | TokenKind::For
+                | TokenKind::In
+                | TokenKind::End
+                | TokenKind::Do
+                | TokenKind::Begin
+                | TokenKind::Rescue
+                | TokenKind::Ensure
+                | TokenKind::Raise
+                | TokenKind::Retry
+                | TokenKind::Break
+                | TokenKind::Continue
+                | TokenKind::Return
+                | TokenKind::Lambda
+                | TokenKind::Super
+                | TokenKind::Case
+                | TokenKind::When
+                | TokenKind::Then
+                | TokenKind::AttrReader
+                | TokenKind::AttrWriter
+                | TokenKind::AttrAccessor
+                | TokenKind::And
+                | TokenKind::Or
+                | TokenKind::Not
+        )
+    }
+}
+
+/// A token with its kind, position and span in the source code
 #[derive(Debug, Clone, PartialEq)]
 pub struct Token {
     pub kind: TokenKind,
     pub position: Position,
+    pub span: Span,
 }
 
 impl Token {
+    /// Create a token with an empty span at `position`
     pub fn new(kind: TokenKind, position: Position) -> Self {
-        Self { kind, position }
+        Self::with_span(kind, position, Span::new(position.offset, position.offset))
+    }
+
+    /// Create a token covering `span` in the source code
+    pub fn with_span(kind: TokenKind, position: Position, span: Span) -> Self {
+        Self {
+            kind,
+            position,
+            span,
+        }
+    }
+
+    /// The original source text of this token
+    pub fn text<'s>(&self, source: &'s str) -> &'s str {
+        self.span.slice(source)
     }
 }
 
@@ -133,6 +236,7 @@ impl fmt::Display for TokenKind {
             // Keywords
             TokenKind::Def => write!(f, "def"),
             TokenKind::Class => write!(f, "class"),
+            TokenKind::Module => write!(f, "module"),
             TokenKind::If => write!(f, "if"),
             TokenKind::Elsif => write!(f, "elsif"),
             TokenKind::Else => write!(f, "else"),
@@ -148,6 +252,7 @@ impl fmt::Display for TokenKind {
             TokenKind::Raise => write!(f, "raise"),
             TokenKind::Break => write!(f, "break"),
             TokenKind::Continue => write!(f, "continue"),
+            TokenKind::Retry => write!(f, "retry"),
             TokenKind::Return => write!(f, "return"),
             TokenKind::Lambda => write!(f, "lambda"),
             TokenKind::Super => write!(f, "super"),
@@ -157,6 +262,9 @@ impl fmt::Display for TokenKind {
             TokenKind::AttrReader => write!(f, "attr_reader"),
             TokenKind::AttrWriter => write!(f, "attr_writer"),
             TokenKind::AttrAccessor => write!(f, "attr_accessor"),
+            TokenKind::And => write!(f, "and"),
+            TokenKind::Or => write!(f, "or"),
+            TokenKind::Not => write!(f, "not"),
 
             // Literals
             TokenKind::Int(n) => write!(f, "{}", n),
@@ -167,7 +275,7 @@ impl fmt::Display for TokenKind {
                 for part in parts {
                     match part {
                         InterpolationPart::Text(s) => write!(f, "{}", s)?,
-                        InterpolationPart::Expression(e) => write!(f, "{{{}}}", e)?,
+                        InterpolationPart::Expression(e, _) => write!(f, "{{{}}}", e)?,
                     }
                 }
                 write!(f, "\"")
@@ -180,6 +288,7 @@ impl fmt::Display for TokenKind {
             TokenKind::Ident(s) => write!(f, "{}", s),
             TokenKind::InstanceVar(s) => write!(f, "@{}", s),
             TokenKind::ClassVar(s) => write!(f, "@@{}", s),
+            TokenKind::Symbol(s) => write!(f, ":{}", s),
 
             // Operators
             TokenKind::Plus => write!(f, "+"),
@@ -189,15 +298,21 @@ impl fmt::Display for TokenKind {
             TokenKind::Percent => write!(f, "%"),
             TokenKind::Equal => write!(f, "="),
             TokenKind::EqualEqual => write!(f, "=="),
+            TokenKind::CaseEqual => write!(f, "==="),
             TokenKind::BangEqual => write!(f, "!="),
             TokenKind::Less => write!(f, "<"),
             TokenKind::Greater => write!(f, ">"),
             TokenKind::LessEqual => write!(f, "<="),
+            TokenKind::Spaceship => write!(f, "<=>"),
+            TokenKind::ShiftLeft => write!(f, "<<"),
             TokenKind::GreaterEqual => write!(f, ">="),
             TokenKind::PlusEqual => write!(f, "+="),
             TokenKind::MinusEqual => write!(f, "-="),
             TokenKind::StarEqual => write!(f, "*="),
             TokenKind::SlashEqual => write!(f, "/="),
+            TokenKind::AmpAmp => write!(f, "&&"),
+            TokenKind::PipePipe => write!(f, "||"),
+            TokenKind::Bang => write!(f, "!"),
 
             // Delimiters
             TokenKind::LParen => write!(f, "("),
@@ -211,6 +326,7 @@ impl fmt::Display for TokenKind {
             TokenKind::DotDot => write!(f, ".."),
             TokenKind::DotDotDot => write!(f, "..."),
             TokenKind::Colon => write!(f, ":"),
+            TokenKind::ColonColon => write!(f, "::"),
             TokenKind::Arrow => write!(f, "->"),
             TokenKind::FatArrow => write!(f, "=>"),
             TokenKind::Pipe => write!(f, "|"),
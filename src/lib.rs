@@ -2,20 +2,52 @@
 // A modern, Ruby-inspired language with powerful metaprogramming capabilities
 
 pub mod ast;
+pub mod ast_cache;
+pub mod builtin_catalog;
 pub mod builtin_classes;
 pub mod callable;
+pub mod char_set;
 pub mod class;
+pub mod duration;
 pub mod environment;
 pub mod error;
+pub mod examples;
 pub mod file_loader;
+pub mod glob;
+pub mod graph;
+pub mod hex;
+pub mod inflector;
 pub mod lexer;
+pub mod lint;
+pub mod literal;
+pub mod messages;
+pub mod module;
+pub mod number_format;
 pub mod object;
 pub mod parser;
+pub mod path_names;
+pub mod pretty_print;
+pub mod rc_file;
 pub mod repl;
 pub mod resolver;
-pub mod runtime;
 pub mod scope;
+pub mod session;
+pub mod source_format;
+pub mod terminal;
+pub mod text_table;
+pub mod typecheck;
+pub mod uri;
 pub mod vm;
+pub mod watch;
+
+// Embedding API
+// The types a host needs to run scripts, re-exported so embedders do not
+// have to know which module defines them. The interpreter itself lives in
+// the `vm` module tree, which is the only definition of VirtualMachine,
+// Heap, CallFrame and GlobalRegistry.
+pub use error::{MetorexError, SourceLocation};
+pub use object::Object;
+pub use vm::{NativeMethod, VirtualMachine, VmHandle, VmHooks, VmOptions};
 
 pub fn version() -> &'static str {
     env!("CARGO_PKG_VERSION")
@@ -0,0 +1,204 @@
+// `.metorex-lint.toml`, which turns lint rules on and off and sets their levels
+// and options. It is read with a small parser for the part of TOML it needs:
+// a table per rule, or a `[rules]` table of levels.
+//
+//     [rules]
+//     shadowed-name = "off"
+//
+//     [rules.long-method]
+//     level = "error"
+//     max_lines = 40
+
+use super::Level;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Name of the lint configuration file looked for in the current directory
+pub const CONFIG_FILE_NAME: &str = ".metorex-lint.toml";
+
+/// A value in the configuration file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigValue {
+    String(String),
+    Int(i64),
+    Bool(bool),
+}
+
+impl ConfigValue {
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            ConfigValue::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ConfigValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigValue::String(value) => write!(f, "\"{}\"", value),
+            ConfigValue::Int(value) => write!(f, "{}", value),
+            ConfigValue::Bool(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// The options of one rule, besides its level
+pub type RuleOptions = BTreeMap<String, ConfigValue>;
+
+/// What the configuration says about one rule
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RuleConfig {
+    /// The level to report at, or `None` for the rule's default
+    pub level: Option<Level>,
+    pub options: RuleOptions,
+}
+
+/// The settings of a `.metorex-lint.toml`, by rule name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LintConfig {
+    pub rules: BTreeMap<String, RuleConfig>,
+}
+
+impl LintConfig {
+    /// Read the configuration file at `path`
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|err| {
+            format!(
+                "cannot read lint configuration '{}': {}",
+                path.display(),
+                err
+            )
+        })?;
+        Self::parse(&text).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    /// Parse the text of a configuration file
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut config = LintConfig::default();
+        // The rule whose table the keys belong to, or `None` under `[rules]`
+        let mut table: Option<Option<String>> = None;
+
+        for (index, line) in text.lines().enumerate() {
+            let fail = |message: String| format!("line {}: {}", index + 1, message);
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('[') {
+                let name = header
+                    .strip_suffix(']')
+                    .ok_or_else(|| fail("expected ']' after the table name".to_string()))?
+                    .trim();
+                table = Some(match name {
+                    "rules" => None,
+                    _ => match name.strip_prefix("rules.") {
+                        Some(rule) if is_rule_name(rule) => {
+                            config.rules.entry(rule.to_string()).or_default();
+                            Some(rule.to_string())
+                        }
+                        _ => return Err(fail(format!("unknown table [{}]", name))),
+                    },
+                });
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| fail("expected key = value".to_string()))?;
+            let key = key.trim();
+            let value = parse_value(value.trim()).ok_or_else(|| {
+                fail(format!(
+                    "expected a quoted string, an integer or a boolean for '{}'",
+                    key
+                ))
+            })?;
+
+            match &table {
+                None => {
+                    return Err(fail(format!(
+                        "'{}' must be inside [rules] or a [rules.NAME] table",
+                        key
+                    )));
+                }
+                // `[rules]` maps rule names to levels
+                Some(None) => {
+                    if !is_rule_name(key) {
+                        return Err(fail(format!("invalid rule name '{}'", key)));
+                    }
+                    let rule = config.rules.entry(key.to_string()).or_default();
+                    if rule.level.is_some() {
+                        return Err(fail(format!(
+                            "the level of '{}' is given more than once",
+                            key
+                        )));
+                    }
+                    rule.level = Some(parse_level(&value).map_err(fail)?);
+                }
+                Some(Some(name)) => {
+                    let rule = config.rules.get_mut(name).expect("table was created");
+                    if key == "level" {
+                        if rule.level.is_some() {
+                            return Err(fail(format!(
+                                "the level of '{}' is given more than once",
+                                name
+                            )));
+                        }
+                        rule.level = Some(parse_level(&value).map_err(fail)?);
+                    } else if rule.options.insert(key.to_string(), value).is_some() {
+                        return Err(fail(format!("'{}' is given more than once", key)));
+                    }
+                }
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// `line` up to a `#` that is not inside a string
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (index, ch) in line.char_indices() {
+        match ch {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Rule names are lowercase words joined by `-` or `_`
+fn is_rule_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+fn parse_value(text: &str) -> Option<ConfigValue> {
+    if let Some(inner) = text
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    {
+        return (!inner.contains('"')).then(|| ConfigValue::String(inner.to_string()));
+    }
+    match text {
+        "true" => Some(ConfigValue::Bool(true)),
+        "false" => Some(ConfigValue::Bool(false)),
+        _ => text.replace('_', "").parse().ok().map(ConfigValue::Int),
+    }
+}
+
+fn parse_level(value: &ConfigValue) -> Result<Level, String> {
+    match value {
+        ConfigValue::String(level) => level.parse(),
+        other => Err(format!(
+            "invalid level {} (expected \"off\", \"warning\" or \"error\")",
+            other
+        )),
+    }
+}
@@ -0,0 +1,291 @@
+// Static checks for likely mistakes and style problems
+// `metorex lint` parses each file and runs a set of rules over the syntax
+// tree. Each rule reports offenses at a level, warning by default, which
+// `.metorex-lint.toml` can change or turn off, along with options such as the
+// longest method allowed. Rules implement `Rule`, usually with an
+// `ast::Visitor` to find the nodes they check, so an embedder can add its
+// own with `Linter::add_rule`. Projects can also write rules in Metorex, in
+// `lint/*.mx`.
+
+mod config;
+mod rules;
+mod script;
+
+pub use config::{CONFIG_FILE_NAME, ConfigValue, LintConfig, RuleConfig, RuleOptions};
+pub use rules::{EmptyRescue, LongMethod, ShadowedName, UnreachableCode, UnusedVariable};
+pub use script::{RULES_DIR, ScriptRule, load_script_rules};
+
+use crate::ast::Statement;
+use crate::error::MetorexError;
+use crate::lexer::{Lexer, Position};
+use crate::parser::Parser;
+use serde::Serialize;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// How seriously an offense is reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    /// The rule does not run
+    Off,
+    Warning,
+    /// Reported offenses make `metorex lint` fail
+    Error,
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "off" => Ok(Level::Off),
+            "warning" | "warn" => Ok(Level::Warning),
+            "error" => Ok(Level::Error),
+            other => Err(format!(
+                "invalid level '{}' (expected off, warning or error)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Off => write!(f, "off"),
+            Level::Warning => write!(f, "warning"),
+            Level::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// The output formats of `metorex lint`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LintFormat {
+    /// One `file:line:column: level: message [rule]` line per offense
+    #[default]
+    Text,
+    /// A JSON array of offenses
+    Json,
+}
+
+impl FromStr for LintFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "text" => Ok(LintFormat::Text),
+            "json" => Ok(LintFormat::Json),
+            other => Err(format!(
+                "invalid lint format '{}' (expected text or json)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for LintFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LintFormat::Text => write!(f, "text"),
+            LintFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// An offense found by a rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub rule: String,
+    pub level: Level,
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Diagnostic {
+    /// Name of the rule parse errors are reported under
+    pub const SYNTAX: &'static str = "syntax";
+
+    /// A parse error reported alongside rule offenses, since a file that
+    /// does not parse cannot be checked
+    pub fn from_parse_error(error: &MetorexError) -> Self {
+        let message = match error {
+            MetorexError::SyntaxError { message, .. } => message.clone(),
+            other => other.to_string(),
+        };
+        let (line, column) = error
+            .location()
+            .map_or((0, 0), |location| (location.line, location.column));
+        Diagnostic {
+            rule: Self::SYNTAX.to_string(),
+            level: Level::Error,
+            message,
+            line,
+            column,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}: {} [{}]",
+            self.line, self.column, self.level, self.message, self.rule
+        )
+    }
+}
+
+/// Where a rule reports what it finds
+#[derive(Debug, Default)]
+pub struct Offenses {
+    found: Vec<(Position, String)>,
+}
+
+impl Offenses {
+    /// Report an offense at `position`
+    pub fn report(&mut self, position: Position, message: impl Into<String>) {
+        self.found.push((position, message.into()));
+    }
+}
+
+/// A check run over the syntax tree of each file
+pub trait Rule {
+    /// The name used in configuration and reports, like `unused-variable`
+    fn name(&self) -> &str;
+
+    /// One line saying what the rule looks for
+    fn description(&self) -> &str;
+
+    /// The level the rule reports at unless configured otherwise
+    fn default_level(&self) -> Level {
+        Level::Warning
+    }
+
+    /// Apply the options from the rule's configuration table. Rules without
+    /// options reject any.
+    fn configure(&mut self, options: &RuleOptions) -> Result<(), String> {
+        match options.keys().next() {
+            Some(key) => Err(format!("unknown option '{}'", key)),
+            None => Ok(()),
+        }
+    }
+
+    /// Report the offenses in a parsed file
+    fn check(&mut self, program: &[Statement], offenses: &mut Offenses);
+}
+
+/// A set of rules and the level each reports at
+pub struct Linter {
+    rules: Vec<(Box<dyn Rule>, Level)>,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Linter {
+    /// A linter with the built-in rules at their default levels
+    pub fn new() -> Self {
+        let mut linter = Self::empty();
+        linter.add_rule(Box::new(UnusedVariable));
+        linter.add_rule(Box::new(ShadowedName));
+        linter.add_rule(Box::new(UnreachableCode));
+        linter.add_rule(Box::new(EmptyRescue));
+        linter.add_rule(Box::new(LongMethod::default()));
+        linter
+    }
+
+    /// A linter with no rules
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Add a rule at its default level
+    pub fn add_rule(&mut self, rule: Box<dyn Rule>) {
+        let level = rule.default_level();
+        self.rules.push((rule, level));
+    }
+
+    /// Add the Metorex rules in `dir` at their default levels. Their names
+    /// must differ from the rules already added.
+    pub fn add_script_rules(&mut self, dir: &Path) -> Result<(), String> {
+        for rule in load_script_rules(dir)? {
+            if self
+                .rules()
+                .any(|(existing, _)| existing.name() == rule.name())
+            {
+                return Err(format!(
+                    "{}: there is already a lint rule named '{}'",
+                    rule.path().display(),
+                    rule.name()
+                ));
+            }
+            self.add_rule(Box::new(rule));
+        }
+        Ok(())
+    }
+
+    /// The rules and their levels, in the order they run
+    pub fn rules(&self) -> impl Iterator<Item = (&dyn Rule, Level)> {
+        self.rules
+            .iter()
+            .map(|(rule, level)| (rule.as_ref(), *level))
+    }
+
+    /// Apply a configuration. Every rule it names must be one of this
+    /// linter's.
+    pub fn configure(&mut self, config: &LintConfig) -> Result<(), String> {
+        for (name, settings) in &config.rules {
+            let (rule, level) = self
+                .rules
+                .iter_mut()
+                .find(|(rule, _)| rule.name() == name)
+                .ok_or_else(|| format!("unknown lint rule '{}'", name))?;
+            rule.configure(&settings.options)
+                .map_err(|err| format!("{}: {}", name, err))?;
+            if let Some(configured) = settings.level {
+                *level = configured;
+            }
+        }
+        Ok(())
+    }
+
+    /// Run every rule that is not off over `program`, returning the offenses
+    /// in source order
+    pub fn lint(&mut self, program: &[Statement]) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for (rule, level) in &mut self.rules {
+            if *level == Level::Off {
+                continue;
+            }
+            let mut offenses = Offenses::default();
+            rule.check(program, &mut offenses);
+            diagnostics.extend(
+                offenses
+                    .found
+                    .into_iter()
+                    .map(|(position, message)| Diagnostic {
+                        rule: rule.name().to_string(),
+                        level: *level,
+                        message,
+                        line: position.line,
+                        column: position.column,
+                    }),
+            );
+        }
+        diagnostics.sort_by_key(|d| (d.line, d.column));
+        diagnostics
+    }
+
+    /// Parse `source` and lint it, or return its parse errors
+    pub fn lint_source(&mut self, source: &str) -> Result<Vec<Diagnostic>, Vec<MetorexError>> {
+        let program = Parser::new(Lexer::new(source).tokenize()).parse()?;
+        Ok(self.lint(&program))
+    }
+}
@@ -0,0 +1,515 @@
+// The built-in lint rules
+
+use super::{Offenses, Rule, RuleOptions};
+use crate::ast::{
+    DestructureTarget, Expression, MatchPattern, Statement, Visitor, walk_expression,
+    walk_statement, walk_statements,
+};
+use crate::lexer::Position;
+use std::collections::HashSet;
+
+/// Reports local variables that are assigned but never read. Parameters and
+/// names starting with `_` are left alone.
+pub struct UnusedVariable;
+
+impl Rule for UnusedVariable {
+    fn name(&self) -> &str {
+        "unused-variable"
+    }
+
+    fn description(&self) -> &str {
+        "Local variables that are assigned but never read"
+    }
+
+    fn check(&mut self, program: &[Statement], offenses: &mut Offenses) {
+        for (name, position) in VariableScopes::analyze(program).unused {
+            offenses.report(
+                position,
+                format!("Variable '{}' is assigned but never used", name),
+            );
+        }
+    }
+}
+
+/// Reports block parameters and pattern bindings that hide a variable of the
+/// same name from an enclosing scope
+pub struct ShadowedName;
+
+impl Rule for ShadowedName {
+    fn name(&self) -> &str {
+        "shadowed-name"
+    }
+
+    fn description(&self) -> &str {
+        "Block parameters and bindings that shadow an outer variable"
+    }
+
+    fn check(&mut self, program: &[Statement], offenses: &mut Offenses) {
+        for (name, position, outer) in VariableScopes::analyze(program).shadowed {
+            offenses.report(
+                position,
+                format!(
+                    "'{}' shadows the variable defined at {}:{}",
+                    name, outer.line, outer.column
+                ),
+            );
+        }
+    }
+}
+
+/// Reports statements after a `return`, `break`, `continue`, `raise` or `retry`
+/// in the same body, which can never run
+pub struct UnreachableCode;
+
+impl Rule for UnreachableCode {
+    fn name(&self) -> &str {
+        "unreachable-code"
+    }
+
+    fn description(&self) -> &str {
+        "Statements after a return, break, continue, raise or retry"
+    }
+
+    fn check(&mut self, program: &[Statement], offenses: &mut Offenses) {
+        struct Finder<'a>(&'a mut Offenses);
+
+        impl Visitor for Finder<'_> {
+            fn visit_statements(&mut self, statements: &[Statement]) {
+                let jump = statements
+                    .iter()
+                    .enumerate()
+                    .find_map(|(index, statement)| {
+                        let keyword = match statement {
+                            Statement::Return { .. } => "return",
+                            Statement::Break { .. } => "break",
+                            Statement::Continue { .. } => "continue",
+                            Statement::Raise { .. } => "raise",
+                            Statement::Retry { .. } => "retry",
+                            _ => return None,
+                        };
+                        Some((index, keyword))
+                    });
+                if let Some((index, keyword)) = jump
+                    && let Some(next) = statements.get(index + 1)
+                {
+                    self.0.report(
+                        next.position(),
+                        format!("Unreachable code after '{}'", keyword),
+                    );
+                }
+                walk_statements(self, statements);
+            }
+        }
+
+        Finder(offenses).visit_statements(program);
+    }
+}
+
+/// Reports rescue clauses with an empty body, which silently swallow the
+/// exceptions they catch
+pub struct EmptyRescue;
+
+impl Rule for EmptyRescue {
+    fn name(&self) -> &str {
+        "empty-rescue"
+    }
+
+    fn description(&self) -> &str {
+        "Rescue clauses that swallow exceptions without handling them"
+    }
+
+    fn check(&mut self, program: &[Statement], offenses: &mut Offenses) {
+        struct Finder<'a>(&'a mut Offenses);
+
+        impl Visitor for Finder<'_> {
+            fn visit_statement(&mut self, statement: &Statement) {
+                if let Statement::Begin { rescue_clauses, .. } = statement {
+                    for clause in rescue_clauses
+                        .iter()
+                        .filter(|clause| clause.body.is_empty())
+                    {
+                        let caught = match clause.exception_types.as_slice() {
+                            [] => "every StandardError".to_string(),
+                            types => types.join(", "),
+                        };
+                        self.0.report(
+                            clause.position,
+                            format!("Empty rescue swallows {} without handling it", caught),
+                        );
+                    }
+                }
+                walk_statement(self, statement);
+            }
+        }
+
+        Finder(offenses).visit_statements(program);
+    }
+}
+
+/// Reports functions and methods whose body runs over `max_lines` lines
+pub struct LongMethod {
+    pub max_lines: usize,
+}
+
+impl Default for LongMethod {
+    fn default() -> Self {
+        Self { max_lines: 30 }
+    }
+}
+
+impl Rule for LongMethod {
+    fn name(&self) -> &str {
+        "long-method"
+    }
+
+    fn description(&self) -> &str {
+        "Methods whose body is longer than max_lines (default 30)"
+    }
+
+    fn configure(&mut self, options: &RuleOptions) -> Result<(), String> {
+        for (key, value) in options {
+            match (key.as_str(), value.as_int()) {
+                ("max_lines", Some(max_lines)) if max_lines > 0 => {
+                    self.max_lines = max_lines as usize;
+                }
+                ("max_lines", _) => {
+                    return Err(format!(
+                        "max_lines must be a positive integer, got {}",
+                        value
+                    ));
+                }
+                _ => return Err(format!("unknown option '{}'", key)),
+            }
+        }
+        Ok(())
+    }
+
+    fn check(&mut self, program: &[Statement], offenses: &mut Offenses) {
+        struct Finder<'a> {
+            max_lines: usize,
+            offenses: &'a mut Offenses,
+        }
+
+        impl Visitor for Finder<'_> {
+            fn visit_statement(&mut self, statement: &Statement) {
+                if let Statement::FunctionDef {
+                    name,
+                    body,
+                    position,
+                    ..
+                }
+                | Statement::MethodDef {
+                    name,
+                    body,
+                    position,
+                    ..
+                } = statement
+                {
+                    let mut last = LastLine(position.line);
+                    last.visit_statements(body);
+                    let lines = last.0 - position.line;
+                    if lines > self.max_lines {
+                        self.offenses.report(
+                            *position,
+                            format!(
+                                "Method '{}' is {} lines long (more than {})",
+                                name, lines, self.max_lines
+                            ),
+                        );
+                    }
+                }
+                walk_statement(self, statement);
+            }
+        }
+
+        Finder {
+            max_lines: self.max_lines,
+            offenses,
+        }
+        .visit_statements(program);
+    }
+}
+
+/// The last line any node visited starts on
+struct LastLine(usize);
+
+impl Visitor for LastLine {
+    fn visit_statement(&mut self, statement: &Statement) {
+        self.0 = self.0.max(statement.position().line);
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        self.0 = self.0.max(expression.position().line);
+        walk_expression(self, expression);
+    }
+}
+
+/// Whether a scope belongs to a definition or to a block inside one
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ScopeKind {
+    /// The top level of the file
+    Program,
+    /// A function, method, class or module body
+    Definition,
+    /// A block, lambda, `for` loop or case branch, which sees the variables
+    /// around it
+    Block,
+}
+
+struct Variable {
+    name: String,
+    position: Position,
+    used: bool,
+    /// Parameters and bindings are never reported as unused
+    parameter: bool,
+}
+
+struct Scope {
+    kind: ScopeKind,
+    variables: Vec<Variable>,
+}
+
+/// Tracks which local variables are declared and read, following the VM's
+/// scoping: every body sees the variables of the bodies around it, and an
+/// assignment to a visible variable updates it rather than declaring a new
+/// one.
+struct VariableScopes {
+    scopes: Vec<Scope>,
+    /// Names read where no variable was visible, which may be variables of
+    /// an enclosing body assigned after the read, such as top-level variables
+    /// a method uses
+    unresolved_reads: HashSet<String>,
+    unused: Vec<(String, Position)>,
+    shadowed: Vec<(String, Position, Position)>,
+}
+
+impl VariableScopes {
+    fn analyze(program: &[Statement]) -> Self {
+        let mut scopes = VariableScopes {
+            scopes: vec![Scope {
+                kind: ScopeKind::Program,
+                variables: Vec::new(),
+            }],
+            unresolved_reads: HashSet::new(),
+            unused: Vec::new(),
+            shadowed: Vec::new(),
+        };
+        scopes.visit_statements(program);
+        scopes.pop_scope();
+        scopes.unused.sort_by_key(|(_, position)| position.offset);
+        scopes
+            .shadowed
+            .sort_by_key(|(_, position, _)| position.offset);
+        scopes
+    }
+
+    fn push_scope(&mut self, kind: ScopeKind) {
+        self.scopes.push(Scope {
+            kind,
+            variables: Vec::new(),
+        });
+    }
+
+    /// Leave the innermost scope, recording its unused variables
+    fn pop_scope(&mut self) {
+        let Some(scope) = self.scopes.pop() else {
+            return;
+        };
+        for variable in scope.variables {
+            let read_elsewhere =
+                scope.kind != ScopeKind::Block && self.unresolved_reads.contains(&variable.name);
+            if !variable.used
+                && !variable.parameter
+                && !read_elsewhere
+                && !variable.name.starts_with('_')
+            {
+                self.unused.push((variable.name, variable.position));
+            }
+        }
+    }
+
+    /// The visible variable named `name`, innermost first
+    fn lookup(&mut self, name: &str) -> Option<&mut Variable> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .flat_map(|scope| scope.variables.iter_mut())
+            .find(|variable| variable.name == name)
+    }
+
+    fn declare(&mut self, name: &str, position: Position, parameter: bool) {
+        let scope = self
+            .scopes
+            .last_mut()
+            .expect("the program scope is never popped");
+        scope.variables.push(Variable {
+            name: name.to_string(),
+            position,
+            used: false,
+            parameter,
+        });
+    }
+
+    /// Declare a block parameter or binding in a new block scope, noting when
+    /// it hides an outer variable
+    fn bind(&mut self, name: &str, position: Position) {
+        if let Some(outer) = self.lookup(name) {
+            let outer = outer.position;
+            self.shadowed.push((name.to_string(), position, outer));
+        }
+        self.declare(name, position, true);
+    }
+
+    fn bind_pattern(&mut self, pattern: &MatchPattern, position: Position) {
+        match pattern {
+            MatchPattern::Identifier(name) | MatchPattern::Rest(name) => {
+                self.bind(name, position);
+            }
+            MatchPattern::Array(patterns) => {
+                for pattern in patterns {
+                    self.bind_pattern(pattern, position);
+                }
+            }
+            MatchPattern::Object(entries) => {
+                for (_, pattern) in entries {
+                    self.bind_pattern(pattern, position);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Visitor for VariableScopes {
+    fn visit_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Assignment {
+                target: Expression::Identifier { name, position },
+                value,
+                ..
+            } => {
+                self.visit_expression(value);
+                if self.lookup(name).is_none() {
+                    self.declare(name, *position, false);
+                }
+            }
+            Statement::FunctionDef {
+                parameters, body, ..
+            }
+            | Statement::MethodDef {
+                parameters, body, ..
+            } => {
+                self.push_scope(ScopeKind::Definition);
+                for parameter in parameters {
+                    if let Some(default_value) = &parameter.default_value {
+                        self.visit_expression(default_value);
+                    }
+                    self.declare(&parameter.name, parameter.position, true);
+                }
+                self.visit_statements(body);
+                self.pop_scope();
+            }
+            Statement::ClassDef { body, .. } | Statement::ModuleDef { body, .. } => {
+                self.push_scope(ScopeKind::Definition);
+                self.visit_statements(body);
+                self.pop_scope();
+            }
+            Statement::Block { statements, .. } => {
+                self.push_scope(ScopeKind::Block);
+                self.visit_statements(statements);
+                self.pop_scope();
+            }
+            Statement::For {
+                variable,
+                iterable,
+                body,
+                position,
+            } => {
+                self.visit_expression(iterable);
+                self.push_scope(ScopeKind::Block);
+                self.bind(variable, *position);
+                self.visit_statements(body);
+                self.pop_scope();
+            }
+            Statement::Match {
+                expression, cases, ..
+            } => {
+                self.visit_expression(expression);
+                for case in cases {
+                    self.push_scope(ScopeKind::Block);
+                    self.bind_pattern(&case.pattern, case.position);
+                    if let Some(guard) = &case.guard {
+                        self.visit_expression(guard);
+                    }
+                    self.visit_statements(&case.body);
+                    self.pop_scope();
+                }
+            }
+            Statement::Begin { rescue_clauses, .. } => {
+                for clause in rescue_clauses {
+                    if let Some(name) = &clause.variable_name
+                        && self.lookup(name).is_none()
+                    {
+                        self.declare(name, clause.position, true);
+                    }
+                }
+                walk_statement(self, statement);
+            }
+            _ => walk_statement(self, statement),
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Identifier { name, .. } => match self.lookup(name) {
+                Some(variable) => variable.used = true,
+                None => {
+                    self.unresolved_reads.insert(name.clone());
+                }
+            },
+            Expression::Lambda {
+                parameters,
+                body,
+                destructured,
+                position,
+                ..
+            } => {
+                self.push_scope(ScopeKind::Block);
+                for (index, parameter) in parameters.iter().enumerate() {
+                    match destructured.iter().find(|d| d.index == index) {
+                        Some(pattern) => {
+                            for name in DestructureTarget::names(&pattern.targets) {
+                                self.bind(name, *position);
+                            }
+                        }
+                        None => self.bind(parameter, *position),
+                    }
+                }
+                self.visit_statements(body);
+                self.pop_scope();
+            }
+            Expression::Case {
+                expression,
+                cases,
+                else_case,
+                ..
+            } => {
+                self.visit_expression(expression);
+                for case in cases {
+                    self.push_scope(ScopeKind::Block);
+                    self.bind_pattern(&case.pattern, case.position);
+                    if let Some(guard) = &case.guard {
+                        self.visit_expression(guard);
+                    }
+                    self.visit_expression(&case.body);
+                    self.pop_scope();
+                }
+                if let Some(else_case) = else_case {
+                    self.visit_expression(else_case);
+                }
+            }
+            _ => walk_expression(self, expression),
+        }
+    }
+}
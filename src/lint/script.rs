@@ -0,0 +1,248 @@
+// Lint rules written in Metorex
+// A project can keep rules in `lint/*.mx`. Each file is a rule named after
+// the file, `lint/no_puts.mx` becoming `no-puts`, and described by its leading
+// comment. The file defines `check(node, diagnostics)`, which is called with
+// every node of the tree `Metorex.parse` would return, parents before their
+// children, and reports offenses with `diagnostics.report(node, message)`.
+// `diagnostics.option(name, default)` reads the rule's configuration table.
+//
+//     # Calls to puts left in from debugging
+//     def check(node, diagnostics)
+//       if node.type == :Call
+//         if node.callee.type == :Identifier
+//           if node.callee.name == "puts"
+//             diagnostics.report(node, "Remove debugging output")
+//           end
+//         end
+//       end
+//     end
+
+use super::{ConfigValue, Offenses, Rule, RuleOptions};
+use crate::ast::Statement;
+use crate::error::MetorexError;
+use crate::lexer::{Lexer, Position};
+use crate::object::Object;
+use crate::parser::Parser;
+use crate::vm::VirtualMachine;
+use indexmap::IndexMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory `metorex lint` loads Metorex rules from
+pub const RULES_DIR: &str = "lint";
+
+/// The method each rule file defines
+const CHECK: &str = "check";
+
+/// Defines the object rules report through before the rule file runs
+const PRELUDE: &str = r#"
+class LintDiagnostics
+  def initialize(options)
+    @options = options
+    @offenses = []
+  end
+
+  # An option from the rule's configuration table, or the default
+  def option(name, default)
+    if @options.has_key?(name)
+      return @options[name]
+    end
+    default
+  end
+
+  # Report an offense at a node, or at a node's position
+  def report(node, message)
+    position = node
+    if node.type != nil
+      position = node.position
+    end
+    @offenses.push([position.line, position.column, message.to_s])
+  end
+end
+"#;
+
+/// A rule loaded from a Metorex file
+pub struct ScriptRule {
+    name: String,
+    description: String,
+    path: PathBuf,
+    vm: VirtualMachine,
+    options: RuleOptions,
+}
+
+impl ScriptRule {
+    /// Run the rule file at `path`, which must define `check`
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let fail = |message: String| format!("{}: {}", path.display(), message);
+        let source = fs::read_to_string(path).map_err(|err| fail(err.to_string()))?;
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default()
+            .replace('_', "-");
+
+        let mut vm = VirtualMachine::new();
+        let prelude = Parser::new(Lexer::new(PRELUDE).tokenize())
+            .parse()
+            .expect("the lint prelude parses");
+        vm.execute_program(&prelude)
+            .map_err(|err| fail(err.to_string()))?;
+        vm.execute_file(path).map_err(|err| fail(err.to_string()))?;
+        if !matches!(vm.environment().get(CHECK), Some(Object::Method(_))) {
+            return Err(fail(format!(
+                "a lint rule must define '{}(node, diagnostics)'",
+                CHECK
+            )));
+        }
+
+        Ok(Self {
+            name,
+            description: leading_comment(&source),
+            path: path.to_path_buf(),
+            vm,
+            options: RuleOptions::new(),
+        })
+    }
+
+    /// The file the rule was loaded from
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Call `check` on `node` and everything under it
+    fn visit(&mut self, node: &Object, diagnostics: &Object) -> Result<(), MetorexError> {
+        if let Some(fields) = self.vm.node_fields(node) {
+            if matches!(fields.get("type"), Some(Object::Symbol(_))) {
+                let check = self.vm.environment().get(CHECK).unwrap_or(Object::Nil);
+                self.vm.invoke_callable(
+                    check,
+                    vec![node.clone(), diagnostics.clone()],
+                    Position::default(),
+                )?;
+            }
+            for (field, value) in &fields {
+                if field != "type" && field != "position" {
+                    self.visit(value, diagnostics)?;
+                }
+            }
+        } else if let Object::Array(elements) = node {
+            let elements = elements.borrow().clone();
+            for element in &elements {
+                self.visit(element, diagnostics)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Walk `program`, returning what the rule reported
+    fn run(&mut self, program: &[Statement]) -> Result<Vec<(Position, String)>, MetorexError> {
+        let options = self
+            .options
+            .iter()
+            .map(|(name, value)| (name.clone(), option_to_object(value)))
+            .collect::<IndexMap<_, _>>();
+        let class = self
+            .vm
+            .environment()
+            .get("LintDiagnostics")
+            .unwrap_or(Object::Nil);
+        let diagnostics =
+            self.vm
+                .invoke_callable(class, vec![Object::dict(options)], Position::default())?;
+
+        let tree = self.vm.program_to_node(program, Position::default())?;
+        self.visit(&tree, &diagnostics)?;
+
+        let Object::Instance(instance) = &diagnostics else {
+            return Ok(Vec::new());
+        };
+        let Some(Object::Array(offenses)) = instance.borrow().get_var("offenses").cloned() else {
+            return Ok(Vec::new());
+        };
+        let offenses = offenses.borrow();
+        Ok(offenses.iter().filter_map(offense_from_object).collect())
+    }
+}
+
+impl Rule for ScriptRule {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Options are handed to the rule as a Hash instead of being checked
+    fn configure(&mut self, options: &RuleOptions) -> Result<(), String> {
+        self.options = options.clone();
+        Ok(())
+    }
+
+    fn check(&mut self, program: &[Statement], offenses: &mut Offenses) {
+        match self.run(program) {
+            Ok(found) => {
+                for (position, message) in found {
+                    offenses.report(position, message);
+                }
+            }
+            // A rule that raises is reported once, at the top of the file
+            Err(err) => offenses.report(
+                Position::default(),
+                format!("{} failed: {}", self.path.display(), err),
+            ),
+        }
+    }
+}
+
+/// Load every `*.mx` rule in `dir`, sorted by file name, or none if the
+/// directory does not exist
+pub fn load_script_rules(dir: &Path) -> Result<Vec<ScriptRule>, String> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let entries = fs::read_dir(dir)
+        .map_err(|err| format!("cannot read lint rules in '{}': {}", dir.display(), err))?;
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "mx"))
+        .collect();
+    paths.sort();
+    paths.iter().map(|path| ScriptRule::load(path)).collect()
+}
+
+/// The `#` comment lines a rule file starts with, joined
+fn leading_comment(source: &str) -> String {
+    source
+        .lines()
+        .map_while(|line| line.trim().strip_prefix('#'))
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn option_to_object(value: &ConfigValue) -> Object {
+    match value {
+        ConfigValue::String(value) => Object::string(value.clone()),
+        ConfigValue::Int(value) => Object::Int(*value),
+        ConfigValue::Bool(value) => Object::Bool(*value),
+    }
+}
+
+/// A `[line, column, message]` entry recorded by `LintDiagnostics#report`
+fn offense_from_object(offense: &Object) -> Option<(Position, String)> {
+    let Object::Array(entry) = offense else {
+        return None;
+    };
+    match entry.borrow().as_slice() {
+        [
+            Object::Int(line),
+            Object::Int(column),
+            Object::String(message),
+        ] => Some((
+            Position::new(*line as usize, *column as usize, 0),
+            message.to_string(),
+        )),
+        _ => None,
+    }
+}
@@ -0,0 +1,149 @@
+// Metorex source for runtime values
+// Backs String#to_source, Object#to_literal and CodeBuilder, so generated
+// code reads a value back exactly rather than relying on hand-written
+// escaping.
+//
+// Strings are written double-quoted, escaping only what the lexer would
+// otherwise read differently: backslashes, quotes, line breaks, tabs and
+// the `#{` that starts an interpolation. Floats are written with a decimal
+// point and without an exponent, which Metorex literals lack. Hash keys come
+// back the way hashes store them: symbols as `:name` and everything else as
+// the String it was stored under.
+
+use crate::object::Object;
+use std::fmt::Write;
+use std::rc::Rc;
+
+/// `text` as a double-quoted String literal
+pub fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            '#' if chars.peek() == Some(&'{') => quoted.push_str("\\#"),
+            _ => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Whether `name` can be written as a symbol literal, like `:name`,
+/// `:valid?` or `:@count`
+pub fn is_plain_symbol(name: &str) -> bool {
+    let name = name
+        .strip_prefix("@@")
+        .or_else(|| name.strip_prefix('@'))
+        .unwrap_or(name);
+    let name = name.strip_suffix(['?', '!']).unwrap_or(name);
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+/// Source that evaluates to `value`: nil, Bools, numbers, Strings, symbols,
+/// Ranges and Arrays and Hashes of those. Anything else, and containers that
+/// hold themselves, have no literal form.
+pub fn to_literal(value: &Object) -> Result<String, String> {
+    let mut out = String::new();
+    write_literal(value, &mut Vec::new(), &mut out)?;
+    Ok(out)
+}
+
+fn write_literal(value: &Object, open: &mut Vec<usize>, out: &mut String) -> Result<(), String> {
+    match value {
+        Object::Nil => out.push_str("nil"),
+        Object::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+        // The lexer reads the digits before applying the minus, and the
+        // smallest Int's digits don't fit in an Int
+        Object::Int(i64::MIN) => out.push_str("(-9223372036854775807 - 1)"),
+        Object::Int(value) => {
+            let _ = write!(out, "{}", value);
+        }
+        Object::Float(value) if value.is_finite() => {
+            let text = value.to_string();
+            out.push_str(&text);
+            if !text.contains('.') {
+                out.push_str(".0");
+            }
+        }
+        Object::Decimal(value) => {
+            let _ = write!(out, "Decimal.new(\"{}\")", value);
+        }
+        Object::String(text) => out.push_str(&quote(text)),
+        Object::Symbol(name) if is_plain_symbol(name) => {
+            out.push(':');
+            out.push_str(name);
+        }
+        Object::Range {
+            start,
+            end,
+            exclusive,
+        } => {
+            write_literal(start, open, out)?;
+            out.push_str(if *exclusive { "..." } else { ".." });
+            write_literal(end, open, out)?;
+        }
+        Object::Array(items) => {
+            enter(Rc::as_ptr(items) as usize, "[...]", open)?;
+            out.push('[');
+            for (i, item) in items.borrow().iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_literal(item, open, out)?;
+            }
+            out.push(']');
+            open.pop();
+        }
+        Object::Dict(dict) => {
+            enter(Rc::as_ptr(dict) as usize, "{...}", open)?;
+            out.push('{');
+            for (i, (key, value)) in dict.borrow().iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                match key.strip_prefix(':') {
+                    Some(name) if is_plain_symbol(name) => out.push_str(key),
+                    _ => out.push_str(&quote(key)),
+                }
+                out.push_str(" => ");
+                write_literal(value, open, out)?;
+            }
+            out.push('}');
+            open.pop();
+        }
+        other => return Err(format!("{} has no literal form", describe(other))),
+    }
+    Ok(())
+}
+
+/// Note that the container at `address` is being written, failing if it
+/// already is, since it then holds itself.
+fn enter(address: usize, shown_as: &str, open: &mut Vec<usize>) -> Result<(), String> {
+    if open.contains(&address) {
+        return Err(format!(
+            "{} contains itself and has no literal form",
+            shown_as
+        ));
+    }
+    open.push(address);
+    Ok(())
+}
+
+fn describe(value: &Object) -> String {
+    match value {
+        Object::Float(value) => format!("Float {}", Object::Float(*value)),
+        Object::Symbol(name) => format!("symbol :{}", name),
+        Object::Instance(instance) => format!("{} instance", instance.borrow().class.name()),
+        other => other.type_name().to_string(),
+    }
+}
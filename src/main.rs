@@ -1,81 +1,958 @@
 // Metorex CLI
 // Command-line interface for the Metorex programming language
 
+use clap::{Args, Parser as CliParser, Subcommand};
+use metorex::ast::Statement;
+use metorex::builtin_catalog::{self, BuiltinClass, BuiltinFunction, MethodKind};
+use metorex::examples::{self, ExampleOutcome};
+use metorex::graph::{GraphFormat, ProgramGraph};
+use metorex::inflector::pluralize;
 use metorex::lexer::Lexer;
+use metorex::lint::{self, Diagnostic, Level, LintConfig, LintFormat, Linter};
+use metorex::messages::{self, MessageCatalog};
 use metorex::parser::Parser;
+use metorex::path_names::strip_verbatim_prefix;
+use metorex::rc_file;
 use metorex::repl::Repl;
-use metorex::vm::VirtualMachine;
-use std::env;
+use metorex::resolver::Resolver;
+use metorex::source_format;
+use metorex::terminal::{ColorChoice, Palette, Style};
+use metorex::typecheck;
+use metorex::vm::{EventLog, PostMortem, VirtualMachine, VmOptions};
+use metorex::watch;
 use std::fs;
+use std::path::{Path, PathBuf};
 use std::process;
+use std::time::Instant;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    // No arguments or explicit REPL flag - start REPL mode
-    if args.len() == 1 || (args.len() == 2 && (args[1] == "repl" || args[1] == "--repl")) {
-        match Repl::new() {
-            Ok(mut repl) => {
-                if let Err(err) = repl.run() {
-                    eprintln!("REPL error: {}", err);
-                    process::exit(1);
-                }
+/// Exit status for a run that failed: a parse, runtime or check error
+const EXIT_FAILURE: i32 = 1;
+
+/// Exit status for a command line that could not be understood
+const EXIT_USAGE: i32 = 2;
+
+const AFTER_HELP: &str = "\
+A file given without a command is run, so `metorex app.mx` is `metorex run app.mx`.
+With neither, the REPL starts.
+
+Exit status: 0 on success, 1 when a program fails to parse, raises an error or
+fails a check, and 2 for usage errors such as unknown flags or VM options.";
+
+/// The Metorex programming language
+#[derive(Debug, CliParser)]
+#[command(
+    name = "metorex",
+    version,
+    after_help = AFTER_HELP
+)]
+struct Cli {
+    #[command(flatten)]
+    global: GlobalArgs,
+
+    #[command(flatten)]
+    run: RunArgs,
+
+    /// Start the REPL (the same as the repl command)
+    #[arg(long, hide = true)]
+    repl: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Flags accepted before or after any command
+#[derive(Debug, Args)]
+struct GlobalArgs {
+    /// When to color output: auto, always or never
+    #[arg(long, global = true, value_name = "WHEN", default_value = "auto")]
+    color: ColorChoice,
+
+    /// Set a VM option, on top of those in MX_OPTS
+    #[arg(long = "vm-opt", global = true, value_name = "KEY=VALUE")]
+    vm_options: Vec<String>,
+
+    /// Check arguments and results of annotated methods at call time
+    #[arg(long, global = true)]
+    check_types: bool,
+
+    /// Skip assert calls and method pre- and postconditions
+    #[arg(long, global = true)]
+    no_contracts: bool,
+
+    /// Report static analysis warnings before running
+    #[arg(short = 'w', long, global = true)]
+    warnings: bool,
+
+    /// Add a directory to the load path `require` searches
+    #[arg(short = 'I', global = true, value_name = "DIR")]
+    include: Vec<PathBuf>,
+
+    /// Require a library before running the program, REPL or tests
+    #[arg(short = 'r', global = true, value_name = "LIB")]
+    require: Vec<String>,
+
+    /// Write an event log of the run to FILE, for replay
+    #[arg(long, global = true, value_name = "FILE")]
+    record: Option<PathBuf>,
+
+    /// Open a REPL where an uncaught error was raised instead of exiting
+    #[arg(long, global = true)]
+    post_mortem: bool,
+
+    /// Run the .metorexrc files before a script, as the REPL does
+    #[arg(long, global = true, conflicts_with = "no_rc")]
+    rc: bool,
+
+    /// Start the REPL without running the .metorexrc files
+    #[arg(long, global = true)]
+    no_rc: bool,
+
+    /// Show error messages from a catalog file of `CODE = message` lines,
+    /// or `en` for the built-in English
+    #[arg(long, global = true, value_name = "LOCALE")]
+    locale: Option<String>,
+}
+
+/// What to run: a script or a program given on the command line
+#[derive(Debug, Args)]
+struct RunArgs {
+    /// Run CODE as the program instead of a file
+    #[arg(short = 'e', value_name = "CODE", conflicts_with = "file")]
+    eval: Option<String>,
+
+    /// The script to run
+    file: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run a script, or the program given with -e
+    Run(RunArgs),
+    /// Start the interactive REPL
+    Repl,
+    /// Normalize whitespace in source files
+    Fmt {
+        /// Report files that need formatting instead of rewriting them
+        #[arg(long)]
+        check: bool,
+        /// The files to format
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Report type errors without running a script
+    #[command(alias = "typecheck")]
+    Check {
+        /// The script to check
+        file: PathBuf,
+    },
+    /// Check source files for likely mistakes, configured by .metorex-lint.toml
+    Lint {
+        /// Files, or directories searched for *.mx files
+        #[arg(default_value = ".")]
+        paths: Vec<PathBuf>,
+        /// The output format: text or json
+        #[arg(long, default_value_t = LintFormat::Text)]
+        format: LintFormat,
+        /// The configuration file to use instead of ./.metorex-lint.toml
+        #[arg(long)]
+        config: Option<PathBuf>,
+    },
+    /// Run test files, each in a fresh VM, and report the ones that fail
+    Test {
+        /// Test files, or directories searched for *_test.mx and test_*.mx
+        #[arg(default_value = ".")]
+        paths: Vec<PathBuf>,
+    },
+    /// Run example programs and compare what they do with their .golden files
+    Examples {
+        /// Example files, or directories searched for *.mx files
+        #[arg(default_value = "examples")]
+        paths: Vec<PathBuf>,
+        /// Write the .golden files from this run instead of comparing
+        #[arg(long)]
+        bless: bool,
+    },
+    /// Print the syntax tree of a script
+    Ast {
+        /// The script to parse
+        file: PathBuf,
+    },
+    /// Print the tokens of a script, one per line
+    Tokens {
+        /// The script to tokenize
+        file: PathBuf,
+    },
+    /// Print a graph of the classes a script defines and the files it requires
+    Graph {
+        /// The script to start from
+        file: PathBuf,
+        /// The output format
+        #[arg(long, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat,
+    },
+    /// Describe the builtin classes, methods and functions
+    Doc {
+        /// Only describe this class or function
+        name: Option<String>,
+        /// Print the whole catalog as JSON
+        #[arg(long, conflicts_with = "name")]
+        json: bool,
+    },
+    /// Re-run a script whenever it or a file it requires changes
+    Watch {
+        /// The script to run
+        file: PathBuf,
+    },
+    /// Run a recorded program again and compare what it does
+    Replay {
+        /// The event log written by --record
+        log: PathBuf,
+    },
+    /// List the builtin catalog (the same as doc)
+    #[command(hide = true, override_usage = "metorex builtins [--json]")]
+    Builtins {
+        /// Print the catalog as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Print an error and exit with the usage error status
+fn usage_error(message: &str) -> ! {
+    eprintln!("Error: {}", message);
+    process::exit(EXIT_USAGE);
+}
+
+/// Switch error messages to the catalog `--locale` names
+fn set_locale(locale: &str) -> Result<(), String> {
+    let catalog = if locale == "en" {
+        MessageCatalog::english()
+    } else {
+        MessageCatalog::load(Path::new(locale))?
+    };
+    messages::set_catalog(catalog);
+    Ok(())
+}
+
+/// The VM options from `MX_OPTS` with the command-line flags applied on top
+fn vm_options(global: &GlobalArgs) -> Result<VmOptions, String> {
+    let mut options = VmOptions::from_env()?;
+    for pair in &global.vm_options {
+        options.apply(pair)?;
+    }
+    options.check_types |= global.check_types;
+    options.contracts &= !global.no_contracts;
+    options.warnings |= global.warnings;
+    options.load_path.extend(global.include.iter().cloned());
+    Ok(options)
+}
+
+/// Require the `-r` libraries into `vm`, exiting if one fails to load
+fn require_libraries(vm: &mut VirtualMachine, libraries: &[String], errors: Palette) {
+    for library in libraries {
+        if let Err(err) = vm.require_library(library) {
+            eprintln!("{}", errors.diagnostic("error", &err, None));
+            process::exit(EXIT_FAILURE);
+        }
+    }
+}
+
+/// The .metorexrc files to run: the one in the home directory, then the one
+/// in the current directory
+fn rc_file_paths() -> Vec<PathBuf> {
+    let project_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    rc_file::rc_file_paths(rc_file::home_dir().as_deref(), &project_dir)
+}
+
+/// Read a source file, exiting if it cannot be read
+fn read_source(path: &Path) -> String {
+    match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) => {
+            eprintln!("Error reading file '{}': {}", path.display(), err);
+            process::exit(EXIT_FAILURE);
+        }
+    }
+}
+
+/// Parse `source`, reporting every parse error and exiting if there are any
+fn parse_or_exit(source: &str, errors: Palette) -> Vec<Statement> {
+    let mut parser = Parser::new(Lexer::new(source).tokenize());
+    match parser.parse() {
+        Ok(program) => program,
+        Err(parse_errors) => {
+            eprintln!("Parse error(s):");
+            for err in parse_errors {
+                eprintln!("  {}", errors.diagnostic("error", &err, Some(source)));
             }
+            process::exit(EXIT_FAILURE);
+        }
+    }
+}
+
+/// Run a script, or the program given with `-e` when `path` is `None`
+fn run_program(
+    path: Option<&Path>,
+    code: Option<&str>,
+    global: &GlobalArgs,
+    options: VmOptions,
+) -> ! {
+    let errors = Palette::for_stderr(global.color);
+
+    // Convert the filename to an absolute path
+    let absolute_path = path.map(
+        |path| match fs::canonicalize(path).map(strip_verbatim_prefix) {
+            Ok(path) => path,
             Err(err) => {
-                eprintln!("Failed to initialize REPL: {}", err);
-                process::exit(1);
+                eprintln!("Error resolving file path '{}': {}", path.display(), err);
+                eprintln!("Run `metorex --help` for the available commands");
+                process::exit(EXIT_FAILURE);
             }
+        },
+    );
+    let source = match (&absolute_path, code) {
+        (Some(path), _) => read_source(path),
+        (None, Some(code)) => code.to_string(),
+        (None, None) => usage_error("nothing to run; give a file or -e CODE"),
+    };
+    if global.record.is_some() && absolute_path.is_none() {
+        usage_error("--record only applies when running a script");
+    }
+
+    let (tokens, lexer_warnings) = Lexer::new(&source).tokenize_with_warnings();
+    let mut parser = Parser::new(tokens);
+    let program = match parser.parse() {
+        Ok(program) => program,
+        Err(parse_errors) => {
+            eprintln!("Parse error(s):");
+            for err in parse_errors {
+                eprintln!("  {}", errors.diagnostic("error", &err, Some(&source)));
+            }
+            process::exit(EXIT_FAILURE);
+        }
+    };
+
+    // Report static analysis warnings when requested
+    if options.warnings {
+        let resolved = Resolver::with_strict_mode(false).resolve(&program);
+        for warning in lexer_warnings.into_iter().chain(resolved.warnings) {
+            eprintln!("{}: {}", errors.paint(Style::Symbol, "warning"), warning);
         }
-        return;
     }
 
-    // File execution mode
-    let filename = &args[1];
+    let mut vm = VirtualMachine::with_options(options);
+
+    // Set the current file path, record it as the main program and mark it as loaded
+    if let Some(path) = &absolute_path {
+        vm.set_current_file(path.clone());
+        vm.set_main_file(path.clone());
+        vm.mark_file_loaded(path.clone());
+    }
+    vm.set_current_source(Some(&source));
+    require_libraries(&mut vm, &global.require, errors);
+    if global.rc {
+        for path in rc_file_paths() {
+            if let Err(err) = rc_file::load_rc_file(&mut vm, &path) {
+                eprintln!("{}", err.report(errors));
+                process::exit(EXIT_FAILURE);
+            }
+        }
+    }
+    if let Some(path) = absolute_path.as_ref().filter(|_| global.record.is_some()) {
+        vm.start_recording(path.clone(), &source);
+    }
+
+    vm.set_post_mortem(global.post_mortem);
+
+    // Shut the VM down before exiting, which skips destructors, unless a
+    // post-mortem session is about to inspect it
+    let result = vm.execute_program(&program);
+    let log = vm.finish_recording(&result);
+    let post_mortem = match &result {
+        Err(err) => vm
+            .take_post_mortem(err)
+            .map(|post_mortem| (vm, post_mortem)),
+        Ok(_) => {
+            drop(vm);
+            None
+        }
+    };
+
+    if let (Some(log), Some(record_path)) = (log, &global.record)
+        && let Err(err) = log.save(record_path)
+    {
+        eprintln!("Error: {}", err);
+    }
 
-    // Convert filename to absolute path
-    let absolute_path = match fs::canonicalize(filename) {
-        Ok(path) => path,
+    if let Err(err) = result {
+        eprintln!(
+            "{}",
+            errors.diagnostic("Runtime error", &err, Some(&source))
+        );
+        if let Some((vm, post_mortem)) = post_mortem {
+            run_post_mortem(vm, post_mortem, global.color);
+        }
+        process::exit(EXIT_FAILURE);
+    }
+    process::exit(0);
+}
+
+/// Open a REPL in the frame that raised a program's uncaught error
+fn run_post_mortem(vm: VirtualMachine, post_mortem: PostMortem, color: ColorChoice) {
+    match Repl::for_post_mortem(color, vm, post_mortem) {
+        Ok(mut repl) => {
+            if let Err(err) = repl.run() {
+                eprintln!("REPL error: {}", err);
+            }
+        }
+        Err(err) => eprintln!("Failed to initialize REPL: {}", err),
+    }
+}
+
+/// Start the REPL with the `-r` libraries loaded, then the .metorexrc files
+/// run unless `--no-rc` was given. A failing rc file is reported and the
+/// REPL starts anyway.
+fn run_repl(global: &GlobalArgs, options: VmOptions) -> ! {
+    let mut repl = match Repl::with_options(global.color, options) {
+        Ok(repl) => repl,
         Err(err) => {
-            eprintln!("Error resolving file path '{}': {}", filename, err);
-            process::exit(1);
+            eprintln!("Failed to initialize REPL: {}", err);
+            process::exit(EXIT_FAILURE);
         }
     };
+    for library in &global.require {
+        if let Err(err) = repl.require(library) {
+            let errors = Palette::for_stderr(global.color);
+            eprintln!("{}", errors.diagnostic("error", &err, None));
+            process::exit(EXIT_FAILURE);
+        }
+    }
+    if !global.no_rc {
+        let errors = Palette::for_stderr(global.color);
+        for err in repl.load_rc_files(rc_file_paths()) {
+            eprintln!("{}", err.report(errors));
+        }
+    }
+    if let Err(err) = repl.run() {
+        eprintln!("REPL error: {}", err);
+        process::exit(EXIT_FAILURE);
+    }
+    process::exit(0);
+}
 
-    // Read the source file
-    let source = match fs::read_to_string(&absolute_path) {
-        Ok(content) => content,
+/// Run the program recorded in `log_path` again and report whether it did
+/// the same things, exiting with status 1 if it diverged
+fn run_replay(log_path: &Path, options: VmOptions, errors: Palette) -> ! {
+    let log = match EventLog::load(log_path) {
+        Ok(log) => log,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(EXIT_FAILURE);
+        }
+    };
+    let replay = match log.replay(options) {
+        Ok(replay) => replay,
         Err(err) => {
-            eprintln!("Error reading file '{}': {}", absolute_path.display(), err);
-            process::exit(1);
+            eprintln!("{}", errors.diagnostic("error", &err, Some(&log.source)));
+            process::exit(EXIT_FAILURE);
         }
     };
 
-    // Tokenize
-    let lexer = Lexer::new(&source);
-    let tokens = lexer.tokenize();
+    if let Err(err) = &replay.result {
+        eprintln!(
+            "{}",
+            errors.diagnostic("Runtime error", err, Some(&log.source))
+        );
+    }
+    match &replay.divergence {
+        None => {
+            eprintln!(
+                "replay: all {} events of {} matched",
+                log.events.len(),
+                log.path.display()
+            );
+            process::exit(0);
+        }
+        Some(divergence) => {
+            eprintln!("replay: diverged at {}", divergence);
+            process::exit(EXIT_FAILURE);
+        }
+    }
+}
 
-    // Parse
-    let mut parser = Parser::new(tokens);
-    let program = match parser.parse() {
-        Ok(prog) => prog,
-        Err(errors) => {
-            eprintln!("Parse error(s):");
-            for err in errors {
-                eprintln!("  {}", err);
+/// Parse `path` and report type errors, exiting with status 1 if any are found
+fn run_typecheck(path: &Path, errors: Palette) -> ! {
+    let program = parse_or_exit(&read_source(path), errors);
+
+    let diagnostics = typecheck::check(&program);
+    if diagnostics.is_empty() {
+        println!("{}: no type errors found", path.display());
+        process::exit(0);
+    }
+    for diagnostic in &diagnostics {
+        eprintln!(
+            "{}:{}:{}: {}: {}",
+            path.display(),
+            diagnostic.position.line,
+            diagnostic.position.column,
+            errors.paint(Style::Symbol, "type error"),
+            diagnostic.message
+        );
+    }
+    process::exit(EXIT_FAILURE);
+}
+
+/// Rewrite `files` with normalized whitespace, or with `check` only list
+/// the ones that need it, exiting with status 1 if any do
+fn run_fmt(files: &[PathBuf], check: bool) -> ! {
+    let mut unformatted = 0;
+    for path in files {
+        let source = read_source(path);
+        let formatted = source_format::format_source(&source);
+        if formatted == source {
+            continue;
+        }
+        if check {
+            println!("{}: needs formatting", path.display());
+            unformatted += 1;
+        } else if let Err(err) = fs::write(path, formatted) {
+            eprintln!("Error writing file '{}': {}", path.display(), err);
+            process::exit(EXIT_FAILURE);
+        } else {
+            println!("{}: formatted", path.display());
+        }
+    }
+    process::exit(if unformatted > 0 { EXIT_FAILURE } else { 0 });
+}
+
+/// `count` followed by `noun`, in the plural unless `count` is 1
+fn counted(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}", count, pluralize(noun))
+    }
+}
+
+/// Lint the source files under `paths`, exiting with status 1 if any has a
+/// parse error or an offense at the error level
+fn run_lint(paths: &[PathBuf], format: LintFormat, config: Option<&Path>, palette: Palette) -> ! {
+    let default_config = Path::new(lint::CONFIG_FILE_NAME);
+    let config = match config {
+        Some(path) => LintConfig::load(path),
+        None if default_config.is_file() => LintConfig::load(default_config),
+        None => Ok(LintConfig::default()),
+    };
+    let mut linter = Linter::new();
+    let configured = linter
+        .add_script_rules(Path::new(lint::RULES_DIR))
+        .and(config)
+        .and_then(|config| linter.configure(&config));
+    if let Err(err) = configured {
+        usage_error(&err);
+    }
+
+    let mut files = Vec::new();
+    for path in paths {
+        collect_files(path, is_source_file, &mut files);
+    }
+
+    let mut reports = Vec::new();
+    for file in &files {
+        let diagnostics = match linter.lint_source(&read_source(file)) {
+            Ok(diagnostics) => diagnostics,
+            Err(errors) => errors.iter().map(Diagnostic::from_parse_error).collect(),
+        };
+        reports.extend(diagnostics.into_iter().map(|diagnostic| (file, diagnostic)));
+    }
+
+    match format {
+        LintFormat::Text => {
+            for (file, diagnostic) in &reports {
+                let style = match diagnostic.level {
+                    Level::Error => Style::Error,
+                    _ => Style::Symbol,
+                };
+                println!(
+                    "{}:{}:{}: {}: {} [{}]",
+                    file.display(),
+                    diagnostic.line,
+                    diagnostic.column,
+                    palette.paint(style, &diagnostic.level.to_string()),
+                    diagnostic.message,
+                    diagnostic.rule
+                );
             }
-            process::exit(1);
+            println!(
+                "{} checked, {}",
+                counted(files.len(), "file"),
+                counted(reports.len(), "offense")
+            );
+        }
+        LintFormat::Json => {
+            let entries: Vec<serde_json::Value> = reports
+                .iter()
+                .map(|(file, diagnostic)| {
+                    let mut entry = serde_json::to_value(diagnostic).unwrap_or_default();
+                    entry["file"] = serde_json::Value::from(file.display().to_string());
+                    entry
+                })
+                .collect();
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&entries).unwrap_or_default()
+            );
         }
+    }
+    let failed = reports
+        .iter()
+        .any(|(_, diagnostic)| diagnostic.level == Level::Error);
+    process::exit(if failed { EXIT_FAILURE } else { 0 });
+}
+
+/// Whether `path` names a test file: `*_test.mx` or `test_*.mx`
+fn is_test_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
     };
+    name.strip_suffix(".mx")
+        .is_some_and(|stem| stem.ends_with("_test") || stem.starts_with("test_"))
+}
+
+/// Whether `path` names a Metorex source file
+fn is_source_file(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "mx")
+}
+
+/// `path` if it is a file, or the files under it that `wanted` accepts,
+/// sorted, skipping hidden and `target` directories
+fn collect_files(path: &Path, wanted: fn(&Path) -> bool, files: &mut Vec<PathBuf>) {
+    if !path.is_dir() {
+        files.push(path.to_path_buf());
+        return;
+    }
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+    // Report `./tests/a_test.mx` found under the default `.` as `tests/a_test.mx`
+    let mut entries: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| {
+            let path = entry.path();
+            path.strip_prefix(".")
+                .map(Path::to_path_buf)
+                .unwrap_or(path)
+        })
+        .collect();
+    entries.sort();
+    for entry in entries {
+        let name = entry
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("");
+        if entry.is_dir() {
+            if !name.starts_with('.') && name != "target" {
+                collect_files(&entry, wanted, files);
+            }
+        } else if wanted(&entry) {
+            files.push(entry);
+        }
+    }
+}
+
+/// Run each test file in a fresh VM, reporting which failed and exiting with
+/// status 1 if any did or none were found
+fn run_tests(paths: &[PathBuf], global: &GlobalArgs, options: VmOptions) -> ! {
+    let palette = Palette::for_stdout(global.color);
+    let mut files = Vec::new();
+    for path in paths {
+        collect_files(path, is_test_file, &mut files);
+    }
+    if files.is_empty() {
+        eprintln!("No test files found (looked for *_test.mx and test_*.mx)");
+        process::exit(EXIT_FAILURE);
+    }
+
+    let mut failed = 0;
+    for file in &files {
+        let mut vm = VirtualMachine::with_options(options.clone());
+        let start = Instant::now();
+        let result = global
+            .require
+            .iter()
+            .try_for_each(|library| vm.require_library(library).map(|_| ()))
+            .and_then(|()| vm.execute_file(file));
+        let elapsed = start.elapsed();
+        let assertions = vm.assertion_count();
+        drop(vm);
+
+        match result {
+            Ok(_) => println!(
+                "{} {} ({} assertions, {:.2?})",
+                palette.paint(Style::String, "ok  "),
+                file.display(),
+                assertions,
+                elapsed
+            ),
+            Err(err) => {
+                failed += 1;
+                println!("{} {}", palette.paint(Style::Error, "FAIL"), file.display());
+                println!("     {}", err);
+            }
+        }
+    }
+
+    println!(
+        "\n{} files, {} passed, {} failed",
+        files.len(),
+        files.len() - failed,
+        failed
+    );
+    process::exit(if failed > 0 { EXIT_FAILURE } else { 0 });
+}
+
+/// Run each example in a fresh VM and compare it with its golden file, or
+/// with `bless` write the golden files, exiting with status 1 if any example
+/// differed or had no golden file, or none were found
+fn run_examples(paths: &[PathBuf], bless: bool, global: &GlobalArgs, options: VmOptions) -> ! {
+    let palette = Palette::for_stdout(global.color);
+    let mut files = Vec::new();
+    for path in paths {
+        collect_files(path, is_source_file, &mut files);
+    }
+    if files.is_empty() {
+        eprintln!("No examples found (looked for *.mx)");
+        process::exit(EXIT_FAILURE);
+    }
+
+    let (mut failed, mut blessed) = (0, 0);
+    for file in &files {
+        match examples::check_example(file, options.clone(), bless) {
+            Ok(ExampleOutcome::Passed) => {
+                println!(
+                    "{} {}",
+                    palette.paint(Style::String, "ok   "),
+                    file.display()
+                )
+            }
+            Ok(ExampleOutcome::Blessed) => {
+                blessed += 1;
+                println!(
+                    "{} {}",
+                    palette.paint(Style::Symbol, "bless"),
+                    file.display()
+                );
+            }
+            Ok(ExampleOutcome::Missing) => {
+                failed += 1;
+                println!(
+                    "{} {}",
+                    palette.paint(Style::Error, "NEW  "),
+                    file.display()
+                );
+                println!(
+                    "      no {} to compare with; run with --bless to write it",
+                    examples::golden_path(file).display()
+                );
+            }
+            Ok(ExampleOutcome::Failed(difference)) => {
+                failed += 1;
+                println!(
+                    "{} {}",
+                    palette.paint(Style::Error, "FAIL "),
+                    file.display()
+                );
+                println!("      {}", difference);
+            }
+            Err(err) => {
+                failed += 1;
+                println!(
+                    "{} {}",
+                    palette.paint(Style::Error, "FAIL "),
+                    file.display()
+                );
+                println!(
+                    "      cannot use {}: {}",
+                    examples::golden_path(file).display(),
+                    err
+                );
+            }
+        }
+    }
+
+    let passed = files.len() - failed - blessed;
+    if bless {
+        println!(
+            "\n{} examples, {} passed, {} blessed, {} failed",
+            files.len(),
+            passed,
+            blessed,
+            failed
+        );
+    } else {
+        println!(
+            "\n{} examples, {} passed, {} failed",
+            files.len(),
+            passed,
+            failed
+        );
+    }
+    process::exit(if failed > 0 { EXIT_FAILURE } else { 0 });
+}
 
-    // Execute
-    let mut vm = VirtualMachine::new();
+/// Print the syntax tree of `path`
+fn print_ast(path: &Path, errors: Palette) {
+    for statement in parse_or_exit(&read_source(path), errors) {
+        println!("{:#?}", statement);
+    }
+}
 
-    // Set the current file path and mark it as loaded
-    vm.set_current_file(absolute_path.clone());
-    vm.mark_file_loaded(absolute_path);
+/// Print the tokens of `path` as `line:column  token`
+fn print_tokens(path: &Path) {
+    let source = read_source(path);
+    for token in Lexer::new(&source).tokenize() {
+        let position = format!("{}:{}", token.position.line, token.position.column);
+        println!("{:<8}{}", position, token.kind);
+    }
+}
+
+/// Print the class hierarchy and require graph of a script and its requires
+fn print_graph(path: &Path, format: GraphFormat, load_path: &[PathBuf]) {
+    match ProgramGraph::build(path, load_path) {
+        Ok(graph) => print!("{}", graph.render(format)),
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            process::exit(EXIT_FAILURE);
+        }
+    }
+}
+
+/// Print a class with its superclass and native methods
+fn print_class_doc(class: &BuiltinClass) {
+    match class.superclass {
+        Some(superclass) => println!("{} < {}  {}", class.name, superclass, class.doc),
+        None => println!("{}  {}", class.name, class.doc),
+    }
+    for method in builtin_catalog::methods_of(class.name) {
+        let marker = match method.kind {
+            MethodKind::Instance => '#',
+            MethodKind::Class => '.',
+        };
+        println!(
+            "  {}{}({}) -> {}  {}",
+            marker,
+            method.name,
+            builtin_catalog::signature(method.parameters),
+            method.returns,
+            method.doc
+        );
+    }
+}
 
-    if let Err(err) = vm.execute_program(&program) {
-        eprintln!("Runtime error: {}", err);
-        process::exit(1);
+/// Print a native function with its signature
+fn print_function_doc(function: &BuiltinFunction) {
+    println!(
+        "  {}({}) -> {}  {}",
+        function.name,
+        builtin_catalog::signature(function.parameters),
+        function.returns,
+        function.doc
+    );
+}
+
+/// Print the builtin catalog as JSON, or as one line per class, method and function
+fn print_builtins(json: bool) {
+    if json {
+        println!("{}", builtin_catalog::to_json());
+        return;
+    }
+    for class in builtin_catalog::CLASSES {
+        print_class_doc(class);
+    }
+    println!("functions");
+    for function in builtin_catalog::FUNCTIONS {
+        print_function_doc(function);
+    }
+}
+
+/// Print the documentation of the builtin class or function `name`
+fn print_doc(name: &str) {
+    if let Some(class) = builtin_catalog::find_class(name) {
+        print_class_doc(class);
+    } else if let Some(function) = builtin_catalog::find_function(name) {
+        print_function_doc(function);
+    } else {
+        eprintln!("No builtin class or function named '{}'", name);
+        process::exit(EXIT_FAILURE);
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let global = &cli.global;
+    let options = vm_options(global).unwrap_or_else(|err| usage_error(&err));
+    if let Some(locale) = &global.locale {
+        set_locale(locale).unwrap_or_else(|err| usage_error(&err));
+    }
+
+    if cli.command.is_some() && (cli.repl || cli.run.file.is_some() || cli.run.eval.is_some()) {
+        usage_error("a script or -e cannot be combined with a command");
+    }
+
+    let runs_a_script = matches!(cli.command, None | Some(Command::Run(_)));
+    if global.record.is_some() && (!runs_a_script || cli.repl) {
+        usage_error("--record only applies when running a script");
+    }
+    if global.post_mortem && (!runs_a_script || cli.repl) {
+        usage_error("--post-mortem only applies when running a script");
+    }
+    let starts_repl = matches!(cli.command, Some(Command::Repl));
+    if (global.rc || global.no_rc) && !runs_a_script && !starts_repl {
+        usage_error("--rc and --no-rc only apply when running a script or the REPL");
+    }
+
+    match &cli.command {
+        None if cli.repl || (cli.run.file.is_none() && cli.run.eval.is_none()) => {
+            run_repl(global, options)
+        }
+        None => run_program(
+            cli.run.file.as_deref(),
+            cli.run.eval.as_deref(),
+            global,
+            options,
+        ),
+        Some(Command::Run(run)) => {
+            run_program(run.file.as_deref(), run.eval.as_deref(), global, options)
+        }
+        Some(Command::Repl) => run_repl(global, options),
+        Some(Command::Fmt { check, files }) => run_fmt(files, *check),
+        Some(Command::Check { file }) => run_typecheck(file, Palette::for_stderr(global.color)),
+        Some(Command::Lint {
+            paths,
+            format,
+            config,
+        }) => run_lint(
+            paths,
+            *format,
+            config.as_deref(),
+            Palette::for_stdout(global.color),
+        ),
+        Some(Command::Test { paths }) => run_tests(paths, global, options),
+        Some(Command::Examples { paths, bless }) => run_examples(paths, *bless, global, options),
+        Some(Command::Ast { file }) => print_ast(file, Palette::for_stderr(global.color)),
+        Some(Command::Tokens { file }) => print_tokens(file),
+        Some(Command::Graph { file, format }) => print_graph(file, *format, &options.load_path),
+        Some(Command::Doc {
+            name: Some(name), ..
+        }) => print_doc(name),
+        Some(Command::Doc { name: None, json }) | Some(Command::Builtins { json }) => {
+            print_builtins(*json)
+        }
+        Some(Command::Watch { file }) => {
+            watch::watch(file, Palette::for_stdout(global.color), &options)
+        }
+        Some(Command::Replay { log }) => {
+            run_replay(log, options, Palette::for_stderr(global.color))
+        }
     }
 }
@@ -0,0 +1,755 @@
+// Message catalog for user-facing errors
+// Parse errors, and the errors the VM core raises for control flow,
+// variables, calls, operators, collections, class definitions and file
+// loading, are built from a `Message`: a stable code such as `E0301` and an
+// English template with `{name}` placeholders. A `MessageCatalog` can replace
+// the template for any code, so a product embedding Metorex can translate or
+// re-brand errors, and match on `MetorexError::code()` instead of on English
+// text. Errors raised by builtin methods and functions, and by class-body
+// directives such as `include` and `delegate`, use their own text and carry
+// no code.
+//
+// Codes are grouped by where the error comes from:
+//   E01xx control flow, E02xx variables, E03xx methods and calls,
+//   E04xx operators, E05xx collections, E06xx classes, E07xx files,
+//   E10xx parsing, E11xx name resolution
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::RwLock;
+
+/// A user-facing message: its code and English template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Message {
+    /// Stable identifier, the same in every locale
+    pub code: &'static str,
+    /// English text, with `{name}` where an argument goes
+    pub template: &'static str,
+}
+
+impl Message {
+    /// Create a message from its code and English template
+    pub const fn new(code: &'static str, template: &'static str) -> Self {
+        Self { code, template }
+    }
+
+    /// The message text in the active catalog, for a message that takes no
+    /// arguments
+    pub fn text(&self) -> String {
+        self.format(&[])
+    }
+
+    /// The message text in the active catalog, with each `{name}` replaced
+    /// by the argument of that name
+    pub fn format(&self, args: &[(&str, &dyn fmt::Display)]) -> String {
+        let active = ACTIVE_CATALOG.read().unwrap_or_else(|e| e.into_inner());
+        let template = match active.as_ref() {
+            Some(catalog) => catalog.template(self),
+            None => self.template,
+        };
+        interpolate(template, args)
+    }
+
+    /// The names of the placeholders in the English template
+    pub fn placeholders(&self) -> Vec<&'static str> {
+        placeholders(self.template)
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code, self.template)
+    }
+}
+
+/// Replace each `{name}` in `template` with the argument of that name.
+/// Braces around anything else, as in "Expected '{' to start block", are
+/// kept as they are.
+pub fn interpolate(template: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let argument = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            let value = args.iter().find(|(arg, _)| *arg == name)?.1;
+            Some((end, value))
+        });
+        match argument {
+            Some((end, value)) => {
+                out.push_str(&value.to_string());
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The `{name}` placeholders in `template`, in order of first use
+fn placeholders(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        if let Some(end) = rest.find('}') {
+            let name = &rest[..end];
+            let is_name =
+                !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c == '_');
+            if is_name && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// Replacement templates for some or all messages, keyed by code
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageCatalog {
+    locale: String,
+    templates: HashMap<&'static str, String>,
+}
+
+impl MessageCatalog {
+    /// The built-in English messages
+    pub fn english() -> Self {
+        Self::new("en")
+    }
+
+    /// An empty catalog for `locale`; messages it does not replace stay in
+    /// English
+    pub fn new(locale: impl Into<String>) -> Self {
+        Self {
+            locale: locale.into(),
+            templates: HashMap::new(),
+        }
+    }
+
+    /// The locale the catalog was created for
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// The template this catalog uses for `message`
+    pub fn template(&self, message: &Message) -> &str {
+        self.templates
+            .get(message.code)
+            .map_or(message.template, String::as_str)
+    }
+
+    /// Replace the template for `code`. The new template may only use the
+    /// placeholders of the English one.
+    pub fn set(&mut self, code: &str, template: impl Into<String>) -> Result<(), String> {
+        let message = lookup(code).ok_or_else(|| format!("unknown message code '{}'", code))?;
+        let template = template.into();
+        let known = message.placeholders();
+        if let Some(unknown) = placeholders(&template)
+            .into_iter()
+            .find(|name| !known.contains(name))
+        {
+            return Err(format!(
+                "{} has no '{{{}}}' placeholder (it has: {})",
+                code,
+                unknown,
+                if known.is_empty() {
+                    "none".to_string()
+                } else {
+                    known.join(", ")
+                }
+            ));
+        }
+        self.templates.insert(message.code, template);
+        Ok(())
+    }
+
+    /// Parse a catalog file: one `CODE = template` per line, with blank
+    /// lines and lines starting with `#` ignored
+    pub fn parse(locale: impl Into<String>, text: &str) -> Result<Self, String> {
+        let mut catalog = Self::new(locale);
+        let mut seen = HashSet::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fail = |reason: String| format!("line {}: {}", index + 1, reason);
+            let (code, template) = line
+                .split_once('=')
+                .ok_or_else(|| fail("expected CODE = message".to_string()))?;
+            let code = code.trim();
+            if !seen.insert(code.to_string()) {
+                return Err(fail(format!("{} is given more than once", code)));
+            }
+            catalog.set(code, template.trim()).map_err(fail)?;
+        }
+        Ok(catalog)
+    }
+
+    /// Load a catalog file, named for its locale, e.g. `es.txt`
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("cannot read message catalog '{}': {}", path.display(), e))?;
+        let locale = path
+            .file_stem()
+            .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+        Self::parse(locale, &text)
+            .map_err(|e| format!("message catalog '{}': {}", path.display(), e))
+    }
+}
+
+impl Default for MessageCatalog {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+/// The catalog messages are formatted with; `None` is plain English
+static ACTIVE_CATALOG: RwLock<Option<MessageCatalog>> = RwLock::new(None);
+
+/// Format messages with `catalog` from now on, in every thread
+pub fn set_catalog(catalog: MessageCatalog) {
+    *ACTIVE_CATALOG.write().unwrap_or_else(|e| e.into_inner()) = Some(catalog);
+}
+
+/// The locale of the active catalog
+pub fn locale() -> String {
+    ACTIVE_CATALOG
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .map_or_else(|| "en".to_string(), |catalog| catalog.locale.clone())
+}
+
+/// The message with `code`, if there is one
+pub fn lookup(code: &str) -> Option<Message> {
+    MESSAGES
+        .iter()
+        .find(|message| message.code == code)
+        .copied()
+}
+
+/// Whether a parse error with `code` means the input stopped before a
+/// construct was closed, so more lines could complete it
+pub fn is_unfinished_input(code: &str) -> bool {
+    UNFINISHED_INPUT.iter().any(|message| message.code == code)
+}
+
+// ============================================================================
+// Control Flow (E01xx)
+// ============================================================================
+
+pub const LOOP_CONTROL: Message =
+    Message::new("E0101", "{keyword} cannot be used outside of a loop");
+pub const BEGIN_EXPRESSION_JUMP: Message = Message::new(
+    "E0102",
+    "{keyword} cannot leave a begin block used as a value",
+);
+pub const STACK_TOO_DEEP: Message = Message::new(
+    "E0103",
+    "stack level too deep (more than {limit} nested calls)",
+);
+pub const RETRY_OUTSIDE_RESCUE: Message =
+    Message::new("E0104", "retry can only be used inside a rescue clause");
+pub const INVALID_TIMEOUT: Message = Message::new(
+    "E0105",
+    "{method}: seconds must be a non-negative number or nil, got {found}",
+);
+
+// ============================================================================
+// Variables (E02xx)
+// ============================================================================
+
+pub const INVALID_ASSIGNMENT_TARGET: Message = Message::new("E0201", "Invalid assignment target");
+pub const UNDEFINED_VARIABLE: Message = Message::new("E0202", "Undefined variable '{name}'");
+pub const UNINITIALIZED_CLASS_VARIABLE: Message =
+    Message::new("E0203", "uninitialized class variable @@{name} in {class}");
+pub const UNDEFINED_SELF: Message = Message::new("E0204", "Undefined self in current context");
+pub const READ_INSTANCE_VARIABLE_ON_NON_INSTANCE: Message = Message::new(
+    "E0205",
+    "Cannot read instance variable @{name} on non-instance",
+);
+pub const SET_INSTANCE_VARIABLE_ON_NON_INSTANCE: Message = Message::new(
+    "E0206",
+    "Cannot set instance variable @{name} on non-instance",
+);
+pub const INSTANCE_VARIABLE_OUTSIDE_METHOD: Message = Message::new(
+    "E0207",
+    "Instance variable @{name} can only be used within a method",
+);
+pub const READ_CLASS_VARIABLE_OUT_OF_CONTEXT: Message = Message::new(
+    "E0208",
+    "Cannot read class variable @@{name} in this context",
+);
+pub const SET_CLASS_VARIABLE_OUT_OF_CONTEXT: Message = Message::new(
+    "E0209",
+    "Cannot set class variable @@{name} in this context",
+);
+pub const CLASS_VARIABLE_OUTSIDE_CLASS: Message = Message::new(
+    "E0210",
+    "Class variable @@{name} can only be used within a class or method",
+);
+pub const METHOD_CALL_ASSIGNMENT: Message =
+    Message::new("E0211", "Cannot assign to method call with arguments");
+
+// ============================================================================
+// Methods and Calls (E03xx)
+// ============================================================================
+
+pub const UNDEFINED_METHOD: Message =
+    Message::new("E0301", "Undefined method '{method}' for type '{type}'");
+pub const PRIVATE_METHOD: Message = Message::new(
+    "E0302",
+    "Private method '{method}' called for type '{type}'",
+);
+pub const METHOD_ARGUMENT_COUNT: Message = Message::new(
+    "E0303",
+    "Method '{method}' expected {expected} argument(s) but received {found}",
+);
+pub const BUILTIN_ARGUMENT_COUNT: Message = Message::new(
+    "E0304",
+    "{name}: wrong number of arguments (given {given}, expected {expected})",
+);
+pub const BUILTIN_PARAMETER_TYPE: Message = Message::new(
+    "E0305",
+    "{name}: {parameter} must be {expected} (got {found})",
+);
+pub const UNRELEASABLE_RESOURCE: Message = Message::new(
+    "E0306",
+    "Resource of type '{type}' must respond to 'close' or 'release'",
+);
+pub const METHOD_MINIMUM_ARGUMENT_COUNT: Message = Message::new(
+    "E0307",
+    "Method '{method}' expected at least {minimum} argument(s) but received {found}",
+);
+pub const NO_FORWARDED_ARGUMENTS: Message = Message::new(
+    "E0308",
+    "No arguments to forward; '...' requires a method declared with a '...' parameter",
+);
+pub const METHOD_ARGUMENT_TYPE: Message = Message::new(
+    "E0309",
+    "Method '{method}' expected argument of type '{expected}' but found '{found}'",
+);
+pub const NOT_CALLABLE: Message = Message::new("E0310", "Object of type '{type}' is not callable");
+pub const CALLABLE_ARGUMENT_COUNT: Message = Message::new(
+    "E0311",
+    "Callable '{callable}' expected {expected} argument(s) but received {found}",
+);
+pub const FORWARDED_ARGUMENTS_OUTSIDE_CALL: Message =
+    Message::new("E0312", "'...' can only be used as a call argument");
+pub const UNDEFINED_SETTER: Message = Message::new("E0313", "Undefined setter method '{method}'");
+pub const SETTER_ON_NON_INSTANCE: Message =
+    Message::new("E0314", "Cannot call setter method '{method}' on {type}");
+pub const SUPER_OUTSIDE_INSTANCE_METHOD: Message = Message::new(
+    "E0315",
+    "super can only be called from within an instance method",
+);
+pub const SUPER_OUTSIDE_METHOD: Message =
+    Message::new("E0316", "super can only be called from within a method");
+pub const SUPER_WITHOUT_METHOD_FRAME: Message =
+    Message::new("E0317", "super called outside of a method context");
+pub const SUPER_WITHOUT_CLASS: Message = Message::new(
+    "E0318",
+    "super called in invalid context (no class information)",
+);
+pub const SUPER_DEFINING_CLASS_NOT_FOUND: Message = Message::new(
+    "E0319",
+    "Could not find defining class '{class}' in inheritance chain",
+);
+pub const SUPER_WITHOUT_SUPERCLASS: Message =
+    Message::new("E0320", "Class {class} has no superclass");
+pub const SUPER_METHOD_NOT_FOUND: Message = Message::new(
+    "E0321",
+    "Superclass {class} does not define method '{method}'",
+);
+
+// ============================================================================
+// Operators (E04xx)
+// ============================================================================
+
+pub const UNARY_OPERATOR_TYPE: Message = Message::new(
+    "E0401",
+    "Cannot apply unary operator '{operator}' to type '{type}'",
+);
+pub const BINARY_OPERATOR_TYPE: Message = Message::new(
+    "E0402",
+    "Cannot apply operator '{operator}' to types '{left}' and '{right}'",
+);
+pub const COMPARISON_FAILED: Message =
+    Message::new("E0403", "comparison of {left} with {right} failed");
+pub const DIVISION_BY_ZERO: Message = Message::new("E0404", "Division by zero");
+pub const DECIMAL_OVERFLOW: Message = Message::new("E0405", "Decimal overflow");
+
+// ============================================================================
+// Collections (E05xx)
+// ============================================================================
+
+pub const INDEX_OUT_OF_BOUNDS: Message = Message::new(
+    "E0501",
+    "Index {index} is out of bounds for array of length {length}",
+);
+pub const UNDEFINED_DICTIONARY_KEY: Message =
+    Message::new("E0502", "Key '{key}' not found in dictionary");
+pub const COLLECTION_IN_USE: Message =
+    Message::new("E0503", "can't modify {type} while it is being iterated");
+pub const NO_PATTERN_MATCHED: Message = Message::new("E0504", "No pattern matched value: {value}");
+pub const ARRAY_INDEX_NOT_INTEGER: Message =
+    Message::new("E0505", "Array index must be an Integer, found {type}");
+pub const INVALID_HASH_KEY: Message = Message::new(
+    "E0506",
+    "Hash key must be a String, Symbol, Integer, Float, Bool, or Nil, found {type}",
+);
+pub const INDEX_ASSIGNMENT_UNSUPPORTED: Message =
+    Message::new("E0507", "Cannot index assign into type '{type}'");
+pub const INDEX_TOO_FAR_PAST_END: Message = Message::new(
+    "E0508",
+    "Index {index} is more than {limit} past the end of an array of length {length}",
+);
+
+// ============================================================================
+// Classes (E06xx)
+// ============================================================================
+
+pub const SUPERCLASS_NOT_A_CLASS: Message =
+    Message::new("E0601", "Superclass '{name}' must be a class");
+pub const UNDEFINED_SUPERCLASS: Message = Message::new("E0602", "Undefined superclass '{name}'");
+pub const SEALED_SUPERCLASS: Message = Message::new(
+    "E0603",
+    "Cannot subclass sealed class '{superclass}': '{name}' is not one of its variants ({variants})",
+);
+pub const ATTRIBUTE_OUTSIDE_CLASS: Message = Message::new(
+    "E0604",
+    "attr_reader, attr_writer, and attr_accessor can only be used inside a class or module definition",
+);
+
+// ============================================================================
+// Files (E07xx)
+// ============================================================================
+
+pub const FILE_NOT_FOUND: Message = Message::new("E0701", "Failed to find file '{path}': {error}");
+pub const FILE_NOT_LOADED: Message = Message::new("E0702", "Failed to load file '{path}': {error}");
+pub const FILE_NOT_PARSED: Message =
+    Message::new("E0703", "Failed to parse file '{path}': {error}");
+pub const FILE_EXECUTION_FAILED: Message =
+    Message::new("E0704", "Error executing file '{path}': {error}");
+
+// ============================================================================
+// Parsing (E10xx)
+// ============================================================================
+
+pub const UNEXPECTED_TOKEN: Message = Message::new("E1001", "Unexpected token: {token}");
+pub const EXPECTED_METHOD_NAME_AFTER_DOT: Message =
+    Message::new("E1002", "Expected method name after '.'");
+pub const EXPECTED_NAME_AFTER_DOUBLE_COLON: Message =
+    Message::new("E1003", "Expected name after '::'");
+pub const EXPECTED_RBRACKET_AFTER_ARRAY_INDEX: Message =
+    Message::new("E1004", "Expected ']' after array index");
+pub const EXPECTED_RPAREN_AFTER_ARGUMENTS: Message =
+    Message::new("E1005", "Expected ')' after arguments");
+pub const EXPECTED_KEYWORD_ARGUMENT_NAME: Message =
+    Message::new("E1006", "Expected keyword argument name");
+pub const EXPECTED_COLON_AFTER_KEYWORD_ARGUMENT_NAME: Message =
+    Message::new("E1007", "Expected ':' after keyword argument name");
+pub const EXPECTED_FUNCTION_CALL_BUT_FOUND_DICTIONARY_LIKE_SYNTAX: Message = Message::new(
+    "E1008",
+    "Expected function call but found dictionary-like syntax",
+);
+pub const ARROW_LAMBDA_PARAMETERS_MUST_BE_IDENTIFIERS: Message =
+    Message::new("E1009", "Arrow lambda parameters must be identifiers");
+pub const LEFT_SIDE_OF_ARROW_MUST_BE_PARAMETERS: Message =
+    Message::new("E1010", "Left side of arrow must be parameter(s)");
+pub const EXPECTED_DO_TO_START_BLOCK: Message =
+    Message::new("E1011", "Expected 'do' to start block");
+pub const EXPECTED_PIPE_AFTER_BLOCK_PARAMETERS: Message =
+    Message::new("E1012", "Expected '|' after block parameters");
+pub const EXPECTED_END_TO_CLOSE_BLOCK: Message =
+    Message::new("E1013", "Expected 'end' to close block");
+pub const EXPECTED_LBRACE_TO_START_BLOCK: Message =
+    Message::new("E1014", "Expected '{' to start block");
+pub const EXPECTED_RBRACE_TO_CLOSE_BLOCK: Message =
+    Message::new("E1015", "Expected '}' to close block");
+pub const EXPECTED_PARAMETER_NAME: Message = Message::new("E1016", "Expected parameter name");
+pub const EXPECTED_RPAREN_AFTER_DESTRUCTURED_PARAMETERS: Message =
+    Message::new("E1017", "Expected ')' after destructured parameters");
+pub const EXPECTED_RPAREN_AFTER_EXPRESSION: Message =
+    Message::new("E1018", "Expected ')' after expression");
+pub const EXPECTED_RBRACKET_AFTER_ARRAY_ELEMENTS: Message =
+    Message::new("E1019", "Expected ']' after array elements");
+pub const EXPECTED_COLON_OR_ARROW_AFTER_DICTIONARY_KEY: Message =
+    Message::new("E1020", "Expected ':' or '=>' after dictionary key");
+pub const EXPECTED_RBRACE_AFTER_DICTIONARY_ENTRIES: Message =
+    Message::new("E1021", "Expected '}' after dictionary entries");
+pub const EXPECTED_PIPE_AFTER_LAMBDA_PARAMETERS: Message =
+    Message::new("E1022", "Expected '|' after lambda parameters");
+pub const EXPECTED_END_AFTER_LAMBDA_BODY: Message =
+    Message::new("E1023", "Expected 'end' after lambda body");
+pub const EXPECTED_END_AFTER_BLOCK_BODY: Message =
+    Message::new("E1024", "Expected 'end' after block body");
+pub const EXPECTED_RPAREN_AFTER_SUPER_ARGUMENTS: Message =
+    Message::new("E1025", "Expected ')' after super arguments");
+pub const EXPECTED_END_AFTER_CASE_EXPRESSION: Message =
+    Message::new("E1026", "Expected 'end' after case expression");
+pub const EXPECTED_EXPRESSION_IN_INTERPOLATION: Message =
+    Message::new("E1027", "Expected expression in interpolation");
+pub const EXPECTED_ATTR_READER: Message = Message::new("E1028", "Expected 'attr_reader'");
+pub const EXPECTED_ATTR_WRITER: Message = Message::new("E1029", "Expected 'attr_writer'");
+pub const EXPECTED_ATTR_ACCESSOR: Message = Message::new("E1030", "Expected 'attr_accessor'");
+pub const EXPECTED_ATTRIBUTE_NAME_SYMBOL: Message =
+    Message::new("E1031", "Expected attribute name symbol like :name");
+pub const EXPECTED_CLASS: Message = Message::new("E1032", "Expected 'class'");
+pub const EXPECTED_CLASS_NAME: Message = Message::new("E1033", "Expected class name");
+pub const EXPECTED_SUPERCLASS_NAME: Message = Message::new("E1034", "Expected superclass name");
+pub const EXPECTED_MODULE: Message = Message::new("E1035", "Expected 'module'");
+pub const EXPECTED_MODULE_NAME: Message = Message::new("E1036", "Expected module name");
+pub const EXPECTED_IF: Message = Message::new("E1037", "Expected 'if'");
+pub const EXPECTED_END_AFTER_IF_STATEMENT: Message =
+    Message::new("E1038", "Expected 'end' after if statement");
+pub const EXPECTED_WHILE: Message = Message::new("E1039", "Expected 'while'");
+pub const EXPECTED_END_AFTER_WHILE_LOOP: Message =
+    Message::new("E1040", "Expected 'end' after while loop");
+pub const EXPECTED_FOR: Message = Message::new("E1041", "Expected 'for'");
+pub const EXPECTED_IDENTIFIER_AFTER_FOR: Message =
+    Message::new("E1042", "Expected identifier after 'for'");
+pub const EXPECTED_IN_AFTER_LOOP_VARIABLE: Message =
+    Message::new("E1043", "Expected 'in' after loop variable");
+pub const EXPECTED_END_AFTER_FOR_LOOP: Message =
+    Message::new("E1044", "Expected 'end' after for loop");
+pub const EXPECTED_BREAK: Message = Message::new("E1045", "Expected 'break'");
+pub const EXPECTED_CONTINUE: Message = Message::new("E1046", "Expected 'continue'");
+pub const EXPECTED_UNLESS: Message = Message::new("E1047", "Expected 'unless'");
+pub const EXPECTED_END_AFTER_UNLESS_STATEMENT: Message =
+    Message::new("E1048", "Expected 'end' after unless statement");
+pub const EXPECTED_RETURN: Message = Message::new("E1049", "Expected 'return'");
+pub const EXPECTED_CASE: Message = Message::new("E1050", "Expected 'case'");
+pub const EXPECTED_END_AFTER_CASE_STATEMENT: Message =
+    Message::new("E1051", "Expected 'end' after case statement");
+pub const EXPECTED_IDENTIFIER_AFTER_ELLIPSIS_IN_ARRAY_PATTERN: Message =
+    Message::new("E1052", "Expected identifier after ... in array pattern");
+pub const EXPECTED_COMMA_OR_RBRACKET_IN_ARRAY_PATTERN: Message =
+    Message::new("E1053", "Expected ',' or ']' in array pattern");
+pub const EXPECTED_RBRACKET_AFTER_ARRAY_PATTERN: Message =
+    Message::new("E1054", "Expected ']' after array pattern");
+pub const EXPECTED_IDENTIFIER_OR_STRING_KEY_IN_OBJECT_PATTERN: Message = Message::new(
+    "E1055",
+    "Expected identifier or string key in object pattern",
+);
+pub const EXPECTED_COMMA_OR_RBRACE_IN_OBJECT_PATTERN: Message =
+    Message::new("E1056", "Expected ',' or '}' in object pattern");
+pub const EXPECTED_RBRACE_AFTER_OBJECT_PATTERN: Message =
+    Message::new("E1057", "Expected '}' after object pattern");
+pub const EXPECTED_STRING_IN_GLOB_PATTERN: Message =
+    Message::new("E1058", "Expected a string literal in glob pattern");
+pub const EXPECTED_RPAREN_AFTER_GLOB_PATTERN: Message =
+    Message::new("E1059", "Expected ')' after glob pattern");
+pub const EXPECTED_BEGIN: Message = Message::new("E1060", "Expected 'begin'");
+pub const EXPECTED_END_AFTER_BEGIN_BLOCK: Message =
+    Message::new("E1061", "Expected 'end' after begin block");
+pub const EXPECTED_VARIABLE_NAME_AFTER_ARROW: Message =
+    Message::new("E1062", "Expected variable name after '=>'");
+pub const EXPECTED_RAISE: Message = Message::new("E1063", "Expected 'raise'");
+pub const EXPECTED_RETRY: Message = Message::new("E1064", "Expected 'retry'");
+pub const EXPECTED_DEF: Message = Message::new("E1065", "Expected 'def'");
+pub const EXPECTED_FUNCTION_NAME: Message = Message::new("E1066", "Expected function name");
+pub const EXPECTED_END_AFTER_FUNCTION_BODY: Message =
+    Message::new("E1067", "Expected 'end' after function body");
+pub const ELLIPSIS_MUST_BE_THE_LAST_PARAMETER: Message =
+    Message::new("E1068", "'...' must be the last parameter");
+pub const EXPECTED_PARAMETER_NAME_AFTER_AMPERSAND: Message =
+    Message::new("E1069", "Expected parameter name after '&'");
+pub const EXPECTED_PARAMETER_NAME_AFTER_STAR: Message =
+    Message::new("E1070", "Expected parameter name after '*'");
+pub const EXPECTED_RPAREN_AFTER_PARAMETERS: Message =
+    Message::new("E1071", "Expected ')' after parameters");
+pub const EXPECTED_TYPE_NAME: Message = Message::new("E1072", "Expected a type name");
+pub const UNEXPECTED_TOKEN_IN_INTERPOLATION: Message =
+    Message::new("E1073", "Unexpected token in interpolation: {token}");
+pub const OPERATOR_CANNOT_BE_CHAINED: Message =
+    Message::new("E1074", "'{operator}' cannot be chained; add parentheses");
+pub const EXPECTED_PATTERN: Message = Message::new("E1075", "Expected pattern, found {token}");
+pub const EXPECTED_END_AFTER_CLASS_BODY: Message =
+    Message::new("E1076", "Expected 'end' after class body");
+pub const EXPECTED_END_AFTER_MODULE_BODY: Message =
+    Message::new("E1077", "Expected 'end' after module body");
+
+// ============================================================================
+// Name Resolution (E11xx)
+// ============================================================================
+
+pub const NUMBERED_PARAMETER_WITH_PARAMETER_LIST: Message = Message::new(
+    "E1101",
+    "Numbered parameter '_{number}' cannot be used in a block with a parameter list",
+);
+pub const IT_WITH_NUMBERED_PARAMETERS: Message = Message::new(
+    "E1102",
+    "'it' cannot be used in a block that uses numbered parameters",
+);
+pub const VARIABLE_ALREADY_DECLARED: Message = Message::new(
+    "E1103",
+    "Variable '{name}' is already declared in this scope at {line}:{column}",
+);
+
+/// Every message, for looking one up by code
+pub const MESSAGES: &[Message] = &[
+    LOOP_CONTROL,
+    BEGIN_EXPRESSION_JUMP,
+    STACK_TOO_DEEP,
+    RETRY_OUTSIDE_RESCUE,
+    INVALID_TIMEOUT,
+    INVALID_ASSIGNMENT_TARGET,
+    UNDEFINED_VARIABLE,
+    UNINITIALIZED_CLASS_VARIABLE,
+    UNDEFINED_SELF,
+    READ_INSTANCE_VARIABLE_ON_NON_INSTANCE,
+    SET_INSTANCE_VARIABLE_ON_NON_INSTANCE,
+    INSTANCE_VARIABLE_OUTSIDE_METHOD,
+    READ_CLASS_VARIABLE_OUT_OF_CONTEXT,
+    SET_CLASS_VARIABLE_OUT_OF_CONTEXT,
+    CLASS_VARIABLE_OUTSIDE_CLASS,
+    METHOD_CALL_ASSIGNMENT,
+    UNDEFINED_METHOD,
+    PRIVATE_METHOD,
+    METHOD_ARGUMENT_COUNT,
+    BUILTIN_ARGUMENT_COUNT,
+    BUILTIN_PARAMETER_TYPE,
+    UNRELEASABLE_RESOURCE,
+    METHOD_MINIMUM_ARGUMENT_COUNT,
+    NO_FORWARDED_ARGUMENTS,
+    METHOD_ARGUMENT_TYPE,
+    NOT_CALLABLE,
+    CALLABLE_ARGUMENT_COUNT,
+    FORWARDED_ARGUMENTS_OUTSIDE_CALL,
+    UNDEFINED_SETTER,
+    SETTER_ON_NON_INSTANCE,
+    SUPER_OUTSIDE_INSTANCE_METHOD,
+    SUPER_OUTSIDE_METHOD,
+    SUPER_WITHOUT_METHOD_FRAME,
+    SUPER_WITHOUT_CLASS,
+    SUPER_DEFINING_CLASS_NOT_FOUND,
+    SUPER_WITHOUT_SUPERCLASS,
+    SUPER_METHOD_NOT_FOUND,
+    UNARY_OPERATOR_TYPE,
+    BINARY_OPERATOR_TYPE,
+    COMPARISON_FAILED,
+    DIVISION_BY_ZERO,
+    DECIMAL_OVERFLOW,
+    INDEX_OUT_OF_BOUNDS,
+    UNDEFINED_DICTIONARY_KEY,
+    COLLECTION_IN_USE,
+    NO_PATTERN_MATCHED,
+    ARRAY_INDEX_NOT_INTEGER,
+    INVALID_HASH_KEY,
+    INDEX_ASSIGNMENT_UNSUPPORTED,
+    INDEX_TOO_FAR_PAST_END,
+    SUPERCLASS_NOT_A_CLASS,
+    UNDEFINED_SUPERCLASS,
+    SEALED_SUPERCLASS,
+    ATTRIBUTE_OUTSIDE_CLASS,
+    FILE_NOT_FOUND,
+    FILE_NOT_LOADED,
+    FILE_NOT_PARSED,
+    FILE_EXECUTION_FAILED,
+    UNEXPECTED_TOKEN,
+    EXPECTED_METHOD_NAME_AFTER_DOT,
+    EXPECTED_NAME_AFTER_DOUBLE_COLON,
+    EXPECTED_RBRACKET_AFTER_ARRAY_INDEX,
+    EXPECTED_RPAREN_AFTER_ARGUMENTS,
+    EXPECTED_KEYWORD_ARGUMENT_NAME,
+    EXPECTED_COLON_AFTER_KEYWORD_ARGUMENT_NAME,
+    EXPECTED_FUNCTION_CALL_BUT_FOUND_DICTIONARY_LIKE_SYNTAX,
+    ARROW_LAMBDA_PARAMETERS_MUST_BE_IDENTIFIERS,
+    LEFT_SIDE_OF_ARROW_MUST_BE_PARAMETERS,
+    EXPECTED_DO_TO_START_BLOCK,
+    EXPECTED_PIPE_AFTER_BLOCK_PARAMETERS,
+    EXPECTED_END_TO_CLOSE_BLOCK,
+    EXPECTED_LBRACE_TO_START_BLOCK,
+    EXPECTED_RBRACE_TO_CLOSE_BLOCK,
+    EXPECTED_PARAMETER_NAME,
+    EXPECTED_RPAREN_AFTER_DESTRUCTURED_PARAMETERS,
+    EXPECTED_RPAREN_AFTER_EXPRESSION,
+    EXPECTED_RBRACKET_AFTER_ARRAY_ELEMENTS,
+    EXPECTED_COLON_OR_ARROW_AFTER_DICTIONARY_KEY,
+    EXPECTED_RBRACE_AFTER_DICTIONARY_ENTRIES,
+    EXPECTED_PIPE_AFTER_LAMBDA_PARAMETERS,
+    EXPECTED_END_AFTER_LAMBDA_BODY,
+    EXPECTED_END_AFTER_BLOCK_BODY,
+    EXPECTED_RPAREN_AFTER_SUPER_ARGUMENTS,
+    EXPECTED_END_AFTER_CASE_EXPRESSION,
+    EXPECTED_EXPRESSION_IN_INTERPOLATION,
+    EXPECTED_ATTR_READER,
+    EXPECTED_ATTR_WRITER,
+    EXPECTED_ATTR_ACCESSOR,
+    EXPECTED_ATTRIBUTE_NAME_SYMBOL,
+    EXPECTED_CLASS,
+    EXPECTED_CLASS_NAME,
+    EXPECTED_SUPERCLASS_NAME,
+    EXPECTED_MODULE,
+    EXPECTED_MODULE_NAME,
+    EXPECTED_IF,
+    EXPECTED_END_AFTER_IF_STATEMENT,
+    EXPECTED_WHILE,
+    EXPECTED_END_AFTER_WHILE_LOOP,
+    EXPECTED_FOR,
+    EXPECTED_IDENTIFIER_AFTER_FOR,
+    EXPECTED_IN_AFTER_LOOP_VARIABLE,
+    EXPECTED_END_AFTER_FOR_LOOP,
+    EXPECTED_BREAK,
+    EXPECTED_CONTINUE,
+    EXPECTED_UNLESS,
+    EXPECTED_END_AFTER_UNLESS_STATEMENT,
+    EXPECTED_RETURN,
+    EXPECTED_CASE,
+    EXPECTED_END_AFTER_CASE_STATEMENT,
+    EXPECTED_IDENTIFIER_AFTER_ELLIPSIS_IN_ARRAY_PATTERN,
+    EXPECTED_COMMA_OR_RBRACKET_IN_ARRAY_PATTERN,
+    EXPECTED_RBRACKET_AFTER_ARRAY_PATTERN,
+    EXPECTED_IDENTIFIER_OR_STRING_KEY_IN_OBJECT_PATTERN,
+    EXPECTED_COMMA_OR_RBRACE_IN_OBJECT_PATTERN,
+    EXPECTED_RBRACE_AFTER_OBJECT_PATTERN,
+    EXPECTED_STRING_IN_GLOB_PATTERN,
+    EXPECTED_RPAREN_AFTER_GLOB_PATTERN,
+    EXPECTED_BEGIN,
+    EXPECTED_END_AFTER_BEGIN_BLOCK,
+    EXPECTED_VARIABLE_NAME_AFTER_ARROW,
+    EXPECTED_RAISE,
+    EXPECTED_RETRY,
+    EXPECTED_DEF,
+    EXPECTED_FUNCTION_NAME,
+    EXPECTED_END_AFTER_FUNCTION_BODY,
+    ELLIPSIS_MUST_BE_THE_LAST_PARAMETER,
+    EXPECTED_PARAMETER_NAME_AFTER_AMPERSAND,
+    EXPECTED_PARAMETER_NAME_AFTER_STAR,
+    EXPECTED_RPAREN_AFTER_PARAMETERS,
+    EXPECTED_TYPE_NAME,
+    UNEXPECTED_TOKEN_IN_INTERPOLATION,
+    OPERATOR_CANNOT_BE_CHAINED,
+    EXPECTED_PATTERN,
+    EXPECTED_END_AFTER_CLASS_BODY,
+    EXPECTED_END_AFTER_MODULE_BODY,
+    NUMBERED_PARAMETER_WITH_PARAMETER_LIST,
+    IT_WITH_NUMBERED_PARAMETERS,
+    VARIABLE_ALREADY_DECLARED,
+];
+
+/// Parse errors that more input could fix, such as a missing `end`
+const UNFINISHED_INPUT: &[Message] = &[
+    EXPECTED_END_TO_CLOSE_BLOCK,
+    EXPECTED_END_AFTER_LAMBDA_BODY,
+    EXPECTED_END_AFTER_BLOCK_BODY,
+    EXPECTED_END_AFTER_CASE_EXPRESSION,
+    EXPECTED_END_AFTER_IF_STATEMENT,
+    EXPECTED_END_AFTER_WHILE_LOOP,
+    EXPECTED_END_AFTER_FOR_LOOP,
+    EXPECTED_END_AFTER_UNLESS_STATEMENT,
+    EXPECTED_END_AFTER_CASE_STATEMENT,
+    EXPECTED_END_AFTER_BEGIN_BLOCK,
+    EXPECTED_END_AFTER_FUNCTION_BODY,
+    EXPECTED_END_AFTER_CLASS_BODY,
+    EXPECTED_END_AFTER_MODULE_BODY,
+];
@@ -0,0 +1,128 @@
+//! Runtime module representation for Metorex
+//! A module is a named set of methods that classes mix in with `include`
+//! (as instance methods) or `extend` (as methods called on the class).
+
+use crate::object::{Method, Object};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Runtime module definition with its method table and included modules.
+#[derive(Debug)]
+pub struct Module {
+    name: String,
+    methods: RefCell<HashMap<String, Rc<Method>>>,
+    included_modules: RefCell<Vec<Rc<Module>>>,
+    constants: RefCell<HashMap<String, Object>>,
+}
+
+impl Module {
+    /// Create a new, empty module.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            methods: RefCell::new(HashMap::new()),
+            included_modules: RefCell::new(Vec::new()),
+            constants: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Return the module name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Define or replace a method on this module.
+    pub fn define_method(&self, name: impl Into<String>, method: Rc<Method>) {
+        self.methods.borrow_mut().insert(name.into(), method);
+    }
+
+    /// Determine whether this module defines a method (without checking the
+    /// modules it includes).
+    pub fn has_own_method(&self, name: &str) -> bool {
+        self.methods.borrow().contains_key(name)
+    }
+
+    /// Look up a method on this module, then on the modules it includes,
+    /// the most recently included first.
+    pub fn find_method(&self, name: &str) -> Option<Rc<Method>> {
+        if let Some(method) = self.methods.borrow().get(name) {
+            return Some(Rc::clone(method));
+        }
+        find_in_modules(&self.included_modules.borrow(), name)
+    }
+
+    /// Return a list of method names defined directly on this module.
+    pub fn method_names(&self) -> Vec<String> {
+        let mut names = self.methods.borrow().keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Define a constant, such as a nested class, inside this module.
+    pub fn set_constant(&self, name: impl Into<String>, value: Object) {
+        self.constants.borrow_mut().insert(name.into(), value);
+    }
+
+    /// Retrieve a constant defined inside this module.
+    pub fn get_constant(&self, name: &str) -> Option<Object> {
+        self.constants.borrow().get(name).cloned()
+    }
+
+    /// Names of the constants defined inside this module, sorted.
+    pub fn constant_names(&self) -> Vec<String> {
+        let mut names = self.constants.borrow().keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Mix another module's methods into this one. Returns `false` if it was
+    /// already included, directly or through another module, or if it
+    /// includes this module.
+    pub fn include(&self, module: &Rc<Module>) -> bool {
+        !module.includes(self) && include_into(&self.included_modules, module)
+    }
+
+    /// The modules included directly in this one, in the order they were included.
+    pub fn included_modules(&self) -> Vec<Rc<Module>> {
+        self.included_modules.borrow().clone()
+    }
+
+    /// Whether `module` is this module or is included in it, directly or
+    /// through another module.
+    pub fn includes(&self, module: &Module) -> bool {
+        std::ptr::eq(self, module) || modules_include(&self.included_modules.borrow(), module)
+    }
+}
+
+impl PartialEq for Module {
+    /// Modules are compared by identity, like the classes they are mixed into.
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl Eq for Module {}
+
+/// Look up a method in a list of mixed-in modules, the last one first.
+pub(crate) fn find_in_modules(modules: &[Rc<Module>], name: &str) -> Option<Rc<Method>> {
+    modules
+        .iter()
+        .rev()
+        .find_map(|module| module.find_method(name))
+}
+
+/// Whether any module in the list is or includes `module`.
+pub(crate) fn modules_include(modules: &[Rc<Module>], module: &Module) -> bool {
+    modules.iter().any(|included| included.includes(module))
+}
+
+/// Append `module` to a list of mixed-in modules unless the list already
+/// includes it.
+pub(crate) fn include_into(modules: &RefCell<Vec<Rc<Module>>>, module: &Rc<Module>) -> bool {
+    if modules_include(&modules.borrow(), module) {
+        return false;
+    }
+    modules.borrow_mut().push(Rc::clone(module));
+    true
+}
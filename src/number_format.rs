@@ -0,0 +1,223 @@
+// Locale-independent number formatting and parsing
+// Backs Integer#format, Float#format, Decimal#format, String#to_number and
+// the numeric humanize methods. The delimiter
+// and decimal separator are always given explicitly, never read from the
+// environment, so the same script prints the same text on every machine.
+
+use rust_decimal::Decimal;
+
+/// Largest number of decimal places `format` will produce
+pub const MAX_PRECISION: usize = 20;
+
+/// How a number is written out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// Inserted between groups of three integer digits; may be empty
+    pub delimiter: String,
+    /// Written between the integer and fractional digits
+    pub separator: String,
+    /// Number of fractional digits
+    pub precision: usize,
+}
+
+impl NumberFormat {
+    /// `1,234,567.89` style with the given number of decimal places
+    pub fn with_precision(precision: usize) -> Self {
+        NumberFormat {
+            delimiter: ",".to_string(),
+            separator: ".".to_string(),
+            precision,
+        }
+    }
+}
+
+/// A number read back from text
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParsedNumber {
+    Int(i64),
+    Float(f64),
+}
+
+/// Write an integer, padding with zero decimals when `precision` asks for them
+pub fn format_integer(value: i64, format: &NumberFormat) -> String {
+    let mut text = String::new();
+    if value < 0 {
+        text.push('-');
+    }
+    text.push_str(&group_digits(
+        &value.unsigned_abs().to_string(),
+        &format.delimiter,
+    ));
+    if format.precision > 0 {
+        text.push_str(&format.separator);
+        text.push_str(&"0".repeat(format.precision));
+    }
+    text
+}
+
+/// Write a float rounded to `precision` decimal places
+///
+/// Non-finite values are written as `NaN`, `Infinity` and `-Infinity`. A value
+/// that rounds to zero loses its minus sign, so `-0.001` gives `0.00`.
+pub fn format_float(value: f64, format: &NumberFormat) -> String {
+    if value.is_nan() {
+        return "NaN".to_string();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "Infinity" } else { "-Infinity" }.to_string();
+    }
+
+    let rounded = format!("{:.*}", format.precision, value.abs());
+    let (whole, fraction) = rounded.split_once('.').unwrap_or((&rounded, ""));
+
+    let mut text = String::new();
+    if value.is_sign_negative() && rounded.bytes().any(|b| matches!(b, b'1'..=b'9')) {
+        text.push('-');
+    }
+    text.push_str(&group_digits(whole, &format.delimiter));
+    if !fraction.is_empty() {
+        text.push_str(&format.separator);
+        text.push_str(fraction);
+    }
+    text
+}
+
+/// Write a Decimal with exactly `precision` decimal places
+///
+/// The value is padded with zeros but never rounded; callers round it first
+/// with the mode they want. A value of zero loses its minus sign.
+pub fn format_decimal(value: Decimal, format: &NumberFormat) -> String {
+    let digits = format!("{:.*}", format.precision, value.abs());
+    let (whole, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+
+    let mut text = String::new();
+    if value.is_sign_negative() && !value.is_zero() {
+        text.push('-');
+    }
+    text.push_str(&group_digits(whole, &format.delimiter));
+    if !fraction.is_empty() {
+        text.push_str(&format.separator);
+        text.push_str(fraction);
+    }
+    text
+}
+
+/// Suffixes for thousands, millions, billions and trillions
+const MAGNITUDES: [&str; 5] = ["", "K", "M", "B", "T"];
+
+/// Write a number scaled to a K, M, B or T suffix, so `1234567` gives `1.2M`
+///
+/// The value is rounded to `precision` decimal places after scaling and
+/// trailing zeros are dropped, so `1000` gives `1K`. Values that round up
+/// into the next magnitude move to it: `999_950` gives `1M`, not `1000K`.
+pub fn humanize(value: f64, format: &NumberFormat) -> String {
+    if !value.is_finite() {
+        return format_float(value, format);
+    }
+
+    let mut scaled = value;
+    let mut magnitude = 0;
+    while magnitude + 1 < MAGNITUDES.len() {
+        let rounded: f64 = format!("{:.*}", format.precision, scaled.abs())
+            .parse()
+            .unwrap_or(f64::INFINITY);
+        if rounded < 1000.0 {
+            break;
+        }
+        scaled /= 1000.0;
+        magnitude += 1;
+    }
+
+    let mut text = format_float(scaled, format);
+    if format.precision > 0 {
+        let trimmed = text.trim_end_matches('0');
+        let trimmed = trimmed.strip_suffix(&format.separator).unwrap_or(trimmed);
+        text = trimmed.to_string();
+    }
+    text.push_str(MAGNITUDES[magnitude]);
+    text
+}
+
+/// Read a number written with the given delimiter and decimal separator
+///
+/// Surrounding whitespace, a leading sign and an exponent are allowed.
+/// Delimiters may only appear between integer digits. Text with a separator
+/// or exponent reads as a Float, as does an integer too large for an Int.
+/// Anything else that is not entirely a number gives `None`.
+pub fn parse_number(text: &str, delimiter: &str, separator: &str) -> Option<ParsedNumber> {
+    let text = text.trim();
+    let (sign, unsigned) = match text.strip_prefix(['-', '+']) {
+        Some(rest) => (&text[..1], rest),
+        None => ("", text),
+    };
+
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        Some(index) => (&unsigned[..index], Some(&unsigned[index + 1..])),
+        None => (unsigned, None),
+    };
+    let (whole, fraction) = match mantissa.split_once(separator) {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (mantissa, None),
+    };
+
+    let whole = strip_delimiters(whole, delimiter)?;
+    if whole.is_empty() || fraction.is_some_and(|digits| !all_digits(digits)) {
+        return None;
+    }
+    if let Some(exponent) = exponent {
+        let digits = exponent.strip_prefix(['-', '+']).unwrap_or(exponent);
+        if !all_digits(digits) {
+            return None;
+        }
+    }
+
+    if fraction.is_none()
+        && exponent.is_none()
+        && let Ok(value) = format!("{}{}", sign, whole).parse()
+    {
+        return Some(ParsedNumber::Int(value));
+    }
+
+    let mut normalized = format!("{}{}", sign, whole);
+    if let Some(fraction) = fraction {
+        normalized.push('.');
+        normalized.push_str(fraction);
+    }
+    if let Some(exponent) = exponent {
+        normalized.push('e');
+        normalized.push_str(exponent);
+    }
+    normalized.parse().ok().map(ParsedNumber::Float)
+}
+
+/// Insert `delimiter` between every group of three digits, counting from the right
+fn group_digits(digits: &str, delimiter: &str) -> String {
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3 * delimiter.len());
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push_str(delimiter);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// The integer digits with delimiters removed, or `None` if a delimiter is
+/// misplaced or a non-digit appears
+fn strip_delimiters(whole: &str, delimiter: &str) -> Option<String> {
+    if delimiter.is_empty() {
+        return all_digits(whole).then(|| whole.to_string());
+    }
+    let groups: Vec<&str> = whole.split(delimiter).collect();
+    if groups.len() > 1 && groups.iter().any(|group| group.is_empty()) {
+        return None;
+    }
+    groups
+        .iter()
+        .all(|group| all_digits(group))
+        .then(|| groups.concat())
+}
+
+fn all_digits(text: &str) -> bool {
+    text.bytes().all(|b| b.is_ascii_digit())
+}
@@ -1,6 +1,6 @@
 // BlockStatement - represents closures/lambdas with captured variables
 
-use crate::ast::Statement;
+use crate::ast::{DestructuredParameter, Statement};
 use crate::callable::Callable;
 use crate::error::MetorexError;
 use crate::lexer::Position;
@@ -20,6 +20,8 @@ pub struct BlockStatement {
     pub body: Vec<Statement>,
     /// Captured variables from outer scope (shared mutable references)
     pub captured_vars: HashMap<String, Rc<RefCell<Object>>>,
+    /// Parameters written as `(a, b)`, unpacked when the block is called
+    pub destructured: Vec<DestructuredParameter>,
 }
 
 impl BlockStatement {
@@ -33,9 +35,16 @@ impl BlockStatement {
             parameters,
             body,
             captured_vars,
+            destructured: Vec::new(),
         }
     }
 
+    /// Unpack the arguments passed for these parenthesized parameters.
+    pub fn with_destructured(mut self, destructured: Vec<DestructuredParameter>) -> Self {
+        self.destructured = destructured;
+        self
+    }
+
     /// Get the captured variables
     pub fn captured_vars(&self) -> &HashMap<String, Rc<RefCell<Object>>> {
         &self.captured_vars
@@ -0,0 +1,219 @@
+// Conversion of Metorex values into Rust types
+//
+// Hosts that run a script for its result (configuration, rules) implement or
+// use FromMetorex to turn the final value into their own types. A failed
+// conversion names the type that was expected, the one that was found and
+// where inside nested arrays and dicts the mismatch is.
+
+use indexmap::IndexMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use super::Object;
+
+/// A Metorex value that does not have the shape a Rust type needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversionError {
+    /// What the Rust type accepts, e.g. "Int" or "Array"
+    pub expected: String,
+    /// What the value was, e.g. "String" or "Int 70000"
+    pub found: String,
+    /// Where the value sits inside the converted value, e.g. `["ports"][2]`;
+    /// empty for the value itself
+    pub path: String,
+}
+
+impl ConversionError {
+    /// A mismatch between `expected` and the type of `found`.
+    pub fn new(expected: impl Into<String>, found: &Object) -> Self {
+        Self {
+            expected: expected.into(),
+            found: found.type_name().to_string(),
+            path: String::new(),
+        }
+    }
+
+    /// The same error for a value found at `index` of an array.
+    pub fn at_index(mut self, index: usize) -> Self {
+        self.path = format!("[{}]{}", index, self.path);
+        self
+    }
+
+    /// The same error for a value found under `key` of a dict.
+    pub fn at_key(mut self, key: &str) -> Self {
+        self.path = format!("[{:?}]{}", key, self.path);
+        self
+    }
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {}, found {}", self.expected, self.found)?;
+        if !self.path.is_empty() {
+            write!(f, " at {}", self.path)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+/// Conversion from a Metorex value into a Rust type.
+pub trait FromMetorex: Sized {
+    fn from_metorex(value: &Object) -> Result<Self, ConversionError>;
+}
+
+/// Convert the entry `key` of a dict, treating a missing key as nil so that
+/// `Option` fields may be left out.
+pub fn field<T: FromMetorex>(dict: &Object, key: &str) -> Result<T, ConversionError> {
+    let Object::Dict(entries) = dict else {
+        return Err(ConversionError::new("Dict", dict));
+    };
+    let entry = entries.borrow().get(key).cloned();
+    match entry {
+        Some(value) => T::from_metorex(&value).map_err(|error| error.at_key(key)),
+        None => T::from_metorex(&Object::Nil).map_err(|error| {
+            ConversionError {
+                found: "nothing".to_string(),
+                ..error
+            }
+            .at_key(key)
+        }),
+    }
+}
+
+impl FromMetorex for Object {
+    fn from_metorex(value: &Object) -> Result<Self, ConversionError> {
+        Ok(value.clone())
+    }
+}
+
+impl FromMetorex for () {
+    fn from_metorex(value: &Object) -> Result<Self, ConversionError> {
+        match value {
+            Object::Nil => Ok(()),
+            other => Err(ConversionError::new("Nil", other)),
+        }
+    }
+}
+
+impl FromMetorex for bool {
+    fn from_metorex(value: &Object) -> Result<Self, ConversionError> {
+        match value {
+            Object::Bool(flag) => Ok(*flag),
+            other => Err(ConversionError::new("Bool", other)),
+        }
+    }
+}
+
+impl FromMetorex for i64 {
+    fn from_metorex(value: &Object) -> Result<Self, ConversionError> {
+        match value {
+            Object::Int(number) => Ok(*number),
+            other => Err(ConversionError::new("Int", other)),
+        }
+    }
+}
+
+/// Integer types narrower than i64 accept Ints within their range.
+macro_rules! from_metorex_int {
+    ($($target:ty),*) => {$(
+        impl FromMetorex for $target {
+            fn from_metorex(value: &Object) -> Result<Self, ConversionError> {
+                let number = i64::from_metorex(value).map_err(|error| ConversionError {
+                    expected: format!("Int ({})", stringify!($target)),
+                    ..error
+                })?;
+                <$target>::try_from(number).map_err(|_| ConversionError {
+                    expected: format!("Int ({})", stringify!($target)),
+                    found: format!("Int {}", number),
+                    path: String::new(),
+                })
+            }
+        }
+    )*};
+}
+
+from_metorex_int!(i8, i16, i32, u8, u16, u32, u64, usize);
+
+impl FromMetorex for f64 {
+    fn from_metorex(value: &Object) -> Result<Self, ConversionError> {
+        match value {
+            Object::Float(number) => Ok(*number),
+            Object::Int(number) => Ok(*number as f64),
+            other => Err(ConversionError::new("Float", other)),
+        }
+    }
+}
+
+impl FromMetorex for String {
+    fn from_metorex(value: &Object) -> Result<Self, ConversionError> {
+        match value {
+            Object::String(text) => Ok(text.as_str().to_string()),
+            Object::Symbol(name) => Ok(name.as_str().to_string()),
+            other => Err(ConversionError::new("String", other)),
+        }
+    }
+}
+
+impl<T: FromMetorex> FromMetorex for Option<T> {
+    fn from_metorex(value: &Object) -> Result<Self, ConversionError> {
+        match value {
+            Object::Nil => Ok(None),
+            other => T::from_metorex(other).map(Some),
+        }
+    }
+}
+
+impl<T: FromMetorex> FromMetorex for Vec<T> {
+    fn from_metorex(value: &Object) -> Result<Self, ConversionError> {
+        match value {
+            Object::Array(elements) => elements
+                .borrow()
+                .iter()
+                .enumerate()
+                .map(|(index, element)| {
+                    T::from_metorex(element).map_err(|error| error.at_index(index))
+                })
+                .collect(),
+            other => Err(ConversionError::new("Array", other)),
+        }
+    }
+}
+
+/// Convert every entry of a dict, keeping the first failure.
+fn dict_entries<T: FromMetorex>(
+    value: &Object,
+) -> Result<impl Iterator<Item = (String, T)>, ConversionError> {
+    let Object::Dict(entries) = value else {
+        return Err(ConversionError::new("Dict", value));
+    };
+    let converted = entries
+        .borrow()
+        .iter()
+        .map(|(key, entry)| {
+            T::from_metorex(entry)
+                .map(|entry| (key.clone(), entry))
+                .map_err(|error| error.at_key(key))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(converted.into_iter())
+}
+
+impl<T: FromMetorex> FromMetorex for IndexMap<String, T> {
+    fn from_metorex(value: &Object) -> Result<Self, ConversionError> {
+        dict_entries(value).map(Iterator::collect)
+    }
+}
+
+impl<T: FromMetorex> FromMetorex for HashMap<String, T> {
+    fn from_metorex(value: &Object) -> Result<Self, ConversionError> {
+        dict_entries(value).map(Iterator::collect)
+    }
+}
+
+impl<T: FromMetorex> FromMetorex for BTreeMap<String, T> {
+    fn from_metorex(value: &Object) -> Result<Self, ConversionError> {
+        dict_entries(value).map(Iterator::collect)
+    }
+}
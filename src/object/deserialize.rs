@@ -0,0 +1,249 @@
+// Conversion of Metorex values back into deserializable Rust values
+//
+// The inverse of `to_object`: arrays become sequences, dicts become maps and
+// structs, symbols name unit enum variants and dicts tagged with a "type"
+// symbol name the other variants. Scripts that build or rewrite ASTs hand
+// them back to the interpreter through `from_object`.
+
+use indexmap::IndexMap;
+use serde::de::value::StringDeserializer;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use std::fmt;
+
+use super::Object;
+
+/// A value that does not have the shape the Rust type needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeserializeError(String);
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for DeserializeError {}
+
+impl de::Error for DeserializeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+/// Convert a Metorex value into any deserializable value.
+pub fn from_object<T: DeserializeOwned>(value: &Object) -> Result<T, DeserializeError> {
+    T::deserialize(ObjectDeserializer(value.clone()))
+}
+
+struct ObjectDeserializer(Object);
+
+impl<'de> de::Deserializer<'de> for ObjectDeserializer {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        match self.0 {
+            Object::Nil => visitor.visit_unit(),
+            Object::Bool(flag) => visitor.visit_bool(flag),
+            Object::Int(number) => visitor.visit_i64(number),
+            Object::Float(number) => visitor.visit_f64(number),
+            // As text, so JSON and other formats keep every digit and the scale
+            Object::Decimal(number) => visitor.visit_str(&number.to_string()),
+            Object::String(text) | Object::Symbol(text) => visitor.visit_str(&text),
+            Object::Array(elements) => {
+                let elements = elements.borrow().clone();
+                visitor.visit_seq(SeqDeserializer(elements.into_iter()))
+            }
+            Object::Dict(entries) => {
+                let entries = entries.borrow().clone();
+                visitor.visit_map(DictDeserializer::new(entries))
+            }
+            other => Err(DeserializeError(format!(
+                "{} has no serialized form",
+                other.type_name()
+            ))),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeserializeError> {
+        match self.0 {
+            Object::Nil => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        match self.0 {
+            Object::Symbol(variant) | Object::String(variant) => visitor.visit_enum(Variant {
+                name: variant.to_string(),
+                fields: None,
+            }),
+            Object::Dict(entries) => {
+                let mut fields = entries.borrow().clone();
+                let variant = match fields.shift_remove("type") {
+                    Some(Object::Symbol(variant) | Object::String(variant)) => variant.to_string(),
+                    _ => {
+                        return Err(DeserializeError(format!(
+                            "{} needs a \"type\" symbol naming the variant",
+                            name
+                        )));
+                    }
+                };
+                visitor.visit_enum(Variant {
+                    name: variant,
+                    fields: Some(fields),
+                })
+            }
+            other => Err(DeserializeError(format!(
+                "expected a {} variant, found {}",
+                name,
+                other.type_name()
+            ))),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+/// Elements of an array.
+struct SeqDeserializer(std::vec::IntoIter<Object>);
+
+impl<'de> SeqAccess<'de> for SeqDeserializer {
+    type Error = DeserializeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, DeserializeError> {
+        self.0
+            .next()
+            .map(|element| seed.deserialize(ObjectDeserializer(element)))
+            .transpose()
+    }
+}
+
+/// Entries of a dict, or the fields of a struct variant.
+struct DictDeserializer {
+    entries: indexmap::map::IntoIter<String, Object>,
+    pending_value: Option<Object>,
+}
+
+impl DictDeserializer {
+    fn new(entries: IndexMap<String, Object>) -> Self {
+        Self {
+            entries: entries.into_iter(),
+            pending_value: None,
+        }
+    }
+}
+
+impl<'de> MapAccess<'de> for DictDeserializer {
+    type Error = DeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, DeserializeError> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.pending_value = Some(value);
+        let key: StringDeserializer<DeserializeError> = key.into_deserializer();
+        seed.deserialize(key).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, DeserializeError> {
+        let value = self
+            .pending_value
+            .take()
+            .ok_or_else(|| DeserializeError("dict value without a key".to_string()))?;
+        seed.deserialize(ObjectDeserializer(value))
+    }
+}
+
+/// An enum variant: a bare symbol, or a tagged dict holding its fields.
+struct Variant {
+    name: String,
+    fields: Option<IndexMap<String, Object>>,
+}
+
+impl Variant {
+    /// The entry holding a newtype or tuple variant's payload.
+    fn payload(self, key: &str) -> Result<Object, DeserializeError> {
+        self.fields
+            .and_then(|mut fields| fields.shift_remove(key))
+            .ok_or_else(|| DeserializeError(format!("{} needs a \"{}\" entry", self.name, key)))
+    }
+}
+
+impl<'de> EnumAccess<'de> for Variant {
+    type Error = DeserializeError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), DeserializeError> {
+        let name: StringDeserializer<DeserializeError> = self.name.clone().into_deserializer();
+        Ok((seed.deserialize(name)?, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Variant {
+    type Error = DeserializeError;
+
+    fn unit_variant(self) -> Result<(), DeserializeError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, DeserializeError> {
+        seed.deserialize(ObjectDeserializer(self.payload("value")?))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        de::Deserializer::deserialize_seq(ObjectDeserializer(self.payload("values")?), visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeserializeError> {
+        match self.fields {
+            Some(fields) => visitor.visit_map(DictDeserializer::new(fields)),
+            None => Err(DeserializeError(format!(
+                "{} needs its fields, not just a symbol",
+                self.name
+            ))),
+        }
+    }
+}
@@ -11,7 +11,11 @@ impl fmt::Display for Object {
             Object::Nil => write!(f, "nil"),
             Object::Bool(b) => write!(f, "{}", b),
             Object::Int(i) => write!(f, "{}", i),
+            Object::Float(fl) if fl.is_infinite() => {
+                write!(f, "{}", if *fl > 0.0 { "Infinity" } else { "-Infinity" })
+            }
             Object::Float(fl) => write!(f, "{}", fl),
+            Object::Decimal(d) => write!(f, "{}", d),
             Object::String(s) => write!(f, "{}", s),
             Object::Symbol(s) => write!(f, ":{}", s),
             Object::Array(arr) => {
@@ -32,7 +36,12 @@ impl fmt::Display for Object {
                     if i > 0 {
                         write!(f, ", ")?;
                     }
-                    write!(f, "{}: {}", key, value)?;
+                    // Symbol keys are stored as ":name" and shown as in a literal
+                    if key.starts_with(':') {
+                        write!(f, "{} => {}", key, value)?;
+                    } else {
+                        write!(f, "{}: {}", key, value)?;
+                    }
                 }
                 write!(f, "}}")
             }
@@ -41,6 +50,7 @@ impl fmt::Display for Object {
                 write!(f, "<{} instance>", instance.class.name())
             }
             Object::Class(class) => write!(f, "<class {}>", class.name()),
+            Object::Module(module) => write!(f, "<module {}>", module.name()),
             Object::Method(method) => write!(f, "<method {}>", method.name),
             Object::Block(_) => write!(f, "<block>"),
             Object::Exception(exc) => {
@@ -1,24 +1,10 @@
-// Exception handling types - Exception and SourceLocation
+// Exception handling types
+// Locations use the same SourceLocation as MetorexError, re-exported here so
+// `object::SourceLocation` and `error::SourceLocation` are one type.
 
 use super::Object;
-
-/// Source location for exceptions
-#[derive(Debug, Clone, PartialEq)]
-pub struct SourceLocation {
-    /// File name or path
-    pub file: String,
-    /// Line number (1-based)
-    pub line: usize,
-    /// Column number (1-based)
-    pub column: usize,
-}
-
-impl SourceLocation {
-    /// Create a new source location
-    pub fn new(file: String, line: usize, column: usize) -> Self {
-        Self { file, line, column }
-    }
-}
+pub use crate::error::SourceLocation;
+use indexmap::IndexMap;
 
 /// Exception object for error handling
 #[derive(Debug, Clone, PartialEq)]
@@ -33,6 +19,9 @@ pub struct Exception {
     pub location: Option<SourceLocation>,
     /// Cause chain (wrapped exception)
     pub cause: Option<Box<Object>>,
+    /// Structured context about the failure (path, errno, operation, ...),
+    /// readable from scripts as methods on the exception
+    pub details: IndexMap<String, Object>,
 }
 
 impl Exception {
@@ -44,6 +33,7 @@ impl Exception {
             backtrace: None,
             location: None,
             cause: None,
+            details: IndexMap::new(),
         }
     }
 
@@ -55,6 +45,7 @@ impl Exception {
             backtrace: Some(backtrace),
             location: None,
             cause: None,
+            details: IndexMap::new(),
         }
     }
 
@@ -70,6 +61,7 @@ impl Exception {
             backtrace: None,
             location: Some(location),
             cause: None,
+            details: IndexMap::new(),
         }
     }
 
@@ -81,6 +73,7 @@ impl Exception {
             backtrace: None,
             location: None,
             cause: Some(Box::new(cause)),
+            details: IndexMap::new(),
         }
     }
 
@@ -98,9 +91,16 @@ impl Exception {
             backtrace,
             location,
             cause: cause.map(Box::new),
+            details: IndexMap::new(),
         }
     }
 
+    /// Attach structured details to the exception
+    pub fn with_details(mut self, details: IndexMap<String, Object>) -> Self {
+        self.details = details;
+        self
+    }
+
     /// Get the full exception chain
     pub fn exception_chain(&self) -> Vec<String> {
         let mut chain = vec![format!("{}: {}", self.exception_type, self.message)];
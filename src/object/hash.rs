@@ -25,6 +25,10 @@ impl ObjectHash {
             Object::Float(f) => Some(Self {
                 hash_value: f.to_string(),
             }),
+            // Equal Decimals hash alike whatever their scale, and whole ones like Ints
+            Object::Decimal(d) => Some(Self {
+                hash_value: d.normalize().to_string(),
+            }),
             Object::String(s) => Some(Self {
                 hash_value: s.to_string(),
             }),
@@ -1,10 +1,15 @@
 // Method struct - represents a class method (bound or unbound)
 
-use crate::ast::Statement;
+use crate::ast::{FORWARDING_PARAMETER, Parameter, Statement, TypeAnnotation};
 use crate::callable::Callable;
 use crate::error::SourceLocation;
+use crate::lexer::{Lexer, Position, Span};
+use crate::parser::Parser;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
 
-use super::Object;
+use super::{BlockStatement, Object};
 
 /// Method definition (function bound to a class)
 #[derive(Debug, Clone, PartialEq)]
@@ -21,6 +26,22 @@ pub struct Method {
     pub owner: Option<String>,
     /// Source location where the method is defined
     pub source_location: Option<SourceLocation>,
+    /// Source text of the file or input the method was defined in
+    pub source: Option<Rc<str>>,
+    /// Declared parameter types, one per parameter, when any were written
+    pub parameter_types: Vec<Option<TypeAnnotation>>,
+    /// Declared result type (`-> Int`)
+    pub return_type: Option<TypeAnnotation>,
+    /// Variables captured by the block the method was defined from
+    pub captured_vars: HashMap<String, Rc<RefCell<Object>>>,
+    /// Conditions checked against the arguments before each call (`pre`)
+    pub preconditions: Vec<Rc<Method>>,
+    /// Conditions checked against the result and arguments after each call
+    /// (`post`)
+    pub postconditions: Vec<Rc<Method>>,
+    /// Full names of the classes and modules the method was defined inside,
+    /// outermost first, for looking up constants lexically
+    pub nesting: Vec<String>,
 }
 
 impl Method {
@@ -33,6 +54,13 @@ impl Method {
             receiver: None,
             owner: None,
             source_location: None,
+            source: None,
+            parameter_types: Vec::new(),
+            return_type: None,
+            captured_vars: HashMap::new(),
+            preconditions: Vec::new(),
+            postconditions: Vec::new(),
+            nesting: Vec::new(),
         }
     }
 
@@ -50,6 +78,13 @@ impl Method {
             receiver: None,
             owner: Some(owner),
             source_location: None,
+            source: None,
+            parameter_types: Vec::new(),
+            return_type: None,
+            captured_vars: HashMap::new(),
+            preconditions: Vec::new(),
+            postconditions: Vec::new(),
+            nesting: Vec::new(),
         }
     }
 
@@ -67,6 +102,13 @@ impl Method {
             receiver: None,
             owner: None,
             source_location: Some(source_location),
+            source: None,
+            parameter_types: Vec::new(),
+            return_type: None,
+            captured_vars: HashMap::new(),
+            preconditions: Vec::new(),
+            postconditions: Vec::new(),
+            nesting: Vec::new(),
         }
     }
 
@@ -85,9 +127,29 @@ impl Method {
             receiver: None,
             owner: Some(owner),
             source_location: Some(source_location),
+            source: None,
+            parameter_types: Vec::new(),
+            return_type: None,
+            captured_vars: HashMap::new(),
+            preconditions: Vec::new(),
+            postconditions: Vec::new(),
+            nesting: Vec::new(),
         }
     }
 
+    /// Create a method from a block (`define_method`), keeping the
+    /// variables the block captured
+    pub fn from_block(name: String, block: &BlockStatement, owner: String) -> Self {
+        let mut method = Self::with_owner(
+            name,
+            block.parameters().to_vec(),
+            block.body().to_vec(),
+            owner,
+        );
+        method.captured_vars = block.captured_vars().clone();
+        method
+    }
+
     /// Bind this method to a receiver
     pub fn bind(&self, receiver: Object) -> Self {
         Self {
@@ -97,6 +159,13 @@ impl Method {
             receiver: Some(Box::new(receiver)),
             owner: self.owner.clone(),
             source_location: self.source_location.clone(),
+            source: self.source.clone(),
+            parameter_types: self.parameter_types.clone(),
+            return_type: self.return_type.clone(),
+            captured_vars: self.captured_vars.clone(),
+            preconditions: self.preconditions.clone(),
+            postconditions: self.postconditions.clone(),
+            nesting: self.nesting.clone(),
         }
     }
 
@@ -109,6 +178,74 @@ impl Method {
     pub fn receiver(&self) -> Option<&Object> {
         self.receiver.as_deref()
     }
+
+    /// Byte span of the definition within `source`, from `def` through its `end`
+    pub fn source_span(&self) -> Option<Span> {
+        let source = self.source.as_deref()?;
+        let start = self.source_location.as_ref()?.offset;
+        let tokens = Lexer::new(source.get(start..)?).tokenize();
+        match Parser::new(tokens).parse_first_statement() {
+            Ok((Statement::FunctionDef { .. } | Statement::MethodDef { .. }, span)) => {
+                Some(Span::new(start + span.start, start + span.end))
+            }
+            _ => None,
+        }
+    }
+
+    /// The definition exactly as it was written, if its source is known
+    pub fn source_text(&self) -> Option<&str> {
+        let span = self.source_span()?;
+        self.source.as_deref().map(|source| span.slice(source))
+    }
+
+    /// The definition as an AST node, rebuilt from the method's name,
+    /// parameters and body
+    pub fn definition(&self) -> Statement {
+        let position = self
+            .source_location
+            .as_ref()
+            .map(|location| Position::new(location.line, location.column, location.offset))
+            .unwrap_or_default();
+        Statement::FunctionDef {
+            name: self.name.clone(),
+            parameters: self
+                .parameters
+                .iter()
+                .enumerate()
+                .map(|(index, name)| {
+                    let parameter = Parameter::simple(name.clone(), position);
+                    match self.parameter_type(index) {
+                        Some(annotation) => parameter.with_type(annotation.clone()),
+                        None => parameter,
+                    }
+                })
+                .collect(),
+            body: self.body.clone(),
+            position,
+            return_type: self.return_type.clone(),
+        }
+    }
+
+    /// The declared type of the parameter at `index`, if one was written
+    pub fn parameter_type(&self, index: usize) -> Option<&TypeAnnotation> {
+        self.parameter_types.get(index).and_then(Option::as_ref)
+    }
+
+    /// Check if this method collects its trailing arguments with `...`
+    pub fn forwards_arguments(&self) -> bool {
+        self.parameters
+            .last()
+            .is_some_and(|param| param == FORWARDING_PARAMETER)
+    }
+
+    /// Number of arguments that must be passed explicitly
+    pub fn required_arity(&self) -> usize {
+        if self.forwards_arguments() {
+            self.parameters.len() - 1
+        } else {
+            self.parameters.len()
+        }
+    }
 }
 
 impl Callable for Method {
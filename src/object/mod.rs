@@ -5,23 +5,32 @@
 mod binding;
 mod block;
 mod constructors;
+mod convert;
+mod deserialize;
 mod display;
 mod exception;
 mod hash;
 mod instance;
 mod method;
 mod operations;
+mod serialize;
 mod types;
 
 // Re-export core types and traits
 pub use binding::Binding;
 pub use block::BlockStatement;
+pub use convert::{ConversionError, FromMetorex, field};
+pub use deserialize::{DeserializeError, from_object};
 pub use exception::{Exception, SourceLocation};
 pub use hash::ObjectHash;
 pub use instance::Instance;
 pub use method::Method;
+pub use serialize::{SerializeError, to_object};
 pub use types::Object;
 
+// Decimal values and their rounding modes come from rust_decimal
+pub use rust_decimal::{Decimal, RoundingStrategy};
+
 // Re-export from callable and class modules
 pub use crate::callable::Callable;
 pub use crate::class::Class;
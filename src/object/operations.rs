@@ -1,6 +1,6 @@
 // Object operations - comparison and boolean logic
 
-use super::Object;
+use super::{Decimal, Object};
 
 impl Object {
     /// Check if this object is truthy (for conditional evaluation)
@@ -28,7 +28,14 @@ impl Object {
                 // Float comparison with epsilon for floating point precision
                 (a - b).abs() < 1e-9
             }
+            // Decimals compare by value, so 1.50 equals 1.5 and 2.00 equals 2
+            (Object::Decimal(a), Object::Decimal(b)) => a == b,
+            (Object::Decimal(a), Object::Int(b)) | (Object::Int(b), Object::Decimal(a)) => {
+                *a == Decimal::from(*b)
+            }
             (Object::String(a), Object::String(b)) => a == b,
+            // The VM interns symbols; ones built by host code compare by name
+            (Object::Symbol(a), Object::Symbol(b)) => Rc::ptr_eq(a, b) || a == b,
             (Object::Array(a), Object::Array(b)) => {
                 let arr_a = a.borrow();
                 let arr_b = b.borrow();
@@ -63,6 +70,7 @@ impl Object {
             // Instance, Class, Method, Block, Binding, and Exception comparisons by reference
             (Object::Instance(a), Object::Instance(b)) => Rc::ptr_eq(a, b),
             (Object::Class(a), Object::Class(b)) => Rc::ptr_eq(a, b),
+            (Object::Module(a), Object::Module(b)) => Rc::ptr_eq(a, b),
             (Object::Method(a), Object::Method(b)) => Rc::ptr_eq(a, b),
             (Object::Block(a), Object::Block(b)) => Rc::ptr_eq(a, b),
             (Object::Binding(a), Object::Binding(b)) => Rc::ptr_eq(a, b),
@@ -99,6 +107,11 @@ impl Object {
                 f.to_bits().hash(&mut hasher);
                 Some(hasher.finish())
             }
+            Object::Decimal(d) => {
+                let mut hasher = DefaultHasher::new();
+                d.normalize().hash(&mut hasher);
+                Some(hasher.finish())
+            }
             Object::String(s) => {
                 let mut hasher = DefaultHasher::new();
                 s.as_str().hash(&mut hasher);
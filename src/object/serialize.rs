@@ -0,0 +1,539 @@
+// Conversion of serializable Rust values into Metorex values
+//
+// Tooling that hands Rust data to scripts (method ASTs, host records) goes
+// through `to_object`, which maps serde's data model onto Metorex values:
+// sequences become arrays, structs and maps become dicts, unit enum variants
+// become symbols and other variants become dicts tagged with a "type" symbol.
+
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde::ser::{self, Impossible};
+use std::fmt;
+
+use super::Object;
+
+/// A value that has no Metorex representation, such as a map with array keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerializeError(String);
+
+impl fmt::Display for SerializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for SerializeError {}
+
+impl ser::Error for SerializeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+/// Convert any serializable value into a Metorex value.
+pub fn to_object<T: Serialize + ?Sized>(value: &T) -> Result<Object, SerializeError> {
+    value.serialize(ObjectSerializer)
+}
+
+/// Dict for an enum variant, tagged with its name under "type".
+fn tagged(variant: &str) -> IndexMap<String, Object> {
+    let mut map = IndexMap::new();
+    map.insert(
+        "type".to_string(),
+        Object::Symbol(variant.to_string().into()),
+    );
+    map
+}
+
+struct ObjectSerializer;
+
+impl ser::Serializer for ObjectSerializer {
+    type Ok = Object;
+    type Error = SerializeError;
+    type SerializeSeq = SeqBuilder;
+    type SerializeTuple = SeqBuilder;
+    type SerializeTupleStruct = SeqBuilder;
+    type SerializeTupleVariant = SeqBuilder;
+    type SerializeMap = DictBuilder;
+    type SerializeStruct = DictBuilder;
+    type SerializeStructVariant = DictBuilder;
+
+    fn serialize_bool(self, v: bool) -> Result<Object, SerializeError> {
+        Ok(Object::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Object, SerializeError> {
+        Ok(Object::Int(v.into()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Object, SerializeError> {
+        Ok(Object::Int(v.into()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Object, SerializeError> {
+        Ok(Object::Int(v.into()))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Object, SerializeError> {
+        Ok(Object::Int(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Object, SerializeError> {
+        Ok(Object::Int(v.into()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Object, SerializeError> {
+        Ok(Object::Int(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Object, SerializeError> {
+        Ok(Object::Int(v.into()))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Object, SerializeError> {
+        i64::try_from(v)
+            .map(Object::Int)
+            .map_err(|_| SerializeError(format!("{} does not fit in an Int", v)))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Object, SerializeError> {
+        Ok(Object::Float(v.into()))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Object, SerializeError> {
+        Ok(Object::Float(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Object, SerializeError> {
+        Ok(Object::string(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Object, SerializeError> {
+        Ok(Object::string(v))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Object, SerializeError> {
+        Ok(Object::array(
+            v.iter().map(|byte| Object::Int((*byte).into())).collect(),
+        ))
+    }
+
+    fn serialize_none(self) -> Result<Object, SerializeError> {
+        Ok(Object::Nil)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Object, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Object, SerializeError> {
+        Ok(Object::Nil)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Object, SerializeError> {
+        Ok(Object::Nil)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Object, SerializeError> {
+        Ok(Object::Symbol(variant.to_string().into()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Object, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Object, SerializeError> {
+        let mut map = tagged(variant);
+        map.insert("value".to_string(), to_object(value)?);
+        Ok(Object::dict(map))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqBuilder, SerializeError> {
+        Ok(SeqBuilder {
+            variant: None,
+            elements: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqBuilder, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SeqBuilder, SerializeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SeqBuilder, SerializeError> {
+        Ok(SeqBuilder {
+            variant: Some(variant),
+            elements: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<DictBuilder, SerializeError> {
+        Ok(DictBuilder {
+            entries: IndexMap::new(),
+            pending_key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<DictBuilder, SerializeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<DictBuilder, SerializeError> {
+        Ok(DictBuilder {
+            entries: tagged(variant),
+            pending_key: None,
+        })
+    }
+}
+
+/// Collects arrays, tuples and tuple variants.
+struct SeqBuilder {
+    variant: Option<&'static str>,
+    elements: Vec<Object>,
+}
+
+impl SeqBuilder {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerializeError> {
+        self.elements.push(to_object(value)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Object, SerializeError> {
+        let array = Object::array(self.elements);
+        Ok(match self.variant {
+            Some(variant) => {
+                let mut map = tagged(variant);
+                map.insert("values".to_string(), array);
+                Object::dict(map)
+            }
+            None => array,
+        })
+    }
+}
+
+impl ser::SerializeSeq for SeqBuilder {
+    type Ok = Object;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Object, SerializeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SeqBuilder {
+    type Ok = Object;
+    type Error = SerializeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Object, SerializeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqBuilder {
+    type Ok = Object;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Object, SerializeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SeqBuilder {
+    type Ok = Object;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Object, SerializeError> {
+        self.finish()
+    }
+}
+
+/// Collects maps, structs and struct variants.
+struct DictBuilder {
+    entries: IndexMap<String, Object>,
+    pending_key: Option<String>,
+}
+
+impl ser::SerializeMap for DictBuilder {
+    type Ok = Object;
+    type Error = SerializeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+        self.pending_key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let key = self
+            .pending_key
+            .take()
+            .ok_or_else(|| SerializeError("map value without a key".to_string()))?;
+        self.entries.insert(key, to_object(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Object, SerializeError> {
+        Ok(Object::dict(self.entries))
+    }
+}
+
+impl ser::SerializeStruct for DictBuilder {
+    type Ok = Object;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.entries.insert(key.to_string(), to_object(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Object, SerializeError> {
+        Ok(Object::dict(self.entries))
+    }
+}
+
+impl ser::SerializeStructVariant for DictBuilder {
+    type Ok = Object;
+    type Error = SerializeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.entries.insert(key.to_string(), to_object(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Object, SerializeError> {
+        Ok(Object::dict(self.entries))
+    }
+}
+
+/// Turns map keys into dict keys; only strings and scalars are accepted.
+struct KeySerializer;
+
+impl KeySerializer {
+    fn scalar(value: impl fmt::Display) -> Result<String, SerializeError> {
+        Ok(value.to_string())
+    }
+
+    fn unsupported(kind: &str) -> SerializeError {
+        SerializeError(format!("{} cannot be used as a dict key", kind))
+    }
+}
+
+impl ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = SerializeError;
+    type SerializeSeq = Impossible<String, SerializeError>;
+    type SerializeTuple = Impossible<String, SerializeError>;
+    type SerializeTupleStruct = Impossible<String, SerializeError>;
+    type SerializeTupleVariant = Impossible<String, SerializeError>;
+    type SerializeMap = Impossible<String, SerializeError>;
+    type SerializeStruct = Impossible<String, SerializeError>;
+    type SerializeStructVariant = Impossible<String, SerializeError>;
+
+    fn serialize_bool(self, v: bool) -> Result<String, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<String, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<String, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<String, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<String, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<String, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<String, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<String, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<String, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<String, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<String, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, SerializeError> {
+        Self::scalar(v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<String, SerializeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, SerializeError> {
+        Err(Self::unsupported("bytes"))
+    }
+
+    fn serialize_none(self) -> Result<String, SerializeError> {
+        Err(Self::unsupported("nil"))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<String, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<String, SerializeError> {
+        Err(Self::unsupported("nil"))
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<String, SerializeError> {
+        Err(Self::unsupported(name))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<String, SerializeError> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, SerializeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, SerializeError> {
+        Err(Self::unsupported(name))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, SerializeError> {
+        Err(Self::unsupported("an array"))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, SerializeError> {
+        Err(Self::unsupported("a tuple"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, SerializeError> {
+        Err(Self::unsupported(name))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerializeError> {
+        Err(Self::unsupported(name))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SerializeError> {
+        Err(Self::unsupported("a dict"))
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, SerializeError> {
+        Err(Self::unsupported(name))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, SerializeError> {
+        Err(Self::unsupported(name))
+    }
+}
@@ -1,8 +1,11 @@
 // Core Object enum definition for runtime value representation
 
 use crate::class::Class;
+use crate::module::Module;
+use indexmap::IndexMap;
+use rust_decimal::Decimal;
 use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 use std::rc::Rc;
 
 use super::{Binding, BlockStatement, Exception, Instance, Method, ObjectHash};
@@ -22,6 +25,9 @@ pub enum Object {
     /// 64-bit floating point number
     Float(f64),
 
+    /// Exact decimal number, for money and other values Floats can't hold
+    Decimal(Decimal),
+
     /// String value (reference counted for efficient copying)
     String(Rc<String>),
 
@@ -31,8 +37,8 @@ pub enum Object {
     /// Array/list of objects (mutable, reference counted)
     Array(Rc<RefCell<Vec<Object>>>),
 
-    /// Dictionary/hash map (mutable, reference counted)
-    Dict(Rc<RefCell<HashMap<String, Object>>>),
+    /// Dictionary/hash map (mutable, reference counted, insertion ordered)
+    Dict(Rc<RefCell<IndexMap<String, Object>>>),
 
     /// Instance of a class
     Instance(Rc<RefCell<Instance>>),
@@ -40,6 +46,9 @@ pub enum Object {
     /// Class object (used for class definitions and instantiation)
     Class(Rc<Class>),
 
+    /// Module object (methods that classes mix in with `include` or `extend`)
+    Module(Rc<Module>),
+
     /// Method object (bound or unbound)
     Method(Rc<Method>),
 
@@ -77,12 +86,14 @@ impl Object {
             Object::Bool(_) => "Bool",
             Object::Int(_) => "Int",
             Object::Float(_) => "Float",
+            Object::Decimal(_) => "Decimal",
             Object::String(_) => "String",
             Object::Symbol(_) => "Symbol",
             Object::Array(_) => "Array",
             Object::Dict(_) => "Dict",
             Object::Instance(_) => "Instance",
             Object::Class(_) => "Class",
+            Object::Module(_) => "Module",
             Object::Method(_) => "Method",
             Object::Block(_) => "Block",
             Object::Exception(_) => "Exception",
@@ -3,6 +3,8 @@
 
 use crate::error::{MetorexError, SourceLocation};
 use crate::lexer::{Position, Token};
+use crate::messages::Message;
+use std::fmt::Display;
 
 /// Error handling state for the parser
 pub struct ErrorHandler {
@@ -36,16 +38,15 @@ impl ErrorHandler {
         SourceLocation::new(position.line, position.column, position.offset)
     }
 
-    /// Create an error at the current token
-    pub fn error_at_current(&self, message: &str, current_token: &Token) -> MetorexError {
-        let location = self.position_to_location(current_token.position);
-        MetorexError::syntax_error(message, location)
-    }
-
-    /// Create an error at the previous token
-    pub fn error_at_previous(&self, message: &str, previous_token: &Token) -> MetorexError {
-        let location = self.position_to_location(previous_token.position);
-        MetorexError::syntax_error(message, location)
+    /// Create an error with the catalog text of `message` at a token
+    pub fn error_at(
+        &self,
+        message: &'static Message,
+        args: &[(&str, &dyn Display)],
+        token: &Token,
+    ) -> MetorexError {
+        let location = self.position_to_location(token.position);
+        MetorexError::syntax_error(message.format(args), location).with_catalog_message(message)
     }
 
     /// Report an error and enter panic mode
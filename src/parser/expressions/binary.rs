@@ -1,128 +1,90 @@
 // Binary operator parsing
-// Handles parsing of binary operations with proper precedence
+// A Pratt parser driven by the precedence table in `parser::precedence`
 
-use crate::ast::{BinaryOp, Expression};
+use crate::ast::{Expression, UnaryOp};
 use crate::error::MetorexError;
-use crate::lexer::TokenKind;
+use crate::lexer::{Position, TokenKind};
+use crate::messages;
 use crate::parser::Parser;
+use crate::parser::precedence::{
+    Associativity, InfixKind, InfixOperator, NOT_OPERAND, infix_operator,
+};
 
 impl Parser {
-    /// Parse equality operators (==, !=)
-    pub(crate) fn parse_equality(&mut self) -> Result<Expression, MetorexError> {
-        let mut expr = self.parse_comparison()?;
-
-        while self.check(&[TokenKind::EqualEqual, TokenKind::BangEqual]) {
-            let op_token = self.advance();
-            let op = match op_token.kind {
-                TokenKind::EqualEqual => BinaryOp::Equal,
-                TokenKind::BangEqual => BinaryOp::NotEqual,
-                _ => unreachable!(),
-            };
-            let right = self.parse_comparison()?;
-            expr = Expression::BinaryOp {
-                op,
-                left: Box::new(expr),
-                right: Box::new(right),
-                position: op_token.position,
-            };
-        }
-
-        Ok(expr)
+    /// Parse a chain of infix operators (lowest precedence: `and`, `or`)
+    pub(crate) fn parse_binary(&mut self) -> Result<Expression, MetorexError> {
+        self.parse_binary_above(0)
     }
 
-    /// Parse comparison operators (<, >, <=, >=)
-    pub(crate) fn parse_comparison(&mut self) -> Result<Expression, MetorexError> {
-        let mut expr = self.parse_range()?;
+    /// Parse operands joined by operators binding at least as tightly as
+    /// `min_precedence`
+    fn parse_binary_above(&mut self, min_precedence: u8) -> Result<Expression, MetorexError> {
+        let mut expr = self.parse_not_operand()?;
+        // Precedence of the last non-associative operator applied at this level
+        let mut closed = None;
+
+        while let Some(operator) = infix_operator(&self.peek().kind) {
+            if operator.precedence < min_precedence {
+                break;
+            }
+            if closed == Some(operator.precedence) {
+                return Err(self.error_at_current_with(
+                    &messages::OPERATOR_CANNOT_BE_CHAINED,
+                    &[("operator", &operator.symbol)],
+                ));
+            }
 
-        while self.check(&[
-            TokenKind::Less,
-            TokenKind::Greater,
-            TokenKind::LessEqual,
-            TokenKind::GreaterEqual,
-        ]) {
             let op_token = self.advance();
-            let op = match op_token.kind {
-                TokenKind::Less => BinaryOp::Less,
-                TokenKind::Greater => BinaryOp::Greater,
-                TokenKind::LessEqual => BinaryOp::LessEqual,
-                TokenKind::GreaterEqual => BinaryOp::GreaterEqual,
-                _ => unreachable!(),
-            };
-            let right = self.parse_range()?;
-            expr = Expression::BinaryOp {
-                op,
-                left: Box::new(expr),
-                right: Box::new(right),
-                position: op_token.position,
+            let right_precedence = match operator.associativity {
+                Associativity::Right => operator.precedence,
+                Associativity::Left | Associativity::NonAssociative => operator.precedence + 1,
             };
-        }
-
-        Ok(expr)
-    }
+            let right = self.parse_binary_above(right_precedence)?;
+            expr = build_infix(operator, expr, right, op_token.position);
 
-    /// Parse range operators (.., ...)
-    pub(crate) fn parse_range(&mut self) -> Result<Expression, MetorexError> {
-        let mut expr = self.parse_term()?;
-
-        if self.check(&[TokenKind::DotDot, TokenKind::DotDotDot]) {
-            let op_token = self.advance();
-            let exclusive = op_token.kind == TokenKind::DotDotDot;
-            let end = self.parse_term()?;
-            expr = Expression::Range {
-                start: Box::new(expr),
-                end: Box::new(end),
-                exclusive,
-                position: op_token.position,
-            };
+            if operator.associativity == Associativity::NonAssociative {
+                closed = Some(operator.precedence);
+            }
         }
 
         Ok(expr)
     }
 
-    /// Parse addition and subtraction
-    pub(crate) fn parse_term(&mut self) -> Result<Expression, MetorexError> {
-        let mut expr = self.parse_factor()?;
-
-        while self.check(&[TokenKind::Plus, TokenKind::Minus]) {
-            let op_token = self.advance();
-            let op = match op_token.kind {
-                TokenKind::Plus => BinaryOp::Add,
-                TokenKind::Minus => BinaryOp::Subtract,
-                _ => unreachable!(),
-            };
-            let right = self.parse_factor()?;
-            expr = Expression::BinaryOp {
-                op,
-                left: Box::new(expr),
-                right: Box::new(right),
-                position: op_token.position,
-            };
+    /// Parse an operand, which may be a `not` applied to a whole comparison
+    /// or `||` chain
+    fn parse_not_operand(&mut self) -> Result<Expression, MetorexError> {
+        if !self.check(&[TokenKind::Not]) {
+            return self.parse_unary();
         }
-
-        Ok(expr)
+        let position = self.advance().position;
+        let operand = self.parse_binary_above(NOT_OPERAND)?;
+        Ok(Expression::UnaryOp {
+            op: UnaryOp::Not,
+            operand: Box::new(operand),
+            position,
+        })
     }
+}
 
-    /// Parse multiplication, division, and modulo
-    pub(crate) fn parse_factor(&mut self) -> Result<Expression, MetorexError> {
-        let mut expr = self.parse_unary()?;
-
-        while self.check(&[TokenKind::Star, TokenKind::Slash, TokenKind::Percent]) {
-            let op_token = self.advance();
-            let op = match op_token.kind {
-                TokenKind::Star => BinaryOp::Multiply,
-                TokenKind::Slash => BinaryOp::Divide,
-                TokenKind::Percent => BinaryOp::Modulo,
-                _ => unreachable!(),
-            };
-            let right = self.parse_unary()?;
-            expr = Expression::BinaryOp {
-                op,
-                left: Box::new(expr),
-                right: Box::new(right),
-                position: op_token.position,
-            };
-        }
-
-        Ok(expr)
+/// The expression `operator` builds from its operands
+fn build_infix(
+    operator: &InfixOperator,
+    left: Expression,
+    right: Expression,
+    position: Position,
+) -> Expression {
+    match &operator.kind {
+        InfixKind::Binary(op) => Expression::BinaryOp {
+            op: op.clone(),
+            left: Box::new(left),
+            right: Box::new(right),
+            position,
+        },
+        InfixKind::Range { exclusive } => Expression::Range {
+            start: Box::new(left),
+            end: Box::new(right),
+            exclusive: *exclusive,
+            position,
+        },
     }
 }
@@ -4,7 +4,9 @@
 use crate::ast::Expression;
 use crate::error::MetorexError;
 use crate::lexer::TokenKind;
+use crate::messages;
 use crate::parser::Parser;
+use crate::parser::precedence::infix_operator;
 
 impl Parser {
     /// Parse function calls and method calls
@@ -18,9 +20,10 @@ impl Parser {
             } else if self.match_token(&[TokenKind::Dot]) {
                 // Method call
                 let method_name = match self.advance().kind {
-                    TokenKind::Ident(name) => name,
+                    TokenKind::Ident(name) => name.to_string(),
                     // Allow keywords as method names (e.g., obj.class, obj.if, etc.)
                     TokenKind::Class => "class".to_string(),
+                    TokenKind::Module => "module".to_string(),
                     TokenKind::If => "if".to_string(),
                     TokenKind::Def => "def".to_string(),
                     TokenKind::End => "end".to_string(),
@@ -42,7 +45,14 @@ impl Parser {
                     TokenKind::Super => "super".to_string(),
                     TokenKind::Case => "case".to_string(),
                     TokenKind::When => "when".to_string(),
-                    _ => return Err(self.error_at_previous("Expected method name after '.'")),
+                    TokenKind::And => "and".to_string(),
+                    TokenKind::Or => "or".to_string(),
+                    TokenKind::Not => "not".to_string(),
+                    _ => {
+                        return Err(
+                            self.error_at_previous(&messages::EXPECTED_METHOD_NAME_AFTER_DOT)
+                        );
+                    }
                 };
 
                 // Check if there are arguments
@@ -69,16 +79,54 @@ impl Parser {
                     trailing_block,
                     position,
                 };
+            } else if self.match_token(&[TokenKind::ColonColon]) {
+                // Scoped constant or method (e.g., Float::INFINITY)
+                let name = match self.advance().kind {
+                    TokenKind::Ident(name) => name.to_string(),
+                    _ => {
+                        return Err(
+                            self.error_at_previous(&messages::EXPECTED_NAME_AFTER_DOUBLE_COLON)
+                        );
+                    }
+                };
+                let arguments = if self.match_token(&[TokenKind::LParen]) {
+                    self.parse_arguments()?
+                } else {
+                    Vec::new()
+                };
+
+                let position = expr.position();
+                expr = Expression::MethodCall {
+                    receiver: Box::new(expr),
+                    method: name,
+                    arguments,
+                    trailing_block: None,
+                    position,
+                };
             } else if self.match_token(&[TokenKind::LBracket]) {
                 // Array indexing
                 let index = self.parse_expression()?;
-                self.expect(TokenKind::RBracket, "Expected ']' after array index")?;
+                self.expect(
+                    TokenKind::RBracket,
+                    &messages::EXPECTED_RBRACKET_AFTER_ARRAY_INDEX,
+                )?;
                 let position = expr.position();
                 expr = Expression::Index {
                     array: Box::new(expr),
                     index: Box::new(index),
                     position,
                 };
+            } else if self.check_block_only_call(&expr) {
+                // Ruby-style call whose only argument is a brace block
+                // (e.g., `pre { |x| x > 0 }`)
+                let block = self.parse_brace_block()?;
+                let position = expr.position();
+                expr = Expression::Call {
+                    callee: Box::new(expr),
+                    arguments: Vec::new(),
+                    trailing_block: Some(Box::new(block)),
+                    position,
+                };
             } else if self.can_start_argument_for_call(&expr) {
                 // Ruby-style function call without parentheses
                 // Only parse this if we have an identifier as the callee
@@ -121,6 +169,7 @@ impl Parser {
     /// Parse function/method arguments (with parentheses)
     pub(crate) fn parse_arguments(&mut self) -> Result<Vec<Expression>, MetorexError> {
         let mut arguments = Vec::new();
+        let mut keywords = Vec::new();
         self.skip_whitespace();
 
         if self.check(&[TokenKind::RParen]) {
@@ -130,7 +179,11 @@ impl Parser {
 
         loop {
             self.skip_whitespace();
-            arguments.push(self.parse_expression()?);
+            if self.check_keyword_argument() {
+                keywords.push(self.parse_keyword_argument(Self::parse_expression)?);
+            } else {
+                arguments.push(self.parse_argument()?);
+            }
             self.skip_whitespace();
 
             if !self.match_token(&[TokenKind::Comma]) {
@@ -139,14 +192,57 @@ impl Parser {
         }
 
         self.skip_whitespace();
-        self.expect(TokenKind::RParen, "Expected ')' after arguments")?;
+        self.expect(
+            TokenKind::RParen,
+            &messages::EXPECTED_RPAREN_AFTER_ARGUMENTS,
+        )?;
 
+        push_keyword_arguments(&mut arguments, keywords);
         Ok(arguments)
     }
 
+    /// Parse a single positional argument, including `...` to forward the
+    /// arguments received by the enclosing method
+    pub(crate) fn parse_argument(&mut self) -> Result<Expression, MetorexError> {
+        if self.check(&[TokenKind::DotDotDot]) {
+            let position = self.advance().position;
+            return Ok(Expression::ForwardedArgs { position });
+        }
+
+        self.parse_expression()
+    }
+
+    /// Check if the next tokens form a keyword argument (`name: value`)
+    fn check_keyword_argument(&self) -> bool {
+        matches!(self.peek().kind, TokenKind::Ident(_))
+            && matches!(self.peek_ahead(1).kind, TokenKind::Colon)
+    }
+
+    /// Parse a keyword argument (`name: value`) into a symbol key and its value
+    fn parse_keyword_argument(
+        &mut self,
+        parse_value: fn(&mut Self) -> Result<Expression, MetorexError>,
+    ) -> Result<(Expression, Expression), MetorexError> {
+        let token = self.advance();
+        let key = match token.kind {
+            TokenKind::Ident(name) => Expression::Symbol {
+                value: name.to_string(),
+                position: token.position,
+            },
+            _ => return Err(self.error_at_previous(&messages::EXPECTED_KEYWORD_ARGUMENT_NAME)),
+        };
+        self.expect(
+            TokenKind::Colon,
+            &messages::EXPECTED_COLON_AFTER_KEYWORD_ARGUMENT_NAME,
+        )?;
+        self.skip_whitespace();
+        let value = parse_value(self)?;
+        Ok((key, value))
+    }
+
     /// Check if the next token can start an argument in a parentheses-less call
     /// Also checks if this looks like a dictionary context (value followed by colon)
-    fn can_start_argument_for_call(&mut self, _callee: &Expression) -> bool {
+    fn can_start_argument_for_call(&mut self, callee: &Expression) -> bool {
         // Don't skip whitespace yet - we need to check if there's a newline first
         // Parentheses-less calls should be on the same line
         if matches!(self.peek().kind, TokenKind::Newline | TokenKind::Comment(_)) {
@@ -155,27 +251,22 @@ impl Parser {
 
         self.skip_whitespace();
 
+        if self.check_symbol_argument(callee) {
+            return true;
+        }
+
+        if self.check_from_keyword() {
+            return false;
+        }
+
         // Don't parse as function call if we see operators or punctuation that
         // indicate we're in a different context (like dictionary key: value)
         // Also check for binary operators that shouldn't start an argument
         if matches!(
             self.peek().kind,
-            TokenKind::Colon
-                | TokenKind::RBrace
-                | TokenKind::Comma
-                | TokenKind::Plus
-                | TokenKind::Minus
-                | TokenKind::Star
-                | TokenKind::Slash
-                | TokenKind::Percent
-                | TokenKind::Equal
-                | TokenKind::EqualEqual
-                | TokenKind::BangEqual
-                | TokenKind::Less
-                | TokenKind::Greater
-                | TokenKind::LessEqual
-                | TokenKind::GreaterEqual
-        ) {
+            TokenKind::Colon | TokenKind::RBrace | TokenKind::Comma | TokenKind::Equal
+        ) || infix_operator(&self.peek().kind).is_some()
+        {
             return false;
         }
 
@@ -220,12 +311,33 @@ impl Parser {
         true
     }
 
+    /// Check if the next token is a symbol argument to a bare function call
+    /// (e.g., `delegate :name`); the lexer has already told the symbol's colon
+    /// apart from the `key: value` syntax of dictionaries
+    fn check_symbol_argument(&self, callee: &Expression) -> bool {
+        matches!(callee, Expression::Identifier { .. })
+            && matches!(self.peek().kind, TokenKind::Symbol(_))
+    }
+
+    /// Check if a bare name is followed by a brace block with parameters; a
+    /// dictionary literal can't start with `|` or `||`, so `name { |x| ... }`
+    /// is always a call
+    fn check_block_only_call(&self, callee: &Expression) -> bool {
+        matches!(callee, Expression::Identifier { .. })
+            && matches!(self.peek().kind, TokenKind::LBrace)
+            && matches!(
+                self.peek_ahead(1).kind,
+                TokenKind::Pipe | TokenKind::PipePipe
+            )
+    }
+
     /// Finish parsing a function call without parentheses (Ruby-style)
     fn finish_call_without_parens(
         &mut self,
         callee: Expression,
     ) -> Result<Expression, MetorexError> {
         let mut arguments = Vec::new();
+        let mut keywords = Vec::new();
         let position = callee.position();
 
         // Parse first argument
@@ -238,9 +350,9 @@ impl Parser {
         if self.check(&[TokenKind::Colon]) {
             // We misidentified this as a function call
             // Return an error - the dictionary parser will handle this correctly
-            return Err(
-                self.error_at_current("Expected function call but found dictionary-like syntax")
-            );
+            return Err(self.error_at_current(
+                &messages::EXPECTED_FUNCTION_CALL_BUT_FOUND_DICTIONARY_LIKE_SYNTAX,
+            ));
         }
 
         // Parse remaining arguments if there are commas
@@ -261,10 +373,15 @@ impl Parser {
                 break;
             }
 
-            arguments.push(self.parse_call()?);
+            if self.check_keyword_argument() {
+                keywords.push(self.parse_keyword_argument(Self::parse_call)?);
+            } else {
+                arguments.push(self.parse_call()?);
+            }
             self.skip_whitespace();
         }
 
+        push_keyword_arguments(&mut arguments, keywords);
         Ok(Expression::Call {
             callee: Box::new(callee),
             arguments,
@@ -273,3 +390,17 @@ impl Parser {
         })
     }
 }
+
+/// Append keyword arguments to a call's arguments as a trailing dictionary
+fn push_keyword_arguments(
+    arguments: &mut Vec<Expression>,
+    keywords: Vec<(Expression, Expression)>,
+) {
+    if let Some((first_key, _)) = keywords.first() {
+        let position = first_key.position();
+        arguments.push(Expression::Dictionary {
+            entries: keywords,
+            position,
+        });
+    }
+}
@@ -6,10 +6,12 @@ mod call;
 mod primary;
 mod unary;
 
-use crate::ast::Expression;
+use crate::ast::{DestructureTarget, DestructuredParameter, Expression, Statement};
 use crate::error::MetorexError;
 use crate::lexer::TokenKind;
+use crate::messages::{self, Message};
 use crate::parser::Parser;
+use crate::resolver::implicit_block_parameters;
 
 impl Parser {
     /// Parse an expression using operator precedence climbing
@@ -40,6 +42,7 @@ impl Parser {
                 parameters: Vec::new(),
                 body,
                 captured_vars: Some(Vec::new()), // Empty vec signals automatic capture
+                destructured: Vec::new(),
                 position: arrow_pos,
             });
         }
@@ -60,8 +63,8 @@ impl Parser {
             if !self.check(&[TokenKind::RParen]) {
                 loop {
                     self.skip_whitespace();
-                    if let TokenKind::Ident(name) = self.peek().kind.clone() {
-                        params.push(name);
+                    if let TokenKind::Ident(name) = &self.peek().kind {
+                        params.push(name.to_string());
                         self.advance();
                     } else {
                         is_param_list = false;
@@ -99,6 +102,7 @@ impl Parser {
                         parameters: params,
                         body,
                         captured_vars: Some(Vec::new()), // Empty vec signals automatic capture
+                        destructured: Vec::new(),
                         position: start_pos,
                     });
                 }
@@ -130,13 +134,15 @@ impl Parser {
                     if let Expression::Identifier { name, .. } = expression.as_ref() {
                         vec![name.clone()]
                     } else {
-                        return Err(
-                            self.error_at_current("Arrow lambda parameters must be identifiers")
-                        );
+                        return Err(self.error_at_current(
+                            &messages::ARROW_LAMBDA_PARAMETERS_MUST_BE_IDENTIFIERS,
+                        ));
                     }
                 }
                 _ => {
-                    return Err(self.error_at_current("Left side of arrow must be parameter(s)"));
+                    return Err(
+                        self.error_at_current(&messages::LEFT_SIDE_OF_ARROW_MUST_BE_PARAMETERS)
+                    );
                 }
             };
 
@@ -151,6 +157,7 @@ impl Parser {
                 parameters,
                 body,
                 captured_vars: Some(Vec::new()), // Empty vec signals automatic capture
+                destructured: Vec::new(),
                 position: expr.position(),
             });
         }
@@ -160,7 +167,7 @@ impl Parser {
 
     /// Parse assignment (lowest precedence)
     pub(crate) fn parse_assignment(&mut self) -> Result<Expression, MetorexError> {
-        self.parse_equality()
+        self.parse_binary()
     }
 
     /// Parse a block: `do |param1, param2| ... end`
@@ -168,36 +175,12 @@ impl Parser {
         let start_pos = self.peek().position;
 
         // Expect 'do' keyword
-        self.expect(TokenKind::Do, "Expected 'do' to start block")?;
+        self.expect(TokenKind::Do, &messages::EXPECTED_DO_TO_START_BLOCK)?;
         self.skip_whitespace();
 
         // Parse block parameters (e.g., |x, y|)
-        let parameters = if self.match_token(&[TokenKind::Pipe]) {
-            let mut params = Vec::new();
-            self.skip_whitespace();
-
-            if !self.check(&[TokenKind::Pipe]) {
-                loop {
-                    self.skip_whitespace();
-                    let param_token = self.advance();
-                    match param_token.kind {
-                        TokenKind::Ident(name) => params.push(name),
-                        _ => return Err(self.error_at_previous("Expected parameter name")),
-                    }
-                    self.skip_whitespace();
-
-                    if !self.match_token(&[TokenKind::Comma]) {
-                        break;
-                    }
-                }
-            }
-
-            self.skip_whitespace();
-            self.expect(TokenKind::Pipe, "Expected '|' after block parameters")?;
-            params
-        } else {
-            Vec::new()
-        };
+        let (has_parameter_list, parameters, destructured) =
+            self.parse_optional_block_parameters(&messages::EXPECTED_PIPE_AFTER_BLOCK_PARAMETERS)?;
 
         self.skip_whitespace();
 
@@ -208,12 +191,15 @@ impl Parser {
             self.skip_whitespace();
         }
 
-        self.expect(TokenKind::End, "Expected 'end' to close block")?;
+        self.expect(TokenKind::End, &messages::EXPECTED_END_TO_CLOSE_BLOCK)?;
+
+        let parameters = block_parameters(parameters, has_parameter_list, &body)?;
 
         Ok(Expression::Lambda {
             parameters,
             body,
             captured_vars: None, // Will be filled by semantic analysis
+            destructured,
             position: start_pos,
         })
     }
@@ -223,36 +209,12 @@ impl Parser {
         let start_pos = self.peek().position;
 
         // Expect '{' to start block
-        self.expect(TokenKind::LBrace, "Expected '{' to start block")?;
+        self.expect(TokenKind::LBrace, &messages::EXPECTED_LBRACE_TO_START_BLOCK)?;
         self.skip_whitespace();
 
         // Parse block parameters (e.g., |x, y|)
-        let parameters = if self.match_token(&[TokenKind::Pipe]) {
-            let mut params = Vec::new();
-            self.skip_whitespace();
-
-            if !self.check(&[TokenKind::Pipe]) {
-                loop {
-                    self.skip_whitespace();
-                    let param_token = self.advance();
-                    match param_token.kind {
-                        TokenKind::Ident(name) => params.push(name),
-                        _ => return Err(self.error_at_previous("Expected parameter name")),
-                    }
-                    self.skip_whitespace();
-
-                    if !self.match_token(&[TokenKind::Comma]) {
-                        break;
-                    }
-                }
-            }
-
-            self.skip_whitespace();
-            self.expect(TokenKind::Pipe, "Expected '|' after block parameters")?;
-            params
-        } else {
-            Vec::new()
-        };
+        let (has_parameter_list, parameters, destructured) =
+            self.parse_optional_block_parameters(&messages::EXPECTED_PIPE_AFTER_BLOCK_PARAMETERS)?;
 
         self.skip_whitespace();
 
@@ -265,13 +227,116 @@ impl Parser {
             self.skip_whitespace();
         }
 
-        self.expect(TokenKind::RBrace, "Expected '}' to close block")?;
+        self.expect(TokenKind::RBrace, &messages::EXPECTED_RBRACE_TO_CLOSE_BLOCK)?;
+
+        let parameters = block_parameters(parameters, has_parameter_list, &body)?;
 
         Ok(Expression::Lambda {
             parameters,
             body,
             captured_vars: None, // Will be filled by semantic analysis
+            destructured,
             position: start_pos,
         })
     }
+
+    /// Parse the parameter list a block may start with: `|a, b|`, or `||`
+    /// for none. Returns whether there was a list, and its parameters.
+    fn parse_optional_block_parameters(
+        &mut self,
+        closing_message: &'static Message,
+    ) -> Result<(bool, Vec<String>, Vec<DestructuredParameter>), MetorexError> {
+        if self.match_token(&[TokenKind::PipePipe]) {
+            return Ok((true, Vec::new(), Vec::new()));
+        }
+        if !self.match_token(&[TokenKind::Pipe]) {
+            return Ok((false, Vec::new(), Vec::new()));
+        }
+        let (parameters, destructured) = self.parse_block_parameters(closing_message)?;
+        Ok((true, parameters, destructured))
+    }
+
+    /// Parse a block parameter list after its opening `|`, through the
+    /// closing `|`. A parenthesized parameter, as in `|(key, value), index|`,
+    /// unpacks the array passed for it when the block is called.
+    fn parse_block_parameters(
+        &mut self,
+        closing_message: &'static Message,
+    ) -> Result<(Vec<String>, Vec<DestructuredParameter>), MetorexError> {
+        let mut parameters = Vec::new();
+        let mut destructured = Vec::new();
+        self.skip_whitespace();
+
+        if !self.check(&[TokenKind::Pipe]) {
+            loop {
+                self.skip_whitespace();
+                if self.match_token(&[TokenKind::LParen]) {
+                    let parameter = DestructuredParameter {
+                        index: parameters.len(),
+                        targets: self.parse_destructure_targets()?,
+                    };
+                    parameters.push(parameter.to_string());
+                    destructured.push(parameter);
+                } else if let TokenKind::Ident(name) = &self.peek().kind {
+                    parameters.push(name.to_string());
+                    self.advance();
+                } else {
+                    return Err(self.error_at_current(&messages::EXPECTED_PARAMETER_NAME));
+                }
+                self.skip_whitespace();
+
+                if !self.match_token(&[TokenKind::Comma]) {
+                    break;
+                }
+            }
+        }
+
+        self.skip_whitespace();
+        self.expect(TokenKind::Pipe, closing_message)?;
+        Ok((parameters, destructured))
+    }
+
+    /// Parse the targets of a parenthesized block parameter after its `(`,
+    /// through the matching `)`
+    fn parse_destructure_targets(&mut self) -> Result<Vec<DestructureTarget>, MetorexError> {
+        let mut targets = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.match_token(&[TokenKind::LParen]) {
+                targets.push(DestructureTarget::Nested(self.parse_destructure_targets()?));
+            } else if let TokenKind::Ident(name) = &self.peek().kind {
+                targets.push(DestructureTarget::Name(name.to_string()));
+                self.advance();
+            } else {
+                return Err(self.error_at_current(&messages::EXPECTED_PARAMETER_NAME));
+            }
+            self.skip_whitespace();
+
+            if !self.match_token(&[TokenKind::Comma]) {
+                break;
+            }
+        }
+
+        self.skip_whitespace();
+        self.expect(
+            TokenKind::RParen,
+            &messages::EXPECTED_RPAREN_AFTER_DESTRUCTURED_PARAMETERS,
+        )?;
+        Ok(targets)
+    }
+}
+
+/// The parameters of a block: those in its parameter list, or the implicit
+/// `_1`, `_2` or `it` its body uses when it has no list
+fn block_parameters(
+    parameters: Vec<String>,
+    has_parameter_list: bool,
+    body: &[Statement],
+) -> Result<Vec<String>, MetorexError> {
+    let implicit = implicit_block_parameters(body, has_parameter_list)?;
+    Ok(if has_parameter_list {
+        parameters
+    } else {
+        implicit
+    })
 }
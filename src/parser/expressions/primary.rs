@@ -1,10 +1,12 @@
 // Primary expression parsing
 // Handles parsing of literals, identifiers, and compound expressions
 
+use super::block_parameters;
 use crate::ast::Expression;
 use crate::ast::node::ExprMatchCase;
 use crate::error::MetorexError;
 use crate::lexer::TokenKind;
+use crate::messages;
 use crate::parser::Parser;
 
 impl Parser {
@@ -34,13 +36,8 @@ impl Parser {
                         crate::lexer::InterpolationPart::Text(text) => {
                             ast_parts.push(crate::ast::node::InterpolationPart::Text(text));
                         }
-                        crate::lexer::InterpolationPart::Expression(expr_str) => {
-                            // Parse the expression string
-                            // For now, we'll create a simple parser for the embedded expression
-                            let expr_lexer = crate::lexer::Lexer::new(&expr_str);
-                            let expr_tokens = expr_lexer.tokenize();
-                            let mut expr_parser = Parser::new(expr_tokens);
-                            let expr = expr_parser.parse_expression()?;
+                        crate::lexer::InterpolationPart::Expression(_, tokens) => {
+                            let expr = Parser::new(tokens).parse_interpolation()?;
                             ast_parts.push(crate::ast::node::InterpolationPart::Expression(
                                 Box::new(expr),
                             ));
@@ -66,34 +63,40 @@ impl Parser {
 
             // Identifiers and variables
             TokenKind::Ident(name) => Ok(Expression::Identifier {
-                name,
+                name: name.to_string(),
                 position: token.position,
             }),
             TokenKind::InstanceVar(name) => Ok(Expression::InstanceVariable {
-                name,
+                name: name.to_string(),
                 position: token.position,
             }),
             TokenKind::ClassVar(name) => Ok(Expression::ClassVariable {
-                name,
+                name: name.to_string(),
                 position: token.position,
             }),
 
-            // Symbol literal (:name)
-            TokenKind::Colon => {
-                let symbol_position = token.position;
-                match self.advance().kind {
-                    TokenKind::Ident(name) => Ok(Expression::Symbol {
-                        value: name,
-                        position: symbol_position,
-                    }),
-                    _ => Err(self.error_at_previous("Expected identifier after ':' for symbol")),
-                }
+            // Symbol literal (:name, :@target, :@@count)
+            TokenKind::Symbol(name) => Ok(Expression::Symbol {
+                value: name.to_string(),
+                position: token.position,
+            }),
+
+            // Begin block used as a value (x = begin ... rescue ... end)
+            TokenKind::Begin => {
+                let block = self.parse_begin_block(token.position)?;
+                Ok(Expression::Begin {
+                    block: Box::new(block),
+                    position: token.position,
+                })
             }
 
             // Grouped expression
             TokenKind::LParen => {
                 let expr = self.parse_expression()?;
-                self.expect(TokenKind::RParen, "Expected ')' after expression")?;
+                self.expect(
+                    TokenKind::RParen,
+                    &messages::EXPECTED_RPAREN_AFTER_EXPRESSION,
+                )?;
                 Ok(Expression::Grouped {
                     expression: Box::new(expr),
                     position: token.position,
@@ -118,7 +121,10 @@ impl Parser {
                 }
 
                 self.skip_whitespace();
-                self.expect(TokenKind::RBracket, "Expected ']' after array elements")?;
+                self.expect(
+                    TokenKind::RBracket,
+                    &messages::EXPECTED_RBRACKET_AFTER_ARRAY_ELEMENTS,
+                )?;
 
                 Ok(Expression::Array {
                     elements,
@@ -143,7 +149,7 @@ impl Parser {
                         } else {
                             self.expect(
                                 TokenKind::Colon,
-                                "Expected ':' or '=>' after dictionary key",
+                                &messages::EXPECTED_COLON_OR_ARROW_AFTER_DICTIONARY_KEY,
                             )?;
                         }
 
@@ -159,7 +165,10 @@ impl Parser {
                 }
 
                 self.skip_whitespace();
-                self.expect(TokenKind::RBrace, "Expected '}' after dictionary entries")?;
+                self.expect(
+                    TokenKind::RBrace,
+                    &messages::EXPECTED_RBRACE_AFTER_DICTIONARY_ENTRIES,
+                )?;
 
                 Ok(Expression::Dictionary {
                     entries,
@@ -176,33 +185,10 @@ impl Parser {
                 self.skip_whitespace();
 
                 // Parse parameters: |param1, param2, ...|
-                let parameters = if self.match_token(&[TokenKind::Pipe]) {
-                    let mut params = Vec::new();
-                    self.skip_whitespace();
-
-                    if !self.check(&[TokenKind::Pipe]) {
-                        loop {
-                            self.skip_whitespace();
-                            if let TokenKind::Ident(name) = self.peek().kind.clone() {
-                                params.push(name);
-                                self.advance();
-                            } else {
-                                return Err(self.error_at_current("Expected parameter name"));
-                            }
-
-                            self.skip_whitespace();
-                            if !self.match_token(&[TokenKind::Comma]) {
-                                break;
-                            }
-                        }
-                    }
-
-                    self.skip_whitespace();
-                    self.expect(TokenKind::Pipe, "Expected '|' after lambda parameters")?;
-                    params
-                } else {
-                    Vec::new()
-                };
+                let (has_parameter_list, parameters, destructured) = self
+                    .parse_optional_block_parameters(
+                        &messages::EXPECTED_PIPE_AFTER_LAMBDA_PARAMETERS,
+                    )?;
 
                 // Parse body statements
                 self.skip_whitespace();
@@ -217,12 +203,15 @@ impl Parser {
                     self.skip_whitespace();
                 }
 
-                self.expect(TokenKind::End, "Expected 'end' after lambda body")?;
+                self.expect(TokenKind::End, &messages::EXPECTED_END_AFTER_LAMBDA_BODY)?;
+
+                let parameters = block_parameters(parameters, has_parameter_list, &body)?;
 
                 Ok(Expression::Lambda {
                     parameters,
                     body,
                     captured_vars: Some(Vec::new()), // Empty vec signals automatic capture
+                    destructured,
                     position: token.position,
                 })
             }
@@ -232,33 +221,10 @@ impl Parser {
                 self.skip_whitespace();
 
                 // Parse optional parameters: |param1, param2, ...|
-                let parameters = if self.match_token(&[TokenKind::Pipe]) {
-                    let mut params = Vec::new();
-                    self.skip_whitespace();
-
-                    if !self.check(&[TokenKind::Pipe]) {
-                        loop {
-                            self.skip_whitespace();
-                            if let TokenKind::Ident(name) = self.peek().kind.clone() {
-                                params.push(name);
-                                self.advance();
-                            } else {
-                                return Err(self.error_at_current("Expected parameter name"));
-                            }
-
-                            self.skip_whitespace();
-                            if !self.match_token(&[TokenKind::Comma]) {
-                                break;
-                            }
-                        }
-                    }
-
-                    self.skip_whitespace();
-                    self.expect(TokenKind::Pipe, "Expected '|' after block parameters")?;
-                    params
-                } else {
-                    Vec::new()
-                };
+                let (has_parameter_list, parameters, destructured) = self
+                    .parse_optional_block_parameters(
+                        &messages::EXPECTED_PIPE_AFTER_BLOCK_PARAMETERS,
+                    )?;
 
                 // Parse body statements
                 self.skip_whitespace();
@@ -273,14 +239,17 @@ impl Parser {
                     self.skip_whitespace();
                 }
 
-                self.expect(TokenKind::End, "Expected 'end' after block body")?;
+                self.expect(TokenKind::End, &messages::EXPECTED_END_AFTER_BLOCK_BODY)?;
 
                 // A standalone block is essentially a lambda with no parameters
                 // that gets evaluated immediately (in this parser representation)
+                let parameters = block_parameters(parameters, has_parameter_list, &body)?;
+
                 Ok(Expression::Lambda {
                     parameters,
                     body,
                     captured_vars: Some(Vec::new()), // Empty vec signals automatic capture
+                    destructured,
                     position: token.position,
                 })
             }
@@ -299,7 +268,7 @@ impl Parser {
                     if !self.check(&[TokenKind::RParen]) {
                         loop {
                             self.skip_whitespace();
-                            args.push(self.parse_expression()?);
+                            args.push(self.parse_argument()?);
                             self.skip_whitespace();
 
                             if !self.match_token(&[TokenKind::Comma]) {
@@ -309,7 +278,10 @@ impl Parser {
                     }
 
                     self.skip_whitespace();
-                    self.expect(TokenKind::RParen, "Expected ')' after super arguments")?;
+                    self.expect(
+                        TokenKind::RParen,
+                        &messages::EXPECTED_RPAREN_AFTER_SUPER_ARGUMENTS,
+                    )?;
                     args
                 } else {
                     // super without parentheses - no arguments
@@ -325,7 +297,10 @@ impl Parser {
             // Case expression: case value when pattern then expr ... end
             TokenKind::Case => self.parse_case_expression(token.position),
 
-            _ => Err(self.error_at_previous(&format!("Unexpected token: {:?}", token.kind))),
+            _ => Err(self.error_at_previous_with(
+                &messages::UNEXPECTED_TOKEN,
+                &[("token", &format!("{:?}", token.kind))],
+            )),
         }
     }
 
@@ -420,7 +395,10 @@ impl Parser {
         };
 
         self.skip_whitespace();
-        self.expect(TokenKind::End, "Expected 'end' after case expression")?;
+        self.expect(
+            TokenKind::End,
+            &messages::EXPECTED_END_AFTER_CASE_EXPRESSION,
+        )?;
 
         Ok(Expression::Case {
             expression,
@@ -429,4 +407,20 @@ impl Parser {
             position: start_pos,
         })
     }
+
+    /// Parse the tokens of a string interpolation as one expression, which
+    /// must use all of them
+    fn parse_interpolation(&mut self) -> Result<Expression, MetorexError> {
+        if self.is_at_end() {
+            return Err(self.error_at_current(&messages::EXPECTED_EXPRESSION_IN_INTERPOLATION));
+        }
+        let expr = self.parse_expression()?;
+        if !self.is_at_end() {
+            return Err(self.error_at_current_with(
+                &messages::UNEXPECTED_TOKEN_IN_INTERPOLATION,
+                &[("token", &format!("{:?}", self.peek().kind))],
+            ));
+        }
+        Ok(expr)
+    }
 }
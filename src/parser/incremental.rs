@@ -0,0 +1,213 @@
+// Incremental re-parsing for editors
+// A ParsedDocument keeps the top-level statements of a source text. After an
+// edit it parses again only the statements the edit touched, then reuses the
+// statements after it, moved to their new place in the text. Each top-level
+// statement carries a NodeId that survives edits elsewhere in the document,
+// so tools can tell which parts of the tree actually changed.
+
+use super::Parser;
+use crate::ast::Statement;
+use crate::error::MetorexError;
+use crate::lexer::{Lexer, Position, Span};
+use std::ops::Range;
+
+/// Identifies a top-level statement across re-parses of its document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(u64);
+
+impl NodeId {
+    /// The number behind the id, unique within its document
+    pub fn get(self) -> u64 {
+        self.0
+    }
+}
+
+/// A top-level statement with its id and the source it was parsed from
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedStatement {
+    id: NodeId,
+    statement: Statement,
+    span: Span,
+}
+
+impl ParsedStatement {
+    /// Stays the same until an edit touches the statement
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    /// The statement's syntax tree
+    pub fn statement(&self) -> &Statement {
+        &self.statement
+    }
+
+    /// The source the statement was parsed from
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// Statements an edit replaced
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Reparse {
+    /// Ids of the statements that are gone
+    pub removed: Vec<NodeId>,
+    /// Ids of the statements parsed for the edited text, in source order
+    pub added: Vec<NodeId>,
+}
+
+/// Source text kept parsed into top-level statements
+#[derive(Debug, Clone)]
+pub struct ParsedDocument {
+    source: String,
+    statements: Vec<ParsedStatement>,
+    next_id: u64,
+}
+
+impl ParsedDocument {
+    /// Parse a whole source text
+    pub fn new(source: impl Into<String>) -> Result<Self, Vec<MetorexError>> {
+        let mut document = Self {
+            source: source.into(),
+            statements: Vec::new(),
+            next_id: 0,
+        };
+        let (statements, _) = document
+            .parse_from(0, 0, &[])
+            .map_err(|error| vec![error])?;
+        document.statements = statements;
+        Ok(document)
+    }
+
+    /// The current source text
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// The top-level statements, in source order
+    pub fn statements(&self) -> &[ParsedStatement] {
+        &self.statements
+    }
+
+    /// The top-level statement whose source covers the offset
+    pub fn statement_at(&self, offset: usize) -> Option<&ParsedStatement> {
+        self.statements
+            .iter()
+            .find(|parsed| parsed.span.start <= offset && offset < parsed.span.end)
+    }
+
+    /// The statements' syntax trees, ready to run
+    pub fn program(&self) -> Vec<Statement> {
+        self.statements
+            .iter()
+            .map(|parsed| parsed.statement.clone())
+            .collect()
+    }
+
+    /// Replace the source in `range` with `replacement` and parse again the
+    /// statements the edit touched. The whole text is lexed again, but only
+    /// the edited statements are parsed.
+    ///
+    /// The text is always updated. When the edited text does not parse, the
+    /// errors are returned and the document holds no statements until a later
+    /// edit makes the whole text parse again.
+    pub fn edit(
+        &mut self,
+        range: Range<usize>,
+        replacement: &str,
+    ) -> Result<Reparse, Vec<MetorexError>> {
+        let old_end = position_at(&self.source, range.end);
+        self.source.replace_range(range.clone(), replacement);
+        let edit_end = range.start + replacement.len();
+        let new_end = position_at(&self.source, edit_end);
+
+        // Statements ending right where the edit starts may grow with it, and
+        // ones starting right where it ends may be joined to it
+        let first = self
+            .statements
+            .partition_point(|parsed| parsed.span.end < range.start);
+        let last = self
+            .statements
+            .partition_point(|parsed| parsed.span.start <= range.end);
+        let mut following = self.statements.split_off(last);
+        let mut removed: Vec<NodeId> = self
+            .statements
+            .drain(first..)
+            .map(|parsed| parsed.id)
+            .collect();
+
+        // Move the statements after the edit to where their text is now
+        for parsed in &mut following {
+            parsed.span = Span::new(
+                parsed.span.start - range.end + edit_end,
+                parsed.span.end - range.end + edit_end,
+            );
+            parsed
+                .statement
+                .for_each_position_mut(&mut |position| shift(position, old_end, new_end));
+        }
+
+        let from = self.statements.last().map_or(0, |parsed| parsed.span.end);
+        let (parsed, reused) = match self.parse_from(from, edit_end, &following) {
+            Ok(result) => result,
+            Err(error) => {
+                self.statements.clear();
+                return Err(vec![error]);
+            }
+        };
+        removed.extend(following.drain(..reused).map(|parsed| parsed.id));
+        let added = parsed.iter().map(|parsed| parsed.id).collect();
+        self.statements.extend(parsed);
+        self.statements.extend(following);
+        Ok(Reparse { removed, added })
+    }
+
+    /// Parse statements starting at `from` until one starts past `edit_end`
+    /// where a `following` statement already starts. Returns the new
+    /// statements and the index of the first following statement to keep.
+    fn parse_from(
+        &mut self,
+        from: usize,
+        edit_end: usize,
+        following: &[ParsedStatement],
+    ) -> Result<(Vec<ParsedStatement>, usize), MetorexError> {
+        let mut parser = Parser::new(Lexer::new(&self.source).tokenize());
+        let mut parsed = Vec::new();
+        let mut offset = from;
+        while let Some((statement, span)) = parser.parse_statement_at(offset)? {
+            if span.start >= edit_end
+                && let Ok(reused) =
+                    following.binary_search_by_key(&span.start, |parsed| parsed.span.start)
+            {
+                return Ok((parsed, reused));
+            }
+            offset = span.end;
+            parsed.push(ParsedStatement {
+                id: NodeId(self.next_id),
+                statement,
+                span,
+            });
+            self.next_id += 1;
+        }
+        Ok((parsed, following.len()))
+    }
+}
+
+/// Where the lexer puts `offset`: lines and columns count from 1, and a
+/// leading byte order mark takes no column
+fn position_at(source: &str, offset: usize) -> Position {
+    let before = &source[..offset];
+    let line = 1 + before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+    let line_text = before[line_start..].trim_start_matches('\u{feff}');
+    Position::new(line, 1 + line_text.chars().count(), offset)
+}
+
+/// Move a position at or after the end of an edit from `old_end` to `new_end`
+fn shift(position: &mut Position, old_end: Position, new_end: Position) {
+    if position.line == old_end.line {
+        position.column = position.column - old_end.column + new_end.column;
+    }
+    position.line = position.line - old_end.line + new_end.line;
+    position.offset = position.offset - old_end.offset + new_end.offset;
+}
@@ -3,15 +3,21 @@
 
 mod error;
 mod expressions;
+mod incremental;
+pub mod precedence;
 mod statements;
 mod token_stream;
 
 use crate::ast::Statement;
 use crate::error::MetorexError;
-use crate::lexer::{Token, TokenKind};
+use crate::lexer::{Span, Token, TokenKind};
+use crate::messages::Message;
+use std::fmt::Display;
 
 use error::ErrorHandler;
-use token_stream::TokenStream;
+
+pub use incremental::{NodeId, ParsedDocument, ParsedStatement, Reparse};
+pub use token_stream::TokenStream;
 
 /// The parser converts a token stream into an AST
 pub struct Parser {
@@ -21,15 +27,23 @@ pub struct Parser {
     error_handler: ErrorHandler,
     /// Track if we're currently parsing inside a class body
     in_class_body: bool,
+    /// Track if we're parsing a raise statement (where `from` introduces a cause)
+    in_raise_statement: bool,
 }
 
 impl Parser {
     /// Create a new parser from a vector of tokens
     pub fn new(tokens: Vec<Token>) -> Self {
+        Self::from_stream(TokenStream::new(tokens))
+    }
+
+    /// Create a parser that continues from a token stream's current position
+    pub fn from_stream(stream: TokenStream) -> Self {
         Self {
-            stream: TokenStream::new(tokens),
+            stream,
             error_handler: ErrorHandler::new(),
             in_class_body: false,
+            in_raise_statement: false,
         }
     }
 
@@ -74,7 +88,11 @@ impl Parser {
     }
 
     /// Expect a specific token kind and consume it, or report an error
-    fn expect(&mut self, kind: TokenKind, message: &str) -> Result<Token, MetorexError> {
+    fn expect(
+        &mut self,
+        kind: TokenKind,
+        message: &'static Message,
+    ) -> Result<Token, MetorexError> {
         if self.match_kind(&kind) {
             Ok(self.advance())
         } else {
@@ -89,19 +107,43 @@ impl Parser {
     }
 
     /// Get a reference to the token stream for advanced operations
-    pub(crate) fn stream(&self) -> &TokenStream {
+    pub fn stream(&self) -> &TokenStream {
         &self.stream
     }
 
+    /// Give back the token stream, positioned after the last parsed token
+    pub fn into_stream(self) -> TokenStream {
+        self.stream
+    }
+
     /// Create an error at the current token
-    fn error_at_current(&self, message: &str) -> MetorexError {
-        self.error_handler.error_at_current(message, self.peek())
+    fn error_at_current(&self, message: &'static Message) -> MetorexError {
+        self.error_at_current_with(message, &[])
+    }
+
+    /// Create an error at the current token, filling in the message's
+    /// placeholders from `args`
+    fn error_at_current_with(
+        &self,
+        message: &'static Message,
+        args: &[(&str, &dyn Display)],
+    ) -> MetorexError {
+        self.error_handler.error_at(message, args, self.peek())
     }
 
     /// Create an error at the previous token
-    fn error_at_previous(&self, message: &str) -> MetorexError {
-        self.error_handler
-            .error_at_previous(message, self.previous())
+    fn error_at_previous(&self, message: &'static Message) -> MetorexError {
+        self.error_at_previous_with(message, &[])
+    }
+
+    /// Create an error at the previous token, filling in the message's
+    /// placeholders from `args`
+    fn error_at_previous_with(
+        &self,
+        message: &'static Message,
+        args: &[(&str, &dyn Display)],
+    ) -> MetorexError {
+        self.error_handler.error_at(message, args, self.previous())
     }
 
     /// Report an error and enter panic mode
@@ -126,6 +168,7 @@ impl Parser {
             // Also synchronize at the start of a new statement
             match self.peek().kind {
                 TokenKind::Class
+                | TokenKind::Module
                 | TokenKind::Def
                 | TokenKind::If
                 | TokenKind::While
@@ -138,6 +181,30 @@ impl Parser {
         }
     }
 
+    /// Parse the statement that starts at or after the source offset, returning
+    /// it with the source span it covers, or `None` when only whitespace and
+    /// comments follow. Tools re-parse an edited region by starting at the end
+    /// of the last statement before the edit.
+    pub fn parse_statement_at(
+        &mut self,
+        offset: usize,
+    ) -> Result<Option<(Statement, Span)>, MetorexError> {
+        self.stream.seek(offset);
+        self.skip_whitespace();
+        if self.is_at_end() {
+            return Ok(None);
+        }
+        self.parse_first_statement().map(Some)
+    }
+
+    /// Parse only the first statement, returning it with the source span it covers
+    pub fn parse_first_statement(&mut self) -> Result<(Statement, Span), MetorexError> {
+        self.skip_whitespace();
+        let start = self.peek().span.start;
+        let statement = self.parse_statement()?;
+        Ok((statement, Span::new(start, self.previous().span.end)))
+    }
+
     /// Parse a complete program (list of statements)
     pub fn parse(&mut self) -> Result<Vec<Statement>, Vec<MetorexError>> {
         let mut statements = Vec::new();
@@ -0,0 +1,116 @@
+// Operator precedence table
+// The single source for how tightly each infix operator binds
+
+use crate::ast::BinaryOp;
+use crate::lexer::TokenKind;
+
+/// How a chain of operators with the same precedence groups
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+    /// `a - b - c` is `(a - b) - c`
+    Left,
+    /// `a ** b ** c` is `a ** (b ** c)`
+    Right,
+    /// `a..b..c` is a syntax error
+    NonAssociative,
+}
+
+/// The expression an infix operator builds
+#[derive(Debug, Clone, PartialEq)]
+pub enum InfixKind {
+    /// `Expression::BinaryOp` with this operator
+    Binary(BinaryOp),
+    /// `Expression::Range`, excluding the end for `...`
+    Range { exclusive: bool },
+}
+
+/// One row of the precedence table
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfixOperator {
+    /// The operator as written in source
+    pub symbol: &'static str,
+    /// The token that introduces it
+    pub token: TokenKind,
+    /// What it builds from its operands
+    pub kind: InfixKind,
+    /// Higher binds tighter; unary `+`/`-` and calls bind tighter than all
+    pub precedence: u8,
+    /// How a chain of operators at this precedence groups
+    pub associativity: Associativity,
+}
+
+const fn binary(
+    symbol: &'static str,
+    token: TokenKind,
+    op: BinaryOp,
+    precedence: u8,
+) -> InfixOperator {
+    InfixOperator {
+        symbol,
+        token,
+        kind: InfixKind::Binary(op),
+        precedence,
+        associativity: Associativity::Left,
+    }
+}
+
+const fn range(symbol: &'static str, token: TokenKind, exclusive: bool) -> InfixOperator {
+    InfixOperator {
+        symbol,
+        token,
+        kind: InfixKind::Range { exclusive },
+        precedence: RANGE,
+        associativity: Associativity::NonAssociative,
+    }
+}
+
+// Precedence levels, loosest first, spaced so new levels fit between them
+const KEYWORD_LOGIC: u8 = 2;
+const LOGICAL_OR: u8 = 6;
+const LOGICAL_AND: u8 = 8;
+const EQUALITY: u8 = 10;
+const COMPARISON: u8 = 20;
+const RANGE: u8 = 30;
+const SHIFT: u8 = 40;
+const TERM: u8 = 50;
+const FACTOR: u8 = 60;
+
+/// How tightly the operand of a prefix `not` binds: everything up to the
+/// next `and` or `or`, so `not a == b` is `not (a == b)`
+pub const NOT_OPERAND: u8 = LOGICAL_OR;
+
+/// Every infix operator, loosest first
+pub const INFIX_OPERATORS: &[InfixOperator] = &[
+    binary("and", TokenKind::And, BinaryOp::And, KEYWORD_LOGIC),
+    binary("or", TokenKind::Or, BinaryOp::Or, KEYWORD_LOGIC),
+    binary("||", TokenKind::PipePipe, BinaryOp::Or, LOGICAL_OR),
+    binary("&&", TokenKind::AmpAmp, BinaryOp::And, LOGICAL_AND),
+    binary("==", TokenKind::EqualEqual, BinaryOp::Equal, EQUALITY),
+    binary("!=", TokenKind::BangEqual, BinaryOp::NotEqual, EQUALITY),
+    binary("===", TokenKind::CaseEqual, BinaryOp::CaseEqual, EQUALITY),
+    binary("<", TokenKind::Less, BinaryOp::Less, COMPARISON),
+    binary(">", TokenKind::Greater, BinaryOp::Greater, COMPARISON),
+    binary("<=", TokenKind::LessEqual, BinaryOp::LessEqual, COMPARISON),
+    binary(
+        ">=",
+        TokenKind::GreaterEqual,
+        BinaryOp::GreaterEqual,
+        COMPARISON,
+    ),
+    binary("<=>", TokenKind::Spaceship, BinaryOp::Spaceship, COMPARISON),
+    range("..", TokenKind::DotDot, false),
+    range("...", TokenKind::DotDotDot, true),
+    binary("<<", TokenKind::ShiftLeft, BinaryOp::ShiftLeft, SHIFT),
+    binary("+", TokenKind::Plus, BinaryOp::Add, TERM),
+    binary("-", TokenKind::Minus, BinaryOp::Subtract, TERM),
+    binary("*", TokenKind::Star, BinaryOp::Multiply, FACTOR),
+    binary("/", TokenKind::Slash, BinaryOp::Divide, FACTOR),
+    binary("%", TokenKind::Percent, BinaryOp::Modulo, FACTOR),
+];
+
+/// The infix operator `kind` introduces, if any
+pub fn infix_operator(kind: &TokenKind) -> Option<&'static InfixOperator> {
+    INFIX_OPERATORS
+        .iter()
+        .find(|operator| operator.token == *kind)
+}
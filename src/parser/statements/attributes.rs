@@ -3,13 +3,14 @@
 use crate::ast::Statement;
 use crate::error::MetorexError;
 use crate::lexer::TokenKind;
+use crate::messages;
 use crate::parser::Parser;
 
 impl Parser {
     /// Parse attr_reader statement: attr_reader :name1, :name2, ...
     pub(crate) fn parse_attr_reader(&mut self) -> Result<Statement, MetorexError> {
         let start_pos = self
-            .expect(TokenKind::AttrReader, "Expected 'attr_reader'")?
+            .expect(TokenKind::AttrReader, &messages::EXPECTED_ATTR_READER)?
             .position;
         self.skip_whitespace();
 
@@ -24,7 +25,7 @@ impl Parser {
     /// Parse attr_writer statement: attr_writer :name1, :name2, ...
     pub(crate) fn parse_attr_writer(&mut self) -> Result<Statement, MetorexError> {
         let start_pos = self
-            .expect(TokenKind::AttrWriter, "Expected 'attr_writer'")?
+            .expect(TokenKind::AttrWriter, &messages::EXPECTED_ATTR_WRITER)?
             .position;
         self.skip_whitespace();
 
@@ -39,7 +40,7 @@ impl Parser {
     /// Parse attr_accessor statement: attr_accessor :name1, :name2, ...
     pub(crate) fn parse_attr_accessor(&mut self) -> Result<Statement, MetorexError> {
         let start_pos = self
-            .expect(TokenKind::AttrAccessor, "Expected 'attr_accessor'")?
+            .expect(TokenKind::AttrAccessor, &messages::EXPECTED_ATTR_ACCESSOR)?
             .position;
         self.skip_whitespace();
 
@@ -51,18 +52,17 @@ impl Parser {
         })
     }
 
-    /// Parse a comma-separated list of symbols (:name1, :name2, ...)
+    /// Parse a comma-separated list of symbols (:name1, :name2, ...),
+    /// optionally in parentheses
     fn parse_symbol_list(&mut self) -> Result<Vec<String>, MetorexError> {
+        let parenthesized = self.match_token(&[TokenKind::LParen]);
+        if parenthesized {
+            self.skip_whitespace();
+        }
         let mut attributes = Vec::new();
 
         // Parse first symbol
-        self.expect(TokenKind::Colon, "Expected ':' before attribute name")?;
-        self.skip_whitespace();
-
-        match self.advance().kind {
-            TokenKind::Ident(name) => attributes.push(name),
-            _ => return Err(self.error_at_previous("Expected attribute name after ':'")),
-        }
+        attributes.push(self.parse_attribute_name()?);
 
         // Parse remaining symbols
         loop {
@@ -73,15 +73,33 @@ impl Parser {
             }
 
             self.skip_whitespace();
-            self.expect(TokenKind::Colon, "Expected ':' before attribute name")?;
-            self.skip_whitespace();
-
-            match self.advance().kind {
-                TokenKind::Ident(name) => attributes.push(name),
-                _ => return Err(self.error_at_previous("Expected attribute name after ':'")),
-            }
+            attributes.push(self.parse_attribute_name()?);
         }
 
+        if parenthesized {
+            self.expect(
+                TokenKind::RParen,
+                &messages::EXPECTED_RPAREN_AFTER_ARGUMENTS,
+            )?;
+        }
         Ok(attributes)
     }
+
+    /// Parse one attribute name, written as a symbol (:name) or a string ("name")
+    fn parse_attribute_name(&mut self) -> Result<String, MetorexError> {
+        match self.advance().kind {
+            TokenKind::Symbol(name) if !name.starts_with('@') => Ok(name.to_string()),
+            TokenKind::String(name) if is_attribute_name(&name) => Ok(name),
+            _ => Err(self.error_at_previous(&messages::EXPECTED_ATTRIBUTE_NAME_SYMBOL)),
+        }
+    }
+}
+
+/// Whether `name` can name an attribute: an identifier, as in `@name`
+fn is_attribute_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_alphabetic() || first == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
 }
@@ -1,19 +1,22 @@
-// Class definition parsing
+// Class and module definition parsing
 
 use crate::ast::Statement;
 use crate::error::MetorexError;
 use crate::lexer::TokenKind;
+use crate::messages::{self, Message};
 use crate::parser::Parser;
 
 impl Parser {
     /// Parse a class definition
     pub(crate) fn parse_class_def(&mut self) -> Result<Statement, MetorexError> {
-        let start_pos = self.expect(TokenKind::Class, "Expected 'class'")?.position;
+        let start_pos = self
+            .expect(TokenKind::Class, &messages::EXPECTED_CLASS)?
+            .position;
         self.skip_whitespace();
 
         let name = match self.advance().kind {
-            TokenKind::Ident(name) => name,
-            _ => return Err(self.error_at_previous("Expected class name")),
+            TokenKind::Ident(name) => name.to_string(),
+            _ => return Err(self.error_at_previous(&messages::EXPECTED_CLASS_NAME)),
         };
 
         self.skip_whitespace();
@@ -22,8 +25,8 @@ impl Parser {
         let superclass = if self.match_token(&[TokenKind::Less]) {
             self.skip_whitespace();
             match self.advance().kind {
-                TokenKind::Ident(parent) => Some(parent),
-                _ => return Err(self.error_at_previous("Expected superclass name")),
+                TokenKind::Ident(parent) => Some(parent.to_string()),
+                _ => return Err(self.error_at_previous(&messages::EXPECTED_SUPERCLASS_NAME)),
             }
         } else {
             None
@@ -31,7 +34,45 @@ impl Parser {
 
         self.skip_whitespace();
 
-        // Parse class body - set flag to indicate we're inside a class
+        let body = self.parse_definition_body(&messages::EXPECTED_END_AFTER_CLASS_BODY)?;
+
+        Ok(Statement::ClassDef {
+            name,
+            superclass,
+            body,
+            position: start_pos,
+        })
+    }
+
+    /// Parse a module definition
+    pub(crate) fn parse_module_def(&mut self) -> Result<Statement, MetorexError> {
+        let start_pos = self
+            .expect(TokenKind::Module, &messages::EXPECTED_MODULE)?
+            .position;
+        self.skip_whitespace();
+
+        let name = match self.advance().kind {
+            TokenKind::Ident(name) => name.to_string(),
+            _ => return Err(self.error_at_previous(&messages::EXPECTED_MODULE_NAME)),
+        };
+
+        self.skip_whitespace();
+        let body = self.parse_definition_body(&messages::EXPECTED_END_AFTER_MODULE_BODY)?;
+
+        Ok(Statement::ModuleDef {
+            name,
+            body,
+            position: start_pos,
+        })
+    }
+
+    /// Parse the statements of a class or module body up to and including
+    /// its `end`, with `def` defining methods
+    fn parse_definition_body(
+        &mut self,
+        missing_end: &'static Message,
+    ) -> Result<Vec<Statement>, MetorexError> {
+        // Set flag to indicate we're inside a class
         let was_in_class = self.in_class_body;
         self.in_class_body = true;
 
@@ -48,13 +89,7 @@ impl Parser {
         // Restore the previous state
         self.in_class_body = was_in_class;
 
-        self.expect(TokenKind::End, "Expected 'end' after class body")?;
-
-        Ok(Statement::ClassDef {
-            name,
-            superclass,
-            body,
-            position: start_pos,
-        })
+        self.expect(TokenKind::End, missing_end)?;
+        Ok(body)
     }
 }
@@ -1,14 +1,15 @@
 // Control flow statement parsing (if, while, for, case)
 
 use crate::ast::{ElsifBranch, MatchCase, MatchPattern, Statement};
-use crate::error::{MetorexError, SourceLocation};
+use crate::error::MetorexError;
 use crate::lexer::TokenKind;
+use crate::messages;
 use crate::parser::Parser;
 
 impl Parser {
     /// Parse an if statement
     pub(crate) fn parse_if_statement(&mut self) -> Result<Statement, MetorexError> {
-        let start_pos = self.expect(TokenKind::If, "Expected 'if'")?.position;
+        let start_pos = self.expect(TokenKind::If, &messages::EXPECTED_IF)?.position;
         self.skip_whitespace();
 
         let condition = self.parse_expression()?;
@@ -71,7 +72,7 @@ impl Parser {
             None
         };
 
-        self.expect(TokenKind::End, "Expected 'end' after if statement")?;
+        self.expect(TokenKind::End, &messages::EXPECTED_END_AFTER_IF_STATEMENT)?;
 
         Ok(Statement::If {
             condition,
@@ -84,7 +85,9 @@ impl Parser {
 
     /// Parse a while loop
     pub(crate) fn parse_while_statement(&mut self) -> Result<Statement, MetorexError> {
-        let start_pos = self.expect(TokenKind::While, "Expected 'while'")?.position;
+        let start_pos = self
+            .expect(TokenKind::While, &messages::EXPECTED_WHILE)?
+            .position;
         self.skip_whitespace();
 
         let condition = self.parse_expression()?;
@@ -105,7 +108,7 @@ impl Parser {
             self.skip_whitespace();
         }
 
-        self.expect(TokenKind::End, "Expected 'end' after while loop")?;
+        self.expect(TokenKind::End, &messages::EXPECTED_END_AFTER_WHILE_LOOP)?;
 
         Ok(Statement::While {
             condition,
@@ -116,29 +119,24 @@ impl Parser {
 
     /// Parse a for loop
     pub(crate) fn parse_for_statement(&mut self) -> Result<Statement, MetorexError> {
-        let start_pos = self.expect(TokenKind::For, "Expected 'for'")?.position;
+        let start_pos = self
+            .expect(TokenKind::For, &messages::EXPECTED_FOR)?
+            .position;
         self.skip_whitespace();
 
         // Parse the loop variable
         let variable = if let TokenKind::Ident(name) = &self.peek().kind {
-            let var_name = name.clone();
+            let var_name = name.to_string();
             self.advance();
             var_name
         } else {
-            return Err(MetorexError::syntax_error(
-                "Expected identifier after 'for'",
-                SourceLocation::new(
-                    self.peek().position.line,
-                    self.peek().position.column,
-                    self.peek().position.offset,
-                ),
-            ));
+            return Err(self.error_at_current(&messages::EXPECTED_IDENTIFIER_AFTER_FOR));
         };
 
         self.skip_whitespace();
 
         // Expect 'in' keyword
-        self.expect(TokenKind::In, "Expected 'in' after loop variable")?;
+        self.expect(TokenKind::In, &messages::EXPECTED_IN_AFTER_LOOP_VARIABLE)?;
         self.skip_whitespace();
 
         // Parse the iterable expression
@@ -160,7 +158,7 @@ impl Parser {
             self.skip_whitespace();
         }
 
-        self.expect(TokenKind::End, "Expected 'end' after for loop")?;
+        self.expect(TokenKind::End, &messages::EXPECTED_END_AFTER_FOR_LOOP)?;
 
         Ok(Statement::For {
             variable,
@@ -172,14 +170,16 @@ impl Parser {
 
     /// Parse a break statement
     pub(crate) fn parse_break_statement(&mut self) -> Result<Statement, MetorexError> {
-        let pos = self.expect(TokenKind::Break, "Expected 'break'")?.position;
+        let pos = self
+            .expect(TokenKind::Break, &messages::EXPECTED_BREAK)?
+            .position;
         Ok(Statement::Break { position: pos })
     }
 
     /// Parse a continue statement
     pub(crate) fn parse_continue_statement(&mut self) -> Result<Statement, MetorexError> {
         let pos = self
-            .expect(TokenKind::Continue, "Expected 'continue'")?
+            .expect(TokenKind::Continue, &messages::EXPECTED_CONTINUE)?
             .position;
         Ok(Statement::Continue { position: pos })
     }
@@ -187,7 +187,7 @@ impl Parser {
     /// Parse an unless statement
     pub(crate) fn parse_unless_statement(&mut self) -> Result<Statement, MetorexError> {
         let start_pos = self
-            .expect(TokenKind::Unless, "Expected 'unless'")?
+            .expect(TokenKind::Unless, &messages::EXPECTED_UNLESS)?
             .position;
         self.skip_whitespace();
 
@@ -222,7 +222,10 @@ impl Parser {
             None
         };
 
-        self.expect(TokenKind::End, "Expected 'end' after unless statement")?;
+        self.expect(
+            TokenKind::End,
+            &messages::EXPECTED_END_AFTER_UNLESS_STATEMENT,
+        )?;
 
         Ok(Statement::Unless {
             condition,
@@ -235,7 +238,7 @@ impl Parser {
     /// Parse a return statement
     pub(crate) fn parse_return_statement(&mut self) -> Result<Statement, MetorexError> {
         let pos = self
-            .expect(TokenKind::Return, "Expected 'return'")?
+            .expect(TokenKind::Return, &messages::EXPECTED_RETURN)?
             .position;
         self.skip_whitespace();
 
@@ -265,7 +268,9 @@ impl Parser {
     ///     else_body
     ///   end
     pub(crate) fn parse_case_statement(&mut self) -> Result<Statement, MetorexError> {
-        let start_pos = self.expect(TokenKind::Case, "Expected 'case'")?.position;
+        let start_pos = self
+            .expect(TokenKind::Case, &messages::EXPECTED_CASE)?
+            .position;
         self.skip_whitespace();
 
         // Parse the expression to match against
@@ -295,6 +300,9 @@ impl Parser {
             };
             self.skip_whitespace();
 
+            // The body may follow `then` on the same line
+            self.match_token(&[TokenKind::Then]);
+
             // Parse the body
             let mut body = Vec::new();
             while !self.check(&[TokenKind::When, TokenKind::Else, TokenKind::End])
@@ -342,7 +350,7 @@ impl Parser {
         }
 
         self.skip_whitespace(); // Skip whitespace before end
-        self.expect(TokenKind::End, "Expected 'end' after case statement")?;
+        self.expect(TokenKind::End, &messages::EXPECTED_END_AFTER_CASE_STATEMENT)?;
 
         Ok(Statement::Match {
             expression,
@@ -362,9 +370,7 @@ impl Parser {
     /// This method is public within the parser module so it can be used
     /// by both statement parsing (case statements) and expression parsing (case expressions)
     pub(in crate::parser) fn parse_case_pattern(&mut self) -> Result<MatchPattern, MetorexError> {
-        let token = self.peek().clone();
-
-        match &token.kind {
+        match &self.peek().kind {
             // Array pattern
             TokenKind::LBracket => {
                 self.advance(); // consume '['
@@ -382,17 +388,12 @@ impl Parser {
 
                         // Next token should be an identifier for the rest binding
                         if let TokenKind::Ident(name) = &self.peek().kind {
-                            let rest_name = name.clone();
+                            let rest_name = name.to_string();
                             self.advance();
                             patterns.push(MatchPattern::Rest(rest_name));
                         } else {
-                            return Err(MetorexError::syntax_error(
-                                "Expected identifier after ... in array pattern".to_string(),
-                                SourceLocation::new(
-                                    self.peek().position.line,
-                                    self.peek().position.column,
-                                    self.peek().position.offset,
-                                ),
+                            return Err(self.error_at_current(
+                                &messages::EXPECTED_IDENTIFIER_AFTER_ELLIPSIS_IN_ARRAY_PATTERN,
                             ));
                         }
                     } else {
@@ -404,12 +405,18 @@ impl Parser {
 
                     // Check for comma
                     if !self.check(&[TokenKind::RBracket]) {
-                        self.expect(TokenKind::Comma, "Expected ',' or ']' in array pattern")?;
+                        self.expect(
+                            TokenKind::Comma,
+                            &messages::EXPECTED_COMMA_OR_RBRACKET_IN_ARRAY_PATTERN,
+                        )?;
                         self.skip_whitespace();
                     }
                 }
 
-                self.expect(TokenKind::RBracket, "Expected ']' after array pattern")?;
+                self.expect(
+                    TokenKind::RBracket,
+                    &messages::EXPECTED_RBRACKET_AFTER_ARRAY_PATTERN,
+                )?;
                 Ok(MatchPattern::Array(patterns))
             }
 
@@ -426,7 +433,7 @@ impl Parser {
 
                     // Expect an identifier as the key
                     let key = if let TokenKind::Ident(name) = &self.peek().kind {
-                        let k = name.clone();
+                        let k = name.to_string();
                         self.advance();
                         k
                     } else if let TokenKind::String(s) = &self.peek().kind {
@@ -434,13 +441,8 @@ impl Parser {
                         self.advance();
                         k
                     } else {
-                        return Err(MetorexError::syntax_error(
-                            "Expected identifier or string key in object pattern".to_string(),
-                            SourceLocation::new(
-                                self.peek().position.line,
-                                self.peek().position.column,
-                                self.peek().position.offset,
-                            ),
+                        return Err(self.error_at_current(
+                            &messages::EXPECTED_IDENTIFIER_OR_STRING_KEY_IN_OBJECT_PATTERN,
                         ));
                     };
 
@@ -461,12 +463,18 @@ impl Parser {
 
                     // Check for comma
                     if !self.check(&[TokenKind::RBrace]) {
-                        self.expect(TokenKind::Comma, "Expected ',' or '}' in object pattern")?;
+                        self.expect(
+                            TokenKind::Comma,
+                            &messages::EXPECTED_COMMA_OR_RBRACE_IN_OBJECT_PATTERN,
+                        )?;
                         self.skip_whitespace();
                     }
                 }
 
-                self.expect(TokenKind::RBrace, "Expected '}' after object pattern")?;
+                self.expect(
+                    TokenKind::RBrace,
+                    &messages::EXPECTED_RBRACE_AFTER_OBJECT_PATTERN,
+                )?;
                 Ok(MatchPattern::Object(key_patterns))
             }
 
@@ -498,6 +506,22 @@ impl Parser {
                 self.advance();
                 Ok(MatchPattern::NilLiteral)
             }
+            // Glob pattern: glob("*.rb")
+            TokenKind::Ident(name)
+                if name == "glob" && self.peek_ahead(1).kind == TokenKind::LParen =>
+            {
+                self.advance(); // consume 'glob'
+                self.advance(); // consume '('
+                let TokenKind::String(glob) = self.peek().kind.clone() else {
+                    return Err(self.error_at_current(&messages::EXPECTED_STRING_IN_GLOB_PATTERN));
+                };
+                self.advance();
+                self.expect(
+                    TokenKind::RParen,
+                    &messages::EXPECTED_RPAREN_AFTER_GLOB_PATTERN,
+                )?;
+                Ok(MatchPattern::Glob(glob))
+            }
             // Wildcard pattern
             TokenKind::Ident(name) if name == "_" => {
                 self.advance();
@@ -505,23 +529,19 @@ impl Parser {
             }
             // Type pattern (capitalized identifiers like Integer, String, Hash, Array)
             TokenKind::Ident(name) if name.chars().next().is_some_and(|c| c.is_uppercase()) => {
-                let type_name = name.clone();
+                let type_name = name.to_string();
                 self.advance();
                 Ok(MatchPattern::Type(type_name))
             }
             // Variable binding pattern
             TokenKind::Ident(name) => {
-                let var_name = name.clone();
+                let var_name = name.to_string();
                 self.advance();
                 Ok(MatchPattern::Identifier(var_name))
             }
-            _ => Err(MetorexError::syntax_error(
-                format!("Expected pattern, found {:?}", token.kind),
-                SourceLocation::new(
-                    token.position.line,
-                    token.position.column,
-                    token.position.offset,
-                ),
+            kind => Err(self.error_at_current_with(
+                &messages::EXPECTED_PATTERN,
+                &[("token", &format!("{:?}", kind))],
             )),
         }
     }
@@ -1,14 +1,26 @@
 // Exception handling statement parsing (begin/rescue/raise)
 
-use crate::ast::{RescueClause, Statement};
-use crate::error::{MetorexError, SourceLocation};
-use crate::lexer::TokenKind;
+use crate::ast::{Expression, RescueClause, Statement};
+use crate::error::MetorexError;
+use crate::lexer::{Position, TokenKind};
+use crate::messages;
 use crate::parser::Parser;
 
 impl Parser {
     /// Parse a begin...rescue...else...ensure...end statement
     pub(crate) fn parse_begin_statement(&mut self) -> Result<Statement, MetorexError> {
-        let start_pos = self.expect(TokenKind::Begin, "Expected 'begin'")?.position;
+        let start_pos = self
+            .expect(TokenKind::Begin, &messages::EXPECTED_BEGIN)?
+            .position;
+        self.parse_begin_block(start_pos)
+    }
+
+    /// Parse what follows `begin` up to and including the closing `end`,
+    /// shared by begin statements and begin blocks used as values
+    pub(crate) fn parse_begin_block(
+        &mut self,
+        start_pos: Position,
+    ) -> Result<Statement, MetorexError> {
         self.skip_whitespace();
 
         // Parse the main body
@@ -33,6 +45,19 @@ impl Parser {
             self.skip_whitespace();
         }
 
+        let statement = self.parse_exception_clauses(body, start_pos)?;
+        self.expect(TokenKind::End, &messages::EXPECTED_END_AFTER_BEGIN_BLOCK)?;
+        Ok(statement)
+    }
+
+    /// Parse the rescue/else/ensure clauses that follow a protected body and
+    /// wrap everything in a Begin statement. The closing `end` is left for the
+    /// caller, so this is shared by `begin` blocks and method bodies.
+    pub(crate) fn parse_exception_clauses(
+        &mut self,
+        body: Vec<Statement>,
+        start_pos: Position,
+    ) -> Result<Statement, MetorexError> {
         // Parse rescue clauses
         let mut rescue_clauses = Vec::new();
         while self.match_token(&[TokenKind::Rescue]) {
@@ -74,8 +99,6 @@ impl Parser {
             None
         };
 
-        self.expect(TokenKind::End, "Expected 'end' after begin block")?;
-
         Ok(Statement::Begin {
             body,
             rescue_clauses,
@@ -85,6 +108,30 @@ impl Parser {
         })
     }
 
+    /// Wrap an expression in a rescue modifier if it is followed by
+    /// `rescue fallback` on the same line
+    pub(crate) fn parse_rescue_modifier(
+        &mut self,
+        expression: Expression,
+    ) -> Result<Expression, MetorexError> {
+        // Parenless calls may have consumed the newline, so compare lines
+        // rather than relying on a Newline token to end the statement
+        if !self.check(&[TokenKind::Rescue])
+            || self.peek().position.line != self.previous().position.line
+        {
+            return Ok(expression);
+        }
+
+        let position = self.advance().position;
+        let fallback = self.parse_expression_with_lambda()?;
+
+        Ok(Expression::RescueModifier {
+            expression: Box::new(expression),
+            fallback: Box::new(fallback),
+            position,
+        })
+    }
+
     /// Parse a rescue clause
     pub(crate) fn parse_rescue_clause(&mut self) -> Result<RescueClause, MetorexError> {
         let start_pos = self.previous().position;
@@ -103,7 +150,7 @@ impl Parser {
         // An exception type is present if:
         // 1. There's no newline after rescue
         // 2. We see an identifier that's NOT followed by '=' (which would be an assignment)
-        if !has_newline && self.check(&[TokenKind::Ident(String::new())]) {
+        if !has_newline && matches!(self.peek().kind, TokenKind::Ident(_)) {
             // Peek ahead to see if this looks like an exception type or an assignment
             // If the next token after the identifier is '=', it's an assignment, not an exception type
             let current_pos = self.stream().current_position();
@@ -115,10 +162,20 @@ impl Parser {
             };
 
             if !next_is_assignment {
-                // Parse exception types
+                // Parse exception types, which may be scoped (`Timeout::Error`)
                 while let TokenKind::Ident(name) = &self.peek().kind {
-                    exception_types.push(name.clone());
+                    let mut name = name.to_string();
                     self.advance();
+                    while self.match_token(&[TokenKind::ColonColon]) {
+                        let TokenKind::Ident(segment) = &self.peek().kind else {
+                            return Err(
+                                self.error_at_current(&messages::EXPECTED_NAME_AFTER_DOUBLE_COLON)
+                            );
+                        };
+                        name = format!("{}::{}", name, segment);
+                        self.advance();
+                    }
+                    exception_types.push(name);
                     self.skip_whitespace();
 
                     // Check for comma (multiple exception types)
@@ -134,18 +191,11 @@ impl Parser {
         if self.match_token(&[TokenKind::FatArrow]) {
             self.skip_whitespace();
             if let TokenKind::Ident(name) = &self.peek().kind {
-                variable_name = Some(name.clone());
+                variable_name = Some(name.to_string());
                 self.advance();
                 self.skip_whitespace();
             } else {
-                return Err(MetorexError::syntax_error(
-                    "Expected variable name after '=>'",
-                    SourceLocation::new(
-                        self.peek().position.line,
-                        self.peek().position.column,
-                        self.peek().position.offset,
-                    ),
-                ));
+                return Err(self.error_at_current(&messages::EXPECTED_VARIABLE_NAME_AFTER_ARROW));
             }
         }
 
@@ -180,23 +230,80 @@ impl Parser {
     }
 
     /// Parse a raise statement
+    ///
+    /// Supports `raise`, `raise value`, `raise ErrorClass, "message"` and an
+    /// explicit cause with `raise value from cause`.
     pub(crate) fn parse_raise_statement(&mut self) -> Result<Statement, MetorexError> {
-        let start_pos = self.expect(TokenKind::Raise, "Expected 'raise'")?.position;
+        let start_pos = self
+            .expect(TokenKind::Raise, &messages::EXPECTED_RAISE)?
+            .position;
         self.skip_whitespace();
 
         // Check if there's an exception expression
         // If the next token is a newline, semicolon, or end, it's a bare raise
-        let exception = if self.check(&[TokenKind::Newline, TokenKind::Semicolon, TokenKind::End])
+        if self.check(&[TokenKind::Newline, TokenKind::Semicolon, TokenKind::End])
             || self.is_at_end()
         {
-            None
-        } else {
-            Some(self.parse_expression()?)
-        };
+            return Ok(Statement::Raise {
+                exception: None,
+                cause: None,
+                position: start_pos,
+            });
+        }
+
+        // `from` is a contextual keyword here, so don't let it be parsed as a call
+        let was_in_raise = self.in_raise_statement;
+        self.in_raise_statement = true;
+        let result = self.parse_raise_operands();
+        self.in_raise_statement = was_in_raise;
+        let (exception, cause) = result?;
 
         Ok(Statement::Raise {
-            exception,
+            exception: Some(exception),
+            cause,
             position: start_pos,
         })
     }
+
+    /// Parse the exception (and optional message and cause) following `raise`
+    fn parse_raise_operands(&mut self) -> Result<(Expression, Option<Expression>), MetorexError> {
+        let mut exception = self.parse_expression()?;
+
+        // Two-argument form: raise ErrorClass, "message" => ErrorClass("message")
+        if self.match_token(&[TokenKind::Comma]) {
+            self.skip_whitespace();
+            let message = self.parse_expression()?;
+            let position = exception.position();
+            exception = Expression::Call {
+                callee: Box::new(exception),
+                arguments: vec![message],
+                trailing_block: None,
+                position,
+            };
+        }
+
+        let cause = if self.check_from_keyword() {
+            self.advance();
+            self.skip_whitespace();
+            Some(self.parse_expression()?)
+        } else {
+            None
+        };
+
+        Ok((exception, cause))
+    }
+
+    /// Check if the next token is the contextual `from` keyword of a raise statement
+    pub(crate) fn check_from_keyword(&self) -> bool {
+        self.in_raise_statement
+            && matches!(&self.peek().kind, TokenKind::Ident(name) if name == "from")
+    }
+
+    /// Parse a retry statement
+    pub(crate) fn parse_retry_statement(&mut self) -> Result<Statement, MetorexError> {
+        let pos = self
+            .expect(TokenKind::Retry, &messages::EXPECTED_RETRY)?
+            .position;
+        Ok(Statement::Retry { position: pos })
+    }
 }
@@ -1,19 +1,35 @@
 // Function definition parsing
 
-use crate::ast::{Parameter, Statement};
+use crate::ast::{Parameter, Statement, TypeAnnotation};
 use crate::error::MetorexError;
 use crate::lexer::TokenKind;
+use crate::messages;
 use crate::parser::Parser;
 
 impl Parser {
     /// Parse a function definition
     pub(crate) fn parse_function_def(&mut self) -> Result<Statement, MetorexError> {
-        let start_pos = self.expect(TokenKind::Def, "Expected 'def'")?.position;
+        let start_pos = self
+            .expect(TokenKind::Def, &messages::EXPECTED_DEF)?
+            .position;
         self.skip_whitespace();
 
         let name = match self.advance().kind {
-            TokenKind::Ident(name) => name,
-            _ => return Err(self.error_at_previous("Expected function name")),
+            TokenKind::Ident(name) => name.to_string(),
+            // `def <=>(other)` lets instances take part in sorting
+            TokenKind::Spaceship => "<=>".to_string(),
+            // `def <<(item)` lets instances collect with `collector << item`
+            TokenKind::ShiftLeft => "<<".to_string(),
+            // `def [](key)` and `def []=(key, value)` make instances indexable
+            TokenKind::LBracket => {
+                self.expect(TokenKind::RBracket, &messages::EXPECTED_FUNCTION_NAME)?;
+                if self.match_token(&[TokenKind::Equal]) {
+                    "[]=".to_string()
+                } else {
+                    "[]".to_string()
+                }
+            }
+            _ => return Err(self.error_at_previous(&messages::EXPECTED_FUNCTION_NAME)),
         };
 
         self.skip_whitespace();
@@ -25,20 +41,34 @@ impl Parser {
             Vec::new()
         };
 
+        // Optional declared result type (`-> Int`)
+        let return_type = if self.match_token(&[TokenKind::Arrow]) {
+            Some(self.parse_type_annotation()?)
+        } else {
+            None
+        };
+
         self.skip_whitespace();
 
         // Parse function body
         let mut body = Vec::new();
-        while !self.check(&[TokenKind::End]) && !self.is_at_end() {
+        while !self.check(&[TokenKind::End, TokenKind::Rescue, TokenKind::Ensure])
+            && !self.is_at_end()
+        {
             self.skip_whitespace();
-            if self.check(&[TokenKind::End]) {
+            if self.check(&[TokenKind::End, TokenKind::Rescue, TokenKind::Ensure]) {
                 break;
             }
             body.push(self.parse_statement()?);
             self.skip_whitespace();
         }
 
-        self.expect(TokenKind::End, "Expected 'end' after function body")?;
+        // A rescue or ensure directly in the body acts as an implicit begin block
+        if self.check(&[TokenKind::Rescue, TokenKind::Ensure]) {
+            body = vec![self.parse_exception_clauses(body, start_pos)?];
+        }
+
+        self.expect(TokenKind::End, &messages::EXPECTED_END_AFTER_FUNCTION_BODY)?;
 
         // Return MethodDef if we're inside a class, otherwise FunctionDef
         if self.in_class_body {
@@ -47,6 +77,7 @@ impl Parser {
                 parameters,
                 body,
                 position: start_pos,
+                return_type,
             })
         } else {
             Ok(Statement::FunctionDef {
@@ -54,6 +85,7 @@ impl Parser {
                 parameters,
                 body,
                 position: start_pos,
+                return_type,
             })
         }
     }
@@ -73,34 +105,64 @@ impl Parser {
 
             let param_pos = self.peek().position;
 
+            // Check for argument forwarding (...), which must be the last parameter
+            if self.match_token(&[TokenKind::DotDotDot]) {
+                params.push(Parameter::forwarding(param_pos));
+                self.skip_whitespace();
+                if !self.check(&[TokenKind::RParen]) {
+                    return Err(
+                        self.error_at_current(&messages::ELLIPSIS_MUST_BE_THE_LAST_PARAMETER)
+                    );
+                }
+                break;
+            }
             // Check for block parameter (&block)
-            if self.match_token(&[TokenKind::Ampersand]) {
+            else if self.match_token(&[TokenKind::Ampersand]) {
                 let name = match self.advance().kind {
-                    TokenKind::Ident(name) => name,
-                    _ => return Err(self.error_at_previous("Expected parameter name after '&'")),
+                    TokenKind::Ident(name) => name.to_string(),
+                    _ => {
+                        return Err(self.error_at_previous(
+                            &messages::EXPECTED_PARAMETER_NAME_AFTER_AMPERSAND,
+                        ));
+                    }
                 };
                 params.push(Parameter::block(name, param_pos));
             }
             // Check for variadic parameter (*args)
             else if self.match_token(&[TokenKind::Star]) {
                 let name = match self.advance().kind {
-                    TokenKind::Ident(name) => name,
-                    _ => return Err(self.error_at_previous("Expected parameter name after '*'")),
+                    TokenKind::Ident(name) => name.to_string(),
+                    _ => {
+                        return Err(
+                            self.error_at_previous(&messages::EXPECTED_PARAMETER_NAME_AFTER_STAR)
+                        );
+                    }
                 };
                 params.push(Parameter::variadic(name, param_pos));
             } else {
                 let name = match self.advance().kind {
-                    TokenKind::Ident(name) => name,
-                    _ => return Err(self.error_at_previous("Expected parameter name")),
+                    TokenKind::Ident(name) => name.to_string(),
+                    _ => return Err(self.error_at_previous(&messages::EXPECTED_PARAMETER_NAME)),
+                };
+
+                // Check for a declared type (`x: Int`)
+                let annotation = if self.match_token(&[TokenKind::Colon]) {
+                    Some(self.parse_type_annotation()?)
+                } else {
+                    None
                 };
 
                 // Check for default value
-                if self.match_token(&[TokenKind::Equal]) {
+                let param = if self.match_token(&[TokenKind::Equal]) {
                     let default = self.parse_expression()?;
-                    params.push(Parameter::with_default(name, default, param_pos));
+                    Parameter::with_default(name, default, param_pos)
                 } else {
-                    params.push(Parameter::simple(name, param_pos));
-                }
+                    Parameter::simple(name, param_pos)
+                };
+                params.push(match annotation {
+                    Some(annotation) => param.with_type(annotation),
+                    None => param,
+                });
             }
 
             self.skip_whitespace();
@@ -111,8 +173,20 @@ impl Parser {
         }
 
         self.skip_whitespace();
-        self.expect(TokenKind::RParen, "Expected ')' after parameters")?;
+        self.expect(
+            TokenKind::RParen,
+            &messages::EXPECTED_RPAREN_AFTER_PARAMETERS,
+        )?;
 
         Ok(params)
     }
+
+    /// Parse a type name after `:` or `->`, such as `Int` or `String?`
+    fn parse_type_annotation(&mut self) -> Result<TypeAnnotation, MetorexError> {
+        let token = self.advance();
+        match token.kind {
+            TokenKind::Ident(name) => Ok(TypeAnnotation::parse(&name, token.position)),
+            _ => Err(self.error_at_previous(&messages::EXPECTED_TYPE_NAME)),
+        }
+    }
 }
@@ -18,9 +18,10 @@ impl Parser {
         // Skip leading whitespace
         self.skip_whitespace();
 
-        let token = self.peek().clone();
-        match &token.kind {
+        let position = self.peek().position;
+        match &self.peek().kind {
             TokenKind::Class => self.parse_class_def(),
+            TokenKind::Module => self.parse_module_def(),
             TokenKind::Def => self.parse_function_def(),
             TokenKind::If => self.parse_if_statement(),
             TokenKind::Unless => self.parse_unless_statement(),
@@ -31,6 +32,7 @@ impl Parser {
             TokenKind::Raise => self.parse_raise_statement(),
             TokenKind::Break => self.parse_break_statement(),
             TokenKind::Continue => self.parse_continue_statement(),
+            TokenKind::Retry => self.parse_retry_statement(),
             TokenKind::Return => self.parse_return_statement(),
             TokenKind::AttrReader => self.parse_attr_reader(),
             TokenKind::AttrWriter => self.parse_attr_writer(),
@@ -49,6 +51,7 @@ impl Parser {
                 ]) {
                     let op_token = self.advance();
                     let value = self.parse_expression_with_lambda()?;
+                    let value = self.parse_rescue_modifier(value)?;
 
                     // Convert compound assignment to regular assignment with binary op
                     let final_value = match op_token.kind {
@@ -83,13 +86,13 @@ impl Parser {
                     Ok(Statement::Assignment {
                         target: expr,
                         value: final_value,
-                        position: token.position,
+                        position,
                     })
                 } else {
                     // It's just an expression statement
                     Ok(Statement::Expression {
-                        expression: expr,
-                        position: token.position,
+                        expression: self.parse_rescue_modifier(expr)?,
+                        position,
                     })
                 }
             }
@@ -26,7 +26,6 @@ impl TokenStream {
     }
 
     /// Get the token at an offset from the current position
-    #[allow(dead_code)]
     pub fn peek_ahead(&self, offset: usize) -> &Token {
         self.tokens
             .get(self.current + offset)
@@ -90,7 +89,6 @@ impl TokenStream {
     }
 
     /// Skip any newline tokens
-    #[allow(dead_code)]
     pub fn skip_newlines(&mut self) {
         while self.match_token(&[TokenKind::Newline]) {
             // Keep consuming newlines
@@ -98,7 +96,6 @@ impl TokenStream {
     }
 
     /// Skip any comment tokens
-    #[allow(dead_code)]
     pub fn skip_comments(&mut self) {
         while matches!(self.peek().kind, TokenKind::Comment(_)) {
             self.advance();
@@ -126,4 +123,11 @@ impl TokenStream {
     pub fn restore_position(&mut self, position: usize) {
         self.current = position;
     }
+
+    /// Move to the first token that starts at or after the source offset
+    pub fn seek(&mut self, offset: usize) {
+        self.current = self
+            .tokens
+            .partition_point(|token| token.span.start < offset && token.kind != TokenKind::EOF);
+    }
 }
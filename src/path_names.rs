@@ -0,0 +1,126 @@
+// Platform-independent path name handling
+// Backs require_relative and the File path helpers (File.join, basename, ...).
+//
+// Paths written in Metorex source may use either `/` or `\` as a separator, so
+// a script behaves the same on every platform. Names are handled as text with
+// `/` separators rather than through `std::path`, whose rules differ between
+// Windows and Unix.
+
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+/// The prefix Windows puts on canonical paths, e.g. `\\?\C:\src\app.mx`
+const VERBATIM_PREFIX: &str = r"\\?\";
+
+/// The prefix of a canonical network path, e.g. `\\?\UNC\server\share`
+const VERBATIM_UNC_PREFIX: &str = r"\\?\UNC\";
+
+/// `path` with every `\` separator written as `/`
+///
+/// A UNC path such as `\\server\share\lib` becomes `//server/share/lib`,
+/// which Windows still reads as a network path. Verbatim `\\?\` paths are
+/// left alone, since Windows does not accept `/` in them.
+pub fn normalize_separators(path: &str) -> Cow<'_, str> {
+    if !path.contains('\\') || path.starts_with(VERBATIM_PREFIX) {
+        Cow::Borrowed(path)
+    } else {
+        Cow::Owned(path.replace('\\', "/"))
+    }
+}
+
+/// A canonical path without the `\\?\` prefix Windows adds to it
+///
+/// `\\?\C:\app.mx` becomes `C:\app.mx` and `\\?\UNC\server\share` becomes
+/// `\\server\share`, so canonical paths print and compare like the paths
+/// users write. Other paths are returned unchanged.
+pub fn strip_verbatim_prefix(path: PathBuf) -> PathBuf {
+    let Some(text) = path.to_str() else {
+        return path;
+    };
+    if let Some(rest) = text.strip_prefix(VERBATIM_UNC_PREFIX) {
+        return PathBuf::from(format!(r"\\{}", rest));
+    }
+    match text.strip_prefix(VERBATIM_PREFIX) {
+        Some(rest) if rest.as_bytes().get(1) == Some(&b':') => PathBuf::from(rest),
+        _ => path,
+    }
+}
+
+/// The key a path is tracked under, lowercased when paths are compared
+/// without regard to case
+pub fn path_key(path: &Path, case_insensitive: bool) -> PathBuf {
+    if case_insensitive {
+        PathBuf::from(path.to_string_lossy().to_lowercase())
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// The parts joined with single `/` separators: `join(["lib/", "/a.mx"])`
+/// is `lib/a.mx`
+pub fn join<S: AsRef<str>>(parts: &[S]) -> String {
+    let mut joined = String::new();
+    for (index, part) in parts.iter().enumerate() {
+        let part = normalize_separators(part.as_ref());
+        if index == 0 {
+            joined.push_str(&part);
+            continue;
+        }
+        let part = part.trim_start_matches('/');
+        if !joined.ends_with('/') {
+            joined.push('/');
+        }
+        joined.push_str(part);
+    }
+    joined
+}
+
+/// The last component of `path`, ignoring trailing separators
+///
+/// `suffix` is removed from the end when present and not the whole name;
+/// `".*"` removes any extension.
+pub fn basename(path: &str, suffix: Option<&str>) -> String {
+    let path = normalize_separators(path);
+    let trimmed = path.trim_end_matches('/');
+    if trimmed.is_empty() {
+        return if path.is_empty() { "" } else { "/" }.to_string();
+    }
+    let name = trimmed.rsplit('/').next().unwrap_or(trimmed);
+    let stem = match suffix {
+        Some(".*") => name.strip_suffix(extname(name).as_str()),
+        Some(suffix) => name.strip_suffix(suffix),
+        None => None,
+    };
+    match stem {
+        Some(stem) if !stem.is_empty() => stem.to_string(),
+        _ => name.to_string(),
+    }
+}
+
+/// Everything before the last component of `path`: `"."` for a bare name
+/// and `"/"` for a name at the root
+pub fn dirname(path: &str) -> String {
+    let path = normalize_separators(path);
+    let trimmed = path.trim_end_matches('/');
+    match trimmed.rfind('/') {
+        Some(end) => {
+            let dir = trimmed[..end].trim_end_matches('/');
+            if dir.is_empty() { "/" } else { dir }.to_string()
+        }
+        None if path.starts_with('/') => "/".to_string(),
+        None => ".".to_string(),
+    }
+}
+
+/// The extension of the last component of `path`, with its dot, or `""`
+///
+/// A leading dot starts a hidden file's name rather than an extension, and
+/// a name ending in a dot has no extension.
+pub fn extname(path: &str) -> String {
+    let name = basename(path, None);
+    let dotted = name.trim_start_matches('.');
+    match dotted.rfind('.') {
+        Some(dot) if dot + 1 < dotted.len() => dotted[dot..].to_string(),
+        _ => String::new(),
+    }
+}
@@ -0,0 +1,225 @@
+// Pretty printer for runtime values
+// Lays out nested Arrays, Hashes and Instances over multiple lines when they
+// do not fit within the configured width
+
+use crate::object::Object;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Default line width used by `pp` and the REPL
+pub const DEFAULT_PRETTY_PRINT_WIDTH: usize = 80;
+
+/// Number of spaces added for each level of nesting
+const INDENT_WIDTH: usize = 2;
+
+/// Width-aware formatter for nested runtime values
+///
+/// A value is printed on one line when it fits; otherwise its elements are
+/// placed on their own lines, one nesting level deeper, and laid out the same
+/// way recursively. Self-referencing containers print as `[...]` or `{...}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrettyPrinter {
+    width: usize,
+}
+
+impl PrettyPrinter {
+    /// Create a pretty printer using the default width
+    pub fn new() -> Self {
+        Self::with_width(DEFAULT_PRETTY_PRINT_WIDTH)
+    }
+
+    /// Create a pretty printer that wraps lines longer than `width`
+    pub fn with_width(width: usize) -> Self {
+        PrettyPrinter { width }
+    }
+
+    /// The maximum line width this printer aims for
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Format a value, breaking it over multiple lines if needed
+    pub fn format(&self, obj: &Object) -> String {
+        let mut out = String::new();
+        self.layout(obj, 0, 0, &mut Vec::new(), &mut out);
+        out
+    }
+
+    /// Format a value on a single line
+    pub fn format_inline(&self, obj: &Object) -> String {
+        inline(obj, &mut Vec::new())
+    }
+
+    /// Append the layout of `obj` to `out`.
+    ///
+    /// `indent` is the nesting indentation of the current line and `column` is
+    /// where the value starts on that line (after any key prefix).
+    fn layout(
+        &self,
+        obj: &Object,
+        indent: usize,
+        column: usize,
+        seen: &mut Vec<usize>,
+        out: &mut String,
+    ) {
+        let flat = inline(obj, seen);
+        if column + flat.len() <= self.width {
+            out.push_str(&flat);
+            return;
+        }
+
+        let Some(address) = container_address(obj) else {
+            out.push_str(&flat);
+            return;
+        };
+        if seen.contains(&address) {
+            out.push_str(&flat);
+            return;
+        }
+        seen.push(address);
+
+        let child_indent = indent + INDENT_WIDTH;
+        let padding = " ".repeat(child_indent);
+
+        match obj {
+            Object::Array(items) => {
+                let items = items.borrow();
+                out.push_str("[\n");
+                for (i, item) in items.iter().enumerate() {
+                    out.push_str(&padding);
+                    self.layout(item, child_indent, child_indent, seen, out);
+                    push_separator(out, i + 1 < items.len());
+                }
+                out.push_str(&" ".repeat(indent));
+                out.push(']');
+            }
+            Object::Dict(map) => {
+                let map = map.borrow();
+                out.push_str("{\n");
+                for (i, (key, value)) in map.iter().enumerate() {
+                    let prefix = format!("\"{}\" => ", key);
+                    out.push_str(&padding);
+                    out.push_str(&prefix);
+                    self.layout(value, child_indent, child_indent + prefix.len(), seen, out);
+                    push_separator(out, i + 1 < map.len());
+                }
+                out.push_str(&" ".repeat(indent));
+                out.push('}');
+            }
+            Object::Instance(instance) => {
+                let instance = instance.borrow();
+                let vars = sorted_instance_vars(&instance.instance_vars);
+                out.push('<');
+                out.push_str(instance.class_name());
+                out.push('\n');
+                for (i, (name, value)) in vars.iter().enumerate() {
+                    let prefix = format!("@{}=", name);
+                    out.push_str(&padding);
+                    out.push_str(&prefix);
+                    self.layout(value, child_indent, child_indent + prefix.len(), seen, out);
+                    push_separator(out, i + 1 < vars.len());
+                }
+                out.push_str(&" ".repeat(indent));
+                out.push('>');
+            }
+            _ => out.push_str(&flat),
+        }
+
+        seen.pop();
+    }
+}
+
+impl Default for PrettyPrinter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// End a multi-line element, adding a comma unless it is the last one
+fn push_separator(out: &mut String, more: bool) {
+    if more {
+        out.push(',');
+    }
+    out.push('\n');
+}
+
+/// Identity of a container value, used to detect self-references
+fn container_address(obj: &Object) -> Option<usize> {
+    match obj {
+        Object::Array(items) => Some(Rc::as_ptr(items) as *const () as usize),
+        Object::Dict(map) => Some(Rc::as_ptr(map) as *const () as usize),
+        Object::Instance(instance) => Some(Rc::as_ptr(instance) as *const () as usize),
+        _ => None,
+    }
+}
+
+/// Instance variables sorted by name so output is deterministic
+fn sorted_instance_vars(vars: &HashMap<String, Object>) -> Vec<(&String, &Object)> {
+    let mut vars: Vec<(&String, &Object)> = vars.iter().collect();
+    vars.sort_by_key(|(name, _)| *name);
+    vars
+}
+
+/// Single-line rendering, matching the REPL's result format
+fn inline(obj: &Object, seen: &mut Vec<usize>) -> String {
+    if let Some(address) = container_address(obj) {
+        if seen.contains(&address) {
+            return match obj {
+                Object::Array(_) => "[...]".to_string(),
+                Object::Dict(_) => "{...}".to_string(),
+                _ => "<...>".to_string(),
+            };
+        }
+        seen.push(address);
+        let text = inline_container(obj, seen);
+        seen.pop();
+        return text;
+    }
+
+    match obj {
+        Object::Nil => "nil".to_string(),
+        Object::Float(f) if f.fract() == 0.0 && f.is_finite() => format!("{:.1}", f),
+        Object::String(s) => format!("\"{}\"", s),
+        Object::Symbol(s) => format!(":{}", s),
+        Object::Range {
+            start,
+            end,
+            exclusive,
+        } => {
+            let dots = if *exclusive { "..." } else { ".." };
+            format!("{}{}{}", inline(start, seen), dots, inline(end, seen))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Single-line rendering of an Array, Hash or Instance
+fn inline_container(obj: &Object, seen: &mut Vec<usize>) -> String {
+    match obj {
+        Object::Array(items) => {
+            let items: Vec<String> = items.borrow().iter().map(|i| inline(i, seen)).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Object::Dict(map) => {
+            let entries: Vec<String> = map
+                .borrow()
+                .iter()
+                .map(|(k, v)| format!("\"{}\" => {}", k, inline(v, seen)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        Object::Instance(instance) => {
+            let instance = instance.borrow();
+            let vars = sorted_instance_vars(&instance.instance_vars);
+            if vars.is_empty() {
+                return format!("<{} instance>", instance.class_name());
+            }
+            let vars: Vec<String> = vars
+                .iter()
+                .map(|(name, value)| format!("@{}={}", name, inline(value, seen)))
+                .collect();
+            format!("<{} {}>", instance.class_name(), vars.join(", "))
+        }
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,133 @@
+// Startup files for the REPL
+// A `.metorexrc` holds Metorex code run before the first prompt, so personal
+// helpers, `require`s and settings such as `Metorex.pp_width(100)` are ready
+// in every session. The one in the home directory runs first, then the one in
+// the directory the REPL starts in, so a project can add to or override the
+// personal setup. `metorex run --rc` runs them before a script as well.
+
+use crate::error::MetorexError;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::terminal::Palette;
+use crate::vm::VirtualMachine;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Name of the startup file looked for in the home and project directories
+pub const RC_FILE_NAME: &str = ".metorexrc";
+
+/// The user's home directory, from `HOME` or, failing that, `USERPROFILE`
+pub fn home_dir() -> Option<PathBuf> {
+    ["HOME", "USERPROFILE"]
+        .into_iter()
+        .filter_map(env::var_os)
+        .find(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+/// The rc files that exist, in the order they run: the one in `home`, then
+/// the one in `project_dir`. A project directory that is the home directory
+/// gives its file once.
+pub fn rc_file_paths(home: Option<&Path>, project_dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for dir in home.into_iter().chain([project_dir]) {
+        let path = dir.join(RC_FILE_NAME);
+        if !path.is_file() {
+            continue;
+        }
+        let canonical = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+        let seen = paths
+            .iter()
+            .any(|other| fs::canonicalize(other).is_ok_and(|other| other == canonical));
+        if !seen {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// Why an rc file could not be loaded
+#[derive(Debug)]
+pub enum RcFileError {
+    /// The file could not be read
+    Read { path: PathBuf, error: io::Error },
+    /// The file has syntax errors, so none of it ran
+    Parse {
+        path: PathBuf,
+        source: String,
+        errors: Vec<MetorexError>,
+    },
+    /// The file raised an error part way through
+    Run {
+        path: PathBuf,
+        source: String,
+        error: Box<MetorexError>,
+    },
+}
+
+impl RcFileError {
+    /// The rc file that failed
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Read { path, .. } | Self::Parse { path, .. } | Self::Run { path, .. } => path,
+        }
+    }
+
+    /// A report naming the file, with each error shown against its line
+    pub fn report(&self, palette: Palette) -> String {
+        let mut out = format!("Error in rc file '{}':", self.path().display());
+        match self {
+            Self::Read { error, .. } => {
+                out.push_str(&format!("\n  {}", error));
+            }
+            Self::Parse { source, errors, .. } => {
+                for error in errors {
+                    let diagnostic = palette.diagnostic("Parse error", error, Some(source));
+                    out.push_str(&format!("\n  {}", diagnostic.replace('\n', "\n  ")));
+                }
+            }
+            Self::Run { source, error, .. } => {
+                let diagnostic = palette.diagnostic("Runtime error", error, Some(source));
+                out.push_str(&format!("\n  {}", diagnostic.replace('\n', "\n  ")));
+            }
+        }
+        out
+    }
+}
+
+/// Run the rc file at `path` in `vm`.
+///
+/// The file runs as the current file, so `require_relative` resolves against
+/// its directory, and the VM's current file and source are restored
+/// afterwards.
+pub fn load_rc_file(vm: &mut VirtualMachine, path: &Path) -> Result<(), RcFileError> {
+    let source = fs::read_to_string(path).map_err(|error| RcFileError::Read {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    let program = Parser::new(Lexer::new(&source).tokenize())
+        .parse()
+        .map_err(|errors| RcFileError::Parse {
+            path: path.to_path_buf(),
+            source: source.clone(),
+            errors,
+        })?;
+
+    let previous_file = vm.get_current_file().cloned();
+    let previous_source = vm.current_source().cloned();
+    vm.set_current_file(fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()));
+    vm.set_current_source(Some(&source));
+    let result = vm.execute_program(&program);
+    match previous_file {
+        Some(file) => vm.set_current_file(file),
+        None => vm.clear_current_file(),
+    }
+    vm.set_current_source(previous_source.as_deref());
+    result.map(|_| ()).map_err(|error| RcFileError::Run {
+        path: path.to_path_buf(),
+        source,
+        error: Box::new(error),
+    })
+}
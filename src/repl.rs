@@ -1,44 +1,240 @@
 // Metorex REPL
 // Interactive Read-Eval-Print Loop for Metorex
 
+use crate::ast::Statement;
 use crate::error::MetorexError;
+use crate::file_loader::parse_file;
 use crate::lexer::Lexer;
+use crate::messages;
 use crate::object::Object;
 use crate::parser::Parser;
-use crate::vm::VirtualMachine;
+use crate::rc_file::{RcFileError, load_rc_file};
+use crate::session::Session;
+use crate::terminal::{ColorChoice, Palette};
+use crate::vm::{DefinitionDiff, PostMortem, PostMortemFrame, VirtualMachine, VmOptions};
+use rustyline::completion::Completer;
 use rustyline::error::ReadlineError;
-use rustyline::{DefaultEditor, Result as RustylineResult};
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Config, Editor, Helper, Result as RustylineResult};
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
 
 const PROMPT: &str = ">> ";
 const CONTINUATION_PROMPT: &str = ".. ";
+const RESULT_PREFIX_WIDTH: usize = "=> ".len();
 const BANNER: &str = include_str!("banner.txt");
 
+/// Variable bound to the most recent result
+pub const LAST_RESULT_VAR: &str = "_";
+
+/// Alias for `_`, as on a calculator
+pub const ANSWER_VAR: &str = "ans";
+
+/// Line editor helper that syntax-highlights input as it is typed
+struct ReplHelper {
+    palette: Palette,
+}
+
+impl Helper for ReplHelper {}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Validator for ReplHelper {}
+
+impl Highlighter for ReplHelper {
+    fn highlight<'l>(&self, line: &'l str, _pos: usize) -> Cow<'l, str> {
+        if self.palette.is_enabled() {
+            Cow::Owned(self.palette.highlight(line))
+        } else {
+            Cow::Borrowed(line)
+        }
+    }
+
+    fn highlight_char(&self, _line: &str, _pos: usize, _forced: bool) -> bool {
+        self.palette.is_enabled()
+    }
+}
+
 pub struct Repl {
     vm: VirtualMachine,
-    editor: DefaultEditor,
+    editor: Editor<ReplHelper, DefaultHistory>,
     buffer: String,
+    session: Session,
+    output: Palette,
+    errors: Palette,
+    options: VmOptions,
+    results: Vec<Object>,
+    /// What the last successful input defined or assigned
+    last_definitions: DefinitionDiff,
+    /// Whether `.paste` is collecting lines until Ctrl-D
+    pasting: bool,
+    /// The failed program being inspected, for a post-mortem session
+    post_mortem: Option<PostMortem>,
+    /// Index of the post-mortem frame inputs are evaluated in
+    frame: usize,
+    /// Startup files run before the first prompt and again after `.reset`
+    rc_files: Vec<PathBuf>,
 }
 
 impl Repl {
-    /// Create a new REPL instance
+    /// Create a new REPL instance, coloring output when attached to a terminal
     pub fn new() -> RustylineResult<Self> {
-        let editor = DefaultEditor::new()?;
+        Self::with_color(ColorChoice::Auto)
+    }
+
+    /// Create a new REPL instance with an explicit color setting
+    pub fn with_color(choice: ColorChoice) -> RustylineResult<Self> {
+        Self::with_options(choice, VmOptions::default())
+    }
+
+    /// Create a new REPL instance whose VM, including after `.reset`, uses `options`
+    pub fn with_options(choice: ColorChoice, options: VmOptions) -> RustylineResult<Self> {
+        let vm = VirtualMachine::with_options(options.clone());
+        Self::with_vm(choice, options, vm)
+    }
+
+    /// Create a REPL for inspecting a program that failed in `vm`
+    ///
+    /// Inputs are evaluated in the frame that raised the error, with the
+    /// exception bound to `$!`. `.backtrace` lists the frames and `.frame N`
+    /// moves to another one.
+    pub fn for_post_mortem(
+        choice: ColorChoice,
+        mut vm: VirtualMachine,
+        post_mortem: PostMortem,
+    ) -> RustylineResult<Self> {
+        vm.set_post_mortem(false);
+        vm.environment()
+            .global_scope()
+            .borrow_mut()
+            .define("$!".to_string(), post_mortem.exception.clone());
+        let options = vm.options().clone();
+        let mut repl = Self::with_vm(choice, options, vm)?;
+        repl.post_mortem = Some(post_mortem);
+        repl.select_frame(0);
+        Ok(repl)
+    }
+
+    fn with_vm(
+        choice: ColorChoice,
+        options: VmOptions,
+        vm: VirtualMachine,
+    ) -> RustylineResult<Self> {
+        let output = Palette::for_stdout(choice);
+        // A bracketed paste arrives as one multi-line input, so it is
+        // evaluated as a single chunk
+        let config = Config::builder().bracketed_paste(true).build();
+        let mut editor = Editor::with_config(config)?;
+        editor.set_helper(Some(ReplHelper { palette: output }));
         Ok(Self {
-            vm: VirtualMachine::new(),
+            vm,
             editor,
             buffer: String::new(),
+            session: Session::new(),
+            output,
+            errors: Palette::for_stderr(choice),
+            options,
+            results: Vec::new(),
+            last_definitions: DefinitionDiff::default(),
+            pasting: false,
+            post_mortem: None,
+            frame: 0,
+            rc_files: Vec::new(),
         })
     }
 
+    /// Load a library into the session, as `require` does
+    pub fn require(&mut self, name: &str) -> Result<bool, MetorexError> {
+        self.vm.require_library(name)
+    }
+
+    /// Run startup files in order, and remember them so `.reset` runs them
+    /// again. A file that fails does not stop the ones after it; the errors
+    /// are returned for the caller to report.
+    ///
+    /// What the files define is not part of the session, so `.save` leaves
+    /// it out.
+    pub fn load_rc_files(&mut self, paths: Vec<PathBuf>) -> Vec<RcFileError> {
+        self.rc_files = paths;
+        self.run_rc_files()
+    }
+
+    fn run_rc_files(&mut self) -> Vec<RcFileError> {
+        let mut errors = Vec::new();
+        for path in &self.rc_files {
+            if let Err(err) = load_rc_file(&mut self.vm, path) {
+                errors.push(err);
+            }
+        }
+        errors
+    }
+
+    /// The failed program this session inspects, if it is a post-mortem one
+    pub fn post_mortem(&self) -> Option<&PostMortem> {
+        self.post_mortem.as_ref()
+    }
+
+    /// Index of the post-mortem frame inputs are evaluated in
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Evaluate later inputs in post-mortem frame `index`, 0 being the one
+    /// that raised. Returns the frame, or `None` if there is no such frame.
+    pub fn select_frame(&mut self, index: usize) -> Option<&PostMortemFrame> {
+        let frame = self.post_mortem.as_ref()?.frames.get(index)?;
+        self.vm.enter_frame(frame);
+        self.frame = index;
+        Some(frame)
+    }
+
+    /// The post-mortem frames, one per line, marking the current one
+    pub fn backtrace(&self) -> Vec<String> {
+        let Some(post_mortem) = &self.post_mortem else {
+            return Vec::new();
+        };
+        post_mortem
+            .frames
+            .iter()
+            .enumerate()
+            .map(|(index, frame)| {
+                let marker = if index == self.frame { '*' } else { ' ' };
+                match frame.location() {
+                    Some(location) => {
+                        format!("{} {}: {} ({})", marker, index, frame.name(), location)
+                    }
+                    None => format!("{} {}: {}", marker, index, frame.name()),
+                }
+            })
+            .collect()
+    }
+
     /// Start the REPL loop
     pub fn run(&mut self) -> RustylineResult<()> {
-        println!("{}", BANNER);
-        println!("Metorex REPL v{}", env!("CARGO_PKG_VERSION"));
-        println!("Type .help for more information, .exit to quit");
+        if self.post_mortem.is_some() {
+            println!("// Post-mortem session: $! holds the exception, inputs run in frame 0");
+            println!("// Type .backtrace to list frames, .frame N to move to one, .exit to quit");
+            for line in self.backtrace() {
+                println!("{}", line);
+            }
+        } else {
+            println!("{}", BANNER);
+            println!("Metorex REPL v{}", env!("CARGO_PKG_VERSION"));
+            println!("Type .help for more information, .exit to quit");
+        }
         println!();
 
         loop {
-            let prompt = if self.buffer.is_empty() {
+            let prompt = if self.buffer.is_empty() && !self.pasting {
                 PROMPT
             } else {
                 CONTINUATION_PROMPT
@@ -49,6 +245,13 @@ impl Repl {
                     // Add to history
                     let _ = self.editor.add_history_entry(&line);
 
+                    // Paste mode collects every line, commands included
+                    if self.pasting {
+                        self.buffer.push_str(&line);
+                        self.buffer.push('\n');
+                        continue;
+                    }
+
                     // Handle special commands
                     if self.buffer.is_empty() && line.trim().starts_with('.') {
                         if self.handle_command(&line) {
@@ -73,6 +276,14 @@ impl Repl {
                     // Ctrl-C - clear buffer and continue
                     println!("^C");
                     self.buffer.clear();
+                    self.pasting = false;
+                }
+                Err(ReadlineError::Eof) if self.pasting => {
+                    // Ctrl-D ends paste mode and runs what was pasted
+                    self.pasting = false;
+                    println!("// Running pasted input");
+                    self.evaluate_buffer();
+                    self.buffer.clear();
                 }
                 Err(ReadlineError::Eof) => {
                     // Ctrl-D - exit
@@ -90,8 +301,12 @@ impl Repl {
     /// Handle special REPL commands
     fn handle_command(&mut self, line: &str) -> bool {
         let cmd = line.trim();
+        let (name, argument) = match cmd.split_once(char::is_whitespace) {
+            Some((name, argument)) => (name, argument.trim()),
+            None => (cmd, ""),
+        };
 
-        match cmd {
+        match name {
             ".exit" | ".quit" => {
                 println!("Goodbye!");
                 return true;
@@ -106,10 +321,52 @@ impl Repl {
                 println!("Type .help for more information, .exit to quit");
                 println!();
             }
+            ".paste" => {
+                self.pasting = true;
+                println!("// Paste mode: Ctrl-D runs the input as one chunk, Ctrl-C cancels");
+            }
+            ".backtrace" | ".bt" if self.post_mortem.is_some() => {
+                for line in self.backtrace() {
+                    println!("{}", line);
+                }
+            }
+            ".frame" if self.post_mortem.is_some() => {
+                let selected = argument
+                    .parse()
+                    .ok()
+                    .and_then(|index| self.select_frame(index))
+                    .map(|frame| frame.name().to_string());
+                match selected {
+                    Some(name) => println!("// frame {}: {}", self.frame, name),
+                    None => eprintln!("Usage: .frame N, where N is a frame from .backtrace"),
+                }
+            }
             ".reset" => {
-                self.vm = VirtualMachine::new();
+                self.post_mortem = None;
+                self.frame = 0;
+                self.vm = VirtualMachine::with_options(self.options.clone());
+                self.session.clear();
+                self.results.clear();
+                for err in self.run_rc_files() {
+                    eprintln!("{}", err.report(self.errors));
+                }
                 println!("VM state reset");
             }
+            ".save" | ".load" | ".reload" if argument.is_empty() => {
+                eprintln!("Usage: {} <file>", name);
+            }
+            ".save" => match self.save_session(Path::new(argument)) {
+                Ok(()) => println!("Saved {} entries to {}", self.session.len(), argument),
+                Err(err) => eprintln!("Error: {}", self.format_error(&err)),
+            },
+            ".load" => match self.load_session(Path::new(argument)) {
+                Ok(count) => println!("Loaded {} entries from {}", count, argument),
+                Err(err) => eprintln!("Error: {}", self.format_error(&err)),
+            },
+            ".reload" => match self.vm.reload_file(Path::new(argument)) {
+                Ok(_) => println!("Reloaded {}", argument),
+                Err(err) => eprintln!("Error: {}", self.format_error(&err)),
+            },
             _ => {
                 eprintln!("Unknown command: {}", cmd);
                 eprintln!("Type .help for available commands");
@@ -122,11 +379,23 @@ impl Repl {
     /// Print help information
     fn print_help(&self) {
         println!("Metorex REPL Commands:");
-        println!("  .help       Show this help message");
-        println!("  .exit       Exit the REPL (or Ctrl-D)");
-        println!("  .quit       Alias for .exit");
-        println!("  .clear      Clear the screen");
-        println!("  .reset      Reset the VM state");
+        println!("  .help         Show this help message");
+        println!("  .exit         Exit the REPL (or Ctrl-D)");
+        println!("  .quit         Alias for .exit");
+        println!("  .clear        Clear the screen");
+        println!("  .reset        Reset the VM state and run the .metorexrc files again");
+        println!("  .paste        Collect lines until Ctrl-D, then run them together");
+        println!("  .save FILE    Save this session's inputs to FILE");
+        println!("  .load FILE    Replay a saved session into the VM");
+        println!("  .reload FILE  Re-run FILE, redefining its classes in place");
+        if self.post_mortem.is_some() {
+            println!("  .backtrace    List the frames of the failed program (or .bt)");
+            println!("  .frame N      Evaluate inputs in frame N of the backtrace");
+        }
+        println!();
+        println!("Result history:");
+        println!("  _, ans      The most recent result");
+        println!("  _1, _2, ... Every result in order, starting from _1");
         println!();
         println!("Keyboard shortcuts:");
         println!("  Ctrl-C      Clear current input buffer");
@@ -136,6 +405,15 @@ impl Repl {
         println!("  The REPL automatically detects incomplete expressions");
         println!("  and prompts for continuation with '..'");
         println!();
+        println!("Definitions:");
+        println!("  After an input defines classes, methods or functions, the REPL");
+        println!("  lists them, e.g. // defined: Point, Point#norm, greet");
+        println!();
+        println!("Failed input:");
+        println!("  Variables, methods and classes an input defined or assigned");
+        println!("  before raising are rolled back; objects it changed in place");
+        println!("  keep their changes");
+        println!();
     }
 
     /// Determine if the current buffer should be evaluated
@@ -155,19 +433,12 @@ impl Repl {
         match parser.parse() {
             Ok(_) => true, // Successfully parsed, ready to evaluate
             Err(errors) => {
-                // Check if error indicates incomplete input
-                for error in &errors {
-                    let error_msg = error.to_string().to_lowercase();
-                    if error_msg.contains("unexpected end of input")
-                        || error_msg.contains("expected 'end'")
-                        || error_msg.contains("unclosed")
-                        || error_msg.contains("incomplete")
-                    {
-                        return false; // Need more input
-                    }
-                }
-                // Other parse errors - try to evaluate anyway to show the error
-                true
+                // A missing `end` means more lines could complete the input;
+                // any other parse error is shown right away
+                !errors
+                    .iter()
+                    .filter_map(MetorexError::code)
+                    .any(messages::is_unfinished_input)
             }
         }
     }
@@ -184,29 +455,172 @@ impl Repl {
             Ok(prog) => prog,
             Err(errors) => {
                 for err in errors {
-                    eprintln!("Parse error: {}", err);
+                    eprintln!(
+                        "{}",
+                        self.errors
+                            .diagnostic("Parse error", &err, Some(&self.buffer))
+                    );
                 }
                 return;
             }
         };
 
         // Execute and display result
-        match self.vm.execute_program(&program) {
+        let source = self.buffer.clone();
+        let outcome = self.execute_input(&program, &source);
+        if outcome.is_ok() && self.last_definitions.defines_anything() {
+            println!(
+                "// defined: {}",
+                self.last_definitions.definitions().join(", ")
+            );
+        }
+        match outcome {
             Ok(Some(result)) => {
+                self.record_result(result.clone());
+
                 // Display non-nil results
                 if !matches!(result, Object::Nil) {
-                    println!("=> {}", Self::format_object(&result));
+                    println!("=> {}", self.format_result(&result));
                 }
             }
             Ok(None) => {
                 // No result (e.g., statements like assignments)
             }
             Err(err) => {
-                eprintln!("Runtime error: {}", self.format_error(&err));
+                eprintln!(
+                    "{}",
+                    self.errors
+                        .diagnostic("Runtime error", &err, Some(&self.buffer))
+                );
             }
         }
     }
 
+    /// Inputs evaluated so far in this session
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    /// Evaluate source as if it had been typed at the prompt, recording it
+    /// in the session when it succeeds
+    pub fn eval(&mut self, source: &str) -> Result<Option<Object>, MetorexError> {
+        let program = parse_file(source, "(repl)")?;
+        let result = self.execute_input(&program, source)?;
+        if let Some(value) = &result {
+            self.record_result(value.clone());
+        }
+        Ok(result)
+    }
+
+    /// What the last input that succeeded defined or assigned
+    pub fn last_definitions(&self) -> &DefinitionDiff {
+        &self.last_definitions
+    }
+
+    /// Run one input as a unit: it is recorded in the session when it
+    /// succeeds, and the bindings it changed are rolled back when it raises
+    fn execute_input(
+        &mut self,
+        program: &[Statement],
+        source: &str,
+    ) -> Result<Option<Object>, MetorexError> {
+        let snapshot = self.vm.snapshot();
+        let checkpoint = self.vm.definitions_checkpoint();
+        self.vm.set_current_source(Some(source));
+        match self.vm.execute_program(program) {
+            Ok(result) => {
+                self.session.record(source);
+                self.last_definitions = self.vm.methods_added_since(&checkpoint);
+                Ok(result)
+            }
+            Err(err) => {
+                self.vm.restore(snapshot);
+                Err(err)
+            }
+        }
+    }
+
+    /// Every result produced so far, oldest first; `_1` is the first
+    pub fn results(&self) -> &[Object] {
+        &self.results
+    }
+
+    /// Remember a result and bind it to `_`, `ans` and the next `_N`
+    fn record_result(&mut self, value: Object) {
+        self.results.push(value.clone());
+        let environment = self.vm.environment_mut();
+        environment.define(format!("_{}", self.results.len()), value.clone());
+        environment.define(LAST_RESULT_VAR.to_string(), value.clone());
+        environment.define(ANSWER_VAR.to_string(), value);
+    }
+
+    /// Remove every result binding from the environment
+    fn unbind_results(&mut self) {
+        let environment = self.vm.environment_mut();
+        for number in 1..=self.results.len() {
+            environment.remove(&format!("_{}", number));
+        }
+        environment.remove(LAST_RESULT_VAR);
+        environment.remove(ANSWER_VAR);
+    }
+
+    /// Bind every recorded result again, after `unbind_results`
+    fn bind_results(&mut self) {
+        let environment = self.vm.environment_mut();
+        for (index, value) in self.results.iter().enumerate() {
+            environment.define(format!("_{}", index + 1), value.clone());
+        }
+        if let Some(last) = self.results.last() {
+            environment.define(LAST_RESULT_VAR.to_string(), last.clone());
+            environment.define(ANSWER_VAR.to_string(), last.clone());
+        }
+    }
+
+    /// Write the session's inputs to `path` as a replayable script
+    pub fn save_session(&self, path: &Path) -> Result<(), MetorexError> {
+        self.session.save(path)
+    }
+
+    /// Replay a saved session into the VM, returning how many entries ran
+    ///
+    /// Loaded entries become part of this session, so saving again keeps them.
+    /// Results from earlier inputs are hidden while the file runs, so it only
+    /// sees `_` values produced by its own entries.
+    pub fn load_session(&mut self, path: &Path) -> Result<usize, MetorexError> {
+        let loaded = Session::load(path)?;
+        self.unbind_results();
+        let replayed = self.replay_entries(&loaded);
+        self.bind_results();
+        replayed?;
+        let count = loaded.len();
+        self.session.extend(loaded);
+        Ok(count)
+    }
+
+    /// Evaluate each entry of a loaded session in order, recording results
+    fn replay_entries(&mut self, session: &Session) -> Result<(), MetorexError> {
+        for (index, entry) in session.entries().iter().enumerate() {
+            let program = parse_file(entry, &format!("session entry {}", index + 1))?;
+            self.vm.set_current_source(Some(entry));
+            if let Some(value) = self.vm.execute_program(&program)? {
+                self.record_result(value);
+            }
+        }
+        Ok(())
+    }
+
+    /// Format an evaluation result, switching to the pretty printer when the
+    /// one-line form would not fit after the `=> ` prompt
+    fn format_result(&self, obj: &Object) -> String {
+        let line = Self::format_object(obj);
+        let printer = self.vm.pretty_printer();
+        if RESULT_PREFIX_WIDTH + line.len() <= printer.width() {
+            self.output.inspect(obj)
+        } else {
+            printer.format(obj)
+        }
+    }
+
     /// Format an object for display
     pub fn format_object(obj: &Object) -> String {
         match obj {
@@ -218,9 +632,10 @@ impl Repl {
                 if f.fract() == 0.0 && f.is_finite() {
                     format!("{:.1}", f)
                 } else {
-                    f.to_string()
+                    obj.to_string()
                 }
             }
+            Object::Decimal(d) => d.to_string(),
             Object::String(s) => format!("\"{}\"", s),
             Object::Symbol(s) => format!(":{}", s),
             Object::Array(items) => {
@@ -231,17 +646,18 @@ impl Repl {
             }
             Object::Dict(map) => {
                 let map_borrowed = map.borrow();
-                let mut entries: Vec<String> = map_borrowed
+                // Hashes keep insertion order, so entries display in that order
+                let entries: Vec<String> = map_borrowed
                     .iter()
                     .map(|(k, v)| format!("\"{}\" => {}", k, Self::format_object(v)))
                     .collect();
-                entries.sort(); // Sort for consistent display
                 format!("{{{}}}", entries.join(", "))
             }
             Object::Block { .. } => "<Block>".to_string(),
             Object::Method { .. } => "<Method>".to_string(),
             Object::NativeFunction(name) => format!("<NativeFunction: {}>", name),
             Object::Class(class) => format!("<Class: {}>", class.name()),
+            Object::Module(module) => format!("<Module: {}>", module.name()),
             Object::Instance(instance) => {
                 let instance_borrowed = instance.borrow();
                 format!("<{} instance>", instance_borrowed.class_name())
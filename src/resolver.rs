@@ -2,16 +2,31 @@
 // This module implements static analysis for variable declarations and usage
 // It tracks variable scopes, detects undefined variables, and identifies shadowing
 
-use crate::ast::node::{Expression, MatchCase, MatchPattern, RescueClause, Statement};
+use crate::ast::node::{
+    DestructureTarget, Expression, FORWARDING_PARAMETER, MatchCase, MatchPattern, RescueClause,
+    Statement,
+};
 use crate::error::{MetorexError, SourceLocation};
 use crate::lexer::Position;
-use std::collections::HashMap;
+use crate::messages::{self, Message};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Display;
 
 /// Convert a Position to SourceLocation
 fn pos_to_loc(pos: Position) -> SourceLocation {
     SourceLocation::new(pos.line, pos.column, pos.offset)
 }
 
+/// A syntax error with the catalog text of `message`, tagged with its code
+fn coded_syntax_error(
+    message: &'static Message,
+    args: &[(&str, &dyn Display)],
+    position: Position,
+) -> MetorexError {
+    MetorexError::syntax_error(message.format(args), pos_to_loc(position))
+        .with_catalog_message(message)
+}
+
 /// Represents information about a variable declaration
 #[derive(Debug, Clone)]
 pub struct VariableInfo {
@@ -63,6 +78,79 @@ pub struct Resolver {
 
     /// Whether to treat undefined variables as errors
     strict_mode: bool,
+
+    /// Variants of each sealed class, declared with `variants :A, :B`
+    sealed_classes: BTreeMap<String, Vec<String>>,
+
+    /// Implicit block parameters referred to, when resolving a block body
+    /// for [`implicit_block_parameters`]
+    implicit_uses: Option<ImplicitUses>,
+
+    /// Number of function, method and class definitions being resolved
+    definition_depth: usize,
+}
+
+/// Uses of implicit block parameters that no scope declares
+#[derive(Debug, Default)]
+struct ImplicitUses {
+    numbered: Vec<(usize, Position)>,
+    it: Option<Position>,
+}
+
+/// Name of the implicit parameter of a block written `arr.map { it.name }`
+const IT_PARAMETER: &str = "it";
+
+/// The parameters a block body refers to without declaring them: `_1`
+/// through the highest numbered parameter it uses, or `it`.
+///
+/// A block with a parameter list may not use numbered parameters; in such a
+/// block, and inside definitions, `it` is an ordinary name. Blocks nested in
+/// the body keep the implicit parameters they use.
+pub fn implicit_block_parameters(
+    body: &[Statement],
+    has_parameter_list: bool,
+) -> Result<Vec<String>, MetorexError> {
+    let mut resolver = Resolver::with_strict_mode(false);
+    resolver.implicit_uses = Some(ImplicitUses::default());
+    resolver.push_scope();
+    for statement in body {
+        resolver.resolve_statement(statement);
+    }
+    let uses = resolver.implicit_uses.take().unwrap_or_default();
+
+    if let Some(&(number, position)) = uses.numbered.first() {
+        if has_parameter_list {
+            return Err(coded_syntax_error(
+                &messages::NUMBERED_PARAMETER_WITH_PARAMETER_LIST,
+                &[("number", &number)],
+                position,
+            ));
+        }
+        if let Some(position) = uses.it {
+            return Err(coded_syntax_error(
+                &messages::IT_WITH_NUMBERED_PARAMETERS,
+                &[],
+                position,
+            ));
+        }
+        let highest = uses.numbered.iter().map(|(number, _)| *number).max();
+        return Ok((1..=highest.unwrap_or(number))
+            .map(|number| format!("_{}", number))
+            .collect());
+    }
+
+    match uses.it {
+        Some(_) if !has_parameter_list => Ok(vec![IT_PARAMETER.to_string()]),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// The number of a numbered block parameter, `_1` through `_9`
+fn numbered_parameter(name: &str) -> Option<usize> {
+    match name.strip_prefix('_')?.as_bytes() {
+        [digit @ b'1'..=b'9'] => Some((digit - b'0') as usize),
+        _ => None,
+    }
 }
 
 impl Resolver {
@@ -74,6 +162,9 @@ impl Resolver {
             errors: Vec::new(),
             warnings: Vec::new(),
             strict_mode: true,
+            sealed_classes: BTreeMap::new(),
+            implicit_uses: None,
+            definition_depth: 0,
         }
     }
 
@@ -86,6 +177,9 @@ impl Resolver {
 
     /// Resolves variables in a list of statements
     pub fn resolve(&mut self, statements: &[Statement]) -> ResolutionResult {
+        // Sealed classes are known up front so cases before their definition are checked
+        self.collect_sealed_classes(statements);
+
         for statement in statements {
             self.resolve_statement(statement);
         }
@@ -125,6 +219,61 @@ impl Resolver {
         }
     }
 
+    /// Records the variants of every sealed class defined in `statements`
+    fn collect_sealed_classes(&mut self, statements: &[Statement]) {
+        for statement in statements {
+            if let Statement::ClassDef { name, body, .. } = statement {
+                if let Some(variants) = declared_variants(body) {
+                    self.sealed_classes.insert(name.clone(), variants);
+                }
+                self.collect_sealed_classes(body);
+            }
+        }
+    }
+
+    /// Warns when a case names some variants of a sealed class but not all of
+    /// them and has no catch-all branch
+    fn check_sealed_case<'a>(
+        &mut self,
+        cases: impl Iterator<Item = (&'a MatchPattern, bool)>,
+        position: Position,
+    ) {
+        let mut handled = HashSet::new();
+        for (pattern, guarded) in cases {
+            match pattern {
+                MatchPattern::Wildcard | MatchPattern::Identifier(_) if !guarded => return,
+                MatchPattern::Type(name) if !guarded => {
+                    handled.insert(name.as_str());
+                }
+                _ => {}
+            }
+        }
+
+        for (sealed, variants) in &self.sealed_classes {
+            if handled.contains(sealed.as_str())
+                || !variants
+                    .iter()
+                    .any(|variant| handled.contains(variant.as_str()))
+            {
+                continue;
+            }
+            let missing: Vec<&str> = variants
+                .iter()
+                .map(String::as_str)
+                .filter(|variant| !handled.contains(variant))
+                .collect();
+            if !missing.is_empty() {
+                self.warnings.push(format!(
+                    "Case at {}:{} does not handle every variant of sealed class '{}': missing {}",
+                    position.line,
+                    position.column,
+                    sealed,
+                    missing.join(", ")
+                ));
+            }
+        }
+    }
+
     /// Enters a new scope
     fn push_scope(&mut self) {
         self.scopes.push(HashMap::new());
@@ -143,12 +292,14 @@ impl Resolver {
     fn declare(&mut self, name: String, position: Position) {
         // Check for shadowing in current scope first
         if let Some(existing) = self.scopes.last().unwrap().get(&name) {
-            self.errors.push(MetorexError::syntax_error(
-                format!(
-                    "Variable '{}' is already declared in this scope at {}:{}",
-                    name, existing.position.line, existing.position.column
-                ),
-                pos_to_loc(position),
+            self.errors.push(coded_syntax_error(
+                &messages::VARIABLE_ALREADY_DECLARED,
+                &[
+                    ("name", &name),
+                    ("line", &existing.position.line),
+                    ("column", &existing.position.column),
+                ],
+                position,
             ));
             return;
         }
@@ -194,10 +345,24 @@ impl Resolver {
         }
 
         // Variable not found
+        if let Some(uses) = &mut self.implicit_uses
+            && self.definition_depth == 0
+        {
+            if let Some(number) = numbered_parameter(name) {
+                uses.numbered.push((number, position));
+                return None;
+            }
+            if name == IT_PARAMETER {
+                uses.it.get_or_insert(position);
+                return None;
+            }
+        }
+
         if self.strict_mode {
-            self.errors.push(MetorexError::syntax_error(
-                format!("Undefined variable '{}'", name),
-                pos_to_loc(position),
+            self.errors.push(coded_syntax_error(
+                &messages::UNDEFINED_VARIABLE,
+                &[("name", &name)],
+                position,
             ));
         }
 
@@ -253,12 +418,14 @@ impl Resolver {
                 parameters,
                 body,
                 position,
+                ..
             } => {
                 // Declare function name in current scope
                 self.declare(name.clone(), *position);
 
                 // Enter function scope
                 self.push_scope();
+                self.definition_depth += 1;
 
                 // Declare parameters
                 for param in parameters {
@@ -274,6 +441,7 @@ impl Resolver {
                 }
 
                 // Exit function scope
+                self.definition_depth -= 1;
                 self.pop_scope();
             }
 
@@ -282,6 +450,7 @@ impl Resolver {
             } => {
                 // Methods are similar to functions but don't declare a name in outer scope
                 self.push_scope();
+                self.definition_depth += 1;
 
                 // Declare parameters
                 for param in parameters {
@@ -296,21 +465,24 @@ impl Resolver {
                     self.resolve_statement(stmt);
                 }
 
+                self.definition_depth -= 1;
                 self.pop_scope();
             }
 
-            Statement::ClassDef { name, body, .. } => {
-                // Class definitions create their own scope
+            Statement::ClassDef { name, body, .. } | Statement::ModuleDef { name, body, .. } => {
+                // Class and module definitions create their own scope
                 self.push_scope();
+                self.definition_depth += 1;
 
                 // Resolve class body
                 for stmt in body {
                     self.resolve_statement(stmt);
                 }
 
+                self.definition_depth -= 1;
                 self.pop_scope();
 
-                // Declare class name after resolving body
+                // Declare the name after resolving body
                 self.declare(name.clone(), Position::default());
             }
 
@@ -402,14 +574,22 @@ impl Resolver {
                 }
             }
 
-            Statement::Break { .. } | Statement::Continue { .. } => {
+            Statement::Break { .. } | Statement::Continue { .. } | Statement::Retry { .. } => {
                 // Nothing to resolve
             }
 
             Statement::Match {
-                expression, cases, ..
+                expression,
+                cases,
+                position,
             } => {
                 self.resolve_expression(expression);
+                self.check_sealed_case(
+                    cases
+                        .iter()
+                        .map(|case| (&case.pattern, case.guard.is_some())),
+                    *position,
+                );
 
                 for case in cases {
                     self.resolve_match_case(case);
@@ -450,10 +630,15 @@ impl Resolver {
                 }
             }
 
-            Statement::Raise { exception, .. } => {
+            Statement::Raise {
+                exception, cause, ..
+            } => {
                 if let Some(expr) = exception {
                     self.resolve_expression(expr);
                 }
+                if let Some(expr) = cause {
+                    self.resolve_expression(expr);
+                }
             }
 
             Statement::Block { statements, .. } => {
@@ -557,7 +742,11 @@ impl Resolver {
             Expression::Call {
                 callee, arguments, ..
             } => {
-                self.resolve_expression(callee);
+                // `it "works" do ... end` calls a method named it
+                let calls_it = matches!(callee.as_ref(), Expression::Identifier { name, .. } if name == IT_PARAMETER);
+                if !(calls_it && self.implicit_uses.is_some()) {
+                    self.resolve_expression(callee);
+                }
                 for arg in arguments {
                     self.resolve_expression(arg);
                 }
@@ -593,13 +782,23 @@ impl Resolver {
             }
 
             Expression::Lambda {
-                parameters, body, ..
+                parameters,
+                body,
+                destructured,
+                ..
             } => {
                 self.push_scope();
 
-                // Declare parameters
-                for param in parameters {
-                    self.declare(param.clone(), Position::default());
+                // Declare parameters, and the names a `(a, b)` parameter unpacks into
+                for (index, param) in parameters.iter().enumerate() {
+                    match destructured.iter().find(|pattern| pattern.index == index) {
+                        Some(pattern) => {
+                            for name in DestructureTarget::names(&pattern.targets) {
+                                self.declare(name.to_string(), Position::default());
+                            }
+                        }
+                        None => self.declare(param.clone(), Position::default()),
+                    }
                 }
 
                 // Resolve lambda body
@@ -614,11 +813,27 @@ impl Resolver {
                 self.resolve_expression(expression);
             }
 
+            Expression::ForwardedArgs { position } => {
+                // `...` reads the arguments bound by the enclosing `...` parameter
+                self.resolve_variable(FORWARDING_PARAMETER, *position);
+            }
+
             Expression::Range { start, end, .. } => {
                 self.resolve_expression(start);
                 self.resolve_expression(end);
             }
 
+            Expression::RescueModifier {
+                expression,
+                fallback,
+                ..
+            } => {
+                self.resolve_expression(expression);
+                self.resolve_expression(fallback);
+            }
+
+            Expression::Begin { block, .. } => self.resolve_statement(block),
+
             Expression::InterpolatedString { parts, .. } => {
                 for part in parts {
                     if let crate::ast::node::InterpolationPart::Expression(expr) = part {
@@ -631,10 +846,18 @@ impl Resolver {
                 expression,
                 cases,
                 else_case,
-                ..
+                position,
             } => {
                 // Resolve the match expression
                 self.resolve_expression(expression);
+                if else_case.is_none() {
+                    self.check_sealed_case(
+                        cases
+                            .iter()
+                            .map(|case| (&case.pattern, case.guard.is_some())),
+                        *position,
+                    );
+                }
 
                 // Resolve each case
                 for case in cases {
@@ -674,6 +897,31 @@ impl Resolver {
     }
 }
 
+/// The class names listed by a `variants :A, :B` call in a class body
+fn declared_variants(body: &[Statement]) -> Option<Vec<String>> {
+    body.iter().find_map(|statement| match statement {
+        Statement::Expression {
+            expression:
+                Expression::Call {
+                    callee, arguments, ..
+                },
+            ..
+        } if matches!(callee.as_ref(), Expression::Identifier { name, .. } if name == "variants") => {
+            Some(
+                arguments
+                    .iter()
+                    .filter_map(|argument| match argument {
+                        Expression::Symbol { value, .. }
+                        | Expression::StringLiteral { value, .. } => Some(value.clone()),
+                        _ => None,
+                    })
+                    .collect(),
+            )
+        }
+        _ => None,
+    })
+}
+
 impl Default for Resolver {
     fn default() -> Self {
         Self::new()
@@ -1,2 +0,0 @@
-// Metorex Runtime Library
-// Contains the runtime execution environment, actor management, and message passing
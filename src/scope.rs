@@ -6,6 +6,14 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 
+/// The bindings of a single scope at one moment, taken by [`Scope::snapshot`]
+/// Each binding keeps its shared cell, so closures that captured it see the
+/// restored value too
+#[derive(Debug, Clone)]
+pub struct ScopeSnapshot {
+    bindings: Vec<(String, Rc<RefCell<Object>>, Object)>,
+}
+
 /// Represents a single scope in the scope chain
 /// Each scope can have a parent scope, forming a chain for variable lookup
 #[derive(Debug)]
@@ -35,6 +43,11 @@ impl Scope {
         }
     }
 
+    /// Returns the parent scope, or None for the global scope
+    pub fn parent(&self) -> Option<Rc<RefCell<Scope>>> {
+        self.parent.clone()
+    }
+
     /// Defines a new variable in the current scope
     /// If the variable already exists in this scope, it will be overwritten
     pub fn define(&mut self, name: String, value: Object) {
@@ -47,6 +60,14 @@ impl Scope {
         self.variables.insert(name, value);
     }
 
+    /// Removes a variable from this scope only, returning its value
+    /// Parent scopes are never affected
+    pub fn remove(&mut self, name: &str) -> Option<Object> {
+        self.variables
+            .remove(name)
+            .map(|value_ref| value_ref.borrow().clone())
+    }
+
     /// Gets a variable value by traversing the scope chain
     /// Returns None if the variable is not found in any scope
     pub fn get(&self, name: &str) -> Option<Object> {
@@ -170,6 +191,30 @@ impl Scope {
 
         all_vars
     }
+
+    /// Captures this scope's bindings and their current values
+    /// Parent scopes are not included
+    pub fn snapshot(&self) -> ScopeSnapshot {
+        ScopeSnapshot {
+            bindings: self
+                .variables
+                .iter()
+                .map(|(name, value_ref)| {
+                    (name.clone(), value_ref.clone(), value_ref.borrow().clone())
+                })
+                .collect(),
+        }
+    }
+
+    /// Puts back the bindings captured by `snapshot`
+    /// Variables defined since are removed and reassigned ones get their old values
+    pub fn restore(&mut self, snapshot: ScopeSnapshot) {
+        self.variables.clear();
+        for (name, value_ref, value) in snapshot.bindings {
+            *value_ref.borrow_mut() = value;
+            self.variables.insert(name, value_ref);
+        }
+    }
 }
 
 impl Default for Scope {
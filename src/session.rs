@@ -0,0 +1,133 @@
+// REPL session persistence
+// A session records every input the REPL evaluated successfully. Saving it
+// writes those inputs out as a Metorex script, so replaying the file rebuilds
+// the same variables, classes and methods in a fresh VM
+
+use crate::error::{MetorexError, SourceLocation};
+use crate::file_loader::parse_file;
+use crate::vm::VirtualMachine;
+use std::fs;
+use std::path::Path;
+
+/// First line of every saved session file
+pub const SESSION_HEADER: &str = "# Metorex REPL session";
+
+/// Comment line that introduces each recorded input in a session file
+pub const ENTRY_MARKER: &str = "# >>";
+
+/// Inputs evaluated during an interactive session, in order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Session {
+    entries: Vec<String>,
+}
+
+impl Session {
+    /// Create an empty session
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an input that evaluated successfully; blank input is ignored
+    pub fn record(&mut self, source: &str) {
+        let source = source.trim_end();
+        if !source.trim().is_empty() {
+            self.entries.push(source.to_string());
+        }
+    }
+
+    /// The recorded inputs, oldest first
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Number of recorded inputs
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether nothing has been recorded yet
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Forget every recorded input
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Append the entries of another session after this one's
+    pub fn extend(&mut self, other: Session) {
+        self.entries.extend(other.entries);
+    }
+
+    /// Render the session as a runnable Metorex script
+    ///
+    /// Entries are separated by `# >>` comment lines, so the file can be run
+    /// directly or loaded back into the REPL entry by entry.
+    pub fn to_source(&self) -> String {
+        let mut source = String::from(SESSION_HEADER);
+        source.push('\n');
+        for entry in &self.entries {
+            source.push_str(ENTRY_MARKER);
+            source.push('\n');
+            source.push_str(entry);
+            source.push('\n');
+        }
+        source
+    }
+
+    /// Split a session script back into entries
+    ///
+    /// Scripts without entry markers, such as hand-written files, become a
+    /// single entry.
+    pub fn from_source(source: &str) -> Self {
+        let mut session = Session::new();
+        let mut current = String::new();
+
+        for line in source.lines() {
+            if line.trim_end() == SESSION_HEADER {
+                continue;
+            }
+            if line.trim_end() == ENTRY_MARKER {
+                session.record(&current);
+                current.clear();
+                continue;
+            }
+            current.push_str(line);
+            current.push('\n');
+        }
+        session.record(&current);
+
+        session
+    }
+
+    /// Write the session to `path`
+    pub fn save(&self, path: &Path) -> Result<(), MetorexError> {
+        fs::write(path, self.to_source()).map_err(|e| {
+            MetorexError::runtime_error(
+                format!("Failed to save session to '{}': {}", path.display(), e),
+                SourceLocation::new(0, 0, 0),
+            )
+        })
+    }
+
+    /// Read a session previously written with `save`
+    pub fn load(path: &Path) -> Result<Self, MetorexError> {
+        let source = fs::read_to_string(path).map_err(|e| {
+            MetorexError::runtime_error(
+                format!("Failed to load session from '{}': {}", path.display(), e),
+                SourceLocation::new(0, 0, 0),
+            )
+        })?;
+        Ok(Self::from_source(&source))
+    }
+
+    /// Evaluate every entry in order, stopping at the first error
+    pub fn replay(&self, vm: &mut VirtualMachine) -> Result<(), MetorexError> {
+        for (index, entry) in self.entries.iter().enumerate() {
+            let program = parse_file(entry, &format!("session entry {}", index + 1))?;
+            vm.execute_program(&program)?;
+        }
+        Ok(())
+    }
+}
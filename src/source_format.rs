@@ -0,0 +1,37 @@
+// Source formatting for `metorex fmt`
+// Normalizes whitespace without changing what a program means
+
+use crate::lexer::line_layouts;
+
+/// Columns a tab in indentation is expanded to
+pub const TAB_WIDTH: usize = 2;
+
+/// `source` with its whitespace normalized
+///
+/// A leading byte order mark is dropped, CRLF line endings become LF, tabs
+/// in indentation become spaces, trailing whitespace is removed and the
+/// text ends with exactly one newline. String literals cannot span lines,
+/// so none of this changes a string's value.
+pub fn format_source(source: &str) -> String {
+    let mut formatted = String::with_capacity(source.len());
+    for line in line_layouts(source) {
+        let body_end = line.text.len() - line.trailing.len();
+        if !line.is_blank() {
+            formatted.push_str(&" ".repeat(line.indent_width(TAB_WIDTH)));
+            formatted.push_str(&line.text[line.indent.len()..body_end]);
+        }
+        formatted.push('\n');
+    }
+
+    let content = formatted.trim_end_matches('\n').len();
+    formatted.truncate(content);
+    if !formatted.is_empty() {
+        formatted.push('\n');
+    }
+    formatted
+}
+
+/// Whether formatting would change `source`
+pub fn needs_formatting(source: &str) -> bool {
+    format_source(source) != source
+}
@@ -0,0 +1,265 @@
+// Terminal output styling for the CLI and REPL
+// Decides whether ANSI colors should be used and applies them to source code,
+// diagnostics and inspected values, so every front end colors output the same way
+
+use crate::error::MetorexError;
+use crate::lexer::{Lexer, TokenKind};
+use crate::object::Object;
+use crate::repl::Repl;
+use std::env;
+use std::fmt;
+use std::io::IsTerminal;
+use std::str::FromStr;
+
+/// When to emit colored output, as selected with `--color=WHEN`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    /// Color only when writing to a terminal and `NO_COLOR` is not set
+    #[default]
+    Auto,
+    /// Always color, even when output is redirected
+    Always,
+    /// Never color
+    Never,
+}
+
+impl ColorChoice {
+    /// Decide whether to color output going to a stream
+    pub fn should_color(self, is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => is_terminal && !no_color_requested(),
+        }
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            other => Err(format!(
+                "invalid color choice '{}' (expected auto, always or never)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ColorChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorChoice::Auto => write!(f, "auto"),
+            ColorChoice::Always => write!(f, "always"),
+            ColorChoice::Never => write!(f, "never"),
+        }
+    }
+}
+
+/// Whether the user opted out of color with a non-empty `NO_COLOR` variable
+pub fn no_color_requested() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// The kinds of text the palette knows how to color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Keyword,
+    Number,
+    String,
+    Symbol,
+    Constant,
+    Variable,
+    ClassName,
+    Comment,
+    Error,
+    LineNumber,
+}
+
+impl Style {
+    /// ANSI SGR parameters for this style
+    fn code(self) -> &'static str {
+        match self {
+            Style::Keyword => "35",
+            Style::Number => "34",
+            Style::String => "32",
+            Style::Symbol => "33",
+            Style::Constant => "36",
+            Style::Variable => "33",
+            Style::ClassName => "1;36",
+            Style::Comment => "90",
+            Style::Error => "1;31",
+            Style::LineNumber => "34",
+        }
+    }
+}
+
+/// Applies colors to output, or passes text through unchanged when disabled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    enabled: bool,
+}
+
+impl Palette {
+    /// Create a palette that colors output only when `enabled` is true
+    pub fn new(enabled: bool) -> Self {
+        Palette { enabled }
+    }
+
+    /// A palette that never colors
+    pub fn plain() -> Self {
+        Self::new(false)
+    }
+
+    /// A palette for text written to standard output
+    pub fn for_stdout(choice: ColorChoice) -> Self {
+        Self::new(choice.should_color(std::io::stdout().is_terminal()))
+    }
+
+    /// A palette for text written to standard error
+    pub fn for_stderr(choice: ColorChoice) -> Self {
+        Self::new(choice.should_color(std::io::stderr().is_terminal()))
+    }
+
+    /// Whether this palette emits color codes
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Wrap `text` in the color codes for `style`
+    pub fn paint(&self, style: Style, text: &str) -> String {
+        if self.enabled {
+            format!("\x1b[{}m{}\x1b[0m", style.code(), text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Syntax-highlight Metorex source code
+    ///
+    /// Whitespace and anything the lexer cannot tokenize are kept verbatim, so
+    /// the highlighted text always reads the same as the input.
+    pub fn highlight(&self, source: &str) -> String {
+        if !self.enabled {
+            return source.to_string();
+        }
+
+        let mut out = String::with_capacity(source.len() * 2);
+        let mut cursor = 0;
+        let mut lexer = Lexer::new(source);
+
+        loop {
+            let token = lexer.next_token();
+            if token.kind == TokenKind::EOF || token.span.start < cursor {
+                break;
+            }
+            out.push_str(&source[cursor..token.span.start]);
+
+            let text = token.text(source);
+            match token_style(&token.kind) {
+                Some(style) => out.push_str(&self.paint(style, text)),
+                None => out.push_str(text),
+            }
+            cursor = token.span.end;
+        }
+
+        out.push_str(&source[cursor..]);
+        out
+    }
+
+    /// Format a value the way the REPL shows results, colored by type
+    pub fn inspect(&self, obj: &Object) -> String {
+        if !self.enabled {
+            return Repl::format_object(obj);
+        }
+
+        match obj {
+            Object::Nil | Object::Bool(_) => self.paint(Style::Constant, &Repl::format_object(obj)),
+            Object::Int(_) | Object::Float(_) | Object::Decimal(_) => {
+                self.paint(Style::Number, &Repl::format_object(obj))
+            }
+            Object::String(_) => self.paint(Style::String, &Repl::format_object(obj)),
+            Object::Symbol(_) => self.paint(Style::Symbol, &Repl::format_object(obj)),
+            Object::Array(items) => {
+                let items: Vec<String> = items.borrow().iter().map(|i| self.inspect(i)).collect();
+                format!("[{}]", items.join(", "))
+            }
+            Object::Dict(map) => {
+                let entries: Vec<String> = map
+                    .borrow()
+                    .iter()
+                    .map(|(k, v)| {
+                        let key = self.paint(Style::String, &format!("\"{}\"", k));
+                        format!("{} => {}", key, self.inspect(v))
+                    })
+                    .collect();
+                format!("{{{}}}", entries.join(", "))
+            }
+            Object::Range {
+                start,
+                end,
+                exclusive,
+            } => {
+                let dots = if *exclusive { "..." } else { ".." };
+                format!("{}{}{}", self.inspect(start), dots, self.inspect(end))
+            }
+            Object::Result(Ok(value)) => format!("<Ok: {}>", self.inspect(value)),
+            Object::Result(Err(error)) => format!("<Err: {}>", self.inspect(error)),
+            Object::Class(_) | Object::Module(_) | Object::Instance(_) => {
+                self.paint(Style::ClassName, &Repl::format_object(obj))
+            }
+            Object::Exception(_) => self.paint(Style::Error, &Repl::format_object(obj)),
+            other => Repl::format_object(other),
+        }
+    }
+
+    /// Format an error as `label: message`, followed by the offending source
+    /// line and a caret when the source is available
+    pub fn diagnostic(&self, label: &str, error: &MetorexError, source: Option<&str>) -> String {
+        let mut out = format!("{}: {}", self.paint(Style::Error, label), error);
+
+        let Some(location) = error.location() else {
+            return out;
+        };
+        let Some(line) = source.and_then(|s| s.lines().nth(location.line.checked_sub(1)?)) else {
+            return out;
+        };
+
+        let number = location.line.to_string();
+        let gutter = " ".repeat(number.len());
+        out.push_str(&format!(
+            "\n{} | {}\n{} | {}{}",
+            self.paint(Style::LineNumber, &number),
+            self.highlight(line),
+            gutter,
+            " ".repeat(location.column.saturating_sub(1)),
+            self.paint(Style::Error, "^")
+        ));
+        out
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::plain()
+    }
+}
+
+/// The style used to highlight a token, if any
+fn token_style(kind: &TokenKind) -> Option<Style> {
+    match kind {
+        kind if kind.is_keyword() => Some(Style::Keyword),
+        TokenKind::Int(_) | TokenKind::Float(_) => Some(Style::Number),
+        TokenKind::String(_) | TokenKind::InterpolatedString(_) => Some(Style::String),
+        TokenKind::True | TokenKind::False | TokenKind::Nil => Some(Style::Constant),
+        TokenKind::InstanceVar(_) | TokenKind::ClassVar(_) => Some(Style::Variable),
+        TokenKind::Symbol(_) => Some(Style::Symbol),
+        TokenKind::Ident(name) if name.starts_with(char::is_uppercase) => Some(Style::ClassName),
+        TokenKind::Comment(_) => Some(Style::Comment),
+        _ => None,
+    }
+}
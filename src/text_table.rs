@@ -0,0 +1,164 @@
+// Aligned plain-text tables
+// Backs Array#to_table. Column widths count characters rather than bytes,
+// so rows holding multibyte text still line up.
+//
+// ASCII layout:
+//   +------+-----+
+//   | name | age |
+//   +------+-----+
+//   | bob  |  42 |
+//   +------+-----+
+//
+// Markdown layout:
+//   | name | age |
+//   | ---- | --: |
+//   | bob  |  42 |
+
+/// How the cells of a column are lined up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// Which kind of table to draw
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableStyle {
+    Ascii,
+    Markdown,
+}
+
+/// Rows of cell text under an optional header row
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    pub headers: Option<Vec<String>>,
+    pub rows: Vec<Vec<String>>,
+    /// Alignment per column; columns past the end are left-aligned
+    pub aligns: Vec<Align>,
+}
+
+impl Table {
+    /// Draw the table without a trailing newline
+    pub fn render(&self, style: TableStyle) -> String {
+        let cells = |row: &[String]| -> Vec<String> {
+            match style {
+                TableStyle::Ascii => row.iter().map(|cell| cell.replace('\n', " ")).collect(),
+                TableStyle::Markdown => row.iter().map(|cell| escape_markdown(cell)).collect(),
+            }
+        };
+        let headers = self.headers.as_deref().map(cells);
+        let rows: Vec<Vec<String>> = self.rows.iter().map(|row| cells(row)).collect();
+
+        let columns = headers
+            .iter()
+            .chain(rows.iter())
+            .map(Vec::len)
+            .max()
+            .unwrap_or(0);
+        if columns == 0 {
+            return String::new();
+        }
+        let minimum = match style {
+            TableStyle::Ascii => 0,
+            // A Markdown delimiter cell needs at least three characters
+            TableStyle::Markdown => 3,
+        };
+        let widths: Vec<usize> = (0..columns)
+            .map(|column| {
+                headers
+                    .iter()
+                    .chain(rows.iter())
+                    .filter_map(|row| row.get(column))
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or(0)
+                    .max(minimum)
+            })
+            .collect();
+
+        let mut lines = Vec::new();
+        match style {
+            TableStyle::Ascii => {
+                let rule = rule_line(&widths);
+                lines.push(rule.clone());
+                if let Some(headers) = &headers {
+                    lines.push(self.row_line(headers, &widths, true));
+                    lines.push(rule.clone());
+                }
+                for row in &rows {
+                    lines.push(self.row_line(row, &widths, false));
+                }
+                if !rows.is_empty() {
+                    lines.push(rule);
+                }
+            }
+            TableStyle::Markdown => {
+                // Markdown always needs a header row, even if it is blank
+                let blank = Vec::new();
+                let headers = headers.as_ref().unwrap_or(&blank);
+                lines.push(self.row_line(headers, &widths, true));
+                lines.push(self.delimiter_line(&widths));
+                for row in &rows {
+                    lines.push(self.row_line(row, &widths, false));
+                }
+            }
+        }
+        lines.join("\n")
+    }
+
+    fn align(&self, column: usize) -> Align {
+        self.aligns.get(column).copied().unwrap_or(Align::Left)
+    }
+
+    /// `| a | b |` with each cell padded to its column width; headers are
+    /// always left-aligned
+    fn row_line(&self, row: &[String], widths: &[usize], header: bool) -> String {
+        let mut line = String::from("|");
+        for (column, width) in widths.iter().enumerate() {
+            let cell = row.get(column).map(String::as_str).unwrap_or("");
+            let padding = " ".repeat(width - cell.chars().count());
+            line.push(' ');
+            if !header && self.align(column) == Align::Right {
+                line.push_str(&padding);
+                line.push_str(cell);
+            } else {
+                line.push_str(cell);
+                line.push_str(&padding);
+            }
+            line.push_str(" |");
+        }
+        line
+    }
+
+    /// `| --- | --: |`, marking right-aligned columns
+    fn delimiter_line(&self, widths: &[usize]) -> String {
+        let mut line = String::from("|");
+        for (column, width) in widths.iter().enumerate() {
+            line.push(' ');
+            match self.align(column) {
+                Align::Left => line.push_str(&"-".repeat(*width)),
+                Align::Right => {
+                    line.push_str(&"-".repeat(width - 1));
+                    line.push(':');
+                }
+            }
+            line.push_str(" |");
+        }
+        line
+    }
+}
+
+/// `+-----+----+` spanning every column
+fn rule_line(widths: &[usize]) -> String {
+    let mut line = String::from("+");
+    for width in widths {
+        line.push_str(&"-".repeat(width + 2));
+        line.push('+');
+    }
+    line
+}
+
+/// Keep cell text from breaking the Markdown row structure
+fn escape_markdown(cell: &str) -> String {
+    cell.replace('|', "\\|").replace('\n', " ")
+}
@@ -0,0 +1,773 @@
+// Best-effort checking of optional type annotations
+// `metorex typecheck FILE` runs `check` over a parsed program. Types are
+// inferred from literals, from declared parameter and result types, through
+// assignments to locals and from the builtin catalog's result types. Anything
+// the checker cannot infer is assumed to be fine, so unannotated code never
+// reports errors. The same compatibility rules back the VM's `check_types`
+// mode, which validates arguments and results while the program runs.
+
+use crate::ast::{BinaryOp, Expression, Parameter, Statement, TypeAnnotation, UnaryOp};
+use crate::builtin_catalog::{self, BuiltinParam, MethodKind, ParamKind};
+use crate::builtin_classes::BuiltinClasses;
+use crate::lexer::Position;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Runtime type names accepted by a builtin type annotation, or `None` if
+/// `name` is not a builtin type.
+pub fn builtin_type_names(name: &str) -> Option<&'static [&'static str]> {
+    Some(match name {
+        "Int" | "Integer" => &["Int"],
+        "Float" => &["Float"],
+        "Decimal" | "BigDecimal" => &["Decimal"],
+        "Numeric" => &["Int", "Float"],
+        "String" => &["String"],
+        "Symbol" => &["Symbol"],
+        "Bool" | "Boolean" => &["Bool"],
+        "Nil" | "NilClass" => &["Nil"],
+        "Array" => &["Array"],
+        "Dict" | "Hash" => &["Dict"],
+        "Set" => &["Set"],
+        "Range" => &["Range"],
+        "Block" | "Proc" => &["Block"],
+        "Method" => &["Method"],
+        _ => return None,
+    })
+}
+
+/// Whether an annotation accepts every value.
+pub fn accepts_anything(annotation: &TypeAnnotation) -> bool {
+    matches!(annotation.name.as_str(), "Any" | "Object")
+}
+
+/// Whether a value satisfies `annotation`. `lineage` starts with the value's
+/// type name (its class name for instances) followed by its superclasses.
+pub fn satisfies(annotation: &TypeAnnotation, lineage: &[&str]) -> bool {
+    let Some(found) = lineage.first() else {
+        return true;
+    };
+    if accepts_anything(annotation) || (annotation.nilable && *found == "Nil") {
+        return true;
+    }
+    match builtin_type_names(&annotation.name) {
+        Some(names) => names.contains(found),
+        None => lineage.contains(&annotation.name.as_str()),
+    }
+}
+
+/// A type error found without running the program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeDiagnostic {
+    pub message: String,
+    pub position: Position,
+}
+
+impl fmt::Display for TypeDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}",
+            self.position.line, self.position.column, self.message
+        )
+    }
+}
+
+/// Check the type annotations in a program, returning every error found in
+/// source order.
+pub fn check(statements: &[Statement]) -> Vec<TypeDiagnostic> {
+    let mut checker = TypeChecker::default();
+    checker.collect_definitions(statements);
+    checker.check_statements(statements);
+    checker.diagnostics.sort_by_key(|d| d.position.offset);
+    checker.diagnostics
+}
+
+/// Declared types of a function or method.
+#[derive(Debug, Clone)]
+struct Signature {
+    name: String,
+    /// Positional parameters, without keyword and block parameters
+    parameters: Vec<(String, Option<TypeAnnotation>)>,
+    /// Whether the last parameter collects the remaining arguments
+    variadic: bool,
+    returns: Option<TypeAnnotation>,
+}
+
+impl Signature {
+    fn new(name: &str, parameters: &[Parameter], returns: Option<&TypeAnnotation>) -> Self {
+        let positional: Vec<&Parameter> = parameters
+            .iter()
+            .filter(|p| !p.is_keyword && !p.is_block)
+            .collect();
+        Self {
+            name: name.to_string(),
+            variadic: positional.last().is_some_and(|p| p.is_variadic),
+            parameters: positional
+                .into_iter()
+                .map(|p| (p.name.clone(), p.type_annotation.clone()))
+                .collect(),
+            returns: returns.cloned(),
+        }
+    }
+
+    /// The signature of a builtin method or function from the catalog.
+    fn builtin(name: &str, parameters: &[BuiltinParam], returns: &str) -> Self {
+        let position = Position::default();
+        let positional: Vec<&BuiltinParam> = parameters
+            .iter()
+            .filter(|p| {
+                matches!(
+                    p.kind,
+                    ParamKind::Required | ParamKind::Optional | ParamKind::Rest
+                )
+            })
+            .collect();
+        Self {
+            name: name.to_string(),
+            variadic: positional.last().is_some_and(|p| p.kind == ParamKind::Rest),
+            parameters: positional
+                .into_iter()
+                .map(|p| {
+                    let annotation = p.ty.map(|ty| TypeAnnotation::parse(ty, position));
+                    (p.name.to_string(), annotation)
+                })
+                .collect(),
+            returns: Some(TypeAnnotation::parse(returns, position)),
+        }
+    }
+
+    /// The parameter an argument at `index` binds to.
+    fn parameter(&self, index: usize) -> Option<&(String, Option<TypeAnnotation>)> {
+        match self.parameters.get(index) {
+            Some(parameter) => Some(parameter),
+            None if self.variadic => self.parameters.last(),
+            None => None,
+        }
+    }
+}
+
+/// The catalog class describing values of a runtime type.
+fn catalog_class(ty: &str) -> &str {
+    match ty {
+        "Int" => "Integer",
+        "Dict" => "Hash",
+        "Symbol" => "String",
+        "Bool" | "Nil" => "Object",
+        other => other,
+    }
+}
+
+#[derive(Default)]
+struct TypeChecker {
+    /// Top-level functions by name
+    functions: HashMap<String, Signature>,
+    /// Methods of each user class by class and method name
+    methods: HashMap<String, HashMap<String, Signature>>,
+    /// Superclass of each user class
+    superclasses: HashMap<String, Option<String>>,
+    /// Names of the builtin classes, which are valid in annotations
+    builtin_classes: HashSet<String>,
+    /// Known types of locals at the current point
+    locals: HashMap<String, String>,
+    /// Name and declared result of the enclosing definition
+    enclosing: Option<(String, TypeAnnotation)>,
+    diagnostics: Vec<TypeDiagnostic>,
+}
+
+impl TypeChecker {
+    /// Record every function, class and method so calls can be checked
+    /// regardless of definition order.
+    fn collect_definitions(&mut self, statements: &[Statement]) {
+        if self.builtin_classes.is_empty() {
+            self.builtin_classes = BuiltinClasses::new().all_classes().into_keys().collect();
+        }
+        for statement in statements {
+            match statement {
+                Statement::FunctionDef {
+                    name,
+                    parameters,
+                    return_type,
+                    ..
+                } => {
+                    let signature = Signature::new(name, parameters, return_type.as_ref());
+                    self.functions.insert(name.clone(), signature);
+                }
+                Statement::ClassDef {
+                    name,
+                    superclass,
+                    body,
+                    ..
+                } => {
+                    self.superclasses.insert(name.clone(), superclass.clone());
+                    for member in body {
+                        if let Statement::MethodDef {
+                            name: method_name,
+                            parameters,
+                            return_type,
+                            ..
+                        } = member
+                        {
+                            let signature =
+                                Signature::new(method_name, parameters, return_type.as_ref());
+                            self.methods
+                                .entry(name.clone())
+                                .or_default()
+                                .insert(method_name.clone(), signature);
+                        }
+                    }
+                    self.collect_definitions(body);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn report(&mut self, message: String, position: Position) {
+        self.diagnostics.push(TypeDiagnostic { message, position });
+    }
+
+    /// `name` followed by its superclasses.
+    fn lineage<'a>(&'a self, name: &'a str) -> Vec<&'a str> {
+        let mut lineage = vec![name];
+        let mut current = name;
+        while let Some(Some(superclass)) = self.superclasses.get(current) {
+            if lineage.contains(&superclass.as_str()) {
+                break;
+            }
+            lineage.push(superclass);
+            current = superclass;
+        }
+        lineage
+    }
+
+    /// Report annotations that name neither a builtin type nor a known class.
+    fn check_annotation(&mut self, annotation: &TypeAnnotation) {
+        let known = accepts_anything(annotation)
+            || builtin_type_names(&annotation.name).is_some()
+            || self.superclasses.contains_key(&annotation.name)
+            || self.builtin_classes.contains(&annotation.name);
+        if !known {
+            self.report(
+                format!("Unknown type '{}'", annotation.name),
+                annotation.position,
+            );
+        }
+    }
+
+    /// The type a value of annotation `annotation` is known to have.
+    fn declared_type(annotation: &TypeAnnotation) -> Option<String> {
+        if annotation.nilable || accepts_anything(annotation) {
+            return None;
+        }
+        match builtin_type_names(&annotation.name) {
+            Some([single]) => Some(single.to_string()),
+            Some(_) => None,
+            None => Some(annotation.name.clone()),
+        }
+    }
+
+    /// Run `check` over `statements` starting from the current locals and
+    /// return the locals it leaves behind, restoring the current ones.
+    fn branch(&mut self, statements: &[Statement]) -> HashMap<String, String> {
+        let before = self.locals.clone();
+        self.check_statements(statements);
+        std::mem::replace(&mut self.locals, before)
+    }
+
+    /// Keep only the locals whose type is the same after every branch.
+    fn merge(&mut self, outcomes: Vec<HashMap<String, String>>) {
+        let mut outcomes = outcomes.into_iter();
+        let Some(mut merged) = outcomes.next() else {
+            return;
+        };
+        for outcome in outcomes {
+            merged.retain(|name, ty| outcome.get(name) == Some(ty));
+        }
+        self.locals = merged;
+    }
+
+    fn check_statements(&mut self, statements: &[Statement]) {
+        for statement in statements {
+            self.check_statement(statement);
+        }
+    }
+
+    fn check_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Expression { expression, .. } => {
+                self.infer(expression);
+            }
+            Statement::Assignment { target, value, .. } => {
+                let ty = self.infer(value);
+                match (target, ty) {
+                    (Expression::Identifier { name, .. }, Some(ty)) => {
+                        self.locals.insert(name.clone(), ty);
+                    }
+                    (Expression::Identifier { name, .. }, None) => {
+                        self.locals.remove(name);
+                    }
+                    (target, _) => {
+                        self.infer(target);
+                    }
+                }
+            }
+            Statement::FunctionDef {
+                name,
+                parameters,
+                body,
+                return_type,
+                ..
+            }
+            | Statement::MethodDef {
+                name,
+                parameters,
+                body,
+                return_type,
+                ..
+            } => self.check_definition(name, parameters, body, return_type.as_ref()),
+            Statement::ClassDef { body, .. } | Statement::ModuleDef { body, .. } => {
+                let outer = std::mem::take(&mut self.locals);
+                self.check_statements(body);
+                self.locals = outer;
+            }
+            Statement::Return { value, position } => {
+                let ty = match value {
+                    Some(value) => self.infer(value),
+                    None => Some("Nil".to_string()),
+                };
+                if let Some(ty) = ty {
+                    self.check_result(&ty, *position);
+                }
+            }
+            Statement::If {
+                condition,
+                then_branch,
+                elsif_branches,
+                else_branch,
+                ..
+            } => {
+                self.infer(condition);
+                let mut outcomes = vec![self.branch(then_branch)];
+                for elsif in elsif_branches {
+                    self.infer(&elsif.condition);
+                    outcomes.push(self.branch(&elsif.body));
+                }
+                match else_branch {
+                    Some(else_branch) => outcomes.push(self.branch(else_branch)),
+                    None => outcomes.push(self.locals.clone()),
+                }
+                self.merge(outcomes);
+            }
+            Statement::Unless {
+                condition,
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                self.infer(condition);
+                let mut outcomes = vec![self.branch(then_branch)];
+                match else_branch {
+                    Some(else_branch) => outcomes.push(self.branch(else_branch)),
+                    None => outcomes.push(self.locals.clone()),
+                }
+                self.merge(outcomes);
+            }
+            Statement::While {
+                condition, body, ..
+            } => {
+                self.infer(condition);
+                let outcomes = vec![self.branch(body), self.locals.clone()];
+                self.merge(outcomes);
+            }
+            Statement::For {
+                variable,
+                iterable,
+                body,
+                ..
+            } => {
+                self.infer(iterable);
+                self.locals.remove(variable);
+                let outcomes = vec![self.branch(body), self.locals.clone()];
+                self.merge(outcomes);
+            }
+            Statement::Match {
+                expression, cases, ..
+            } => {
+                self.infer(expression);
+                let mut outcomes = vec![self.locals.clone()];
+                for case in cases {
+                    outcomes.push(self.branch(&case.body));
+                }
+                self.merge(outcomes);
+            }
+            Statement::Begin {
+                body,
+                rescue_clauses,
+                else_clause,
+                ensure_block,
+                ..
+            } => {
+                let mut outcomes = vec![self.locals.clone(), self.branch(body)];
+                for rescue in rescue_clauses {
+                    outcomes.push(self.branch(&rescue.body));
+                }
+                if let Some(else_clause) = else_clause {
+                    outcomes.push(self.branch(else_clause));
+                }
+                self.merge(outcomes);
+                if let Some(ensure_block) = ensure_block {
+                    let outcomes = vec![self.branch(ensure_block), self.locals.clone()];
+                    self.merge(outcomes);
+                }
+            }
+            Statement::Block { statements, .. } => self.check_statements(statements),
+            Statement::Raise {
+                exception, cause, ..
+            } => {
+                for expression in exception.iter().chain(cause) {
+                    self.infer(expression);
+                }
+            }
+            Statement::Break { .. }
+            | Statement::Continue { .. }
+            | Statement::Retry { .. }
+            | Statement::AttrReader { .. }
+            | Statement::AttrWriter { .. }
+            | Statement::AttrAccessor { .. } => {}
+        }
+    }
+
+    /// Check a function or method body with its parameters' declared types.
+    fn check_definition(
+        &mut self,
+        name: &str,
+        parameters: &[Parameter],
+        body: &[Statement],
+        return_type: Option<&TypeAnnotation>,
+    ) {
+        for annotation in parameters
+            .iter()
+            .filter_map(|p| p.type_annotation.as_ref())
+            .chain(return_type)
+        {
+            self.check_annotation(annotation);
+        }
+
+        let locals = parameters
+            .iter()
+            .filter_map(|p| {
+                let ty = Self::declared_type(p.type_annotation.as_ref()?)?;
+                Some((p.name.clone(), ty))
+            })
+            .collect();
+        let outer_locals = std::mem::replace(&mut self.locals, locals);
+        let outer_enclosing = std::mem::replace(
+            &mut self.enclosing,
+            return_type.map(|annotation| (name.to_string(), annotation.clone())),
+        );
+
+        // The last expression is the implicit result
+        if let Some((Statement::Expression { expression, .. }, rest)) = body.split_last() {
+            self.check_statements(rest);
+            if let Some(ty) = self.infer(expression) {
+                self.check_result(&ty, expression.position());
+            }
+        } else {
+            self.check_statements(body);
+        }
+
+        self.locals = outer_locals;
+        self.enclosing = outer_enclosing;
+    }
+
+    /// Report a result of type `ty` that the enclosing definition does not declare.
+    fn check_result(&mut self, ty: &str, position: Position) {
+        let Some((name, declared)) = &self.enclosing else {
+            return;
+        };
+        if !satisfies(declared, &self.lineage(ty)) {
+            let message = format!("'{}' declares result {} but returns {}", name, declared, ty);
+            self.report(message, position);
+        }
+    }
+
+    /// Report arguments whose inferred types the callee does not accept.
+    fn check_arguments(&mut self, signature: &Signature, arguments: &[Expression]) {
+        let found: Vec<Option<String>> = arguments.iter().map(|arg| self.infer(arg)).collect();
+        for (index, (ty, argument)) in found.iter().zip(arguments).enumerate() {
+            let Some((parameter, annotation)) = signature.parameter(index) else {
+                break;
+            };
+            if let (Some(annotation), Some(ty)) = (annotation, ty)
+                && !satisfies(annotation, &self.lineage(ty))
+            {
+                let message = format!(
+                    "argument '{}' of '{}' expects {}, found {}",
+                    parameter, signature.name, annotation, ty
+                );
+                self.report(message, argument.position());
+            }
+        }
+    }
+
+    /// Find a method on a user class or one of its superclasses.
+    fn find_method(&self, class: &str, method: &str) -> Option<Signature> {
+        self.lineage(class).into_iter().find_map(|name| {
+            self.methods
+                .get(name)
+                .and_then(|methods| methods.get(method))
+                .cloned()
+        })
+    }
+
+    /// The result type of a call to a function or method with this signature.
+    fn result_type(signature: &Signature) -> Option<String> {
+        signature.returns.as_ref().and_then(Self::declared_type)
+    }
+
+    fn infer_method_call(
+        &mut self,
+        receiver: &Expression,
+        method: &str,
+        arguments: &[Expression],
+    ) -> Option<String> {
+        // `Point.new(...)` checks the arguments against `initialize`
+        if let Expression::Identifier { name, .. } = receiver
+            && method == "new"
+            && self.superclasses.contains_key(name)
+        {
+            match self.find_method(name, "initialize") {
+                Some(signature) => self.check_arguments(&signature, arguments),
+                None => arguments.iter().for_each(|arg| {
+                    self.infer(arg);
+                }),
+            }
+            return Some(name.clone());
+        }
+
+        let receiver_type = self.infer(receiver);
+        if let Some(class) = receiver_type.as_deref()
+            && self.superclasses.contains_key(class)
+            && let Some(signature) = self.find_method(class, method)
+        {
+            self.check_arguments(&signature, arguments);
+            return Self::result_type(&signature);
+        }
+
+        // `File.fnmatch(...)` and other class methods of builtin classes
+        let builtin = match (receiver, &receiver_type) {
+            (Expression::Identifier { name, .. }, None) if !self.locals.contains_key(name) => {
+                builtin_catalog::find_method(name, method, MethodKind::Class)
+            }
+            (_, Some(ty)) => {
+                builtin_catalog::find_method(catalog_class(ty), method, MethodKind::Instance)
+            }
+            (_, None) => None,
+        };
+        match builtin {
+            Some(builtin) => {
+                let signature = Signature::builtin(method, builtin.parameters, builtin.returns);
+                self.check_arguments(&signature, arguments);
+                Self::result_type(&signature)
+            }
+            None => {
+                for argument in arguments {
+                    self.infer(argument);
+                }
+                // Every object responds to to_s
+                (method == "to_s" && arguments.is_empty()).then(|| "String".to_string())
+            }
+        }
+    }
+
+    /// Infer the type of an expression, checking the calls inside it.
+    fn infer(&mut self, expression: &Expression) -> Option<String> {
+        let ty = match expression {
+            Expression::IntLiteral { .. } => "Int",
+            Expression::FloatLiteral { .. } => "Float",
+            Expression::StringLiteral { .. } => "String",
+            Expression::BoolLiteral { .. } => "Bool",
+            Expression::NilLiteral { .. } => "Nil",
+            Expression::Symbol { .. } => "Symbol",
+            Expression::InterpolatedString { parts, .. } => {
+                for part in parts {
+                    if let crate::ast::InterpolationPart::Expression(expression) = part {
+                        self.infer(expression);
+                    }
+                }
+                "String"
+            }
+            Expression::Identifier { name, .. } => return self.locals.get(name).cloned(),
+            Expression::BinaryOp {
+                op, left, right, ..
+            } => {
+                let left = self.infer(left);
+                let right = self.infer(right);
+                return infer_binary(op, left.as_deref()?, right.as_deref()?);
+            }
+            Expression::UnaryOp { op, operand, .. } => {
+                let operand = self.infer(operand);
+                return match (op, operand.as_deref()) {
+                    (UnaryOp::Not, _) => Some("Bool".to_string()),
+                    (UnaryOp::Plus | UnaryOp::Minus, Some("Int" | "Float")) => operand,
+                    _ => None,
+                };
+            }
+            Expression::Call {
+                callee,
+                arguments,
+                trailing_block,
+                ..
+            } => {
+                if let Some(block) = trailing_block {
+                    self.infer(block);
+                }
+                let signature = match callee.as_ref() {
+                    Expression::Identifier { name, .. } => {
+                        self.functions.get(name).cloned().or_else(|| {
+                            builtin_catalog::find_function(name).map(|builtin| {
+                                Signature::builtin(name, builtin.parameters, builtin.returns)
+                            })
+                        })
+                    }
+                    _ => None,
+                };
+                return match signature {
+                    Some(signature) => {
+                        self.check_arguments(&signature, arguments);
+                        Self::result_type(&signature)
+                    }
+                    None => {
+                        for argument in arguments {
+                            self.infer(argument);
+                        }
+                        None
+                    }
+                };
+            }
+            Expression::MethodCall {
+                receiver,
+                method,
+                arguments,
+                trailing_block,
+                ..
+            } => {
+                if let Some(block) = trailing_block {
+                    self.infer(block);
+                }
+                return self.infer_method_call(receiver, method, arguments);
+            }
+            Expression::Array { elements, .. } => {
+                for element in elements {
+                    self.infer(element);
+                }
+                "Array"
+            }
+            Expression::Dictionary { entries, .. } => {
+                for (key, value) in entries {
+                    self.infer(key);
+                    self.infer(value);
+                }
+                "Dict"
+            }
+            Expression::Index { array, index, .. } => {
+                self.infer(array);
+                self.infer(index);
+                return None;
+            }
+            Expression::Lambda { body, .. } => {
+                let outcome = self.branch(body);
+                self.merge(vec![outcome, self.locals.clone()]);
+                "Block"
+            }
+            Expression::Grouped { expression, .. } => return self.infer(expression),
+            Expression::Range { start, end, .. } => {
+                self.infer(start);
+                self.infer(end);
+                "Range"
+            }
+            Expression::Super { arguments, .. } => {
+                for argument in arguments {
+                    self.infer(argument);
+                }
+                return None;
+            }
+            Expression::Case {
+                expression,
+                cases,
+                else_case,
+                ..
+            } => {
+                self.infer(expression);
+                for case in cases {
+                    self.infer(&case.body);
+                }
+                if let Some(else_case) = else_case {
+                    self.infer(else_case);
+                }
+                return None;
+            }
+            Expression::RescueModifier {
+                expression,
+                fallback,
+                ..
+            } => {
+                self.infer(expression);
+                self.infer(fallback);
+                return None;
+            }
+            Expression::Begin { block, .. } => {
+                self.check_statement(block);
+                return None;
+            }
+            Expression::InstanceVariable { .. }
+            | Expression::ClassVariable { .. }
+            | Expression::SelfExpr { .. }
+            | Expression::ForwardedArgs { .. } => return None,
+        };
+        Some(ty.to_string())
+    }
+}
+
+/// The result type of a binary operation on values of known types.
+fn infer_binary(op: &BinaryOp, left: &str, right: &str) -> Option<String> {
+    let ty = match (op, left, right) {
+        (
+            BinaryOp::Equal
+            | BinaryOp::NotEqual
+            | BinaryOp::CaseEqual
+            | BinaryOp::Less
+            | BinaryOp::Greater
+            | BinaryOp::LessEqual
+            | BinaryOp::GreaterEqual,
+            _,
+            _,
+        ) => "Bool",
+        (
+            BinaryOp::Add
+            | BinaryOp::Subtract
+            | BinaryOp::Multiply
+            | BinaryOp::Divide
+            | BinaryOp::Modulo,
+            "Int",
+            "Int",
+        ) => "Int",
+        (
+            BinaryOp::Add
+            | BinaryOp::Subtract
+            | BinaryOp::Multiply
+            | BinaryOp::Divide
+            | BinaryOp::Modulo,
+            "Int" | "Float",
+            "Int" | "Float",
+        ) => "Float",
+        (BinaryOp::Add, "String", "String") => "String",
+        (BinaryOp::Modulo, "String", _) => "String",
+        (BinaryOp::Add, "Array", "Array") => "Array",
+        (BinaryOp::ShiftLeft, "Array", _) => "Array",
+        // Either operand can be the result
+        (BinaryOp::And | BinaryOp::Or, _, _) if left == right => left,
+        _ => return None,
+    };
+    Some(ty.to_string())
+}
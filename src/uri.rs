@@ -0,0 +1,271 @@
+// URI parsing and percent-encoding
+// Backs the URI builtin: URI.parse splits a reference into the components of
+// RFC 3986 (scheme://userinfo@host:port/path?query#fragment), and the
+// encoding helpers produce and read percent-encoded text and
+// application/x-www-form-urlencoded query strings.
+
+use std::fmt;
+
+/// A parsed URI reference; relative references have no scheme or host
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Uri {
+    /// Lowercased scheme, such as `https`
+    pub scheme: Option<String>,
+    /// The `user:password` part before `@`
+    pub userinfo: Option<String>,
+    /// Host name or address; IPv6 addresses keep their brackets
+    pub host: Option<String>,
+    /// Port written in the URI, if any
+    pub port: Option<u16>,
+    /// Path, possibly empty
+    pub path: String,
+    /// Text after `?`, without it
+    pub query: Option<String>,
+    /// Text after `#`, without it
+    pub fragment: Option<String>,
+}
+
+impl Uri {
+    /// Split `text` into its components
+    ///
+    /// Text containing whitespace, non-ASCII or other characters a URI may not
+    /// contain is rejected rather than guessed at.
+    pub fn parse(text: &str) -> Result<Uri, String> {
+        if let Some(c) = text.chars().find(|&c| !is_uri_char(c)) {
+            return Err(format!("bad URI {:?} (invalid character {:?})", text, c));
+        }
+
+        let mut uri = Uri::default();
+        let mut rest = text;
+
+        if let Some((fragment_start, fragment)) = rest.split_once('#') {
+            uri.fragment = Some(fragment.to_string());
+            rest = fragment_start;
+        }
+        if let Some((query_start, query)) = rest.split_once('?') {
+            uri.query = Some(query.to_string());
+            rest = query_start;
+        }
+        if let Some((scheme, after)) = split_scheme(rest) {
+            uri.scheme = Some(scheme.to_ascii_lowercase());
+            rest = after;
+        }
+
+        if let Some(after) = rest.strip_prefix("//") {
+            let end = after.find('/').unwrap_or(after.len());
+            parse_authority(&after[..end], &mut uri)
+                .map_err(|err| format!("bad URI {:?} ({})", text, err))?;
+            rest = &after[end..];
+        }
+        uri.path = rest.to_string();
+        Ok(uri)
+    }
+
+    /// The written port, or the usual port for the scheme
+    pub fn effective_port(&self) -> Option<u16> {
+        self.port
+            .or_else(|| self.scheme.as_deref().and_then(default_port))
+    }
+}
+
+impl fmt::Display for Uri {
+    /// Writes the URI back out, leaving off a port that is the scheme's default
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(scheme) = &self.scheme {
+            write!(f, "{}:", scheme)?;
+        }
+        if let Some(host) = &self.host {
+            write!(f, "//")?;
+            if let Some(userinfo) = &self.userinfo {
+                write!(f, "{}@", userinfo)?;
+            }
+            write!(f, "{}", host)?;
+            if let Some(port) = self.port
+                && Some(port) != self.scheme.as_deref().and_then(default_port)
+            {
+                write!(f, ":{}", port)?;
+            }
+        }
+        write!(f, "{}", self.path)?;
+        if let Some(query) = &self.query {
+            write!(f, "?{}", query)?;
+        }
+        if let Some(fragment) = &self.fragment {
+            write!(f, "#{}", fragment)?;
+        }
+        Ok(())
+    }
+}
+
+/// The port a scheme uses when none is written
+pub fn default_port(scheme: &str) -> Option<u16> {
+    match scheme {
+        "http" | "ws" => Some(80),
+        "https" | "wss" => Some(443),
+        "ftp" => Some(21),
+        _ => None,
+    }
+}
+
+/// Percent-encode everything except the unreserved characters `A-Z a-z 0-9 - . _ ~`
+pub fn encode_component(text: &str) -> String {
+    percent_encode(
+        text,
+        |b| b.is_ascii_alphanumeric() || b"-._~".contains(&b),
+        false,
+    )
+}
+
+/// Decode `%XX` escapes
+pub fn decode_component(text: &str) -> Result<String, String> {
+    percent_decode(text, false)
+}
+
+/// Encode one key or value of a form query: spaces become `+` and everything
+/// except `A-Z a-z 0-9 * - . _` is percent-encoded
+pub fn encode_www_form_component(text: &str) -> String {
+    percent_encode(
+        text,
+        |b| b.is_ascii_alphanumeric() || b"*-._".contains(&b),
+        true,
+    )
+}
+
+/// Decode one key or value of a form query, reading `+` as a space
+pub fn decode_www_form_component(text: &str) -> Result<String, String> {
+    percent_decode(text, true)
+}
+
+/// Join key/value pairs into a form query; a pair without a value is written
+/// as the bare key
+pub fn encode_www_form(pairs: &[(String, Option<String>)]) -> String {
+    pairs
+        .iter()
+        .map(|(key, value)| match value {
+            Some(value) => format!(
+                "{}={}",
+                encode_www_form_component(key),
+                encode_www_form_component(value)
+            ),
+            None => encode_www_form_component(key),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Split a form query into decoded key/value pairs, in order
+///
+/// Both `&` and `;` separate pairs; a key without `=` gets an empty value.
+pub fn decode_www_form(query: &str) -> Result<Vec<(String, String)>, String> {
+    query
+        .split(['&', ';'])
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            Ok((
+                decode_www_form_component(key)?,
+                decode_www_form_component(value)?,
+            ))
+        })
+        .collect()
+}
+
+/// The scheme and the rest, if `text` starts with one
+fn split_scheme(text: &str) -> Option<(&str, &str)> {
+    let (scheme, rest) = text.split_once(':')?;
+    let mut chars = scheme.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c));
+    valid.then_some((scheme, rest))
+}
+
+/// Fill in userinfo, host and port from the text between `//` and the path
+fn parse_authority(authority: &str, uri: &mut Uri) -> Result<(), String> {
+    let host_port = match authority.rsplit_once('@') {
+        Some((userinfo, host_port)) => {
+            uri.userinfo = Some(userinfo.to_string());
+            host_port
+        }
+        None => authority,
+    };
+
+    let (host, port) = if host_port.starts_with('[') {
+        let close = host_port
+            .find(']')
+            .ok_or_else(|| "unterminated IPv6 address".to_string())?;
+        let port = match &host_port[close + 1..] {
+            "" => None,
+            rest => Some(
+                rest.strip_prefix(':')
+                    .ok_or_else(|| format!("unexpected {:?} after IPv6 address", rest))?,
+            ),
+        };
+        (&host_port[..=close], port)
+    } else {
+        match host_port.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (host_port, None),
+        }
+    };
+
+    uri.host = Some(host.to_string());
+    uri.port = match port {
+        None | Some("") => None,
+        Some(port) => Some(
+            port.bytes()
+                .all(|b| b.is_ascii_digit())
+                .then(|| port.parse().ok())
+                .flatten()
+                .ok_or_else(|| format!("invalid port {:?}", port))?,
+        ),
+    };
+    Ok(())
+}
+
+/// Characters allowed anywhere in a URI: unreserved, reserved and `%`
+fn is_uri_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "-._~:/?#[]@!$&'()*+,;=%".contains(c)
+}
+
+fn percent_encode(text: &str, keep: impl Fn(u8) -> bool, space_as_plus: bool) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if keep(byte) {
+            encoded.push(byte as char);
+        } else if byte == b' ' && space_as_plus {
+            encoded.push('+');
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+fn percent_decode(text: &str, plus_as_space: bool) -> Result<String, String> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let escape = bytes
+                    .get(i + 1..i + 3)
+                    .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+                    .and_then(|hex| std::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| format!("invalid %-encoding in {:?}", text))?;
+                decoded.push(escape);
+                i += 3;
+            }
+            b'+' if plus_as_space => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            byte => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| format!("{:?} does not decode to valid UTF-8", text))
+}
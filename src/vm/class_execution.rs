@@ -1,19 +1,25 @@
 // Class and function definition execution for the Metorex VM.
-// This module handles class and function definition statements.
+// This module handles class, module and function definition statements.
 
 use super::ControlFlow;
+use super::contracts::PendingContracts;
 use super::core::VirtualMachine;
+use super::errors::{
+    sealed_superclass_error, superclass_not_a_class_error, undefined_superclass_error,
+};
 use super::utils::*;
 
-use crate::ast::{Expression, Statement};
+use crate::ast::{Expression, FORWARDING_PARAMETER, Parameter, Statement, TypeAnnotation};
 use crate::class::Class;
 use crate::error::MetorexError;
 use crate::lexer::Position;
+use crate::module::Module;
 use crate::object::{Method, Object};
 use std::rc::Rc;
 
 impl VirtualMachine {
-    /// Execute class definition - create a Class object and register it in the environment.
+    /// Execute class definition - create a Class object and register it in the
+    /// environment, or as a constant of the class or module it is nested in.
     pub(crate) fn execute_class_def(
         &mut self,
         name: &str,
@@ -23,46 +29,74 @@ impl VirtualMachine {
     ) -> Result<ControlFlow, MetorexError> {
         // Resolve superclass if specified
         let superclass = if let Some(super_name) = superclass_name {
-            match self.environment().get(super_name) {
+            match self.resolve_constant(super_name) {
                 Some(Object::Class(class)) => Some(class),
-                Some(_) => {
-                    return Err(MetorexError::runtime_error(
-                        format!("Superclass '{}' must be a class", super_name),
-                        position_to_location(position),
-                    ));
-                }
-                None => {
-                    return Err(MetorexError::runtime_error(
-                        format!("Undefined superclass '{}'", super_name),
-                        position_to_location(position),
-                    ));
-                }
+                Some(_) => return Err(superclass_not_a_class_error(super_name, position)),
+                None => return Err(undefined_superclass_error(super_name, position)),
             }
         } else {
             None
         };
 
-        // Create the class object
-        let class = Rc::new(Class::new(name, superclass));
+        // A sealed superclass only admits the variants it lists
+        if let Some(variants) = superclass.as_ref().and_then(|class| class.variants())
+            && !variants.iter().any(|variant| variant == name)
+        {
+            return Err(sealed_superclass_error(
+                superclass_name.unwrap_or_default(),
+                name,
+                &variants,
+                position,
+            ));
+        }
+
+        // Create the class object, or reopen the existing one during a reload.
+        // A class defined inside another is named after it, like `Outer::Inner`
+        let qualified_name = self.qualified_name(name);
+        let class = match self.class_to_reopen(name, superclass.as_ref()) {
+            Some(existing) => existing,
+            None => Rc::new(Class::new(qualified_name.as_str(), superclass)),
+        };
+        self.trace_class(&qualified_name, position)?;
+
+        self.within_namespace(Object::Class(Rc::clone(&class)), |vm| {
+            vm.define_class_body(&class, body, position)
+        })?;
+
+        // Register the class in the enclosing class or module, or the environment
+        self.define_constant(name, Object::Class(class));
+
+        Ok(ControlFlow::Next)
+    }
 
-        // Process the class body to extract methods and instance variable declarations
+    /// Process a class body to extract methods, instance variable
+    /// declarations, constants and nested definitions.
+    fn define_class_body(
+        &mut self,
+        class: &Rc<Class>,
+        body: &[Statement],
+        position: Position,
+    ) -> Result<(), MetorexError> {
+        let mut contracts = PendingContracts::default();
         for statement in body {
             match statement {
                 Statement::MethodDef {
                     name: method_name,
                     parameters,
                     body: method_body,
-                    ..
+                    position: method_position,
+                    return_type,
                 } => {
-                    // Create a Method object
-                    let param_names: Vec<String> =
-                        parameters.iter().map(|p| p.name.clone()).collect();
-                    let method = Rc::new(Method::new(
-                        method_name.clone(),
-                        param_names,
-                        method_body.clone(),
-                    ));
-                    class.define_method(method_name, method);
+                    // Create a Method object with the conditions declared before it
+                    let mut method = self.defined_method(
+                        method_name,
+                        parameters,
+                        method_body,
+                        return_type.as_ref(),
+                        *method_position,
+                    );
+                    contracts.attach(&mut method);
+                    class.define_method(method_name, Rc::new(method));
                 }
                 Statement::Assignment {
                     target: Expression::InstanceVariable { name: var_name, .. },
@@ -78,7 +112,20 @@ impl VirtualMachine {
                 } => {
                     // Class variable initialization (e.g., @@count = 0 in class body)
                     let initial_value = self.evaluate_expression(value)?;
-                    class.set_class_var(var_name, initial_value);
+                    class.assign_class_var(var_name, initial_value);
+                }
+                Statement::Assignment {
+                    target: Expression::Identifier { name: constant, .. },
+                    value,
+                    ..
+                } if constant.starts_with(char::is_uppercase) => {
+                    // Constant definition (e.g., LIMIT = 10 in class body)
+                    let value = self.evaluate_expression(value)?;
+                    class.set_constant(constant, value);
+                }
+                Statement::ClassDef { .. } | Statement::ModuleDef { .. } => {
+                    // Nested definitions become constants of this class
+                    self.execute_statement(statement)?;
                 }
                 Statement::Expression {
                     expression: Expression::InstanceVariable { name: var_name, .. },
@@ -87,79 +134,92 @@ impl VirtualMachine {
                     // Instance variable declaration without assignment
                     class.declare_instance_var(var_name);
                 }
-                Statement::AttrReader { attributes, .. } => {
-                    // Generate getter methods for each attribute
+                Statement::AttrReader { attributes, .. }
+                | Statement::AttrWriter { attributes, .. }
+                | Statement::AttrAccessor { attributes, .. } => {
+                    for (method_name, method) in attribute_methods(statement, position) {
+                        class.define_method(method_name, method);
+                    }
                     for attr_name in attributes {
-                        let getter_body = vec![Statement::Return {
-                            value: Some(Expression::InstanceVariable {
-                                name: attr_name.clone(),
-                                position,
-                            }),
-                            position,
-                        }];
-                        let method = Rc::new(Method::new(attr_name.clone(), vec![], getter_body));
-                        class.define_method(attr_name, method);
                         class.declare_instance_var(attr_name);
                     }
                 }
-                Statement::AttrWriter { attributes, .. } => {
-                    // Generate setter methods for each attribute
-                    for attr_name in attributes {
-                        let setter_body = vec![Statement::Assignment {
-                            target: Expression::InstanceVariable {
-                                name: attr_name.clone(),
-                                position,
-                            },
-                            value: Expression::Identifier {
-                                name: "value".to_string(),
-                                position,
-                            },
-                            position,
-                        }];
-                        let method = Rc::new(Method::new(
-                            format!("{}=", attr_name),
-                            vec!["value".to_string()],
-                            setter_body,
-                        ));
-                        class.define_method(format!("{}=", attr_name), method);
-                        class.declare_instance_var(attr_name);
+                Statement::Expression {
+                    expression:
+                        Expression::Call {
+                            callee,
+                            arguments,
+                            position: call_position,
+                            ..
+                        },
+                    ..
+                } if matches!(callee.as_ref(), Expression::Identifier { name, .. } if name == "delegate") =>
+                {
+                    self.define_delegators(class, arguments, *call_position)?;
+                }
+                Statement::Expression {
+                    expression:
+                        Expression::Call {
+                            callee,
+                            arguments,
+                            position: call_position,
+                            ..
+                        },
+                    ..
+                } if matches!(callee.as_ref(), Expression::Identifier { name, .. } if name == "variants") =>
+                {
+                    self.seal_class(class, arguments, *call_position)?;
+                }
+                Statement::Expression {
+                    expression:
+                        Expression::Call {
+                            callee,
+                            arguments,
+                            trailing_block,
+                            position: call_position,
+                        },
+                    ..
+                } if matches!(callee.as_ref(), Expression::Identifier { name, .. } if name == "pre" || name == "post") =>
+                {
+                    let kind = match callee.as_ref() {
+                        Expression::Identifier { name, .. } if name == "pre" => "pre",
+                        _ => "post",
+                    };
+                    let condition = self.contract_condition(
+                        kind,
+                        class,
+                        arguments,
+                        trailing_block.as_deref(),
+                        *call_position,
+                    )?;
+                    contracts.add(kind, condition, *call_position);
+                }
+                Statement::Expression {
+                    expression:
+                        Expression::Call {
+                            callee,
+                            arguments,
+                            position: call_position,
+                            ..
+                        },
+                    ..
+                } if matches!(callee.as_ref(), Expression::Identifier { name, .. } if name == "include") => {
+                    for module in self.mixin_modules("include", arguments, *call_position)? {
+                        class.include_module(&module);
                     }
                 }
-                Statement::AttrAccessor { attributes, .. } => {
-                    // Generate both getter and setter methods for each attribute
-                    for attr_name in attributes {
-                        // Getter
-                        let getter_body = vec![Statement::Return {
-                            value: Some(Expression::InstanceVariable {
-                                name: attr_name.clone(),
-                                position,
-                            }),
-                            position,
-                        }];
-                        let getter_method =
-                            Rc::new(Method::new(attr_name.clone(), vec![], getter_body));
-                        class.define_method(attr_name, getter_method);
-
-                        // Setter
-                        let setter_body = vec![Statement::Assignment {
-                            target: Expression::InstanceVariable {
-                                name: attr_name.clone(),
-                                position,
-                            },
-                            value: Expression::Identifier {
-                                name: "value".to_string(),
-                                position,
-                            },
-                            position,
-                        }];
-                        let setter_method = Rc::new(Method::new(
-                            format!("{}=", attr_name),
-                            vec!["value".to_string()],
-                            setter_body,
-                        ));
-                        class.define_method(format!("{}=", attr_name), setter_method);
-
-                        class.declare_instance_var(attr_name);
+                Statement::Expression {
+                    expression:
+                        Expression::Call {
+                            callee,
+                            arguments,
+                            position: call_position,
+                            ..
+                        },
+                    ..
+                } if matches!(callee.as_ref(), Expression::Identifier { name, .. } if name == "extend") => {
+                    for module in self.mixin_modules("extend", arguments, *call_position)? {
+                        class.extend_module(&module);
                     }
                 }
                 _ => {
@@ -169,36 +229,254 @@ impl VirtualMachine {
             }
         }
 
-        // Register the class in the environment
-        self.environment_mut()
-            .define(name.to_string(), Object::Class(class));
+        contracts.finish()
+    }
+
+    /// Execute module definition - create a Module object and register it in the
+    /// environment, or as a constant of the class or module it is nested in.
+    pub(crate) fn execute_module_def(
+        &mut self,
+        name: &str,
+        body: &[Statement],
+        position: Position,
+    ) -> Result<ControlFlow, MetorexError> {
+        let qualified_name = self.qualified_name(name);
+        let module = Rc::new(Module::new(qualified_name.as_str()));
+        self.trace_class(&qualified_name, position)?;
+
+        self.within_namespace(Object::Module(Rc::clone(&module)), |vm| {
+            vm.define_module_body(&module, body, position)
+        })?;
+
+        self.define_constant(name, Object::Module(module));
 
         Ok(ControlFlow::Next)
     }
 
+    /// Process a module body to extract methods, mixins, constants and
+    /// nested definitions.
+    fn define_module_body(
+        &mut self,
+        module: &Rc<Module>,
+        body: &[Statement],
+        position: Position,
+    ) -> Result<(), MetorexError> {
+        for statement in body {
+            match statement {
+                Statement::MethodDef {
+                    name: method_name,
+                    parameters,
+                    body: method_body,
+                    position: method_position,
+                    return_type,
+                } => {
+                    let method = Rc::new(self.defined_method(
+                        method_name,
+                        parameters,
+                        method_body,
+                        return_type.as_ref(),
+                        *method_position,
+                    ));
+                    module.define_method(method_name, method);
+                }
+                Statement::Expression {
+                    expression:
+                        Expression::Call {
+                            callee,
+                            arguments,
+                            position: call_position,
+                            ..
+                        },
+                    ..
+                } if matches!(callee.as_ref(), Expression::Identifier { name, .. } if name == "include") => {
+                    for included in self.mixin_modules("include", arguments, *call_position)? {
+                        module.include(&included);
+                    }
+                }
+                Statement::AttrReader { .. }
+                | Statement::AttrWriter { .. }
+                | Statement::AttrAccessor { .. } => {
+                    // Classes that include the module get the accessors
+                    for (method_name, method) in attribute_methods(statement, position) {
+                        module.define_method(method_name, method);
+                    }
+                }
+                Statement::Assignment {
+                    target: Expression::Identifier { name: constant, .. },
+                    value,
+                    ..
+                } if constant.starts_with(char::is_uppercase) => {
+                    let value = self.evaluate_expression(value)?;
+                    module.set_constant(constant, value);
+                }
+                Statement::ClassDef { .. } | Statement::ModuleDef { .. } => {
+                    self.execute_statement(statement)?;
+                }
+                _ => {
+                    // As in class bodies, other statements are ignored for now
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Evaluate the arguments of `include` or `extend`, each of which must be
+    /// a module.
+    fn mixin_modules(
+        &mut self,
+        keyword: &str,
+        arguments: &[Expression],
+        position: Position,
+    ) -> Result<Vec<Rc<Module>>, MetorexError> {
+        if arguments.is_empty() {
+            return Err(MetorexError::runtime_error(
+                format!("{} requires a module", keyword),
+                position_to_location(position),
+            ));
+        }
+
+        let mut modules = Vec::new();
+        for argument in self.evaluate_arguments(arguments)? {
+            match argument {
+                Object::Module(module) => modules.push(module),
+                other => {
+                    return Err(MetorexError::type_error(
+                        format!("{} expects modules, found '{}'", keyword, other.type_name()),
+                        position_to_location(position),
+                    ));
+                }
+            }
+        }
+        Ok(modules)
+    }
+
+    /// Handle `variants :A, :B` in a class body, sealing the class so that only
+    /// the listed classes may inherit from it.
+    fn seal_class(
+        &mut self,
+        class: &Rc<Class>,
+        arguments: &[Expression],
+        position: Position,
+    ) -> Result<(), MetorexError> {
+        let mut variants = Vec::new();
+        for argument in self.evaluate_arguments(arguments)? {
+            match argument {
+                Object::Symbol(name) | Object::String(name) => variants.push(name.to_string()),
+                other => {
+                    return Err(MetorexError::type_error(
+                        format!(
+                            "variants expects class names as symbols, found '{}'",
+                            other.type_name()
+                        ),
+                        position_to_location(position),
+                    ));
+                }
+            }
+        }
+        class.seal(variants);
+        Ok(())
+    }
+
+    /// Handle `delegate :name, ..., to: :target` in a class body by generating
+    /// methods that forward all of their arguments to the same method on the target.
+    /// The target is an instance variable (`:@target`) or a method on self (`:target`).
+    fn define_delegators(
+        &mut self,
+        class: &Rc<Class>,
+        arguments: &[Expression],
+        position: Position,
+    ) -> Result<(), MetorexError> {
+        let mut method_names = Vec::new();
+        let mut target = None;
+
+        for argument in self.evaluate_arguments(arguments)? {
+            match argument {
+                Object::Symbol(name) | Object::String(name) => method_names.push(name),
+                Object::Dict(options) => {
+                    target = match options.borrow().get(":to") {
+                        Some(Object::Symbol(name) | Object::String(name)) => Some(Rc::clone(name)),
+                        _ => None,
+                    };
+                }
+                other => {
+                    return Err(MetorexError::type_error(
+                        format!(
+                            "delegate expects method names as symbols, found '{}'",
+                            other.type_name()
+                        ),
+                        position_to_location(position),
+                    ));
+                }
+            }
+        }
+
+        let target = target.ok_or_else(|| {
+            MetorexError::runtime_error(
+                "delegate requires a 'to:' target",
+                position_to_location(position),
+            )
+        })?;
+
+        let receiver = match target.strip_prefix('@') {
+            Some(var_name) => Expression::InstanceVariable {
+                name: var_name.to_string(),
+                position,
+            },
+            None => Expression::MethodCall {
+                receiver: Box::new(Expression::SelfExpr { position }),
+                method: target.to_string(),
+                arguments: Vec::new(),
+                trailing_block: None,
+                position,
+            },
+        };
+
+        for method_name in method_names {
+            let body = vec![Statement::Return {
+                value: Some(Expression::MethodCall {
+                    receiver: Box::new(receiver.clone()),
+                    method: method_name.to_string(),
+                    arguments: vec![Expression::ForwardedArgs { position }],
+                    trailing_block: None,
+                    position,
+                }),
+                position,
+            }];
+            let method = Rc::new(Method::new(
+                method_name.to_string(),
+                vec![FORWARDING_PARAMETER.to_string()],
+                body,
+            ));
+            class.define_method(method_name.as_str(), method);
+        }
+
+        Ok(())
+    }
+
     /// Execute function definition - create a Method object and register it in the environment as a function.
     pub(crate) fn execute_function_def(
         &mut self,
         name: &str,
-        parameters: &[crate::ast::Parameter],
+        parameters: &[Parameter],
         body: &[Statement],
+        return_type: Option<&TypeAnnotation>,
         position: crate::lexer::Position,
     ) -> Result<ControlFlow, MetorexError> {
-        // Extract parameter names from the parameter definitions
-        let param_names: Vec<String> = parameters.iter().map(|p| p.name.clone()).collect();
-
-        // Create source location from position
-        let source_location =
-            crate::error::SourceLocation::new(position.line, position.column, position.offset);
-
         // Create a Method object to represent the function
         // (Method objects can represent both class methods and standalone functions)
-        let function = Rc::new(Method::with_source_location(
-            name.to_string(),
-            param_names,
-            body.to_vec(),
-            source_location,
-        ));
+        let function = Rc::new(self.defined_method(name, parameters, body, return_type, position));
+
+        // A top-level `def` also becomes a private method of Object, so it can
+        // be called as `self.name` from any method
+        if self
+            .environment()
+            .get("self")
+            .is_some_and(|receiver| self.is_main_object(&receiver))
+        {
+            self.builtins()
+                .object_class
+                .define_private_method(name, Rc::clone(&function));
+        }
 
         // Register the function in the environment
         self.environment_mut()
@@ -206,4 +484,82 @@ impl VirtualMachine {
 
         Ok(ControlFlow::Next)
     }
+
+    /// Build the Method for a `def`, remembering its declared types and the
+    /// file and source text it was defined in.
+    fn defined_method(
+        &self,
+        name: &str,
+        parameters: &[Parameter],
+        body: &[Statement],
+        return_type: Option<&TypeAnnotation>,
+        position: Position,
+    ) -> Method {
+        let source_location = match self.get_current_file() {
+            Some(file) => crate::error::SourceLocation::with_filename(
+                position.line,
+                position.column,
+                position.offset,
+                file.to_string_lossy().into_owned(),
+            ),
+            None => {
+                crate::error::SourceLocation::new(position.line, position.column, position.offset)
+            }
+        };
+        let mut method = Method::with_source_location(
+            name.to_string(),
+            parameters.iter().map(|p| p.name.clone()).collect(),
+            body.to_vec(),
+            source_location,
+        );
+        method.parameter_types = parameters
+            .iter()
+            .map(|p| p.type_annotation.clone())
+            .collect();
+        method.return_type = return_type.cloned();
+        method.source = self.current_source().cloned();
+        method.nesting = self.nesting_names();
+        method
+    }
+}
+
+/// The getters and setters an `attr_reader`, `attr_writer` or `attr_accessor`
+/// statement defines, keyed by method name
+fn attribute_methods(statement: &Statement, position: Position) -> Vec<(String, Rc<Method>)> {
+    let (attributes, reader, writer) = match statement {
+        Statement::AttrReader { attributes, .. } => (attributes, true, false),
+        Statement::AttrWriter { attributes, .. } => (attributes, false, true),
+        Statement::AttrAccessor { attributes, .. } => (attributes, true, true),
+        _ => return Vec::new(),
+    };
+
+    let mut methods = Vec::new();
+    for attr_name in attributes {
+        let variable = Expression::InstanceVariable {
+            name: attr_name.clone(),
+            position,
+        };
+        if reader {
+            let getter_body = vec![Statement::Return {
+                value: Some(variable.clone()),
+                position,
+            }];
+            let getter = Method::new(attr_name.clone(), vec![], getter_body);
+            methods.push((attr_name.clone(), Rc::new(getter)));
+        }
+        if writer {
+            let setter_name = format!("{}=", attr_name);
+            let setter_body = vec![Statement::Assignment {
+                target: variable,
+                value: Expression::Identifier {
+                    name: "value".to_string(),
+                    position,
+                },
+                position,
+            }];
+            let setter = Method::new(setter_name.clone(), vec!["value".to_string()], setter_body);
+            methods.push((setter_name, Rc::new(setter)));
+        }
+    }
+    methods
 }
@@ -0,0 +1,230 @@
+//! Tracing collection of reference cycles.
+//!
+//! Reference counting frees a value as soon as nothing points at it, but
+//! values that point at each other, like an instance holding a block that
+//! captured the instance, keep each other alive forever. The collector finds
+//! them with a mark and sweep over the heap's handles:
+//!
+//! 1. Starting from the tracked values, it builds the graph of arrays,
+//!    hashes, instances, blocks and captured variables they reach.
+//! 2. A value referenced more often than the graph explains is held from
+//!    outside it: by a variable in the environment, a call frame, a class or
+//!    a native still working with it. Those values are the roots, as is any
+//!    value that is borrowed while the collector runs.
+//! 3. Everything reachable from a root is marked.
+//! 4. The unmarked values are only held by each other. The sweep empties
+//!    them, which breaks their cycles and lets reference counting free them.
+//!
+//! Finding the roots from reference counts rather than by walking the
+//! environment and call stack also covers values only a native's locals hold
+//! mid-call, so a collection is safe at any allocation.
+//!
+//! Values stay ordinary `Rc`s throughout. This is cycle collection layered on
+//! reference counting, not a tracing collector over a heap of handles the VM
+//! owns: nothing is moved, and acyclic garbage is still freed by its counts.
+
+use crate::object::Object;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// A value the collector traces through
+enum Node {
+    Value(Object),
+    /// A variable captured by a block, shared with the scope it came from
+    Cell(Rc<RefCell<Object>>),
+}
+
+fn address<T: ?Sized>(rc: &Rc<T>) -> usize {
+    Rc::as_ptr(rc) as *const () as usize
+}
+
+impl Node {
+    /// Values that can hold other values; everything else is a leaf.
+    fn from_object(object: &Object) -> Option<Self> {
+        match object {
+            Object::Array(_) | Object::Dict(_) | Object::Instance(_) | Object::Block(_) => {
+                Some(Node::Value(object.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    fn address(&self) -> usize {
+        match self {
+            Node::Value(Object::Array(rc)) => address(rc),
+            Node::Value(Object::Dict(rc)) => address(rc),
+            Node::Value(Object::Instance(rc)) => address(rc),
+            Node::Value(Object::Block(rc)) => address(rc),
+            Node::Value(_) => unreachable!("only containers become nodes"),
+            Node::Cell(rc) => address(rc),
+        }
+    }
+
+    fn strong_count(&self) -> usize {
+        match self {
+            Node::Value(Object::Array(rc)) => Rc::strong_count(rc),
+            Node::Value(Object::Dict(rc)) => Rc::strong_count(rc),
+            Node::Value(Object::Instance(rc)) => Rc::strong_count(rc),
+            Node::Value(Object::Block(rc)) => Rc::strong_count(rc),
+            Node::Value(_) => unreachable!("only containers become nodes"),
+            Node::Cell(rc) => Rc::strong_count(rc),
+        }
+    }
+
+    /// The nodes this one references, once per reference. `None` when the
+    /// value is borrowed, so its contents can't be read.
+    fn children(&self) -> Option<Vec<Node>> {
+        let children = match self {
+            Node::Value(Object::Array(array)) => array
+                .try_borrow()
+                .ok()?
+                .iter()
+                .filter_map(Node::from_object)
+                .collect(),
+            Node::Value(Object::Dict(dict)) => dict
+                .try_borrow()
+                .ok()?
+                .values()
+                .filter_map(Node::from_object)
+                .collect(),
+            Node::Value(Object::Instance(instance)) => instance
+                .try_borrow()
+                .ok()?
+                .instance_vars
+                .values()
+                .filter_map(Node::from_object)
+                .collect(),
+            Node::Value(Object::Block(block)) => block
+                .captured_vars
+                .values()
+                .map(|cell| Node::Cell(Rc::clone(cell)))
+                .collect(),
+            Node::Value(_) => Vec::new(),
+            Node::Cell(cell) => Node::from_object(&*cell.try_borrow().ok()?)
+                .into_iter()
+                .collect(),
+        };
+        Some(children)
+    }
+
+    /// Move out every value this node holds. A block's captured variables
+    /// are cells of their own, emptied when they are swept.
+    fn clear(&self, released: &mut Vec<Object>) {
+        match self {
+            Node::Value(Object::Array(array)) => {
+                if let Ok(mut array) = array.try_borrow_mut() {
+                    released.append(&mut array);
+                }
+            }
+            Node::Value(Object::Dict(dict)) => {
+                if let Ok(mut dict) = dict.try_borrow_mut() {
+                    released.extend(dict.drain(..).map(|(_, value)| value));
+                }
+            }
+            Node::Value(Object::Instance(instance)) => {
+                if let Ok(mut instance) = instance.try_borrow_mut() {
+                    released.extend(instance.instance_vars.drain().map(|(_, value)| value));
+                }
+            }
+            Node::Value(_) => {}
+            Node::Cell(cell) => {
+                if let Ok(mut value) = cell.try_borrow_mut() {
+                    released.push(std::mem::replace(&mut *value, Object::Nil));
+                }
+            }
+        }
+    }
+}
+
+/// The values reachable from the tracked ones, each held once, with the
+/// references between them.
+#[derive(Default)]
+struct Graph {
+    nodes: Vec<Node>,
+    edges: Vec<Vec<usize>>,
+    /// Nodes whose contents were borrowed while the graph was built
+    borrowed: Vec<bool>,
+    by_address: HashMap<usize, usize>,
+}
+
+impl Graph {
+    fn add(&mut self, node: Node) -> usize {
+        let address = node.address();
+        if let Some(&index) = self.by_address.get(&address) {
+            return index;
+        }
+        let index = self.nodes.len();
+        self.by_address.insert(address, index);
+        self.nodes.push(node);
+        self.edges.push(Vec::new());
+        self.borrowed.push(false);
+        index
+    }
+
+    fn build(values: Vec<Object>) -> Self {
+        let mut graph = Graph::default();
+        for value in values {
+            if let Some(node) = Node::from_object(&value) {
+                graph.add(node);
+            }
+        }
+        let mut next = 0;
+        while next < graph.nodes.len() {
+            match graph.nodes[next].children() {
+                Some(children) => {
+                    for child in children {
+                        let index = graph.add(child);
+                        graph.edges[next].push(index);
+                    }
+                }
+                None => graph.borrowed[next] = true,
+            }
+            next += 1;
+        }
+        graph
+    }
+
+    /// Mark every node reachable from one held outside the graph.
+    fn mark(&self) -> Vec<bool> {
+        let mut internal = vec![0; self.nodes.len()];
+        for &child in self.edges.iter().flatten() {
+            internal[child] += 1;
+        }
+        // The graph holds one reference to each node itself
+        let mut pending: Vec<usize> = (0..self.nodes.len())
+            .filter(|&index| {
+                self.borrowed[index] || self.nodes[index].strong_count() > internal[index] + 1
+            })
+            .collect();
+
+        let mut marked = vec![false; self.nodes.len()];
+        while let Some(index) = pending.pop() {
+            if !std::mem::replace(&mut marked[index], true) {
+                pending.extend(self.edges[index].iter().filter(|&&child| !marked[child]));
+            }
+        }
+        marked
+    }
+}
+
+/// Free the cycles among `values` and the values they reach that nothing
+/// outside them holds. Returns how many arrays, hashes, instances and blocks
+/// were swept.
+pub(super) fn collect_cycles(values: Vec<Object>) -> usize {
+    let graph = Graph::build(values);
+    let marked = graph.mark();
+
+    let mut released = Vec::new();
+    let mut swept = 0;
+    for (node, _) in graph.nodes.iter().zip(marked).filter(|(_, marked)| !marked) {
+        node.clear(&mut released);
+        if let Node::Value(_) = node {
+            swept += 1;
+        }
+    }
+    // Free the emptied values only once the sweep no longer borrows them
+    drop(released);
+    drop(graph);
+    swept
+}
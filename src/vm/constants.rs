@@ -0,0 +1,132 @@
+//! Constants defined inside class and module bodies.
+//!
+//! `class Outer; class Inner; end; end` makes `Inner` a constant of `Outer`
+//! named `Outer::Inner`, read as `Outer::Inner`, rather than a top-level
+//! name. A body may also define constants with `LIMIT = 10`.
+//!
+//! While a class or module body runs, and while a method defined in one runs,
+//! the VM keeps the classes and modules it is lexically inside as its
+//! nesting. A capitalized name resolves to a constant of the innermost of
+//! them that defines it, then to one the innermost class inherits, and only
+//! then to a top-level name. `Module.nesting` returns the nesting, innermost
+//! first.
+
+use super::VirtualMachine;
+use crate::object::{Method, Object};
+
+impl VirtualMachine {
+    /// The constant `name` as seen from the current nesting, if an enclosing
+    /// class or module defines it.
+    pub(super) fn lexical_constant(&self, name: &str) -> Option<Object> {
+        if self.nesting.is_empty() || !name.starts_with(char::is_uppercase) {
+            return None;
+        }
+        self.nesting
+            .iter()
+            .rev()
+            .find_map(|namespace| own_constant(namespace, name))
+            .or_else(|| match self.nesting.last() {
+                Some(Object::Class(class)) => class.lookup_constant(name),
+                _ => None,
+            })
+    }
+
+    /// Resolve a class or module name written in a definition, such as a
+    /// superclass: a constant visible from here, or a top-level name.
+    pub(super) fn resolve_constant(&self, name: &str) -> Option<Object> {
+        self.lexical_constant(name)
+            .or_else(|| self.environment().get(name))
+    }
+
+    /// The value `name` is defined as where a definition is running: a
+    /// constant of the innermost class or module, or a top-level name.
+    pub(super) fn defined_constant(&self, name: &str) -> Option<Object> {
+        match self.nesting.last() {
+            Some(namespace) => own_constant(namespace, name),
+            None => self.environment().get(name),
+        }
+    }
+
+    /// Define `name` in the innermost class or module being defined, or as a
+    /// top-level name outside of one.
+    pub(super) fn define_constant(&mut self, name: &str, value: Object) {
+        match self.nesting.last() {
+            Some(Object::Class(class)) => class.set_constant(name, value),
+            Some(Object::Module(module)) => module.set_constant(name, value),
+            _ => self.environment_mut().define(name.to_string(), value),
+        }
+    }
+
+    /// The full name of a class or module defined here, like `Outer::Inner`.
+    pub(super) fn qualified_name(&self, name: &str) -> String {
+        match self.nesting.last().and_then(namespace_name) {
+            Some(outer) => format!("{}::{}", outer, name),
+            None => name.to_string(),
+        }
+    }
+
+    /// Full names of the current nesting, outermost first, for a method
+    /// defined here to remember.
+    pub(super) fn nesting_names(&self) -> Vec<String> {
+        self.nesting
+            .iter()
+            .filter_map(|namespace| namespace_name(namespace).map(str::to_string))
+            .collect()
+    }
+
+    /// Run `body` with `namespace` as the innermost class or module.
+    pub(super) fn within_namespace<T>(
+        &mut self,
+        namespace: Object,
+        body: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        self.nesting.push(namespace);
+        let result = body(self);
+        self.nesting.pop();
+        result
+    }
+
+    /// Switch to the nesting `method` was defined in, returning the nesting
+    /// to restore once it returns.
+    pub(super) fn enter_method_nesting(&mut self, method: &Method) -> Vec<Object> {
+        let nesting = method
+            .nesting
+            .iter()
+            .filter_map(|name| self.qualified_constant(name))
+            .collect();
+        std::mem::replace(&mut self.nesting, nesting)
+    }
+
+    /// The class, module or constant with the full name `name`, like
+    /// `Outer::Inner`, followed from a top-level name.
+    pub(super) fn qualified_constant(&self, name: &str) -> Option<Object> {
+        let mut segments = name.split("::");
+        let mut namespace = self.environment().get(segments.next()?)?;
+        for segment in segments {
+            namespace = own_constant(&namespace, segment)?;
+        }
+        Some(namespace)
+    }
+
+    /// `Module.nesting` - the current nesting, innermost first
+    pub(crate) fn module_nesting(&self) -> Object {
+        Object::array(self.nesting.iter().rev().cloned().collect())
+    }
+}
+
+/// A constant defined directly in a class or module.
+fn own_constant(namespace: &Object, name: &str) -> Option<Object> {
+    match namespace {
+        Object::Class(class) => class.get_constant(name),
+        Object::Module(module) => module.get_constant(name),
+        _ => None,
+    }
+}
+
+fn namespace_name(namespace: &Object) -> Option<&str> {
+    match namespace {
+        Object::Class(class) => Some(class.name()),
+        Object::Module(module) => Some(module.name()),
+        _ => None,
+    }
+}
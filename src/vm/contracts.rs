@@ -0,0 +1,224 @@
+//! Script-level assertions and method contracts.
+//!
+//! `assert(condition, message)` raises an `AssertionError` when the condition
+//! is false or nil. `raise_unless(condition, message)` raises a RuntimeError
+//! instead, or the exception class given before the message.
+//!
+//! Inside a class body, `pre { |x| ... }` and `post { |result, x| ... }`
+//! attach conditions to the method defined next. Preconditions receive the
+//! call's arguments, and postconditions receive the result followed by the
+//! arguments. Both run with the receiver as `self`, so they can read instance
+//! variables. A condition that returns false or nil raises `AssertionError`.
+//!
+//! Turning off the `contracts` option skips `assert` and every pre- and
+//! postcondition, so production runs pay nothing for them. `raise_unless`
+//! always checks, since it guards against bad input rather than bugs.
+
+use super::VirtualMachine;
+use super::errors::{method_argument_error, raised_exception_error};
+use super::utils::position_to_location;
+use crate::ast::Expression;
+use crate::class::Class;
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::{Method, Object};
+use std::rc::Rc;
+
+/// Conditions declared in a class body, waiting for the `def` they apply to
+#[derive(Default)]
+pub(super) struct PendingContracts {
+    preconditions: Vec<Rc<Method>>,
+    postconditions: Vec<Rc<Method>>,
+    /// The first `pre` or `post` still waiting, reported if no `def` follows
+    first: Option<(&'static str, Position)>,
+}
+
+impl PendingContracts {
+    /// Hold a condition declared with `pre` or `post` for the next method.
+    pub(super) fn add(&mut self, kind: &'static str, condition: Rc<Method>, position: Position) {
+        match kind {
+            "pre" => self.preconditions.push(condition),
+            _ => self.postconditions.push(condition),
+        }
+        self.first.get_or_insert((kind, position));
+    }
+
+    /// Give the waiting conditions to the method just defined.
+    pub(super) fn attach(&mut self, method: &mut Method) {
+        method.preconditions = std::mem::take(&mut self.preconditions);
+        method.postconditions = std::mem::take(&mut self.postconditions);
+        self.first = None;
+    }
+
+    /// Fail if a condition was declared after the last method of the class.
+    pub(super) fn finish(self) -> Result<(), MetorexError> {
+        match self.first {
+            Some((kind, position)) => Err(MetorexError::runtime_error(
+                format!("'{}' must be followed by a method definition", kind),
+                position_to_location(position),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+impl VirtualMachine {
+    /// assert(condition, message = "assertion failed")
+    pub(super) fn assert(
+        &mut self,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        if !self.options().contracts {
+            return Ok(Object::Nil);
+        }
+        self.assertions += 1;
+        let message = match arguments {
+            [condition] | [condition, _] if condition.is_truthy() => return Ok(Object::Nil),
+            [_] => "assertion failed".to_string(),
+            [_, Object::String(message)] => message.to_string(),
+            _ => {
+                return Err(method_argument_error(
+                    "assert",
+                    2,
+                    arguments.len(),
+                    position,
+                ));
+            }
+        };
+        Err(raised_exception_error("AssertionError", message, position))
+    }
+
+    /// raise_unless(condition, message) or
+    /// raise_unless(condition, ExceptionClass, message = "")
+    pub(super) fn raise_unless(
+        &mut self,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        let (condition, exception_type, message) = match arguments {
+            [condition, Object::String(message)] => {
+                (condition, "RuntimeError", message.to_string())
+            }
+            [condition, Object::Class(class), rest @ ..] if self.is_exception_class(class) => {
+                let message = match rest {
+                    [] => String::new(),
+                    [Object::String(message)] => message.to_string(),
+                    _ => return Err(raise_unless_usage_error(position)),
+                };
+                (condition, class.name(), message)
+            }
+            _ => return Err(raise_unless_usage_error(position)),
+        };
+        if condition.is_truthy() {
+            Ok(Object::Nil)
+        } else {
+            Err(raised_exception_error(exception_type, message, position))
+        }
+    }
+
+    /// Build the condition a class body declares with `pre { ... }` or
+    /// `post { ... }`, as a method of the class so it runs with the
+    /// receiver as `self`.
+    pub(super) fn contract_condition(
+        &mut self,
+        kind: &str,
+        class: &Class,
+        arguments: &[Expression],
+        block: Option<&Expression>,
+        position: Position,
+    ) -> Result<Rc<Method>, MetorexError> {
+        let usage_error = || {
+            MetorexError::runtime_error(
+                format!(
+                    "'{}' expects only a block, like {} {{ |x| x > 0 }}",
+                    kind, kind
+                ),
+                position_to_location(position),
+            )
+        };
+        let (Some(block), true) = (block, arguments.is_empty()) else {
+            return Err(usage_error());
+        };
+        match self.evaluate_expression(block)? {
+            Object::Block(block) => Ok(Rc::new(Method::from_block(
+                kind.to_string(),
+                &block,
+                class.name().to_string(),
+            ))),
+            _ => Err(usage_error()),
+        }
+    }
+
+    /// Check a method's preconditions against the arguments of a call.
+    pub(super) fn check_preconditions(
+        &mut self,
+        class: &Class,
+        method: &Method,
+        receiver: &Object,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<(), MetorexError> {
+        if method.preconditions.is_empty() || !self.options().contracts {
+            return Ok(());
+        }
+        let values = arguments.to_vec();
+        self.check_conditions("precondition", class, method, receiver, values, position)
+    }
+
+    /// Check a method's postconditions against its result and arguments.
+    pub(super) fn check_postconditions(
+        &mut self,
+        class: &Class,
+        method: &Method,
+        receiver: &Object,
+        result: &Object,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<(), MetorexError> {
+        if method.postconditions.is_empty() || !self.options().contracts {
+            return Ok(());
+        }
+        let mut values = vec![result.clone()];
+        values.extend_from_slice(arguments);
+        self.check_conditions("postcondition", class, method, receiver, values, position)
+    }
+
+    /// Run a method's pre- or postconditions, passing each as many of
+    /// `values` as it has parameters and nil for any beyond them.
+    fn check_conditions(
+        &mut self,
+        kind: &str,
+        class: &Class,
+        method: &Method,
+        receiver: &Object,
+        values: Vec<Object>,
+        position: Position,
+    ) -> Result<(), MetorexError> {
+        let conditions = match kind {
+            "precondition" => &method.preconditions,
+            _ => &method.postconditions,
+        };
+        for condition in conditions {
+            let mut values = values.clone();
+            values.resize(condition.parameters.len(), Object::Nil);
+            let holds = self.execute_method_body(class, condition, receiver.clone(), values)?;
+            if !holds.is_truthy() {
+                return Err(raised_exception_error(
+                    "AssertionError",
+                    format!("{} of '{}#{}' failed", kind, class.name(), method.name),
+                    position,
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn raise_unless_usage_error(position: Position) -> MetorexError {
+    raised_exception_error(
+        "ArgumentError",
+        "raise_unless expects a condition and a message, or a condition, an exception class and an optional message".to_string(),
+        position,
+    )
+}
@@ -17,6 +17,8 @@ pub(crate) enum ControlFlow {
     Break { position: Position },
     /// A continue statement was encountered.
     Continue { position: Position },
+    /// A retry statement was encountered inside a rescue clause.
+    Retry { position: Position },
     /// An exception was raised and is propagating.
     Exception {
         exception: Object,
@@ -66,6 +66,7 @@ impl VirtualMachine {
         body: &[Statement],
     ) -> Result<ControlFlow, MetorexError> {
         loop {
+            self.check_interrupt(condition.position())?;
             let condition_value = self.evaluate_expression(condition)?;
 
             if !is_truthy(&condition_value) {
@@ -76,6 +77,7 @@ impl VirtualMachine {
                 ControlFlow::Next => continue,
                 ControlFlow::Break { .. } => break,
                 ControlFlow::Continue { .. } => continue,
+                ControlFlow::Retry { position } => return Ok(ControlFlow::Retry { position }),
                 ControlFlow::Return { value, position } => {
                     return Ok(ControlFlow::Return { value, position });
                 }
@@ -115,10 +117,9 @@ impl VirtualMachine {
                 exclusive,
             } => {
                 // Convert range to array of integers
-                match (*start, *end) {
-                    (Object::Int(start_val), Object::Int(end_val)) => {
+                match self.range_int_bounds(&start, &end, exclusive, position)? {
+                    Some((start_val, end_inclusive)) => {
                         let mut elements = Vec::new();
-                        let end_inclusive = if exclusive { end_val - 1 } else { end_val };
 
                         if start_val <= end_inclusive {
                             for i in start_val..=end_inclusive {
@@ -132,7 +133,7 @@ impl VirtualMachine {
                         }
                         elements
                     }
-                    _ => {
+                    None => {
                         return Err(MetorexError::type_error(
                             "Range bounds must be integers for iteration",
                             position_to_location(position),
@@ -163,6 +164,7 @@ impl VirtualMachine {
                 ControlFlow::Next => continue,
                 ControlFlow::Break { .. } => break,
                 ControlFlow::Continue { .. } => continue,
+                ControlFlow::Retry { position } => return Ok(ControlFlow::Retry { position }),
                 ControlFlow::Return { value, position } => {
                     return Ok(ControlFlow::Return { value, position });
                 }
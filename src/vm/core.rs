@@ -2,19 +2,52 @@
 // This module defines the runtime scaffolding that powers execution.
 
 use super::errors::*;
+use super::event_log::Recording;
 use super::init::*;
+use super::native_functions::is_auto_called_function;
+use super::post_mortem::Unwinding;
+use super::random::Random;
+use super::scratch::ArgumentBuffers;
+use super::teardown::Finalizer;
+use super::timeout::Deadline;
+use super::trace::Tracing;
+use super::type_feedback::TypeFeedback;
 use super::utils::*;
-use super::{CallFrame, ControlFlow, GlobalRegistry, Heap};
+use super::{
+    CallFrame, ClassStats, ControlFlow, GlobalRegistry, Heap, HeapStats, LeakReport, NativeMethod,
+    StringInterner, TypeFeedbackStats, VmOptions,
+};
 
-use crate::ast::{Expression, Statement};
+use crate::ast::{BinaryOp, Expression, Statement};
+use crate::ast_cache::AstCache;
 use crate::builtin_classes::BuiltinClasses;
 use crate::environment::Environment;
 use crate::error::MetorexError;
-use crate::object::{BlockStatement, Object};
+use crate::lexer::Position;
+use crate::messages;
+use crate::object::{BlockStatement, FromMetorex, Object, RoundingStrategy};
+use crate::path_names::path_key;
+use crate::pretty_print::PrettyPrinter;
 use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+/// Default number of times `retry` may re-run a single begin block.
+pub const DEFAULT_MAX_RETRIES: usize = 100;
+
+/// How Float division and modulo by zero behave.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FloatDivisionPolicy {
+    /// Raise a "Division by zero" error, as Integer division does.
+    #[default]
+    Raise,
+    /// Follow IEEE 754: return Infinity, -Infinity or NaN.
+    Ieee,
+}
 
 /// Core virtual machine responsible for executing Metorex programs.
 pub struct VirtualMachine {
@@ -24,7 +57,45 @@ pub struct VirtualMachine {
     heap: Rc<RefCell<Heap>>,
     builtins: BuiltinClasses,
     current_file: Option<PathBuf>,
-    loaded_files: HashSet<PathBuf>,
+    current_source: Option<Rc<str>>,
+    main_file: Option<PathBuf>,
+    /// Loaded files by their tracking key, see [`VirtualMachine::loaded_file_key`]
+    pub(super) loaded_files: HashMap<PathBuf, PathBuf>,
+    pub(super) reload_depth: usize,
+    string_interner: Option<StringInterner>,
+    symbols: RefCell<StringInterner>,
+    type_feedback: Option<TypeFeedback>,
+    pub(super) argument_buffers: ArgumentBuffers,
+    pub(super) host_methods: HashMap<(String, String), NativeMethod>,
+    max_retries: usize,
+    float_division: FloatDivisionPolicy,
+    decimal_rounding: RoundingStrategy,
+    /// `assert` calls checked so far
+    pub(super) assertions: usize,
+    pub(super) input: Option<Box<dyn BufRead>>,
+    pub(super) output: Option<Box<dyn Write>>,
+    pub(super) line_editor: Option<rustyline::DefaultEditor>,
+    pub(super) readline_history: Vec<String>,
+    pretty_printer: PrettyPrinter,
+    options: VmOptions,
+    ast_cache: Option<AstCache>,
+    call_depth: usize,
+    pub(super) random: Random,
+    main_object: Object,
+    pub(super) finalizers: Vec<Finalizer>,
+    pub(super) interrupt_requested: Arc<AtomicBool>,
+    /// Deadlines of the running `Timeout.timeout` blocks, outermost first
+    pub(super) deadlines: Vec<Deadline>,
+    /// Classes and modules the running code is lexically inside, outermost
+    /// first
+    pub(super) nesting: Vec<Object>,
+    pub(super) tracing: Tracing,
+    pub(super) recording: Option<Recording>,
+    /// Frames the current error has unwound through, while post-mortem
+    /// recording is on
+    pub(super) unwinding: Option<Unwinding>,
+    #[cfg(feature = "sqlite")]
+    pub(super) sqlite: super::sqlite::SqliteConnections,
 }
 
 impl VirtualMachine {
@@ -35,9 +106,12 @@ impl VirtualMachine {
 
         initialize_builtin_methods(&builtins);
 
+        // `self` outside any method or class body, printed as "main"
+        let main_object = Object::instance(Rc::clone(&builtins.object_class));
+
         let mut globals = GlobalRegistry::new();
         register_builtin_classes(&mut globals, &builtins);
-        register_singletons(&mut globals);
+        register_singletons(&mut globals, &main_object);
         register_native_functions(&mut globals);
 
         seed_environment_with_globals(&mut environment, &globals);
@@ -49,10 +123,79 @@ impl VirtualMachine {
             heap: Rc::new(RefCell::new(Heap::default())),
             builtins,
             current_file: None,
-            loaded_files: HashSet::new(),
+            current_source: None,
+            main_file: None,
+            loaded_files: HashMap::new(),
+            reload_depth: 0,
+            string_interner: None,
+            symbols: RefCell::new(StringInterner::new()),
+            type_feedback: None,
+            argument_buffers: ArgumentBuffers::default(),
+            host_methods: HashMap::new(),
+            max_retries: DEFAULT_MAX_RETRIES,
+            float_division: FloatDivisionPolicy::default(),
+            decimal_rounding: RoundingStrategy::MidpointAwayFromZero,
+            assertions: 0,
+            input: None,
+            output: None,
+            line_editor: None,
+            readline_history: Vec::new(),
+            pretty_printer: PrettyPrinter::new(),
+            options: VmOptions::default(),
+            ast_cache: None,
+            call_depth: 0,
+            random: Random::new(None),
+            main_object,
+            finalizers: Vec::new(),
+            interrupt_requested: Arc::new(AtomicBool::new(false)),
+            deadlines: Vec::new(),
+            nesting: Vec::new(),
+            tracing: Tracing::default(),
+            recording: None,
+            unwinding: None,
+            #[cfg(feature = "sqlite")]
+            sqlite: super::sqlite::SqliteConnections::default(),
         }
     }
 
+    /// Construct a virtual machine configured with `options`.
+    pub fn with_options(options: VmOptions) -> Self {
+        let mut vm = Self::new();
+        vm.set_intern_string_literals(options.optimization_level >= 1);
+        vm.set_type_feedback(options.optimization_level >= 2);
+        vm.random = Random::new(options.seed);
+        if options.ast_cache {
+            vm.set_ast_cache(Some(AstCache::new(AstCache::default_dir())));
+        }
+        vm.options = options;
+        vm
+    }
+
+    /// Construct a virtual machine configured from the `MX_OPTS` environment variable.
+    pub fn from_env() -> Result<Self, String> {
+        VmOptions::from_env().map(Self::with_options)
+    }
+
+    /// The options this VM was created with.
+    pub fn options(&self) -> &VmOptions {
+        &self.options
+    }
+
+    /// Cache the parsed ASTs of files run with `execute_file`, or stop caching with `None`.
+    pub fn set_ast_cache(&mut self, cache: Option<AstCache>) {
+        self.ast_cache = cache;
+    }
+
+    /// The AST cache used for loaded files, if enabled.
+    pub fn ast_cache(&self) -> Option<&AstCache> {
+        self.ast_cache.as_ref()
+    }
+
+    /// Set the maximum depth of nested calls before "stack level too deep" is raised.
+    pub fn set_max_stack_depth(&mut self, depth: usize) {
+        self.options.max_stack_depth = depth;
+    }
+
     /// Access the environment.
     pub fn environment(&self) -> &Environment {
         &self.environment
@@ -78,29 +221,301 @@ impl VirtualMachine {
         &mut self.globals
     }
 
+    /// Define a global visible to every program this VM runs, replacing any
+    /// existing global or top-level variable of the same name.
+    pub fn set_global(&mut self, name: impl Into<String>, value: Object) {
+        let name = name.into();
+        self.globals.set(name.clone(), value.clone());
+        self.environment
+            .global_scope()
+            .borrow_mut()
+            .define(name, value);
+    }
+
     /// Borrow the heap allocator.
     pub fn heap(&self) -> Rc<RefCell<Heap>> {
         Rc::clone(&self.heap)
     }
 
+    /// Snapshot allocation and collection statistics.
+    pub fn heap_stats(&self) -> HeapStats {
+        self.heap.borrow().stats()
+    }
+
+    /// The `object_id` the heap has assigned to a value.
+    pub(crate) fn object_id(&self, object: &Object) -> i64 {
+        self.heap.borrow_mut().object_id(object)
+    }
+
+    /// Record a newly allocated object on the heap and hand it back.
+    ///
+    /// In debug builds the heap can ask for the call frames behind each
+    /// allocation, which leak reports show.
+    pub(crate) fn track_allocation(&self, object: Object) -> Object {
+        let mut heap = self.heap.borrow_mut();
+        let backtrace = if cfg!(debug_assertions) && heap.records_backtraces() {
+            self.call_stack_trace()
+        } else {
+            Vec::new()
+        };
+        heap.allocate_with_backtrace(&object, backtrace);
+        drop(heap);
+        object
+    }
+
+    /// Allocation and live object counts for each class.
+    pub fn heap_stats_by_class(&self) -> BTreeMap<String, ClassStats> {
+        self.heap.borrow().stats_by_class()
+    }
+
+    /// Run `f`, collect, and report the arrays, hashes and instances it
+    /// allocated that are still alive, such as objects kept by a reference
+    /// cycle. In debug builds each leak carries the call frames that
+    /// allocated it.
+    ///
+    /// Values `f` stores in variables or globals outlive it too, so keep
+    /// what it creates local to the check.
+    pub fn check_leaks(&mut self, f: impl FnOnce(&mut Self)) -> LeakReport {
+        let (start, recording) = {
+            let mut heap = self.heap.borrow_mut();
+            (heap.allocation_count(), heap.set_record_backtraces(true))
+        };
+        f(self);
+        let mut heap = self.heap.borrow_mut();
+        heap.set_record_backtraces(recording);
+        heap.leaks_since(start)
+    }
+
+    /// Like `check_leaks`, but panic with the report if anything leaked.
+    pub fn assert_no_leaks(&mut self, f: impl FnOnce(&mut Self)) {
+        let report = self.check_leaks(f);
+        assert!(report.is_empty(), "{}", report);
+    }
+
+    /// Enable or disable interning of string literals.
+    /// While enabled, identical literals evaluate to the same shared string.
+    pub fn set_intern_string_literals(&mut self, enabled: bool) {
+        if !enabled {
+            self.string_interner = None;
+        } else if self.string_interner.is_none() {
+            self.string_interner = Some(StringInterner::new());
+        }
+    }
+
+    /// Check if string literal interning is enabled.
+    pub fn interns_string_literals(&self) -> bool {
+        self.string_interner.is_some()
+    }
+
+    /// Number of distinct string literals interned so far.
+    pub fn interned_string_count(&self) -> usize {
+        self.string_interner.as_ref().map_or(0, StringInterner::len)
+    }
+
+    /// The symbol named `name`. Symbols are always interned, so every
+    /// `:name` in a program shares one string and compares by pointer.
+    pub fn symbol(&self, name: &str) -> Object {
+        Object::Symbol(self.symbols.borrow_mut().intern(name))
+    }
+
+    /// Enable or disable type feedback for binary operators.
+    /// While enabled, operators that keep seeing two Ints take a fast path.
+    pub fn set_type_feedback(&mut self, enabled: bool) {
+        if !enabled {
+            self.type_feedback = None;
+        } else if self.type_feedback.is_none() {
+            self.type_feedback = Some(TypeFeedback::new());
+        }
+    }
+
+    /// Check if type feedback is enabled.
+    pub fn uses_type_feedback(&self) -> bool {
+        self.type_feedback.is_some()
+    }
+
+    /// What type feedback has done so far; all zero while it is disabled.
+    pub fn type_feedback_stats(&self) -> TypeFeedbackStats {
+        self.type_feedback
+            .as_ref()
+            .map_or_else(TypeFeedbackStats::default, TypeFeedback::stats)
+    }
+
+    /// Set how many times `retry` may re-run a single begin block before the
+    /// exception is re-raised.
+    pub fn set_max_retries(&mut self, max_retries: usize) {
+        self.max_retries = max_retries;
+    }
+
+    /// Maximum number of retries allowed per begin block.
+    pub fn max_retries(&self) -> usize {
+        self.max_retries
+    }
+
+    /// Choose how Float division by zero behaves. Integer division by zero always raises.
+    pub fn set_float_division_policy(&mut self, policy: FloatDivisionPolicy) {
+        self.float_division = policy;
+    }
+
+    /// The current Float division-by-zero policy.
+    pub fn float_division_policy(&self) -> FloatDivisionPolicy {
+        self.float_division
+    }
+
+    /// Choose how Decimals round when a call does not name a rounding mode.
+    /// The default rounds halves away from zero.
+    pub fn set_decimal_rounding(&mut self, mode: RoundingStrategy) {
+        self.decimal_rounding = mode;
+    }
+
+    /// The rounding mode Decimals use by default.
+    pub fn decimal_rounding(&self) -> RoundingStrategy {
+        self.decimal_rounding
+    }
+
+    /// How many `assert` calls this VM has checked, for test reports.
+    pub fn assertion_count(&self) -> usize {
+        self.assertions
+    }
+
+    /// Set the line width `pp` wraps nested values at.
+    pub fn set_pretty_print_width(&mut self, width: usize) {
+        self.pretty_printer = PrettyPrinter::with_width(width);
+    }
+
+    /// The pretty printer used by `pp`.
+    pub fn pretty_printer(&self) -> PrettyPrinter {
+        self.pretty_printer
+    }
+
+    /// The top-level `self`, an Object instance that prints as "main".
+    pub fn main_object(&self) -> &Object {
+        &self.main_object
+    }
+
+    /// Whether `object` is the top-level `self`.
+    pub(crate) fn is_main_object(&self, object: &Object) -> bool {
+        match (object, &self.main_object) {
+            (Object::Instance(object), Object::Instance(main)) => Rc::ptr_eq(object, main),
+            _ => false,
+        }
+    }
+
     /// Set the current file being executed.
     pub fn set_current_file(&mut self, path: PathBuf) {
         self.current_file = Some(path);
     }
 
+    /// Forget the current file, as for code that is not running from one.
+    pub fn clear_current_file(&mut self) {
+        self.current_file = None;
+    }
+
     /// Get the current file being executed.
     pub fn get_current_file(&self) -> Option<&PathBuf> {
         self.current_file.as_ref()
     }
 
+    /// Set the source text the next program was parsed from, so methods it
+    /// defines can report their own source. Pass `None` for programs built
+    /// without source text.
+    pub fn set_current_source(&mut self, source: Option<&str>) {
+        self.current_source = source.map(Rc::from);
+    }
+
+    /// The source text of the program being executed, if known.
+    pub fn current_source(&self) -> Option<&Rc<str>> {
+        self.current_source.as_ref()
+    }
+
+    /// Set the file that was run directly, as opposed to loaded via require.
+    pub fn set_main_file(&mut self, path: PathBuf) {
+        self.main_file = Some(path);
+    }
+
+    /// Get the file that was run directly.
+    pub fn main_file(&self) -> Option<&PathBuf> {
+        self.main_file.as_ref()
+    }
+
+    /// Whether the code currently executing belongs to the main file.
+    ///
+    /// Code outside any file, such as REPL input, counts as the main program.
+    pub fn is_main_file(&self) -> bool {
+        self.current_file == self.main_file
+    }
+
+    /// Resolve a bare name to its value without calling it.
+    fn lookup_identifier(&self, name: &str, position: Position) -> Result<Object, MetorexError> {
+        match self
+            .lexical_constant(name)
+            .or_else(|| self.environment.get(name))
+        {
+            Some(value) => Ok(value),
+            None => self.unbound_identifier(name, position),
+        }
+    }
+
+    /// A name that is neither a constant nor a variable: one of the loader's
+    /// identifiers, or an undefined variable. Kept out of line, as inlining
+    /// it into every variable read slowed tight loops by a quarter.
+    #[cold]
+    #[inline(never)]
+    fn unbound_identifier(&self, name: &str, position: Position) -> Result<Object, MetorexError> {
+        self.loader_identifier(name)
+            .ok_or_else(|| undefined_variable_error(name, position))
+    }
+
+    /// The value of a bare name bound to a native function: `gets` and the
+    /// like are called, so `gets.chomp` works without parens, and any other
+    /// function is returned as is. Out of line for the same reason as
+    /// [`Self::unbound_identifier`].
+    #[cold]
+    #[inline(never)]
+    fn auto_call(&mut self, value: Object, position: Position) -> Result<Object, MetorexError> {
+        match &value {
+            Object::NativeFunction(function) if is_auto_called_function(function) => {
+                self.call_native_function(function, Vec::new(), position)
+            }
+            _ => Ok(value),
+        }
+    }
+
+    /// Values the loader provides to every file: `__FILE__` is the path of
+    /// the running file and `main?` is true unless it was loaded via require.
+    fn loader_identifier(&self, name: &str) -> Option<Object> {
+        match name {
+            "__FILE__" => Some(
+                self.current_file
+                    .as_ref()
+                    .map(|path| Object::string(path.to_string_lossy()))
+                    .unwrap_or(Object::Nil),
+            ),
+            "main?" => Some(Object::Bool(self.is_main_file())),
+            _ => None,
+        }
+    }
+
     /// Mark a file as loaded in the registry.
     pub fn mark_file_loaded(&mut self, path: PathBuf) {
-        self.loaded_files.insert(path);
+        self.loaded_files.insert(self.loaded_file_key(&path), path);
     }
 
     /// Check if a file has already been loaded.
-    pub fn is_file_loaded(&self, path: &PathBuf) -> bool {
-        self.loaded_files.contains(path)
+    pub fn is_file_loaded(&self, path: &Path) -> bool {
+        self.loaded_files.contains_key(&self.loaded_file_key(path))
+    }
+
+    /// The key a loaded file is tracked under: its path, lowercased when the
+    /// `case_insensitive_paths` option is on.
+    pub(super) fn loaded_file_key(&self, path: &Path) -> PathBuf {
+        path_key(path, self.options.case_insensitive_paths)
+    }
+
+    /// Every file loaded so far, including required files, in sorted order.
+    pub fn loaded_files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = self.loaded_files.values().cloned().collect();
+        files.sort();
+        files
     }
 
     /// Run a closure with a new call frame pushed onto the stack.
@@ -114,16 +529,63 @@ impl VirtualMachine {
         result
     }
 
+    /// Run a call body one level deeper, failing once the configured stack
+    /// depth is exceeded instead of overflowing the native stack.
+    pub(crate) fn with_call_depth<F, R>(
+        &mut self,
+        position: Position,
+        action: F,
+    ) -> Result<R, MetorexError>
+    where
+        F: FnOnce(&mut Self) -> Result<R, MetorexError>,
+    {
+        if self.call_depth >= self.options.max_stack_depth {
+            return Err(stack_too_deep_error(self.options.max_stack_depth, position));
+        }
+        self.call_depth += 1;
+        let result = action(self);
+        self.call_depth -= 1;
+        result
+    }
+
     /// Inspect the current call stack (top is last element).
     pub fn call_stack(&self) -> &[CallFrame] {
         &self.call_stack
     }
 
+    /// The call stack as backtrace lines, e.g. `Point#move (3:5)`, in the
+    /// order frames were entered.
+    pub(crate) fn call_stack_trace(&self) -> Vec<String> {
+        self.call_stack
+            .iter()
+            .map(|frame| match frame.location() {
+                Some(location) => format!("{} ({})", frame.name(), location),
+                None => frame.name().to_string(),
+            })
+            .collect()
+    }
+
     /// Get the name of the current method being executed (from the top of the call stack).
     pub(crate) fn get_current_method_name(&self) -> Option<&str> {
         self.call_stack.last().map(|frame| frame.name())
     }
 
+    /// Execute a program with `locals` defined as top-level variables first.
+    ///
+    /// This is how embedders pass parameters to a script without splicing
+    /// assignments into its source. The locals behave as if the program had
+    /// assigned them, so they remain defined once it finishes.
+    pub fn execute_program_with_locals(
+        &mut self,
+        statements: &[Statement],
+        locals: &[(&str, Object)],
+    ) -> Result<Option<Object>, MetorexError> {
+        for (name, value) in locals {
+            self.environment.define(name.to_string(), value.clone());
+        }
+        self.execute_program(statements)
+    }
+
     /// Execute a sequence of statements and return an optional result (from return statements).
     pub fn execute_program(
         &mut self,
@@ -138,6 +600,8 @@ impl VirtualMachine {
                 position,
             } = statement
             {
+                self.check_interrupt(*position)?;
+                self.trace_line(*position)?;
                 let result = self.evaluate_expression(expression)?;
 
                 // Ruby-style auto-call: if expression statement evaluates to a Method
@@ -154,44 +618,30 @@ impl VirtualMachine {
             }
 
             // Match statements also produce values
-            if let Statement::Match { .. } = statement {
-                match self.execute_statement(statement)? {
-                    ControlFlow::Return { value, .. } => {
-                        last_value = Some(value);
-                        continue;
-                    }
-                    ControlFlow::Next => {}
-                    ControlFlow::Exception {
-                        exception,
-                        position,
-                    } => {
-                        return Err(MetorexError::runtime_error(
-                            format!("Uncaught exception: {}", format_exception(&exception)),
-                            position_to_location(position),
-                        ));
-                    }
-                    ControlFlow::Break { position } => {
-                        return Err(loop_control_error("break", position));
-                    }
-                    ControlFlow::Continue { position } => {
-                        return Err(loop_control_error("continue", position));
-                    }
-                }
-                continue;
-            }
+            let flow = if let Statement::Match {
+                expression,
+                cases,
+                position,
+            } = statement
+            {
+                self.check_interrupt(*position)?;
+                self.trace_line(*position)?;
+                let (flow, value) = self.run_match(expression, cases, *position)?;
+                last_value = Some(value);
+                flow
+            } else {
+                self.execute_statement(statement)?
+            };
 
-            // Execute other statements
-            match self.execute_statement(statement)? {
+            // Handle jumps out of the statement
+            match flow {
                 ControlFlow::Next => {}
                 ControlFlow::Return { value, .. } => return Ok(Some(value)),
                 ControlFlow::Exception {
                     exception,
                     position,
                 } => {
-                    return Err(MetorexError::runtime_error(
-                        format!("Uncaught exception: {}", format_exception(&exception)),
-                        position_to_location(position),
-                    ));
+                    return Err(self.uncaught_at_top_level(exception, position));
                 }
                 ControlFlow::Break { position } => {
                     return Err(loop_control_error("break", position));
@@ -199,6 +649,9 @@ impl VirtualMachine {
                 ControlFlow::Continue { position } => {
                     return Err(loop_control_error("continue", position));
                 }
+                ControlFlow::Retry { position } => {
+                    return Err(retry_outside_rescue_error(position));
+                }
             }
         }
 
@@ -210,7 +663,8 @@ impl VirtualMachine {
     /// This method loads and executes a file, handling:
     /// - File deduplication (files are only executed once)
     /// - Current file path tracking (for require_relative)
-    /// - Automatic path canonicalization
+    /// - Automatic path canonicalization, resolving symlinks so a file reached
+    ///   through several links runs once
     /// - Proper restoration of the previous current file
     ///
     /// # Arguments
@@ -220,28 +674,12 @@ impl VirtualMachine {
     /// * `Ok(Object)` - The result of executing the file (or Nil if already loaded)
     /// * `Err(MetorexError)` - If loading, parsing, or execution fails
     pub fn execute_file(&mut self, path: &std::path::Path) -> Result<Object, MetorexError> {
-        use crate::error::SourceLocation;
-        use crate::file_loader::{find_file_path, load_file_source, parse_file};
-
-        // Find the actual file path (with extension auto-detection)
-        let actual_path = find_file_path(path).map_err(|e| {
-            MetorexError::runtime_error(
-                format!("Failed to find file '{}': {}", path.display(), e),
-                SourceLocation::new(0, 0, 0),
-            )
-        })?;
-
-        // Canonicalize the file path to absolute path for proper deduplication
-        let canonical_path = actual_path.canonicalize().map_err(|e| {
-            MetorexError::runtime_error(
-                format!(
-                    "Failed to canonicalize file path '{}': {}",
-                    actual_path.display(),
-                    e
-                ),
-                SourceLocation::new(0, 0, 0),
-            )
-        })?;
+        use crate::file_loader::{canonical_file_path, load_file_source, parse_file};
+
+        // Find the file (with extension auto-detection) and canonicalize it for
+        // proper deduplication
+        let canonical_path = canonical_file_path(path)
+            .map_err(|e| file_error(&messages::FILE_NOT_FOUND, path, &e))?;
 
         // Check if file is already loaded (deduplication)
         if self.is_file_loaded(&canonical_path) {
@@ -251,43 +689,75 @@ impl VirtualMachine {
         // Mark file as loaded before executing to prevent circular dependencies
         self.mark_file_loaded(canonical_path.clone());
 
-        // Save the current file path to restore later
+        // The first file the loader runs is the main program
+        if self.main_file.is_none() {
+            self.set_main_file(canonical_path.clone());
+        }
+
+        // Save the current file path and source to restore later
         let previous_file = self.current_file.clone();
+        let previous_source = self.current_source.clone();
 
         // Load file source with error context
-        let source = load_file_source(&canonical_path).map_err(|e| {
-            MetorexError::runtime_error(
-                format!("Failed to load file '{}': {}", canonical_path.display(), e),
-                SourceLocation::new(0, 0, 0),
-            )
-        })?;
+        let source = load_file_source(&canonical_path)
+            .map_err(|e| file_error(&messages::FILE_NOT_LOADED, &canonical_path, &e))?;
 
-        // Parse file with error context
-        let statements = parse_file(&source, &canonical_path.to_string_lossy()).map_err(|e| {
-            MetorexError::runtime_error(
-                format!("Failed to parse file '{}': {}", canonical_path.display(), e),
-                SourceLocation::new(0, 0, 0),
-            )
-        })?;
+        // Parse file with error context, reusing a cached AST when the source is unchanged
+        let statements = match self.ast_cache.as_mut() {
+            Some(cache) => cache.load_or_parse(&canonical_path, &source),
+            None => parse_file(&source, &canonical_path.to_string_lossy()),
+        }
+        .map_err(|e| file_error(&messages::FILE_NOT_PARSED, &canonical_path, &e))?;
 
         // Update current file path for require_relative calls within this file
         self.set_current_file(canonical_path.clone());
+        self.set_current_source(Some(&source));
 
         // Execute the parsed statements
-        let result = self.execute_program(&statements).map_err(|e| {
-            MetorexError::runtime_error(
-                format!("Error executing file '{}': {}", canonical_path.display(), e),
-                SourceLocation::new(0, 0, 0),
-            )
-        })?;
+        let result = self
+            .execute_program(&statements)
+            .map_err(|e| file_error(&messages::FILE_EXECUTION_FAILED, &canonical_path, &e))?;
 
         // Restore previous current file path
         self.current_file = previous_file;
+        self.current_source = previous_source;
 
         // Return the result or Nil if no return value
         Ok(result.unwrap_or(Object::Nil))
     }
 
+    /// Load the library `name` from the load path, once, as `require` does.
+    ///
+    /// Returns `true` if the library ran now and `false` if it had already
+    /// been loaded.
+    pub fn require_library(&mut self, name: &str) -> Result<bool, MetorexError> {
+        use crate::file_loader::{canonical_file_path, find_in_load_path};
+
+        let path = find_in_load_path(&self.options.load_path, name)?;
+        let was_already_loaded = self.is_file_loaded(&canonical_file_path(&path)?);
+        self.execute_file(&path)?;
+        Ok(!was_already_loaded)
+    }
+
+    /// Execute a file and convert its final value into `T`.
+    ///
+    /// A value of the wrong shape is reported as a type error naming the
+    /// expected and found types and where in the value they differ.
+    pub fn execute_file_typed<T: FromMetorex>(
+        &mut self,
+        path: &std::path::Path,
+    ) -> Result<T, MetorexError> {
+        let value = self.execute_file(path)?;
+        T::from_metorex(&value).map_err(|error| {
+            MetorexError::type_error_with_types(
+                format!("Result of '{}': {}", path.display(), error),
+                crate::error::SourceLocation::new(0, 0, 0),
+                error.expected,
+                error.found,
+            )
+        })
+    }
+
     /// Evaluate an expression to a runtime value.
     pub(crate) fn evaluate_expression(
         &mut self,
@@ -296,21 +766,28 @@ impl VirtualMachine {
         match expression {
             Expression::IntLiteral { value, .. } => Ok(Object::Int(*value)),
             Expression::FloatLiteral { value, .. } => Ok(Object::Float(*value)),
-            Expression::StringLiteral { value, .. } => Ok(Object::String(Rc::new(value.clone()))),
-            Expression::Symbol { value, .. } => Ok(Object::Symbol(Rc::new(value.clone()))),
+            Expression::StringLiteral { value, .. } => match &mut self.string_interner {
+                Some(interner) => Ok(Object::String(interner.intern(value))),
+                None => Ok(Object::String(Rc::new(value.clone()))),
+            },
+            Expression::Symbol { value, .. } => Ok(self.symbol(value)),
             Expression::InterpolatedString { parts, .. } => self
                 .evaluate_interpolated_string(parts)
                 .map(|s| Object::String(Rc::new(s))),
             Expression::BoolLiteral { value, .. } => Ok(Object::Bool(*value)),
             Expression::NilLiteral { .. } => Ok(Object::Nil),
-            Expression::Identifier { name, position } => self
-                .environment
-                .get(name)
-                .ok_or_else(|| undefined_variable_error(name, *position)),
+            Expression::Identifier { name, position } => {
+                let value = self.lookup_identifier(name, *position)?;
+                match value {
+                    Object::NativeFunction(_) => self.auto_call(value, *position),
+                    _ => Ok(value),
+                }
+            }
             Expression::Lambda {
                 parameters,
                 body,
                 captured_vars,
+                destructured,
                 ..
             } => {
                 let mut captured = HashMap::new();
@@ -329,8 +806,9 @@ impl VirtualMachine {
                     }
                 }
                 // If captured_vars is None, don't capture anything (regular blocks for .each, etc.)
-                let block = BlockStatement::new(parameters.clone(), body.clone(), captured);
-                Ok(Object::Block(Rc::new(block)))
+                let block = BlockStatement::new(parameters.clone(), body.clone(), captured)
+                    .with_destructured(destructured.clone());
+                Ok(self.track_allocation(Object::Block(Rc::new(block))))
             }
             Expression::Grouped { expression, .. } => self.evaluate_expression(expression),
             Expression::UnaryOp {
@@ -348,7 +826,23 @@ impl VirtualMachine {
                 position,
             } => {
                 let left_value = self.evaluate_expression(left)?;
+                // `&&` and `||` only evaluate the right side when the left
+                // side does not already decide the result
+                match op {
+                    BinaryOp::And if !is_truthy(&left_value) => return Ok(left_value),
+                    BinaryOp::Or if is_truthy(&left_value) => return Ok(left_value),
+                    BinaryOp::And | BinaryOp::Or => return self.evaluate_expression(right),
+                    _ => {}
+                }
                 let right_value = self.evaluate_expression(right)?;
+                if let Some(feedback) = self.type_feedback.as_mut() {
+                    if let Some(result) =
+                        feedback.try_fast_path(expression, op, &left_value, &right_value)
+                    {
+                        return Ok(result);
+                    }
+                    feedback.record(expression, op, &left_value, &right_value);
+                }
                 self.evaluate_binary_operation(op, left_value, right_value, *position)
             }
             Expression::Array { elements, .. } => self.evaluate_array_literal(elements),
@@ -378,16 +872,31 @@ impl VirtualMachine {
             Expression::Call {
                 callee,
                 arguments,
+                trailing_block,
                 position,
-                ..
             } => {
-                let callable = self.evaluate_expression(callee)?;
-                let mut evaluated_args = Vec::with_capacity(arguments.len());
-                for argument in arguments {
-                    evaluated_args.push(self.evaluate_expression(argument)?);
+                let callable = match callee.as_ref() {
+                    Expression::Identifier { name, position } => {
+                        self.lookup_identifier(name, *position)?
+                    }
+                    other => self.evaluate_expression(other)?,
+                };
+                let mut evaluated_args = self.evaluate_arguments(arguments)?;
+                // A trailing block is passed as the last argument, as with method calls
+                if let Some(block_expr) = trailing_block {
+                    evaluated_args.push(self.evaluate_expression(block_expr)?);
                 }
                 self.invoke_callable(callable, evaluated_args, *position)
             }
+            Expression::RescueModifier {
+                expression,
+                fallback,
+                ..
+            } => self.evaluate_rescue_modifier(expression, fallback),
+            Expression::Begin { block, .. } => self.evaluate_begin(block),
+            Expression::ForwardedArgs { position } => {
+                Err(forwarded_arguments_outside_call_error(*position))
+            }
             Expression::SelfExpr { position } => self
                 .environment
                 .get("self")
@@ -399,40 +908,27 @@ impl VirtualMachine {
                         let instance = instance_rc.borrow();
                         Ok(instance.get_var(name).cloned().unwrap_or(Object::Nil))
                     }
-                    Some(_) => Err(MetorexError::runtime_error(
-                        format!("Cannot read instance variable @{} on non-instance", name),
-                        position_to_location(*position),
-                    )),
-                    None => Err(MetorexError::runtime_error(
-                        format!(
-                            "Instance variable @{} can only be used within a method",
-                            name
-                        ),
-                        position_to_location(*position),
+                    Some(_) => Err(instance_variable_on_non_instance_error(
+                        name, false, *position,
                     )),
+                    None => Err(instance_variable_outside_method_error(name, *position)),
                 }
             }
             Expression::ClassVariable { name, position } => {
                 // Class variables can be read within a method or class context
-                match self.environment.get("self") {
+                let receiver = self.environment.get("self");
+                match receiver.filter(|receiver| !self.is_main_object(receiver)) {
                     Some(Object::Instance(instance_rc)) => {
-                        let instance = instance_rc.borrow();
-                        Ok(instance.class.get_class_var(name).unwrap_or(Object::Nil))
-                    }
-                    Some(Object::Class(class)) => {
-                        Ok(class.get_class_var(name).unwrap_or(Object::Nil))
+                        let class = Rc::clone(&instance_rc.borrow().class);
+                        class.lookup_class_var(name).ok_or_else(|| {
+                            uninitialized_class_variable_error(name, class.name(), *position)
+                        })
                     }
-                    Some(_) => Err(MetorexError::runtime_error(
-                        format!("Cannot read class variable @@{} in this context", name),
-                        position_to_location(*position),
-                    )),
-                    None => Err(MetorexError::runtime_error(
-                        format!(
-                            "Class variable @@{} can only be used within a class or method",
-                            name
-                        ),
-                        position_to_location(*position),
-                    )),
+                    Some(Object::Class(class)) => class.lookup_class_var(name).ok_or_else(|| {
+                        uninitialized_class_variable_error(name, class.name(), *position)
+                    }),
+                    Some(_) => Err(class_variable_out_of_context_error(name, false, *position)),
+                    None => Err(class_variable_outside_class_error(name, *position)),
                 }
             }
             Expression::Super {
@@ -443,36 +939,28 @@ impl VirtualMachine {
                 let instance = match self.environment.get("self") {
                     Some(Object::Instance(instance_rc)) => instance_rc,
                     Some(_) => {
-                        return Err(MetorexError::runtime_error(
-                            "super can only be called from within an instance method".to_string(),
-                            position_to_location(*position),
+                        return Err(super_error(
+                            &messages::SUPER_OUTSIDE_INSTANCE_METHOD,
+                            &[],
+                            *position,
                         ));
                     }
                     None => {
-                        return Err(MetorexError::runtime_error(
-                            "super can only be called from within a method".to_string(),
-                            position_to_location(*position),
-                        ));
+                        return Err(super_error(&messages::SUPER_OUTSIDE_METHOD, &[], *position));
                     }
                 };
 
                 // Get the current method name from the call stack
                 // The call stack stores method names as "Class#method", so we need to extract both parts
                 let current_frame = self.get_current_method_name().ok_or_else(|| {
-                    MetorexError::runtime_error(
-                        "super called outside of a method context".to_string(),
-                        position_to_location(*position),
-                    )
+                    super_error(&messages::SUPER_WITHOUT_METHOD_FRAME, &[], *position)
                 })?;
 
                 // Extract the class name and method name (format: "Class#method")
                 let (class_name, method_name) = if let Some(pos) = current_frame.rfind('#') {
                     (&current_frame[..pos], &current_frame[pos + 1..])
                 } else {
-                    return Err(MetorexError::runtime_error(
-                        "super called in invalid context (no class information)".to_string(),
-                        position_to_location(*position),
-                    ));
+                    return Err(super_error(&messages::SUPER_WITHOUT_CLASS, &[], *position));
                 };
 
                 // Get the instance's class to walk the inheritance chain
@@ -494,40 +982,33 @@ impl VirtualMachine {
                 };
 
                 let defining_class = defining_class.ok_or_else(|| {
-                    MetorexError::runtime_error(
-                        format!(
-                            "Could not find defining class '{}' in inheritance chain",
-                            class_name
-                        ),
-                        position_to_location(*position),
+                    super_error(
+                        &messages::SUPER_DEFINING_CLASS_NOT_FOUND,
+                        &[("class", &class_name)],
+                        *position,
                     )
                 })?;
 
                 // Get the parent class of the defining class
                 let parent_class = defining_class.superclass().ok_or_else(|| {
-                    MetorexError::runtime_error(
-                        format!("Class {} has no superclass", class_name),
-                        position_to_location(*position),
+                    super_error(
+                        &messages::SUPER_WITHOUT_SUPERCLASS,
+                        &[("class", &class_name)],
+                        *position,
                     )
                 })?;
 
                 // Look up the method in the parent class
                 let method = parent_class.find_method(method_name).ok_or_else(|| {
-                    MetorexError::runtime_error(
-                        format!(
-                            "Superclass {} does not define method '{}'",
-                            parent_class.name(),
-                            method_name
-                        ),
-                        position_to_location(*position),
+                    super_error(
+                        &messages::SUPER_METHOD_NOT_FOUND,
+                        &[("class", &parent_class.name()), ("method", &method_name)],
+                        *position,
                     )
                 })?;
 
                 // Evaluate the arguments
-                let mut evaluated_args = Vec::with_capacity(arguments.len());
-                for arg in arguments {
-                    evaluated_args.push(self.evaluate_expression(arg)?);
-                }
+                let evaluated_args = self.evaluate_arguments(arguments)?;
 
                 // Drop the borrow before invoking the method
                 drop(instance_borrowed);
@@ -0,0 +1,179 @@
+//! Checkpoints of what a program has defined, and what it defined since.
+//!
+//! [`VirtualMachine::definitions_checkpoint`] records the bindings of the
+//! global scope and the methods of every class bound there.
+//! [`VirtualMachine::methods_added_since`] lists what changed after it: the
+//! classes, methods and functions defined and the variables assigned. The
+//! REPL prints the definitions after each input, and a harness running
+//! several test files in one VM can check that a file leaves nothing behind
+//! for the next one.
+//!
+//! A binding counts as new when its name was unbound at the checkpoint or
+//! now holds a different value, so redefining a class or method lists it
+//! again. Removed bindings are not reported.
+
+use super::VirtualMachine;
+use crate::class::Class;
+use crate::object::{Method, Object};
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+/// The methods defined directly on a class, by name
+type MethodTable = HashMap<String, Rc<Method>>;
+
+/// The global bindings and class methods at one moment, taken by
+/// [`VirtualMachine::definitions_checkpoint`].
+#[derive(Debug, Clone)]
+pub struct DefinitionCheckpoint {
+    bindings: HashMap<String, Object>,
+    classes: Vec<(Rc<Class>, MethodTable)>,
+}
+
+/// What was defined or assigned after a checkpoint, each list sorted by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DefinitionDiff {
+    /// Classes and modules bound to a new or different name, such as `Point`
+    pub classes: Vec<String>,
+    /// Methods added to or redefined on a class, or defined in a new
+    /// module, written `Point#norm`
+    pub methods: Vec<String>,
+    /// Top-level functions defined with `def`
+    pub functions: Vec<String>,
+    /// Other global variables assigned
+    pub variables: Vec<String>,
+}
+
+impl DefinitionDiff {
+    /// Whether nothing was defined or assigned.
+    pub fn is_empty(&self) -> bool {
+        !self.defines_anything() && self.variables.is_empty()
+    }
+
+    /// Whether any class, method or function was defined.
+    pub fn defines_anything(&self) -> bool {
+        !(self.classes.is_empty() && self.methods.is_empty() && self.functions.is_empty())
+    }
+
+    /// The classes, methods and functions, each class followed by its
+    /// methods: `["Point", "Point#norm", "greet"]`.
+    pub fn definitions(&self) -> Vec<String> {
+        let mut names: Vec<&String> = self.classes.iter().chain(&self.methods).collect();
+        names.sort_by_key(|name| (owner(name), name.contains('#'), name.as_str()));
+        names.into_iter().chain(&self.functions).cloned().collect()
+    }
+}
+
+/// The class a class or method name belongs to.
+fn owner(name: &str) -> &str {
+    name.split_once('#').map_or(name, |(class, _)| class)
+}
+
+impl fmt::Display for DefinitionDiff {
+    /// `defined: Point, Point#norm; assigned: origin`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if self.defines_anything() {
+            parts.push(format!("defined: {}", self.definitions().join(", ")));
+        }
+        if !self.variables.is_empty() {
+            parts.push(format!("assigned: {}", self.variables.join(", ")));
+        }
+        write!(f, "{}", parts.join("; "))
+    }
+}
+
+impl VirtualMachine {
+    /// Record the global bindings and the methods of every class bound in
+    /// the global scope.
+    pub fn definitions_checkpoint(&self) -> DefinitionCheckpoint {
+        let bindings = self.global_bindings();
+        let classes = bindings
+            .values()
+            .filter_map(|value| match value {
+                Object::Class(class) => Some((Rc::clone(class), own_methods(class))),
+                _ => None,
+            })
+            .collect();
+        DefinitionCheckpoint { bindings, classes }
+    }
+
+    /// What was defined or assigned in the global scope since `checkpoint`.
+    ///
+    /// Top-level functions are also private methods of Object; they are
+    /// listed once, as functions.
+    pub fn methods_added_since(&self, checkpoint: &DefinitionCheckpoint) -> DefinitionDiff {
+        let mut diff = DefinitionDiff::default();
+        for (name, value) in self.global_bindings() {
+            let unchanged = checkpoint
+                .bindings
+                .get(&name)
+                .is_some_and(|before| same_binding(before, &value));
+
+            match &value {
+                Object::Class(class) => {
+                    let before = checkpoint
+                        .classes
+                        .iter()
+                        .find(|(known, _)| Rc::ptr_eq(known, class))
+                        .map(|(_, methods)| methods);
+                    if !unchanged {
+                        diff.classes.push(name.clone());
+                    }
+                    let is_object = Rc::ptr_eq(class, &self.builtins().object_class);
+                    for (method_name, method) in own_methods(class) {
+                        let known = before
+                            .and_then(|methods| methods.get(&method_name))
+                            .is_some_and(|known| Rc::ptr_eq(known, &method));
+                        // Top-level functions are listed as functions instead
+                        let function = is_object && class.is_private_method(&method_name);
+                        if !known && !function {
+                            diff.methods.push(format!("{}#{}", name, method_name));
+                        }
+                    }
+                }
+                _ if unchanged => {}
+                Object::Module(module) => {
+                    diff.classes.push(name.clone());
+                    for method_name in module.method_names() {
+                        diff.methods.push(format!("{}#{}", name, method_name));
+                    }
+                }
+                Object::Method(function) if function.name == name => diff.functions.push(name),
+                _ => diff.variables.push(name),
+            }
+        }
+        diff.classes.sort();
+        diff.methods.sort();
+        diff.functions.sort();
+        diff.variables.sort();
+        diff
+    }
+
+    /// Every binding in the global scope.
+    fn global_bindings(&self) -> HashMap<String, Object> {
+        self.environment()
+            .global_scope()
+            .borrow()
+            .collect_all_vars()
+    }
+}
+
+/// The methods defined directly on `class`.
+fn own_methods(class: &Class) -> MethodTable {
+    class
+        .method_names()
+        .into_iter()
+        .filter_map(|name| class.find_method(&name).map(|method| (name, method)))
+        .collect()
+}
+
+/// Whether a binding still holds the value it had, comparing classes and
+/// methods by identity.
+fn same_binding(before: &Object, now: &Object) -> bool {
+    match (before, now) {
+        (Object::Class(before), Object::Class(now)) => Rc::ptr_eq(before, now),
+        (Object::Method(before), Object::Method(now)) => Rc::ptr_eq(before, now),
+        _ => before == now,
+    }
+}
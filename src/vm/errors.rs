@@ -3,11 +3,49 @@
 //! This module provides helper functions for constructing various runtime, type,
 //! and internal errors that can occur during VM execution.
 
-use super::utils::position_to_location;
-use crate::ast::{BinaryOp, Expression, Statement, UnaryOp};
-use crate::error::MetorexError;
+use super::type_checks::describe_type;
+use super::utils::{format_exception, position_to_location};
+use crate::ast::{BinaryOp, Expression, Statement, TypeAnnotation, UnaryOp};
+use crate::error::{MetorexError, SourceLocation};
 use crate::lexer::Position;
-use crate::object::Object;
+use crate::messages::{self, Message};
+use crate::object::{Exception, Object};
+use indexmap::IndexMap;
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::path::Path;
+use std::rc::Rc;
+
+/// A runtime error with the catalog text of `message`, tagged with its code.
+fn coded_runtime_error(
+    message: &'static Message,
+    args: &[(&str, &dyn Display)],
+    position: Position,
+) -> MetorexError {
+    MetorexError::runtime_error(message.format(args), position_to_location(position))
+        .with_catalog_message(message)
+}
+
+/// A type error with the catalog text of `message`, tagged with its code.
+fn coded_type_error(
+    message: &'static Message,
+    args: &[(&str, &dyn Display)],
+    position: Position,
+) -> MetorexError {
+    MetorexError::type_error(message.format(args), position_to_location(position))
+        .with_catalog_message(message)
+}
+
+/// Raise an exception with the catalog text of `message`, tagged with its code.
+fn coded_exception_error(
+    exception_type: &str,
+    message: &'static Message,
+    args: &[(&str, &dyn Display)],
+    position: Position,
+) -> MetorexError {
+    raised_exception_error(exception_type, message.format(args), position)
+        .with_catalog_message(message)
+}
 
 // ============================================================================
 // Control Flow Errors
@@ -15,40 +53,161 @@ use crate::object::Object;
 
 /// Produce a runtime error for unsupported control-flow usage (e.g., break outside loop).
 pub(super) fn loop_control_error(keyword: &str, position: Position) -> MetorexError {
-    MetorexError::runtime_error(
-        format!("{keyword} cannot be used outside of a loop"),
-        position_to_location(position),
+    coded_runtime_error(&messages::LOOP_CONTROL, &[("keyword", &keyword)], position)
+}
+
+/// Produce a runtime error for `return`, `break` or `continue` inside a
+/// `begin` block whose value is being used.
+pub(super) fn begin_expression_jump_error(keyword: &str, position: Position) -> MetorexError {
+    coded_runtime_error(
+        &messages::BEGIN_EXPRESSION_JUMP,
+        &[("keyword", &keyword)],
+        position,
+    )
+}
+
+/// Produce a runtime error when nested calls exceed the configured stack depth.
+pub(super) fn stack_too_deep_error(limit: usize, position: Position) -> MetorexError {
+    coded_runtime_error(&messages::STACK_TOO_DEEP, &[("limit", &limit)], position)
+}
+
+/// Produce a runtime error for `retry` used outside of a rescue clause.
+pub(super) fn retry_outside_rescue_error(position: Position) -> MetorexError {
+    coded_runtime_error(&messages::RETRY_OUTSIDE_RESCUE, &[], position)
+}
+
+/// Raise an ArgumentError when `Timeout.timeout` is given a limit that is not
+/// a non-negative number of seconds.
+pub(super) fn invalid_timeout_error(
+    method: &str,
+    found: &dyn Display,
+    position: Position,
+) -> MetorexError {
+    coded_exception_error(
+        "ArgumentError",
+        &messages::INVALID_TIMEOUT,
+        &[("method", &method), ("found", found)],
+        position,
     )
 }
 
+/// Raise an exception object from native code so scripts can rescue it.
+pub(super) fn raised_exception_error(
+    exception_type: &str,
+    message: String,
+    position: Position,
+) -> MetorexError {
+    raised_exception_error_with_details(exception_type, message, IndexMap::new(), position)
+}
+
+/// Raise an exception from native code carrying structured `details`, which
+/// scripts read as methods on the rescued exception.
+pub(super) fn raised_exception_error_with_details(
+    exception_type: &str,
+    message: String,
+    details: IndexMap<String, Object>,
+    position: Position,
+) -> MetorexError {
+    let exception = Object::Exception(Rc::new(RefCell::new(
+        Exception::new(exception_type.to_string(), message).with_details(details),
+    )));
+    MetorexError::UncaughtException {
+        message: format_exception(&exception),
+        exception,
+        location: position_to_location(position),
+        catalog_message: None,
+    }
+}
+
 // ============================================================================
 // Variable and Assignment Errors
 // ============================================================================
 
 /// Produce a runtime error when attempting to assign to an invalid target.
 pub(super) fn invalid_assignment_target_error(target: &Expression) -> MetorexError {
-    MetorexError::runtime_error(
-        "Invalid assignment target",
-        position_to_location(target.position()),
-    )
+    coded_runtime_error(&messages::INVALID_ASSIGNMENT_TARGET, &[], target.position())
 }
 
 /// Produce a runtime error for referencing an undefined variable.
 pub(super) fn undefined_variable_error(name: &str, position: Position) -> MetorexError {
-    MetorexError::runtime_error(
-        format!("Undefined variable '{name}'"),
-        position_to_location(position),
+    coded_runtime_error(&messages::UNDEFINED_VARIABLE, &[("name", &name)], position)
+}
+
+/// Raise a NameError for reading a class variable that was never assigned.
+pub(super) fn uninitialized_class_variable_error(
+    name: &str,
+    class_name: &str,
+    position: Position,
+) -> MetorexError {
+    coded_exception_error(
+        "NameError",
+        &messages::UNINITIALIZED_CLASS_VARIABLE,
+        &[("name", &name), ("class", &class_name)],
+        position,
     )
 }
 
 /// Produce a runtime error when accessing `self` outside of a method context.
 pub(super) fn undefined_self_error(position: Position) -> MetorexError {
-    MetorexError::runtime_error(
-        "Undefined self in current context",
-        position_to_location(position),
+    coded_runtime_error(&messages::UNDEFINED_SELF, &[], position)
+}
+
+/// Produce a runtime error for reading or setting an instance variable when
+/// `self` is not an instance.
+pub(super) fn instance_variable_on_non_instance_error(
+    name: &str,
+    assigning: bool,
+    position: Position,
+) -> MetorexError {
+    let message = if assigning {
+        &messages::SET_INSTANCE_VARIABLE_ON_NON_INSTANCE
+    } else {
+        &messages::READ_INSTANCE_VARIABLE_ON_NON_INSTANCE
+    };
+    coded_runtime_error(message, &[("name", &name)], position)
+}
+
+/// Produce a runtime error for an instance variable used outside of a method.
+pub(super) fn instance_variable_outside_method_error(
+    name: &str,
+    position: Position,
+) -> MetorexError {
+    coded_runtime_error(
+        &messages::INSTANCE_VARIABLE_OUTSIDE_METHOD,
+        &[("name", &name)],
+        position,
     )
 }
 
+/// Produce a runtime error for reading or setting a class variable when
+/// `self` is neither a class nor an instance.
+pub(super) fn class_variable_out_of_context_error(
+    name: &str,
+    assigning: bool,
+    position: Position,
+) -> MetorexError {
+    let message = if assigning {
+        &messages::SET_CLASS_VARIABLE_OUT_OF_CONTEXT
+    } else {
+        &messages::READ_CLASS_VARIABLE_OUT_OF_CONTEXT
+    };
+    coded_runtime_error(message, &[("name", &name)], position)
+}
+
+/// Produce a runtime error for a class variable used outside of a class or method.
+pub(super) fn class_variable_outside_class_error(name: &str, position: Position) -> MetorexError {
+    coded_runtime_error(
+        &messages::CLASS_VARIABLE_OUTSIDE_CLASS,
+        &[("name", &name)],
+        position,
+    )
+}
+
+/// Produce a runtime error for assigning to a method call that has arguments.
+pub(super) fn method_call_assignment_error(position: Position) -> MetorexError {
+    coded_runtime_error(&messages::METHOD_CALL_ASSIGNMENT, &[], position)
+}
+
 // ============================================================================
 // Method and Callable Errors
 // ============================================================================
@@ -59,13 +218,23 @@ pub(super) fn undefined_method_error(
     receiver: &Object,
     position: Position,
 ) -> MetorexError {
-    MetorexError::runtime_error(
-        format!(
-            "Undefined method '{}' for type '{}'",
-            method,
-            receiver.type_name()
-        ),
-        position_to_location(position),
+    coded_runtime_error(
+        &messages::UNDEFINED_METHOD,
+        &[("method", &method), ("type", &receiver.type_name())],
+        position,
+    )
+}
+
+/// Produce a runtime error when a private method is called with an explicit receiver.
+pub(super) fn private_method_error(
+    method: &str,
+    receiver: &Object,
+    position: Position,
+) -> MetorexError {
+    coded_runtime_error(
+        &messages::PRIVATE_METHOD,
+        &[("method", &method), ("type", &receiver.type_name())],
+        position,
     )
 }
 
@@ -76,15 +245,100 @@ pub(super) fn method_argument_error(
     found: usize,
     position: Position,
 ) -> MetorexError {
-    MetorexError::runtime_error(
-        format!(
-            "Method '{}' expected {} argument(s) but received {}",
-            method, expected, found
-        ),
-        position_to_location(position),
+    coded_runtime_error(
+        &messages::METHOD_ARGUMENT_COUNT,
+        &[
+            ("method", &method),
+            ("expected", &expected),
+            ("found", &found),
+        ],
+        position,
+    )
+}
+
+/// Raise an ArgumentError when a builtin receives a number of arguments its
+/// catalog signature does not allow.
+pub(super) fn builtin_arity_error(
+    name: &str,
+    given: usize,
+    min: usize,
+    max: Option<usize>,
+    position: Position,
+) -> MetorexError {
+    let expected = match max {
+        Some(max) if max == min => min.to_string(),
+        Some(max) => format!("{}..{}", min, max),
+        None => format!("{}+", min),
+    };
+    coded_exception_error(
+        "ArgumentError",
+        &messages::BUILTIN_ARGUMENT_COUNT,
+        &[("name", &name), ("given", &given), ("expected", &expected)],
+        position,
+    )
+}
+
+/// Raise a TypeError naming the builtin parameter an argument does not
+/// satisfy, e.g. "center: padding must be a String (got Int)".
+pub(super) fn builtin_parameter_type_error(
+    name: &str,
+    parameter: &str,
+    expected: &TypeAnnotation,
+    found: &Object,
+    position: Position,
+) -> MetorexError {
+    let article = if expected.name.starts_with(['A', 'E', 'I', 'O', 'U']) {
+        "an"
+    } else {
+        "a"
+    };
+    let nil = if expected.nilable { " or nil" } else { "" };
+    let expected = format!("{} {}{}", article, expected.name, nil);
+    coded_exception_error(
+        "TypeError",
+        &messages::BUILTIN_PARAMETER_TYPE,
+        &[
+            ("name", &name),
+            ("parameter", &parameter),
+            ("expected", &expected),
+            ("found", &describe_type(found)),
+        ],
+        position,
     )
 }
 
+/// Produce a runtime error when a scoped resource has no way to be released.
+pub(super) fn unreleasable_resource_error(resource: &Object, position: Position) -> MetorexError {
+    coded_runtime_error(
+        &messages::UNRELEASABLE_RESOURCE,
+        &[("type", &resource.type_name())],
+        position,
+    )
+}
+
+/// Produce a runtime error when a method with a `...` parameter receives too few arguments.
+pub(super) fn method_minimum_argument_error(
+    method: &str,
+    minimum: usize,
+    found: usize,
+    position: Position,
+) -> MetorexError {
+    coded_runtime_error(
+        &messages::METHOD_MINIMUM_ARGUMENT_COUNT,
+        &[
+            ("method", &method),
+            ("minimum", &minimum),
+            ("found", &found),
+        ],
+        position,
+    )
+}
+
+/// Produce a runtime error when `...` is used without a `...` parameter in scope.
+pub(super) fn no_forwarded_arguments_error(position: Position) -> MetorexError {
+    coded_runtime_error(&messages::NO_FORWARDED_ARGUMENTS, &[], position)
+}
+
 /// Produce a type error for invalid method argument type.
 pub(super) fn method_argument_type_error(
     method: &str,
@@ -92,22 +346,23 @@ pub(super) fn method_argument_type_error(
     found: &Object,
     position: Position,
 ) -> MetorexError {
-    MetorexError::type_error(
-        format!(
-            "Method '{}' expected argument of type '{}' but found '{}'",
-            method,
-            expected,
-            found.type_name()
-        ),
-        position_to_location(position),
+    coded_type_error(
+        &messages::METHOD_ARGUMENT_TYPE,
+        &[
+            ("method", &method),
+            ("expected", &expected),
+            ("found", &found.type_name()),
+        ],
+        position,
     )
 }
 
 /// Produce a runtime error when attempting to call a non-callable object.
 pub(super) fn not_callable_error(value: &Object, position: Position) -> MetorexError {
-    MetorexError::runtime_error(
-        format!("Object of type '{}' is not callable", value.type_name()),
-        position_to_location(position),
+    coded_runtime_error(
+        &messages::NOT_CALLABLE,
+        &[("type", &value.type_name())],
+        position,
     )
 }
 
@@ -118,28 +373,68 @@ pub(super) fn callable_argument_error(
     found: usize,
     position: Position,
 ) -> MetorexError {
-    MetorexError::runtime_error(
-        format!(
-            "Callable '{}' expected {} argument(s) but received {}",
-            callable_name, expected, found
-        ),
-        position_to_location(position),
+    coded_runtime_error(
+        &messages::CALLABLE_ARGUMENT_COUNT,
+        &[
+            ("callable", &callable_name),
+            ("expected", &expected),
+            ("found", &found),
+        ],
+        position,
     )
 }
 
+/// Produce a runtime error for `...` used anywhere but as a call argument.
+pub(super) fn forwarded_arguments_outside_call_error(position: Position) -> MetorexError {
+    coded_runtime_error(&messages::FORWARDED_ARGUMENTS_OUTSIDE_CALL, &[], position)
+}
+
+/// Produce a runtime error when an instance has no `name=` method to assign through.
+pub(super) fn undefined_setter_error(setter: &str, position: Position) -> MetorexError {
+    coded_runtime_error(
+        &messages::UNDEFINED_SETTER,
+        &[("method", &setter)],
+        position,
+    )
+}
+
+/// Produce a runtime error when assigning through a setter on a value that is
+/// not an instance.
+pub(super) fn setter_on_non_instance_error(
+    setter: &str,
+    receiver: &Object,
+    position: Position,
+) -> MetorexError {
+    coded_runtime_error(
+        &messages::SETTER_ON_NON_INSTANCE,
+        &[("method", &setter), ("type", &receiver.type_name())],
+        position,
+    )
+}
+
+/// Produce a runtime error for a `super` call that has no method to continue:
+/// one of the `SUPER_*` messages.
+pub(super) fn super_error(
+    message: &'static Message,
+    args: &[(&str, &dyn Display)],
+    position: Position,
+) -> MetorexError {
+    coded_runtime_error(message, args, position)
+}
+
 // ============================================================================
 // Operator Errors
 // ============================================================================
 
 /// Produce a type error for unary operations.
 pub(super) fn unary_type_error(op: &UnaryOp, value: &Object, position: Position) -> MetorexError {
-    MetorexError::type_error(
-        format!(
-            "Cannot apply unary operator '{:?}' to type '{}'",
-            op,
-            value.type_name()
-        ),
-        position_to_location(position),
+    coded_type_error(
+        &messages::UNARY_OPERATOR_TYPE,
+        &[
+            ("operator", &format!("{:?}", op)),
+            ("type", &value.type_name()),
+        ],
+        position,
     )
 }
 
@@ -150,20 +445,42 @@ pub(super) fn binary_type_error(
     right: &Object,
     position: Position,
 ) -> MetorexError {
-    MetorexError::type_error(
-        format!(
-            "Cannot apply operator '{:?}' to types '{}' and '{}'",
-            op,
-            left.type_name(),
-            right.type_name()
-        ),
-        position_to_location(position),
+    coded_type_error(
+        &messages::BINARY_OPERATOR_TYPE,
+        &[
+            ("operator", &format!("{:?}", op)),
+            ("left", &left.type_name()),
+            ("right", &right.type_name()),
+        ],
+        position,
+    )
+}
+
+/// Raise a TypeError when sorting meets two values it cannot order.
+pub(super) fn comparison_failed_error(
+    left: &Object,
+    right: &Object,
+    position: Position,
+) -> MetorexError {
+    coded_exception_error(
+        "TypeError",
+        &messages::COMPARISON_FAILED,
+        &[
+            ("left", &describe_type(left)),
+            ("right", &describe_type(right)),
+        ],
+        position,
     )
 }
 
 /// Produce a divide-by-zero runtime error.
 pub(super) fn divide_by_zero_error(position: Position) -> MetorexError {
-    MetorexError::runtime_error("Division by zero", position_to_location(position))
+    coded_runtime_error(&messages::DIVISION_BY_ZERO, &[], position)
+}
+
+/// Produce a runtime error for Decimal arithmetic whose result has too many digits.
+pub(super) fn decimal_overflow_error(position: Position) -> MetorexError {
+    coded_runtime_error(&messages::DECIMAL_OVERFLOW, &[], position)
 }
 
 // ============================================================================
@@ -176,21 +493,151 @@ pub(super) fn index_out_of_bounds_error(
     length: usize,
     position: Position,
 ) -> MetorexError {
-    MetorexError::runtime_error(
-        format!(
-            "Index {} is out of bounds for array of length {}",
-            index, length
-        ),
-        position_to_location(position),
+    coded_runtime_error(
+        &messages::INDEX_OUT_OF_BOUNDS,
+        &[("index", &index), ("length", &length)],
+        position,
+    )
+}
+
+/// Produce a runtime error when an array is indexed by something other than an Int.
+pub(super) fn array_index_not_integer_error(index: &Object, position: Position) -> MetorexError {
+    coded_runtime_error(
+        &messages::ARRAY_INDEX_NOT_INTEGER,
+        &[("type", &index.type_name())],
+        position,
+    )
+}
+
+/// Produce a runtime error when a value cannot be used as a hash key.
+pub(super) fn invalid_hash_key_error(key: &Object, position: Position) -> MetorexError {
+    coded_runtime_error(
+        &messages::INVALID_HASH_KEY,
+        &[("type", &key.type_name())],
+        position,
+    )
+}
+
+/// Produce a type error for `value[index] = ...` on a value that does not support it.
+pub(super) fn index_assignment_unsupported_error(
+    value: &Object,
+    position: Position,
+) -> MetorexError {
+    coded_type_error(
+        &messages::INDEX_ASSIGNMENT_UNSUPPORTED,
+        &[("type", &value.type_name())],
+        position,
+    )
+}
+
+/// Produce a runtime error when assigning to an index so far past the end of
+/// an array that growing it would mean filling more than `limit` slots.
+pub(super) fn index_too_far_past_end_error(
+    index: i64,
+    length: usize,
+    limit: usize,
+    position: Position,
+) -> MetorexError {
+    coded_runtime_error(
+        &messages::INDEX_TOO_FAR_PAST_END,
+        &[("index", &index), ("limit", &limit), ("length", &length)],
+        position,
     )
 }
 
 /// Produce a runtime error when a dictionary key is missing.
 pub(super) fn undefined_dictionary_key_error(key: &str, position: Position) -> MetorexError {
+    coded_runtime_error(
+        &messages::UNDEFINED_DICTIONARY_KEY,
+        &[("key", &key)],
+        position,
+    )
+}
+
+/// Raise a ConcurrentModificationError for a collection changed while it
+/// is being iterated in a way the iteration cannot follow.
+pub(super) fn concurrent_modification_error(message: String, position: Position) -> MetorexError {
+    raised_exception_error("ConcurrentModificationError", message, position)
+}
+
+/// Raise a ConcurrentModificationError when a collection is written while a
+/// native method still holds it borrowed.
+pub(super) fn collection_in_use_error(collection: &Object, position: Position) -> MetorexError {
+    coded_exception_error(
+        "ConcurrentModificationError",
+        &messages::COLLECTION_IN_USE,
+        &[("type", &collection.type_name())],
+        position,
+    )
+}
+
+/// Produce a runtime error when no case of a match or case expression matches.
+pub(super) fn no_pattern_matched_error(value: &Object, position: Position) -> MetorexError {
+    coded_runtime_error(&messages::NO_PATTERN_MATCHED, &[("value", value)], position)
+}
+
+// ============================================================================
+// Class Definition Errors
+// ============================================================================
+
+/// Produce a runtime error when a class names a superclass that is not a class.
+pub(super) fn superclass_not_a_class_error(name: &str, position: Position) -> MetorexError {
+    coded_runtime_error(
+        &messages::SUPERCLASS_NOT_A_CLASS,
+        &[("name", &name)],
+        position,
+    )
+}
+
+/// Produce a runtime error when a class names a superclass that is not defined.
+pub(super) fn undefined_superclass_error(name: &str, position: Position) -> MetorexError {
+    coded_runtime_error(
+        &messages::UNDEFINED_SUPERCLASS,
+        &[("name", &name)],
+        position,
+    )
+}
+
+/// Produce a runtime error when a class inherits from a sealed class that
+/// does not list it as a variant.
+pub(super) fn sealed_superclass_error(
+    superclass: &str,
+    name: &str,
+    variants: &[String],
+    position: Position,
+) -> MetorexError {
+    coded_runtime_error(
+        &messages::SEALED_SUPERCLASS,
+        &[
+            ("superclass", &superclass),
+            ("name", &name),
+            ("variants", &variants.join(", ")),
+        ],
+        position,
+    )
+}
+
+/// Produce a runtime error for `attr_reader` and friends outside a class or module body.
+pub(super) fn attribute_outside_class_error(position: Position) -> MetorexError {
+    coded_runtime_error(&messages::ATTRIBUTE_OUTSIDE_CLASS, &[], position)
+}
+
+// ============================================================================
+// File Errors
+// ============================================================================
+
+/// Produce a runtime error for a file that could not be found, read, parsed
+/// or run: one of the `FILE_*` messages.
+pub(super) fn file_error(
+    message: &'static Message,
+    path: &Path,
+    error: &dyn Display,
+) -> MetorexError {
     MetorexError::runtime_error(
-        format!("Key '{}' not found in dictionary", key),
-        position_to_location(position),
+        message.format(&[("path", &path.display()), ("error", error)]),
+        SourceLocation::new(0, 0, 0),
     )
+    .with_catalog_message(message)
 }
 
 // ============================================================================
@@ -0,0 +1,97 @@
+//! Running Metorex source given as a String.
+//!
+//! `eval(source)` lexes, parses and runs `source` in the scope it is called
+//! from, so it reads and assigns the caller's variables, and returns the
+//! value of the last statement. `binding.eval(source)` runs it with the
+//! variables of a Binding instead, the way a block runs with the variables it
+//! captured: assigning one of them updates it wherever it is shared, while
+//! new variables last only as long as the call.
+//!
+//! Source that does not parse raises a rescuable SyntaxError.
+
+use super::VirtualMachine;
+use super::errors::{method_argument_error, method_argument_type_error, raised_exception_error};
+use super::method_invocation::body_result;
+use crate::ast::Statement;
+use crate::error::MetorexError;
+use crate::lexer::{Lexer, Position};
+use crate::object::{Binding, Object};
+use crate::parser::Parser;
+
+impl VirtualMachine {
+    /// `eval(source)` - run source in the caller's scope.
+    pub(super) fn eval_source(
+        &mut self,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        let source = eval_argument("eval", arguments, position)?;
+        let statements = parse_eval_source(source, position)?;
+        self.run_eval(source, &statements)
+    }
+
+    /// `Binding#eval(source)` - run source with the binding's variables.
+    pub(crate) fn eval_in_binding(
+        &mut self,
+        binding: &Binding,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        let source = eval_argument("eval", arguments, position)?;
+        let statements = parse_eval_source(source, position)?;
+
+        self.environment_mut().push_scope();
+        for (name, value_ref) in &binding.variables {
+            self.environment_mut()
+                .define_shared(name.clone(), value_ref.clone());
+        }
+        let result = self.run_eval(source, &statements);
+        self.environment_mut().pop_scope();
+        result
+    }
+
+    /// Run parsed eval source, with methods it defines remembering their
+    /// text rather than the surrounding file's.
+    fn run_eval(&mut self, source: &str, statements: &[Statement]) -> Result<Object, MetorexError> {
+        let previous_source = self.current_source().cloned();
+        self.set_current_source(Some(source));
+        let result = self
+            .execute_statements_for_value(statements)
+            .and_then(|(flow, value)| body_result(flow, value));
+        self.set_current_source(previous_source.as_deref());
+        result
+    }
+}
+
+/// The source string `eval` was given as its only argument
+fn eval_argument<'a>(
+    method_name: &str,
+    arguments: &'a [Object],
+    position: Position,
+) -> Result<&'a str, MetorexError> {
+    match arguments {
+        [Object::String(source)] => Ok(source.as_str()),
+        [other] => Err(method_argument_type_error(
+            method_name,
+            "String",
+            other,
+            position,
+        )),
+        _ => Err(method_argument_error(
+            method_name,
+            1,
+            arguments.len(),
+            position,
+        )),
+    }
+}
+
+/// Parse eval source, raising the first parse error as a SyntaxError
+fn parse_eval_source(source: &str, position: Position) -> Result<Vec<Statement>, MetorexError> {
+    Parser::new(Lexer::new(source).tokenize())
+        .parse()
+        .map_err(|errors| {
+            let message = errors.first().map_or_else(String::new, |e| e.to_string());
+            raised_exception_error("SyntaxError", message, position)
+        })
+}
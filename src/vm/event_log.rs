@@ -0,0 +1,333 @@
+//! Recording a run for bug reports, and replaying it.
+//!
+//! `metorex --record trace.mxr script.mx` logs what the program did: the
+//! position of every statement it executed, every exception a script raised,
+//! each line it read from input or wrote to output, and how it finished. The
+//! log also carries the script's source and the seed `rand` used, so
+//! `metorex replay trace.mxr` runs the same program again, feeding it the
+//! same input, and points at the first event where the two runs differ.
+//!
+//! A log is a JSON Lines file: a header object describing the program,
+//! followed by one object per event. Files the script requires are read from
+//! disk again when replaying.
+
+use super::random::Random;
+use super::trace::{HookId, TraceEvent, TraceEventKind, VmHooks};
+use super::utils::format_exception;
+use super::{VirtualMachine, VmOptions};
+use crate::error::{MetorexError, SourceLocation};
+use crate::file_loader::parse_file;
+use crate::object::Object;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fmt;
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Value of the header's `format` field.
+const LOG_FORMAT: &str = "metorex-event-log";
+
+/// Version of the log layout, bumped when events change shape.
+const LOG_VERSION: u32 = 1;
+
+/// Something a recorded program did.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum LoggedEvent {
+    /// A statement started at this position.
+    Line { line: usize, column: usize },
+    /// A script `raise` produced this exception.
+    Raise {
+        line: usize,
+        column: usize,
+        exception: String,
+    },
+    /// A line was read from input, with its newline; `None` at end of input.
+    Input { text: Option<String> },
+    /// Text was written to output.
+    Output { text: String },
+    /// The program finished, with the error it stopped on, if any.
+    Finish { error: Option<String> },
+}
+
+impl fmt::Display for LoggedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoggedEvent::Line { line, column } => write!(f, "line {}:{}", line, column),
+            LoggedEvent::Raise {
+                line,
+                column,
+                exception,
+            } => write!(f, "raise of {} at {}:{}", exception, line, column),
+            LoggedEvent::Input { text: Some(text) } => write!(f, "input {:?}", text),
+            LoggedEvent::Input { text: None } => write!(f, "end of input"),
+            LoggedEvent::Output { text } => write!(f, "output {:?}", text),
+            LoggedEvent::Finish { error: None } => write!(f, "successful finish"),
+            LoggedEvent::Finish { error: Some(error) } => write!(f, "finish with {:?}", error),
+        }
+    }
+}
+
+/// A recorded run: the program, the seed it used and what it did.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventLog {
+    /// The script that was run.
+    pub path: PathBuf,
+    /// The script's source when it was recorded.
+    pub source: String,
+    /// The seed `rand` used.
+    pub seed: u64,
+    /// Everything the program did, in order.
+    pub events: Vec<LoggedEvent>,
+}
+
+/// First line of a log file.
+#[derive(Serialize, Deserialize)]
+struct LogHeader {
+    format: String,
+    version: u32,
+    path: PathBuf,
+    seed: u64,
+    source: String,
+}
+
+/// The first event at which two runs differ. `None` on either side means
+/// that run had already ended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    /// Index of the event in both logs.
+    pub index: usize,
+    /// What the recorded run did.
+    pub expected: Option<LoggedEvent>,
+    /// What the replay did instead.
+    pub found: Option<LoggedEvent>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let describe = |event: &Option<LoggedEvent>| match event {
+            Some(event) => event.to_string(),
+            None => "nothing more".to_string(),
+        };
+        write!(
+            f,
+            "event {}: recorded {}, replay gave {}",
+            self.index + 1,
+            describe(&self.expected),
+            describe(&self.found)
+        )
+    }
+}
+
+/// The outcome of running a recorded program again.
+#[derive(Debug)]
+pub struct Replay {
+    /// What the replay did.
+    pub log: EventLog,
+    /// How the replayed program finished.
+    pub result: Result<Option<Object>, MetorexError>,
+    /// Where the replay stopped matching the recording, if it did.
+    pub divergence: Option<Divergence>,
+}
+
+impl EventLog {
+    /// An empty log for a run of `source`.
+    pub fn new(path: PathBuf, source: String, seed: u64) -> Self {
+        Self {
+            path,
+            source,
+            seed,
+            events: Vec::new(),
+        }
+    }
+
+    /// The log as JSON Lines: the header, then one line per event.
+    pub fn to_json_lines(&self) -> String {
+        let header = LogHeader {
+            format: LOG_FORMAT.to_string(),
+            version: LOG_VERSION,
+            path: self.path.clone(),
+            seed: self.seed,
+            source: self.source.clone(),
+        };
+        let mut lines = vec![serde_json::to_string(&header).expect("log header serializes")];
+        lines.extend(
+            self.events
+                .iter()
+                .map(|event| serde_json::to_string(event).expect("logged events serialize")),
+        );
+        lines.join("\n") + "\n"
+    }
+
+    /// Read a log written by [`Self::to_json_lines`].
+    pub fn from_json_lines(text: &str) -> Result<Self, String> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        let Some((_, header)) = lines.next() else {
+            return Err("the event log is empty".to_string());
+        };
+        let header: LogHeader = serde_json::from_str(header)
+            .map_err(|err| format!("line 1: not an event log header: {}", err))?;
+        if header.format != LOG_FORMAT {
+            return Err(format!("line 1: unknown log format '{}'", header.format));
+        }
+        if header.version != LOG_VERSION {
+            return Err(format!(
+                "line 1: log version {} is not supported (expected {})",
+                header.version, LOG_VERSION
+            ));
+        }
+        let events = lines
+            .map(|(index, line)| {
+                serde_json::from_str(line).map_err(|err| format!("line {}: {}", index + 1, err))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            path: header.path,
+            source: header.source,
+            seed: header.seed,
+            events,
+        })
+    }
+
+    /// Write the log to `path`.
+    pub fn save(&self, path: &Path) -> Result<(), MetorexError> {
+        fs::write(path, self.to_json_lines())
+            .map_err(|err| log_error(format!("Failed to write '{}': {}", path.display(), err)))
+    }
+
+    /// Read a log from `path`.
+    pub fn load(path: &Path) -> Result<Self, MetorexError> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| log_error(format!("Failed to read '{}': {}", path.display(), err)))?;
+        Self::from_json_lines(&text)
+            .map_err(|err| log_error(format!("Invalid event log '{}': {}", path.display(), err)))
+    }
+
+    /// Everything the program read from input, in order, as one text.
+    pub fn input(&self) -> String {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                LoggedEvent::Input { text: Some(text) } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The first event at which `other` stops matching this log.
+    pub fn first_difference(&self, other: &EventLog) -> Option<Divergence> {
+        let length = self.events.len().max(other.events.len());
+        (0..length).find_map(|index| {
+            let expected = self.events.get(index);
+            let found = other.events.get(index);
+            (expected != found).then(|| Divergence {
+                index,
+                expected: expected.cloned(),
+                found: found.cloned(),
+            })
+        })
+    }
+
+    /// Run the recorded program again with its seed and input, recording
+    /// the new run and comparing it with this one.
+    ///
+    /// Output is written as the program runs, as in the original run.
+    pub fn replay(&self, mut options: VmOptions) -> Result<Replay, MetorexError> {
+        let program = parse_file(&self.source, &self.path.display().to_string())?;
+        options.seed = Some(self.seed);
+
+        let mut vm = VirtualMachine::with_options(options);
+        vm.set_current_file(self.path.clone());
+        vm.set_current_source(Some(&self.source));
+        vm.set_main_file(self.path.clone());
+        vm.mark_file_loaded(self.path.clone());
+        vm.set_input(Cursor::new(self.input().into_bytes()));
+
+        vm.start_recording(self.path.clone(), &self.source);
+        let result = vm.execute_program(&program);
+        let log = vm
+            .finish_recording(&result)
+            .expect("recording was started above");
+        let divergence = self.first_difference(&log);
+        Ok(Replay {
+            log,
+            result,
+            divergence,
+        })
+    }
+}
+
+/// Recording state kept by the VM while `--record` is on.
+pub(super) struct Recording {
+    log: Rc<RefCell<EventLog>>,
+    hooks: HookId,
+}
+
+/// Trace hook adding statements and raises to the log.
+struct Recorder(Rc<RefCell<EventLog>>);
+
+impl VmHooks for Recorder {
+    fn on_event(&mut self, _vm: &VirtualMachine, event: &TraceEvent) {
+        let position = event.position;
+        let logged = match (event.kind, &event.raised_exception) {
+            (TraceEventKind::Line, _) => LoggedEvent::Line {
+                line: position.line,
+                column: position.column,
+            },
+            (TraceEventKind::Raise, Some(exception)) => LoggedEvent::Raise {
+                line: position.line,
+                column: position.column,
+                exception: format_exception(exception),
+            },
+            _ => return,
+        };
+        self.0.borrow_mut().events.push(logged);
+    }
+}
+
+impl VirtualMachine {
+    /// Start recording this run of `source`. `rand` is reseeded with the
+    /// `seed` option, or a fresh seed, which the log keeps for replaying.
+    pub fn start_recording(&mut self, path: PathBuf, source: &str) {
+        let seed = self
+            .options()
+            .seed
+            .unwrap_or_else(|| Random::new(None).next_u64());
+        self.random = Random::new(Some(seed));
+
+        let log = Rc::new(RefCell::new(EventLog::new(path, source.to_string(), seed)));
+        let hooks = self.add_hooks(Rc::new(RefCell::new(Recorder(Rc::clone(&log)))));
+        self.recording = Some(Recording { log, hooks });
+    }
+
+    /// Stop recording, logging how the run finished, and return the log.
+    /// Returns `None` if the VM was not recording.
+    pub fn finish_recording(
+        &mut self,
+        result: &Result<Option<Object>, MetorexError>,
+    ) -> Option<EventLog> {
+        let recording = self.recording.take()?;
+        self.remove_hooks(recording.hooks);
+        let mut log = recording.log.borrow().clone();
+        log.events.push(LoggedEvent::Finish {
+            error: result.as_ref().err().map(|err| err.to_string()),
+        });
+        Some(log)
+    }
+
+    /// Add an input or output event to the log, if this run is recorded.
+    pub(super) fn log_event(&self, event: LoggedEvent) {
+        if let Some(recording) = &self.recording {
+            recording.log.borrow_mut().events.push(event);
+        }
+    }
+}
+
+fn log_error(message: String) -> MetorexError {
+    MetorexError::runtime_error(message, SourceLocation::new(0, 0, 0))
+}
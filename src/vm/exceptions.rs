@@ -3,9 +3,10 @@
 
 use super::ControlFlow;
 use super::core::VirtualMachine;
+use super::errors::*;
 use super::utils::*;
 
-use crate::ast::Statement;
+use crate::ast::{Expression, RescueClause, Statement};
 use crate::class::Class;
 use crate::error::MetorexError;
 use crate::lexer::Position;
@@ -16,7 +17,8 @@ impl VirtualMachine {
     /// Execute a raise statement to throw an exception.
     pub(crate) fn execute_raise(
         &mut self,
-        exception: &Option<crate::ast::Expression>,
+        exception: &Option<Expression>,
+        cause: &Option<Expression>,
         position: Position,
     ) -> Result<ControlFlow, MetorexError> {
         let exception_obj = if let Some(expr) = exception {
@@ -60,8 +62,34 @@ impl VirtualMachine {
             }
         };
 
+        // Chain the cause: explicit `from` wins (`from nil` suppresses chaining),
+        // otherwise raising inside a rescue wraps the exception being handled
+        if let Some(cause_expr) = cause {
+            match self.evaluate_expression(cause_expr)? {
+                Object::Nil => set_exception_cause(&exception_obj, None),
+                cause_obj @ Object::Exception(_) => {
+                    set_exception_cause(&exception_obj, Some(cause_obj))
+                }
+                other => {
+                    return Err(MetorexError::type_error(
+                        format!(
+                            "Exception cause must be an Exception or nil, found '{}'",
+                            other.type_name()
+                        ),
+                        position_to_location(cause_expr.position()),
+                    ));
+                }
+            }
+        } else if exception.is_some()
+            && let Some(current @ Object::Exception(_)) = self.environment().get("$!")
+            && exception_cause(&exception_obj).is_none()
+        {
+            set_exception_cause(&exception_obj, Some(current));
+        }
+
         // Capture stack trace and add source location to exception
         let exception_obj = self.add_stack_trace_to_exception(exception_obj, position);
+        self.trace_raise(&exception_obj, position)?;
 
         Ok(ControlFlow::Exception {
             exception: exception_obj,
@@ -76,29 +104,20 @@ impl VirtualMachine {
 
             // Add source location if not already set
             if exc.location.is_none() {
-                exc.location = Some(crate::object::SourceLocation::new(
-                    "script".to_string(),
-                    position.line,
-                    position.column,
-                ));
+                let mut location = position_to_location(position);
+                location.filename = self
+                    .get_current_file()
+                    .map(|path| path.to_string_lossy().into());
+                exc.location = Some(location);
             }
 
-            // Generate stack trace from call stack
-            let backtrace: Vec<String> = self
-                .call_stack()
-                .iter()
-                .map(|frame| {
-                    if let Some(loc) = frame.location() {
-                        format!("  at {} ({})", frame.name(), loc)
-                    } else {
-                        format!("  at {}", frame.name())
-                    }
-                })
-                .collect();
-
-            // Add current position to backtrace
+            // Add current position to the stack trace from the call stack
             let mut full_backtrace = vec![format!("  at {}:{}", position.line, position.column)];
-            full_backtrace.extend(backtrace);
+            full_backtrace.extend(
+                self.call_stack_trace()
+                    .into_iter()
+                    .map(|frame| format!("  at {}", frame)),
+            );
 
             exc.backtrace = Some(full_backtrace);
 
@@ -113,75 +132,150 @@ impl VirtualMachine {
     pub(crate) fn execute_begin(
         &mut self,
         body: &[Statement],
-        rescue_clauses: &[crate::ast::RescueClause],
+        rescue_clauses: &[RescueClause],
         else_clause: &Option<Vec<Statement>>,
         ensure_block: &Option<Vec<Statement>>,
-        _position: Position,
     ) -> Result<ControlFlow, MetorexError> {
-        // Execute the try block
-        let body_result = self.execute_statements_internal(body);
-
-        // Track whether an exception was handled
-        let mut handled_exception = false;
-        let mut final_result = body_result;
+        self.run_begin(body, rescue_clauses, else_clause, ensure_block)
+            .map(|(flow, _)| flow)
+    }
 
-        // Convert UncaughtException errors to ControlFlow::Exception
-        if let Err(MetorexError::UncaughtException {
-            exception,
-            location,
+    /// Evaluate a `begin` block used as an expression, producing the value of
+    /// the body, rescue clause or else clause that ran last.
+    pub(crate) fn evaluate_begin(&mut self, block: &Statement) -> Result<Object, MetorexError> {
+        let Statement::Begin {
+            body,
+            rescue_clauses,
+            else_clause,
+            ensure_block,
             ..
-        }) = &final_result
-        {
-            final_result = Ok(ControlFlow::Exception {
-                exception: exception.clone(),
-                position: Position {
-                    line: location.line,
-                    column: location.column,
-                    offset: 0,
+        } = block
+        else {
+            return Err(unimplemented_statement_error(block));
+        };
+
+        match self.run_begin(body, rescue_clauses, else_clause, ensure_block)? {
+            (ControlFlow::Next, value) => Ok(value),
+            (
+                ControlFlow::Exception {
+                    exception,
+                    position,
                 },
-            });
+                _,
+            ) => Err(MetorexError::UncaughtException {
+                message: format_exception(&exception),
+                exception,
+                location: position_to_location(position),
+                catalog_message: None,
+            }),
+            (ControlFlow::Return { position, .. }, _) => {
+                Err(begin_expression_jump_error("return", position))
+            }
+            (ControlFlow::Break { position }, _) => {
+                Err(begin_expression_jump_error("break", position))
+            }
+            (ControlFlow::Continue { position }, _) => {
+                Err(begin_expression_jump_error("continue", position))
+            }
+            (ControlFlow::Retry { position }, _) => Err(retry_outside_rescue_error(position)),
         }
+    }
 
-        // If an exception occurred, try to match rescue clauses
-        if let Ok(ControlFlow::Exception {
-            exception,
-            position: _ex_pos,
-        }) = &final_result
-        {
-            // Store the current exception in $! for access in rescue blocks
-            self.environment_mut()
-                .define("$!".to_string(), exception.clone());
-
-            // Try each rescue clause in order
-            for rescue_clause in rescue_clauses {
-                if self.exception_matches(exception, &rescue_clause.exception_types)? {
-                    // Bind exception to variable if specified (=> e)
-                    if let Some(var_name) = &rescue_clause.variable_name {
-                        self.environment_mut()
-                            .define(var_name.clone(), exception.clone());
+    /// Run a begin/rescue/else/ensure block, returning its control flow
+    /// together with the value of the last statement of the branch that
+    /// completed. The ensure clause runs for its effects only.
+    pub(crate) fn run_begin(
+        &mut self,
+        body: &[Statement],
+        rescue_clauses: &[RescueClause],
+        else_clause: &Option<Vec<Statement>>,
+        ensure_block: &Option<Vec<Statement>>,
+    ) -> Result<(ControlFlow, Object), MetorexError> {
+        let mut retries = 0;
+
+        let mut final_result = loop {
+            // Execute the try block
+            let mut final_result = self.execute_statements_for_value(body);
+
+            // Convert UncaughtException errors to ControlFlow::Exception
+            if let Err(MetorexError::UncaughtException {
+                exception,
+                location,
+                ..
+            }) = &final_result
+            {
+                final_result = Ok((
+                    ControlFlow::Exception {
+                        exception: exception.clone(),
+                        position: Position {
+                            line: location.line,
+                            column: location.column,
+                            offset: 0,
+                        },
+                    },
+                    Object::Nil,
+                ));
+            }
+
+            // If an exception occurred, try to match rescue clauses
+            if let Ok((
+                ControlFlow::Exception {
+                    exception,
+                    position: ex_pos,
+                },
+                _,
+            )) = &final_result
+            {
+                let exception = exception.clone();
+                let ex_pos = *ex_pos;
+
+                // Store the current exception in $! for access in rescue blocks,
+                // restoring the outer one (if any) once this rescue is done
+                let previous_exception = self.environment().get("$!").unwrap_or(Object::Nil);
+                self.environment_mut()
+                    .define("$!".to_string(), exception.clone());
+
+                // Try each rescue clause in order
+                for rescue_clause in rescue_clauses {
+                    if self.exception_matches(&exception, &rescue_clause.exception_types, ex_pos)? {
+                        // Bind exception to variable if specified (=> e)
+                        if let Some(var_name) = &rescue_clause.variable_name {
+                            self.environment_mut()
+                                .define(var_name.clone(), exception.clone());
+                        }
+
+                        // Execute the rescue block
+                        final_result = self.execute_statements_for_value(&rescue_clause.body);
+                        break;
                     }
+                }
+
+                self.environment_mut()
+                    .define("$!".to_string(), previous_exception);
 
-                    // Execute the rescue block
-                    final_result = self.execute_statements_internal(&rescue_clause.body);
-                    handled_exception = true;
-                    break;
+                // retry re-runs the body, giving up (re-raising) once the limit is reached
+                if let Ok((ControlFlow::Retry { .. }, _)) = final_result {
+                    if retries < self.max_retries() {
+                        retries += 1;
+                        continue;
+                    }
+                    final_result = Ok((
+                        ControlFlow::Exception {
+                            exception,
+                            position: ex_pos,
+                        },
+                        Object::Nil,
+                    ));
+                }
+            } else if matches!(final_result, Ok((ControlFlow::Next, _))) {
+                // No exception occurred - execute else clause if present
+                if let Some(else_stmts) = else_clause {
+                    final_result = self.execute_statements_for_value(else_stmts);
                 }
             }
 
-            // If exception wasn't handled, it will propagate
-            if !handled_exception {
-                // Keep the exception result to propagate it
-                // Don't execute else clause
-            } else {
-                // Clear the $! variable since exception was handled
-                self.environment_mut().define("$!".to_string(), Object::Nil);
-            }
-        } else if final_result.is_ok() && matches!(final_result, Ok(ControlFlow::Next)) {
-            // No exception occurred - execute else clause if present
-            if let Some(else_stmts) = else_clause {
-                final_result = self.execute_statements_internal(else_stmts);
-            }
-        }
+            break final_result;
+        };
 
         // Always execute ensure block, regardless of what happened
         if let Some(ensure_stmts) = ensure_block {
@@ -190,19 +284,16 @@ impl VirtualMachine {
             // If ensure block raises an exception or changes control flow,
             // it overrides the previous result
             match ensure_result {
-                Ok(ControlFlow::Exception { .. }) => {
-                    final_result = ensure_result;
-                }
                 Ok(ControlFlow::Next) => {
                     // Ensure completed normally, don't override final_result
                 }
-                Ok(_) => {
-                    // Other control flow (return, break, continue)
-                    final_result = ensure_result;
+                Ok(flow) => {
+                    // Exception or other control flow (return, break, continue)
+                    final_result = Ok((flow, Object::Nil));
                 }
-                Err(_) => {
+                Err(error) => {
                     // Error in ensure block overrides previous result
-                    final_result = ensure_result;
+                    final_result = Err(error);
                 }
             }
         }
@@ -210,42 +301,71 @@ impl VirtualMachine {
         final_result
     }
 
-    /// Check if an exception matches the given exception type list.
+    /// Evaluate an `expression rescue fallback` modifier.
+    ///
+    /// Like a bare `rescue` clause, any raised exception is caught; the
+    /// fallback is evaluated with the exception available in `$!`.
+    pub(crate) fn evaluate_rescue_modifier(
+        &mut self,
+        expression: &Expression,
+        fallback: &Expression,
+    ) -> Result<Object, MetorexError> {
+        match self.evaluate_expression(expression) {
+            Err(MetorexError::UncaughtException { exception, .. }) => {
+                let previous_exception = self.environment().get("$!").unwrap_or(Object::Nil);
+                self.environment_mut().define("$!".to_string(), exception);
+                let result = self.evaluate_expression(fallback);
+                self.environment_mut()
+                    .define("$!".to_string(), previous_exception);
+                result
+            }
+            other => other,
+        }
+    }
+
+    /// Check if an exception matches the given exception type list, testing
+    /// each named pattern with `===`.
     pub(crate) fn exception_matches(
-        &self,
+        &mut self,
         exception: &Object,
         exception_types: &[String],
+        position: Position,
     ) -> Result<bool, MetorexError> {
         // Empty exception_types list means catch all exceptions
         if exception_types.is_empty() {
             return Ok(true);
         }
 
-        // Get the exception's type name
-        let exception_type_name = match exception {
-            Object::Exception(ex) => ex.borrow().exception_type.clone(),
-            _ => return Ok(false),
-        };
+        if !matches!(exception, Object::Exception(_)) {
+            return Ok(false);
+        }
 
-        // Check if the exception's type matches any of the specified types
+        // Each rescued name must resolve to something `===` can test with
         for type_name in exception_types {
-            // Look up the exception type class in the environment
-            if let Some(Object::Class(target_class)) = self.environment().get(type_name) {
-                // Get the class for this exception type
-                if let Some(Object::Class(exception_class)) =
-                    self.environment().get(&exception_type_name)
-                {
-                    // Check if exception_class is the target_class or a subclass of it
-                    if Self::is_class_or_subclass(&exception_class, &target_class) {
-                        return Ok(true);
-                    }
-                }
+            if let Some(pattern) = self.rescued_pattern(type_name, position)
+                && self.case_equals(&pattern, exception, position)?
+            {
+                return Ok(true);
             }
         }
 
         Ok(false)
     }
 
+    /// The value a rescued name refers to, looked up like any other constant
+    /// and following scoped names such as `Timeout::Error` the way
+    /// `Timeout::Error` is evaluated elsewhere.
+    fn rescued_pattern(&mut self, name: &str, position: Position) -> Option<Object> {
+        let mut segments = name.split("::");
+        let mut pattern = self.resolve_constant(segments.next()?)?;
+        for segment in segments {
+            pattern = self
+                .dispatch_method(pattern, segment, Vec::new(), true, position)
+                .ok()?;
+        }
+        Some(pattern)
+    }
+
     /// Check if a class is the same as or a subclass of another class.
     pub(crate) fn is_class_or_subclass(class: &Rc<Class>, target: &Rc<Class>) -> bool {
         if Rc::ptr_eq(class, target) {
@@ -260,3 +380,29 @@ impl VirtualMachine {
         false
     }
 }
+
+/// Get the cause of an exception object, if any.
+fn exception_cause(exception: &Object) -> Option<Object> {
+    match exception {
+        Object::Exception(exc) => exc.borrow().cause.as_deref().cloned(),
+        _ => None,
+    }
+}
+
+/// Set (or clear) the cause of an exception object, refusing to create a cycle.
+fn set_exception_cause(exception: &Object, cause: Option<Object>) {
+    let Object::Exception(exc) = exception else {
+        return;
+    };
+
+    // Walk the cause's own chain; linking back to this exception would loop forever
+    let mut link = cause.clone();
+    while let Some(Object::Exception(linked)) = link {
+        if Rc::ptr_eq(&linked, exc) {
+            return;
+        }
+        link = linked.borrow().cause.as_deref().cloned();
+    }
+
+    exc.borrow_mut().cause = cause.map(Box::new);
+}
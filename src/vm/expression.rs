@@ -6,16 +6,20 @@
 //! - Dictionary literals
 //! - Index operations (array/dictionary access)
 
-use crate::ast::{Expression, InterpolationPart};
+use crate::ast::{Expression, FORWARDING_PARAMETER, InterpolationPart};
 use crate::error::MetorexError;
 use crate::lexer::Position;
 use crate::object::Object;
+use indexmap::IndexMap;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::fmt::Write;
 use std::rc::Rc;
 
 use super::core::VirtualMachine;
-use super::errors::{index_out_of_bounds_error, undefined_dictionary_key_error};
+use super::errors::{
+    index_out_of_bounds_error, no_forwarded_arguments_error, undefined_dictionary_key_error,
+};
+use super::native_methods::node_field;
 use super::utils::{object_to_dict_key, position_to_location};
 
 impl VirtualMachine {
@@ -24,21 +28,57 @@ impl VirtualMachine {
         &mut self,
         parts: &[InterpolationPart],
     ) -> Result<String, MetorexError> {
-        let mut buffer = String::new();
+        // Size for the literal text up front; values are written straight
+        // into the buffer rather than through a temporary String each
+        let text_len = parts
+            .iter()
+            .map(|part| match part {
+                InterpolationPart::Text(text) => text.len(),
+                InterpolationPart::Expression(_) => 0,
+            })
+            .sum();
+        let mut buffer = String::with_capacity(text_len);
 
         for part in parts {
             match part {
                 InterpolationPart::Text(text) => buffer.push_str(text),
-                InterpolationPart::Expression(expr) => {
-                    let value = self.evaluate_expression(expr)?;
-                    buffer.push_str(&value.to_string());
-                }
+                InterpolationPart::Expression(expr) => match self.evaluate_expression(expr)? {
+                    Object::String(text) => buffer.push_str(&text),
+                    value => {
+                        let _ = write!(buffer, "{}", value);
+                    }
+                },
             }
         }
 
         Ok(buffer)
     }
 
+    /// Evaluate call arguments, splicing in any arguments forwarded with `...`.
+    ///
+    /// The list comes from the VM's pool with room for a trailing block;
+    /// callers that only borrow it should hand it back with
+    /// `argument_buffers.give_back`.
+    pub(crate) fn evaluate_arguments(
+        &mut self,
+        arguments: &[Expression],
+    ) -> Result<Vec<Object>, MetorexError> {
+        let mut evaluated = self.argument_buffers.take(arguments.len() + 1);
+        for argument in arguments {
+            if let Expression::ForwardedArgs { position } = argument {
+                match self.environment().get(FORWARDING_PARAMETER) {
+                    Some(Object::Array(forwarded)) => {
+                        evaluated.extend(forwarded.borrow().iter().cloned())
+                    }
+                    _ => return Err(no_forwarded_arguments_error(*position)),
+                }
+            } else {
+                evaluated.push(self.evaluate_expression(argument)?);
+            }
+        }
+        Ok(evaluated)
+    }
+
     /// Evaluate array literal expressions.
     pub(crate) fn evaluate_array_literal(
         &mut self,
@@ -48,7 +88,7 @@ impl VirtualMachine {
         for element in elements {
             evaluated.push(self.evaluate_expression(element)?);
         }
-        Ok(Object::Array(Rc::new(RefCell::new(evaluated))))
+        Ok(self.track_allocation(Object::Array(Rc::new(RefCell::new(evaluated)))))
     }
 
     /// Evaluate dictionary literal expressions.
@@ -56,7 +96,7 @@ impl VirtualMachine {
         &mut self,
         entries: &[(Expression, Expression)],
     ) -> Result<Object, MetorexError> {
-        let mut map = HashMap::with_capacity(entries.len());
+        let mut map = IndexMap::with_capacity(entries.len());
 
         for (key_expr, value_expr) in entries {
             let key_value = self.evaluate_expression(key_expr)?;
@@ -74,19 +114,19 @@ impl VirtualMachine {
             map.insert(key_string, value);
         }
 
-        Ok(Object::Dict(Rc::new(RefCell::new(map))))
+        Ok(self.track_allocation(Object::Dict(Rc::new(RefCell::new(map)))))
     }
 
     /// Evaluate indexing operations on arrays and dictionaries.
     pub(crate) fn evaluate_index_operation(
-        &self,
+        &mut self,
         collection: Object,
         key: Object,
         position: Position,
     ) -> Result<Object, MetorexError> {
         match collection {
-            Object::Array(elements_rc) => match key {
-                Object::Int(index) => {
+            Object::Array(elements_rc) => match self.implicit_int(&key, "array index", position)? {
+                Some(index) => {
                     let elements = elements_rc.borrow();
                     if index < 0 || (index as usize) >= elements.len() {
                         Err(index_out_of_bounds_error(index, elements.len(), position))
@@ -94,7 +134,7 @@ impl VirtualMachine {
                         Ok(elements[index as usize].clone())
                     }
                 }
-                _ => Err(MetorexError::type_error(
+                None => Err(MetorexError::type_error(
                     format!("Array index must be an Integer, found {}", key.type_name()),
                     position_to_location(position),
                 )),
@@ -116,6 +156,18 @@ impl VirtualMachine {
                     .ok_or_else(|| undefined_dictionary_key_error(&key_string, position))
             }
 
+            Object::Instance(_) if let Some(entries) = self.identity_hash_entries(&collection) => {
+                self.identity_hash_get(&entries, &key, position)
+            }
+            Object::Instance(_) if let Some(fields) = self.node_fields(&collection) => {
+                node_field(&fields, &key, position)
+            }
+            Object::Instance(_)
+                if let Some((class, method)) = self.lookup_method(&collection, "[]") =>
+            {
+                self.invoke_method(class, method, collection, vec![key], position)
+            }
+
             other => Err(MetorexError::type_error(
                 format!("Cannot index into type '{}'", other.type_name()),
                 position_to_location(position),
@@ -7,7 +7,7 @@ use crate::object::Object;
 use std::collections::HashMap;
 
 /// Registry that owns global objects accessible throughout the VM.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct GlobalRegistry {
     objects: HashMap<String, Object>,
 }
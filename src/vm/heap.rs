@@ -1,25 +1,457 @@
 //! Heap memory management for the Metorex virtual machine.
 //!
-//! This module provides a lightweight heap structure that tracks allocated objects.
-//! Currently a stub implementation; will be expanded for garbage collection integration.
+//! Runtime values are reference counted, so memory is reclaimed as soon as the
+//! last reference goes away. The heap keeps weak handles to the containers,
+//! instances and blocks the VM allocates so scripts and embedders can inspect
+//! allocation counts and live objects. A collection traces from those handles
+//! to free the cycles nothing else reaches (see the `collector` module), then
+//! sweeps the handles of objects that have been freed. It runs automatically
+//! once enough handles have accumulated, unless collection has been disabled.
+//! After each collection the threshold becomes twice the number of handles
+//! still live, so a program whose live set keeps growing is traced a
+//! logarithmic number of times rather than at every allocation.
+//!
+//! The collector works over these `Rc` values directly. Objects are not
+//! handles into a heap the VM owns, so it cannot move or compact them, and it
+//! finds roots from reference counts rather than by scanning the environment
+//! and call stack (see the `collector` module).
+//!
+//! Each tracked allocation remembers its class, so `stats_by_class` can break
+//! the counts down and a leak check can name the objects that outlived it.
+//! While a leak check runs in a debug build, allocations also remember the
+//! call stack that made them.
+//!
+//! The heap also hands out `object_id`s. An object receives the next id the
+//! first time one is asked for, and the heap holds a weak handle to it for as
+//! long as it lives. The weak handle keeps the allocation's address from being
+//! reused, so a later object never inherits a freed object's id.
+
+use super::collector;
+use crate::object::{
+    Binding, BlockStatement, Class, Exception, Instance, Method, Object, ObjectHash,
+};
+use indexmap::IndexMap;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::rc::{Rc, Weak};
+use std::time::{Duration, Instant};
+
+/// Smallest number of tracked handles that triggers an automatic collection.
+/// The threshold grows past it to twice the live handles after a collection.
+pub const DEFAULT_COLLECTION_THRESHOLD: usize = 10_000;
+
+/// `object_id` of `false`; `nil` and `true` follow, and Ints take the odd
+/// numbers, so none of them collide with the ids the heap assigns.
+const FALSE_ID: i64 = 0;
+const NIL_ID: i64 = 8;
+const TRUE_ID: i64 = 20;
+/// First id the heap assigns; later ones step by 8.
+const FIRST_ASSIGNED_ID: i64 = 24;
+
+/// Snapshot of heap activity, as reported by `GC.stats` and `vm.heap_stats()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeapStats {
+    /// Total number of objects allocated since the VM started.
+    pub allocations: usize,
+    /// Tracked objects that are still reachable.
+    pub live_objects: usize,
+    /// Number of collections run so far.
+    pub collections: usize,
+    /// Duration of the most recent collection.
+    pub last_pause: Duration,
+    /// Combined duration of every collection.
+    pub total_pause: Duration,
+    /// Whether automatic collections are enabled.
+    pub enabled: bool,
+    /// Number of tracked handles that triggers the next automatic collection.
+    pub threshold: usize,
+}
+
+/// Allocation counts for one class, as reported by `Heap::stats_by_class`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClassStats {
+    /// Objects of the class allocated since the VM started.
+    pub allocations: usize,
+    /// Tracked objects of the class that are still reachable.
+    pub live_objects: usize,
+}
+
+/// A tracked object that was still alive at the end of a leak check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leak {
+    /// Class of the object, e.g. `Array` or the name of a user class.
+    pub class_name: String,
+    /// Call frames that were active when the object was allocated, in the
+    /// order they were entered. Only recorded in debug builds.
+    pub backtrace: Vec<String>,
+}
+
+/// The objects a leak check found still alive, in allocation order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LeakReport {
+    pub leaks: Vec<Leak>,
+}
+
+impl LeakReport {
+    /// Whether every object allocated during the check was freed.
+    pub fn is_empty(&self) -> bool {
+        self.leaks.is_empty()
+    }
+
+    /// How many leaked objects of each class the report holds.
+    pub fn counts_by_class(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for leak in &self.leaks {
+            *counts.entry(leak.class_name.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
 
-use crate::object::Object;
+impl fmt::Display for LeakReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.leaks.is_empty() {
+            return write!(f, "no objects leaked");
+        }
+        let noun = if self.leaks.len() == 1 {
+            "object"
+        } else {
+            "objects"
+        };
+        write!(f, "{} {} leaked:", self.leaks.len(), noun)?;
+        for leak in &self.leaks {
+            write!(f, "\n  {}", leak.class_name)?;
+            for frame in &leak.backtrace {
+                write!(f, "\n    at {}", frame)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Weak handle to a heap-allocated runtime value.
+#[derive(Debug)]
+enum TrackedObject {
+    Array(Weak<RefCell<Vec<Object>>>),
+    Dict(Weak<RefCell<IndexMap<String, Object>>>),
+    Instance(Weak<RefCell<Instance>>),
+    String(Weak<String>),
+    Set(Weak<RefCell<HashSet<ObjectHash>>>),
+    Class(Weak<Class>),
+    Method(Weak<Method>),
+    Block(Weak<BlockStatement>),
+    Exception(Weak<RefCell<Exception>>),
+    Binding(Weak<Binding>),
+}
+
+impl TrackedObject {
+    fn track(object: &Object) -> Option<Self> {
+        match object {
+            Object::Array(array) => Some(TrackedObject::Array(Rc::downgrade(array))),
+            Object::Dict(dict) => Some(TrackedObject::Dict(Rc::downgrade(dict))),
+            Object::Instance(instance) => Some(TrackedObject::Instance(Rc::downgrade(instance))),
+            Object::Block(block) => Some(TrackedObject::Block(Rc::downgrade(block))),
+            _ => None,
+        }
+    }
+
+    /// The value behind a handle from `track`, if it is still alive.
+    fn upgrade(&self) -> Option<Object> {
+        match self {
+            TrackedObject::Array(weak) => weak.upgrade().map(Object::Array),
+            TrackedObject::Dict(weak) => weak.upgrade().map(Object::Dict),
+            TrackedObject::Instance(weak) => weak.upgrade().map(Object::Instance),
+            TrackedObject::Block(weak) => weak.upgrade().map(Object::Block),
+            _ => None,
+        }
+    }
+
+    /// The allocation address and a weak handle for any value with identity.
+    /// Values without one (nil, numbers, symbols, ranges...) give `None`.
+    fn identify(object: &Object) -> Option<(usize, Self)> {
+        fn address<T: ?Sized>(rc: &Rc<T>) -> usize {
+            Rc::as_ptr(rc) as *const () as usize
+        }
+        Some(match object {
+            Object::Array(rc) => (address(rc), TrackedObject::Array(Rc::downgrade(rc))),
+            Object::Dict(rc) => (address(rc), TrackedObject::Dict(Rc::downgrade(rc))),
+            Object::Instance(rc) => (address(rc), TrackedObject::Instance(Rc::downgrade(rc))),
+            Object::String(rc) => (address(rc), TrackedObject::String(Rc::downgrade(rc))),
+            Object::Set(rc) => (address(rc), TrackedObject::Set(Rc::downgrade(rc))),
+            Object::Class(rc) => (address(rc), TrackedObject::Class(Rc::downgrade(rc))),
+            Object::Method(rc) => (address(rc), TrackedObject::Method(Rc::downgrade(rc))),
+            Object::Block(rc) => (address(rc), TrackedObject::Block(Rc::downgrade(rc))),
+            Object::Exception(rc) => (address(rc), TrackedObject::Exception(Rc::downgrade(rc))),
+            Object::Binding(rc) => (address(rc), TrackedObject::Binding(Rc::downgrade(rc))),
+            _ => return None,
+        })
+    }
+
+    fn is_live(&self) -> bool {
+        match self {
+            TrackedObject::Array(weak) => weak.strong_count() > 0,
+            TrackedObject::Dict(weak) => weak.strong_count() > 0,
+            TrackedObject::Instance(weak) => weak.strong_count() > 0,
+            TrackedObject::String(weak) => weak.strong_count() > 0,
+            TrackedObject::Set(weak) => weak.strong_count() > 0,
+            TrackedObject::Class(weak) => weak.strong_count() > 0,
+            TrackedObject::Method(weak) => weak.strong_count() > 0,
+            TrackedObject::Block(weak) => weak.strong_count() > 0,
+            TrackedObject::Exception(weak) => weak.strong_count() > 0,
+            TrackedObject::Binding(weak) => weak.strong_count() > 0,
+        }
+    }
+}
+
+/// A tracked allocation with what a leak report needs to describe it.
+#[derive(Debug)]
+struct Allocation {
+    handle: TrackedObject,
+    class_name: Rc<str>,
+    /// Value of the allocation counter when the object was allocated
+    serial: usize,
+    backtrace: Vec<String>,
+}
+
+/// The class an allocation is counted under: an instance's class, otherwise
+/// the value's type.
+fn class_name_of(object: &Object) -> Rc<str> {
+    match object {
+        Object::Instance(instance) => Rc::from(instance.borrow().class_name()),
+        _ => Rc::from(object.type_name()),
+    }
+}
 
-/// Lightweight heap placeholder that will evolve with the runtime.
-#[derive(Debug, Default)]
+/// Tracks heap allocations made by the virtual machine.
+#[derive(Debug)]
 pub struct Heap {
-    /// Tracks allocated objects for future GC integration.
-    allocated: Vec<Object>,
+    tracked: Vec<Allocation>,
+    allocations: usize,
+    allocations_by_class: HashMap<Rc<str>, usize>,
+    record_backtraces: bool,
+    collections: usize,
+    last_pause: Duration,
+    total_pause: Duration,
+    enabled: bool,
+    /// Tracked handles that trigger the next automatic collection
+    threshold: usize,
+    /// The threshold never drops below this after a collection
+    minimum_threshold: usize,
+    /// Ids handed out to objects with identity, by allocation address
+    identities: HashMap<usize, (TrackedObject, i64)>,
+    /// Ids handed out to values without identity, by value
+    value_ids: HashMap<String, i64>,
+    next_id: i64,
 }
 
 impl Heap {
-    /// Allocate an object on the heap (no-op stub for now).
-    pub fn allocate(&mut self, object: Object) {
-        self.allocated.push(object);
+    /// Record an allocation. Arrays, hashes, instances and blocks are tracked
+    /// until freed.
+    pub fn allocate(&mut self, object: &Object) {
+        self.allocate_with_backtrace(object, Vec::new());
     }
 
-    /// Returns number of tracked allocations (for testing/introspection).
+    /// Record an allocation along with the call frames that made it.
+    pub fn allocate_with_backtrace(&mut self, object: &Object, backtrace: Vec<String>) {
+        let class_name = class_name_of(object);
+        *self
+            .allocations_by_class
+            .entry(Rc::clone(&class_name))
+            .or_insert(0) += 1;
+        if let Some(handle) = TrackedObject::track(object) {
+            self.tracked.push(Allocation {
+                handle,
+                class_name,
+                serial: self.allocations,
+                backtrace,
+            });
+        }
+        self.allocations += 1;
+
+        if self.enabled && self.tracked.len() >= self.threshold {
+            self.collect();
+        }
+    }
+
+    /// Returns the total number of recorded allocations.
     pub fn allocation_count(&self) -> usize {
-        self.allocated.len()
+        self.allocations
+    }
+
+    /// Count tracked objects that are still reachable.
+    pub fn live_objects(&self) -> usize {
+        self.tracked.iter().filter(|t| t.handle.is_live()).count()
+    }
+
+    /// Allocation and live object counts for each class allocated so far.
+    pub fn stats_by_class(&self) -> BTreeMap<String, ClassStats> {
+        let mut stats: BTreeMap<String, ClassStats> = self
+            .allocations_by_class
+            .iter()
+            .map(|(name, &allocations)| {
+                let class_stats = ClassStats {
+                    allocations,
+                    live_objects: 0,
+                };
+                (name.to_string(), class_stats)
+            })
+            .collect();
+        for allocation in self.tracked.iter().filter(|t| t.handle.is_live()) {
+            if let Some(class_stats) = stats.get_mut(allocation.class_name.as_ref()) {
+                class_stats.live_objects += 1;
+            }
+        }
+        stats
+    }
+
+    /// Whether allocations record the call frames that made them.
+    pub fn records_backtraces(&self) -> bool {
+        self.record_backtraces
+    }
+
+    /// Start or stop recording allocation backtraces, returning the previous setting.
+    pub fn set_record_backtraces(&mut self, record: bool) -> bool {
+        std::mem::replace(&mut self.record_backtraces, record)
+    }
+
+    /// Run a collection, then report the tracked objects allocated since the
+    /// allocation counter read `start` that are still alive.
+    pub fn leaks_since(&mut self, start: usize) -> LeakReport {
+        self.collect();
+        let leaks = self
+            .tracked
+            .iter()
+            .filter(|allocation| allocation.serial >= start)
+            .map(|allocation| Leak {
+                class_name: allocation.class_name.to_string(),
+                backtrace: allocation.backtrace.clone(),
+            })
+            .collect();
+        LeakReport { leaks }
+    }
+
+    /// Free unreachable cycles among the tracked objects, then sweep the
+    /// handles of freed objects, returning how many were reclaimed.
+    pub fn collect(&mut self) -> usize {
+        let start = Instant::now();
+        let live = self
+            .tracked
+            .iter()
+            .filter_map(|allocation| allocation.handle.upgrade())
+            .collect();
+        collector::collect_cycles(live);
+
+        let before = self.tracked.len();
+        self.tracked
+            .retain(|allocation| allocation.handle.is_live());
+        let reclaimed = before - self.tracked.len();
+        self.identities.retain(|_, (handle, _)| handle.is_live());
+        self.threshold = self
+            .minimum_threshold
+            .max(self.tracked.len().saturating_mul(2));
+
+        self.last_pause = start.elapsed();
+        self.total_pause += self.last_pause;
+        self.collections += 1;
+        reclaimed
+    }
+
+    /// Enable or disable automatic collections, returning the previous setting.
+    pub fn set_enabled(&mut self, enabled: bool) -> bool {
+        std::mem::replace(&mut self.enabled, enabled)
+    }
+
+    /// Whether automatic collections are enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Set how many tracked handles trigger the next automatic collection,
+    /// and the least the threshold can fall to as it adapts afterwards.
+    pub fn set_collection_threshold(&mut self, threshold: usize) {
+        self.minimum_threshold = threshold.max(1);
+        self.threshold = self.minimum_threshold;
+    }
+
+    /// How many tracked handles trigger the next automatic collection.
+    pub fn collection_threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// The `object_id` of a value, stable for the value's lifetime.
+    ///
+    /// `false`, `nil`, `true` and Ints have fixed ids. Objects with identity
+    /// get the next free id the first time one is asked for. Other values
+    /// (Floats, Symbols, Ranges...) have no identity of their own, so equal
+    /// values share an id.
+    pub fn object_id(&mut self, object: &Object) -> i64 {
+        match object {
+            Object::Bool(false) => return FALSE_ID,
+            Object::Nil => return NIL_ID,
+            Object::Bool(true) => return TRUE_ID,
+            Object::Int(n) => {
+                if let Some(id) = n.checked_mul(2).and_then(|double| double.checked_add(1)) {
+                    return id;
+                }
+            }
+            _ => {}
+        }
+
+        if let Some((address, handle)) = TrackedObject::identify(object) {
+            if let Some((_, id)) = self.identities.get(&address) {
+                return *id;
+            }
+            let id = self.assign_id();
+            self.identities.insert(address, (handle, id));
+            return id;
+        }
+
+        let value = format!("{:?}", object);
+        if let Some(id) = self.value_ids.get(&value) {
+            return *id;
+        }
+        let id = self.assign_id();
+        self.value_ids.insert(value, id);
+        id
+    }
+
+    fn assign_id(&mut self) -> i64 {
+        let id = self.next_id;
+        self.next_id += 8;
+        id
+    }
+
+    /// Snapshot the current heap statistics.
+    pub fn stats(&self) -> HeapStats {
+        HeapStats {
+            allocations: self.allocations,
+            live_objects: self.live_objects(),
+            collections: self.collections,
+            last_pause: self.last_pause,
+            total_pause: self.total_pause,
+            enabled: self.enabled,
+            threshold: self.threshold,
+        }
+    }
+}
+
+impl Default for Heap {
+    fn default() -> Self {
+        Self {
+            tracked: Vec::new(),
+            allocations: 0,
+            allocations_by_class: HashMap::new(),
+            record_backtraces: false,
+            collections: 0,
+            last_pause: Duration::ZERO,
+            total_pause: Duration::ZERO,
+            enabled: true,
+            threshold: DEFAULT_COLLECTION_THRESHOLD,
+            minimum_threshold: DEFAULT_COLLECTION_THRESHOLD,
+            identities: HashMap::new(),
+            value_ids: HashMap::new(),
+            next_id: FIRST_ASSIGNED_ID,
+        }
     }
 }
@@ -12,9 +12,28 @@ use crate::object::Object;
 pub(super) fn initialize_builtin_methods(builtins: &BuiltinClasses) {
     builtin_classes::init_object_methods(builtins.object_class.as_ref());
     builtin_classes::init_string_methods(builtins.string_class.as_ref());
+    builtin_classes::init_integer_methods(builtins.integer_class.as_ref());
+    builtin_classes::init_float_methods(builtins.float_class.as_ref());
+    builtin_classes::init_decimal_methods(builtins.decimal_class.as_ref());
     builtin_classes::init_array_methods(builtins.array_class.as_ref());
+    builtin_classes::init_enumerator_methods(builtins.enumerator_class.as_ref());
+    builtin_classes::init_string_builder_methods(builtins.string_builder_class.as_ref());
+    builtin_classes::init_code_builder_methods(builtins.code_builder_class.as_ref());
     builtin_classes::init_hash_methods(builtins.hash_class.as_ref());
+    builtin_classes::init_identity_hash_methods(builtins.identity_hash_class.as_ref());
     builtin_classes::init_exception_methods(builtins.exception_class.as_ref());
+    builtin_classes::init_file_methods(builtins.file_class.as_ref());
+    builtin_classes::init_uri_methods(builtins.uri_class.as_ref());
+    builtin_classes::init_ast_node_methods(builtins.ast_node_class.as_ref());
+    builtin_classes::init_trace_point_methods(builtins.trace_point_class.as_ref());
+    builtin_classes::init_schema_methods(
+        builtins.schema_class.as_ref(),
+        builtins.schema_error_class.as_ref(),
+    );
+    builtin_classes::init_sqlite_methods(
+        builtins.sqlite_class.as_ref(),
+        builtins.sqlite_statement_class.as_ref(),
+    );
 }
 
 /// Register all built-in classes in the global registry.
@@ -24,21 +43,39 @@ pub(super) fn register_builtin_classes(globals: &mut GlobalRegistry, builtins: &
     }
 }
 
-/// Register singleton values (nil, true, false) in the global registry.
-pub(super) fn register_singletons(globals: &mut GlobalRegistry) {
+/// Register singleton values (nil, true, false, and `main` as the top-level
+/// `self`) in the global registry.
+pub(super) fn register_singletons(globals: &mut GlobalRegistry, main_object: &Object) {
     globals.set("nil", Object::Nil);
     globals.set("true", Object::Bool(true));
     globals.set("false", Object::Bool(false));
+    globals.set("self", main_object.clone());
 }
 
 /// Register native functions in the global registry.
 pub(super) fn register_native_functions(globals: &mut GlobalRegistry) {
     globals.set("puts", Object::NativeFunction("puts".to_string()));
     globals.set("method", Object::NativeFunction("method".to_string()));
+    globals.set("pp", Object::NativeFunction("pp".to_string()));
     globals.set(
         "require_relative",
         Object::NativeFunction("require_relative".to_string()),
     );
+    globals.set("require", Object::NativeFunction("require".to_string()));
+    globals.set("reload!", Object::NativeFunction("reload!".to_string()));
+    globals.set("with", Object::NativeFunction("with".to_string()));
+    globals.set("gets", Object::NativeFunction("gets".to_string()));
+    globals.set("rand", Object::NativeFunction("rand".to_string()));
+    globals.set("sleep", Object::NativeFunction("sleep".to_string()));
+    globals.set("retry_on", Object::NativeFunction("retry_on".to_string()));
+    globals.set("eval", Object::NativeFunction("eval".to_string()));
+    globals.set("format", Object::NativeFunction("format".to_string()));
+    globals.set("sprintf", Object::NativeFunction("sprintf".to_string()));
+    globals.set("assert", Object::NativeFunction("assert".to_string()));
+    globals.set(
+        "raise_unless",
+        Object::NativeFunction("raise_unless".to_string()),
+    );
 }
 
 /// Seed the environment with values from the global registry.
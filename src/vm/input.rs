@@ -0,0 +1,155 @@
+//! Standard input for scripts.
+//!
+//! `gets` reads from the process's stdin unless an embedder or test supplies
+//! another reader, so console programs can be driven without a terminal.
+//! `Readline.readline` shares the REPL's line editor when stdin is a
+//! terminal and falls back to the same input otherwise.
+
+use super::VirtualMachine;
+use super::event_log::LoggedEvent;
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::vm::utils::position_to_location;
+use rustyline::DefaultEditor;
+use rustyline::error::ReadlineError;
+use std::io::{self, BufRead, IsTerminal};
+
+impl VirtualMachine {
+    /// Read script input from `reader` instead of the process's stdin.
+    pub fn set_input<R: BufRead + 'static>(&mut self, reader: R) {
+        self.input = Some(Box::new(reader));
+    }
+
+    /// Go back to reading script input from the process's stdin.
+    pub fn reset_input(&mut self) {
+        self.input = None;
+    }
+
+    /// Implement `gets`: show an optional prompt, then read one line.
+    ///
+    /// The line keeps its trailing newline; end of input returns nil.
+    pub(crate) fn read_input_line(
+        &mut self,
+        prompt: Option<&str>,
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        self.read_plain_line(prompt, "gets", position)
+    }
+
+    /// Implement `Readline.readline`: read one line with editing and history.
+    ///
+    /// The REPL's line editor is used when stdin is a terminal; otherwise, or
+    /// when an input reader was supplied, the line is read like `gets`. The
+    /// line comes back without its newline and end of input returns nil.
+    /// Blank lines are never added to the history.
+    pub(crate) fn read_edited_line(
+        &mut self,
+        prompt: &str,
+        add_to_history: bool,
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        let line = if self.input.is_none() && io::stdin().is_terminal() {
+            if self.line_editor.is_none() {
+                let editor = DefaultEditor::new()
+                    .map_err(|err| readline_error(err.to_string(), position))?;
+                self.line_editor = Some(editor);
+            }
+            let editor = self
+                .line_editor
+                .as_mut()
+                .expect("line editor was just created");
+            match editor.readline(prompt) {
+                Ok(line) => {
+                    self.log_event(LoggedEvent::Input {
+                        text: Some(format!("{}\n", line)),
+                    });
+                    line
+                }
+                Err(ReadlineError::Eof) => {
+                    self.log_event(LoggedEvent::Input { text: None });
+                    return Ok(Object::Nil);
+                }
+                Err(ReadlineError::Interrupted) => {
+                    return Err(readline_error("interrupted".to_string(), position));
+                }
+                Err(err) => return Err(readline_error(err.to_string(), position)),
+            }
+        } else {
+            match self.read_plain_line(Some(prompt), "Readline.readline", position)? {
+                Object::String(line) => {
+                    let line = line.strip_suffix('\n').unwrap_or(&line);
+                    line.strip_suffix('\r').unwrap_or(line).to_string()
+                }
+                other => return Ok(other),
+            }
+        };
+
+        if add_to_history && !line.trim().is_empty() {
+            if let Some(editor) = self.line_editor.as_mut() {
+                let _ = editor.add_history_entry(line.as_str());
+            }
+            self.readline_history.push(line.clone());
+        }
+        Ok(Object::string(line))
+    }
+
+    /// Lines added to the history by `Readline.readline`, oldest first.
+    pub(crate) fn readline_history(&self) -> &[String] {
+        &self.readline_history
+    }
+
+    /// Forget the `Readline.readline` history.
+    pub(crate) fn clear_readline_history(&mut self) {
+        self.readline_history.clear();
+        if let Some(editor) = self.line_editor.as_mut() {
+            let _ = editor.clear_history();
+        }
+    }
+
+    /// Show an optional prompt, then read one line from the script's input.
+    ///
+    /// Pending output is flushed first so a prompt is visible before the
+    /// program waits, which matters when stdin is an interactive terminal.
+    fn read_plain_line(
+        &mut self,
+        prompt: Option<&str>,
+        caller: &str,
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        if let Some(prompt) = prompt {
+            self.write_output(prompt);
+        }
+        self.flush_output();
+
+        let mut line = String::new();
+        let read = match self.input.as_mut() {
+            Some(reader) => reader.read_line(&mut line),
+            None => io::stdin().lock().read_line(&mut line),
+        };
+
+        match read {
+            Ok(0) => {
+                self.log_event(LoggedEvent::Input { text: None });
+                Ok(Object::Nil)
+            }
+            Ok(_) => {
+                self.log_event(LoggedEvent::Input {
+                    text: Some(line.clone()),
+                });
+                Ok(Object::string(line))
+            }
+            Err(err) => Err(MetorexError::runtime_error(
+                format!("{} failed to read input: {}", caller, err),
+                position_to_location(position),
+            )),
+        }
+    }
+}
+
+fn readline_error(message: String, position: Position) -> MetorexError {
+    MetorexError::runtime_error(
+        format!("Readline.readline: {}", message),
+        position_to_location(position),
+    )
+}
@@ -0,0 +1,97 @@
+//! Implicit conversion to Int (the `to_int` protocol).
+//!
+//! Array indices, the bounds of a range being iterated and builtin parameters
+//! declared `Int` all convert their value the same way. An Int is used as it
+//! is, a Float only when it is a whole number, and any other object when it
+//! defines `to_int`, which must return an Int. Floats are never truncated: a
+//! fractional, infinite or NaN Float raises a TypeError naming the value.
+
+use super::VirtualMachine;
+use super::errors::raised_exception_error;
+use super::type_checks::describe_type;
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+
+impl VirtualMachine {
+    /// Convert `value` to an Int for `context` (such as "array index").
+    /// Returns `Ok(None)` for values that take no part in the protocol, so
+    /// the caller can report the type it expected.
+    pub(crate) fn implicit_int(
+        &mut self,
+        value: &Object,
+        context: &str,
+        position: Position,
+    ) -> Result<Option<i64>, MetorexError> {
+        match value {
+            Object::Int(number) => Ok(Some(*number)),
+            Object::Float(number) => float_to_exact_int(*number, context, position).map(Some),
+            Object::Instance(_) => {
+                let Some((class, method)) = self.lookup_method(value, "to_int") else {
+                    return Ok(None);
+                };
+                match self.invoke_method(class, method, value.clone(), vec![], position)? {
+                    Object::Int(number) => Ok(Some(number)),
+                    other => Err(raised_exception_error(
+                        "TypeError",
+                        format!(
+                            "{}: {}#to_int must return an Int (got {})",
+                            context,
+                            describe_type(value),
+                            describe_type(&other)
+                        ),
+                        position,
+                    )),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// The first and last integers of a range, converting both bounds.
+    /// Returns `Ok(None)` when a bound takes no part in the protocol.
+    pub(crate) fn range_int_bounds(
+        &mut self,
+        start: &Object,
+        end: &Object,
+        exclusive: bool,
+        position: Position,
+    ) -> Result<Option<(i64, i64)>, MetorexError> {
+        let Some(first) = self.implicit_int(start, "range start", position)? else {
+            return Ok(None);
+        };
+        let Some(end) = self.implicit_int(end, "range end", position)? else {
+            return Ok(None);
+        };
+        let last = if exclusive { end - 1 } else { end };
+        Ok(Some((first, last)))
+    }
+}
+
+/// The Int a Float equals, or a TypeError when it is not a whole number.
+pub(crate) fn float_to_exact_int(
+    value: f64,
+    context: &str,
+    position: Position,
+) -> Result<i64, MetorexError> {
+    // i64::MAX rounds up to 2^63 as a Float, so the upper bound is exclusive
+    if !value.is_finite() || value < i64::MIN as f64 || value >= i64::MAX as f64 {
+        return Err(raised_exception_error(
+            "TypeError",
+            format!("{}: {} has no Int value", context, Object::Float(value)),
+            position,
+        ));
+    }
+    if value.fract() != 0.0 {
+        return Err(raised_exception_error(
+            "TypeError",
+            format!(
+                "{}: {} is not a whole number",
+                context,
+                Object::Float(value)
+            ),
+            position,
+        ));
+    }
+    Ok(value as i64)
+}
@@ -0,0 +1,77 @@
+//! Interrupting a running virtual machine from another thread.
+//!
+//! A VM is single threaded, so a host that wants to cancel a runaway script
+//! asks for an interrupt through a [`VmHandle`] instead. The VM checks the
+//! request before every statement and every loop iteration and raises an
+//! `Interrupt` exception there, which the script may rescue; otherwise it
+//! reaches the embedder as the error returned by `execute_program`.
+
+use super::VirtualMachine;
+use super::errors::raised_exception_error;
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Name of the exception class raised for an interrupt.
+pub const INTERRUPT_EXCEPTION: &str = "Interrupt";
+
+/// A thread-safe handle for interrupting a virtual machine.
+#[derive(Debug, Clone)]
+pub struct VmHandle {
+    requested: Arc<AtomicBool>,
+}
+
+impl VmHandle {
+    /// Ask the VM to raise `Interrupt` at its next statement boundary.
+    ///
+    /// Requests made before the VM reaches a boundary are delivered once.
+    pub fn interrupt(&self) {
+        self.requested.store(true, Ordering::Release);
+    }
+
+    /// Whether an interrupt has been requested but not yet delivered.
+    pub fn is_interrupt_pending(&self) -> bool {
+        self.requested.load(Ordering::Acquire)
+    }
+}
+
+impl VirtualMachine {
+    /// A handle other threads can use to interrupt this VM.
+    pub fn handle(&self) -> VmHandle {
+        VmHandle {
+            requested: Arc::clone(&self.interrupt_requested),
+        }
+    }
+
+    /// Raise `Interrupt` if a handle asked for one, or `Timeout::Error` if a
+    /// `Timeout.timeout` block ran out of time.
+    pub(super) fn check_interrupt(&self, position: Position) -> Result<(), MetorexError> {
+        if self.interrupt_requested.load(Ordering::Relaxed)
+            && self.interrupt_requested.swap(false, Ordering::Acquire)
+        {
+            return Err(raised_exception_error(
+                INTERRUPT_EXCEPTION,
+                "interrupted".to_string(),
+                position,
+            ));
+        }
+        if !self.deadlines.is_empty() {
+            self.check_deadlines(position)?;
+        }
+        Ok(())
+    }
+}
+
+impl MetorexError {
+    /// Whether this error is an `Interrupt` the script did not rescue.
+    pub fn is_interrupt(&self) -> bool {
+        match self {
+            MetorexError::UncaughtException {
+                exception: crate::object::Object::Exception(exception),
+                ..
+            } => exception.borrow().exception_type == INTERRUPT_EXCEPTION,
+            _ => false,
+        }
+    }
+}
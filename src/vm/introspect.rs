@@ -0,0 +1,284 @@
+//! Canonical dumps of interpreter state.
+//!
+//! [`dump_state`] renders the variables a program left in the global scope
+//! and the classes it defined as plain text. The text depends only on what a
+//! program can observe, never on addresses or hash order, so two runs of the
+//! same program (or two execution engines running it) can be compared with
+//! a string equality check.
+
+use super::VirtualMachine;
+use crate::class::Class;
+use crate::object::Object;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::rc::Rc;
+
+/// Render the observable state of `vm` after a program has run.
+///
+/// Variables are listed by name, skipping the builtin classes, singletons
+/// and native functions every VM starts with unless the program rebound
+/// them. Classes are those reachable from the listed variables, including
+/// superclasses and the classes of instances; builtin classes are named but
+/// not expanded.
+pub fn dump_state(vm: &VirtualMachine) -> String {
+    let builtins = vm.builtins().all_classes();
+    let is_builtin = |class: &Rc<Class>| {
+        builtins.values().any(|builtin| Rc::ptr_eq(builtin, class))
+            || Rc::ptr_eq(class, &vm.builtins().sqlite_statement_class)
+            || Rc::ptr_eq(class, &vm.builtins().identity_hash_class)
+            || Rc::ptr_eq(class, &vm.builtins().ast_node_class)
+    };
+
+    let variables: BTreeMap<String, Object> = vm
+        .environment()
+        .global_scope()
+        .borrow()
+        .collect_all_vars()
+        .into_iter()
+        .filter(|(name, value)| !is_seeded(vm, name, value))
+        .collect();
+
+    let mut classes = BTreeMap::new();
+    for value in variables.values() {
+        collect_classes(value, &mut classes, &mut Vec::new());
+    }
+    classes.retain(|_, class| !is_builtin(class));
+
+    let mut out = String::from("variables:\n");
+    for (name, value) in &variables {
+        let _ = writeln!(out, "  {} = {}", name, render(value));
+    }
+    out.push_str("classes:\n");
+    for class in classes.values() {
+        render_class(class, &mut out);
+    }
+    out
+}
+
+/// Whether `name` still holds the global the VM was created with.
+fn is_seeded(vm: &VirtualMachine, name: &str, value: &Object) -> bool {
+    match (vm.globals().get(name), value) {
+        (Some(Object::Class(seeded)), Object::Class(class)) => Rc::ptr_eq(&seeded, class),
+        (Some(seeded), value) => seeded == *value,
+        (None, _) => false,
+    }
+}
+
+/// Gather every class reachable from `value`, keyed by name.
+fn collect_classes(
+    value: &Object,
+    classes: &mut BTreeMap<String, Rc<Class>>,
+    seen: &mut Vec<*const ()>,
+) {
+    match value {
+        Object::Class(class) => collect_class(class, classes),
+        Object::Instance(instance) if enter(seen, instance.as_ptr() as *const ()) => {
+            let instance = instance.borrow();
+            collect_class(&instance.class, classes);
+            for value in instance.instance_vars.values() {
+                collect_classes(value, classes, seen);
+            }
+            seen.pop();
+        }
+        Object::Array(items) if enter(seen, items.as_ptr() as *const ()) => {
+            for item in items.borrow().iter() {
+                collect_classes(item, classes, seen);
+            }
+            seen.pop();
+        }
+        Object::Dict(entries) if enter(seen, entries.as_ptr() as *const ()) => {
+            for item in entries.borrow().values() {
+                collect_classes(item, classes, seen);
+            }
+            seen.pop();
+        }
+        Object::Result(Ok(inner) | Err(inner)) => collect_classes(inner, classes, seen),
+        _ => {}
+    }
+}
+
+fn collect_class(class: &Rc<Class>, classes: &mut BTreeMap<String, Rc<Class>>) {
+    if classes.contains_key(class.name()) {
+        return;
+    }
+    classes.insert(class.name().to_string(), Rc::clone(class));
+    for value in class
+        .class_var_names()
+        .iter()
+        .filter_map(|name| class.get_class_var(name))
+    {
+        collect_classes(&value, classes, &mut Vec::new());
+    }
+    if let Some(superclass) = class.superclass() {
+        collect_class(&superclass, classes);
+    }
+}
+
+fn render_class(class: &Class, out: &mut String) {
+    match class.superclass() {
+        Some(superclass) => {
+            let _ = writeln!(out, "  class {} < {}", class.name(), superclass.name());
+        }
+        None => {
+            let _ = writeln!(out, "  class {}", class.name());
+        }
+    }
+    for module in class.included_modules() {
+        let _ = writeln!(out, "    include {}", module.name());
+    }
+    for module in class.extended_modules() {
+        let _ = writeln!(out, "    extend {}", module.name());
+    }
+    for name in class.instance_variables() {
+        let _ = writeln!(out, "    @{}", name);
+    }
+    for name in class.class_var_names() {
+        if let Some(value) = class.get_class_var(&name) {
+            let _ = writeln!(out, "    @@{} = {}", name, render(&value));
+        }
+    }
+    for name in class.method_names() {
+        if let Some(method) = class.find_method(&name) {
+            let _ = writeln!(out, "    def {}({})", name, method.parameters.join(", "));
+        }
+    }
+}
+
+/// Render a value unambiguously: Strings are quoted, Floats keep their
+/// fraction, and unordered collections are sorted.
+pub fn render(value: &Object) -> String {
+    let mut out = String::new();
+    render_into(value, &mut out, &mut Vec::new());
+    out
+}
+
+fn render_into(value: &Object, out: &mut String, seen: &mut Vec<*const ()>) {
+    match value {
+        Object::Float(number) if number.is_nan() => out.push_str("NaN"),
+        Object::Float(number) if number.is_infinite() => out.push_str(if *number > 0.0 {
+            "Infinity"
+        } else {
+            "-Infinity"
+        }),
+        Object::Float(number) => {
+            let _ = write!(out, "{:?}", number);
+        }
+        Object::Decimal(number) => {
+            let _ = write!(out, "Decimal({:?})", number.to_string());
+        }
+        Object::String(text) => {
+            let _ = write!(out, "{:?}", text.as_str());
+        }
+        Object::Array(items) => {
+            if !enter(seen, items.as_ptr() as *const ()) {
+                out.push_str("[...]");
+                return;
+            }
+            out.push('[');
+            for (index, item) in items.borrow().iter().enumerate() {
+                if index > 0 {
+                    out.push_str(", ");
+                }
+                render_into(item, out, seen);
+            }
+            out.push(']');
+            seen.pop();
+        }
+        Object::Dict(entries) => {
+            if !enter(seen, entries.as_ptr() as *const ()) {
+                out.push_str("{...}");
+                return;
+            }
+            out.push('{');
+            for (index, (key, item)) in entries.borrow().iter().enumerate() {
+                if index > 0 {
+                    out.push_str(", ");
+                }
+                let _ = write!(out, "{:?} => ", key);
+                render_into(item, out, seen);
+            }
+            out.push('}');
+            seen.pop();
+        }
+        Object::Set(elements) => {
+            let mut elements: Vec<String> = elements
+                .borrow()
+                .iter()
+                .map(|element| element.hash_value.clone())
+                .collect();
+            elements.sort();
+            let _ = write!(out, "#{{{}}}", elements.join(", "));
+        }
+        Object::Instance(instance) => {
+            let class_name = instance.borrow().class.name().to_string();
+            if !enter(seen, instance.as_ptr() as *const ()) {
+                let _ = write!(out, "#<{} ...>", class_name);
+                return;
+            }
+            let _ = write!(out, "#<{}", class_name);
+            let instance = instance.borrow();
+            let vars: BTreeMap<_, _> = instance.instance_vars.iter().collect();
+            for (index, (name, item)) in vars.into_iter().enumerate() {
+                out.push_str(if index == 0 { " " } else { ", " });
+                let _ = write!(out, "@{}=", name);
+                render_into(item, out, seen);
+            }
+            out.push('>');
+            seen.pop();
+        }
+        Object::Method(method) => {
+            let _ = write!(
+                out,
+                "<method {}({})>",
+                method.name,
+                method.parameters.join(", ")
+            );
+        }
+        Object::Block(block) => {
+            let _ = write!(out, "<block |{}|>", block.parameters.join(", "));
+        }
+        Object::Exception(exception) => {
+            let exception = exception.borrow();
+            let _ = write!(
+                out,
+                "<{}: {:?}>",
+                exception.exception_type, exception.message
+            );
+        }
+        Object::Result(result) => {
+            let (tag, inner) = match result {
+                Ok(inner) => ("Ok", inner),
+                Err(inner) => ("Err", inner),
+            };
+            let _ = write!(out, "{}(", tag);
+            render_into(inner, out, seen);
+            out.push(')');
+        }
+        Object::Range {
+            start,
+            end,
+            exclusive,
+        } => {
+            render_into(start, out, seen);
+            out.push_str(if *exclusive { "..." } else { ".." });
+            render_into(end, out, seen);
+        }
+        Object::Binding(binding) => {
+            let mut names = binding.keys();
+            names.sort();
+            let _ = write!(out, "<binding {}>", names.join(", "));
+        }
+        other => {
+            let _ = write!(out, "{}", other);
+        }
+    }
+}
+
+/// Record a collection being rendered; false if it is already being rendered.
+fn enter(seen: &mut Vec<*const ()>, collection: *const ()) -> bool {
+    if seen.contains(&collection) {
+        return false;
+    }
+    seen.push(collection);
+    true
+}
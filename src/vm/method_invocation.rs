@@ -6,7 +6,7 @@
 use super::errors::*;
 use super::utils::*;
 use super::{CallFrame, ControlFlow, VirtualMachine};
-use crate::ast::Statement;
+use crate::ast::{DestructureTarget, FORWARDING_PARAMETER};
 use crate::callable::Callable;
 use crate::class::Class;
 use crate::error::{MetorexError, StackFrame};
@@ -27,19 +27,13 @@ impl VirtualMachine {
             Object::Block(block) => block.call(self, arguments, position),
             Object::Method(method) => {
                 // Call standalone function (represented as Method object)
-                // Validate argument count
-                let expected = method.parameters.len();
-                let found = arguments.len();
-                if expected != found {
-                    return Err(method_argument_error(
-                        &method.name,
-                        expected,
-                        found,
-                        position,
-                    ));
-                }
+                check_method_arity(&method, arguments.len(), position)?;
+                self.check_argument_types(&method, &arguments, position)?;
                 // Execute function body without self
-                self.execute_function_body(&method, arguments)
+                let result = self
+                    .with_call_depth(position, |vm| vm.execute_function_body(&method, arguments))?;
+                self.check_result_type(&method, &result, position)?;
+                Ok(result)
             }
             Object::Class(class) => {
                 // Check if this is an exception class
@@ -72,7 +66,7 @@ impl VirtualMachine {
                     let instance = Rc::new(RefCell::new(crate::object::Instance::new(Rc::clone(
                         &class,
                     ))));
-                    let instance_obj = Object::Instance(Rc::clone(&instance));
+                    let instance_obj = self.track_allocation(Object::Instance(instance));
 
                     // Look for an 'initialize' method and call it if present
                     if let Some(init_method) = class.find_method("initialize") {
@@ -127,7 +121,7 @@ impl VirtualMachine {
 
         let execution_result = self.with_call_frame(
             CallFrame::new(frame_name.clone(), frame_location_string),
-            move |vm| vm.execute_block_body(block, arguments),
+            move |vm| vm.with_call_depth(position, |vm| vm.execute_block_body(block, arguments)),
         );
 
         match execution_result {
@@ -151,51 +145,52 @@ impl VirtualMachine {
                     .define_shared(name.clone(), value_ref.clone());
             }
 
-            // Define parameters as regular variables
-            for (param, argument) in block.parameters().iter().zip(arguments.into_iter()) {
-                self.environment_mut().define(param.clone(), argument);
-            }
-
-            let mut last_value = Object::Nil;
-
-            for statement in block.body() {
-                if let Statement::Expression { expression, .. } = statement {
-                    last_value = self.evaluate_expression(expression)?;
-                    continue;
-                }
-
-                match self.execute_statement(statement)? {
-                    ControlFlow::Next => {}
-                    ControlFlow::Return { value, .. } => {
-                        last_value = value;
-                        break;
-                    }
-                    ControlFlow::Exception {
-                        exception,
-                        position,
-                    } => {
-                        return Err(MetorexError::UncaughtException {
-                            exception: exception.clone(),
-                            location: position_to_location(position),
-                            message: format_exception(&exception),
-                        });
-                    }
-                    ControlFlow::Break { position } => {
-                        return Err(loop_control_error("break", position));
-                    }
-                    ControlFlow::Continue { position } => {
-                        return Err(loop_control_error("continue", position));
-                    }
-                }
-            }
+            self.bind_block_parameters(block, arguments);
 
-            Ok(last_value)
+            let (flow, value) = self.execute_statements_for_value(block.body())?;
+            body_result(flow, value)
         })();
 
+        self.record_unwind(&result, || block.name().to_string());
         self.environment_mut().pop_scope();
         result
     }
 
+    /// Define a block's parameters as regular variables, unpacking the
+    /// arguments of parameters written as `(a, b)`.
+    fn bind_block_parameters(&mut self, block: &BlockStatement, arguments: Vec<Object>) {
+        for (index, (param, argument)) in block.parameters().iter().zip(arguments).enumerate() {
+            match block
+                .destructured
+                .iter()
+                .find(|pattern| pattern.index == index)
+            {
+                Some(pattern) => self.bind_destructured(&pattern.targets, argument),
+                None => self.environment_mut().define(param.clone(), argument),
+            }
+        }
+    }
+
+    /// Bind the elements of an Array to `targets`. Missing elements bind
+    /// nil and extra ones are ignored; any other value binds to the first
+    /// target, as a one-element Array would.
+    fn bind_destructured(&mut self, targets: &[DestructureTarget], value: Object) {
+        let elements = match &value {
+            Object::Array(items) => items.borrow().clone(),
+            _ => vec![value],
+        };
+        let mut elements = elements.into_iter();
+        for target in targets {
+            let element = elements.next().unwrap_or(Object::Nil);
+            match target {
+                DestructureTarget::Name(name) => {
+                    self.environment_mut().define(name.clone(), element);
+                }
+                DestructureTarget::Nested(inner) => self.bind_destructured(inner, element),
+            }
+        }
+    }
+
     /// Execute a block body and return ControlFlow (for use in iterators like .each)
     /// This version propagates Break/Continue instead of converting them to errors
     pub(crate) fn execute_block_with_control_flow(
@@ -212,10 +207,7 @@ impl VirtualMachine {
                     .define_shared(name.clone(), value_ref.clone());
             }
 
-            // Define parameters as regular variables
-            for (param, argument) in block.parameters().iter().zip(arguments.into_iter()) {
-                self.environment_mut().define(param.clone(), argument);
-            }
+            self.bind_block_parameters(block, arguments);
 
             for statement in block.body() {
                 match self.execute_statement(statement)? {
@@ -223,6 +215,7 @@ impl VirtualMachine {
                     flow @ (ControlFlow::Return { .. }
                     | ControlFlow::Break { .. }
                     | ControlFlow::Continue { .. }
+                    | ControlFlow::Retry { .. }
                     | ControlFlow::Exception { .. }) => {
                         return Ok(flow);
                     }
@@ -232,6 +225,7 @@ impl VirtualMachine {
             Ok(ControlFlow::Next)
         })();
 
+        self.record_unwind(&result, || block.name().to_string());
         self.environment_mut().pop_scope();
         result
     }
@@ -254,43 +248,59 @@ impl VirtualMachine {
             &arguments,
             position,
         )? {
+            self.argument_buffers.give_back(arguments);
             return Ok(result);
         }
 
-        let expected = method.parameters.len();
-        let found = arguments.len();
-        if expected != found {
-            return Err(method_argument_error(
-                &method_name,
-                expected,
-                found,
-                position,
-            ));
-        }
+        check_method_arity(&method, arguments.len(), position)?;
+        self.check_argument_types(&method, &arguments, position)?;
+
+        let self_for_body = method
+            .receiver()
+            .cloned()
+            .unwrap_or_else(|| receiver.clone());
+        self.check_preconditions(&class, &method, &self_for_body, &arguments, position)?;
+        // Postconditions see the arguments the body consumes
+        let contract_arguments = if method.postconditions.is_empty() {
+            Vec::new()
+        } else {
+            arguments.clone()
+        };
 
         let frame_name = format!("{}#{}", class.name(), method_name);
         let frame_location = position_to_location(position);
         let frame_location_string = Some(format!("{}", frame_location));
 
         let method_for_body = Rc::clone(&method);
-        let self_for_body = method
-            .receiver()
-            .cloned()
-            .unwrap_or_else(|| receiver.clone());
-        let arguments_for_body = arguments.clone();
+        let class_for_body = Rc::clone(&class);
+        let receiver_for_body = self_for_body.clone();
         let execution_result = self.with_call_frame(
             CallFrame::new(frame_name.clone(), frame_location_string),
             move |vm| {
-                vm.execute_method_body(
-                    method_for_body.as_ref(),
-                    self_for_body.clone(),
-                    arguments_for_body.clone(),
-                )
+                vm.with_call_depth(position, |vm| {
+                    vm.execute_method_body(
+                        class_for_body.as_ref(),
+                        method_for_body.as_ref(),
+                        receiver_for_body,
+                        arguments,
+                    )
+                })
             },
         );
 
         match execution_result {
-            Ok(value) => Ok(value),
+            Ok(value) => {
+                self.check_result_type(&method, &value, position)?;
+                self.check_postconditions(
+                    &class,
+                    &method,
+                    &self_for_body,
+                    &value,
+                    &contract_arguments,
+                    position,
+                )?;
+                Ok(value)
+            }
             Err(error) => Err(error.with_stack_frame(StackFrame::new(frame_name, frame_location))),
         }
     }
@@ -298,58 +308,30 @@ impl VirtualMachine {
     /// Execute the body of a method within a fresh scope.
     pub(crate) fn execute_method_body(
         &mut self,
+        class: &Class,
         method: &Method,
         self_value: Object,
         arguments: Vec<Object>,
     ) -> Result<Object, MetorexError> {
         self.environment_mut().push_scope();
+        let outer_nesting = self.enter_method_nesting(method);
 
+        let mut traced = false;
         let result = (|| -> Result<Object, MetorexError> {
+            self.bind_captured_vars(method);
             self.environment_mut()
                 .define("self".to_string(), self_value.clone());
 
-            for (param, value) in method.parameters.iter().zip(arguments.into_iter()) {
-                self.environment_mut().define(param.clone(), value);
-            }
-
-            // Execute all statements, tracking the last expression value
-            let body = method.body();
-            let mut last_value = Object::Nil;
-
-            for (i, statement) in body.iter().enumerate() {
-                let is_last = i == body.len() - 1;
-
-                // If this is the last statement and it's an expression, capture its value
-                if is_last && let Statement::Expression { expression, .. } = statement {
-                    last_value = self.evaluate_expression(expression)?;
-                    continue;
-                }
-
-                match self.execute_statement(statement)? {
-                    ControlFlow::Next => continue,
-                    ControlFlow::Return { value, .. } => return Ok(value),
-                    ControlFlow::Exception {
-                        exception,
-                        position,
-                    } => {
-                        return Err(MetorexError::UncaughtException {
-                            exception: exception.clone(),
-                            location: position_to_location(position),
-                            message: format_exception(&exception),
-                        });
-                    }
-                    ControlFlow::Break { position } => {
-                        return Err(loop_control_error("break", position));
-                    }
-                    ControlFlow::Continue { position } => {
-                        return Err(loop_control_error("continue", position));
-                    }
-                }
-            }
+            self.bind_method_arguments(method, arguments);
+            traced = self.trace_call(method, Some(class.name()))?;
 
-            Ok(last_value)
+            let (flow, value) = self.execute_statements_for_value(method.body())?;
+            body_result(flow, value)
         })();
 
+        let result = self.trace_return(traced, result);
+        self.record_unwind(&result, || format!("{}#{}", class.name(), method.name));
+        self.nesting = outer_nesting;
         self.environment_mut().pop_scope();
         result
     }
@@ -361,53 +343,49 @@ impl VirtualMachine {
         arguments: Vec<Object>,
     ) -> Result<Object, MetorexError> {
         self.environment_mut().push_scope();
+        let outer_nesting = self.enter_method_nesting(function);
 
+        let mut traced = false;
         let result = (|| -> Result<Object, MetorexError> {
             // Bind parameters to arguments (no self for standalone functions)
-            for (param, value) in function.parameters.iter().zip(arguments.into_iter()) {
-                self.environment_mut().define(param.clone(), value);
-            }
+            self.bind_captured_vars(function);
+            self.bind_method_arguments(function, arguments);
+            traced = self.trace_call(function, None)?;
 
-            // Execute all statements, tracking the last expression value
-            let body = function.body();
-            let mut last_value = Object::Nil;
+            let (flow, value) = self.execute_statements_for_value(function.body())?;
+            body_result(flow, value)
+        })();
 
-            for (i, statement) in body.iter().enumerate() {
-                let is_last = i == body.len() - 1;
+        let result = self.trace_return(traced, result);
+        self.record_unwind(&result, || function.name.clone());
+        self.nesting = outer_nesting;
+        self.environment_mut().pop_scope();
+        result
+    }
 
-                // If this is the last statement and it's an expression, capture its value
-                if is_last && let Statement::Expression { expression, .. } = statement {
-                    last_value = self.evaluate_expression(expression)?;
-                    continue;
-                }
+    /// Share the variables captured by the block a method was defined from.
+    fn bind_captured_vars(&mut self, method: &Method) {
+        for (name, value_ref) in &method.captured_vars {
+            self.environment_mut()
+                .define_shared(name.clone(), value_ref.clone());
+        }
+    }
 
-                match self.execute_statement(statement)? {
-                    ControlFlow::Next => continue,
-                    ControlFlow::Return { value, .. } => return Ok(value),
-                    ControlFlow::Exception {
-                        exception,
-                        position,
-                    } => {
-                        return Err(MetorexError::UncaughtException {
-                            exception: exception.clone(),
-                            location: position_to_location(position),
-                            message: format_exception(&exception),
-                        });
-                    }
-                    ControlFlow::Break { position } => {
-                        return Err(loop_control_error("break", position));
-                    }
-                    ControlFlow::Continue { position } => {
-                        return Err(loop_control_error("continue", position));
-                    }
-                }
-            }
+    /// Bind arguments to a method's parameters in the current scope.
+    /// Arguments beyond the named parameters are collected into the `...` parameter.
+    fn bind_method_arguments(&mut self, method: &Method, mut arguments: Vec<Object>) {
+        let required = method.required_arity();
+        let forwarded = arguments.split_off(required.min(arguments.len()));
 
-            Ok(last_value)
-        })();
+        for (param, value) in method.parameters.iter().zip(arguments.drain(..)) {
+            self.environment_mut().define(param.clone(), value);
+        }
+        self.argument_buffers.give_back(arguments);
 
-        self.environment_mut().pop_scope();
-        result
+        if method.forwards_arguments() {
+            self.environment_mut()
+                .define(FORWARDING_PARAMETER.to_string(), Object::array(forwarded));
+        }
     }
 
     /// Check if a class is an exception class (Exception or its subclasses)
@@ -438,3 +416,51 @@ impl VirtualMachine {
         false
     }
 }
+
+/// The result of a method, function or block body that finished with `flow`,
+/// where `value` is the value of its last statement.
+pub(super) fn body_result(flow: ControlFlow, value: Object) -> Result<Object, MetorexError> {
+    match flow {
+        ControlFlow::Next => Ok(value),
+        ControlFlow::Return { value, .. } => Ok(value),
+        ControlFlow::Exception {
+            exception,
+            position,
+        } => Err(MetorexError::UncaughtException {
+            message: format_exception(&exception),
+            exception,
+            location: position_to_location(position),
+            catalog_message: None,
+        }),
+        ControlFlow::Break { position } => Err(loop_control_error("break", position)),
+        ControlFlow::Continue { position } => Err(loop_control_error("continue", position)),
+        ControlFlow::Retry { position } => Err(retry_outside_rescue_error(position)),
+    }
+}
+
+/// Validate the number of arguments passed to a method or standalone function.
+fn check_method_arity(
+    method: &Method,
+    found: usize,
+    position: Position,
+) -> Result<(), MetorexError> {
+    let required = method.required_arity();
+    if method.forwards_arguments() {
+        if found < required {
+            return Err(method_minimum_argument_error(
+                &method.name,
+                required,
+                found,
+                position,
+            ));
+        }
+    } else if required != found {
+        return Err(method_argument_error(
+            &method.name,
+            required,
+            found,
+            position,
+        ));
+    }
+    Ok(())
+}
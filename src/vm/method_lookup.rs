@@ -23,10 +23,7 @@ impl VirtualMachine {
         position: Position,
     ) -> Result<Object, MetorexError> {
         let receiver = self.evaluate_expression(receiver_expr)?;
-        let mut arguments = Vec::with_capacity(argument_exprs.len());
-        for argument in argument_exprs {
-            arguments.push(self.evaluate_expression(argument)?);
-        }
+        let mut arguments = self.evaluate_arguments(argument_exprs)?;
 
         // If there's a trailing block, evaluate it and append to arguments
         if let Some(block_expr) = trailing_block {
@@ -34,46 +31,109 @@ impl VirtualMachine {
             arguments.push(block_obj);
         }
 
-        match self.lookup_method(&receiver, method_name) {
-            Some((class, method)) => {
-                self.invoke_method(class, method, receiver, arguments, position)
+        // Private methods can only be called with an implicit receiver or `self.`
+        let explicit_receiver = !match receiver_expr {
+            Expression::SelfExpr { .. } => true,
+            Expression::Identifier { name, .. } => name == "self",
+            _ => false,
+        };
+        self.dispatch_method(
+            receiver,
+            method_name,
+            arguments,
+            explicit_receiver,
+            position,
+        )
+    }
+
+    /// Call `method_name` on an evaluated receiver, falling back to native
+    /// methods and then `method_missing`. Private methods are only reachable
+    /// when `explicit_receiver` is false.
+    pub(crate) fn dispatch_method(
+        &mut self,
+        receiver: Object,
+        method_name: &str,
+        arguments: Vec<Object>,
+        explicit_receiver: bool,
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        match self.try_dispatch_method(
+            receiver.clone(),
+            method_name,
+            arguments,
+            explicit_receiver,
+            position,
+        )? {
+            Some(result) => Ok(result),
+            None if explicit_receiver
+                && self
+                    .lookup_method(&receiver, method_name)
+                    .is_some_and(|(class, _)| class.is_private_method(method_name)) =>
+            {
+                Err(private_method_error(method_name, &receiver, position))
             }
-            None => {
-                // Try native method as fallback
-                let class = self.builtins().class_of(&receiver);
-                if let Some(result) =
-                    self.call_native_method(&class, &receiver, method_name, &arguments, position)?
-                {
-                    Ok(result)
-                } else {
-                    // Try method_missing as a final fallback
-                    if let Some((method_missing_class, method_missing_method)) =
-                        self.lookup_method(&receiver, "method_missing")
-                    {
-                        // Call method_missing with the method name as a string argument
-                        let method_name_obj = Object::String(Rc::new(method_name.to_string()));
-                        self.invoke_method(
-                            method_missing_class,
-                            method_missing_method,
-                            receiver,
-                            vec![method_name_obj],
-                            position,
-                        )
-                    } else {
-                        Err(undefined_method_error(method_name, &receiver, position))
-                    }
-                }
+            None => Err(undefined_method_error(method_name, &receiver, position)),
+        }
+    }
+
+    /// Like `dispatch_method`, but returns `None` instead of failing when
+    /// nothing, not even `method_missing`, handles the call.
+    pub(crate) fn try_dispatch_method(
+        &mut self,
+        receiver: Object,
+        method_name: &str,
+        arguments: Vec<Object>,
+        explicit_receiver: bool,
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let found = self
+            .lookup_method(&receiver, method_name)
+            .filter(|(class, _)| !(explicit_receiver && class.is_private_method(method_name)));
+
+        if let Some((class, method)) = found {
+            return self
+                .invoke_method(class, method, receiver, arguments, position)
+                .map(Some);
+        }
+
+        // Try native method as fallback
+        let class = self.builtins().class_of(&receiver);
+        if let Some(result) =
+            self.call_native_method(&class, &receiver, method_name, &arguments, position)?
+        {
+            self.argument_buffers.give_back(arguments);
+            return Ok(Some(result));
+        }
+
+        // Try method_missing as a final fallback
+        match self.lookup_method(&receiver, "method_missing") {
+            Some((method_missing_class, method_missing_method)) => {
+                // Call method_missing with the method name as a string argument
+                let method_name_obj = Object::String(Rc::new(method_name.to_string()));
+                self.invoke_method(
+                    method_missing_class,
+                    method_missing_method,
+                    receiver,
+                    vec![method_name_obj],
+                    position,
+                )
+                .map(Some)
             }
+            None => Ok(None),
         }
     }
 
     /// Look up a method on the receiver and return its class and method definition.
+    ///
+    /// User classes without a superclass do not inherit from Object's method
+    /// table, so the private methods top-level `def`s add to Object are
+    /// checked last for every receiver.
     pub(crate) fn lookup_method(
         &self,
         receiver: &Object,
         method_name: &str,
     ) -> Option<(Rc<Class>, Rc<Method>)> {
-        match receiver {
+        let found = match receiver {
             Object::Instance(instance_rc) => {
                 let instance_ref = instance_rc.borrow();
                 let class = Rc::clone(&instance_ref.class);
@@ -81,12 +141,22 @@ impl VirtualMachine {
                 class.find_method(method_name).map(|method| (class, method))
             }
             Object::Class(class_rc) => class_rc
-                .find_method(method_name)
+                .find_class_method(method_name)
                 .map(|method| (Rc::clone(class_rc), method)),
             _ => {
                 let class = self.builtins().class_of(receiver);
                 class.find_method(method_name).map(|method| (class, method))
             }
-        }
+        };
+        found.or_else(|| {
+            let object_class = &self.builtins().object_class;
+            if object_class.is_private_method(method_name) {
+                object_class
+                    .find_method(method_name)
+                    .map(|method| (Rc::clone(object_class), method))
+            } else {
+                None
+            }
+        })
     }
 }
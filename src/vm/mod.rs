@@ -4,27 +4,66 @@
 
 mod call_frame;
 mod class_execution;
+mod collector;
+mod constants;
+mod contracts;
 mod control_flow;
 mod control_structures;
 mod core;
+mod definitions;
 mod errors;
+mod eval;
+mod event_log;
 mod exceptions;
 mod expression;
 mod global_registry;
 mod heap;
 mod init;
+mod input;
+mod int_conversion;
+mod interrupt;
+pub mod introspect;
 mod method_invocation;
 mod method_lookup;
 mod native_functions;
 mod native_methods;
+mod native_signatures;
 mod operators;
+mod options;
+mod output;
 mod pattern_matching;
+mod post_mortem;
+mod random;
+mod reload;
+mod resources;
+mod scratch;
+mod snapshot;
+#[cfg(feature = "sqlite")]
+mod sqlite;
 mod statement;
+mod string_interner;
+mod teardown;
+mod timeout;
+mod trace;
+mod type_checks;
+mod type_feedback;
 mod utils;
 
 pub use call_frame::CallFrame;
-pub use core::VirtualMachine;
+pub use core::{DEFAULT_MAX_RETRIES, FloatDivisionPolicy, VirtualMachine};
+pub use definitions::{DefinitionCheckpoint, DefinitionDiff};
+pub use event_log::{Divergence, EventLog, LoggedEvent, Replay};
 pub use global_registry::GlobalRegistry;
-pub use heap::Heap;
+pub use heap::{ClassStats, DEFAULT_COLLECTION_THRESHOLD, Heap, HeapStats, Leak, LeakReport};
+pub use interrupt::{INTERRUPT_EXCEPTION, VmHandle};
+pub use native_methods::NativeMethod;
+pub use options::{DEFAULT_MAX_STACK_DEPTH, MAX_OPTIMIZATION_LEVEL, MX_OPTS_VAR, VmOptions};
+pub use post_mortem::{MAIN_FRAME, PostMortem, PostMortemFrame};
+pub use scratch::ArgumentBuffers;
+pub use snapshot::VmSnapshot;
+pub use string_interner::StringInterner;
+pub use timeout::{DEFAULT_RETRY_ATTEMPTS, DEFAULT_RETRY_BACKOFF, TIMEOUT_EXCEPTION};
+pub use trace::{HookId, TraceEvent, TraceEventKind, VmHooks};
+pub use type_feedback::{HOT_THRESHOLD, TypeFeedbackStats};
 
 pub(crate) use control_flow::ControlFlow;
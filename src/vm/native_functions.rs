@@ -3,9 +3,20 @@
 //! This module contains implementations of global built-in functions like puts, print, etc.
 
 use super::VirtualMachine;
+use super::errors::method_argument_error;
 use crate::error::MetorexError;
 use crate::lexer::Position;
 use crate::object::Object;
+use crate::pretty_print::PrettyPrinter;
+use std::time::{Duration, Instant};
+
+/// Native functions that run when named without parentheses, like Ruby methods.
+const AUTO_CALLED_FUNCTIONS: [&str; 2] = ["gets", "rand"];
+
+/// Whether naming `function` on its own calls it rather than referring to it.
+pub(crate) fn is_auto_called_function(function: &str) -> bool {
+    AUTO_CALLED_FUNCTIONS.contains(&function)
+}
 
 impl VirtualMachine {
     /// Call a native function by name.
@@ -15,16 +26,41 @@ impl VirtualMachine {
         arguments: Vec<Object>,
         position: Position,
     ) -> Result<Object, MetorexError> {
+        let arguments = self
+            .check_builtin_function_arguments(name, &arguments, position)?
+            .unwrap_or(arguments);
+
         match name {
             "puts" => {
                 // puts prints each argument on a new line
                 for arg in &arguments {
                     // Try to call to_s or inspect method if it exists on the object
                     let output = self.get_string_representation(arg, position)?;
-                    println!("{}", output);
+                    self.write_output(&format!("{}\n", output));
                 }
                 Ok(Object::Nil)
             }
+            "pp" => {
+                // pp prints each argument with nested values laid out over
+                // multiple lines; a trailing `width:` option overrides the width
+                let mut arguments = arguments;
+                let printer = match pretty_print_options(&arguments) {
+                    Some(width) => {
+                        arguments.pop();
+                        PrettyPrinter::with_width(pretty_print_width(&width, position)?)
+                    }
+                    None => self.pretty_printer(),
+                };
+                for arg in &arguments {
+                    let output = printer.format(arg);
+                    self.write_output(&format!("{}\n", output));
+                }
+                Ok(match arguments.len() {
+                    0 => Object::Nil,
+                    1 => arguments.remove(0),
+                    _ => Object::array(arguments),
+                })
+            }
             "method" => {
                 // method(:name) returns a Method object for the given method name
                 if arguments.len() != 1 {
@@ -107,27 +143,16 @@ impl VirtualMachine {
                             )
                         })?;
 
-                // Find the actual file path with extension auto-detection
-                let actual_path =
-                    crate::file_loader::find_file_path(&resolved_path).map_err(|e| {
+                // Find the file and canonicalize it, as execute_file does, for
+                // deduplication checking
+                let canonical_path = crate::file_loader::canonical_file_path(&resolved_path)
+                    .map_err(|e| {
                         MetorexError::runtime_error(
                             format!("Error in require_relative: {}", e),
                             crate::vm::utils::position_to_location(position),
                         )
                     })?;
 
-                // Canonicalize to get the absolute path for deduplication checking
-                let canonical_path = actual_path.canonicalize().map_err(|e| {
-                    MetorexError::runtime_error(
-                        format!(
-                            "Failed to canonicalize path '{}': {}",
-                            actual_path.display(),
-                            e
-                        ),
-                        crate::vm::utils::position_to_location(position),
-                    )
-                })?;
-
                 // Check if file was already loaded BEFORE executing
                 let was_already_loaded = self.is_file_loaded(&canonical_path);
 
@@ -142,6 +167,141 @@ impl VirtualMachine {
                 // Return true if newly loaded, false if already loaded (Ruby behavior)
                 Ok(Object::Bool(!was_already_loaded))
             }
+            "require" => {
+                // require(name) loads a library from the load path, once
+                let name = match arguments.as_slice() {
+                    [Object::String(name)] => name.as_ref(),
+                    _ => {
+                        return Err(MetorexError::runtime_error(
+                            "require() expects a library name",
+                            crate::vm::utils::position_to_location(position),
+                        ));
+                    }
+                };
+                self.require_library(name).map(Object::Bool).map_err(|e| {
+                    MetorexError::runtime_error(
+                        format!("Error in require: {}", e),
+                        crate::vm::utils::position_to_location(position),
+                    )
+                })
+            }
+            "reload!" => {
+                // reload!(path) re-runs a required file, redefining its classes in place
+                let path = match arguments.as_slice() {
+                    [Object::String(path)] => path.as_ref(),
+                    [other] => {
+                        return Err(MetorexError::runtime_error(
+                            format!(
+                                "reload!() expects a String argument, got {}",
+                                other.type_name()
+                            ),
+                            crate::vm::utils::position_to_location(position),
+                        ));
+                    }
+                    _ => {
+                        return Err(MetorexError::runtime_error(
+                            format!("reload!() expects 1 argument, got {}", arguments.len()),
+                            crate::vm::utils::position_to_location(position),
+                        ));
+                    }
+                };
+
+                // Paths are relative to the calling file, or the working directory in the REPL
+                let resolved_path = match self.get_current_file() {
+                    Some(current_file) => {
+                        crate::file_loader::resolve_relative_path(current_file, path).map_err(
+                            |e| {
+                                MetorexError::runtime_error(
+                                    format!("Failed to resolve path '{}': {}", path, e),
+                                    crate::vm::utils::position_to_location(position),
+                                )
+                            },
+                        )?
+                    }
+                    None => std::path::PathBuf::from(path),
+                };
+
+                self.reload_file(&resolved_path).map_err(|e| {
+                    MetorexError::runtime_error(
+                        format!("Error in reload!: {}", e),
+                        crate::vm::utils::position_to_location(position),
+                    )
+                })?;
+
+                Ok(Object::Bool(true))
+            }
+            "with" => {
+                // with(resource) do |r| ... end closes the resource once the block is done
+                let mut arguments = arguments.into_iter();
+                match (arguments.next(), arguments.next(), arguments.next()) {
+                    (Some(resource), Some(block @ Object::Block(_)), None) => {
+                        self.with_resource(resource, block, position)
+                    }
+                    _ => Err(MetorexError::runtime_error(
+                        "with() expects a resource and a block",
+                        crate::vm::utils::position_to_location(position),
+                    )),
+                }
+            }
+            "gets" => {
+                // gets reads one line of input, after printing an optional prompt
+                match arguments.as_slice() {
+                    [] => self.read_input_line(None, position),
+                    [Object::String(prompt)] => self.read_input_line(Some(prompt), position),
+                    _ => Err(MetorexError::runtime_error(
+                        "gets() expects an optional prompt String",
+                        crate::vm::utils::position_to_location(position),
+                    )),
+                }
+            }
+            "rand" => {
+                // rand returns a Float in [0, 1), or an Integer in [0, n) given n
+                match arguments.as_slice() {
+                    [] => Ok(Object::Float(self.random.next_float())),
+                    [Object::Int(bound)] if *bound > 0 => {
+                        Ok(Object::Int(self.random.next_below(*bound)))
+                    }
+                    _ => Err(MetorexError::runtime_error(
+                        "rand() expects no arguments or a positive Integer",
+                        crate::vm::utils::position_to_location(position),
+                    )),
+                }
+            }
+            "sleep" => {
+                // sleep pauses for a whole or fractional number of seconds
+                let seconds = match arguments.as_slice() {
+                    [Object::Int(seconds)] => *seconds as f64,
+                    [Object::Float(seconds)] => *seconds,
+                    _ => {
+                        return Err(MetorexError::runtime_error(
+                            "sleep() expects a number of seconds",
+                            crate::vm::utils::position_to_location(position),
+                        ));
+                    }
+                };
+                let deadline = Duration::try_from_secs_f64(seconds)
+                    .ok()
+                    .and_then(|duration| Instant::now().checked_add(duration))
+                    .ok_or_else(|| {
+                        MetorexError::runtime_error(
+                            format!("sleep() cannot pause for {} seconds", seconds),
+                            crate::vm::utils::position_to_location(position),
+                        )
+                    })?;
+                self.sleep_until(deadline, position)?;
+                Ok(Object::Int(seconds.round() as i64))
+            }
+            "format" | "sprintf" => {
+                let Some((Object::String(template), values)) = arguments.split_first() else {
+                    return Err(method_argument_error(name, 1, arguments.len(), position));
+                };
+                self.format_string(name, template, values, position)
+                    .map(Object::string)
+            }
+            "assert" => self.assert(&arguments, position),
+            "raise_unless" => self.raise_unless(&arguments, position),
+            "retry_on" => self.retry_on(&arguments, position),
+            "eval" => self.eval_source(&arguments, position),
             _ => Err(MetorexError::runtime_error(
                 format!("Unknown native function: {}", name),
                 crate::vm::utils::position_to_location(position),
@@ -149,8 +309,26 @@ impl VirtualMachine {
         }
     }
 
+    /// Sleep in short slices so an interrupt from a `VmHandle` cuts the
+    /// pause short instead of waiting for it to finish.
+    pub(super) fn sleep_until(
+        &self,
+        deadline: Instant,
+        position: Position,
+    ) -> Result<(), MetorexError> {
+        const SLICE: Duration = Duration::from_millis(10);
+        loop {
+            self.check_interrupt(position)?;
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(());
+            }
+            std::thread::sleep(remaining.min(SLICE));
+        }
+    }
+
     /// Get the string representation of an object by calling to_s or inspect if available.
-    fn get_string_representation(
+    pub(crate) fn get_string_representation(
         &mut self,
         obj: &Object,
         position: Position,
@@ -181,3 +359,35 @@ impl VirtualMachine {
         }
     }
 }
+
+/// Extract the `width:` keyword option passed as the trailing Hash of a `pp` call.
+fn pretty_print_options(arguments: &[Object]) -> Option<Object> {
+    if arguments.len() < 2 {
+        return None;
+    }
+    match arguments.last() {
+        Some(Object::Dict(options)) => {
+            let options = options.borrow();
+            if options.len() == 1 {
+                options.get(":width").cloned()
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Validate a `pp` width option.
+pub(super) fn pretty_print_width(
+    width: &Object,
+    position: Position,
+) -> Result<usize, MetorexError> {
+    match width {
+        Object::Int(width) if *width > 0 => Ok(*width as usize),
+        other => Err(MetorexError::runtime_error(
+            format!("pp width must be a positive Int, got {}", other),
+            crate::vm::utils::position_to_location(position),
+        )),
+    }
+}
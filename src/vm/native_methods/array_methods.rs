@@ -1,11 +1,14 @@
 //! Native method implementations for the Array class.
 
+use super::iteration::optional_block;
 use crate::error::MetorexError;
 use crate::lexer::Position;
 use crate::object::Object;
+use crate::text_table::{Align, Table, TableStyle};
 use crate::vm::VirtualMachine;
 use crate::vm::errors::*;
 use crate::vm::utils::position_to_location;
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -44,7 +47,10 @@ impl VirtualMachine {
                     ));
                 }
                 if let Object::Array(array_rc) = receiver {
-                    array_rc.borrow_mut().push(arguments[0].clone());
+                    array_rc
+                        .try_borrow_mut()
+                        .map_err(|_| collection_in_use_error(receiver, position))?
+                        .push(arguments[0].clone());
                     Ok(Some(receiver.clone()))
                 } else {
                     Ok(None)
@@ -60,7 +66,11 @@ impl VirtualMachine {
                     ));
                 }
                 if let Object::Array(array_rc) = receiver {
-                    Ok(Some(array_rc.borrow_mut().pop().unwrap_or(Object::Nil)))
+                    let popped = array_rc
+                        .try_borrow_mut()
+                        .map_err(|_| collection_in_use_error(receiver, position))?
+                        .pop();
+                    Ok(Some(popped.unwrap_or(Object::Nil)))
                 } else {
                     Ok(None)
                 }
@@ -80,7 +90,18 @@ impl VirtualMachine {
                     position,
                 )?))
             }
-            "each" => {
+            // Iterators called without a block return an Enumerator over the
+            // elements
+            "each" | "each_mutating!" | "map" | "select" | "filter" | "sort_by"
+                if arguments.is_empty() =>
+            {
+                let Object::Array(array_rc) = receiver else {
+                    return Ok(None);
+                };
+                let elements = array_rc.borrow().clone();
+                self.yield_each(receiver, None, elements.into_iter())
+            }
+            "each" | "each_mutating!" => {
                 // each takes a block parameter
                 if arguments.len() != 1 {
                     return Err(method_argument_error(
@@ -103,15 +124,31 @@ impl VirtualMachine {
                         }
                     };
 
-                    let array = array_rc.borrow();
-                    for element in array.iter() {
-                        let args = vec![element.clone()];
-                        match self.execute_block_with_control_flow(&block, args)? {
+                    // each walks a snapshot, so the block may change the array
+                    // without changing what is visited. each_mutating! walks the
+                    // live array: it visits elements the block appends or
+                    // replaces, but cannot follow the block removing elements.
+                    let snapshot = (method_name == "each").then(|| array_rc.borrow().clone());
+                    let mut index = 0;
+                    loop {
+                        let element = match &snapshot {
+                            Some(elements) => elements.get(index).cloned(),
+                            None => array_rc.borrow().get(index).cloned(),
+                        };
+                        let Some(element) = element else {
+                            break;
+                        };
+                        let length = array_rc.borrow().len();
+
+                        match self.execute_block_with_control_flow(&block, vec![element])? {
                             super::super::ControlFlow::Next
-                            | super::super::ControlFlow::Continue { .. } => {
-                                continue;
-                            }
+                            | super::super::ControlFlow::Continue { .. } => {}
                             super::super::ControlFlow::Break { .. } => break,
+                            super::super::ControlFlow::Retry { position } => {
+                                return Err(super::super::errors::retry_outside_rescue_error(
+                                    position,
+                                ));
+                            }
                             super::super::ControlFlow::Return { value: _, position } => {
                                 return Err(super::super::errors::loop_control_error(
                                     "return", position,
@@ -130,12 +167,33 @@ impl VirtualMachine {
                                 ));
                             }
                         }
+
+                        if snapshot.is_none() && array_rc.borrow().len() < length {
+                            return Err(concurrent_modification_error(
+                                format!("Array shrank during {}", method_name),
+                                position,
+                            ));
+                        }
+                        index += 1;
                     }
                     Ok(Some(receiver.clone()))
                 } else {
                     Ok(None)
                 }
             }
+            "each_with_index" => {
+                let Object::Array(array_rc) = receiver else {
+                    return Ok(None);
+                };
+                let block = optional_block(method_name, arguments, 0, position)?;
+                // Like each, walk a snapshot so the block may change the array
+                let elements = array_rc.borrow().clone();
+                let groups = elements
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, element)| vec![element, Object::Int(index as i64)]);
+                self.yield_each_values(receiver, block, groups)
+            }
             "map" => {
                 // map takes a block parameter
                 if arguments.len() != 1 {
@@ -159,7 +217,8 @@ impl VirtualMachine {
                         }
                     };
 
-                    let array = array_rc.borrow();
+                    // Iterate over a snapshot so the block may change the array
+                    let array = array_rc.borrow().clone();
                     let mut results = Vec::new();
                     for element in array.iter() {
                         let args = vec![element.clone()];
@@ -194,7 +253,7 @@ impl VirtualMachine {
                         }
                     };
 
-                    let array = array_rc.borrow();
+                    let array = array_rc.borrow().clone();
                     let mut results = Vec::new();
                     for element in array.iter() {
                         let args = vec![element.clone()];
@@ -210,6 +269,50 @@ impl VirtualMachine {
                     Ok(None)
                 }
             }
+            "grep" => {
+                // grep keeps the elements the pattern matches with `===`, and
+                // maps them through the block when one is given
+                if arguments.is_empty() || arguments.len() > 2 {
+                    return Err(method_argument_error(
+                        method_name,
+                        1,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                if let Object::Array(array_rc) = receiver {
+                    let block = match arguments.get(1) {
+                        Some(Object::Block(block)) => Some(block.clone()),
+                        Some(other) => {
+                            return Err(method_argument_type_error(
+                                method_name,
+                                "Block",
+                                other,
+                                position,
+                            ));
+                        }
+                        None => None,
+                    };
+
+                    // The pattern and block may change the array, so iterate over a snapshot
+                    let elements = array_rc.borrow().clone();
+                    let mut results = Vec::new();
+                    for element in elements {
+                        if !self.case_equals(&arguments[0], &element, position)? {
+                            continue;
+                        }
+                        match &block {
+                            Some(block) => {
+                                results.push(self.execute_block_body(block, vec![element])?)
+                            }
+                            None => results.push(element),
+                        }
+                    }
+                    Ok(Some(Object::Array(Rc::new(RefCell::new(results)))))
+                } else {
+                    Ok(None)
+                }
+            }
             "reduce" => {
                 // reduce takes a block parameter with 2 arguments (accumulator, element)
                 // and optionally an initial value as the first argument
@@ -222,7 +325,7 @@ impl VirtualMachine {
                     ));
                 }
                 if let Object::Array(array_rc) = receiver {
-                    let array = array_rc.borrow();
+                    let array = array_rc.borrow().clone();
 
                     // Check if we have an initial value
                     let (block, initial_value, start_index) = if arguments.len() == 2 {
@@ -381,7 +484,320 @@ impl VirtualMachine {
                     Ok(None)
                 }
             }
+            "sort" => {
+                // sort is stable; the optional block compares two elements
+                // and returns a negative number, zero or a positive number
+                let block = optional_block(method_name, arguments, 0, position)?;
+                let Object::Array(array_rc) = receiver else {
+                    return Ok(None);
+                };
+                let array = array_rc.borrow().clone();
+                let sorted = self.sort_values(array, block.as_deref(), position)?;
+                Ok(Some(Object::array(sorted)))
+            }
+            "sort_by" => {
+                if arguments.len() != 1 {
+                    return Err(method_argument_error(
+                        method_name,
+                        1,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let Object::Array(array_rc) = receiver else {
+                    return Ok(None);
+                };
+                let block = match &arguments[0] {
+                    Object::Block(block) => block.clone(),
+                    other => {
+                        return Err(method_argument_type_error(
+                            method_name,
+                            "Block",
+                            other,
+                            position,
+                        ));
+                    }
+                };
+
+                // Compute each element's key once up front rather than on
+                // every comparison
+                let array = array_rc.borrow().clone();
+                let mut keyed = Vec::with_capacity(array.len());
+                for element in array {
+                    let sort_key = self.execute_block_body(&block, vec![element.clone()])?;
+                    keyed.push((sort_key, element));
+                }
+                let sorted = self.sort_keyed(keyed, position)?;
+                Ok(Some(Object::array(sorted)))
+            }
+            "join" => {
+                let Object::Array(array_rc) = receiver else {
+                    return Ok(None);
+                };
+                let separator = match arguments {
+                    [] => "",
+                    [Object::String(separator)] => separator.as_str(),
+                    [other] => {
+                        return Err(method_argument_type_error(
+                            method_name,
+                            "String",
+                            other,
+                            position,
+                        ));
+                    }
+                    _ => {
+                        return Err(method_argument_error(
+                            method_name,
+                            1,
+                            arguments.len(),
+                            position,
+                        ));
+                    }
+                };
+                let joined = join_array(array_rc, separator, &mut Vec::new(), position)?;
+                Ok(Some(Object::string(joined)))
+            }
+            "to_table" => {
+                let Object::Array(array_rc) = receiver else {
+                    return Ok(None);
+                };
+                let (headers, style) = table_options(method_name, arguments, position)?;
+                let table = build_table(&array_rc.borrow(), headers, position)?;
+                Ok(Some(Object::string(table.render(style))))
+            }
             _ => Ok(None),
         }
     }
 }
+
+/// Join the elements of `array` for `Array#join`, writing them into one
+/// String sized up front. Nested arrays are joined with the same separator;
+/// `open` holds the arrays being joined so a cycle raises instead of looping.
+fn join_array(
+    array: &Rc<RefCell<Vec<Object>>>,
+    separator: &str,
+    open: &mut Vec<*const RefCell<Vec<Object>>>,
+    position: Position,
+) -> Result<String, MetorexError> {
+    if open.contains(&Rc::as_ptr(array)) {
+        return Err(raised_exception_error(
+            "ArgumentError",
+            "recursive array join".to_string(),
+            position,
+        ));
+    }
+    open.push(Rc::as_ptr(array));
+
+    let items = array.borrow();
+    let mut pieces = Vec::with_capacity(items.len());
+    for item in items.iter() {
+        let piece = match item {
+            Object::String(text) | Object::Symbol(text) => Cow::Borrowed(text.as_str()),
+            Object::Nil => Cow::Borrowed(""),
+            Object::Array(nested) => Cow::Owned(join_array(nested, separator, open, position)?),
+            value => Cow::Owned(value.to_string()),
+        };
+        pieces.push(piece);
+    }
+    open.pop();
+
+    let length = pieces.iter().map(|piece| piece.len()).sum::<usize>()
+        + separator.len() * pieces.len().saturating_sub(1);
+    let mut joined = String::with_capacity(length);
+    for (index, piece) in pieces.iter().enumerate() {
+        if index > 0 {
+            joined.push_str(separator);
+        }
+        joined.push_str(piece);
+    }
+    Ok(joined)
+}
+
+/// Read the `headers:` and `markdown:` keywords of `Array#to_table`.
+fn table_options(
+    method_name: &str,
+    arguments: &[Object],
+    position: Position,
+) -> Result<(Option<Vec<Object>>, TableStyle), MetorexError> {
+    let options = match arguments {
+        [] => return Ok((None, TableStyle::Ascii)),
+        [Object::Dict(options)] => options.borrow(),
+        [other] => {
+            return Err(method_argument_type_error(
+                method_name,
+                "keyword options",
+                other,
+                position,
+            ));
+        }
+        _ => {
+            return Err(method_argument_error(
+                method_name,
+                1,
+                arguments.len(),
+                position,
+            ));
+        }
+    };
+
+    let mut headers = None;
+    let mut style = TableStyle::Ascii;
+    for (key, value) in options.iter() {
+        match (key.as_str(), value) {
+            (":headers", Object::Nil) => headers = None,
+            (":headers", Object::Array(labels)) => headers = Some(labels.borrow().clone()),
+            (":markdown", Object::Bool(markdown)) => {
+                style = if *markdown {
+                    TableStyle::Markdown
+                } else {
+                    TableStyle::Ascii
+                };
+            }
+            (":headers" | ":markdown", other) => {
+                let expected = if key == ":headers" {
+                    "an Array"
+                } else {
+                    "a Bool"
+                };
+                return Err(MetorexError::runtime_error(
+                    format!(
+                        "{}: {} must be {}, got {}",
+                        method_name,
+                        key.trim_start_matches(':'),
+                        expected,
+                        other
+                    ),
+                    position_to_location(position),
+                ));
+            }
+            _ => {
+                return Err(MetorexError::runtime_error(
+                    format!(
+                        "{}: unknown option {} (expected headers or markdown)",
+                        method_name,
+                        key.trim_start_matches(':')
+                    ),
+                    position_to_location(position),
+                ));
+            }
+        }
+    }
+    Ok((headers, style))
+}
+
+/// Lay out rows that are all Hashes or all Arrays.
+///
+/// Hash rows become one column per key, in order of first appearance, unless
+/// `headers` picks the keys; Array rows are positional and `headers` only
+/// labels them.
+fn build_table(
+    rows: &[Object],
+    headers: Option<Vec<Object>>,
+    position: Position,
+) -> Result<Table, MetorexError> {
+    let labels = headers
+        .map(|headers| {
+            headers
+                .iter()
+                .map(|header| match header {
+                    Object::String(label) | Object::Symbol(label) => Ok(label.to_string()),
+                    other => Err(MetorexError::runtime_error(
+                        format!(
+                            "to_table: headers must be Strings or Symbols, got {}",
+                            other
+                        ),
+                        position_to_location(position),
+                    )),
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?;
+
+    let hash_rows = matches!(rows.first(), Some(Object::Dict(_)));
+    let mut keys: Vec<String> = Vec::new();
+    let mut dicts = Vec::new();
+    let mut arrays = Vec::new();
+    for row in rows {
+        match row {
+            Object::Dict(dict) if hash_rows => {
+                let dict = dict.borrow();
+                for key in dict.keys() {
+                    if !keys.contains(key) {
+                        keys.push(key.clone());
+                    }
+                }
+                dicts.push(dict.clone());
+            }
+            Object::Array(cells) if !hash_rows => arrays.push(cells.borrow().clone()),
+            other => {
+                let expected = if hash_rows { "a Hash" } else { "an Array" };
+                return Err(MetorexError::runtime_error(
+                    format!(
+                        "to_table: every row must be {} like the first, got {}",
+                        expected,
+                        other.type_name()
+                    ),
+                    position_to_location(position),
+                ));
+            }
+        }
+    }
+
+    let (headers, rows) = if hash_rows {
+        // Symbol keys are stored with their leading colon
+        let labels = labels.unwrap_or_else(|| {
+            keys.iter()
+                .map(|key| key.trim_start_matches(':').to_string())
+                .collect()
+        });
+        let rows = dicts
+            .iter()
+            .map(|dict| {
+                labels
+                    .iter()
+                    .map(|label| {
+                        dict.get(label)
+                            .or_else(|| dict.get(&format!(":{}", label)))
+                            .cloned()
+                            .unwrap_or(Object::Nil)
+                    })
+                    .collect()
+            })
+            .collect::<Vec<Vec<Object>>>();
+        (Some(labels), rows)
+    } else {
+        (labels, arrays)
+    };
+
+    // Columns holding only numbers are right-aligned so the digits line up
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let aligns = (0..columns)
+        .map(|column| {
+            let mut cells = rows
+                .iter()
+                .filter_map(|row| row.get(column))
+                .filter(|cell| !matches!(cell, Object::Nil))
+                .peekable();
+            let numeric = cells.peek().is_some()
+                && cells.all(|cell| matches!(cell, Object::Int(_) | Object::Float(_)));
+            if numeric { Align::Right } else { Align::Left }
+        })
+        .collect();
+
+    Ok(Table {
+        headers,
+        rows: rows
+            .iter()
+            .map(|row| row.iter().map(table_cell).collect())
+            .collect(),
+        aligns,
+    })
+}
+
+/// Cell text for a value; nil leaves the cell blank.
+fn table_cell(value: &Object) -> String {
+    match value {
+        Object::Nil => String::new(),
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,245 @@
+//! Script-visible syntax trees: `Metorex.parse`, `Metorex.eval_ast` and the
+//! `Metorex::Node` objects they exchange.
+//!
+//! A parsed program is converted with [`to_object`] into the same Hashes and
+//! Symbols `Method#ast` returns, then every Hash becomes a node whose fields
+//! are kept, in order, in its `fields` instance variable. Fields can be read
+//! as methods (`node.left.value`), so tools written in Metorex can walk a tree
+//! without indexing Hashes. Evaluating reverses both steps with
+//! [`from_object`], so a tree a script built or rewrote runs as long as it
+//! has the shape the parser would have produced.
+
+use crate::ast::{Expression, Statement};
+use crate::error::MetorexError;
+use crate::lexer::{Lexer, Position};
+use crate::object::{Object, from_object, to_object};
+use crate::parser::Parser;
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use crate::vm::method_invocation::body_result;
+use crate::vm::type_checks::describe_type;
+use indexmap::IndexMap;
+use std::rc::Rc;
+
+/// Node type of the root `Metorex.parse` returns.
+const PROGRAM: &str = "Program";
+
+impl VirtualMachine {
+    /// Metorex.parse(source) - the Program node for `source`.
+    pub(crate) fn parse_source_to_node(
+        &self,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        let source = match arguments {
+            [Object::String(source)] => source,
+            [other] => {
+                return Err(method_argument_type_error(
+                    "parse", "String", other, position,
+                ));
+            }
+            _ => return Err(method_argument_error("parse", 1, arguments.len(), position)),
+        };
+        let statements = Parser::new(Lexer::new(source).tokenize())
+            .parse()
+            .map_err(|errors| {
+                let message = errors.first().map_or_else(String::new, |e| e.to_string());
+                raised_exception_error("SyntaxError", message, position)
+            })?;
+        self.program_to_node(&statements, position)
+    }
+
+    /// The Program node for already parsed statements.
+    pub(crate) fn program_to_node(
+        &self,
+        statements: &[Statement],
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        let statements = to_object(statements)
+            .map_err(|error| raised_exception_error("ValueError", error.to_string(), position))?;
+
+        let mut program = IndexMap::new();
+        program.insert("type".to_string(), self.symbol(PROGRAM));
+        program.insert("statements".to_string(), statements);
+        Ok(self.object_to_node(&Object::dict(program)))
+    }
+
+    /// Metorex.eval_ast(ast) - run a node, or an Array of statement nodes, in
+    /// the caller's scope and return the value of the last statement.
+    pub(crate) fn evaluate_ast_node(
+        &mut self,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        let [ast] = arguments else {
+            return Err(method_argument_error(
+                "eval_ast",
+                1,
+                arguments.len(),
+                position,
+            ));
+        };
+        let invalid = |message: String| {
+            raised_exception_error(
+                "ValueError",
+                format!("eval_ast: invalid syntax tree: {}", message),
+                position,
+            )
+        };
+
+        let tree = self.node_to_object(ast);
+        let statements: Vec<Statement> = match &tree {
+            Object::Array(_) => from_object(&tree).map_err(|e| invalid(e.to_string()))?,
+            Object::Dict(fields) => {
+                let is_program = matches!(
+                    fields.borrow().get("type"),
+                    Some(Object::Symbol(name)) if name.as_str() == PROGRAM
+                );
+                if is_program {
+                    let statements = fields.borrow().get("statements").cloned();
+                    let statements = statements
+                        .ok_or_else(|| invalid("Program needs \"statements\"".to_string()))?;
+                    from_object(&statements).map_err(|e| invalid(e.to_string()))?
+                } else {
+                    vec![statement_from_node(&tree, position).map_err(invalid)?]
+                }
+            }
+            other => {
+                return Err(raised_exception_error(
+                    "TypeError",
+                    format!(
+                        "eval_ast: ast must be a Metorex::Node, Array or Hash (got {})",
+                        describe_type(other)
+                    ),
+                    position,
+                ));
+            }
+        };
+
+        let (flow, value) = self.execute_statements_for_value(&statements)?;
+        body_result(flow, value)
+    }
+
+    /// Execute native methods for Metorex::Node.
+    pub(crate) fn call_ast_node_method(
+        &mut self,
+        receiver: &Object,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let Some(fields) = self.node_fields(receiver) else {
+            return Ok(None);
+        };
+        let result = match (method_name, arguments) {
+            ("type", []) => fields.get("type").cloned().unwrap_or(Object::Nil),
+            ("fields", []) => Object::array(
+                fields
+                    .keys()
+                    .filter(|name| name.as_str() != "type")
+                    .map(|name| self.symbol(name))
+                    .collect(),
+            ),
+            ("to_h", []) => self.node_to_object(receiver),
+            ("[]", [name]) => node_field(&fields, name, position)?,
+            // Every other field can be read as a method
+            (name, []) if name != "type" => match fields.get(name) {
+                Some(value) => value.clone(),
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        Ok(Some(result))
+    }
+
+    /// The fields of a Metorex::Node, or `None` for any other value.
+    pub(crate) fn node_fields(&self, object: &Object) -> Option<IndexMap<String, Object>> {
+        let Object::Instance(instance) = object else {
+            return None;
+        };
+        let instance = instance.borrow();
+        if !Rc::ptr_eq(&instance.class, &self.builtins().ast_node_class) {
+            return None;
+        }
+        match instance.get_var("fields") {
+            Some(Object::Dict(fields)) => Some(fields.borrow().clone()),
+            _ => None,
+        }
+    }
+
+    /// Turn every Hash in a converted tree into a node.
+    fn object_to_node(&self, value: &Object) -> Object {
+        match value {
+            Object::Dict(entries) => {
+                let fields = entries
+                    .borrow()
+                    .iter()
+                    .map(|(name, value)| (name.clone(), self.object_to_node(value)))
+                    .collect();
+                let node = Object::instance(Rc::clone(&self.builtins().ast_node_class));
+                if let Object::Instance(instance) = &node {
+                    instance
+                        .borrow_mut()
+                        .set_var("fields".to_string(), Object::dict(fields));
+                }
+                self.track_allocation(node)
+            }
+            Object::Array(elements) => Object::array(
+                elements
+                    .borrow()
+                    .iter()
+                    .map(|element| self.object_to_node(element))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+
+    /// Turn every node in a tree back into a Hash.
+    fn node_to_object(&self, value: &Object) -> Object {
+        if let Some(fields) = self.node_fields(value) {
+            return Object::dict(
+                fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), self.node_to_object(value)))
+                    .collect(),
+            );
+        }
+        match value {
+            Object::Array(elements) => Object::array(
+                elements
+                    .borrow()
+                    .iter()
+                    .map(|element| self.node_to_object(element))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+}
+
+/// The field `name` of a node, or nil if it has none.
+pub(crate) fn node_field(
+    fields: &IndexMap<String, Object>,
+    name: &Object,
+    position: Position,
+) -> Result<Object, MetorexError> {
+    match name {
+        Object::String(name) | Object::Symbol(name) => {
+            Ok(fields.get(name.as_str()).cloned().unwrap_or(Object::Nil))
+        }
+        other => Err(method_argument_type_error("[]", "String", other, position)),
+    }
+}
+
+/// A statement node, or an expression node run as a statement.
+fn statement_from_node(tree: &Object, position: Position) -> Result<Statement, String> {
+    from_object::<Statement>(tree).or_else(|statement_error| {
+        from_object::<Expression>(tree)
+            .map(|expression| Statement::Expression {
+                expression,
+                position,
+            })
+            .map_err(|_| statement_error.to_string())
+    })
+}
@@ -0,0 +1,80 @@
+//! Native method implementations for the Base64 class.
+
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use base64::Engine;
+use base64::alphabet;
+use base64::engine::DecodePaddingMode;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig, STANDARD};
+
+/// URL-safe alphabet that pads when encoding and accepts input with or
+/// without padding, since tokens in URLs often have it stripped.
+const URL_SAFE: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+impl VirtualMachine {
+    /// Execute class methods on Base64.
+    pub(crate) fn call_base64_method(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let engine = match method_name {
+            "encode" | "decode" => &STANDARD,
+            "urlsafe_encode" | "urlsafe_decode" => &URL_SAFE,
+            _ => return Ok(None),
+        };
+        let text = match arguments {
+            [Object::String(text)] => text,
+            [other] => {
+                return Err(method_argument_type_error(
+                    method_name,
+                    "String",
+                    other,
+                    position,
+                ));
+            }
+            _ => {
+                return Err(method_argument_error(
+                    method_name,
+                    1,
+                    arguments.len(),
+                    position,
+                ));
+            }
+        };
+
+        if method_name.ends_with("encode") {
+            return Ok(Some(Object::string(engine.encode(text.as_bytes()))));
+        }
+
+        // Line breaks from wrapped output are not part of the data
+        let encoded: Vec<u8> = text.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+        let decoded = engine
+            .decode(encoded)
+            .map_err(|err| base64_error(method_name, err.to_string(), position))?;
+        let decoded = String::from_utf8(decoded).map_err(|_| {
+            base64_error(
+                method_name,
+                "decoded data is not valid UTF-8 text".to_string(),
+                position,
+            )
+        })?;
+        Ok(Some(Object::string(decoded)))
+    }
+}
+
+/// A rescuable ArgumentError for input that does not decode
+fn base64_error(method_name: &str, message: String, position: Position) -> MetorexError {
+    raised_exception_error(
+        "ArgumentError",
+        format!("Base64.{}: {}", method_name, message),
+        position,
+    )
+}
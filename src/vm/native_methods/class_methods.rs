@@ -0,0 +1,191 @@
+//! Native reflection methods available on every Class object.
+
+use crate::class::Class;
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::{Method, Object};
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use std::rc::Rc;
+
+impl VirtualMachine {
+    /// Execute class-variable and mixin reflection methods on a Class receiver.
+    pub(crate) fn call_class_method(
+        &mut self,
+        class: &Rc<Class>,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        match method_name {
+            "class_variable_get" => {
+                if arguments.len() != 1 {
+                    return Err(method_argument_error(
+                        method_name,
+                        1,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let name = class_variable_name(method_name, &arguments[0], position)?;
+                class
+                    .lookup_class_var(name)
+                    .map(Some)
+                    .ok_or_else(|| uninitialized_class_variable_error(name, class.name(), position))
+            }
+            "class_variable_set" => {
+                if arguments.len() != 2 {
+                    return Err(method_argument_error(
+                        method_name,
+                        2,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let name = class_variable_name(method_name, &arguments[0], position)?;
+                class.assign_class_var(name, arguments[1].clone());
+                Ok(Some(arguments[1].clone()))
+            }
+            "class_variable_defined?" => {
+                if arguments.len() != 1 {
+                    return Err(method_argument_error(
+                        method_name,
+                        1,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let name = class_variable_name(method_name, &arguments[0], position)?;
+                Ok(Some(Object::Bool(class.class_var_defined(name))))
+            }
+            "class_variables" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let names = class
+                    .class_var_names()
+                    .into_iter()
+                    .map(|name| self.symbol(&format!("@@{}", name)))
+                    .collect();
+                Ok(Some(Object::array(names)))
+            }
+            // define_method(name) do |args| ... end - add an instance method
+            // whose body is the block, with the variables it captured
+            "define_method" => {
+                let [name, Object::Block(block)] = arguments else {
+                    return Err(match arguments {
+                        [_, other] => {
+                            method_argument_type_error(method_name, "Block", other, position)
+                        }
+                        _ => method_argument_error(method_name, 2, arguments.len(), position),
+                    });
+                };
+                let name = match name {
+                    Object::Symbol(name) | Object::String(name) => name.as_str(),
+                    other => {
+                        return Err(method_argument_type_error(
+                            method_name,
+                            "Symbol",
+                            other,
+                            position,
+                        ));
+                    }
+                };
+                if !block.destructured.is_empty() {
+                    return Err(raised_exception_error(
+                        "ArgumentError",
+                        "define_method blocks cannot unpack parameters written as (a, b)"
+                            .to_string(),
+                        position,
+                    ));
+                }
+                let mut method =
+                    Method::from_block(name.to_string(), block, class.name().to_string());
+                method.nesting = self.nesting_names();
+                // A `do ... end` block only sees its surroundings while they
+                // last, so the method keeps the variables visible here
+                for (variable, value_ref) in self.environment().current_scope_var_refs() {
+                    method.captured_vars.entry(variable).or_insert(value_ref);
+                }
+                class.define_method(name, Rc::new(method));
+                Ok(Some(self.symbol(name)))
+            }
+            "include?" => match &arguments[0] {
+                Object::Module(module) => Ok(Some(Object::Bool(class.includes_module(module)))),
+                other => Err(method_argument_type_error(
+                    method_name,
+                    "Module",
+                    other,
+                    position,
+                )),
+            },
+            "included_modules" => {
+                let modules = class
+                    .included_modules()
+                    .into_iter()
+                    .map(Object::Module)
+                    .collect();
+                Ok(Some(Object::array(modules)))
+            }
+            "constants" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let names = class
+                    .constant_names()
+                    .into_iter()
+                    .map(|name| self.symbol(&name))
+                    .collect();
+                Ok(Some(Object::array(names)))
+            }
+            // Outer::Inner - a constant defined in the class or an ancestor
+            name if arguments.is_empty() && name.starts_with(char::is_uppercase) => {
+                Ok(class.lookup_constant(name))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Extract the bare variable name from a `:@@name` symbol or `"@@name"` string.
+fn class_variable_name<'a>(
+    method_name: &str,
+    argument: &'a Object,
+    position: Position,
+) -> Result<&'a str, MetorexError> {
+    let full_name = match argument {
+        Object::Symbol(name) | Object::String(name) => name.as_str(),
+        other => {
+            return Err(method_argument_type_error(
+                method_name,
+                "Symbol",
+                other,
+                position,
+            ));
+        }
+    };
+
+    match full_name.strip_prefix("@@") {
+        Some(name)
+            if name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_alphanumeric() || c == '_') =>
+        {
+            Ok(name)
+        }
+        _ => Err(raised_exception_error(
+            "NameError",
+            format!("'{}' is not allowed as a class variable name", full_name),
+            position,
+        )),
+    }
+}
@@ -0,0 +1,238 @@
+//! Native method implementations for CodeBuilder and the literal helpers.
+//!
+//! A CodeBuilder writes generated source one line at a time, indenting each
+//! line to the current depth, so templates never count spaces by hand.
+//! `indent { ... }` and `block("def name") { ... }` nest the lines their
+//! block writes and restore the depth afterwards, even when the block
+//! raises. The text lives in the `buffer` instance variable, the depth in
+//! `depth` and the unit of indentation in `indent`.
+//!
+//! `String#to_source` and `Object#to_literal` give the source that reads a
+//! value back, so values can be spliced into generated code safely.
+
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::literal;
+use crate::object::Object;
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use std::rc::Rc;
+
+impl VirtualMachine {
+    /// CodeBuilder.new(indent = "  ") - an empty builder indenting by `indent`
+    pub(crate) fn new_code_builder(
+        &mut self,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        let indent = match arguments {
+            [] => Rc::new("  ".to_string()),
+            [Object::String(indent)] => Rc::clone(indent),
+            [other] => return Err(method_argument_type_error("new", "String", other, position)),
+            _ => return Err(method_argument_error("new", 1, arguments.len(), position)),
+        };
+        let builder = Object::instance(Rc::clone(&self.builtins().code_builder_class));
+        if let Object::Instance(instance) = &builder {
+            let mut instance = instance.borrow_mut();
+            instance.set_var("buffer".to_string(), Object::string(String::new()));
+            instance.set_var("depth".to_string(), Object::Int(0));
+            instance.set_var("indent".to_string(), Object::String(indent));
+        }
+        Ok(self.track_allocation(builder))
+    }
+
+    /// Execute native methods for the CodeBuilder class.
+    pub(crate) fn call_code_builder_method(
+        &mut self,
+        receiver: &Object,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let Object::Instance(instance) = receiver else {
+            return Ok(None);
+        };
+        let most = match method_name {
+            "line" | "indent" => 1,
+            "block" => 3,
+            "dedent" | "depth" | "to_s" => 0,
+            _ => return Ok(None),
+        };
+        if arguments.len() > most {
+            return Err(method_argument_error(
+                method_name,
+                most,
+                arguments.len(),
+                position,
+            ));
+        }
+        let result = match (method_name, arguments) {
+            // line(text = "") - write `text` on its own line at the current
+            // depth; each line of a multi-line text is indented
+            ("line", _) => {
+                let text = match arguments.first() {
+                    Some(text) => self.get_string_representation(text, position)?,
+                    None => String::new(),
+                };
+                self.write_code_lines(receiver, &text);
+                receiver.clone()
+            }
+            // indent { ... } nests the lines the block writes; without a
+            // block the depth stays one deeper
+            ("indent", []) => {
+                self.shift_code_depth(receiver, 1, position)?;
+                receiver.clone()
+            }
+            ("indent", [block @ Object::Block(_)]) => {
+                self.write_code_block(receiver, None, None, block.clone(), position)?;
+                receiver.clone()
+            }
+            ("dedent", []) => {
+                self.shift_code_depth(receiver, -1, position)?;
+                receiver.clone()
+            }
+            // block(header, footer = "end") { ... } - the header, the lines
+            // the block writes one level deeper, then the footer
+            ("block", [header, footer @ .., block @ Object::Block(_)]) => {
+                let header = self.get_string_representation(header, position)?;
+                let footer = match footer {
+                    [footer] => self.get_string_representation(footer, position)?,
+                    _ => "end".to_string(),
+                };
+                self.write_code_block(
+                    receiver,
+                    Some(&header),
+                    Some(&footer),
+                    block.clone(),
+                    position,
+                )?;
+                receiver.clone()
+            }
+            ("depth", []) => instance
+                .borrow()
+                .get_var("depth")
+                .cloned()
+                .unwrap_or(Object::Int(0)),
+            ("to_s", []) => instance
+                .borrow()
+                .get_var("buffer")
+                .cloned()
+                .unwrap_or_else(|| Object::string(String::new())),
+            _ => {
+                return Err(raised_exception_error(
+                    "ArgumentError",
+                    format!(
+                        "{}: expects {}",
+                        method_name,
+                        match method_name {
+                            "block" => "a header, an optional footer and a block",
+                            _ => "a block",
+                        }
+                    ),
+                    position,
+                ));
+            }
+        };
+        Ok(Some(result))
+    }
+
+    /// Append `text` to a builder, one indented line per line of text. Blank
+    /// lines get no indentation.
+    fn write_code_lines(&self, builder: &Object, text: &str) {
+        let Object::Instance(instance) = builder else {
+            return;
+        };
+        let mut instance = instance.borrow_mut();
+        let depth = match instance.get_var("depth") {
+            Some(Object::Int(depth)) => *depth as usize,
+            _ => 0,
+        };
+        let indent = match instance.get_var("indent") {
+            Some(Object::String(indent)) => indent.repeat(depth),
+            _ => String::new(),
+        };
+        let Some(Object::String(buffer)) = instance.instance_vars.get_mut("buffer") else {
+            return;
+        };
+        let buffer = Rc::make_mut(buffer);
+        let lines: Vec<&str> = if text.is_empty() {
+            vec![""]
+        } else {
+            text.lines().collect()
+        };
+        for line in lines {
+            if !line.is_empty() {
+                buffer.push_str(&indent);
+            }
+            buffer.push_str(line);
+            buffer.push('\n');
+        }
+    }
+
+    /// Write an optional header, run `block` with the builder one level
+    /// deeper, then write an optional footer. The depth is restored whether
+    /// or not the block succeeds.
+    fn write_code_block(
+        &mut self,
+        builder: &Object,
+        header: Option<&str>,
+        footer: Option<&str>,
+        block: Object,
+        position: Position,
+    ) -> Result<(), MetorexError> {
+        if let Some(header) = header {
+            self.write_code_lines(builder, header);
+        }
+        self.shift_code_depth(builder, 1, position)?;
+        let result = self.invoke_callable(block, vec![builder.clone()], position);
+        self.shift_code_depth(builder, -1, position)?;
+        result?;
+        if let Some(footer) = footer {
+            self.write_code_lines(builder, footer);
+        }
+        Ok(())
+    }
+
+    fn shift_code_depth(
+        &self,
+        builder: &Object,
+        change: i64,
+        position: Position,
+    ) -> Result<(), MetorexError> {
+        let Object::Instance(instance) = builder else {
+            return Ok(());
+        };
+        let mut instance = instance.borrow_mut();
+        let depth = match instance.get_var("depth") {
+            Some(Object::Int(depth)) => *depth + change,
+            _ => change,
+        };
+        if depth < 0 {
+            return Err(raised_exception_error(
+                "ArgumentError",
+                "dedent: already at depth 0".to_string(),
+                position,
+            ));
+        }
+        instance.set_var("depth".to_string(), Object::Int(depth));
+        Ok(())
+    }
+
+    /// value.to_literal - source that evaluates to the value
+    pub(crate) fn literal_source(
+        &self,
+        method_name: &str,
+        value: &Object,
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        literal::to_literal(value)
+            .map(Object::string)
+            .map_err(|message| {
+                raised_exception_error(
+                    "TypeError",
+                    format!("{}: {}", method_name, message),
+                    position,
+                )
+            })
+    }
+}
@@ -0,0 +1,246 @@
+//! Native method implementations for the Decimal class.
+//!
+//! Decimals are exact base-10 numbers with up to 28 significant digits, so
+//! sums of money never pick up the binary rounding errors Floats do. A
+//! Decimal keeps the scale it was written with, so `Decimal.new("1.50")`
+//! prints as 1.50. Arithmetic with Ints stays exact; mixing with a Float
+//! raises, since the Float has already lost digits.
+//!
+//! Rounding uses the VM's rounding mode unless a call names its own.
+//! `Decimal.rounding_mode` reads the mode and, given a symbol, sets it.
+
+use super::number_format_options::apply_number_format_options;
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::number_format::{NumberFormat, format_decimal};
+use crate::object::{Decimal, Object, RoundingStrategy};
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use rust_decimal::prelude::ToPrimitive;
+
+/// Rounding modes by the symbol scripts name them with
+const ROUNDING_MODES: [(&str, RoundingStrategy); 7] = [
+    ("half_up", RoundingStrategy::MidpointAwayFromZero),
+    ("half_down", RoundingStrategy::MidpointTowardZero),
+    ("half_even", RoundingStrategy::MidpointNearestEven),
+    ("up", RoundingStrategy::AwayFromZero),
+    ("down", RoundingStrategy::ToZero),
+    ("ceiling", RoundingStrategy::ToPositiveInfinity),
+    ("floor", RoundingStrategy::ToNegativeInfinity),
+];
+
+/// Most decimal places a Decimal can hold
+const MAX_SCALE: i64 = 28;
+
+impl VirtualMachine {
+    /// Decimal.new(value) - an exact Decimal from a String, Int, Float or Decimal
+    pub(crate) fn new_decimal(
+        &mut self,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        match arguments {
+            [value] => to_decimal(value, position).map(Object::Decimal),
+            _ => Err(method_argument_error("new", 1, arguments.len(), position)),
+        }
+    }
+
+    /// Execute methods called on the Decimal class itself.
+    pub(crate) fn call_decimal_class_method(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        match method_name {
+            // Decimal.rounding_mode(mode = nil) - the default mode; setting one
+            // returns the mode it replaces
+            "rounding_mode" => {
+                let current = self.symbol(rounding_mode_name(self.decimal_rounding()));
+                if let [mode] = arguments {
+                    let mode = rounding_mode(method_name, mode, position)?;
+                    self.set_decimal_rounding(mode);
+                }
+                Ok(Some(current))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Execute native methods for the Decimal class.
+    pub(crate) fn call_decimal_method(
+        &mut self,
+        receiver: &Object,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let Object::Decimal(value) = receiver else {
+            return Ok(None);
+        };
+        let value = *value;
+        let result = match method_name {
+            // round(places = 0, mode = Decimal.rounding_mode)
+            "round" => {
+                let places = decimal_places(method_name, arguments.first(), position)?;
+                let mode = match arguments.get(1) {
+                    Some(mode) => rounding_mode(method_name, mode, position)?,
+                    None => self.decimal_rounding(),
+                };
+                Object::Decimal(value.round_dp_with_strategy(places, mode))
+            }
+            "floor" | "ceil" | "truncate" => {
+                let places = decimal_places(method_name, arguments.first(), position)?;
+                let mode = match method_name {
+                    "floor" => RoundingStrategy::ToNegativeInfinity,
+                    "ceil" => RoundingStrategy::ToPositiveInfinity,
+                    _ => RoundingStrategy::ToZero,
+                };
+                Object::Decimal(value.round_dp_with_strategy(places, mode))
+            }
+            "to_i" | "to_int" => match value.trunc().to_i64() {
+                Some(int) => Object::Int(int),
+                None => {
+                    return Err(raised_exception_error(
+                        "ArgumentError",
+                        format!("{}: {} is too large for an Int", method_name, value),
+                        position,
+                    ));
+                }
+            },
+            "to_f" => Object::Float(value.to_f64().unwrap_or(f64::NAN)),
+            "to_d" => Object::Decimal(value),
+            "abs" => Object::Decimal(value.abs()),
+            "scale" => Object::Int(i64::from(value.scale())),
+            "zero?" => Object::Bool(value.is_zero()),
+            "positive?" => Object::Bool(value.is_sign_positive() && !value.is_zero()),
+            "negative?" => Object::Bool(value.is_sign_negative() && !value.is_zero()),
+            "format" => {
+                let mut format = NumberFormat::with_precision(2);
+                apply_number_format_options(method_name, arguments, true, &mut format, position)?;
+                let rounded =
+                    value.round_dp_with_strategy(format.precision as u32, self.decimal_rounding());
+                Object::string(format_decimal(rounded, &format))
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(result))
+    }
+}
+
+/// A value as a Decimal. Floats convert from their shortest written form,
+/// so `0.1` gives exactly 0.1 rather than the binary value behind it.
+pub(super) fn to_decimal(value: &Object, position: Position) -> Result<Decimal, MetorexError> {
+    let invalid = |text: String| {
+        raised_exception_error(
+            "ArgumentError",
+            format!("invalid value for Decimal: {}", text),
+            position,
+        )
+    };
+    match value {
+        Object::Decimal(value) => Ok(*value),
+        Object::Int(value) => Ok(Decimal::from(*value)),
+        Object::Float(value) if value.is_finite() => {
+            parse_decimal(&value.to_string()).ok_or_else(|| invalid(value.to_string()))
+        }
+        Object::Float(value) => Err(invalid(Object::Float(*value).to_string())),
+        Object::String(text) => parse_decimal(text).ok_or_else(|| invalid(format!("{:?}", text))),
+        other => Err(method_argument_type_error(
+            "new",
+            "String, Int or Float",
+            other,
+            position,
+        )),
+    }
+}
+
+/// Read a whole string as a Decimal, allowing surrounding whitespace and an
+/// exponent, like `1.5e3`.
+pub(super) fn parse_decimal(text: &str) -> Option<Decimal> {
+    let text = text.trim();
+    if text.contains(['e', 'E']) {
+        Decimal::from_scientific(text).ok()
+    } else {
+        Decimal::from_str_exact(text).ok()
+    }
+}
+
+/// The leading decimal number of `text`, or zero, like String#to_f.
+pub(super) fn decimal_prefix(text: &str) -> Decimal {
+    let text = text.trim_start();
+    let unsigned = text.strip_prefix(['-', '+']).unwrap_or(text);
+    let whole = unsigned.bytes().take_while(u8::is_ascii_digit).count();
+    let mut end = text.len() - unsigned.len() + whole;
+    if let Some(fraction) = unsigned[whole..].strip_prefix('.') {
+        let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        if digits > 0 {
+            end += 1 + digits;
+        }
+    }
+    Decimal::from_str_exact(&text[..end]).unwrap_or(Decimal::ZERO)
+}
+
+fn decimal_places(
+    method_name: &str,
+    places: Option<&Object>,
+    position: Position,
+) -> Result<u32, MetorexError> {
+    match places {
+        None => Ok(0),
+        Some(Object::Int(places)) if (0..=MAX_SCALE).contains(places) => Ok(*places as u32),
+        Some(other) => Err(raised_exception_error(
+            "ArgumentError",
+            format!(
+                "{}: places must be an Int from 0 to {}, got {}",
+                method_name, MAX_SCALE, other
+            ),
+            position,
+        )),
+    }
+}
+
+fn rounding_mode(
+    method_name: &str,
+    mode: &Object,
+    position: Position,
+) -> Result<RoundingStrategy, MetorexError> {
+    let name = match mode {
+        Object::Symbol(name) => name,
+        other => {
+            return Err(method_argument_type_error(
+                method_name,
+                "Symbol",
+                other,
+                position,
+            ));
+        }
+    };
+    ROUNDING_MODES
+        .iter()
+        .find(|(mode_name, _)| mode_name == &name.as_str())
+        .map(|(_, mode)| *mode)
+        .ok_or_else(|| {
+            let names: Vec<String> = ROUNDING_MODES
+                .iter()
+                .map(|(mode_name, _)| format!(":{}", mode_name))
+                .collect();
+            raised_exception_error(
+                "ArgumentError",
+                format!(
+                    "{}: unknown rounding mode :{} (expected one of {})",
+                    method_name,
+                    name,
+                    names.join(", ")
+                ),
+                position,
+            )
+        })
+}
+
+fn rounding_mode_name(mode: RoundingStrategy) -> &'static str {
+    ROUNDING_MODES
+        .iter()
+        .find(|(_, candidate)| *candidate == mode)
+        .map_or("half_up", |(name, _)| name)
+}
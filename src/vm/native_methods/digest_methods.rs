@@ -0,0 +1,46 @@
+//! Native method implementations for the Digest class.
+
+use crate::error::MetorexError;
+use crate::hex;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+impl VirtualMachine {
+    /// Execute class methods on Digest.
+    pub(crate) fn call_digest_method(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let digest: fn(&[u8]) -> Vec<u8> = match method_name {
+            "sha256" => |data| Sha256::digest(data).to_vec(),
+            "sha1" => |data| Sha1::digest(data).to_vec(),
+            "md5" => |data| Md5::digest(data).to_vec(),
+            _ => return Ok(None),
+        };
+        match arguments {
+            // Digest.sha256(data) - lowercase hex digest of the String's bytes
+            [Object::String(data)] => {
+                Ok(Some(Object::string(hex::encode(&digest(data.as_bytes())))))
+            }
+            [other] => Err(method_argument_type_error(
+                method_name,
+                "String",
+                other,
+                position,
+            )),
+            _ => Err(method_argument_error(
+                method_name,
+                1,
+                arguments.len(),
+                position,
+            )),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Native method implementations for Enumerator, what the iterator methods of
+//! builtin classes return when they are called without a block.
+//!
+//! An enumerator walks its items one at a time under the caller's control:
+//! `next` returns the item at its cursor and moves on, `peek` looks without
+//! moving, and `rewind` starts over. The items are gathered when the
+//! enumerator is created and kept in the `items` instance variable, with the
+//! cursor in `index`. Iterators that yield several values at once, such as
+//! `each_with_index`, store each group as an Array and set `grouped`, so
+//! `each` can spread a group over a block's parameters again.
+//!
+//! Array methods without an Enumerator version of their own, such as `map`
+//! and `select`, run on all of the items.
+
+use super::iteration::optional_block;
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Items, cursor and grouping of an Enumerator
+struct EnumeratorState {
+    items: Rc<RefCell<Vec<Object>>>,
+    index: usize,
+    grouped: bool,
+}
+
+impl VirtualMachine {
+    /// An Enumerator over `items`. `grouped` marks items that are Arrays of
+    /// the values an iterator yields together.
+    pub(super) fn new_enumerator(&mut self, items: Vec<Object>, grouped: bool) -> Object {
+        let enumerator = Object::instance(Rc::clone(&self.builtins().enumerator_class));
+        if let Object::Instance(instance) = &enumerator {
+            let mut instance = instance.borrow_mut();
+            instance.set_var("items".to_string(), Object::array(items));
+            instance.set_var("index".to_string(), Object::Int(0));
+            instance.set_var("grouped".to_string(), Object::Bool(grouped));
+        }
+        self.track_allocation(enumerator)
+    }
+
+    /// Execute native methods for the Enumerator class.
+    pub(crate) fn call_enumerator_method(
+        &mut self,
+        receiver: &Object,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let Some(state) = self.enumerator_state(receiver) else {
+            return Ok(None);
+        };
+        let expected = match method_name {
+            "next" | "peek" | "rewind" | "to_a" | "size" => 0,
+            "each" => return self.enumerator_each(receiver, state, arguments, position),
+            _ => {
+                let items = Object::array(state.items.borrow().clone());
+                return self.call_array_method(&items, method_name, arguments, position);
+            }
+        };
+        if arguments.len() != expected {
+            return Err(method_argument_error(
+                method_name,
+                expected,
+                arguments.len(),
+                position,
+            ));
+        }
+
+        let result = match method_name {
+            "next" | "peek" => {
+                let item = state.items.borrow().get(state.index).cloned();
+                let Some(item) = item else {
+                    return Err(raised_exception_error(
+                        "StopIteration",
+                        "iteration reached an end".to_string(),
+                        position,
+                    ));
+                };
+                if method_name == "next" {
+                    set_enumerator_index(receiver, state.index + 1);
+                }
+                item
+            }
+            "rewind" => {
+                set_enumerator_index(receiver, 0);
+                receiver.clone()
+            }
+            "to_a" => Object::array(state.items.borrow().clone()),
+            "size" => Object::Int(state.items.borrow().len() as i64),
+            _ => unreachable!(),
+        };
+        Ok(Some(result))
+    }
+
+    /// Enumerator#each - yield every item from the start, without moving the
+    /// cursor; a group is spread over a block taking several parameters
+    fn enumerator_each(
+        &mut self,
+        receiver: &Object,
+        state: EnumeratorState,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let Some(block) = optional_block("each", arguments, 0, position)? else {
+            return Ok(Some(receiver.clone()));
+        };
+        let items = state.items.borrow().clone();
+        if state.grouped && block.parameters.len() >= 2 {
+            let groups = items.into_iter().map(|group| match group {
+                Object::Array(values) => values.borrow().clone(),
+                value => vec![value],
+            });
+            self.yield_each_values(receiver, Some(block), groups)
+        } else {
+            self.yield_each(receiver, Some(block), items.into_iter())
+        }
+    }
+
+    /// The state of an Enumerator, or `None` for any other value.
+    fn enumerator_state(&self, object: &Object) -> Option<EnumeratorState> {
+        let Object::Instance(instance) = object else {
+            return None;
+        };
+        let instance = instance.borrow();
+        if !Rc::ptr_eq(&instance.class, &self.builtins().enumerator_class) {
+            return None;
+        }
+        match (
+            instance.get_var("items"),
+            instance.get_var("index"),
+            instance.get_var("grouped"),
+        ) {
+            (Some(Object::Array(items)), Some(Object::Int(index)), Some(Object::Bool(grouped))) => {
+                Some(EnumeratorState {
+                    items: Rc::clone(items),
+                    index: *index as usize,
+                    grouped: *grouped,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Move an Enumerator's cursor to `index`.
+fn set_enumerator_index(enumerator: &Object, index: usize) {
+    if let Object::Instance(instance) = enumerator {
+        instance
+            .borrow_mut()
+            .set_var("index".to_string(), Object::Int(index as i64));
+    }
+}
@@ -14,7 +14,7 @@ impl VirtualMachine {
         &mut self,
         receiver: &Object,
         method_name: &str,
-        _arguments: &[Object],
+        arguments: &[Object],
         position: Position,
     ) -> Result<Option<Object>, MetorexError> {
         let exception = match receiver {
@@ -38,6 +38,11 @@ impl VirtualMachine {
                 let exception_type = exception.borrow().exception_type.clone();
                 Ok(Some(Object::String(Rc::new(exception_type))))
             }
+            "cause" => {
+                // Return the wrapped exception, or nil if there is none
+                let cause = exception.borrow().cause.as_deref().cloned();
+                Ok(Some(cause.unwrap_or(Object::Nil)))
+            }
             "backtrace" => {
                 // Return the backtrace as an Array of Strings
                 let backtrace = exception.borrow().backtrace.clone();
@@ -59,7 +64,8 @@ impl VirtualMachine {
 
                 // Add location if available
                 if let Some(ref location) = exc.location {
-                    result = format!("{} (at {}:{})", result, location.file, location.line);
+                    let file = location.filename.as_deref().unwrap_or("script");
+                    result = format!("{} (at {}:{})", result, file, location.line);
                 }
 
                 // Add backtrace if available
@@ -73,8 +79,29 @@ impl VirtualMachine {
                     }
                 }
 
+                // Add the cause chain if available
+                for cause in exc.exception_chain().iter().skip(1) {
+                    if !result.ends_with('\n') {
+                        result.push('\n');
+                    }
+                    result.push_str("Caused by: ");
+                    result.push_str(cause);
+                }
+
                 Ok(Some(Object::String(Rc::new(result))))
             }
+            "details" => {
+                // Return the structured details as a Hash with symbol keys
+                let details = exception
+                    .borrow()
+                    .details
+                    .iter()
+                    .map(|(key, value)| (format!(":{}", key), value.clone()))
+                    .collect();
+                Ok(Some(Object::dict(details)))
+            }
+            // Each detail reads as a method, like `e.path` or `e.errno`
+            name if arguments.is_empty() => Ok(exception.borrow().details.get(name).cloned()),
             _ => Ok(None), // No native method found, let it fall through
         }
     }
@@ -0,0 +1,363 @@
+//! Native method implementations for the File class.
+//!
+//! Paths may use `/` or `\` as a separator on every platform; the helpers
+//! return them with `/`, so scripts produce the same paths everywhere.
+//!
+//! `File.read`, `File.write`, `File.exists?` and `File.delete` work on whole
+//! files. `File.open(path)` gives a File instance for reading a file line by
+//! line with `each_line`, which reads one line at a time rather than the
+//! whole file. The instance keeps only its path, in the `path` instance
+//! variable, so there is no handle to release and `close` does nothing; it
+//! exists so a File works with `with` like other resources, and the block form
+//! of `File.open` closes its File the same way. Failures raise an IOError naming
+//! the method and the path, whose details are readable on the rescued
+//! exception: `e.operation`, `e.path`, `e.errno` (nil when the OS gave none),
+//! `e.kind` (a Symbol such as `:not_found`) and `e.elapsed`, the seconds the
+//! failed call took.
+
+use super::string_methods::{chomp_newline, line_options};
+use crate::error::MetorexError;
+use crate::glob::fnmatch;
+use crate::inflector::underscore;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::path_names::{basename, dirname, extname, join, normalize_separators};
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use indexmap::IndexMap;
+use std::fs;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::rc::Rc;
+use std::time::Instant;
+
+impl VirtualMachine {
+    /// Execute class methods on File.
+    pub(crate) fn call_file_method(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        match method_name {
+            // File.fnmatch(pattern, path) - shell-style glob match
+            "fnmatch" | "fnmatch?" => match arguments {
+                [Object::String(pattern), Object::String(path)] => Ok(Some(Object::Bool(fnmatch(
+                    pattern,
+                    &normalize_separators(path),
+                )))),
+                [Object::String(_), other] | [other, _] => Err(method_argument_type_error(
+                    method_name,
+                    "String",
+                    other,
+                    position,
+                )),
+                _ => Err(method_argument_error(
+                    method_name,
+                    2,
+                    arguments.len(),
+                    position,
+                )),
+            },
+            // File.join(*parts) - the parts joined with single `/` separators
+            "join" => {
+                let parts = arguments
+                    .iter()
+                    .map(|part| match part {
+                        Object::String(part) => Ok(part.as_str()),
+                        other => Err(method_argument_type_error(
+                            method_name,
+                            "String",
+                            other,
+                            position,
+                        )),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Some(Object::string(join(&parts))))
+            }
+            // File.basename(path, suffix = nil) - the last path component
+            "basename" => match arguments {
+                [Object::String(path)] => Ok(Some(Object::string(basename(path, None)))),
+                [Object::String(path), Object::String(suffix)] => {
+                    Ok(Some(Object::string(basename(path, Some(suffix)))))
+                }
+                [Object::String(_), other] | [other, ..] => Err(method_argument_type_error(
+                    method_name,
+                    "String",
+                    other,
+                    position,
+                )),
+                [] => Err(method_argument_error(method_name, 1, 0, position)),
+            },
+            // File.dirname(path) / File.extname(path)
+            "dirname" | "extname" => match arguments {
+                [Object::String(path)] => {
+                    let result = if method_name == "dirname" {
+                        dirname(path)
+                    } else {
+                        extname(path)
+                    };
+                    Ok(Some(Object::string(result)))
+                }
+                [other] => Err(method_argument_type_error(
+                    method_name,
+                    "String",
+                    other,
+                    position,
+                )),
+                _ => Err(method_argument_error(
+                    method_name,
+                    1,
+                    arguments.len(),
+                    position,
+                )),
+            },
+            // File.read(path) - the whole file as a String
+            "read" => {
+                let path = path_argument(method_name, arguments, 1, position)?;
+                let started = Instant::now();
+                let text = fs::read_to_string(path)
+                    .map_err(|err| file_error("File.read", path, &err, started, position))?;
+                Ok(Some(Object::string(text)))
+            }
+            // File.write(path, content) - replace the file's contents,
+            // returning the number of bytes written
+            "write" => {
+                let path = path_argument(method_name, arguments, 2, position)?;
+                let content = self.get_string_representation(&arguments[1], position)?;
+                let started = Instant::now();
+                fs::write(path, &content)
+                    .map_err(|err| file_error("File.write", path, &err, started, position))?;
+                Ok(Some(Object::Int(content.len() as i64)))
+            }
+            // File.exists?(path) - whether anything exists at the path
+            "exists?" | "exist?" => {
+                let path = path_argument(method_name, arguments, 1, position)?;
+                Ok(Some(Object::Bool(Path::new(path).exists())))
+            }
+            // File.delete(path) - remove a file
+            "delete" => {
+                let path = path_argument(method_name, arguments, 1, position)?;
+                let started = Instant::now();
+                fs::remove_file(path)
+                    .map_err(|err| file_error("File.delete", path, &err, started, position))?;
+                Ok(Some(Object::Nil))
+            }
+            // File.open(path) { |file| ... } - the block's result, or the
+            // File itself without a block
+            "open" => {
+                let (block, arguments) = match arguments.split_last() {
+                    Some((block @ Object::Block(_), rest)) => (Some(block.clone()), rest),
+                    _ => (None, arguments),
+                };
+                let file = self.open_file("File.open", arguments, position)?;
+                match block {
+                    Some(block) => self.with_resource(file, block, position).map(Some),
+                    None => Ok(Some(file)),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// File.new(path) - a File for reading the existing file at `path`
+    pub(crate) fn new_file(
+        &mut self,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        self.open_file("File.new", arguments, position)
+    }
+
+    fn open_file(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        let path = path_argument(method_name, arguments, 1, position)?;
+        let started = Instant::now();
+        if Path::new(path).is_dir() {
+            let err = io::Error::new(io::ErrorKind::IsADirectory, "Is a directory");
+            return Err(file_error(method_name, path, &err, started, position));
+        }
+        fs::File::open(path)
+            .map_err(|err| file_error(method_name, path, &err, started, position))?;
+        let file = Object::instance(Rc::clone(&self.builtins().file_class));
+        if let Object::Instance(instance) = &file {
+            instance
+                .borrow_mut()
+                .set_var("path".to_string(), Object::string(path.to_string()));
+        }
+        Ok(self.track_allocation(file))
+    }
+
+    /// Execute native methods for File instances.
+    pub(crate) fn call_file_instance_method(
+        &mut self,
+        receiver: &Object,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let Object::Instance(instance) = receiver else {
+            return Ok(None);
+        };
+        let path = match instance.borrow().get_var("path") {
+            Some(Object::String(path)) => Rc::clone(path),
+            _ => return Ok(None),
+        };
+        let qualified = format!("File#{}", method_name);
+        match method_name {
+            // close - nothing to release, since no handle is kept open
+            "close" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                Ok(Some(Object::Nil))
+            }
+            "path" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                Ok(Some(Object::String(path)))
+            }
+            "read" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let started = Instant::now();
+                let text = fs::read_to_string(path.as_str())
+                    .map_err(|err| file_error(&qualified, &path, &err, started, position))?;
+                Ok(Some(Object::string(text)))
+            }
+            // each_line(chomp: false) { |line| ... } reads and yields one line
+            // at a time; without a block the lines come back as an Enumerator
+            "each_line" => {
+                let (options, block) = match arguments.split_last() {
+                    Some((Object::Block(block), rest)) => (rest, Some(Rc::clone(block))),
+                    _ => (arguments, None),
+                };
+                let chomp = line_options(method_name, options, position)?;
+                let started = Instant::now();
+                let file = fs::File::open(path.as_str())
+                    .map_err(|err| file_error(&qualified, &path, &err, started, position))?;
+                let mut reader = BufReader::new(file);
+                let Some(block) = block else {
+                    let mut lines = Vec::new();
+                    while let Some(line) = next_line(&mut reader, chomp)
+                        .map_err(|err| file_error(&qualified, &path, &err, started, position))?
+                    {
+                        lines.push(line);
+                    }
+                    return self.yield_each(receiver, None, lines.into_iter());
+                };
+                while let Some(line) = next_line(&mut reader, chomp)
+                    .map_err(|err| file_error(&qualified, &path, &err, started, position))?
+                {
+                    if !self.yield_to_block(&block, vec![line])? {
+                        break;
+                    }
+                }
+                Ok(Some(receiver.clone()))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// The next line of `reader` as a String, or None at the end of the file
+fn next_line(reader: &mut impl BufRead, chomp: bool) -> io::Result<Option<Object>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(Object::string(if chomp {
+        chomp_newline(&line)
+    } else {
+        &line
+    })))
+}
+
+/// The path a File class method was given as its first argument, after
+/// checking that it was given `count` arguments
+fn path_argument<'a>(
+    method_name: &str,
+    arguments: &'a [Object],
+    count: usize,
+    position: Position,
+) -> Result<&'a str, MetorexError> {
+    if arguments.len() != count {
+        return Err(method_argument_error(
+            method_name,
+            count,
+            arguments.len(),
+            position,
+        ));
+    }
+    match &arguments[0] {
+        Object::String(path) => Ok(path.as_str()),
+        other => Err(method_argument_type_error(
+            method_name,
+            "String",
+            other,
+            position,
+        )),
+    }
+}
+
+/// An IOError for a failed operation on `path`, like
+/// "File.read: notes.txt: No such file or directory", carrying the operation,
+/// path, errno, error kind and the seconds since `started` as details
+fn file_error(
+    method_name: &str,
+    path: &str,
+    err: &io::Error,
+    started: Instant,
+    position: Position,
+) -> MetorexError {
+    // Drop the " (os error 2)" suffix so messages read the same everywhere
+    let reason = err.to_string();
+    let reason = match reason.find(" (os error ") {
+        Some(end) => &reason[..end],
+        None => &reason,
+    };
+    let details = IndexMap::from([
+        ("operation".to_string(), Object::string(method_name)),
+        ("path".to_string(), Object::string(path)),
+        (
+            "errno".to_string(),
+            err.raw_os_error()
+                .map_or(Object::Nil, |errno| Object::Int(errno.into())),
+        ),
+        (
+            "kind".to_string(),
+            Object::Symbol(underscore(&format!("{:?}", err.kind())).into()),
+        ),
+        (
+            "elapsed".to_string(),
+            Object::Float(started.elapsed().as_secs_f64()),
+        ),
+    ]);
+    raised_exception_error_with_details(
+        "IOError",
+        format!("{}: {}: {}", method_name, path, reason),
+        details,
+        position,
+    )
+}
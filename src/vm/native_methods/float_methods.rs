@@ -1,13 +1,44 @@
 //! Native method implementations for the Float class.
 
+use super::decimal_methods::to_decimal;
+use super::number_format_options::apply_number_format_options;
+use crate::duration::{ago_in_words, format_duration};
 use crate::error::MetorexError;
 use crate::lexer::Position;
+use crate::number_format::{NumberFormat, format_float, humanize};
 use crate::object::Object;
 use crate::vm::VirtualMachine;
 use crate::vm::errors::*;
+use crate::vm::int_conversion::float_to_exact_int;
 use crate::vm::utils::position_to_location;
 
 impl VirtualMachine {
+    /// Execute methods called on the Float class itself, such as its constants.
+    pub(crate) fn call_float_class_method(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let value = match method_name {
+            "INFINITY" => f64::INFINITY,
+            "NAN" => f64::NAN,
+            "EPSILON" => f64::EPSILON,
+            "MAX" => f64::MAX,
+            "MIN" => f64::MIN_POSITIVE,
+            _ => return Ok(None),
+        };
+        if !arguments.is_empty() {
+            return Err(method_argument_error(
+                method_name,
+                0,
+                arguments.len(),
+                position,
+            ));
+        }
+        Ok(Some(Object::Float(value)))
+    }
+
     /// Execute native methods for the Float class.
     pub(crate) fn call_float_method(
         &mut self,
@@ -17,6 +48,87 @@ impl VirtualMachine {
         position: Position,
     ) -> Result<Option<Object>, MetorexError> {
         match method_name {
+            "round" if arguments.is_empty() => match receiver {
+                Object::Float(float_value) => Ok(Some(float_to_int(
+                    float_value.round(),
+                    method_name,
+                    position,
+                )?)),
+                _ => Ok(None),
+            },
+            "floor" | "ceil" | "to_i" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                match receiver {
+                    Object::Float(float_value) => {
+                        let whole = match method_name {
+                            "floor" => float_value.floor(),
+                            "ceil" => float_value.ceil(),
+                            _ => float_value.trunc(),
+                        };
+                        Ok(Some(float_to_int(whole, method_name, position)?))
+                    }
+                    _ => Ok(None),
+                }
+            }
+            // Unlike to_i, to_int never truncates: it is the conversion
+            // used wherever an Int is required
+            "to_int" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                match receiver {
+                    Object::Float(float_value) => Ok(Some(Object::Int(float_to_exact_int(
+                        *float_value,
+                        method_name,
+                        position,
+                    )?))),
+                    _ => Ok(None),
+                }
+            }
+            "abs" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                match receiver {
+                    Object::Float(float_value) => Ok(Some(Object::Float(float_value.abs()))),
+                    _ => Ok(None),
+                }
+            }
+            "finite?" | "nan?" | "infinite?" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                match receiver {
+                    Object::Float(float_value) => Ok(Some(Object::Bool(match method_name {
+                        "finite?" => float_value.is_finite(),
+                        "nan?" => float_value.is_nan(),
+                        _ => float_value.is_infinite(),
+                    }))),
+                    _ => Ok(None),
+                }
+            }
             "round" => {
                 if arguments.len() != 1 {
                     return Err(method_argument_error(
@@ -57,7 +169,71 @@ impl VirtualMachine {
                     Ok(None)
                 }
             }
+            "format" => match receiver {
+                Object::Float(float_value) => {
+                    let mut format = NumberFormat::with_precision(2);
+                    apply_number_format_options(
+                        method_name,
+                        arguments,
+                        true,
+                        &mut format,
+                        position,
+                    )?;
+                    Ok(Some(Object::string(format_float(*float_value, &format))))
+                }
+                _ => Ok(None),
+            },
+            "humanize" => match receiver {
+                Object::Float(float_value) => {
+                    let mut format = NumberFormat::with_precision(1);
+                    apply_number_format_options(
+                        method_name,
+                        arguments,
+                        true,
+                        &mut format,
+                        position,
+                    )?;
+                    Ok(Some(Object::string(humanize(*float_value, &format))))
+                }
+                _ => Ok(None),
+            },
+            // Float#to_d - the Float's shortest written form as a Decimal
+            "to_d" => to_decimal(receiver, position).map(|value| Some(Object::Decimal(value))),
+            "to_duration" | "ago_in_words" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let Object::Float(float_value) = receiver else {
+                    return Ok(None);
+                };
+                let text = if method_name == "to_duration" {
+                    format_duration(*float_value)
+                } else {
+                    ago_in_words(*float_value)
+                };
+                Ok(Some(Object::string(text)))
+            }
             _ => Ok(None),
         }
     }
 }
+
+/// Convert an already-rounded float to an Int, rejecting NaN and out-of-range values.
+fn float_to_int(value: f64, method_name: &str, position: Position) -> Result<Object, MetorexError> {
+    if value.is_finite() && value >= i64::MIN as f64 && value <= i64::MAX as f64 {
+        Ok(Object::Int(value as i64))
+    } else {
+        Err(MetorexError::runtime_error(
+            format!(
+                "Float.{} cannot convert {} to an Integer",
+                method_name, value
+            ),
+            position_to_location(position),
+        ))
+    }
+}
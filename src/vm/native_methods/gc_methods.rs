@@ -0,0 +1,140 @@
+//! Native method implementations for the GC class.
+
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::vm::errors::*;
+use crate::vm::utils::position_to_location;
+use crate::vm::{ClassStats, HeapStats, VirtualMachine};
+use indexmap::IndexMap;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+impl VirtualMachine {
+    /// Execute class methods on GC.
+    pub(crate) fn call_gc_method(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let result = match method_name {
+            "stats" | "start" | "enable" | "disable" | "count" | "enabled?" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let heap = self.heap();
+                let mut heap = heap.borrow_mut();
+                match method_name {
+                    "stats" => stats_to_hash(&heap.stats()),
+                    "start" => {
+                        heap.collect();
+                        Object::Nil
+                    }
+                    // enable/disable report whether collection was disabled before the call
+                    "enable" => Object::Bool(!heap.set_enabled(true)),
+                    "disable" => Object::Bool(!heap.set_enabled(false)),
+                    "count" => Object::Int(heap.stats().collections as i64),
+                    _ => Object::Bool(heap.is_enabled()),
+                }
+            }
+            "stats_by_class" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                class_stats_to_hash(&self.heap_stats_by_class())
+            }
+            // GC.assert_no_leaks { ... } - raise if objects the block allocated outlive it
+            "assert_no_leaks" => {
+                let block = match arguments {
+                    [block @ Object::Block(_)] => block.clone(),
+                    _ => {
+                        return Err(MetorexError::runtime_error(
+                            "assert_no_leaks expects a block".to_string(),
+                            position_to_location(position),
+                        ));
+                    }
+                };
+                // The block's value is dropped before the check so it does not count as a leak
+                let mut outcome = Ok(());
+                let report = self.check_leaks(|vm| {
+                    outcome = vm.invoke_callable(block, Vec::new(), position).map(drop);
+                });
+                outcome?;
+                if !report.is_empty() {
+                    return Err(raised_exception_error(
+                        "RuntimeError",
+                        report.to_string(),
+                        position,
+                    ));
+                }
+                Object::Nil
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(result))
+    }
+}
+
+/// Convert heap statistics to a Hash keyed by symbols, with pauses in milliseconds.
+fn stats_to_hash(stats: &HeapStats) -> Object {
+    let mut map = IndexMap::new();
+    map.insert(
+        ":allocations".to_string(),
+        Object::Int(stats.allocations as i64),
+    );
+    map.insert(
+        ":live_objects".to_string(),
+        Object::Int(stats.live_objects as i64),
+    );
+    map.insert(
+        ":collections".to_string(),
+        Object::Int(stats.collections as i64),
+    );
+    map.insert(
+        ":last_pause_ms".to_string(),
+        Object::Float(millis(stats.last_pause)),
+    );
+    map.insert(
+        ":total_pause_ms".to_string(),
+        Object::Float(millis(stats.total_pause)),
+    );
+    map.insert(":enabled".to_string(), Object::Bool(stats.enabled));
+    map.insert(
+        ":threshold".to_string(),
+        Object::Int(stats.threshold as i64),
+    );
+    Object::dict(map)
+}
+
+/// Convert per-class statistics to a Hash of class names to counter Hashes.
+fn class_stats_to_hash(stats: &BTreeMap<String, ClassStats>) -> Object {
+    let mut map = IndexMap::new();
+    for (class_name, class_stats) in stats {
+        let mut counts = IndexMap::new();
+        counts.insert(
+            ":allocations".to_string(),
+            Object::Int(class_stats.allocations as i64),
+        );
+        counts.insert(
+            ":live_objects".to_string(),
+            Object::Int(class_stats.live_objects as i64),
+        );
+        map.insert(class_name.clone(), Object::dict(counts));
+    }
+    Object::dict(map)
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
@@ -1,11 +1,15 @@
 //! Native method implementations for the Hash class.
 
+use super::iteration::optional_block;
+use crate::callable::Callable;
 use crate::error::MetorexError;
 use crate::lexer::Position;
 use crate::object::Object;
 use crate::vm::VirtualMachine;
 use crate::vm::errors::*;
+use crate::vm::utils::object_to_dict_key;
 use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::rc::Rc;
 
 impl VirtualMachine {
@@ -64,21 +68,13 @@ impl VirtualMachine {
                 }
                 if let Object::Dict(dict_rc) = receiver {
                     let key_obj = &arguments[0];
-                    // Convert key object to string representation (same as used for dict keys)
-                    let key_str = match key_obj {
-                        Object::String(s) => s.as_str().to_string(),
-                        Object::Int(i) => i.to_string(),
-                        Object::Float(f) => f.to_string(),
-                        Object::Bool(b) => b.to_string(),
-                        Object::Nil => "nil".to_string(),
-                        _ => {
-                            return Err(method_argument_type_error(
-                                method_name,
-                                "String, Integer, Float, Bool, or Nil",
-                                key_obj,
-                                position,
-                            ));
-                        }
+                    let Some(key_str) = object_to_dict_key(key_obj) else {
+                        return Err(method_argument_type_error(
+                            method_name,
+                            "String, Symbol, Integer, Float, Bool, or Nil",
+                            key_obj,
+                            position,
+                        ));
                     };
                     let dict = dict_rc.borrow();
                     Ok(Some(Object::Bool(dict.contains_key(&key_str))))
@@ -97,15 +93,7 @@ impl VirtualMachine {
                 }
                 if let Object::Dict(dict_rc) = receiver {
                     let dict = dict_rc.borrow();
-                    let entries: Vec<Object> = dict
-                        .iter()
-                        .map(|(k, v)| {
-                            Object::Array(Rc::new(RefCell::new(vec![
-                                Object::string(k.clone()),
-                                v.clone(),
-                            ])))
-                        })
-                        .collect();
+                    let entries: Vec<Object> = dict.iter().map(|(k, v)| entry_pair(k, v)).collect();
                     Ok(Some(Object::Array(Rc::new(RefCell::new(entries)))))
                 } else {
                     Ok(None)
@@ -126,6 +114,120 @@ impl VirtualMachine {
                     Ok(None)
                 }
             }
+            // identity? - plain Hashes compare keys by value
+            "identity?" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                Ok(Some(Object::Bool(false)))
+            }
+            // each - yields [key, value] pairs, or the key and value apart
+            // to a block taking two parameters
+            "each" => {
+                let Object::Dict(dict_rc) = receiver else {
+                    return Ok(None);
+                };
+                let block = optional_block(method_name, arguments, 0, position)?;
+                let spread = block
+                    .as_ref()
+                    .is_none_or(|block| block.parameters().len() >= 2);
+                let entries: Vec<Vec<Object>> = dict_rc
+                    .borrow()
+                    .iter()
+                    .map(|(key, value)| {
+                        if spread {
+                            vec![Object::string(key.clone()), value.clone()]
+                        } else {
+                            vec![entry_pair(key, value)]
+                        }
+                    })
+                    .collect();
+                self.yield_each_values(receiver, block, entries.into_iter())
+            }
+            // Without a block, an Enumerator over the [key, value] pairs
+            "sort_by" | "min_by" | "max_by" if arguments.is_empty() => {
+                let Object::Dict(dict_rc) = receiver else {
+                    return Ok(None);
+                };
+                let entries: Vec<Vec<Object>> = dict_rc
+                    .borrow()
+                    .iter()
+                    .map(|(key, value)| vec![Object::string(key.clone()), value.clone()])
+                    .collect();
+                self.yield_each_values(receiver, None, entries.into_iter())
+            }
+            "sort_by" | "min_by" | "max_by" => {
+                if arguments.len() != 1 {
+                    return Err(method_argument_error(
+                        method_name,
+                        1,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let Object::Dict(dict_rc) = receiver else {
+                    return Ok(None);
+                };
+                let block = match &arguments[0] {
+                    Object::Block(block) => block.clone(),
+                    other => {
+                        return Err(method_argument_type_error(
+                            method_name,
+                            "Block",
+                            other,
+                            position,
+                        ));
+                    }
+                };
+
+                // Pair each [key, value] entry with the block's sort key
+                let entries: Vec<(String, Object)> = dict_rc
+                    .borrow()
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect();
+                let mut keyed = Vec::with_capacity(entries.len());
+                for (key, value) in entries {
+                    let args = if block.parameters().len() >= 2 {
+                        vec![Object::string(key.clone()), value.clone()]
+                    } else {
+                        vec![entry_pair(&key, &value)]
+                    };
+                    let sort_key = self.execute_block_body(&block, args)?;
+                    keyed.push((sort_key, entry_pair(&key, &value)));
+                }
+
+                if method_name == "sort_by" {
+                    // sort_by is stable, so entries with equal keys keep insertion order
+                    let pairs = self.sort_keyed(keyed, position)?;
+                    return Ok(Some(Object::Array(Rc::new(RefCell::new(pairs)))));
+                }
+
+                // min_by/max_by return the first entry with the smallest/largest key
+                let wanted = if method_name == "min_by" {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                };
+                let mut best: Option<(Object, Object)> = None;
+                for (sort_key, pair) in keyed {
+                    let replace = match &best {
+                        Some((best_key, _)) => {
+                            self.compare_values(&sort_key, best_key, position)? == wanted
+                        }
+                        None => true,
+                    };
+                    if replace {
+                        best = Some((sort_key, pair));
+                    }
+                }
+                Ok(Some(best.map(|(_, pair)| pair).unwrap_or(Object::Nil)))
+            }
             "[]" => {
                 if arguments.len() != 1 {
                     return Err(method_argument_error(
@@ -145,3 +247,11 @@ impl VirtualMachine {
         }
     }
 }
+
+/// Build the [key, value] array used to represent a Hash entry.
+fn entry_pair(key: &str, value: &Object) -> Object {
+    Object::Array(Rc::new(RefCell::new(vec![
+        Object::string(key),
+        value.clone(),
+    ])))
+}
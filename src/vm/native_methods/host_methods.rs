@@ -0,0 +1,98 @@
+//! Host-defined native methods registered by embedders at runtime.
+//!
+//! Embedders can extend any class (including builtins like String) with Rust
+//! closures via `VirtualMachine::define_native_method`. Each registration also
+//! installs a placeholder `Method` on the class so lookup and reflection
+//! (`respond_to?`) see it; method tables are consulted on every call, so new
+//! definitions take effect immediately.
+
+use crate::ast::FORWARDING_PARAMETER;
+use crate::class::Class;
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::{Method, Object};
+use crate::vm::VirtualMachine;
+use crate::vm::utils::position_to_location;
+use std::rc::Rc;
+
+/// Rust closure backing a host-defined method: receives the receiver and the
+/// evaluated arguments, and returns the result or an error message.
+pub type NativeMethod = Rc<dyn Fn(&Object, &[Object]) -> Result<Object, String>>;
+
+impl VirtualMachine {
+    /// Register a Rust closure as a method on the named class.
+    ///
+    /// ```ignore
+    /// vm.define_native_method("String", "shout", |receiver, _args| {
+    ///     Ok(Object::string(format!("{}!", receiver).to_uppercase()))
+    /// })?;
+    /// ```
+    pub fn define_native_method<F>(
+        &mut self,
+        class_name: &str,
+        method_name: &str,
+        function: F,
+    ) -> Result<(), MetorexError>
+    where
+        F: Fn(&Object, &[Object]) -> Result<Object, String> + 'static,
+    {
+        let class = match self.environment().get(class_name) {
+            Some(Object::Class(class)) => class,
+            _ => {
+                return Err(MetorexError::runtime_error(
+                    format!(
+                        "Cannot define native method on unknown class '{}'",
+                        class_name
+                    ),
+                    position_to_location(Position::default()),
+                ));
+            }
+        };
+
+        // Placeholder so method lookup and reflection see the host method
+        let placeholder = Rc::new(Method::with_owner(
+            method_name.to_string(),
+            vec![FORWARDING_PARAMETER.to_string()],
+            vec![],
+            class_name.to_string(),
+        ));
+        class.define_method(method_name, placeholder);
+
+        self.host_methods.insert(
+            (class_name.to_string(), method_name.to_string()),
+            Rc::new(function),
+        );
+
+        Ok(())
+    }
+
+    /// Execute a host-defined method found on the class or one of its ancestors.
+    pub(crate) fn call_host_method(
+        &mut self,
+        class: &Class,
+        receiver: &Object,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        if self.host_methods.is_empty() {
+            return Ok(None);
+        }
+
+        let mut current = Some(class.name().to_string());
+        let mut superclass = class.superclass();
+        while let Some(class_name) = current {
+            let key = (class_name, method_name.to_string());
+            if let Some(function) = self.host_methods.get(&key).cloned() {
+                return function(receiver, arguments).map(Some).map_err(|message| {
+                    MetorexError::runtime_error(message, position_to_location(position))
+                });
+            }
+
+            current = superclass.as_ref().map(|parent| parent.name().to_string());
+            superclass = superclass.and_then(|parent| parent.superclass());
+        }
+
+        Ok(None)
+    }
+}
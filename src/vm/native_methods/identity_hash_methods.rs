@@ -0,0 +1,180 @@
+//! Native method implementations for IdentityHash, the Hash `Hash.identity`
+//! creates.
+//!
+//! Keys are matched by `object_id` rather than by value, so any object can be
+//! a key and two equal Arrays are two different keys. That is what visited
+//! sets need when walking object graphs that may contain cycles. The entries
+//! live in the `entries` instance variable as [key, value] pairs indexed by
+//! the key's id, in insertion order.
+
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use indexmap::IndexMap;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Entries of an IdentityHash, indexed by each key's `object_id`
+type IdentityEntries = Rc<RefCell<IndexMap<String, Object>>>;
+
+impl VirtualMachine {
+    /// Execute class methods on Hash.
+    pub(crate) fn call_hash_class_method(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        match method_name {
+            // Hash.identity - an empty Hash that compares keys by identity
+            "identity" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let hash = Object::instance(Rc::clone(&self.builtins().identity_hash_class));
+                if let Object::Instance(instance) = &hash {
+                    instance
+                        .borrow_mut()
+                        .set_var("entries".to_string(), Object::Dict(Rc::default()));
+                }
+                Ok(Some(self.track_allocation(hash)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Execute native methods for the IdentityHash class.
+    pub(crate) fn call_identity_hash_method(
+        &mut self,
+        receiver: &Object,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let Some(entries) = self.identity_hash_entries(receiver) else {
+            return Ok(None);
+        };
+        let expected = match method_name {
+            "[]" | "has_key?" | "key?" | "delete" => 1,
+            "[]=" => 2,
+            "keys" | "values" | "entries" | "to_a" | "length" | "size" | "identity?" => 0,
+            _ => return Ok(None),
+        };
+        if arguments.len() != expected {
+            return Err(method_argument_error(
+                method_name,
+                expected,
+                arguments.len(),
+                position,
+            ));
+        }
+
+        let result = match method_name {
+            "[]" => self.identity_hash_get(&entries, &arguments[0], position)?,
+            "[]=" => {
+                self.identity_hash_insert(&entries, &arguments[0], arguments[1].clone(), position)?;
+                arguments[1].clone()
+            }
+            "has_key?" | "key?" => {
+                let id = self.identity_key(&arguments[0]);
+                Object::Bool(entries.borrow().contains_key(&id))
+            }
+            // delete(key) - remove an entry, returning its value or nil
+            "delete" => {
+                let id = self.identity_key(&arguments[0]);
+                let mut entries = entries
+                    .try_borrow_mut()
+                    .map_err(|_| collection_in_use_error(receiver, position))?;
+                entries
+                    .shift_remove(&id)
+                    .map_or(Object::Nil, |pair| pair_part(&pair, 1))
+            }
+            "keys" | "values" => {
+                let part = usize::from(method_name == "values");
+                Object::array(
+                    entries
+                        .borrow()
+                        .values()
+                        .map(|pair| pair_part(pair, part))
+                        .collect(),
+                )
+            }
+            "entries" | "to_a" => Object::array(
+                entries
+                    .borrow()
+                    .values()
+                    .map(|pair| Object::array(vec![pair_part(pair, 0), pair_part(pair, 1)]))
+                    .collect(),
+            ),
+            "length" | "size" => Object::Int(entries.borrow().len() as i64),
+            "identity?" => Object::Bool(true),
+            _ => unreachable!(),
+        };
+        Ok(Some(result))
+    }
+
+    /// The entries of an IdentityHash, or `None` for any other value.
+    pub(crate) fn identity_hash_entries(&self, object: &Object) -> Option<IdentityEntries> {
+        let Object::Instance(instance) = object else {
+            return None;
+        };
+        let instance = instance.borrow();
+        if !Rc::ptr_eq(&instance.class, &self.builtins().identity_hash_class) {
+            return None;
+        }
+        match instance.get_var("entries") {
+            Some(Object::Dict(entries)) => Some(Rc::clone(entries)),
+            _ => None,
+        }
+    }
+
+    /// The value stored under `key`, compared by identity.
+    pub(crate) fn identity_hash_get(
+        &self,
+        entries: &IdentityEntries,
+        key: &Object,
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        let id = self.identity_key(key);
+        entries
+            .borrow()
+            .get(&id)
+            .map(|pair| pair_part(pair, 1))
+            .ok_or_else(|| undefined_dictionary_key_error(&key.to_string(), position))
+    }
+
+    /// Store `value` under `key`, compared by identity.
+    pub(crate) fn identity_hash_insert(
+        &self,
+        entries: &IdentityEntries,
+        key: &Object,
+        value: Object,
+        position: Position,
+    ) -> Result<(), MetorexError> {
+        let id = self.identity_key(key);
+        let mut entries = entries
+            .try_borrow_mut()
+            .map_err(|_| collection_in_use_error(&Object::Dict(Rc::clone(entries)), position))?;
+        entries.insert(id, Object::array(vec![key.clone(), value]));
+        Ok(())
+    }
+
+    fn identity_key(&self, key: &Object) -> String {
+        self.object_id(key).to_string()
+    }
+}
+
+/// The key (0) or value (1) of a stored [key, value] pair
+fn pair_part(pair: &Object, index: usize) -> Object {
+    match pair {
+        Object::Array(pair) => pair.borrow().get(index).cloned().unwrap_or(Object::Nil),
+        _ => Object::Nil,
+    }
+}
@@ -0,0 +1,81 @@
+//! Native method implementations for the Inflector class.
+//!
+//! Each method takes a name as a String or Symbol and returns a String, so
+//! `Inflector.camelize(:user_name)` gives "UserName".
+
+use crate::error::MetorexError;
+use crate::inflector;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+
+impl VirtualMachine {
+    /// Execute class methods on Inflector.
+    pub(crate) fn call_inflector_method(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let inflect: fn(&str) -> String = match method_name {
+            "underscore" => inflector::underscore,
+            "dasherize" => inflector::dasherize,
+            "titleize" => inflector::titleize,
+            "pluralize" => inflector::pluralize,
+            "singularize" => inflector::singularize,
+            // camelize(name, first_letter = :upper)
+            "camelize" => {
+                let upper_first = match arguments.get(1) {
+                    None => true,
+                    Some(Object::Symbol(first)) if first.as_str() == "upper" => true,
+                    Some(Object::Symbol(first)) if first.as_str() == "lower" => false,
+                    Some(other) => {
+                        return Err(raised_exception_error(
+                            "ArgumentError",
+                            format!(
+                                "{}: first letter must be :upper or :lower, got {}",
+                                method_name, other
+                            ),
+                            position,
+                        ));
+                    }
+                };
+                let name = name_argument(method_name, arguments, 2, position)?;
+                return Ok(Some(Object::string(inflector::camelize(
+                    &name,
+                    upper_first,
+                ))));
+            }
+            _ => return Ok(None),
+        };
+        let name = name_argument(method_name, arguments, 1, position)?;
+        Ok(Some(Object::string(inflect(&name))))
+    }
+}
+
+/// The name in the first of at most `max` arguments, as text.
+fn name_argument(
+    method_name: &str,
+    arguments: &[Object],
+    max: usize,
+    position: Position,
+) -> Result<String, MetorexError> {
+    match arguments.first() {
+        Some(Object::String(name) | Object::Symbol(name)) if arguments.len() <= max => {
+            Ok(name.to_string())
+        }
+        Some(other) if arguments.len() <= max => Err(method_argument_type_error(
+            method_name,
+            "String or Symbol",
+            other,
+            position,
+        )),
+        _ => Err(method_argument_error(
+            method_name,
+            1,
+            arguments.len(),
+            position,
+        )),
+    }
+}
@@ -0,0 +1,229 @@
+//! Native method implementations for the Integer class.
+
+use super::iteration::optional_block;
+use super::number_format_options::apply_number_format_options;
+use crate::duration::{ago_in_words, format_duration};
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::number_format::{NumberFormat, format_integer, humanize};
+use crate::object::{Decimal, Object};
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+
+impl VirtualMachine {
+    /// Execute native methods for the Integer class.
+    pub(crate) fn call_integer_method(
+        &mut self,
+        receiver: &Object,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let Object::Int(value) = receiver else {
+            return Ok(None);
+        };
+        let value = *value;
+
+        match method_name {
+            "times" => {
+                let block = optional_block(method_name, arguments, 0, position)?;
+                self.yield_each(receiver, block, (0..value.max(0)).map(Object::Int))
+            }
+            "upto" | "downto" => {
+                let block = optional_block(method_name, arguments, 1, position)?;
+                let limit = match &arguments[0] {
+                    Object::Int(limit) => *limit,
+                    other => {
+                        return Err(method_argument_type_error(
+                            method_name,
+                            "Integer",
+                            other,
+                            position,
+                        ));
+                    }
+                };
+
+                if method_name == "upto" {
+                    self.yield_each(receiver, block, (value..=limit).map(Object::Int))
+                } else {
+                    self.yield_each(receiver, block, (limit..=value).rev().map(Object::Int))
+                }
+            }
+            "abs" | "even?" | "odd?" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                Ok(Some(match method_name {
+                    "abs" => {
+                        Object::Int(int_in_range(value.unsigned_abs(), method_name, position)?)
+                    }
+                    "even?" => Object::Bool(value % 2 == 0),
+                    _ => Object::Bool(value % 2 != 0),
+                }))
+            }
+            "gcd" => {
+                let other = match arguments {
+                    [Object::Int(other)] => *other,
+                    [other] => {
+                        return Err(method_argument_type_error(
+                            method_name,
+                            "Integer",
+                            other,
+                            position,
+                        ));
+                    }
+                    _ => {
+                        return Err(method_argument_error(
+                            method_name,
+                            1,
+                            arguments.len(),
+                            position,
+                        ));
+                    }
+                };
+                let divisor = gcd(value.unsigned_abs(), other.unsigned_abs());
+                Ok(Some(Object::Int(int_in_range(
+                    divisor,
+                    method_name,
+                    position,
+                )?)))
+            }
+            "to_s" => {
+                let base = match arguments {
+                    [] => 10,
+                    [Object::Int(base)] if (2..=36).contains(base) => *base as u32,
+                    [Object::Int(base)] => {
+                        return Err(raised_exception_error(
+                            "ArgumentError",
+                            format!("Integer#to_s base must be between 2 and 36, got {}", base),
+                            position,
+                        ));
+                    }
+                    [other] => {
+                        return Err(method_argument_type_error(
+                            method_name,
+                            "Integer",
+                            other,
+                            position,
+                        ));
+                    }
+                    _ => {
+                        return Err(method_argument_error(
+                            method_name,
+                            1,
+                            arguments.len(),
+                            position,
+                        ));
+                    }
+                };
+                Ok(Some(Object::string(to_radix(value, base))))
+            }
+            "to_f" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                Ok(Some(Object::Float(value as f64)))
+            }
+            "to_d" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                Ok(Some(Object::Decimal(Decimal::from(value))))
+            }
+            "to_int" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                Ok(Some(Object::Int(value)))
+            }
+            "format" => {
+                let mut format = NumberFormat::with_precision(0);
+                apply_number_format_options(method_name, arguments, true, &mut format, position)?;
+                Ok(Some(Object::string(format_integer(value, &format))))
+            }
+            "humanize" => {
+                let mut format = NumberFormat::with_precision(1);
+                apply_number_format_options(method_name, arguments, true, &mut format, position)?;
+                Ok(Some(Object::string(humanize(value as f64, &format))))
+            }
+            "to_duration" | "ago_in_words" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let text = if method_name == "to_duration" {
+                    format_duration(value as f64)
+                } else {
+                    ago_in_words(value as f64)
+                };
+                Ok(Some(Object::string(text)))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// The greatest common divisor of `a` and `b`, by Euclid's algorithm
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// `value` as an Int, or an ArgumentError when only the magnitude of
+/// `i64::MIN` reaches it
+fn int_in_range(value: u64, method_name: &str, position: Position) -> Result<i64, MetorexError> {
+    i64::try_from(value).map_err(|_| {
+        raised_exception_error(
+            "ArgumentError",
+            format!(
+                "Integer#{} result {} is out of the Int range",
+                method_name, value
+            ),
+            position,
+        )
+    })
+}
+
+/// `value` written in `base` (2 to 36) with lowercase digits
+fn to_radix(value: i64, base: u32) -> String {
+    let mut magnitude = value.unsigned_abs();
+    let mut digits = Vec::new();
+    loop {
+        let digit = (magnitude % base as u64) as u32;
+        digits.push(char::from_digit(digit, base).unwrap_or('?'));
+        magnitude /= base as u64;
+        if magnitude == 0 {
+            break;
+        }
+    }
+    if value < 0 {
+        digits.push('-');
+    }
+    digits.iter().rev().collect()
+}
@@ -0,0 +1,101 @@
+//! Block iteration shared by the iterator methods of builtin classes, such as
+//! `Integer#times` and `String#each_line`.
+
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::{BlockStatement, Object};
+use crate::vm::errors::*;
+use crate::vm::utils::{format_exception, position_to_location};
+use crate::vm::{ControlFlow, VirtualMachine};
+use std::rc::Rc;
+
+impl VirtualMachine {
+    /// Yield each item to the block and return the receiver, or return an
+    /// Enumerator over the items when no block is given.
+    ///
+    /// `break` inside the block stops the iteration early and `continue` moves on
+    /// to the following item.
+    pub(super) fn yield_each(
+        &mut self,
+        receiver: &Object,
+        block: Option<Rc<BlockStatement>>,
+        items: impl Iterator<Item = Object>,
+    ) -> Result<Option<Object>, MetorexError> {
+        let Some(block) = block else {
+            return Ok(Some(self.new_enumerator(items.collect(), false)));
+        };
+
+        for item in items {
+            if !self.yield_to_block(&block, vec![item])? {
+                break;
+            }
+        }
+
+        Ok(Some(receiver.clone()))
+    }
+
+    /// Like [`Self::yield_each`] for iterators that pass the block several
+    /// values at a time, such as an element and its index. Without a block
+    /// the Enumerator holds each group of values as an Array.
+    pub(super) fn yield_each_values(
+        &mut self,
+        receiver: &Object,
+        block: Option<Rc<BlockStatement>>,
+        groups: impl Iterator<Item = Vec<Object>>,
+    ) -> Result<Option<Object>, MetorexError> {
+        let Some(block) = block else {
+            let groups = groups.map(Object::array).collect();
+            return Ok(Some(self.new_enumerator(groups, true)));
+        };
+
+        for values in groups {
+            if !self.yield_to_block(&block, values)? {
+                break;
+            }
+        }
+
+        Ok(Some(receiver.clone()))
+    }
+
+    /// Run the block for one item of an iteration. Returns false when the
+    /// block breaks out of the iteration.
+    pub(super) fn yield_to_block(
+        &mut self,
+        block: &BlockStatement,
+        arguments: Vec<Object>,
+    ) -> Result<bool, MetorexError> {
+        match self.execute_block_with_control_flow(block, arguments)? {
+            ControlFlow::Next | ControlFlow::Continue { .. } => Ok(true),
+            ControlFlow::Break { .. } => Ok(false),
+            ControlFlow::Retry { position } => Err(retry_outside_rescue_error(position)),
+            ControlFlow::Return { position, .. } => Err(loop_control_error("return", position)),
+            ControlFlow::Exception {
+                exception,
+                position,
+            } => Err(MetorexError::runtime_error(
+                format!("Uncaught exception: {}", format_exception(&exception)),
+                position_to_location(position),
+            )),
+        }
+    }
+}
+
+/// Validate the positional argument count of an iterator method and extract
+/// its optional trailing block.
+pub(super) fn optional_block(
+    method_name: &str,
+    arguments: &[Object],
+    expected: usize,
+    position: Position,
+) -> Result<Option<Rc<BlockStatement>>, MetorexError> {
+    match arguments.get(expected) {
+        Some(Object::Block(block)) if arguments.len() == expected + 1 => Ok(Some(Rc::clone(block))),
+        None if arguments.len() == expected => Ok(None),
+        _ => Err(method_argument_error(
+            method_name,
+            expected,
+            arguments.len(),
+            position,
+        )),
+    }
+}
@@ -0,0 +1,151 @@
+//! Native method implementations for the Metorex class, which describes the
+//! interpreter and how the host configured it.
+
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use crate::vm::native_functions::pretty_print_width;
+use indexmap::IndexMap;
+use std::path::{Path, PathBuf};
+
+/// Cargo features this build was compiled with, in alphabetical order.
+const FEATURES: &[(&str, bool)] = &[
+    ("sqlite", cfg!(feature = "sqlite")),
+    ("toml", cfg!(feature = "toml")),
+    ("yaml", cfg!(feature = "yaml")),
+];
+
+impl VirtualMachine {
+    /// Execute class methods on Metorex.
+    pub(crate) fn call_metorex_method(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        match method_name {
+            "parse" => return self.parse_source_to_node(arguments, position).map(Some),
+            "eval_ast" => return self.evaluate_ast_node(arguments, position).map(Some),
+            // Metorex.pp_width gives the width `pp` and the REPL wrap at;
+            // Metorex.pp_width(n) sets it, as a .metorexrc might
+            "pp_width" => {
+                return match arguments {
+                    [] => Ok(Some(Object::Int(self.pretty_printer().width() as i64))),
+                    [width] => {
+                        let width = pretty_print_width(width, position)?;
+                        self.set_pretty_print_width(width);
+                        Ok(Some(Object::Int(width as i64)))
+                    }
+                    _ => Err(method_argument_error(
+                        method_name,
+                        1,
+                        arguments.len(),
+                        position,
+                    )),
+                };
+            }
+            _ => {}
+        }
+        let expected = match method_name {
+            "VERSION" | "features" | "vm_options" | "load_path" => 0,
+            "feature?" => 1,
+            _ => return Ok(None),
+        };
+        if arguments.len() != expected {
+            return Err(method_argument_error(
+                method_name,
+                expected,
+                arguments.len(),
+                position,
+            ));
+        }
+
+        let result = match method_name {
+            "VERSION" => Object::string(crate::version()),
+            "features" => Object::array(enabled_features().map(Object::string).collect()),
+            "feature?" => match &arguments[0] {
+                Object::String(name) | Object::Symbol(name) => {
+                    Object::Bool(enabled_features().any(|feature| feature == name.as_str()))
+                }
+                other => {
+                    return Err(method_argument_type_error(
+                        method_name,
+                        "String",
+                        other,
+                        position,
+                    ));
+                }
+            },
+            "vm_options" => self.vm_options_hash(),
+            // require_relative resolves against the running file's directory,
+            // which code outside a file, such as REPL input, does not have;
+            // require searches the configured load path
+            _ => Object::array(
+                self.get_current_file()
+                    .and_then(|file| file.parent())
+                    .map(|dir| {
+                        if dir.as_os_str().is_empty() {
+                            Path::new(".")
+                        } else {
+                            dir
+                        }
+                    })
+                    .into_iter()
+                    .chain(self.options().load_path.iter().map(PathBuf::as_path))
+                    .map(|dir| Object::string(dir.to_string_lossy()))
+                    .collect(),
+            ),
+        };
+        Ok(Some(result))
+    }
+
+    /// The VM options as a Hash keyed by the same names `MX_OPTS` uses.
+    fn vm_options_hash(&self) -> Object {
+        let options = self.options();
+        let seed = options.seed.map_or(Object::Nil, |seed| {
+            i64::try_from(seed).map_or_else(|_| Object::string(seed.to_string()), Object::Int)
+        });
+
+        let mut map = IndexMap::new();
+        map.insert(
+            ":stack_depth".to_string(),
+            Object::Int(options.max_stack_depth as i64),
+        );
+        map.insert(
+            ":opt_level".to_string(),
+            Object::Int(options.optimization_level as i64),
+        );
+        map.insert(":warnings".to_string(), Object::Bool(options.warnings));
+        map.insert(":seed".to_string(), seed);
+        map.insert(":ast_cache".to_string(), Object::Bool(options.ast_cache));
+        map.insert(
+            ":check_types".to_string(),
+            Object::Bool(options.check_types),
+        );
+        map.insert(":contracts".to_string(), Object::Bool(options.contracts));
+        map.insert(
+            ":case_insensitive_paths".to_string(),
+            Object::Bool(options.case_insensitive_paths),
+        );
+        map.insert(
+            ":load_path".to_string(),
+            Object::array(
+                options
+                    .load_path
+                    .iter()
+                    .map(|dir| Object::string(dir.to_string_lossy()))
+                    .collect(),
+            ),
+        );
+        Object::dict(map)
+    }
+}
+
+fn enabled_features() -> impl Iterator<Item = &'static str> {
+    FEATURES
+        .iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| *name)
+}
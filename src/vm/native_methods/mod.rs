@@ -4,12 +4,39 @@
 //! standard classes like Object, String, and Array.
 
 mod array_methods;
+mod ast_methods;
+mod base64_methods;
+mod class_methods;
+mod code_builder_methods;
+mod decimal_methods;
+mod digest_methods;
+mod enumerator_methods;
 mod exception_methods;
+mod file_methods;
 mod float_methods;
+mod gc_methods;
 mod hash_methods;
+mod host_methods;
+mod identity_hash_methods;
+mod inflector_methods;
+mod integer_methods;
+mod iteration;
+mod metorex_methods;
+mod module_methods;
+mod number_format_options;
 mod object_methods;
 mod range_methods;
+mod readline_methods;
+mod schema_methods;
+mod sorting;
+mod sqlite_methods;
+mod string_builder_methods;
 mod string_methods;
+mod timeout_methods;
+mod toml_methods;
+mod trace_point_methods;
+mod uri_methods;
+mod yaml_methods;
 
 use super::VirtualMachine;
 use crate::class::Class;
@@ -18,6 +45,9 @@ use crate::lexer::Position;
 use crate::object::Object;
 use std::rc::Rc;
 
+pub(crate) use ast_methods::node_field;
+pub use host_methods::NativeMethod;
+
 impl VirtualMachine {
     /// Attempt to execute a native (built-in) method implementation.
     ///
@@ -32,6 +62,17 @@ impl VirtualMachine {
         arguments: &[Object],
         position: Position,
     ) -> Result<Option<Object>, MetorexError> {
+        // Host-defined methods take precedence so embedders can extend or override builtins
+        if let Some(result) =
+            self.call_host_method(class, receiver, method_name, arguments, position)?
+        {
+            return Ok(Some(result));
+        }
+
+        let converted =
+            self.check_builtin_method_arguments(class, receiver, method_name, arguments, position)?;
+        let arguments = converted.as_deref().unwrap_or(arguments);
+
         // Special handling for Block/Lambda objects
         if let Object::Block(block) = receiver {
             match method_name {
@@ -48,9 +89,31 @@ impl VirtualMachine {
             }
         }
 
+        if let Object::Module(module) = receiver
+            && let Some(result) =
+                self.call_module_method(module, method_name, arguments, position)?
+        {
+            return Ok(Some(result));
+        }
+
         // Special handling for Class objects
         if let Object::Class(class_rc) = receiver {
             match method_name {
+                "new" if Rc::ptr_eq(class_rc, &self.builtins().trace_point_class) => {
+                    return self.new_trace_point(arguments, position).map(Some);
+                }
+                "new" if Rc::ptr_eq(class_rc, &self.builtins().string_builder_class) => {
+                    return self.new_string_builder(arguments, position).map(Some);
+                }
+                "new" if Rc::ptr_eq(class_rc, &self.builtins().code_builder_class) => {
+                    return self.new_code_builder(arguments, position).map(Some);
+                }
+                "new" if Rc::ptr_eq(class_rc, &self.builtins().file_class) => {
+                    return self.new_file(arguments, position).map(Some);
+                }
+                "new" if Rc::ptr_eq(class_rc, &self.builtins().decimal_class) => {
+                    return self.new_decimal(arguments, position).map(Some);
+                }
                 "new" => {
                     // Delegate to invoke_callable which handles instance creation and initialize
                     return self
@@ -66,6 +129,129 @@ impl VirtualMachine {
                 }
                 _ => {}
             }
+
+            if let Some(result) =
+                self.call_class_method(class_rc, method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
+
+            if Rc::ptr_eq(class_rc, &self.builtins().string_class)
+                && let Some(result) =
+                    self.call_string_class_method(method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
+
+            if Rc::ptr_eq(class_rc, &self.builtins().float_class)
+                && let Some(result) =
+                    self.call_float_class_method(method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
+
+            if Rc::ptr_eq(class_rc, &self.builtins().decimal_class)
+                && let Some(result) =
+                    self.call_decimal_class_method(method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
+
+            if Rc::ptr_eq(class_rc, &self.builtins().timeout_class)
+                && let Some(result) = self.call_timeout_method(method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
+
+            if Rc::ptr_eq(class_rc, &self.builtins().module_class)
+                && let Some(result) =
+                    self.call_module_class_method(method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
+
+            if Rc::ptr_eq(class_rc, &self.builtins().gc_class)
+                && let Some(result) = self.call_gc_method(method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
+
+            if Rc::ptr_eq(class_rc, &self.builtins().file_class)
+                && let Some(result) = self.call_file_method(method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
+
+            if Rc::ptr_eq(class_rc, &self.builtins().base64_class)
+                && let Some(result) = self.call_base64_method(method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
+
+            if Rc::ptr_eq(class_rc, &self.builtins().digest_class)
+                && let Some(result) = self.call_digest_method(method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
+
+            if Rc::ptr_eq(class_rc, &self.builtins().inflector_class)
+                && let Some(result) =
+                    self.call_inflector_method(method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
+
+            if Rc::ptr_eq(class_rc, &self.builtins().uri_class)
+                && let Some(result) =
+                    self.call_uri_class_method(method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
+
+            if Rc::ptr_eq(class_rc, &self.builtins().sqlite_class)
+                && let Some(result) =
+                    self.call_sqlite_class_method(method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
+
+            if Rc::ptr_eq(class_rc, &self.builtins().yaml_class)
+                && let Some(result) = self.call_yaml_method(method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
+
+            if Rc::ptr_eq(class_rc, &self.builtins().schema_class)
+                && let Some(result) =
+                    self.call_schema_class_method(method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
+
+            if Rc::ptr_eq(class_rc, &self.builtins().toml_class)
+                && let Some(result) = self.call_toml_method(method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
+
+            if Rc::ptr_eq(class_rc, &self.builtins().metorex_class)
+                && let Some(result) = self.call_metorex_method(method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
+
+            if Rc::ptr_eq(class_rc, &self.builtins().readline_class)
+                && let Some(result) = self.call_readline_method(method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
+
+            if Rc::ptr_eq(class_rc, &self.builtins().hash_class)
+                && let Some(result) =
+                    self.call_hash_class_method(method_name, arguments, position)?
+            {
+                return Ok(Some(result));
+            }
         }
 
         // Special handling for Method objects
@@ -79,11 +265,25 @@ impl VirtualMachine {
                     return Ok(Some(Object::String(Rc::new(owner_name.to_string()))));
                 }
                 "source_location" => {
-                    if let Some(loc) = &method_obj.source_location {
-                        return Ok(Some(Object::String(Rc::new(loc.to_string()))));
-                    } else {
-                        return Ok(Some(Object::String(Rc::new("unknown".to_string()))));
-                    }
+                    // [file, line], with a nil file for methods defined outside a file
+                    let location = method_obj.source_location.as_ref().map(|loc| {
+                        let file = loc.filename.clone().map_or(Object::Nil, Object::string);
+                        Object::array(vec![file, Object::Int(loc.line as i64)])
+                    });
+                    return Ok(Some(location.unwrap_or(Object::Nil)));
+                }
+                "source" => {
+                    let source = method_obj.source_text().map(Object::string);
+                    return Ok(Some(source.unwrap_or(Object::Nil)));
+                }
+                "ast" => {
+                    let ast = crate::object::to_object(&method_obj.definition()).map_err(|e| {
+                        MetorexError::runtime_error(
+                            format!("Cannot convert AST of '{}': {}", method_obj.name, e),
+                            crate::vm::utils::position_to_location(position),
+                        )
+                    })?;
+                    return Ok(Some(ast));
                 }
                 "parameters" => {
                     // Return an array of parameter names
@@ -100,16 +300,118 @@ impl VirtualMachine {
             }
         }
 
+        // Special handling for Binding objects
+        if let Object::Binding(binding) = receiver {
+            match (method_name, arguments) {
+                ("local_variables", []) => {
+                    let mut names = binding.keys();
+                    names.sort();
+                    return Ok(Some(Object::array(
+                        names.into_iter().map(|name| self.symbol(&name)).collect(),
+                    )));
+                }
+                ("local_variable_get", [Object::Symbol(name) | Object::String(name)]) => {
+                    let value = binding.get(name).ok_or_else(|| {
+                        super::errors::raised_exception_error(
+                            "NameError",
+                            format!("local variable '{}' is not defined for the binding", name),
+                            position,
+                        )
+                    })?;
+                    return Ok(Some(value.borrow().clone()));
+                }
+                ("eval", _) => {
+                    return self.eval_in_binding(binding, arguments, position).map(Some);
+                }
+                _ => {}
+            }
+        }
+
         // Dispatch to the appropriate class-specific method implementation
-        match class.name() {
-            "Object" => self.call_object_method(receiver, method_name, arguments, position),
-            "String" => self.call_string_method(receiver, method_name, arguments, position),
-            "Array" => self.call_array_method(receiver, method_name, arguments, position),
-            "Hash" => self.call_hash_method(receiver, method_name, arguments, position),
-            "Float" => self.call_float_method(receiver, method_name, arguments, position),
-            "Range" => self.call_range_method(receiver, method_name, arguments, position),
-            "Exception" => self.call_exception_method(receiver, method_name, arguments, position),
-            _ => Ok(None),
+        let result = match class.name() {
+            "Object" => return self.call_object_method(receiver, method_name, arguments, position),
+            "String" => self.call_string_method(receiver, method_name, arguments, position)?,
+            "Enumerator" => {
+                self.call_enumerator_method(receiver, method_name, arguments, position)?
+            }
+            "StringBuilder" => {
+                self.call_string_builder_method(receiver, method_name, arguments, position)?
+            }
+            "CodeBuilder" => {
+                self.call_code_builder_method(receiver, method_name, arguments, position)?
+            }
+            "File" => self.call_file_instance_method(receiver, method_name, arguments, position)?,
+            "Array" => self.call_array_method(receiver, method_name, arguments, position)?,
+            "Hash" => self.call_hash_method(receiver, method_name, arguments, position)?,
+            "IdentityHash" => {
+                self.call_identity_hash_method(receiver, method_name, arguments, position)?
+            }
+            "Integer" => self.call_integer_method(receiver, method_name, arguments, position)?,
+            "Float" => self.call_float_method(receiver, method_name, arguments, position)?,
+            "Decimal" => self.call_decimal_method(receiver, method_name, arguments, position)?,
+            "Range" => self.call_range_method(receiver, method_name, arguments, position)?,
+            "Exception" => {
+                self.call_exception_method(receiver, method_name, arguments, position)?
+            }
+            "URI" => self.call_uri_method(receiver, method_name, arguments, position)?,
+            "Metorex::Node" => {
+                self.call_ast_node_method(receiver, method_name, arguments, position)?
+            }
+            "TracePoint" => {
+                self.call_trace_point_method(receiver, method_name, arguments, position)?
+            }
+            "Schema" => self.call_schema_method(receiver, method_name, arguments, position)?,
+            "Schema::Error" => {
+                self.call_schema_error_method(receiver, method_name, arguments, position)?
+            }
+            #[cfg(feature = "sqlite")]
+            "SQLite" => self.call_sqlite_method(receiver, method_name, arguments, position)?,
+            #[cfg(feature = "sqlite")]
+            "SQLite::Statement" => {
+                self.call_sqlite_statement_method(receiver, method_name, arguments, position)?
+            }
+            _ => None,
+        };
+
+        // Object's builtins (to_s, class, respond_to?) apply to every receiver
+        // unless a subclass overrides them
+        if result.is_none() && self.resolves_to_object_builtin(class, method_name) {
+            return self.call_object_method(receiver, method_name, arguments, position);
+        }
+
+        // Every value has an identity, can be sent messages by name, can be
+        // asked for its literal and its instance variables, including
+        // instances of user classes that do not inherit Object's method table
+        if result.is_none()
+            && matches!(
+                method_name,
+                "object_id"
+                    | "equal?"
+                    | "respond_to?"
+                    | "send"
+                    | "__send__"
+                    | "to_literal"
+                    | "instance_variables"
+                    | "instance_variable_get"
+                    | "instance_variable_set"
+                    | "instance_variable_defined?"
+            )
+            && class.find_method(method_name).is_none()
+        {
+            return self.call_object_method(receiver, method_name, arguments, position);
+        }
+
+        Ok(result)
+    }
+
+    /// Check if a method name resolves to the builtin defined on Object itself.
+    fn resolves_to_object_builtin(&self, class: &Class, method_name: &str) -> bool {
+        match (
+            class.find_method(method_name),
+            self.builtins().object_class.find_method(method_name),
+        ) {
+            (Some(found), Some(builtin)) => Rc::ptr_eq(&found, &builtin),
+            _ => false,
         }
     }
 }
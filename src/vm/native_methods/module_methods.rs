@@ -0,0 +1,89 @@
+//! Native reflection methods available on every Module object.
+
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::module::Module;
+use crate::object::Object;
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use std::rc::Rc;
+
+impl VirtualMachine {
+    /// Execute reflection methods on a Module receiver.
+    pub(crate) fn call_module_method(
+        &mut self,
+        module: &Rc<Module>,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        match method_name {
+            "name" => Ok(Some(Object::String(Rc::new(module.name().to_string())))),
+            "include?" => match &arguments[0] {
+                Object::Module(other) => Ok(Some(Object::Bool(
+                    !Rc::ptr_eq(module, other) && module.includes(other),
+                ))),
+                other => Err(method_argument_type_error(
+                    method_name,
+                    "Module",
+                    other,
+                    position,
+                )),
+            },
+            "instance_methods" => {
+                let names = module
+                    .method_names()
+                    .into_iter()
+                    .map(|name| self.symbol(&name))
+                    .collect();
+                Ok(Some(Object::array(names)))
+            }
+            "constants" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let names = module
+                    .constant_names()
+                    .into_iter()
+                    .map(|name| self.symbol(&name))
+                    .collect();
+                Ok(Some(Object::array(names)))
+            }
+            // Outer::Inner - a constant defined in the module
+            name if arguments.is_empty() && name.starts_with(char::is_uppercase) => {
+                Ok(module.get_constant(name))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Execute class methods on Module.
+    pub(crate) fn call_module_class_method(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        match method_name {
+            // Module.nesting - the classes and modules the call is lexically
+            // inside, innermost first
+            "nesting" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                Ok(Some(self.module_nesting()))
+            }
+            _ => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Keyword options shared by `Integer#format`, `Float#format` and `String#to_number`.
+
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::number_format::{MAX_PRECISION, NumberFormat};
+use crate::object::Object;
+use crate::vm::errors::*;
+
+/// Read the `delimiter:`, `separator:` and (when `allow_precision` is set)
+/// `precision:` keywords into `format`.
+///
+/// Keywords arrive as a trailing Hash keyed by symbol; no positional
+/// arguments are accepted.
+pub(super) fn apply_number_format_options(
+    method_name: &str,
+    arguments: &[Object],
+    allow_precision: bool,
+    format: &mut NumberFormat,
+    position: Position,
+) -> Result<(), MetorexError> {
+    let options = match arguments {
+        [] => return Ok(()),
+        [Object::Dict(options)] => options.borrow(),
+        [other] => {
+            return Err(method_argument_type_error(
+                method_name,
+                "keyword options",
+                other,
+                position,
+            ));
+        }
+        _ => {
+            return Err(method_argument_error(
+                method_name,
+                1,
+                arguments.len(),
+                position,
+            ));
+        }
+    };
+
+    for (key, value) in options.iter() {
+        match key.as_str() {
+            ":delimiter" => {
+                format.delimiter = option_string(method_name, "delimiter", value, position)?
+            }
+            ":separator" => {
+                format.separator = option_string(method_name, "separator", value, position)?;
+                if format.separator.is_empty() {
+                    return Err(option_error(
+                        method_name,
+                        "separator must not be empty".to_string(),
+                        position,
+                    ));
+                }
+            }
+            ":precision" if allow_precision => {
+                format.precision = match value {
+                    Object::Int(precision) if (0..=MAX_PRECISION as i64).contains(precision) => {
+                        *precision as usize
+                    }
+                    other => {
+                        return Err(option_error(
+                            method_name,
+                            format!(
+                                "precision must be an Int from 0 to {}, got {}",
+                                MAX_PRECISION, other
+                            ),
+                            position,
+                        ));
+                    }
+                };
+            }
+            _ => {
+                let expected = if allow_precision {
+                    "delimiter, separator or precision"
+                } else {
+                    "delimiter or separator"
+                };
+                return Err(option_error(
+                    method_name,
+                    format!(
+                        "unknown option {} (expected {})",
+                        key.trim_start_matches(':'),
+                        expected
+                    ),
+                    position,
+                ));
+            }
+        }
+    }
+
+    if format.delimiter == format.separator {
+        return Err(option_error(
+            method_name,
+            "delimiter and separator must differ".to_string(),
+            position,
+        ));
+    }
+    Ok(())
+}
+
+fn option_string(
+    method_name: &str,
+    option: &str,
+    value: &Object,
+    position: Position,
+) -> Result<String, MetorexError> {
+    match value {
+        Object::String(text) => Ok(text.to_string()),
+        other => Err(option_error(
+            method_name,
+            format!("{} must be a String, got {}", option, other),
+            position,
+        )),
+    }
+}
+
+/// A rescuable ArgumentError for a keyword option the method cannot use
+fn option_error(method_name: &str, message: String, position: Position) -> MetorexError {
+    raised_exception_error(
+        "ArgumentError",
+        format!("{}: {}", method_name, message),
+        position,
+    )
+}
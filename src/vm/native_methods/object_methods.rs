@@ -5,6 +5,7 @@ use crate::lexer::Position;
 use crate::object::Object;
 use crate::vm::VirtualMachine;
 use crate::vm::errors::*;
+use std::rc::Rc;
 
 impl VirtualMachine {
     /// Execute native methods for the Object class.
@@ -25,6 +26,9 @@ impl VirtualMachine {
                         position,
                     ));
                 }
+                if self.is_main_object(receiver) {
+                    return Ok(Some(Object::string("main")));
+                }
                 Ok(Some(Object::string(receiver.to_string())))
             }
             "class" => {
@@ -48,7 +52,7 @@ impl VirtualMachine {
                     ));
                 }
                 let method_query = match &arguments[0] {
-                    Object::String(name) => name.as_str().to_string(),
+                    Object::String(name) | Object::Symbol(name) => name.as_str().to_string(),
                     other => {
                         return Err(method_argument_type_error(
                             method_name,
@@ -58,11 +62,196 @@ impl VirtualMachine {
                         ));
                     }
                 };
+                // Private methods, such as top-level `def`s, are not advertised
+                Ok(Some(Object::Bool(
+                    self.lookup_method(receiver, &method_query)
+                        .is_some_and(|(class, _)| !class.is_private_method(&method_query)),
+                )))
+            }
+            // send(name, *args) - call a method by name, private ones included,
+            // raising NoMethodError when nothing answers to it
+            "send" | "__send__" => {
+                let Some((name, rest)) = arguments.split_first() else {
+                    return Err(method_argument_error(method_name, 1, 0, position));
+                };
+                let name = match name {
+                    Object::String(name) | Object::Symbol(name) => Rc::clone(name),
+                    other => {
+                        return Err(method_argument_type_error(
+                            method_name,
+                            "Symbol",
+                            other,
+                            position,
+                        ));
+                    }
+                };
+                match self.try_dispatch_method(
+                    receiver.clone(),
+                    &name,
+                    rest.to_vec(),
+                    false,
+                    position,
+                )? {
+                    Some(result) => Ok(Some(result)),
+                    None => Err(raised_exception_error(
+                        "NoMethodError",
+                        format!(
+                            "Undefined method '{}' for {}",
+                            name,
+                            self.builtins().class_of(receiver).name()
+                        ),
+                        position,
+                    )),
+                }
+            }
+            // to_literal - Metorex source that evaluates to the receiver
+            "to_literal" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                self.literal_source(method_name, receiver, position)
+                    .map(Some)
+            }
+            "object_id" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                Ok(Some(Object::Int(self.object_id(receiver))))
+            }
+            // equal?(other) - identity, not value, comparison
+            "equal?" => {
+                if arguments.len() != 1 {
+                    return Err(method_argument_error(
+                        method_name,
+                        1,
+                        arguments.len(),
+                        position,
+                    ));
+                }
                 Ok(Some(Object::Bool(
-                    self.lookup_method(receiver, &method_query).is_some(),
+                    self.object_id(receiver) == self.object_id(&arguments[0]),
                 )))
             }
+            // instance_variables - names of the receiver's instance
+            // variables as Symbols like :@name, in sorted order
+            "instance_variables" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let mut names = match receiver {
+                    Object::Instance(instance) => {
+                        instance.borrow().instance_vars.keys().cloned().collect()
+                    }
+                    _ => Vec::new(),
+                };
+                names.sort();
+                let names = names
+                    .into_iter()
+                    .map(|name| self.symbol(&format!("@{}", name)))
+                    .collect();
+                Ok(Some(Object::array(names)))
+            }
+            // instance_variable_get(:@name) - the variable's value, or nil
+            // when it has not been set
+            "instance_variable_get" | "instance_variable_defined?" => {
+                if arguments.len() != 1 {
+                    return Err(method_argument_error(
+                        method_name,
+                        1,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let name = instance_variable_name(method_name, &arguments[0], position)?;
+                let value = match receiver {
+                    Object::Instance(instance) => instance.borrow().get_var(name).cloned(),
+                    _ => None,
+                };
+                if method_name == "instance_variable_defined?" {
+                    return Ok(Some(Object::Bool(value.is_some())));
+                }
+                Ok(Some(value.unwrap_or(Object::Nil)))
+            }
+            // instance_variable_set(:@name, value) - assign the variable,
+            // returning the value
+            "instance_variable_set" => {
+                if arguments.len() != 2 {
+                    return Err(method_argument_error(
+                        method_name,
+                        2,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let name = instance_variable_name(method_name, &arguments[0], position)?;
+                let Object::Instance(instance) = receiver else {
+                    return Err(raised_exception_error(
+                        "TypeError",
+                        format!(
+                            "Cannot set instance variable @{} on {}",
+                            name,
+                            self.builtins().class_of(receiver).name()
+                        ),
+                        position,
+                    ));
+                };
+                instance
+                    .borrow_mut()
+                    .set_var(name.to_string(), arguments[1].clone());
+                Ok(Some(arguments[1].clone()))
+            }
             _ => Ok(None),
         }
     }
 }
+
+/// Extract the bare variable name from a `:@name` symbol or `"@name"` string.
+fn instance_variable_name<'a>(
+    method_name: &str,
+    argument: &'a Object,
+    position: Position,
+) -> Result<&'a str, MetorexError> {
+    let full_name = match argument {
+        Object::Symbol(name) | Object::String(name) => name.as_str(),
+        other => {
+            return Err(method_argument_type_error(
+                method_name,
+                "Symbol",
+                other,
+                position,
+            ));
+        }
+    };
+
+    match full_name.strip_prefix('@') {
+        Some(name)
+            if name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_alphanumeric() || c == '_') =>
+        {
+            Ok(name)
+        }
+        _ => Err(raised_exception_error(
+            "NameError",
+            format!(
+                "'{}' is not allowed as an instance variable name",
+                full_name
+            ),
+            position,
+        )),
+    }
+}
@@ -19,6 +19,26 @@ impl VirtualMachine {
         position: Position,
     ) -> Result<Option<Object>, MetorexError> {
         match method_name {
+            // Without a block, an Enumerator over the integers in the range
+            "each" | "map" if arguments.is_empty() => {
+                let Object::Range {
+                    start,
+                    end,
+                    exclusive,
+                } = receiver
+                else {
+                    return Ok(None);
+                };
+                let Some((start_val, end_inclusive)) =
+                    self.range_int_bounds(start, end, *exclusive, position)?
+                else {
+                    return Err(MetorexError::runtime_error(
+                        format!("Range.{} only supports integer ranges", method_name),
+                        position_to_location(position),
+                    ));
+                };
+                self.yield_each(receiver, None, (start_val..=end_inclusive).map(Object::Int))
+            }
             "each" => {
                 // each takes a block parameter
                 if arguments.len() != 1 {
@@ -48,16 +68,21 @@ impl VirtualMachine {
                     };
 
                     // Only support integer ranges for now
-                    match (start.as_ref(), end.as_ref()) {
-                        (Object::Int(start_val), Object::Int(end_val)) => {
-                            let end_inclusive = if *exclusive { *end_val - 1 } else { *end_val };
-
-                            for i in *start_val..=end_inclusive {
+                    match self.range_int_bounds(start, end, *exclusive, position)? {
+                        Some((start_val, end_inclusive)) => {
+                            for i in start_val..=end_inclusive {
                                 let args = vec![Object::Int(i)];
                                 match self.execute_block_with_control_flow(&block, args)? {
                                     super::super::ControlFlow::Next
                                     | super::super::ControlFlow::Continue { .. } => continue,
                                     super::super::ControlFlow::Break { .. } => break,
+                                    super::super::ControlFlow::Retry { position } => {
+                                        return Err(
+                                            super::super::errors::retry_outside_rescue_error(
+                                                position,
+                                            ),
+                                        );
+                                    }
                                     super::super::ControlFlow::Return { value: _, position } => {
                                         return Err(super::super::errors::loop_control_error(
                                             "return", position,
@@ -79,7 +104,7 @@ impl VirtualMachine {
                             }
                             Ok(Some(receiver.clone()))
                         }
-                        _ => Err(MetorexError::runtime_error(
+                        None => Err(MetorexError::runtime_error(
                             "Range.each only supports integer ranges".to_string(),
                             position_to_location(position),
                         )),
@@ -103,15 +128,13 @@ impl VirtualMachine {
                     exclusive,
                 } = receiver
                 {
-                    match (start.as_ref(), end.as_ref()) {
-                        (Object::Int(start_val), Object::Int(end_val)) => {
-                            let end_inclusive = if *exclusive { *end_val - 1 } else { *end_val };
-
+                    match self.range_int_bounds(start, end, *exclusive, position)? {
+                        Some((start_val, end_inclusive)) => {
                             let elements: Vec<Object> =
-                                (*start_val..=end_inclusive).map(Object::Int).collect();
+                                (start_val..=end_inclusive).map(Object::Int).collect();
                             Ok(Some(Object::Array(Rc::new(RefCell::new(elements)))))
                         }
-                        _ => Err(MetorexError::runtime_error(
+                        None => Err(MetorexError::runtime_error(
                             "Range.to_a only supports integer ranges".to_string(),
                             position_to_location(position),
                         )),
@@ -129,26 +152,11 @@ impl VirtualMachine {
                         position,
                     ));
                 }
-                if let Object::Range {
-                    start,
-                    end,
-                    exclusive,
-                } = receiver
-                {
-                    match (start.as_ref(), end.as_ref(), &arguments[0]) {
-                        (Object::Int(start_val), Object::Int(end_val), Object::Int(test_val)) => {
-                            let in_range = if *exclusive {
-                                *test_val >= *start_val && *test_val < *end_val
-                            } else {
-                                *test_val >= *start_val && *test_val <= *end_val
-                            };
-                            Ok(Some(Object::Bool(in_range)))
-                        }
-                        _ => Err(MetorexError::runtime_error(
-                            "Range.include? only supports integer ranges".to_string(),
-                            position_to_location(position),
-                        )),
-                    }
+                // The same test `case`/`when` applies, so Float bounds and
+                // values compare numerically
+                if matches!(receiver, Object::Range { .. }) {
+                    let covered = self.case_equals(receiver, &arguments[0], position)?;
+                    Ok(Some(Object::Bool(covered)))
                 } else {
                     Ok(None)
                 }
@@ -182,19 +190,17 @@ impl VirtualMachine {
                     };
 
                     // Only support integer ranges for now
-                    match (start.as_ref(), end.as_ref()) {
-                        (Object::Int(start_val), Object::Int(end_val)) => {
-                            let end_inclusive = if *exclusive { *end_val - 1 } else { *end_val };
-
+                    match self.range_int_bounds(start, end, *exclusive, position)? {
+                        Some((start_val, end_inclusive)) => {
                             let mut results = Vec::new();
-                            for i in *start_val..=end_inclusive {
+                            for i in start_val..=end_inclusive {
                                 let args = vec![Object::Int(i)];
                                 let value = self.execute_block_body(&block, args)?;
                                 results.push(value);
                             }
                             Ok(Some(Object::Array(Rc::new(RefCell::new(results)))))
                         }
-                        _ => Err(MetorexError::runtime_error(
+                        None => Err(MetorexError::runtime_error(
                             "Range.map only supports integer ranges".to_string(),
                             position_to_location(position),
                         )),
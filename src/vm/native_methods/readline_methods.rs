@@ -0,0 +1,74 @@
+//! Native method implementations for the Readline class.
+//!
+//! Scripts use these to build their own interactive prompts with the same
+//! line editing and history the REPL offers.
+
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+
+impl VirtualMachine {
+    /// Execute class methods on Readline.
+    pub(crate) fn call_readline_method(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        match method_name {
+            // Readline.readline(prompt = "", add_to_history = false)
+            "readline" => {
+                let (prompt, add_to_history) = match arguments {
+                    [] => ("", false),
+                    [Object::String(prompt)] => (prompt.as_str(), false),
+                    [Object::String(prompt), add_to_history] => {
+                        (prompt.as_str(), add_to_history.is_truthy())
+                    }
+                    [other] | [other, _] => {
+                        return Err(method_argument_type_error(
+                            method_name,
+                            "String",
+                            other,
+                            position,
+                        ));
+                    }
+                    _ => {
+                        return Err(method_argument_error(
+                            method_name,
+                            2,
+                            arguments.len(),
+                            position,
+                        ));
+                    }
+                };
+                let prompt = prompt.to_string();
+                self.read_edited_line(&prompt, add_to_history, position)
+                    .map(Some)
+            }
+            // Readline.history - the lines added so far, oldest first
+            "history" | "clear_history" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                if method_name == "clear_history" {
+                    self.clear_readline_history();
+                    return Ok(Some(Object::Nil));
+                }
+                let lines = self
+                    .readline_history()
+                    .iter()
+                    .map(|line| Object::string(line.clone()))
+                    .collect();
+                Ok(Some(Object::array(lines)))
+            }
+            _ => Ok(None),
+        }
+    }
+}
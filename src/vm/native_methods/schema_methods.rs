@@ -0,0 +1,549 @@
+//! Native method implementations for the Schema class.
+//!
+//! A Schema describes the shape a value must have, so a script can check
+//! configuration read from JSON, YAML or CSV in one call instead of testing
+//! each field by hand:
+//!
+//! ```text
+//! user = Schema.hash(name: Schema.string, age: Schema.int.optional)
+//! errors = Schema.array(user).validate(users)
+//! ```
+//!
+//! Schemas are ordinary instances whose rules live in instance variables,
+//! so `instance_variables` and `instance_variable_get` show them like any
+//! other object's state. Methods that refine a schema, like `optional` or
+//! `where`, return a copy, so a schema can be shared between fields.
+//!
+//! `validate` returns a `Schema::Error` for every problem it finds, each with
+//! the path to the offending value, like `users[2].age`.
+
+use crate::class::Class;
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use crate::vm::type_checks::describe_type;
+use indexmap::IndexMap;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Class methods that build a schema for a single value
+const SCALARS: [&str; 8] = [
+    "string", "int", "float", "number", "decimal", "bool", "symbol", "any",
+];
+
+/// The rules of a schema, read from its instance variables
+struct Rules {
+    kind: String,
+    optional: bool,
+    strict: bool,
+    /// Schemas of a Hash's fields, by field name
+    fields: IndexMap<String, Object>,
+    /// Schema of an Array's items
+    items: Option<Object>,
+    /// The values a `one_of` schema allows
+    values: Vec<Object>,
+    /// The class an `of` schema's values must belong to
+    class: Option<Rc<Class>>,
+    /// `where` checks as [message, block] pairs
+    checks: Vec<(String, Object)>,
+}
+
+/// A problem `validate` found: the path to the value and what is wrong
+type Problem = (String, String);
+
+impl VirtualMachine {
+    /// Execute methods called on the Schema class itself.
+    pub(crate) fn call_schema_class_method(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let schema = match method_name {
+            name if SCALARS.contains(&name) => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                self.new_schema(name)
+            }
+            // Schema.array(items = Schema.any)
+            "array" => {
+                let schema = self.new_schema(method_name);
+                if let Some(items) = arguments.first() {
+                    self.expect_schema(method_name, "items", items, position)?;
+                    set_rule(&schema, "items", items.clone());
+                }
+                schema
+            }
+            // Schema.hash(name: schema, ...) - a Hash with the given fields
+            "hash" => {
+                let mut fields = IndexMap::new();
+                match arguments {
+                    [] => {}
+                    [Object::Dict(entries)] => {
+                        for (key, field) in entries.borrow().iter() {
+                            let name = key.strip_prefix(':').unwrap_or(key);
+                            self.expect_schema(method_name, name, field, position)?;
+                            fields.insert(name.to_string(), field.clone());
+                        }
+                    }
+                    [other] => {
+                        return Err(method_argument_type_error(
+                            method_name,
+                            "Hash of schemas",
+                            other,
+                            position,
+                        ));
+                    }
+                    _ => {
+                        return Err(method_argument_error(
+                            method_name,
+                            1,
+                            arguments.len(),
+                            position,
+                        ));
+                    }
+                }
+                let schema = self.new_schema(method_name);
+                set_rule(&schema, "fields", Object::dict(fields));
+                schema
+            }
+            // Schema.one_of(value, ...) - one of the listed values
+            "one_of" => {
+                if arguments.is_empty() {
+                    return Err(raised_exception_error(
+                        "ArgumentError",
+                        "one_of expects at least one value".to_string(),
+                        position,
+                    ));
+                }
+                let schema = self.new_schema(method_name);
+                set_rule(&schema, "values", Object::array(arguments.to_vec()));
+                schema
+            }
+            // Schema.of(Class) - an instance of the class or a subclass
+            "of" => match arguments {
+                [class @ Object::Class(_)] => {
+                    let schema = self.new_schema(method_name);
+                    set_rule(&schema, "class", class.clone());
+                    schema
+                }
+                [other] => {
+                    return Err(method_argument_type_error(
+                        method_name,
+                        "Class",
+                        other,
+                        position,
+                    ));
+                }
+                _ => {
+                    return Err(method_argument_error(
+                        method_name,
+                        1,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+            },
+            _ => return Ok(None),
+        };
+        Ok(Some(schema))
+    }
+
+    /// Execute native methods on Schema instances.
+    pub(crate) fn call_schema_method(
+        &mut self,
+        receiver: &Object,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        if self.schema_rules(receiver).is_none() {
+            return Ok(None);
+        }
+        let result = match method_name {
+            "optional" | "strict" => {
+                let schema = copy_schema(receiver);
+                set_rule(&schema, method_name, Object::Bool(true));
+                self.track_allocation(schema)
+            }
+            // where(message = "is invalid") { |value| ... } - add a check the
+            // value must pass once its type is right
+            "where" => {
+                let (message, block) = match arguments {
+                    [block @ Object::Block(_)] => ("is invalid".to_string(), block),
+                    [Object::String(message), block @ Object::Block(_)] => {
+                        (message.to_string(), block)
+                    }
+                    _ => {
+                        return Err(raised_exception_error(
+                            "ArgumentError",
+                            "where expects an optional message and a block".to_string(),
+                            position,
+                        ));
+                    }
+                };
+                let schema = copy_schema(receiver);
+                let mut checks = match rule(&schema, "checks") {
+                    Some(Object::Array(checks)) => checks.borrow().clone(),
+                    _ => Vec::new(),
+                };
+                checks.push(Object::array(vec![Object::string(message), block.clone()]));
+                set_rule(&schema, "checks", Object::array(checks));
+                self.track_allocation(schema)
+            }
+            "validate" | "valid?" | "validate!" => {
+                let [value] = arguments else {
+                    return Err(method_argument_error(
+                        method_name,
+                        1,
+                        arguments.len(),
+                        position,
+                    ));
+                };
+                let mut problems = Vec::new();
+                self.validate_value(receiver, value, String::new(), &mut problems, position)?;
+                match method_name {
+                    "valid?" => Object::Bool(problems.is_empty()),
+                    "validate!" if problems.is_empty() => value.clone(),
+                    "validate!" => {
+                        let described: Vec<String> = problems
+                            .iter()
+                            .map(|(path, message)| describe_problem(path, message))
+                            .collect();
+                        return Err(raised_exception_error(
+                            "ValueError",
+                            format!("invalid value: {}", described.join("; ")),
+                            position,
+                        ));
+                    }
+                    _ => Object::array(
+                        problems
+                            .into_iter()
+                            .map(|(path, message)| self.schema_error(path, message))
+                            .collect(),
+                    ),
+                }
+            }
+            "optional?" => Object::Bool(self.schema_rules(receiver).is_some_and(|r| r.optional)),
+            "type" => match self.schema_rules(receiver) {
+                Some(rules) => self.symbol(&rules.kind),
+                None => Object::Nil,
+            },
+            _ => return Ok(None),
+        };
+        Ok(Some(result))
+    }
+
+    /// Execute native methods on the errors `Schema#validate` returns.
+    pub(crate) fn call_schema_error_method(
+        &mut self,
+        receiver: &Object,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let Object::Instance(instance) = receiver else {
+            return Ok(None);
+        };
+        if !matches!(method_name, "path" | "message" | "to_s") {
+            return Ok(None);
+        }
+        if !arguments.is_empty() {
+            return Err(method_argument_error(
+                method_name,
+                0,
+                arguments.len(),
+                position,
+            ));
+        }
+        let instance = instance.borrow();
+        let text = |name: &str| match instance.get_var(name) {
+            Some(Object::String(text)) => text.to_string(),
+            _ => String::new(),
+        };
+        Ok(Some(match method_name {
+            "to_s" => Object::string(describe_problem(&text("path"), &text("message"))),
+            name => Object::string(text(name)),
+        }))
+    }
+
+    fn new_schema(&self, kind: &str) -> Object {
+        let schema = Object::instance(Rc::clone(&self.builtins().schema_class));
+        set_rule(&schema, "type", self.symbol(kind));
+        set_rule(&schema, "optional", Object::Bool(false));
+        self.track_allocation(schema)
+    }
+
+    fn schema_error(&self, path: String, message: String) -> Object {
+        let error = Object::instance(Rc::clone(&self.builtins().schema_error_class));
+        set_rule(&error, "path", Object::string(path));
+        set_rule(&error, "message", Object::string(message));
+        self.track_allocation(error)
+    }
+
+    /// The rules of a Schema instance, or `None` for any other value.
+    fn schema_rules(&self, schema: &Object) -> Option<Rules> {
+        let Object::Instance(instance) = schema else {
+            return None;
+        };
+        let instance = instance.borrow();
+        if !self
+            .builtins()
+            .is_subclass_of(&instance.class, &self.builtins().schema_class)
+        {
+            return None;
+        }
+        let kind = match instance.get_var("type") {
+            Some(Object::Symbol(kind)) => kind.to_string(),
+            _ => return None,
+        };
+        let flag = |name: &str| matches!(instance.get_var(name), Some(Object::Bool(true)));
+        let array = |name: &str| match instance.get_var(name) {
+            Some(Object::Array(items)) => items.borrow().clone(),
+            _ => Vec::new(),
+        };
+        Some(Rules {
+            kind,
+            optional: flag("optional"),
+            strict: flag("strict"),
+            fields: match instance.get_var("fields") {
+                Some(Object::Dict(fields)) => fields.borrow().clone(),
+                _ => IndexMap::new(),
+            },
+            items: instance.get_var("items").cloned(),
+            values: array("values"),
+            class: match instance.get_var("class") {
+                Some(Object::Class(class)) => Some(Rc::clone(class)),
+                _ => None,
+            },
+            checks: array("checks")
+                .into_iter()
+                .filter_map(|check| match check {
+                    Object::Array(pair) => match pair.borrow().as_slice() {
+                        [Object::String(message), block] => {
+                            Some((message.to_string(), block.clone()))
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                })
+                .collect(),
+        })
+    }
+
+    fn expect_schema(
+        &self,
+        method_name: &str,
+        name: &str,
+        value: &Object,
+        position: Position,
+    ) -> Result<(), MetorexError> {
+        if self.schema_rules(value).is_some() {
+            return Ok(());
+        }
+        Err(raised_exception_error(
+            "ArgumentError",
+            format!(
+                "{}: '{}' must be a Schema, got {}",
+                method_name,
+                name,
+                describe_type(value)
+            ),
+            position,
+        ))
+    }
+
+    /// Check `value` against `schema`, adding a problem for each rule it
+    /// breaks. Checks and nested values are only examined once the value
+    /// has the right type.
+    fn validate_value(
+        &mut self,
+        schema: &Object,
+        value: &Object,
+        path: String,
+        problems: &mut Vec<Problem>,
+        position: Position,
+    ) -> Result<(), MetorexError> {
+        let Some(rules) = self.schema_rules(schema) else {
+            return Ok(());
+        };
+        if let Object::Nil = value {
+            if !rules.optional {
+                problems.push((path, "is required".to_string()));
+            }
+            return Ok(());
+        }
+        if let Some(message) = self.type_problem(&rules, value) {
+            problems.push((path, message));
+            return Ok(());
+        }
+
+        match value {
+            Object::Array(items) if rules.kind == "array" => {
+                if let Some(item_schema) = &rules.items {
+                    let items = items.borrow().clone();
+                    for (index, item) in items.iter().enumerate() {
+                        let item_path = format!("{}[{}]", path, index);
+                        self.validate_value(item_schema, item, item_path, problems, position)?;
+                    }
+                }
+            }
+            Object::Dict(entries) if rules.kind == "hash" => {
+                let entries = entries.borrow().clone();
+                for (name, field) in &rules.fields {
+                    let field_value = entries
+                        .get(name)
+                        .or_else(|| entries.get(&format!(":{}", name)));
+                    let field_path = join_path(&path, name);
+                    match field_value {
+                        Some(field_value) => {
+                            self.validate_value(field, field_value, field_path, problems, position)?
+                        }
+                        None if self.schema_rules(field).is_some_and(|r| r.optional) => {}
+                        None => problems.push((field_path, "is required".to_string())),
+                    }
+                }
+                if rules.strict {
+                    for key in entries.keys() {
+                        let name = key.strip_prefix(':').unwrap_or(key);
+                        if !rules.fields.contains_key(name) {
+                            problems.push((join_path(&path, name), "is not allowed".to_string()));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        for (message, block) in rules.checks {
+            let passed = self.invoke_callable(block, vec![value.clone()], position)?;
+            if !passed.is_truthy() {
+                problems.push((path.clone(), message));
+            }
+        }
+        Ok(())
+    }
+
+    /// What is wrong with `value`, if it has the wrong type for `rules` or
+    /// is not one of the values they allow.
+    fn type_problem(&self, rules: &Rules, value: &Object) -> Option<String> {
+        let matches = match rules.kind.as_str() {
+            "string" => matches!(value, Object::String(_)),
+            "int" => matches!(value, Object::Int(_)),
+            "float" => matches!(value, Object::Float(_)),
+            "number" => matches!(
+                value,
+                Object::Int(_) | Object::Float(_) | Object::Decimal(_)
+            ),
+            "decimal" => matches!(value, Object::Decimal(_)),
+            "bool" => matches!(value, Object::Bool(_)),
+            "symbol" => matches!(value, Object::Symbol(_)),
+            "array" => matches!(value, Object::Array(_)),
+            "hash" => matches!(value, Object::Dict(_)),
+            "one_of" => {
+                if rules.values.iter().any(|allowed| allowed.equals(value)) {
+                    return None;
+                }
+                let allowed: Vec<String> = rules.values.iter().map(quoted).collect();
+                return Some(format!(
+                    "expected one of {}, got {}",
+                    allowed.join(", "),
+                    quoted(value)
+                ));
+            }
+            "of" => {
+                let class = rules.class.as_ref()?;
+                if self.builtins().is_instance_of(value, class) {
+                    return None;
+                }
+                return Some(format!(
+                    "expected {}, got {}",
+                    class.name(),
+                    describe_type(value)
+                ));
+            }
+            _ => true,
+        };
+        if matches {
+            return None;
+        }
+        Some(format!(
+            "expected {}, got {}",
+            type_name(&rules.kind),
+            describe_type(value)
+        ))
+    }
+}
+
+fn rule(schema: &Object, name: &str) -> Option<Object> {
+    match schema {
+        Object::Instance(instance) => instance.borrow().get_var(name).cloned(),
+        _ => None,
+    }
+}
+
+fn set_rule(schema: &Object, name: &str, value: Object) {
+    if let Object::Instance(instance) = schema {
+        instance.borrow_mut().set_var(name.to_string(), value);
+    }
+}
+
+/// A new schema instance with the same class and rules as `schema`
+fn copy_schema(schema: &Object) -> Object {
+    match schema {
+        Object::Instance(instance) => {
+            let copy = instance.borrow().clone();
+            Object::Instance(Rc::new(RefCell::new(copy)))
+        }
+        other => other.clone(),
+    }
+}
+
+/// The type errors report a value of the wrong type should have had
+fn type_name(kind: &str) -> &'static str {
+    match kind {
+        "string" => "String",
+        "int" => "Int",
+        "float" => "Float",
+        "number" => "Int, Float or Decimal",
+        "decimal" => "Decimal",
+        "bool" => "Bool",
+        "symbol" => "Symbol",
+        "array" => "Array",
+        "hash" => "Hash",
+        _ => "a value",
+    }
+}
+
+/// A value as it would be written in source, so Strings show their quotes
+fn quoted(value: &Object) -> String {
+    match value {
+        Object::String(text) => format!("{:?}", text.as_str()),
+        other => other.to_string(),
+    }
+}
+
+fn join_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+/// A problem as `path: message`, or just the message for the value itself
+fn describe_problem(path: &str, message: &str) -> String {
+    if path.is_empty() {
+        message.to_string()
+    } else {
+        format!("{}: {}", path, message)
+    }
+}
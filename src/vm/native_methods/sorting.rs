@@ -0,0 +1,101 @@
+//! Ordering shared by `Array#sort`, `Array#sort_by` and `Hash#sort_by`.
+//!
+//! Comparisons may run user code and may fail, so sorting uses its own stable
+//! merge sort rather than `slice::sort_by`, which cannot stop on an error and
+//! may panic when a user-defined ordering is inconsistent.
+
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::{BlockStatement, Object};
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use std::cmp::Ordering;
+
+impl VirtualMachine {
+    /// Order two values for sorting.
+    ///
+    /// A block decides when one is given; otherwise a `<=>` method defined on
+    /// the left value, then the natural ordering of numbers, strings and
+    /// arrays. Values that cannot be ordered raise a TypeError naming both.
+    pub(super) fn sort_compare(
+        &mut self,
+        block: Option<&BlockStatement>,
+        left: &Object,
+        right: &Object,
+        position: Position,
+    ) -> Result<Ordering, MetorexError> {
+        let verdict = if let Some(block) = block {
+            self.execute_block_body(block, vec![left.clone(), right.clone()])?
+        } else if let Object::Instance(_) = left {
+            match self.lookup_method(left, "<=>") {
+                Some((class, method)) => {
+                    self.invoke_method(class, method, left.clone(), vec![right.clone()], position)?
+                }
+                None => return Err(comparison_failed_error(left, right, position)),
+            }
+        } else {
+            return self
+                .compare_values(left, right, position)
+                .map_err(|_| comparison_failed_error(left, right, position));
+        };
+
+        match verdict {
+            Object::Int(n) => Ok(n.cmp(&0)),
+            Object::Float(n) if !n.is_nan() => Ok(n.partial_cmp(&0.0).unwrap_or(Ordering::Equal)),
+            _ => Err(comparison_failed_error(left, right, position)),
+        }
+    }
+
+    /// Sort values, keeping equal elements in their original order.
+    pub(super) fn sort_values(
+        &mut self,
+        values: Vec<Object>,
+        block: Option<&BlockStatement>,
+        position: Position,
+    ) -> Result<Vec<Object>, MetorexError> {
+        merge_sort(values, &mut |a, b| self.sort_compare(block, a, b, position))
+    }
+
+    /// Sort items by keys computed once per item beforehand, keeping items
+    /// with equal keys in their original order.
+    pub(super) fn sort_keyed<T>(
+        &mut self,
+        keyed: Vec<(Object, T)>,
+        position: Position,
+    ) -> Result<Vec<T>, MetorexError> {
+        let sorted = merge_sort(keyed, &mut |(a, _), (b, _)| {
+            self.sort_compare(None, a, b, position)
+        })?;
+        Ok(sorted.into_iter().map(|(_, item)| item).collect())
+    }
+}
+
+/// Stable top-down merge sort whose comparison may fail; the first failure
+/// stops the sort.
+fn merge_sort<T, E>(
+    mut items: Vec<T>,
+    compare: &mut impl FnMut(&T, &T) -> Result<Ordering, E>,
+) -> Result<Vec<T>, E> {
+    if items.len() <= 1 {
+        return Ok(items);
+    }
+    let right = items.split_off(items.len() / 2);
+    let left = merge_sort(items, compare)?;
+    let right = merge_sort(right, compare)?;
+
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+        // A right item only overtakes a strictly greater left one, which
+        // keeps the sort stable
+        if compare(a, b)? == Ordering::Greater {
+            merged.extend(right.next());
+        } else {
+            merged.extend(left.next());
+        }
+    }
+    merged.extend(left);
+    merged.extend(right);
+    Ok(merged)
+}
@@ -0,0 +1,385 @@
+//! Native method implementations for the SQLite class.
+//!
+//! `SQLite.open` needs the `sqlite` cargo feature; without it the method
+//! exists but raises an error explaining how to enable it. Queries return an
+//! Array with one Hash per row, keyed by column name.
+
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use crate::vm::utils::position_to_location;
+
+impl VirtualMachine {
+    /// Execute class methods on SQLite.
+    pub(crate) fn call_sqlite_class_method(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        if method_name != "open" {
+            return Ok(None);
+        }
+
+        // SQLite.open(path) or SQLite.open(path) do |db| ... end
+        let (path, block) = match arguments {
+            [Object::String(path)] => (path, None),
+            [Object::String(path), block @ Object::Block(_)] => (path, Some(block.clone())),
+            [other] | [other, Object::Block(_)] => {
+                return Err(method_argument_type_error(
+                    method_name,
+                    "String",
+                    other,
+                    position,
+                ));
+            }
+            _ => {
+                return Err(method_argument_error(
+                    method_name,
+                    1,
+                    arguments.len(),
+                    position,
+                ));
+            }
+        };
+
+        #[cfg(feature = "sqlite")]
+        {
+            let connection = rusqlite::Connection::open(path.as_str())
+                .map_err(|err| sqlite_error(format!("cannot open {}: {}", path, err), position))?;
+            let database = self.sqlite_database(connection, path);
+            match block {
+                Some(block) => self.with_resource(database, block, position).map(Some),
+                None => Ok(Some(database)),
+            }
+        }
+
+        #[cfg(not(feature = "sqlite"))]
+        {
+            let _ = (path, block);
+            Err(sqlite_error(
+                "SQLite.open is unavailable: metorex was built without the sqlite feature"
+                    .to_string(),
+                position,
+            ))
+        }
+    }
+
+    /// Execute native methods on SQLite database instances.
+    #[cfg(feature = "sqlite")]
+    pub(crate) fn call_sqlite_method(
+        &mut self,
+        receiver: &Object,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let Object::Instance(instance) = receiver else {
+            return Ok(None);
+        };
+        let handle = match instance.borrow().get_var("handle") {
+            Some(Object::Int(handle)) => *handle,
+            _ => return Ok(None),
+        };
+
+        match method_name {
+            // db.execute(sql, params = nil) - run one statement, returning its rows
+            "execute" => {
+                let (sql, params) = sql_arguments(method_name, arguments, position)?;
+                let connection = self.sqlite_connection(handle, position)?;
+                let rows = convert::run_query(connection, sql, params)
+                    .map_err(|err| sqlite_error(err, position))?;
+                Ok(Some(Object::array(rows)))
+            }
+            // db.execute_batch(sql) - run several statements, such as a schema
+            "execute_batch" => {
+                let (sql, _) = sql_arguments(method_name, arguments, position)?;
+                self.sqlite_connection(handle, position)?
+                    .execute_batch(sql)
+                    .map_err(|err| sqlite_error(err.to_string(), position))?;
+                Ok(Some(Object::Nil))
+            }
+            // db.prepare(sql) - a statement that can be executed repeatedly
+            "prepare" => {
+                let (sql, _) = sql_arguments(method_name, arguments, position)?;
+                // Compile now so syntax errors surface at prepare time
+                self.sqlite_connection(handle, position)?
+                    .prepare_cached(sql)
+                    .map_err(|err| sqlite_error(err.to_string(), position))?;
+                let statement =
+                    Object::instance(std::rc::Rc::clone(&self.builtins().sqlite_statement_class));
+                if let Object::Instance(statement) = &statement {
+                    let mut statement = statement.borrow_mut();
+                    statement.set_var("database".to_string(), receiver.clone());
+                    statement.set_var("sql".to_string(), Object::string(sql));
+                }
+                Ok(Some(statement))
+            }
+            // db.transaction do |db| ... end - commit if the block succeeds, else roll back
+            "transaction" => {
+                let block = match arguments {
+                    [block @ Object::Block(_)] => block.clone(),
+                    _ => {
+                        return Err(sqlite_error(
+                            "transaction expects a block".to_string(),
+                            position,
+                        ));
+                    }
+                };
+                self.sqlite_batch(handle, "BEGIN", position)?;
+                let result = self.invoke_callable(block, vec![receiver.clone()], position);
+                let finished = match result {
+                    Ok(_) => self.sqlite_batch(handle, "COMMIT", position),
+                    Err(_) => self.sqlite_batch(handle, "ROLLBACK", position),
+                };
+                let value = result?;
+                finished?;
+                Ok(Some(value))
+            }
+            "changes" | "last_insert_row_id" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let connection = self.sqlite_connection(handle, position)?;
+                Ok(Some(Object::Int(if method_name == "changes" {
+                    connection.changes() as i64
+                } else {
+                    connection.last_insert_rowid()
+                })))
+            }
+            "close" => {
+                if let Some(connection) = self.sqlite.remove(handle) {
+                    connection
+                        .close()
+                        .map_err(|(_, err)| sqlite_error(err.to_string(), position))?;
+                }
+                Ok(Some(Object::Nil))
+            }
+            "closed?" => Ok(Some(Object::Bool(self.sqlite.get(handle).is_none()))),
+            _ => Ok(None),
+        }
+    }
+
+    /// Execute native methods on prepared statements.
+    #[cfg(feature = "sqlite")]
+    pub(crate) fn call_sqlite_statement_method(
+        &mut self,
+        receiver: &Object,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let Object::Instance(instance) = receiver else {
+            return Ok(None);
+        };
+        let (database, sql) = {
+            let instance = instance.borrow();
+            match (instance.get_var("database"), instance.get_var("sql")) {
+                (Some(database), Some(Object::String(sql))) => (database.clone(), sql.clone()),
+                _ => return Ok(None),
+            }
+        };
+
+        match method_name {
+            // statement.execute(params = nil) - run the statement with new parameters
+            "execute" => {
+                if arguments.len() > 1 {
+                    return Err(method_argument_error(
+                        method_name,
+                        1,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let mut database_arguments = vec![Object::String(sql)];
+                database_arguments.extend(arguments.iter().cloned());
+                self.call_sqlite_method(&database, method_name, &database_arguments, position)
+            }
+            "sql" => Ok(Some(Object::String(sql))),
+            _ => Ok(None),
+        }
+    }
+
+    /// Store `connection` and build the SQLite instance that refers to it.
+    #[cfg(feature = "sqlite")]
+    fn sqlite_database(&mut self, connection: rusqlite::Connection, path: &str) -> Object {
+        let handle = self.sqlite.insert(connection);
+        let database = Object::instance(std::rc::Rc::clone(&self.builtins().sqlite_class));
+        if let Object::Instance(instance) = &database {
+            let mut instance = instance.borrow_mut();
+            instance.set_var("handle".to_string(), Object::Int(handle));
+            instance.set_var("path".to_string(), Object::string(path));
+        }
+        database
+    }
+
+    /// The open connection for `handle`.
+    #[cfg(feature = "sqlite")]
+    fn sqlite_connection(
+        &self,
+        handle: i64,
+        position: Position,
+    ) -> Result<&rusqlite::Connection, MetorexError> {
+        self.sqlite
+            .get(handle)
+            .ok_or_else(|| sqlite_error("database is closed".to_string(), position))
+    }
+
+    #[cfg(feature = "sqlite")]
+    fn sqlite_batch(&self, handle: i64, sql: &str, position: Position) -> Result<(), MetorexError> {
+        self.sqlite_connection(handle, position)?
+            .execute_batch(sql)
+            .map_err(|err| sqlite_error(err.to_string(), position))
+    }
+}
+
+/// Split `(sql, params = nil)` arguments.
+#[cfg(feature = "sqlite")]
+fn sql_arguments<'a>(
+    method_name: &str,
+    arguments: &'a [Object],
+    position: Position,
+) -> Result<(&'a str, Option<&'a Object>), MetorexError> {
+    match arguments {
+        [Object::String(sql)] => Ok((sql, None)),
+        [Object::String(sql), params] if method_name == "execute" => Ok((sql, Some(params))),
+        [other, ..] if !matches!(other, Object::String(_)) => Err(method_argument_type_error(
+            method_name,
+            "String",
+            other,
+            position,
+        )),
+        _ => Err(method_argument_error(
+            method_name,
+            1,
+            arguments.len(),
+            position,
+        )),
+    }
+}
+
+fn sqlite_error(message: String, position: Position) -> MetorexError {
+    MetorexError::runtime_error(
+        format!("SQLite: {}", message),
+        position_to_location(position),
+    )
+}
+
+#[cfg(feature = "sqlite")]
+mod convert {
+    use crate::object::Object;
+    use indexmap::IndexMap;
+    use rusqlite::Connection;
+    use rusqlite::types::{Value, ValueRef};
+
+    /// Run a single statement with optional parameters and collect its rows.
+    ///
+    /// Parameters are an Array for `?` placeholders or a Hash for named ones;
+    /// every placeholder must be given a value.
+    pub(super) fn run_query(
+        connection: &Connection,
+        sql: &str,
+        params: Option<&Object>,
+    ) -> Result<Vec<Object>, String> {
+        let mut statement = connection.prepare_cached(sql).map_err(|e| e.to_string())?;
+        let expected = statement.parameter_count();
+
+        let given = match params {
+            None | Some(Object::Nil) => 0,
+            Some(Object::Array(values)) => {
+                let values = values.borrow();
+                for (index, value) in values.iter().enumerate() {
+                    if index < expected {
+                        statement
+                            .raw_bind_parameter(index + 1, to_sql(value)?)
+                            .map_err(|e| e.to_string())?;
+                    }
+                }
+                values.len()
+            }
+            Some(Object::Dict(values)) => {
+                let values = values.borrow();
+                for (key, value) in values.iter() {
+                    let name = if key.starts_with([':', '@', '$']) {
+                        key.clone()
+                    } else {
+                        format!(":{}", key)
+                    };
+                    let index = statement
+                        .parameter_index(&name)
+                        .map_err(|e| e.to_string())?
+                        .ok_or_else(|| format!("no parameter named {}", name))?;
+                    statement
+                        .raw_bind_parameter(index, to_sql(value)?)
+                        .map_err(|e| e.to_string())?;
+                }
+                values.len()
+            }
+            Some(other) => {
+                return Err(format!(
+                    "parameters must be an Array or Hash, got {}",
+                    other.type_name()
+                ));
+            }
+        };
+        if given != expected {
+            return Err(format!(
+                "statement expects {} parameter(s), got {}",
+                expected, given
+            ));
+        }
+
+        let columns: Vec<String> = statement
+            .column_names()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let mut rows = statement.raw_query();
+        let mut results = Vec::new();
+        while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+            let mut entries = IndexMap::new();
+            for (index, column) in columns.iter().enumerate() {
+                let value = row.get_ref(index).map_err(|e| e.to_string())?;
+                entries.insert(column.clone(), from_sql(value));
+            }
+            results.push(Object::dict(entries));
+        }
+        Ok(results)
+    }
+
+    fn to_sql(value: &Object) -> Result<Value, String> {
+        Ok(match value {
+            Object::Nil => Value::Null,
+            Object::Bool(value) => Value::Integer(i64::from(*value)),
+            Object::Int(value) => Value::Integer(*value),
+            Object::Float(value) => Value::Real(*value),
+            // Bound as text so no digits are lost to a REAL column
+            Object::Decimal(value) => Value::Text(value.to_string()),
+            Object::String(text) | Object::Symbol(text) => Value::Text(text.to_string()),
+            other => return Err(format!("cannot bind {} as a parameter", other.type_name())),
+        })
+    }
+
+    /// Blobs have no String form, so they are returned as Arrays of byte values.
+    fn from_sql(value: ValueRef<'_>) -> Object {
+        match value {
+            ValueRef::Null => Object::Nil,
+            ValueRef::Integer(value) => Object::Int(value),
+            ValueRef::Real(value) => Object::Float(value),
+            ValueRef::Text(text) => Object::string(String::from_utf8_lossy(text)),
+            ValueRef::Blob(bytes) => Object::array(
+                bytes
+                    .iter()
+                    .map(|byte| Object::Int(i64::from(*byte)))
+                    .collect(),
+            ),
+        }
+    }
+}
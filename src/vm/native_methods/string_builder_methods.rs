@@ -0,0 +1,132 @@
+//! Native method implementations for StringBuilder and `String.build`.
+//!
+//! Strings are immutable, so building one with `+` copies everything written
+//! so far on every step. A StringBuilder keeps its text in the `buffer`
+//! instance variable and appends to it in place; `to_s` hands out the buffer
+//! itself, which is only copied if the builder is appended to again.
+
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use std::fmt::Write;
+use std::rc::Rc;
+
+impl VirtualMachine {
+    /// StringBuilder.new(initial = "") - a builder starting with `initial`
+    pub(crate) fn new_string_builder(
+        &mut self,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        let initial = match arguments {
+            [] => Rc::new(String::new()),
+            [Object::String(initial)] => Rc::clone(initial),
+            [other] => return Err(method_argument_type_error("new", "String", other, position)),
+            _ => return Err(method_argument_error("new", 1, arguments.len(), position)),
+        };
+        let builder = Object::instance(Rc::clone(&self.builtins().string_builder_class));
+        if let Object::Instance(instance) = &builder {
+            instance
+                .borrow_mut()
+                .set_var("buffer".to_string(), Object::String(initial));
+        }
+        Ok(self.track_allocation(builder))
+    }
+
+    /// Execute class methods on String.
+    pub(crate) fn call_string_class_method(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        match method_name {
+            // String.build { |s| s << ... } - the text the block appends
+            "build" => {
+                let [block @ Object::Block(_)] = arguments else {
+                    return Err(method_argument_error(
+                        method_name,
+                        1,
+                        arguments.len(),
+                        position,
+                    ));
+                };
+                let builder = self.new_string_builder(&[], position)?;
+                self.invoke_callable(block.clone(), vec![builder.clone()], position)?;
+                Ok(self.string_builder_buffer(&builder))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Execute native methods for the StringBuilder class.
+    pub(crate) fn call_string_builder_method(
+        &mut self,
+        receiver: &Object,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let Object::Instance(instance) = receiver else {
+            return Ok(None);
+        };
+        let expected = match method_name {
+            "<<" | "append" => 1,
+            "to_s" | "length" | "empty?" => 0,
+            _ => return Ok(None),
+        };
+        if arguments.len() != expected {
+            return Err(method_argument_error(
+                method_name,
+                expected,
+                arguments.len(),
+                position,
+            ));
+        }
+
+        // Read the appended value before borrowing the buffer, since a
+        // builder may be appended to itself
+        let text = match arguments.first() {
+            Some(Object::String(text)) => Some(Rc::clone(text)),
+            Some(value) if let Some(Object::String(text)) = self.string_builder_buffer(value) => {
+                Some(text)
+            }
+            Some(value) => {
+                let mut text = String::new();
+                let _ = write!(text, "{}", value);
+                Some(Rc::new(text))
+            }
+            None => None,
+        };
+
+        let mut instance = instance.borrow_mut();
+        let Some(Object::String(buffer)) = instance.instance_vars.get_mut("buffer") else {
+            return Ok(None);
+        };
+        let result = match (method_name, text) {
+            ("<<" | "append", Some(text)) => {
+                Rc::make_mut(buffer).push_str(&text);
+                receiver.clone()
+            }
+            ("to_s", _) => Object::String(Rc::clone(buffer)),
+            ("length", _) => Object::Int(buffer.chars().count() as i64),
+            ("empty?", _) => Object::Bool(buffer.is_empty()),
+            _ => unreachable!(),
+        };
+        Ok(Some(result))
+    }
+
+    /// The text `value` holds, if it is a StringBuilder.
+    fn string_builder_buffer(&self, value: &Object) -> Option<Object> {
+        let Object::Instance(instance) = value else {
+            return None;
+        };
+        let instance = instance.borrow();
+        if !Rc::ptr_eq(&instance.class, &self.builtins().string_builder_class) {
+            return None;
+        }
+        instance.get_var("buffer").cloned()
+    }
+}
@@ -1,12 +1,21 @@
 //! Native method implementations for the String class.
 
+use super::decimal_methods::decimal_prefix;
+use super::number_format_options::apply_number_format_options;
+use crate::char_set::{self, CharSet};
 use crate::error::MetorexError;
+use crate::hex;
 use crate::lexer::Position;
+use crate::literal;
+use crate::number_format::{NumberFormat, ParsedNumber, parse_number};
 use crate::object::Object;
 use crate::vm::VirtualMachine;
 use crate::vm::errors::*;
+use crate::vm::utils::position_to_location;
 use std::cell::RefCell;
+use std::iter::Peekable;
 use std::rc::Rc;
+use std::str::Chars;
 
 impl VirtualMachine {
     /// Execute native methods for the String class.
@@ -85,6 +94,41 @@ impl VirtualMachine {
                     ))
                 }
             }
+            // format % value or format % [values] - sprintf-style formatting
+            "%" => {
+                if arguments.len() != 1 {
+                    return Err(method_argument_error(
+                        method_name,
+                        1,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let Object::String(template) = receiver else {
+                    return Ok(None);
+                };
+                let values = match &arguments[0] {
+                    Object::Array(values) => values.borrow().clone(),
+                    value => vec![value.clone()],
+                };
+                let formatted = self.format_string("String#%", template, &values, position)?;
+                Ok(Some(Object::string(formatted)))
+            }
+            // to_source - the String as a double-quoted literal
+            "to_source" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                match receiver {
+                    Object::String(text) => Ok(Some(Object::string(literal::quote(text)))),
+                    _ => Ok(None),
+                }
+            }
             "trim" => {
                 if !arguments.is_empty() {
                     return Err(method_argument_error(
@@ -154,8 +198,35 @@ impl VirtualMachine {
                     Ok(None)
                 }
             }
-            "each_char" => {
-                // each_char takes a block parameter
+            "chomp" => {
+                if arguments.len() > 1 {
+                    return Err(method_argument_error(
+                        method_name,
+                        1,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let Object::String(string_value) = receiver else {
+                    return Ok(None);
+                };
+                let chomped = match arguments.first() {
+                    None => chomp_newline(string_value),
+                    Some(Object::String(suffix)) => string_value
+                        .strip_suffix(suffix.as_str())
+                        .unwrap_or(string_value),
+                    Some(other) => {
+                        return Err(method_argument_type_error(
+                            method_name,
+                            "String",
+                            other,
+                            position,
+                        ));
+                    }
+                };
+                Ok(Some(Object::string(chomped)))
+            }
+            "match_glob" => {
                 if arguments.len() != 1 {
                     return Err(method_argument_error(
                         method_name,
@@ -164,30 +235,710 @@ impl VirtualMachine {
                         position,
                     ));
                 }
+                let Object::String(string_value) = receiver else {
+                    return Ok(None);
+                };
+                match &arguments[0] {
+                    Object::String(pattern) => Ok(Some(Object::Bool(crate::glob::fnmatch(
+                        pattern,
+                        string_value,
+                    )))),
+                    other => Err(method_argument_type_error(
+                        method_name,
+                        "String",
+                        other,
+                        position,
+                    )),
+                }
+            }
+            "chop" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
                 if let Object::String(string_value) = receiver {
-                    let block = match &arguments[0] {
-                        Object::Block(block) => block.clone(),
-                        _ => {
-                            return Err(method_argument_type_error(
-                                method_name,
-                                "Block",
-                                &arguments[0],
-                                position,
-                            ));
+                    // A trailing "\r\n" counts as a single character
+                    let chopped = match string_value.strip_suffix("\r\n") {
+                        Some(rest) => rest,
+                        None => {
+                            let mut chars = string_value.chars();
+                            chars.next_back();
+                            chars.as_str()
                         }
                     };
-
-                    for ch in string_value.chars() {
-                        let char_str = Object::string(ch.to_string());
-                        let args = vec![char_str];
-                        self.execute_block_body(&block, args)?;
+                    Ok(Some(Object::string(chopped)))
+                } else {
+                    Ok(None)
+                }
+            }
+            "to_i" => {
+                if arguments.len() > 1 {
+                    return Err(method_argument_error(
+                        method_name,
+                        1,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let Object::String(string_value) = receiver else {
+                    return Ok(None);
+                };
+                let base = match arguments.first() {
+                    None => 10,
+                    Some(Object::Int(base)) if (2..=36).contains(base) => *base as u32,
+                    Some(Object::Int(base)) => {
+                        return Err(MetorexError::runtime_error(
+                            format!("String#to_i base must be between 2 and 36, got {}", base),
+                            position_to_location(position),
+                        ));
+                    }
+                    Some(other) => {
+                        return Err(method_argument_type_error(
+                            method_name,
+                            "Integer",
+                            other,
+                            position,
+                        ));
                     }
-                    Ok(Some(receiver.clone()))
+                };
+                match parse_integer_prefix(string_value, base) {
+                    Some(value) => Ok(Some(Object::Int(value))),
+                    None => Err(MetorexError::runtime_error(
+                        format!("String#to_i value out of range: {}", string_value.trim()),
+                        position_to_location(position),
+                    )),
+                }
+            }
+            "to_f" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                if let Object::String(string_value) = receiver {
+                    Ok(Some(Object::Float(parse_float_prefix(string_value))))
                 } else {
                     Ok(None)
                 }
             }
+            "to_d" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                if let Object::String(string_value) = receiver {
+                    Ok(Some(Object::Decimal(decimal_prefix(string_value))))
+                } else {
+                    Ok(None)
+                }
+            }
+            "to_number" => {
+                let Object::String(string_value) = receiver else {
+                    return Ok(None);
+                };
+                let mut format = NumberFormat::with_precision(0);
+                apply_number_format_options(method_name, arguments, false, &mut format, position)?;
+                Ok(Some(
+                    match parse_number(string_value, &format.delimiter, &format.separator) {
+                        Some(ParsedNumber::Int(value)) => Object::Int(value),
+                        Some(ParsedNumber::Float(value)) => Object::Float(value),
+                        None => Object::Nil,
+                    },
+                ))
+            }
+            "hex_encode" | "hex_decode" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let Object::String(string_value) = receiver else {
+                    return Ok(None);
+                };
+                if method_name == "hex_encode" {
+                    return Ok(Some(Object::string(hex::encode(string_value.as_bytes()))));
+                }
+                let decoded = hex::decode(string_value)
+                    .and_then(|bytes| {
+                        String::from_utf8(bytes)
+                            .map_err(|_| "decoded data is not valid UTF-8 text".to_string())
+                    })
+                    .map_err(|err| {
+                        raised_exception_error(
+                            "ArgumentError",
+                            format!("String#hex_decode: {}", err),
+                            position,
+                        )
+                    })?;
+                Ok(Some(Object::string(decoded)))
+            }
+            "each_char" | "each_byte" | "each_line" => {
+                let Object::String(string_value) = receiver else {
+                    return Ok(None);
+                };
+                // Without a block the items are returned as an array
+                let (options, block) = match arguments.split_last() {
+                    Some((Object::Block(block), rest)) => (rest, Some(Rc::clone(block))),
+                    _ => (arguments, None),
+                };
+                let items = match method_name {
+                    "each_line" => {
+                        let chomp = line_options(method_name, options, position)?;
+                        line_objects(string_value, chomp)
+                    }
+                    _ if !options.is_empty() => {
+                        return Err(method_argument_error(
+                            method_name,
+                            0,
+                            options.len(),
+                            position,
+                        ));
+                    }
+                    "each_char" => string_value
+                        .chars()
+                        .map(|c| Object::string(c.to_string()))
+                        .collect(),
+                    _ => string_value
+                        .bytes()
+                        .map(|b| Object::Int(b as i64))
+                        .collect(),
+                };
+                self.yield_each(receiver, block, items.into_iter())
+            }
+            "lines" => {
+                let Object::String(string_value) = receiver else {
+                    return Ok(None);
+                };
+                let chomp = line_options(method_name, arguments, position)?;
+                Ok(Some(Object::array(line_objects(string_value, chomp))))
+            }
+            "ljust" | "rjust" | "center" => {
+                if arguments.is_empty() || arguments.len() > 2 {
+                    return Err(method_argument_error(
+                        method_name,
+                        2,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let Object::String(string_value) = receiver else {
+                    return Ok(None);
+                };
+                let width = match &arguments[0] {
+                    Object::Int(width) => *width,
+                    other => {
+                        return Err(method_argument_type_error(
+                            method_name,
+                            "Integer",
+                            other,
+                            position,
+                        ));
+                    }
+                };
+                let pad = match arguments.get(1) {
+                    None => " ",
+                    Some(Object::String(pad)) => pad.as_str(),
+                    Some(other) => {
+                        return Err(method_argument_type_error(
+                            method_name,
+                            "String",
+                            other,
+                            position,
+                        ));
+                    }
+                };
+                if pad.is_empty() {
+                    return Err(raised_exception_error(
+                        "ArgumentError",
+                        format!("String#{}: zero width padding", method_name),
+                        position,
+                    ));
+                }
+                // Widths count characters, so multibyte text lines up in columns
+                let missing = usize::try_from(width)
+                    .unwrap_or(0)
+                    .saturating_sub(string_value.chars().count());
+                let (left, right) = match method_name {
+                    "ljust" => (0, missing),
+                    "rjust" => (missing, 0),
+                    _ => (missing / 2, missing - missing / 2),
+                };
+                let mut justified = padding(pad, left);
+                justified.push_str(string_value);
+                justified.push_str(&padding(pad, right));
+                Ok(Some(Object::string(justified)))
+            }
+            "tr" => {
+                if arguments.len() != 2 {
+                    return Err(method_argument_error(
+                        method_name,
+                        2,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let Object::String(string_value) = receiver else {
+                    return Ok(None);
+                };
+                let from = parse_char_set(method_name, &arguments[0], position)?;
+                let to = match &arguments[1] {
+                    Object::String(spec) => CharSet::parse_sequence(spec).map_err(|message| {
+                        raised_exception_error(
+                            "ArgumentError",
+                            format!("String#tr: {}", message),
+                            position,
+                        )
+                    })?,
+                    other => {
+                        return Err(method_argument_type_error(
+                            method_name,
+                            "String",
+                            other,
+                            position,
+                        ));
+                    }
+                };
+                Ok(Some(Object::string(char_set::translate(
+                    string_value,
+                    &from,
+                    &to,
+                ))))
+            }
+            "squeeze" | "delete" | "count" => {
+                // delete and count need at least one set; squeeze without one
+                // collapses every run
+                if method_name != "squeeze" && arguments.is_empty() {
+                    return Err(method_argument_error(method_name, 1, 0, position));
+                }
+                let Object::String(string_value) = receiver else {
+                    return Ok(None);
+                };
+                let sets = arguments
+                    .iter()
+                    .map(|spec| parse_char_set(method_name, spec, position))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let result = match method_name {
+                    "squeeze" => Object::string(char_set::squeeze(string_value, &sets)),
+                    "delete" => Object::string(
+                        string_value
+                            .chars()
+                            .filter(|&ch| !char_set::in_all(&sets, ch))
+                            .collect::<String>(),
+                    ),
+                    _ => Object::Int(
+                        string_value
+                            .chars()
+                            .filter(|&ch| char_set::in_all(&sets, ch))
+                            .count() as i64,
+                    ),
+                };
+                Ok(Some(result))
+            }
             _ => Ok(None),
         }
     }
 }
+
+/// Read the `chomp:` keyword of `String#lines` and `String#each_line`.
+pub(super) fn line_options(
+    method_name: &str,
+    arguments: &[Object],
+    position: Position,
+) -> Result<bool, MetorexError> {
+    let options = match arguments {
+        [] => return Ok(false),
+        [Object::Dict(options)] => options.borrow(),
+        [other] => {
+            return Err(method_argument_type_error(
+                method_name,
+                "keyword options",
+                other,
+                position,
+            ));
+        }
+        _ => {
+            return Err(method_argument_error(
+                method_name,
+                1,
+                arguments.len(),
+                position,
+            ));
+        }
+    };
+
+    let mut chomp = false;
+    for (key, value) in options.iter() {
+        match (key.as_str(), value) {
+            (":chomp", Object::Bool(flag)) => chomp = *flag,
+            (":chomp", other) => {
+                return Err(MetorexError::runtime_error(
+                    format!("{}: chomp must be a Bool, got {}", method_name, other),
+                    position_to_location(position),
+                ));
+            }
+            _ => {
+                return Err(MetorexError::runtime_error(
+                    format!(
+                        "{}: unknown option {} (expected chomp)",
+                        method_name,
+                        key.trim_start_matches(':')
+                    ),
+                    position_to_location(position),
+                ));
+            }
+        }
+    }
+    Ok(chomp)
+}
+
+impl VirtualMachine {
+    /// Fill the `%` directives of `template` with `arguments` in order, the
+    /// way `format` and `String#%` do. Arguments left over are ignored.
+    pub(crate) fn format_string(
+        &mut self,
+        method_name: &str,
+        template: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<String, MetorexError> {
+        let format_error = |message: String| {
+            raised_exception_error(
+                "ArgumentError",
+                format!("{}: {}", method_name, message),
+                position,
+            )
+        };
+        let mut output = String::with_capacity(template.len());
+        let mut arguments = arguments.iter();
+        let mut chars = template.chars().peekable();
+        while let Some(ch) = chars.next() {
+            if ch != '%' {
+                output.push(ch);
+                continue;
+            }
+            if chars.next_if_eq(&'%').is_some() {
+                output.push('%');
+                continue;
+            }
+            let spec = parse_format_spec(&mut chars).map_err(format_error)?;
+            let Some(value) = arguments.next() else {
+                return Err(format_error("too few arguments".to_string()));
+            };
+            let text = match spec.conversion {
+                's' => {
+                    let text = self.get_string_representation(value, position)?;
+                    match spec.precision {
+                        Some(precision) => text.chars().take(precision).collect(),
+                        None => text,
+                    }
+                }
+                'd' | 'i' => match value {
+                    Object::Int(int) => spec.signed(*int < 0, int.unsigned_abs().to_string()),
+                    Object::Float(float) if float.is_finite() => {
+                        spec.signed(*float <= -1.0, format!("{}", float.abs().trunc()))
+                    }
+                    Object::Decimal(decimal) => {
+                        let whole = decimal.trunc();
+                        spec.signed(
+                            whole.is_sign_negative() && !whole.is_zero(),
+                            whole.abs().to_string(),
+                        )
+                    }
+                    other => return Err(format_error(spec.type_problem("a number", other))),
+                },
+                _ => {
+                    let precision = spec.precision.unwrap_or(6);
+                    match value {
+                        Object::Int(int) => spec.signed(
+                            *int < 0,
+                            format!("{:.*}", precision, int.unsigned_abs() as f64),
+                        ),
+                        Object::Float(float) => spec.signed(
+                            float.is_sign_negative() && *float != 0.0,
+                            format!("{:.*}", precision, float.abs()),
+                        ),
+                        Object::Decimal(decimal) => {
+                            let rounded = decimal
+                                .round_dp_with_strategy(precision as u32, self.decimal_rounding());
+                            spec.signed(
+                                rounded.is_sign_negative() && !rounded.is_zero(),
+                                format!("{:.*}", precision, rounded.abs()),
+                            )
+                        }
+                        other => return Err(format_error(spec.type_problem("a number", other))),
+                    }
+                }
+            };
+            output.push_str(&spec.pad(text));
+        }
+        Ok(output)
+    }
+}
+
+/// One `%` directive of a format string, like `%-8s`, `%+d` or `%08.3f`
+#[derive(Debug, Default)]
+struct FormatSpec {
+    /// `-`: pad on the right
+    left: bool,
+    /// `0`: pad numbers with zeros after the sign
+    zero: bool,
+    /// `+`: show a plus sign on numbers that aren't negative
+    plus: bool,
+    /// ` `: leave a space where a plus sign would go
+    space: bool,
+    width: Option<usize>,
+    precision: Option<usize>,
+    /// `s`, `d`, `i` or `f`
+    conversion: char,
+}
+
+impl FormatSpec {
+    /// A formatted number: its digits behind the sign the flags ask for.
+    /// Zero padding goes between the sign and the digits.
+    fn signed(&self, negative: bool, digits: String) -> String {
+        let sign = if negative {
+            "-"
+        } else if self.plus {
+            "+"
+        } else if self.space {
+            " "
+        } else {
+            ""
+        };
+        match self.width {
+            Some(width) if self.zero && !self.left => {
+                let zeros = width.saturating_sub(sign.len() + digits.chars().count());
+                format!("{}{}{}", sign, "0".repeat(zeros), digits)
+            }
+            _ => format!("{}{}", sign, digits),
+        }
+    }
+
+    /// `text` padded with spaces to the directive's width.
+    fn pad(&self, text: String) -> String {
+        let width = self.width.unwrap_or(0);
+        let padding = " ".repeat(width.saturating_sub(text.chars().count()));
+        if self.left {
+            text + &padding
+        } else {
+            padding + &text
+        }
+    }
+
+    fn type_problem(&self, expected: &str, value: &Object) -> String {
+        format!(
+            "%{} expects {}, got {}",
+            self.conversion,
+            expected,
+            value.type_name()
+        )
+    }
+}
+
+/// Read the directive after a `%`: flags, then an optional width, an
+/// optional `.precision` and the conversion letter.
+fn parse_format_spec(chars: &mut Peekable<Chars>) -> Result<FormatSpec, String> {
+    let mut spec = FormatSpec::default();
+    while let Some(flag) = chars.next_if(|ch| matches!(ch, '-' | '0' | '+' | ' ')) {
+        match flag {
+            '-' => spec.left = true,
+            '0' => spec.zero = true,
+            '+' => spec.plus = true,
+            _ => spec.space = true,
+        }
+    }
+    spec.width = read_count(chars)?;
+    if chars.next_if_eq(&'.').is_some() {
+        spec.precision = Some(read_count(chars)?.unwrap_or(0));
+    }
+    match chars.next() {
+        Some(conversion @ ('s' | 'd' | 'i' | 'f')) => {
+            spec.conversion = conversion;
+            Ok(spec)
+        }
+        Some(other) => Err(format!("unknown format directive '%{}'", other)),
+        None => Err("incomplete format directive at the end".to_string()),
+    }
+}
+
+/// Read the digits of a width or precision, if there are any.
+fn read_count(chars: &mut Peekable<Chars>) -> Result<Option<usize>, String> {
+    let mut digits = String::new();
+    while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+        digits.push(digit);
+    }
+    if digits.is_empty() {
+        return Ok(None);
+    }
+    digits
+        .parse()
+        .map(Some)
+        .map_err(|_| format!("width or precision {} is too large", digits))
+}
+
+/// The lines of `text`, each keeping its "\n" unless `chomp` removes the
+/// line ending.
+fn line_objects(text: &str, chomp: bool) -> Vec<Object> {
+    text.split_inclusive('\n')
+        .map(|line| Object::string(if chomp { chomp_newline(line) } else { line }))
+        .collect()
+}
+
+/// Repeat `pad` cyclically until it is `width` characters long.
+fn padding(pad: &str, width: usize) -> String {
+    pad.chars().cycle().take(width).collect()
+}
+
+/// Read a tr-style character set argument.
+fn parse_char_set(
+    method_name: &str,
+    spec: &Object,
+    position: Position,
+) -> Result<CharSet, MetorexError> {
+    let Object::String(spec) = spec else {
+        return Err(method_argument_type_error(
+            method_name,
+            "String",
+            spec,
+            position,
+        ));
+    };
+    CharSet::parse(spec).map_err(|message| {
+        raised_exception_error(
+            "ArgumentError",
+            format!("String#{}: {}", method_name, message),
+            position,
+        )
+    })
+}
+
+/// Remove one trailing line ending ("\n", "\r\n" or "\r").
+pub(super) fn chomp_newline(text: &str) -> &str {
+    text.strip_suffix("\r\n")
+        .or_else(|| text.strip_suffix('\n'))
+        .or_else(|| text.strip_suffix('\r'))
+        .unwrap_or(text)
+}
+
+/// Parse the leading integer of `text` the way `String#to_i` does.
+///
+/// Leading whitespace, a sign, a radix prefix matching `base` (0x, 0o, 0b)
+/// and underscores between digits are accepted; parsing stops at the first
+/// character that is not a digit, and a string without digits yields 0.
+/// Returns None if the value does not fit in an Integer.
+fn parse_integer_prefix(text: &str, base: u32) -> Option<i64> {
+    let text = text.trim_start();
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let prefix = match base {
+        16 => Some("0x"),
+        8 => Some("0o"),
+        2 => Some("0b"),
+        _ => None,
+    };
+    let text = prefix
+        .and_then(|p| {
+            text.strip_prefix(p)
+                .or_else(|| text.strip_prefix(&p.to_uppercase()))
+        })
+        .unwrap_or(text);
+
+    let mut value: i64 = 0;
+    let mut previous_was_digit = false;
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if let Some(digit) = ch.to_digit(base) {
+            let digit = digit as i64;
+            value = value.checked_mul(base as i64)?;
+            value = if negative {
+                value.checked_sub(digit)?
+            } else {
+                value.checked_add(digit)?
+            };
+            previous_was_digit = true;
+        } else if ch == '_'
+            && previous_was_digit
+            && chars.peek().is_some_and(|next| next.is_digit(base))
+        {
+            previous_was_digit = false;
+        } else {
+            break;
+        }
+    }
+    Some(value)
+}
+
+/// Parse the leading decimal number of `text` the way `String#to_f` does.
+///
+/// Accepts leading whitespace, a sign, underscores between digits, a
+/// fractional part and an exponent; anything unparseable yields 0.0.
+fn parse_float_prefix(text: &str) -> f64 {
+    let text = text.trim_start();
+    let bytes = text.as_bytes();
+    let mut number = String::new();
+    let mut i = 0;
+
+    let digits = |i: &mut usize, number: &mut String| {
+        let start = *i;
+        while *i < bytes.len() {
+            let byte = bytes[*i];
+            if byte.is_ascii_digit() {
+                number.push(byte as char);
+            } else if byte == b'_'
+                && *i > start
+                && bytes[*i - 1].is_ascii_digit()
+                && bytes.get(*i + 1).is_some_and(u8::is_ascii_digit)
+            {
+                // Underscores only separate digits
+            } else {
+                break;
+            }
+            *i += 1;
+        }
+        *i > start
+    };
+
+    if let Some(&sign @ (b'+' | b'-')) = bytes.first() {
+        number.push(sign as char);
+        i += 1;
+    }
+    let has_integer = digits(&mut i, &mut number);
+
+    if bytes.get(i) == Some(&b'.') && bytes.get(i + 1).is_some_and(u8::is_ascii_digit) {
+        number.push('.');
+        i += 1;
+        digits(&mut i, &mut number);
+    } else if !has_integer {
+        return 0.0;
+    }
+
+    if matches!(bytes.get(i), Some(b'e' | b'E')) {
+        let mut exponent = String::from("e");
+        let mut j = i + 1;
+        if let Some(&sign @ (b'+' | b'-')) = bytes.get(j) {
+            exponent.push(sign as char);
+            j += 1;
+        }
+        if digits(&mut j, &mut exponent) {
+            number.push_str(&exponent);
+        }
+    }
+
+    number.parse().unwrap_or(0.0)
+}
@@ -0,0 +1,36 @@
+//! Native method implementations for the Timeout class.
+
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::vm::VirtualMachine;
+use crate::vm::utils::position_to_location;
+use std::rc::Rc;
+
+impl VirtualMachine {
+    /// Execute class methods on Timeout.
+    pub(crate) fn call_timeout_method(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        match method_name {
+            // Timeout::Error - the class of the error an expired timeout raises
+            "Error" => Ok(Some(Object::Class(Rc::clone(
+                &self.builtins().timeout_error_class,
+            )))),
+            // Timeout.timeout(seconds) { ... } - the block's value, unless it runs too long
+            "timeout" => match arguments {
+                [seconds, block @ Object::Block(_)] => self
+                    .call_with_timeout(seconds, block.clone(), position)
+                    .map(Some),
+                _ => Err(MetorexError::runtime_error(
+                    "timeout expects a number of seconds and a block",
+                    position_to_location(position),
+                )),
+            },
+            _ => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Native method implementations for the TOML class.
+//!
+//! Parsing needs the `toml` cargo feature; without it the method exists but
+//! raises an error explaining how to enable it.
+
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use crate::vm::utils::position_to_location;
+
+impl VirtualMachine {
+    /// Execute class methods on TOML.
+    pub(crate) fn call_toml_method(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        if method_name != "parse" {
+            return Ok(None);
+        }
+
+        // TOML.parse(text) - read a TOML document into a Hash
+        let text = match arguments {
+            [Object::String(text)] => text,
+            [other] => {
+                return Err(method_argument_type_error(
+                    method_name,
+                    "String",
+                    other,
+                    position,
+                ));
+            }
+            _ => {
+                return Err(method_argument_error(
+                    method_name,
+                    1,
+                    arguments.len(),
+                    position,
+                ));
+            }
+        };
+
+        #[cfg(feature = "toml")]
+        {
+            text.parse::<toml::Table>()
+                .map(|table| Some(from_toml(toml::Value::Table(table))))
+                .map_err(|err| {
+                    MetorexError::runtime_error(
+                        format!("TOML.parse: {}", err.message()),
+                        position_to_location(position),
+                    )
+                })
+        }
+
+        #[cfg(not(feature = "toml"))]
+        {
+            let _ = text;
+            Err(MetorexError::runtime_error(
+                "TOML.parse is unavailable: metorex was built without the toml feature",
+                position_to_location(position),
+            ))
+        }
+    }
+}
+
+/// Map a TOML value onto Metorex objects; dates and times become Strings.
+#[cfg(feature = "toml")]
+fn from_toml(value: toml::Value) -> Object {
+    match value {
+        toml::Value::String(text) => Object::string(text),
+        toml::Value::Integer(value) => Object::Int(value),
+        toml::Value::Float(value) => Object::Float(value),
+        toml::Value::Boolean(value) => Object::Bool(value),
+        toml::Value::Datetime(datetime) => Object::string(datetime.to_string()),
+        toml::Value::Array(items) => Object::array(items.into_iter().map(from_toml).collect()),
+        toml::Value::Table(table) => Object::dict(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, from_toml(value)))
+                .collect(),
+        ),
+    }
+}
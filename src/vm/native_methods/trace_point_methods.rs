@@ -0,0 +1,213 @@
+//! Native method implementations for the TracePoint class.
+//!
+//! A trace point keeps the events it listens for and its block in instance
+//! variables. While the block runs, the details of the current event are
+//! stored in further instance variables that the readers (`event`, `lineno`,
+//! `binding`, ...) return; outside the block the readers raise.
+
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use crate::vm::trace::{TraceEvent, TraceEventKind};
+use std::rc::Rc;
+
+/// Instance variables describing the event being delivered.
+const EVENT_DETAILS: [&str; 8] = [
+    "event",
+    "lineno",
+    "path",
+    "method_name",
+    "class_name",
+    "binding",
+    "return_value",
+    "raised_exception",
+];
+
+impl VirtualMachine {
+    /// TracePoint.new(*events, &block) - listen for `events`, or every event
+    /// when none are given.
+    pub(crate) fn new_trace_point(
+        &mut self,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        let Some((block @ Object::Block(_), names)) = arguments.split_last() else {
+            return Err(raised_exception_error(
+                "ValueError",
+                "TracePoint.new requires a block".to_string(),
+                position,
+            ));
+        };
+
+        let mut events = Vec::new();
+        for name in names {
+            let kind = match name {
+                Object::Symbol(name) => TraceEventKind::from_name(name).ok_or_else(|| {
+                    raised_exception_error(
+                        "ValueError",
+                        format!("unknown TracePoint event :{}", name),
+                        position,
+                    )
+                })?,
+                other => {
+                    return Err(method_argument_type_error("new", "Symbol", other, position));
+                }
+            };
+            events.push(self.symbol(kind.name()));
+        }
+        if events.is_empty() {
+            events = TraceEventKind::ALL
+                .iter()
+                .map(|kind| self.symbol(kind.name()))
+                .collect();
+        }
+
+        let trace_point = Object::instance(Rc::clone(&self.builtins().trace_point_class));
+        if let Object::Instance(instance) = &trace_point {
+            let mut instance = instance.borrow_mut();
+            instance.set_var("events".to_string(), Object::array(events));
+            instance.set_var("block".to_string(), block.clone());
+        }
+        Ok(trace_point)
+    }
+
+    /// Execute native methods on TracePoint instances.
+    pub(crate) fn call_trace_point_method(
+        &mut self,
+        receiver: &Object,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let Object::Instance(instance) = receiver else {
+            return Ok(None);
+        };
+
+        match method_name {
+            // enable - start tracing; with a block, trace only while it runs
+            "enable" => match arguments {
+                [] => {
+                    let was_enabled = !self.enable_trace_point(instance);
+                    Ok(Some(Object::Bool(was_enabled)))
+                }
+                [block @ Object::Block(_)] => {
+                    let newly_enabled = self.enable_trace_point(instance);
+                    let result = self.invoke_callable(block.clone(), vec![], position);
+                    if newly_enabled {
+                        self.disable_trace_point(instance);
+                    }
+                    result.map(Some)
+                }
+                _ => Err(method_argument_error(
+                    method_name,
+                    0,
+                    arguments.len(),
+                    position,
+                )),
+            },
+            "disable" | "enabled?" => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let enabled = if method_name == "disable" {
+                    self.disable_trace_point(instance)
+                } else {
+                    self.is_trace_point_enabled(instance)
+                };
+                Ok(Some(Object::Bool(enabled)))
+            }
+            name if EVENT_DETAILS.contains(&name) => {
+                if !arguments.is_empty() {
+                    return Err(method_argument_error(
+                        method_name,
+                        0,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let instance = instance.borrow();
+                if matches!(instance.get_var("event"), None | Some(Object::Nil)) {
+                    return Err(raised_exception_error(
+                        "RuntimeError",
+                        format!("TracePoint#{} is only available inside its block", name),
+                        position,
+                    ));
+                }
+                Ok(Some(instance.get_var(name).cloned().unwrap_or(Object::Nil)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// The block of a trace point, if it listens for events of `kind`.
+    pub(crate) fn trace_point_block_for(
+        &self,
+        trace_point: &Object,
+        kind: TraceEventKind,
+    ) -> Option<Object> {
+        let Object::Instance(instance) = trace_point else {
+            return None;
+        };
+        let instance = instance.borrow();
+        let Some(Object::Array(events)) = instance.get_var("events") else {
+            return None;
+        };
+        let listening = events
+            .borrow()
+            .iter()
+            .any(|event| matches!(event, Object::Symbol(name) if name.as_str() == kind.name()));
+        listening
+            .then(|| instance.get_var("block").cloned())
+            .flatten()
+    }
+
+    /// Fill in the event details a trace point's readers return, or clear
+    /// them once its block has finished.
+    pub(crate) fn set_trace_point_event(
+        &self,
+        trace_point: &Object,
+        event: Option<(&TraceEvent, &Object)>,
+    ) {
+        let Object::Instance(instance) = trace_point else {
+            return;
+        };
+        let mut instance = instance.borrow_mut();
+        let Some((event, binding)) = event else {
+            for name in EVENT_DETAILS {
+                instance.set_var(name.to_string(), Object::Nil);
+            }
+            return;
+        };
+
+        let text = |value: &Option<String>| value.clone().map_or(Object::Nil, Object::string);
+        instance.set_var("event".to_string(), self.symbol(event.kind.name()));
+        instance.set_var(
+            "lineno".to_string(),
+            Object::Int(event.position.line as i64),
+        );
+        instance.set_var(
+            "path".to_string(),
+            event.path.as_ref().map_or(Object::Nil, |path| {
+                Object::string(path.display().to_string())
+            }),
+        );
+        instance.set_var("method_name".to_string(), text(&event.method_name));
+        instance.set_var("class_name".to_string(), text(&event.class_name));
+        instance.set_var("binding".to_string(), binding.clone());
+        instance.set_var(
+            "return_value".to_string(),
+            event.return_value.clone().unwrap_or(Object::Nil),
+        );
+        instance.set_var(
+            "raised_exception".to_string(),
+            event.raised_exception.clone().unwrap_or(Object::Nil),
+        );
+    }
+}
@@ -0,0 +1,256 @@
+//! Native method implementations for the URI class.
+//!
+//! `URI.parse` returns a URI instance whose components are stored as
+//! instance variables, so user subclasses and `inspect` see them like any
+//! other object's state.
+
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::uri::{self, Uri};
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use indexmap::IndexMap;
+use std::rc::Rc;
+
+/// Instance variables holding the components of a parsed URI.
+const COMPONENTS: [&str; 7] = [
+    "scheme", "userinfo", "host", "port", "path", "query", "fragment",
+];
+
+impl VirtualMachine {
+    /// Execute class methods on URI.
+    pub(crate) fn call_uri_class_method(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let result = match method_name {
+            // URI.parse(text) - split a URI into its components
+            "parse" => {
+                let text = string_argument(method_name, arguments, position)?;
+                let parsed =
+                    Uri::parse(text).map_err(|err| uri_error(method_name, err, position))?;
+                self.uri_instance(&parsed)
+            }
+            // URI.encode_www_form(params) - build a form query from a Hash or pairs
+            "encode_www_form" => {
+                if arguments.len() != 1 {
+                    return Err(method_argument_error(
+                        method_name,
+                        1,
+                        arguments.len(),
+                        position,
+                    ));
+                }
+                let pairs = form_pairs(method_name, &arguments[0], position)?;
+                Object::string(uri::encode_www_form(&pairs))
+            }
+            // URI.decode_www_form(query) - split a form query into [key, value] pairs
+            "decode_www_form" => {
+                let query = string_argument(method_name, arguments, position)?;
+                let pairs = uri::decode_www_form(query)
+                    .map_err(|err| uri_error(method_name, err, position))?;
+                Object::array(
+                    pairs
+                        .into_iter()
+                        .map(|(key, value)| {
+                            Object::array(vec![Object::string(key), Object::string(value)])
+                        })
+                        .collect(),
+                )
+            }
+            "encode_www_form_component" | "encode_uri_component" => {
+                let text = string_argument(method_name, arguments, position)?;
+                Object::string(if method_name == "encode_www_form_component" {
+                    uri::encode_www_form_component(text)
+                } else {
+                    uri::encode_component(text)
+                })
+            }
+            "decode_www_form_component" | "decode_uri_component" => {
+                let text = string_argument(method_name, arguments, position)?;
+                let decoded = if method_name == "decode_www_form_component" {
+                    uri::decode_www_form_component(text)
+                } else {
+                    uri::decode_component(text)
+                };
+                Object::string(decoded.map_err(|err| uri_error(method_name, err, position))?)
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(result))
+    }
+
+    /// Execute native methods on URI instances.
+    pub(crate) fn call_uri_method(
+        &mut self,
+        receiver: &Object,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        let Object::Instance(instance) = receiver else {
+            return Ok(None);
+        };
+        if !COMPONENTS.contains(&method_name) && !matches!(method_name, "to_s" | "query_params") {
+            return Ok(None);
+        }
+        if !arguments.is_empty() {
+            return Err(method_argument_error(
+                method_name,
+                0,
+                arguments.len(),
+                position,
+            ));
+        }
+
+        let instance = instance.borrow();
+        let component = |name: &str| instance.get_var(name).cloned().unwrap_or(Object::Nil);
+        match method_name {
+            "to_s" => {
+                let text = |name: &str| match component(name) {
+                    Object::String(text) => Some(text.to_string()),
+                    _ => None,
+                };
+                let scheme = text("scheme");
+                let port = match component("port") {
+                    Object::Int(port) => u16::try_from(port).ok(),
+                    _ => None,
+                };
+                let parsed = Uri {
+                    port,
+                    scheme,
+                    userinfo: text("userinfo"),
+                    host: text("host"),
+                    path: text("path").unwrap_or_default(),
+                    query: text("query"),
+                    fragment: text("fragment"),
+                };
+                Ok(Some(Object::string(parsed.to_string())))
+            }
+            "query_params" => {
+                let Object::String(query) = component("query") else {
+                    return Ok(Some(Object::empty_dict()));
+                };
+                let pairs = uri::decode_www_form(&query)
+                    .map_err(|err| uri_error(method_name, err, position))?;
+                let params: IndexMap<String, Object> = pairs
+                    .into_iter()
+                    .map(|(key, value)| (key, Object::string(value)))
+                    .collect();
+                Ok(Some(Object::dict(params)))
+            }
+            _ => Ok(Some(component(method_name))),
+        }
+    }
+
+    /// Build a URI instance holding the components of `parsed`.
+    fn uri_instance(&self, parsed: &Uri) -> Object {
+        let object = Object::instance(Rc::clone(&self.builtins().uri_class));
+        if let Object::Instance(instance) = &object {
+            let mut instance = instance.borrow_mut();
+            let text = |value: &Option<String>| value.clone().map_or(Object::Nil, Object::string);
+            instance.set_var("scheme".to_string(), text(&parsed.scheme));
+            instance.set_var("userinfo".to_string(), text(&parsed.userinfo));
+            instance.set_var("host".to_string(), text(&parsed.host));
+            instance.set_var(
+                "port".to_string(),
+                parsed
+                    .effective_port()
+                    .map_or(Object::Nil, |port| Object::Int(i64::from(port))),
+            );
+            instance.set_var("path".to_string(), Object::string(parsed.path.clone()));
+            instance.set_var("query".to_string(), text(&parsed.query));
+            instance.set_var("fragment".to_string(), text(&parsed.fragment));
+        }
+        object
+    }
+}
+
+/// The single String argument of a URI class method.
+fn string_argument<'a>(
+    method_name: &str,
+    arguments: &'a [Object],
+    position: Position,
+) -> Result<&'a str, MetorexError> {
+    match arguments {
+        [Object::String(text)] => Ok(text),
+        [other] => Err(method_argument_type_error(
+            method_name,
+            "String",
+            other,
+            position,
+        )),
+        _ => Err(method_argument_error(
+            method_name,
+            1,
+            arguments.len(),
+            position,
+        )),
+    }
+}
+
+/// Read form parameters from a Hash or an Array of `[key, value]` pairs.
+///
+/// Symbol keys lose their colon, values are written with `to_s`, and a nil
+/// value leaves just the key.
+fn form_pairs(
+    method_name: &str,
+    params: &Object,
+    position: Position,
+) -> Result<Vec<(String, Option<String>)>, MetorexError> {
+    let value_text = |value: &Object| match value {
+        Object::Nil => None,
+        other => Some(other.to_string()),
+    };
+
+    match params {
+        Object::Dict(dict) => Ok(dict
+            .borrow()
+            .iter()
+            .map(|(key, value)| {
+                let key = key.strip_prefix(':').unwrap_or(key);
+                (key.to_string(), value_text(value))
+            })
+            .collect()),
+        Object::Array(pairs) => pairs
+            .borrow()
+            .iter()
+            .map(|pair| match pair {
+                Object::Array(pair) => match pair.borrow().as_slice() {
+                    [key, value] => Ok((key.to_string(), value_text(value))),
+                    _ => Err(uri_error(
+                        method_name,
+                        format!(
+                            "expected [key, value] pairs, got {}",
+                            Object::Array(Rc::clone(pair))
+                        ),
+                        position,
+                    )),
+                },
+                other => Err(uri_error(
+                    method_name,
+                    format!("expected [key, value] pairs, got {}", other),
+                    position,
+                )),
+            })
+            .collect(),
+        other => Err(method_argument_type_error(
+            method_name,
+            "Hash or Array",
+            other,
+            position,
+        )),
+    }
+}
+
+/// A rescuable ArgumentError for input the URI module cannot handle
+fn uri_error(method_name: &str, message: String, position: Position) -> MetorexError {
+    raised_exception_error(
+        "ArgumentError",
+        format!("URI.{}: {}", method_name, message),
+        position,
+    )
+}
@@ -0,0 +1,164 @@
+//! Native method implementations for the YAML class.
+//!
+//! Parsing and dumping need the `yaml` cargo feature; without it the methods
+//! exist but raise an error explaining how to enable them.
+
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::vm::VirtualMachine;
+use crate::vm::errors::*;
+use crate::vm::utils::position_to_location;
+
+impl VirtualMachine {
+    /// Execute class methods on YAML.
+    pub(crate) fn call_yaml_method(
+        &mut self,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Object>, MetorexError> {
+        if !matches!(method_name, "parse" | "dump") {
+            return Ok(None);
+        }
+        if arguments.len() != 1 {
+            return Err(method_argument_error(
+                method_name,
+                1,
+                arguments.len(),
+                position,
+            ));
+        }
+
+        #[cfg(feature = "yaml")]
+        {
+            let result = match (method_name, &arguments[0]) {
+                // YAML.parse(text) - read one YAML document into Hash/Array/scalars
+                ("parse", Object::String(text)) => serde_yaml::from_str(text)
+                    .map_err(|err| err.to_string())
+                    .and_then(convert::from_yaml),
+                ("parse", other) => {
+                    return Err(method_argument_type_error(
+                        method_name,
+                        "String",
+                        other,
+                        position,
+                    ));
+                }
+                // YAML.dump(value) - write a value as a YAML document
+                _ => convert::to_yaml(&arguments[0], &mut Vec::new()).and_then(|value| {
+                    serde_yaml::to_string(&value)
+                        .map(Object::string)
+                        .map_err(|err| err.to_string())
+                }),
+            };
+            result.map(Some).map_err(|message| {
+                MetorexError::runtime_error(
+                    format!("YAML.{}: {}", method_name, message),
+                    position_to_location(position),
+                )
+            })
+        }
+
+        #[cfg(not(feature = "yaml"))]
+        Err(MetorexError::runtime_error(
+            format!(
+                "YAML.{} is unavailable: metorex was built without the yaml feature",
+                method_name
+            ),
+            position_to_location(position),
+        ))
+    }
+}
+
+#[cfg(feature = "yaml")]
+mod convert {
+    use crate::object::Object;
+    use indexmap::IndexMap;
+    use serde_yaml::{Mapping, Number, Value};
+
+    /// Map a YAML value onto Metorex objects; mapping keys become Strings.
+    pub(super) fn from_yaml(value: Value) -> Result<Object, String> {
+        Ok(match value {
+            Value::Null => Object::Nil,
+            Value::Bool(value) => Object::Bool(value),
+            Value::Number(number) => from_number(&number),
+            Value::String(text) => Object::string(text),
+            Value::Sequence(items) => {
+                Object::array(items.into_iter().map(from_yaml).collect::<Result<_, _>>()?)
+            }
+            Value::Mapping(mapping) => {
+                let mut entries = IndexMap::new();
+                for (key, value) in mapping {
+                    entries.insert(key_text(key)?, from_yaml(value)?);
+                }
+                Object::dict(entries)
+            }
+            Value::Tagged(tagged) => from_yaml(tagged.value)?,
+        })
+    }
+
+    /// Convert a value for dumping; `seen` holds the collections being
+    /// written so a structure that contains itself is rejected.
+    pub(super) fn to_yaml(value: &Object, seen: &mut Vec<*const ()>) -> Result<Value, String> {
+        Ok(match value {
+            Object::Nil => Value::Null,
+            Object::Bool(value) => Value::Bool(*value),
+            Object::Int(value) => Value::Number(Number::from(*value)),
+            Object::Float(value) => Value::Number(Number::from(*value)),
+            // Written as text, since a YAML number would be read back as a Float
+            Object::Decimal(value) => Value::String(value.to_string()),
+            Object::String(text) | Object::Symbol(text) => Value::String(text.to_string()),
+            Object::Array(items) => {
+                enter(seen, items.as_ptr() as *const ())?;
+                let sequence = items
+                    .borrow()
+                    .iter()
+                    .map(|item| to_yaml(item, seen))
+                    .collect::<Result<_, _>>()?;
+                seen.pop();
+                Value::Sequence(sequence)
+            }
+            Object::Dict(entries) => {
+                enter(seen, entries.as_ptr() as *const ())?;
+                let mut mapping = Mapping::new();
+                for (key, value) in entries.borrow().iter() {
+                    let key = key.strip_prefix(':').unwrap_or(key);
+                    mapping.insert(Value::String(key.to_string()), to_yaml(value, seen)?);
+                }
+                seen.pop();
+                Value::Mapping(mapping)
+            }
+            other => return Err(format!("cannot dump {} to YAML", other.type_name())),
+        })
+    }
+
+    fn from_number(number: &Number) -> Object {
+        match number.as_i64() {
+            Some(value) => Object::Int(value),
+            None => Object::Float(number.as_f64().unwrap_or(f64::NAN)),
+        }
+    }
+
+    /// Hash keys are Strings, so scalar keys are written out as text.
+    fn key_text(key: Value) -> Result<String, String> {
+        match key {
+            Value::String(text) => Ok(text),
+            Value::Number(number) => Ok(number.to_string()),
+            Value::Bool(value) => Ok(value.to_string()),
+            Value::Null => Ok(String::new()),
+            Value::Tagged(tagged) => key_text(tagged.value),
+            Value::Sequence(_) | Value::Mapping(_) => {
+                Err("mapping keys must be scalars".to_string())
+            }
+        }
+    }
+
+    fn enter(seen: &mut Vec<*const ()>, collection: *const ()) -> Result<(), String> {
+        if seen.contains(&collection) {
+            return Err("cannot dump a recursive structure".to_string());
+        }
+        seen.push(collection);
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+//! Argument checking for builtin methods and functions.
+//!
+//! The parameters of every native are declared once, in
+//! [`crate::builtin_catalog`], which also backs `metorex builtins` and the
+//! static typechecker. Calls are checked against those declarations before
+//! the native runs, so argument errors name the parameter and its expected
+//! type instead of each native describing its own arity. Arguments for `Int`
+//! parameters are converted by the `to_int` protocol first, so natives only
+//! ever see Ints there.
+//!
+//! The check runs on every native call, so the catalog is prepared once, on
+//! first use: each class's methods, inherited ones included, are indexed by
+//! name with their parameter types already parsed.
+
+use super::VirtualMachine;
+use super::errors::*;
+use super::type_checks::value_lineage;
+use crate::ast::TypeAnnotation;
+use crate::builtin_catalog::{self, BuiltinParam, MethodKind, ParamKind};
+use crate::class::Class;
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::typecheck::{accepts_anything, satisfies};
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+use std::sync::OnceLock;
+
+/// A catalog signature prepared for checking calls against it
+struct Signature {
+    min: usize,
+    max: Option<usize>,
+    takes_block: bool,
+    takes_keywords: bool,
+    /// Positional parameters, with the type each declares unless it accepts
+    /// anything
+    declared: Vec<(&'static BuiltinParam, Option<TypeAnnotation>)>,
+}
+
+impl Signature {
+    fn new(parameters: &'static [BuiltinParam]) -> Self {
+        let takes = |kinds: &[ParamKind]| parameters.iter().any(|p| kinds.contains(&p.kind));
+        let declared: Vec<_> = parameters
+            .iter()
+            .filter(|p| {
+                matches!(
+                    p.kind,
+                    ParamKind::Required | ParamKind::Optional | ParamKind::Rest
+                )
+            })
+            .map(|parameter| {
+                let annotation = parameter
+                    .ty
+                    .map(|ty| TypeAnnotation::parse(ty, Position::default()))
+                    .filter(|annotation| !accepts_anything(annotation));
+                (parameter, annotation)
+            })
+            .collect();
+        // Keyword options are not counted, unlike in the catalog's max_arity
+        let max = match declared.last() {
+            Some((last, _)) if last.kind == ParamKind::Rest => None,
+            _ => Some(declared.len()),
+        };
+        Signature {
+            min: builtin_catalog::min_arity(parameters),
+            max,
+            takes_block: takes(&[ParamKind::Block, ParamKind::OptionalBlock]),
+            takes_keywords: takes(&[ParamKind::Keywords]),
+            declared,
+        }
+    }
+}
+
+/// A map keyed by catalog names
+type NameMap<K, V> = HashMap<K, V, BuildHasherDefault<NameHasher>>;
+
+/// Every catalog signature, keyed the way calls look them up
+struct Signatures {
+    /// Methods by (class, method, kind), including the ones each class
+    /// inherits
+    methods: NameMap<(&'static str, &'static str, MethodKind), Signature>,
+    functions: NameMap<&'static str, Signature>,
+}
+
+/// The catalog signatures, prepared on first use so a call costs one hash
+/// lookup rather than a walk of the catalog.
+fn signatures() -> &'static Signatures {
+    static SIGNATURES: OnceLock<Signatures> = OnceLock::new();
+    SIGNATURES.get_or_init(|| {
+        let mut methods = NameMap::default();
+        for class in builtin_catalog::CLASSES {
+            // A class's own methods come first, so they win over inherited ones
+            let mut current = Some(class);
+            while let Some(ancestor) = current {
+                for method in builtin_catalog::methods_of(ancestor.name) {
+                    methods
+                        .entry((class.name, method.name, method.kind))
+                        .or_insert_with(|| Signature::new(method.parameters));
+                }
+                current = ancestor.superclass.and_then(builtin_catalog::find_class);
+            }
+        }
+        let functions = builtin_catalog::FUNCTIONS
+            .iter()
+            .map(|function| (function.name, Signature::new(function.parameters)))
+            .collect();
+        Signatures { methods, functions }
+    })
+}
+
+impl VirtualMachine {
+    /// Check a call to a builtin method against its catalog signature,
+    /// returning the arguments when any had to be converted to an Int.
+    /// Methods the catalog does not describe are left to the native.
+    pub(crate) fn check_builtin_method_arguments(
+        &mut self,
+        class: &Class,
+        receiver: &Object,
+        method_name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Vec<Object>>, MetorexError> {
+        let (class_name, kind) = match receiver {
+            Object::Class(class) => (class.name(), MethodKind::Class),
+            Object::Module(_) => ("Module", MethodKind::Instance),
+            _ => (class.name(), MethodKind::Instance),
+        };
+        match signatures().methods.get(&(class_name, method_name, kind)) {
+            Some(signature) => self.check_arguments(method_name, signature, arguments, position),
+            None => Ok(None),
+        }
+    }
+
+    /// Check a call to a native function against its catalog signature,
+    /// returning the arguments when any had to be converted to an Int.
+    pub(crate) fn check_builtin_function_arguments(
+        &mut self,
+        name: &str,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Vec<Object>>, MetorexError> {
+        match signatures().functions.get(name) {
+            Some(signature) => self.check_arguments(name, signature, arguments, position),
+            None => Ok(None),
+        }
+    }
+
+    /// Match positional arguments to parameters, checking the count and any
+    /// declared types. Blocks and keyword options are left to the native.
+    fn check_arguments(
+        &mut self,
+        name: &str,
+        signature: &Signature,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Option<Vec<Object>>, MetorexError> {
+        let mut positional = arguments;
+        // A lambda passed for a required parameter is an argument, not the block
+        if signature.takes_block
+            && positional.len() > signature.min
+            && let [rest @ .., Object::Block(_)] = positional
+        {
+            positional = rest;
+        }
+        if signature.takes_keywords
+            && let [rest @ .., Object::Dict(_)] = positional
+        {
+            positional = rest;
+        }
+
+        if positional.len() < signature.min
+            || signature.max.is_some_and(|max| positional.len() > max)
+        {
+            return Err(builtin_arity_error(
+                name,
+                positional.len(),
+                signature.min,
+                signature.max,
+                position,
+            ));
+        }
+
+        let declared = &signature.declared;
+        let mut converted: Option<Vec<Object>> = None;
+        for (index, argument) in positional.iter().enumerate() {
+            // Extra arguments all belong to a trailing rest parameter
+            let (parameter, annotation) = &declared[index.min(declared.len() - 1)];
+            let Some(annotation) = annotation else {
+                continue;
+            };
+            if argument_satisfies(annotation, argument) {
+                continue;
+            }
+            if annotation.name == "Int" {
+                let context = format!("{}: {}", name, parameter.name);
+                if let Some(number) = self.implicit_int(argument, &context, position)? {
+                    converted.get_or_insert_with(|| arguments.to_vec())[index] =
+                        Object::Int(number);
+                    continue;
+                }
+            }
+            return Err(builtin_parameter_type_error(
+                name,
+                parameter.name,
+                annotation,
+                argument,
+                position,
+            ));
+        }
+        Ok(converted)
+    }
+}
+
+/// Whether `argument` satisfies `annotation`. The value's own type is tried
+/// first; the lineage of an instance's superclasses is only built when that
+/// does not match.
+fn argument_satisfies(annotation: &TypeAnnotation, argument: &Object) -> bool {
+    let own_type_matches = match argument {
+        Object::Instance(instance) => satisfies(annotation, &[instance.borrow().class.name()]),
+        Object::Exception(exception) => {
+            satisfies(annotation, &[exception.borrow().exception_type.as_str()])
+        }
+        other => return satisfies(annotation, &[other.type_name()]),
+    };
+    own_type_matches || {
+        let lineage = value_lineage(argument);
+        let lineage: Vec<&str> = lineage.iter().map(String::as_str).collect();
+        satisfies(annotation, &lineage)
+    }
+}
+
+/// FNV-1a, for the short names signatures are looked up by on every native
+/// call. The table is fixed once built, so the flooding resistance of the
+/// default hasher buys nothing here, while its cost showed in call-heavy loops.
+struct NameHasher(u64);
+
+impl Default for NameHasher {
+    fn default() -> Self {
+        NameHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for NameHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
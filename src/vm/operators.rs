@@ -1,21 +1,30 @@
 //! Operator evaluation functions for the Metorex VM.
 //!
 //! This module contains the logic for evaluating unary and binary operators including:
-//! - Unary operations (+, -)
-//! - Binary operations (+, -, *, /, %)
-//! - Comparison operations (<, >, <=, >=, ==, !=)
+//! - Unary operations (+, -, !)
+//! - Binary operations (+, -, *, /, %), exact when a Decimal is involved
+//! - Comparison operations (<, >, <=, >=, <=>, ==, !=)
+//! - Appending (<<) to Arrays and objects that define it
+//! - Logical operations (&&, ||), which pick one operand by its truthiness
+//! - Case equality (===) used by `case`/`when`, `rescue` and `Array#grep`
+//! - Value ordering used by sorting helpers
 
 use crate::ast::{BinaryOp, UnaryOp};
 use crate::error::MetorexError;
 use crate::lexer::Position;
-use crate::object::Object;
+use crate::object::{Decimal, Object};
+use std::cmp::Ordering;
 use std::rc::Rc;
 
-use super::core::VirtualMachine;
-use super::errors::{binary_type_error, divide_by_zero_error, unary_type_error};
+use super::core::{FloatDivisionPolicy, VirtualMachine};
+use super::errors::{
+    binary_type_error, collection_in_use_error, decimal_overflow_error, divide_by_zero_error,
+    unary_type_error,
+};
+use super::utils::is_truthy;
 
 impl VirtualMachine {
-    /// Evaluate a unary operation (`+` or `-`).
+    /// Evaluate a unary operation (`+`, `-` or `!`).
     pub(crate) fn evaluate_unary_operation(
         &self,
         op: &UnaryOp,
@@ -24,20 +33,22 @@ impl VirtualMachine {
     ) -> Result<Object, MetorexError> {
         match op {
             UnaryOp::Plus => match value {
-                Object::Int(_) | Object::Float(_) => Ok(value),
+                Object::Int(_) | Object::Float(_) | Object::Decimal(_) => Ok(value),
                 _ => Err(unary_type_error(op, &value, position)),
             },
             UnaryOp::Minus => match value {
                 Object::Int(v) => Ok(Object::Int(-v)),
                 Object::Float(v) => Ok(Object::Float(-v)),
+                Object::Decimal(v) => Ok(Object::Decimal(-v)),
                 _ => Err(unary_type_error(op, &value, position)),
             },
+            UnaryOp::Not => Ok(Object::Bool(!is_truthy(&value))),
         }
     }
 
     /// Evaluate a binary operation across runtime values.
     pub(crate) fn evaluate_binary_operation(
-        &self,
+        &mut self,
         op: &BinaryOp,
         left: Object,
         right: Object,
@@ -47,14 +58,26 @@ impl VirtualMachine {
 
         match op {
             Add => self.evaluate_addition(left, right, position),
+            // "%05.1f" % value formats like sprintf
+            Modulo if matches!(left, Object::String(_)) => {
+                let formatted = self.call_string_method(&left, "%", &[right], position)?;
+                Ok(formatted.unwrap_or(Object::Nil))
+            }
             Subtract | Multiply | Divide | Modulo => {
                 self.evaluate_numeric_binary(op, left, right, position)
             }
             Equal => Ok(Object::Bool(left.equals(&right))),
             NotEqual => Ok(Object::Bool(!left.equals(&right))),
+            CaseEqual => Ok(Object::Bool(self.case_equals(&left, &right, position)?)),
             Less | Greater | LessEqual | GreaterEqual => {
                 self.evaluate_comparison(op, left, right, position)
             }
+            Spaceship => self.evaluate_spaceship(left, right, position),
+            ShiftLeft => self.evaluate_shift_left(left, right, position),
+            // Expressions short-circuit before the right side is evaluated;
+            // this is the result once both sides are known
+            And => Ok(if is_truthy(&left) { right } else { left }),
+            Or => Ok(if is_truthy(&left) { left } else { right }),
             Assign | AddAssign | SubtractAssign | MultiplyAssign | DivideAssign => {
                 Err(MetorexError::internal_error(format!(
                     "Assignment operation '{:?}' should be handled by statement execution",
@@ -64,6 +87,54 @@ impl VirtualMachine {
         }
     }
 
+    /// Evaluate `left <=> right`: a `<=>` method defined on the left operand
+    /// decides, otherwise values with a natural ordering give -1, 0 or 1 and
+    /// anything else gives nil.
+    pub(crate) fn evaluate_spaceship(
+        &mut self,
+        left: Object,
+        right: Object,
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        if let Object::Instance(_) = left
+            && let Some((class, method)) = self.lookup_method(&left, "<=>")
+        {
+            return self.invoke_method(class, method, left, vec![right], position);
+        }
+        Ok(match self.compare_values(&left, &right, position) {
+            Ok(ordering) => Object::Int(ordering as i64),
+            Err(_) => Object::Nil,
+        })
+    }
+
+    /// Evaluate `left << right`: an Array pushes the value and objects that
+    /// define `<<`, such as a StringBuilder, decide for themselves.
+    pub(crate) fn evaluate_shift_left(
+        &mut self,
+        left: Object,
+        right: Object,
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        if let Object::Array(items) = &left {
+            items
+                .try_borrow_mut()
+                .map_err(|_| collection_in_use_error(&left, position))?
+                .push(right);
+            return Ok(left);
+        }
+        if let Object::Instance(_) = left
+            && let Some((class, method)) = self.lookup_method(&left, "<<")
+        {
+            return self.invoke_method(class, method, left, vec![right], position);
+        }
+        Err(binary_type_error(
+            BinaryOp::ShiftLeft,
+            &left,
+            &right,
+            position,
+        ))
+    }
+
     /// Handle addition across supported operand types.
     pub(crate) fn evaluate_addition(
         &self,
@@ -71,6 +142,9 @@ impl VirtualMachine {
         right: Object,
         position: Position,
     ) -> Result<Object, MetorexError> {
+        if let Some((a, b)) = decimal_operands(&left, &right) {
+            return evaluate_decimal_binary(&BinaryOp::Add, a, b, position);
+        }
         match (left, right) {
             (Object::Int(a), Object::Int(b)) => Ok(Object::Int(a + b)),
             (Object::Float(a), Object::Float(b)) => Ok(Object::Float(a + b)),
@@ -93,6 +167,9 @@ impl VirtualMachine {
         right: Object,
         position: Position,
     ) -> Result<Object, MetorexError> {
+        if let Some((a, b)) = decimal_operands(&left, &right) {
+            return evaluate_decimal_binary(op, a, b, position);
+        }
         match (left, right) {
             (Object::Int(a), Object::Int(b)) => match op {
                 BinaryOp::Subtract => Ok(Object::Int(a - b)),
@@ -115,67 +192,42 @@ impl VirtualMachine {
                 }
                 _ => unreachable!(),
             },
-            (Object::Float(a), Object::Float(b)) => match op {
-                BinaryOp::Subtract => Ok(Object::Float(a - b)),
-                BinaryOp::Multiply => Ok(Object::Float(a * b)),
-                BinaryOp::Divide => {
-                    if b == 0.0 {
-                        Err(divide_by_zero_error(position))
-                    } else {
-                        Ok(Object::Float(a / b))
-                    }
-                }
-                BinaryOp::Modulo => {
-                    if b == 0.0 {
-                        Err(divide_by_zero_error(position))
-                    } else {
-                        Ok(Object::Float(a % b))
-                    }
-                }
-                _ => unreachable!(),
-            },
-            (Object::Int(a), Object::Float(b)) => match op {
-                BinaryOp::Subtract => Ok(Object::Float((a as f64) - b)),
-                BinaryOp::Multiply => Ok(Object::Float((a as f64) * b)),
-                BinaryOp::Divide => {
-                    if b == 0.0 {
-                        Err(divide_by_zero_error(position))
-                    } else {
-                        Ok(Object::Float((a as f64) / b))
-                    }
-                }
-                BinaryOp::Modulo => {
-                    if b == 0.0 {
-                        Err(divide_by_zero_error(position))
-                    } else {
-                        Ok(Object::Float((a as f64) % b))
-                    }
-                }
-                _ => unreachable!(),
-            },
-            (Object::Float(a), Object::Int(b)) => match op {
-                BinaryOp::Subtract => Ok(Object::Float(a - (b as f64))),
-                BinaryOp::Multiply => Ok(Object::Float(a * (b as f64))),
-                BinaryOp::Divide => {
-                    if b == 0 {
-                        Err(divide_by_zero_error(position))
-                    } else {
-                        Ok(Object::Float(a / (b as f64)))
-                    }
-                }
-                BinaryOp::Modulo => {
-                    if b == 0 {
-                        Err(divide_by_zero_error(position))
-                    } else {
-                        Ok(Object::Float(a % (b as f64)))
-                    }
-                }
-                _ => unreachable!(),
-            },
+            (Object::Float(a), Object::Float(b)) => self.evaluate_float_binary(op, a, b, position),
+            (Object::Int(a), Object::Float(b)) => {
+                self.evaluate_float_binary(op, a as f64, b, position)
+            }
+            (Object::Float(a), Object::Int(b)) => {
+                self.evaluate_float_binary(op, a, b as f64, position)
+            }
             (lhs, rhs) => Err(binary_type_error(op.clone(), &lhs, &rhs, position)),
         }
     }
 
+    /// Evaluate `-`, `*`, `/` and `%` when either operand is a Float.
+    ///
+    /// Dividing by zero raises unless the VM follows IEEE semantics, in which
+    /// case it yields Infinity or NaN.
+    fn evaluate_float_binary(
+        &self,
+        op: &BinaryOp,
+        a: f64,
+        b: f64,
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        let divides = matches!(op, BinaryOp::Divide | BinaryOp::Modulo);
+        if divides && b == 0.0 && self.float_division_policy() == FloatDivisionPolicy::Raise {
+            return Err(divide_by_zero_error(position));
+        }
+
+        match op {
+            BinaryOp::Subtract => Ok(Object::Float(a - b)),
+            BinaryOp::Multiply => Ok(Object::Float(a * b)),
+            BinaryOp::Divide => Ok(Object::Float(a / b)),
+            BinaryOp::Modulo => Ok(Object::Float(a % b)),
+            _ => unreachable!(),
+        }
+    }
+
     /// Evaluate comparison operations on numeric operands.
     pub(crate) fn evaluate_comparison(
         &self,
@@ -184,6 +236,16 @@ impl VirtualMachine {
         right: Object,
         position: Position,
     ) -> Result<Object, MetorexError> {
+        if let Some((a, b)) = decimal_operands(&left, &right) {
+            let ordering = a.cmp(&b);
+            return Ok(Object::Bool(match op {
+                BinaryOp::Less => ordering.is_lt(),
+                BinaryOp::Greater => ordering.is_gt(),
+                BinaryOp::LessEqual => ordering.is_le(),
+                BinaryOp::GreaterEqual => ordering.is_ge(),
+                _ => unreachable!(),
+            }));
+        }
         let (lhs, rhs) = match (&left, &right) {
             (Object::Int(a), Object::Int(b)) => (*a as f64, *b as f64),
             (Object::Float(a), Object::Float(b)) => (*a, *b),
@@ -204,4 +266,132 @@ impl VirtualMachine {
 
         Ok(Object::Bool(result))
     }
+
+    /// Evaluate `pattern === value`, the test shared by `case`/`when` type
+    /// patterns, `rescue` clauses and `Array#grep`.
+    ///
+    /// Classes match their instances, modules match instances of classes
+    /// that include them, ranges match values they contain, blocks and
+    /// functions are called with the value and match when the result is
+    /// truthy, and any other pattern matches values equal to it.
+    pub(crate) fn case_equals(
+        &mut self,
+        pattern: &Object,
+        value: &Object,
+        position: Position,
+    ) -> Result<bool, MetorexError> {
+        let matched = match pattern {
+            Object::Class(class) => match value {
+                Object::Exception(exception) => {
+                    let type_name = exception.borrow().exception_type.clone();
+                    let exception_class = self
+                        .qualified_constant(&type_name)
+                        .or_else(|| self.builtins().scoped_class(&type_name).map(Object::Class));
+                    match exception_class {
+                        Some(Object::Class(exception_class)) => {
+                            Self::is_class_or_subclass(&exception_class, class)
+                        }
+                        _ => self.builtins().is_instance_of(value, class),
+                    }
+                }
+                _ => self.builtins().is_instance_of(value, class),
+            },
+            Object::Module(module) => match value {
+                Object::Instance(instance) => instance.borrow().class.includes_module(module),
+                _ => false,
+            },
+            Object::Range {
+                start,
+                end,
+                exclusive,
+            } => {
+                let above_start = self
+                    .compare_values(start, value, position)
+                    .is_ok_and(|ordering| ordering != Ordering::Greater);
+                let below_end = self
+                    .compare_values(value, end, position)
+                    .is_ok_and(|ordering| {
+                        ordering == Ordering::Less || (!exclusive && ordering == Ordering::Equal)
+                    });
+                above_start && below_end
+            }
+            Object::Block(_) | Object::Method(_) => {
+                let result =
+                    self.invoke_callable(pattern.clone(), vec![value.clone()], position)?;
+                is_truthy(&result)
+            }
+            _ => pattern.equals(value),
+        };
+        Ok(matched)
+    }
+
+    /// Order two values for sorting and min/max selection.
+    ///
+    /// Numbers compare numerically (Int mixes with Float and with Decimal),
+    /// strings compare lexicographically and arrays compare element by element.
+    pub(crate) fn compare_values(
+        &self,
+        left: &Object,
+        right: &Object,
+        position: Position,
+    ) -> Result<Ordering, MetorexError> {
+        if let Some((a, b)) = decimal_operands(left, right) {
+            return Ok(a.cmp(&b));
+        }
+        let ordering = match (left, right) {
+            (Object::Int(a), Object::Int(b)) => Some(a.cmp(b)),
+            (Object::Float(a), Object::Float(b)) => a.partial_cmp(b),
+            (Object::Int(a), Object::Float(b)) => (*a as f64).partial_cmp(b),
+            (Object::Float(a), Object::Int(b)) => a.partial_cmp(&(*b as f64)),
+            (Object::String(a), Object::String(b)) => Some(a.cmp(b)),
+            (Object::Array(a), Object::Array(b)) => {
+                let (a, b) = (a.borrow(), b.borrow());
+                for (x, y) in a.iter().zip(b.iter()) {
+                    let ordering = self.compare_values(x, y, position)?;
+                    if ordering != Ordering::Equal {
+                        return Ok(ordering);
+                    }
+                }
+                Some(a.len().cmp(&b.len()))
+            }
+            _ => None,
+        };
+
+        ordering.ok_or_else(|| binary_type_error(BinaryOp::Less, left, right, position))
+    }
+}
+
+/// Both operands as Decimals when one is a Decimal and the other a Decimal or
+/// an Int. Floats don't mix with Decimals: their digits are already inexact.
+fn decimal_operands(left: &Object, right: &Object) -> Option<(Decimal, Decimal)> {
+    match (left, right) {
+        (Object::Decimal(a), Object::Decimal(b)) => Some((*a, *b)),
+        (Object::Decimal(a), Object::Int(b)) => Some((*a, Decimal::from(*b))),
+        (Object::Int(a), Object::Decimal(b)) => Some((Decimal::from(*a), *b)),
+        _ => None,
+    }
+}
+
+/// Evaluate `+`, `-`, `*`, `/` and `%` exactly. Quotients that don't
+/// terminate are cut to the 28 significant digits a Decimal holds.
+fn evaluate_decimal_binary(
+    op: &BinaryOp,
+    a: Decimal,
+    b: Decimal,
+    position: Position,
+) -> Result<Object, MetorexError> {
+    if matches!(op, BinaryOp::Divide | BinaryOp::Modulo) && b.is_zero() {
+        return Err(divide_by_zero_error(position));
+    }
+    let result = match op {
+        BinaryOp::Add => a.checked_add(b),
+        BinaryOp::Subtract => a.checked_sub(b),
+        BinaryOp::Multiply => a.checked_mul(b),
+        BinaryOp::Divide => a.checked_div(b),
+        BinaryOp::Modulo => a.checked_rem(b),
+        _ => unreachable!(),
+    };
+    result
+        .map(Object::Decimal)
+        .ok_or_else(|| decimal_overflow_error(position))
 }
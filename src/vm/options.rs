@@ -0,0 +1,177 @@
+//! Tunable settings for the virtual machine.
+//!
+//! Options are written as `key=value` pairs, either in the `MX_OPTS`
+//! environment variable (separated by spaces or commas) or one per
+//! `--vm-opt` flag on the command line, so deployments can adjust the
+//! interpreter without changing code.
+
+use std::env;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Environment variable read by [`VmOptions::from_env`].
+pub const MX_OPTS_VAR: &str = "MX_OPTS";
+
+/// Default limit on nested method, function and block calls.
+pub const DEFAULT_MAX_STACK_DEPTH: usize = 10_000;
+
+/// Highest accepted optimization level.
+pub const MAX_OPTIMIZATION_LEVEL: u8 = 3;
+
+/// Settings applied when a virtual machine is created.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VmOptions {
+    /// Calls nested deeper than this raise "stack level too deep" (`stack_depth`).
+    pub max_stack_depth: usize,
+    /// Optimization level from 0 to 3 (`opt_level`). Level 1 and above intern
+    /// string literals; level 2 and above specialize hot Int arithmetic.
+    pub optimization_level: u8,
+    /// Report static analysis warnings, such as unknown string escapes, unused
+    /// variables and cases that miss variants of a sealed class, before
+    /// running a script (`warnings`).
+    pub warnings: bool,
+    /// Seed for `rand`, making random numbers repeatable (`seed`).
+    pub seed: Option<u64>,
+    /// Reuse parsed ASTs of required files from the on-disk cache (`ast_cache`).
+    pub ast_cache: bool,
+    /// Validate arguments and results of methods with type annotations at
+    /// call time, raising `TypeError` on a mismatch (`check_types`).
+    pub check_types: bool,
+    /// Check `assert` calls and method pre- and postconditions
+    /// (`contracts`). Turn off for production runs that should skip them.
+    pub contracts: bool,
+    /// Track loaded files by case-insensitive path, so a file required as
+    /// `Lib.mx` and `lib.mx` runs once, as on Windows (`case_insensitive_paths`).
+    pub case_insensitive_paths: bool,
+    /// Directories `require` searches for libraries, in order (`load_path`,
+    /// written like `PATH`; the command line adds to it with `-I`).
+    pub load_path: Vec<PathBuf>,
+}
+
+impl Default for VmOptions {
+    fn default() -> Self {
+        Self {
+            max_stack_depth: DEFAULT_MAX_STACK_DEPTH,
+            optimization_level: 0,
+            warnings: false,
+            seed: None,
+            ast_cache: false,
+            check_types: false,
+            contracts: true,
+            case_insensitive_paths: false,
+            load_path: Vec::new(),
+        }
+    }
+}
+
+impl VmOptions {
+    /// Default options updated with the settings in `MX_OPTS`, if it is set.
+    pub fn from_env() -> Result<Self, String> {
+        let mut options = Self::default();
+        if let Some(opts) = env::var_os(MX_OPTS_VAR) {
+            let opts = opts
+                .into_string()
+                .map_err(|_| format!("{} is not valid UTF-8", MX_OPTS_VAR))?;
+            options
+                .apply_all(&opts)
+                .map_err(|err| format!("{}: {}", MX_OPTS_VAR, err))?;
+        }
+        Ok(options)
+    }
+
+    /// Apply every `key=value` pair in a space or comma separated list.
+    pub fn apply_all(&mut self, opts: &str) -> Result<(), String> {
+        opts.split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|pair| !pair.is_empty())
+            .try_for_each(|pair| self.apply(pair))
+    }
+
+    /// Apply a single `key=value` pair.
+    pub fn apply(&mut self, pair: &str) -> Result<(), String> {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("invalid VM option '{}' (expected key=value)", pair))?;
+        self.set(key.trim(), value.trim())
+    }
+
+    /// Set the option named `key` from its textual value.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "stack_depth" => {
+                self.max_stack_depth = match value.parse() {
+                    Ok(depth) if depth > 0 => depth,
+                    _ => return Err(invalid_value(key, value, "a positive integer")),
+                };
+            }
+            "opt_level" => {
+                self.optimization_level = match value.parse() {
+                    Ok(level) if level <= MAX_OPTIMIZATION_LEVEL => level,
+                    _ => return Err(invalid_value(key, value, "a level from 0 to 3")),
+                };
+            }
+            "warnings" => self.warnings = parse_flag(key, value)?,
+            "seed" => {
+                self.seed = match value {
+                    "none" => None,
+                    _ => Some(
+                        value
+                            .parse()
+                            .map_err(|_| invalid_value(key, value, "an unsigned integer"))?,
+                    ),
+                };
+            }
+            "ast_cache" => self.ast_cache = parse_flag(key, value)?,
+            "check_types" => self.check_types = parse_flag(key, value)?,
+            "contracts" => self.contracts = parse_flag(key, value)?,
+            "case_insensitive_paths" => self.case_insensitive_paths = parse_flag(key, value)?,
+            "load_path" => {
+                self.load_path = env::split_paths(value)
+                    .filter(|dir| !dir.as_os_str().is_empty())
+                    .collect();
+            }
+            _ => {
+                return Err(format!(
+                    "unknown VM option '{}' (expected stack_depth, opt_level, warnings, seed, ast_cache, check_types, contracts, case_insensitive_paths or load_path)",
+                    key
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for VmOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "stack_depth={} opt_level={} warnings={} seed={} ast_cache={} check_types={} contracts={} case_insensitive_paths={} load_path={}",
+            self.max_stack_depth,
+            self.optimization_level,
+            self.warnings,
+            self.seed
+                .map_or_else(|| "none".to_string(), |seed| seed.to_string()),
+            self.ast_cache,
+            self.check_types,
+            self.contracts,
+            self.case_insensitive_paths,
+            env::join_paths(&self.load_path)
+                .map(|paths| paths.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        )
+    }
+}
+
+fn parse_flag(key: &str, value: &str) -> Result<bool, String> {
+    match value {
+        "true" | "on" | "1" => Ok(true),
+        "false" | "off" | "0" => Ok(false),
+        _ => Err(invalid_value(key, value, "true or false")),
+    }
+}
+
+fn invalid_value(key: &str, value: &str, expected: &str) -> String {
+    format!(
+        "invalid value '{}' for VM option '{}' (expected {})",
+        value, key, expected
+    )
+}
@@ -0,0 +1,42 @@
+//! Standard output for scripts.
+//!
+//! `puts`, `pp` and the prompts of `gets` write to the process's stdout
+//! unless an embedder or test supplies another writer, so what a program
+//! prints can be captured and compared without spawning a process.
+
+use super::VirtualMachine;
+use super::event_log::LoggedEvent;
+use std::io::{self, Write};
+
+impl VirtualMachine {
+    /// Write script output to `writer` instead of the process's stdout.
+    pub fn set_output<W: Write + 'static>(&mut self, writer: W) {
+        self.output = Some(Box::new(writer));
+    }
+
+    /// Go back to writing script output to the process's stdout.
+    pub fn reset_output(&mut self) {
+        self.output = None;
+    }
+
+    /// Write `text` to the script's output, logging it when recording.
+    pub(crate) fn write_output(&mut self, text: &str) {
+        match self.output.as_mut() {
+            Some(writer) => {
+                let _ = writer.write_all(text.as_bytes());
+            }
+            None => print!("{}", text),
+        }
+        self.log_event(LoggedEvent::Output {
+            text: text.to_string(),
+        });
+    }
+
+    /// Flush pending script output, so a prompt shows before input is read.
+    pub(crate) fn flush_output(&mut self) {
+        let _ = match self.output.as_mut() {
+            Some(writer) => writer.flush(),
+            None => io::stdout().flush(),
+        };
+    }
+}
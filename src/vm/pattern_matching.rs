@@ -3,13 +3,15 @@
 
 use super::ControlFlow;
 use super::core::VirtualMachine;
+use super::errors::no_pattern_matched_error;
 use super::utils::*;
 
+use crate::ast::Expression;
 use crate::ast::node::ExprMatchCase;
-use crate::ast::{Expression, Statement};
 use crate::error::MetorexError;
 use crate::lexer::Position;
 use crate::object::Object;
+use indexmap::IndexMap;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
@@ -22,78 +24,75 @@ impl VirtualMachine {
         cases: &[crate::ast::MatchCase],
         position: Position,
     ) -> Result<ControlFlow, MetorexError> {
+        self.run_match(expression, cases, position)
+            .map(|(flow, _)| flow)
+    }
+
+    /// Run a match statement, returning its control flow together with the
+    /// value of the last statement of the case that ran.
+    pub(crate) fn run_match(
+        &mut self,
+        expression: &Expression,
+        cases: &[crate::ast::MatchCase],
+        position: Position,
+    ) -> Result<(ControlFlow, Object), MetorexError> {
         // Evaluate the value to match against
         let match_value = self.evaluate_expression(expression)?;
 
-        // Try each case in order
+        // Try each case in order; the first whose pattern and guard match
+        // runs with the pattern's bindings in scope
         for case in cases {
-            // Try to match the pattern
-            let mut bindings: HashMap<String, Object> = HashMap::new();
-            if self.match_pattern(&case.pattern, &match_value, &mut bindings, position)? {
-                // Pattern matched! Now check guard if present
-                if let Some(guard_expr) = &case.guard {
-                    // Push a new scope for guard evaluation with bindings
-                    self.environment_mut().push_scope();
-                    for (name, value) in &bindings {
-                        self.environment_mut().define(name.clone(), value.clone());
-                    }
-
-                    let guard_result = self.evaluate_expression(guard_expr)?;
-                    self.environment_mut().pop_scope();
-
-                    // If guard evaluates to false, skip this case
-                    if !is_truthy(&guard_result) {
-                        continue;
-                    }
-                }
-
-                // Pattern and guard matched! Execute the body with bindings
-                self.environment_mut().push_scope();
-                for (name, value) in bindings {
-                    self.environment_mut().define(name, value);
-                }
+            if let Some(bindings) =
+                self.match_case(&case.pattern, case.guard.as_ref(), &match_value, position)?
+            {
+                return self
+                    .with_bindings(&bindings, |vm| vm.execute_statements_for_value(&case.body));
+            }
+        }
 
-                // Execute the body and track the last expression value
-                let mut last_value = Object::Nil;
-                for statement in &case.body {
-                    // If it's an expression statement, track its value
-                    if let Statement::Expression { expression, .. } = statement {
-                        last_value = self.evaluate_expression(expression)?;
-                        continue;
-                    }
+        Err(no_pattern_matched_error(&match_value, position))
+    }
 
-                    // Execute other statements
-                    match self.execute_statement(statement)? {
-                        ControlFlow::Next => {}
-                        flow => {
-                            self.environment_mut().pop_scope();
-                            return Ok(flow);
-                        }
-                    }
-                }
+    /// The bindings of `pattern` when it matches `value` and `guard`, if
+    /// any, is truthy with those bindings in scope; `None` otherwise.
+    fn match_case(
+        &mut self,
+        pattern: &crate::ast::MatchPattern,
+        guard: Option<&Expression>,
+        value: &Object,
+        position: Position,
+    ) -> Result<Option<HashMap<String, Object>>, MetorexError> {
+        let mut bindings = HashMap::new();
+        if !self.match_pattern(pattern, value, &mut bindings, position)? {
+            return Ok(None);
+        }
+        let Some(guard) = guard else {
+            return Ok(Some(bindings));
+        };
 
-                self.environment_mut().pop_scope();
+        let guard_result = self.with_bindings(&bindings, |vm| vm.evaluate_expression(guard))?;
+        Ok(is_truthy(&guard_result).then_some(bindings))
+    }
 
-                // Return the last expression value using Return control flow
-                // (but this is not a true return statement, just a value)
-                return Ok(ControlFlow::Return {
-                    value: last_value,
-                    position,
-                });
-            }
+    /// Run `f` in a new scope holding `bindings`
+    fn with_bindings<T>(
+        &mut self,
+        bindings: &HashMap<String, Object>,
+        f: impl FnOnce(&mut Self) -> Result<T, MetorexError>,
+    ) -> Result<T, MetorexError> {
+        self.environment_mut().push_scope();
+        for (name, value) in bindings {
+            self.environment_mut().define(name.clone(), value.clone());
         }
-
-        // No pattern matched
-        Err(MetorexError::runtime_error(
-            format!("No pattern matched value: {}", match_value),
-            position_to_location(position),
-        ))
+        let result = f(self);
+        self.environment_mut().pop_scope();
+        result
     }
 
     /// Match a pattern against a value and collect variable bindings.
     /// Returns true if the pattern matches, false otherwise.
     pub(crate) fn match_pattern(
-        &self,
+        &mut self,
         pattern: &crate::ast::MatchPattern,
         value: &Object,
         bindings: &mut HashMap<String, Object>,
@@ -136,7 +135,8 @@ impl VirtualMachine {
             // Array pattern - destructure arrays
             MatchPattern::Array(patterns) => match value {
                 Object::Array(array_rc) => {
-                    let array = array_rc.borrow();
+                    // Patterns may run code, so match against a snapshot
+                    let array = array_rc.borrow().clone();
                     self.match_array_pattern(patterns, &array, bindings, position)
                 }
                 _ => Ok(false),
@@ -151,44 +151,36 @@ impl VirtualMachine {
             // Object pattern - destructure dictionaries
             MatchPattern::Object(key_patterns) => match value {
                 Object::Dict(dict_rc) => {
-                    let dict = dict_rc.borrow();
+                    let dict = dict_rc.borrow().clone();
                     self.match_object_pattern(key_patterns, &dict, bindings, position)
                 }
                 _ => Ok(false),
             },
 
-            // Type pattern - match based on object type
+            // Glob pattern - shell-style match against a string
+            MatchPattern::Glob(glob) => match value {
+                Object::String(text) => Ok(crate::glob::fnmatch(glob, text)),
+                _ => Ok(false),
+            },
+
+            // Type pattern - a constant tested with `===`, so it may name a
+            // class, a range or a lambda bound in scope
             MatchPattern::Type(type_name) => {
-                let actual_type = value.type_name();
+                if let Some(pattern) = self.environment().get(type_name) {
+                    return self.case_equals(&pattern, value, position);
+                }
 
-                // Support both Ruby-style names (Integer, Hash) and internal names (Int, Dict)
+                // Names with no binding: internal type names and aliases
                 let matches = match type_name.as_str() {
-                    // Direct type name matches
-                    name if name == actual_type => true,
-
-                    // Ruby-style aliases
-                    "Integer" => matches!(value, Object::Int(_)),
-                    "Float" => matches!(value, Object::Float(_)),
-                    "String" => matches!(value, Object::String(_)),
-                    "Array" => matches!(value, Object::Array(_)),
-                    "Hash" | "Dict" => matches!(value, Object::Dict(_)),
+                    name if name == value.type_name() => true,
+                    "Dict" => matches!(value, Object::Dict(_)),
                     "TrueClass" | "FalseClass" | "Boolean" => matches!(value, Object::Bool(_)),
                     "NilClass" => matches!(value, Object::Nil),
                     "Class" => matches!(value, Object::Class(_)),
+                    "Module" => matches!(value, Object::Module(_)),
                     "Method" => matches!(value, Object::Method(_)),
-                    "Exception" => matches!(value, Object::Exception(_)),
-                    "Set" => matches!(value, Object::Set(_)),
                     "Range" => matches!(value, Object::Range { .. }),
-
-                    // Check for class instances
-                    _ => {
-                        if let Object::Instance(instance_rc) = value {
-                            let instance = instance_rc.borrow();
-                            instance.class_name() == *type_name
-                        } else {
-                            false
-                        }
-                    }
+                    _ => false,
                 };
 
                 Ok(matches)
@@ -198,7 +190,7 @@ impl VirtualMachine {
 
     /// Match an array pattern against an array value.
     pub(crate) fn match_array_pattern(
-        &self,
+        &mut self,
         patterns: &[crate::ast::MatchPattern],
         array: &[Object],
         bindings: &mut HashMap<String, Object>,
@@ -276,9 +268,9 @@ impl VirtualMachine {
 
     /// Match an object/dictionary pattern against a dictionary value.
     pub(crate) fn match_object_pattern(
-        &self,
+        &mut self,
         key_patterns: &[(String, crate::ast::MatchPattern)],
-        dict: &HashMap<String, Object>,
+        dict: &IndexMap<String, Object>,
         bindings: &mut HashMap<String, Object>,
         position: Position,
     ) -> Result<bool, MetorexError> {
@@ -297,19 +289,29 @@ impl VirtualMachine {
         Ok(true)
     }
 
-    /// Evaluate a case expression (pattern matching in expression context).
-    /// This is a placeholder stub that will be fully implemented in Phase 3.
+    /// Evaluate a case expression (pattern matching in expression context)
+    /// to the body of the first case whose pattern and guard match, or to
+    /// the else arm when none does.
     pub(crate) fn evaluate_case_expression(
         &mut self,
-        _expression: &Expression,
-        _cases: &[ExprMatchCase],
-        _else_case: Option<&Expression>,
+        expression: &Expression,
+        cases: &[ExprMatchCase],
+        else_case: Option<&Expression>,
         position: Position,
     ) -> Result<Object, MetorexError> {
-        // Placeholder implementation - will be completed in Phase 3
-        Err(MetorexError::runtime_error(
-            "Case expressions are not yet implemented (Phase 3)".to_string(),
-            position_to_location(position),
-        ))
+        let match_value = self.evaluate_expression(expression)?;
+
+        for case in cases {
+            if let Some(bindings) =
+                self.match_case(&case.pattern, case.guard.as_ref(), &match_value, position)?
+            {
+                return self.with_bindings(&bindings, |vm| vm.evaluate_expression(&case.body));
+            }
+        }
+
+        match else_case {
+            Some(body) => self.evaluate_expression(body),
+            None => Err(no_pattern_matched_error(&match_value, position)),
+        }
     }
 }
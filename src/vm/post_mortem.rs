@@ -0,0 +1,192 @@
+//! Post-mortem debugging of uncaught errors.
+//!
+//! While post-mortem recording is on, every method, function and block body
+//! an error unwinds through records its scope before the scope is popped.
+//! When the error reaches the top level, [`VirtualMachine::take_post_mortem`]
+//! hands back those frames, innermost first, so a REPL can be opened in the
+//! frame that raised the error and moved up the backtrace from there.
+//!
+//! Frames are recorded per error: an error that is rescued and replaced by
+//! another starts a fresh backtrace, so only the error that escaped is
+//! described.
+
+use super::VirtualMachine;
+use super::utils::{format_exception, position_to_location};
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+use crate::scope::Scope;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Name of the outermost frame, the program's top level.
+pub const MAIN_FRAME: &str = "<main>";
+
+/// One frame of a failed program, with the scope its code was running in.
+#[derive(Debug, Clone)]
+pub struct PostMortemFrame {
+    name: String,
+    location: Option<String>,
+    scope: Rc<RefCell<Scope>>,
+}
+
+impl PostMortemFrame {
+    /// The frame's method, function or block name, e.g. `Point#move`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Where the frame was called from.
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+
+    /// The scope holding the frame's local variables.
+    pub fn scope(&self) -> Rc<RefCell<Scope>> {
+        Rc::clone(&self.scope)
+    }
+}
+
+/// An uncaught error with the frames it unwound through.
+#[derive(Debug, Clone)]
+pub struct PostMortem {
+    /// The exception that escaped; runtime errors are wrapped in a RuntimeError.
+    pub exception: Object,
+    /// The frames, innermost first, ending with the top level.
+    pub frames: Vec<PostMortemFrame>,
+}
+
+/// What tells one error apart from another while it unwinds.
+#[derive(Debug, Clone, PartialEq)]
+enum ErrorKey {
+    /// A raised exception, by the identity of its object.
+    Exception(usize),
+    /// Any other error, by its rendering without the stack trace.
+    Message(String),
+}
+
+impl ErrorKey {
+    fn of(error: &MetorexError) -> Self {
+        match error {
+            MetorexError::UncaughtException {
+                exception: Object::Exception(exception),
+                ..
+            } => ErrorKey::Exception(Rc::as_ptr(exception) as *const () as usize),
+            MetorexError::RuntimeError {
+                message, location, ..
+            } => ErrorKey::Message(format!("{}: {}", location, message)),
+            other => ErrorKey::Message(other.to_string()),
+        }
+    }
+}
+
+/// Frames recorded for the error currently unwinding.
+#[derive(Debug, Default)]
+pub(super) struct Unwinding {
+    error: Option<ErrorKey>,
+    /// The exception behind an error raised at the top level, whose error
+    /// only carries its message
+    exception: Option<Object>,
+    frames: Vec<PostMortemFrame>,
+}
+
+impl VirtualMachine {
+    /// Start or stop recording the frames uncaught errors unwind through.
+    pub fn set_post_mortem(&mut self, enabled: bool) {
+        self.unwinding = enabled.then(Unwinding::default);
+    }
+
+    /// Whether uncaught errors record the frames they unwind through.
+    pub fn post_mortem_enabled(&self) -> bool {
+        self.unwinding.is_some()
+    }
+
+    /// Record the current scope as frame `name` when `result` is an error
+    /// leaving it. Called by method, function and block bodies before their
+    /// scope is popped.
+    pub(super) fn record_unwind<T>(
+        &mut self,
+        result: &Result<T, MetorexError>,
+        name: impl FnOnce() -> String,
+    ) {
+        let (Some(unwinding), Err(error)) = (&self.unwinding, result) else {
+            return;
+        };
+        let key = ErrorKey::of(error);
+        let same_error = unwinding.error.as_ref() == Some(&key);
+        let name = name();
+        // Functions and some blocks run without a call frame of their own
+        let location = self
+            .call_stack()
+            .last()
+            .filter(|frame| frame.name() == name)
+            .and_then(|frame| frame.location())
+            .map(str::to_string);
+        let frame = PostMortemFrame {
+            name,
+            location,
+            scope: self.environment().current_scope(),
+        };
+
+        let Some(unwinding) = &mut self.unwinding else {
+            return;
+        };
+        if !same_error {
+            unwinding.error = Some(key);
+            unwinding.exception = None;
+            unwinding.frames.clear();
+        }
+        unwinding.frames.push(frame);
+    }
+
+    /// The error for an exception raised at the top level of a program,
+    /// remembering the exception for post-mortem debugging.
+    pub(super) fn uncaught_at_top_level(
+        &mut self,
+        exception: Object,
+        position: Position,
+    ) -> MetorexError {
+        let error = MetorexError::runtime_error(
+            format!("Uncaught exception: {}", format_exception(&exception)),
+            position_to_location(position),
+        );
+        if let Some(unwinding) = &mut self.unwinding {
+            unwinding.error = Some(ErrorKey::of(&error));
+            unwinding.exception = Some(exception);
+            unwinding.frames.clear();
+        }
+        error
+    }
+
+    /// The frames `error` unwound through, ending with the top level, or
+    /// `None` when post-mortem recording is off. Recording starts over for
+    /// the next error.
+    pub fn take_post_mortem(&mut self, error: &MetorexError) -> Option<PostMortem> {
+        let unwinding = self.unwinding.replace(Unwinding::default())?;
+        let (mut frames, recorded_exception) = if unwinding.error == Some(ErrorKey::of(error)) {
+            (unwinding.frames, unwinding.exception)
+        } else {
+            (Vec::new(), None)
+        };
+        frames.push(PostMortemFrame {
+            name: MAIN_FRAME.to_string(),
+            location: None,
+            scope: self.environment().global_scope(),
+        });
+
+        let exception = recorded_exception.unwrap_or_else(|| match error {
+            MetorexError::UncaughtException { exception, .. } => exception.clone(),
+            MetorexError::RuntimeError { message, .. } => {
+                Object::exception("RuntimeError", message.clone())
+            }
+            other => Object::exception("RuntimeError", other.to_string()),
+        });
+        Some(PostMortem { exception, frames })
+    }
+
+    /// Evaluate later code in `frame`'s scope, with the global scope and the
+    /// scopes around the frame still visible.
+    pub fn enter_frame(&mut self, frame: &PostMortemFrame) {
+        self.environment_mut().enter_scope(frame.scope());
+    }
+}
@@ -0,0 +1,43 @@
+//! Pseudo-random numbers for `rand`.
+//!
+//! A SplitMix64 generator: small, fast and fully determined by its seed, so a
+//! fixed `seed` option makes every run produce the same sequence.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Seeded pseudo-random number generator owned by the VM.
+#[derive(Debug, Clone)]
+pub(crate) struct Random {
+    state: u64,
+}
+
+impl Random {
+    /// Create a generator from `seed`, or from the clock when no seed is given.
+    pub(crate) fn new(seed: Option<u64>) -> Self {
+        let state = seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_nanos() as u64)
+        });
+        Self { state }
+    }
+
+    /// Next 64 random bits.
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A Float in `[0, 1)`.
+    pub(crate) fn next_float(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// An Integer in `[0, bound)`; `bound` must be positive.
+    pub(crate) fn next_below(&mut self, bound: i64) -> i64 {
+        (self.next_u64() % bound as u64) as i64
+    }
+}
@@ -0,0 +1,71 @@
+//! Reloading files into a running virtual machine.
+//!
+//! `reload!` re-reads a file and runs it again even though it was already
+//! required. While a reload is running, class definitions reopen the class
+//! that is already bound under the same name instead of replacing it, so
+//! objects created before the reload pick up the new method bodies.
+
+use super::VirtualMachine;
+use crate::class::Class;
+use crate::error::{MetorexError, SourceLocation};
+use crate::file_loader::{canonical_file_path, find_file_path};
+use crate::object::Object;
+use std::path::Path;
+use std::rc::Rc;
+
+impl VirtualMachine {
+    /// Re-execute a file, redefining the classes and methods it declares in place.
+    ///
+    /// Methods that the new version of the file no longer defines are kept, and
+    /// a class whose superclass changed is replaced by a fresh class. Files that
+    /// were never loaded are simply loaded.
+    pub fn reload_file(&mut self, path: &Path) -> Result<Object, MetorexError> {
+        let actual_path = find_file_path(path).map_err(|e| {
+            MetorexError::runtime_error(
+                format!("Failed to find file '{}': {}", path.display(), e),
+                SourceLocation::new(0, 0, 0),
+            )
+        })?;
+        if let Ok(canonical_path) = canonical_file_path(&actual_path) {
+            let key = self.loaded_file_key(&canonical_path);
+            self.loaded_files.remove(&key);
+        }
+
+        self.reload_depth += 1;
+        let result = self.execute_file(&actual_path);
+        self.reload_depth -= 1;
+        result
+    }
+
+    /// Whether a `reload!` is currently executing.
+    pub fn is_reloading(&self) -> bool {
+        self.reload_depth > 0
+    }
+
+    /// The existing class a reloaded `class` statement should reopen, if any.
+    ///
+    /// Only user-defined classes whose superclass is unchanged are reopened.
+    pub(super) fn class_to_reopen(
+        &self,
+        name: &str,
+        superclass: Option<&Rc<Class>>,
+    ) -> Option<Rc<Class>> {
+        if !self.is_reloading() {
+            return None;
+        }
+
+        let Object::Class(existing) = self.defined_constant(name)? else {
+            return None;
+        };
+        if self.nesting.is_empty() && self.builtins().all_classes().contains_key(name) {
+            return None;
+        }
+
+        let same_superclass = match (existing.superclass(), superclass) {
+            (None, None) => true,
+            (Some(old), Some(new)) => Rc::ptr_eq(&old, new),
+            _ => false,
+        };
+        same_superclass.then_some(existing)
+    }
+}
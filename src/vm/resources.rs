@@ -0,0 +1,60 @@
+//! Scoped resource handling for the virtual machine.
+//!
+//! Block forms that hand a resource to a block and must release it afterwards
+//! (the `with` builtin, and `open`-style constructors taking a block) share this
+//! implementation so the cleanup runs even when the block raises.
+
+use super::VirtualMachine;
+use super::errors::*;
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+
+/// Methods tried, in order, to release a resource once its block finishes.
+const RELEASE_METHODS: [&str; 2] = ["close", "release"];
+
+impl VirtualMachine {
+    /// Yield `resource` to `block` and release it afterwards, as if by an ensure.
+    ///
+    /// An error raised by the block takes precedence over one raised while
+    /// releasing the resource.
+    pub(crate) fn with_resource(
+        &mut self,
+        resource: Object,
+        block: Object,
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        let Some(release_method) = self.find_release_method(&resource) else {
+            return Err(unreleasable_resource_error(&resource, position));
+        };
+
+        let result = self.invoke_callable(block, vec![resource.clone()], position);
+        let released = self.release_resource(&resource, release_method, position);
+
+        let value = result?;
+        released?;
+        Ok(value)
+    }
+
+    /// Find the first release method the resource responds to.
+    fn find_release_method(&self, resource: &Object) -> Option<&'static str> {
+        RELEASE_METHODS
+            .into_iter()
+            .find(|name| self.lookup_method(resource, name).is_some())
+    }
+
+    /// Call the resource's release method.
+    fn release_resource(
+        &mut self,
+        resource: &Object,
+        method_name: &str,
+        position: Position,
+    ) -> Result<(), MetorexError> {
+        match self.lookup_method(resource, method_name) {
+            Some((class, method)) => self
+                .invoke_method(class, method, resource.clone(), vec![], position)
+                .map(|_| ()),
+            None => Err(unreleasable_resource_error(resource, position)),
+        }
+    }
+}
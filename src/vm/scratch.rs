@@ -0,0 +1,47 @@
+//! Reusable buffers for expression evaluation.
+//!
+//! Every call evaluates its arguments into a fresh list. Native methods only
+//! borrow that list and method bodies move the values out of it, so once a
+//! call is done the empty list is handed back here and the next call reuses
+//! its allocation instead of asking the allocator again.
+//!
+//! `benches/argument_buffers.rs` compares the pool with allocating each list.
+
+use crate::object::Object;
+
+/// Most argument lists kept for reuse; deeper call chains allocate.
+const MAX_POOLED_BUFFERS: usize = 32;
+
+/// Lists that grew beyond this many slots are dropped rather than kept, so
+/// one large splat does not pin its memory for the rest of the run.
+const MAX_POOLED_CAPACITY: usize = 16;
+
+/// Pool of empty argument lists.
+#[derive(Debug, Default)]
+pub struct ArgumentBuffers {
+    free: Vec<Vec<Object>>,
+}
+
+impl ArgumentBuffers {
+    /// An empty list able to hold `capacity` arguments.
+    pub fn take(&mut self, capacity: usize) -> Vec<Object> {
+        match self.free.pop() {
+            Some(mut buffer) => {
+                buffer.reserve(capacity);
+                buffer
+            }
+            None => Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Return a list whose arguments are no longer needed.
+    pub fn give_back(&mut self, mut buffer: Vec<Object>) {
+        let capacity = buffer.capacity();
+        if capacity == 0 || capacity > MAX_POOLED_CAPACITY || self.free.len() >= MAX_POOLED_BUFFERS
+        {
+            return;
+        }
+        buffer.clear();
+        self.free.push(buffer);
+    }
+}
@@ -0,0 +1,34 @@
+//! Snapshots of the state a failed evaluation should not leave behind.
+//!
+//! The REPL evaluates each input inside a snapshot and restores it when the
+//! input raises, so a chunk that fails halfway does not leave some of its
+//! variables, methods and classes defined. Only bindings are captured: an
+//! object changed in place, such as an array that was pushed to or a class
+//! reopened during `reload!`, keeps its changes.
+
+use super::{GlobalRegistry, VirtualMachine};
+use crate::environment::EnvironmentSnapshot;
+
+/// Variable bindings and globals captured by [`VirtualMachine::snapshot`].
+#[derive(Debug, Clone)]
+pub struct VmSnapshot {
+    environment: EnvironmentSnapshot,
+    globals: GlobalRegistry,
+}
+
+impl VirtualMachine {
+    /// Capture the current bindings and globals.
+    pub fn snapshot(&self) -> VmSnapshot {
+        VmSnapshot {
+            environment: self.environment().snapshot(),
+            globals: self.globals().clone(),
+        }
+    }
+
+    /// Return the bindings and globals to a snapshot, undoing definitions
+    /// and assignments made since it was taken.
+    pub fn restore(&mut self, snapshot: VmSnapshot) {
+        self.environment_mut().restore(snapshot.environment);
+        *self.globals_mut() = snapshot.globals;
+    }
+}
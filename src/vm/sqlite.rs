@@ -0,0 +1,39 @@
+//! Open SQLite connections.
+//!
+//! A connection cannot live inside an Object, so the VM keeps every open
+//! database here and SQLite instances hold only an integer handle into the
+//! table. Closing a database removes its entry.
+
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+/// Connections opened by `SQLite.open`, keyed by handle.
+#[derive(Default)]
+pub(crate) struct SqliteConnections {
+    next_handle: i64,
+    open: HashMap<i64, Connection>,
+}
+
+impl SqliteConnections {
+    /// Take ownership of `connection` and return its handle.
+    pub(crate) fn insert(&mut self, connection: Connection) -> i64 {
+        self.next_handle += 1;
+        self.open.insert(self.next_handle, connection);
+        self.next_handle
+    }
+
+    /// The connection for `handle`, unless it has been closed.
+    pub(crate) fn get(&self, handle: i64) -> Option<&Connection> {
+        self.open.get(&handle)
+    }
+
+    /// Remove and return the connection for `handle`.
+    pub(crate) fn remove(&mut self, handle: i64) -> Option<Connection> {
+        self.open.remove(&handle)
+    }
+
+    /// Close every open connection.
+    pub(crate) fn close_all(&mut self) {
+        self.open.clear();
+    }
+}
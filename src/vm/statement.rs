@@ -11,12 +11,20 @@ use crate::error::MetorexError;
 use crate::object::Object;
 use std::rc::Rc;
 
+/// How many nil slots assigning past the end of an array may add, so a
+/// mistyped index such as `a[1000000000] = 1` is an error rather than a
+/// gigabytes-large allocation
+const MAX_INDEX_GAP: usize = 1_000_000;
+
 impl VirtualMachine {
     /// Evaluate a statement and produce control-flow information for the caller.
     pub(crate) fn execute_statement(
         &mut self,
         statement: &Statement,
     ) -> Result<ControlFlow, MetorexError> {
+        self.check_interrupt(statement.position())?;
+        self.trace_line(statement.position())?;
+
         match statement {
             Statement::Expression {
                 expression,
@@ -94,8 +102,13 @@ impl VirtualMachine {
                 body,
                 position,
             } => self.execute_class_def(name, superclass.as_deref(), body, *position),
+            Statement::ModuleDef {
+                name,
+                body,
+                position,
+            } => self.execute_module_def(name, body, *position),
             Statement::MethodDef { .. } => {
-                // MethodDef should only appear inside ClassDef bodies, not at top level
+                // MethodDef should only appear inside ClassDef and ModuleDef bodies, not at top level
                 Err(unimplemented_statement_error(statement))
             }
             Statement::Begin {
@@ -103,12 +116,16 @@ impl VirtualMachine {
                 rescue_clauses,
                 else_clause,
                 ensure_block,
-                position,
-            } => self.execute_begin(body, rescue_clauses, else_clause, ensure_block, *position),
+                ..
+            } => self.execute_begin(body, rescue_clauses, else_clause, ensure_block),
             Statement::Raise {
                 exception,
+                cause,
                 position,
-            } => self.execute_raise(exception, *position),
+            } => self.execute_raise(exception, cause, *position),
+            Statement::Retry { position } => Ok(ControlFlow::Retry {
+                position: *position,
+            }),
             Statement::Match {
                 expression,
                 cases,
@@ -119,15 +136,13 @@ impl VirtualMachine {
                 parameters,
                 body,
                 position,
-            } => self.execute_function_def(name, parameters, body, *position),
+                return_type,
+            } => self.execute_function_def(name, parameters, body, return_type.as_ref(), *position),
             Statement::AttrReader { position, .. }
             | Statement::AttrWriter { position, .. }
             | Statement::AttrAccessor { position, .. } => {
-                // These are only processed during class definition, not as standalone statements
-                Err(MetorexError::runtime_error(
-                    "attr_reader, attr_writer, and attr_accessor can only be used inside a class definition",
-                    position_to_location(*position),
-                ))
+                // These are only processed during class and module definitions, not as standalone statements
+                Err(attribute_outside_class_error(*position))
             }
         }
     }
@@ -157,6 +172,62 @@ impl VirtualMachine {
         Ok(ControlFlow::Next)
     }
 
+    /// Execute statements like [`Self::execute_statements_internal`], also
+    /// producing the value of the last one when it is an expression.
+    pub(crate) fn execute_statements_for_value(
+        &mut self,
+        statements: &[Statement],
+    ) -> Result<(ControlFlow, Object), MetorexError> {
+        let Some((last, rest)) = statements.split_last() else {
+            return Ok((ControlFlow::Next, Object::Nil));
+        };
+        match self.execute_statements_internal(rest)? {
+            ControlFlow::Next => {}
+            flow => return Ok((flow, Object::Nil)),
+        }
+
+        match last {
+            Statement::Expression {
+                expression,
+                position,
+            } => {
+                self.check_interrupt(*position)?;
+                self.trace_line(*position)?;
+                let value = self.evaluate_expression(expression)?;
+
+                // Ruby-style auto-call of a bare method name, as in execute_statement
+                if matches!(expression, Expression::Identifier { .. })
+                    && matches!(value, Object::Method(_))
+                {
+                    let value = self.invoke_callable(value, vec![], *position)?;
+                    return Ok((ControlFlow::Next, value));
+                }
+                Ok((ControlFlow::Next, value))
+            }
+            Statement::Begin {
+                body,
+                rescue_clauses,
+                else_clause,
+                ensure_block,
+                position,
+            } => {
+                self.check_interrupt(*position)?;
+                self.trace_line(*position)?;
+                self.run_begin(body, rescue_clauses, else_clause, ensure_block)
+            }
+            Statement::Match {
+                expression,
+                cases,
+                position,
+            } => {
+                self.check_interrupt(*position)?;
+                self.trace_line(*position)?;
+                self.run_match(expression, cases, *position)
+            }
+            statement => Ok((self.execute_statement(statement)?, Object::Nil)),
+        }
+    }
+
     /// Assign a value to the given target expression.
     pub(crate) fn assign_value(
         &mut self,
@@ -178,43 +249,28 @@ impl VirtualMachine {
                         instance.set_var(name.clone(), value);
                         Ok(())
                     }
-                    Some(_) => Err(MetorexError::runtime_error(
-                        format!("Cannot set instance variable @{} on non-instance", name),
-                        position_to_location(*position),
-                    )),
-                    None => Err(MetorexError::runtime_error(
-                        format!(
-                            "Instance variable @{} can only be used within a method",
-                            name
-                        ),
-                        position_to_location(*position),
+                    Some(_) => Err(instance_variable_on_non_instance_error(
+                        name, true, *position,
                     )),
+                    None => Err(instance_variable_outside_method_error(name, *position)),
                 }
             }
             Expression::ClassVariable { name, position } => {
                 // Class variables can only be set within a method or class context
                 // For now, we'll look for 'self' to get the class
-                match self.environment().get("self") {
+                let receiver = self.environment().get("self");
+                match receiver.filter(|receiver| !self.is_main_object(receiver)) {
                     Some(Object::Instance(instance_rc)) => {
                         let instance = instance_rc.borrow();
-                        instance.class.set_class_var(name.clone(), value);
+                        instance.class.assign_class_var(name.clone(), value);
                         Ok(())
                     }
                     Some(Object::Class(class)) => {
-                        class.set_class_var(name.clone(), value);
+                        class.assign_class_var(name.clone(), value);
                         Ok(())
                     }
-                    Some(_) => Err(MetorexError::runtime_error(
-                        format!("Cannot set class variable @@{} in this context", name),
-                        position_to_location(*position),
-                    )),
-                    None => Err(MetorexError::runtime_error(
-                        format!(
-                            "Class variable @@{} can only be used within a class or method",
-                            name
-                        ),
-                        position_to_location(*position),
-                    )),
+                    Some(_) => Err(class_variable_out_of_context_error(name, true, *position)),
+                    None => Err(class_variable_outside_class_error(name, *position)),
                 }
             }
             Expression::Index {
@@ -229,49 +285,57 @@ impl VirtualMachine {
                 match obj {
                     Object::Array(array_rc) => {
                         // Array index assignment
-                        if let Object::Int(i) = idx {
-                            let mut array = array_rc.borrow_mut();
+                        if let Some(i) = self.implicit_int(&idx, "array index", *position)? {
+                            let mut array = array_rc.try_borrow_mut().map_err(|_| {
+                                collection_in_use_error(&Object::Array(array_rc.clone()), *position)
+                            })?;
                             let len = array.len() as i64;
                             let actual_index = if i < 0 { len + i } else { i };
 
-                            if actual_index < 0 || actual_index >= len {
-                                return Err(MetorexError::runtime_error(
-                                    format!("Array index out of bounds: {}", i),
-                                    position_to_location(*position),
+                            if actual_index < 0 {
+                                return Err(index_out_of_bounds_error(i, array.len(), *position));
+                            }
+                            // Assigning past the end grows the array, filling the gap with nil
+                            let actual_index = actual_index as usize;
+                            if actual_index > array.len() + MAX_INDEX_GAP {
+                                return Err(index_too_far_past_end_error(
+                                    i,
+                                    array.len(),
+                                    MAX_INDEX_GAP,
+                                    *position,
                                 ));
                             }
-                            array[actual_index as usize] = value;
+                            if actual_index >= array.len() {
+                                array.resize(actual_index + 1, Object::Nil);
+                            }
+                            array[actual_index] = value;
                             Ok(())
                         } else {
-                            Err(MetorexError::runtime_error(
-                                "Array index must be an integer",
-                                position_to_location(*position),
-                            ))
+                            Err(array_index_not_integer_error(&idx, *position))
                         }
                     }
                     Object::Dict(dict_rc) => {
                         // Hash/Dict index assignment
-                        let key_str = match idx {
-                            Object::String(s) => s.as_str().to_string(),
-                            Object::Int(i) => i.to_string(),
-                            Object::Float(f) => f.to_string(),
-                            Object::Bool(b) => b.to_string(),
-                            Object::Nil => "nil".to_string(),
-                            _ => {
-                                return Err(MetorexError::runtime_error(
-                                    "Hash key must be a String, Integer, Float, Bool, or Nil",
-                                    position_to_location(*position),
-                                ));
-                            }
+                        let Some(key_str) = object_to_dict_key(&idx) else {
+                            return Err(invalid_hash_key_error(&idx, *position));
                         };
-                        let mut dict = dict_rc.borrow_mut();
+                        let mut dict = dict_rc.try_borrow_mut().map_err(|_| {
+                            collection_in_use_error(&Object::Dict(dict_rc.clone()), *position)
+                        })?;
                         dict.insert(key_str, value);
                         Ok(())
                     }
-                    _ => Err(MetorexError::runtime_error(
-                        "Cannot index assign on this type",
-                        position_to_location(*position),
-                    )),
+                    Object::Instance(_) if let Some(entries) = self.identity_hash_entries(&obj) => {
+                        self.identity_hash_insert(&entries, &idx, value, *position)
+                    }
+                    // Collections written in Metorex define `[]=(key, value)`
+                    Object::Instance(_)
+                        if let Some((class, method)) = self.lookup_method(&obj, "[]=") =>
+                    {
+                        self.invoke_method(class, method, obj, vec![idx, value], *position)?;
+                        Ok(())
+                    }
+                    other => Err(index_assignment_unsupported_error(&other, *position)),
                 }
             }
             Expression::MethodCall {
@@ -307,26 +371,17 @@ impl VirtualMachine {
                                 )?;
                                 Ok(())
                             } else {
-                                Err(MetorexError::runtime_error(
-                                    format!("Undefined setter method '{}'", setter_method),
-                                    position_to_location(*position),
-                                ))
+                                Err(undefined_setter_error(&setter_method, *position))
                             }
                         }
-                        _ => Err(MetorexError::runtime_error(
-                            format!(
-                                "Cannot call setter method '{}' on {}",
-                                setter_method,
-                                receiver_obj.type_name()
-                            ),
-                            position_to_location(*position),
+                        _ => Err(setter_on_non_instance_error(
+                            &setter_method,
+                            &receiver_obj,
+                            *position,
                         )),
                     }
                 } else {
-                    Err(MetorexError::runtime_error(
-                        "Cannot assign to method call with arguments",
-                        position_to_location(*position),
-                    ))
+                    Err(method_call_assignment_error(*position))
                 }
             }
             _ => Err(invalid_assignment_target_error(target)),
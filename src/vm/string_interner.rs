@@ -0,0 +1,42 @@
+//! String literal interning for the Metorex virtual machine.
+//!
+//! When enabled, every evaluation of an identical string literal shares a single
+//! `Rc<String>` instead of allocating a fresh copy. Strings are immutable values
+//! in Metorex (string methods always return new strings), so sharing is safe.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Table of interned string literals keyed by their contents.
+#[derive(Debug, Default)]
+pub struct StringInterner {
+    literals: HashMap<String, Rc<String>>,
+}
+
+impl StringInterner {
+    /// Create an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the shared string for `value`, allocating it on first use.
+    pub fn intern(&mut self, value: &str) -> Rc<String> {
+        if let Some(existing) = self.literals.get(value) {
+            return Rc::clone(existing);
+        }
+
+        let shared = Rc::new(value.to_string());
+        self.literals.insert(value.to_string(), Rc::clone(&shared));
+        shared
+    }
+
+    /// Number of distinct literals interned so far.
+    pub fn len(&self) -> usize {
+        self.literals.len()
+    }
+
+    /// Check if no literals have been interned yet.
+    pub fn is_empty(&self) -> bool {
+        self.literals.is_empty()
+    }
+}
@@ -0,0 +1,55 @@
+//! Releasing what a virtual machine holds when it goes away.
+//!
+//! Host code and natives that hand OS resources to a VM register finalizers
+//! here. [`VirtualMachine::shutdown`] runs them, closes the databases and
+//! input the VM still owns and flushes stdout; dropping the VM shuts it down
+//! if that has not happened yet.
+
+use super::VirtualMachine;
+use std::io::{self, Write};
+
+/// Cleanup run once when the VM shuts down.
+pub(super) type Finalizer = Box<dyn FnOnce(&mut VirtualMachine)>;
+
+impl VirtualMachine {
+    /// Run `finalizer` when the VM shuts down.
+    ///
+    /// Finalizers run newest first, so a resource registered after the ones
+    /// it depends on is released before them.
+    pub fn register_finalizer(&mut self, finalizer: impl FnOnce(&mut VirtualMachine) + 'static) {
+        self.finalizers.push(Box::new(finalizer));
+    }
+
+    /// Number of finalizers that have not run yet.
+    pub fn pending_finalizers(&self) -> usize {
+        self.finalizers.len()
+    }
+
+    /// Run every registered finalizer, close open databases and input, and
+    /// flush stdout.
+    ///
+    /// Finalizers registered while shutting down run too. Calling this more
+    /// than once is harmless; the VM stays usable afterwards, but anything
+    /// it closed stays closed.
+    pub fn shutdown(&mut self) {
+        while let Some(finalizer) = self.finalizers.pop() {
+            finalizer(self);
+        }
+
+        #[cfg(feature = "sqlite")]
+        self.sqlite.close_all();
+        self.input = None;
+        self.line_editor = None;
+
+        if let Some(output) = self.output.as_mut() {
+            let _ = output.flush();
+        }
+        let _ = io::stdout().flush();
+    }
+}
+
+impl Drop for VirtualMachine {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
@@ -0,0 +1,188 @@
+//! Time limits and retries for scripts that wait on the network or on other
+//! processes.
+//!
+//! `Timeout.timeout(seconds) { ... }` sets a deadline the VM checks at the
+//! same boundaries as interrupts, before every statement, every loop
+//! iteration and every slice of a `sleep`, so a block that runs too long
+//! raises `Timeout::Error` where it is. A native call that blocks, such as
+//! reading input, finishes before the deadline is noticed.
+//!
+//! `retry_on(error, attempts: 3, backoff: 0.1) { ... }` runs a block again
+//! when it raises an error the first argument matches, waiting `backoff`
+//! seconds before the first retry and twice as long before each one after.
+
+use super::VirtualMachine;
+use super::errors::{invalid_timeout_error, raised_exception_error};
+use super::type_checks::describe_type;
+use super::utils::position_to_location;
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::Object;
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+/// Name of the exception class raised when a timeout expires.
+pub const TIMEOUT_EXCEPTION: &str = "Timeout::Error";
+
+/// How many times `retry_on` runs its block unless given `attempts:`.
+pub const DEFAULT_RETRY_ATTEMPTS: i64 = 3;
+
+/// Seconds `retry_on` waits before its first retry unless given `backoff:`.
+pub const DEFAULT_RETRY_BACKOFF: f64 = 0.1;
+
+/// The end of a running `Timeout.timeout` block.
+#[derive(Debug)]
+pub(super) struct Deadline {
+    expires_at: Instant,
+    /// Set once `Timeout::Error` has been raised, so a block that rescues it
+    /// can finish
+    expired: Cell<bool>,
+}
+
+impl VirtualMachine {
+    /// Raise `Timeout::Error` if a running timeout block is past its deadline.
+    pub(super) fn check_deadlines(&self, position: Position) -> Result<(), MetorexError> {
+        let now = Instant::now();
+        for deadline in &self.deadlines {
+            if !deadline.expired.get() && deadline.expires_at <= now {
+                deadline.expired.set(true);
+                return Err(raised_exception_error(
+                    TIMEOUT_EXCEPTION,
+                    "execution expired".to_string(),
+                    position,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Call `block`, raising `Timeout::Error` inside it once it has run for
+    /// `seconds`. Without a positive limit the block runs to completion.
+    pub(crate) fn call_with_timeout(
+        &mut self,
+        seconds: &Object,
+        block: Object,
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        let seconds = match seconds {
+            Object::Nil => 0.0,
+            Object::Int(seconds) => *seconds as f64,
+            Object::Float(seconds) => *seconds,
+            other => {
+                return Err(invalid_timeout_error(
+                    "timeout",
+                    &describe_type(other),
+                    position,
+                ));
+            }
+        };
+        if seconds < 0.0 || seconds.is_nan() {
+            return Err(invalid_timeout_error("timeout", &seconds, position));
+        }
+        // A limit too far away to represent never expires
+        let expires_at = Duration::try_from_secs_f64(seconds)
+            .ok()
+            .and_then(|duration| Instant::now().checked_add(duration));
+        let Some(expires_at) = expires_at.filter(|_| seconds > 0.0) else {
+            return self.invoke_callable(block, Vec::new(), position);
+        };
+
+        self.deadlines.push(Deadline {
+            expires_at,
+            expired: Cell::new(false),
+        });
+        let result = self.invoke_callable(block, Vec::new(), position);
+        self.deadlines.pop();
+        result
+    }
+
+    /// Run `retry_on(error, attempts:, backoff:) { ... }`.
+    pub(super) fn retry_on(
+        &mut self,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<Object, MetorexError> {
+        let (pattern, options, block) = match arguments {
+            [pattern, block @ Object::Block(_)] => (pattern, None, block),
+            [pattern, Object::Dict(options), block @ Object::Block(_)] => {
+                (pattern, Some(options), block)
+            }
+            _ => {
+                return Err(MetorexError::runtime_error(
+                    "retry_on() expects an error class, optional attempts: and backoff:, and a block",
+                    position_to_location(position),
+                ));
+            }
+        };
+
+        let mut attempts = DEFAULT_RETRY_ATTEMPTS;
+        let mut backoff = DEFAULT_RETRY_BACKOFF;
+        for (key, value) in options
+            .map(|options| options.borrow().clone())
+            .unwrap_or_default()
+        {
+            match (key.as_str(), value) {
+                (":attempts", Object::Int(count)) if count > 0 => attempts = count,
+                (":attempts", other) => {
+                    return Err(retry_option_error(
+                        format!("attempts must be a positive Int, got {}", other),
+                        position,
+                    ));
+                }
+                (":backoff", Object::Int(seconds)) if seconds >= 0 => backoff = seconds as f64,
+                (":backoff", Object::Float(seconds)) if seconds >= 0.0 && seconds.is_finite() => {
+                    backoff = seconds
+                }
+                (":backoff", other) => {
+                    return Err(retry_option_error(
+                        format!("backoff must be a number of seconds, got {}", other),
+                        position,
+                    ));
+                }
+                (key, _) => {
+                    return Err(retry_option_error(
+                        format!(
+                            "unknown option {} (expected attempts or backoff)",
+                            key.trim_start_matches(':')
+                        ),
+                        position,
+                    ));
+                }
+            }
+        }
+
+        let mut wait = backoff;
+        let mut attempt = 1;
+        loop {
+            let result = self.invoke_callable(block.clone(), Vec::new(), position);
+            // Interrupts stop the program however broad the pattern is
+            let retry = match &result {
+                Err(error @ MetorexError::UncaughtException { exception, .. })
+                    if attempt < attempts && !error.is_interrupt() =>
+                {
+                    self.case_equals(pattern, exception, position)?
+                }
+                _ => false,
+            };
+            if !retry {
+                return result;
+            }
+            let deadline = Duration::try_from_secs_f64(wait)
+                .ok()
+                .and_then(|duration| Instant::now().checked_add(duration))
+                .ok_or_else(|| {
+                    retry_option_error(format!("cannot wait {} seconds", wait), position)
+                })?;
+            self.sleep_until(deadline, position)?;
+            wait *= 2.0;
+            attempt += 1;
+        }
+    }
+}
+
+fn retry_option_error(message: String, position: Position) -> MetorexError {
+    MetorexError::runtime_error(
+        format!("retry_on: {}", message),
+        position_to_location(position),
+    )
+}
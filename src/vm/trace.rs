@@ -0,0 +1,330 @@
+//! Execution tracing for debuggers, profilers and coverage tools.
+//!
+//! The VM reports five kinds of events while it runs a program: a method or
+//! function being called and returning, a statement starting on a line, a
+//! class body being opened and a script `raise`. Embedders observe them by
+//! registering a [`VmHooks`] implementation with [`VirtualMachine::add_hooks`];
+//! scripts do the same through `TracePoint`, whose block receives the trace
+//! point itself with the event's details filled in.
+//!
+//! Events raised while a hook or trace point block is running are not
+//! reported, so tracing code never traces itself. When nothing is
+//! registered, each event site costs a single branch.
+
+use super::VirtualMachine;
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::{Binding, Instance, Method, Object};
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+/// The kinds of events the VM reports while tracing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TraceEventKind {
+    /// A method or function was entered, after its arguments were bound.
+    Call,
+    /// A method or function finished normally.
+    Return,
+    /// A statement is about to run.
+    Line,
+    /// A class body is about to be processed.
+    Class,
+    /// A script `raise` produced an exception.
+    Raise,
+}
+
+impl TraceEventKind {
+    /// Every event kind, in the order `TracePoint.new` lists them by default.
+    pub const ALL: [TraceEventKind; 5] = [
+        TraceEventKind::Call,
+        TraceEventKind::Return,
+        TraceEventKind::Line,
+        TraceEventKind::Class,
+        TraceEventKind::Raise,
+    ];
+
+    /// The Symbol name scripts use for this event, without the colon.
+    pub fn name(self) -> &'static str {
+        match self {
+            TraceEventKind::Call => "call",
+            TraceEventKind::Return => "return",
+            TraceEventKind::Line => "line",
+            TraceEventKind::Class => "class",
+            TraceEventKind::Raise => "raise",
+        }
+    }
+
+    /// Look up an event kind by its Symbol name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+}
+
+/// A single traced event.
+#[derive(Debug, Clone)]
+pub struct TraceEvent {
+    /// What happened.
+    pub kind: TraceEventKind,
+    /// Where it happened: the statement for `Line`, the definition for
+    /// `Call`, `Return` and `Class`, and the `raise` for `Raise`.
+    pub position: Position,
+    /// The file being executed, if the program came from one.
+    pub path: Option<PathBuf>,
+    /// The method or function running when the event fired.
+    pub method_name: Option<String>,
+    /// The class owning that method, or the class being defined.
+    pub class_name: Option<String>,
+    /// The value being returned, for `Return` events.
+    pub return_value: Option<Object>,
+    /// The exception being raised, for `Raise` events.
+    pub raised_exception: Option<Object>,
+}
+
+/// Receiver of trace events from a running virtual machine.
+///
+/// The VM is passed in read-only so a hook can inspect it, for example by
+/// taking [`VirtualMachine::binding`] or walking the call stack.
+pub trait VmHooks {
+    /// Called once for every event while the hooks are registered.
+    fn on_event(&mut self, vm: &VirtualMachine, event: &TraceEvent);
+}
+
+/// Identifies a registered [`VmHooks`] so it can be removed later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HookId(u64);
+
+/// The method or function a traced event belongs to.
+#[derive(Debug, Clone)]
+struct TraceFrame {
+    method_name: String,
+    class_name: Option<String>,
+    position: Position,
+}
+
+/// Tracing state kept by the VM.
+#[derive(Default)]
+pub(super) struct Tracing {
+    hooks: Vec<(HookId, Rc<RefCell<dyn VmHooks>>)>,
+    next_id: u64,
+    /// Enabled `TracePoint` instances, in the order they were enabled.
+    trace_points: Vec<Rc<RefCell<Instance>>>,
+    /// Methods entered while tracing, innermost last.
+    frames: Vec<TraceFrame>,
+    /// Set while hooks or trace point blocks run, to suppress nested events.
+    dispatching: bool,
+}
+
+impl Tracing {
+    /// Whether an event fired now would reach anyone.
+    pub(super) fn is_active(&self) -> bool {
+        !self.dispatching && (!self.hooks.is_empty() || !self.trace_points.is_empty())
+    }
+}
+
+impl VirtualMachine {
+    /// Register hooks to receive every trace event from now on.
+    pub fn add_hooks(&mut self, hooks: Rc<RefCell<dyn VmHooks>>) -> HookId {
+        let id = HookId(self.tracing.next_id);
+        self.tracing.next_id += 1;
+        self.tracing.hooks.push((id, hooks));
+        id
+    }
+
+    /// Unregister hooks, returning whether they were registered.
+    pub fn remove_hooks(&mut self, id: HookId) -> bool {
+        let before = self.tracing.hooks.len();
+        self.tracing.hooks.retain(|(hook_id, _)| *hook_id != id);
+        self.tracing.hooks.len() != before
+    }
+
+    /// The variables visible in the scope that is currently executing.
+    pub fn binding(&self) -> Binding {
+        Binding::new(self.environment().current_scope_var_refs())
+    }
+
+    /// Start delivering events to a `TracePoint` instance.
+    pub(super) fn enable_trace_point(&mut self, trace_point: &Rc<RefCell<Instance>>) -> bool {
+        if self.is_trace_point_enabled(trace_point) {
+            return false;
+        }
+        self.tracing.trace_points.push(Rc::clone(trace_point));
+        true
+    }
+
+    /// Stop delivering events to a `TracePoint` instance.
+    pub(super) fn disable_trace_point(&mut self, trace_point: &Rc<RefCell<Instance>>) -> bool {
+        let before = self.tracing.trace_points.len();
+        self.tracing
+            .trace_points
+            .retain(|enabled| !Rc::ptr_eq(enabled, trace_point));
+        self.tracing.trace_points.len() != before
+    }
+
+    /// Whether a `TracePoint` instance is receiving events.
+    pub(super) fn is_trace_point_enabled(&self, trace_point: &Rc<RefCell<Instance>>) -> bool {
+        self.tracing
+            .trace_points
+            .iter()
+            .any(|enabled| Rc::ptr_eq(enabled, trace_point))
+    }
+
+    /// Report a statement about to run.
+    pub(super) fn trace_line(&mut self, position: Position) -> Result<(), MetorexError> {
+        if !self.tracing.is_active() {
+            return Ok(());
+        }
+        self.fire_trace_event(TraceEventKind::Line, position, None, None, None)
+    }
+
+    /// Report entry into a method of `class_name`, or a function, and
+    /// remember it as the current frame. Returns whether a frame was pushed,
+    /// which the caller passes back to [`Self::trace_return`].
+    pub(super) fn trace_call(
+        &mut self,
+        method: &Method,
+        class_name: Option<&str>,
+    ) -> Result<bool, MetorexError> {
+        if !self.tracing.is_active() {
+            return Ok(false);
+        }
+        let position = method
+            .source_location
+            .as_ref()
+            .map(|location| Position::new(location.line, location.column, location.offset))
+            .unwrap_or_default();
+        self.tracing.frames.push(TraceFrame {
+            method_name: method.name.clone(),
+            class_name: class_name.map(str::to_string),
+            position,
+        });
+        if let Err(error) = self.fire_trace_event(TraceEventKind::Call, position, None, None, None)
+        {
+            self.tracing.frames.pop();
+            return Err(error);
+        }
+        Ok(true)
+    }
+
+    /// Report a method or function finishing with `result`, then drop the
+    /// frame [`Self::trace_call`] pushed.
+    pub(super) fn trace_return(
+        &mut self,
+        traced: bool,
+        result: Result<Object, MetorexError>,
+    ) -> Result<Object, MetorexError> {
+        if !traced {
+            return result;
+        }
+        let fired = match (&result, self.tracing.frames.last()) {
+            (Ok(value), Some(frame)) if self.tracing.is_active() => {
+                let position = frame.position;
+                self.fire_trace_event(
+                    TraceEventKind::Return,
+                    position,
+                    None,
+                    Some(value.clone()),
+                    None,
+                )
+            }
+            _ => Ok(()),
+        };
+        self.tracing.frames.pop();
+        fired.and(result)
+    }
+
+    /// Report a class body about to be processed.
+    pub(super) fn trace_class(
+        &mut self,
+        class_name: &str,
+        position: Position,
+    ) -> Result<(), MetorexError> {
+        if !self.tracing.is_active() {
+            return Ok(());
+        }
+        self.fire_trace_event(
+            TraceEventKind::Class,
+            position,
+            Some(class_name),
+            None,
+            None,
+        )
+    }
+
+    /// Report an exception produced by a script `raise`.
+    pub(super) fn trace_raise(
+        &mut self,
+        exception: &Object,
+        position: Position,
+    ) -> Result<(), MetorexError> {
+        if !self.tracing.is_active() {
+            return Ok(());
+        }
+        self.fire_trace_event(
+            TraceEventKind::Raise,
+            position,
+            None,
+            None,
+            Some(exception.clone()),
+        )
+    }
+
+    fn fire_trace_event(
+        &mut self,
+        kind: TraceEventKind,
+        position: Position,
+        class_name: Option<&str>,
+        return_value: Option<Object>,
+        raised_exception: Option<Object>,
+    ) -> Result<(), MetorexError> {
+        let frame = self.tracing.frames.last();
+        let event = TraceEvent {
+            kind,
+            position,
+            path: self.get_current_file().cloned(),
+            method_name: frame.map(|frame| frame.method_name.clone()),
+            class_name: class_name
+                .map(str::to_string)
+                .or_else(|| frame.and_then(|frame| frame.class_name.clone())),
+            return_value,
+            raised_exception,
+        };
+
+        self.tracing.dispatching = true;
+        let hooks: Vec<_> = self
+            .tracing
+            .hooks
+            .iter()
+            .map(|(_, hooks)| Rc::clone(hooks))
+            .collect();
+        for hooks in hooks {
+            hooks.borrow_mut().on_event(self, &event);
+        }
+        let result = self.run_trace_points(&event);
+        self.tracing.dispatching = false;
+        result
+    }
+
+    /// Call the block of every enabled trace point listening for the event.
+    fn run_trace_points(&mut self, event: &TraceEvent) -> Result<(), MetorexError> {
+        if self.tracing.trace_points.is_empty() {
+            return Ok(());
+        }
+        let trace_points: Vec<Object> = self
+            .tracing
+            .trace_points
+            .iter()
+            .map(|instance| Object::Instance(Rc::clone(instance)))
+            .collect();
+        let binding = Object::Binding(Rc::new(self.binding()));
+        for trace_point in trace_points {
+            if let Some(block) = self.trace_point_block_for(&trace_point, event.kind) {
+                self.set_trace_point_event(&trace_point, Some((event, &binding)));
+                let result = self.invoke_callable(block, vec![trace_point.clone()], event.position);
+                self.set_trace_point_event(&trace_point, None);
+                result?;
+            }
+        }
+        Ok(())
+    }
+}
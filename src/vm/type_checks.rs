@@ -0,0 +1,125 @@
+//! Call-time validation of type annotations (`--check-types`).
+//!
+//! When the `check_types` option is on, arguments passed to a method or
+//! function with annotated parameters, and the values it returns, are checked
+//! against the annotations. A mismatch raises a `TypeError` that scripts can
+//! rescue.
+
+use super::VirtualMachine;
+use super::errors::raised_exception_error;
+use crate::ast::TypeAnnotation;
+use crate::error::MetorexError;
+use crate::lexer::Position;
+use crate::object::{Method, Object};
+use crate::typecheck::{accepts_anything, builtin_type_names, satisfies};
+
+impl VirtualMachine {
+    /// Check the arguments of a call against the method's parameter types.
+    pub(super) fn check_argument_types(
+        &self,
+        method: &Method,
+        arguments: &[Object],
+        position: Position,
+    ) -> Result<(), MetorexError> {
+        if !self.options().check_types {
+            return Ok(());
+        }
+        for (index, argument) in arguments.iter().enumerate() {
+            let Some(annotation) = method.parameter_type(index) else {
+                continue;
+            };
+            if !self.value_satisfies(annotation, argument, position)? {
+                return Err(raised_exception_error(
+                    "TypeError",
+                    format!(
+                        "argument '{}' of '{}' expects {}, got {}",
+                        method.parameters[index],
+                        method.name,
+                        annotation,
+                        describe_type(argument)
+                    ),
+                    position,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the value a method returned against its declared result type.
+    pub(super) fn check_result_type(
+        &self,
+        method: &Method,
+        result: &Object,
+        position: Position,
+    ) -> Result<(), MetorexError> {
+        let Some(annotation) = method.return_type.as_ref() else {
+            return Ok(());
+        };
+        if !self.options().check_types || self.value_satisfies(annotation, result, position)? {
+            return Ok(());
+        }
+        Err(raised_exception_error(
+            "TypeError",
+            format!(
+                "'{}' declares result {} but returned {}",
+                method.name,
+                annotation,
+                describe_type(result)
+            ),
+            position,
+        ))
+    }
+
+    /// Whether `value` satisfies `annotation`, which must name a builtin type
+    /// or a class bound in the current environment.
+    fn value_satisfies(
+        &self,
+        annotation: &TypeAnnotation,
+        value: &Object,
+        position: Position,
+    ) -> Result<bool, MetorexError> {
+        let known = accepts_anything(annotation)
+            || builtin_type_names(&annotation.name).is_some()
+            || matches!(
+                self.environment().get(&annotation.name),
+                Some(Object::Class(_))
+            );
+        if !known {
+            return Err(raised_exception_error(
+                "TypeError",
+                format!("Unknown type '{}'", annotation.name),
+                position,
+            ));
+        }
+
+        let lineage = value_lineage(value);
+        let lineage: Vec<&str> = lineage.iter().map(String::as_str).collect();
+        Ok(satisfies(annotation, &lineage))
+    }
+}
+
+/// The value's type name followed by the names of its superclasses.
+pub(super) fn value_lineage(value: &Object) -> Vec<String> {
+    match value {
+        Object::Instance(instance) => {
+            let mut lineage = Vec::new();
+            let mut class = Some(instance.borrow().class.clone());
+            while let Some(current) = class {
+                lineage.push(current.name().to_string());
+                class = current.superclass();
+            }
+            lineage.push("Object".to_string());
+            lineage
+        }
+        Object::Exception(exception) => vec![
+            exception.borrow().exception_type.clone(),
+            "Exception".to_string(),
+        ],
+        other => vec![other.type_name().to_string()],
+    }
+}
+
+/// The name reported for a value's type in a `TypeError`.
+pub(super) fn describe_type(value: &Object) -> String {
+    value_lineage(value).swap_remove(0)
+}
@@ -0,0 +1,187 @@
+//! Type feedback for binary operators.
+//!
+//! Each `BinaryOp` node records the operand types it sees. Once a node has
+//! seen only Int operands for [`HOT_THRESHOLD`] evaluations it is treated as
+//! monomorphic and takes an Int-Int fast path that skips the general operator
+//! dispatch. The fast path re-checks its operands every time; any other
+//! operand types, an overflow or a zero divisor deoptimize the node back to
+//! the general path, which then reports errors exactly as before.
+//!
+//! Nodes are identified by address in a map with a cheap multiplicative
+//! hash. Each node keeps its own entry, so nodes never evict each other's
+//! feedback, and a program's behaviour under feedback does not depend on
+//! where its AST happened to be allocated. A reused address only costs
+//! feedback, never correctness, because the fast path is guarded and
+//! always performs the operator of the node being evaluated.
+
+use crate::ast::{BinaryOp, Expression};
+use crate::object::Object;
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+
+/// Consecutive Int-Int evaluations before a node is specialized.
+pub const HOT_THRESHOLD: u32 = 16;
+
+/// Most nodes tracked at once. Past this the table starts over, so a long
+/// REPL session does not keep entries for ASTs that are long gone.
+const MAX_TRACKED_NODES: usize = 4096;
+
+/// Counters describing what the type feedback table has done.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TypeFeedbackStats {
+    /// Nodes that became specialized.
+    pub specializations: u64,
+    /// Specialized nodes that fell back to the general path.
+    pub deoptimizations: u64,
+    /// Evaluations answered by the Int-Int fast path.
+    pub fast_path_hits: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Slot {
+    int_hits: u32,
+    specialized: bool,
+}
+
+/// Per-VM table of operand type feedback for binary operators.
+#[derive(Debug)]
+pub(crate) struct TypeFeedback {
+    slots: HashMap<usize, Slot, BuildHasherDefault<AddressHasher>>,
+    stats: TypeFeedbackStats,
+}
+
+impl TypeFeedback {
+    pub(crate) fn new() -> Self {
+        Self {
+            slots: HashMap::default(),
+            stats: TypeFeedbackStats::default(),
+        }
+    }
+
+    pub(crate) fn stats(&self) -> TypeFeedbackStats {
+        self.stats
+    }
+
+    /// Evaluate `op` on the Int-Int fast path if `node` is specialized.
+    ///
+    /// Returns None when the node is not specialized or its guard fails; the
+    /// caller then evaluates the operation in full and reports the operands
+    /// with [`TypeFeedback::record`].
+    pub(crate) fn try_fast_path(
+        &mut self,
+        node: &Expression,
+        op: &BinaryOp,
+        left: &Object,
+        right: &Object,
+    ) -> Option<Object> {
+        let slot = self.slot(node);
+        if !slot.specialized {
+            return None;
+        }
+        let result = match (left, right) {
+            (Object::Int(a), Object::Int(b)) => int_operation(op, *a, *b),
+            _ => None,
+        };
+        match result {
+            Some(_) => self.stats.fast_path_hits += 1,
+            None => {
+                *slot = Slot::default();
+                self.stats.deoptimizations += 1;
+            }
+        }
+        result
+    }
+
+    /// Record the operands a node was evaluated with on the general path.
+    pub(crate) fn record(
+        &mut self,
+        node: &Expression,
+        op: &BinaryOp,
+        left: &Object,
+        right: &Object,
+    ) {
+        let slot = self.slot(node);
+        if !specializable(op) || !matches!((left, right), (Object::Int(_), Object::Int(_))) {
+            slot.int_hits = 0;
+            return;
+        }
+        slot.int_hits += 1;
+        if slot.int_hits >= HOT_THRESHOLD && !slot.specialized {
+            slot.specialized = true;
+            self.stats.specializations += 1;
+        }
+    }
+
+    /// The slot for `node`, starting the table over when it is full.
+    fn slot(&mut self, node: &Expression) -> &mut Slot {
+        let address = node as *const Expression as usize;
+        if self.slots.len() >= MAX_TRACKED_NODES && !self.slots.contains_key(&address) {
+            self.slots.clear();
+        }
+        self.slots.entry(address).or_default()
+    }
+}
+
+/// Hashes a node address with one multiply. Addresses are already unique,
+/// so this only has to spread them over the table's buckets.
+#[derive(Default)]
+struct AddressHasher(u64);
+
+impl Hasher for AddressHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 << 8) | u64::from(*byte);
+        }
+    }
+
+    fn write_usize(&mut self, address: usize) {
+        // Fibonacci hashing: the high bits of the product mix every bit of
+        // the address, and the table's buckets are chosen from both ends
+        let product = (address as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        self.0 = product ^ (product >> 32);
+    }
+}
+
+/// Whether `op` has an Int-Int fast path.
+fn specializable(op: &BinaryOp) -> bool {
+    use BinaryOp::*;
+    matches!(
+        op,
+        Add | Subtract
+            | Multiply
+            | Divide
+            | Modulo
+            | Less
+            | Greater
+            | LessEqual
+            | GreaterEqual
+            | Equal
+            | NotEqual
+    )
+}
+
+/// `op` on two Ints, or None where the general path must decide: overflow,
+/// division by zero and operators without an Int-Int specialization.
+fn int_operation(op: &BinaryOp, a: i64, b: i64) -> Option<Object> {
+    Some(match op {
+        BinaryOp::Add => Object::Int(a.checked_add(b)?),
+        BinaryOp::Subtract => Object::Int(a.checked_sub(b)?),
+        BinaryOp::Multiply => Object::Int(a.checked_mul(b)?),
+        BinaryOp::Divide if b != 0 => match a.checked_rem(b)? {
+            0 => Object::Int(a.checked_div(b)?),
+            _ => Object::Float((a as f64) / (b as f64)),
+        },
+        BinaryOp::Modulo if b != 0 => Object::Int(a.checked_rem(b)?),
+        BinaryOp::Less => Object::Bool(a < b),
+        BinaryOp::Greater => Object::Bool(a > b),
+        BinaryOp::LessEqual => Object::Bool(a <= b),
+        BinaryOp::GreaterEqual => Object::Bool(a >= b),
+        BinaryOp::Equal => Object::Bool(a == b),
+        BinaryOp::NotEqual => Object::Bool(a != b),
+        _ => return None,
+    })
+}
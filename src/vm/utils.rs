@@ -15,8 +15,14 @@ pub(super) fn position_to_location(position: Position) -> SourceLocation {
 pub(super) fn format_exception(exception: &Object) -> String {
     match exception {
         Object::Exception(ex) => {
-            let exc = ex.borrow();
-            format!("{}: {}", exc.exception_type, exc.message)
+            // The first entry is the exception itself, the rest is its cause chain
+            let mut chain = ex.borrow().exception_chain().into_iter();
+            let mut formatted = chain.next().unwrap_or_default();
+            for cause in chain {
+                formatted.push_str("\nCaused by: ");
+                formatted.push_str(&cause);
+            }
+            formatted
         }
         _ => format!("{:?}", exception),
     }
@@ -29,6 +35,7 @@ pub(super) fn object_to_dict_key(value: &Object) -> Option<String> {
         Object::Symbol(s) => Some(format!(":{}", s)),
         Object::Int(i) => Some(i.to_string()),
         Object::Float(f) => Some(f.to_string()),
+        Object::Decimal(d) => Some(d.normalize().to_string()),
         Object::Bool(b) => Some(b.to_string()),
         Object::Nil => Some("nil".to_string()),
         _ => None,
@@ -0,0 +1,151 @@
+// Watch mode for the Metorex CLI
+// Re-runs a script whenever it, or any file it required, changes on disk
+
+use crate::error::MetorexError;
+use crate::file_loader::canonical_file_path;
+use crate::terminal::{Palette, Style};
+use crate::vm::{VirtualMachine, VmOptions};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// How often watched files are checked for changes
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The outcome of running a script once
+#[derive(Debug)]
+pub struct ScriptRun {
+    /// Whether the script finished without an error
+    pub result: Result<(), MetorexError>,
+    /// How long the run took
+    pub elapsed: Duration,
+    /// The script and every file it loaded, which the watcher monitors
+    pub files: Vec<PathBuf>,
+}
+
+/// Run a script in a fresh VM, recording the files it loaded
+pub fn run_script(path: &Path) -> ScriptRun {
+    run_script_with_options(path, &VmOptions::default())
+}
+
+/// Run a script in a fresh VM configured with `options`
+pub fn run_script_with_options(path: &Path, options: &VmOptions) -> ScriptRun {
+    let mut vm = VirtualMachine::with_options(options.clone());
+    let start = Instant::now();
+    let result = vm.execute_file(path).map(|_| ());
+    let elapsed = start.elapsed();
+
+    let mut files = vm.loaded_files();
+    let entry = canonical_file_path(path).unwrap_or_else(|_| path.to_path_buf());
+    if !files.contains(&entry) {
+        files.insert(0, entry);
+    }
+
+    ScriptRun {
+        result,
+        elapsed,
+        files,
+    }
+}
+
+/// Tracks modification times of a set of files
+///
+/// Files that cannot be read are recorded as missing, so creating or deleting
+/// one also counts as a change.
+#[derive(Debug, Default)]
+pub struct FileWatcher {
+    modified: HashMap<PathBuf, Option<SystemTime>>,
+}
+
+impl FileWatcher {
+    /// Start watching `files`, remembering their current modification times
+    pub fn new(files: &[PathBuf]) -> Self {
+        let mut watcher = FileWatcher::default();
+        watcher.watch(files);
+        watcher
+    }
+
+    /// Replace the watched set with `files`
+    pub fn watch(&mut self, files: &[PathBuf]) {
+        self.modified = files
+            .iter()
+            .map(|path| (path.clone(), modified_time(path)))
+            .collect();
+    }
+
+    /// The files currently being watched, in sorted order
+    pub fn files(&self) -> Vec<&PathBuf> {
+        let mut files: Vec<&PathBuf> = self.modified.keys().collect();
+        files.sort();
+        files
+    }
+
+    /// Files whose modification time differs from when they were recorded
+    pub fn changed_files(&self) -> Vec<&PathBuf> {
+        let mut changed: Vec<&PathBuf> = self
+            .modified
+            .iter()
+            .filter(|(path, recorded)| modified_time(path) != **recorded)
+            .map(|(path, _)| path)
+            .collect();
+        changed.sort();
+        changed
+    }
+
+    /// Block until a watched file changes, checking every `interval`
+    pub fn wait_for_change(&self, interval: Duration) -> Vec<PathBuf> {
+        loop {
+            let changed = self.changed_files();
+            if !changed.is_empty() {
+                return changed.into_iter().cloned().collect();
+            }
+            thread::sleep(interval);
+        }
+    }
+}
+
+/// Last modification time of a file, or None if it cannot be read
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// Run `path` and re-run it on every change, until the process is interrupted
+pub fn watch(path: &Path, palette: Palette, options: &VmOptions) -> ! {
+    let mut watcher = FileWatcher::default();
+    let mut changed: Vec<PathBuf> = Vec::new();
+
+    loop {
+        print!("\x1B[2J\x1B[1;1H");
+        for file in &changed {
+            println!("Changed: {}", file.display());
+        }
+        let run = run_script_with_options(path, options);
+
+        match &run.result {
+            Ok(()) => println!(
+                "\n{} in {:.2?}",
+                palette.paint(Style::String, "Finished"),
+                run.elapsed
+            ),
+            Err(err) => {
+                eprintln!("{}", palette.diagnostic("Runtime error", err, None));
+                println!(
+                    "\n{} after {:.2?}",
+                    palette.paint(Style::Error, "Failed"),
+                    run.elapsed
+                );
+            }
+        }
+
+        watcher.watch(&run.files);
+        println!(
+            "Watching {} file{} for changes (Ctrl-C to stop)",
+            run.files.len(),
+            if run.files.len() == 1 { "" } else { "s" }
+        );
+
+        changed = watcher.wait_for_change(DEFAULT_POLL_INTERVAL);
+    }
+}
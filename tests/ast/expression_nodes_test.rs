@@ -352,6 +352,7 @@ fn test_lambda_expression() {
             position: pos(1, 10),
         }],
         captured_vars: None,
+        destructured: Vec::new(),
         position: pos(1, 1),
     };
     assert_eq!(expr.position(), pos(1, 1));
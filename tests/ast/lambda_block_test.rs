@@ -13,6 +13,7 @@ fn test_lambda_without_captured_vars() {
             position: pos(1, 10),
         }],
         captured_vars: None,
+        destructured: Vec::new(),
         position: pos(1, 1),
     };
     assert_eq!(expr.position(), pos(1, 1));
@@ -39,6 +40,7 @@ fn test_lambda_with_captured_vars() {
             position: pos(1, 10),
         }],
         captured_vars: Some(vec!["y".to_string()]),
+        destructured: Vec::new(),
         position: pos(1, 1),
     };
     assert_eq!(expr.position(), pos(1, 1));
@@ -73,6 +75,7 @@ fn test_lambda_with_multiple_captured_vars() {
             position: pos(1, 10),
         }],
         captured_vars: Some(vec!["a".to_string(), "b".to_string(), "c".to_string()]),
+        destructured: Vec::new(),
         position: pos(1, 1),
     };
     assert_eq!(expr.position(), pos(1, 1));
@@ -99,6 +102,7 @@ fn test_lambda_implicit_return() {
             position: pos(1, 10),
         }],
         captured_vars: None,
+        destructured: Vec::new(),
         position: pos(1, 1),
     };
     assert_eq!(expr.position(), pos(1, 1));
@@ -138,6 +142,7 @@ fn test_lambda_with_multiple_statements() {
             },
         ],
         captured_vars: None,
+        destructured: Vec::new(),
         position: pos(1, 1),
     };
     assert_eq!(expr.position(), pos(1, 1));
@@ -156,6 +161,7 @@ fn test_lambda_no_parameters() {
             position: pos(1, 5),
         }],
         captured_vars: None,
+        destructured: Vec::new(),
         position: pos(1, 1),
     };
     assert_eq!(expr.position(), pos(1, 1));
@@ -182,6 +188,7 @@ fn test_lambda_with_instance_var_capture() {
             position: pos(1, 10),
         }],
         captured_vars: Some(vec!["@count".to_string()]),
+        destructured: Vec::new(),
         position: pos(1, 1),
     };
     assert_eq!(expr.position(), pos(1, 1));
@@ -208,6 +215,7 @@ fn test_nested_lambdas() {
             position: pos(2, 10),
         }],
         captured_vars: Some(vec!["x".to_string()]),
+        destructured: Vec::new(),
         position: pos(2, 1),
     };
 
@@ -218,6 +226,7 @@ fn test_nested_lambdas() {
             position: pos(2, 1),
         }],
         captured_vars: None,
+        destructured: Vec::new(),
         position: pos(1, 1),
     };
 
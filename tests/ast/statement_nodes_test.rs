@@ -56,6 +56,7 @@ fn test_method_def() {
             position: pos(2, 3),
         }],
         position: pos(1, 1),
+        return_type: None,
     };
     assert_eq!(stmt.position(), pos(1, 1));
     assert!(stmt.is_definition());
@@ -40,6 +40,7 @@ fn test_block_assigned_to_variable() {
                     position: pos(1, 20),
                 }],
                 captured_vars: None,
+                destructured: Vec::new(),
                 position: pos(1, 13),
             },
             position: pos(1, 1),
@@ -132,6 +133,7 @@ fn test_block_passed_as_argument_with_function() {
                 },
             ],
             position: pos(1, 1),
+            return_type: None,
         },
         // Create increment block
         Statement::Assignment {
@@ -157,6 +159,7 @@ fn test_block_passed_as_argument_with_function() {
                     position: pos(6, 23),
                 }],
                 captured_vars: None,
+                destructured: Vec::new(),
                 position: pos(6, 13),
             },
             position: pos(6, 1),
@@ -232,11 +235,13 @@ fn test_block_returned_from_function_standalone() {
                         position: pos(2, 13),
                     }],
                     captured_vars: Some(vec!["n".to_string()]),
+                    destructured: Vec::new(),
                     position: pos(2, 3),
                 },
                 position: pos(2, 3),
             }],
             position: pos(1, 1),
+            return_type: None,
         },
         // Call function to get block
         Statement::Assignment {
@@ -314,6 +319,7 @@ fn test_block_with_multiple_parameters() {
                     position: pos(1, 26),
                 }],
                 captured_vars: None,
+                destructured: Vec::new(),
                 position: pos(1, 13),
             },
             position: pos(1, 1),
@@ -371,6 +377,7 @@ fn test_block_with_no_parameters() {
                     position: pos(1, 21),
                 }],
                 captured_vars: None,
+                destructured: Vec::new(),
                 position: pos(1, 14),
             },
             position: pos(1, 1),
@@ -433,6 +440,7 @@ fn test_block_stored_in_array() {
                             position: pos(2, 13),
                         }],
                         captured_vars: None,
+                        destructured: Vec::new(),
                         position: pos(2, 3),
                     },
                     Expression::Lambda {
@@ -453,6 +461,7 @@ fn test_block_stored_in_array() {
                             position: pos(3, 13),
                         }],
                         captured_vars: None,
+                        destructured: Vec::new(),
                         position: pos(3, 3),
                     },
                     Expression::Lambda {
@@ -473,6 +482,7 @@ fn test_block_stored_in_array() {
                             position: pos(4, 13),
                         }],
                         captured_vars: None,
+                        destructured: Vec::new(),
                         position: pos(4, 3),
                     },
                 ],
@@ -552,11 +562,13 @@ fn test_block_returned_from_method() {
                             position: pos(3, 15),
                         }],
                         captured_vars: Some(vec!["factor".to_string()]),
+                        destructured: Vec::new(),
                         position: pos(3, 12),
                     }),
                     position: pos(3, 5),
                 }],
                 position: pos(2, 3),
+                return_type: None,
             }],
             position: pos(1, 1),
         },
@@ -680,11 +692,13 @@ fn test_nested_block_closures() {
                             position: pos(3, 15),
                         }],
                         captured_vars: Some(vec!["outer".to_string(), "x".to_string()]),
+                        destructured: Vec::new(),
                         position: pos(3, 3),
                     },
                     position: pos(3, 3),
                 }],
                 captured_vars: Some(vec!["outer".to_string()]),
+                destructured: Vec::new(),
                 position: pos(2, 15),
             },
             position: pos(2, 1),
@@ -794,6 +808,7 @@ fn test_array_map_with_block() {
                         position: pos(2, 28),
                     }],
                     captured_vars: None,
+                    destructured: Vec::new(),
                     position: pos(2, 24),
                 })),
                 position: pos(2, 11),
@@ -859,6 +874,7 @@ fn test_range_map_with_block() {
                     position: pos(1, 28),
                 }],
                 captured_vars: None,
+                destructured: Vec::new(),
                 position: pos(1, 24),
             })),
             position: pos(1, 11),
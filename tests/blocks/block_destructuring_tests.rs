@@ -0,0 +1,168 @@
+// Destructuring block parameters: do |(a, b), i| ... end
+
+use metorex::ast::{DestructureTarget, DestructuredParameter, Expression, Statement};
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+
+fn run(source: &str) -> VirtualMachine {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens).parse().expect("Parsing failed");
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program).expect("Execution failed");
+    vm
+}
+
+/// The value of `result` after running `source`
+fn result_of(source: &str) -> Object {
+    run(source).environment().get("result").unwrap()
+}
+
+fn ints(values: &[i64]) -> Object {
+    Object::array(values.iter().copied().map(Object::Int).collect())
+}
+
+fn strings(values: &[&str]) -> Object {
+    Object::array(values.iter().map(|v| Object::string(*v)).collect())
+}
+
+fn name(name: &str) -> DestructureTarget {
+    DestructureTarget::Name(name.to_string())
+}
+
+#[test]
+fn test_parser_records_destructured_parameters() {
+    let tokens = Lexer::new("items.each do |(a, (b, c)), i|\n  a\nend").tokenize();
+    let program = Parser::new(tokens).parse().expect("Parsing failed");
+
+    let Statement::Expression {
+        expression:
+            Expression::MethodCall {
+                trailing_block: Some(block),
+                ..
+            },
+        ..
+    } = &program[0]
+    else {
+        panic!("Expected a method call with a block, got {:?}", program[0]);
+    };
+    let Expression::Lambda {
+        parameters,
+        destructured,
+        ..
+    } = block.as_ref()
+    else {
+        panic!("Expected a block, got {:?}", block);
+    };
+
+    assert_eq!(
+        parameters,
+        &vec!["(a, (b, c))".to_string(), "i".to_string()]
+    );
+    assert_eq!(
+        destructured,
+        &vec![DestructuredParameter {
+            index: 0,
+            targets: vec![
+                name("a"),
+                DestructureTarget::Nested(vec![name("b"), name("c")])
+            ],
+        }]
+    );
+}
+
+#[test]
+fn test_unclosed_destructured_parameter_is_a_parse_error() {
+    let tokens = Lexer::new("[1].each do |(a|\nend").tokenize();
+    let error = Parser::new(tokens).parse().unwrap_err();
+    assert!(
+        format!("{:?}", error).contains("Expected ')' after destructured parameters"),
+        "{:?}",
+        error
+    );
+}
+
+#[test]
+fn test_hash_each_destructures_pairs() {
+    let source = r#"
+result = []
+{"a" => 1, "b" => 2}.each do |(key, value)|
+  result.push(key + value.to_s)
+end
+"#;
+    assert_eq!(result_of(source), strings(&["a1", "b2"]));
+}
+
+#[test]
+fn test_each_with_index_destructures_the_element() {
+    let source = r#"
+result = []
+[[1, 2], [3, 4]].each_with_index do |(a, b), i|
+  result.push(a * b + i)
+end
+"#;
+    assert_eq!(result_of(source), ints(&[2, 13]));
+}
+
+#[test]
+fn test_brace_blocks_destructure() {
+    let source = "result = []\n[[1, 2], [3, 4]].each { |(a, b)| result.push(a + b) }";
+    assert_eq!(result_of(source), ints(&[3, 7]));
+}
+
+#[test]
+fn test_nested_destructuring() {
+    let source = r#"
+result = []
+[[1, [2, 3]], [4, [5, 6]]].each do |(a, (b, c))|
+  result.push(a + b + c)
+end
+"#;
+    assert_eq!(result_of(source), ints(&[6, 15]));
+}
+
+#[test]
+fn test_missing_elements_bind_nil_and_extras_are_dropped() {
+    let source = r#"
+result = []
+[[1], [2, 3, 4], 5].each do |(a, b)|
+  result.push([a, b])
+end
+"#;
+    assert_eq!(
+        result_of(source),
+        Object::array(vec![
+            Object::array(vec![Object::Int(1), Object::Nil]),
+            ints(&[2, 3]),
+            Object::array(vec![Object::Int(5), Object::Nil]),
+        ])
+    );
+}
+
+#[test]
+fn test_lambdas_destructure_their_arguments() {
+    let source = "area = lambda |(w, h)| w * h end\nresult = area.call([6, 7])";
+    assert_eq!(result_of(source), Object::Int(42));
+}
+
+#[test]
+fn test_destructured_names_are_local_to_the_block() {
+    let vm = run("a = 10\n[[1, 2]].each do |(a, b)|\n  a\nend");
+    assert_eq!(vm.environment().get("a"), Some(Object::Int(10)));
+    assert_eq!(vm.environment().get("b"), None);
+}
+
+#[test]
+fn test_each_with_index_yields_elements_and_indices() {
+    let source = "result = []\n[:a, :b].each_with_index do |x, i|\n  result.push([x, i])\nend";
+    let expected = Object::array(vec![
+        Object::array(vec![Object::Symbol("a".to_string().into()), Object::Int(0)]),
+        Object::array(vec![Object::Symbol("b".to_string().into()), Object::Int(1)]),
+    ]);
+    assert_eq!(result_of(source), expected);
+    assert_eq!(
+        result_of("result = [:a, :b].each_with_index.to_a"),
+        expected
+    );
+}
@@ -36,6 +36,7 @@ fn block_returns_last_expression() {
                     position: pos(1, 9),
                 }],
                 captured_vars: None,
+                destructured: Vec::new(),
                 position: pos(1, 5),
             },
             position: pos(1, 1),
@@ -105,6 +106,7 @@ fn block_captures_outer_variable() {
                     position: pos(2, 11),
                 }],
                 captured_vars: Some(vec!["outer".to_string()]),
+                destructured: Vec::new(),
                 position: pos(2, 5),
             },
             position: pos(2, 1),
@@ -155,6 +157,7 @@ fn block_argument_mismatch_raises_error() {
                     position: pos(1, 12),
                 }],
                 captured_vars: None,
+                destructured: Vec::new(),
                 position: pos(1, 5),
             },
             position: pos(1, 1),
@@ -214,6 +217,7 @@ fn block_return_statement_exits_early() {
                     },
                 ],
                 captured_vars: None,
+                destructured: Vec::new(),
                 position: pos(1, 5),
             },
             position: pos(1, 1),
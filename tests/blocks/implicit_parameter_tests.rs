@@ -0,0 +1,143 @@
+// Implicit block parameters: arr.map { _1 * 2 } and arr.map { it.name }
+
+use metorex::ast::{Expression, Statement};
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::resolver::implicit_block_parameters;
+use metorex::vm::VirtualMachine;
+
+fn parse(source: &str) -> Result<Vec<Statement>, String> {
+    let tokens = Lexer::new(source).tokenize();
+    Parser::new(tokens).parse().map_err(|errors| {
+        errors
+            .iter()
+            .map(|error| error.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    })
+}
+
+/// The value of `result` after running `source`
+fn result_of(source: &str) -> Object {
+    let program = parse(source).expect("Parsing failed");
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program).expect("Execution failed");
+    vm.environment().get("result").unwrap()
+}
+
+/// The parameters of the block assigned to `block` in `source`
+fn parameters_of(source: &str) -> Vec<String> {
+    let program = parse(source).expect("Parsing failed");
+    match &program[0] {
+        Statement::Assignment {
+            value: Expression::Lambda { parameters, .. },
+            ..
+        } => parameters.clone(),
+        other => panic!("Expected a block assignment, got {:?}", other),
+    }
+}
+
+fn ints(values: &[i64]) -> Object {
+    Object::array(values.iter().copied().map(Object::Int).collect())
+}
+
+#[test]
+fn test_numbered_parameters_set_the_block_arity() {
+    assert_eq!(parameters_of("block = lambda do _1 * 2 end"), vec!["_1"]);
+    assert_eq!(
+        parameters_of("block = lambda do _3 end"),
+        vec!["_1", "_2", "_3"]
+    );
+    assert_eq!(parameters_of("block = lambda do it end"), vec!["it"]);
+    assert!(parameters_of("block = lambda do 1 end").is_empty());
+}
+
+#[test]
+fn test_numbered_parameters_in_brace_blocks() {
+    assert_eq!(
+        result_of("result = [1, 2, 3].map { _1 * 2 }"),
+        ints(&[2, 4, 6])
+    );
+    assert_eq!(
+        result_of("result = []\n{\"a\" => 1}.each { result.push(_2) }"),
+        ints(&[1])
+    );
+}
+
+#[test]
+fn test_it_names_the_only_argument() {
+    assert_eq!(
+        result_of("result = [1, 2, 3].map { it + 1 }"),
+        ints(&[2, 3, 4])
+    );
+    assert_eq!(
+        result_of("result = [\"ab\", \"c\"].map do\n  it.length\nend"),
+        ints(&[2, 1])
+    );
+}
+
+#[test]
+fn test_lambdas_take_implicit_parameters() {
+    assert_eq!(
+        result_of("add = lambda do _1 + _2 end\nresult = add.call(3, 4)"),
+        Object::Int(7)
+    );
+}
+
+#[test]
+fn test_nested_blocks_keep_their_own_parameters() {
+    assert_eq!(
+        result_of("result = [[1, 2], [3]].map { _1.map { _1 * 10 } }"),
+        Object::array(vec![ints(&[10, 20]), ints(&[30])])
+    );
+}
+
+#[test]
+fn test_numbered_parameters_cannot_mix_with_a_parameter_list() {
+    let error = parse("[1].map { |x| _1 }").unwrap_err();
+    assert!(
+        error.contains("Numbered parameter '_1' cannot be used in a block with a parameter list"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_it_cannot_mix_with_numbered_parameters() {
+    let error = parse("[1].map { _1 + it }").unwrap_err();
+    assert!(
+        error.contains("'it' cannot be used in a block that uses numbered parameters"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_it_is_an_ordinary_name_outside_implicit_blocks() {
+    let program = parse("it = 5\nresult = [1].map { |x| it }").unwrap();
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(ints(&[5])));
+
+    // `it "does something" do ... end` calls a method named it
+    let program = parse("describe = lambda do\n  it(\"works\")\nend").unwrap();
+    let Statement::Assignment {
+        value: Expression::Lambda { parameters, .. },
+        ..
+    } = &program[0]
+    else {
+        panic!("Expected a block assignment");
+    };
+    assert!(parameters.is_empty());
+}
+
+#[test]
+fn test_definitions_in_the_body_do_not_count() {
+    let program = parse("def helper\n  _1\nend").unwrap();
+    assert!(
+        implicit_block_parameters(&program, false)
+            .unwrap()
+            .is_empty()
+    );
+}
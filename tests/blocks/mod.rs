@@ -1,4 +1,6 @@
 mod block_as_object_tests;
+mod block_destructuring_tests;
 mod block_execution_tests;
 mod function_definition_tests;
+mod implicit_parameter_tests;
 mod lambda_tests;
@@ -1,6 +1,7 @@
 // Tests for attr_reader, attr_writer, and attr_accessor
 
 use metorex::lexer::Lexer;
+use metorex::object::Object;
 use metorex::parser::Parser;
 use metorex::vm::VirtualMachine;
 
@@ -26,6 +27,17 @@ fn execute_source(source: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Run `source` and return the value of `result` as text
+fn eval_result(source: &str) -> String {
+    let program = Parser::new(Lexer::new(source).tokenize()).parse().unwrap();
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program).unwrap();
+    vm.environment()
+        .get("result")
+        .unwrap_or(Object::Nil)
+        .to_string()
+}
+
 #[test]
 fn test_attr_reader_single() {
     let source = r#"
@@ -254,3 +266,70 @@ puts p.y
     let result = execute_source(source);
     assert!(result.is_ok());
 }
+
+#[test]
+fn test_attr_names_in_parentheses_and_strings() {
+    let result = eval_result(
+        r#"
+class Person
+  attr_accessor(:name, :age)
+  attr_reader "nickname"
+
+  def initialize(name)
+    @name = name
+    @nickname = "the " + name
+  end
+end
+
+person = Person.new("ada")
+person.age = 36
+result = [person.name, person.age, person.nickname]
+"#,
+    );
+    assert_eq!(result, "[ada, 36, the ada]");
+}
+
+#[test]
+fn test_attr_names_must_be_identifiers() {
+    let source = "class Person\n  attr_reader \"first name\"\nend\n";
+    let errors = Parser::new(Lexer::new(source).tokenize())
+        .parse()
+        .unwrap_err();
+    assert!(
+        errors[0]
+            .to_string()
+            .contains("Expected attribute name symbol like :name"),
+        "{}",
+        errors[0]
+    );
+}
+
+#[test]
+fn test_attr_methods_in_modules_are_mixed_in() {
+    let result = eval_result(
+        r#"
+module Labelled
+  attr_accessor :label
+end
+
+class Box
+  include Labelled
+end
+
+box = Box.new
+box.label = "fragile"
+result = box.label
+"#,
+    );
+    assert_eq!(result, "fragile");
+}
+
+#[test]
+fn test_attr_methods_outside_a_class_are_an_error() {
+    let err = execute_source("attr_reader :name\n").unwrap_err();
+    assert!(
+        err.contains("inside a class or module definition"),
+        "{}",
+        err
+    );
+}
@@ -0,0 +1,205 @@
+// Tests for the builtin catalog and its JSON export
+
+use metorex::ast::TypeAnnotation;
+use metorex::builtin_catalog::{
+    self, CLASSES, FUNCTIONS, METHODS, MethodKind, ParamKind, max_arity, min_arity,
+};
+use metorex::lexer::Position;
+use metorex::object::Object;
+use metorex::typecheck::builtin_type_names;
+use metorex::vm::VirtualMachine;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Whether a catalog type names a builtin type or a catalogued class
+fn is_known_type(ty: &str) -> bool {
+    let annotation = TypeAnnotation::parse(ty, Position::default());
+    annotation.name == "Any"
+        || builtin_type_names(&annotation.name).is_some()
+        || builtin_catalog::find_class(&annotation.name).is_some()
+}
+
+#[test]
+fn test_global_classes_match_the_registered_builtins() {
+    let registered: BTreeSet<String> = VirtualMachine::new()
+        .builtins()
+        .all_classes()
+        .into_keys()
+        .collect();
+    let catalogued: BTreeSet<String> = CLASSES
+        .iter()
+        .filter(|class| class.global)
+        .map(|class| class.name.to_string())
+        .collect();
+    assert_eq!(catalogued, registered);
+}
+
+#[test]
+fn test_superclasses_match_the_builtin_hierarchy() {
+    let vm = VirtualMachine::new();
+    for (name, class) in vm.builtins().all_classes() {
+        let catalogued = builtin_catalog::find_class(&name).unwrap();
+        assert_eq!(
+            catalogued.superclass,
+            class
+                .superclass()
+                .map(|superclass| superclass.name().to_string())
+                .as_deref(),
+            "superclass of {}",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_registered_builtin_methods_are_catalogued() {
+    let vm = VirtualMachine::new();
+    let builtins = vm.builtins();
+    let mut classes = builtins.all_classes().into_values().collect::<Vec<_>>();
+    classes.push(builtins.sqlite_statement_class.clone());
+    classes.push(builtins.identity_hash_class.clone());
+    classes.push(builtins.ast_node_class.clone());
+
+    for class in classes {
+        for name in class.method_names() {
+            assert!(
+                builtin_catalog::find_method(class.name(), &name, MethodKind::Instance).is_some(),
+                "{}#{} is registered but not catalogued",
+                class.name(),
+                name
+            );
+        }
+    }
+}
+
+#[test]
+fn test_native_functions_are_catalogued() {
+    let vm = VirtualMachine::new();
+    for (name, value) in vm.globals().iter() {
+        if let Object::NativeFunction(_) = value {
+            assert!(
+                builtin_catalog::find_function(name).is_some(),
+                "{} is not catalogued",
+                name
+            );
+        }
+    }
+    for function in FUNCTIONS {
+        assert_eq!(
+            vm.environment().get(function.name),
+            Some(Object::NativeFunction(function.name.to_string()))
+        );
+    }
+}
+
+#[test]
+fn test_catalog_entries_are_well_formed() {
+    let mut seen = BTreeSet::new();
+    for method in METHODS {
+        assert!(
+            builtin_catalog::find_class(method.class).is_some(),
+            "{} is not a catalogued class",
+            method.class
+        );
+        assert!(
+            seen.insert((method.class, method.name, method.kind == MethodKind::Class)),
+            "{}#{} is catalogued twice",
+            method.class,
+            method.name
+        );
+        assert!(
+            !method.doc.is_empty(),
+            "{}#{} has no doc",
+            method.class,
+            method.name
+        );
+        assert!(is_known_type(method.returns), "{}", method.returns);
+        for parameter in method.parameters {
+            assert!(parameter.ty.is_none_or(is_known_type), "{:?}", parameter);
+        }
+    }
+}
+
+#[test]
+fn test_find_method_walks_superclasses() {
+    let message =
+        builtin_catalog::find_method("TypeError", "message", MethodKind::Instance).unwrap();
+    assert_eq!(message.class, "Exception");
+    let to_s = builtin_catalog::find_method("String", "to_s", MethodKind::Instance).unwrap();
+    assert_eq!(to_s.class, "Object");
+    assert!(builtin_catalog::find_method("URI", "parse", MethodKind::Instance).is_none());
+    assert!(builtin_catalog::find_method("URI", "parse", MethodKind::Class).is_some());
+    assert!(builtin_catalog::find_method("Nope", "to_s", MethodKind::Instance).is_none());
+}
+
+#[test]
+fn test_arity_ignores_blocks_and_stops_at_rest_parameters() {
+    let arity = |class: &str, name: &str| {
+        let method = builtin_catalog::find_method(class, name, MethodKind::Instance).unwrap();
+        (min_arity(method.parameters), max_arity(method.parameters))
+    };
+    assert_eq!(arity("String", "length"), (0, Some(0)));
+    assert_eq!(arity("String", "chomp"), (0, Some(1)));
+    assert_eq!(arity("Array", "map"), (0, Some(0)));
+    assert_eq!(arity("Array", "grep"), (1, Some(1)));
+    assert_eq!(arity("Array", "zip"), (0, None));
+    assert_eq!(arity("Integer", "format"), (0, Some(1)));
+}
+
+#[test]
+fn test_signature_renders_parameter_kinds() {
+    let grep = builtin_catalog::find_method("Array", "grep", MethodKind::Instance).unwrap();
+    assert_eq!(
+        builtin_catalog::signature(grep.parameters),
+        "pattern, [&block]"
+    );
+    let chomp = builtin_catalog::find_method("String", "chomp", MethodKind::Instance).unwrap();
+    assert_eq!(
+        builtin_catalog::signature(chomp.parameters),
+        "[suffix: String]"
+    );
+    let zip = builtin_catalog::find_method("Array", "zip", MethodKind::Instance).unwrap();
+    assert_eq!(builtin_catalog::signature(zip.parameters), "*others: Array");
+}
+
+#[test]
+fn test_json_export_lists_classes_methods_and_functions() {
+    let catalog: Value = serde_json::from_str(&builtin_catalog::to_json()).unwrap();
+    assert_eq!(catalog["version"], builtin_catalog::CATALOG_FORMAT_VERSION);
+
+    let classes = catalog["classes"].as_array().unwrap();
+    assert_eq!(classes.len(), CLASSES.len());
+    let array = classes
+        .iter()
+        .find(|class| class["name"] == "Array")
+        .unwrap();
+    assert_eq!(array["superclass"], "Object");
+    assert_eq!(array["global"], true);
+
+    let zip = array["methods"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|method| method["name"] == "zip")
+        .unwrap();
+    assert_eq!(zip["kind"], "instance");
+    assert_eq!(zip["min_arity"], 0);
+    assert!(zip["max_arity"].is_null());
+    assert_eq!(zip["returns"], "Array");
+    assert_eq!(zip["parameters"][0]["kind"], "rest");
+    assert_eq!(zip["parameters"][0]["type"], "Array");
+
+    let functions = catalog["functions"].as_array().unwrap();
+    assert_eq!(functions.len(), FUNCTIONS.len());
+    let puts = functions.iter().find(|f| f["name"] == "puts").unwrap();
+    assert!(puts.get("kind").is_none());
+    assert_eq!(puts["returns"], "Nil");
+}
+
+#[test]
+fn test_block_parameters_are_marked() {
+    let reduce = builtin_catalog::find_method("Array", "reduce", MethodKind::Instance).unwrap();
+    assert_eq!(reduce.parameters[1].kind, ParamKind::Block);
+    let times = builtin_catalog::find_method("Integer", "times", MethodKind::Instance).unwrap();
+    assert_eq!(times.parameters[0].kind, ParamKind::OptionalBlock);
+}
@@ -83,6 +83,7 @@ fn test_exception_hierarchy_creation() {
     assert_eq!(builtins.runtime_error_class.name(), "RuntimeError");
     assert_eq!(builtins.type_error_class.name(), "TypeError");
     assert_eq!(builtins.value_error_class.name(), "ValueError");
+    assert_eq!(builtins.argument_error_class.name(), "ArgumentError");
 }
 
 #[test]
@@ -287,12 +288,17 @@ fn test_all_classes() {
     let builtins = BuiltinClasses::new();
     let all = builtins.all_classes();
 
-    assert_eq!(all.len(), 12);
+    assert_eq!(all.len(), 41);
     assert!(all.contains_key("Object"));
     assert!(all.contains_key("String"));
     assert!(all.contains_key("Integer"));
     assert!(all.contains_key("Float"));
+    assert!(all.contains_key("Decimal"));
+    assert!(all.contains_key("BigDecimal"));
     assert!(all.contains_key("Array"));
+    assert!(all.contains_key("StringBuilder"));
+    assert!(all.contains_key("CodeBuilder"));
+    assert!(all.contains_key("Enumerator"));
     assert!(all.contains_key("Hash"));
     assert!(all.contains_key("Set"));
     assert!(all.contains_key("Exception"));
@@ -300,6 +306,29 @@ fn test_all_classes() {
     assert!(all.contains_key("RuntimeError"));
     assert!(all.contains_key("TypeError"));
     assert!(all.contains_key("ValueError"));
+    assert!(all.contains_key("ArgumentError"));
+    assert!(all.contains_key("NameError"));
+    assert!(all.contains_key("NoMethodError"));
+    assert!(all.contains_key("SyntaxError"));
+    assert!(all.contains_key("ConcurrentModificationError"));
+    assert!(all.contains_key("AssertionError"));
+    assert!(all.contains_key("StopIteration"));
+    assert!(all.contains_key("Interrupt"));
+    assert!(all.contains_key("Timeout"));
+    assert!(!all.contains_key("Timeout::Error"));
+    assert!(all.contains_key("GC"));
+    assert!(all.contains_key("Module"));
+    assert!(all.contains_key("File"));
+    assert!(all.contains_key("URI"));
+    assert!(all.contains_key("Base64"));
+    assert!(all.contains_key("Digest"));
+    assert!(all.contains_key("Inflector"));
+    assert!(all.contains_key("SQLite"));
+    assert!(all.contains_key("YAML"));
+    assert!(all.contains_key("TOML"));
+    assert!(all.contains_key("TracePoint"));
+    assert!(all.contains_key("Schema"));
+    assert!(all.contains_key("Metorex"));
 }
 
 #[test]
@@ -324,6 +353,8 @@ fn test_init_object_methods() {
     assert!(object_class.find_method("to_s").is_some());
     assert!(object_class.find_method("class").is_some());
     assert!(object_class.find_method("respond_to?").is_some());
+    assert!(object_class.find_method("send").is_some());
+    assert!(object_class.find_method("__send__").is_some());
 }
 
 #[test]
@@ -74,6 +74,7 @@ fn test_class_with_initialize_method() {
                 },
             ],
             position: pos(2, 3),
+            return_type: None,
         }],
         position: pos(1, 1),
     }];
@@ -170,6 +171,7 @@ fn test_create_instance_with_initialize() {
                     },
                 ],
                 position: pos(2, 3),
+                return_type: None,
             }],
             position: pos(1, 1),
         },
@@ -241,6 +243,7 @@ fn test_instance_variable_access() {
                         position: pos(3, 5),
                     }],
                     position: pos(2, 3),
+                    return_type: None,
                 },
                 Statement::MethodDef {
                     name: "get_count".to_string(),
@@ -253,6 +256,7 @@ fn test_instance_variable_access() {
                         position: pos(6, 5),
                     }],
                     position: pos(5, 3),
+                    return_type: None,
                 },
             ],
             position: pos(1, 1),
@@ -356,6 +360,7 @@ fn test_class_variable_access() {
                         position: pos(4, 5),
                     }],
                     position: pos(3, 3),
+                    return_type: None,
                 },
                 Statement::MethodDef {
                     name: "get_total".to_string(),
@@ -368,6 +373,7 @@ fn test_class_variable_access() {
                         position: pos(7, 5),
                     }],
                     position: pos(6, 3),
+                    return_type: None,
                 },
             ],
             position: pos(1, 1),
@@ -460,6 +466,7 @@ fn test_class_with_inheritance() {
                     position: pos(3, 5),
                 }],
                 position: pos(2, 3),
+                return_type: None,
             }],
             position: pos(1, 1),
         },
@@ -111,6 +111,7 @@ fn test_class_with_single_method() {
                 position: pos(3, 5),
             }],
             position: pos(2, 3),
+            return_type: None,
         }],
         position: pos(1, 1),
     };
@@ -147,6 +148,7 @@ fn test_class_with_multiple_methods() {
                     position: pos(3, 5),
                 }],
                 position: pos(2, 3),
+                return_type: None,
             },
             Statement::MethodDef {
                 name: "subtract".to_string(),
@@ -170,6 +172,7 @@ fn test_class_with_multiple_methods() {
                     position: pos(7, 5),
                 }],
                 position: pos(6, 3),
+                return_type: None,
             },
         ],
         position: pos(1, 1),
@@ -216,6 +219,7 @@ fn test_class_with_constructor() {
                 },
             ],
             position: pos(2, 3),
+            return_type: None,
         }],
         position: pos(1, 1),
     };
@@ -260,6 +264,7 @@ fn test_class_with_constructor_and_methods() {
                     },
                 ],
                 position: pos(2, 3),
+                return_type: None,
             },
             Statement::MethodDef {
                 name: "area".to_string(),
@@ -280,6 +285,7 @@ fn test_class_with_constructor_and_methods() {
                     position: pos(8, 5),
                 }],
                 position: pos(7, 3),
+                return_type: None,
             },
         ],
         position: pos(1, 1),
@@ -310,6 +316,7 @@ fn test_class_with_instance_variable_initialization() {
                 position: pos(3, 5),
             }],
             position: pos(2, 3),
+            return_type: None,
         }],
         position: pos(1, 1),
     };
@@ -365,6 +372,7 @@ fn test_class_with_multiple_instance_variables() {
                 },
             ],
             position: pos(2, 3),
+            return_type: None,
         }],
         position: pos(1, 1),
     };
@@ -394,6 +402,7 @@ fn test_class_with_class_variable() {
                 position: pos(3, 5),
             }],
             position: pos(2, 3),
+            return_type: None,
         }],
         position: pos(1, 1),
     };
@@ -423,6 +432,7 @@ fn test_class_method_with_default_parameters() {
             ],
             body: vec![],
             position: pos(2, 3),
+            return_type: None,
         }],
         position: pos(1, 1),
     };
@@ -440,6 +450,7 @@ fn test_class_method_with_variadic_parameters() {
             parameters: vec![Parameter::variadic("messages".to_string(), pos(2, 11))],
             body: vec![],
             position: pos(2, 3),
+            return_type: None,
         }],
         position: pos(1, 1),
     };
@@ -457,6 +468,7 @@ fn test_class_method_with_keyword_parameters() {
             parameters: vec![Parameter::keyword("options".to_string(), pos(2, 17))],
             body: vec![],
             position: pos(2, 3),
+            return_type: None,
         }],
         position: pos(1, 1),
     };
@@ -487,6 +499,7 @@ fn test_class_with_getter_and_setter() {
                     position: pos(3, 5),
                 }],
                 position: pos(2, 3),
+                return_type: None,
             },
             Statement::MethodDef {
                 name: "get_value".to_string(),
@@ -499,6 +512,7 @@ fn test_class_with_getter_and_setter() {
                     position: pos(7, 5),
                 }],
                 position: pos(6, 3),
+                return_type: None,
             },
             Statement::MethodDef {
                 name: "set_value".to_string(),
@@ -515,6 +529,7 @@ fn test_class_with_getter_and_setter() {
                     position: pos(11, 5),
                 }],
                 position: pos(10, 3),
+                return_type: None,
             },
         ],
         position: pos(1, 1),
@@ -564,6 +579,7 @@ fn test_class_method_with_conditional() {
                 position: pos(3, 5),
             }],
             position: pos(2, 3),
+            return_type: None,
         }],
         position: pos(1, 1),
     };
@@ -666,6 +682,7 @@ fn test_class_method_with_loop() {
                 },
             ],
             position: pos(2, 3),
+            return_type: None,
         }],
         position: pos(1, 1),
     };
@@ -691,6 +708,7 @@ fn test_inherited_class_with_override() {
                 position: pos(3, 5),
             }],
             position: pos(2, 3),
+            return_type: None,
         }],
         position: pos(1, 1),
     };
@@ -709,6 +727,7 @@ fn test_inherited_class_with_override() {
                 position: pos(9, 5),
             }],
             position: pos(8, 3),
+            return_type: None,
         }],
         position: pos(7, 1),
     };
@@ -733,6 +752,7 @@ fn test_inherited_class_with_additional_methods() {
                     position: pos(3, 5),
                 }],
                 position: pos(2, 3),
+                return_type: None,
             },
             Statement::MethodDef {
                 name: "fetch".to_string(),
@@ -745,6 +765,7 @@ fn test_inherited_class_with_additional_methods() {
                     position: pos(7, 5),
                 }],
                 position: pos(6, 3),
+                return_type: None,
             },
         ],
         position: pos(1, 1),
@@ -782,8 +803,10 @@ fn test_class_with_nested_function() {
                     position: pos(4, 7),
                 }],
                 position: pos(3, 5),
+                return_type: None,
             }],
             position: pos(2, 3),
+            return_type: None,
         }],
         position: pos(1, 1),
     };
@@ -819,6 +842,7 @@ fn test_class_with_self_reference() {
                 },
             ],
             position: pos(2, 3),
+            return_type: None,
         }],
         position: pos(1, 1),
     };
@@ -869,6 +893,7 @@ fn test_class_with_exception_handling() {
                 position: pos(3, 5),
             }],
             position: pos(2, 3),
+            return_type: None,
         }],
         position: pos(1, 1),
     };
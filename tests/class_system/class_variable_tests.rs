@@ -0,0 +1,219 @@
+// Tests for class variable inheritance semantics and class variable reflection
+
+use crate::common::execute_source;
+use metorex::class::Class;
+use metorex::object::Object;
+use std::rc::Rc;
+
+fn symbols(names: &[&str]) -> Object {
+    Object::array(
+        names
+            .iter()
+            .map(|name| Object::Symbol(Rc::new(name.to_string())))
+            .collect(),
+    )
+}
+
+const COUNTERS: &str = r#"
+class Base
+  @@count = 0
+
+  def bump()
+    @@count += 1
+  end
+
+  def count()
+    @@count
+  end
+end
+
+class Child < Base
+  def bump_by_ten()
+    @@count += 10
+  end
+end
+"#;
+
+// ============================================================================
+// Class API
+// ============================================================================
+
+#[test]
+fn test_lookup_class_var_walks_superclasses() {
+    let base = Rc::new(Class::new("Base", None));
+    let child = Class::new("Child", Some(Rc::clone(&base)));
+    base.set_class_var("count", Object::Int(1));
+
+    assert_eq!(child.lookup_class_var("count"), Some(Object::Int(1)));
+    assert_eq!(child.get_class_var("count"), None);
+    assert!(child.class_var_defined("count"));
+    assert!(!child.class_var_defined("missing"));
+}
+
+#[test]
+fn test_assign_class_var_updates_defining_ancestor() {
+    let base = Rc::new(Class::new("Base", None));
+    let child = Class::new("Child", Some(Rc::clone(&base)));
+    base.set_class_var("count", Object::Int(1));
+
+    child.assign_class_var("count", Object::Int(2));
+    child.assign_class_var("own", Object::Int(3));
+
+    assert_eq!(base.get_class_var("count"), Some(Object::Int(2)));
+    assert_eq!(base.get_class_var("own"), None);
+    assert_eq!(child.get_class_var("own"), Some(Object::Int(3)));
+    assert_eq!(child.class_var_names(), vec!["count", "own"]);
+}
+
+// ============================================================================
+// Inheritance semantics
+// ============================================================================
+
+#[test]
+fn test_class_variable_is_shared_with_subclasses() {
+    let source = format!(
+        "{}\nBase.new.bump\nChild.new.bump_by_ten\nChild.new.bump\ntotal = Base.new.count",
+        COUNTERS
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(vm.environment().get("total"), Some(Object::Int(12)));
+}
+
+#[test]
+fn test_subclass_variable_defined_first_is_not_shared() {
+    let source = r#"
+class Parent
+  def read()
+    @@label
+  end
+end
+
+class Kid < Parent
+  @@label = "kid"
+
+  def label()
+    @@label
+  end
+end
+
+result = Kid.new.label
+defined = Parent.class_variable_defined?(:@@label)
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(Object::string("kid")));
+    assert_eq!(vm.environment().get("defined"), Some(Object::Bool(false)));
+}
+
+#[test]
+fn test_reading_undefined_class_variable_raises_name_error() {
+    let source = r#"
+class Empty
+  def read()
+    @@missing
+  end
+end
+
+Empty.new.read
+"#;
+    let error = execute_source(source).err().unwrap();
+    assert!(error.contains("NameError: uninitialized class variable @@missing in Empty"));
+}
+
+#[test]
+fn test_undefined_class_variable_error_can_be_rescued() {
+    let source = r#"
+class Empty
+  def read()
+    @@missing
+  end
+end
+
+message = nil
+begin
+  Empty.new.read
+rescue NameError => e
+  message = e.message
+end
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(
+        vm.environment().get("message"),
+        Some(Object::string(
+            "uninitialized class variable @@missing in Empty"
+        ))
+    );
+}
+
+// ============================================================================
+// Reflection
+// ============================================================================
+
+#[test]
+fn test_class_variable_get_and_set() {
+    let source = format!(
+        r#"{}
+Base.new.bump
+before = Child.class_variable_get(:@@count)
+returned = Child.class_variable_set(:@@count, 40)
+after = Base.class_variable_get("@@count")
+"#,
+        COUNTERS
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(vm.environment().get("before"), Some(Object::Int(1)));
+    assert_eq!(vm.environment().get("returned"), Some(Object::Int(40)));
+    assert_eq!(vm.environment().get("after"), Some(Object::Int(40)));
+}
+
+#[test]
+fn test_class_variable_defined() {
+    let source = format!(
+        r#"{}
+on_base = Base.class_variable_defined?(:@@count)
+on_child = Child.class_variable_defined?(:@@count)
+missing = Child.class_variable_defined?(:@@missing)
+"#,
+        COUNTERS
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(vm.environment().get("on_base"), Some(Object::Bool(true)));
+    assert_eq!(vm.environment().get("on_child"), Some(Object::Bool(true)));
+    assert_eq!(vm.environment().get("missing"), Some(Object::Bool(false)));
+}
+
+#[test]
+fn test_class_variables_includes_inherited_names() {
+    let source = format!(
+        r#"{}
+Child.class_variable_set(:@@extra, true)
+child_vars = Child.class_variables
+base_vars = Base.class_variables
+"#,
+        COUNTERS
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(
+        vm.environment().get("child_vars"),
+        Some(symbols(&["@@count", "@@extra"]))
+    );
+    assert_eq!(
+        vm.environment().get("base_vars"),
+        Some(symbols(&["@@count"]))
+    );
+}
+
+#[test]
+fn test_class_variable_get_undefined_raises_name_error() {
+    let error = execute_source(&format!("{}\nBase.class_variable_get(:@@nope)", COUNTERS))
+        .err()
+        .unwrap();
+    assert!(error.contains("uninitialized class variable @@nope in Base"));
+}
+
+#[test]
+fn test_class_variable_name_must_start_with_at_at() {
+    let error = execute_source(&format!("{}\nBase.class_variable_get(:count)", COUNTERS))
+        .err()
+        .unwrap();
+    assert!(error.contains("'count' is not allowed as a class variable name"));
+}
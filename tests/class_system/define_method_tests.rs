@@ -0,0 +1,135 @@
+// Tests for defining instance methods at runtime with `define_method`
+
+use crate::common::execute_source;
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+
+fn execute_error(source: &str) -> String {
+    match execute_source(source) {
+        Ok(_) => panic!("expected an error"),
+        Err(err) => err,
+    }
+}
+
+fn get(vm: &VirtualMachine, name: &str) -> Object {
+    vm.environment().get(name).unwrap()
+}
+
+const POINT: &str = r#"
+class Point
+  def initialize(x)
+    @x = x
+  end
+end
+"#;
+
+#[test]
+fn test_define_method_installs_an_instance_method() {
+    let source = format!(
+        "{}{}",
+        POINT,
+        r#"
+name = Point.define_method(:scaled) do |factor|
+  @x * factor
+end
+point = Point.new(3)
+result = point.scaled(4)
+responds = point.respond_to?(:scaled)
+"#
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(get(&vm, "result"), Object::Int(12));
+    assert_eq!(get(&vm, "responds"), Object::Bool(true));
+    assert_eq!(get(&vm, "name"), vm.symbol("scaled"));
+}
+
+#[test]
+fn test_define_method_accepts_a_string_name() {
+    let source = format!(
+        "{}{}",
+        POINT,
+        r#"
+Point.define_method("x") do
+  @x
+end
+result = Point.new(7).x
+"#
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(get(&vm, "result"), Object::Int(7));
+}
+
+#[test]
+fn test_defined_method_shares_captured_variables() {
+    let source = format!(
+        "{}{}",
+        POINT,
+        r#"
+calls = 0
+Point.define_method(:x) do
+  calls += 1
+  @x
+end
+point = Point.new(5)
+point.x
+point.x
+calls += 10
+total = point.x
+"#
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(get(&vm, "total"), Object::Int(5));
+    assert_eq!(get(&vm, "calls"), Object::Int(13));
+}
+
+#[test]
+fn test_defined_methods_keep_block_locals_after_the_block_returns() {
+    let source = format!(
+        "{}{}",
+        POINT,
+        r#"
+["north", "south"].each do |direction|
+  label = direction + "!"
+  Point.define_method(direction) do
+    label
+  end
+end
+point = Point.new(0)
+north = point.north
+south = point.south
+"#
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(get(&vm, "north"), Object::string("north!"));
+    assert_eq!(get(&vm, "south"), Object::string("south!"));
+}
+
+#[test]
+fn test_parameters_shadow_captured_variables() {
+    let source = format!(
+        "{}{}",
+        POINT,
+        r#"
+factor = 100
+Point.define_method(:scaled) do |factor|
+  @x * factor
+end
+result = Point.new(2).scaled(3)
+"#
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(get(&vm, "result"), Object::Int(6));
+    assert_eq!(get(&vm, "factor"), Object::Int(100));
+}
+
+#[test]
+fn test_define_method_checks_its_arguments() {
+    let err = execute_error(&format!("{}Point.define_method(:x)\n", POINT));
+    assert!(err.contains("define_method"), "{}", err);
+
+    let err = execute_error(&format!("{}Point.define_method(:x, 1)\n", POINT));
+    assert!(err.contains("Block"), "{}", err);
+
+    let err = execute_error(&format!("{}Point.define_method(1) do\n  1\nend\n", POINT));
+    assert!(err.contains("Symbol"), "{}", err);
+}
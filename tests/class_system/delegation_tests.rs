@@ -0,0 +1,351 @@
+// Tests for `...` argument forwarding and the `delegate` class macro
+
+use metorex::ast::{Expression, Statement};
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+use std::rc::Rc;
+
+fn parse_source(source: &str) -> Result<Vec<Statement>, String> {
+    let tokens = Lexer::new(source).tokenize();
+    let mut parser = Parser::new(tokens);
+    parser.parse().map_err(|e| format!("{:?}", e))
+}
+
+fn execute_source(source: &str) -> Result<VirtualMachine, String> {
+    let statements = parse_source(source)?;
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&statements)
+        .map_err(|e| format!("{:?}", e))?;
+    Ok(vm)
+}
+
+fn string(value: &str) -> Object {
+    Object::String(Rc::new(value.to_string()))
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+#[test]
+fn test_parse_forwarding_parameter() {
+    let statements = parse_source("def wrap(first, ...)\n  target(...)\nend").unwrap();
+
+    match &statements[0] {
+        Statement::FunctionDef {
+            parameters, body, ..
+        } => {
+            assert_eq!(parameters.len(), 2);
+            assert!(!parameters[0].is_forwarding());
+            assert!(parameters[1].is_forwarding());
+
+            match &body[0] {
+                Statement::Expression {
+                    expression: Expression::Call { arguments, .. },
+                    ..
+                } => {
+                    assert!(matches!(arguments[0], Expression::ForwardedArgs { .. }));
+                }
+                other => panic!("Expected call statement, got {:?}", other),
+            }
+        }
+        other => panic!("Expected function definition, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_forwarding_parameter_must_be_last() {
+    let result = parse_source("def wrap(..., other)\nend");
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_parse_keyword_arguments_as_trailing_dictionary() {
+    let statements = parse_source("configure(1, to: :@target, size: 2)").unwrap();
+
+    match &statements[0] {
+        Statement::Expression {
+            expression: Expression::Call { arguments, .. },
+            ..
+        } => {
+            assert_eq!(arguments.len(), 2);
+            match &arguments[1] {
+                Expression::Dictionary { entries, .. } => {
+                    assert_eq!(entries.len(), 2);
+                    assert!(matches!(
+                        &entries[0],
+                        (Expression::Symbol { value: key, .. }, Expression::Symbol { value, .. })
+                            if key == "to" && value == "@target"
+                    ));
+                }
+                other => panic!("Expected dictionary, got {:?}", other),
+            }
+        }
+        other => panic!("Expected call statement, got {:?}", other),
+    }
+}
+
+// ============================================================================
+// Argument forwarding
+// ============================================================================
+
+#[test]
+fn test_forward_all_arguments_to_function() {
+    let source = r#"
+def add(a, b)
+  a + b
+end
+
+def wrapper(...)
+  add(...)
+end
+
+result = wrapper(2, 3)
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(Object::Int(5)));
+}
+
+#[test]
+fn test_forward_remaining_arguments_after_named_parameter() {
+    let source = r#"
+def add(a, b)
+  a + b
+end
+
+def scaled(factor, ...)
+  factor * add(...)
+end
+
+result = scaled(10, 1, 2)
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(Object::Int(30)));
+}
+
+#[test]
+fn test_forward_keyword_arguments() {
+    let source = r#"
+def lookup(options)
+  options[:key]
+end
+
+def wrapper(...)
+  lookup(...)
+end
+
+result = wrapper(key: "found")
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(string("found")));
+}
+
+#[test]
+fn test_forward_trailing_block() {
+    let source = r#"
+def run_block(value, blk)
+  blk.call(value)
+end
+
+def wrapper(...)
+  run_block(...)
+end
+
+result = wrapper(4) do |x|
+  x * 2
+end
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(Object::Int(8)));
+}
+
+#[test]
+fn test_forward_arguments_to_method_call() {
+    let source = r#"
+class Greeter
+  def greet(greeting, name)
+    greeting + ", " + name
+  end
+
+  def greet_twice(...)
+    self.greet(...) + "! " + self.greet(...)
+  end
+end
+
+g = Greeter.new
+result = g.greet_twice("Hi", "Ann")
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(
+        vm.environment().get("result"),
+        Some(string("Hi, Ann! Hi, Ann"))
+    );
+}
+
+#[test]
+fn test_forward_arguments_to_super() {
+    let source = r#"
+class Base
+  def describe(a, b)
+    a + b
+  end
+end
+
+class Child < Base
+  def describe(...)
+    super(...) * 2
+  end
+end
+
+result = Child.new.describe(3, 4)
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(Object::Int(14)));
+}
+
+#[test]
+fn test_forwarding_requires_named_arguments() {
+    let source = r#"
+def scaled(factor, ...)
+  factor
+end
+
+scaled()
+"#;
+    let error = execute_source(source).err().unwrap();
+    assert!(error.contains("expected at least 1 argument(s) but received 0"));
+}
+
+#[test]
+fn test_forwarding_without_forwarding_parameter_fails() {
+    let source = r#"
+def add(a, b)
+  a + b
+end
+
+def broken(a, b)
+  add(...)
+end
+
+broken(1, 2)
+"#;
+    let error = execute_source(source).err().unwrap();
+    assert!(error.contains("No arguments to forward"));
+}
+
+// ============================================================================
+// delegate
+// ============================================================================
+
+#[test]
+fn test_delegate_to_instance_variable() {
+    let source = r#"
+class Engine
+  def start(mode)
+    "started " + mode
+  end
+
+  def stop()
+    "stopped"
+  end
+end
+
+class Car
+  delegate :start, :stop, to: :@engine
+
+  def initialize()
+    @engine = Engine.new
+  end
+end
+
+car = Car.new
+started = car.start("fast")
+stopped = car.stop
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(
+        vm.environment().get("started"),
+        Some(string("started fast"))
+    );
+    assert_eq!(vm.environment().get("stopped"), Some(string("stopped")));
+}
+
+#[test]
+fn test_delegate_to_method() {
+    let source = r#"
+class Inner
+  def double(x)
+    x * 2
+  end
+end
+
+class Wrapper
+  attr_reader :inner
+  delegate :double, to: :inner
+
+  def initialize()
+    @inner = Inner.new
+  end
+end
+
+result = Wrapper.new.double(21)
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(Object::Int(42)));
+}
+
+#[test]
+fn test_delegate_with_parentheses() {
+    let source = r#"
+class Inner
+  def size()
+    3
+  end
+end
+
+class Wrapper
+  delegate(:size, to: :@inner)
+
+  def initialize()
+    @inner = Inner.new
+  end
+end
+
+result = Wrapper.new.size
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(Object::Int(3)));
+}
+
+#[test]
+fn test_delegate_to_native_object() {
+    let source = r#"
+class Name
+  delegate :upcase, :length, to: :@value
+
+  def initialize(value)
+    @value = value
+  end
+end
+
+n = Name.new("ada")
+shout = n.upcase
+size = n.length
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(vm.environment().get("shout"), Some(string("ADA")));
+    assert_eq!(vm.environment().get("size"), Some(Object::Int(3)));
+}
+
+#[test]
+fn test_delegate_requires_target() {
+    let source = r#"
+class Wrapper
+  delegate :size
+end
+"#;
+    let error = execute_source(source).err().unwrap();
+    assert!(error.contains("delegate requires a 'to:' target"));
+}
@@ -38,6 +38,7 @@ fn test_basic_inheritance_method_lookup() {
                     position: pos(3, 5),
                 }],
                 position: pos(2, 3),
+                return_type: None,
             }],
             position: pos(1, 1),
         },
@@ -122,6 +123,7 @@ fn test_method_overriding() {
                     position: pos(3, 5),
                 }],
                 position: pos(2, 3),
+                return_type: None,
             }],
             position: pos(1, 1),
         },
@@ -139,6 +141,7 @@ fn test_method_overriding() {
                     position: pos(8, 5),
                 }],
                 position: pos(7, 3),
+                return_type: None,
             }],
             position: pos(6, 1),
         },
@@ -222,6 +225,7 @@ fn test_inheritance_chain_method_lookup() {
                     position: pos(3, 5),
                 }],
                 position: pos(2, 3),
+                return_type: None,
             }],
             position: pos(1, 1),
         },
@@ -239,6 +243,7 @@ fn test_inheritance_chain_method_lookup() {
                     position: pos(8, 5),
                 }],
                 position: pos(7, 3),
+                return_type: None,
             }],
             position: pos(6, 1),
         },
@@ -256,6 +261,7 @@ fn test_inheritance_chain_method_lookup() {
                     position: pos(13, 5),
                 }],
                 position: pos(12, 3),
+                return_type: None,
             }],
             position: pos(11, 1),
         },
@@ -336,6 +342,7 @@ fn test_inherited_initialize_method() {
                         position: pos(3, 5),
                     }],
                     position: pos(2, 3),
+                    return_type: None,
                 },
                 Statement::MethodDef {
                     name: "get_name".to_string(),
@@ -348,6 +355,7 @@ fn test_inherited_initialize_method() {
                         position: pos(6, 5),
                     }],
                     position: pos(5, 3),
+                    return_type: None,
                 },
             ],
             position: pos(1, 1),
@@ -459,6 +467,7 @@ fn test_overriding_with_instance_variables() {
                         },
                     ],
                     position: pos(2, 3),
+                    return_type: None,
                 },
                 Statement::MethodDef {
                     name: "speak".to_string(),
@@ -471,6 +480,7 @@ fn test_overriding_with_instance_variables() {
                         position: pos(7, 5),
                     }],
                     position: pos(6, 3),
+                    return_type: None,
                 },
             ],
             position: pos(1, 1),
@@ -506,6 +516,7 @@ fn test_overriding_with_instance_variables() {
                     },
                 ],
                 position: pos(11, 3),
+                return_type: None,
             }],
             position: pos(10, 1),
         },
@@ -579,6 +590,7 @@ fn test_multiple_inheritance_levels() {
                     position: pos(3, 5),
                 }],
                 position: pos(2, 3),
+                return_type: None,
             }],
             position: pos(1, 1),
         },
@@ -596,6 +608,7 @@ fn test_multiple_inheritance_levels() {
                     position: pos(8, 5),
                 }],
                 position: pos(7, 3),
+                return_type: None,
             }],
             position: pos(6, 1),
         },
@@ -613,6 +626,7 @@ fn test_multiple_inheritance_levels() {
                     position: pos(13, 5),
                 }],
                 position: pos(12, 3),
+                return_type: None,
             }],
             position: pos(11, 1),
         },
@@ -0,0 +1,118 @@
+// Tests for instance variable reflection: instance_variables,
+// instance_variable_get, instance_variable_set and instance_variable_defined?
+
+use crate::common::execute_source;
+use metorex::object::Object;
+use std::rc::Rc;
+
+fn symbols(names: &[&str]) -> Object {
+    Object::array(
+        names
+            .iter()
+            .map(|name| Object::Symbol(Rc::new(name.to_string())))
+            .collect(),
+    )
+}
+
+const POINT: &str = r#"
+class Point
+  def initialize(x, y)
+    @y = y
+    @x = x
+  end
+
+  def x()
+    @x
+  end
+end
+
+point = Point.new(1, 2)
+"#;
+
+#[test]
+fn test_instance_variables_lists_sorted_names() {
+    let vm = execute_source(&format!("{}\nnames = point.instance_variables", POINT)).unwrap();
+    assert_eq!(vm.environment().get("names"), Some(symbols(&["@x", "@y"])));
+}
+
+#[test]
+fn test_instance_variable_get_reads_values() {
+    let source = format!(
+        r#"{}
+x = point.instance_variable_get(:@x)
+y = point.instance_variable_get("@y")
+missing = point.instance_variable_get(:@missing)
+"#,
+        POINT
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(vm.environment().get("x"), Some(Object::Int(1)));
+    assert_eq!(vm.environment().get("y"), Some(Object::Int(2)));
+    assert_eq!(vm.environment().get("missing"), Some(Object::Nil));
+}
+
+#[test]
+fn test_instance_variable_set_is_seen_by_methods() {
+    let source = format!(
+        r#"{}
+returned = point.instance_variable_set(:@x, 10)
+x = point.x
+point.instance_variable_set(:@z, 3)
+names = point.instance_variables
+"#,
+        POINT
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(vm.environment().get("returned"), Some(Object::Int(10)));
+    assert_eq!(vm.environment().get("x"), Some(Object::Int(10)));
+    assert_eq!(
+        vm.environment().get("names"),
+        Some(symbols(&["@x", "@y", "@z"]))
+    );
+}
+
+#[test]
+fn test_instance_variable_defined() {
+    let source = format!(
+        r#"{}
+set = point.instance_variable_defined?(:@x)
+missing = point.instance_variable_defined?(:@missing)
+"#,
+        POINT
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(vm.environment().get("set"), Some(Object::Bool(true)));
+    assert_eq!(vm.environment().get("missing"), Some(Object::Bool(false)));
+}
+
+#[test]
+fn test_values_without_instance_variables() {
+    let source = r#"
+names = 5.instance_variables
+value = "text".instance_variable_get(:@x)
+begin
+  5.instance_variable_set(:@x, 1)
+rescue TypeError => e
+  message = e.message
+end
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(vm.environment().get("names"), Some(symbols(&[])));
+    assert_eq!(vm.environment().get("value"), Some(Object::Nil));
+    assert_eq!(
+        vm.environment().get("message"),
+        Some(Object::string("Cannot set instance variable @x on Integer"))
+    );
+}
+
+#[test]
+fn test_instance_variable_name_must_start_with_at() {
+    let error = execute_source(&format!("{}\npoint.instance_variable_get(:x)", POINT))
+        .err()
+        .unwrap();
+    assert!(error.contains("'x' is not allowed as an instance variable name"));
+    let error = execute_source(&format!("{}\npoint.instance_variable_get(:@@x)", POINT))
+        .err()
+        .unwrap();
+    assert!(error.contains("'@@x' is not allowed as an instance variable name"));
+}
@@ -0,0 +1,94 @@
+// Tests for the top-level `main` object and top-level method definitions
+
+use crate::common::execute_source;
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+
+fn entry(vm: &VirtualMachine, name: &str) -> Object {
+    vm.environment()
+        .get(name)
+        .unwrap_or_else(|| panic!("{} is not defined", name))
+}
+
+#[test]
+fn self_at_top_level_is_main() {
+    let vm = execute_source("me = self\nname = self.to_s\nkind = self.class").unwrap();
+
+    assert_eq!(&entry(&vm, "me"), vm.main_object());
+    assert_eq!(entry(&vm, "name"), Object::string("main"));
+    assert_eq!(
+        entry(&vm, "kind"),
+        Object::Class(vm.builtins().object_class.clone())
+    );
+}
+
+#[test]
+fn main_can_hold_instance_variables() {
+    let vm = execute_source("@count = 2\ncount = @count + 1").unwrap();
+
+    assert_eq!(entry(&vm, "count"), Object::Int(3));
+}
+
+#[test]
+fn top_level_def_becomes_a_private_method_of_object() {
+    let vm = execute_source(
+        r#"
+def double(x)
+  x * 2
+end
+class Doubler
+  def run(x)
+    self.double(x)
+  end
+end
+a = double(1)
+b = self.double(2)
+c = Doubler.new.run(3)
+"#,
+    )
+    .unwrap();
+
+    assert!(vm.builtins().object_class.is_private_method("double"));
+    assert_eq!(entry(&vm, "a"), Object::Int(2));
+    assert_eq!(entry(&vm, "b"), Object::Int(4));
+    assert_eq!(entry(&vm, "c"), Object::Int(6));
+}
+
+#[test]
+fn top_level_def_rejects_an_explicit_receiver() {
+    let err = execute_source("def double(x)\n  x * 2\nend\n5.double(1)")
+        .err()
+        .unwrap();
+
+    assert!(
+        err.contains("Private method 'double' called for type 'Int'"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn top_level_def_does_not_hide_builtin_methods() {
+    let vm = execute_source("def length\n  0\nend\nn = \"abc\".length\nr = 5.respond_to?(:length)")
+        .unwrap();
+
+    assert_eq!(entry(&vm, "n"), Object::Int(3));
+    assert_eq!(entry(&vm, "r"), Object::Bool(false));
+}
+
+#[test]
+fn class_variables_are_not_available_on_main() {
+    let err = execute_source("@@count = 1").err().unwrap();
+    assert!(
+        err.contains("Class variable @@count can only be used within a class or method"),
+        "{}",
+        err
+    );
+
+    let err = execute_source("x = @@count").err().unwrap();
+    assert!(
+        err.contains("Class variable @@count can only be used within a class or method"),
+        "{}",
+        err
+    );
+}
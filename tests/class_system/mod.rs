@@ -1,7 +1,16 @@
 mod attr_methods_tests;
+mod builtin_catalog_tests;
 mod builtin_classes_tests;
 mod class_instantiation_tests;
 mod class_parsing_tests;
 mod class_system_tests;
+mod class_variable_tests;
+mod define_method_tests;
+mod delegation_tests;
 mod inheritance_tests;
+mod instance_variable_tests;
+mod main_object_tests;
+mod module_tests;
+mod nested_definition_tests;
 mod object_tests;
+mod sealed_class_tests;
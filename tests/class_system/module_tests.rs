@@ -0,0 +1,266 @@
+// Tests for `module` definitions and mixing them into classes with `include`
+// and `extend`
+
+use metorex::ast::Statement;
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+use std::rc::Rc;
+
+fn parse_source(source: &str) -> Result<Vec<Statement>, String> {
+    let tokens = Lexer::new(source).tokenize();
+    let mut parser = Parser::new(tokens);
+    parser.parse().map_err(|e| format!("{:?}", e))
+}
+
+fn execute_source(source: &str) -> Result<VirtualMachine, String> {
+    let statements = parse_source(source)?;
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&statements)
+        .map_err(|e| format!("{:?}", e))?;
+    Ok(vm)
+}
+
+fn string(value: &str) -> Object {
+    Object::String(Rc::new(value.to_string()))
+}
+
+const GREETING: &str = r#"
+module Greeting
+  def greet
+    "Hello, " + self.name
+  end
+end
+
+class Person
+  include Greeting
+
+  def name
+    "Ann"
+  end
+end
+"#;
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+#[test]
+fn test_parse_module_definition() {
+    let statements = parse_source("module Greeting\n  def greet\n    1\n  end\nend").unwrap();
+
+    match &statements[0] {
+        Statement::ModuleDef { name, body, .. } => {
+            assert_eq!(name, "Greeting");
+            assert!(matches!(&body[0], Statement::MethodDef { name, .. } if name == "greet"));
+        }
+        other => panic!("Expected ModuleDef, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_module_without_end_is_a_parse_error() {
+    let error = parse_source("module Greeting\n  def greet\n  end\n").unwrap_err();
+    assert!(error.contains("Expected 'end' after module body"));
+}
+
+#[test]
+fn test_module_requires_a_name() {
+    let error = parse_source("module 1\nend").unwrap_err();
+    assert!(error.contains("Expected module name"));
+}
+
+// ============================================================================
+// Runtime
+// ============================================================================
+
+#[test]
+fn test_module_is_bound_to_its_name() {
+    let vm = execute_source("module Greeting\nend\n").unwrap();
+    let Some(Object::Module(module)) = vm.environment().get("Greeting") else {
+        panic!("Greeting should be a module");
+    };
+    assert_eq!(module.name(), "Greeting");
+    assert_eq!(Object::Module(module).to_string(), "<module Greeting>");
+}
+
+#[test]
+fn test_included_methods_are_instance_methods() {
+    let source = format!("{}result = Person.new.greet\n", GREETING);
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(string("Hello, Ann")));
+}
+
+#[test]
+fn test_class_methods_override_included_ones() {
+    let source = r#"
+module Named
+  def name
+    "module"
+  end
+end
+
+class Thing
+  include Named
+
+  def name
+    "class"
+  end
+end
+
+result = Thing.new.name
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(string("class")));
+}
+
+#[test]
+fn test_included_methods_come_before_the_superclass() {
+    let source = r#"
+module Loud
+  def speak
+    "LOUD"
+  end
+end
+
+class Animal
+  def speak
+    "quiet"
+  end
+end
+
+class Dog < Animal
+  include Loud
+end
+
+result = Dog.new.speak
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(string("LOUD")));
+}
+
+#[test]
+fn test_later_includes_take_precedence() {
+    let source = r#"
+module First
+  def which
+    "first"
+  end
+end
+
+module Second
+  def which
+    "second"
+  end
+end
+
+class Both
+  include First
+  include Second
+end
+
+result = Both.new.which
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(string("second")));
+}
+
+#[test]
+fn test_subclasses_inherit_included_methods() {
+    let source = format!(
+        "{}class Child < Person\nend\nresult = Child.new.greet\n",
+        GREETING
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(string("Hello, Ann")));
+}
+
+#[test]
+fn test_modules_can_include_modules() {
+    let source = format!(
+        "{}module Polite\n  include Greeting\nend\nclass Guest\n  include Polite\n  def name\n    \"Bo\"\n  end\nend\nresult = Guest.new.greet\n",
+        GREETING
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(string("Hello, Bo")));
+}
+
+#[test]
+fn test_extended_methods_are_called_on_the_class() {
+    let source = r#"
+module Factory
+  def build
+    self.new
+  end
+end
+
+class Widget
+  extend Factory
+end
+
+result = Widget.build
+"#;
+    let vm = execute_source(source).unwrap();
+    match vm.environment().get("result") {
+        Some(Object::Instance(instance)) => assert_eq!(instance.borrow().class_name(), "Widget"),
+        other => panic!("Expected a Widget, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_extended_methods_are_not_instance_methods() {
+    let source = "module Factory\n  def build\n    1\n  end\nend\nclass Widget\n  extend Factory\nend\nWidget.new.build\n";
+    let error = execute_source(source).err().unwrap();
+    assert!(error.contains("Undefined method 'build'"));
+}
+
+#[test]
+fn test_include_requires_modules() {
+    let error = execute_source("class Thing\n  include 3\nend\n")
+        .err()
+        .unwrap();
+    assert!(error.contains("include expects modules, found 'Int'"));
+
+    let error = execute_source("class Other\nend\nclass Thing\n  extend Other\nend\n")
+        .err()
+        .unwrap();
+    assert!(error.contains("extend expects modules, found 'Class'"));
+}
+
+#[test]
+fn test_modules_match_instances_of_including_classes() {
+    let source = format!(
+        "{}class Child < Person\nend\nchild = Child.new\nmixed = Greeting === child\nother = Greeting === 3\nmatched = false\ncase child\nwhen Greeting\n  matched = true\nend\n",
+        GREETING
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(vm.environment().get("mixed"), Some(Object::Bool(true)));
+    assert_eq!(vm.environment().get("other"), Some(Object::Bool(false)));
+    assert_eq!(vm.environment().get("matched"), Some(Object::Bool(true)));
+}
+
+#[test]
+fn test_mixin_reflection() {
+    let source = format!(
+        "{}module Polite\n  include Greeting\nend\nclass Child < Person\nend\nincluded = Child.include?(Greeting)\ndirect = Person.included_modules\nnested = Polite.include?(Greeting)\nnames = Greeting.instance_methods\nname = Greeting.name\n",
+        GREETING
+    );
+    let vm = execute_source(&source).unwrap();
+    let Some(Object::Module(greeting)) = vm.environment().get("Greeting") else {
+        panic!("Greeting should be a module");
+    };
+    assert_eq!(vm.environment().get("included"), Some(Object::Bool(true)));
+    assert_eq!(
+        vm.environment().get("direct"),
+        Some(Object::array(vec![Object::Module(greeting)]))
+    );
+    assert_eq!(vm.environment().get("nested"), Some(Object::Bool(true)));
+    assert_eq!(
+        vm.environment().get("names"),
+        Some(Object::array(vec![Object::Symbol(Rc::new(
+            "greet".to_string()
+        ))]))
+    );
+    assert_eq!(vm.environment().get("name"), Some(string("Greeting")));
+}
@@ -0,0 +1,239 @@
+// Tests for classes and modules defined inside other classes and modules,
+// constants in their bodies and `Module.nesting`
+
+use crate::common::execute_source;
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+use std::rc::Rc;
+
+fn get(vm: &VirtualMachine, name: &str) -> String {
+    vm.environment()
+        .get(name)
+        .unwrap_or_else(|| panic!("{} is not defined", name))
+        .to_string()
+}
+
+fn symbols(names: &[&str]) -> Object {
+    Object::array(
+        names
+            .iter()
+            .map(|name| Object::Symbol(Rc::new(name.to_string())))
+            .collect(),
+    )
+}
+
+const SHAPES: &str = r#"
+module Shapes
+  SIDES = 4
+
+  class Base
+    LABEL = "base"
+  end
+
+  class Square < Base
+    def sides()
+      SIDES
+    end
+
+    def label()
+      LABEL
+    end
+
+    def helper_name()
+      Helper.new.name
+    end
+
+    def nesting()
+      Module.nesting
+    end
+  end
+
+  class Helper
+    def name()
+      "helper"
+    end
+  end
+
+  module Inner
+    class Deep
+      def sides()
+        SIDES
+      end
+
+      def nesting()
+        Module.nesting
+      end
+    end
+  end
+end
+"#;
+
+#[test]
+fn test_nested_classes_are_named_after_their_namespace() {
+    let source = format!(
+        r#"{}
+square = Shapes::Square.name
+deep = Shapes::Inner::Deep.name
+inner = Shapes::Inner.name
+"#,
+        SHAPES
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(get(&vm, "square"), "Shapes::Square");
+    assert_eq!(get(&vm, "deep"), "Shapes::Inner::Deep");
+    assert_eq!(get(&vm, "inner"), "Shapes::Inner");
+}
+
+#[test]
+fn test_nested_classes_are_not_top_level_names() {
+    let error = execute_source(&format!("{}\nSquare.new", SHAPES))
+        .err()
+        .unwrap();
+    assert!(error.contains("Undefined variable 'Square'"), "{}", error);
+}
+
+#[test]
+fn test_methods_see_constants_of_enclosing_definitions() {
+    let source = format!(
+        r#"{}
+square = Shapes::Square.new
+sides = square.sides
+helper = square.helper_name
+deep_sides = Shapes::Inner::Deep.new.sides
+"#,
+        SHAPES
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(vm.environment().get("sides"), Some(Object::Int(4)));
+    assert_eq!(get(&vm, "helper"), "helper");
+    assert_eq!(vm.environment().get("deep_sides"), Some(Object::Int(4)));
+}
+
+#[test]
+fn test_methods_see_constants_of_superclasses() {
+    let vm = execute_source(&format!("{}\nlabel = Shapes::Square.new.label", SHAPES)).unwrap();
+    assert_eq!(get(&vm, "label"), "base");
+}
+
+#[test]
+fn test_scoped_constants_are_read_with_double_colon() {
+    let source = format!(
+        r#"{}
+sides = Shapes::SIDES
+label = Shapes::Square::LABEL
+"#,
+        SHAPES
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(vm.environment().get("sides"), Some(Object::Int(4)));
+    assert_eq!(get(&vm, "label"), "base");
+}
+
+#[test]
+fn test_module_nesting_lists_enclosing_definitions_innermost_first() {
+    let source = format!(
+        r#"{}
+square = Shapes::Square.new.nesting
+deep = Shapes::Inner::Deep.new.nesting
+top = Module.nesting
+"#,
+        SHAPES
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(
+        get(&vm, "square"),
+        "[<class Shapes::Square>, <module Shapes>]"
+    );
+    assert_eq!(
+        get(&vm, "deep"),
+        "[<class Shapes::Inner::Deep>, <module Shapes::Inner>, <module Shapes>]"
+    );
+    assert_eq!(get(&vm, "top"), "[]");
+}
+
+#[test]
+fn test_constants_lists_names_defined_inside() {
+    let source = format!(
+        r#"{}
+module_constants = Shapes.constants
+class_constants = Shapes::Base.constants
+"#,
+        SHAPES
+    );
+    let vm = execute_source(&source).unwrap();
+    assert_eq!(
+        vm.environment().get("module_constants"),
+        Some(symbols(&["Base", "Helper", "Inner", "SIDES", "Square"]))
+    );
+    assert_eq!(
+        vm.environment().get("class_constants"),
+        Some(symbols(&["LABEL"]))
+    );
+}
+
+#[test]
+fn test_enclosing_constants_shadow_top_level_names() {
+    let source = r#"
+class Config
+  def source()
+    "top"
+  end
+end
+
+class App
+  class Config
+    def source()
+      "nested"
+    end
+  end
+
+  def config_source()
+    Config.new.source
+  end
+end
+
+nested = App.new.config_source
+top = Config.new.source
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(get(&vm, "nested"), "nested");
+    assert_eq!(get(&vm, "top"), "top");
+}
+
+#[test]
+fn test_nested_exception_classes_are_rescued_by_either_name() {
+    let source = r#"
+module Api
+  class Error < StandardError
+  end
+
+  class Client
+    def fetch()
+      begin
+        raise Error.new("first")
+      rescue Error => e
+        @inner = e.message
+      end
+      raise Error.new("second")
+    end
+
+    def inner()
+      @inner
+    end
+  end
+end
+
+client = Api::Client.new
+begin
+  client.fetch
+rescue Api::Error => e
+  outer = e.message
+  type = e.type
+end
+inner = client.inner
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(get(&vm, "inner"), "first");
+    assert_eq!(get(&vm, "outer"), "second");
+    assert_eq!(get(&vm, "type"), "Api::Error");
+}
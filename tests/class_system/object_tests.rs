@@ -1,6 +1,7 @@
 // Unit tests for Metorex runtime Object system
 // Tests object creation, type checking, equality, hashing, and string representation
 
+use indexmap::IndexMap;
 use metorex::object::{BlockStatement, Class, Exception, Instance, Method, Object, ObjectHash};
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
@@ -71,7 +72,7 @@ fn test_empty_array() {
 
 #[test]
 fn test_dict_object() {
-    let mut map = HashMap::new();
+    let mut map = IndexMap::new();
     map.insert("x".to_string(), Object::Int(1));
     map.insert("y".to_string(), Object::Int(2));
     let obj = Object::dict(map);
@@ -272,17 +273,17 @@ fn test_equals_array_nested() {
 
 #[test]
 fn test_equals_dict_simple() {
-    let mut map1 = HashMap::new();
+    let mut map1 = IndexMap::new();
     map1.insert("x".to_string(), Object::Int(10));
     map1.insert("y".to_string(), Object::Int(20));
     let dict1 = Object::Dict(Rc::new(RefCell::new(map1)));
 
-    let mut map2 = HashMap::new();
+    let mut map2 = IndexMap::new();
     map2.insert("x".to_string(), Object::Int(10));
     map2.insert("y".to_string(), Object::Int(20));
     let dict2 = Object::Dict(Rc::new(RefCell::new(map2)));
 
-    let mut map3 = HashMap::new();
+    let mut map3 = IndexMap::new();
     map3.insert("x".to_string(), Object::Int(10));
     let dict3 = Object::Dict(Rc::new(RefCell::new(map3)));
 
@@ -292,24 +293,24 @@ fn test_equals_dict_simple() {
 
 #[test]
 fn test_equals_dict_nested() {
-    let mut inner1 = HashMap::new();
+    let mut inner1 = IndexMap::new();
     inner1.insert("a".to_string(), Object::Int(1));
 
-    let mut map1 = HashMap::new();
+    let mut map1 = IndexMap::new();
     map1.insert("x".to_string(), Object::Dict(Rc::new(RefCell::new(inner1))));
     let dict1 = Object::Dict(Rc::new(RefCell::new(map1)));
 
-    let mut inner2 = HashMap::new();
+    let mut inner2 = IndexMap::new();
     inner2.insert("a".to_string(), Object::Int(1));
 
-    let mut map2 = HashMap::new();
+    let mut map2 = IndexMap::new();
     map2.insert("x".to_string(), Object::Dict(Rc::new(RefCell::new(inner2))));
     let dict2 = Object::Dict(Rc::new(RefCell::new(map2)));
 
-    let mut inner3 = HashMap::new();
+    let mut inner3 = IndexMap::new();
     inner3.insert("a".to_string(), Object::Int(2));
 
-    let mut map3 = HashMap::new();
+    let mut map3 = IndexMap::new();
     map3.insert("x".to_string(), Object::Dict(Rc::new(RefCell::new(inner3))));
     let dict3 = Object::Dict(Rc::new(RefCell::new(map3)));
 
@@ -362,6 +363,13 @@ fn test_equals_method() {
         receiver: None,
         owner: None,
         source_location: None,
+        source: None,
+        parameter_types: Vec::new(),
+        return_type: None,
+        captured_vars: HashMap::new(),
+        preconditions: Vec::new(),
+        postconditions: Vec::new(),
+        nesting: Vec::new(),
     });
     let method2 = Rc::clone(&method1);
     let method3 = Rc::new(Method {
@@ -371,6 +379,13 @@ fn test_equals_method() {
         receiver: None,
         owner: None,
         source_location: None,
+        source: None,
+        parameter_types: Vec::new(),
+        return_type: None,
+        captured_vars: HashMap::new(),
+        preconditions: Vec::new(),
+        postconditions: Vec::new(),
+        nesting: Vec::new(),
     });
 
     let obj1 = Object::Method(method1);
@@ -387,12 +402,14 @@ fn test_equals_block() {
         parameters: vec![],
         body: vec![],
         captured_vars: HashMap::new(),
+        destructured: Vec::new(),
     });
     let block2 = Rc::clone(&block1);
     let block3 = Rc::new(BlockStatement {
         parameters: vec![],
         body: vec![],
         captured_vars: HashMap::new(),
+        destructured: Vec::new(),
     });
 
     let obj1 = Object::Block(block1);
@@ -581,7 +598,7 @@ fn test_to_string_array() {
 
 #[test]
 fn test_to_string_dict() {
-    let mut map = HashMap::new();
+    let mut map = IndexMap::new();
     map.insert("x".to_string(), Object::Int(10));
     let dict = Object::Dict(Rc::new(RefCell::new(map)));
     let s = dict.to_string();
@@ -0,0 +1,154 @@
+// Tests for sealed classes declared with `variants` and exhaustive case warnings
+
+use metorex::ast::Statement;
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::resolver::Resolver;
+use metorex::vm::VirtualMachine;
+
+fn parse_source(source: &str) -> Vec<Statement> {
+    let tokens = Lexer::new(source).tokenize();
+    Parser::new(tokens).parse().expect("source should parse")
+}
+
+fn execute_source(source: &str) -> Result<VirtualMachine, String> {
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&parse_source(source))
+        .map_err(|e| e.to_string())?;
+    Ok(vm)
+}
+
+/// Warnings about sealed classes, ignoring unused-variable noise
+fn sealed_warnings(source: &str) -> Vec<String> {
+    Resolver::with_strict_mode(false)
+        .resolve(&parse_source(source))
+        .warnings
+        .into_iter()
+        .filter(|warning| warning.contains("sealed"))
+        .collect()
+}
+
+const SHAPES: &str = r#"
+class Shape
+  variants :Circle, :Square, :Triangle
+end
+
+class Circle < Shape
+end
+
+class Square < Shape
+end
+
+class Triangle < Shape
+end
+"#;
+
+// ============================================================================
+// Runtime
+// ============================================================================
+
+#[test]
+fn test_variants_are_recorded_on_the_class() {
+    let vm = execute_source(SHAPES).unwrap();
+    let Some(Object::Class(shape)) = vm.environment().get("Shape") else {
+        panic!("Shape should be a class");
+    };
+    assert_eq!(
+        shape.variants(),
+        Some(vec![
+            "Circle".to_string(),
+            "Square".to_string(),
+            "Triangle".to_string()
+        ])
+    );
+}
+
+#[test]
+fn test_unsealed_classes_have_no_variants() {
+    let vm = execute_source("class Plain\nend\n").unwrap();
+    let Some(Object::Class(plain)) = vm.environment().get("Plain") else {
+        panic!("Plain should be a class");
+    };
+    assert_eq!(plain.variants(), None);
+}
+
+#[test]
+fn test_unlisted_subclass_is_rejected() {
+    let source = format!("{}class Hexagon < Shape\nend\n", SHAPES);
+    let error = execute_source(&source).err().unwrap();
+    assert!(error.contains(
+        "Cannot subclass sealed class 'Shape': 'Hexagon' is not one of its variants (Circle, Square, Triangle)"
+    ));
+}
+
+#[test]
+fn test_variants_require_symbols() {
+    let error = execute_source("class Shape\n  variants 1\nend\n")
+        .err()
+        .unwrap();
+    assert!(error.contains("variants expects class names as symbols, found 'Int'"));
+}
+
+// ============================================================================
+// Exhaustiveness warnings
+// ============================================================================
+
+#[test]
+fn test_case_missing_a_variant_warns() {
+    let source = format!(
+        "{}s = Circle.new\ncase s\nwhen Circle\n  1\nwhen Square\n  2\nend\n",
+        SHAPES
+    );
+    assert_eq!(
+        sealed_warnings(&source),
+        vec![
+            "Case at 15:1 does not handle every variant of sealed class 'Shape': missing Triangle"
+        ]
+    );
+}
+
+#[test]
+fn test_case_covering_every_variant_is_quiet() {
+    let source = format!(
+        "{}s = Circle.new\ncase s\nwhen Circle\n  1\nwhen Square\n  2\nwhen Triangle\n  3\nend\n",
+        SHAPES
+    );
+    assert!(sealed_warnings(&source).is_empty());
+}
+
+#[test]
+fn test_else_branch_makes_case_exhaustive() {
+    let source = format!(
+        "{}s = Circle.new\ncase s\nwhen Circle\n  1\nelse\n  2\nend\n",
+        SHAPES
+    );
+    assert!(sealed_warnings(&source).is_empty());
+}
+
+#[test]
+fn test_matching_the_sealed_class_itself_is_exhaustive() {
+    let source = format!(
+        "{}s = Circle.new\ncase s\nwhen Circle\n  1\nwhen Shape\n  2\nend\n",
+        SHAPES
+    );
+    assert!(sealed_warnings(&source).is_empty());
+}
+
+#[test]
+fn test_guarded_variant_does_not_count_as_handled() {
+    let source = format!(
+        "{}s = Circle.new\nbig = true\ncase s\nwhen Circle if big\n  1\nwhen Square\n  2\nwhen Triangle\n  3\nend\n",
+        SHAPES
+    );
+    assert_eq!(
+        sealed_warnings(&source),
+        vec!["Case at 16:1 does not handle every variant of sealed class 'Shape': missing Circle"]
+    );
+}
+
+#[test]
+fn test_case_over_unrelated_types_is_quiet() {
+    let source = format!("{}x = 1\ncase x\nwhen Integer\n  1\nend\n", SHAPES);
+    assert!(sealed_warnings(&source).is_empty());
+}
@@ -1,3 +1,111 @@
 // Common test utilities and constants
 
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+use std::ffi::OsStr;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
 pub const EXAMPLES_DIR: &str = "tests/_examples";
+
+/// Parse and execute `source` on `vm`, reporting a parse or runtime error as
+/// a String
+pub fn run(vm: &mut VirtualMachine, source: &str) -> Result<(), String> {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens)
+        .parse()
+        .map_err(|e| format!("{:?}", e))?;
+    vm.execute_program(&program)
+        .map(|_| ())
+        .map_err(|e| format!("{}", e))
+}
+
+/// Run `source` on a fresh VM and return the VM, or the error as a String
+pub fn execute_source(source: &str) -> Result<VirtualMachine, String> {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, source)?;
+    Ok(vm)
+}
+
+/// The value of the expression `source`, evaluated on `vm`
+pub fn eval_in(vm: &mut VirtualMachine, source: &str) -> Object {
+    run(vm, &format!("result = {}", source)).unwrap();
+    vm.environment().get("result").unwrap()
+}
+
+/// The value of the expression `source`, evaluated on a fresh VM
+pub fn eval(source: &str) -> Object {
+    eval_in(&mut VirtualMachine::new(), source)
+}
+
+/// The error running `source` on a fresh VM reports
+pub fn eval_error(source: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, source).unwrap_err()
+}
+
+/// Create a fresh directory named `metorex_<name>` under the system temp dir
+/// for one test
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("metorex_{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir.canonicalize().unwrap()
+}
+
+/// A fresh temp dir named after `name` holding `source` as `main.mx`
+pub fn script_dir(name: &str, source: &str) -> PathBuf {
+    let dir = temp_dir(name);
+    fs::write(dir.join("main.mx"), source).unwrap();
+    dir
+}
+
+/// A command that runs the metorex binary in `dir`, without the caller's
+/// `MX_OPTS` and with color off
+pub fn metorex_command(dir: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_metorex"));
+    command
+        .env_remove("MX_OPTS")
+        .env("NO_COLOR", "1")
+        .current_dir(dir);
+    command
+}
+
+/// Run the metorex binary in `dir` with `args`
+pub fn metorex<S: AsRef<OsStr>>(dir: &Path, args: &[S]) -> Output {
+    metorex_command(dir)
+        .args(args)
+        .output()
+        .expect("failed to run metorex")
+}
+
+/// Run `command` to completion with `input` on its stdin
+pub fn output_with_input(command: &mut Command, input: &str) -> Output {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run metorex");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// What a process wrote to stdout
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// What a process wrote to stderr
+pub fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
@@ -0,0 +1,151 @@
+// Tests for running case expressions, whose value is the body of the first
+// matching case, and case statements inside methods
+
+use metorex::error::MetorexError;
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+
+/// Run `source` and return the value of `result`
+fn eval(source: &str) -> Result<Object, MetorexError> {
+    let program = Parser::new(Lexer::new(source).tokenize())
+        .parse()
+        .expect("source should parse");
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program)?;
+    Ok(vm.environment().get("result").unwrap_or(Object::Nil))
+}
+
+fn eval_text(source: &str) -> String {
+    eval(source).unwrap().to_string()
+}
+
+#[test]
+fn test_literal_patterns_pick_the_matching_case() {
+    let result = eval_text(
+        r#"
+def name(value)
+  case value
+  when 1 then "one"
+  when "two" then "two"
+  when nil then "nothing"
+  when true then "yes"
+  else "other"
+  end
+end
+result = [name(1), name("two"), name(nil), name(true), name(3.5)]
+"#,
+    );
+    assert_eq!(result, "[one, two, nothing, yes, other]");
+}
+
+#[test]
+fn test_type_patterns_match_by_class() {
+    let result = eval_text(
+        r#"
+def kind(value)
+  case value
+  when Int then "int"
+  when String then "string"
+  when Array then "array"
+  when _ then "something"
+  end
+end
+result = [kind(4), kind("a"), kind([1]), kind(1.5)]
+"#,
+    );
+    assert_eq!(result, "[int, string, array, something]");
+}
+
+#[test]
+fn test_array_patterns_bind_elements_for_the_body() {
+    let result = eval_text(
+        r##"
+def describe(items)
+  case items
+  when [] then "empty"
+  when [only] then "just #{only}"
+  when [first, ...rest] then "#{first} and #{rest.length} more"
+  end
+end
+result = [describe([]), describe([7]), describe([1, 2, 3])]
+"##,
+    );
+    assert_eq!(result, "[empty, just 7, 1 and 2 more]");
+}
+
+#[test]
+fn test_bindings_do_not_leak_out_of_the_case() {
+    let err = eval(
+        r#"
+value = case [1, 2]
+when [a, b] then a + b
+end
+result = a
+"#,
+    )
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("Undefined variable 'a'"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_guards_fall_through_to_later_cases() {
+    let result = eval_text(
+        r#"
+limit = 10
+def size(n, limit)
+  case n
+  when x if x > limit then "big"
+  when x if x > limit / 2 then "medium"
+  else "small"
+  end
+end
+result = [size(20, limit), size(7, limit), size(1, limit)]
+"#,
+    );
+    assert_eq!(result, "[big, medium, small]");
+}
+
+#[test]
+fn test_no_match_without_else_is_an_error() {
+    let err = eval("result = case 3\nwhen 1 then \"one\"\nend\n").unwrap_err();
+    assert_eq!(err.code(), Some("E0504"));
+    assert!(
+        err.to_string().contains("No pattern matched value: 3"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_case_statements_do_not_end_the_enclosing_method() {
+    let result = eval_text(
+        r#"
+def visit(value, seen)
+  case value
+  when [x, y]
+    seen.push(x + y)
+  else
+    seen.push(value)
+  end
+  seen.push("after")
+  seen
+end
+def label(value)
+  case value
+  when 1
+    "one"
+  else
+    "many"
+  end
+end
+result = [visit([1, 2], []), visit(5, []), label(1), label(2)]
+"#,
+    );
+    assert_eq!(result, "[[3, after], [5, after], one, many]");
+}
@@ -1,4 +1,5 @@
 mod case_execution_tests;
+mod case_expression_execution_tests;
 mod case_expression_parsing_tests;
 mod case_parsing_tests;
 mod complex_flow_execution_tests;
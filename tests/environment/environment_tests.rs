@@ -332,3 +332,46 @@ fn test_deep_nesting() {
     // Should be back at global scope
     assert_eq!(env.current_depth(), 0);
 }
+
+#[test]
+fn test_environment_restore_undoes_definitions_and_assignments() {
+    let mut env = Environment::new();
+    env.define("kept".to_string(), int(1));
+    let snapshot = env.snapshot();
+
+    env.set("kept", int(2));
+    env.define("added".to_string(), int(3));
+    env.remove("kept");
+    env.restore(snapshot);
+
+    assert_eq!(env.get("kept"), Some(int(1)));
+    assert_eq!(env.get("added"), None);
+}
+
+#[test]
+fn test_environment_restore_pops_scopes_pushed_since() {
+    let mut env = Environment::new();
+    let snapshot = env.snapshot();
+
+    env.push_scope();
+    env.push_scope();
+    env.define("inner".to_string(), int(1));
+    env.restore(snapshot);
+
+    assert_eq!(env.current_depth(), 0);
+    assert_eq!(env.get("inner"), None);
+}
+
+#[test]
+fn test_environment_restore_resets_shared_bindings() {
+    let mut env = Environment::new();
+    env.define("count".to_string(), int(0));
+    let captured = env.get_ref("count").unwrap();
+    let snapshot = env.snapshot();
+
+    *captured.borrow_mut() = int(5);
+    env.restore(snapshot);
+
+    assert_eq!(*captured.borrow(), int(0));
+    assert_eq!(env.get("count"), Some(int(0)));
+}
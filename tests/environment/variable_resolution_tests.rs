@@ -109,6 +109,7 @@ fn test_variable_shadowing_warning() {
             position: Position::default(),
         }],
         position: Position::default(),
+        return_type: None,
     };
 
     let result = resolver.resolve(&[stmt1, stmt2]);
@@ -145,6 +146,7 @@ fn test_function_parameters_in_scope() {
             position: Position::default(),
         }],
         position: Position::default(),
+        return_type: None,
     };
 
     let result = resolver.resolve(&[stmt]);
@@ -180,6 +182,7 @@ fn test_nested_scopes() {
             position: Position::default(),
         }],
         position: Position::default(),
+        return_type: None,
     };
 
     let result = resolver.resolve(&[outer_var, func]);
@@ -397,6 +400,7 @@ fn test_lambda_parameter_scope() {
                 position: Position::default(),
             }],
             captured_vars: None,
+            destructured: Vec::new(),
             position: Position::default(),
         },
         position: Position::default(),
@@ -498,6 +502,7 @@ fn test_class_definition_scope() {
                 position: Position::default(),
             }],
             position: Position::default(),
+            return_type: None,
         }],
         position: Position::default(),
     };
@@ -590,6 +595,7 @@ fn test_return_statement_resolution() {
             },
         ],
         position: Position::default(),
+        return_type: None,
     };
 
     let result = resolver.resolve(&[stmt]);
@@ -221,6 +221,7 @@ fn test_nested_method_call_shows_stack_trace() {
                     position: pos_at(3, 5),
                 }],
                 position: pos_at(2, 3),
+                return_type: None,
             },
             Statement::MethodDef {
                 name: "method_b".to_string(),
@@ -238,6 +239,7 @@ fn test_nested_method_call_shows_stack_trace() {
                     position: pos_at(6, 5),
                 }],
                 position: pos_at(5, 3),
+                return_type: None,
             },
             Statement::MethodDef {
                 name: "method_c".to_string(),
@@ -250,6 +252,7 @@ fn test_nested_method_call_shows_stack_trace() {
                     position: pos_at(9, 5),
                 }],
                 position: pos_at(8, 3),
+                return_type: None,
             },
         ],
         position: pos_at(1, 1),
@@ -403,6 +406,7 @@ fn test_method_argument_count_error_has_location() {
                     position: pos(),
                 }],
                 position: pos(),
+                return_type: None,
             }],
             position: pos(),
         },
@@ -476,7 +480,7 @@ fn test_callable_not_callable_error() {
 }
 
 #[test]
-fn test_self_outside_method_context_error() {
+fn test_self_outside_method_context_is_main() {
     let mut vm = VirtualMachine::new();
 
     // Reference 'self' outside a method
@@ -487,12 +491,8 @@ fn test_self_outside_method_context_error() {
         position: pos_at(40, 1),
     };
 
-    let result = vm.execute_program(&[stmt]);
-    assert!(result.is_err());
-
-    let error = result.unwrap_err();
-    assert!(error.to_string().contains("Undefined self"));
-    assert!(error.to_string().contains("40:1"));
+    let result = vm.execute_program(&[stmt]).unwrap();
+    assert_eq!(result.as_ref(), Some(vm.main_object()));
 }
 
 #[test]
@@ -571,6 +571,7 @@ fn test_error_in_deeply_nested_calls() {
                 position: pos(),
             }],
             position: pos(),
+            return_type: None,
         });
     }
     methods.push(Statement::MethodDef {
@@ -584,6 +585,7 @@ fn test_error_in_deeply_nested_calls() {
             position: pos(),
         }],
         position: pos(),
+        return_type: None,
     });
 
     let statements = vec![
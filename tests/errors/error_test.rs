@@ -24,7 +24,9 @@ fn test_syntax_error_creation() {
     let err = MetorexError::syntax_error("Unexpected token", loc.clone());
 
     match err {
-        MetorexError::SyntaxError { message, location } => {
+        MetorexError::SyntaxError {
+            message, location, ..
+        } => {
             assert_eq!(message, "Unexpected token");
             assert_eq!(location, loc);
         }
@@ -42,6 +44,7 @@ fn test_runtime_error_creation() {
             message,
             location,
             stack_trace,
+            ..
         } => {
             assert_eq!(message, "Division by zero");
             assert_eq!(location, loc);
@@ -68,6 +71,7 @@ fn test_runtime_error_with_stack_trace() {
             message,
             location,
             stack_trace,
+            ..
         } => {
             assert_eq!(message, "Null pointer");
             assert_eq!(location, loc);
@@ -90,6 +94,7 @@ fn test_type_error_creation() {
             location,
             expected,
             found,
+            ..
         } => {
             assert_eq!(message, "Type mismatch");
             assert_eq!(location, loc);
@@ -116,6 +121,7 @@ fn test_type_error_with_types() {
             location,
             expected,
             found,
+            ..
         } => {
             assert_eq!(message, "Cannot add String and Int");
             assert_eq!(location, loc);
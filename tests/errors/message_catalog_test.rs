@@ -0,0 +1,238 @@
+// Tests for the error message catalog: codes, templates, catalog files and
+// the codes parse and runtime errors carry
+
+use crate::common::temp_dir;
+use metorex::error::{MetorexError, SourceLocation};
+use metorex::lexer::Lexer;
+use metorex::messages::{self, MESSAGES, MessageCatalog, interpolate};
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+use std::collections::HashSet;
+use std::fs;
+
+fn parse_errors(source: &str) -> Vec<MetorexError> {
+    Parser::new(Lexer::new(source).tokenize())
+        .parse()
+        .expect_err("expected a parse error")
+}
+
+fn run_error(source: &str) -> MetorexError {
+    let program = Parser::new(Lexer::new(source).tokenize()).parse().unwrap();
+    VirtualMachine::new()
+        .execute_program(&program)
+        .expect_err("expected a runtime error")
+}
+
+#[test]
+fn test_codes_are_unique_and_well_formed() {
+    let mut seen = HashSet::new();
+    for message in MESSAGES {
+        assert!(seen.insert(message.code), "duplicate code {}", message.code);
+        assert_eq!(message.code.len(), 5, "{}", message);
+        assert!(message.code.starts_with('E'), "{}", message);
+        assert!(message.code[1..].chars().all(|c| c.is_ascii_digit()));
+        assert_eq!(messages::lookup(message.code), Some(*message));
+    }
+    assert_eq!(messages::lookup("E9999"), None);
+}
+
+#[test]
+fn test_messages_are_listed_in_code_order() {
+    for pair in MESSAGES.windows(2) {
+        assert!(
+            pair[0].code < pair[1].code,
+            "{} is listed before {}",
+            pair[0],
+            pair[1]
+        );
+    }
+}
+
+#[test]
+fn test_interpolation_fills_named_placeholders_only() {
+    let text = interpolate(
+        "'{operator}' cannot be chained near '{' and {missing}",
+        &[("operator", &"==")],
+    );
+    assert_eq!(text, "'==' cannot be chained near '{' and {missing}");
+    assert_eq!(
+        messages::UNDEFINED_METHOD.placeholders(),
+        ["method", "type"]
+    );
+    assert_eq!(
+        messages::INDEX_OUT_OF_BOUNDS.format(&[("index", &5), ("length", &2)]),
+        "Index 5 is out of bounds for array of length 2"
+    );
+}
+
+#[test]
+fn test_catalog_overrides_templates_by_code() {
+    let mut catalog = MessageCatalog::new("pirate");
+    catalog
+        .set("E0301", "Arr, {type} knows no '{method}'")
+        .unwrap();
+    assert_eq!(catalog.locale(), "pirate");
+    assert_eq!(
+        catalog.template(&messages::UNDEFINED_METHOD),
+        "Arr, {type} knows no '{method}'"
+    );
+    // Messages the catalog does not replace stay in English
+    assert_eq!(
+        catalog.template(&messages::DIVISION_BY_ZERO),
+        "Division by zero"
+    );
+    assert_eq!(
+        MessageCatalog::english().template(&messages::UNDEFINED_METHOD),
+        messages::UNDEFINED_METHOD.template
+    );
+}
+
+#[test]
+fn test_catalog_rejects_unknown_codes_and_placeholders() {
+    let mut catalog = MessageCatalog::new("xx");
+    let err = catalog.set("E9999", "nope").unwrap_err();
+    assert!(err.contains("unknown message code 'E9999'"), "{}", err);
+
+    let err = catalog.set("E0404", "{divisor} is zero").unwrap_err();
+    assert!(err.contains("no '{divisor}' placeholder"), "{}", err);
+    assert!(err.contains("none"), "{}", err);
+
+    let err = catalog.set("E0301", "{name}?").unwrap_err();
+    assert!(err.contains("method, type"), "{}", err);
+}
+
+#[test]
+fn test_catalog_files_are_parsed_line_by_line() {
+    let catalog = MessageCatalog::parse(
+        "es",
+        "# Spanish\n\nE0404 = División por cero\nE0202 =  Variable '{name}' no definida  \n",
+    )
+    .unwrap();
+    assert_eq!(
+        catalog.template(&messages::DIVISION_BY_ZERO),
+        "División por cero"
+    );
+    assert_eq!(
+        catalog.template(&messages::UNDEFINED_VARIABLE),
+        "Variable '{name}' no definida"
+    );
+
+    let err = MessageCatalog::parse("es", "E0404 = a\nE0404 = b\n").unwrap_err();
+    assert_eq!(err, "line 2: E0404 is given more than once");
+    let err = MessageCatalog::parse("es", "\nE0404 division\n").unwrap_err();
+    assert_eq!(err, "line 2: expected CODE = message");
+}
+
+#[test]
+fn test_catalog_files_take_their_locale_from_the_file_name() {
+    let dir = temp_dir("message_catalog");
+    let path = dir.join("fr.txt");
+    fs::write(&path, "E0404 = Division par zéro\n").unwrap();
+
+    let catalog = MessageCatalog::load(&path).unwrap();
+    assert_eq!(catalog.locale(), "fr");
+    assert_eq!(
+        catalog.template(&messages::DIVISION_BY_ZERO),
+        "Division par zéro"
+    );
+
+    let err = MessageCatalog::load(&dir.join("missing.txt")).unwrap_err();
+    assert!(err.contains("cannot read message catalog"), "{}", err);
+}
+
+#[test]
+fn test_parse_errors_carry_codes() {
+    let errors = parse_errors("if true\n  x = 1\n");
+    assert_eq!(
+        errors[0].code(),
+        Some(messages::EXPECTED_END_AFTER_IF_STATEMENT.code)
+    );
+    assert!(messages::is_unfinished_input(errors[0].code().unwrap()));
+
+    let errors = parse_errors("1..2..3\n");
+    assert_eq!(
+        errors[0].code(),
+        Some(messages::OPERATOR_CANNOT_BE_CHAINED.code)
+    );
+    assert!(!messages::is_unfinished_input(errors[0].code().unwrap()));
+    assert!(
+        errors[0].to_string().contains("'..' cannot be chained"),
+        "{}",
+        errors[0]
+    );
+
+    let errors = parse_errors("class Point\n  def x\n  end\n");
+    assert_eq!(
+        errors[0].code(),
+        Some(messages::EXPECTED_END_AFTER_CLASS_BODY.code)
+    );
+}
+
+#[test]
+fn test_runtime_errors_carry_codes() {
+    let err = run_error("x = 1\nx.frobnicate\n");
+    assert_eq!(err.code(), Some("E0301"));
+    assert!(err.to_string().contains("Undefined method 'frobnicate'"));
+
+    assert_eq!(run_error("1 / 0\n").code(), Some("E0404"));
+    assert_eq!(run_error("puts(missing)\n").code(), Some("E0202"));
+
+    // Raised exceptions built from the catalog keep their code too
+    let err = run_error("class Counter\n  def count\n    @@count\n  end\nend\nCounter.new.count\n");
+    assert_eq!(err.code(), Some("E0203"));
+}
+
+#[test]
+fn test_class_and_super_errors_carry_codes() {
+    let err = run_error(
+        "class Point < Missing
+end
+",
+    );
+    assert_eq!(err.code(), Some(messages::UNDEFINED_SUPERCLASS.code));
+    assert!(
+        err.to_string().contains("Undefined superclass 'Missing'"),
+        "{}",
+        err
+    );
+
+    let err = run_error(
+        "class Base
+end
+class Point < Base
+  def x
+    super
+  end
+end
+Point.new.x
+",
+    );
+    assert_eq!(err.code(), Some(messages::SUPER_METHOD_NOT_FOUND.code));
+    assert!(
+        err.to_string()
+            .contains("Superclass Base does not define method 'x'"),
+        "{}",
+        err
+    );
+
+    assert_eq!(
+        run_error("puts(@@count)\n").code(),
+        Some(messages::CLASS_VARIABLE_OUTSIDE_CLASS.code)
+    );
+    assert_eq!(
+        run_error(
+            "attr_reader :x
+"
+        )
+        .code(),
+        Some(messages::ATTRIBUTE_OUTSIDE_CLASS.code)
+    );
+}
+
+#[test]
+fn test_errors_not_built_from_the_catalog_have_no_code() {
+    let err = run_error("raise \"custom\"\n");
+    assert_eq!(err.code(), None);
+    let err = MetorexError::runtime_error("anything", SourceLocation::new(1, 1, 0));
+    assert_eq!(err.code(), None);
+}
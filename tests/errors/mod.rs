@@ -1,2 +1,3 @@
 mod error_reporting_tests;
 mod error_test;
+mod message_catalog_test;
@@ -0,0 +1,242 @@
+// Tests for begin/rescue/else/ensure blocks used as values
+
+use metorex::ast::{Expression, Statement};
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+use std::rc::Rc;
+
+fn parse_code(code: &str) -> Vec<Statement> {
+    let tokens = Lexer::new(code).tokenize();
+    Parser::new(tokens).parse().expect("parse failed")
+}
+
+fn execute_code(code: &str) -> Result<VirtualMachine, String> {
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&parse_code(code))
+        .map_err(|e| e.to_string())?;
+    Ok(vm)
+}
+
+fn result_of(code: &str) -> Option<Object> {
+    execute_code(code).unwrap().environment().get("result")
+}
+
+fn string(value: &str) -> Object {
+    Object::String(Rc::new(value.to_string()))
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+#[test]
+fn test_parse_begin_in_assignment() {
+    let statements = parse_code("x = begin risky() rescue nil end");
+    match &statements[0] {
+        Statement::Assignment {
+            value: Expression::Begin { block, .. },
+            ..
+        } => assert!(matches!(**block, Statement::Begin { .. })),
+        other => panic!("Expected assignment of a begin block, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_begin_at_statement_start_is_a_statement() {
+    let statements = parse_code("begin\n  risky()\nrescue\n  nil\nend");
+    assert!(matches!(statements[0], Statement::Begin { .. }));
+}
+
+// ============================================================================
+// Values
+// ============================================================================
+
+#[test]
+fn test_single_line_begin_rescue_yields_fallback() {
+    let code = r#"
+def risky()
+  raise "bad"
+end
+result = begin risky() rescue nil end
+"#;
+    assert_eq!(result_of(code), Some(Object::Nil));
+}
+
+#[test]
+fn test_begin_yields_body_value_without_exception() {
+    let code = r#"
+result = begin
+  a = 20
+  a + 1
+rescue
+  0
+end
+"#;
+    assert_eq!(result_of(code), Some(Object::Int(21)));
+}
+
+#[test]
+fn test_begin_yields_value_of_matching_rescue_clause() {
+    let code = r#"
+result = begin
+  raise ValueError, "bad input"
+rescue TypeError
+  "type"
+rescue ValueError => e
+  "value: " + e.message
+end
+"#;
+    assert_eq!(result_of(code), Some(string("value: bad input")));
+}
+
+#[test]
+fn test_begin_yields_else_value_and_ignores_ensure_value() {
+    let code = r#"
+log = []
+result = begin
+  1
+else
+  2
+ensure
+  log.push("ensure")
+  3
+end
+"#;
+    let vm = execute_code(code).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(Object::Int(2)));
+    assert_eq!(
+        vm.environment().get("log"),
+        Some(Object::array(vec![string("ensure")]))
+    );
+}
+
+#[test]
+fn test_begin_value_after_retry() {
+    let code = r#"
+attempts = 0
+result = begin
+  attempts += 1
+  if attempts < 3
+    raise "flaky"
+  end
+  attempts * 10
+rescue
+  retry
+end
+"#;
+    assert_eq!(result_of(code), Some(Object::Int(30)));
+}
+
+#[test]
+fn test_begin_as_argument_and_operand() {
+    let code = r#"
+values = []
+values.push(begin
+  raise "x"
+rescue
+  "rescued"
+end)
+result = [values, begin 5 end + 1]
+"#;
+    assert_eq!(
+        result_of(code),
+        Some(Object::array(vec![
+            Object::array(vec![string("rescued")]),
+            Object::Int(6),
+        ]))
+    );
+}
+
+#[test]
+fn test_unrescued_exception_propagates_from_begin_value() {
+    let code = r#"
+result = begin
+  raise ValueError, "nope"
+rescue TypeError
+  0
+end
+"#;
+    let error = execute_code(code).err().unwrap();
+    assert!(error.contains("nope"), "{}", error);
+}
+
+#[test]
+fn test_return_cannot_leave_begin_value() {
+    let code = r#"
+def f()
+  x = begin
+    return 1
+  end
+  x
+end
+f()
+"#;
+    let error = execute_code(code).err().unwrap();
+    assert!(
+        error.contains("return cannot leave a begin block used as a value"),
+        "{}",
+        error
+    );
+}
+
+// ============================================================================
+// Bodies ending in a begin block
+// ============================================================================
+
+#[test]
+fn test_function_with_rescue_returns_branch_value() {
+    let code = r#"
+def parse(text)
+  if text == ""
+    raise "bad"
+  end
+  text
+rescue
+  "default"
+end
+
+result = [parse("given"), parse("")]
+"#;
+    assert_eq!(
+        result_of(code),
+        Some(Object::array(vec![string("given"), string("default")]))
+    );
+}
+
+#[test]
+fn test_method_with_rescue_returns_branch_value() {
+    let code = r#"
+class Loader
+  def load()
+    raise "missing"
+  rescue => e
+    "fallback for " + e.message
+  end
+end
+
+result = Loader.new.load()
+"#;
+    assert_eq!(result_of(code), Some(string("fallback for missing")));
+}
+
+#[test]
+fn test_block_ending_in_begin_yields_its_value() {
+    let code = r#"
+result = [1, 0].map { |n|
+  begin
+    if n == 0
+      raise "zero"
+    end
+    10 / n
+  rescue
+    -1
+  end
+}
+"#;
+    assert_eq!(
+        result_of(code),
+        Some(Object::array(vec![Object::Int(10), Object::Int(-1)]))
+    );
+}
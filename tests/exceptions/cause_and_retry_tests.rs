@@ -0,0 +1,335 @@
+// Tests for exception cause chaining, the two-argument raise form and retry
+
+use crate::common::run;
+use metorex::ast::{Expression, Statement};
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::{DEFAULT_MAX_RETRIES, VirtualMachine};
+use std::rc::Rc;
+
+fn parse_code(code: &str) -> Vec<Statement> {
+    let tokens = Lexer::new(code).tokenize();
+    Parser::new(tokens).parse().expect("parse failed")
+}
+
+fn execute_code(code: &str) -> Result<VirtualMachine, String> {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, code)?;
+    Ok(vm)
+}
+
+fn string(value: &str) -> Object {
+    Object::String(Rc::new(value.to_string()))
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+#[test]
+fn test_parse_two_argument_raise() {
+    let statements = parse_code(r#"raise ValueError, "bad value""#);
+    match &statements[0] {
+        Statement::Raise {
+            exception: Some(Expression::Call {
+                callee, arguments, ..
+            }),
+            cause: None,
+            ..
+        } => {
+            assert!(
+                matches!(callee.as_ref(), Expression::Identifier { name, .. } if name == "ValueError")
+            );
+            assert_eq!(arguments.len(), 1);
+        }
+        other => panic!("Expected two-argument raise, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_raise_from() {
+    let statements = parse_code("raise wrapper from original");
+    match &statements[0] {
+        Statement::Raise {
+            exception: Some(Expression::Identifier { name, .. }),
+            cause: Some(Expression::Identifier { name: cause, .. }),
+            ..
+        } => {
+            assert_eq!(name, "wrapper");
+            assert_eq!(cause, "original");
+        }
+        other => panic!("Expected raise with cause, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_from_is_still_a_valid_identifier() {
+    let vm = execute_code("from = 5\nto = from + 1").unwrap();
+    assert_eq!(vm.environment().get("to"), Some(Object::Int(6)));
+}
+
+// ============================================================================
+// Two-argument raise
+// ============================================================================
+
+#[test]
+fn test_two_argument_raise_sets_class_and_message() {
+    let vm = execute_code(
+        r#"
+begin
+  raise ValueError, "bad value"
+rescue ValueError => e
+  kind = e.type
+  message = e.message
+end
+"#,
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("kind"), Some(string("ValueError")));
+    assert_eq!(vm.environment().get("message"), Some(string("bad value")));
+}
+
+#[test]
+fn test_two_argument_raise_with_custom_exception_class() {
+    let vm = execute_code(
+        r#"
+class NotFound < StandardError
+end
+
+begin
+  raise NotFound, "missing"
+rescue StandardError => e
+  message = e.message
+end
+"#,
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("message"), Some(string("missing")));
+}
+
+// ============================================================================
+// Cause chaining
+// ============================================================================
+
+#[test]
+fn test_raise_inside_rescue_sets_cause() {
+    let vm = execute_code(
+        r#"
+begin
+  begin
+    raise "low level"
+  rescue => e
+    raise TypeError, "high level"
+  end
+rescue TypeError => outer
+  cause_message = outer.cause.message
+end
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        vm.environment().get("cause_message"),
+        Some(string("low level"))
+    );
+}
+
+#[test]
+fn test_raise_outside_rescue_has_no_cause() {
+    let vm = execute_code(
+        r#"
+begin
+  raise "first"
+rescue => e
+  handled = true
+end
+
+begin
+  raise "second"
+rescue => e
+  cause = e.cause
+end
+"#,
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("cause"), Some(Object::Nil));
+}
+
+#[test]
+fn test_explicit_raise_from() {
+    let vm = execute_code(
+        r#"
+original = RuntimeError.new("disk full")
+wrapper = ValueError.new("save failed")
+begin
+  raise wrapper from original
+rescue ValueError => e
+  cause_message = e.cause.message
+end
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        vm.environment().get("cause_message"),
+        Some(string("disk full"))
+    );
+}
+
+#[test]
+fn test_raise_from_nil_suppresses_automatic_cause() {
+    let vm = execute_code(
+        r#"
+begin
+  begin
+    raise "low level"
+  rescue => e
+    raise "clean" from nil
+  end
+rescue => outer
+  cause = outer.cause
+end
+"#,
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("cause"), Some(Object::Nil));
+}
+
+#[test]
+fn test_raise_from_requires_exception() {
+    let error = execute_code(r#"raise "oops" from 42"#).err().unwrap();
+    assert!(error.contains("Exception cause must be an Exception or nil"));
+}
+
+#[test]
+fn test_reraising_rescued_exception_does_not_cause_itself() {
+    let vm = execute_code(
+        r#"
+begin
+  begin
+    raise "only once"
+  rescue => e
+    raise e
+  end
+rescue => outer
+  cause = outer.cause
+end
+"#,
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("cause"), Some(Object::Nil));
+}
+
+#[test]
+fn test_uncaught_exception_displays_cause_chain() {
+    let error = execute_code(
+        r#"
+begin
+  raise "connection refused"
+rescue => e
+  raise RuntimeError, "request failed"
+end
+"#,
+    )
+    .err()
+    .unwrap();
+    assert!(error.contains("RuntimeError: request failed"));
+    assert!(error.contains("Caused by: RuntimeError: connection refused"));
+}
+
+// ============================================================================
+// retry
+// ============================================================================
+
+#[test]
+fn test_retry_reruns_begin_body() {
+    let vm = execute_code(
+        r#"
+attempts = 0
+begin
+  attempts += 1
+  if attempts < 3
+    raise "flaky"
+  end
+  result = "succeeded"
+rescue
+  retry
+end
+"#,
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("attempts"), Some(Object::Int(3)));
+    assert_eq!(vm.environment().get("result"), Some(string("succeeded")));
+}
+
+#[test]
+fn test_retry_is_conditional() {
+    let vm = execute_code(
+        r#"
+attempts = 0
+begin
+  attempts += 1
+  raise "always fails"
+rescue
+  if attempts < 2
+    retry
+  end
+  gave_up = true
+end
+"#,
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("attempts"), Some(Object::Int(2)));
+    assert_eq!(vm.environment().get("gave_up"), Some(Object::Bool(true)));
+}
+
+#[test]
+fn test_retry_limit_reraises_exception() {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(vm.max_retries(), DEFAULT_MAX_RETRIES);
+    vm.set_max_retries(3);
+
+    let error = run(
+        &mut vm,
+        r#"
+attempts = 0
+begin
+  attempts += 1
+  raise "never works"
+rescue
+  retry
+end
+"#,
+    )
+    .err()
+    .unwrap();
+    assert!(error.contains("never works"));
+    assert_eq!(vm.environment().get("attempts"), Some(Object::Int(4)));
+}
+
+#[test]
+fn test_ensure_runs_once_after_retries() {
+    let vm = execute_code(
+        r#"
+attempts = 0
+ensured = 0
+begin
+  attempts += 1
+  if attempts < 3
+    raise "flaky"
+  end
+rescue
+  retry
+ensure
+  ensured += 1
+end
+"#,
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("ensured"), Some(Object::Int(1)));
+}
+
+#[test]
+fn test_retry_outside_rescue_is_an_error() {
+    let error = execute_code("retry").err().unwrap();
+    assert!(error.contains("retry can only be used inside a rescue clause"));
+}
@@ -219,6 +219,22 @@ x
     assert_eq!(result, Some(Object::Int(1))); // First rescue matched
 }
 
+#[test]
+fn test_rescue_scoped_exception_names() {
+    let code = r#"
+begin
+  raise IOError, "disk"
+rescue Missing::Error
+  x = 1
+rescue Timeout::Error, IOError
+  x = 2
+end
+x
+"#;
+    let result = execute_code(code).unwrap();
+    assert_eq!(result, Some(Object::Int(2)));
+}
+
 // ============================================================================
 // Bare Raise Tests
 // ============================================================================
@@ -43,7 +43,7 @@ fn test_exception_with_backtrace() {
 
 #[test]
 fn test_exception_with_location() {
-    let location = SourceLocation::new("test.mx".to_string(), 42, 10);
+    let location = SourceLocation::with_filename(42, 10, 0, "test.mx".to_string());
     let exc = Exception::with_location(
         "TypeError".to_string(),
         "Type mismatch".to_string(),
@@ -72,7 +72,7 @@ fn test_exception_with_cause() {
 #[test]
 fn test_exception_with_all() {
     let backtrace = vec!["at test (test.mx:1)".to_string()];
-    let location = SourceLocation::new("test.mx".to_string(), 1, 1);
+    let location = SourceLocation::with_filename(1, 1, 0, "test.mx".to_string());
     let cause = Object::exception("ValueError", "Root cause");
 
     let exc = Exception::with_all(
@@ -96,18 +96,18 @@ fn test_exception_with_all() {
 
 #[test]
 fn test_source_location_new() {
-    let loc = SourceLocation::new("main.mx".to_string(), 10, 5);
+    let loc = SourceLocation::with_filename(10, 5, 0, "main.mx".to_string());
 
-    assert_eq!(loc.file, "main.mx");
+    assert_eq!(loc.filename.as_deref(), Some("main.mx"));
     assert_eq!(loc.line, 10);
     assert_eq!(loc.column, 5);
 }
 
 #[test]
 fn test_source_location_equality() {
-    let loc1 = SourceLocation::new("test.mx".to_string(), 1, 1);
-    let loc2 = SourceLocation::new("test.mx".to_string(), 1, 1);
-    let loc3 = SourceLocation::new("test.mx".to_string(), 2, 1);
+    let loc1 = SourceLocation::with_filename(1, 1, 0, "test.mx".to_string());
+    let loc2 = SourceLocation::with_filename(1, 1, 0, "test.mx".to_string());
+    let loc3 = SourceLocation::with_filename(2, 1, 0, "test.mx".to_string());
 
     assert_eq!(loc1, loc2);
     assert_ne!(loc1, loc3);
@@ -213,7 +213,7 @@ fn test_exception_with_full_context() {
         "at function_a (file.mx:10)".to_string(),
     ];
 
-    let location = SourceLocation::new("file.mx".to_string(), 30, 15);
+    let location = SourceLocation::with_filename(30, 15, 0, "file.mx".to_string());
 
     let root_cause = Object::exception("ValueError", "Invalid parameter");
 
@@ -264,10 +264,10 @@ fn test_exception_long_message() {
 
 #[test]
 fn test_source_location_different_files() {
-    let loc1 = SourceLocation::new("file1.mx".to_string(), 10, 5);
-    let loc2 = SourceLocation::new("file2.mx".to_string(), 10, 5);
+    let loc1 = SourceLocation::with_filename(10, 5, 0, "file1.mx".to_string());
+    let loc2 = SourceLocation::with_filename(10, 5, 0, "file2.mx".to_string());
 
-    assert_ne!(loc1.file, loc2.file);
+    assert_ne!(loc1.filename, loc2.filename);
     assert_ne!(loc1, loc2);
 }
 
@@ -17,6 +17,7 @@ fn test_raise_with_expression() {
             value: "Error occurred".to_string(),
             position: pos(1, 7),
         }),
+        cause: None,
         position: pos(1, 1),
     };
     assert_eq!(stmt.position(), pos(1, 1));
@@ -38,6 +39,7 @@ fn test_raise_with_call() {
             trailing_block: None,
             position: pos(1, 7),
         }),
+        cause: None,
         position: pos(1, 1),
     };
     assert_eq!(stmt.position(), pos(1, 1));
@@ -48,6 +50,7 @@ fn test_bare_raise() {
     // Bare raise re-raises the current exception
     let stmt = Statement::Raise {
         exception: None,
+        cause: None,
         position: pos(1, 1),
     };
     assert_eq!(stmt.position(), pos(1, 1));
@@ -445,6 +448,7 @@ fn test_raise_in_rescue_clause() {
                 },
                 Statement::Raise {
                     exception: None, // Bare raise - re-raise
+                    cause: None,
                     position: pos(5, 5),
                 },
             ],
@@ -489,6 +493,7 @@ fn test_begin_in_method() {
         parameters: vec![],
         body: vec![begin_stmt],
         position: pos(1, 1),
+        return_type: None,
     };
 
     assert_eq!(method.position(), pos(1, 1));
@@ -1,3 +1,6 @@
+mod begin_expression_tests;
+mod cause_and_retry_tests;
 mod exception_execution_tests;
 mod exception_objects_tests;
 mod exception_parsing_tests;
+mod rescue_modifier_tests;
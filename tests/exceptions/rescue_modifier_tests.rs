@@ -0,0 +1,343 @@
+// Tests for method-level rescue/ensure clauses and the inline rescue modifier
+
+use metorex::ast::{Expression, Statement};
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+use std::rc::Rc;
+
+fn parse_code(code: &str) -> Vec<Statement> {
+    let tokens = Lexer::new(code).tokenize();
+    Parser::new(tokens).parse().expect("parse failed")
+}
+
+fn execute_code(code: &str) -> Result<VirtualMachine, String> {
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&parse_code(code))
+        .map_err(|e| e.to_string())?;
+    Ok(vm)
+}
+
+fn string(value: &str) -> Object {
+    Object::String(Rc::new(value.to_string()))
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+#[test]
+fn test_parse_def_with_rescue_wraps_body_in_begin() {
+    let statements = parse_code("def f()\n  risky()\nrescue => e\n  handle(e)\nend");
+    match &statements[0] {
+        Statement::FunctionDef { body, .. } => {
+            assert_eq!(body.len(), 1);
+            match &body[0] {
+                Statement::Begin {
+                    body,
+                    rescue_clauses,
+                    ensure_block,
+                    ..
+                } => {
+                    assert_eq!(body.len(), 1);
+                    assert_eq!(rescue_clauses.len(), 1);
+                    assert_eq!(rescue_clauses[0].variable_name.as_deref(), Some("e"));
+                    assert!(ensure_block.is_none());
+                }
+                other => panic!("Expected begin statement, got {:?}", other),
+            }
+        }
+        other => panic!("Expected function definition, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_def_without_rescue_is_unchanged() {
+    let statements = parse_code("def f()\n  1\n  2\nend");
+    match &statements[0] {
+        Statement::FunctionDef { body, .. } => {
+            assert_eq!(body.len(), 2);
+            assert!(matches!(body[0], Statement::Expression { .. }));
+        }
+        other => panic!("Expected function definition, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_rescue_modifier_in_assignment() {
+    let statements = parse_code("value = risky() rescue 0");
+    match &statements[0] {
+        Statement::Assignment {
+            value:
+                Expression::RescueModifier {
+                    expression,
+                    fallback,
+                    ..
+                },
+            ..
+        } => {
+            assert!(matches!(**expression, Expression::Call { .. }));
+            assert!(matches!(
+                **fallback,
+                Expression::IntLiteral { value: 0, .. }
+            ));
+        }
+        other => panic!("Expected assignment with rescue modifier, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_rescue_on_next_line_is_not_a_modifier() {
+    let statements = parse_code("begin\n  risky()\nrescue\n  0\nend");
+    match &statements[0] {
+        Statement::Begin {
+            body,
+            rescue_clauses,
+            ..
+        } => {
+            assert!(matches!(
+                body[0],
+                Statement::Expression {
+                    expression: Expression::Call { .. },
+                    ..
+                }
+            ));
+            assert_eq!(rescue_clauses.len(), 1);
+        }
+        other => panic!("Expected begin statement, got {:?}", other),
+    }
+}
+
+// ============================================================================
+// Method-level rescue
+// ============================================================================
+
+#[test]
+fn test_function_rescue_catches_exception() {
+    let code = r#"
+def fetch()
+  raise "boom"
+rescue => e
+  return "recovered: " + e.message
+end
+
+result = fetch()
+"#;
+    let vm = execute_code(code).unwrap();
+    assert_eq!(
+        vm.environment().get("result"),
+        Some(string("recovered: boom"))
+    );
+}
+
+#[test]
+fn test_function_rescue_matches_exception_type() {
+    let code = r#"
+class NotFound < StandardError
+end
+
+def lookup(missing)
+  if missing
+    raise NotFound, "no such key"
+  end
+  raise "other failure"
+rescue NotFound
+  return "not found"
+rescue => e
+  return "failed: " + e.message
+end
+
+first = lookup(true)
+second = lookup(false)
+"#;
+    let vm = execute_code(code).unwrap();
+    assert_eq!(vm.environment().get("first"), Some(string("not found")));
+    assert_eq!(
+        vm.environment().get("second"),
+        Some(string("failed: other failure"))
+    );
+}
+
+#[test]
+fn test_function_ensure_runs_on_success_and_failure() {
+    let code = r#"
+log = []
+
+def work(fail, log)
+  if fail
+    raise "boom"
+  end
+  log.push("worked")
+rescue
+  log.push("rescued")
+ensure
+  log.push("cleanup")
+end
+
+work(false, log)
+work(true, log)
+"#;
+    let vm = execute_code(code).unwrap();
+    let log = match vm.environment().get("log") {
+        Some(Object::Array(items)) => items.borrow().clone(),
+        other => panic!("Expected array, got {:?}", other),
+    };
+    assert_eq!(
+        log,
+        vec![
+            string("worked"),
+            string("cleanup"),
+            string("rescued"),
+            string("cleanup"),
+        ]
+    );
+}
+
+#[test]
+fn test_function_ensure_without_rescue_propagates_exception() {
+    let code = r#"
+def work()
+  raise "boom"
+ensure
+  puts "cleanup"
+end
+
+work()
+"#;
+    let error = execute_code(code).err().unwrap();
+    assert!(error.contains("boom"));
+}
+
+#[test]
+fn test_method_rescue_inside_class() {
+    let code = r#"
+class Parser
+  def parse(input)
+    raise RuntimeError, "bad input: " + input
+  rescue RuntimeError => e
+    return "error: " + e.message
+  end
+end
+
+result = Parser.new.parse("x")
+"#;
+    let vm = execute_code(code).unwrap();
+    assert_eq!(
+        vm.environment().get("result"),
+        Some(string("error: bad input: x"))
+    );
+}
+
+#[test]
+fn test_function_rescue_with_retry() {
+    let code = r#"
+attempts = [0]
+
+def flaky(attempts)
+  attempts[0] = attempts[0] + 1
+  if attempts[0] < 3
+    raise "not yet"
+  end
+  return attempts[0]
+rescue
+  retry
+end
+
+result = flaky(attempts)
+"#;
+    let vm = execute_code(code).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(Object::Int(3)));
+}
+
+// ============================================================================
+// Rescue modifier
+// ============================================================================
+
+#[test]
+fn test_rescue_modifier_returns_fallback_on_exception() {
+    let code = r#"
+def risky()
+  raise "boom"
+end
+
+value = risky() rescue "default"
+"#;
+    let vm = execute_code(code).unwrap();
+    assert_eq!(vm.environment().get("value"), Some(string("default")));
+}
+
+#[test]
+fn test_rescue_modifier_returns_value_without_exception() {
+    let vm = execute_code("value = 42 rescue 0").unwrap();
+    assert_eq!(vm.environment().get("value"), Some(Object::Int(42)));
+}
+
+#[test]
+fn test_rescue_modifier_with_compound_assignment() {
+    let code = r#"
+def risky()
+  raise "boom"
+end
+
+total = 5
+total += risky() rescue 10
+"#;
+    let vm = execute_code(code).unwrap();
+    assert_eq!(vm.environment().get("total"), Some(Object::Int(15)));
+}
+
+#[test]
+fn test_rescue_modifier_statement_evaluates_fallback() {
+    let code = r#"
+log = []
+
+def risky()
+  raise "boom"
+end
+
+risky() rescue log.push("rescued")
+"#;
+    let vm = execute_code(code).unwrap();
+    match vm.environment().get("log") {
+        Some(Object::Array(items)) => assert_eq!(*items.borrow(), vec![string("rescued")]),
+        other => panic!("Expected array, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_rescue_modifier_inside_method() {
+    let code = r#"
+class Config
+  def port(value)
+    parsed = self.parse(value) rescue 8080
+    return parsed
+  end
+
+  def parse(value)
+    raise "invalid port"
+  end
+end
+
+result = Config.new.port("abc")
+"#;
+    let vm = execute_code(code).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(Object::Int(8080)));
+}
+
+#[test]
+fn test_rescue_modifier_fallback_errors_propagate() {
+    let code = r#"
+def risky()
+  raise "first"
+end
+
+def also_risky()
+  raise "second"
+end
+
+value = risky() rescue also_risky()
+"#;
+    let error = execute_code(code).err().unwrap();
+    assert!(error.contains("second"));
+}
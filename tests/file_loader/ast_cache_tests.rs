@@ -0,0 +1,152 @@
+// Tests for the on-disk AST cache used when loading required files
+
+use crate::common::temp_dir;
+use metorex::ast_cache::{AST_SCHEMA_HASH, AstCache, CACHE_EXTENSION, CacheStats};
+use metorex::file_loader::parse_file;
+use metorex::object::Object;
+use metorex::vm::{VirtualMachine, VmOptions};
+use std::fs;
+use std::path::PathBuf;
+
+fn stats(hits: usize, misses: usize) -> CacheStats {
+    CacheStats { hits, misses }
+}
+
+#[test]
+fn test_cached_ast_matches_parsed_ast() {
+    let dir = temp_dir("ast_cache_round_trip");
+    let source = "class Point\n  def initialize(x)\n    @x = x\n  end\nend\nvalues = [1, 2.5, \"three\", :four]\ncase values.length\nwhen 4\n  puts(\"#{values}\")\nend\n";
+    let path = dir.join("point.mx");
+    let mut cache = AstCache::new(dir.join("cache"));
+
+    let parsed = cache.load_or_parse(&path, source).unwrap();
+    let cached = cache.load_or_parse(&path, source).unwrap();
+
+    assert_eq!(parsed, parse_file(source, "point.mx").unwrap());
+    assert_eq!(cached, parsed);
+    assert_eq!(cache.stats(), stats(1, 1));
+    assert!(cache.entry_path(&path).exists());
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_changed_source_invalidates_entry() {
+    let dir = temp_dir("ast_cache_invalidate");
+    let path = dir.join("value.mx");
+    let mut cache = AstCache::new(dir.join("cache"));
+
+    cache.load_or_parse(&path, "x = 1\n").unwrap();
+    let updated = cache.load_or_parse(&path, "x = 2\n").unwrap();
+
+    assert_eq!(updated, parse_file("x = 2\n", "value.mx").unwrap());
+    assert_eq!(cache.stats(), stats(0, 2));
+
+    cache.load_or_parse(&path, "x = 2\n").unwrap();
+    assert_eq!(cache.stats(), stats(1, 2));
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_corrupt_entry_is_reparsed() {
+    let dir = temp_dir("ast_cache_corrupt");
+    let path = dir.join("value.mx");
+    let mut cache = AstCache::new(dir.join("cache"));
+
+    cache.load_or_parse(&path, "x = 1\n").unwrap();
+    fs::write(cache.entry_path(&path), b"not an ast").unwrap();
+    let statements = cache.load_or_parse(&path, "x = 1\n").unwrap();
+
+    assert_eq!(statements, parse_file("x = 1\n", "value.mx").unwrap());
+    assert_eq!(cache.stats(), stats(0, 2));
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_entry_from_another_ast_schema_is_reparsed() {
+    let dir = temp_dir("ast_cache_schema");
+    let path = dir.join("value.mx");
+    let mut cache = AstCache::new(dir.join("cache"));
+
+    cache.load_or_parse(&path, "x = 1\n").unwrap();
+    let entry = fs::read(cache.entry_path(&path)).unwrap();
+    let schema = AST_SCHEMA_HASH.as_bytes();
+    let at = entry
+        .windows(schema.len())
+        .position(|window| window == schema)
+        .expect("entry should record the AST schema");
+    let mut other = entry.clone();
+    other[at..at + schema.len()].copy_from_slice(&[b'0'; 16][..schema.len()]);
+    fs::write(cache.entry_path(&path), other).unwrap();
+    cache.load_or_parse(&path, "x = 1\n").unwrap();
+
+    assert_eq!(cache.stats(), stats(0, 2));
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_parse_errors_are_not_cached() {
+    let dir = temp_dir("ast_cache_parse_error");
+    let path = dir.join("broken.mx");
+    let mut cache = AstCache::new(dir.join("cache"));
+
+    assert!(cache.load_or_parse(&path, "def (\n").is_err());
+    assert!(!cache.entry_path(&path).exists());
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_entries_are_keyed_by_path() {
+    let cache = AstCache::new("/tmp/cache");
+    let a = cache.entry_path(&PathBuf::from("/project/a.mx"));
+    let b = cache.entry_path(&PathBuf::from("/project/b.mx"));
+
+    assert_ne!(a, b);
+    assert_eq!(a, cache.entry_path(&PathBuf::from("/project/a.mx")));
+    assert_eq!(a.extension().unwrap(), CACHE_EXTENSION);
+    assert!(a.starts_with(cache.dir()));
+}
+
+#[test]
+fn test_clear_removes_entries() {
+    let dir = temp_dir("ast_cache_clear");
+    let mut cache = AstCache::new(dir.join("cache"));
+    cache.load_or_parse(&dir.join("a.mx"), "a = 1\n").unwrap();
+    cache.load_or_parse(&dir.join("b.mx"), "b = 1\n").unwrap();
+
+    assert_eq!(cache.clear().unwrap(), 2);
+    assert_eq!(cache.clear().unwrap(), 0);
+    assert_eq!(AstCache::new(dir.join("missing")).clear().unwrap(), 0);
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_vm_uses_cache_for_required_files() {
+    let dir = temp_dir("ast_cache_vm");
+    let main = dir.join("main.mx");
+    fs::write(dir.join("helper.mx"), "def helper\n  41\nend\n").unwrap();
+    fs::write(&main, "require_relative \"helper\"\nx = helper() + 1\n").unwrap();
+
+    for expected in [stats(0, 2), stats(2, 0)] {
+        let mut vm = VirtualMachine::new();
+        vm.set_ast_cache(Some(AstCache::new(dir.join("cache"))));
+        vm.execute_file(&main).unwrap();
+
+        assert_eq!(vm.environment().get("x"), Some(Object::Int(42)));
+        assert_eq!(vm.ast_cache().unwrap().stats(), expected);
+    }
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_ast_cache_option_enables_cache() {
+    let mut options = VmOptions::default();
+    assert!(
+        VirtualMachine::with_options(options.clone())
+            .ast_cache()
+            .is_none()
+    );
+
+    options.apply("ast_cache=on").unwrap();
+    let vm = VirtualMachine::with_options(options);
+    assert_eq!(vm.ast_cache().unwrap().dir(), AstCache::default_dir());
+}
@@ -0,0 +1,172 @@
+// Tests for the class hierarchy and require graph behind `metorex graph`
+
+use crate::common::temp_dir;
+use metorex::graph::{DefinitionKind, GraphFormat, ProgramGraph};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Create a fresh temp dir holding `files`
+fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = temp_dir(&format!("graph_{}", name));
+    for (file, source) in files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, source).unwrap();
+    }
+    dir
+}
+
+fn build(dir: &Path, load_path: &[PathBuf]) -> ProgramGraph {
+    ProgramGraph::build(&dir.join("main.mx"), load_path).expect("graph should build")
+}
+
+#[test]
+fn test_classes_record_superclasses_and_mixins() {
+    let dir = project(
+        "classes",
+        &[(
+            "main.mx",
+            "module Printable\nend\nclass Shape\nend\nclass Square < Shape\n  include Printable\n  extend Comparable\nend\n",
+        )],
+    );
+    let graph = build(&dir, &[]);
+    let names: Vec<&str> = graph.definitions.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, ["Printable", "Shape", "Square"]);
+    assert_eq!(graph.definitions[0].kind, DefinitionKind::Module);
+
+    let square = graph.definition("Square").unwrap();
+    assert_eq!(square.kind, DefinitionKind::Class);
+    assert_eq!(square.superclass.as_deref(), Some("Shape"));
+    assert_eq!(square.includes, ["Printable"]);
+    assert_eq!(square.extends, ["Comparable"]);
+    assert_eq!(square.file, dir.join("main.mx"));
+}
+
+#[test]
+fn test_reopened_classes_are_merged() {
+    let dir = project(
+        "reopened",
+        &[(
+            "main.mx",
+            "class Point\n  include Walkable\nend\nif true\n  class Point\n    include Drawable\n  end\nend\n",
+        )],
+    );
+    let graph = build(&dir, &[]);
+    assert_eq!(graph.definitions.len(), 1);
+    assert_eq!(graph.definitions[0].includes, ["Walkable", "Drawable"]);
+}
+
+#[test]
+fn test_requires_are_followed_once_and_missing_ones_recorded() {
+    let dir = project(
+        "requires",
+        &[
+            (
+                "main.mx",
+                "require_relative \"lib/shapes\"\nrequire \"util\"\nrequire \"nowhere\"\n",
+            ),
+            (
+                "lib/shapes.mx",
+                "require_relative \"../main\"\nclass Shape\nend\n",
+            ),
+            ("vendor/util.mx", "module Util\nend\n"),
+        ],
+    );
+    let graph = build(&dir, &[dir.join("vendor")]);
+    assert_eq!(
+        graph.files,
+        [
+            dir.join("main.mx"),
+            dir.join("lib/shapes.mx"),
+            dir.join("vendor/util.mx")
+        ]
+    );
+    let edges: Vec<(&Path, Option<&Path>)> = graph
+        .requires
+        .iter()
+        .map(|r| (r.from.as_path(), r.to.as_deref()))
+        .collect();
+    assert_eq!(
+        edges,
+        [
+            (
+                dir.join("main.mx").as_path(),
+                Some(dir.join("lib/shapes.mx").as_path())
+            ),
+            (
+                dir.join("main.mx").as_path(),
+                Some(dir.join("vendor/util.mx").as_path())
+            ),
+            (dir.join("main.mx").as_path(), None),
+            (
+                dir.join("lib/shapes.mx").as_path(),
+                Some(dir.join("main.mx").as_path())
+            ),
+        ]
+    );
+    assert_eq!(graph.requires[2].name, "nowhere");
+    assert_eq!(
+        graph.definition("Util").unwrap().file,
+        dir.join("vendor/util.mx")
+    );
+}
+
+#[test]
+fn test_shadowed_require_is_not_followed() {
+    let dir = project(
+        "shadowed",
+        &[(
+            "main.mx",
+            "def require(name)\n  name\nend\nrequire \"anything\"\n",
+        )],
+    );
+    assert!(build(&dir, &[]).requires.is_empty());
+}
+
+#[test]
+fn test_parse_errors_name_the_file() {
+    let dir = project(
+        "broken",
+        &[
+            ("main.mx", "require_relative \"broken\"\n"),
+            ("broken.mx", "class Broken\n"),
+        ],
+    );
+    let err = ProgramGraph::build(&dir.join("main.mx"), &[]).unwrap_err();
+    assert_eq!(err.path, dir.join("broken.mx"));
+    assert!(err.to_string().contains("broken.mx: "), "{}", err);
+}
+
+#[test]
+fn test_dot_output_uses_paths_relative_to_the_script() {
+    let dir = project(
+        "dot",
+        &[
+            (
+                "main.mx",
+                "require_relative \"lib/shape\"\nclass Square < Shape\n  include Comparable\nend\n",
+            ),
+            ("lib/shape.mx", "class Shape\nend\n"),
+        ],
+    );
+    let dot = build(&dir, &[]).render(GraphFormat::Dot);
+    assert!(dot.starts_with("digraph metorex {\n"), "{}", dot);
+    assert!(
+        dot.contains("\"class:Square\" -> \"class:Shape\" [arrowhead=empty];"),
+        "{}",
+        dot
+    );
+    assert!(
+        dot.contains("\"class:Square\" -> \"class:Comparable\" [style=dashed, label=\"include\"];"),
+        "{}",
+        dot
+    );
+    // Comparable is used but not defined, so it is drawn dotted
+    assert!(dot.contains("\"class:Comparable\" [label=\"Comparable\", shape=box, style=dotted];"));
+    assert!(
+        dot.contains("\"file:main.mx\" -> \"file:lib/shape.mx\";"),
+        "{}",
+        dot
+    );
+    assert!(!dot.contains(dir.to_str().unwrap()), "{}", dot);
+}
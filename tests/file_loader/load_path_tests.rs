@@ -0,0 +1,115 @@
+// Tests for finding libraries on the load path and loading them with `require`
+
+use crate::common::temp_dir;
+use metorex::file_loader::find_in_load_path;
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::{VirtualMachine, VmOptions};
+use std::fs;
+use std::path::PathBuf;
+
+fn vm_with_load_path(load_path: Vec<PathBuf>) -> VirtualMachine {
+    VirtualMachine::with_options(VmOptions {
+        load_path,
+        ..VmOptions::default()
+    })
+}
+
+fn run(vm: &mut VirtualMachine, source: &str) -> Result<Object, String> {
+    let program = Parser::new(Lexer::new(source).tokenize())
+        .parse()
+        .map_err(|e| format!("{:?}", e))?;
+    vm.execute_program(&program)
+        .map(|value| value.unwrap_or(Object::Nil))
+        .map_err(|e| e.to_string())
+}
+
+#[test]
+fn test_finds_the_first_directory_holding_the_library() {
+    let first = temp_dir("load_path_first");
+    let second = temp_dir("load_path_second");
+    fs::write(second.join("util.mx"), "").unwrap();
+
+    let found = find_in_load_path(&[first.clone(), second.clone()], "util").unwrap();
+    assert_eq!(found, second.join("util.mx"));
+
+    fs::write(first.join("util.mx"), "").unwrap();
+    let found = find_in_load_path(&[first.clone(), second], "util").unwrap();
+    assert_eq!(found, first.join("util.mx"));
+}
+
+#[test]
+fn test_finds_libraries_in_subdirectories() {
+    let dir = temp_dir("load_path_nested");
+    fs::create_dir_all(dir.join("text")).unwrap();
+    fs::write(dir.join("text").join("wrap.mx"), "").unwrap();
+
+    let found = find_in_load_path(std::slice::from_ref(&dir), "text/wrap").unwrap();
+    assert_eq!(found, dir.join("text").join("wrap.mx"));
+}
+
+#[test]
+fn test_missing_library_names_the_searched_directories() {
+    let dir = temp_dir("load_path_missing");
+    let err = find_in_load_path(std::slice::from_ref(&dir), "nope").unwrap_err();
+    assert!(
+        err.to_string().contains(&format!(
+            "cannot load such file -- nope (load path: {})",
+            dir.display()
+        )),
+        "{}",
+        err
+    );
+
+    let err = find_in_load_path(&[], "nope").unwrap_err();
+    assert!(err.to_string().contains("(load path: empty)"), "{}", err);
+}
+
+#[test]
+fn test_absolute_names_skip_the_load_path() {
+    let dir = temp_dir("load_path_absolute");
+    let path = dir.join("direct.mx");
+    fs::write(&path, "").unwrap();
+
+    let name = dir.join("direct").display().to_string();
+    assert_eq!(find_in_load_path(&[], &name).unwrap(), path);
+}
+
+#[test]
+fn test_require_loads_a_library_once() {
+    let dir = temp_dir("load_path_require_once");
+    fs::write(dir.join("counter.mx"), "loaded = true\n").unwrap();
+
+    let mut vm = vm_with_load_path(vec![dir]);
+    assert_eq!(run(&mut vm, "require(\"counter\")"), Ok(Object::Bool(true)));
+    assert_eq!(vm.environment().get("loaded"), Some(Object::Bool(true)));
+
+    run(&mut vm, "loaded = false").unwrap();
+    assert_eq!(
+        run(&mut vm, "require(\"counter\")"),
+        Ok(Object::Bool(false))
+    );
+    assert_eq!(vm.environment().get("loaded"), Some(Object::Bool(false)));
+}
+
+#[test]
+fn test_require_reports_missing_libraries() {
+    let mut vm = vm_with_load_path(Vec::new());
+    let err = run(&mut vm, "require(\"nope\")").unwrap_err();
+    assert!(
+        err.contains("Error in require: ") && err.contains("cannot load such file -- nope"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_require_library_from_the_host() {
+    let dir = temp_dir("load_path_host");
+    fs::write(dir.join("greeting.mx"), "greeting = \"hi\"\n").unwrap();
+
+    let mut vm = vm_with_load_path(vec![dir]);
+    assert!(vm.require_library("greeting").unwrap());
+    assert!(!vm.require_library("greeting").unwrap());
+}
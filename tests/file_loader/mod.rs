@@ -1,3 +1,7 @@
+mod ast_cache_tests;
+mod graph_tests;
 mod load_file_source_tests;
+mod load_path_tests;
 mod parse_file_tests;
+mod path_names_tests;
 mod resolve_path_tests;
@@ -0,0 +1,132 @@
+// Tests for separator normalization, path helpers and extension inference
+
+use crate::common::{eval, temp_dir};
+use metorex::file_loader::find_file_path;
+use metorex::object::Object;
+use metorex::path_names::{
+    basename, dirname, extname, join, normalize_separators, path_key, strip_verbatim_prefix,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn test_backslashes_become_forward_slashes() {
+    assert_eq!(
+        normalize_separators(r"lib\util\text.mx"),
+        "lib/util/text.mx"
+    );
+    assert_eq!(
+        normalize_separators(r"\\server\share\a.mx"),
+        "//server/share/a.mx"
+    );
+    assert_eq!(normalize_separators("lib/a.mx"), "lib/a.mx");
+}
+
+#[test]
+fn test_verbatim_paths_are_left_alone() {
+    assert_eq!(normalize_separators(r"\\?\C:\lib\a.mx"), r"\\?\C:\lib\a.mx");
+}
+
+#[test]
+fn test_strip_verbatim_prefix() {
+    assert_eq!(
+        strip_verbatim_prefix(PathBuf::from(r"\\?\C:\lib\a.mx")),
+        PathBuf::from(r"C:\lib\a.mx")
+    );
+    assert_eq!(
+        strip_verbatim_prefix(PathBuf::from(r"\\?\UNC\server\share\a.mx")),
+        PathBuf::from(r"\\server\share\a.mx")
+    );
+    assert_eq!(
+        strip_verbatim_prefix(PathBuf::from(r"\\?\Volume{1234}\a.mx")),
+        PathBuf::from(r"\\?\Volume{1234}\a.mx")
+    );
+    assert_eq!(
+        strip_verbatim_prefix(PathBuf::from("/srv/app/a.mx")),
+        PathBuf::from("/srv/app/a.mx")
+    );
+}
+
+#[test]
+fn test_path_key_folds_case_only_when_asked() {
+    let path = Path::new("/Src/Lib.mx");
+    assert_eq!(path_key(path, false), PathBuf::from("/Src/Lib.mx"));
+    assert_eq!(path_key(path, true), PathBuf::from("/src/lib.mx"));
+}
+
+#[test]
+fn test_join_uses_single_separators() {
+    assert_eq!(join(&["lib/", "/util", "a.mx"]), "lib/util/a.mx");
+    assert_eq!(join(&[r"lib\util", "a.mx"]), "lib/util/a.mx");
+    assert_eq!(join(&["/", "a"]), "/a");
+    assert_eq!(join::<&str>(&[]), "");
+}
+
+#[test]
+fn test_basename() {
+    assert_eq!(basename("lib/util/a.mx", None), "a.mx");
+    assert_eq!(basename(r"lib\util\a.mx", Some(".mx")), "a");
+    assert_eq!(basename("a.tar.gz", Some(".*")), "a.tar");
+    assert_eq!(basename("lib/util/", None), "util");
+    assert_eq!(basename(".mx", Some(".mx")), ".mx");
+    assert_eq!(basename("/", None), "/");
+}
+
+#[test]
+fn test_dirname() {
+    assert_eq!(dirname("lib/util/a.mx"), "lib/util");
+    assert_eq!(dirname(r"lib\a.mx"), "lib");
+    assert_eq!(dirname("a.mx"), ".");
+    assert_eq!(dirname("/a.mx"), "/");
+    assert_eq!(dirname("lib//a.mx"), "lib");
+}
+
+#[test]
+fn test_extname() {
+    assert_eq!(extname("lib/a.mx"), ".mx");
+    assert_eq!(extname("a.tar.gz"), ".gz");
+    assert_eq!(extname("lib/.profile"), "");
+    assert_eq!(extname("notes."), "");
+    assert_eq!(extname("lib.d/readme"), "");
+}
+
+#[test]
+fn test_file_helpers_from_metorex() {
+    assert_eq!(
+        eval(r#"File.join("lib\\util", "a.mx")"#),
+        Object::string("lib/util/a.mx")
+    );
+    assert_eq!(
+        eval(r#"File.basename("lib/a.mx", ".mx")"#),
+        Object::string("a")
+    );
+    assert_eq!(eval(r#"File.dirname("lib/a.mx")"#), Object::string("lib"));
+    assert_eq!(eval(r#"File.extname("lib/a.mx")"#), Object::string(".mx"));
+    assert_eq!(
+        eval(r#"File.fnmatch("lib/*.mx", "lib\\a.mx")"#),
+        Object::Bool(true)
+    );
+}
+
+#[test]
+fn test_extension_is_appended_to_dotted_names() {
+    let dir = temp_dir("paths_dotted");
+    fs::write(dir.join("parser.v2.mx"), "x = 1").unwrap();
+
+    assert_eq!(
+        find_file_path(&dir.join("parser.v2")).unwrap(),
+        dir.join("parser.v2.mx")
+    );
+}
+
+#[test]
+fn test_directory_does_not_hide_a_source_file() {
+    let dir = temp_dir("paths_directory");
+    fs::create_dir_all(dir.join("lib")).unwrap();
+    fs::write(dir.join("lib.mx"), "x = 1").unwrap();
+
+    assert_eq!(
+        find_file_path(&dir.join("lib")).unwrap(),
+        dir.join("lib.mx")
+    );
+}
@@ -0,0 +1,30 @@
+use crate::common::metorex;
+use std::path::Path;
+
+#[test]
+fn test_builtins_json_is_the_catalog() {
+    let output = metorex(Path::new("."), &["builtins", "--json"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim_end(),
+        metorex::builtin_catalog::to_json()
+    );
+}
+
+#[test]
+fn test_builtins_lists_signatures() {
+    let output = metorex(Path::new("."), &["builtins"]);
+    assert!(output.status.success());
+    let listing = String::from_utf8_lossy(&output.stdout);
+    assert!(listing.contains("String < Object  Immutable UTF-8 text\n"));
+    assert!(listing.contains("  #match_glob(pattern: String) -> Bool  "));
+    assert!(listing.contains("  .parse(text: String) -> URI  "));
+    assert!(listing.contains("  puts(*values) -> Nil  "));
+}
+
+#[test]
+fn test_builtins_rejects_unknown_arguments() {
+    let output = metorex(Path::new("."), &["builtins", "--yaml"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Usage: metorex builtins [--json]"));
+}
@@ -0,0 +1,278 @@
+use crate::common::{metorex, metorex_command, output_with_input, stderr, stdout, temp_dir};
+
+#[test]
+fn test_version_flag() {
+    let output = metorex(&std::env::temp_dir(), &["--version"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), format!("metorex {}\n", metorex::version()));
+}
+
+#[test]
+fn test_help_lists_the_commands() {
+    let output = metorex(&std::env::temp_dir(), &["--help"]);
+    assert!(output.status.success());
+    let help = stdout(&output);
+    for command in [
+        "run", "repl", "fmt", "check", "test", "ast", "tokens", "doc",
+    ] {
+        assert!(help.contains(&format!("\n  {} ", command)), "{}", help);
+    }
+}
+
+#[test]
+fn test_a_file_runs_with_or_without_the_run_command() {
+    let dir = temp_dir("cli_commands_run");
+    std::fs::write(dir.join("app.mx"), "puts(\"ran\")\n").unwrap();
+
+    for args in [
+        &["app.mx"][..],
+        &["run", "app.mx"],
+        &["-w", "run", "app.mx"],
+    ] {
+        let output = metorex(&dir, args);
+        assert!(output.status.success(), "{:?}: {}", args, stderr(&output));
+        assert_eq!(stdout(&output), "ran\n");
+    }
+}
+
+#[test]
+fn test_eval_runs_code_from_the_command_line() {
+    let output = metorex(&std::env::temp_dir(), &["-e", "puts(1 + 2)"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "3\n");
+
+    let output = metorex(&std::env::temp_dir(), &["-e", "raise \"boom\""]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_include_and_require_flags() {
+    let dir = temp_dir("cli_commands_require");
+    std::fs::create_dir_all(dir.join("lib")).unwrap();
+    std::fs::write(
+        dir.join("lib").join("util.mx"),
+        "def helper\n  \"helped\"\nend\n",
+    )
+    .unwrap();
+
+    let output = metorex(&dir, &["-I", "lib", "-r", "util", "-e", "puts(helper())"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "helped\n");
+
+    let output = metorex(
+        &dir,
+        &[
+            "-I",
+            "lib",
+            "-e",
+            "puts(require(\"util\"))\nputs(require(\"util\"))",
+        ],
+    );
+    assert_eq!(stdout(&output), "true\nfalse\n");
+
+    let output = metorex(&dir, &["-r", "util", "-e", "1"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stderr(&output).contains("cannot load such file -- util"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn test_usage_errors_exit_with_status_2() {
+    let dir = std::env::temp_dir();
+    for args in [
+        &["--no-such-flag"][..],
+        &["fmt"],
+        &["run", "a.mx", "b.mx"],
+        &["-e", "1", "a.mx"],
+        &["-e", "1", "tokens", "a.mx"],
+    ] {
+        let output = metorex(&dir, args);
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+        assert!(!stderr(&output).is_empty(), "{:?}", args);
+    }
+}
+
+#[test]
+fn test_missing_file_exits_with_status_1() {
+    let output = metorex(&std::env::temp_dir(), &["metorex_no_such_file.mx"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("Error resolving file path 'metorex_no_such_file.mx'"));
+}
+
+#[test]
+fn test_tokens_and_ast_commands() {
+    let dir = temp_dir("cli_commands_tokens");
+    std::fs::write(dir.join("app.mx"), "x = 1\n").unwrap();
+
+    let output = metorex(&dir, &["tokens", "app.mx"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        stdout(&output),
+        "1:1     x\n1:3     =\n1:5     1\n1:6     \\n\n2:1     EOF\n"
+    );
+
+    let output = metorex(&dir, &["ast", "app.mx"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(
+        stdout(&output).starts_with("Assignment {"),
+        "{}",
+        stdout(&output)
+    );
+}
+
+#[test]
+fn test_check_command_reports_type_errors() {
+    let dir = temp_dir("cli_commands_check");
+    std::fs::write(dir.join("ok.mx"), "def add(x: Int) -> Int\n  x\nend\n").unwrap();
+    std::fs::write(
+        dir.join("bad.mx"),
+        "def add(x: Int) -> Int\n  x\nend\nadd(\"a\")\n",
+    )
+    .unwrap();
+
+    assert!(metorex(&dir, &["check", "ok.mx"]).status.success());
+    let output = metorex(&dir, &["--color=never", "check", "bad.mx"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stderr(&output).contains("type error"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn test_fmt_command_rewrites_or_checks_files() {
+    let dir = temp_dir("cli_commands_fmt");
+    let path = dir.join("messy.mx");
+    std::fs::write(&path, "if true\n\tputs(1)  \nend\n\n").unwrap();
+
+    let output = metorex(&dir, &["fmt", "--check", "messy.mx"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "messy.mx: needs formatting\n");
+
+    let output = metorex(&dir, &["fmt", "messy.mx"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "if true\n  puts(1)\nend\n"
+    );
+    assert!(
+        metorex(&dir, &["fmt", "--check", "messy.mx"])
+            .status
+            .success()
+    );
+}
+
+#[test]
+fn test_test_command_runs_each_test_file() {
+    let dir = temp_dir("cli_commands_test");
+    std::fs::create_dir_all(dir.join("tests")).unwrap();
+    std::fs::write(dir.join("tests").join("math_test.mx"), "1 + 1\n").unwrap();
+    std::fs::write(dir.join("tests").join("test_fail.mx"), "raise \"boom\"\n").unwrap();
+    std::fs::write(
+        dir.join("tests").join("helper.mx"),
+        "raise \"not a test\"\n",
+    )
+    .unwrap();
+
+    let output = metorex(&dir, &["test"]);
+    assert_eq!(output.status.code(), Some(1));
+    let report = stdout(&output);
+    assert!(report.contains("ok   tests/math_test.mx"), "{}", report);
+    assert!(report.contains("FAIL tests/test_fail.mx"), "{}", report);
+    assert!(
+        report.ends_with("\n2 files, 1 passed, 1 failed\n"),
+        "{}",
+        report
+    );
+
+    std::fs::remove_file(dir.join("tests").join("test_fail.mx")).unwrap();
+    assert!(metorex(&dir, &["test", "tests"]).status.success());
+
+    let output = metorex(&dir, &["test", "missing_dir"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_test_command_counts_assertions_and_can_skip_them() {
+    let dir = temp_dir("cli_commands_test_assertions");
+    std::fs::write(
+        dir.join("checks_test.mx"),
+        "assert(1 + 1 == 2)\nassert(true, \"fine\")\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("broken_test.mx"), "assert(false, \"broken\")\n").unwrap();
+
+    let output = metorex(&dir, &["--color=never", "test"]);
+    let report = stdout(&output);
+    assert!(
+        report.contains("ok   checks_test.mx (2 assertions, "),
+        "{}",
+        report
+    );
+    assert!(
+        report.contains("FAIL broken_test.mx") && report.contains("AssertionError: broken"),
+        "{}",
+        report
+    );
+
+    let output = metorex(&dir, &["--color=never", "--no-contracts", "test"]);
+    assert!(output.status.success(), "{}", stdout(&output));
+    assert!(
+        stdout(&output).contains("ok   checks_test.mx (0 assertions, "),
+        "{}",
+        stdout(&output)
+    );
+}
+
+#[test]
+fn test_doc_command() {
+    let dir = std::env::temp_dir();
+    let output = metorex(&dir, &["doc", "Array"]);
+    assert!(output.status.success());
+    assert!(
+        stdout(&output).starts_with("Array < Object"),
+        "{}",
+        stdout(&output)
+    );
+    assert!(stdout(&output).contains("  #length() -> Int"));
+
+    let output = metorex(&dir, &["doc", "NoSuchThing"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("No builtin class or function named 'NoSuchThing'"));
+}
+
+#[test]
+fn test_post_mortem_opens_a_repl_in_the_raising_frame() {
+    let dir = temp_dir("cli_commands_post_mortem");
+    std::fs::write(
+        dir.join("app.mx"),
+        "def check(limit)\n  total = limit * 2\n  raise \"too big\"\nend\ncheck(21)\n",
+    )
+    .unwrap();
+
+    let output = output_with_input(
+        metorex_command(&dir).args(["--color=never", "--post-mortem", "app.mx"]),
+        "total\n$!.message\n.frame 1\n.exit\n",
+    );
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("too big"), "{}", stderr(&output));
+    let session = stdout(&output);
+    assert!(session.contains("* 0: check\n  1: <main>"), "{}", session);
+    assert!(
+        session.contains("=> 42\n=> \"too big\"\n// frame 1: <main>"),
+        "{}",
+        session
+    );
+}
+
+#[test]
+fn test_post_mortem_only_applies_to_scripts() {
+    let output = metorex(&std::env::temp_dir(), &["--post-mortem", "doc", "Array"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("--post-mortem only applies when running a script"));
+}
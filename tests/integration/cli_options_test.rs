@@ -0,0 +1,60 @@
+use crate::common::{metorex, metorex_command, script_dir, stderr, stdout};
+
+const RECURSE: &str = "def down(n)\n  if n == 0\n    return 0\n  end\n  return 1 + down(n - 1)\nend\nunused = 1\nputs(down(30))\nputs(rand(1000))\n";
+
+#[test]
+fn test_mx_opts_configures_the_vm() {
+    let dir = script_dir("cli_options_env", RECURSE);
+    let output = metorex_command(&dir)
+        .env("MX_OPTS", "stack_depth=10")
+        .arg("main.mx")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("stack level too deep"));
+}
+
+#[test]
+fn test_vm_opt_flag_overrides_mx_opts() {
+    let dir = script_dir("cli_options_flag", RECURSE);
+    let output = metorex_command(&dir)
+        .env("MX_OPTS", "stack_depth=10")
+        .args(["--vm-opt", "stack_depth=100", "--vm-opt=seed=5", "main.mx"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).starts_with("30\n"));
+
+    let again = metorex(&dir, &["--vm-opt=seed=5", "main.mx"]);
+    assert_eq!(stdout(&output), stdout(&again));
+}
+
+#[test]
+fn test_warnings_option_reports_resolver_warnings() {
+    let dir = script_dir("cli_options_warnings", RECURSE);
+    let quiet = metorex(&dir, &["main.mx"]);
+    assert!(!stderr(&quiet).contains("warning"));
+
+    let noisy = metorex_command(&dir)
+        .env("MX_OPTS", "warnings=on")
+        .arg("main.mx")
+        .output()
+        .unwrap();
+    assert!(stderr(&noisy).contains("warning: Unused variable 'unused'"));
+}
+
+#[test]
+fn test_invalid_options_exit_with_usage_error() {
+    let dir = script_dir("cli_options_invalid", RECURSE);
+    let bad_env = metorex_command(&dir)
+        .env("MX_OPTS", "opt_level=9")
+        .arg("main.mx")
+        .output()
+        .unwrap();
+    assert_eq!(bad_env.status.code(), Some(2));
+    assert!(stderr(&bad_env).contains("MX_OPTS: invalid value '9'"));
+
+    let bad_flag = metorex(&dir, &["--vm-opt", "nope", "main.mx"]);
+    assert_eq!(bad_flag.status.code(), Some(2));
+    assert!(stderr(&bad_flag).contains("expected key=value"));
+}
@@ -0,0 +1,142 @@
+use crate::common::{metorex, temp_dir};
+use metorex::examples::{self, ExampleOutcome, ExecutionSnapshot, first_difference};
+use metorex::vm::VmOptions;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A directory under the system temp dir holding the given example files
+fn setup(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = temp_dir(&format!("examples_cli_{}", name));
+    for (file, source) in files {
+        fs::write(dir.join(file), source).unwrap();
+    }
+    dir
+}
+
+const COUNTER: &str = "class Counter\n  def initialize\n    @count = 0\n  end\n\n  def tick\n    @count += 1\n    @count\n  end\nend\n\ncounter = Counter.new\ncounter.tick\nputs(counter.tick)\n";
+
+#[test]
+fn test_the_bundled_examples_match_their_golden_files() {
+    let output = metorex(Path::new(env!("CARGO_MANIFEST_DIR")), &["examples"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{}", stdout);
+    assert!(stdout.contains(" 0 failed"), "{}", stdout);
+}
+
+#[test]
+fn test_a_snapshot_captures_output_status_and_definitions() {
+    let dir = setup(
+        "snapshot",
+        &[
+            ("counter.mx", COUNTER),
+            (
+                "ask.mx",
+                "name = gets.chomp\nputs(\"Hi #{name}\")\nraise \"done\"\n",
+            ),
+            ("ask.input", "Ada\n"),
+        ],
+    );
+
+    let snapshot = ExecutionSnapshot::capture(&dir.join("counter.mx"), VmOptions::default());
+    assert_eq!(snapshot.stdout, "2\n");
+    assert_eq!(snapshot.exit_status, 0);
+    assert_eq!(snapshot.error, None);
+    assert_eq!(
+        snapshot.to_string(),
+        "exit: 0\ndefined: Counter, Counter#initialize, Counter#tick\nassigned: counter\n--- stdout\n2\n"
+    );
+
+    let snapshot = ExecutionSnapshot::capture(&dir.join("ask.mx"), VmOptions::default());
+    assert_eq!(snapshot.stdout, "Hi Ada\n");
+    assert_eq!(snapshot.exit_status, 1);
+    assert!(snapshot.error.unwrap().ends_with("RuntimeError: done"));
+}
+
+#[test]
+fn test_first_difference_points_at_the_first_line_that_changed() {
+    assert_eq!(first_difference("a\nb\n", "a\nb\n"), None);
+    let difference = first_difference("a\nb\n", "a\nc\n").unwrap();
+    assert_eq!(difference.line, 2);
+    assert_eq!(
+        difference.to_string(),
+        "line 2: expected \"b\", found \"c\""
+    );
+    assert_eq!(
+        first_difference("a\n", "a\nb\n").unwrap().to_string(),
+        "line 2: expected \"\", found \"b\""
+    );
+    assert_eq!(
+        first_difference("a\n", "a").unwrap().to_string(),
+        "line 2: expected \"\", found end of file"
+    );
+}
+
+#[test]
+fn test_check_example_compares_and_blesses_golden_files() {
+    let dir = setup("check", &[("counter.mx", COUNTER)]);
+    let example = dir.join("counter.mx");
+    let check = |bless| examples::check_example(&example, VmOptions::default(), bless).unwrap();
+
+    assert_eq!(check(false), ExampleOutcome::Missing);
+    assert!(!examples::golden_path(&example).exists());
+    assert_eq!(check(true), ExampleOutcome::Blessed);
+    assert_eq!(check(false), ExampleOutcome::Passed);
+    assert_eq!(check(true), ExampleOutcome::Passed);
+
+    fs::write(
+        &example,
+        COUNTER.replace("puts(counter.tick)", "puts(counter.tick + 1)"),
+    )
+    .unwrap();
+    match check(false) {
+        ExampleOutcome::Failed(difference) => {
+            assert_eq!(difference.line, 5);
+            assert_eq!(difference.expected.as_deref(), Some("2"));
+            assert_eq!(difference.found.as_deref(), Some("3"));
+        }
+        other => panic!("expected a failure, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_examples_reports_new_failing_and_blessed_examples() {
+    let dir = setup(
+        "cli",
+        &[("counter.mx", COUNTER), ("hello.mx", "puts(\"hi\")\n")],
+    );
+
+    let output = metorex(&dir, &["examples", "."]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(stdout.contains("NEW   counter.mx\n      no counter.golden to compare with; run with --bless to write it"), "{}", stdout);
+    assert!(
+        stdout.ends_with("2 examples, 0 passed, 2 failed\n"),
+        "{}",
+        stdout
+    );
+
+    let output = metorex(&dir, &["examples", ".", "--bless"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(
+        String::from_utf8_lossy(&output.stdout)
+            .ends_with("2 examples, 0 passed, 2 blessed, 0 failed\n")
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("hello.golden")).unwrap(),
+        "exit: 0\n--- stdout\nhi\n"
+    );
+
+    fs::write(dir.join("hello.mx"), "puts(\"hello\")\n").unwrap();
+    let output = metorex(&dir, &["examples", "hello.mx", "counter.mx"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout,
+        "FAIL  hello.mx\n      line 3: expected \"hi\", found \"hello\"\nok    counter.mx\n\n2 examples, 1 passed, 1 failed\n"
+    );
+
+    let empty = setup("empty", &[]);
+    let output = metorex(&empty, &["examples", "."]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No examples found"));
+}
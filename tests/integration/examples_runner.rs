@@ -1,17 +1,27 @@
 // Examples runner
 
-use std::process::Command;
+use std::path::Path;
 
-use crate::common::EXAMPLES_DIR;
+use crate::common::{EXAMPLES_DIR, metorex, metorex_command, output_with_input, stdout};
 
 fn run_example(path: &str) -> String {
-    let binary = env!("CARGO_BIN_EXE_metorex");
-    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
     let full_path = format!("{}/{}", EXAMPLES_DIR, path);
-    let mut cmd = Command::new(binary);
-    cmd.current_dir(manifest_dir).arg(&full_path);
+    let output = metorex(manifest_dir, &[&full_path]);
+    assert!(
+        output.status.success(),
+        "example {} exited with status {:?}",
+        path,
+        output.status
+    );
+
+    stdout(&output)
+}
 
-    let output = cmd.output().expect("failed to execute example");
+fn run_example_with_input(path: &str, input: &str) -> String {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let full_path = format!("{}/{}", EXAMPLES_DIR, path);
+    let output = output_with_input(metorex_command(manifest_dir).arg(&full_path), input);
     assert!(
         output.status.success(),
         "example {} exited with status {:?}",
@@ -19,7 +29,7 @@ fn run_example(path: &str) -> String {
         output.status
     );
 
-    String::from_utf8(output.stdout).expect("stdout was not utf8")
+    stdout(&output)
 }
 
 #[test]
@@ -28,6 +38,13 @@ fn test_basics_greeting_line_execution() {
     assert_eq!(output, "Hello, Ada!\n");
 }
 
+#[test]
+fn test_basics_console_input_execution() {
+    let expected = "What is your name? How old are you? \nHello, Ada!\nNext year you will be 37.\nTotal: 7\ntrue\n";
+    let output = run_example_with_input("basics/console_input.mx", "Ada\n36\n4.5\n");
+    assert_eq!(output, expected);
+}
+
 #[test]
 fn test_basics_string_methods_execution() {
     let expected = r#"=== Basic String Methods ===
@@ -205,6 +222,35 @@ Goodbye, Bob!
     assert_eq!(output, expected);
 }
 
+#[test]
+fn test_metaprogramming_define_method_execution() {
+    let expected = "RED\nBLUE\n512\n256\n2\ntrue\n";
+    let output = run_example("metaprogramming/define_method.mx");
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn test_metaprogramming_syntax_trees_execution() {
+    let expected = r#"=== Statements ===
+:Assignment at line 1
+:Expression at line 2
+
+=== Fields ===
+[:target, :value, :position]
+:BinaryOp
+:Multiply
+
+=== Identifiers ===
+[total, price, puts, total, tax]
+
+=== Evaluating ===
+32
+30
+"#;
+    let output = run_example("metaprogramming/syntax_trees.mx");
+    assert_eq!(output, expected);
+}
+
 #[test]
 fn test_algorithms_filter_even_numbers_execution() {
     let expected = "[2, 4, 6]\n";
@@ -490,6 +536,12 @@ fn test_advanced_exception_handling_execution() {
     assert_eq!(output, expected);
 }
 
+#[test]
+fn test_advanced_traits_execution() {
+    let output = run_example("advanced/traits.mx");
+    assert_eq!(output, "Drawing circle\n");
+}
+
 #[test]
 fn test_errors_exception_hierarchy_execution() {
     let expected = "Example 1: Different exception types\nCaught RuntimeError: Runtime error occurred\nCaught TypeError: Type mismatch\nCaught ValueError: Invalid value\n\nExample 2: Catching StandardError\nCaught as StandardError: A runtime error\nCaught as StandardError: A type error\n\nExample 3: Specific to general exception handling\nSpecific handler for RuntimeError: Runtime issue\nSpecific handler for TypeError: Type issue\nGeneral handler for StandardError: Value issue\n\nExample 4: Exception type checking\nRuntimeError is a StandardError: true\nError message: Test error\n";
@@ -563,8 +615,12 @@ main
 
 #[test]
 fn test_introspection_code_object_execution() {
-    let expected = r#"greet.source_location = 1:1
-calculate.source_location = 5:1
+    let expected = r#"greet.source_location line = 1
+calculate.source_location line = 5
+def calculate(x, y)
+  x + y
+end
+:FunctionDef
 "#;
     let output = run_example("introspection/code_object.mx");
     assert_eq!(output, expected);
@@ -577,7 +633,7 @@ simple_func
 nil
 Object
 Object
-<Binding with 22 vars>
+<Binding with 65 vars>
 18
 "#;
     let output = run_example("introspection/closure_namespace.mx");
@@ -0,0 +1,67 @@
+use crate::common::{metorex, temp_dir};
+use std::fs;
+use std::path::PathBuf;
+
+/// A directory under the system temp dir holding `main.mx` and `lib/shape.mx`
+fn setup(name: &str, main: &str) -> PathBuf {
+    let dir = temp_dir(&format!("graph_cli_{}", name));
+    fs::create_dir_all(dir.join("lib")).unwrap();
+    fs::write(dir.join("main.mx"), main).unwrap();
+    fs::write(dir.join("lib/shape.mx"), "class Shape\nend\n").unwrap();
+    dir
+}
+
+#[test]
+fn test_graph_prints_dot_for_the_script_and_its_requires() {
+    let dir = setup(
+        "dot",
+        "require_relative \"lib/shape\"\nclass Square < Shape\nend\n",
+    );
+    for args in [
+        &["graph", "main.mx"][..],
+        &["graph", "main.mx", "--format", "dot"],
+    ] {
+        let output = metorex(&dir, args);
+        assert_eq!(output.status.code(), Some(0));
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.starts_with("digraph metorex {"), "{}", stdout);
+        assert!(
+            stdout.contains("\"class:Square\" -> \"class:Shape\""),
+            "{}",
+            stdout
+        );
+        assert!(
+            stdout.contains("\"file:main.mx\" -> \"file:lib/shape.mx\""),
+            "{}",
+            stdout
+        );
+    }
+}
+
+#[test]
+fn test_graph_searches_the_include_path_for_require() {
+    let dir = setup("include", "require \"shape\"\n");
+    let output = metorex(&dir, &["-I", "lib", "graph", "main.mx"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("\"file:main.mx\" -> \"file:lib/shape.mx\""),
+        "{}",
+        stdout
+    );
+
+    let output = metorex(&dir, &["graph", "main.mx"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("shape (not found)"), "{}", stdout);
+}
+
+#[test]
+fn test_graph_errors() {
+    let dir = setup("errors", "class Broken\n");
+    let output = metorex(&dir, &["graph", "main.mx"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("main.mx"));
+
+    let output = metorex(&dir, &["graph", "main.mx", "--format", "svg"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid graph format 'svg'"));
+}
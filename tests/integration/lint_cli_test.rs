@@ -0,0 +1,114 @@
+use crate::common::{metorex, temp_dir};
+use std::fs;
+use std::path::PathBuf;
+
+/// A directory under the system temp dir holding `main.mx`
+fn setup(name: &str, main: &str) -> PathBuf {
+    let dir = temp_dir(&format!("lint_cli_{}", name));
+    fs::write(dir.join("main.mx"), main).unwrap();
+    dir
+}
+
+const UNUSED: &str = "def f\n  total = 1\n  return 2\n  puts(3)\nend\n";
+
+#[test]
+fn test_lint_prints_offenses_and_a_summary() {
+    let dir = setup("text", UNUSED);
+    let output = metorex(&dir, &["lint"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0), "{}", stdout);
+    assert_eq!(
+        stdout,
+        "main.mx:2:3: warning: Variable 'total' is assigned but never used [unused-variable]\n\
+         main.mx:4:3: warning: Unreachable code after 'return' [unreachable-code]\n\
+         1 file checked, 2 offenses\n"
+    );
+}
+
+#[test]
+fn test_lint_summary_counts_in_the_plural() {
+    let dir = setup("plural", "puts(1)\n");
+    fs::write(dir.join("other.mx"), "puts(2)\n").unwrap();
+    let output = metorex(&dir, &["lint"]);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "2 files checked, 0 offenses\n"
+    );
+}
+
+#[test]
+fn test_lint_prints_json() {
+    let dir = setup("json", UNUSED);
+    let output = metorex(&dir, &["lint", "main.mx", "--format", "json"]);
+    let offenses: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(offenses.as_array().unwrap().len(), 2);
+    assert_eq!(offenses[0]["file"], "main.mx");
+    assert_eq!(offenses[0]["rule"], "unused-variable");
+    assert_eq!(offenses[0]["level"], "warning");
+    assert_eq!(offenses[0]["line"], 2);
+    assert_eq!(offenses[0]["column"], 3);
+}
+
+#[test]
+fn test_lint_reads_the_configuration_file() {
+    let dir = setup("config", UNUSED);
+    fs::write(
+        dir.join(".metorex-lint.toml"),
+        "[rules]\nunused-variable = \"off\"\nunreachable-code = \"error\"\n",
+    )
+    .unwrap();
+    let output = metorex(&dir, &["lint", "main.mx"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert_eq!(
+        stdout,
+        "main.mx:4:3: error: Unreachable code after 'return' [unreachable-code]\n\
+         1 file checked, 1 offense\n"
+    );
+
+    fs::write(
+        dir.join("quiet.toml"),
+        "[rules]\nunreachable-code = \"off\"\n",
+    )
+    .unwrap();
+    let output = metorex(&dir, &["lint", "main.mx", "--config", "quiet.toml"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("1 file checked, 1 offense\n"));
+}
+
+#[test]
+fn test_lint_fails_on_syntax_errors_and_bad_configuration() {
+    let dir = setup("errors", "def f(\n");
+    let output = metorex(&dir, &["lint", "main.mx"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("error:"));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("[syntax]"));
+
+    fs::write(dir.join("bad.toml"), "[rules]\nno-such-rule = \"error\"\n").unwrap();
+    let output = metorex(&dir, &["lint", "main.mx", "--config", "bad.toml"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown lint rule 'no-such-rule'"));
+}
+
+#[test]
+fn test_lint_runs_the_rules_in_the_lint_directory() {
+    let dir = setup("scripts", "puts(1)\n");
+    fs::create_dir_all(dir.join("lint")).unwrap();
+    fs::write(
+        dir.join("lint/no_literals.mx"),
+        "def check(node, diagnostics)\n  if node.type == :IntLiteral\n    diagnostics.report(node, \"Name this number\")\n  end\nend\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join(".metorex-lint.toml"),
+        "[rules]\nno-literals = \"error\"\n",
+    )
+    .unwrap();
+    let output = metorex(&dir, &["lint", "main.mx"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert_eq!(
+        stdout,
+        "main.mx:1:6: error: Name this number [no-literals]\n1 file checked, 1 offense\n"
+    );
+}
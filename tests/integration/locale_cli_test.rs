@@ -0,0 +1,62 @@
+use crate::common::{metorex, stderr, temp_dir};
+use std::fs;
+use std::path::PathBuf;
+
+/// A directory under the system temp dir holding `script.mx` and `es.txt`
+fn setup(name: &str, script: &str, catalog: &str) -> PathBuf {
+    let dir = temp_dir(&format!("locale_cli_{}", name));
+    fs::write(dir.join("script.mx"), script).unwrap();
+    fs::write(dir.join("es.txt"), catalog).unwrap();
+    dir
+}
+
+const SPANISH: &str = "\
+# Spanish messages
+E0301 = Método '{method}' no definido para el tipo '{type}'
+E1038 = Falta 'end' después del if
+";
+
+#[test]
+fn test_locale_translates_runtime_and_parse_errors() {
+    let dir = setup("runtime", "x = 1\nx.frob\n", SPANISH);
+    let output = metorex(&dir, &["--locale", "es.txt", "script.mx"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        stderr(&output).contains("Método 'frob' no definido para el tipo 'Int'"),
+        "{}",
+        stderr(&output)
+    );
+
+    fs::write(dir.join("broken.mx"), "if true\n  x = 1\n").unwrap();
+    let output = metorex(&dir, &["check", "--locale", "es.txt", "broken.mx"]);
+    assert!(
+        stderr(&output).contains("Falta 'end' después del if"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn test_locale_en_keeps_the_built_in_messages() {
+    let dir = setup("english", "x = 1\nx.frob\n", SPANISH);
+    let output = metorex(&dir, &["--locale", "en", "script.mx"]);
+    assert!(
+        stderr(&output).contains("Undefined method 'frob' for type 'Int'"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn test_bad_catalogs_are_usage_errors() {
+    let dir = setup("bad", "puts(1)\n", "E0404 = {dividend} / 0\n");
+    let output = metorex(&dir, &["--locale", "es.txt", "script.mx"]);
+    assert_eq!(output.status.code(), Some(2));
+    let err = stderr(&output);
+    assert!(err.contains("es.txt"), "{}", err);
+    assert!(err.contains("line 1"), "{}", err);
+
+    let output = metorex(&dir, &["--locale", "missing.txt", "script.mx"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("cannot read message catalog"));
+}
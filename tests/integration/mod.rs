@@ -1,3 +1,13 @@
+mod builtins_cli_test;
+mod cli_commands_test;
+mod cli_options_test;
+mod examples_cli_test;
 mod examples_runner;
+mod graph_cli_test;
+mod lint_cli_test;
+mod locale_cli_test;
+mod rc_file_cli_test;
+mod record_replay_cli_test;
 mod test_runner;
+mod type_check_cli_test;
 mod version_test;
@@ -0,0 +1,98 @@
+use crate::common::{metorex_command, output_with_input, stderr, stdout, temp_dir};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const SCRIPT: &str = "puts(greet(\"world\"))\n";
+
+/// A home directory and a project directory with a script in it, under the
+/// system temp dir
+fn setup(name: &str, home_rc: &str) -> (PathBuf, PathBuf) {
+    let root = temp_dir(&format!("rc_cli_{}", name));
+    let home = root.join("home");
+    let project = root.join("project");
+    fs::create_dir_all(&home).unwrap();
+    fs::create_dir_all(&project).unwrap();
+    fs::write(home.join(".metorexrc"), home_rc).unwrap();
+    fs::write(project.join("main.mx"), SCRIPT).unwrap();
+    (home, project)
+}
+
+/// A metorex command in `project` with `home` as the home directory
+fn command(home: &Path, project: &Path) -> Command {
+    let mut command = metorex_command(project);
+    command.env("HOME", home).env("USERPROFILE", home);
+    command
+}
+
+const GREET: &str = "def greet(name)\n  \"hello #{name}\"\nend\n";
+
+#[test]
+fn test_rc_flag_runs_rc_files_before_a_script() {
+    let (home, project) = setup("run", GREET);
+    let output = command(&home, &project)
+        .args(["run", "--rc", "main.mx"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "hello world\n");
+
+    // Without the flag a script does not see the rc file
+    let output = command(&home, &project).args(["main.mx"]).output().unwrap();
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_repl_runs_rc_files_unless_no_rc() {
+    let (home, project) = setup("repl", GREET);
+    let input = "puts(greet(\"repl\"))\n.exit\n";
+    let output = output_with_input(command(&home, &project).args(["repl"]), input);
+    assert!(
+        stdout(&output).contains("hello repl"),
+        "{}",
+        stdout(&output)
+    );
+
+    let output = output_with_input(command(&home, &project).args(["repl", "--no-rc"]), input);
+    assert!(!stdout(&output).contains("hello repl"));
+    assert!(stderr(&output).contains("greet"), "{}", stderr(&output));
+}
+
+#[test]
+fn test_rc_file_parse_errors_are_reported_with_the_file_name() {
+    let (home, project) = setup("parse_error", "def greet(\n");
+    let output = command(&home, &project)
+        .args(["--rc", "main.mx"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let err = stderr(&output);
+    assert!(err.contains("Error in rc file"), "{}", err);
+    assert!(err.contains(".metorexrc"), "{}", err);
+    assert!(err.contains("Parse error"), "{}", err);
+
+    // The REPL reports the broken file and starts anyway
+    let output = output_with_input(
+        command(&home, &project).args(["repl"]),
+        "puts(1 + 1)\n.exit\n",
+    );
+    assert!(stderr(&output).contains("Error in rc file"));
+    assert!(stdout(&output).contains("2\n"));
+}
+
+#[test]
+fn test_rc_flags_only_apply_to_scripts_and_the_repl() {
+    let (home, project) = setup("usage", GREET);
+    let output = command(&home, &project)
+        .args(["check", "--rc", "main.mx"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("--rc"));
+
+    let output = command(&home, &project)
+        .args(["--rc", "--no-rc", "main.mx"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
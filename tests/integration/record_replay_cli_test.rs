@@ -0,0 +1,54 @@
+use crate::common::{metorex, metorex_command, output_with_input, stderr, stdout, temp_dir};
+use std::fs;
+
+const SCRIPT: &str = "name = gets\nputs(\"hi \" + name)\nputs(rand(1000000))\n";
+
+#[test]
+fn test_recorded_runs_replay_with_the_same_output() {
+    let dir = temp_dir("record_replay");
+    fs::write(dir.join("main.mx"), SCRIPT).unwrap();
+
+    let recorded = output_with_input(
+        metorex_command(&dir).args(["--record", "main.mxr", "main.mx"]),
+        "ada\n",
+    );
+    assert!(recorded.status.success(), "{}", stderr(&recorded));
+    assert!(stdout(&recorded).starts_with("hi ada\n"));
+
+    let replayed = metorex(&dir, &["replay", "main.mxr"]);
+    assert!(replayed.status.success(), "{}", stderr(&replayed));
+    assert_eq!(stdout(&replayed), stdout(&recorded));
+    assert!(stderr(&replayed).contains("replay: all 7 events of"));
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_replay_reports_a_changed_script() {
+    let dir = temp_dir("record_diverge");
+    fs::write(dir.join("main.mx"), "puts(1)\n").unwrap();
+
+    let recorded = metorex(&dir, &["--record", "main.mxr", "main.mx"]);
+    assert!(recorded.status.success(), "{}", stderr(&recorded));
+
+    let log = dir.join("main.mxr");
+    let text = fs::read_to_string(&log).unwrap();
+    fs::write(&log, text.replace("\"1\\n\"", "\"2\\n\"")).unwrap();
+    let replayed = metorex(&dir, &["replay", "main.mxr"]);
+    assert_eq!(replayed.status.code(), Some(1));
+    assert!(
+        stderr(&replayed).contains("replay: diverged at event 2: recorded output"),
+        "{}",
+        stderr(&replayed)
+    );
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_record_needs_a_script() {
+    let dir = temp_dir("record_needs_script");
+    let output = metorex(&dir, &["--record=trace.mxr", "builtins"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("--record only applies when running a script"));
+}
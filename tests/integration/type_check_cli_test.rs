@@ -0,0 +1,45 @@
+use crate::common::{metorex, script_dir, stderr, stdout};
+
+const TYPED: &str =
+    "def add(x: Int, y: Int) -> Int\n  x + y\nend\nputs(add(1, 2))\nputs(add(\"a\", 2))\n";
+
+#[test]
+fn test_typecheck_reports_errors_without_running() {
+    let dir = script_dir("type_check_errors", TYPED);
+    let output = metorex(&dir, &["--color=never", "typecheck", "main.mx"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).is_empty(), "{}", stdout(&output));
+    assert!(
+        stderr(&output)
+            .contains("5:10: type error: argument 'x' of 'add' expects Int, found String"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn test_typecheck_accepts_well_typed_files() {
+    let dir = script_dir(
+        "type_check_clean",
+        "def add(x: Int) -> Int\n  x\nend\nadd(1)\n",
+    );
+    let output = metorex(&dir, &["typecheck", "main.mx"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("no type errors found"));
+}
+
+#[test]
+fn test_check_types_flag_validates_calls() {
+    let dir = script_dir("type_check_flag", TYPED);
+    let unchecked = metorex(&dir, &["main.mx"]);
+    assert!(stderr(&unchecked).contains("Cannot apply operator"));
+
+    let checked = metorex(&dir, &["--check-types", "main.mx"]);
+    assert!(!checked.status.success());
+    assert_eq!(stdout(&checked), "3\n");
+    assert!(
+        stderr(&checked).contains("argument 'x' of 'add' expects Int, got String"),
+        "{}",
+        stderr(&checked)
+    );
+}
@@ -168,6 +168,49 @@ fn test_lexer_carriage_return_handling() {
     assert_eq!(token.kind, TokenKind::Newline);
 }
 
+#[test]
+fn test_lexer_crlf_is_one_newline() {
+    let source = "x = 1\r\ny # note\r\n";
+    let tokens = Lexer::new(source).tokenize();
+    let kinds: Vec<&TokenKind> = tokens.iter().map(|t| &t.kind).collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            &TokenKind::Ident("x".into()),
+            &TokenKind::Equal,
+            &TokenKind::Int(1),
+            &TokenKind::Newline,
+            &TokenKind::Ident("y".into()),
+            &TokenKind::Comment("note".to_string()),
+            &TokenKind::Newline,
+            &TokenKind::EOF,
+        ]
+    );
+    assert_eq!(tokens[3].text(source), "\r\n");
+    assert_eq!(tokens[4].position, Position::new(2, 1, 7));
+    assert_eq!(tokens[7].position, Position::new(3, 1, source.len()));
+}
+
+#[test]
+fn test_lexer_crlf_does_not_leak_into_strings() {
+    let tokens = Lexer::new("'a'\r\nr\"b\"\r\n").tokenize();
+
+    assert_eq!(tokens[0].kind, TokenKind::String("a".to_string()));
+    assert_eq!(tokens[2].kind, TokenKind::String("b".to_string()));
+    assert_eq!(tokens[3].kind, TokenKind::Newline);
+}
+
+#[test]
+fn test_lexer_skips_byte_order_mark() {
+    let source = "\u{feff}puts 1";
+    let tokens = Lexer::new(source).tokenize();
+
+    assert_eq!(tokens[0].kind, TokenKind::Ident("puts".into()));
+    assert_eq!(tokens[0].position, Position::new(1, 1, 3));
+    assert_eq!(tokens[0].text(source), "puts");
+}
+
 #[test]
 fn test_lexer_mixed_whitespace() {
     let mut lexer = Lexer::new(" \t \t # comment");
@@ -205,15 +248,15 @@ fn test_lexer_offset_tracking() {
 
     let token1 = lexer.next_token();
     assert_eq!(token1.position.offset, 0);
-    assert_eq!(token1.kind, TokenKind::Ident("a".to_string()));
+    assert_eq!(token1.kind, TokenKind::Ident("a".into()));
 
     let token2 = lexer.next_token();
     assert_eq!(token2.position.offset, 2);
-    assert_eq!(token2.kind, TokenKind::Ident("b".to_string()));
+    assert_eq!(token2.kind, TokenKind::Ident("b".into()));
 
     let token3 = lexer.next_token();
     assert_eq!(token3.position.offset, 4);
-    assert_eq!(token3.kind, TokenKind::Ident("c".to_string()));
+    assert_eq!(token3.kind, TokenKind::Ident("c".into()));
 }
 
 #[test]
@@ -269,7 +312,7 @@ fn test_lexer_identifier_character_handling() {
 
     let token = lexer.next_token();
     // Identifiers are now recognized
-    assert_eq!(token.kind, TokenKind::Ident("x".to_string()));
+    assert_eq!(token.kind, TokenKind::Ident("x".into()));
 }
 
 #[test]
@@ -325,7 +368,7 @@ fn test_lexer_identifier_then_newline() {
     let mut lexer = Lexer::new("x\n");
 
     let token1 = lexer.next_token();
-    assert_eq!(token1.kind, TokenKind::Ident("x".to_string()));
+    assert_eq!(token1.kind, TokenKind::Ident("x".into()));
 
     let token2 = lexer.next_token();
     assert_eq!(token2.kind, TokenKind::Newline);
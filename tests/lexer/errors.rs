@@ -53,7 +53,7 @@ fn test_lexer_invalid_character_in_stream() {
     let tokens: Vec<_> = lexer.collect();
 
     // Should lex valid tokens and skip invalid character
-    assert_eq!(tokens[0].kind, TokenKind::Ident("x".to_string()));
+    assert_eq!(tokens[0].kind, TokenKind::Ident("x".into()));
     assert_eq!(tokens[1].kind, TokenKind::Equal);
     assert_eq!(tokens[2].kind, TokenKind::Int(1));
     // $ returns EOF, so iteration stops
@@ -63,8 +63,7 @@ fn test_lexer_invalid_character_in_stream() {
 fn test_lexer_standalone_bang() {
     let mut lexer = Lexer::new("!");
     let token = lexer.next_token();
-    // Standalone ! returns EOF (not yet implemented as unary operator)
-    assert_eq!(token.kind, TokenKind::EOF);
+    assert_eq!(token.kind, TokenKind::Bang);
 }
 
 #[test]
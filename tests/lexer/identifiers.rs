@@ -8,42 +8,42 @@ use metorex::lexer::{Lexer, TokenKind};
 fn test_lexer_simple_identifier() {
     let mut lexer = Lexer::new("foo");
     let token = lexer.next_token();
-    assert_eq!(token.kind, TokenKind::Ident("foo".to_string()));
+    assert_eq!(token.kind, TokenKind::Ident("foo".into()));
 }
 
 #[test]
 fn test_lexer_identifier_with_underscores() {
     let mut lexer = Lexer::new("foo_bar_baz");
     let token = lexer.next_token();
-    assert_eq!(token.kind, TokenKind::Ident("foo_bar_baz".to_string()));
+    assert_eq!(token.kind, TokenKind::Ident("foo_bar_baz".into()));
 }
 
 #[test]
 fn test_lexer_identifier_starting_with_underscore() {
     let mut lexer = Lexer::new("_private");
     let token = lexer.next_token();
-    assert_eq!(token.kind, TokenKind::Ident("_private".to_string()));
+    assert_eq!(token.kind, TokenKind::Ident("_private".into()));
 }
 
 #[test]
 fn test_lexer_identifier_with_numbers() {
     let mut lexer = Lexer::new("var123");
     let token = lexer.next_token();
-    assert_eq!(token.kind, TokenKind::Ident("var123".to_string()));
+    assert_eq!(token.kind, TokenKind::Ident("var123".into()));
 }
 
 #[test]
 fn test_lexer_identifier_all_underscores() {
     let mut lexer = Lexer::new("___");
     let token = lexer.next_token();
-    assert_eq!(token.kind, TokenKind::Ident("___".to_string()));
+    assert_eq!(token.kind, TokenKind::Ident("___".into()));
 }
 
 #[test]
 fn test_lexer_single_letter_identifier() {
     let mut lexer = Lexer::new("x");
     let token = lexer.next_token();
-    assert_eq!(token.kind, TokenKind::Ident("x".to_string()));
+    assert_eq!(token.kind, TokenKind::Ident("x".into()));
 }
 
 #[test]
@@ -51,27 +51,27 @@ fn test_lexer_multiple_identifiers() {
     let mut lexer = Lexer::new("foo bar baz");
 
     let token1 = lexer.next_token();
-    assert_eq!(token1.kind, TokenKind::Ident("foo".to_string()));
+    assert_eq!(token1.kind, TokenKind::Ident("foo".into()));
 
     let token2 = lexer.next_token();
-    assert_eq!(token2.kind, TokenKind::Ident("bar".to_string()));
+    assert_eq!(token2.kind, TokenKind::Ident("bar".into()));
 
     let token3 = lexer.next_token();
-    assert_eq!(token3.kind, TokenKind::Ident("baz".to_string()));
+    assert_eq!(token3.kind, TokenKind::Ident("baz".into()));
 }
 
 #[test]
 fn test_lexer_uppercase_identifier() {
     let mut lexer = Lexer::new("FOO");
     let token = lexer.next_token();
-    assert_eq!(token.kind, TokenKind::Ident("FOO".to_string()));
+    assert_eq!(token.kind, TokenKind::Ident("FOO".into()));
 }
 
 #[test]
 fn test_lexer_mixed_case_identifier() {
     let mut lexer = Lexer::new("FooBar");
     let token = lexer.next_token();
-    assert_eq!(token.kind, TokenKind::Ident("FooBar".to_string()));
+    assert_eq!(token.kind, TokenKind::Ident("FooBar".into()));
 }
 
 // ===== Keyword Tests =====
@@ -146,12 +146,21 @@ fn test_lexer_keyword_nil() {
     assert_eq!(token.kind, TokenKind::Nil);
 }
 
+#[test]
+fn test_lexer_keyword_logic() {
+    let mut lexer = Lexer::new("and or not android");
+    assert_eq!(lexer.next_token().kind, TokenKind::And);
+    assert_eq!(lexer.next_token().kind, TokenKind::Or);
+    assert_eq!(lexer.next_token().kind, TokenKind::Not);
+    assert_eq!(lexer.next_token().kind, TokenKind::Ident("android".into()));
+}
+
 #[test]
 fn test_lexer_keyword_not_partial_match() {
     let mut lexer = Lexer::new("definition");
     let token = lexer.next_token();
     // Should be identifier, not keyword
-    assert_eq!(token.kind, TokenKind::Ident("definition".to_string()));
+    assert_eq!(token.kind, TokenKind::Ident("definition".into()));
 }
 
 #[test]
@@ -159,7 +168,7 @@ fn test_lexer_keyword_with_suffix() {
     let mut lexer = Lexer::new("class_name");
     let token = lexer.next_token();
     // Should be identifier, not keyword
-    assert_eq!(token.kind, TokenKind::Ident("class_name".to_string()));
+    assert_eq!(token.kind, TokenKind::Ident("class_name".into()));
 }
 
 #[test]
@@ -167,7 +176,7 @@ fn test_lexer_keyword_case_sensitive() {
     let mut lexer = Lexer::new("Class");
     let token = lexer.next_token();
     // Keywords are case-sensitive, so this should be an identifier
-    assert_eq!(token.kind, TokenKind::Ident("Class".to_string()));
+    assert_eq!(token.kind, TokenKind::Ident("Class".into()));
 }
 
 #[test]
@@ -193,31 +202,28 @@ fn test_lexer_multiple_keywords() {
 fn test_lexer_instance_variable() {
     let mut lexer = Lexer::new("@name");
     let token = lexer.next_token();
-    assert_eq!(token.kind, TokenKind::InstanceVar("name".to_string()));
+    assert_eq!(token.kind, TokenKind::InstanceVar("name".into()));
 }
 
 #[test]
 fn test_lexer_instance_variable_with_underscores() {
     let mut lexer = Lexer::new("@_private_var");
     let token = lexer.next_token();
-    assert_eq!(
-        token.kind,
-        TokenKind::InstanceVar("_private_var".to_string())
-    );
+    assert_eq!(token.kind, TokenKind::InstanceVar("_private_var".into()));
 }
 
 #[test]
 fn test_lexer_instance_variable_with_numbers() {
     let mut lexer = Lexer::new("@value123");
     let token = lexer.next_token();
-    assert_eq!(token.kind, TokenKind::InstanceVar("value123".to_string()));
+    assert_eq!(token.kind, TokenKind::InstanceVar("value123".into()));
 }
 
 #[test]
 fn test_lexer_instance_variable_single_char() {
     let mut lexer = Lexer::new("@x");
     let token = lexer.next_token();
-    assert_eq!(token.kind, TokenKind::InstanceVar("x".to_string()));
+    assert_eq!(token.kind, TokenKind::InstanceVar("x".into()));
 }
 
 #[test]
@@ -225,13 +231,13 @@ fn test_lexer_multiple_instance_variables() {
     let mut lexer = Lexer::new("@foo @bar @baz");
 
     let token1 = lexer.next_token();
-    assert_eq!(token1.kind, TokenKind::InstanceVar("foo".to_string()));
+    assert_eq!(token1.kind, TokenKind::InstanceVar("foo".into()));
 
     let token2 = lexer.next_token();
-    assert_eq!(token2.kind, TokenKind::InstanceVar("bar".to_string()));
+    assert_eq!(token2.kind, TokenKind::InstanceVar("bar".into()));
 
     let token3 = lexer.next_token();
-    assert_eq!(token3.kind, TokenKind::InstanceVar("baz".to_string()));
+    assert_eq!(token3.kind, TokenKind::InstanceVar("baz".into()));
 }
 
 // ===== Class Variable Tests =====
@@ -240,28 +246,28 @@ fn test_lexer_multiple_instance_variables() {
 fn test_lexer_class_variable() {
     let mut lexer = Lexer::new("@@count");
     let token = lexer.next_token();
-    assert_eq!(token.kind, TokenKind::ClassVar("count".to_string()));
+    assert_eq!(token.kind, TokenKind::ClassVar("count".into()));
 }
 
 #[test]
 fn test_lexer_class_variable_with_underscores() {
     let mut lexer = Lexer::new("@@_shared_state");
     let token = lexer.next_token();
-    assert_eq!(token.kind, TokenKind::ClassVar("_shared_state".to_string()));
+    assert_eq!(token.kind, TokenKind::ClassVar("_shared_state".into()));
 }
 
 #[test]
 fn test_lexer_class_variable_with_numbers() {
     let mut lexer = Lexer::new("@@version2");
     let token = lexer.next_token();
-    assert_eq!(token.kind, TokenKind::ClassVar("version2".to_string()));
+    assert_eq!(token.kind, TokenKind::ClassVar("version2".into()));
 }
 
 #[test]
 fn test_lexer_class_variable_single_char() {
     let mut lexer = Lexer::new("@@x");
     let token = lexer.next_token();
-    assert_eq!(token.kind, TokenKind::ClassVar("x".to_string()));
+    assert_eq!(token.kind, TokenKind::ClassVar("x".into()));
 }
 
 #[test]
@@ -269,13 +275,13 @@ fn test_lexer_multiple_class_variables() {
     let mut lexer = Lexer::new("@@foo @@bar @@baz");
 
     let token1 = lexer.next_token();
-    assert_eq!(token1.kind, TokenKind::ClassVar("foo".to_string()));
+    assert_eq!(token1.kind, TokenKind::ClassVar("foo".into()));
 
     let token2 = lexer.next_token();
-    assert_eq!(token2.kind, TokenKind::ClassVar("bar".to_string()));
+    assert_eq!(token2.kind, TokenKind::ClassVar("bar".into()));
 
     let token3 = lexer.next_token();
-    assert_eq!(token3.kind, TokenKind::ClassVar("baz".to_string()));
+    assert_eq!(token3.kind, TokenKind::ClassVar("baz".into()));
 }
 
 // ===== Mixed Identifier, Keyword, and Variable Tests =====
@@ -285,13 +291,13 @@ fn test_lexer_identifier_and_keyword() {
     let mut lexer = Lexer::new("var if name");
 
     let token1 = lexer.next_token();
-    assert_eq!(token1.kind, TokenKind::Ident("var".to_string()));
+    assert_eq!(token1.kind, TokenKind::Ident("var".into()));
 
     let token2 = lexer.next_token();
     assert_eq!(token2.kind, TokenKind::If);
 
     let token3 = lexer.next_token();
-    assert_eq!(token3.kind, TokenKind::Ident("name".to_string()));
+    assert_eq!(token3.kind, TokenKind::Ident("name".into()));
 }
 
 #[test]
@@ -299,13 +305,13 @@ fn test_lexer_all_variable_types() {
     let mut lexer = Lexer::new("local @instance @@class");
 
     let token1 = lexer.next_token();
-    assert_eq!(token1.kind, TokenKind::Ident("local".to_string()));
+    assert_eq!(token1.kind, TokenKind::Ident("local".into()));
 
     let token2 = lexer.next_token();
-    assert_eq!(token2.kind, TokenKind::InstanceVar("instance".to_string()));
+    assert_eq!(token2.kind, TokenKind::InstanceVar("instance".into()));
 
     let token3 = lexer.next_token();
-    assert_eq!(token3.kind, TokenKind::ClassVar("class".to_string()));
+    assert_eq!(token3.kind, TokenKind::ClassVar("class".into()));
 }
 
 #[test]
@@ -316,13 +322,13 @@ fn test_lexer_keywords_and_variables() {
     assert_eq!(token1.kind, TokenKind::Def);
 
     let token2 = lexer.next_token();
-    assert_eq!(token2.kind, TokenKind::InstanceVar("name".to_string()));
+    assert_eq!(token2.kind, TokenKind::InstanceVar("name".into()));
 
     let token3 = lexer.next_token();
     assert_eq!(token3.kind, TokenKind::Class);
 
     let token4 = lexer.next_token();
-    assert_eq!(token4.kind, TokenKind::ClassVar("count".to_string()));
+    assert_eq!(token4.kind, TokenKind::ClassVar("count".into()));
 
     let token5 = lexer.next_token();
     assert_eq!(token5.kind, TokenKind::End);
@@ -333,13 +339,13 @@ fn test_lexer_identifier_with_keyword_substring() {
     let mut lexer = Lexer::new("ifdef classify endgame");
 
     let token1 = lexer.next_token();
-    assert_eq!(token1.kind, TokenKind::Ident("ifdef".to_string()));
+    assert_eq!(token1.kind, TokenKind::Ident("ifdef".into()));
 
     let token2 = lexer.next_token();
-    assert_eq!(token2.kind, TokenKind::Ident("classify".to_string()));
+    assert_eq!(token2.kind, TokenKind::Ident("classify".into()));
 
     let token3 = lexer.next_token();
-    assert_eq!(token3.kind, TokenKind::Ident("endgame".to_string()));
+    assert_eq!(token3.kind, TokenKind::Ident("endgame".into()));
 }
 
 #[test]
@@ -347,7 +353,7 @@ fn test_lexer_empty_instance_variable() {
     let mut lexer = Lexer::new("@ ");
     let token = lexer.next_token();
     // Instance variable with empty name
-    assert_eq!(token.kind, TokenKind::InstanceVar("".to_string()));
+    assert_eq!(token.kind, TokenKind::InstanceVar("".into()));
 }
 
 #[test]
@@ -355,5 +361,33 @@ fn test_lexer_empty_class_variable() {
     let mut lexer = Lexer::new("@@ ");
     let token = lexer.next_token();
     // Class variable with empty name
-    assert_eq!(token.kind, TokenKind::ClassVar("".to_string()));
+    assert_eq!(token.kind, TokenKind::ClassVar("".into()));
+}
+
+#[test]
+fn test_lexer_current_exception_variable() {
+    let tokens = Lexer::new("puts($!.message)").tokenize();
+    assert_eq!(tokens[2].kind, TokenKind::Ident("$!".into()));
+    assert_eq!(tokens[3].kind, TokenKind::Dot);
+}
+
+#[test]
+fn test_lexer_symbols() {
+    let tokens = Lexer::new(":name :empty? :@target :@@count :class").tokenize();
+    assert_eq!(tokens[0].kind, TokenKind::Symbol("name".into()));
+    assert_eq!(tokens[1].kind, TokenKind::Symbol("empty?".into()));
+    assert_eq!(tokens[2].kind, TokenKind::Symbol("@target".into()));
+    assert_eq!(tokens[3].kind, TokenKind::Symbol("@@count".into()));
+    assert_eq!(tokens[4].kind, TokenKind::Symbol("class".into()));
+}
+
+#[test]
+fn test_lexer_colon_after_value_is_not_a_symbol() {
+    let tokens = Lexer::new("{x: 1, \"y\":z}").tokenize();
+    let kinds: Vec<TokenKind> = tokens.into_iter().map(|token| token.kind).collect();
+    assert_eq!(kinds[1], TokenKind::Ident("x".into()));
+    assert_eq!(kinds[2], TokenKind::Colon);
+    assert_eq!(kinds[5], TokenKind::String("y".into()));
+    assert_eq!(kinds[6], TokenKind::Colon);
+    assert_eq!(kinds[7], TokenKind::Ident("z".into()));
 }
@@ -1,5 +1,6 @@
 // Higher-level lexer integration scenarios
 
+use super::interpolation::expression_source;
 use metorex::lexer::{InterpolationPart, Lexer, TokenKind};
 
 // ===== Integration Tests =====
@@ -11,16 +12,16 @@ fn test_lexer_full_expression() {
     let tokens: Vec<_> = lexer.collect();
 
     assert_eq!(tokens[0].kind, TokenKind::Def);
-    assert_eq!(tokens[1].kind, TokenKind::Ident("add".to_string()));
+    assert_eq!(tokens[1].kind, TokenKind::Ident("add".into()));
     assert_eq!(tokens[2].kind, TokenKind::LParen);
-    assert_eq!(tokens[3].kind, TokenKind::Ident("x".to_string()));
+    assert_eq!(tokens[3].kind, TokenKind::Ident("x".into()));
     assert_eq!(tokens[4].kind, TokenKind::Comma);
-    assert_eq!(tokens[5].kind, TokenKind::Ident("y".to_string()));
+    assert_eq!(tokens[5].kind, TokenKind::Ident("y".into()));
     assert_eq!(tokens[6].kind, TokenKind::RParen);
     assert_eq!(tokens[7].kind, TokenKind::Newline);
-    assert_eq!(tokens[8].kind, TokenKind::Ident("x".to_string()));
+    assert_eq!(tokens[8].kind, TokenKind::Ident("x".into()));
     assert_eq!(tokens[9].kind, TokenKind::Plus);
-    assert_eq!(tokens[10].kind, TokenKind::Ident("y".to_string()));
+    assert_eq!(tokens[10].kind, TokenKind::Ident("y".into()));
     assert_eq!(tokens[11].kind, TokenKind::Newline);
     assert_eq!(tokens[12].kind, TokenKind::End);
 }
@@ -31,32 +32,26 @@ fn test_lexer_class_definition() {
     let mut lexer = Lexer::new(source);
 
     assert_eq!(lexer.next_token().kind, TokenKind::Class);
-    assert_eq!(
-        lexer.next_token().kind,
-        TokenKind::Ident("Person".to_string())
-    );
+    assert_eq!(lexer.next_token().kind, TokenKind::Ident("Person".into()));
     assert_eq!(lexer.next_token().kind, TokenKind::Newline);
     assert_eq!(lexer.next_token().kind, TokenKind::Def);
     assert_eq!(
         lexer.next_token().kind,
-        TokenKind::Ident("initialize".to_string())
+        TokenKind::Ident("initialize".into())
     );
     assert_eq!(lexer.next_token().kind, TokenKind::LParen);
     assert_eq!(
         lexer.next_token().kind,
-        TokenKind::InstanceVar("name".to_string())
+        TokenKind::InstanceVar("name".into())
     );
     assert_eq!(lexer.next_token().kind, TokenKind::Comma);
     assert_eq!(
         lexer.next_token().kind,
-        TokenKind::InstanceVar("age".to_string())
+        TokenKind::InstanceVar("age".into())
     );
     assert_eq!(lexer.next_token().kind, TokenKind::RParen);
     assert_eq!(lexer.next_token().kind, TokenKind::Newline);
-    assert_eq!(
-        lexer.next_token().kind,
-        TokenKind::ClassVar("count".to_string())
-    );
+    assert_eq!(lexer.next_token().kind, TokenKind::ClassVar("count".into()));
     assert_eq!(lexer.next_token().kind, TokenKind::PlusEqual);
 }
 
@@ -88,7 +83,7 @@ fn test_lexer_array_and_hash_syntax() {
     assert_eq!(tokens[5].kind, TokenKind::Int(3));
     assert_eq!(tokens[6].kind, TokenKind::RBracket);
     assert_eq!(tokens[7].kind, TokenKind::LBrace);
-    assert_eq!(tokens[8].kind, TokenKind::Ident("x".to_string()));
+    assert_eq!(tokens[8].kind, TokenKind::Ident("x".into()));
     assert_eq!(tokens[9].kind, TokenKind::Colon);
 }
 
@@ -102,9 +97,9 @@ fn test_lexer_string_interpolation_complex() {
         TokenKind::InterpolatedString(parts) => {
             assert_eq!(parts.len(), 5);
             assert_eq!(parts[0], InterpolationPart::Text("Hello ".to_string()));
-            assert_eq!(parts[1], InterpolationPart::Expression("name".to_string()));
+            assert_eq!(expression_source(&parts[1]), "name");
             assert_eq!(parts[2], InterpolationPart::Text(", you are ".to_string()));
-            assert_eq!(parts[3], InterpolationPart::Expression("age".to_string()));
+            assert_eq!(expression_source(&parts[3]), "age");
             assert_eq!(parts[4], InterpolationPart::Text(" years old".to_string()));
         }
         _ => panic!("Expected InterpolatedString"),
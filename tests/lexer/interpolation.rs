@@ -2,6 +2,14 @@
 
 use metorex::lexer::{InterpolationPart, Lexer, TokenKind};
 
+/// The source text of an interpolated expression part
+pub(super) fn expression_source(part: &InterpolationPart) -> &str {
+    match part {
+        InterpolationPart::Expression(source, _) => source,
+        other => panic!("Expected an expression part, got {:?}", other),
+    }
+}
+
 // ===== String Interpolation Tests =====
 
 #[test]
@@ -13,7 +21,7 @@ fn test_lexer_interpolated_string_simple() {
         TokenKind::InterpolatedString(parts) => {
             assert_eq!(parts.len(), 2);
             assert_eq!(parts[0], InterpolationPart::Text("hello ".to_string()));
-            assert_eq!(parts[1], InterpolationPart::Expression("name".to_string()));
+            assert_eq!(expression_source(&parts[1]), "name");
         }
         _ => panic!("Expected InterpolatedString, got {:?}", token.kind),
     }
@@ -27,11 +35,11 @@ fn test_lexer_interpolated_string_multiple() {
     match token.kind {
         TokenKind::InterpolatedString(parts) => {
             assert_eq!(parts.len(), 5);
-            assert_eq!(parts[0], InterpolationPart::Expression("x".to_string()));
+            assert_eq!(expression_source(&parts[0]), "x");
             assert_eq!(parts[1], InterpolationPart::Text(" + ".to_string()));
-            assert_eq!(parts[2], InterpolationPart::Expression("y".to_string()));
+            assert_eq!(expression_source(&parts[2]), "y");
             assert_eq!(parts[3], InterpolationPart::Text(" = ".to_string()));
-            assert_eq!(parts[4], InterpolationPart::Expression("z".to_string()));
+            assert_eq!(expression_source(&parts[4]), "z");
         }
         _ => panic!("Expected InterpolatedString, got {:?}", token.kind),
     }
@@ -45,10 +53,7 @@ fn test_lexer_interpolated_string_at_start() {
     match token.kind {
         TokenKind::InterpolatedString(parts) => {
             assert_eq!(parts.len(), 2);
-            assert_eq!(
-                parts[0],
-                InterpolationPart::Expression("greeting".to_string())
-            );
+            assert_eq!(expression_source(&parts[0]), "greeting");
             assert_eq!(parts[1], InterpolationPart::Text(", world!".to_string()));
         }
         _ => panic!("Expected InterpolatedString, got {:?}", token.kind),
@@ -64,7 +69,7 @@ fn test_lexer_interpolated_string_at_end() {
         TokenKind::InterpolatedString(parts) => {
             assert_eq!(parts.len(), 2);
             assert_eq!(parts[0], InterpolationPart::Text("result: ".to_string()));
-            assert_eq!(parts[1], InterpolationPart::Expression("value".to_string()));
+            assert_eq!(expression_source(&parts[1]), "value");
         }
         _ => panic!("Expected InterpolatedString, got {:?}", token.kind),
     }
@@ -78,7 +83,7 @@ fn test_lexer_interpolated_string_only_expression() {
     match token.kind {
         TokenKind::InterpolatedString(parts) => {
             assert_eq!(parts.len(), 1);
-            assert_eq!(parts[0], InterpolationPart::Expression("value".to_string()));
+            assert_eq!(expression_source(&parts[0]), "value");
         }
         _ => panic!("Expected InterpolatedString, got {:?}", token.kind),
     }
@@ -112,11 +117,61 @@ fn test_lexer_interpolated_string_with_complex_expression() {
         TokenKind::InterpolatedString(parts) => {
             assert_eq!(parts.len(), 2);
             assert_eq!(parts[0], InterpolationPart::Text("result: ".to_string()));
-            assert_eq!(
-                parts[1],
-                InterpolationPart::Expression("x + y * 2".to_string())
-            );
+            assert_eq!(expression_source(&parts[1]), "x + y * 2");
         }
         _ => panic!("Expected InterpolatedString, got {:?}", token.kind),
     }
 }
+
+#[test]
+fn test_lexer_interpolation_braces_in_nested_string() {
+    let mut lexer = Lexer::new(r##""a#{ "}" + x }b""##);
+    let token = lexer.next_token();
+
+    match token.kind {
+        TokenKind::InterpolatedString(parts) => {
+            assert_eq!(parts.len(), 3);
+            assert_eq!(expression_source(&parts[1]), r#" "}" + x "#);
+            assert_eq!(parts[2], InterpolationPart::Text("b".to_string()));
+        }
+        _ => panic!("Expected InterpolatedString, got {:?}", token.kind),
+    }
+}
+
+#[test]
+fn test_lexer_interpolation_tokens_keep_source_positions() {
+    let mut lexer = Lexer::new("x = 1\n\"v #{ {\"k\" => x}[\"k\"] }\"");
+    let tokens: Vec<_> = std::iter::from_fn(|| Some(lexer.next_token()))
+        .take_while(|token| token.kind != TokenKind::EOF)
+        .collect();
+
+    let TokenKind::InterpolatedString(parts) = &tokens[4].kind else {
+        panic!("Expected InterpolatedString, got {:?}", tokens[4].kind);
+    };
+    let InterpolationPart::Expression(_, inner) = &parts[1] else {
+        panic!("Expected an expression part, got {:?}", parts[1]);
+    };
+    let kinds: Vec<&TokenKind> = inner.iter().map(|token| &token.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            &TokenKind::LBrace,
+            &TokenKind::String("k".to_string()),
+            &TokenKind::FatArrow,
+            &TokenKind::Ident("x".into()),
+            &TokenKind::RBrace,
+            &TokenKind::LBracket,
+            &TokenKind::String("k".to_string()),
+            &TokenKind::RBracket,
+            &TokenKind::EOF,
+        ]
+    );
+    assert_eq!(inner[0].position.line, 2);
+    assert_eq!(inner[0].position.column, 7);
+}
+
+#[test]
+fn test_lexer_unterminated_interpolation() {
+    let mut lexer = Lexer::new("\"a #{ b\nc\"");
+    assert_eq!(lexer.next_token().kind, TokenKind::EOF);
+}
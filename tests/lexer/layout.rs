@@ -0,0 +1,65 @@
+// Tests for the indentation and line ending facts of source lines
+
+use metorex::lexer::{LineEnding, Span, line_layouts, strip_bom};
+
+#[test]
+fn test_line_layouts_split_lines_and_endings() {
+    let layouts = line_layouts("a\r\nb\nc");
+
+    let texts: Vec<&str> = layouts.iter().map(|line| line.text).collect();
+    let endings: Vec<LineEnding> = layouts.iter().map(|line| line.ending).collect();
+    assert_eq!(texts, vec!["a", "b", "c"]);
+    assert_eq!(
+        endings,
+        vec![LineEnding::CrLf, LineEnding::Lf, LineEnding::None]
+    );
+    assert_eq!(layouts[2].number, 3);
+    assert_eq!(layouts[2].start, 5);
+}
+
+#[test]
+fn test_line_layouts_no_line_after_final_newline() {
+    assert_eq!(line_layouts("a\n").len(), 1);
+    assert!(line_layouts("").is_empty());
+}
+
+#[test]
+fn test_line_layouts_indent_and_trailing_whitespace() {
+    let source = "def f\n  \tx = 1 \t\n";
+    let line = line_layouts(source)[1];
+
+    assert_eq!(line.indent, "  \t");
+    assert_eq!(line.trailing, " \t");
+    assert!(line.has_mixed_indent());
+    assert!(!line.is_blank());
+    assert_eq!(line.trailing_span(), Span::new(14, 16));
+    assert_eq!(line.trailing_span().slice(source), " \t");
+}
+
+#[test]
+fn test_line_layouts_blank_line_is_all_trailing() {
+    let line = line_layouts("   \r\n")[0];
+
+    assert!(line.is_blank());
+    assert_eq!(line.indent, "");
+    assert_eq!(line.trailing, "   ");
+}
+
+#[test]
+fn test_indent_width_expands_tabs_to_tab_stops() {
+    let line = line_layouts(" \t\tx")[0];
+
+    assert_eq!(line.indent_width(4), 8);
+    assert_eq!(line.indent_width(8), 16);
+    assert_eq!(line_layouts("  x")[0].indent_width(4), 2);
+}
+
+#[test]
+fn test_line_layouts_skip_byte_order_mark() {
+    let source = "\u{feff}  x";
+    let line = line_layouts(source)[0];
+
+    assert_eq!(strip_bom(source), "  x");
+    assert_eq!(line.start, 3);
+    assert_eq!(line.indent, "  ");
+}
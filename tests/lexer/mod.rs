@@ -4,8 +4,10 @@ mod identifiers;
 mod integration;
 mod interpolation;
 mod iterators;
+mod layout;
 mod mixed_tokens;
 mod numbers;
 mod operators;
+mod spans;
 mod strings;
 mod token_test;
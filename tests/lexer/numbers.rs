@@ -112,3 +112,61 @@ fn test_lexer_multiple_floats() {
     let token3 = lexer.next_token();
     assert_eq!(token3.kind, TokenKind::Float(3.3));
 }
+
+// ===== Number Followed By Dot Tests =====
+
+fn token_kinds(source: &str) -> Vec<TokenKind> {
+    Lexer::new(source)
+        .tokenize()
+        .into_iter()
+        .map(|token| token.kind)
+        .collect()
+}
+
+#[test]
+fn test_lexer_integer_method_call_keeps_dot() {
+    assert_eq!(
+        token_kinds("3.times"),
+        vec![
+            TokenKind::Int(3),
+            TokenKind::Dot,
+            TokenKind::Ident("times".into()),
+            TokenKind::EOF,
+        ]
+    );
+}
+
+#[test]
+fn test_lexer_float_method_call_keeps_dot() {
+    assert_eq!(
+        token_kinds("3.14.floor"),
+        vec![
+            TokenKind::Float(3.14),
+            TokenKind::Dot,
+            TokenKind::Ident("floor".into()),
+            TokenKind::EOF,
+        ]
+    );
+}
+
+#[test]
+fn test_lexer_integer_range_after_number() {
+    assert_eq!(
+        token_kinds("5..10"),
+        vec![
+            TokenKind::Int(5),
+            TokenKind::DotDot,
+            TokenKind::Int(10),
+            TokenKind::EOF,
+        ]
+    );
+    assert_eq!(
+        token_kinds("5...10"),
+        vec![
+            TokenKind::Int(5),
+            TokenKind::DotDotDot,
+            TokenKind::Int(10),
+            TokenKind::EOF,
+        ]
+    );
+}
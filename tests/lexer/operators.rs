@@ -69,6 +69,13 @@ fn test_lexer_operator_equal_equal() {
     assert_eq!(token.kind, TokenKind::EqualEqual);
 }
 
+#[test]
+fn test_lexer_operator_case_equal() {
+    let tokens = Lexer::new("Integer === x == y").tokenize();
+    assert_eq!(tokens[1].kind, TokenKind::CaseEqual);
+    assert_eq!(tokens[3].kind, TokenKind::EqualEqual);
+}
+
 #[test]
 fn test_lexer_operator_bang_equal() {
     let mut lexer = Lexer::new("!=");
@@ -90,6 +97,14 @@ fn test_lexer_operator_greater_equal() {
     assert_eq!(token.kind, TokenKind::GreaterEqual);
 }
 
+#[test]
+fn test_lexer_operator_spaceship() {
+    let mut lexer = Lexer::new("a <=> b");
+    lexer.next_token();
+    assert_eq!(lexer.next_token().kind, TokenKind::Spaceship);
+    assert!(matches!(lexer.next_token().kind, TokenKind::Ident(_)));
+}
+
 #[test]
 fn test_lexer_operator_arrow() {
     let mut lexer = Lexer::new("->");
@@ -97,6 +112,26 @@ fn test_lexer_operator_arrow() {
     assert_eq!(token.kind, TokenKind::Arrow);
 }
 
+#[test]
+fn test_lexer_logical_operators() {
+    let kinds: Vec<_> = Lexer::new("!a && b || c")
+        .tokenize()
+        .into_iter()
+        .map(|token| token.kind)
+        .collect();
+    assert_eq!(kinds[0], TokenKind::Bang);
+    assert_eq!(kinds[2], TokenKind::AmpAmp);
+    assert_eq!(kinds[4], TokenKind::PipePipe);
+}
+
+#[test]
+fn test_lexer_bang_before_equal_is_not_equal() {
+    let mut lexer = Lexer::new("a != !b");
+    lexer.next_token();
+    assert_eq!(lexer.next_token().kind, TokenKind::BangEqual);
+    assert_eq!(lexer.next_token().kind, TokenKind::Bang);
+}
+
 // ===== Compound Assignment Operator Tests =====
 
 #[test]
@@ -192,6 +227,15 @@ fn test_lexer_delimiter_colon() {
     assert_eq!(token.kind, TokenKind::Colon);
 }
 
+#[test]
+fn test_lexer_delimiter_colon_colon() {
+    let tokens = Lexer::new("Float::NAN :sym").tokenize();
+    assert_eq!(tokens[0].kind, TokenKind::Ident("Float".into()));
+    assert_eq!(tokens[1].kind, TokenKind::ColonColon);
+    assert_eq!(tokens[2].kind, TokenKind::Ident("NAN".into()));
+    assert_eq!(tokens[3].kind, TokenKind::Symbol("sym".into()));
+}
+
 #[test]
 fn test_lexer_delimiter_semicolon() {
     let mut lexer = Lexer::new(";");
@@ -261,13 +305,13 @@ fn test_lexer_comparison_expression() {
     let mut lexer = Lexer::new("x == y");
 
     let token1 = lexer.next_token();
-    assert_eq!(token1.kind, TokenKind::Ident("x".to_string()));
+    assert_eq!(token1.kind, TokenKind::Ident("x".into()));
 
     let token2 = lexer.next_token();
     assert_eq!(token2.kind, TokenKind::EqualEqual);
 
     let token3 = lexer.next_token();
-    assert_eq!(token3.kind, TokenKind::Ident("y".to_string()));
+    assert_eq!(token3.kind, TokenKind::Ident("y".into()));
 }
 
 #[test]
@@ -275,7 +319,7 @@ fn test_lexer_assignment_expression() {
     let mut lexer = Lexer::new("x = 42");
 
     let token1 = lexer.next_token();
-    assert_eq!(token1.kind, TokenKind::Ident("x".to_string()));
+    assert_eq!(token1.kind, TokenKind::Ident("x".into()));
 
     let token2 = lexer.next_token();
     assert_eq!(token2.kind, TokenKind::Equal);
@@ -289,7 +333,7 @@ fn test_lexer_compound_assignment() {
     let mut lexer = Lexer::new("x += 5");
 
     let token1 = lexer.next_token();
-    assert_eq!(token1.kind, TokenKind::Ident("x".to_string()));
+    assert_eq!(token1.kind, TokenKind::Ident("x".into()));
 
     let token2 = lexer.next_token();
     assert_eq!(token2.kind, TokenKind::PlusEqual);
@@ -303,19 +347,19 @@ fn test_lexer_function_call_syntax() {
     let mut lexer = Lexer::new("foo(x, y)");
 
     let token1 = lexer.next_token();
-    assert_eq!(token1.kind, TokenKind::Ident("foo".to_string()));
+    assert_eq!(token1.kind, TokenKind::Ident("foo".into()));
 
     let token2 = lexer.next_token();
     assert_eq!(token2.kind, TokenKind::LParen);
 
     let token3 = lexer.next_token();
-    assert_eq!(token3.kind, TokenKind::Ident("x".to_string()));
+    assert_eq!(token3.kind, TokenKind::Ident("x".into()));
 
     let token4 = lexer.next_token();
     assert_eq!(token4.kind, TokenKind::Comma);
 
     let token5 = lexer.next_token();
-    assert_eq!(token5.kind, TokenKind::Ident("y".to_string()));
+    assert_eq!(token5.kind, TokenKind::Ident("y".into()));
 
     let token6 = lexer.next_token();
     assert_eq!(token6.kind, TokenKind::RParen);
@@ -352,13 +396,13 @@ fn test_lexer_method_chaining() {
     let mut lexer = Lexer::new("obj.method");
 
     let token1 = lexer.next_token();
-    assert_eq!(token1.kind, TokenKind::Ident("obj".to_string()));
+    assert_eq!(token1.kind, TokenKind::Ident("obj".into()));
 
     let token2 = lexer.next_token();
     assert_eq!(token2.kind, TokenKind::Dot);
 
     let token3 = lexer.next_token();
-    assert_eq!(token3.kind, TokenKind::Ident("method".to_string()));
+    assert_eq!(token3.kind, TokenKind::Ident("method".into()));
 }
 
 #[test]
@@ -366,13 +410,13 @@ fn test_lexer_arrow_function_syntax() {
     let mut lexer = Lexer::new("x -> x + 1");
 
     let token1 = lexer.next_token();
-    assert_eq!(token1.kind, TokenKind::Ident("x".to_string()));
+    assert_eq!(token1.kind, TokenKind::Ident("x".into()));
 
     let token2 = lexer.next_token();
     assert_eq!(token2.kind, TokenKind::Arrow);
 
     let token3 = lexer.next_token();
-    assert_eq!(token3.kind, TokenKind::Ident("x".to_string()));
+    assert_eq!(token3.kind, TokenKind::Ident("x".into()));
 
     let token4 = lexer.next_token();
     assert_eq!(token4.kind, TokenKind::Plus);
@@ -386,13 +430,13 @@ fn test_lexer_operators_without_spaces() {
     let mut lexer = Lexer::new("x==y");
 
     let token1 = lexer.next_token();
-    assert_eq!(token1.kind, TokenKind::Ident("x".to_string()));
+    assert_eq!(token1.kind, TokenKind::Ident("x".into()));
 
     let token2 = lexer.next_token();
     assert_eq!(token2.kind, TokenKind::EqualEqual);
 
     let token3 = lexer.next_token();
-    assert_eq!(token3.kind, TokenKind::Ident("y".to_string()));
+    assert_eq!(token3.kind, TokenKind::Ident("y".into()));
 }
 
 #[test]
@@ -463,13 +507,13 @@ fn test_lexer_complex_expression() {
     assert_eq!(token1.kind, TokenKind::LParen);
 
     let token2 = lexer.next_token();
-    assert_eq!(token2.kind, TokenKind::Ident("x".to_string()));
+    assert_eq!(token2.kind, TokenKind::Ident("x".into()));
 
     let token3 = lexer.next_token();
     assert_eq!(token3.kind, TokenKind::Plus);
 
     let token4 = lexer.next_token();
-    assert_eq!(token4.kind, TokenKind::Ident("y".to_string()));
+    assert_eq!(token4.kind, TokenKind::Ident("y".into()));
 
     let token5 = lexer.next_token();
     assert_eq!(token5.kind, TokenKind::RParen);
@@ -481,13 +525,13 @@ fn test_lexer_complex_expression() {
     assert_eq!(token7.kind, TokenKind::LParen);
 
     let token8 = lexer.next_token();
-    assert_eq!(token8.kind, TokenKind::Ident("a".to_string()));
+    assert_eq!(token8.kind, TokenKind::Ident("a".into()));
 
     let token9 = lexer.next_token();
     assert_eq!(token9.kind, TokenKind::Minus);
 
     let token10 = lexer.next_token();
-    assert_eq!(token10.kind, TokenKind::Ident("b".to_string()));
+    assert_eq!(token10.kind, TokenKind::Ident("b".into()));
 
     let token11 = lexer.next_token();
     assert_eq!(token11.kind, TokenKind::RParen);
@@ -496,5 +540,5 @@ fn test_lexer_complex_expression() {
     assert_eq!(token12.kind, TokenKind::Slash);
 
     let token13 = lexer.next_token();
-    assert_eq!(token13.kind, TokenKind::Ident("z".to_string()));
+    assert_eq!(token13.kind, TokenKind::Ident("z".into()));
 }
@@ -0,0 +1,108 @@
+// Tests for token spans and identifier interning
+
+use metorex::lexer::{Interner, Lexer, Span, Symbol, TokenKind};
+
+#[test]
+fn test_span_slice() {
+    let span = Span::new(4, 7);
+    assert_eq!(span.len(), 3);
+    assert!(!span.is_empty());
+    assert_eq!(span.slice("def foo"), "foo");
+}
+
+#[test]
+fn test_span_slice_out_of_range() {
+    assert_eq!(Span::new(3, 20).slice("abc"), "");
+}
+
+#[test]
+fn test_token_text_recovers_source() {
+    let source = "name = \"a\\tb\" # note\n@count += 1.5";
+    let tokens = Lexer::new(source).tokenize();
+    let texts: Vec<&str> = tokens.iter().map(|t| t.text(source)).collect();
+
+    assert_eq!(
+        texts,
+        vec![
+            "name",
+            "=",
+            "\"a\\tb\"",
+            "# note",
+            "\n",
+            "@count",
+            "+=",
+            "1.5",
+            ""
+        ]
+    );
+}
+
+#[test]
+fn test_span_matches_position_offset() {
+    let source = "x = 1\ny = 22";
+    for token in Lexer::new(source).tokenize() {
+        assert_eq!(token.span.start, token.position.offset);
+    }
+}
+
+#[test]
+fn test_string_span_keeps_quotes_and_escapes() {
+    let source = "'it\\'s'";
+    let tokens = Lexer::new(source).tokenize();
+
+    assert_eq!(tokens[0].kind, TokenKind::String("it's".to_string()));
+    assert_eq!(tokens[0].text(source), source);
+}
+
+#[test]
+fn test_repeated_identifiers_share_symbol() {
+    let tokens = Lexer::new("total = total + total").tokenize();
+    let names: Vec<&Symbol> = tokens
+        .iter()
+        .filter_map(|t| match &t.kind {
+            TokenKind::Ident(name) => Some(name),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(names.len(), 3);
+    assert!(names[0].ptr_eq(names[1]));
+    assert!(names[1].ptr_eq(names[2]));
+}
+
+#[test]
+fn test_lexer_interner_counts_distinct_names() {
+    let mut lexer = Lexer::new("a = b\na = @b\nif a then end");
+    while lexer.next_token().kind != TokenKind::EOF {}
+
+    // Keywords are never interned
+    assert_eq!(lexer.interner().len(), 2);
+    assert!(lexer.interner().get("a").is_some());
+    assert!(lexer.interner().get("if").is_none());
+}
+
+#[test]
+fn test_interner_reuses_allocation() {
+    let mut interner = Interner::new();
+    assert!(interner.is_empty());
+
+    let first = interner.intern("value");
+    let second = interner.intern("value");
+    let other = interner.intern("other");
+
+    assert!(first.ptr_eq(&second));
+    assert!(!first.ptr_eq(&other));
+    assert_eq!(interner.len(), 2);
+}
+
+#[test]
+fn test_symbol_compares_by_content() {
+    let mut interner = Interner::new();
+    let interned = interner.intern("foo");
+
+    assert_eq!(interned, Symbol::from("foo"));
+    assert_eq!(interned, "foo");
+    assert_eq!(interned.as_str(), "foo");
+    assert_eq!(format!("{}", interned), "foo");
+    assert_eq!(format!("{:?}", interned), "\"foo\"");
+}
@@ -114,3 +114,71 @@ fn test_lexer_string_with_unknown_escape() {
     // Unknown escapes keep the backslash
     assert_eq!(token.kind, TokenKind::String("test\\xabc".to_string()));
 }
+
+#[test]
+fn test_lexer_unknown_escape_in_double_quotes_warns() {
+    let (tokens, warnings) = Lexer::new(r#"x = "a\qb""#).tokenize_with_warnings();
+    assert_eq!(tokens[2].kind, TokenKind::String("a\\qb".to_string()));
+    assert_eq!(warnings.len(), 1);
+    assert!(
+        warnings[0].starts_with(r"Unknown escape sequence '\q' at 1:7"),
+        "{}",
+        warnings[0]
+    );
+}
+
+#[test]
+fn test_lexer_known_escapes_do_not_warn() {
+    let (_, warnings) = Lexer::new(r#""a\n\t\r\\\"\#{b}""#).tokenize_with_warnings();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+}
+
+#[test]
+fn test_lexer_unknown_escape_in_single_quotes_does_not_warn() {
+    let (tokens, warnings) = Lexer::new(r"'a\qb'").tokenize_with_warnings();
+    assert_eq!(tokens[0].kind, TokenKind::String("a\\qb".to_string()));
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn test_lexer_peek_does_not_duplicate_warnings() {
+    let mut lexer = Lexer::new(r#""\q""#);
+    lexer.peek_token();
+    lexer.next_token();
+    assert_eq!(lexer.warnings().len(), 1);
+}
+
+#[test]
+fn test_lexer_raw_string_keeps_backslashes() {
+    let mut lexer = Lexer::new(r#"r"C:\new\table\\""#);
+    assert_eq!(
+        lexer.next_token().kind,
+        TokenKind::String(r"C:\new\table\\".to_string())
+    );
+    assert!(lexer.warnings().is_empty());
+}
+
+#[test]
+fn test_lexer_raw_string_does_not_interpolate() {
+    let mut lexer = Lexer::new(r##"r"#{name}""##);
+    assert_eq!(
+        lexer.next_token().kind,
+        TokenKind::String("#{name}".to_string())
+    );
+}
+
+#[test]
+fn test_lexer_raw_string_with_single_quotes() {
+    let mut lexer = Lexer::new(r#"r'say "hi"\n'"#);
+    assert_eq!(
+        lexer.next_token().kind,
+        TokenKind::String(r#"say "hi"\n"#.to_string())
+    );
+}
+
+#[test]
+fn test_lexer_r_identifier_is_not_a_raw_string() {
+    let tokens = Lexer::new("r + rx").tokenize();
+    assert!(matches!(tokens[0].kind, TokenKind::Ident(_)));
+    assert!(matches!(tokens[2].kind, TokenKind::Ident(_)));
+}
@@ -63,7 +63,7 @@ fn test_literal_tokens() {
 
 #[test]
 fn test_identifier_token() {
-    let ident = TokenKind::Ident("variable_name".to_string());
+    let ident = TokenKind::Ident("variable_name".into());
     assert_eq!(ident.to_string(), "variable_name");
 }
 
@@ -82,6 +82,7 @@ fn test_operator_tokens() {
         (TokenKind::Greater, ">"),
         (TokenKind::LessEqual, "<="),
         (TokenKind::GreaterEqual, ">="),
+        (TokenKind::Spaceship, "<=>"),
     ];
 
     for (kind, expected) in operators {
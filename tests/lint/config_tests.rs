@@ -0,0 +1,98 @@
+// Tests for reading .metorex-lint.toml
+
+use crate::common::temp_dir;
+use metorex::lint::{ConfigValue, Level, LintConfig};
+use std::fs;
+
+#[test]
+fn test_levels_and_options_are_read_per_rule() {
+    let config = LintConfig::parse(
+        r#"
+# Project lint settings
+[rules]
+shadowed-name = "off"
+empty-rescue = "error"   # never swallow errors
+
+[rules.long-method]
+level = "warning"
+max_lines = 1_000
+strict = true
+label = "a # not a comment"
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(config.rules["shadowed-name"].level, Some(Level::Off));
+    assert_eq!(config.rules["empty-rescue"].level, Some(Level::Error));
+    let long_method = &config.rules["long-method"];
+    assert_eq!(long_method.level, Some(Level::Warning));
+    assert_eq!(long_method.options["max_lines"], ConfigValue::Int(1000));
+    assert_eq!(long_method.options["strict"], ConfigValue::Bool(true));
+    assert_eq!(
+        long_method.options["label"],
+        ConfigValue::String("a # not a comment".to_string())
+    );
+}
+
+#[test]
+fn test_a_rule_table_without_a_level_keeps_the_default() {
+    let config = LintConfig::parse("[rules.long-method]\nmax_lines = 10\n").unwrap();
+    assert_eq!(config.rules["long-method"].level, None);
+    assert_eq!(LintConfig::parse("").unwrap(), LintConfig::default());
+}
+
+#[test]
+fn test_malformed_configuration_is_reported_with_its_line() {
+    let cases = [
+        (
+            "max_lines = 3\n",
+            "line 1: 'max_lines' must be inside [rules] or a [rules.NAME] table",
+        ),
+        ("[lint]\n", "line 1: unknown table [lint]"),
+        ("[rules\n", "line 1: expected ']' after the table name"),
+        ("[rules]\n\nempty-rescue\n", "line 3: expected key = value"),
+        (
+            "[rules]\nempty-rescue = loud\n",
+            "line 2: expected a quoted string, an integer or a boolean for 'empty-rescue'",
+        ),
+        (
+            "[rules]\nempty-rescue = \"loud\"\n",
+            "line 2: invalid level 'loud' (expected off, warning or error)",
+        ),
+        (
+            "[rules]\nempty-rescue = 1\n",
+            "line 2: invalid level 1 (expected \"off\", \"warning\" or \"error\")",
+        ),
+        (
+            "[rules]\nlong-method = \"off\"\n[rules.long-method]\nlevel = \"error\"\n",
+            "line 4: the level of 'long-method' is given more than once",
+        ),
+        (
+            "[rules.long-method]\nmax_lines = 1\nmax_lines = 2\n",
+            "line 3: 'max_lines' is given more than once",
+        ),
+    ];
+    for (text, expected) in cases {
+        assert_eq!(LintConfig::parse(text).unwrap_err(), expected, "{:?}", text);
+    }
+}
+
+#[test]
+fn test_configuration_files_are_loaded_from_disk() {
+    let dir = temp_dir("lint_config");
+    let path = dir.join(".metorex-lint.toml");
+    fs::write(&path, "[rules]\nunused-variable = \"off\"\n").unwrap();
+    let config = LintConfig::load(&path).unwrap();
+    assert_eq!(config.rules["unused-variable"].level, Some(Level::Off));
+
+    fs::write(&path, "[rules]\nunused-variable = off\n").unwrap();
+    let err = LintConfig::load(&path).unwrap_err();
+    assert!(
+        err.starts_with(&format!("{}: line 2:", path.display())),
+        "{}",
+        err
+    );
+
+    let err = LintConfig::load(&dir.join("missing.toml")).unwrap_err();
+    assert!(err.contains("cannot read lint configuration"), "{}", err);
+}
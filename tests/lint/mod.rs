@@ -0,0 +1,4 @@
+mod config_tests;
+mod rule_tests;
+mod script_rule_tests;
+mod visitor_tests;
@@ -0,0 +1,271 @@
+// Tests for the built-in lint rules and the Linter that runs them
+
+use metorex::ast::{Expression, Statement};
+use metorex::lint::{Diagnostic, Level, LintConfig, Linter, Offenses, Rule};
+
+fn lint(source: &str) -> Vec<Diagnostic> {
+    Linter::new()
+        .lint_source(source)
+        .expect("source should parse")
+}
+
+/// `rule` offenses as `line:column message`
+fn offenses(source: &str, rule: &str) -> Vec<String> {
+    lint(source)
+        .into_iter()
+        .filter(|diagnostic| diagnostic.rule == rule)
+        .map(|d| format!("{}:{} {}", d.line, d.column, d.message))
+        .collect()
+}
+
+#[test]
+fn test_unused_variables_are_reported_per_scope() {
+    let source = "\
+used = 1
+unused = 2
+_ignored = 3
+puts(used)
+def total(items)
+  count = 0
+  sum = 0
+  items.each do |item|
+    sum += item
+  end
+  sum
+end
+";
+    assert_eq!(
+        offenses(source, "unused-variable"),
+        [
+            "2:1 Variable 'unused' is assigned but never used",
+            "6:3 Variable 'count' is assigned but never used"
+        ]
+    );
+}
+
+#[test]
+fn test_variables_read_by_methods_or_later_code_are_used() {
+    let source = "\
+def show
+  puts(greeting)
+end
+greeting = \"hi\"
+limit = 3
+def check(n)
+  n > limit
+end
+";
+    assert!(offenses(source, "unused-variable").is_empty());
+}
+
+#[test]
+fn test_block_parameters_and_bindings_that_shadow_are_reported() {
+    let source = "\
+item = 1
+[1, 2].each do |item|
+  puts(item)
+end
+for item in [3]
+  puts(item)
+end
+case [1, 2]
+when [item, other]
+  puts(other)
+end
+[1].each do |fresh|
+  puts(fresh)
+end
+puts(item)
+";
+    assert_eq!(
+        offenses(source, "shadowed-name"),
+        [
+            "2:13 'item' shadows the variable defined at 1:1",
+            "5:1 'item' shadows the variable defined at 1:1",
+            "9:1 'item' shadows the variable defined at 1:1"
+        ]
+    );
+}
+
+#[test]
+fn test_code_after_a_jump_is_unreachable() {
+    let source = "\
+def first(items)
+  return items[0]
+  puts(\"after return\")
+end
+while true
+  break
+  puts(\"after break\")
+end
+def fail
+  if true
+    raise \"boom\"
+    puts(\"after raise\")
+  end
+end
+";
+    assert_eq!(
+        offenses(source, "unreachable-code"),
+        [
+            "3:3 Unreachable code after 'return'",
+            "7:3 Unreachable code after 'break'",
+            "12:5 Unreachable code after 'raise'"
+        ]
+    );
+}
+
+#[test]
+fn test_empty_rescue_clauses_are_reported() {
+    let source = "\
+begin
+  risky
+rescue
+end
+begin
+  risky
+rescue IOError, TypeError
+rescue => e
+  puts(e)
+end
+";
+    assert_eq!(
+        offenses(source, "empty-rescue"),
+        [
+            "3:1 Empty rescue swallows every StandardError without handling it",
+            "7:1 Empty rescue swallows IOError, TypeError without handling it"
+        ]
+    );
+}
+
+#[test]
+fn test_long_methods_are_reported_over_the_configured_limit() {
+    let body: String = (0..31).map(|i| format!("  puts({})\n", i)).collect();
+    let source = format!("def long\n{}end\ndef short\n  1\nend\n", body);
+    assert_eq!(
+        offenses(&source, "long-method"),
+        ["1:1 Method 'long' is 31 lines long (more than 30)"]
+    );
+
+    let config = LintConfig::parse("[rules.long-method]\nmax_lines = 40\n").unwrap();
+    let mut linter = Linter::new();
+    linter.configure(&config).unwrap();
+    assert!(linter.lint_source(&source).unwrap().is_empty());
+}
+
+#[test]
+fn test_configured_levels_apply_and_off_rules_do_not_run() {
+    let config =
+        LintConfig::parse("[rules]\nunused-variable = \"error\"\nunreachable-code = \"off\"\n")
+            .unwrap();
+    let mut linter = Linter::new();
+    linter.configure(&config).unwrap();
+
+    let diagnostics = linter
+        .lint_source("def f\n  x = 1\n  return 2\n  3\nend\n")
+        .unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].rule, "unused-variable");
+    assert_eq!(diagnostics[0].level, Level::Error);
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "2:3: error: Variable 'x' is assigned but never used [unused-variable]"
+    );
+}
+
+#[test]
+fn test_configuring_unknown_rules_and_options_fails() {
+    let mut linter = Linter::new();
+    let config = LintConfig::parse("[rules]\nno-such-rule = \"error\"\n").unwrap();
+    assert_eq!(
+        linter.configure(&config).unwrap_err(),
+        "unknown lint rule 'no-such-rule'"
+    );
+
+    let config = LintConfig::parse("[rules.empty-rescue]\nmax_lines = 3\n").unwrap();
+    assert_eq!(
+        linter.configure(&config).unwrap_err(),
+        "empty-rescue: unknown option 'max_lines'"
+    );
+
+    let config = LintConfig::parse("[rules.long-method]\nmax_lines = 0\n").unwrap();
+    assert!(
+        linter
+            .configure(&config)
+            .unwrap_err()
+            .contains("max_lines must be a positive integer")
+    );
+}
+
+/// A custom rule written against the public API
+struct NoPuts;
+
+impl Rule for NoPuts {
+    fn name(&self) -> &str {
+        "no-puts"
+    }
+
+    fn description(&self) -> &str {
+        "Top-level calls to puts"
+    }
+
+    fn check(&mut self, program: &[Statement], offenses: &mut Offenses) {
+        for statement in program {
+            if let Statement::Expression {
+                expression: Expression::Call { callee, .. },
+                ..
+            } = statement
+                && matches!(callee.as_ref(), Expression::Identifier { name, .. } if name == "puts")
+            {
+                offenses.report(statement.position(), "Use a logger instead of puts");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_custom_rules_run_alongside_the_built_in_ones() {
+    let mut linter = Linter::new();
+    linter.add_rule(Box::new(NoPuts));
+    let config = LintConfig::parse("[rules.no-puts]\nlevel = \"error\"\n").unwrap();
+    linter.configure(&config).unwrap();
+
+    let names: Vec<&str> = linter.rules().map(|(rule, _)| rule.name()).collect();
+    assert_eq!(
+        names,
+        [
+            "unused-variable",
+            "shadowed-name",
+            "unreachable-code",
+            "empty-rescue",
+            "long-method",
+            "no-puts"
+        ]
+    );
+
+    let diagnostics = linter.lint_source("x = 1\nputs(2)\n").unwrap();
+    let found: Vec<(&str, Level)> = diagnostics
+        .iter()
+        .map(|d| (d.rule.as_str(), d.level))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            ("unused-variable", Level::Warning),
+            ("no-puts", Level::Error)
+        ]
+    );
+}
+
+#[test]
+fn test_parse_errors_become_syntax_diagnostics() {
+    let errors = Linter::new().lint_source("if true\n").unwrap_err();
+    let diagnostic = Diagnostic::from_parse_error(&errors[0]);
+    assert_eq!(diagnostic.rule, Diagnostic::SYNTAX);
+    assert_eq!(diagnostic.level, Level::Error);
+    assert!(
+        diagnostic.message.contains("Expected 'end'"),
+        "{}",
+        diagnostic
+    );
+}
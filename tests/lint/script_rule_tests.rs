@@ -0,0 +1,171 @@
+// Tests for lint rules written in Metorex
+
+use crate::common::temp_dir;
+use metorex::lint::{Level, LintConfig, Linter, Rule, ScriptRule};
+use std::fs;
+use std::path::PathBuf;
+
+const NO_PUTS: &str = r#"# Calls to puts left in from debugging
+# before a release
+def check(node, diagnostics)
+  if node.type == :Call
+    if node.callee.type == :Identifier
+      if node.callee.name == "puts"
+        diagnostics.report(node, "Remove debugging output")
+      end
+    end
+  end
+end
+"#;
+
+const MAX_PARAMS: &str = r#"def check(node, diagnostics)
+  if node.type == :FunctionDef
+    if node.parameters.length > diagnostics.option("max", 2)
+      diagnostics.report(node.position, "'#{node.name}' has too many parameters")
+    end
+  end
+end
+"#;
+
+/// A `lint` directory under a fresh temp dir holding the given rule files
+fn rules_dir(name: &str, rules: &[(&str, &str)]) -> PathBuf {
+    let dir = temp_dir(&format!("script_rules_{}", name)).join("lint");
+    fs::create_dir_all(&dir).unwrap();
+    for (file, source) in rules {
+        fs::write(dir.join(file), source).unwrap();
+    }
+    dir
+}
+
+/// Offenses of a linter with only the rules in `dir`, as `line:column message [rule]`
+fn lint_with(linter: &mut Linter, source: &str) -> Vec<String> {
+    linter
+        .lint_source(source)
+        .unwrap()
+        .into_iter()
+        .map(|d| format!("{}:{} {} [{}]", d.line, d.column, d.message, d.rule))
+        .collect()
+}
+
+#[test]
+fn test_rules_are_named_and_described_by_their_file() {
+    let dir = rules_dir(
+        "names",
+        &[("no_puts.mx", NO_PUTS), ("max_params.mx", MAX_PARAMS)],
+    );
+    let rule = ScriptRule::load(&dir.join("no_puts.mx")).unwrap();
+    assert_eq!(rule.name(), "no-puts");
+    assert_eq!(
+        rule.description(),
+        "Calls to puts left in from debugging before a release"
+    );
+
+    let mut linter = Linter::new();
+    linter.add_script_rules(&dir).unwrap();
+    let rules: Vec<(String, Level)> = linter
+        .rules()
+        .map(|(rule, level)| (rule.name().to_string(), level))
+        .skip(5)
+        .collect();
+    assert_eq!(
+        rules,
+        [
+            ("max-params".to_string(), Level::Warning),
+            ("no-puts".to_string(), Level::Warning)
+        ]
+    );
+}
+
+#[test]
+fn test_check_is_called_with_every_node() {
+    let dir = rules_dir("nodes", &[("no_puts.mx", NO_PUTS)]);
+    let mut linter = Linter::empty();
+    linter.add_script_rules(&dir).unwrap();
+    let source = "\
+puts(1)
+def shout(name)
+  [name].each do |n|
+    puts(\"#{n}!\")
+  end
+end
+log(puts(2))
+";
+    assert_eq!(
+        lint_with(&mut linter, source),
+        [
+            "1:1 Remove debugging output [no-puts]",
+            "4:5 Remove debugging output [no-puts]",
+            "7:5 Remove debugging output [no-puts]"
+        ]
+    );
+}
+
+#[test]
+fn test_rules_read_options_and_levels_from_the_configuration() {
+    let dir = rules_dir("options", &[("max_params.mx", MAX_PARAMS)]);
+    let source = "def f(a, b, c)\n  a\nend\n";
+
+    let mut linter = Linter::empty();
+    linter.add_script_rules(&dir).unwrap();
+    assert_eq!(
+        lint_with(&mut linter, source),
+        ["1:1 'f' has too many parameters [max-params]"]
+    );
+
+    let mut linter = Linter::empty();
+    linter.add_script_rules(&dir).unwrap();
+    let config = LintConfig::parse("[rules.max-params]\nlevel = \"error\"\nmax = 3\n").unwrap();
+    linter.configure(&config).unwrap();
+    assert!(lint_with(&mut linter, source).is_empty());
+
+    let config = LintConfig::parse("[rules.max-params]\nmax = 1\n").unwrap();
+    linter.configure(&config).unwrap();
+    let diagnostics = linter.lint_source(source).unwrap();
+    assert_eq!(diagnostics[0].level, Level::Error);
+}
+
+#[test]
+fn test_a_rule_that_raises_is_reported_at_the_top_of_the_file() {
+    let dir = rules_dir(
+        "raises",
+        &[(
+            "broken.mx",
+            "def check(node, diagnostics)\n  raise \"boom\"\nend\n",
+        )],
+    );
+    let mut linter = Linter::empty();
+    linter.add_script_rules(&dir).unwrap();
+    let offenses = lint_with(&mut linter, "x = 1\n");
+    assert_eq!(offenses.len(), 1);
+    assert!(offenses[0].starts_with("0:0 "), "{}", offenses[0]);
+    assert!(offenses[0].contains("broken.mx failed:"), "{}", offenses[0]);
+    assert!(offenses[0].contains("boom"), "{}", offenses[0]);
+}
+
+#[test]
+fn test_invalid_rule_files_are_rejected() {
+    let dir = rules_dir("invalid", &[("nothing.mx", "x = 1\n")]);
+    let err = Linter::new().add_script_rules(&dir).err().unwrap();
+    assert!(
+        err.ends_with("nothing.mx: a lint rule must define 'check(node, diagnostics)'"),
+        "{}",
+        err
+    );
+
+    let dir = rules_dir("syntax", &[("broken.mx", "def check(\n")]);
+    let err = Linter::new().add_script_rules(&dir).err().unwrap();
+    assert!(err.contains("broken.mx: "), "{}", err);
+
+    let dir = rules_dir("clash", &[("unused_variable.mx", NO_PUTS)]);
+    let err = Linter::new().add_script_rules(&dir).err().unwrap();
+    assert!(
+        err.ends_with("unused_variable.mx: there is already a lint rule named 'unused-variable'"),
+        "{}",
+        err
+    );
+
+    let missing = temp_dir("script_rules_missing").join("lint");
+    let mut linter = Linter::empty();
+    linter.add_script_rules(&missing).unwrap();
+    assert_eq!(linter.rules().count(), 0);
+}
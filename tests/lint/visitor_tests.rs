@@ -0,0 +1,89 @@
+// Tests for walking syntax trees with ast::Visitor
+
+use metorex::ast::{Expression, Statement, Visitor, walk_expression, walk_statement};
+use metorex::lexer::Lexer;
+use metorex::parser::Parser;
+
+fn parse(source: &str) -> Vec<Statement> {
+    Parser::new(Lexer::new(source).tokenize()).parse().unwrap()
+}
+
+/// Collects every identifier read, in the order visited
+#[derive(Default)]
+struct Identifiers(Vec<String>);
+
+impl Visitor for Identifiers {
+    fn visit_expression(&mut self, expression: &Expression) {
+        if let Expression::Identifier { name, .. } = expression {
+            self.0.push(name.clone());
+        }
+        walk_expression(self, expression);
+    }
+}
+
+#[test]
+fn test_the_default_walk_reaches_nested_nodes() {
+    let program = parse(
+        r##"
+def run(limit = default_limit)
+  items.each do |item|
+    if item > limit
+      puts("#{item} is big")
+    end
+  end
+  begin
+    risky(items[first])
+  rescue
+    fallback
+  end
+  value = case mode
+    when 1 then one
+    else other
+  end
+end
+"##,
+    );
+    let mut identifiers = Identifiers::default();
+    identifiers.visit_statements(&program);
+    assert_eq!(
+        identifiers.0,
+        [
+            "default_limit",
+            "items",
+            "item",
+            "limit",
+            "puts",
+            "item",
+            "risky",
+            "items",
+            "first",
+            "fallback",
+            "value",
+            "mode",
+            "one",
+            "other"
+        ]
+    );
+}
+
+/// Counts statements, skipping the bodies of methods
+#[derive(Default)]
+struct TopLevelStatements(usize);
+
+impl Visitor for TopLevelStatements {
+    fn visit_statement(&mut self, statement: &Statement) {
+        self.0 += 1;
+        if !matches!(statement, Statement::FunctionDef { .. }) {
+            walk_statement(self, statement);
+        }
+    }
+}
+
+#[test]
+fn test_overriding_a_hook_can_stop_the_walk() {
+    let program = parse("def f\n  a = 1\n  b = 2\nend\nif true\n  c = 3\nend\n");
+    let mut counter = TopLevelStatements::default();
+    counter.visit_statements(&program);
+    // The def, the if and the assignment inside the if
+    assert_eq!(counter.0, 3);
+}
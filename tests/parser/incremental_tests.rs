@@ -0,0 +1,165 @@
+// Tests for re-parsing only the edited statements of a document
+
+use metorex::ast::Statement;
+use metorex::lexer::Lexer;
+use metorex::parser::{ParsedDocument, Parser, TokenStream};
+
+const SOURCE: &str = "x = 1\n\ndef double(n)\n  n * 2\nend\n\n# result\ny = double(x)\nputs(y)\n";
+
+fn full_parse(source: &str) -> Vec<Statement> {
+    Parser::new(Lexer::new(source).tokenize()).parse().unwrap()
+}
+
+/// Apply an edit by replacing the first occurrence of `old` with `new`
+fn edit(document: &mut ParsedDocument, old: &str, new: &str) -> metorex::parser::Reparse {
+    let start = document.source().find(old).unwrap();
+    document.edit(start..start + old.len(), new).unwrap()
+}
+
+#[test]
+fn test_parse_statement_at_starts_at_the_next_statement() {
+    let mut parser = Parser::new(Lexer::new(SOURCE).tokenize());
+    let (statement, span) = parser.parse_statement_at(6).unwrap().unwrap();
+    assert!(matches!(statement, Statement::FunctionDef { .. }));
+    assert_eq!(span.slice(SOURCE), "def double(n)\n  n * 2\nend");
+
+    let (_, span) = parser.parse_statement_at(span.end).unwrap().unwrap();
+    assert_eq!(span.slice(SOURCE), "y = double(x)");
+
+    let end = SOURCE.len() - 1;
+    assert!(parser.parse_statement_at(end).unwrap().is_none());
+}
+
+#[test]
+fn test_token_stream_seeks_to_an_offset() {
+    let mut stream = TokenStream::new(Lexer::new("a = 10\nb").tokenize());
+    stream.seek(4);
+    assert_eq!(stream.peek().span.start, 4);
+    stream.seek(5);
+    assert_eq!(stream.peek().span.start, 6);
+    stream.seek(100);
+    assert!(stream.is_at_end());
+
+    let parser = Parser::from_stream(stream);
+    assert!(parser.into_stream().is_at_end());
+}
+
+#[test]
+fn test_document_splits_top_level_statements() {
+    let document = ParsedDocument::new(SOURCE).unwrap();
+    let spans: Vec<&str> = document
+        .statements()
+        .iter()
+        .map(|parsed| parsed.span().slice(SOURCE))
+        .collect();
+    assert_eq!(
+        spans,
+        vec![
+            "x = 1",
+            "def double(n)\n  n * 2\nend",
+            "y = double(x)",
+            "puts(y)"
+        ]
+    );
+    assert_eq!(document.program(), full_parse(SOURCE));
+    assert_eq!(
+        document.statement_at(20).unwrap().id(),
+        document.statements()[1].id()
+    );
+    assert!(document.statement_at(5).is_none());
+}
+
+#[test]
+fn test_edit_reparses_only_the_touched_statement() {
+    let mut document = ParsedDocument::new(SOURCE).unwrap();
+    let ids: Vec<_> = document
+        .statements()
+        .iter()
+        .map(|parsed| parsed.id())
+        .collect();
+
+    let reparse = edit(&mut document, "n * 2", "n * 2 + 1");
+    assert_eq!(reparse.removed, vec![ids[1]]);
+    assert_eq!(reparse.added.len(), 1);
+
+    let new_ids: Vec<_> = document
+        .statements()
+        .iter()
+        .map(|parsed| parsed.id())
+        .collect();
+    assert_eq!(new_ids, vec![ids[0], reparse.added[0], ids[2], ids[3]]);
+    assert_eq!(document.program(), full_parse(document.source()));
+}
+
+#[test]
+fn test_edit_moves_later_statements() {
+    let mut document = ParsedDocument::new(SOURCE).unwrap();
+    let ids: Vec<_> = document
+        .statements()
+        .iter()
+        .map(|parsed| parsed.id())
+        .collect();
+
+    edit(&mut document, "x = 1", "x = [\n  1,\n  2\n]");
+    assert_eq!(document.statements()[3].id(), ids[3]);
+    assert_eq!(
+        document.statements()[3].span().slice(document.source()),
+        "puts(y)"
+    );
+    // Positions inside the moved statements match a fresh parse
+    assert_eq!(document.program(), full_parse(document.source()));
+
+    edit(&mut document, ",\n  2", "");
+    assert_eq!(document.program(), full_parse(document.source()));
+}
+
+#[test]
+fn test_edit_that_splits_a_statement() {
+    let mut document = ParsedDocument::new(SOURCE).unwrap();
+    let ids: Vec<_> = document
+        .statements()
+        .iter()
+        .map(|parsed| parsed.id())
+        .collect();
+
+    let reparse = edit(
+        &mut document,
+        "n * 2",
+        "n * 2\nend\n\ndef triple(n)\n  n * 3",
+    );
+    assert_eq!(reparse.removed, vec![ids[1]]);
+    assert_eq!(reparse.added.len(), 2);
+    assert_eq!(document.statements().len(), 5);
+    assert_eq!(document.statements()[3].id(), ids[2]);
+    assert_eq!(document.program(), full_parse(document.source()));
+}
+
+#[test]
+fn test_edit_that_opens_a_block() {
+    let mut document = ParsedDocument::new(SOURCE).unwrap();
+    assert!(document.edit(0..5, "class Box").is_err());
+    assert!(document.statements().is_empty());
+
+    // With the block closed, the whole text parses again
+    let end = document.source().len();
+    let reparse = document.edit(end..end, "end\n").unwrap();
+    assert_eq!(reparse.added.len(), 1);
+    assert!(matches!(
+        document.statements()[0].statement(),
+        Statement::ClassDef { .. }
+    ));
+    assert_eq!(document.program(), full_parse(document.source()));
+}
+
+#[test]
+fn test_edit_that_breaks_the_syntax() {
+    let mut document = ParsedDocument::new(SOURCE).unwrap();
+    let start = document.source().find("n * 2").unwrap();
+    assert!(document.edit(start..start + 1, "(").is_err());
+    assert!(document.source().contains("( * 2"));
+    assert!(document.statements().is_empty());
+
+    edit(&mut document, "( * 2", "n * 3");
+    assert_eq!(document.program(), full_parse(document.source()));
+    assert_eq!(document.statements().len(), 4);
+}
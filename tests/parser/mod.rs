@@ -1,2 +1,4 @@
+mod incremental_tests;
 mod parser_error_recovery_tests;
 mod parser_tests;
+mod precedence_tests;
@@ -69,6 +69,21 @@ fn test_parse_function_def() {
     }
 }
 
+#[test]
+fn test_parse_index_method_defs() {
+    let statements = parse_source("def [](key)\nend\ndef []=(key, value)\nend").unwrap();
+    let names: Vec<_> = statements
+        .iter()
+        .map(|statement| match statement {
+            Statement::FunctionDef {
+                name, parameters, ..
+            } => (name.as_str(), parameters.len()),
+            _ => panic!("Expected FunctionDef statement"),
+        })
+        .collect();
+    assert_eq!(names, vec![("[]", 1), ("[]=", 2)]);
+}
+
 #[test]
 fn test_parse_class_def() {
     let result = parse_source("class Foo\nend");
@@ -140,6 +155,32 @@ fn test_parse_method_call() {
     }
 }
 
+#[test]
+fn test_parse_call_with_only_a_brace_block() {
+    let result = parse_source("pre { |x| x > 0 }");
+    assert!(result.is_ok());
+    let statements = result.unwrap();
+    assert_eq!(statements.len(), 1);
+
+    match &statements[0] {
+        Statement::Expression { expression, .. } => match expression {
+            Expression::Call {
+                arguments,
+                trailing_block,
+                ..
+            } => {
+                assert!(arguments.is_empty());
+                assert!(matches!(
+                    trailing_block.as_deref(),
+                    Some(Expression::Lambda { parameters, .. }) if parameters == &["x"]
+                ));
+            }
+            _ => panic!("Expected Call"),
+        },
+        _ => panic!("Expected Expression statement"),
+    }
+}
+
 #[test]
 fn test_parse_array_literal() {
     let result = parse_source("[1, 2, 3]");
@@ -0,0 +1,142 @@
+use metorex::ast::{Expression, Statement};
+use metorex::lexer::Lexer;
+use metorex::parser::Parser;
+use metorex::parser::precedence::{Associativity, INFIX_OPERATORS, InfixKind, infix_operator};
+
+/// Parse a single expression and render it with every operation parenthesized
+fn grouping(source: &str) -> String {
+    let statements = Parser::new(Lexer::new(source).tokenize())
+        .parse()
+        .unwrap_or_else(|errors| panic!("{} should parse: {:?}", source, errors));
+    match &statements[..] {
+        [Statement::Expression { expression, .. }] => render(expression),
+        other => panic!("expected one expression, got {:?}", other),
+    }
+}
+
+fn render(expression: &Expression) -> String {
+    match expression {
+        Expression::BinaryOp {
+            op, left, right, ..
+        } => format!("({} {} {})", render(left), op, render(right)),
+        Expression::Range {
+            start,
+            end,
+            exclusive,
+            ..
+        } => {
+            let symbol = if *exclusive { "..." } else { ".." };
+            format!("({}{}{})", render(start), symbol, render(end))
+        }
+        Expression::UnaryOp { op, operand, .. } => format!("({:?} {})", op, render(operand)),
+        Expression::Identifier { name, .. } => name.clone(),
+        Expression::IntLiteral { value, .. } => value.to_string(),
+        other => panic!("unexpected expression {:?}", other),
+    }
+}
+
+// ============================================================================
+// The table
+// ============================================================================
+
+#[test]
+fn table_lists_each_token_once_loosest_first() {
+    for (index, operator) in INFIX_OPERATORS.iter().enumerate() {
+        assert_eq!(
+            infix_operator(&operator.token),
+            Some(operator),
+            "{} appears twice",
+            operator.symbol
+        );
+        if let Some(next) = INFIX_OPERATORS.get(index + 1) {
+            assert!(
+                operator.precedence <= next.precedence,
+                "{} is listed before the looser {}",
+                operator.symbol,
+                next.symbol
+            );
+        }
+    }
+}
+
+#[test]
+fn table_symbols_lex_to_their_tokens() {
+    for operator in INFIX_OPERATORS {
+        let tokens = Lexer::new(&format!("a {} b", operator.symbol)).tokenize();
+        assert_eq!(tokens[1].kind, operator.token, "{}", operator.symbol);
+    }
+}
+
+// ============================================================================
+// Grouping follows the table
+// ============================================================================
+
+#[test]
+fn every_pair_of_binary_operators_groups_by_precedence() {
+    let binary = INFIX_OPERATORS
+        .iter()
+        .filter(|operator| matches!(operator.kind, InfixKind::Binary(_)));
+    // `and` and `or` parse to the same operations as `&&` and `||`
+    let rendered = |kind: &InfixKind| match kind {
+        InfixKind::Binary(op) => op.to_string(),
+        _ => unreachable!(),
+    };
+    for first in binary.clone() {
+        for second in binary.clone() {
+            assert_eq!(first.associativity, Associativity::Left);
+            let source = format!("a {} b {} c", first.symbol, second.symbol);
+            let (op1, op2) = (rendered(&first.kind), rendered(&second.kind));
+            let expected = if first.precedence >= second.precedence {
+                format!("((a {} b) {} c)", op1, op2)
+            } else {
+                format!("(a {} (b {} c))", op1, op2)
+            };
+            assert_eq!(grouping(&source), expected, "{}", source);
+        }
+    }
+}
+
+#[test]
+fn ranges_sit_between_comparison_and_appending() {
+    assert_eq!(grouping("a + 1..b * 2"), "((a + 1)..(b * 2))");
+    assert_eq!(grouping("a << 1...b"), "((a << 1)...b)");
+    assert_eq!(grouping("a..b == c"), "((a..b) == c)");
+    assert_eq!(grouping("a < b..c"), "(a < (b..c))");
+}
+
+#[test]
+fn unary_operators_bind_tighter_than_every_infix_operator() {
+    assert_eq!(grouping("-a * b"), "((Minus a) * b)");
+    assert_eq!(grouping("a - -b"), "(a - (Minus b))");
+    assert_eq!(grouping("!a == b"), "((Not a) == b)");
+}
+
+#[test]
+fn logical_operators_sit_below_comparison() {
+    assert_eq!(grouping("a == 1 || b < 2"), "((a == 1) || (b < 2))");
+    assert_eq!(grouping("a || b && c"), "(a || (b && c))");
+    assert_eq!(grouping("a && b || c"), "((a && b) || c)");
+}
+
+#[test]
+fn keyword_logic_is_looser_than_symbolic_logic() {
+    assert_eq!(grouping("a and b || c"), "(a && (b || c))");
+    assert_eq!(grouping("a or b and c"), "((a || b) && c)");
+    assert_eq!(grouping("not a == b"), "(Not (a == b))");
+    assert_eq!(grouping("not a || b and c"), "((Not (a || b)) && c)");
+}
+
+#[test]
+fn ranges_cannot_be_chained() {
+    for source in ["1..2..3", "1...2..3", "a == 1..2...3"] {
+        let errors = Parser::new(Lexer::new(source).tokenize())
+            .parse()
+            .unwrap_err();
+        assert!(
+            errors[0].to_string().contains("cannot be chained"),
+            "{}: {}",
+            source,
+            errors[0]
+        );
+    }
+}
@@ -0,0 +1,4 @@
+// Pretty printer tests module
+
+mod pretty_printer_tests;
+mod source_format_tests;
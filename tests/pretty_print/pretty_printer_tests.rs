@@ -0,0 +1,164 @@
+use indexmap::IndexMap;
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::pretty_print::{DEFAULT_PRETTY_PRINT_WIDTH, PrettyPrinter};
+use metorex::vm::VirtualMachine;
+
+fn run(source: &str) -> Result<VirtualMachine, String> {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens)
+        .parse()
+        .map_err(|e| format!("{:?}", e))?;
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program).map_err(|e| format!("{}", e))?;
+    Ok(vm)
+}
+
+fn ints(values: &[i64]) -> Object {
+    Object::array(values.iter().map(|v| Object::Int(*v)).collect())
+}
+
+fn sample_dict() -> Object {
+    let mut map = IndexMap::new();
+    map.insert("name".to_string(), Object::string("metorex"));
+    map.insert("numbers".to_string(), ints(&[1, 2, 3]));
+    Object::dict(map)
+}
+
+// ============================================================================
+// Layout
+// ============================================================================
+
+#[test]
+fn test_default_width() {
+    assert_eq!(PrettyPrinter::new().width(), DEFAULT_PRETTY_PRINT_WIDTH);
+    assert_eq!(PrettyPrinter::default(), PrettyPrinter::new());
+}
+
+#[test]
+fn test_scalars_format_like_repl() {
+    let printer = PrettyPrinter::new();
+    assert_eq!(printer.format(&Object::Nil), "nil");
+    assert_eq!(printer.format(&Object::Float(2.0)), "2.0");
+    assert_eq!(printer.format(&Object::string("hi")), "\"hi\"");
+    assert_eq!(
+        printer.format(&Object::Symbol(std::rc::Rc::new("ok".to_string()))),
+        ":ok"
+    );
+}
+
+#[test]
+fn test_value_that_fits_stays_on_one_line() {
+    let printer = PrettyPrinter::new();
+    assert_eq!(
+        printer.format(&sample_dict()),
+        "{\"name\" => \"metorex\", \"numbers\" => [1, 2, 3]}"
+    );
+}
+
+#[test]
+fn test_long_hash_breaks_entries_onto_lines() {
+    let printer = PrettyPrinter::with_width(30);
+    assert_eq!(
+        printer.format(&sample_dict()),
+        "{\n  \"name\" => \"metorex\",\n  \"numbers\" => [1, 2, 3]\n}"
+    );
+}
+
+#[test]
+fn test_nested_values_break_only_where_needed() {
+    let mut map = IndexMap::new();
+    map.insert("short".to_string(), ints(&[1, 2]));
+    map.insert("long".to_string(), ints(&[100, 200, 300, 400, 500, 600]));
+    let printer = PrettyPrinter::with_width(24);
+    assert_eq!(
+        printer.format(&Object::dict(map)),
+        "{\n  \"short\" => [1, 2],\n  \"long\" => [\n    100,\n    200,\n    300,\n    400,\n    500,\n    600\n  ]\n}"
+    );
+}
+
+#[test]
+fn test_self_referencing_array_is_elided() {
+    let array = ints(&[1]);
+    if let Object::Array(items) = &array {
+        items.borrow_mut().push(array.clone());
+    }
+    let printer = PrettyPrinter::new();
+    assert_eq!(printer.format(&array), "[1, [...]]");
+    assert_eq!(printer.format_inline(&array), "[1, [...]]");
+    // Break the cycle so the test does not leak
+    if let Object::Array(items) = &array {
+        items.borrow_mut().clear();
+    }
+}
+
+// ============================================================================
+// Instances
+// ============================================================================
+
+const POINT_CLASS: &str = r#"
+class Point
+  def initialize(x, y)
+    @y = y
+    @x = x
+  end
+end
+"#;
+
+#[test]
+fn test_instance_shows_sorted_instance_variables() {
+    let vm = run(&format!("{}\np = Point.new(1, 2)", POINT_CLASS)).unwrap();
+    let point = vm.environment().get("p").unwrap();
+    assert_eq!(PrettyPrinter::new().format(&point), "<Point @x=1, @y=2>");
+}
+
+#[test]
+fn test_wide_instance_breaks_instance_variables() {
+    let vm = run(&format!(
+        "{}\np = Point.new(\"a long x coordinate\", \"a long y coordinate\")",
+        POINT_CLASS
+    ))
+    .unwrap();
+    let point = vm.environment().get("p").unwrap();
+    assert_eq!(
+        PrettyPrinter::with_width(30).format(&point),
+        "<Point\n  @x=\"a long x coordinate\",\n  @y=\"a long y coordinate\"\n>"
+    );
+}
+
+// ============================================================================
+// pp builtin
+// ============================================================================
+
+#[test]
+fn test_pp_returns_its_argument() {
+    let vm = run("result = pp([1, 2, 3])").unwrap();
+    assert_eq!(vm.environment().get("result"), Some(ints(&[1, 2, 3])));
+}
+
+#[test]
+fn test_pp_with_multiple_arguments_returns_array() {
+    let vm = run("result = pp(1, 2)").unwrap();
+    assert_eq!(vm.environment().get("result"), Some(ints(&[1, 2])));
+}
+
+#[test]
+fn test_pp_width_option_is_not_returned() {
+    let vm = run("result = pp([1, 2], width: 10)").unwrap();
+    assert_eq!(vm.environment().get("result"), Some(ints(&[1, 2])));
+}
+
+#[test]
+fn test_pp_rejects_invalid_width() {
+    let error = run("pp([1, 2], width: 0)").err().unwrap();
+    assert!(error.contains("pp width must be a positive Int"));
+}
+
+#[test]
+fn test_vm_pretty_print_width_is_configurable() {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(vm.pretty_printer().width(), DEFAULT_PRETTY_PRINT_WIDTH);
+    vm.set_pretty_print_width(40);
+    assert_eq!(vm.pretty_printer().width(), 40);
+}
@@ -0,0 +1,52 @@
+// Tests for the whitespace normalization behind `metorex fmt`
+
+use metorex::source_format::{format_source, needs_formatting};
+
+#[test]
+fn test_trailing_whitespace_is_removed() {
+    assert_eq!(format_source("x = 1  \ny = 2\t\n"), "x = 1\ny = 2\n");
+}
+
+#[test]
+fn test_indentation_tabs_become_spaces() {
+    assert_eq!(
+        format_source("def f\n\tif x\n\t\t1\n\tend\nend\n"),
+        "def f\n  if x\n    1\n  end\nend\n"
+    );
+}
+
+#[test]
+fn test_tabs_after_indentation_are_kept() {
+    assert_eq!(format_source("x =\t1\n"), "x =\t1\n");
+}
+
+#[test]
+fn test_line_endings_and_bom_are_normalized() {
+    assert_eq!(
+        format_source("\u{feff}x = 1\r\ny = 2\r\n"),
+        "x = 1\ny = 2\n"
+    );
+}
+
+#[test]
+fn test_file_ends_with_exactly_one_newline() {
+    assert_eq!(format_source("x = 1"), "x = 1\n");
+    assert_eq!(format_source("x = 1\n\n\n"), "x = 1\n");
+    assert_eq!(format_source(""), "");
+    assert_eq!(format_source("\n\n"), "");
+}
+
+#[test]
+fn test_whitespace_inside_strings_is_kept() {
+    assert_eq!(
+        format_source("  text = \"a  \tb  \"  \n"),
+        "  text = \"a  \tb  \"\n"
+    );
+}
+
+#[test]
+fn test_formatting_is_idempotent() {
+    let formatted = format_source("\tx = 1 \r\n\r\n");
+    assert!(!needs_formatting(&formatted));
+    assert!(needs_formatting("\tx = 1 \r\n"));
+}
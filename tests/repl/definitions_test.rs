@@ -0,0 +1,37 @@
+// Tests for listing what each REPL input defined
+
+use metorex::repl::Repl;
+
+fn definitions(repl: &mut Repl, source: &str) -> Vec<String> {
+    repl.eval(source).unwrap();
+    repl.last_definitions().definitions()
+}
+
+#[test]
+fn test_input_lists_its_classes_methods_and_functions() {
+    let mut repl = Repl::new().unwrap();
+    let source = "class Point\n  def norm\n    0\n  end\nend\ndef greet\n  1\nend";
+
+    assert_eq!(
+        definitions(&mut repl, source),
+        vec!["Point", "Point#norm", "greet"]
+    );
+}
+
+#[test]
+fn test_input_without_definitions_lists_nothing() {
+    let mut repl = Repl::new().unwrap();
+    repl.eval("def greet\n  1\nend").unwrap();
+
+    assert!(definitions(&mut repl, "x = greet()").is_empty());
+    assert_eq!(repl.last_definitions().variables, vec!["x"]);
+}
+
+#[test]
+fn test_failed_input_keeps_the_previous_definitions() {
+    let mut repl = Repl::new().unwrap();
+    repl.eval("def greet\n  1\nend").unwrap();
+
+    assert!(repl.eval("def other\n  2\nend\nraise \"boom\"").is_err());
+    assert_eq!(repl.last_definitions().definitions(), vec!["greet"]);
+}
@@ -1,3 +1,9 @@
 // REPL tests module
 
+mod definitions_test;
+mod post_mortem_test;
+mod rc_file_test;
 mod repl_test;
+mod result_history_test;
+mod session_test;
+mod transaction_test;
@@ -0,0 +1,87 @@
+// Tests for REPL sessions opened on a program's uncaught error
+
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::repl::Repl;
+use metorex::terminal::ColorChoice;
+use metorex::vm::VirtualMachine;
+
+const PROGRAM: &str = r#"
+def check(limit)
+  total = limit * 2
+  raise "over the limit"
+end
+
+def outer
+  label = "outer"
+  check(21)
+end
+
+outer()
+"#;
+
+fn failed_session() -> Repl {
+    let mut vm = VirtualMachine::new();
+    vm.set_post_mortem(true);
+    let program = Parser::new(Lexer::new(PROGRAM).tokenize()).parse().unwrap();
+    let error = vm.execute_program(&program).unwrap_err();
+    let post_mortem = vm.take_post_mortem(&error).unwrap();
+    Repl::for_post_mortem(ColorChoice::Never, vm, post_mortem).unwrap()
+}
+
+fn value(repl: &mut Repl, source: &str) -> Object {
+    repl.eval(source).unwrap().unwrap_or(Object::Nil)
+}
+
+#[test]
+fn test_session_starts_in_the_raising_frame() {
+    let mut repl = failed_session();
+    assert_eq!(repl.frame(), 0);
+    assert_eq!(value(&mut repl, "total"), Object::Int(42));
+    assert_eq!(value(&mut repl, "limit + 1"), Object::Int(22));
+    assert_eq!(
+        value(&mut repl, "$!.message"),
+        Object::string("over the limit")
+    );
+}
+
+#[test]
+fn test_backtrace_marks_the_current_frame() {
+    let mut repl = failed_session();
+    assert_eq!(
+        repl.backtrace(),
+        vec!["* 0: check", "  1: outer", "  2: <main>"]
+    );
+
+    assert_eq!(
+        repl.select_frame(1).map(|frame| frame.name()),
+        Some("outer")
+    );
+    assert_eq!(repl.backtrace()[1], "* 1: outer");
+    assert_eq!(value(&mut repl, "label"), Object::string("outer"));
+    assert!(repl.eval("total").is_err());
+}
+
+#[test]
+fn test_selecting_a_missing_frame_keeps_the_current_one() {
+    let mut repl = failed_session();
+    assert!(repl.select_frame(3).is_none());
+    assert_eq!(repl.frame(), 0);
+    assert_eq!(value(&mut repl, "total"), Object::Int(42));
+}
+
+#[test]
+fn test_failed_input_stays_in_the_frame() {
+    let mut repl = failed_session();
+    assert!(repl.eval("extra = 1\nraise \"again\"").is_err());
+    assert_eq!(value(&mut repl, "total"), Object::Int(42));
+    assert!(repl.post_mortem().is_some());
+}
+
+#[test]
+fn test_regular_sessions_have_no_backtrace() {
+    let repl = Repl::new().unwrap();
+    assert!(repl.post_mortem().is_none());
+    assert!(repl.backtrace().is_empty());
+}
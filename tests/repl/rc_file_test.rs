@@ -0,0 +1,106 @@
+// Tests for finding and running .metorexrc startup files
+
+use crate::common::temp_dir;
+use metorex::object::Object;
+use metorex::rc_file::{RC_FILE_NAME, RcFileError, load_rc_file, rc_file_paths};
+use metorex::repl::Repl;
+use metorex::terminal::Palette;
+use metorex::vm::VirtualMachine;
+use std::fs;
+
+#[test]
+fn test_rc_files_run_home_first_then_project() {
+    let home = temp_dir("rc_file_order_home");
+    let project = temp_dir("rc_file_order_project");
+    fs::write(home.join(RC_FILE_NAME), "").unwrap();
+    fs::write(project.join(RC_FILE_NAME), "").unwrap();
+
+    let paths = rc_file_paths(Some(&home), &project);
+    assert_eq!(paths, [home.join(RC_FILE_NAME), project.join(RC_FILE_NAME)]);
+}
+
+#[test]
+fn test_missing_rc_files_are_skipped_and_shared_ones_listed_once() {
+    let home = temp_dir("rc_file_missing_home");
+    let project = temp_dir("rc_file_missing_project");
+    assert!(rc_file_paths(Some(&home), &project).is_empty());
+    assert!(rc_file_paths(None, &project).is_empty());
+
+    fs::write(home.join(RC_FILE_NAME), "").unwrap();
+    assert_eq!(rc_file_paths(Some(&home), &home), [home.join(RC_FILE_NAME)]);
+}
+
+#[test]
+fn test_rc_file_defines_helpers_and_settings() {
+    let dir = temp_dir("rc_file_helpers");
+    fs::write(dir.join("helpers.mx"), "def double(n)\n  n * 2\nend\n").unwrap();
+    let rc = dir.join(RC_FILE_NAME);
+    fs::write(
+        &rc,
+        "require_relative \"helpers\"\ndef greet(name)\n  \"hi #{name}\"\nend\nMetorex.pp_width(40)\n",
+    )
+    .unwrap();
+
+    let mut vm = VirtualMachine::new();
+    load_rc_file(&mut vm, &rc).unwrap();
+    assert_eq!(vm.pretty_printer().width(), 40);
+    assert!(vm.get_current_file().is_none());
+
+    let mut repl = Repl::new().unwrap();
+    assert!(repl.load_rc_files(vec![rc]).is_empty());
+    assert_eq!(
+        repl.eval("greet(\"ada\")").unwrap(),
+        Some(Object::string("hi ada"))
+    );
+    assert_eq!(repl.eval("double(21)").unwrap(), Some(Object::Int(42)));
+    assert_eq!(
+        repl.eval("Metorex.pp_width").unwrap(),
+        Some(Object::Int(40))
+    );
+    // What an rc file defines is not part of the saved session
+    assert_eq!(repl.session().len(), 3);
+}
+
+#[test]
+fn test_parse_errors_name_the_rc_file_and_line() {
+    let dir = temp_dir("rc_file_parse_error");
+    let rc = dir.join(RC_FILE_NAME);
+    fs::write(&rc, "x = 1\ndef broken(\n").unwrap();
+
+    let mut vm = VirtualMachine::new();
+    let err = load_rc_file(&mut vm, &rc).unwrap_err();
+    assert!(matches!(err, RcFileError::Parse { .. }));
+    assert_eq!(err.path(), rc.as_path());
+    let report = err.report(Palette::plain());
+    assert!(
+        report.starts_with(&format!("Error in rc file '{}':", rc.display())),
+        "{}",
+        report
+    );
+    assert!(report.contains("Parse error"), "{}", report);
+    // Nothing in a file that fails to parse runs
+    assert!(vm.environment().get("x").is_none());
+}
+
+#[test]
+fn test_runtime_errors_are_reported_and_later_rc_files_still_run() {
+    let home = temp_dir("rc_file_runtime_home");
+    let project = temp_dir("rc_file_runtime_project");
+    fs::write(home.join(RC_FILE_NAME), "missing_helper(1)\n").unwrap();
+    fs::write(project.join(RC_FILE_NAME), "ready = true\n").unwrap();
+
+    let mut repl = Repl::new().unwrap();
+    let errors = repl.load_rc_files(rc_file_paths(Some(&home), &project));
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0], RcFileError::Run { .. }));
+    let report = errors[0].report(Palette::plain());
+    assert!(report.contains("missing_helper(1)"), "{}", report);
+    assert_eq!(repl.eval("ready").unwrap(), Some(Object::Bool(true)));
+}
+
+#[test]
+fn test_pp_width_rejects_non_positive_widths() {
+    let mut repl = Repl::new().unwrap();
+    let err = repl.eval("Metorex.pp_width(0)").unwrap_err();
+    assert!(err.to_string().contains("pp width"), "{}", err);
+}
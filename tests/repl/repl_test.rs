@@ -300,10 +300,7 @@ fn test_repl_string_methods() {
 
     match (&results[0], &results[1], &results[2]) {
         (Some(Object::String(s1)), Some(Object::String(s2)), Some(Object::Int(4)))
-            if s1.as_str() == "HELLO" && s2.as_str() == "world" =>
-        {
-            ()
-        }
+            if s1.as_str() == "HELLO" && s2.as_str() == "world" => {}
         other => panic!("Expected correct string method results, got {:?}", other),
     }
 }
@@ -0,0 +1,83 @@
+// Tests for the REPL result history variables `_`, `_1..` and `ans`
+
+use crate::common::temp_dir;
+use metorex::object::Object;
+use metorex::repl::{ANSWER_VAR, LAST_RESULT_VAR, Repl};
+use std::fs;
+
+fn value(repl: &mut Repl, source: &str) -> Object {
+    repl.eval(source).unwrap().unwrap_or(Object::Nil)
+}
+
+#[test]
+fn test_last_result_is_bound_to_underscore_and_ans() {
+    let mut repl = Repl::new().unwrap();
+    repl.eval("6 * 7").unwrap();
+
+    assert_eq!(value(&mut repl, LAST_RESULT_VAR), Object::Int(42));
+    assert_eq!(value(&mut repl, "ans + 1"), Object::Int(43));
+    assert_eq!(value(&mut repl, "_ * 2"), Object::Int(86));
+}
+
+#[test]
+fn test_results_are_numbered_in_order() {
+    let mut repl = Repl::new().unwrap();
+    repl.eval("10").unwrap();
+    repl.eval("x = 5").unwrap();
+    repl.eval("20").unwrap();
+
+    assert_eq!(repl.results(), [Object::Int(10), Object::Int(20)]);
+    assert_eq!(value(&mut repl, "_1 + _2"), Object::Int(30));
+    assert_eq!(value(&mut repl, "_3"), Object::Int(30));
+}
+
+#[test]
+fn test_statements_without_a_value_keep_the_last_result() {
+    let mut repl = Repl::new().unwrap();
+    repl.eval("\"kept\"").unwrap();
+    repl.eval("y = 1").unwrap();
+
+    assert_eq!(value(&mut repl, ANSWER_VAR), Object::string("kept"));
+}
+
+#[test]
+fn test_loaded_files_do_not_see_earlier_results() {
+    let dir = temp_dir("result_history_load");
+    let leaky = dir.join("leaky.mx");
+    fs::write(&leaky, "seen = _\n").unwrap();
+
+    let mut repl = Repl::new().unwrap();
+    repl.eval("1").unwrap();
+    let err = repl.load_session(&leaky).unwrap_err();
+    assert!(
+        err.to_string().contains("Undefined variable '_'"),
+        "{}",
+        err
+    );
+
+    // The bindings come back once the load finishes
+    assert_eq!(value(&mut repl, "_1"), Object::Int(1));
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_saved_sessions_replay_their_own_results() {
+    let dir = temp_dir("result_history_replay");
+    let path = dir.join("session.mx");
+
+    let mut repl = Repl::new().unwrap();
+    repl.eval("2 + 3").unwrap();
+    repl.eval("doubled = _ * 2").unwrap();
+    repl.save_session(&path).unwrap();
+
+    let mut restored = Repl::new().unwrap();
+    restored.eval("100").unwrap();
+    assert_eq!(restored.load_session(&path).unwrap(), 2);
+
+    assert_eq!(value(&mut restored, "doubled"), Object::Int(10));
+    assert_eq!(
+        restored.results(),
+        [Object::Int(100), Object::Int(5), Object::Int(10)]
+    );
+    fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,85 @@
+// Tests for saving and loading REPL sessions
+
+use crate::common::temp_dir;
+use metorex::object::Object;
+use metorex::repl::Repl;
+use metorex::session::{ENTRY_MARKER, SESSION_HEADER, Session};
+use metorex::vm::VirtualMachine;
+use std::fs;
+
+#[test]
+fn test_session_records_non_blank_inputs() {
+    let mut session = Session::new();
+    session.record("x = 1");
+    session.record("   \n");
+    session.record("y = 2\n");
+
+    assert_eq!(session.entries(), ["x = 1", "y = 2"]);
+}
+
+#[test]
+fn test_session_source_round_trip() {
+    let mut session = Session::new();
+    session.record("x = 1");
+    session.record("def double(n)\n  n * 2\nend");
+
+    let source = session.to_source();
+    assert!(source.starts_with(SESSION_HEADER));
+    assert_eq!(source.matches(ENTRY_MARKER).count(), 2);
+    assert_eq!(Session::from_source(&source), session);
+}
+
+#[test]
+fn test_session_without_markers_is_one_entry() {
+    let session = Session::from_source("a = 1\nb = a + 1\n");
+    assert_eq!(session.entries(), ["a = 1\nb = a + 1"]);
+}
+
+#[test]
+fn test_session_replay_rebuilds_state() {
+    let session = Session::from_source(
+        "# >>\nclass Counter\n  def initialize\n    @n = 0\n  end\n  def tick\n    @n = @n + 1\n    @n\n  end\nend\n# >>\nc = Counter.new\nc.tick\ncount = c.tick\n",
+    );
+    let mut vm = VirtualMachine::new();
+    session.replay(&mut vm).unwrap();
+
+    assert_eq!(vm.environment().get("count"), Some(Object::Int(2)));
+}
+
+#[test]
+fn test_session_replay_stops_at_error() {
+    let session = Session::from_source("# >>\nx = 1\n# >>\nundefined_thing\n# >>\ny = 2\n");
+    let mut vm = VirtualMachine::new();
+
+    assert!(session.replay(&mut vm).is_err());
+    assert_eq!(vm.environment().get("x"), Some(Object::Int(1)));
+    assert_eq!(vm.environment().get("y"), None);
+}
+
+#[test]
+fn test_repl_save_and_load_session() {
+    let path = temp_dir("repl_session").join("session.mxs");
+
+    let mut repl = Repl::new().unwrap();
+    repl.eval("def greet(name)\n  \"hi \" + name\nend").unwrap();
+    repl.eval("who = \"ada\"").unwrap();
+    assert!(repl.eval("missing_method()").is_err());
+    assert_eq!(repl.session().len(), 2);
+    repl.save_session(&path).unwrap();
+
+    let mut restored = Repl::new().unwrap();
+    assert_eq!(restored.load_session(&path).unwrap(), 2);
+    let result = restored.eval("greet(who)").unwrap();
+    assert_eq!(result, Some(Object::string("hi ada")));
+    assert_eq!(restored.session().len(), 3);
+
+    fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_repl_load_missing_session() {
+    let mut repl = Repl::new().unwrap();
+    let path = temp_dir("repl_missing_session").join("missing.mxs");
+    let err = repl.load_session(&path).unwrap_err();
+    assert!(err.to_string().contains("Failed to load session"));
+}
@@ -0,0 +1,73 @@
+// Tests for rolling back REPL inputs that raise partway through
+
+use metorex::object::Object;
+use metorex::repl::Repl;
+
+fn value(repl: &mut Repl, source: &str) -> Object {
+    repl.eval(source).unwrap().unwrap_or(Object::Nil)
+}
+
+#[test]
+fn test_failed_input_restores_assigned_variables() {
+    let mut repl = Repl::new().unwrap();
+    repl.eval("x = 1").unwrap();
+
+    assert!(repl.eval("x = 2\ny = 3\nraise \"boom\"\nz = 4").is_err());
+    assert_eq!(value(&mut repl, "x"), Object::Int(1));
+    assert!(repl.eval("y").is_err());
+}
+
+#[test]
+fn test_failed_input_removes_its_methods_and_classes() {
+    let mut repl = Repl::new().unwrap();
+
+    assert!(
+        repl.eval("def helper\n  1\nend\nclass Widget\nend\nmissing_method()")
+            .is_err()
+    );
+    assert!(repl.eval("helper").is_err());
+    assert!(repl.eval("Widget.new").is_err());
+}
+
+#[test]
+fn test_failed_input_restores_variables_closures_captured() {
+    let mut repl = Repl::new().unwrap();
+    repl.eval("count = 0\nbump = lambda ||\n  count = count + 1\n  count\nend")
+        .unwrap();
+
+    assert!(repl.eval("bump.call\nbump.call\nraise \"boom\"").is_err());
+    assert_eq!(value(&mut repl, "count"), Object::Int(0));
+    assert_eq!(value(&mut repl, "bump.call"), Object::Int(1));
+}
+
+#[test]
+fn test_objects_changed_in_place_keep_their_changes() {
+    let mut repl = Repl::new().unwrap();
+    repl.eval("items = []").unwrap();
+
+    assert!(repl.eval("items.push(1)\nraise \"boom\"").is_err());
+    assert_eq!(
+        value(&mut repl, "items"),
+        Object::array(vec![Object::Int(1)])
+    );
+}
+
+#[test]
+fn test_failed_input_is_not_recorded() {
+    let mut repl = Repl::new().unwrap();
+    repl.eval("1 + 1").unwrap();
+
+    assert!(repl.eval("2 + 2\nraise \"boom\"").is_err());
+    assert_eq!(repl.session().len(), 1);
+    assert_eq!(repl.results(), [Object::Int(2)]);
+    assert_eq!(value(&mut repl, "_"), Object::Int(2));
+}
+
+#[test]
+fn test_successful_input_after_a_rollback_runs_normally() {
+    let mut repl = Repl::new().unwrap();
+
+    assert!(repl.eval("a = 1\nraise \"boom\"").is_err());
+    repl.eval("a = 10\nb = a * 2").unwrap();
+    assert_eq!(value(&mut repl, "b"), Object::Int(20));
+}
@@ -1,5 +1,7 @@
 // Tests for require_relative native function
 
+mod path_tests;
+
 use metorex::object::Object;
 use metorex::vm::VirtualMachine;
 use std::rc::Rc;
@@ -21,8 +23,8 @@ fn require_relative_with_wrong_number_of_arguments() {
     use std::fs;
     use std::path::Path;
 
-    let temp_dir = std::env::temp_dir();
-    let test_file = temp_dir.join("require_test_args.mx");
+    let dir = crate::common::temp_dir("require_with_wrong_number_of_arguments");
+    let test_file = dir.join("require_test_args.mx");
 
     // Test with 2 arguments - since require_relative uses call syntax, we need to test
     // by actually calling it as a function
@@ -32,7 +34,11 @@ fn require_relative_with_wrong_number_of_arguments() {
     let _ = fs::remove_file(&test_file);
     assert!(result.is_err());
     let err_msg = format!("{}", result.unwrap_err());
-    assert!(err_msg.contains("expects 1 argument"));
+    assert!(
+        err_msg.contains("require_relative: wrong number of arguments (given 2, expected 1)"),
+        "{}",
+        err_msg
+    );
 }
 
 #[test]
@@ -40,8 +46,8 @@ fn require_relative_with_non_string_argument() {
     use std::fs;
     use std::path::Path;
 
-    let temp_dir = std::env::temp_dir();
-    let test_file = temp_dir.join("require_test_type.mx");
+    let dir = crate::common::temp_dir("require_with_non_string_argument");
+    let test_file = dir.join("require_test_type.mx");
 
     // Test with integer
     fs::write(&test_file, "require_relative(42)").unwrap();
@@ -50,7 +56,11 @@ fn require_relative_with_non_string_argument() {
     let _ = fs::remove_file(&test_file);
     assert!(result.is_err());
     let err_msg = format!("{}", result.unwrap_err());
-    assert!(err_msg.contains("expects a String argument"));
+    assert!(
+        err_msg.contains("require_relative: path must be a String (got Int)"),
+        "{}",
+        err_msg
+    );
 
     // Test with boolean
     fs::write(&test_file, "require_relative(true)").unwrap();
@@ -59,7 +69,11 @@ fn require_relative_with_non_string_argument() {
     let _ = fs::remove_file(&test_file);
     assert!(result.is_err());
     let err_msg = format!("{}", result.unwrap_err());
-    assert!(err_msg.contains("expects a String argument"));
+    assert!(
+        err_msg.contains("require_relative: path must be a String (got Bool)"),
+        "{}",
+        err_msg
+    );
 }
 
 #[test]
@@ -67,8 +81,8 @@ fn require_relative_with_invalid_path() {
     use std::fs;
     use std::path::Path;
 
-    let temp_dir = std::env::temp_dir();
-    let test_file = temp_dir.join("require_test_invalid.mx");
+    let dir = crate::common::temp_dir("require_with_invalid_path");
+    let test_file = dir.join("require_test_invalid.mx");
 
     fs::write(
         &test_file,
@@ -87,9 +101,9 @@ fn require_relative_returns_true_for_new_file() {
     use std::path::Path;
 
     // Create a temporary test file
-    let temp_dir = std::env::temp_dir();
-    let main_file = temp_dir.join("require_test_main.mx");
-    let helper_file = temp_dir.join("require_test_helper.mx");
+    let dir = crate::common::temp_dir("require_returns_true_for_new_file");
+    let main_file = dir.join("require_test_main.mx");
+    let helper_file = dir.join("require_test_helper.mx");
 
     fs::write(&main_file, "require_relative(\"require_test_helper\")").unwrap();
     fs::write(&helper_file, "x = 1").unwrap();
@@ -112,9 +126,9 @@ fn require_relative_returns_false_for_already_loaded_file() {
     use std::path::Path;
 
     // Create a temporary test file
-    let temp_dir = std::env::temp_dir();
-    let main_file = temp_dir.join("require_test_main2.mx");
-    let helper_file = temp_dir.join("require_test_helper2.mx");
+    let dir = crate::common::temp_dir("require_returns_false_for_already_loaded_file");
+    let main_file = dir.join("require_test_main2.mx");
+    let helper_file = dir.join("require_test_helper2.mx");
 
     fs::write(
         &main_file,
@@ -146,9 +160,9 @@ fn require_relative_makes_variables_accessible() {
     use std::path::Path;
 
     // Create a temporary test file
-    let temp_dir = std::env::temp_dir();
-    let main_file = temp_dir.join("require_test_main3.mx");
-    let helper_file = temp_dir.join("require_test_helper3.mx");
+    let dir = crate::common::temp_dir("require_makes_variables_accessible");
+    let main_file = dir.join("require_test_main3.mx");
+    let helper_file = dir.join("require_test_helper3.mx");
 
     fs::write(&main_file, "require_relative(\"require_test_helper3\")").unwrap();
     fs::write(&helper_file, "shared_var = \"from helper\"").unwrap();
@@ -169,3 +183,109 @@ fn require_relative_makes_variables_accessible() {
         Some(Object::String(Rc::new(String::from("from helper"))))
     );
 }
+
+#[test]
+fn main_guard_skips_demo_code_when_required() {
+    use std::fs;
+    use std::path::Path;
+
+    let dir = crate::common::temp_dir("require_main_guard_skips_demo_code_when_required");
+    let main_file = dir.join("require_test_main_guard.mx");
+    let lib_file = dir.join("require_test_lib_guard.mx");
+
+    fs::write(
+        &main_file,
+        "require_relative(\"require_test_lib_guard\")\nmain_is_main = main?",
+    )
+    .unwrap();
+    fs::write(
+        &lib_file,
+        "lib_is_main = main?\nif main?\n  demo_ran = true\nend",
+    )
+    .unwrap();
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.execute_file(Path::new(&main_file));
+
+    let _ = fs::remove_file(&main_file);
+    let _ = fs::remove_file(&lib_file);
+
+    assert!(result.is_ok());
+    assert_eq!(
+        vm.environment().get("lib_is_main"),
+        Some(Object::Bool(false))
+    );
+    assert_eq!(
+        vm.environment().get("main_is_main"),
+        Some(Object::Bool(true))
+    );
+    assert_eq!(vm.environment().get("demo_ran"), None);
+}
+
+#[test]
+fn main_guard_runs_demo_code_when_executed_directly() {
+    use std::fs;
+    use std::path::Path;
+
+    let dir = crate::common::temp_dir("require_main_guard_runs_demo_code_when_executed_directly");
+    let lib_file = dir.join("require_test_lib_direct.mx");
+    fs::write(&lib_file, "if main?\n  demo_ran = true\nend").unwrap();
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.execute_file(Path::new(&lib_file));
+    let lib_path = lib_file.canonicalize().unwrap();
+    let _ = fs::remove_file(&lib_file);
+
+    assert!(result.is_ok());
+    assert_eq!(vm.environment().get("demo_ran"), Some(Object::Bool(true)));
+    assert_eq!(vm.main_file(), Some(&lib_path));
+}
+
+#[test]
+fn file_constant_names_the_running_file() {
+    use std::fs;
+    use std::path::Path;
+
+    let dir = crate::common::temp_dir("require_file_constant_names_the_running_file");
+    let main_file = dir.join("require_test_main_file.mx");
+    let lib_file = dir.join("require_test_lib_file.mx");
+
+    fs::write(
+        &main_file,
+        "require_relative(\"require_test_lib_file\")\nmain_path = __FILE__",
+    )
+    .unwrap();
+    fs::write(&lib_file, "lib_path = __FILE__").unwrap();
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.execute_file(Path::new(&main_file));
+    let main_path = main_file.canonicalize().unwrap();
+    let lib_path = lib_file.canonicalize().unwrap();
+
+    let _ = fs::remove_file(&main_file);
+    let _ = fs::remove_file(&lib_file);
+
+    assert!(result.is_ok());
+    assert_eq!(
+        vm.environment().get("main_path"),
+        Some(Object::string(main_path.to_string_lossy()))
+    );
+    assert_eq!(
+        vm.environment().get("lib_path"),
+        Some(Object::string(lib_path.to_string_lossy()))
+    );
+}
+
+#[test]
+fn main_guard_outside_a_file() {
+    use metorex::lexer::Lexer;
+    use metorex::parser::Parser;
+
+    let tokens = Lexer::new("in_main = main?\nfile = __FILE__").tokenize();
+    let program = Parser::new(tokens).parse().unwrap();
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program).unwrap();
+
+    assert_eq!(vm.environment().get("in_main"), Some(Object::Bool(true)));
+    assert_eq!(vm.environment().get("file"), Some(Object::Nil));
+}
@@ -0,0 +1,84 @@
+// Tests for how require_relative resolves and tracks paths
+
+use crate::common::temp_dir;
+use metorex::object::Object;
+use metorex::vm::{VirtualMachine, VmOptions};
+use std::fs;
+use std::path::PathBuf;
+
+/// Create a fresh project directory with an empty `lib` folder for one test
+fn temp_project(name: &str) -> PathBuf {
+    let dir = temp_dir(&format!("require_{}", name));
+    fs::create_dir_all(dir.join("lib")).unwrap();
+    dir
+}
+
+#[test]
+fn backslash_separators_resolve_on_every_platform() {
+    let dir = temp_project("backslash");
+    fs::write(dir.join("lib/util.mx"), "loaded = 1").unwrap();
+    fs::write(
+        dir.join("main.mx"),
+        "first = require_relative(\"lib\\\\util\")",
+    )
+    .unwrap();
+
+    let mut vm = VirtualMachine::new();
+    vm.execute_file(&dir.join("main.mx")).unwrap();
+    assert_eq!(vm.environment().get("first"), Some(Object::Bool(true)));
+    assert_eq!(vm.environment().get("loaded"), Some(Object::Int(1)));
+}
+
+#[test]
+fn mixed_separators_load_a_file_once() {
+    let dir = temp_project("mixed");
+    fs::write(dir.join("lib/util.mx"), "count = 1").unwrap();
+    fs::write(
+        dir.join("main.mx"),
+        "first = require_relative(\"lib/util\")\nsecond = require_relative(\"lib\\\\util.mx\")",
+    )
+    .unwrap();
+
+    let mut vm = VirtualMachine::new();
+    vm.execute_file(&dir.join("main.mx")).unwrap();
+    assert_eq!(vm.environment().get("first"), Some(Object::Bool(true)));
+    assert_eq!(vm.environment().get("second"), Some(Object::Bool(false)));
+    assert_eq!(vm.loaded_files().len(), 2);
+}
+
+#[cfg(unix)]
+#[test]
+fn a_file_reached_through_a_symlink_loads_once() {
+    let dir = temp_project("symlink");
+    fs::write(dir.join("lib/util.mx"), "count = 1").unwrap();
+    std::os::unix::fs::symlink(dir.join("lib/util.mx"), dir.join("alias.mx")).unwrap();
+    fs::write(
+        dir.join("main.mx"),
+        "first = require_relative(\"alias\")\nsecond = require_relative(\"lib/util\")",
+    )
+    .unwrap();
+
+    let mut vm = VirtualMachine::new();
+    vm.execute_file(&dir.join("main.mx")).unwrap();
+    assert_eq!(vm.environment().get("first"), Some(Object::Bool(true)));
+    assert_eq!(vm.environment().get("second"), Some(Object::Bool(false)));
+    assert!(vm.loaded_files().contains(&dir.join("lib/util.mx")));
+}
+
+#[test]
+fn case_insensitive_paths_track_files_regardless_of_case() {
+    let dir = temp_project("case");
+    let options = VmOptions {
+        case_insensitive_paths: true,
+        ..VmOptions::default()
+    };
+    let mut vm = VirtualMachine::with_options(options);
+    vm.mark_file_loaded(dir.join("Lib/Util.mx"));
+
+    assert!(vm.is_file_loaded(&dir.join("lib/util.mx")));
+    assert_eq!(vm.loaded_files(), vec![dir.join("Lib/Util.mx")]);
+
+    let mut vm = VirtualMachine::new();
+    vm.mark_file_loaded(dir.join("Lib/Util.mx"));
+    assert!(!vm.is_file_loaded(&dir.join("lib/util.mx")));
+}
@@ -0,0 +1,3 @@
+// Terminal styling tests module
+
+mod terminal_tests;
@@ -0,0 +1,134 @@
+// Tests for color selection, syntax highlighting and colored diagnostics
+
+use indexmap::IndexMap;
+use metorex::error::{MetorexError, SourceLocation};
+use metorex::object::Object;
+use metorex::terminal::{ColorChoice, Palette, Style};
+
+fn strip_ansi(text: &str) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\x1b' {
+            for c in chars.by_ref() {
+                if c == 'm' {
+                    break;
+                }
+            }
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+// ============================================================================
+// Color choice
+// ============================================================================
+
+#[test]
+fn test_color_choice_parsing() {
+    assert_eq!("auto".parse(), Ok(ColorChoice::Auto));
+    assert_eq!("always".parse(), Ok(ColorChoice::Always));
+    assert_eq!("never".parse(), Ok(ColorChoice::Never));
+
+    let err = "sometimes".parse::<ColorChoice>().unwrap_err();
+    assert!(err.contains("invalid color choice 'sometimes'"));
+}
+
+#[test]
+fn test_color_choice_display_round_trip() {
+    for choice in [ColorChoice::Auto, ColorChoice::Always, ColorChoice::Never] {
+        assert_eq!(choice.to_string().parse(), Ok(choice));
+    }
+}
+
+#[test]
+fn test_should_color() {
+    assert!(ColorChoice::Always.should_color(false));
+    assert!(!ColorChoice::Never.should_color(true));
+    assert!(!ColorChoice::Auto.should_color(false));
+}
+
+// ============================================================================
+// Painting and highlighting
+// ============================================================================
+
+#[test]
+fn test_plain_palette_leaves_text_alone() {
+    let palette = Palette::plain();
+    assert_eq!(palette.paint(Style::Error, "boom"), "boom");
+    assert_eq!(palette.highlight("def f() end"), "def f() end");
+}
+
+#[test]
+fn test_paint_wraps_text_in_ansi_codes() {
+    let palette = Palette::new(true);
+    assert_eq!(palette.paint(Style::Error, "boom"), "\x1b[1;31mboom\x1b[0m");
+}
+
+#[test]
+fn test_highlight_colors_tokens() {
+    let palette = Palette::new(true);
+    let highlighted = palette.highlight("def f(x) return 1 end");
+
+    assert!(highlighted.contains(&palette.paint(Style::Keyword, "def")));
+    assert!(highlighted.contains(&palette.paint(Style::Keyword, "return")));
+    assert!(highlighted.contains(&palette.paint(Style::Number, "1")));
+}
+
+#[test]
+fn test_highlight_preserves_source_text() {
+    let palette = Palette::new(true);
+    let source = "  @count = Point.new(\"a\\tb\", nil)   # done";
+    assert_eq!(strip_ansi(&palette.highlight(source)), source);
+}
+
+#[test]
+fn test_highlight_keeps_unlexable_tail() {
+    let palette = Palette::new(true);
+    let source = "x = \"unterminated";
+    assert_eq!(strip_ansi(&palette.highlight(source)), source);
+}
+
+// ============================================================================
+// Inspect and diagnostics
+// ============================================================================
+
+#[test]
+fn test_inspect_colors_by_type() {
+    let palette = Palette::new(true);
+    let mut map = IndexMap::new();
+    map.insert("k".to_string(), Object::Int(1));
+    let value = Object::array(vec![Object::Nil, Object::string("s"), Object::dict(map)]);
+
+    let text = palette.inspect(&value);
+    assert!(text.contains(&palette.paint(Style::Constant, "nil")));
+    assert!(text.contains(&palette.paint(Style::String, "\"s\"")));
+    assert!(text.contains(&palette.paint(Style::Number, "1")));
+    assert_eq!(strip_ansi(&text), "[nil, \"s\", {\"k\" => 1}]");
+}
+
+#[test]
+fn test_inspect_plain_matches_repl_format() {
+    let value = Object::array(vec![Object::Int(1), Object::Float(2.0)]);
+    assert_eq!(Palette::plain().inspect(&value), "[1, 2.0]");
+}
+
+#[test]
+fn test_diagnostic_includes_snippet_and_caret() {
+    let err = MetorexError::runtime_error("boom", SourceLocation::new(2, 5, 0));
+    let text = Palette::plain().diagnostic("Runtime error", &err, Some("a = 1\nb = c\n"));
+
+    assert_eq!(
+        text,
+        "Runtime error: Runtime error at 2:5: boom\n2 | b = c\n  |     ^"
+    );
+}
+
+#[test]
+fn test_diagnostic_without_source() {
+    let err = MetorexError::runtime_error("boom", SourceLocation::new(9, 1, 0));
+    let text = Palette::plain().diagnostic("Runtime error", &err, Some("one line"));
+    assert_eq!(text, "Runtime error: Runtime error at 9:1: boom");
+}
@@ -1 +1,2 @@
+mod type_annotation_tests;
 mod type_system_tests;
@@ -0,0 +1,228 @@
+// Tests for type annotation syntax and the static `typecheck` pass
+
+use metorex::ast::{Statement, TypeAnnotation};
+use metorex::lexer::{Lexer, Position};
+use metorex::parser::Parser;
+use metorex::typecheck::{self, satisfies};
+
+fn parse(source: &str) -> Vec<Statement> {
+    let tokens = Lexer::new(source).tokenize();
+    Parser::new(tokens).parse().expect("program should parse")
+}
+
+/// Diagnostics for `source` rendered as "line:column: message"
+fn check(source: &str) -> Vec<String> {
+    typecheck::check(&parse(source))
+        .iter()
+        .map(|diagnostic| diagnostic.to_string())
+        .collect()
+}
+
+fn annotation(written: &str) -> TypeAnnotation {
+    TypeAnnotation::parse(written, Position::new(1, 1, 0))
+}
+
+#[test]
+fn test_parse_parameter_and_result_annotations() {
+    let program = parse("def add(x: Int, y: Int?) -> Float\n  x + y\nend\n");
+    let Statement::FunctionDef {
+        parameters,
+        return_type,
+        ..
+    } = &program[0]
+    else {
+        panic!("expected a function definition, got {:?}", program[0]);
+    };
+
+    let types: Vec<String> = parameters
+        .iter()
+        .map(|p| p.type_annotation.as_ref().unwrap().to_string())
+        .collect();
+    assert_eq!(types, vec!["Int", "Int?"]);
+    assert!(parameters[1].type_annotation.as_ref().unwrap().nilable);
+    assert_eq!(return_type.as_ref().unwrap().name, "Float");
+}
+
+#[test]
+fn test_parse_annotation_before_default_value() {
+    let program = parse("def greet(name: String = \"you\")\n  name\nend\n");
+    let Statement::FunctionDef { parameters, .. } = &program[0] else {
+        panic!("expected a function definition");
+    };
+    assert_eq!(
+        parameters[0].type_annotation.as_ref().unwrap().name,
+        "String"
+    );
+    assert!(parameters[0].default_value.is_some());
+}
+
+#[test]
+fn test_parse_method_annotations() {
+    let program = parse("class Point\n  def scale(by: Numeric) -> Point\n    self\n  end\nend\n");
+    let Statement::ClassDef { body, .. } = &program[0] else {
+        panic!("expected a class definition");
+    };
+    let Statement::MethodDef {
+        parameters,
+        return_type,
+        ..
+    } = &body[0]
+    else {
+        panic!("expected a method definition, got {:?}", body[0]);
+    };
+    assert_eq!(
+        parameters[0].type_annotation.as_ref().unwrap().name,
+        "Numeric"
+    );
+    assert_eq!(return_type.as_ref().unwrap().name, "Point");
+}
+
+#[test]
+fn test_unannotated_definitions_have_no_types() {
+    let program = parse("def add(x, y)\n  x + y\nend\n");
+    let Statement::FunctionDef {
+        parameters,
+        return_type,
+        ..
+    } = &program[0]
+    else {
+        panic!("expected a function definition");
+    };
+    assert!(parameters.iter().all(|p| p.type_annotation.is_none()));
+    assert!(return_type.is_none());
+}
+
+#[test]
+fn test_satisfies_builtin_and_class_types() {
+    assert!(satisfies(&annotation("Int"), &["Int"]));
+    assert!(!satisfies(&annotation("Int"), &["Float"]));
+    assert!(satisfies(&annotation("Numeric"), &["Float"]));
+    assert!(satisfies(&annotation("Integer"), &["Int"]));
+    assert!(satisfies(&annotation("String?"), &["Nil"]));
+    assert!(!satisfies(&annotation("String"), &["Nil"]));
+    assert!(satisfies(&annotation("Any"), &["Dict"]));
+    assert!(satisfies(&annotation("Animal"), &["Dog", "Animal"]));
+    assert!(!satisfies(&annotation("Dog"), &["Animal"]));
+}
+
+#[test]
+fn test_check_accepts_well_typed_program() {
+    let source = "def add(x: Int, y: Int) -> Int\n  x + y\nend\n\
+                  total = add(1, 2)\n\
+                  add(total, \"ab\".length)\n";
+    assert!(check(source).is_empty());
+}
+
+#[test]
+fn test_check_reports_literal_argument_mismatch() {
+    let source = "def add(x: Int, y: Int) -> Int\n  x + y\nend\nadd(\"a\", 2)\n";
+    assert_eq!(
+        check(source),
+        vec!["4:5: argument 'x' of 'add' expects Int, found String"]
+    );
+}
+
+#[test]
+fn test_check_follows_types_through_locals() {
+    let source = "def shout(text: String) -> String\n  text.upcase\nend\n\
+                  n = 1.5\nm = n.floor\nshout(m)\n";
+    assert_eq!(
+        check(source),
+        vec!["6:7: argument 'text' of 'shout' expects String, found Int"]
+    );
+}
+
+#[test]
+fn test_check_infers_logical_operators() {
+    let source = "def shout(text: String) -> String\n  text\nend\n\
+                  shout(!x)\nshout(\"a\" || \"b\")\nshout(1 && \"b\")\n";
+    assert_eq!(
+        check(source),
+        vec!["4:7: argument 'text' of 'shout' expects String, found Bool"]
+    );
+}
+
+#[test]
+fn test_check_forgets_locals_assigned_differently_in_branches() {
+    let source = "def shout(text: String) -> String\n  text\nend\n\
+                  x = \"a\"\nif true\n  x = 1\nend\nshout(x)\n";
+    assert!(check(source).is_empty());
+}
+
+#[test]
+fn test_check_reports_mismatched_result() {
+    let source = "def name() -> String\n  42\nend\n\
+                  def size() -> Int\n  return \"big\"\nend\n";
+    assert_eq!(
+        check(source),
+        vec![
+            "2:3: 'name' declares result String but returns Int",
+            "5:3: 'size' declares result Int but returns String",
+        ]
+    );
+}
+
+#[test]
+fn test_check_uses_class_hierarchy() {
+    let source = "class Animal\nend\nclass Dog < Animal\nend\n\
+                  def pet(a: Animal) -> Animal\n  a\nend\n\
+                  pet(Dog.new)\npet(3)\n";
+    assert_eq!(
+        check(source),
+        vec!["9:5: argument 'a' of 'pet' expects Animal, found Int"]
+    );
+}
+
+#[test]
+fn test_check_method_and_initialize_arguments() {
+    let source = "class Point\n  def initialize(x: Int)\n    @x = x\n  end\n\
+                  \x20 def shift(by: Int) -> Point\n    self\n  end\nend\n\
+                  p = Point.new(\"a\")\np.shift(1.5)\n";
+    assert_eq!(
+        check(source),
+        vec![
+            "9:15: argument 'x' of 'initialize' expects Int, found String",
+            "10:9: argument 'by' of 'shift' expects Int, found Float",
+        ]
+    );
+}
+
+#[test]
+fn test_check_reports_unknown_types() {
+    let source = "def f(x: Strng) -> Int?\n  1\nend\n";
+    assert_eq!(check(source), vec!["1:10: Unknown type 'Strng'"]);
+}
+
+#[test]
+fn test_check_builtin_method_arguments() {
+    let source = "ok = \"a.mx\".match_glob(3)\n";
+    assert_eq!(
+        check(source),
+        vec!["1:24: argument 'pattern' of 'match_glob' expects String, found Int"]
+    );
+}
+
+#[test]
+fn test_check_builtin_class_methods_and_functions() {
+    let source = "def count(n: Int) -> Int\n  n\nend\n\
+                  count(File.fnmatch(\"*.mx\", 3))\n\
+                  count(Digest.sha256(\"a\"))\n\
+                  count(gets)\n";
+    assert_eq!(
+        check(source),
+        vec![
+            "4:7: argument 'n' of 'count' expects Int, found Bool",
+            "4:28: argument 'path' of 'fnmatch' expects String, found Int",
+            "5:7: argument 'n' of 'count' expects Int, found String",
+        ]
+    );
+}
+
+#[test]
+fn test_check_rest_parameters_apply_to_every_argument() {
+    let source = "pairs = [1, 2].zip([3, 4], \"x\")\n";
+    assert_eq!(
+        check(source),
+        vec!["1:28: argument 'others' of 'zip' expects Array, found String"]
+    );
+}
@@ -0,0 +1,96 @@
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+
+fn run(source: &str) -> VirtualMachine {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens).parse().expect("parse failed");
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program).expect("execution failed");
+    vm
+}
+
+fn entry(vm: &VirtualMachine, name: &str) -> Object {
+    vm.environment()
+        .get(name)
+        .unwrap_or_else(|| panic!("{} is not defined", name))
+}
+
+#[test]
+fn nested_calls_keep_their_own_arguments() {
+    let vm = run(r##"
+class Pair
+  def join(a, b)
+    "#{a}-#{b}"
+  end
+end
+p = Pair.new
+items = []
+i = 0
+while i < 3
+  items.push(p.join(i.to_s, p.join([i, i + 1].length, "x")))
+  i = i + 1
+end
+"##);
+
+    assert_eq!(
+        entry(&vm, "items"),
+        Object::array(vec![
+            Object::string("0-2-x"),
+            Object::string("1-2-x"),
+            Object::string("2-2-x"),
+        ])
+    );
+}
+
+#[test]
+fn arguments_are_not_reused_after_a_failed_native_call() {
+    let mut vm = VirtualMachine::new();
+    let tokens = Lexer::new("\"abc\".hex_decode").tokenize();
+    let program = Parser::new(tokens).parse().expect("parse failed");
+    assert!(vm.execute_program(&program).is_err());
+
+    let tokens = Lexer::new("a = \"6869\".hex_decode").tokenize();
+    let program = Parser::new(tokens).parse().expect("parse failed");
+    vm.execute_program(&program).expect("execution failed");
+
+    assert_eq!(entry(&vm, "a"), Object::string("hi"));
+}
+
+#[test]
+fn forwarded_arguments_survive_buffer_reuse() {
+    let vm = run(r##"
+def inner(a, b, c)
+  [a, b, c]
+end
+def outer(...)
+  "x".upcase
+  inner(...)
+end
+first = outer(1, 2, 3)
+second = outer(4, 5, 6)
+"##);
+
+    assert_eq!(
+        entry(&vm, "first"),
+        Object::array(vec![Object::Int(1), Object::Int(2), Object::Int(3)])
+    );
+    assert_eq!(
+        entry(&vm, "second"),
+        Object::array(vec![Object::Int(4), Object::Int(5), Object::Int(6)])
+    );
+}
+
+#[test]
+fn interpolation_writes_each_kind_of_value() {
+    let vm = run(r##"
+name = "Ada"
+s = "#{name}: #{1} #{2.5} #{nil} #{[1, "b"]} #{:sym} #{true}"
+"##);
+
+    assert_eq!(
+        entry(&vm, "s"),
+        Object::string("Ada: 1 2.5 nil [1, b] :sym true")
+    );
+}
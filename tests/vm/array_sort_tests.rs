@@ -0,0 +1,259 @@
+use crate::common::{eval, eval_error, eval_in, run};
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+
+fn strings(values: &[&str]) -> Object {
+    Object::array(values.iter().map(|value| Object::string(*value)).collect())
+}
+
+fn ints(values: &[i64]) -> Object {
+    Object::array(values.iter().map(|value| Object::Int(*value)).collect())
+}
+
+const POINT: &str = "class Point
+  def initialize(x, label)
+    @x = x
+    @label = label
+  end
+
+  def x
+    @x
+  end
+
+  def label
+    @label
+  end
+
+  def <=>(other)
+    @x <=> other.x
+  end
+end";
+
+// ============================================================================
+// The <=> operator
+// ============================================================================
+
+#[test]
+fn spaceship_orders_naturally_comparable_values() {
+    assert_eq!(eval("1 <=> 2"), Object::Int(-1));
+    assert_eq!(eval("2.5 <=> 2"), Object::Int(1));
+    assert_eq!(eval("\"a\" <=> \"a\""), Object::Int(0));
+    assert_eq!(eval("[1, 2] <=> [1, 3]"), Object::Int(-1));
+}
+
+#[test]
+fn spaceship_is_nil_for_values_without_an_ordering() {
+    assert_eq!(eval("1 <=> \"1\""), Object::Nil);
+    assert_eq!(eval("nil <=> nil"), Object::Nil);
+}
+
+#[test]
+fn spaceship_calls_a_user_defined_method() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, POINT).unwrap();
+    assert_eq!(
+        eval_in(&mut vm, "Point.new(1, \"a\") <=> Point.new(5, \"b\")"),
+        Object::Int(-1)
+    );
+}
+
+// ============================================================================
+// Array#sort
+// ============================================================================
+
+#[test]
+fn sort_orders_mixed_numbers_and_leaves_the_receiver_alone() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, "values = [3, 1.5, -2, 2]\nsorted = values.sort").unwrap();
+    assert_eq!(
+        vm.environment().get("sorted"),
+        Some(Object::array(vec![
+            Object::Int(-2),
+            Object::Float(1.5),
+            Object::Int(2),
+            Object::Int(3),
+        ]))
+    );
+    assert_eq!(
+        vm.environment().get("values"),
+        Some(Object::array(vec![
+            Object::Int(3),
+            Object::Float(1.5),
+            Object::Int(-2),
+            Object::Int(2),
+        ]))
+    );
+}
+
+#[test]
+fn sort_with_a_block_uses_its_comparison() {
+    assert_eq!(
+        eval("[\"b\", \"c\", \"a\"].sort { |a, b| b <=> a }"),
+        strings(&["c", "b", "a"])
+    );
+}
+
+#[test]
+fn sort_is_stable_for_equal_elements() {
+    // Every pair compares equal by first letter, so the order must not change
+    assert_eq!(
+        eval("[\"bx\", \"ay\", \"bz\", \"aw\", \"by\"].sort { |a, b| a.chars[0] <=> b.chars[0] }"),
+        strings(&["ay", "aw", "bx", "bz", "by"])
+    );
+}
+
+#[test]
+fn sort_falls_back_to_a_user_defined_spaceship() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, POINT).unwrap();
+    assert_eq!(
+        eval_in(
+            &mut vm,
+            "[Point.new(2, \"a\"), Point.new(1, \"b\"), Point.new(2, \"c\")].sort.map { |p| p.label }"
+        ),
+        strings(&["b", "a", "c"])
+    );
+}
+
+#[test]
+fn sort_raises_a_type_error_naming_the_pair() {
+    let error = eval_error("[1, \"a\"].sort");
+    assert!(
+        error.contains("comparison of Int with String failed"),
+        "{}",
+        error
+    );
+
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, "class Box\nend").unwrap();
+    let error = run(&mut vm, "[Box.new, 1].sort").unwrap_err();
+    assert!(
+        error.contains("comparison of Box with Int failed"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn sort_errors_can_be_rescued() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "begin
+  [2, nil].sort
+rescue TypeError => e
+  message = e.message
+end",
+    )
+    .unwrap();
+    assert_eq!(
+        vm.environment().get("message"),
+        Some(Object::string("comparison of Int with Nil failed"))
+    );
+}
+
+#[test]
+fn sort_rejects_a_block_that_does_not_return_a_number() {
+    let error = eval_error("[1, 2].sort { |a, b| true }");
+    assert!(
+        error.contains("comparison of Int with Int failed"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn sort_checks_its_arguments() {
+    let error = eval_error("[1, 2].sort(1)");
+    assert!(error.contains("sort"), "{}", error);
+}
+
+// ============================================================================
+// Array#sort_by
+// ============================================================================
+
+#[test]
+fn sort_by_orders_by_the_block_key() {
+    assert_eq!(
+        eval("[\"ccc\", \"a\", \"bb\"].sort_by { |s| s.length }"),
+        strings(&["a", "bb", "ccc"])
+    );
+}
+
+#[test]
+fn sort_by_computes_each_key_once() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "calls = 0
+sorted = [5, 3, 9, 1, 7, 2, 8, 6, 4, 0].sort_by { |n|
+  calls += 1
+  0 - n
+}",
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("calls"), Some(Object::Int(10)));
+    assert_eq!(
+        vm.environment().get("sorted"),
+        Some(ints(&[9, 8, 7, 6, 5, 4, 3, 2, 1, 0]))
+    );
+}
+
+#[test]
+fn sort_by_is_stable_for_equal_keys() {
+    assert_eq!(
+        eval("[\"bb\", \"a\", \"cc\", \"d\", \"aa\"].sort_by { |s| s.length }"),
+        strings(&["a", "d", "bb", "cc", "aa"])
+    );
+}
+
+#[test]
+fn sort_by_raises_a_type_error_for_incomparable_keys() {
+    let error = eval_error("[0, 1].sort_by { |n| [\"zero\", 1][n] }");
+    assert!(
+        error.contains("comparison of String with Int failed"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn sort_by_without_a_block_returns_an_enumerator() {
+    assert_eq!(
+        eval("[2, 1].sort_by.to_a"),
+        Object::array(vec![Object::Int(2), Object::Int(1)])
+    );
+    let error = eval_error("[1, 2].sort_by(3)");
+    assert!(error.contains("sort_by"), "{}", error);
+}
+
+#[test]
+fn large_mixed_arrays_sort_correctly() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "values = []
+i = 0
+while i < 2000
+  if i % 2 == 0
+    values.push((i * 7919) % 2003)
+  else
+    values.push(((i * 104729) % 2003) + 0.5)
+  end
+  i += 1
+end
+sorted = values.sort",
+    )
+    .unwrap();
+    let Some(Object::Array(sorted)) = vm.environment().get("sorted") else {
+        panic!("expected an Array");
+    };
+    let as_f64 = |value: &Object| match value {
+        Object::Int(n) => *n as f64,
+        Object::Float(n) => *n,
+        other => panic!("unexpected {:?}", other),
+    };
+    let sorted = sorted.borrow();
+    assert_eq!(sorted.len(), 2000);
+    assert!(sorted.windows(2).all(|w| as_f64(&w[0]) <= as_f64(&w[1])));
+}
@@ -0,0 +1,150 @@
+use crate::common::{eval, eval_error, run};
+use metorex::builtin_catalog::{self, MethodKind};
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+
+/// The message of the exception `source` raises, rescued as `class`
+fn rescued_message(source: &str, class: &str) -> Object {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        &format!(
+            "begin\n  {}\nrescue {} => e\n  message = e.message\nend",
+            source, class
+        ),
+    )
+    .unwrap();
+    vm.environment().get("message").unwrap_or(Object::Nil)
+}
+
+// ============================================================================
+// Argument types
+// ============================================================================
+
+#[test]
+fn type_errors_name_the_parameter_and_expected_type() {
+    let error = eval_error("\"abc\".center(10, 1)");
+    assert!(
+        error.contains("center: pad must be a String (got Int)"),
+        "{}",
+        error
+    );
+    let error = eval_error("\"abc\".center(\"10\")");
+    assert!(
+        error.contains("center: width must be an Int (got String)"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn type_errors_name_user_classes() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, "class Point\nend").unwrap();
+    let error = run(&mut vm, "[1].zip(Point.new)").unwrap_err();
+    assert!(
+        error.contains("zip: others must be an Array (got Point)"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn every_rest_argument_is_checked() {
+    let error = eval_error("[1].zip([2], [3], 4)");
+    assert!(
+        error.contains("zip: others must be an Array (got Int)"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn type_errors_can_be_rescued() {
+    assert_eq!(
+        rescued_message("\"abc\".ljust(nil)", "TypeError"),
+        Object::string("ljust: width must be an Int (got Nil)")
+    );
+}
+
+#[test]
+fn native_function_arguments_are_checked() {
+    assert_eq!(
+        rescued_message("sleep(\"1\")", "TypeError"),
+        Object::string("sleep: seconds must be a Numeric (got String)")
+    );
+}
+
+// ============================================================================
+// Argument counts
+// ============================================================================
+
+#[test]
+fn arity_errors_give_the_allowed_range() {
+    assert_eq!(
+        rescued_message("\"abc\".center", "ArgumentError"),
+        Object::string("center: wrong number of arguments (given 0, expected 1..2)")
+    );
+    assert_eq!(
+        rescued_message("\"abc\".center(1, \"a\", \"b\")", "ArgumentError"),
+        Object::string("center: wrong number of arguments (given 3, expected 1..2)")
+    );
+}
+
+#[test]
+fn rest_parameters_take_any_number_of_arguments() {
+    assert_eq!(
+        eval("[1].zip([2], [3], [4])"),
+        Object::array(vec![Object::array(vec![
+            Object::Int(1),
+            Object::Int(2),
+            Object::Int(3),
+            Object::Int(4),
+        ])])
+    );
+}
+
+#[test]
+fn argument_error_is_a_standard_error() {
+    assert_eq!(
+        rescued_message("GC.start(1)", "StandardError"),
+        Object::string("start: wrong number of arguments (given 1, expected 0)")
+    );
+}
+
+// ============================================================================
+// Blocks and keyword options
+// ============================================================================
+
+#[test]
+fn trailing_blocks_and_keywords_are_not_counted() {
+    assert_eq!(
+        eval("[1, \"two\", 3].grep(Integer) do |x| x * 10 end"),
+        Object::array(vec![Object::Int(10), Object::Int(30)])
+    );
+    assert_eq!(
+        eval("[[1]].to_table({:markdown => true})"),
+        Object::string("|     |\n| --: |\n|   1 |")
+    );
+}
+
+#[test]
+fn a_lambda_for_a_required_parameter_is_an_argument() {
+    assert_eq!(
+        eval("[1, nil, 2].grep(lambda do |x| x == nil end)"),
+        Object::array(vec![Object::Nil])
+    );
+}
+
+// ============================================================================
+// Signature table
+// ============================================================================
+
+#[test]
+fn checked_signatures_come_from_the_builtin_catalog() {
+    let center = builtin_catalog::find_method("String", "center", MethodKind::Instance).unwrap();
+    assert_eq!(builtin_catalog::min_arity(center.parameters), 1);
+    assert_eq!(builtin_catalog::max_arity(center.parameters), Some(2));
+    assert_eq!(center.parameters[1].name, "pad");
+    assert_eq!(center.parameters[1].ty, Some("String"));
+}
@@ -0,0 +1,99 @@
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+
+fn eval(source: &str, name: &str) -> Object {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens).parse().expect("program should parse");
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program).expect("program should run");
+    vm.environment()
+        .get(name)
+        .expect("variable should be defined")
+}
+
+fn ints(values: &[i64]) -> Object {
+    Object::array(values.iter().map(|v| Object::Int(*v)).collect())
+}
+
+#[test]
+fn class_case_equals_tests_instances_including_subclasses() {
+    let source = "class Shape\nend\nclass Circle < Shape\nend\n\
+                  a = Integer === 3\nb = Integer === \"3\"\nc = Shape === Circle.new\nd = Circle === Shape.new";
+    assert_eq!(eval(source, "a"), Object::Bool(true));
+    assert_eq!(eval(source, "b"), Object::Bool(false));
+    assert_eq!(eval(source, "c"), Object::Bool(true));
+    assert_eq!(eval(source, "d"), Object::Bool(false));
+}
+
+#[test]
+fn range_case_equals_tests_inclusion() {
+    let source = "a = (1..5) === 5\nb = (1...5) === 5\nc = (1..5) === 2.5\nd = (1..5) === \"x\"";
+    assert_eq!(eval(source, "a"), Object::Bool(true));
+    assert_eq!(eval(source, "b"), Object::Bool(false));
+    assert_eq!(eval(source, "c"), Object::Bool(true));
+    assert_eq!(eval(source, "d"), Object::Bool(false));
+}
+
+#[test]
+fn lambda_case_equals_calls_the_lambda() {
+    let source = "even = lambda do |x| x % 2 == 0 end\na = even === 4\nb = even === 3";
+    assert_eq!(eval(source, "a"), Object::Bool(true));
+    assert_eq!(eval(source, "b"), Object::Bool(false));
+}
+
+#[test]
+fn other_values_case_equal_when_equal() {
+    let source = "a = 3 === 3\nb = \"x\" === \"y\"";
+    assert_eq!(eval(source, "a"), Object::Bool(true));
+    assert_eq!(eval(source, "b"), Object::Bool(false));
+}
+
+#[test]
+fn case_when_matches_subclass_instances() {
+    let source = "class Shape\nend\nclass Circle < Shape\nend\n\
+                  def kind(v)\n  case v\n  when Integer\n    \"int\"\n  when Shape\n    \"shape\"\n  else\n    \"other\"\n  end\nend\n\
+                  a = kind(Circle.new)\nb = kind(1)\nc = kind(\"s\")";
+    assert_eq!(eval(source, "a"), Object::string("shape"));
+    assert_eq!(eval(source, "b"), Object::string("int"));
+    assert_eq!(eval(source, "c"), Object::string("other"));
+}
+
+#[test]
+fn case_when_tests_constants_bound_to_ranges_and_lambdas() {
+    let source = "SMALL = 1..9\nNEGATIVE = lambda do |x| x < 0 end\n\
+                  def size(v)\n  case v\n  when SMALL\n    \"small\"\n  when NEGATIVE\n    \"negative\"\n  else\n    \"large\"\n  end\nend\n\
+                  a = size(4)\nb = size(-2)\nc = size(40)";
+    assert_eq!(eval(source, "a"), Object::string("small"));
+    assert_eq!(eval(source, "b"), Object::string("negative"));
+    assert_eq!(eval(source, "c"), Object::string("large"));
+}
+
+#[test]
+fn grep_selects_elements_matched_by_case_equality() {
+    let source = "list = [1, \"two\", 3.5, 7, nil, 12]\n\
+                  a = list.grep(Integer)\nb = list.grep(1..8)\n\
+                  c = list.grep(lambda do |x| x == nil end)";
+    assert_eq!(eval(source, "a"), ints(&[1, 7, 12]));
+    assert_eq!(
+        eval(source, "b"),
+        Object::array(vec![Object::Int(1), Object::Float(3.5), Object::Int(7)])
+    );
+    assert_eq!(eval(source, "c"), Object::array(vec![Object::Nil]));
+}
+
+#[test]
+fn grep_maps_matches_through_a_block() {
+    let source = "a = [1, \"two\", 3].grep(Integer) do |x| x * 10 end";
+    assert_eq!(eval(source, "a"), ints(&[10, 30]));
+}
+
+#[test]
+fn rescue_matches_through_case_equality() {
+    let source = "IS_BAD = lambda do |e| e.message == \"bad\" end\n\
+                  begin\n  raise TypeError, \"bad\"\nrescue IS_BAD => e\n  a = \"lambda\"\nend\n\
+                  begin\n  raise TypeError, \"bad\"\nrescue StandardError\n  b = \"class\"\nend";
+    assert_eq!(eval(source, "a"), Object::string("lambda"));
+    assert_eq!(eval(source, "b"), Object::string("class"));
+}
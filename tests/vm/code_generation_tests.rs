@@ -0,0 +1,275 @@
+use crate::common::{eval, run};
+use metorex::lexer::{Lexer, TokenKind};
+use metorex::literal;
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+
+fn eval_text(source: &str) -> String {
+    eval(source).to_string()
+}
+
+/// Evaluate `source`, then evaluate the literal `to_literal` gives for it,
+/// returning both values.
+fn round_trip(source: &str) -> (Object, Object) {
+    let literal = eval_text(&format!("({}).to_literal", source));
+    (eval(source), eval(&literal))
+}
+
+// ============================================================================
+// Quoting
+// ============================================================================
+
+#[test]
+fn quote_escapes_what_the_lexer_would_read_differently() {
+    assert_eq!(literal::quote("plain"), r#""plain""#);
+    assert_eq!(
+        literal::quote("say \"hi\"\n\tback\\slash\r"),
+        r#""say \"hi\"\n\tback\\slash\r""#
+    );
+    assert_eq!(literal::quote("#{name} #tag"), r##""\#{name} #tag""##);
+    assert_eq!(literal::quote("it's ünïcode"), r#""it's ünïcode""#);
+}
+
+#[test]
+fn quoted_strings_lex_back_to_the_same_text() {
+    for text in [
+        "",
+        "a\"b",
+        "\\n is not a newline",
+        "#{x}",
+        "#",
+        "tab\there\n",
+    ] {
+        let tokens = Lexer::new(&literal::quote(text)).tokenize();
+        assert_eq!(
+            tokens[0].kind,
+            TokenKind::String(text.to_string()),
+            "{:?}",
+            text
+        );
+    }
+}
+
+#[test]
+fn string_to_source_quotes_the_receiver() {
+    assert_eq!(
+        eval(r#""a \"quoted\" \#{word}".to_source"#),
+        Object::string(r#""a \"quoted\" \#{word}""#)
+    );
+}
+
+// ============================================================================
+// Literals
+// ============================================================================
+
+#[test]
+fn scalars_have_literals() {
+    assert_eq!(eval_text("nil.to_literal"), "nil");
+    assert_eq!(eval_text("false.to_literal"), "false");
+    assert_eq!(eval_text("(-42).to_literal"), "-42");
+    assert_eq!(eval_text("2.0.to_literal"), "2.0");
+    assert_eq!(eval_text("0.1.to_literal"), "0.1");
+    assert_eq!(eval_text(":valid?.to_literal"), ":valid?");
+    assert_eq!(
+        eval_text(r#"Decimal.new("1.50").to_literal"#),
+        r#"Decimal.new("1.50")"#
+    );
+    assert_eq!(
+        literal::to_literal(&Object::Float(1e20)).unwrap(),
+        "100000000000000000000.0"
+    );
+}
+
+#[test]
+fn nested_collections_have_literals() {
+    assert_eq!(
+        eval_text(r#"[1, [2.5, "x"], 1...4].to_literal"#),
+        r#"[1, [2.5, "x"], 1...4]"#
+    );
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r##"
+h = {}
+h[:name] = "ann"
+h["full name"] = "Ann Lee"
+h[3] = [:a]
+result = h.to_literal
+"##,
+    )
+    .unwrap();
+    assert_eq!(
+        vm.environment().get("result"),
+        Some(Object::string(
+            r#"{:name => "ann", "full name" => "Ann Lee", "3" => [:a]}"#
+        ))
+    );
+}
+
+#[test]
+fn literals_evaluate_back_to_equal_values() {
+    for source in [
+        r#"[nil, true, -7, 3.25, "line\nbreak \#{not} interpolated", :sym]"#,
+        r#"{"a" => [1, {"b" => 2.0}], "c" => 1..2}"#,
+        r#"Decimal.new("-0.010")"#,
+    ] {
+        let (original, read_back) = round_trip(source);
+        assert_eq!(original, read_back, "{}", source);
+    }
+    assert_eq!(
+        literal::to_literal(&Object::Int(i64::MIN)).map(|source| eval(&source)),
+        Ok(Object::Int(i64::MIN))
+    );
+}
+
+#[test]
+fn values_without_a_literal_raise_type_error() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r##"
+class Point
+end
+messages = []
+items = [1]
+items.push(items)
+for value in [Point.new, [1, Point.new], items]
+  begin
+    value.to_literal
+  rescue TypeError => e
+    messages.push(e.message)
+  end
+end
+"##,
+    )
+    .unwrap();
+    assert_eq!(
+        vm.environment().get("messages"),
+        Some(Object::array(vec![
+            Object::string("to_literal: Point instance has no literal form"),
+            Object::string("to_literal: Point instance has no literal form"),
+            Object::string("to_literal: [...] contains itself and has no literal form"),
+        ]))
+    );
+    assert_eq!(
+        literal::to_literal(&Object::Float(f64::NAN)),
+        Err("Float NaN has no literal form".to_string())
+    );
+}
+
+// ============================================================================
+// CodeBuilder
+// ============================================================================
+
+#[test]
+fn code_builder_indents_nested_blocks() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r##"
+code = CodeBuilder.new
+code.line("# generated")
+code.block("class Point") do |c|
+  c.block("def initialize(x)") do |c|
+    c.line("@x = " + 5.to_literal)
+  end
+  c.line
+  c.block("def label") do |c|
+    c.line("text = " + "(x)".to_source + "\ntext")
+  end
+end
+result = code.to_s
+"##,
+    )
+    .unwrap();
+    assert_eq!(
+        vm.environment().get("result"),
+        Some(Object::string(
+            "# generated\nclass Point\n  def initialize(x)\n    @x = 5\n  end\n\n  def label\n    text = \"(x)\"\n    text\n  end\nend\n"
+        ))
+    );
+}
+
+#[test]
+fn code_builder_output_parses_and_runs() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r##"
+code = CodeBuilder.new("    ")
+code.block("def greeting(name)") do |c|
+  c.line("\"Hello, \\\"\" + name + \"\\\"\"")
+end
+code.line("result = greeting(" + "ann #{1}".to_source + ")")
+source = code.to_s
+"##,
+    )
+    .unwrap();
+    let Some(Object::String(source)) = vm.environment().get("source") else {
+        panic!("expected the generated source");
+    };
+    assert!(source.contains("\n    \"Hello, "), "{}", source);
+
+    let mut generated = VirtualMachine::new();
+    run(&mut generated, &source).unwrap();
+    assert_eq!(
+        generated.environment().get("result"),
+        Some(Object::string("Hello, \"ann 1\""))
+    );
+}
+
+#[test]
+fn indent_and_dedent_track_the_depth() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r##"
+code = CodeBuilder.new("\t")
+code.indent
+code.line("a")
+depth = code.depth
+code.indent do |c|
+  c.line("b")
+end
+code.dedent
+code.line("c")
+result = code.to_s
+"##,
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("depth"), Some(Object::Int(1)));
+    assert_eq!(
+        vm.environment().get("result"),
+        Some(Object::string("\ta\n\t\tb\nc\n"))
+    );
+}
+
+#[test]
+fn the_depth_is_restored_when_a_block_raises() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r##"
+code = CodeBuilder.new
+begin
+  code.block("if ready") do |c|
+    raise "stop"
+  end
+rescue RuntimeError
+end
+depth = code.depth
+message = nil
+begin
+  code.dedent
+rescue ArgumentError => e
+  message = e.message
+end
+"##,
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("depth"), Some(Object::Int(0)));
+    assert_eq!(
+        vm.environment().get("message"),
+        Some(Object::string("dedent: already at depth 0"))
+    );
+}
@@ -0,0 +1,200 @@
+use crate::common::run;
+use metorex::object::Object;
+use metorex::vm::{VirtualMachine, VmOptions};
+
+fn without_contracts() -> VirtualMachine {
+    VirtualMachine::with_options(VmOptions {
+        contracts: false,
+        ..VmOptions::default()
+    })
+}
+
+const ACCOUNT: &str = r#"
+class Account
+  def initialize(balance)
+    @balance = balance
+  end
+
+  pre { |amount| amount > 0 }
+  pre { |amount| amount <= @balance }
+  post { |result, amount| result >= 0 }
+  def withdraw(amount)
+    @balance = @balance - amount
+    @balance
+  end
+
+  post { |result| result.length > 0 }
+  def label
+    ""
+  end
+end
+
+account = Account.new(10)
+"#;
+
+/// Run `source` after the Account class and return the message of the
+/// AssertionError it raises, or nil
+fn contract_failure(vm: &mut VirtualMachine, source: &str) -> Option<Object> {
+    run(
+        vm,
+        &format!(
+            "{}message = nil\nbegin\n  {}\nrescue AssertionError => e\n  message = e.message\nend\n",
+            ACCOUNT, source
+        ),
+    )
+    .unwrap();
+    vm.environment().get("message")
+}
+
+// ============================================================================
+// assert and raise_unless
+// ============================================================================
+
+#[test]
+fn assert_raises_assertion_error_with_the_message() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"
+assert(1 < 2, "math works")
+messages = []
+begin
+  assert(nil, "value was nil")
+rescue AssertionError => e
+  messages.push(e.message)
+end
+begin
+  assert(false)
+rescue StandardError => e
+  messages.push(e.message)
+end
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        vm.environment().get("messages"),
+        Some(Object::array(vec![
+            Object::string("value was nil"),
+            Object::string("assertion failed")
+        ]))
+    );
+    assert_eq!(vm.assertion_count(), 3);
+}
+
+#[test]
+fn uncaught_assertions_report_their_location() {
+    let mut vm = VirtualMachine::new();
+    let error = run(&mut vm, "x = 1\nassert(x == 2, \"x should be 2\")\n").unwrap_err();
+    assert!(
+        error.contains("2:1") && error.contains("AssertionError: x should be 2"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn raise_unless_raises_runtime_error_or_the_given_class() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"
+raise_unless(true, "never raised")
+plain = nil
+begin
+  raise_unless(false, "plain")
+rescue RuntimeError => e
+  plain = e.message
+end
+typed = nil
+begin
+  raise_unless(nil, ArgumentError, "count must be positive")
+rescue ArgumentError => e
+  typed = e.message
+end
+"#,
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("plain"), Some(Object::string("plain")));
+    assert_eq!(
+        vm.environment().get("typed"),
+        Some(Object::string("count must be positive"))
+    );
+}
+
+#[test]
+fn raise_unless_rejects_a_missing_message() {
+    let mut vm = VirtualMachine::new();
+    let error = run(&mut vm, "raise_unless(false, 42)").unwrap_err();
+    assert!(
+        error.contains("raise_unless expects a condition and a message"),
+        "{}",
+        error
+    );
+}
+
+// ============================================================================
+// Method contracts
+// ============================================================================
+
+#[test]
+fn methods_run_when_their_conditions_hold() {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(
+        contract_failure(&mut vm, "left = account.withdraw(4)"),
+        Some(Object::Nil)
+    );
+    assert_eq!(vm.environment().get("left"), Some(Object::Int(6)));
+}
+
+#[test]
+fn preconditions_check_arguments_and_instance_variables() {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(
+        contract_failure(&mut vm, "account.withdraw(-1)"),
+        Some(Object::string("precondition of 'Account#withdraw' failed"))
+    );
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(
+        contract_failure(&mut vm, "account.withdraw(11)"),
+        Some(Object::string("precondition of 'Account#withdraw' failed"))
+    );
+}
+
+#[test]
+fn postconditions_check_the_result() {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(
+        contract_failure(&mut vm, "account.label"),
+        Some(Object::string("postcondition of 'Account#label' failed"))
+    );
+}
+
+#[test]
+fn contracts_off_skips_asserts_and_conditions() {
+    let mut vm = without_contracts();
+    assert_eq!(
+        contract_failure(&mut vm, "assert(false)\n  left = account.withdraw(-5)"),
+        Some(Object::Nil)
+    );
+    assert_eq!(vm.environment().get("left"), Some(Object::Int(15)));
+    assert_eq!(vm.assertion_count(), 0);
+
+    // raise_unless guards input, so it still checks
+    let error = run(&mut vm, "raise_unless(false, \"bad input\")").unwrap_err();
+    assert!(error.contains("RuntimeError: bad input"), "{}", error);
+}
+
+#[test]
+fn conditions_must_be_blocks_before_a_method() {
+    let mut vm = VirtualMachine::new();
+    let error = run(&mut vm, "class A\n  def f\n  end\n  pre { |x| x }\nend\n").unwrap_err();
+    assert!(
+        error.contains("'pre' must be followed by a method definition"),
+        "{}",
+        error
+    );
+
+    let error = run(&mut vm, "class B\n  post(1)\n  def f\n  end\nend\n").unwrap_err();
+    assert!(error.contains("'post' expects only a block"), "{}", error);
+}
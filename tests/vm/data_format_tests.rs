@@ -0,0 +1,110 @@
+use crate::common::eval_error;
+#[cfg(any(feature = "yaml", feature = "toml"))]
+use metorex::object::Object;
+
+#[cfg(any(feature = "yaml", feature = "toml"))]
+#[cfg(any(feature = "yaml", feature = "toml"))]
+fn entry(hash: &Object, key: &str) -> Object {
+    let Object::Dict(entries) = hash else {
+        panic!("expected a Hash, got {:?}", hash);
+    };
+    entries.borrow().get(key).cloned().unwrap_or(Object::Nil)
+}
+
+#[test]
+fn arguments_are_checked_with_or_without_features() {
+    assert!(eval_error("YAML.parse").contains("argument"));
+    assert!(eval_error("TOML.parse(1)").contains("String"));
+}
+
+// ============================================================================
+// YAML
+// ============================================================================
+
+#[cfg(not(feature = "yaml"))]
+#[test]
+fn yaml_reports_missing_feature() {
+    let err = eval_error(r#"YAML.parse("a: 1")"#);
+    assert!(err.contains("built without the yaml feature"), "{}", err);
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn yaml_parse_maps_to_hashes_arrays_and_scalars() {
+    let config = eval(
+        r#"YAML.parse("name: app\nport: 8080\nratio: 0.5\ndebug: true\nhosts: [a, b]\nempty: ~\n1: one")"#,
+    );
+    assert_eq!(entry(&config, "name"), Object::string("app"));
+    assert_eq!(entry(&config, "port"), Object::Int(8080));
+    assert_eq!(entry(&config, "ratio"), Object::Float(0.5));
+    assert_eq!(entry(&config, "debug"), Object::Bool(true));
+    assert_eq!(
+        entry(&config, "hosts"),
+        Object::array(vec![Object::string("a"), Object::string("b")])
+    );
+    assert_eq!(entry(&config, "empty"), Object::Nil);
+    assert_eq!(entry(&config, "1"), Object::string("one"));
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn yaml_dump_round_trips_through_parse() {
+    assert_eq!(
+        eval(r#"YAML.dump(YAML.parse("b: [1, x]\na: {c: 2.5}"))"#),
+        Object::string("b:\n- 1\n- x\na:\n  c: 2.5\n")
+    );
+    assert_eq!(
+        eval("YAML.dump(retries: 3, verbose: false)"),
+        Object::string("retries: 3\nverbose: false\n")
+    );
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn yaml_reports_invalid_documents_and_values() {
+    assert!(eval_error(r#"YAML.parse("a: [1")"#).contains("YAML.parse"));
+    assert!(eval_error(r#"YAML.parse("[1]: x")"#).contains("keys must be scalars"));
+    assert!(eval_error("a = [1]\na.push(a)\nYAML.dump(a)").contains("recursive"));
+    assert!(eval_error("YAML.dump(1..3)").contains("cannot dump"));
+}
+
+// ============================================================================
+// TOML
+// ============================================================================
+
+#[cfg(not(feature = "toml"))]
+#[test]
+fn toml_reports_missing_feature() {
+    let err = eval_error(r#"TOML.parse("a = 1")"#);
+    assert!(err.contains("built without the toml feature"), "{}", err);
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn toml_parse_keeps_tables_in_document_order() {
+    let config = eval(
+        r#"TOML.parse("title = \"x\"\n[server]\nport = 80\nstarted = 1979-05-27T07:32:00Z\nweights = [1, 2.5]")"#,
+    );
+    assert_eq!(entry(&config, "title"), Object::string("x"));
+    let server = entry(&config, "server");
+    assert_eq!(entry(&server, "port"), Object::Int(80));
+    assert_eq!(
+        entry(&server, "started"),
+        Object::string("1979-05-27T07:32:00Z")
+    );
+    assert_eq!(
+        entry(&server, "weights"),
+        Object::array(vec![Object::Int(1), Object::Float(2.5)])
+    );
+    let Object::Dict(entries) = config else {
+        unreachable!()
+    };
+    let keys: Vec<String> = entries.borrow().keys().cloned().collect();
+    assert_eq!(keys, ["title", "server"]);
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn toml_reports_invalid_documents() {
+    assert!(eval_error(r#"TOML.parse("a = ")"#).contains("TOML.parse"));
+}
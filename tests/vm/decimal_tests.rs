@@ -0,0 +1,222 @@
+use crate::common::{eval, eval_error, run};
+use metorex::number_format::{NumberFormat, format_decimal};
+use metorex::object::{Decimal, Object, RoundingStrategy, from_object};
+use metorex::vm::VirtualMachine;
+use std::str::FromStr;
+
+fn eval_text(source: &str) -> String {
+    eval(source).to_string()
+}
+
+fn decimal(text: &str) -> Object {
+    Object::Decimal(Decimal::from_str(text).unwrap())
+}
+
+// ============================================================================
+// Construction and conversion
+// ============================================================================
+
+#[test]
+fn decimal_new_parses_strings_exactly_and_keeps_the_scale() {
+    assert_eq!(eval_text(r#"Decimal.new("12.50")"#), "12.50");
+    assert_eq!(eval_text(r#"Decimal.new(" -0.001 ")"#), "-0.001");
+    assert_eq!(eval_text(r#"Decimal.new("1.5e3")"#), "1500");
+    assert_eq!(eval_text("Decimal.new(7)"), "7");
+    assert_eq!(eval_text("Decimal.new(0.1)"), "0.1");
+    assert_eq!(eval_text(r#"BigDecimal.new("2.5")"#), "2.5");
+    assert_eq!(
+        eval(r#"Decimal.new("2.5").class == BigDecimal"#),
+        Object::Bool(true)
+    );
+}
+
+#[test]
+fn decimal_new_raises_argument_error_for_text_that_is_not_a_number() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"
+message = nil
+begin
+  Decimal.new("12,50")
+rescue ArgumentError => e
+  message = e.message
+end
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        vm.environment().get("message"),
+        Some(Object::string("invalid value for Decimal: \"12,50\""))
+    );
+}
+
+#[test]
+fn numbers_and_strings_convert_with_to_d() {
+    assert_eq!(eval("5.to_d"), decimal("5"));
+    assert_eq!(eval("0.1.to_d"), decimal("0.1"));
+    assert_eq!(eval(r#""19.99 USD".to_d"#), decimal("19.99"));
+    assert_eq!(eval(r#""-3.".to_d"#), decimal("-3"));
+    assert_eq!(eval(r#""abc".to_d"#), decimal("0"));
+}
+
+#[test]
+fn decimals_convert_to_ints_and_floats() {
+    assert_eq!(eval(r#"Decimal.new("-7.9").to_i"#), Object::Int(-7));
+    assert_eq!(eval(r#"Decimal.new("2.25").to_f"#), Object::Float(2.25));
+    assert_eq!(eval(r#"Decimal.new("3.50").scale"#), Object::Int(2));
+    assert_eq!(eval(r#"Decimal.new("-3.50").abs"#), decimal("3.50"));
+}
+
+// ============================================================================
+// Arithmetic and comparison
+// ============================================================================
+
+#[test]
+fn decimal_arithmetic_is_exact() {
+    assert_eq!(
+        eval(r#"Decimal.new("0.1") + Decimal.new("0.2") == Decimal.new("0.3")"#),
+        Object::Bool(true)
+    );
+    assert_eq!(eval_text(r#"Decimal.new("19.99") * 3"#), "59.97");
+    assert_eq!(eval_text(r#"10 - Decimal.new("0.01")"#), "9.99");
+    assert_eq!(eval_text(r#"Decimal.new("7.5") % 2"#), "1.5");
+    assert_eq!(
+        eval_text(r#"Decimal.new("1") / 3"#),
+        "0.3333333333333333333333333333"
+    );
+    assert_eq!(eval_text(r#"-Decimal.new("4.20")"#), "-4.20");
+}
+
+#[test]
+fn decimal_arithmetic_rejects_floats_and_zero_divisors() {
+    let error = eval_error(r#"Decimal.new("1.5") + 1.5"#);
+    assert!(error.contains("'Decimal' and 'Float'"), "{}", error);
+
+    let error = eval_error(r#"Decimal.new("1.5") / 0"#);
+    assert!(error.contains("Division by zero"), "{}", error);
+}
+
+#[test]
+fn decimals_compare_and_sort_with_ints() {
+    assert_eq!(eval(r#"Decimal.new("2.00") == 2"#), Object::Bool(true));
+    assert_eq!(
+        eval(r#"Decimal.new("1.50") == Decimal.new("1.5")"#),
+        Object::Bool(true)
+    );
+    assert_eq!(eval(r#"Decimal.new("9.99") < 10"#), Object::Bool(true));
+    assert_eq!(
+        eval(r#"[Decimal.new("3"), 1, Decimal.new("2.5")].sort"#),
+        Object::array(vec![Object::Int(1), decimal("2.5"), decimal("3")])
+    );
+}
+
+#[test]
+fn equal_decimals_find_the_same_hash_key() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"
+prices = {}
+prices[Decimal.new("1.50")] = "small"
+found = prices.key?(Decimal.new("1.5"))
+"#,
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("found"), Some(Object::Bool(true)));
+}
+
+// ============================================================================
+// Rounding
+// ============================================================================
+
+#[test]
+fn round_uses_the_named_mode() {
+    assert_eq!(eval_text(r#"Decimal.new("2.675").round(2)"#), "2.68");
+    assert_eq!(
+        eval_text(r#"Decimal.new("2.665").round(2, :half_even)"#),
+        "2.66"
+    );
+    assert_eq!(
+        eval_text(r#"Decimal.new("2.665").round(2, :half_down)"#),
+        "2.66"
+    );
+    assert_eq!(eval_text(r#"Decimal.new("-2.5").round(0, :up)"#), "-3");
+    assert_eq!(eval_text(r#"Decimal.new("-2.5").round(0, :down)"#), "-2");
+    assert_eq!(eval_text(r#"Decimal.new("2.1").round(0, :ceiling)"#), "3");
+    assert_eq!(eval_text(r#"Decimal.new("-2.1").floor"#), "-3");
+    assert_eq!(eval_text(r#"Decimal.new("2.19").ceil(1)"#), "2.2");
+    assert_eq!(eval_text(r#"Decimal.new("-2.19").truncate(1)"#), "-2.1");
+}
+
+#[test]
+fn rounding_mode_sets_the_default_and_returns_the_old_one() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"
+before = Decimal.rounding_mode
+replaced = Decimal.rounding_mode(:half_even)
+rounded = Decimal.new("0.125").round(2)
+"#,
+    )
+    .unwrap();
+    let half_up = vm.symbol("half_up");
+    assert_eq!(vm.environment().get("before"), Some(half_up.clone()));
+    assert_eq!(vm.environment().get("replaced"), Some(half_up));
+    assert_eq!(vm.environment().get("rounded"), Some(decimal("0.12")));
+    assert_eq!(vm.decimal_rounding(), RoundingStrategy::MidpointNearestEven);
+}
+
+#[test]
+fn round_rejects_unknown_modes_and_bad_places() {
+    let error = eval_error(r#"Decimal.new("1.5").round(0, :sideways)"#);
+    assert!(
+        error.contains("unknown rounding mode :sideways"),
+        "{}",
+        error
+    );
+
+    let error = eval_error(r#"Decimal.new("1.5").round(-1)"#);
+    assert!(
+        error.contains("places must be an Int from 0 to 28"),
+        "{}",
+        error
+    );
+}
+
+// ============================================================================
+// Formatting and interop
+// ============================================================================
+
+#[test]
+fn decimal_format_groups_and_rounds_by_the_default_mode() {
+    assert_eq!(
+        eval(r#"Decimal.new("1234567.895").format"#),
+        Object::string("1,234,567.90")
+    );
+    assert_eq!(
+        eval(r#"Decimal.new("1234.5").format(delimiter: ".", separator: ",", precision: 3)"#),
+        Object::string("1.234,500")
+    );
+    assert_eq!(
+        eval(r#"Decimal.new("-0.001").format"#),
+        Object::string("0.00")
+    );
+}
+
+#[test]
+fn format_decimal_pads_without_rounding() {
+    let format = NumberFormat::with_precision(4);
+    let value = Decimal::from_str("-12345.5").unwrap();
+    assert_eq!(format_decimal(value, &format), "-12,345.5000");
+}
+
+#[test]
+fn decimals_serialize_as_exact_text() {
+    let value: serde_json::Value = from_object(&Object::array(vec![decimal("19.90")])).unwrap();
+    assert_eq!(value, serde_json::json!(["19.90"]));
+
+    let value: Decimal = from_object(&decimal("19.90")).unwrap();
+    assert_eq!(value.to_string(), "19.90");
+}
@@ -0,0 +1,99 @@
+use metorex::lexer::Lexer;
+use metorex::parser::Parser;
+use metorex::vm::{DefinitionDiff, VirtualMachine};
+
+fn execute(vm: &mut VirtualMachine, source: &str) {
+    let program = Parser::new(Lexer::new(source).tokenize()).parse().unwrap();
+    vm.execute_program(&program).unwrap();
+}
+
+/// What running `source` in `vm` defined or assigned
+fn changes(vm: &mut VirtualMachine, source: &str) -> DefinitionDiff {
+    let checkpoint = vm.definitions_checkpoint();
+    execute(vm, source);
+    vm.methods_added_since(&checkpoint)
+}
+
+// ============================================================================
+// Definitions
+// ============================================================================
+
+#[test]
+fn lists_new_classes_methods_functions_and_variables() {
+    let mut vm = VirtualMachine::new();
+    let diff = changes(
+        &mut vm,
+        "class Shape\n  def area\n    0\n  end\nend\nclass Square < Shape\n  def side\n    1\n  end\nend\ndef helper\n  1\nend\ncount = 2",
+    );
+
+    assert_eq!(diff.classes, vec!["Shape", "Square"]);
+    assert_eq!(diff.methods, vec!["Shape#area", "Square#side"]);
+    assert_eq!(diff.functions, vec!["helper"]);
+    assert_eq!(diff.variables, vec!["count"]);
+    assert_eq!(
+        diff.to_string(),
+        "defined: Shape, Shape#area, Square, Square#side, helper; assigned: count"
+    );
+}
+
+#[test]
+fn a_fresh_vm_changes_nothing_without_a_program() {
+    let mut vm = VirtualMachine::new();
+    let diff = changes(&mut vm, "");
+
+    assert!(diff.is_empty());
+    assert_eq!(diff.to_string(), "");
+}
+
+#[test]
+fn unchanged_bindings_are_not_listed_again() {
+    let mut vm = VirtualMachine::new();
+    execute(&mut vm, "def helper\n  1\nend\nlimit = 3");
+
+    assert!(
+        changes(&mut vm, "limit = 3\ntotal = helper()\ntotal = nil").variables == vec!["total"]
+    );
+    assert!(changes(&mut vm, "limit = 3").is_empty());
+}
+
+#[test]
+fn redefinitions_are_listed() {
+    let mut vm = VirtualMachine::new();
+    execute(&mut vm, "def helper\n  1\nend\nlimit = 3");
+
+    let diff = changes(&mut vm, "def helper\n  2\nend\nlimit = 4");
+    assert_eq!(diff.functions, vec!["helper"]);
+    assert_eq!(diff.variables, vec!["limit"]);
+}
+
+#[test]
+fn modules_are_listed_with_their_methods() {
+    let mut vm = VirtualMachine::new();
+    let diff = changes(&mut vm, "module Greeting\n  def greet\n    1\n  end\nend");
+
+    assert_eq!(diff.to_string(), "defined: Greeting, Greeting#greet");
+    assert!(changes(&mut vm, "x = 1").variables == vec!["x"]);
+}
+
+// ============================================================================
+// Leak detection
+// ============================================================================
+
+#[test]
+fn detects_state_a_test_file_leaks_into_the_next() {
+    let mut vm = VirtualMachine::new();
+    let before_files = vm.definitions_checkpoint();
+
+    let first = changes(
+        &mut vm,
+        "def check\n  true\nend\ncache = []\nclass Fixture\nend",
+    );
+    let second = changes(&mut vm, "result = check()");
+
+    assert_eq!(first.definitions(), vec!["Fixture", "check"]);
+    assert_eq!(first.variables, vec!["cache"]);
+    assert_eq!(second.variables, vec!["result"]);
+    let leaked = vm.methods_added_since(&before_files);
+    assert!(!leaked.is_empty());
+    assert_eq!(leaked.definitions(), vec!["Fixture", "check"]);
+}
@@ -0,0 +1,73 @@
+use crate::common::{eval, eval_error};
+use metorex::duration::{ago_in_words, format_duration};
+use metorex::object::Object;
+use std::time::{Duration, Instant};
+
+// ============================================================================
+// Durations
+// ============================================================================
+
+#[test]
+fn durations_list_their_non_zero_parts() {
+    assert_eq!(format_duration(3725.0), "1h 2m 5s");
+    assert_eq!(format_duration(86_400.0), "1d");
+    assert_eq!(format_duration(90_061.5), "1d 1h 1m 1s 500ms");
+    assert_eq!(format_duration(0.25), "250ms");
+    assert_eq!(format_duration(0.0), "0s");
+    assert_eq!(format_duration(0.0001), "0s");
+    assert_eq!(format_duration(-90.0), "-1m 30s");
+}
+
+#[test]
+fn numbers_convert_to_durations() {
+    assert_eq!(eval("3725.to_duration"), Object::string("1h 2m 5s"));
+    assert_eq!(eval("1.5.to_duration"), Object::string("1s 500ms"));
+    assert!(eval_error("1.to_duration(2)").contains("to_duration"));
+}
+
+// ============================================================================
+// Time ago in words
+// ============================================================================
+
+#[test]
+fn elapsed_seconds_read_as_rough_words() {
+    assert_eq!(ago_in_words(10.0), "less than a minute ago");
+    assert_eq!(ago_in_words(75.0), "1 minute ago");
+    assert_eq!(ago_in_words(300.0), "5 minutes ago");
+    assert_eq!(ago_in_words(3600.0), "about 1 hour ago");
+    assert_eq!(ago_in_words(5.0 * 3600.0), "about 5 hours ago");
+    assert_eq!(ago_in_words(30.0 * 3600.0), "1 day ago");
+    assert_eq!(ago_in_words(3.0 * 86_400.0), "3 days ago");
+    assert_eq!(ago_in_words(40.0 * 86_400.0), "about 1 month ago");
+    assert_eq!(ago_in_words(100.0 * 86_400.0), "3 months ago");
+    assert_eq!(ago_in_words(400.0 * 86_400.0), "about 1 year ago");
+    assert_eq!(ago_in_words(3.0 * 365.0 * 86_400.0), "about 3 years ago");
+}
+
+#[test]
+fn negative_spans_are_in_the_future() {
+    assert_eq!(ago_in_words(-7200.0), "in about 2 hours");
+    assert_eq!(
+        eval("(0 - 300).ago_in_words"),
+        Object::string("in 5 minutes")
+    );
+    assert_eq!(eval("90.0.ago_in_words"), Object::string("2 minutes ago"));
+}
+
+// ============================================================================
+// sleep
+// ============================================================================
+
+#[test]
+fn sleep_pauses_for_fractional_seconds() {
+    let started = Instant::now();
+    assert_eq!(eval("sleep(0.05)"), Object::Int(0));
+    assert!(started.elapsed() >= Duration::from_millis(50));
+    assert_eq!(eval("sleep(0)"), Object::Int(0));
+}
+
+#[test]
+fn sleep_rejects_negative_and_non_numeric_durations() {
+    assert!(eval_error("sleep(0 - 1)").contains("sleep() cannot pause for -1 seconds"));
+    assert!(eval_error("sleep(\"1\")").contains("sleep: seconds must be a Numeric (got String)"));
+}
@@ -0,0 +1,120 @@
+use metorex::ast::Statement;
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+
+fn parse(source: &str) -> Vec<Statement> {
+    let tokens = Lexer::new(source).tokenize();
+    Parser::new(tokens).parse().expect("program should parse")
+}
+
+#[test]
+fn locals_are_visible_to_the_program() {
+    let mut vm = VirtualMachine::new();
+    let program = parse("greeting = \"Hello, \" + name\ngreeting");
+    let result = vm
+        .execute_program_with_locals(&program, &[("name", Object::string("Ada"))])
+        .unwrap();
+    assert_eq!(result, Some(Object::string("Hello, Ada")));
+}
+
+#[test]
+fn locals_are_not_interpreted_as_source() {
+    let mut vm = VirtualMachine::new();
+    let program = parse("input.length");
+    let result = vm
+        .execute_program_with_locals(&program, &[("input", Object::string("\"; raise \"x"))])
+        .unwrap();
+    assert_eq!(result, Some(Object::Int(11)));
+}
+
+#[test]
+fn locals_can_be_reassigned_and_stay_defined() {
+    let mut vm = VirtualMachine::new();
+    let program = parse("count = count + 1");
+    vm.execute_program_with_locals(
+        &program,
+        &[("count", Object::Int(41)), ("unused", Object::Nil)],
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("count"), Some(Object::Int(42)));
+    assert_eq!(vm.environment().get("unused"), Some(Object::Nil));
+}
+
+#[test]
+fn globals_are_visible_across_programs() {
+    let mut vm = VirtualMachine::new();
+    let config = Object::dict(
+        [("mode".to_string(), Object::string("fast"))]
+            .into_iter()
+            .collect(),
+    );
+    vm.set_global("config", config.clone());
+    assert_eq!(vm.globals().get("config"), Some(config));
+
+    let first = vm.execute_program(&parse("config[\"mode\"]")).unwrap();
+    let second = vm
+        .execute_program(&parse("config[\"mode\"] + \"!\""))
+        .unwrap();
+    assert_eq!(first, Some(Object::string("fast")));
+    assert_eq!(second, Some(Object::string("fast!")));
+}
+
+#[test]
+fn globals_are_visible_inside_functions() {
+    let mut vm = VirtualMachine::new();
+    vm.set_global("limit", Object::Int(3));
+    let result = vm
+        .execute_program(&parse("def over(n)\n  n > limit\nend\nover(5)"))
+        .unwrap();
+    assert_eq!(result, Some(Object::Bool(true)));
+}
+
+#[test]
+fn set_global_replaces_an_existing_value() {
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&parse("limit = 1")).unwrap();
+    vm.set_global("limit", Object::Int(9));
+    assert_eq!(
+        vm.execute_program(&parse("limit")).unwrap(),
+        Some(Object::Int(9))
+    );
+}
+
+#[test]
+fn the_crate_root_re_exports_the_embedding_api() {
+    let options: metorex::VmOptions = metorex::vm::VmOptions::default();
+    let mut vm: metorex::VirtualMachine = VirtualMachine::with_options(options);
+    let _handle: metorex::VmHandle = vm.handle();
+
+    let result: Result<Option<metorex::Object>, metorex::MetorexError> =
+        vm.execute_program(&parse("1 + 2"));
+    assert_eq!(result.unwrap(), Some(Object::Int(3)));
+}
+
+#[test]
+fn exceptions_and_errors_share_one_source_location_type() {
+    let location: metorex::object::SourceLocation =
+        metorex::error::SourceLocation::with_filename(3, 7, 0, "job.mx".to_string());
+    let same: metorex::SourceLocation = location.clone();
+    assert_eq!(location, same);
+}
+
+#[test]
+fn raised_exceptions_record_the_running_file() {
+    let mut vm = VirtualMachine::new();
+    vm.set_current_file(std::path::PathBuf::from("jobs/nightly.mx"));
+    vm.execute_program(&parse(
+        "begin\n  raise \"boom\"\nrescue => e\n  text = e.to_s\nend",
+    ))
+    .unwrap();
+    let Some(Object::String(text)) = vm.environment().get("text") else {
+        panic!("expected the rescued exception's text");
+    };
+    assert!(
+        text.starts_with("RuntimeError: boom (at jobs/nightly.mx:2)"),
+        "{}",
+        text
+    );
+}
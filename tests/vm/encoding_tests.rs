@@ -0,0 +1,137 @@
+use crate::common::{eval, eval_error, run};
+use metorex::hex;
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+
+// ============================================================================
+// Base64
+// ============================================================================
+
+#[test]
+fn base64_round_trips_strings() {
+    assert_eq!(
+        eval(r#"Base64.encode("hello world")"#),
+        Object::string("aGVsbG8gd29ybGQ=")
+    );
+    assert_eq!(
+        eval(r#"Base64.decode("aGVsbG8gd29ybGQ=")"#),
+        Object::string("hello world")
+    );
+    assert_eq!(eval(r#"Base64.encode("")"#), Object::string(""));
+}
+
+#[test]
+fn base64_decode_ignores_line_breaks() {
+    assert_eq!(
+        eval(r#"Base64.decode("aGVsbG8g\nd29ybGQ=\n")"#),
+        Object::string("hello world")
+    );
+}
+
+#[test]
+fn base64_urlsafe_uses_url_alphabet_and_optional_padding() {
+    assert_eq!(
+        eval(r#"Base64.urlsafe_encode("??>>")"#),
+        Object::string("Pz8-Pg==")
+    );
+    assert_eq!(
+        eval(r#"Base64.urlsafe_decode("Pz8-Pg")"#),
+        Object::string("??>>")
+    );
+    assert_eq!(
+        eval(r#"Base64.urlsafe_decode("Pz8-Pg==")"#),
+        Object::string("??>>")
+    );
+}
+
+#[test]
+fn base64_decode_reports_invalid_input() {
+    assert!(eval_error(r#"Base64.decode("not base64!")"#).contains("Base64.decode"));
+    assert!(eval_error(r#"Base64.decode("/w==")"#).contains("not valid UTF-8"));
+    assert!(eval_error("Base64.encode(1)").contains("String"));
+}
+
+// ============================================================================
+// Hex
+// ============================================================================
+
+#[test]
+fn string_hex_encode_and_decode_round_trip() {
+    assert_eq!(eval(r#""hi!".hex_encode"#), Object::string("686921"));
+    assert_eq!(eval(r#""686921".hex_decode"#), Object::string("hi!"));
+    assert_eq!(eval(r#""C3A9".hex_decode"#), Object::string("é"));
+}
+
+#[test]
+fn hex_decode_reports_malformed_digits() {
+    assert_eq!(
+        hex::decode("abc"),
+        Err("odd number of hex digits (3)".into())
+    );
+    assert_eq!(
+        hex::decode("a0zz"),
+        Err("invalid hex digit at offset 2".into())
+    );
+    assert_eq!(
+        hex::decode("0G"),
+        Err("invalid hex digit at offset 1".into())
+    );
+    assert!(eval_error(r#""ff".hex_decode"#).contains("not valid UTF-8"));
+}
+
+#[test]
+fn decoding_errors_raise_a_rescuable_argument_error() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"begin
+  Base64.decode("not base64!")
+rescue ArgumentError => e
+  base64_message = e.message
+end
+begin
+  "a0zz".hex_decode
+rescue ArgumentError => e
+  hex_message = e.message
+end"#,
+    )
+    .unwrap();
+    let base64_message = vm.environment().get("base64_message").unwrap().to_string();
+    assert!(
+        base64_message.starts_with("Base64.decode: "),
+        "{}",
+        base64_message
+    );
+    assert_eq!(
+        vm.environment().get("hex_message"),
+        Some(Object::string(
+            "String#hex_decode: invalid hex digit at offset 2"
+        ))
+    );
+}
+
+// ============================================================================
+// Digest
+// ============================================================================
+
+#[test]
+fn digest_computes_known_checksums() {
+    assert_eq!(
+        eval(r#"Digest.sha256("abc")"#),
+        Object::string("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+    );
+    assert_eq!(
+        eval(r#"Digest.sha1("abc")"#),
+        Object::string("a9993e364706816aba3e25717850c26c9cd0d89d")
+    );
+    assert_eq!(
+        eval(r#"Digest.md5("")"#),
+        Object::string("d41d8cd98f00b204e9800998ecf8427e")
+    );
+}
+
+#[test]
+fn digest_requires_one_string() {
+    assert!(eval_error("Digest.sha256(42)").contains("String"));
+    assert!(eval_error(r#"Digest.md5("a", "b")"#).contains("argument"));
+}
@@ -0,0 +1,204 @@
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+
+fn run(source: &str) -> Result<VirtualMachine, String> {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens)
+        .parse()
+        .map_err(|e| format!("{:?}", e))?;
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program).map_err(|e| format!("{}", e))?;
+    Ok(vm)
+}
+
+fn get(vm: &VirtualMachine, name: &str) -> Option<Object> {
+    vm.environment().get(name)
+}
+
+fn ints(values: &[i64]) -> Object {
+    Object::array(values.iter().map(|v| Object::Int(*v)).collect())
+}
+
+// ============================================================================
+// External iteration
+// ============================================================================
+
+#[test]
+fn blockless_iterators_return_enumerators() {
+    let vm = run("a = [1].each\nb = 2.times\nc = \"ab\".each_char\nd = Enumerator === a").unwrap();
+    for name in ["a", "b", "c"] {
+        let Some(Object::Instance(instance)) = get(&vm, name) else {
+            panic!("{} should be an Enumerator", name);
+        };
+        assert_eq!(instance.borrow().class.name(), "Enumerator");
+    }
+    assert_eq!(get(&vm, "d"), Some(Object::Bool(true)));
+}
+
+#[test]
+fn next_and_peek_walk_the_items() {
+    let source = "e = [10, 20].each
+first = e.next
+peeked = e.peek
+second = e.next";
+    let vm = run(source).unwrap();
+    assert_eq!(get(&vm, "first"), Some(Object::Int(10)));
+    assert_eq!(get(&vm, "peeked"), Some(Object::Int(20)));
+    assert_eq!(get(&vm, "second"), Some(Object::Int(20)));
+}
+
+#[test]
+fn next_raises_stop_iteration_at_the_end() {
+    let source = "e = 1.times
+e.next
+message = nil
+begin
+  e.next
+rescue StopIteration => err
+  message = err.message
+end";
+    let vm = run(source).unwrap();
+    assert_eq!(
+        get(&vm, "message"),
+        Some(Object::string("iteration reached an end"))
+    );
+
+    let err = run("e = [].each\ne.peek").err().unwrap();
+    assert!(err.contains("StopIteration"), "{}", err);
+}
+
+#[test]
+fn rewind_starts_over() {
+    let vm =
+        run("e = 3.downto(1)\ne.next\ne.next\nsame = e.rewind.equal?(e)\nfirst = e.next").unwrap();
+    assert_eq!(get(&vm, "same"), Some(Object::Bool(true)));
+    assert_eq!(get(&vm, "first"), Some(Object::Int(3)));
+}
+
+#[test]
+fn enumerators_walk_a_snapshot() {
+    let vm =
+        run("items = [1, 2]\ne = items.each\nitems.push(3)\nall = e.to_a\nsize = e.size").unwrap();
+    assert_eq!(get(&vm, "all"), Some(ints(&[1, 2])));
+    assert_eq!(get(&vm, "size"), Some(Object::Int(2)));
+}
+
+/// Walk the Enumerator `expression` returns with next, peek, rewind and to_a,
+/// returning [first, peeked, rewound, all].
+fn walk(expression: &str) -> Vec<Object> {
+    let source = format!(
+        "e = {}\nfirst = e.next\npeeked = e.peek\ne.rewind\nrewound = e.next\nall = e.to_a",
+        expression
+    );
+    let vm = run(&source).unwrap();
+    ["first", "peeked", "rewound", "all"]
+        .iter()
+        .map(|name| get(&vm, name).unwrap())
+        .collect()
+}
+
+fn pair(first: Object, second: Object) -> Object {
+    Object::array(vec![first, second])
+}
+
+#[test]
+fn map_without_a_block_enumerates_the_elements() {
+    assert_eq!(
+        walk("[5, 6, 7].map"),
+        vec![
+            Object::Int(5),
+            Object::Int(6),
+            Object::Int(5),
+            ints(&[5, 6, 7])
+        ]
+    );
+}
+
+#[test]
+fn select_without_a_block_enumerates_the_elements() {
+    assert_eq!(
+        walk("[1, 2].select"),
+        vec![
+            Object::Int(1),
+            Object::Int(2),
+            Object::Int(1),
+            ints(&[1, 2])
+        ]
+    );
+}
+
+#[test]
+fn each_with_index_without_a_block_enumerates_pairs() {
+    let a = pair(Object::string("a"), Object::Int(0));
+    let b = pair(Object::string("b"), Object::Int(1));
+    assert_eq!(
+        walk("[\"a\", \"b\"].each_with_index"),
+        vec![a.clone(), b.clone(), a.clone(), Object::array(vec![a, b])]
+    );
+}
+
+#[test]
+fn hash_each_without_a_block_enumerates_key_value_pairs() {
+    let x = pair(Object::string("x"), Object::Int(1));
+    let y = pair(Object::string("y"), Object::Int(2));
+    assert_eq!(
+        walk("{\"x\" => 1, \"y\" => 2}.each"),
+        vec![x.clone(), y.clone(), x.clone(), Object::array(vec![x, y])]
+    );
+}
+
+#[test]
+fn other_iterators_without_a_block_return_enumerators() {
+    let source = "sorted = [3, 1].sort_by.to_a
+filtered = [4].filter.next
+range = (1..3).map.to_a
+cheapest = {\"a\" => 1}.min_by.next";
+    let vm = run(source).unwrap();
+    assert_eq!(get(&vm, "sorted"), Some(ints(&[3, 1])));
+    assert_eq!(get(&vm, "filtered"), Some(Object::Int(4)));
+    assert_eq!(get(&vm, "range"), Some(ints(&[1, 2, 3])));
+    assert_eq!(
+        get(&vm, "cheapest"),
+        Some(pair(Object::string("a"), Object::Int(1)))
+    );
+}
+
+// ============================================================================
+// Internal iteration
+// ============================================================================
+
+#[test]
+fn each_yields_every_item_without_moving_the_cursor() {
+    let source = "e = [1, 2, 3].each
+e.next
+seen = []
+e.each { |x| seen.push(x) }
+after = e.next";
+    let vm = run(source).unwrap();
+    assert_eq!(get(&vm, "seen"), Some(ints(&[1, 2, 3])));
+    assert_eq!(get(&vm, "after"), Some(Object::Int(2)));
+}
+
+#[test]
+fn each_spreads_grouped_values() {
+    let source = "indices = []
+[:a, :b].each_with_index.each { |x, i| indices.push(i) }
+pair = {\"k\" => 1}.each.next";
+    let vm = run(source).unwrap();
+    assert_eq!(get(&vm, "indices"), Some(ints(&[0, 1])));
+    assert_eq!(
+        get(&vm, "pair"),
+        Some(Object::array(vec![Object::string("k"), Object::Int(1)]))
+    );
+}
+
+#[test]
+fn array_methods_run_on_the_items() {
+    let vm =
+        run("evens = 5.times.select { |x| x % 2 == 0 }\ndoubled = [1, 2].each.map { |x| x * 2 }")
+            .unwrap();
+    assert_eq!(get(&vm, "evens"), Some(ints(&[0, 2, 4])));
+    assert_eq!(get(&vm, "doubled"), Some(ints(&[2, 4])));
+}
@@ -0,0 +1,106 @@
+// Tests for the global eval function and Binding#eval
+
+use crate::common::execute_source;
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+
+fn get(vm: &VirtualMachine, name: &str) -> Object {
+    vm.environment()
+        .get(name)
+        .unwrap_or_else(|| panic!("{} is not defined", name))
+}
+
+#[test]
+fn test_eval_returns_the_last_expression_value() {
+    let vm = execute_source(r#"result = eval("a = 2\na * 21")"#).unwrap();
+    assert_eq!(get(&vm, "result"), Object::Int(42));
+}
+
+#[test]
+fn test_eval_reads_and_assigns_caller_variables() {
+    let source = r#"
+count = 1
+eval("count = count + 10")
+eval("created = count * 2")
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(get(&vm, "count"), Object::Int(11));
+    assert_eq!(get(&vm, "created"), Object::Int(22));
+}
+
+#[test]
+fn test_eval_runs_in_the_calling_method_scope() {
+    let source = r#"
+def scaled(n)
+  factor = 3
+  eval("n * factor")
+end
+
+result = scaled(5)
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(get(&vm, "result"), Object::Int(15));
+}
+
+#[test]
+fn test_eval_defines_methods_and_classes() {
+    let source = r#"
+eval("def double(n)\n  n * 2\nend")
+eval("class Counter\n  def start()\n    7\n  end\nend")
+doubled = double(4)
+start = Counter.new.start
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(get(&vm, "doubled"), Object::Int(8));
+    assert_eq!(get(&vm, "start"), Object::Int(7));
+}
+
+#[test]
+fn test_eval_of_invalid_source_raises_a_rescuable_syntax_error() {
+    let source = r#"
+begin
+  eval("def (")
+rescue SyntaxError => e
+  type = e.type
+end
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(get(&vm, "type"), Object::string("SyntaxError"));
+}
+
+#[test]
+fn test_eval_requires_a_string() {
+    let error = execute_source("eval(42)").err().unwrap();
+    assert!(error.contains("String"), "{}", error);
+}
+
+#[test]
+fn test_binding_eval_sees_captured_variables() {
+    let source = r#"
+base = 10
+block = lambda do |x|
+  x + base
+end
+scope = block.binding
+result = scope.eval("base * 4")
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(get(&vm, "result"), Object::Int(40));
+}
+
+#[test]
+fn test_binding_eval_updates_shared_variables_only() {
+    let source = r#"
+total = 1
+block = lambda do |x|
+  x + total
+end
+scope = block.binding
+scope.eval("total = 5\nscratch = 9")
+after = block.call(1)
+"#;
+    let vm = execute_source(source).unwrap();
+    assert_eq!(get(&vm, "total"), Object::Int(5));
+    assert_eq!(get(&vm, "after"), Object::Int(6));
+    assert_eq!(vm.environment().get("scratch"), None);
+}
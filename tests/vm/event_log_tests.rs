@@ -0,0 +1,201 @@
+use crate::common::temp_dir;
+use metorex::lexer::Lexer;
+use metorex::parser::Parser;
+use metorex::vm::{EventLog, LoggedEvent, VirtualMachine, VmOptions};
+use std::io::Cursor;
+use std::path::PathBuf;
+
+/// Run `source` while recording it, with `input` as its stdin
+fn record(source: &str, input: &str) -> EventLog {
+    let program = Parser::new(Lexer::new(source).tokenize()).parse().unwrap();
+    let mut vm = VirtualMachine::new();
+    vm.set_input(Cursor::new(input.to_string()));
+    vm.start_recording(PathBuf::from("script.mx"), source);
+    let result = vm.execute_program(&program);
+    vm.finish_recording(&result).unwrap()
+}
+
+fn line(line: usize, column: usize) -> LoggedEvent {
+    LoggedEvent::Line { line, column }
+}
+
+fn output(text: &str) -> LoggedEvent {
+    LoggedEvent::Output {
+        text: text.to_string(),
+    }
+}
+
+// ============================================================================
+// Recording
+// ============================================================================
+
+#[test]
+fn records_statements_input_and_output() {
+    let log = record("name = gets\nputs(\"hi \" + name)", "ada\n");
+
+    assert_eq!(
+        log.events,
+        vec![
+            line(1, 1),
+            LoggedEvent::Input {
+                text: Some("ada\n".to_string())
+            },
+            line(2, 1),
+            output("hi ada\n\n"),
+            LoggedEvent::Finish { error: None },
+        ]
+    );
+    assert_eq!(log.input(), "ada\n");
+}
+
+#[test]
+fn records_end_of_input_and_prompts() {
+    let log = record("line = gets(\"> \")", "");
+
+    assert_eq!(
+        log.events,
+        vec![
+            line(1, 1),
+            output("> "),
+            LoggedEvent::Input { text: None },
+            LoggedEvent::Finish { error: None },
+        ]
+    );
+}
+
+#[test]
+fn records_raises_and_the_error_the_run_stopped_on() {
+    let log = record(
+        "begin\n  raise \"caught\"\nrescue => e\nend\nraise ArgumentError, \"bad\"",
+        "",
+    );
+
+    assert!(log.events.contains(&LoggedEvent::Raise {
+        line: 2,
+        column: 3,
+        exception: "RuntimeError: caught".to_string(),
+    }));
+    let Some(LoggedEvent::Finish { error: Some(error) }) = log.events.last() else {
+        panic!("expected a failed finish, got {:?}", log.events.last());
+    };
+    assert!(error.contains("ArgumentError: bad"), "{}", error);
+}
+
+#[test]
+fn recording_keeps_the_seed_option() {
+    let source = "x = 1";
+    let program = Parser::new(Lexer::new(source).tokenize()).parse().unwrap();
+    let mut vm = VirtualMachine::with_options(VmOptions {
+        seed: Some(42),
+        ..VmOptions::default()
+    });
+    vm.start_recording(PathBuf::from("script.mx"), source);
+    let result = vm.execute_program(&program);
+
+    assert_eq!(vm.finish_recording(&result).unwrap().seed, 42);
+    assert!(vm.finish_recording(&result).is_none());
+}
+
+// ============================================================================
+// Log files
+// ============================================================================
+
+#[test]
+fn logs_round_trip_through_json_lines() {
+    let log = record("puts(gets)\nputs(rand(10))", "x\n");
+    let text = log.to_json_lines();
+
+    assert!(text.starts_with("{\"format\":\"metorex-event-log\",\"version\":1,"));
+    assert_eq!(text.lines().count(), log.events.len() + 1);
+    assert_eq!(EventLog::from_json_lines(&text).unwrap(), log);
+}
+
+#[test]
+fn logs_round_trip_through_files() {
+    let path = temp_dir("event_log").join("events.mxr");
+    let log = record("puts(1)", "");
+    log.save(&path).unwrap();
+
+    assert_eq!(EventLog::load(&path).unwrap(), log);
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn reading_rejects_other_files() {
+    assert_eq!(
+        EventLog::from_json_lines("").unwrap_err(),
+        "the event log is empty"
+    );
+    assert_eq!(
+        EventLog::from_json_lines(
+            "{\"format\":\"other\",\"version\":1,\"path\":\"a\",\"seed\":1,\"source\":\"\"}"
+        )
+        .unwrap_err(),
+        "line 1: unknown log format 'other'"
+    );
+    assert_eq!(
+        EventLog::from_json_lines(
+            "{\"format\":\"metorex-event-log\",\"version\":9,\"path\":\"a\",\"seed\":1,\"source\":\"\"}"
+        )
+        .unwrap_err(),
+        "line 1: log version 9 is not supported (expected 1)"
+    );
+    let bad_event = format!("{}{{\"event\":\"jump\"}}\n", record("", "").to_json_lines());
+    assert!(
+        EventLog::from_json_lines(&bad_event)
+            .unwrap_err()
+            .starts_with("line 3: unknown variant `jump`")
+    );
+}
+
+// ============================================================================
+// Replay
+// ============================================================================
+
+#[test]
+fn replay_reproduces_input_and_random_numbers() {
+    let log = record(
+        "a = gets\nb = gets\nputs(a + b)\nputs(rand(1000000))\nputs(rand)",
+        "one\ntwo\n",
+    );
+    let replay = log.replay(VmOptions::default()).unwrap();
+
+    assert_eq!(replay.divergence, None);
+    assert_eq!(replay.log, log);
+    assert!(replay.result.is_ok());
+}
+
+#[test]
+fn replay_reproduces_failures() {
+    let log = record("x = [1, 2]\nputs(x[0.5])", "");
+    let replay = log.replay(VmOptions::default()).unwrap();
+
+    assert_eq!(replay.divergence, None);
+    assert!(replay.result.is_err());
+}
+
+#[test]
+fn replay_reports_the_first_difference() {
+    let mut log = record("puts(1)\nputs(2)", "");
+    log.events[3] = output("3\n");
+    let replay = log.replay(VmOptions::default()).unwrap();
+
+    let divergence = replay.divergence.unwrap();
+    assert_eq!(divergence.index, 3);
+    assert_eq!(
+        divergence.to_string(),
+        "event 4: recorded output \"3\\n\", replay gave output \"2\\n\""
+    );
+}
+
+#[test]
+fn replay_reports_a_run_that_ends_early() {
+    let mut log = record("puts(1)", "");
+    log.events.push(line(2, 1));
+    let replay = log.replay(VmOptions::default()).unwrap();
+
+    assert_eq!(
+        replay.divergence.unwrap().to_string(),
+        "event 4: recorded line 2:1, replay gave nothing more"
+    );
+}
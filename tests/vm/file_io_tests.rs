@@ -0,0 +1,339 @@
+// Tests for File.read, File.write, File.exists?, File.delete, reading a
+// File line by line and the details attached to IOErrors
+
+use crate::common::{run, temp_dir};
+use metorex::lexer::Lexer;
+use metorex::literal;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+use std::cell::Cell;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Run `source` with `path` bound to the given path, returning `result`.
+fn eval_with_path(path: &Path, source: &str) -> Result<Object, String> {
+    let source = format!(
+        "path = {}\n{}",
+        literal::quote(&path.to_string_lossy()),
+        source.trim()
+    );
+    let tokens = Lexer::new(&source).tokenize();
+    let program = Parser::new(tokens)
+        .parse()
+        .map_err(|e| format!("{:?}", e))?;
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program).map_err(|e| format!("{}", e))?;
+    Ok(vm.environment().get("result").unwrap_or(Object::Nil))
+}
+
+fn eval_text(path: &Path, source: &str) -> String {
+    eval_with_path(path, source).unwrap().to_string()
+}
+
+#[test]
+fn write_then_read_round_trips_the_contents() {
+    let path = temp_dir("file_io_round_trip").join("notes.txt");
+    let result = eval_text(
+        &path,
+        r#"
+written = File.write(path, "one\ntwo\n")
+result = [written, File.read(path)]
+"#,
+    );
+    assert_eq!(result, "[8, one\ntwo\n]");
+    assert_eq!(fs::read_to_string(&path).unwrap(), "one\ntwo\n");
+}
+
+#[test]
+fn write_replaces_contents_and_converts_values_to_strings() {
+    let path = temp_dir("file_io_replace").join("count.txt");
+    fs::write(&path, "old contents").unwrap();
+    let result = eval_text(
+        &path,
+        r#"
+File.write(path, 42)
+result = File.read(path)
+"#,
+    );
+    assert_eq!(result, "42");
+}
+
+#[test]
+fn exists_reports_files_and_directories() {
+    let dir = temp_dir("file_io_exists");
+    let path = dir.join("here.txt");
+    fs::write(&path, "").unwrap();
+    let result = eval_text(
+        &path,
+        r#"
+result = [File.exists?(path), File.exist?(path), File.exists?(path + ".missing"), File.exists?(File.dirname(path))]
+"#,
+    );
+    assert_eq!(result, "[true, true, false, true]");
+}
+
+#[test]
+fn delete_removes_the_file() {
+    let path = temp_dir("file_io_delete").join("gone.txt");
+    fs::write(&path, "bye").unwrap();
+    let result = eval_text(
+        &path,
+        r#"
+File.delete(path)
+result = File.exists?(path)
+"#,
+    );
+    assert_eq!(result, "false");
+    assert!(!path.exists());
+}
+
+#[test]
+fn each_line_yields_lines_in_order() {
+    let path = temp_dir("file_io_each_line").join("lines.txt");
+    fs::write(&path, "alpha\nbeta\r\ngamma").unwrap();
+    let result = eval_text(
+        &path,
+        r#"
+lines = []
+kept = []
+file = File.open(path)
+file.each_line do |line|
+  lines.push(line)
+end
+file.each_line(chomp: true) do |line|
+  kept.push(line)
+end
+result = [lines, kept]
+"#,
+    );
+    assert_eq!(result, "[[alpha\n, beta\r\n, gamma], [alpha, beta, gamma]]");
+}
+
+#[test]
+fn each_line_stops_at_break_and_returns_an_enumerator_without_a_block() {
+    let path = temp_dir("file_io_each_line_break").join("lines.txt");
+    fs::write(&path, "a\nb\nc\n").unwrap();
+    let result = eval_text(
+        &path,
+        r#"
+seen = []
+file = File.new(path)
+file.each_line(chomp: true) do |line|
+  if line == "b"
+    break
+  end
+  seen.push(line)
+end
+all = file.each_line(chomp: true).to_a
+same_path = file.path == path
+result = [seen, all, same_path]
+"#,
+    );
+    assert_eq!(result, "[[a], [a, b, c], true]");
+}
+
+#[test]
+fn open_with_a_block_returns_the_block_result() {
+    let path = temp_dir("file_io_open_block").join("data.txt");
+    fs::write(&path, "x\ny\n").unwrap();
+    let result = eval_text(
+        &path,
+        r#"
+result = File.open(path) { |file| file.read }
+"#,
+    );
+    assert_eq!(result, "x\ny\n");
+}
+
+#[test]
+fn close_does_nothing_and_files_work_with_with() {
+    let path = temp_dir("file_io_with").join("data.txt");
+    fs::write(&path, "x\ny\n").unwrap();
+    let result = eval_text(
+        &path,
+        r#"
+file = File.open(path)
+closed = file.close
+first = with(File.open(path)) do |f|
+  f.read
+end
+result = [closed, first]
+"#,
+    );
+    assert_eq!(result, "[nil, x\ny\n]");
+}
+
+#[test]
+fn open_with_a_block_closes_the_file_when_the_block_raises() {
+    let path = temp_dir("file_io_open_raise").join("data.txt");
+    fs::write(&path, "x\n").unwrap();
+    let closes = Rc::new(Cell::new(0));
+    let mut vm = VirtualMachine::new();
+    let counter = Rc::clone(&closes);
+    vm.define_native_method("File", "close", move |_receiver, _args| {
+        counter.set(counter.get() + 1);
+        Ok(Object::Nil)
+    })
+    .unwrap();
+
+    let source = format!(
+        r#"
+path = {}
+begin
+  File.open(path) do |f|
+    raise "stop"
+  end
+rescue => e
+  message = e.message
+end
+"#,
+        literal::quote(&path.to_string_lossy())
+    );
+    run(&mut vm, &source).unwrap();
+    assert_eq!(
+        vm.environment().get("message"),
+        Some(Object::string("stop"))
+    );
+    assert_eq!(closes.get(), 1);
+}
+
+#[test]
+fn missing_files_raise_a_rescuable_io_error() {
+    let path = temp_dir("file_io_missing").join("absent.txt");
+    let result = eval_text(
+        &path,
+        r#"
+messages = []
+begin
+  File.read(path)
+rescue IOError => e
+  messages.push(e.message)
+end
+begin
+  File.delete(path)
+rescue StandardError => e
+  messages.push(e.message)
+end
+begin
+  File.open(path)
+rescue IOError => e
+  messages.push(e.message)
+end
+result = messages
+"#,
+    );
+    let shown = path.to_string_lossy();
+    assert!(
+        result.contains(&format!("File.read: {}: ", shown)),
+        "{}",
+        result
+    );
+    assert!(
+        result.contains(&format!("File.delete: {}: ", shown)),
+        "{}",
+        result
+    );
+    assert!(
+        result.contains(&format!("File.open: {}: ", shown)),
+        "{}",
+        result
+    );
+}
+
+#[test]
+fn opening_a_directory_raises_io_error() {
+    let dir = temp_dir("file_io_directory");
+    let result = eval_text(
+        &dir,
+        r#"
+begin
+  File.open(path)
+rescue IOError => e
+  result = e.message
+end
+"#,
+    );
+    assert_eq!(
+        result,
+        format!("File.open: {}: Is a directory", dir.to_string_lossy())
+    );
+}
+
+#[test]
+fn io_errors_carry_readable_details() {
+    let path = temp_dir("file_io_details").join("missing.txt");
+    let result = eval_text(
+        &path,
+        r#"
+begin
+  File.read(path)
+rescue IOError => e
+  result = [e.operation, e.path == path, e.kind, e.errno > 0, e.elapsed >= 0]
+end
+"#,
+    );
+    assert_eq!(result, "[File.read, true, :not_found, true, true]");
+}
+
+#[test]
+fn io_error_details_are_available_as_a_hash() {
+    let path = temp_dir("file_io_details_hash").join("missing.txt");
+    let result = eval_text(
+        &path,
+        r#"
+begin
+  File.delete(path)
+rescue IOError => e
+  details = e.details
+  result = [details.keys, details[:kind] == e.kind]
+end
+"#,
+    );
+    assert_eq!(
+        result,
+        "[[:operation, :path, :errno, :kind, :elapsed], true]"
+    );
+}
+
+#[test]
+fn directory_errors_have_a_kind_but_no_errno() {
+    let dir = temp_dir("file_io_details_directory");
+    let result = eval_text(
+        &dir,
+        r#"
+begin
+  File.open(path)
+rescue IOError => e
+  result = [e.kind, e.errno, e.operation]
+end
+"#,
+    );
+    assert_eq!(result, "[:is_a_directory, nil, File.open]");
+}
+
+#[test]
+fn raised_exceptions_have_no_details() {
+    let path = temp_dir("file_io_no_details").join("unused.txt");
+    let result = eval_text(
+        &path,
+        r#"
+begin
+  raise "plain"
+rescue => e
+  result = e.details
+end
+"#,
+    );
+    assert_eq!(result, "{}");
+}
+
+#[test]
+fn file_methods_check_their_arguments() {
+    let path = temp_dir("file_io_arguments").join("unused.txt");
+    let error = eval_with_path(&path, "File.read(42)").unwrap_err();
+    assert!(error.contains("read"), "{}", error);
+    let error = eval_with_path(&path, "File.write(path)").unwrap_err();
+    assert!(error.contains("write"), "{}", error);
+}
@@ -0,0 +1,133 @@
+use crate::common::run;
+use metorex::object::Object;
+use metorex::vm::{FloatDivisionPolicy, VirtualMachine};
+
+fn float_entry(vm: &VirtualMachine, name: &str) -> f64 {
+    match vm.environment().get(name) {
+        Some(Object::Float(value)) => value,
+        other => panic!("Expected Float for {}, got {:?}", name, other),
+    }
+}
+
+fn ieee_vm() -> VirtualMachine {
+    let mut vm = VirtualMachine::new();
+    vm.set_float_division_policy(FloatDivisionPolicy::Ieee);
+    vm
+}
+
+// ============================================================================
+// Division policy
+// ============================================================================
+
+#[test]
+fn float_division_by_zero_raises_by_default() {
+    let mut vm = VirtualMachine::new();
+    assert_eq!(vm.float_division_policy(), FloatDivisionPolicy::Raise);
+
+    for source in [
+        "x = 1.0 / 0",
+        "x = 1 / 0.0",
+        "x = 1.5 % 0.0",
+        "x = 2.0\nx /= 0",
+    ] {
+        let err = run(&mut vm, source).unwrap_err();
+        assert!(err.contains("Division by zero"), "{}: {}", source, err);
+    }
+}
+
+#[test]
+fn ieee_policy_returns_infinity_and_nan() {
+    let mut vm = ieee_vm();
+    run(
+        &mut vm,
+        "pos = 1.0 / 0\nneg = -1 / 0.0\nundefined = 0.0 / 0.0\nrem = 5.5 % 0.0",
+    )
+    .unwrap();
+
+    assert_eq!(float_entry(&vm, "pos"), f64::INFINITY);
+    assert_eq!(float_entry(&vm, "neg"), f64::NEG_INFINITY);
+    assert!(float_entry(&vm, "undefined").is_nan());
+    assert!(float_entry(&vm, "rem").is_nan());
+}
+
+#[test]
+fn ieee_policy_keeps_integer_division_by_zero_an_error() {
+    let mut vm = ieee_vm();
+    assert!(
+        run(&mut vm, "x = 1 / 0")
+            .unwrap_err()
+            .contains("Division by zero")
+    );
+    assert!(
+        run(&mut vm, "x = 1 % 0")
+            .unwrap_err()
+            .contains("Division by zero")
+    );
+}
+
+#[test]
+fn ieee_policy_leaves_ordinary_division_alone() {
+    let mut vm = ieee_vm();
+    run(&mut vm, "half = 1.0 / 2\nexact = 6 / 3").unwrap();
+    assert_eq!(float_entry(&vm, "half"), 0.5);
+    assert_eq!(vm.environment().get("exact"), Some(Object::Int(2)));
+}
+
+// ============================================================================
+// Float constants and predicates
+// ============================================================================
+
+#[test]
+fn float_constants() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "inf = Float::INFINITY\nnan = Float::NAN\neps = Float::EPSILON\nneg = -Float::INFINITY",
+    )
+    .unwrap();
+
+    assert_eq!(float_entry(&vm, "inf"), f64::INFINITY);
+    assert!(float_entry(&vm, "nan").is_nan());
+    assert_eq!(float_entry(&vm, "eps"), f64::EPSILON);
+    assert_eq!(float_entry(&vm, "neg"), f64::NEG_INFINITY);
+}
+
+#[test]
+fn float_predicates() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "a = 1.5.finite?\nb = Float::INFINITY.finite?\nc = Float::NAN.nan?\nd = 2.0.nan?\ne = Float::INFINITY.infinite?\nf = Float::NAN.infinite?",
+    )
+    .unwrap();
+
+    let expected = [
+        ("a", true),
+        ("b", false),
+        ("c", true),
+        ("d", false),
+        ("e", true),
+        ("f", false),
+    ];
+    for (name, value) in expected {
+        assert_eq!(
+            vm.environment().get(name),
+            Some(Object::Bool(value)),
+            "{}",
+            name
+        );
+    }
+}
+
+#[test]
+fn unknown_scoped_name_is_an_error() {
+    let mut vm = VirtualMachine::new();
+    assert!(run(&mut vm, "x = Float::NOPE").is_err());
+}
+
+#[test]
+fn non_finite_floats_display_like_ruby() {
+    assert_eq!(Object::Float(f64::INFINITY).to_string(), "Infinity");
+    assert_eq!(Object::Float(f64::NEG_INFINITY).to_string(), "-Infinity");
+    assert_eq!(Object::Float(f64::NAN).to_string(), "NaN");
+}
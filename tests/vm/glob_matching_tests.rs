@@ -0,0 +1,154 @@
+use crate::common::{eval, run};
+use metorex::glob::fnmatch;
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+
+// ============================================================================
+// Glob syntax
+// ============================================================================
+
+#[test]
+fn star_matches_within_one_path_segment() {
+    assert!(fnmatch("*.rb", "main.rb"));
+    assert!(fnmatch("*", ""));
+    assert!(fnmatch("lib/*.rb", "lib/util.rb"));
+    assert!(!fnmatch("*.rb", "lib/util.rb"));
+    assert!(!fnmatch("*.rb", "main.mx"));
+}
+
+#[test]
+fn double_star_crosses_directories() {
+    assert!(fnmatch("**/*.rb", "lib/a/b.rb"));
+    assert!(fnmatch("**/*.rb", "b.rb"));
+    assert!(fnmatch("src/**", "src/a/b/c"));
+    assert!(!fnmatch("**/*.rb", "lib/b.mx"));
+}
+
+#[test]
+fn question_mark_matches_one_character() {
+    assert!(fnmatch("file?.txt", "file1.txt"));
+    assert!(!fnmatch("file?.txt", "file10.txt"));
+    assert!(!fnmatch("a?b", "a/b"));
+}
+
+#[test]
+fn character_classes() {
+    assert!(fnmatch("[abc].mx", "b.mx"));
+    assert!(fnmatch("v[0-9]", "v7"));
+    assert!(!fnmatch("v[0-9]", "vx"));
+    assert!(fnmatch("[!a-c]x", "dx"));
+    assert!(fnmatch("[^a-c]x", "dx"));
+    assert!(!fnmatch("[!a-c]x", "bx"));
+    assert!(fnmatch("[]]", "]"));
+    assert!(fnmatch("[a-]", "-"));
+    assert!(fnmatch("[", "["));
+}
+
+#[test]
+fn brace_alternatives() {
+    assert!(fnmatch("*.{rb,mx}", "a.rb"));
+    assert!(fnmatch("*.{rb,mx}", "a.mx"));
+    assert!(!fnmatch("*.{rb,mx}", "a.py"));
+    assert!(fnmatch("{src,lib/{a,b}}/*.mx", "lib/b/x.mx"));
+    assert!(fnmatch("{,pre}fix", "fix"));
+}
+
+#[test]
+fn backslash_escapes_special_characters() {
+    assert!(fnmatch("\\*.txt", "*.txt"));
+    assert!(!fnmatch("\\*.txt", "a.txt"));
+    assert!(fnmatch("what\\?", "what?"));
+    assert!(fnmatch("\\{a,b}", "{a,b}"));
+}
+
+#[test]
+fn matching_is_by_character_not_byte() {
+    assert!(fnmatch("caf?", "café"));
+    assert!(fnmatch("[é]", "é"));
+}
+
+// ============================================================================
+// File.fnmatch and String#match_glob
+// ============================================================================
+
+#[test]
+fn file_fnmatch() {
+    assert_eq!(
+        eval("File.fnmatch(\"*.txt\", \"notes.txt\")"),
+        Object::Bool(true)
+    );
+    assert_eq!(
+        eval("File.fnmatch?(\"*.txt\", \"notes.md\")"),
+        Object::Bool(false)
+    );
+
+    let mut vm = VirtualMachine::new();
+    let err = run(&mut vm, "File.fnmatch(\"*.txt\")").unwrap_err();
+    assert!(err.contains("(given 1, expected 2)"), "{}", err);
+    let err = run(&mut vm, "File.fnmatch(\"*.txt\", 3)").unwrap_err();
+    assert!(
+        err.contains("fnmatch: path must be a String (got Int)"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn string_match_glob() {
+    assert_eq!(
+        eval("\"report-2024.csv\".match_glob(\"report-*.csv\")"),
+        Object::Bool(true)
+    );
+    assert_eq!(
+        eval("\"report.txt\".match_glob(\"*.csv\")"),
+        Object::Bool(false)
+    );
+
+    let mut vm = VirtualMachine::new();
+    let err = run(&mut vm, "\"a\".match_glob(1)").unwrap_err();
+    assert!(
+        err.contains("match_glob: pattern must be a String (got Int)"),
+        "{}",
+        err
+    );
+}
+
+// ============================================================================
+// case/when and ===
+// ============================================================================
+
+#[test]
+fn case_when_accepts_glob_patterns() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "def kind(name)\n  case name\n  when glob(\"*.{rb,mx}\")\n    \"source\"\n  when glob(\"**/*.md\")\n    \"docs\"\n  when \"*\"\n    \"star\"\n  else\n    \"other\"\n  end\nend\na = kind(\"main.mx\")\nb = kind(\"docs/guide.md\")\nc = kind(\"*\")\nd = kind(\"notes.txt\")\ne = kind(42)",
+    )
+    .unwrap();
+
+    for (name, expected) in [
+        ("a", "source"),
+        ("b", "docs"),
+        ("c", "star"),
+        ("d", "other"),
+        ("e", "other"),
+    ] {
+        assert_eq!(vm.environment().get(name), Some(Object::string(expected)));
+    }
+}
+
+#[test]
+fn case_equal_operator() {
+    assert_eq!(eval("Integer === 3"), Object::Bool(true));
+    assert_eq!(eval("String === 3"), Object::Bool(false));
+    assert_eq!(
+        eval("StandardError === RuntimeError.new(\"x\")"),
+        Object::Bool(true)
+    );
+    assert_eq!(eval("(1..5) === 5"), Object::Bool(true));
+    assert_eq!(eval("(1...5) === 5"), Object::Bool(false));
+    assert_eq!(eval("(1..5) === 2.5"), Object::Bool(true));
+    assert_eq!(eval("(1..5) === \"3\""), Object::Bool(false));
+    assert_eq!(eval("\"a\" === \"a\""), Object::Bool(true));
+    assert_eq!(eval("1 === 2"), Object::Bool(false));
+}
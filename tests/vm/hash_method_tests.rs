@@ -0,0 +1,213 @@
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+
+fn run(source: &str) -> Result<VirtualMachine, String> {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens)
+        .parse()
+        .map_err(|e| format!("{:?}", e))?;
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program).map_err(|e| format!("{}", e))?;
+    Ok(vm)
+}
+
+fn pair(key: &str, value: i64) -> Object {
+    Object::array(vec![Object::string(key), Object::Int(value)])
+}
+
+fn strings(values: &[&str]) -> Object {
+    Object::array(values.iter().map(|v| Object::string(*v)).collect())
+}
+
+const SCORES: &str = r#"scores = {"zed" => 3, "amy" => 1, "max" => 2, "bob" => 1}"#;
+
+// ============================================================================
+// Insertion order
+// ============================================================================
+
+#[test]
+fn hash_keys_follow_insertion_order() {
+    let vm = run(&format!(
+        "{}\nkeys = scores.keys\nvalues = scores.values",
+        SCORES
+    ))
+    .unwrap();
+    assert_eq!(
+        vm.environment().get("keys"),
+        Some(strings(&["zed", "amy", "max", "bob"]))
+    );
+    assert_eq!(
+        vm.environment().get("values"),
+        Some(Object::array(vec![
+            Object::Int(3),
+            Object::Int(1),
+            Object::Int(2),
+            Object::Int(1),
+        ]))
+    );
+}
+
+#[test]
+fn hash_to_a_returns_pairs_in_insertion_order() {
+    let vm = run(&format!("{}\npairs = scores.to_a", SCORES)).unwrap();
+    assert_eq!(
+        vm.environment().get("pairs"),
+        Some(Object::array(vec![
+            pair("zed", 3),
+            pair("amy", 1),
+            pair("max", 2),
+            pair("bob", 1),
+        ]))
+    );
+}
+
+#[test]
+fn hash_display_uses_insertion_order() {
+    let vm = run(&format!("{}\ntext = scores.to_s", SCORES)).unwrap();
+    assert_eq!(
+        vm.environment().get("text"),
+        Some(Object::string("{zed: 3, amy: 1, max: 2, bob: 1}"))
+    );
+}
+
+#[test]
+fn hash_equality_ignores_order() {
+    let vm = run(r#"same = {"a" => 1, "b" => 2} == {"b" => 2, "a" => 1}"#).unwrap();
+    assert_eq!(vm.environment().get("same"), Some(Object::Bool(true)));
+}
+
+// ============================================================================
+// sort_by / min_by / max_by
+// ============================================================================
+
+#[test]
+fn hash_sort_by_value_is_stable() {
+    let vm = run(&format!(
+        "{}\nsorted = scores.sort_by do |name, score|\n  score\nend",
+        SCORES
+    ))
+    .unwrap();
+    assert_eq!(
+        vm.environment().get("sorted"),
+        Some(Object::array(vec![
+            pair("amy", 1),
+            pair("bob", 1),
+            pair("max", 2),
+            pair("zed", 3),
+        ]))
+    );
+}
+
+#[test]
+fn hash_sort_by_with_single_pair_parameter() {
+    let vm = run(&format!(
+        "{}\nsorted = scores.sort_by {{ |entry| entry[0] }}",
+        SCORES
+    ))
+    .unwrap();
+    assert_eq!(
+        vm.environment().get("sorted"),
+        Some(Object::array(vec![
+            pair("amy", 1),
+            pair("bob", 1),
+            pair("max", 2),
+            pair("zed", 3),
+        ]))
+    );
+}
+
+#[test]
+fn hash_sort_by_array_keys_compares_elementwise() {
+    let vm = run(&format!(
+        "{}\nsorted = scores.sort_by {{ |name, score| [score, name] }}\nfirst = sorted[0]",
+        SCORES
+    ))
+    .unwrap();
+    assert_eq!(vm.environment().get("first"), Some(pair("amy", 1)));
+}
+
+#[test]
+fn hash_sort_by_rejects_incomparable_keys() {
+    let error = run(r#"
+mixed = {"a" => 1, "b" => "two"}
+mixed.sort_by { |k, v| v }
+"#)
+    .err()
+    .unwrap();
+    assert!(
+        error.contains("comparison of Int with String failed"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn hash_min_by_and_max_by_return_first_extreme_entry() {
+    let vm = run(&format!(
+        "{}\nlowest = scores.min_by {{ |name, score| score }}\nhighest = scores.max_by {{ |name, score| score }}",
+        SCORES
+    ))
+    .unwrap();
+    assert_eq!(vm.environment().get("lowest"), Some(pair("amy", 1)));
+    assert_eq!(vm.environment().get("highest"), Some(pair("zed", 3)));
+}
+
+#[test]
+fn hash_min_by_on_empty_hash_is_nil() {
+    let vm = run("empty = {}\nlowest = empty.min_by { |k, v| v }").unwrap();
+    assert_eq!(vm.environment().get("lowest"), Some(Object::Nil));
+}
+
+#[test]
+fn hash_sort_by_requires_block() {
+    let error = run(&format!("{}\nscores.sort_by(1)", SCORES))
+        .err()
+        .unwrap();
+    assert!(error.contains("sort_by"));
+}
+
+// ============================================================================
+// each
+// ============================================================================
+
+#[test]
+fn hash_each_yields_keys_and_values_in_insertion_order() {
+    let vm = run(&format!(
+        "{}\nseen = []\nscores.each do |name, score|\n  seen.push(name)\n  seen.push(score)\nend",
+        SCORES
+    ))
+    .unwrap();
+    assert_eq!(
+        vm.environment().get("seen"),
+        Some(Object::array(vec![
+            Object::string("zed"),
+            Object::Int(3),
+            Object::string("amy"),
+            Object::Int(1),
+            Object::string("max"),
+            Object::Int(2),
+            Object::string("bob"),
+            Object::Int(1),
+        ]))
+    );
+}
+
+#[test]
+fn hash_each_yields_pairs_to_a_single_parameter() {
+    let vm = run("seen = []\n{\"a\" => 1}.each { |entry| seen.push(entry) }").unwrap();
+    assert_eq!(
+        vm.environment().get("seen"),
+        Some(Object::array(vec![pair("a", 1)]))
+    );
+}
+
+#[test]
+fn hash_each_without_block_enumerates_pairs() {
+    let vm = run("pairs = {\"a\" => 1, \"b\" => 2}.each.to_a").unwrap();
+    assert_eq!(
+        vm.environment().get("pairs"),
+        Some(Object::array(vec![pair("a", 1), pair("b", 2)]))
+    );
+}
@@ -0,0 +1,486 @@
+use crate::common::run;
+use metorex::object::Object;
+use metorex::vm::{ClassStats, DEFAULT_COLLECTION_THRESHOLD, Heap, VirtualMachine};
+
+fn int_entry(vm: &VirtualMachine, name: &str) -> i64 {
+    match vm.environment().get(name) {
+        Some(Object::Int(value)) => value,
+        other => panic!("Expected Int for {}, got {:?}", name, other),
+    }
+}
+
+// ============================================================================
+// Heap
+// ============================================================================
+
+#[test]
+fn heap_tracks_containers_until_they_are_freed() {
+    let mut heap = Heap::default();
+    let kept = Object::array(vec![Object::Int(1)]);
+    heap.allocate(&kept);
+    heap.allocate(&Object::empty_dict());
+    heap.allocate(&Object::Int(5));
+
+    assert_eq!(heap.allocation_count(), 3);
+    assert_eq!(heap.live_objects(), 1);
+    assert_eq!(heap.collect(), 1);
+
+    let stats = heap.stats();
+    assert_eq!(stats.collections, 1);
+    assert_eq!(stats.live_objects, 1);
+    assert!(stats.total_pause >= stats.last_pause);
+}
+
+#[test]
+fn heap_collects_automatically_at_threshold() {
+    let mut heap = Heap::default();
+    heap.set_collection_threshold(2);
+    heap.allocate(&Object::empty_array());
+    assert_eq!(heap.stats().collections, 0);
+    heap.allocate(&Object::empty_array());
+    assert_eq!(heap.stats().collections, 1);
+}
+
+#[test]
+fn threshold_grows_with_the_live_set() {
+    let mut heap = Heap::default();
+    heap.set_collection_threshold(2);
+    let live: Vec<Object> = (0..3).map(|_| Object::empty_array()).collect();
+    for value in &live {
+        heap.allocate(value);
+    }
+    // Collected at 2 live handles, so the next collection waits for 4
+    assert_eq!(heap.stats().collections, 1);
+    assert_eq!(heap.collection_threshold(), 4);
+    assert_eq!(heap.stats().threshold, 4);
+
+    drop(live);
+    heap.collect();
+    assert_eq!(heap.collection_threshold(), 2);
+}
+
+#[test]
+fn allocating_a_growing_live_set_collects_a_logarithmic_number_of_times() {
+    let mut heap = Heap::default();
+    let count = DEFAULT_COLLECTION_THRESHOLD * 16;
+    let live: Vec<Object> = (0..count).map(|_| Object::empty_array()).collect();
+    for value in &live {
+        heap.allocate(value);
+    }
+    // Thresholds of 1x, 2x, 4x, 8x and 16x the default
+    assert_eq!(heap.stats().collections, 5);
+    assert_eq!(heap.stats().live_objects, count);
+}
+
+#[test]
+fn disabled_heap_skips_automatic_collection() {
+    let mut heap = Heap::default();
+    heap.set_collection_threshold(1);
+    assert!(heap.set_enabled(false));
+    heap.allocate(&Object::empty_array());
+    assert_eq!(heap.stats().collections, 0);
+    assert!(!heap.is_enabled());
+
+    // Explicit collections still run while disabled
+    heap.collect();
+    assert_eq!(heap.stats().collections, 1);
+}
+
+#[test]
+fn heap_counts_allocations_by_class() {
+    let mut heap = Heap::default();
+    let kept = Object::array(vec![]);
+    heap.allocate(&kept);
+    heap.allocate(&Object::empty_array());
+    heap.allocate(&Object::empty_dict());
+
+    let stats = heap.stats_by_class();
+    assert_eq!(
+        stats["Array"],
+        ClassStats {
+            allocations: 2,
+            live_objects: 1
+        }
+    );
+    assert_eq!(
+        stats["Dict"],
+        ClassStats {
+            allocations: 1,
+            live_objects: 0
+        }
+    );
+    assert_eq!(stats.len(), 2);
+}
+
+#[test]
+fn heap_reports_objects_alive_since_a_point() {
+    let mut heap = Heap::default();
+    let before = Object::empty_array();
+    heap.allocate(&before);
+    let start = heap.allocation_count();
+    let kept = Object::empty_dict();
+    heap.allocate(&kept);
+    heap.allocate_with_backtrace(&Object::empty_array(), vec!["frame".to_string()]);
+
+    let report = heap.leaks_since(start);
+    assert_eq!(report.leaks.len(), 1);
+    assert_eq!(report.leaks[0].class_name, "Dict");
+    assert_eq!(report.to_string(), "1 object leaked:\n  Dict");
+    assert_eq!(heap.stats().collections, 1);
+
+    drop(kept);
+    assert!(heap.leaks_since(start).is_empty());
+}
+
+// ============================================================================
+// Cycle collection
+// ============================================================================
+
+fn array_cycle() -> (Object, Object) {
+    let first = Object::empty_array();
+    let second = Object::array(vec![first.clone()]);
+    if let Object::Array(elements) = &first {
+        elements.borrow_mut().push(second.clone());
+    }
+    (first, second)
+}
+
+#[test]
+fn collect_frees_unreachable_cycles() {
+    let mut heap = Heap::default();
+    let (first, second) = array_cycle();
+    heap.allocate(&first);
+    heap.allocate(&second);
+    drop((first, second));
+
+    assert_eq!(heap.live_objects(), 2);
+    assert_eq!(heap.collect(), 2);
+    assert_eq!(heap.live_objects(), 0);
+}
+
+#[test]
+fn collect_keeps_cycles_held_from_outside() {
+    let mut heap = Heap::default();
+    let (first, second) = array_cycle();
+    heap.allocate(&first);
+    heap.allocate(&second);
+    drop(second);
+
+    assert_eq!(heap.collect(), 0);
+    assert_eq!(heap.live_objects(), 2);
+    match &first {
+        Object::Array(elements) => match &elements.borrow()[0] {
+            Object::Array(second) => assert_eq!(second.borrow().len(), 1),
+            other => panic!("Expected the second array, got {:?}", other),
+        },
+        other => panic!("Expected Array, got {:?}", other),
+    }
+}
+
+#[test]
+fn gc_start_frees_instances_captured_by_their_own_blocks() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"
+class Button
+  def initialize(label)
+    @label = label
+    me = self
+    @on_click = lambda do
+      me.label
+    end
+  end
+
+  def label
+    @label
+  end
+
+  def click
+    @on_click.call
+  end
+end
+
+def scratch
+  Button.new("discarded")
+  nil
+end
+
+kept = Button.new("ok")
+scratch()
+GC.start
+clicked = kept.click
+live = GC.stats[:live_objects]
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(vm.environment().get("clicked"), Some(Object::string("ok")));
+    // The kept button and its block survive; the discarded pair does not
+    assert_eq!(int_entry(&vm, "live"), 2);
+    assert_eq!(vm.heap_stats_by_class()["Button"].live_objects, 1);
+}
+
+// ============================================================================
+// VM integration
+// ============================================================================
+
+#[test]
+fn vm_heap_stats_count_literal_and_instance_allocations() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"
+class Point
+end
+
+list = [1, 2]
+options = {"a" => 1}
+point = Point.new
+"#,
+    )
+    .unwrap();
+
+    let stats = vm.heap_stats();
+    assert_eq!(stats.allocations, 3);
+    assert_eq!(stats.live_objects, 3);
+    assert!(stats.enabled);
+}
+
+#[test]
+fn vm_heap_stats_drop_freed_objects() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"
+def scratch()
+  temp = [1, 2, 3]
+  temp.length
+end
+
+scratch()
+scratch()
+"#,
+    )
+    .unwrap();
+
+    let stats = vm.heap_stats();
+    assert_eq!(stats.allocations, 2);
+    assert_eq!(stats.live_objects, 0);
+}
+
+const CYCLE: &str = r#"
+class Node
+  def link(other)
+    @other = other
+  end
+end
+
+class Graph
+  @@kept = nil
+
+  def cycle
+    a = Node.new
+    b = Node.new
+    a.link(b)
+    b.link(a)
+    nil
+  end
+
+  def tree
+    a = Node.new
+    a.link([Node.new])
+    nil
+  end
+
+  def keep
+    a = Node.new
+    a.link(Node.new)
+    @@kept = a
+    nil
+  end
+end
+"#;
+
+#[test]
+fn vm_heap_stats_by_class_name_user_classes() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, CYCLE).unwrap();
+    run(
+        &mut vm,
+        "kept = Node.new
+Graph.new.tree
+",
+    )
+    .unwrap();
+
+    let stats = vm.heap_stats_by_class();
+    assert_eq!(
+        stats["Node"],
+        ClassStats {
+            allocations: 3,
+            live_objects: 1
+        }
+    );
+    assert_eq!(stats["Array"].live_objects, 0);
+    assert_eq!(stats["Graph"].allocations, 1);
+}
+
+#[test]
+fn check_leaks_reports_objects_that_outlive_the_check() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, CYCLE).unwrap();
+
+    let report = vm.check_leaks(|vm| run(vm, "Graph.new.tree").unwrap());
+    assert!(report.is_empty(), "{}", report);
+
+    let report = vm.check_leaks(|vm| run(vm, "Graph.new.cycle").unwrap());
+    assert!(report.is_empty(), "{}", report);
+
+    let report = vm.check_leaks(|vm| run(vm, "Graph.new.keep").unwrap());
+    assert_eq!(report.counts_by_class().get("Node"), Some(&2));
+    assert_eq!(report.leaks.len(), 2);
+    if cfg!(debug_assertions) {
+        assert_eq!(report.leaks[0].backtrace, vec!["Graph#keep (1:1)"]);
+    }
+    assert!(!vm.heap().borrow().records_backtraces());
+}
+
+#[test]
+#[should_panic(expected = "2 objects leaked:\n  Node")]
+fn assert_no_leaks_panics_with_the_report() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, CYCLE).unwrap();
+    vm.assert_no_leaks(|vm| run(vm, "Graph.new.keep").unwrap());
+}
+
+// ============================================================================
+// GC class
+// ============================================================================
+
+#[test]
+fn gc_stats_returns_hash_of_counters() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"
+kept = [1]
+stats = GC.stats
+allocations = stats[:allocations]
+live = stats[:live_objects]
+collections = stats[:collections]
+enabled = stats[:enabled]
+pause = stats[:total_pause_ms]
+threshold = stats[:threshold]
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(int_entry(&vm, "allocations"), 1);
+    assert_eq!(int_entry(&vm, "live"), 1);
+    assert_eq!(int_entry(&vm, "collections"), 0);
+    assert_eq!(vm.environment().get("enabled"), Some(Object::Bool(true)));
+    assert_eq!(
+        int_entry(&vm, "threshold"),
+        DEFAULT_COLLECTION_THRESHOLD as i64
+    );
+    assert!(matches!(
+        vm.environment().get("pause"),
+        Some(Object::Float(_))
+    ));
+}
+
+#[test]
+fn gc_start_runs_a_collection() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, "result = GC.start\nGC.start\ncount = GC.count").unwrap();
+
+    assert_eq!(vm.environment().get("result"), Some(Object::Nil));
+    assert_eq!(int_entry(&vm, "count"), 2);
+    assert_eq!(vm.heap_stats().collections, 2);
+}
+
+#[test]
+fn gc_disable_and_enable_report_previous_state() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"
+first = GC.disable
+second = GC.disable
+disabled = GC.enabled?
+third = GC.enable
+fourth = GC.enable
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(vm.environment().get("first"), Some(Object::Bool(false)));
+    assert_eq!(vm.environment().get("second"), Some(Object::Bool(true)));
+    assert_eq!(vm.environment().get("disabled"), Some(Object::Bool(false)));
+    assert_eq!(vm.environment().get("third"), Some(Object::Bool(true)));
+    assert_eq!(vm.environment().get("fourth"), Some(Object::Bool(false)));
+    assert!(vm.heap_stats().enabled);
+}
+
+#[test]
+fn gc_methods_reject_arguments() {
+    let mut vm = VirtualMachine::new();
+    let error = run(&mut vm, "GC.start(1)").err().unwrap();
+    assert!(
+        error.contains("start: wrong number of arguments (given 1, expected 0)"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn gc_stats_by_class_returns_counters_per_class() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "kept = [[1], {\"a\" => 1}]\nstats = GC.stats_by_class\narrays = stats[\"Array\"][:allocations]\nlive = stats[\"Dict\"][:live_objects]\n",
+    )
+    .unwrap();
+
+    assert_eq!(int_entry(&vm, "arrays"), 2);
+    assert_eq!(int_entry(&vm, "live"), 1);
+}
+
+#[test]
+fn gc_assert_no_leaks_raises_for_objects_that_outlive_the_block() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, CYCLE).unwrap();
+    run(
+        &mut vm,
+        r#"
+clean = GC.assert_no_leaks do
+  Graph.new.tree
+  Graph.new.cycle
+  [1, 2]
+end
+
+message = nil
+begin
+  GC.assert_no_leaks do
+    Graph.new.keep
+  end
+rescue RuntimeError => e
+  message = e.message
+end
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(vm.environment().get("clean"), Some(Object::Nil));
+    match vm.environment().get("message") {
+        Some(Object::String(message)) => {
+            assert!(
+                message.starts_with("2 objects leaked:\n  Node"),
+                "{}",
+                message
+            )
+        }
+        other => panic!("Expected the leak report, got {:?}", other),
+    }
+}
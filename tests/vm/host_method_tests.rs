@@ -0,0 +1,117 @@
+use crate::common::run;
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+use std::rc::Rc;
+
+fn string(value: &str) -> Object {
+    Object::String(Rc::new(value.to_string()))
+}
+
+fn define_shout(vm: &mut VirtualMachine) {
+    vm.define_native_method("String", "shout", |receiver, _args| match receiver {
+        Object::String(s) => Ok(Object::string(format!("{}!", s.to_uppercase()))),
+        other => Err(format!("cannot shout a {}", other.type_name())),
+    })
+    .expect("String class should exist");
+}
+
+#[test]
+fn host_method_extends_builtin_class() {
+    let mut vm = VirtualMachine::new();
+    define_shout(&mut vm);
+
+    run(&mut vm, r#"loud = "hey".shout"#).unwrap();
+    assert_eq!(vm.environment().get("loud"), Some(string("HEY!")));
+}
+
+#[test]
+fn host_method_receives_arguments() {
+    let mut vm = VirtualMachine::new();
+    vm.define_native_method("Array", "sum_with", |receiver, args| {
+        let Object::Array(items) = receiver else {
+            return Err("expected array".to_string());
+        };
+        let mut total = match args.first() {
+            Some(Object::Int(start)) => *start,
+            _ => return Err("sum_with expects an Int".to_string()),
+        };
+        for item in items.borrow().iter() {
+            if let Object::Int(value) = item {
+                total += value;
+            }
+        }
+        Ok(Object::Int(total))
+    })
+    .unwrap();
+
+    run(&mut vm, "total = [1, 2, 3].sum_with(10)").unwrap();
+    assert_eq!(vm.environment().get("total"), Some(Object::Int(16)));
+}
+
+#[test]
+fn host_method_errors_become_runtime_errors() {
+    let mut vm = VirtualMachine::new();
+    vm.define_native_method("String", "explode", |_receiver, _args| {
+        Err("boom".to_string())
+    })
+    .unwrap();
+
+    let error = run(&mut vm, r#""x".explode"#).unwrap_err();
+    assert!(error.contains("boom"));
+}
+
+#[test]
+fn respond_to_sees_host_methods() {
+    let mut vm = VirtualMachine::new();
+
+    run(&mut vm, r#"before = "x".respond_to?(:shout)"#).unwrap();
+    define_shout(&mut vm);
+    run(&mut vm, r#"after = "x".respond_to?(:shout)"#).unwrap();
+
+    assert_eq!(vm.environment().get("before"), Some(Object::Bool(false)));
+    assert_eq!(vm.environment().get("after"), Some(Object::Bool(true)));
+}
+
+#[test]
+fn host_method_on_user_class_is_inherited() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"
+class Animal
+end
+
+class Dog < Animal
+end
+"#,
+    )
+    .unwrap();
+
+    vm.define_native_method("Animal", "kind", |_receiver, _args| {
+        Ok(Object::string("animal"))
+    })
+    .unwrap();
+
+    run(&mut vm, "kind = Dog.new.kind").unwrap();
+    assert_eq!(vm.environment().get("kind"), Some(string("animal")));
+}
+
+#[test]
+fn redefining_host_method_replaces_previous_definition() {
+    let mut vm = VirtualMachine::new();
+    define_shout(&mut vm);
+    vm.define_native_method("String", "shout", |_receiver, _args| {
+        Ok(Object::string("quiet"))
+    })
+    .unwrap();
+
+    run(&mut vm, r#"result = "hey".shout"#).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(string("quiet")));
+}
+
+#[test]
+fn defining_on_unknown_class_fails() {
+    let mut vm = VirtualMachine::new();
+    let result = vm.define_native_method("Missing", "x", |_receiver, _args| Ok(Object::Nil));
+    assert!(result.is_err());
+}
@@ -0,0 +1,140 @@
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+
+fn run(source: &str) -> Result<VirtualMachine, String> {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens)
+        .parse()
+        .map_err(|e| format!("{:?}", e))?;
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program).map_err(|e| format!("{}", e))?;
+    Ok(vm)
+}
+
+fn get(vm: &VirtualMachine, name: &str) -> Option<Object> {
+    vm.environment().get(name)
+}
+
+fn ints(values: &[i64]) -> Object {
+    Object::array(values.iter().map(|v| Object::Int(*v)).collect())
+}
+
+// ============================================================================
+// Arrays and hashes
+// ============================================================================
+
+#[test]
+fn array_elements_are_replaced_from_either_end() {
+    let vm = run("items = [1, 2, 3]\nitems[0] = 10\nitems[-1] = 30").unwrap();
+    assert_eq!(get(&vm, "items"), Some(ints(&[10, 2, 30])));
+}
+
+#[test]
+fn assigning_past_the_end_grows_the_array_with_nil() {
+    let vm = run("items = [1]\nitems[3] = 4").unwrap();
+    assert_eq!(
+        get(&vm, "items"),
+        Some(Object::array(vec![
+            Object::Int(1),
+            Object::Nil,
+            Object::Nil,
+            Object::Int(4)
+        ]))
+    );
+}
+
+#[test]
+fn assigning_before_the_start_is_an_error() {
+    let err = run("items = [1, 2]\nitems[-3] = 0").err().unwrap();
+    assert!(
+        err.contains("Index -3 is out of bounds for array of length 2"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn assigning_far_past_the_end_is_an_error() {
+    let err = run("items = [1, 2]\nitems[1000000000] = 0").err().unwrap();
+    assert!(
+        err.contains("Index 1000000000 is more than 1000000 past the end of an array of length 2"),
+        "{}",
+        err
+    );
+    let vm = run("items = []\nitems[1000000] = 0\nn = items.length").unwrap();
+    assert_eq!(get(&vm, "n"), Some(Object::Int(1_000_001)));
+}
+
+#[test]
+fn invalid_indexes_and_keys_are_errors() {
+    let err = run("items = [1]\nitems[\"a\"] = 0").err().unwrap();
+    assert!(
+        err.contains("Array index must be an Integer, found String"),
+        "{}",
+        err
+    );
+    let err = run("h = {}\nh[[1]] = 0").err().unwrap();
+    assert!(err.contains("Hash key must be a String"), "{}", err);
+}
+
+#[test]
+fn hash_entries_are_added_and_replaced() {
+    let vm = run("h = {\"a\" => 1}\nh[\"a\"] = 2\nh[:b] = 3\nx = h[\"a\"]\ny = h[:b]").unwrap();
+    assert_eq!(get(&vm, "x"), Some(Object::Int(2)));
+    assert_eq!(get(&vm, "y"), Some(Object::Int(3)));
+}
+
+#[test]
+fn nested_elements_and_compound_assignment() {
+    let vm =
+        run("h = {\"xs\" => [1, 2]}\nh[\"xs\"][1] = 7\nh[\"xs\"][0] += 4\nxs = h[\"xs\"]").unwrap();
+    assert_eq!(get(&vm, "xs"), Some(ints(&[5, 7])));
+}
+
+#[test]
+fn other_values_can_not_be_index_assigned() {
+    let err = run("x = 5\nx[0] = 1").err().unwrap();
+    assert!(
+        err.contains("Cannot index assign into type 'Int'"),
+        "{}",
+        err
+    );
+}
+
+// ============================================================================
+// Objects that define [] and []=
+// ============================================================================
+
+const BAG: &str = "class Bag\n\
+  def initialize\n    @items = {}\n  end\n\
+  def [](key)\n    if @items.has_key?(key)\n      return @items[key]\n    end\n    0\n  end\n\
+  def []=(key, value)\n    @items[key] = value\n  end\n\
+end\n\
+bag = Bag.new\n";
+
+#[test]
+fn instances_dispatch_to_their_index_methods() {
+    let vm = run(&format!(
+        "{}bag[\"a\"] = 2\nbag[\"a\"] += 3\na = bag[\"a\"]\nmissing = bag[\"z\"]",
+        BAG
+    ))
+    .unwrap();
+    assert_eq!(get(&vm, "a"), Some(Object::Int(5)));
+    assert_eq!(get(&vm, "missing"), Some(Object::Int(0)));
+}
+
+#[test]
+fn index_methods_answer_respond_to() {
+    let vm = run(&format!("{}x = bag.respond_to?(\"[]=\")", BAG)).unwrap();
+    assert_eq!(get(&vm, "x"), Some(Object::Bool(true)));
+}
+
+#[test]
+fn instances_without_index_methods_can_not_be_index_assigned() {
+    let err = run("class Plain\nend\np = Plain.new\np[0] = 1")
+        .err()
+        .unwrap();
+    assert!(err.contains("Cannot index assign into type"), "{}", err);
+}
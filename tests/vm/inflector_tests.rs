@@ -0,0 +1,143 @@
+use crate::common::{eval, eval_error};
+use metorex::inflector;
+use metorex::object::Object;
+
+// ============================================================================
+// Case conversion
+// ============================================================================
+
+#[test]
+fn underscore_splits_camel_case_and_keeps_acronyms_together() {
+    assert_eq!(inflector::underscore("UserName"), "user_name");
+    assert_eq!(inflector::underscore("userName"), "user_name");
+    assert_eq!(
+        inflector::underscore("HTTPServerError"),
+        "http_server_error"
+    );
+    assert_eq!(inflector::underscore("Version2Parser"), "version2_parser");
+    assert_eq!(inflector::underscore("Admin::UserName"), "admin/user_name");
+    assert_eq!(inflector::underscore("user-name"), "user_name");
+}
+
+#[test]
+fn camelize_joins_words_and_nests_paths() {
+    assert_eq!(inflector::camelize("user_name", true), "UserName");
+    assert_eq!(inflector::camelize("user_name", false), "userName");
+    assert_eq!(
+        inflector::camelize("admin/user_name", true),
+        "Admin::UserName"
+    );
+    assert_eq!(inflector::camelize("http-server", true), "HttpServer");
+}
+
+#[test]
+fn dasherize_and_titleize() {
+    assert_eq!(inflector::dasherize("user_name"), "user-name");
+    assert_eq!(inflector::titleize("user_name"), "User Name");
+    assert_eq!(inflector::titleize("HTTPServer"), "Http Server");
+}
+
+// ============================================================================
+// Plurals
+// ============================================================================
+
+#[test]
+fn pluralize_follows_suffix_rules() {
+    for (singular, plural) in [
+        ("user", "users"),
+        ("category", "categories"),
+        ("key", "keys"),
+        ("box", "boxes"),
+        ("match", "matches"),
+        ("status", "statuses"),
+        ("shelf", "shelves"),
+        ("analysis", "analyses"),
+    ] {
+        assert_eq!(inflector::pluralize(singular), plural);
+        assert_eq!(inflector::singularize(plural), singular);
+    }
+}
+
+#[test]
+fn irregular_and_uncountable_words_round_trip() {
+    for (singular, plural) in [
+        ("person", "people"),
+        ("child", "children"),
+        ("movie", "movies"),
+        ("sheep", "sheep"),
+        ("data", "data"),
+    ] {
+        assert_eq!(inflector::pluralize(singular), plural);
+        assert_eq!(inflector::singularize(plural), singular);
+    }
+    assert_eq!(inflector::pluralize("people"), "people");
+    assert_eq!(inflector::singularize("case"), "case");
+    assert_eq!(inflector::singularize("cases"), "case");
+    assert_eq!(inflector::singularize("class"), "class");
+}
+
+#[test]
+fn only_the_last_word_changes_and_keeps_its_case() {
+    assert_eq!(inflector::pluralize("user_category"), "user_categories");
+    assert_eq!(inflector::pluralize("LineItem"), "LineItems");
+    assert_eq!(inflector::pluralize("Person"), "People");
+    assert_eq!(inflector::pluralize("API"), "APIs");
+    assert_eq!(inflector::singularize("order-lines"), "order-line");
+}
+
+// ============================================================================
+// Inflector class
+// ============================================================================
+
+#[test]
+fn names_without_a_last_word_are_unchanged() {
+    assert_eq!(inflector::pluralize(""), "");
+    assert_eq!(inflector::singularize(""), "");
+    assert_eq!(inflector::pluralize("user_"), "user_");
+    assert_eq!(eval("Inflector.pluralize(\"\")"), Object::string(""));
+}
+
+#[test]
+fn inflector_methods_take_strings_or_symbols() {
+    assert_eq!(
+        eval("Inflector.camelize(:user_name)"),
+        Object::string("UserName")
+    );
+    assert_eq!(
+        eval(r#"Inflector.camelize("user_name", :lower)"#),
+        Object::string("userName")
+    );
+    assert_eq!(
+        eval(r#"Inflector.underscore("LineItem")"#),
+        Object::string("line_item")
+    );
+    assert_eq!(
+        eval(r#"Inflector.pluralize(:child)"#),
+        Object::string("children")
+    );
+    assert_eq!(
+        eval(r#"Inflector.singularize("Categories")"#),
+        Object::string("Category")
+    );
+    assert_eq!(
+        eval(r#"Inflector.titleize("line_item")"#),
+        Object::string("Line Item")
+    );
+    assert_eq!(
+        eval(r#"Inflector.dasherize("line_item")"#),
+        Object::string("line-item")
+    );
+}
+
+#[test]
+fn inflector_methods_reject_other_arguments() {
+    let error = eval_error("Inflector.underscore(5)");
+    assert!(error.contains("String or Symbol"), "{}", error);
+
+    let error = eval_error(r#"Inflector.camelize("a_b", :middle)"#);
+    assert!(
+        error.contains("camelize: first letter must be :upper or :lower, got :middle"),
+        "{}",
+        error
+    );
+}
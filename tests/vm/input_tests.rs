@@ -0,0 +1,264 @@
+use crate::common::run;
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+use std::io::Cursor;
+
+fn vm_with_input(input: &str) -> VirtualMachine {
+    let mut vm = VirtualMachine::new();
+    vm.set_input(Cursor::new(input.to_string()));
+    vm
+}
+
+fn entry(vm: &VirtualMachine, name: &str) -> Object {
+    vm.environment()
+        .get(name)
+        .unwrap_or_else(|| panic!("{} is not defined", name))
+}
+
+fn string_entry(vm: &VirtualMachine, name: &str) -> String {
+    match entry(vm, name) {
+        Object::String(value) => value.to_string(),
+        other => panic!("Expected String for {}, got {:?}", name, other),
+    }
+}
+
+// ============================================================================
+// gets
+// ============================================================================
+
+#[test]
+fn gets_returns_lines_with_their_newline() {
+    let mut vm = vm_with_input("first\nsecond");
+    run(&mut vm, "a = gets\nb = gets()").unwrap();
+
+    assert_eq!(string_entry(&vm, "a"), "first\n");
+    assert_eq!(string_entry(&vm, "b"), "second");
+}
+
+#[test]
+fn gets_returns_nil_at_end_of_input() {
+    let mut vm = vm_with_input("");
+    run(&mut vm, "line = gets\ndone = line == nil").unwrap();
+
+    assert_eq!(entry(&vm, "line"), Object::Nil);
+    assert_eq!(entry(&vm, "done"), Object::Bool(true));
+}
+
+#[test]
+fn gets_accepts_a_prompt() {
+    let mut vm = vm_with_input("Ada\n");
+    run(&mut vm, "name = gets(\"Name? \").chomp").unwrap();
+
+    assert_eq!(string_entry(&vm, "name"), "Ada");
+}
+
+#[test]
+fn gets_rejects_a_non_string_prompt() {
+    let mut vm = vm_with_input("Ada\n");
+    let err = run(&mut vm, "gets(1)").unwrap_err();
+
+    assert!(
+        err.contains("gets: prompt must be a String (got Int)"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn gets_chomp_to_i_pipeline() {
+    let mut vm = vm_with_input("42\r\n  -7 apples\n");
+    run(&mut vm, "a = gets.chomp.to_i\nb = gets.to_i\nsum = a + b").unwrap();
+
+    assert_eq!(entry(&vm, "a"), Object::Int(42));
+    assert_eq!(entry(&vm, "b"), Object::Int(-7));
+    assert_eq!(entry(&vm, "sum"), Object::Int(35));
+}
+
+#[test]
+fn gets_can_be_shadowed_by_a_local() {
+    let mut vm = vm_with_input("unused\n");
+    run(&mut vm, "gets = 5\nx = gets + 1").unwrap();
+
+    assert_eq!(entry(&vm, "x"), Object::Int(6));
+}
+
+#[test]
+fn reset_input_goes_back_to_stdin() {
+    let mut vm = vm_with_input("line\n");
+    vm.reset_input();
+    vm.set_input(Cursor::new("other\n".to_string()));
+    run(&mut vm, "x = gets").unwrap();
+
+    assert_eq!(string_entry(&vm, "x"), "other\n");
+}
+
+// ============================================================================
+// String#chomp and String#chop
+// ============================================================================
+
+#[test]
+fn chomp_removes_one_trailing_line_ending() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "a = \"hi\\n\".chomp\nb = \"hi\\r\\n\".chomp\nc = \"hi\\r\".chomp\nd = \"hi\\n\\n\".chomp\ne = \"hi\".chomp",
+    )
+    .unwrap();
+
+    assert_eq!(string_entry(&vm, "a"), "hi");
+    assert_eq!(string_entry(&vm, "b"), "hi");
+    assert_eq!(string_entry(&vm, "c"), "hi");
+    assert_eq!(string_entry(&vm, "d"), "hi\n");
+    assert_eq!(string_entry(&vm, "e"), "hi");
+}
+
+#[test]
+fn chomp_with_suffix_removes_only_that_suffix() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "a = \"hello.rb\".chomp(\".rb\")\nb = \"hello\".chomp(\"x\")",
+    )
+    .unwrap();
+
+    assert_eq!(string_entry(&vm, "a"), "hello");
+    assert_eq!(string_entry(&vm, "b"), "hello");
+}
+
+#[test]
+fn chop_treats_crlf_as_one_character() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "a = \"abc\".chop\nb = \"ab\\r\\n\".chop\nc = \"\".chop",
+    )
+    .unwrap();
+
+    assert_eq!(string_entry(&vm, "a"), "ab");
+    assert_eq!(string_entry(&vm, "b"), "ab");
+    assert_eq!(string_entry(&vm, "c"), "");
+}
+
+// ============================================================================
+// String#to_i and String#to_f
+// ============================================================================
+
+#[test]
+fn to_i_parses_the_leading_integer() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "a = \"123abc\".to_i\nb = \"  -45\".to_i\nc = \"+8\".to_i\nd = \"1_000\".to_i\ne = \"abc\".to_i",
+    )
+    .unwrap();
+
+    assert_eq!(entry(&vm, "a"), Object::Int(123));
+    assert_eq!(entry(&vm, "b"), Object::Int(-45));
+    assert_eq!(entry(&vm, "c"), Object::Int(8));
+    assert_eq!(entry(&vm, "d"), Object::Int(1000));
+    assert_eq!(entry(&vm, "e"), Object::Int(0));
+}
+
+#[test]
+fn to_i_accepts_a_base() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "a = \"ff\".to_i(16)\nb = \"1010\".to_i(2)\nc = \"z\".to_i(36)\nd = \"19\".to_i(8)",
+    )
+    .unwrap();
+
+    assert_eq!(entry(&vm, "a"), Object::Int(255));
+    assert_eq!(entry(&vm, "b"), Object::Int(10));
+    assert_eq!(entry(&vm, "c"), Object::Int(35));
+    assert_eq!(entry(&vm, "d"), Object::Int(1));
+}
+
+#[test]
+fn to_i_rejects_invalid_bases_and_overflow() {
+    let mut vm = VirtualMachine::new();
+
+    let err = run(&mut vm, "\"10\".to_i(1)").unwrap_err();
+    assert!(err.contains("base"), "{}", err);
+
+    let err = run(&mut vm, "\"10\".to_i(37)").unwrap_err();
+    assert!(err.contains("base"), "{}", err);
+
+    let err = run(&mut vm, "\"99999999999999999999\".to_i").unwrap_err();
+    assert!(err.contains("String#to_i value out of range"), "{}", err);
+}
+
+#[test]
+fn to_f_parses_the_leading_number() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "a = \"3.5kg\".to_f\nb = \" -2\".to_f\nc = \"1e3\".to_f\nd = \"abc\".to_f",
+    )
+    .unwrap();
+
+    assert_eq!(entry(&vm, "a"), Object::Float(3.5));
+    assert_eq!(entry(&vm, "b"), Object::Float(-2.0));
+    assert_eq!(entry(&vm, "c"), Object::Float(1000.0));
+    assert_eq!(entry(&vm, "d"), Object::Float(0.0));
+}
+
+// ============================================================================
+// Readline
+// ============================================================================
+
+#[test]
+fn readline_returns_lines_without_their_newline() {
+    let mut vm = vm_with_input("first\r\nsecond\n");
+    run(
+        &mut vm,
+        "a = Readline.readline(\"> \")\nb = Readline.readline",
+    )
+    .unwrap();
+
+    assert_eq!(string_entry(&vm, "a"), "first");
+    assert_eq!(string_entry(&vm, "b"), "second");
+}
+
+#[test]
+fn readline_returns_nil_at_end_of_input() {
+    let mut vm = vm_with_input("");
+    run(&mut vm, "line = Readline.readline(\"> \", true)").unwrap();
+
+    assert_eq!(entry(&vm, "line"), Object::Nil);
+}
+
+#[test]
+fn readline_adds_non_blank_lines_to_history_when_asked() {
+    let mut vm = vm_with_input("ls\n\n  \nhelp\npwd\n");
+    run(
+        &mut vm,
+        "5.times do |i|\n  Readline.readline(\"> \", i != 3)\nend\nh = Readline.history",
+    )
+    .unwrap();
+
+    assert_eq!(
+        entry(&vm, "h"),
+        Object::array(vec![Object::string("ls"), Object::string("pwd")])
+    );
+}
+
+#[test]
+fn readline_clear_history_forgets_earlier_lines() {
+    let mut vm = vm_with_input("ls\npwd\n");
+    run(
+        &mut vm,
+        "Readline.readline(\"\", true)\nReadline.clear_history\nReadline.readline(\"\", true)\nh = Readline.history",
+    )
+    .unwrap();
+
+    assert_eq!(entry(&vm, "h"), Object::array(vec![Object::string("pwd")]));
+}
+
+#[test]
+fn readline_rejects_a_non_string_prompt() {
+    let mut vm = vm_with_input("ls\n");
+    let err = run(&mut vm, "Readline.readline(1)").unwrap_err();
+
+    assert!(err.contains("String"), "{}", err);
+}
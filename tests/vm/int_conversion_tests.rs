@@ -0,0 +1,203 @@
+use crate::common::{eval, eval_in, run};
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+
+/// The value of `result` after running `source`
+fn result_of(source: &str) -> Object {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, source).unwrap();
+    vm.environment().get("result").unwrap()
+}
+
+/// The message of the TypeError `source` raises
+fn type_error(source: &str) -> Object {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        &format!(
+            "begin\n  {}\nrescue TypeError => e\n  message = e.message\nend",
+            source
+        ),
+    )
+    .unwrap();
+    vm.environment().get("message").unwrap_or(Object::Nil)
+}
+
+fn ints(values: &[i64]) -> Object {
+    Object::array(values.iter().copied().map(Object::Int).collect())
+}
+
+// ============================================================================
+// Array indexing
+// ============================================================================
+
+#[test]
+fn whole_floats_index_arrays() {
+    assert_eq!(eval("[10, 20, 30][1.0]"), Object::Int(20));
+    assert_eq!(eval("[10, 20, 30][-0.0]"), Object::Int(10));
+}
+
+#[test]
+fn whole_floats_index_array_assignment() {
+    assert_eq!(
+        result_of("a = [1, 2, 3]\na[0.0] = 9\nresult = a"),
+        ints(&[9, 2, 3])
+    );
+}
+
+#[test]
+fn fractional_float_indices_are_rejected() {
+    assert_eq!(
+        type_error("[1, 2][0.5]"),
+        Object::string("array index: 0.5 is not a whole number")
+    );
+    assert_eq!(
+        type_error("a = [1, 2]\n  a[1.25] = 3"),
+        Object::string("array index: 1.25 is not a whole number")
+    );
+}
+
+#[test]
+fn non_finite_float_indices_are_rejected() {
+    assert_eq!(
+        type_error("[1][Float::NAN]"),
+        Object::string("array index: NaN has no Int value")
+    );
+    assert_eq!(
+        type_error("[1][Float::INFINITY]"),
+        Object::string("array index: Infinity has no Int value")
+    );
+}
+
+#[test]
+fn objects_defining_to_int_index_arrays() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "class Second\n  def to_int\n    1\n  end\nend\nitems = [:a, :b, :c]",
+    )
+    .unwrap();
+    assert_eq!(
+        eval_in(&mut vm, "items[Second.new]"),
+        Object::Symbol("b".to_string().into())
+    );
+    run(&mut vm, "items[Second.new] = :z").unwrap();
+    assert_eq!(
+        eval_in(&mut vm, "items[1]"),
+        Object::Symbol("z".to_string().into())
+    );
+}
+
+#[test]
+fn to_int_must_return_an_int() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, "class Half\n  def to_int\n    0.5\n  end\nend").unwrap();
+    run(
+        &mut vm,
+        "begin\n  [1][Half.new]\nrescue TypeError => e\n  message = e.message\nend",
+    )
+    .unwrap();
+    assert_eq!(
+        vm.environment().get("message"),
+        Some(Object::string(
+            "array index: Half#to_int must return an Int (got Float)"
+        ))
+    );
+}
+
+#[test]
+fn dictionary_keys_match_equal_whole_floats() {
+    assert_eq!(
+        eval("{1 => :one}[1.0]"),
+        Object::Symbol("one".to_string().into())
+    );
+    assert_eq!(
+        result_of("h = {}\nh[2.0] = :two\nresult = h[2]"),
+        Object::Symbol("two".to_string().into())
+    );
+}
+
+// ============================================================================
+// Ranges
+// ============================================================================
+
+#[test]
+fn whole_float_bounds_iterate() {
+    assert_eq!(eval("(1.0..3.0).to_a"), ints(&[1, 2, 3]));
+    assert_eq!(eval("(0...2.0).map { |i| i * 10 }"), ints(&[0, 10]));
+    assert_eq!(
+        result_of("seen = []\nfor i in 1.0..2\n  seen.push(i)\nend\nresult = seen"),
+        ints(&[1, 2])
+    );
+    assert_eq!(
+        result_of("seen = []\n(2.0..3.0).each { |i| seen.push(i) }\nresult = seen"),
+        ints(&[2, 3])
+    );
+}
+
+#[test]
+fn fractional_bounds_are_rejected_when_iterating() {
+    assert_eq!(
+        type_error("(1..2.5).to_a"),
+        Object::string("range end: 2.5 is not a whole number")
+    );
+    assert_eq!(
+        type_error("for i in 0.5..2\n  i\n  end"),
+        Object::string("range start: 0.5 is not a whole number")
+    );
+}
+
+#[test]
+fn include_compares_like_case_when() {
+    assert_eq!(eval("(1..5).include?(2.5)"), Object::Bool(true));
+    assert_eq!(eval("(1.5..2.5).include?(2)"), Object::Bool(true));
+    assert_eq!(eval("(1...5).include?(5.0)"), Object::Bool(false));
+    assert_eq!(eval("(1..5).include?(\"3\")"), Object::Bool(false));
+}
+
+// ============================================================================
+// Builtin Int parameters
+// ============================================================================
+
+#[test]
+fn int_parameters_accept_whole_floats() {
+    assert_eq!(eval("\"ab\".center(6.0, \"*\")"), Object::string("**ab**"));
+    assert_eq!(eval("1.upto(3.0).to_a"), ints(&[1, 2, 3]));
+}
+
+#[test]
+fn int_parameters_accept_objects_defining_to_int() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, "class Four\n  def to_int\n    4\n  end\nend").unwrap();
+    assert_eq!(
+        eval_in(&mut vm, "\"x\".rjust(Four.new, \".\")"),
+        Object::string("...x")
+    );
+}
+
+#[test]
+fn int_parameters_reject_fractional_floats() {
+    assert_eq!(
+        type_error("\"ab\".center(6.5)"),
+        Object::string("center: width: 6.5 is not a whole number")
+    );
+    assert_eq!(
+        type_error("\"ab\".center(\"6\")"),
+        Object::string("center: width must be an Int (got String)")
+    );
+}
+
+// ============================================================================
+// to_int
+// ============================================================================
+
+#[test]
+fn numbers_define_to_int() {
+    assert_eq!(eval("7.to_int"), Object::Int(7));
+    assert_eq!(eval("-3.0.to_int"), Object::Int(-3));
+    assert_eq!(eval("2.9.to_i"), Object::Int(2));
+    assert_eq!(
+        type_error("2.9.to_int"),
+        Object::string("to_int: 2.9 is not a whole number")
+    );
+}
@@ -0,0 +1,137 @@
+use metorex::error::MetorexError;
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::{INTERRUPT_EXCEPTION, VirtualMachine};
+use std::thread;
+use std::time::Duration;
+
+fn run(vm: &mut VirtualMachine, source: &str) -> Result<Option<Object>, MetorexError> {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens).parse().expect("program should parse");
+    vm.execute_program(&program)
+}
+
+#[test]
+fn a_pending_interrupt_stops_the_next_program() {
+    let mut vm = VirtualMachine::new();
+    let handle = vm.handle();
+    handle.interrupt();
+    assert!(handle.is_interrupt_pending());
+
+    let error = run(&mut vm, "x = 1").err().unwrap();
+    assert!(error.is_interrupt());
+    assert!(!handle.is_interrupt_pending());
+    assert_eq!(vm.environment().get("x"), None);
+}
+
+#[test]
+fn an_interrupt_is_delivered_once() {
+    let mut vm = VirtualMachine::new();
+    vm.handle().interrupt();
+    assert!(run(&mut vm, "x = 1").is_err());
+
+    run(&mut vm, "x = 2").unwrap();
+    assert_eq!(vm.environment().get("x"), Some(Object::Int(2)));
+}
+
+#[test]
+fn other_errors_are_not_interrupts() {
+    let mut vm = VirtualMachine::new();
+    assert!(!run(&mut vm, "1 / 0").err().unwrap().is_interrupt());
+    assert!(!run(&mut vm, "raise \"boom\"").err().unwrap().is_interrupt());
+}
+
+#[test]
+fn another_thread_can_interrupt_a_runaway_loop() {
+    let mut vm = VirtualMachine::new();
+    let handle = vm.handle();
+    let interrupter = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        handle.interrupt();
+    });
+
+    let error = run(&mut vm, "n = 0\nwhile true\n  n = n + 1\nend")
+        .err()
+        .unwrap();
+    interrupter.join().unwrap();
+    assert!(error.is_interrupt());
+    assert!(matches!(vm.environment().get("n"), Some(Object::Int(n)) if n > 0));
+}
+
+#[test]
+fn an_empty_loop_body_is_still_interruptible() {
+    let mut vm = VirtualMachine::new();
+    let handle = vm.handle();
+    let interrupter = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        handle.interrupt();
+    });
+
+    let error = run(&mut vm, "while true\nend").err().unwrap();
+    interrupter.join().unwrap();
+    assert!(error.is_interrupt());
+}
+
+#[test]
+fn scripts_can_rescue_an_interrupt() {
+    let mut vm = VirtualMachine::new();
+    let handle = vm.handle();
+    let interrupter = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        handle.interrupt();
+    });
+
+    run(
+        &mut vm,
+        "begin\n  while true\n    busy = 1\n  end\nrescue Interrupt => e\n  caught = e.message\n  kind = Interrupt === e\nend",
+    )
+    .unwrap();
+    interrupter.join().unwrap();
+    assert_eq!(
+        vm.environment().get("caught"),
+        Some(Object::string("interrupted"))
+    );
+    assert_eq!(vm.environment().get("kind"), Some(Object::Bool(true)));
+}
+
+#[test]
+fn interrupt_is_not_a_standard_error() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "a = StandardError === Interrupt.new(\"x\")\nb = Exception === Interrupt.new(\"x\")",
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("a"), Some(Object::Bool(false)));
+    assert_eq!(vm.environment().get("b"), Some(Object::Bool(true)));
+    assert_eq!(INTERRUPT_EXCEPTION, "Interrupt");
+}
+
+#[test]
+fn top_level_expressions_check_for_interrupts() {
+    let mut vm = VirtualMachine::new();
+    vm.handle().interrupt();
+    assert!(
+        run(&mut vm, "puts(\"never\")")
+            .err()
+            .unwrap()
+            .is_interrupt()
+    );
+}
+
+#[test]
+fn an_interrupt_cuts_a_sleep_short() {
+    let mut vm = VirtualMachine::new();
+    let handle = vm.handle();
+    let interrupter = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        handle.interrupt();
+    });
+
+    let started = std::time::Instant::now();
+    let error = run(&mut vm, "sleep(30)").err().unwrap();
+    interrupter.join().unwrap();
+    assert!(error.is_interrupt());
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
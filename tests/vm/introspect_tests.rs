@@ -0,0 +1,81 @@
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+use metorex::vm::introspect::{dump_state, render};
+
+fn run(source: &str) -> VirtualMachine {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens).parse().expect("program should parse");
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program).expect("program should run");
+    vm
+}
+
+#[test]
+fn dump_of_an_empty_program_lists_nothing() {
+    let vm = run("");
+    assert_eq!(dump_state(&vm), "variables:\nclasses:\n");
+}
+
+#[test]
+fn dump_lists_variables_by_name_with_quoted_strings() {
+    let vm = run("zeta = 1.0\nalpha = \"hi\"\nlist = [1, :sym, nil]\nh = {\"b\" => 2, \"a\" => 1}");
+    assert_eq!(
+        dump_state(&vm),
+        "variables:\n  alpha = \"hi\"\n  h = {\"b\" => 2, \"a\" => 1}\n  list = [1, :sym, nil]\n  zeta = 1.0\nclasses:\n"
+    );
+}
+
+#[test]
+fn dump_includes_rebound_globals() {
+    let vm = run("puts = 3");
+    assert_eq!(dump_state(&vm), "variables:\n  puts = 3\nclasses:\n");
+}
+
+#[test]
+fn dump_expands_user_classes_reachable_from_variables() {
+    let vm = run("class Animal\n  def speak\n    \"...\"\n  end\nend\n\
+         class Dog < Animal\n  @@count = 0\n  def initialize(name)\n    @name = name\n  end\n  def speak\n    \"Woof\"\n  end\nend\n\
+         rex = Dog.new(\"Rex\")\n\
+         def greet(who, greeting)\n  greeting\nend");
+    let dump = dump_state(&vm);
+    assert_eq!(
+        dump,
+        "variables:\n  \
+           Animal = <class Animal>\n  \
+           Dog = <class Dog>\n  \
+           greet = <method greet(who, greeting)>\n  \
+           rex = #<Dog @name=\"Rex\">\n\
+         classes:\n  \
+           class Animal\n    \
+             def speak()\n  \
+           class Dog < Animal\n    \
+             @@count = 0\n    \
+             def initialize(name)\n    \
+             def speak()\n"
+    );
+}
+
+#[test]
+fn dump_is_identical_for_repeated_runs() {
+    let source = "h = {\"z\" => 1, \"a\" => [1.5, nil]}\nclass Point\n  def initialize(x, y)\n    @y = y\n    @x = x\n  end\nend\np = Point.new(1, 2)";
+    let dump = dump_state(&run(source));
+    assert_eq!(dump, dump_state(&run(source)));
+    assert!(dump.contains("  p = #<Point @x=1, @y=2>\n"), "{}", dump);
+}
+
+#[test]
+fn render_marks_recursive_structures() {
+    let vm = run("a = [1]\na.push(a)");
+    let a = vm.environment().get("a").unwrap();
+    assert_eq!(render(&a), "[1, [...]]");
+}
+
+#[test]
+fn render_distinguishes_strings_and_floats() {
+    assert_eq!(render(&Object::string("1")), "\"1\"");
+    assert_eq!(render(&Object::Int(1)), "1");
+    assert_eq!(render(&Object::Float(1.0)), "1.0");
+    assert_eq!(render(&Object::Float(f64::NAN)), "NaN");
+}
@@ -0,0 +1,69 @@
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+
+fn eval(source: &str, name: &str) -> Object {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens).parse().expect("program should parse");
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program).expect("program should run");
+    vm.environment()
+        .get(name)
+        .expect("variable should be defined")
+}
+
+#[test]
+fn and_returns_the_first_falsy_operand_or_the_last() {
+    assert_eq!(eval("x = 1 && 2", "x"), Object::Int(2));
+    assert_eq!(eval("x = nil && 2", "x"), Object::Nil);
+    assert_eq!(eval("x = false and 2", "x"), Object::Bool(false));
+}
+
+#[test]
+fn or_returns_the_first_truthy_operand_or_the_last() {
+    assert_eq!(eval("x = nil || 5", "x"), Object::Int(5));
+    assert_eq!(eval("x = 0 || 5", "x"), Object::Int(0));
+    assert_eq!(eval("x = false or nil", "x"), Object::Nil);
+}
+
+#[test]
+fn the_right_operand_is_only_evaluated_when_needed() {
+    let source = "calls = []\n\
+                  def touch(calls, value)\n  calls.push(value)\n  value\nend\n\
+                  false && touch(calls, 1)\n\
+                  true || touch(calls, 2)\n\
+                  true && touch(calls, 3)\n\
+                  nil or touch(calls, 4)";
+    assert_eq!(
+        eval(source, "calls"),
+        Object::array(vec![Object::Int(3), Object::Int(4)])
+    );
+}
+
+#[test]
+fn a_short_circuited_operand_can_not_raise() {
+    assert_eq!(eval("x = nil && undefined_name", "x"), Object::Nil);
+    assert_eq!(eval("x = 1 || 1 / 0", "x"), Object::Int(1));
+}
+
+#[test]
+fn not_returns_a_boolean() {
+    assert_eq!(eval("x = !nil", "x"), Object::Bool(true));
+    assert_eq!(eval("x = !0", "x"), Object::Bool(false));
+    assert_eq!(eval("x = not 1 == 2", "x"), Object::Bool(true));
+    assert_eq!(eval("x = !!\"text\"", "x"), Object::Bool(true));
+}
+
+#[test]
+fn conditions_combine_with_logical_operators() {
+    let source = "a = 3\nx = :no\nif a > 1 && a < 5 and not a == 4\n  x = :yes\nend";
+    assert_eq!(eval(source, "x"), Object::Symbol("yes".to_string().into()));
+}
+
+#[test]
+fn blocks_may_still_declare_empty_parameters() {
+    let source = "f = lambda do ||\n  42\nend\nx = f.call\ny = [1].map { || 7 }";
+    assert_eq!(eval(source, "x"), Object::Int(42));
+    assert_eq!(eval(source, "y"), Object::array(vec![Object::Int(7)]));
+}
@@ -455,5 +455,12 @@ fn float_round_with_non_integer_precision_fails() {
     }];
 
     let result = vm.execute_program(&statements);
-    assert!(matches!(result, Err(MetorexError::TypeError { .. })));
+    let Err(MetorexError::UncaughtException { message, .. }) = result else {
+        panic!("expected a TypeError, got {:?}", result);
+    };
+    assert!(
+        message.contains("TypeError: round: precision must be an Int (got String)"),
+        "{}",
+        message
+    );
 }
@@ -0,0 +1,156 @@
+// Tests for Method#source, Method#source_location and Method#ast
+
+use crate::common::temp_dir;
+use metorex::ast::Statement;
+use metorex::lexer::{Lexer, Span};
+use metorex::object::{Object, to_object};
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+use serde::Serialize;
+use std::fs;
+
+fn run(source: &str) -> Option<Object> {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens).parse().expect("program should parse");
+    let mut vm = VirtualMachine::new();
+    vm.set_current_source(Some(source));
+    vm.execute_program(&program).unwrap()
+}
+
+const SOURCE: &str = "x = 1\n\ndef add(a, b)\n  # sum\n  a + b\nend\n";
+
+#[test]
+fn test_source_returns_the_definition_text() {
+    let result = run(&format!("{}method(:add).source", SOURCE));
+    assert_eq!(
+        result,
+        Some(Object::string("def add(a, b)\n  # sum\n  a + b\nend"))
+    );
+}
+
+#[test]
+fn test_source_covers_nested_blocks() {
+    let source = "def check(n)\n  if n > 0\n    while n > 0 do\n      n = n - 1\n    end\n  end\n  n\nend\nmethod(:check).source";
+    let result = run(source);
+    assert_eq!(
+        result,
+        Some(Object::string(&source[..source.find("\nmethod").unwrap()]))
+    );
+}
+
+#[test]
+fn test_source_is_nil_without_source_text() {
+    let tokens = Lexer::new(SOURCE).tokenize();
+    let mut program = Parser::new(tokens).parse().unwrap();
+    let tokens = Lexer::new("method(:add).source").tokenize();
+    program.extend(Parser::new(tokens).parse().unwrap());
+
+    let mut vm = VirtualMachine::new();
+    assert_eq!(vm.execute_program(&program).unwrap(), Some(Object::Nil));
+}
+
+#[test]
+fn test_source_location_without_file() {
+    let result = run(&format!("{}method(:add).source_location", SOURCE));
+    assert_eq!(
+        result,
+        Some(Object::array(vec![Object::Nil, Object::Int(3)]))
+    );
+}
+
+#[test]
+fn test_source_location_names_the_file() {
+    let dir = temp_dir("method_source");
+    let path = dir.join("defs.mx");
+    fs::write(&path, format!("{}method(:add).source_location", SOURCE)).unwrap();
+    let canonical = path.canonicalize().unwrap();
+
+    let mut vm = VirtualMachine::new();
+    let result = vm.execute_file(&path).unwrap();
+    assert_eq!(
+        result,
+        Object::array(vec![
+            Object::string(canonical.to_string_lossy()),
+            Object::Int(3)
+        ])
+    );
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_ast_describes_the_definition() {
+    let result = run(&format!(
+        "{}a = method(:add).ast\n[a[\"type\"], a[\"name\"], a[\"parameters\"][1][\"name\"], a[\"body\"].length]",
+        SOURCE
+    ));
+    assert_eq!(
+        result,
+        Some(Object::array(vec![
+            Object::Symbol("FunctionDef".to_string().into()),
+            Object::string("add"),
+            Object::string("b"),
+            Object::Int(1)
+        ]))
+    );
+}
+
+#[test]
+fn test_class_methods_remember_their_source() {
+    let source = "class Point\n  def norm\n    0\n  end\nend\n";
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens).parse().unwrap();
+    let mut vm = VirtualMachine::new();
+    vm.set_current_source(Some(source));
+    vm.execute_program(&program).unwrap();
+
+    let Some(Object::Class(class)) = vm.environment().get("Point") else {
+        panic!("Point should be a class");
+    };
+    let method = class.find_method("norm").unwrap();
+    assert_eq!(method.source_text(), Some("def norm\n    0\n  end"));
+    assert_eq!(method.source_span(), Some(Span::new(14, 34)));
+}
+
+#[test]
+fn test_parse_first_statement_reports_its_span() {
+    let source = "\n  def f\n    1\n  end\nputs 2\n";
+    let tokens = Lexer::new(source).tokenize();
+    let (statement, span) = Parser::new(tokens).parse_first_statement().unwrap();
+    assert!(matches!(statement, Statement::FunctionDef { .. }));
+    assert_eq!(span.slice(source), "def f\n    1\n  end");
+}
+
+#[derive(Serialize)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Pair(i64, i64),
+    Rect { width: i64, height: i64 },
+}
+
+#[test]
+fn test_to_object_maps_enum_variants() {
+    let shapes = vec![
+        Shape::Empty,
+        Shape::Circle(1.5),
+        Shape::Pair(1, 2),
+        Shape::Rect {
+            width: 3,
+            height: 4,
+        },
+    ];
+    let object = to_object(&shapes).unwrap();
+    assert_eq!(
+        object.to_string(),
+        "[:Empty, {type: :Circle, value: 1.5}, {type: :Pair, values: [1, 2]}, \
+         {type: :Rect, width: 3, height: 4}]"
+    );
+}
+
+#[test]
+fn test_to_object_rejects_composite_map_keys() {
+    let mut map = std::collections::BTreeMap::new();
+    map.insert(vec![1], "one");
+    let err = to_object(&map).unwrap_err();
+    assert_eq!(err.to_string(), "an array cannot be used as a dict key");
+}
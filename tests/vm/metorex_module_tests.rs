@@ -0,0 +1,125 @@
+use crate::common::{eval, eval_in, run};
+use metorex::object::Object;
+use metorex::vm::{VirtualMachine, VmOptions};
+use std::path::PathBuf;
+
+fn symbol_key(name: &str) -> String {
+    format!(":{}", name)
+}
+
+#[test]
+fn version_matches_the_crate() {
+    assert_eq!(
+        eval("Metorex::VERSION"),
+        Object::string(env!("CARGO_PKG_VERSION"))
+    );
+}
+
+#[test]
+fn features_list_the_compiled_in_cargo_features() {
+    let mut expected = Vec::new();
+    if cfg!(feature = "sqlite") {
+        expected.push(Object::string("sqlite"));
+    }
+    if cfg!(feature = "toml") {
+        expected.push(Object::string("toml"));
+    }
+    if cfg!(feature = "yaml") {
+        expected.push(Object::string("yaml"));
+    }
+    assert_eq!(eval("Metorex.features"), Object::array(expected));
+}
+
+#[test]
+fn feature_query_accepts_strings_and_symbols() {
+    assert_eq!(
+        eval("Metorex.feature?(:sqlite)"),
+        Object::Bool(cfg!(feature = "sqlite"))
+    );
+    assert_eq!(
+        eval("Metorex.feature?(\"yaml\")"),
+        Object::Bool(cfg!(feature = "yaml"))
+    );
+    assert_eq!(eval("Metorex.feature?(:jit)"), Object::Bool(false));
+}
+
+#[test]
+fn scripts_can_branch_on_a_missing_feature() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "if Metorex.feature?(:toml)
+  mode = \"parse\"
+else
+  mode = \"skip\"
+end",
+    )
+    .unwrap();
+    let expected = if cfg!(feature = "toml") {
+        "parse"
+    } else {
+        "skip"
+    };
+    assert_eq!(vm.environment().get("mode"), Some(Object::string(expected)));
+}
+
+#[test]
+fn vm_options_reflect_the_host_configuration() {
+    let options = VmOptions {
+        optimization_level: 2,
+        seed: Some(42),
+        check_types: true,
+        ..VmOptions::default()
+    };
+    let mut vm = VirtualMachine::with_options(options);
+    let Object::Dict(map) = eval_in(&mut vm, "Metorex.vm_options") else {
+        panic!("expected a Hash");
+    };
+    let map = map.borrow();
+    let keys: Vec<&str> = map.keys().map(String::as_str).collect();
+    assert_eq!(
+        keys,
+        vec![
+            ":stack_depth",
+            ":opt_level",
+            ":warnings",
+            ":seed",
+            ":ast_cache",
+            ":check_types",
+            ":contracts",
+            ":case_insensitive_paths",
+            ":load_path"
+        ]
+    );
+    assert_eq!(map[&symbol_key("opt_level")], Object::Int(2));
+    assert_eq!(map[&symbol_key("seed")], Object::Int(42));
+    assert_eq!(map[&symbol_key("check_types")], Object::Bool(true));
+    assert_eq!(map[&symbol_key("warnings")], Object::Bool(false));
+}
+
+#[test]
+fn load_path_is_the_running_files_directory() {
+    assert_eq!(eval("Metorex.load_path"), Object::array(vec![]));
+
+    let mut vm = VirtualMachine::new();
+    vm.set_current_file(PathBuf::from("scripts/report.mx"));
+    assert_eq!(
+        eval_in(&mut vm, "Metorex.load_path"),
+        Object::array(vec![Object::string("scripts")])
+    );
+
+    vm.set_current_file(PathBuf::from("report.mx"));
+    assert_eq!(
+        eval_in(&mut vm, "Metorex.load_path"),
+        Object::array(vec![Object::string(".")])
+    );
+}
+
+#[test]
+fn metorex_methods_check_their_arguments() {
+    let mut vm = VirtualMachine::new();
+    let error = run(&mut vm, "Metorex.features(1)").unwrap_err();
+    assert!(error.contains("features"), "{}", error);
+    let error = run(&mut vm, "Metorex.feature?(1)").unwrap_err();
+    assert!(error.contains("feature?"), "{}", error);
+}
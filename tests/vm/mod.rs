@@ -1,4 +1,61 @@
+mod argument_buffer_tests;
+mod array_sort_tests;
+mod builtin_signature_tests;
+mod case_equality_tests;
+mod code_generation_tests;
+mod contracts_tests;
+mod data_format_tests;
+mod decimal_tests;
+mod definitions_tests;
+mod duration_tests;
+mod embedding_tests;
+mod encoding_tests;
+mod enumerator_tests;
+mod eval_tests;
+mod event_log_tests;
+mod file_io_tests;
+mod float_semantics_tests;
+mod glob_matching_tests;
+mod hash_method_tests;
+mod heap_tests;
+mod host_method_tests;
+mod index_assignment_tests;
+mod inflector_tests;
+mod input_tests;
+mod int_conversion_tests;
+mod interrupt_tests;
+mod introspect_tests;
+mod logical_operator_tests;
 mod method_dispatch_tests;
+mod method_source_tests;
+mod metorex_module_tests;
+mod number_format_tests;
+mod numeric_method_tests;
+mod object_identity_tests;
+mod output_tests;
+mod post_mortem_tests;
+mod reentrancy_tests;
+mod reload_tests;
+mod resource_tests;
+mod schema_tests;
+mod sqlite_tests;
+mod string_builder_tests;
+mod string_format_tests;
+mod string_interning_tests;
+mod string_interpolation_tests;
+mod string_iteration_tests;
+mod string_layout_tests;
+mod symbol_tests;
+mod syntax_tree_tests;
+mod teardown_tests;
+mod text_table_tests;
+mod timeout_tests;
+mod trace_point_tests;
+mod type_check_tests;
+mod type_feedback_tests;
+mod typed_result_tests;
+mod uri_tests;
 mod vm_expression_tests;
 mod vm_initialization_tests;
+mod vm_options_tests;
 mod vm_statement_tests;
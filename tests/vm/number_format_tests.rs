@@ -0,0 +1,194 @@
+use crate::common::{eval, eval_error, run};
+use metorex::number_format::{NumberFormat, ParsedNumber, format_float, humanize, parse_number};
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+
+// ============================================================================
+// Formatting
+// ============================================================================
+
+#[test]
+fn integer_format_groups_thousands() {
+    assert_eq!(eval("1234567.format"), Object::string("1,234,567"));
+    assert_eq!(eval("999.format"), Object::string("999"));
+    assert_eq!(eval("(-1000).format"), Object::string("-1,000"));
+    assert_eq!(eval("0.format"), Object::string("0"));
+}
+
+#[test]
+fn integer_format_pads_requested_decimals() {
+    assert_eq!(
+        eval("1234.format(precision: 2)"),
+        Object::string("1,234.00")
+    );
+}
+
+#[test]
+fn float_format_defaults_to_two_decimals() {
+    assert_eq!(eval("1234567.891.format"), Object::string("1,234,567.89"));
+    assert_eq!(eval("0.5.format"), Object::string("0.50"));
+    assert_eq!(eval("1234.5.format(precision: 0)"), Object::string("1,234"));
+}
+
+#[test]
+fn float_format_accepts_custom_delimiter_and_separator() {
+    assert_eq!(
+        eval(r#"1234567.5.format(delimiter: ".", separator: ",", precision: 1)"#),
+        Object::string("1.234.567,5")
+    );
+    assert_eq!(
+        eval(r#"1234567.5.format(delimiter: "")"#),
+        Object::string("1234567.50")
+    );
+}
+
+#[test]
+fn float_format_drops_sign_of_values_rounding_to_zero() {
+    assert_eq!(eval("(-0.001).format"), Object::string("0.00"));
+    assert_eq!(eval("(-1234.5).format"), Object::string("-1,234.50"));
+}
+
+#[test]
+fn float_format_writes_non_finite_values_by_name() {
+    let format = NumberFormat::with_precision(2);
+    assert_eq!(format_float(f64::NAN, &format), "NaN");
+    assert_eq!(format_float(f64::NEG_INFINITY, &format), "-Infinity");
+}
+
+#[test]
+fn format_rejects_invalid_options() {
+    assert!(eval_error("1.format(precision: -1)").contains("precision must be an Int"));
+    assert!(eval_error("1.format(precision: 21)").contains("from 0 to 20"));
+    assert!(eval_error("1.format(width: 3)").contains("unknown option width"));
+    assert!(eval_error(r#"1.format(delimiter: ".")"#).contains("must differ"));
+    assert!(eval_error("1.format(delimiter: 5)").contains("delimiter must be a String"));
+}
+
+#[test]
+fn invalid_options_raise_a_rescuable_argument_error() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "begin
+  1.format(precision: -1)
+rescue ArgumentError => e
+  precision_message = e.message
+end
+begin
+  1.format(width: 3)
+rescue ArgumentError => e
+  width_message = e.message
+end",
+    )
+    .unwrap();
+    let precision_message = vm
+        .environment()
+        .get("precision_message")
+        .unwrap()
+        .to_string();
+    assert!(
+        precision_message.contains("precision must be an Int"),
+        "{}",
+        precision_message
+    );
+    let width_message = vm.environment().get("width_message").unwrap().to_string();
+    assert!(
+        width_message.contains("unknown option width"),
+        "{}",
+        width_message
+    );
+}
+
+// ============================================================================
+// Humanizing
+// ============================================================================
+
+#[test]
+fn humanize_scales_to_a_magnitude_suffix() {
+    let format = NumberFormat::with_precision(1);
+    assert_eq!(humanize(1_234_567.0, &format), "1.2M");
+    assert_eq!(humanize(999.0, &format), "999");
+    assert_eq!(humanize(1000.0, &format), "1K");
+    assert_eq!(humanize(-1500.0, &format), "-1.5K");
+    assert_eq!(humanize(7_300_000_000.0, &format), "7.3B");
+    assert_eq!(humanize(2.5e15, &format), "2,500T");
+}
+
+#[test]
+fn humanize_carries_rounding_into_the_next_magnitude() {
+    let format = NumberFormat::with_precision(1);
+    assert_eq!(humanize(999_950.0, &format), "1M");
+    assert_eq!(humanize(999_949.0, &format), "999.9K");
+    assert_eq!(humanize(f64::INFINITY, &format), "Infinity");
+}
+
+#[test]
+fn numbers_humanize_with_options() {
+    assert_eq!(eval("1234567.humanize"), Object::string("1.2M"));
+    assert_eq!(eval("12.345.humanize"), Object::string("12.3"));
+    assert_eq!(
+        eval("1234567.humanize(precision: 2)"),
+        Object::string("1.23M")
+    );
+    assert_eq!(
+        eval("1234567.humanize(precision: 2, delimiter: \".\", separator: \",\")"),
+        Object::string("1,23M")
+    );
+    assert_eq!(eval("1500.5.humanize(precision: 0)"), Object::string("2K"));
+    assert!(eval_error("1.humanize(scale: 2)").contains("unknown option scale"));
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+#[test]
+fn to_number_reads_delimited_integers_and_floats() {
+    assert_eq!(eval(r#""1,234,567".to_number"#), Object::Int(1_234_567));
+    assert_eq!(
+        eval(r#""1,234,567.89".to_number"#),
+        Object::Float(1_234_567.89)
+    );
+    assert_eq!(eval(r#"" -42 ".to_number"#), Object::Int(-42));
+    assert_eq!(eval(r#""1.5e3".to_number"#), Object::Float(1500.0));
+}
+
+#[test]
+fn to_number_accepts_custom_delimiter_and_separator() {
+    assert_eq!(
+        eval(r#""1.234.567,5".to_number(delimiter: ".", separator: ",")"#),
+        Object::Float(1_234_567.5)
+    );
+}
+
+#[test]
+fn to_number_returns_nil_for_malformed_text() {
+    for text in ["", "abc", "1,,000", ",100", "100,", "1.2.3", "12abc", "-"] {
+        assert_eq!(
+            eval(&format!("{:?}.to_number", text)),
+            Object::Nil,
+            "{:?}",
+            text
+        );
+    }
+}
+
+#[test]
+fn to_number_rejects_precision_option() {
+    assert!(eval_error(r#""1".to_number(precision: 2)"#).contains("unknown option precision"));
+}
+
+#[test]
+fn formatted_numbers_parse_back() {
+    let format = NumberFormat::with_precision(3);
+    let text = format_float(-9_876_543.125, &format);
+    assert_eq!(text, "-9,876,543.125");
+    assert_eq!(
+        parse_number(&text, ",", "."),
+        Some(ParsedNumber::Float(-9_876_543.125))
+    );
+    assert_eq!(
+        parse_number("99,999,999,999,999,999,999", ",", "."),
+        Some(ParsedNumber::Float(1e20))
+    );
+}
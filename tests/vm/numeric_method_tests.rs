@@ -0,0 +1,194 @@
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+
+fn run(source: &str) -> Result<VirtualMachine, String> {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens)
+        .parse()
+        .map_err(|e| format!("{:?}", e))?;
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program).map_err(|e| format!("{}", e))?;
+    Ok(vm)
+}
+
+fn ints(values: &[i64]) -> Object {
+    Object::array(values.iter().map(|v| Object::Int(*v)).collect())
+}
+
+// ============================================================================
+// Integer literal methods
+// ============================================================================
+
+#[test]
+fn integer_times_yields_each_index() {
+    let vm = run(r#"
+seen = []
+result = 3.times do |i|
+  seen.push(i)
+end
+"#)
+    .unwrap();
+    assert_eq!(vm.environment().get("seen"), Some(ints(&[0, 1, 2])));
+    assert_eq!(vm.environment().get("result"), Some(Object::Int(3)));
+}
+
+#[test]
+fn integer_times_without_block_enumerates_values() {
+    let vm = run("values = 4.times.to_a").unwrap();
+    assert_eq!(vm.environment().get("values"), Some(ints(&[0, 1, 2, 3])));
+}
+
+#[test]
+fn integer_times_supports_break() {
+    let vm = run(r#"
+count = 0
+10.times do |i|
+  if i == 2
+    break
+  end
+  count += 1
+end
+"#)
+    .unwrap();
+    assert_eq!(vm.environment().get("count"), Some(Object::Int(2)));
+}
+
+#[test]
+fn integer_upto_and_downto() {
+    let vm = run(r#"
+up = 1.upto(3).to_a
+down = 3.downto(1).to_a
+total = 0
+1.upto(4) do |i|
+  total += i
+end
+"#)
+    .unwrap();
+    assert_eq!(vm.environment().get("up"), Some(ints(&[1, 2, 3])));
+    assert_eq!(vm.environment().get("down"), Some(ints(&[3, 2, 1])));
+    assert_eq!(vm.environment().get("total"), Some(Object::Int(10)));
+}
+
+#[test]
+fn integer_upto_requires_integer_limit() {
+    let error = run(r#"1.upto("3")"#).err().unwrap();
+    assert!(
+        error.contains("upto: limit must be an Int (got String)"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn integer_abs_parity_and_gcd() {
+    let vm = run(r#"
+n = -7
+magnitudes = [n.abs, 7.abs, 0.abs]
+parity = [4.even?, 4.odd?, n.even?, n.odd?, 0.even?]
+divisors = [12.gcd(18), n.gcd(21), 5.gcd(0), 0.gcd(0)]
+"#)
+    .unwrap();
+    assert_eq!(vm.environment().get("magnitudes"), Some(ints(&[7, 7, 0])));
+    assert_eq!(
+        vm.environment().get("parity"),
+        Some(Object::array(
+            [true, false, false, true, true]
+                .into_iter()
+                .map(Object::Bool)
+                .collect()
+        ))
+    );
+    assert_eq!(vm.environment().get("divisors"), Some(ints(&[6, 7, 5, 0])));
+}
+
+#[test]
+fn integer_to_s_takes_an_optional_base() {
+    let vm = run(r#"
+n = -255
+written = [255.to_s, 255.to_s(16), n.to_s(2), 35.to_s(36), 0.to_s(8)]
+"#)
+    .unwrap();
+    assert_eq!(
+        vm.environment().get("written").unwrap().to_string(),
+        "[255, ff, -11111111, z, 0]"
+    );
+}
+
+#[test]
+fn integer_numeric_methods_check_their_arguments() {
+    let error = run("3.to_s(37)").err().unwrap();
+    assert!(
+        error.contains("ArgumentError: Integer#to_s base must be between 2 and 36, got 37"),
+        "{}",
+        error
+    );
+    let error = run(r#"12.gcd("4")"#).err().unwrap();
+    assert!(
+        error.contains("gcd: other must be an Int (got String)"),
+        "{}",
+        error
+    );
+    let error = run("4.even?(1)").err().unwrap();
+    assert!(error.contains("even?"), "{}", error);
+}
+
+// ============================================================================
+// Float literal methods
+// ============================================================================
+
+#[test]
+fn float_literal_rounding_methods() {
+    let vm = run(r#"
+floored = 3.14.floor
+ceiled = 3.14.ceil
+rounded = 2.5.round
+precise = 2.567.round(2)
+truncated = -2.7.to_i
+"#)
+    .unwrap();
+    assert_eq!(vm.environment().get("floored"), Some(Object::Int(3)));
+    assert_eq!(vm.environment().get("ceiled"), Some(Object::Int(4)));
+    assert_eq!(vm.environment().get("rounded"), Some(Object::Int(3)));
+    assert_eq!(vm.environment().get("precise"), Some(Object::Float(2.57)));
+    assert_eq!(vm.environment().get("truncated"), Some(Object::Int(-2)));
+}
+
+#[test]
+fn float_abs() {
+    let vm = run("x = -2.5\nvalues = [x.abs, 1.5.abs]").unwrap();
+    assert_eq!(
+        vm.environment().get("values"),
+        Some(Object::array(vec![Object::Float(2.5), Object::Float(1.5)]))
+    );
+}
+
+#[test]
+fn numeric_literals_respond_to_their_methods() {
+    let vm = run(r#"
+int_times = 3.respond_to?(:times)
+float_floor = 1.5.respond_to?(:floor)
+"#)
+    .unwrap();
+    assert_eq!(vm.environment().get("int_times"), Some(Object::Bool(true)));
+    assert_eq!(
+        vm.environment().get("float_floor"),
+        Some(Object::Bool(true))
+    );
+}
+
+#[test]
+fn range_literal_after_integer_is_not_a_method_call() {
+    let vm = run(r#"
+r = 5..10
+values = r.to_a
+size = (5...10).to_a.length
+"#)
+    .unwrap();
+    assert_eq!(
+        vm.environment().get("values"),
+        Some(ints(&[5, 6, 7, 8, 9, 10]))
+    );
+    assert_eq!(vm.environment().get("size"), Some(Object::Int(5)));
+}
@@ -0,0 +1,200 @@
+use crate::common::{eval, eval_error, eval_in, run};
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+
+// ============================================================================
+// object_id
+// ============================================================================
+
+#[test]
+fn object_id_is_stable_for_an_object() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, "a = [1, 2]\nb = a").unwrap();
+    assert_eq!(
+        eval_in(&mut vm, "a.object_id == a.object_id"),
+        Object::Bool(true)
+    );
+    assert_eq!(
+        eval_in(&mut vm, "a.object_id == b.object_id"),
+        Object::Bool(true)
+    );
+}
+
+#[test]
+fn equal_values_have_different_ids() {
+    assert_eq!(
+        eval("[1, 2].object_id == [1, 2].object_id"),
+        Object::Bool(false)
+    );
+    assert_eq!(eval("{}.object_id == {}.object_id"), Object::Bool(false));
+}
+
+#[test]
+fn immediates_have_fixed_ids() {
+    assert_eq!(eval("false.object_id"), Object::Int(0));
+    assert_eq!(eval("nil.object_id"), Object::Int(8));
+    assert_eq!(eval("true.object_id"), Object::Int(20));
+    assert_eq!(eval("3.object_id"), Object::Int(7));
+    assert_eq!(eval("(0 - 1).object_id"), Object::Int(-1));
+}
+
+#[test]
+fn ids_are_the_same_in_every_run() {
+    let ids = || {
+        let mut vm = VirtualMachine::new();
+        run(&mut vm, "class Point\nend").unwrap();
+        eval_in(
+            &mut vm,
+            "[Point.new.object_id, [].object_id, Point.object_id]",
+        )
+    };
+    assert_eq!(ids(), ids());
+}
+
+#[test]
+fn ids_of_freed_objects_are_not_reused() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "ids = {}
+i = 0
+while i < 50
+  ids[Object.new.object_id] = true
+  GC.start
+  i += 1
+end",
+    )
+    .unwrap();
+    assert_eq!(eval_in(&mut vm, "ids.length"), Object::Int(50));
+}
+
+#[test]
+fn object_id_takes_no_arguments() {
+    let error = eval_error("[].object_id(1)");
+    assert!(error.contains("object_id"), "{}", error);
+}
+
+// ============================================================================
+// equal?
+// ============================================================================
+
+#[test]
+fn equal_compares_identity_not_value() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, "a = \"text\"\nb = a\nc = \"te\" + \"xt\"").unwrap();
+    assert_eq!(eval_in(&mut vm, "a.equal?(b)"), Object::Bool(true));
+    assert_eq!(eval_in(&mut vm, "a == c"), Object::Bool(true));
+    assert_eq!(eval_in(&mut vm, "a.equal?(c)"), Object::Bool(false));
+}
+
+#[test]
+fn equal_holds_for_immediates_of_the_same_value() {
+    assert_eq!(eval("5.equal?(5)"), Object::Bool(true));
+    assert_eq!(eval("nil.equal?(nil)"), Object::Bool(true));
+    assert_eq!(eval("nil.equal?(false)"), Object::Bool(false));
+}
+
+#[test]
+fn equal_works_for_user_instances() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, "class Point\nend\np = Point.new\nq = Point.new").unwrap();
+    assert_eq!(eval_in(&mut vm, "p.equal?(p)"), Object::Bool(true));
+    assert_eq!(eval_in(&mut vm, "p.equal?(q)"), Object::Bool(false));
+}
+
+// ============================================================================
+// Hash.identity
+// ============================================================================
+
+#[test]
+fn identity_hash_keeps_equal_keys_apart() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "a = [1, 2]
+b = [1, 2]
+h = Hash.identity
+h[a] = \"a\"
+h[b] = \"b\"",
+    )
+    .unwrap();
+    assert_eq!(eval_in(&mut vm, "h.length"), Object::Int(2));
+    assert_eq!(eval_in(&mut vm, "h[a]"), Object::string("a"));
+    assert_eq!(eval_in(&mut vm, "h[b]"), Object::string("b"));
+    assert_eq!(eval_in(&mut vm, "h.key?([1, 2])"), Object::Bool(false));
+    assert_eq!(eval_in(&mut vm, "h.keys[0].equal?(a)"), Object::Bool(true));
+}
+
+#[test]
+fn identity_hash_supports_the_hash_readers() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "h = Hash.identity
+h[:x] = 1
+h[2] = 3",
+    )
+    .unwrap();
+    assert_eq!(
+        eval_in(&mut vm, "h.values"),
+        Object::array(vec![Object::Int(1), Object::Int(3)])
+    );
+    assert_eq!(
+        eval_in(&mut vm, "h.entries[1]"),
+        Object::array(vec![Object::Int(2), Object::Int(3)])
+    );
+    assert_eq!(eval_in(&mut vm, "h.delete(2)"), Object::Int(3));
+    assert_eq!(eval_in(&mut vm, "h.delete(2)"), Object::Nil);
+    assert_eq!(eval_in(&mut vm, "h.size"), Object::Int(1));
+}
+
+#[test]
+fn identity_mode_is_reported() {
+    assert_eq!(eval("Hash.identity.identity?"), Object::Bool(true));
+    assert_eq!(eval("{}.identity?"), Object::Bool(false));
+    assert_eq!(
+        eval("Hash.identity.class.name"),
+        Object::string("IdentityHash")
+    );
+}
+
+#[test]
+fn missing_identity_keys_raise() {
+    let error = eval_error("h = Hash.identity\nh[[1]]");
+    assert!(error.contains("not found"), "{}", error);
+}
+
+#[test]
+fn identity_hash_detects_cycles_in_deep_dup() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "def deep_dup(value, seen)
+  if value.class != Array
+    return value
+  end
+  if seen.key?(value)
+    return seen[value]
+  end
+  seen[value] = []
+  value.each do |item|
+    seen[value].push(deep_dup(item, seen))
+  end
+  seen[value]
+end
+original = [1, [2]]
+original.push(original)
+copy = deep_dup(original, Hash.identity)",
+    )
+    .unwrap();
+    assert_eq!(eval_in(&mut vm, "copy.length"), Object::Int(3));
+    assert_eq!(eval_in(&mut vm, "copy[2].equal?(copy)"), Object::Bool(true));
+    assert_eq!(
+        eval_in(&mut vm, "copy[2].equal?(original)"),
+        Object::Bool(false)
+    );
+    assert_eq!(
+        eval_in(&mut vm, "copy[1].equal?(original[1])"),
+        Object::Bool(false)
+    );
+}
@@ -0,0 +1,71 @@
+use metorex::lexer::Lexer;
+use metorex::parser::Parser;
+use metorex::vm::{LoggedEvent, VirtualMachine};
+use std::cell::RefCell;
+use std::io::{self, Cursor, Write};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+/// A writer whose contents the test can read after the VM wrote to it
+#[derive(Clone, Default)]
+struct Captured(Rc<RefCell<Vec<u8>>>);
+
+impl Captured {
+    fn text(&self) -> String {
+        String::from_utf8(self.0.borrow().clone()).unwrap()
+    }
+}
+
+impl Write for Captured {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn run(vm: &mut VirtualMachine, source: &str) {
+    let program = Parser::new(Lexer::new(source).tokenize()).parse().unwrap();
+    vm.execute_program(&program).unwrap();
+}
+
+#[test]
+fn test_puts_pp_and_prompts_write_to_the_output() {
+    let output = Captured::default();
+    let mut vm = VirtualMachine::new();
+    vm.set_input(Cursor::new("Ada\n"));
+    vm.set_output(output.clone());
+    run(
+        &mut vm,
+        "puts(\"one\", 2)\npp([1, {\"a\" => nil}])\nname = gets(\"Name? \")\nputs(name.chomp)",
+    );
+    assert_eq!(output.text(), "one\n2\n[1, {\"a\" => nil}]\nName? Ada\n");
+}
+
+#[test]
+fn test_reset_output_stops_capturing() {
+    let output = Captured::default();
+    let mut vm = VirtualMachine::new();
+    vm.set_output(output.clone());
+    run(&mut vm, "puts(1)");
+    vm.reset_output();
+    run(&mut vm, "x = 2");
+    assert_eq!(output.text(), "1\n");
+}
+
+#[test]
+fn test_captured_output_is_still_recorded() {
+    let source = "puts(\"logged\")";
+    let mut vm = VirtualMachine::new();
+    vm.set_output(Captured::default());
+    vm.start_recording(PathBuf::from("script.mx"), source);
+    let program = Parser::new(Lexer::new(source).tokenize()).parse().unwrap();
+    let result = vm.execute_program(&program);
+    let log = vm.finish_recording(&result).unwrap();
+    assert!(log.events.contains(&LoggedEvent::Output {
+        text: "logged\n".to_string()
+    }));
+}
@@ -0,0 +1,156 @@
+// Tests for recording the frames an uncaught error unwinds through
+
+use metorex::error::MetorexError;
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::{MAIN_FRAME, PostMortem, VirtualMachine};
+
+const ACCOUNT: &str = r#"
+class Account
+  def initialize(balance)
+    @balance = balance
+  end
+
+  def withdraw(amount)
+    remaining = @balance - amount
+    if remaining < 0
+      raise "insufficient funds"
+    end
+    @balance = remaining
+  end
+end
+
+def pay(account, amount)
+  note = "paying"
+  account.withdraw(amount)
+end
+
+acct = Account.new(10)
+"#;
+
+fn run(vm: &mut VirtualMachine, source: &str) -> Result<Option<Object>, MetorexError> {
+    let program = Parser::new(Lexer::new(source).tokenize()).parse().unwrap();
+    vm.execute_program(&program)
+}
+
+/// Run `source` after ACCOUNT with post-mortem recording on, expecting it to fail
+fn post_mortem(vm: &mut VirtualMachine, source: &str) -> PostMortem {
+    vm.set_post_mortem(true);
+    run(vm, ACCOUNT).unwrap();
+    let error = run(vm, source).unwrap_err();
+    vm.take_post_mortem(&error)
+        .expect("post-mortem recording is on")
+}
+
+fn frame_names(post_mortem: &PostMortem) -> Vec<&str> {
+    post_mortem
+        .frames
+        .iter()
+        .map(|frame| frame.name())
+        .collect()
+}
+
+fn exception_message(exception: &Object) -> String {
+    match exception {
+        Object::Exception(exception) => exception.borrow().message.clone(),
+        other => panic!("Expected an exception, got {:?}", other),
+    }
+}
+
+#[test]
+fn frames_run_from_the_raise_to_the_top_level() {
+    let mut vm = VirtualMachine::new();
+    let post_mortem = post_mortem(&mut vm, "pay(acct, 50)");
+
+    assert_eq!(
+        frame_names(&post_mortem),
+        vec!["Account#withdraw", "pay", MAIN_FRAME]
+    );
+    assert_eq!(
+        exception_message(&post_mortem.exception),
+        "insufficient funds"
+    );
+
+    let raising = post_mortem.frames[0].scope();
+    assert_eq!(raising.borrow().get("remaining"), Some(Object::Int(-40)));
+    assert!(raising.borrow().get("self").is_some());
+    assert_eq!(
+        post_mortem.frames[1].scope().borrow().get("note"),
+        Some(Object::string("paying"))
+    );
+}
+
+#[test]
+fn entering_a_frame_evaluates_in_its_scope() {
+    let mut vm = VirtualMachine::new();
+    let post_mortem = post_mortem(&mut vm, "pay(acct, 50)");
+
+    vm.enter_frame(&post_mortem.frames[0]);
+    let value = run(&mut vm, "[amount, remaining, @balance, acct]").unwrap();
+    let Some(Object::Array(values)) = value else {
+        panic!("Expected an Array");
+    };
+    assert_eq!(
+        values.borrow()[..3],
+        [Object::Int(50), Object::Int(-40), Object::Int(10)]
+    );
+
+    vm.enter_frame(&post_mortem.frames[2]);
+    assert!(run(&mut vm, "remaining").is_err());
+    assert_eq!(vm.environment().current_depth(), 0);
+}
+
+#[test]
+fn block_frames_are_recorded() {
+    let mut vm = VirtualMachine::new();
+    let post_mortem = post_mortem(&mut vm, "[60].each do |amount|\n  pay(acct, amount)\nend");
+
+    assert_eq!(post_mortem.frames.len(), 4);
+    assert_eq!(post_mortem.frames[1].name(), "pay");
+    assert_eq!(
+        post_mortem.frames[2].scope().borrow().get("amount"),
+        Some(Object::Int(60))
+    );
+}
+
+#[test]
+fn rescued_errors_do_not_leave_frames_behind() {
+    let mut vm = VirtualMachine::new();
+    let post_mortem = post_mortem(
+        &mut vm,
+        "begin\n  pay(acct, 50)\nrescue\nend\nacct.missing_method",
+    );
+
+    assert_eq!(frame_names(&post_mortem), vec![MAIN_FRAME]);
+    assert!(
+        exception_message(&post_mortem.exception).contains("missing_method"),
+        "{:?}",
+        post_mortem.exception
+    );
+}
+
+#[test]
+fn top_level_raise_keeps_its_exception() {
+    let mut vm = VirtualMachine::new();
+    let post_mortem = post_mortem(
+        &mut vm,
+        "class Oops < StandardError\nend\nraise Oops, \"top\"",
+    );
+
+    assert_eq!(frame_names(&post_mortem), vec![MAIN_FRAME]);
+    let Object::Exception(exception) = &post_mortem.exception else {
+        panic!("Expected an exception");
+    };
+    assert_eq!(exception.borrow().exception_type, "Oops");
+    assert_eq!(exception.borrow().message, "top");
+}
+
+#[test]
+fn nothing_is_recorded_while_disabled() {
+    let mut vm = VirtualMachine::new();
+    assert!(!vm.post_mortem_enabled());
+    run(&mut vm, ACCOUNT).unwrap();
+    let error = run(&mut vm, "pay(acct, 50)").unwrap_err();
+    assert!(vm.take_post_mortem(&error).is_none());
+}
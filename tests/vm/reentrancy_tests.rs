@@ -0,0 +1,103 @@
+// Mutating an array while iterating it: iteration methods walk a snapshot,
+// each_mutating! walks the live array, and nothing panics on a re-borrow.
+
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+
+fn run(source: &str) -> Result<VirtualMachine, String> {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens)
+        .parse()
+        .map_err(|e| format!("{:?}", e))?;
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program).map_err(|e| format!("{}", e))?;
+    Ok(vm)
+}
+
+fn get(vm: &VirtualMachine, name: &str) -> Option<Object> {
+    vm.environment().get(name)
+}
+
+fn ints(values: &[i64]) -> Object {
+    Object::array(values.iter().map(|v| Object::Int(*v)).collect())
+}
+
+#[test]
+fn each_visits_a_snapshot_while_the_block_pushes() {
+    let vm =
+        run("a = [1, 2]\nseen = []\na.each do |x|\n  seen.push(x)\n  a.push(x * 10)\nend").unwrap();
+    assert_eq!(get(&vm, "seen"), Some(ints(&[1, 2])));
+    assert_eq!(get(&vm, "a"), Some(ints(&[1, 2, 10, 20])));
+}
+
+#[test]
+fn each_visits_a_snapshot_while_the_block_pops() {
+    let vm = run("a = [1, 2, 3]\nseen = []\na.each do |x|\n  seen.push(x)\n  a.pop\nend").unwrap();
+    assert_eq!(get(&vm, "seen"), Some(ints(&[1, 2, 3])));
+    assert_eq!(get(&vm, "a"), Some(ints(&[])));
+}
+
+#[test]
+fn map_select_and_reduce_iterate_snapshots() {
+    let vm = run("a = [1, 2]\n\
+         m = a.map do |x|\n  a[0] = 9\n  x\nend\n\
+         b = [1, 2, 3]\n\
+         s = b.select do |x|\n  b.pop\n  true\nend\n\
+         c = [1, 2]\n\
+         r = c.reduce(0) do |acc, x|\n  c.push(x)\n  acc + x\nend")
+    .unwrap();
+    assert_eq!(get(&vm, "m"), Some(ints(&[1, 2])));
+    assert_eq!(get(&vm, "a"), Some(ints(&[9, 2])));
+    assert_eq!(get(&vm, "s"), Some(ints(&[1, 2, 3])));
+    assert_eq!(get(&vm, "r"), Some(Object::Int(3)));
+    assert_eq!(get(&vm, "c"), Some(ints(&[1, 2, 1, 2])));
+}
+
+#[test]
+fn each_mutating_visits_appended_and_replaced_elements() {
+    let vm = run("a = [1, 2]\nseen = []\na.each_mutating! do |x|\n  seen.push(x)\n  if x < 3\n    a.push(x + 2)\n  end\n  if x == 1\n    a[1] = 5\n  end\nend").unwrap();
+    assert_eq!(get(&vm, "seen"), Some(ints(&[1, 5, 3])));
+    assert_eq!(get(&vm, "a"), Some(ints(&[1, 5, 3])));
+}
+
+#[test]
+fn each_mutating_stops_at_break() {
+    let vm = run("a = [1]\na.each_mutating! do |x|\n  if a.length > 4\n    break\n  end\n  a.push(x + 1)\nend").unwrap();
+    assert_eq!(get(&vm, "a"), Some(ints(&[1, 2, 3, 4, 5])));
+}
+
+#[test]
+fn each_mutating_raises_when_the_array_shrinks() {
+    let error = run("a = [1, 2, 3]\na.each_mutating! do |x|\n  a.pop\nend")
+        .err()
+        .unwrap();
+    assert!(
+        error.contains("ConcurrentModificationError: Array shrank during each_mutating!"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn concurrent_modification_errors_can_be_rescued() {
+    let vm = run("a = [1, 2, 3]\n\
+         begin\n  a.each_mutating! do |x|\n    a.pop\n  end\n\
+         rescue ConcurrentModificationError => e\n  first = e.message\nend\n\
+         begin\n  a.each_mutating! do |x|\n    a.pop\n  end\n\
+         rescue RuntimeError\n  second = \"as RuntimeError\"\nend")
+    .unwrap();
+    assert_eq!(
+        get(&vm, "first"),
+        Some(Object::string("Array shrank during each_mutating!"))
+    );
+    assert_eq!(get(&vm, "second"), Some(Object::string("as RuntimeError")));
+}
+
+#[test]
+fn reading_the_array_inside_each_is_allowed() {
+    let vm = run("a = [1, 2, 3]\ntotal = 0\na.each do |x|\n  total = total + a.length + a[0]\nend")
+        .unwrap();
+    assert_eq!(get(&vm, "total"), Some(Object::Int(12)));
+}
@@ -0,0 +1,145 @@
+// Tests for reload! and VirtualMachine::reload_file
+
+use crate::common::temp_dir;
+use metorex::ast::Statement;
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+use std::fs;
+
+fn parse(source: &str) -> Vec<Statement> {
+    let tokens = Lexer::new(source).tokenize();
+    Parser::new(tokens).parse().expect("program should parse")
+}
+
+fn greeter(greeting: &str) -> String {
+    format!(
+        "class Greeter\n  def hi\n    \"{}\"\n  end\nend\n",
+        greeting
+    )
+}
+
+#[test]
+fn test_reload_is_registered_globally() {
+    let vm = VirtualMachine::new();
+    assert_eq!(
+        vm.environment().get("reload!"),
+        Some(Object::NativeFunction("reload!".to_string()))
+    );
+}
+
+#[test]
+fn test_reload_redefines_methods_for_existing_instances() {
+    let dir = temp_dir("reload_instances");
+    let helper = dir.join("helper.mx");
+    let main = dir.join("main.mx");
+    fs::write(&helper, greeter("old")).unwrap();
+    fs::write(&main, "require_relative \"helper\"\ng = Greeter.new\n").unwrap();
+
+    let mut vm = VirtualMachine::new();
+    vm.execute_file(&main).unwrap();
+    fs::write(&helper, greeter("new")).unwrap();
+    vm.reload_file(&helper).unwrap();
+
+    let result = vm
+        .execute_program(&parse("[g.hi, Greeter.new.hi]"))
+        .unwrap();
+    assert_eq!(
+        result,
+        Some(Object::array(vec![
+            Object::string("new"),
+            Object::string("new")
+        ]))
+    );
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_require_relative_does_not_rerun_a_loaded_file() {
+    let dir = temp_dir("reload_require");
+    let helper = dir.join("helper.mx");
+    let main = dir.join("main.mx");
+    fs::write(&helper, greeter("old")).unwrap();
+    fs::write(&main, "require_relative \"helper\"\n").unwrap();
+
+    let mut vm = VirtualMachine::new();
+    vm.execute_file(&main).unwrap();
+    fs::write(&helper, greeter("new")).unwrap();
+    vm.set_current_file(main.clone());
+    let result = vm
+        .execute_program(&parse("require_relative(\"helper\")\nGreeter.new.hi"))
+        .unwrap();
+
+    assert_eq!(result, Some(Object::string("old")));
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_reload_function_resolves_relative_to_current_file() {
+    let dir = temp_dir("reload_relative");
+    let helper = dir.join("helper.mx");
+    let main = dir.join("main.mx");
+    fs::write(&helper, greeter("old")).unwrap();
+    fs::write(&main, "require_relative \"helper\"\n").unwrap();
+
+    let mut vm = VirtualMachine::new();
+    vm.execute_file(&main).unwrap();
+    fs::write(&helper, greeter("new")).unwrap();
+    vm.set_current_file(main.clone());
+    let result = vm
+        .execute_program(&parse(
+            "loaded = reload!(\"helper\")\n[loaded, Greeter.new.hi]",
+        ))
+        .unwrap();
+
+    assert_eq!(
+        result,
+        Some(Object::array(vec![
+            Object::Bool(true),
+            Object::string("new")
+        ]))
+    );
+    assert!(!vm.is_reloading());
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_reload_keeps_subclasses_attached() {
+    let dir = temp_dir("reload_subclass");
+    let base = dir.join("base.mx");
+    fs::write(&base, greeter("old")).unwrap();
+
+    let mut vm = VirtualMachine::new();
+    vm.execute_file(&base).unwrap();
+    vm.execute_program(&parse("class Loud < Greeter\nend\nl = Loud.new"))
+        .unwrap();
+    fs::write(&base, greeter("new")).unwrap();
+    vm.reload_file(&base).unwrap();
+
+    let result = vm.execute_program(&parse("l.hi")).unwrap();
+    assert_eq!(result, Some(Object::string("new")));
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_class_definition_outside_reload_replaces_the_class() {
+    let mut vm = VirtualMachine::new();
+    let program = parse(&format!(
+        "{}g = Greeter.new\n{}g.hi",
+        greeter("old"),
+        greeter("new")
+    ));
+    let result = vm.execute_program(&program).unwrap();
+    assert_eq!(result, Some(Object::string("old")));
+}
+
+#[test]
+fn test_reload_missing_file_fails() {
+    let dir = temp_dir("reload_missing");
+    let mut vm = VirtualMachine::new();
+    let err = vm.reload_file(&dir.join("nope.mx")).unwrap_err();
+    assert!(err.to_string().contains("Failed to find file"));
+    assert!(!vm.is_reloading());
+    fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,137 @@
+// Tests for the `with` builtin, which releases a resource once its block is done
+
+use crate::common::run;
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+use std::cell::Cell;
+use std::rc::Rc;
+
+fn execute(source: &str) -> Result<VirtualMachine, String> {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, source)?;
+    Ok(vm)
+}
+
+const RESOURCE_CLASSES: &str = r#"
+class Connection
+  attr_reader :closed
+
+  def initialize()
+    @closed = false
+  end
+
+  def query()
+    "rows"
+  end
+
+  def close()
+    @closed = true
+  end
+end
+
+class Lock
+  attr_reader :released
+
+  def initialize()
+    @released = false
+  end
+
+  def release()
+    @released = true
+  end
+end
+"#;
+
+#[test]
+fn with_yields_resource_and_returns_block_value() {
+    let source = format!(
+        "{}\nconn = Connection.new\nresult = with(conn) do |c|\n  c.query\nend\nclosed = conn.closed\n",
+        RESOURCE_CLASSES
+    );
+    let vm = execute(&source).unwrap();
+    assert_eq!(vm.environment().get("result"), Some(Object::string("rows")));
+    assert_eq!(vm.environment().get("closed"), Some(Object::Bool(true)));
+}
+
+#[test]
+fn with_closes_resource_when_block_raises() {
+    let source = format!(
+        r#"{}
+conn = Connection.new
+message = nil
+begin
+  with(conn) do |c|
+    raise "query failed"
+  end
+rescue => e
+  message = e.message
+end
+closed = conn.closed
+"#,
+        RESOURCE_CLASSES
+    );
+    let vm = execute(&source).unwrap();
+    assert_eq!(
+        vm.environment().get("message"),
+        Some(Object::string("query failed"))
+    );
+    assert_eq!(vm.environment().get("closed"), Some(Object::Bool(true)));
+}
+
+#[test]
+fn with_falls_back_to_release() {
+    let source = format!(
+        "{}\nlock = Lock.new\nwith(lock) do |l|\n  l\nend\nreleased = lock.released\n",
+        RESOURCE_CLASSES
+    );
+    let vm = execute(&source).unwrap();
+    assert_eq!(vm.environment().get("released"), Some(Object::Bool(true)));
+}
+
+#[test]
+fn with_rejects_resource_without_close_or_release() {
+    let error = execute("with(42) do |n|\n  n\nend").err().unwrap();
+    assert!(error.contains("must respond to 'close' or 'release'"));
+}
+
+#[test]
+fn with_requires_a_block() {
+    let error = execute(&format!("{}\nwith(Connection.new)", RESOURCE_CLASSES))
+        .err()
+        .unwrap();
+    assert!(error.contains("with() expects a resource and a block"));
+}
+
+#[test]
+fn with_error_from_block_takes_precedence_over_close_error() {
+    let source = r#"
+class Broken
+  def close()
+    raise "close failed"
+  end
+end
+
+with(Broken.new) do |b|
+  raise "block failed"
+end
+"#;
+    let error = execute(source).err().unwrap();
+    assert!(error.contains("block failed"));
+}
+
+#[test]
+fn with_releases_host_defined_resources() {
+    let closes = Rc::new(Cell::new(0));
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, "class Handle\nend").unwrap();
+
+    let counter = Rc::clone(&closes);
+    vm.define_native_method("Handle", "close", move |_receiver, _args| {
+        counter.set(counter.get() + 1);
+        Ok(Object::Nil)
+    })
+    .unwrap();
+
+    run(&mut vm, "with(Handle.new) do |h|\n  h\nend").unwrap();
+    assert_eq!(closes.get(), 1);
+}
@@ -0,0 +1,233 @@
+use crate::common::run;
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+
+/// The problems `schema` finds in `value`, each as `path: message`
+fn problems(schema: &str, value: &str) -> Vec<String> {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        &format!(
+            "schema = {}\nresult = schema.validate({}).map {{ |e| e.to_s }}",
+            schema, value
+        ),
+    )
+    .unwrap();
+    match vm.environment().get("result") {
+        Some(Object::Array(items)) => items.borrow().iter().map(|e| e.to_string()).collect(),
+        other => panic!("expected an Array, got {:?}", other),
+    }
+}
+
+const USER: &str = r#"Schema.hash(name: Schema.string, age: Schema.int.optional, role: Schema.one_of("admin", "guest"))"#;
+
+// ============================================================================
+// Types and fields
+// ============================================================================
+
+#[test]
+fn scalar_schemas_check_the_type() {
+    assert!(problems("Schema.string", r#""ok""#).is_empty());
+    assert_eq!(
+        problems("Schema.int", r#""7""#),
+        vec!["expected Int, got String"]
+    );
+    assert!(problems("Schema.number", "2.5").is_empty());
+    assert!(problems("Schema.number", r#"Decimal.new("2.5")"#).is_empty());
+    assert_eq!(problems("Schema.bool", "1"), vec!["expected Bool, got Int"]);
+    assert_eq!(problems("Schema.any", "nil"), vec!["is required"]);
+}
+
+#[test]
+fn hash_schemas_report_missing_and_mistyped_fields() {
+    assert!(problems(USER, r#"{"name" => "ann", "role" => "admin"}"#).is_empty());
+    assert_eq!(
+        problems(USER, r#"{"age" => "3", "role" => "root"}"#),
+        vec![
+            "name: is required",
+            "age: expected Int, got String",
+            r#"role: expected one of "admin", "guest", got "root""#,
+        ]
+    );
+}
+
+#[test]
+fn hash_schemas_find_fields_by_string_or_symbol_key() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"
+schema = Schema.hash(name: Schema.string)
+by_symbol = {}
+by_symbol[:name] = "ann"
+found = schema.valid?(by_symbol)
+"#,
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("found"), Some(Object::Bool(true)));
+}
+
+#[test]
+fn nested_problems_report_their_path() {
+    let config = format!("Schema.hash(users: Schema.array({}))", USER);
+    assert_eq!(
+        problems(
+            &config,
+            r#"{"users" => [{"name" => "ann", "role" => "guest"}, {"name" => "bo", "role" => "guest"}, {"name" => "cy", "age" => 1.5, "role" => "guest"}]}"#
+        ),
+        vec!["users[2].age: expected Int, got Float"]
+    );
+    assert_eq!(
+        problems(&config, r#"{"users" => "everyone"}"#),
+        vec!["users: expected Array, got String"]
+    );
+}
+
+#[test]
+fn strict_hashes_reject_unknown_fields() {
+    let schema = "Schema.hash(port: Schema.int).strict";
+    assert!(problems(schema, r#"{"port" => 80}"#).is_empty());
+    assert_eq!(
+        problems(schema, r#"{"port" => 80, "prot" => 81}"#),
+        vec!["prot: is not allowed"]
+    );
+    assert!(
+        problems(
+            "Schema.hash(port: Schema.int)",
+            r#"{"port" => 80, "prot" => 81}"#
+        )
+        .is_empty()
+    );
+}
+
+#[test]
+fn of_accepts_instances_of_a_class_and_its_subclasses() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"
+class Shape
+end
+class Circle < Shape
+end
+schema = Schema.of(Shape)
+circle = schema.valid?(Circle.new)
+text = schema.validate("round")[0].message
+int = Schema.of(Integer).valid?(3)
+"#,
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("circle"), Some(Object::Bool(true)));
+    assert_eq!(
+        vm.environment().get("text"),
+        Some(Object::string("expected Shape, got String"))
+    );
+    assert_eq!(vm.environment().get("int"), Some(Object::Bool(true)));
+}
+
+// ============================================================================
+// Refining schemas
+// ============================================================================
+
+#[test]
+fn where_adds_checks_that_run_once_the_type_is_right() {
+    let port = r#"Schema.int.where("must be a port") { |n| n > 0 }.where("must be a port") { |n| n < 65536 }"#;
+    assert!(problems(port, "8080").is_empty());
+    assert_eq!(problems(port, "70000"), vec!["must be a port"]);
+    assert_eq!(problems(port, r#""80""#), vec!["expected Int, got String"]);
+    assert_eq!(
+        problems("Schema.string.where { |s| s.length > 0 }", r#""""#),
+        vec!["is invalid"]
+    );
+}
+
+#[test]
+fn refining_returns_a_copy() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"
+base = Schema.int
+loose = base.optional
+checked = base.where { |n| n > 0 }
+result = [base.optional?, loose.optional?, base.valid?(-1), checked.valid?(-1), loose.type]
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        vm.environment().get("result"),
+        Some(Object::array(vec![
+            Object::Bool(false),
+            Object::Bool(true),
+            Object::Bool(true),
+            Object::Bool(false),
+            vm.symbol("int"),
+        ]))
+    );
+}
+
+// ============================================================================
+// Results and errors
+// ============================================================================
+
+#[test]
+fn validate_bang_returns_the_value_or_raises_value_error() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        &format!(
+            r#"
+user = {}
+kept = user.validate!({{"name" => "ann", "role" => "guest"}})
+message = nil
+begin
+  user.validate!({{"role" => "guest", "age" => "x"}})
+rescue ValueError => e
+  message = e.message
+end
+"#,
+            USER
+        ),
+    )
+    .unwrap();
+    assert!(matches!(
+        vm.environment().get("kept"),
+        Some(Object::Dict(_))
+    ));
+    assert_eq!(
+        vm.environment().get("message"),
+        Some(Object::string(
+            "invalid value: name: is required; age: expected Int, got String"
+        ))
+    );
+}
+
+#[test]
+fn schema_errors_expose_path_and_message() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"
+error = Schema.hash(age: Schema.int).validate({"age" => nil})[0]
+path = error.path
+message = error.message
+"#,
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("path"), Some(Object::string("age")));
+    assert_eq!(
+        vm.environment().get("message"),
+        Some(Object::string("is required"))
+    );
+}
+
+#[test]
+fn builders_reject_values_that_are_not_schemas() {
+    let mut vm = VirtualMachine::new();
+    let error = run(&mut vm, "Schema.hash(name: String)").unwrap_err();
+    assert!(
+        error.contains("hash: 'name' must be a Schema, got Class"),
+        "{}",
+        error
+    );
+}
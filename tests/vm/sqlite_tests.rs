@@ -0,0 +1,178 @@
+use crate::common::eval_error;
+#[cfg(feature = "sqlite")]
+use metorex::object::Object;
+
+#[test]
+fn sqlite_open_checks_its_arguments() {
+    assert!(eval_error("SQLite.open(1)").contains("String"));
+    assert!(eval_error("SQLite.open").contains("argument"));
+}
+
+#[cfg(not(feature = "sqlite"))]
+#[test]
+fn sqlite_reports_missing_feature() {
+    let err = eval_error(r#"SQLite.open(":memory:")"#);
+    assert!(err.contains("built without the sqlite feature"), "{}", err);
+}
+
+#[cfg(feature = "sqlite")]
+const SCHEMA: &str = r#"
+db = SQLite.open(":memory:")
+db.execute_batch("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, score REAL);")
+"#;
+
+#[cfg(feature = "sqlite")]
+fn with_database(source: &str) -> VirtualMachine {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, &format!("{}{}", SCHEMA, source)).unwrap();
+    vm
+}
+
+#[cfg(feature = "sqlite")]
+fn row(entries: &[(&str, Object)]) -> Object {
+    Object::dict(
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect(),
+    )
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn execute_returns_rows_as_hashes() {
+    let vm = with_database(
+        r#"
+db.execute("INSERT INTO users (name, score) VALUES (?, ?)", ["ann", 1.5])
+id = db.last_insert_row_id
+rows = db.execute("SELECT id, name, score FROM users")
+"#,
+    );
+    assert_eq!(vm.environment().get("id"), Some(Object::Int(1)));
+    assert_eq!(
+        vm.environment().get("rows"),
+        Some(Object::array(vec![row(&[
+            ("id", Object::Int(1)),
+            ("name", Object::string("ann")),
+            ("score", Object::Float(1.5)),
+        ])]))
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn prepared_statements_bind_named_parameters() {
+    let vm = with_database(
+        r#"
+insert = db.prepare("INSERT INTO users (name, score) VALUES (:name, :score)")
+insert.execute(name: "bob", score: 2.0)
+insert.execute({"name" => "cy", "score" => nil})
+changed = db.changes
+sql = insert.sql
+rows = db.execute("SELECT name, score FROM users ORDER BY id")
+"#,
+    );
+    assert_eq!(vm.environment().get("changed"), Some(Object::Int(1)));
+    assert_eq!(
+        vm.environment().get("sql"),
+        Some(Object::string(
+            "INSERT INTO users (name, score) VALUES (:name, :score)"
+        ))
+    );
+    assert_eq!(
+        vm.environment().get("rows"),
+        Some(Object::array(vec![
+            row(&[
+                ("name", Object::string("bob")),
+                ("score", Object::Float(2.0))
+            ]),
+            row(&[("name", Object::string("cy")), ("score", Object::Nil)]),
+        ]))
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn transaction_commits_or_rolls_back() {
+    let vm = with_database(
+        r#"
+db.transaction do |t|
+  t.execute("INSERT INTO users (name) VALUES (?)", ["kept"])
+end
+begin
+  db.transaction do |t|
+    t.execute("INSERT INTO users (name) VALUES (?)", ["dropped"])
+    raise "boom"
+  end
+rescue => e
+  message = e.message
+end
+rows = db.execute("SELECT name FROM users")
+"#,
+    );
+    assert_eq!(
+        vm.environment().get("message"),
+        Some(Object::string("boom"))
+    );
+    assert_eq!(
+        vm.environment().get("rows"),
+        Some(Object::array(vec![row(&[(
+            "name",
+            Object::string("kept")
+        )])]))
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn open_with_block_closes_the_database() {
+    let vm = with_database(
+        r#"
+saved = nil
+SQLite.open(":memory:") do |d|
+  saved = d
+  two = d.execute("SELECT 1 + 1 AS two")
+end
+closed = saved.closed?
+"#,
+    );
+    assert_eq!(vm.environment().get("closed"), Some(Object::Bool(true)));
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn blobs_come_back_as_byte_arrays() {
+    let vm = with_database(r#"rows = db.execute("SELECT x'01ff' AS b")"#);
+    assert_eq!(
+        vm.environment().get("rows"),
+        Some(Object::array(vec![row(&[(
+            "b",
+            Object::array(vec![Object::Int(1), Object::Int(255)])
+        )])]))
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_errors_become_runtime_errors() {
+    let cases = [
+        ("db.execute(\"SELEC 1\")", "syntax error"),
+        (
+            "db.execute(\"SELECT ?\", [])",
+            "expects 1 parameter(s), got 0",
+        ),
+        (
+            "db.execute(\"SELECT :a\", {\"b\" => 1})",
+            "no parameter named :b",
+        ),
+        ("db.execute(\"SELECT ?\", [[1]])", "cannot bind Array"),
+        ("db.close\ndb.execute(\"SELECT 1\")", "database is closed"),
+        ("db.transaction", "expects a block"),
+    ];
+    for (source, expected) in cases {
+        let mut vm = VirtualMachine::new();
+        let err = run(&mut vm, &format!("{}{}", SCHEMA, source)).unwrap_err();
+        assert!(err.contains("SQLite: "), "{}", err);
+        assert!(err.contains(expected), "{}: {}", source, err);
+    }
+}
@@ -0,0 +1,159 @@
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+
+fn run(source: &str) -> Result<VirtualMachine, String> {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens)
+        .parse()
+        .map_err(|e| format!("{:?}", e))?;
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program).map_err(|e| format!("{}", e))?;
+    Ok(vm)
+}
+
+fn get(vm: &VirtualMachine, name: &str) -> Option<Object> {
+    vm.environment().get(name)
+}
+
+// ============================================================================
+// The << operator
+// ============================================================================
+
+#[test]
+fn shift_left_pushes_onto_arrays_and_chains() {
+    let vm = run("items = [1]\nresult = items << 2 << 3").unwrap();
+    let expected = Object::array(vec![Object::Int(1), Object::Int(2), Object::Int(3)]);
+    assert_eq!(get(&vm, "items"), Some(expected.clone()));
+    assert_eq!(get(&vm, "result"), Some(expected));
+}
+
+#[test]
+fn shift_left_binds_looser_than_addition() {
+    let vm = run("items = []\nitems << 1 + 2").unwrap();
+    assert_eq!(get(&vm, "items"), Some(Object::array(vec![Object::Int(3)])));
+}
+
+#[test]
+fn shift_left_calls_user_defined_methods() {
+    let source = "class Log
+  def initialize
+    @lines = []
+  end
+  def <<(line)
+    @lines.push(\"> \" + line)
+    self
+  end
+  def lines
+    @lines
+  end
+end
+log = Log.new
+log << \"a\" << \"b\"
+lines = log.lines";
+    let vm = run(source).unwrap();
+    assert_eq!(
+        get(&vm, "lines"),
+        Some(Object::array(vec![
+            Object::string("> a"),
+            Object::string("> b")
+        ]))
+    );
+}
+
+#[test]
+fn shift_left_rejects_other_values() {
+    let err = run("x = 1 << 2").err().unwrap();
+    assert!(err.contains("Cannot apply operator"), "{}", err);
+}
+
+// ============================================================================
+// Array#join
+// ============================================================================
+
+#[test]
+fn join_places_the_separator_between_elements() {
+    let vm =
+        run("a = [\"x\", \"y\", \"z\"].join(\", \")\nb = [\"x\", \"y\"].join\nc = [].join(\"-\")")
+            .unwrap();
+    assert_eq!(get(&vm, "a"), Some(Object::string("x, y, z")));
+    assert_eq!(get(&vm, "b"), Some(Object::string("xy")));
+    assert_eq!(get(&vm, "c"), Some(Object::string("")));
+}
+
+#[test]
+fn join_formats_values_and_nested_arrays() {
+    let vm = run("a = [1, nil, :sym, 2.5, [3, [4]], []].join(\",\")").unwrap();
+    assert_eq!(get(&vm, "a"), Some(Object::string("1,,sym,2.5,3,4,")));
+}
+
+#[test]
+fn join_raises_on_recursive_arrays() {
+    let err = run("a = [1]\na << a\ntext = a.join(\",\")").err().unwrap();
+    assert!(
+        err.contains("ArgumentError: recursive array join"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn join_requires_a_string_separator() {
+    let err = run("text = [1, 2].join(3)").err().unwrap();
+    assert!(err.contains("String"), "{}", err);
+}
+
+// ============================================================================
+// StringBuilder and String.build
+// ============================================================================
+
+#[test]
+fn string_builder_appends_in_place() {
+    let source = "b = StringBuilder.new(\"ab\")
+same = (b << \"c\" << 1).equal?(b)
+b.append(nil)
+text = b.to_s
+length = b.length
+empty = StringBuilder.new.empty?";
+    let vm = run(source).unwrap();
+    assert_eq!(get(&vm, "same"), Some(Object::Bool(true)));
+    assert_eq!(get(&vm, "text"), Some(Object::string("abc1nil")));
+    assert_eq!(get(&vm, "length"), Some(Object::Int(7)));
+    assert_eq!(get(&vm, "empty"), Some(Object::Bool(true)));
+}
+
+#[test]
+fn string_builder_to_s_is_a_snapshot() {
+    let source = "b = StringBuilder.new
+b << \"one\"
+first = b.to_s
+b << \" two\"
+second = b.to_s
+b << b";
+    let vm = run(source).unwrap();
+    assert_eq!(get(&vm, "first"), Some(Object::string("one")));
+    assert_eq!(get(&vm, "second"), Some(Object::string("one two")));
+}
+
+#[test]
+fn string_builder_appends_other_builders_text() {
+    let vm = run("b = StringBuilder.new(\"ab\")\nb << b\ntext = b.to_s").unwrap();
+    assert_eq!(get(&vm, "text"), Some(Object::string("abab")));
+}
+
+#[test]
+fn string_build_returns_what_the_block_appends() {
+    let source = "text = String.build do |s|
+  3.times { |i| s << i << \",\" }
+  s << \"done\"
+end";
+    let vm = run(source).unwrap();
+    assert_eq!(get(&vm, "text"), Some(Object::string("0,1,2,done")));
+}
+
+#[test]
+fn string_build_requires_a_block() {
+    let err = run("text = String.build").err().unwrap();
+    assert!(err.contains("build"), "{}", err);
+}
@@ -0,0 +1,127 @@
+use crate::common::run;
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+
+fn eval_text(source: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, &format!("result = {}", source)).unwrap();
+    vm.environment().get("result").unwrap().to_string()
+}
+
+/// The message of the ArgumentError `source` raises
+fn argument_error(source: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        &format!(
+            "message = nil\nbegin\n  {}\nrescue ArgumentError => e\n  message = e.message\nend",
+            source
+        ),
+    )
+    .unwrap();
+    vm.environment().get("message").unwrap().to_string()
+}
+
+// ============================================================================
+// Directives
+// ============================================================================
+
+#[test]
+fn format_fills_directives_in_order() {
+    assert_eq!(
+        eval_text(r#"format("Hello %s, you are %d", "ann", 30)"#),
+        "Hello ann, you are 30"
+    );
+    assert_eq!(eval_text(r#"sprintf("%s=%i", :size, 4)"#), ":size=4");
+    assert_eq!(eval_text(r#"format("100%% done")"#), "100% done");
+}
+
+#[test]
+fn strings_pad_to_a_width_and_truncate_to_a_precision() {
+    assert_eq!(
+        eval_text(r#"format("[%5s|%-5s|%.2s]", "ab", "cd", "xyz")"#),
+        "[   ab|cd   |xy]"
+    );
+    assert_eq!(eval_text(r#"format("%3s", "wider")"#), "wider");
+}
+
+#[test]
+fn integers_take_sign_and_zero_padding_flags() {
+    assert_eq!(
+        eval_text(r#"format("%+d|% d|%05d|%-6d|%4d", 5, 7, -42, 12, -3)"#),
+        "+5| 7|-0042|12    |  -3"
+    );
+    assert_eq!(eval_text(r#"format("%d|%d", 9.9, -3.7)"#), "9|-3");
+    assert_eq!(eval_text(r#"format("%d", Decimal.new("-12.5"))"#), "-12");
+}
+
+#[test]
+fn floats_round_to_the_precision() {
+    assert_eq!(eval_text(r#"format("%f", 1)"#), "1.000000");
+    assert_eq!(
+        eval_text(r#"format("%08.3f|%.0f|%+.1f", 3.14159, 2.5, 0.25)"#),
+        "0003.142|2|+0.2"
+    );
+    assert_eq!(eval_text(r#"format("%.1f", Decimal.new("2.25"))"#), "2.3");
+}
+
+#[test]
+fn strings_use_to_s_of_instances() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"
+class Point
+  def to_s
+    "(1, 2)"
+  end
+end
+result = format("at %s", Point.new)
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        vm.environment().get("result"),
+        Some(Object::string("at (1, 2)"))
+    );
+}
+
+// ============================================================================
+// String#%
+// ============================================================================
+
+#[test]
+fn percent_formats_one_value_or_an_array_of_them() {
+    assert_eq!(eval_text(r#""%.2f%%" % 99.5"#), "99.50%");
+    assert_eq!(eval_text(r#""%s and %s" % ["a", "b"]"#), "a and b");
+    assert_eq!(eval_text(r#""%-4s|" % "ok""#), "ok  |");
+}
+
+#[test]
+fn percent_keeps_integer_modulo() {
+    assert_eq!(eval_text("7 % 3"), "1");
+}
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+#[test]
+fn bad_directives_and_arguments_raise_argument_error() {
+    assert_eq!(
+        argument_error(r#"format("%d", "x")"#),
+        "format: %d expects a number, got String"
+    );
+    assert_eq!(
+        argument_error(r#"format("%s %s", 1)"#),
+        "format: too few arguments"
+    );
+    assert_eq!(
+        argument_error(r#""%q" % 1"#),
+        "String#%: unknown format directive '%q'"
+    );
+    assert_eq!(
+        argument_error(r#"sprintf("50%")"#),
+        "sprintf: incomplete format directive at the end"
+    );
+}
@@ -0,0 +1,118 @@
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::{StringInterner, VirtualMachine};
+use std::rc::Rc;
+
+fn run(vm: &mut VirtualMachine, source: &str) {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens).parse().expect("parse failed");
+    vm.execute_program(&program).expect("execution failed");
+}
+
+fn array_strings(vm: &VirtualMachine, name: &str) -> Vec<Rc<String>> {
+    match vm.environment().get(name) {
+        Some(Object::Array(elements)) => elements
+            .borrow()
+            .iter()
+            .map(|element| match element {
+                Object::String(s) => Rc::clone(s),
+                other => panic!("Expected string, got {:?}", other),
+            })
+            .collect(),
+        other => panic!("Expected array, got {:?}", other),
+    }
+}
+
+const REPEATED_LITERALS: &str = r#"
+items = []
+i = 0
+while i < 3
+  items.push("row")
+  i += 1
+end
+"#;
+
+#[test]
+fn interner_returns_shared_string() {
+    let mut interner = StringInterner::new();
+    assert!(interner.is_empty());
+
+    let first = interner.intern("hello");
+    let second = interner.intern("hello");
+    let other = interner.intern("world");
+
+    assert!(Rc::ptr_eq(&first, &second));
+    assert!(!Rc::ptr_eq(&first, &other));
+    assert_eq!(interner.len(), 2);
+}
+
+#[test]
+fn string_literals_are_not_interned_by_default() {
+    let mut vm = VirtualMachine::new();
+    assert!(!vm.interns_string_literals());
+
+    run(&mut vm, REPEATED_LITERALS);
+
+    let strings = array_strings(&vm, "items");
+    assert_eq!(strings.len(), 3);
+    assert!(!Rc::ptr_eq(&strings[0], &strings[1]));
+    assert_eq!(vm.interned_string_count(), 0);
+}
+
+#[test]
+fn interned_string_literals_share_one_allocation() {
+    let mut vm = VirtualMachine::new();
+    vm.set_intern_string_literals(true);
+    assert!(vm.interns_string_literals());
+
+    run(&mut vm, REPEATED_LITERALS);
+
+    let strings = array_strings(&vm, "items");
+    assert_eq!(strings.len(), 3);
+    assert!(strings.iter().all(|s| Rc::ptr_eq(s, &strings[0])));
+    assert_eq!(strings[0].as_str(), "row");
+    assert_eq!(vm.interned_string_count(), 1);
+}
+
+#[test]
+fn interned_literals_behave_as_values() {
+    let mut vm = VirtualMachine::new();
+    vm.set_intern_string_literals(true);
+
+    run(
+        &mut vm,
+        r#"
+a = "hi"
+b = a.upcase
+c = "hi" + "!"
+same = "hi" == a
+"#,
+    );
+
+    assert_eq!(
+        vm.environment().get("a"),
+        Some(Object::String(Rc::new("hi".to_string())))
+    );
+    assert_eq!(
+        vm.environment().get("b"),
+        Some(Object::String(Rc::new("HI".to_string())))
+    );
+    assert_eq!(
+        vm.environment().get("c"),
+        Some(Object::String(Rc::new("hi!".to_string())))
+    );
+    assert_eq!(vm.environment().get("same"), Some(Object::Bool(true)));
+}
+
+#[test]
+fn disabling_interning_drops_the_table() {
+    let mut vm = VirtualMachine::new();
+    vm.set_intern_string_literals(true);
+    run(&mut vm, r#"x = "one""#);
+    assert_eq!(vm.interned_string_count(), 1);
+
+    vm.set_intern_string_literals(false);
+    assert!(!vm.interns_string_literals());
+    assert_eq!(vm.interned_string_count(), 0);
+}
@@ -0,0 +1,84 @@
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+
+fn run(source: &str) -> Result<VirtualMachine, String> {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens).parse().map_err(|errors| {
+        errors
+            .iter()
+            .map(|error| error.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    })?;
+    let mut vm = VirtualMachine::new();
+    vm.execute_program(&program)
+        .map_err(|error| error.to_string())?;
+    Ok(vm)
+}
+
+fn eval(source: &str) -> Object {
+    let vm = run(source).unwrap();
+    vm.environment().get("result").unwrap()
+}
+
+#[test]
+fn interpolates_a_hash_literal() {
+    assert_eq!(
+        eval(r##"result = "#{ {"a" => 1}["a"] }""##),
+        Object::string("1")
+    );
+}
+
+#[test]
+fn braces_in_nested_strings_do_not_end_the_interpolation() {
+    assert_eq!(
+        eval(r##"result = "<#{ "}" + '{' + "a\"}" }>""##),
+        Object::string("<}{a\"}>")
+    );
+}
+
+#[test]
+fn interpolates_nested_interpolated_strings() {
+    assert_eq!(
+        eval("h = {\"a\" => 1}\nresult = \"x #{ \"in #{h[\"a\"]} ner\" } y\""),
+        Object::string("x in 1 ner y")
+    );
+}
+
+#[test]
+fn interpolates_method_calls_with_blocks() {
+    assert_eq!(
+        eval(
+            r##"result = "#{ [1, 2].map { |x| x * 2 }.join(",") }|#{[1, 2].map do |x| x end.length}""##
+        ),
+        Object::string("2,4|2")
+    );
+}
+
+#[test]
+fn reports_errors_at_their_position_in_the_file() {
+    let error = run("x = 1\ny = \"a #{x +} b\"").err().unwrap();
+    assert!(error.contains("2:"), "{}", error);
+}
+
+#[test]
+fn rejects_tokens_after_the_interpolated_expression() {
+    let error = run("y = \"#{1 2}\"").err().unwrap();
+    assert!(
+        error.contains("Unexpected token in interpolation"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn rejects_an_empty_interpolation() {
+    let error = run("y = \"#{}\"").err().unwrap();
+    assert!(
+        error.contains("Expected expression in interpolation"),
+        "{}",
+        error
+    );
+}
@@ -0,0 +1,125 @@
+use crate::common::{eval, eval_error, run};
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+
+fn strings(values: &[&str]) -> Object {
+    Object::array(values.iter().map(|value| Object::string(*value)).collect())
+}
+
+fn ints(values: &[i64]) -> Object {
+    Object::array(values.iter().map(|value| Object::Int(*value)).collect())
+}
+
+const TEXT: &str = "\"héllo\\nwörld\\r\\nlast\"";
+
+// ============================================================================
+// Array forms
+// ============================================================================
+
+#[test]
+fn lines_keep_their_line_endings() {
+    assert_eq!(
+        eval(&format!("{}.lines", TEXT)),
+        strings(&["héllo\n", "wörld\r\n", "last"])
+    );
+    assert_eq!(
+        eval("\"a\\n\\nb\\n\".lines"),
+        strings(&["a\n", "\n", "b\n"])
+    );
+    assert_eq!(eval("\"\".lines"), strings(&[]));
+}
+
+#[test]
+fn lines_with_chomp_drop_line_endings() {
+    assert_eq!(
+        eval(&format!("{}.lines(chomp: true)", TEXT)),
+        strings(&["héllo", "wörld", "last"])
+    );
+}
+
+#[test]
+fn iterators_without_a_block_return_enumerators() {
+    assert_eq!(eval("\"日本\".each_char.to_a"), strings(&["日", "本"]));
+    assert_eq!(eval("\"é\".each_byte.to_a"), ints(&[195, 169]));
+    assert_eq!(
+        eval("\"a\\nb\".each_line(chomp: true).to_a"),
+        strings(&["a", "b"])
+    );
+}
+
+// ============================================================================
+// Block forms
+// ============================================================================
+
+#[test]
+fn each_line_yields_lines_and_returns_the_receiver() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        &format!(
+            "seen = []
+returned = {}.each_line(chomp: true) {{ |line| seen.push(line.length) }}",
+            TEXT
+        ),
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("seen"), Some(ints(&[5, 5, 4])));
+    assert_eq!(
+        vm.environment().get("returned"),
+        Some(Object::string("héllo\nwörld\r\nlast"))
+    );
+}
+
+#[test]
+fn each_char_and_each_byte_yield_utf8_units() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "chars = []
+bytes = []
+\"añ\".each_char do |c|
+  chars.push(c)
+end
+\"añ\".each_byte { |b| bytes.push(b) }",
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("chars"), Some(strings(&["a", "ñ"])));
+    assert_eq!(vm.environment().get("bytes"), Some(ints(&[97, 195, 177])));
+}
+
+#[test]
+fn break_and_continue_control_string_iteration() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "kept = []
+\"one\\n#skip\\ntwo\\nSTOP\\nthree\".each_line(chomp: true) { |line|
+  if line == \"STOP\"
+    break
+  end
+  if line.match_glob(\"#*\")
+    continue
+  end
+  kept.push(line)
+}",
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("kept"), Some(strings(&["one", "two"])));
+}
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+#[test]
+fn iterators_reject_bad_arguments() {
+    assert!(eval_error("\"x\".each_char(1)").contains("each_char"));
+    assert!(eval_error("\"x\".lines(strip: true)").contains("unknown option strip"));
+    assert!(eval_error("\"x\".lines(chomp: 1)").contains("chomp must be a Bool"));
+}
+
+#[test]
+fn errors_raised_in_the_block_propagate() {
+    let error = eval_error("\"ab\".each_char { |c| raise \"bad \" + c }");
+    assert!(error.contains("bad a"), "{}", error);
+}
@@ -0,0 +1,191 @@
+use crate::common::{eval, eval_error};
+use metorex::char_set::{self, CharSet};
+use metorex::object::Object;
+
+fn set(spec: &str) -> CharSet {
+    CharSet::parse(spec).unwrap()
+}
+
+// ============================================================================
+// Character sets
+// ============================================================================
+
+#[test]
+fn char_sets_hold_listed_characters_and_ranges() {
+    let vowels = set("aeiou");
+    assert!(vowels.contains('e'));
+    assert!(!vowels.contains('x'));
+
+    let hex = set("0-9a-f");
+    assert!(hex.contains('7'));
+    assert!(hex.contains('c'));
+    assert!(!hex.contains('g'));
+}
+
+#[test]
+fn leading_caret_negates_the_set() {
+    let consonants = set("^aeiou");
+    assert!(consonants.is_negated());
+    assert!(consonants.contains('x'));
+    assert!(!consonants.contains('a'));
+
+    let caret = set("^");
+    assert!(!caret.is_negated());
+    assert!(caret.contains('^'));
+}
+
+#[test]
+fn dashes_at_the_ends_and_escapes_are_literal() {
+    let signs = set("-+");
+    assert!(signs.contains('-'));
+    assert!(signs.contains('+'));
+    assert!(!signs.contains(','));
+
+    let escaped = set("a\\-c");
+    assert!(escaped.contains('-'));
+    assert!(!escaped.contains('b'));
+
+    assert!(set("\\^a").contains('^'));
+}
+
+#[test]
+fn reversed_ranges_are_rejected() {
+    assert!(CharSet::parse("z-a").is_err());
+}
+
+#[test]
+fn translate_pads_a_short_replacement_with_its_last_character() {
+    let to = CharSet::parse_sequence("xy").unwrap();
+    assert_eq!(char_set::translate("abcd", &set("a-d"), &to), "xyyy");
+    assert_eq!(
+        char_set::translate("hello", &set("^l"), &CharSet::parse_sequence("*").unwrap()),
+        "**ll*"
+    );
+    assert_eq!(
+        char_set::translate("hello", &set("l"), &CharSet::parse_sequence("").unwrap()),
+        "heo"
+    );
+}
+
+#[test]
+fn squeeze_collapses_runs_in_every_set() {
+    assert_eq!(char_set::squeeze("aaabbb  cc", &[]), "ab c");
+    assert_eq!(char_set::squeeze("aaabbbccc", &[set("a-b")]), "abccc");
+    assert_eq!(
+        char_set::squeeze("aaabbbccc", &[set("a-b"), set("b-c")]),
+        "aaabccc"
+    );
+}
+
+// ============================================================================
+// Justification
+// ============================================================================
+
+#[test]
+fn ljust_and_rjust_pad_to_a_width() {
+    assert_eq!(eval("\"ab\".ljust(5)"), Object::string("ab   "));
+    assert_eq!(eval("\"ab\".rjust(5)"), Object::string("   ab"));
+    assert_eq!(eval("\"ab\".ljust(7, \"12\")"), Object::string("ab12121"));
+    assert_eq!(eval("\"ab\".rjust(7, \"12\")"), Object::string("12121ab"));
+}
+
+#[test]
+fn center_puts_the_extra_padding_on_the_right() {
+    assert_eq!(eval("\"ab\".center(7, \"*\")"), Object::string("**ab***"));
+    assert_eq!(eval("\"ab\".center(8, \"12\")"), Object::string("121ab121"));
+}
+
+#[test]
+fn justification_counts_characters_not_bytes() {
+    assert_eq!(eval("\"日本\".ljust(4, \".\")"), Object::string("日本.."));
+    assert_eq!(
+        eval("\"日本\".center(7, \"*\")"),
+        Object::string("**日本***")
+    );
+    assert_eq!(eval("\"ab\".rjust(4, \"é\")"), Object::string("ééab"));
+}
+
+#[test]
+fn narrow_widths_leave_the_string_unchanged() {
+    assert_eq!(eval("\"hello\".ljust(3)"), Object::string("hello"));
+    assert_eq!(eval("\"hello\".center(-1)"), Object::string("hello"));
+}
+
+#[test]
+fn empty_padding_raises_argument_error() {
+    assert_eq!(
+        eval(
+            "begin
+  \"x\".center(4, \"\")
+rescue ArgumentError => e
+  e.message
+end"
+        ),
+        Object::string("String#center: zero width padding")
+    );
+}
+
+// ============================================================================
+// tr, squeeze, delete and count
+// ============================================================================
+
+#[test]
+fn tr_maps_characters_between_sets() {
+    assert_eq!(
+        eval("\"hello\".tr(\"el\", \"ip\")"),
+        Object::string("hippo")
+    );
+    assert_eq!(
+        eval("\"hello\".tr(\"a-y\", \"b-z\")"),
+        Object::string("ifmmp")
+    );
+    assert_eq!(eval("\"hello\".tr(\"^l\", \"-\")"), Object::string("--ll-"));
+    assert_eq!(
+        eval("\"日本語\".tr(\"本\", \"x\")"),
+        Object::string("日x語")
+    );
+}
+
+#[test]
+fn squeeze_delete_and_count_take_character_sets() {
+    assert_eq!(eval("\"mississippi\".squeeze"), Object::string("misisipi"));
+    assert_eq!(
+        eval("\"mississippi\".squeeze(\"s\")"),
+        Object::string("misisippi")
+    );
+    assert_eq!(
+        eval("\"hello world\".delete(\"l\")"),
+        Object::string("heo word")
+    );
+    assert_eq!(
+        eval("\"hello world\".delete(\"a-z\", \"^o\")"),
+        Object::string("o o")
+    );
+    assert_eq!(eval("\"hello world\".count(\"lo\")"), Object::Int(5));
+    assert_eq!(
+        eval("\"hello world\".count(\"a-z\", \"^l\")"),
+        Object::Int(7)
+    );
+    assert_eq!(eval("\"日本日\".count(\"日\")"), Object::Int(2));
+}
+
+#[test]
+fn delete_and_count_require_a_set() {
+    assert!(eval_error("\"abc\".delete").contains("delete"));
+    assert!(eval_error("\"abc\".count").contains("count"));
+}
+
+#[test]
+fn invalid_sets_raise_argument_error() {
+    assert_eq!(
+        eval(
+            "begin
+  \"abc\".delete(\"z-a\")
+rescue ArgumentError => e
+  e.message
+end"
+        ),
+        Object::string("String#delete: invalid range \"z-a\" in character set")
+    );
+    assert!(eval_error("\"abc\".tr(1, \"x\")").contains("tr"));
+}
@@ -0,0 +1,203 @@
+// Tests for interned symbols and calling methods by name with `send`
+
+use crate::common::{eval, run};
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+use std::rc::Rc;
+
+/// Run a whole program and return the value it leaves in `result`
+fn eval_program(source: &str) -> Object {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, source).unwrap();
+    vm.environment().get("result").unwrap()
+}
+
+const GREETER: &str = r#"
+class Greeter
+  def hello(name)
+    "hello " + name
+  end
+
+  def twice(value, &block)
+    block.call(block.call(value))
+  end
+end
+g = Greeter.new
+"#;
+
+fn eval_with_greeter(source: &str) -> Result<Object, String> {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, &format!("{}result = {}", GREETER, source))?;
+    Ok(vm.environment().get("result").unwrap())
+}
+
+#[test]
+fn equal_symbols_share_one_string() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, "a = :ready\nb = :ready\nc = :done").unwrap();
+    let symbol = |name| match vm.environment().get(name) {
+        Some(Object::Symbol(text)) => text,
+        other => panic!("Expected symbol, got {:?}", other),
+    };
+    assert!(Rc::ptr_eq(&symbol("a"), &symbol("b")));
+    assert!(!Rc::ptr_eq(&symbol("a"), &symbol("c")));
+    assert_eq!(vm.symbol("ready"), Object::Symbol(symbol("a")));
+}
+
+#[test]
+fn symbols_compare_by_identity() {
+    assert_eq!(eval(":a == :a"), Object::Bool(true));
+    assert_eq!(eval(":a == :b"), Object::Bool(false));
+    assert_eq!(eval(":a.equal?(:a)"), Object::Bool(true));
+    assert_eq!(eval(":a.object_id == :a.object_id"), Object::Bool(true));
+}
+
+#[test]
+fn symbols_are_dictionary_keys() {
+    assert_eq!(eval("{:a => 1, :b => 2}[:b]"), Object::Int(2));
+    assert_eq!(eval("{\"a\": :b}[\"a\"]"), eval(":b"));
+    assert_eq!(eval("{:a => 1}.key?(:a)"), Object::Bool(true));
+    assert_eq!(eval("{:a => 1}.key?(\"a\")"), Object::Bool(false));
+    assert_eq!(
+        eval("begin\n  d = {}\n  d[:a] = 3\n  d[:a]\nend"),
+        Object::Int(3)
+    );
+}
+
+#[test]
+fn symbol_keys_display_as_in_a_literal() {
+    assert_eq!(
+        eval("{:a => 1, \"b\" => :c}").to_string(),
+        "{:a => 1, b: :c}"
+    );
+}
+
+#[test]
+fn send_calls_a_method_named_by_a_symbol_or_string() {
+    assert_eq!(
+        eval_with_greeter("g.send(:hello, \"ann\")"),
+        Ok(Object::string("hello ann"))
+    );
+    assert_eq!(
+        eval_with_greeter("g.send(\"hello\", \"bo\")"),
+        Ok(Object::string("hello bo"))
+    );
+    assert_eq!(eval("[1, 2, 3].send(:length)"), Object::Int(3));
+}
+
+#[test]
+fn send_passes_blocks_along() {
+    assert_eq!(
+        eval_with_greeter("g.send(:twice, 3) do |x| x * 2 end"),
+        Ok(Object::Int(12))
+    );
+}
+
+#[test]
+fn send_reports_unknown_methods_and_bad_names() {
+    let err = eval_with_greeter("g.send(:missing)").unwrap_err();
+    assert!(err.contains("Undefined method 'missing'"), "{}", err);
+
+    let err = eval_with_greeter("g.send(1)").unwrap_err();
+    assert!(err.contains("send"), "{}", err);
+}
+
+#[test]
+fn send_raises_a_rescuable_no_method_error() {
+    let source = r#"
+begin
+  g.send(:missing, 1)
+rescue NoMethodError => e
+  e.message
+end
+"#;
+    assert_eq!(
+        eval_with_greeter(source.trim()),
+        Ok(Object::string("Undefined method 'missing' for Greeter"))
+    );
+
+    let source = r#"
+begin
+  5.send("missing")
+rescue NameError => e
+  e.message
+end
+"#;
+    assert_eq!(
+        eval_with_greeter(source.trim()),
+        Ok(Object::string("Undefined method 'missing' for Integer"))
+    );
+}
+
+#[test]
+fn send_leaves_errors_raised_inside_the_method_alone() {
+    let mut vm = VirtualMachine::new();
+    let err = run(
+        &mut vm,
+        "class Box\n  def open\n    5.missing\n  end\nend\nBox.new.send(:open)",
+    )
+    .unwrap_err();
+    assert!(
+        err.contains("Undefined method 'missing' for type 'Int'"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn send_falls_back_to_method_missing_and_reaches_private_methods() {
+    let source = r#"
+class Ghost
+  def method_missing(name)
+    "no " + name
+  end
+end
+result = Ghost.new.send(:boo)
+"#;
+    assert_eq!(eval_program(source), Object::string("no boo"));
+
+    let source = r#"
+def secret
+  42
+end
+result = self.send(:secret)
+"#;
+    assert_eq!(eval_program(source), Object::Int(42));
+}
+
+#[test]
+fn underscore_send_works_when_a_class_defines_its_own_send() {
+    let source = r#"
+class Mailer
+  def send(to)
+    "mailed " + to
+  end
+
+  def deliver
+    "delivered"
+  end
+end
+m = Mailer.new
+result = [m.send("ann"), m.__send__(:deliver), m.__send__(:send, "bo")]
+"#;
+    assert_eq!(
+        eval_program(source),
+        Object::array(vec![
+            Object::string("mailed ann"),
+            Object::string("delivered"),
+            Object::string("mailed bo"),
+        ])
+    );
+}
+
+#[test]
+fn respond_to_accepts_symbols() {
+    assert_eq!(
+        eval_with_greeter("g.respond_to?(:hello)"),
+        Ok(Object::Bool(true))
+    );
+    assert_eq!(
+        eval_with_greeter("g.respond_to?(:missing)"),
+        Ok(Object::Bool(false))
+    );
+}
@@ -0,0 +1,210 @@
+use crate::common::{eval, eval_in, run};
+use metorex::ast::Statement;
+use metorex::lexer::Lexer;
+use metorex::object::{Object, from_object, to_object};
+use metorex::parser::Parser;
+use metorex::vm::VirtualMachine;
+
+fn parse(source: &str) -> Vec<Statement> {
+    Parser::new(Lexer::new(source).tokenize()).parse().unwrap()
+}
+
+/// The message of the exception `source` raises, rescued as `class`
+fn rescued_message(source: &str, class: &str) -> Object {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        &format!(
+            "begin\n  {}\nrescue {} => e\n  message = e.message\nend",
+            source, class
+        ),
+    )
+    .unwrap();
+    vm.environment().get("message").unwrap_or(Object::Nil)
+}
+
+fn symbol(name: &str) -> Object {
+    Object::Symbol(name.to_string().into())
+}
+
+// ============================================================================
+// Metorex.parse
+// ============================================================================
+
+#[test]
+fn parse_returns_a_program_node() {
+    assert_eq!(eval("Metorex.parse(\"1\").type"), symbol("Program"));
+    assert_eq!(
+        eval("Metorex.parse(\"1\\n2\").statements.length"),
+        Object::Int(2)
+    );
+}
+
+#[test]
+fn fields_can_be_read_as_methods() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "ast = Metorex.parse(\"1 + 2\")\nsum = ast.statements[0].expression",
+    )
+    .unwrap();
+    assert_eq!(eval_in(&mut vm, "sum.type"), symbol("BinaryOp"));
+    assert_eq!(eval_in(&mut vm, "sum.op"), symbol("Add"));
+    assert_eq!(eval_in(&mut vm, "sum.right.value"), Object::Int(2));
+    assert_eq!(eval_in(&mut vm, "sum.position.line"), Object::Int(1));
+    assert_eq!(
+        eval_in(&mut vm, "sum.fields"),
+        Object::array(vec![
+            symbol("op"),
+            symbol("left"),
+            symbol("right"),
+            symbol("position"),
+        ])
+    );
+}
+
+#[test]
+fn fields_can_be_indexed_by_name() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "range = Metorex.parse(\"1..5\").statements[0].expression",
+    )
+    .unwrap();
+    assert_eq!(eval_in(&mut vm, "range[\"end\"].value"), Object::Int(5));
+    assert_eq!(eval_in(&mut vm, "range[:start].value"), Object::Int(1));
+    assert_eq!(eval_in(&mut vm, "range[\"missing\"]"), Object::Nil);
+}
+
+#[test]
+fn node_types_compare_with_symbols() {
+    assert_eq!(
+        eval("Metorex.parse(\"x\").statements[0].expression.type == :Identifier"),
+        Object::Bool(true)
+    );
+}
+
+#[test]
+fn to_h_gives_the_hashes_method_ast_uses() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, "def add(a, b)\n  a + b\nend").unwrap();
+    assert_eq!(
+        eval_in(
+            &mut vm,
+            "Metorex.parse(\"def add(a, b)\\n  a + b\\nend\").statements[0].to_h[\"body\"] == method(:add).ast[\"body\"]"
+        ),
+        Object::Bool(true)
+    );
+}
+
+#[test]
+fn syntax_errors_can_be_rescued() {
+    let message = rescued_message("Metorex.parse(\"1 +\")", "SyntaxError");
+    let Object::String(message) = message else {
+        panic!("expected a message, got {:?}", message);
+    };
+    assert!(message.contains("1:3"), "{}", message);
+}
+
+#[test]
+fn parse_requires_a_string() {
+    assert_eq!(
+        rescued_message("Metorex.parse(1)", "TypeError"),
+        Object::string("parse: source must be a String (got Int)")
+    );
+}
+
+// ============================================================================
+// Metorex.eval_ast
+// ============================================================================
+
+#[test]
+fn eval_ast_runs_in_the_callers_scope() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "x = 10\nMetorex.eval_ast(Metorex.parse(\"y = x * 3\"))",
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("y"), Some(Object::Int(30)));
+}
+
+#[test]
+fn eval_ast_returns_the_last_value() {
+    assert_eq!(
+        eval("Metorex.eval_ast(Metorex.parse(\"1\\n2 + 3\"))"),
+        Object::Int(5)
+    );
+    assert_eq!(eval("Metorex.eval_ast([])"), Object::Nil);
+}
+
+#[test]
+fn eval_ast_accepts_statements_and_expressions() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, "ast = Metorex.parse(\"4 * 5\")").unwrap();
+    assert_eq!(
+        eval_in(&mut vm, "Metorex.eval_ast(ast.statements)"),
+        Object::Int(20)
+    );
+    assert_eq!(
+        eval_in(&mut vm, "Metorex.eval_ast(ast.statements[0])"),
+        Object::Int(20)
+    );
+    assert_eq!(
+        eval_in(&mut vm, "Metorex.eval_ast(ast.statements[0].expression)"),
+        Object::Int(20)
+    );
+}
+
+#[test]
+fn eval_ast_runs_rewritten_trees() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        "tree = Metorex.parse(\"3 + 4\").statements[0].to_h
+tree[\"expression\"][\"op\"] = :Multiply",
+    )
+    .unwrap();
+    assert_eq!(eval_in(&mut vm, "Metorex.eval_ast(tree)"), Object::Int(12));
+}
+
+#[test]
+fn eval_ast_rejects_malformed_trees() {
+    let message = rescued_message("Metorex.eval_ast({\"type\" => :Nope})", "ValueError");
+    let Object::String(message) = message else {
+        panic!("expected a message, got {:?}", message);
+    };
+    assert!(
+        message.starts_with("eval_ast: invalid syntax tree: unknown variant `Nope`"),
+        "{}",
+        message
+    );
+    assert_eq!(
+        rescued_message("Metorex.eval_ast(5)", "TypeError"),
+        Object::string("eval_ast: ast must be a Metorex::Node, Array or Hash (got Int)")
+    );
+}
+
+// ============================================================================
+// Object conversion
+// ============================================================================
+
+#[test]
+fn from_object_inverts_to_object() {
+    let statements = parse(
+        "class Point\n  def initialize(x)\n    @x = x\n  end\nend\nresult = [1, 2.5, \"s\", :sym, nil].map { |v| v }\n",
+    );
+    let object = to_object(&statements).unwrap();
+    let round_trip: Vec<Statement> = from_object(&object).unwrap();
+    assert_eq!(round_trip, statements);
+}
+
+#[test]
+fn from_object_names_the_problem() {
+    let error = from_object::<Vec<Statement>>(&Object::Int(1)).unwrap_err();
+    assert!(
+        error.to_string().contains("expected a sequence"),
+        "{}",
+        error
+    );
+}
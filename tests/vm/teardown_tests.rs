@@ -0,0 +1,84 @@
+use crate::common::run;
+use metorex::object::Object;
+use metorex::vm::VirtualMachine;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+/// A finalizer that appends `name` to `log` when it runs.
+fn finalizer(log: &Log, name: &'static str) -> impl FnOnce(&mut VirtualMachine) + 'static {
+    let log = Rc::clone(log);
+    move |_| log.borrow_mut().push(name)
+}
+
+#[test]
+fn dropping_the_vm_runs_finalizers_newest_first() {
+    let log = Log::default();
+    let mut vm = VirtualMachine::new();
+    vm.register_finalizer(finalizer(&log, "file"));
+    vm.register_finalizer(finalizer(&log, "socket"));
+    assert_eq!(vm.pending_finalizers(), 2);
+    assert!(log.borrow().is_empty());
+
+    drop(vm);
+    assert_eq!(*log.borrow(), vec!["socket", "file"]);
+}
+
+#[test]
+fn shutdown_runs_each_finalizer_once() {
+    let log = Log::default();
+    let mut vm = VirtualMachine::new();
+    vm.register_finalizer(finalizer(&log, "file"));
+
+    vm.shutdown();
+    vm.shutdown();
+    assert_eq!(vm.pending_finalizers(), 0);
+    drop(vm);
+    assert_eq!(*log.borrow(), vec!["file"]);
+}
+
+#[test]
+fn finalizers_registered_during_shutdown_also_run() {
+    let log = Log::default();
+    let late = finalizer(&log, "late");
+    let mut vm = VirtualMachine::new();
+    vm.register_finalizer(move |vm| vm.register_finalizer(late));
+    vm.register_finalizer(finalizer(&log, "early"));
+
+    vm.shutdown();
+    assert_eq!(*log.borrow(), vec!["early", "late"]);
+}
+
+#[test]
+fn finalizers_can_still_use_the_vm() {
+    let seen = Rc::new(RefCell::new(None));
+    let slot = Rc::clone(&seen);
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, "total = 40 + 2").unwrap();
+    vm.register_finalizer(move |vm| *slot.borrow_mut() = vm.environment().get("total"));
+
+    drop(vm);
+    assert_eq!(*seen.borrow(), Some(Object::Int(42)));
+}
+
+#[test]
+fn the_vm_remains_usable_after_shutdown() {
+    let mut vm = VirtualMachine::new();
+    vm.shutdown();
+    run(&mut vm, "x = [1, 2].map do |n| n * 2 end").unwrap();
+    assert_eq!(
+        vm.environment().get("x"),
+        Some(Object::array(vec![Object::Int(2), Object::Int(4)]))
+    );
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn shutdown_closes_open_databases() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, "db = SQLite.open(\":memory:\")").unwrap();
+    vm.shutdown();
+    let error = run(&mut vm, "db.execute(\"SELECT 1\")").unwrap_err();
+    assert!(error.contains("closed"), "{}", error);
+}
@@ -0,0 +1,176 @@
+use crate::common::run;
+use metorex::object::Object;
+use metorex::text_table::{Align, Table, TableStyle};
+use metorex::vm::VirtualMachine;
+
+fn render(source: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, &format!("result = {}", source)).unwrap();
+    match vm.environment().get("result").unwrap() {
+        Object::String(text) => text.to_string(),
+        other => panic!("expected a String, got {:?}", other),
+    }
+}
+
+fn render_error(source: &str) -> String {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, source).unwrap_err()
+}
+
+fn strings(cells: &[&str]) -> Vec<String> {
+    cells.iter().map(|cell| cell.to_string()).collect()
+}
+
+const PEOPLE: &str =
+    "[{:name => \"bob\", :age => 42}, {:name => \"ann\", :age => 7, :city => \"Kyoto\"}]";
+
+// ============================================================================
+// Layout
+// ============================================================================
+
+#[test]
+fn ascii_tables_frame_headers_and_rows() {
+    let table = Table {
+        headers: Some(strings(&["name", "n"])),
+        rows: vec![strings(&["bob", "42"]), strings(&["alexandra", "7"])],
+        aligns: vec![Align::Left, Align::Right],
+    };
+    assert_eq!(
+        table.render(TableStyle::Ascii),
+        "\
++-----------+----+
+| name      | n  |
++-----------+----+
+| bob       | 42 |
+| alexandra |  7 |
++-----------+----+"
+    );
+}
+
+#[test]
+fn markdown_tables_mark_right_aligned_columns() {
+    let table = Table {
+        headers: Some(strings(&["a", "b"])),
+        rows: vec![strings(&["x|y", "1"])],
+        aligns: vec![Align::Left, Align::Right],
+    };
+    assert_eq!(
+        table.render(TableStyle::Markdown),
+        "\
+| a    | b   |
+| ---- | --: |
+| x\\|y |   1 |"
+    );
+}
+
+#[test]
+fn widths_count_characters_and_short_rows_are_padded() {
+    let table = Table {
+        headers: None,
+        rows: vec![strings(&["日本", "x"]), strings(&["abc"])],
+        aligns: Vec::new(),
+    };
+    assert_eq!(
+        table.render(TableStyle::Ascii),
+        "\
++-----+---+
+| 日本  | x |
+| abc |   |
++-----+---+"
+    );
+}
+
+#[test]
+fn empty_tables_render_nothing() {
+    assert_eq!(Table::default().render(TableStyle::Ascii), "");
+    assert_eq!(Table::default().render(TableStyle::Markdown), "");
+}
+
+// ============================================================================
+// Array#to_table
+// ============================================================================
+
+#[test]
+fn hash_rows_use_their_keys_as_headers() {
+    assert_eq!(
+        render(&format!("{}.to_table", PEOPLE)),
+        "\
++------+-----+-------+
+| name | age | city  |
++------+-----+-------+
+| bob  |  42 |       |
+| ann  |   7 | Kyoto |
++------+-----+-------+"
+    );
+}
+
+#[test]
+fn headers_pick_and_order_hash_columns() {
+    assert_eq!(
+        render(&format!("{}.to_table(headers: [:age, \"name\"])", PEOPLE)),
+        "\
++-----+------+
+| age | name |
++-----+------+
+|  42 | bob  |
+|   7 | ann  |
++-----+------+"
+    );
+}
+
+#[test]
+fn array_rows_are_labelled_by_headers() {
+    assert_eq!(
+        render("[[\"tea\", 2.5], [\"cake\", nil]].to_table(headers: [\"item\", \"price\"])"),
+        "\
++------+-------+
+| item | price |
++------+-------+
+| tea  |   2.5 |
+| cake |       |
++------+-------+"
+    );
+    assert_eq!(
+        render("[[1, 2], [3, 4]].to_table"),
+        "\
++---+---+
+| 1 | 2 |
+| 3 | 4 |
++---+---+"
+    );
+}
+
+#[test]
+fn markdown_option_renders_a_markdown_table() {
+    assert_eq!(
+        render(&format!("{}.to_table(markdown: true)", PEOPLE)),
+        "\
+| name | age | city  |
+| ---- | --: | ----- |
+| bob  |  42 |       |
+| ann  |   7 | Kyoto |"
+    );
+}
+
+#[test]
+fn mixed_rows_and_bad_options_are_errors() {
+    let error = render_error("[[1], {\"a\" => 1}].to_table");
+    assert!(
+        error.contains("every row must be an Array like the first"),
+        "{}",
+        error
+    );
+
+    let error = render_error("[[1]].to_table(markdown: 1)");
+    assert!(error.contains("markdown must be a Bool"), "{}", error);
+
+    let error = render_error("[[1]].to_table(border: true)");
+    assert!(error.contains("unknown option border"), "{}", error);
+
+    let error = render_error("[[1]].to_table(headers: [1])");
+    assert!(
+        error.contains("headers must be Strings or Symbols"),
+        "{}",
+        error
+    );
+}
@@ -0,0 +1,188 @@
+use metorex::error::MetorexError;
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::{TIMEOUT_EXCEPTION, VirtualMachine};
+use std::time::{Duration, Instant};
+
+fn run(vm: &mut VirtualMachine, source: &str) -> Result<Option<Object>, MetorexError> {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens).parse().expect("program should parse");
+    vm.execute_program(&program)
+}
+
+fn eval(source: &str, name: &str) -> Object {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, source).expect("program should run");
+    vm.environment()
+        .get(name)
+        .expect("variable should be defined")
+}
+
+// ============================================================================
+// Timeout.timeout
+// ============================================================================
+
+#[test]
+fn a_block_that_finishes_in_time_returns_its_value() {
+    assert_eq!(
+        eval("x = Timeout.timeout(5) do\n  40 + 2\nend", "x"),
+        Object::Int(42)
+    );
+    assert_eq!(eval("x = Timeout.timeout(nil) { 7 }", "x"), Object::Int(7));
+    assert_eq!(eval("x = Timeout.timeout(0) { 8 }", "x"), Object::Int(8));
+}
+
+#[test]
+fn a_runaway_loop_raises_timeout_error() {
+    let mut vm = VirtualMachine::new();
+    let error = run(
+        &mut vm,
+        "Timeout.timeout(0.02) do\n  while true\n  end\nend",
+    )
+    .err()
+    .unwrap();
+    assert!(error.to_string().contains(TIMEOUT_EXCEPTION), "{}", error);
+    assert!(!error.is_interrupt());
+}
+
+#[test]
+fn a_timeout_cuts_a_sleep_short() {
+    let started = Instant::now();
+    let source = "x = :slept\nbegin\n  Timeout.timeout(0.02) do\n    sleep(5)\n  end\nrescue Timeout::Error => e\n  x = e.message\nend";
+    assert_eq!(eval(source, "x"), Object::string("execution expired"));
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[test]
+fn timeout_error_is_a_runtime_error() {
+    let source = "x = nil\nbegin\n  Timeout.timeout(0.01) { sleep(1) }\nrescue RuntimeError\n  x = :rescued\nend\n\
+                  scoped = Timeout::Error";
+    assert_eq!(
+        eval(source, "x"),
+        Object::Symbol("rescued".to_string().into())
+    );
+    match eval(source, "scoped") {
+        Object::Class(class) => {
+            assert_eq!(class.name(), TIMEOUT_EXCEPTION);
+            assert_eq!(class.superclass().unwrap().name(), "RuntimeError");
+        }
+        other => panic!("expected a class, got {:?}", other),
+    }
+}
+
+#[test]
+fn a_rescued_timeout_lets_the_block_finish() {
+    let source = "x = Timeout.timeout(0.01) do\n  begin\n    sleep(1)\n  rescue Timeout::Error\n  end\n  sleep(0.02)\n  :finished\nend";
+    assert_eq!(
+        eval(source, "x"),
+        Object::Symbol("finished".to_string().into())
+    );
+}
+
+#[test]
+fn the_outer_of_nested_timeouts_can_expire_first() {
+    let source = "x = nil\nbegin\n  Timeout.timeout(0.02) do\n    Timeout.timeout(10) do\n      sleep(5)\n    end\n  end\nrescue Timeout::Error\n  x = :outer\nend";
+    assert_eq!(
+        eval(source, "x"),
+        Object::Symbol("outer".to_string().into())
+    );
+}
+
+#[test]
+fn a_negative_limit_is_an_error() {
+    let mut vm = VirtualMachine::new();
+    let error = run(&mut vm, "Timeout.timeout(-1) { 1 }").err().unwrap();
+    assert_eq!(error.code(), Some("E0105"));
+    assert!(
+        error
+            .to_string()
+            .contains("timeout: seconds must be a non-negative number or nil, got -1"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn an_invalid_limit_raises_a_rescuable_argument_error() {
+    let source = "x = []
+[-1, \"soon\"].each do |limit|
+  begin
+    Timeout.timeout(limit) { 1 }
+  rescue ArgumentError => e
+    x.push(e.message)
+  end
+end";
+    assert_eq!(
+        eval(source, "x"),
+        Object::array(vec![
+            Object::string("timeout: seconds must be a non-negative number or nil, got -1"),
+            Object::string("timeout: seconds must be a non-negative number or nil, got String"),
+        ])
+    );
+}
+
+// ============================================================================
+// retry_on
+// ============================================================================
+
+#[test]
+fn retry_on_runs_the_block_again_until_it_succeeds() {
+    let source = "tries = []\nx = retry_on(IOError, attempts: 4, backoff: 0) do\n  tries.push(1)\n  if tries.length < 3\n    raise IOError, \"flaky\"\n  end\n  :ok\nend\nn = tries.length";
+    assert_eq!(eval(source, "x"), Object::Symbol("ok".to_string().into()));
+    assert_eq!(eval(source, "n"), Object::Int(3));
+}
+
+#[test]
+fn retry_on_reraises_after_the_last_attempt() {
+    let source = "tries = []\nx = nil\nbegin\n  retry_on(IOError, attempts: 2, backoff: 0) do\n    tries.push(1)\n    raise IOError, \"down\"\n  end\nrescue IOError => e\n  x = e.message\nend\nn = tries.length";
+    assert_eq!(eval(source, "x"), Object::string("down"));
+    assert_eq!(eval(source, "n"), Object::Int(2));
+}
+
+#[test]
+fn retry_on_tries_three_times_by_default() {
+    let source = "tries = []\nbegin\n  retry_on(IOError, backoff: 0) do\n    tries.push(1)\n    raise IOError, \"down\"\n  end\nrescue IOError\nend\nn = tries.length";
+    assert_eq!(eval(source, "n"), Object::Int(3));
+}
+
+#[test]
+fn retry_on_only_retries_matching_errors() {
+    let source = "tries = []\nbegin\n  retry_on(IOError, backoff: 0) do\n    tries.push(1)\n    raise TypeError, \"bug\"\n  end\nrescue TypeError\nend\nn = tries.length";
+    assert_eq!(eval(source, "n"), Object::Int(1));
+}
+
+#[test]
+fn retry_on_waits_longer_before_each_retry() {
+    let started = Instant::now();
+    let source = "tries = []\nbegin\n  retry_on(IOError, attempts: 3, backoff: 0.02) do\n    tries.push(1)\n    raise IOError, \"down\"\n  end\nrescue IOError\nend";
+    eval(source, "tries");
+    // 0.02 seconds before the second attempt and 0.04 before the third
+    assert!(started.elapsed() >= Duration::from_millis(60));
+}
+
+#[test]
+fn retry_on_retries_timeouts() {
+    let source = "tries = []\nx = retry_on(Timeout::Error, backoff: 0) do\n  tries.push(1)\n  Timeout.timeout(0.01) do\n    if tries.length < 2\n      sleep(1)\n    end\n    :fast\n  end\nend";
+    assert_eq!(eval(source, "x"), Object::Symbol("fast".to_string().into()));
+}
+
+#[test]
+fn retry_on_rejects_unknown_and_invalid_options() {
+    let mut vm = VirtualMachine::new();
+    for (source, message) in [
+        ("retry_on(IOError, tries: 2) { 1 }", "unknown option tries"),
+        (
+            "retry_on(IOError, attempts: 0) { 1 }",
+            "attempts must be a positive Int",
+        ),
+        (
+            "retry_on(IOError, backoff: -1) { 1 }",
+            "backoff must be a number of seconds",
+        ),
+        ("retry_on(IOError)", "expects an error class"),
+    ] {
+        let error = run(&mut vm, source).err().unwrap();
+        assert!(error.to_string().contains(message), "{}: {}", source, error);
+    }
+}
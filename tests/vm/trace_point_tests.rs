@@ -0,0 +1,347 @@
+use metorex::error::MetorexError;
+use metorex::lexer::Lexer;
+use metorex::object::Object;
+use metorex::parser::Parser;
+use metorex::vm::{TraceEvent, TraceEventKind, VirtualMachine, VmHooks};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn run(vm: &mut VirtualMachine, source: &str) -> Result<Option<Object>, MetorexError> {
+    let tokens = Lexer::new(source).tokenize();
+    let program = Parser::new(tokens).parse().expect("program should parse");
+    vm.execute_program(&program)
+}
+
+fn run_ok(vm: &mut VirtualMachine, source: &str) -> Object {
+    run(vm, source).unwrap().unwrap_or(Object::Nil)
+}
+
+/// Records every event as "kind line method class".
+#[derive(Default)]
+struct Recorder {
+    events: Vec<String>,
+    bindings: Vec<Vec<String>>,
+}
+
+impl VmHooks for Recorder {
+    fn on_event(&mut self, vm: &VirtualMachine, event: &TraceEvent) {
+        self.events.push(format!(
+            "{} {} {} {}",
+            event.kind.name(),
+            event.position.line,
+            event.method_name.as_deref().unwrap_or("-"),
+            event.class_name.as_deref().unwrap_or("-"),
+        ));
+        if event.kind == TraceEventKind::Call {
+            let mut names = vm.binding().keys();
+            names.retain(|name| name.len() == 1);
+            names.sort();
+            self.bindings.push(names);
+        }
+    }
+}
+
+const PROGRAM: &str = "\
+class Greeter
+  def greet(name)
+    \"hi \" + name
+  end
+end
+def twice(x)
+  x * 2
+end
+g = Greeter.new
+g.greet(\"bob\")
+twice(21)
+";
+
+#[test]
+fn hooks_receive_call_return_line_and_class_events() {
+    let mut vm = VirtualMachine::new();
+    let recorder = Rc::new(RefCell::new(Recorder::default()));
+    vm.add_hooks(recorder.clone());
+    run(&mut vm, PROGRAM).unwrap();
+
+    assert_eq!(
+        recorder.borrow().events,
+        vec![
+            "line 1 - -",
+            "class 1 - Greeter",
+            "line 6 - -",
+            "line 9 - -",
+            "line 10 - -",
+            "call 2 greet Greeter",
+            "line 3 greet Greeter",
+            "return 2 greet Greeter",
+            "line 11 - -",
+            "call 6 twice -",
+            "line 7 twice -",
+            "return 6 twice -",
+        ]
+    );
+    assert_eq!(recorder.borrow().bindings, vec![vec!["g"], vec!["g", "x"]]);
+}
+
+#[test]
+fn hooks_receive_raise_events_with_the_exception() {
+    struct Raises(Vec<Object>);
+    impl VmHooks for Raises {
+        fn on_event(&mut self, _vm: &VirtualMachine, event: &TraceEvent) {
+            if let Some(exception) = &event.raised_exception {
+                assert_eq!(event.kind, TraceEventKind::Raise);
+                self.0.push(exception.clone());
+            }
+        }
+    }
+
+    let mut vm = VirtualMachine::new();
+    let raises = Rc::new(RefCell::new(Raises(Vec::new())));
+    vm.add_hooks(raises.clone());
+    run(&mut vm, "begin\n  raise \"boom\"\nrescue => e\n  nil\nend").unwrap();
+
+    let raises = raises.borrow();
+    assert_eq!(raises.0.len(), 1);
+    let Object::Exception(exception) = &raises.0[0] else {
+        panic!("expected an exception");
+    };
+    assert_eq!(exception.borrow().message, "boom");
+}
+
+#[test]
+fn removed_hooks_stop_receiving_events() {
+    let mut vm = VirtualMachine::new();
+    let recorder = Rc::new(RefCell::new(Recorder::default()));
+    let id = vm.add_hooks(recorder.clone());
+    run(&mut vm, "x = 1").unwrap();
+
+    assert!(vm.remove_hooks(id));
+    assert!(!vm.remove_hooks(id));
+    run(&mut vm, "y = 2").unwrap();
+    assert_eq!(recorder.borrow().events, vec!["line 1 - -"]);
+}
+
+#[test]
+fn event_kinds_round_trip_through_their_names() {
+    for kind in TraceEventKind::ALL {
+        assert_eq!(TraceEventKind::from_name(kind.name()), Some(kind));
+    }
+    assert_eq!(TraceEventKind::from_name("c_call"), None);
+}
+
+#[test]
+fn trace_points_report_the_events_they_listen_for() {
+    let mut vm = VirtualMachine::new();
+    let result = run_ok(
+        &mut vm,
+        "seen = []
+def add(a, b)
+  a + b
+end
+tp = TracePoint.new(:call, :return) { |t|
+  seen.push([t.event, t.method_name, t.lineno, t.return_value])
+}
+tp.enable
+add(1, 2)
+tp.disable
+add(3, 4)
+seen",
+    );
+
+    let symbol = |name: &str| Object::Symbol(Rc::new(name.to_string()));
+    assert_eq!(
+        result,
+        Object::array(vec![
+            Object::array(vec![
+                symbol("call"),
+                Object::string("add"),
+                Object::Int(2),
+                Object::Nil,
+            ]),
+            Object::array(vec![
+                symbol("return"),
+                Object::string("add"),
+                Object::Int(2),
+                Object::Int(3),
+            ]),
+        ])
+    );
+}
+
+#[test]
+fn trace_point_blocks_can_read_the_binding() {
+    let mut vm = VirtualMachine::new();
+    let result = run_ok(
+        &mut vm,
+        "seen = []
+def scale(value, factor)
+  value * factor
+end
+TracePoint.new(:call) { |t| seen.push(t.binding.local_variable_get(:factor)) }.enable
+scale(2, 10)
+scale(3, 7)
+seen",
+    );
+    assert_eq!(result, Object::array(vec![Object::Int(10), Object::Int(7)]));
+}
+
+#[test]
+fn trace_points_see_class_and_raise_events() {
+    let mut vm = VirtualMachine::new();
+    let result = run_ok(
+        &mut vm,
+        "seen = []
+tp = TracePoint.new(:class, :raise) { |t|
+  seen.push([t.event, t.class_name, t.raised_exception])
+}
+tp.enable
+class Widget
+end
+begin
+  raise \"boom\"
+rescue => e
+  nil
+end
+tp.disable
+seen",
+    );
+
+    let Object::Array(seen) = result else {
+        panic!("expected an array");
+    };
+    let seen = seen.borrow();
+    assert_eq!(seen.len(), 2);
+    assert_eq!(
+        seen[0],
+        Object::array(vec![
+            Object::Symbol(Rc::new("class".to_string())),
+            Object::string("Widget"),
+            Object::Nil,
+        ])
+    );
+    let Object::Array(raise) = &seen[1] else {
+        panic!("expected an array");
+    };
+    let raise = raise.borrow();
+    assert_eq!(raise[0], Object::Symbol(Rc::new("raise".to_string())));
+    assert_eq!(raise[1], Object::Nil);
+    let Object::Exception(exception) = &raise[2] else {
+        panic!("expected an exception");
+    };
+    assert_eq!(exception.borrow().message, "boom");
+}
+
+#[test]
+fn enable_with_a_block_traces_only_while_it_runs() {
+    let mut vm = VirtualMachine::new();
+    let result = run_ok(
+        &mut vm,
+        "lines = []
+tp = TracePoint.new(:line) { |t| lines.push(t.lineno) }
+tp.enable {
+  a = 1
+  b = 2
+}
+c = 3
+[lines, tp.enabled?]",
+    );
+    assert_eq!(
+        result,
+        Object::array(vec![
+            Object::array(vec![Object::Int(4), Object::Int(5)]),
+            Object::Bool(false),
+        ])
+    );
+}
+
+#[test]
+fn enable_and_disable_report_the_previous_state() {
+    let mut vm = VirtualMachine::new();
+    let result = run_ok(
+        &mut vm,
+        "tp = TracePoint.new(:call) { |t| nil }
+[tp.enabled?, tp.enable, tp.enable, tp.enabled?, tp.disable, tp.disable]",
+    );
+    assert_eq!(
+        result,
+        Object::array(vec![
+            Object::Bool(false),
+            Object::Bool(false),
+            Object::Bool(true),
+            Object::Bool(true),
+            Object::Bool(true),
+            Object::Bool(false),
+        ])
+    );
+}
+
+#[test]
+fn trace_point_without_events_listens_for_everything() {
+    let mut vm = VirtualMachine::new();
+    let result = run_ok(
+        &mut vm,
+        "kinds = []
+tp = TracePoint.new { |t| kinds.push(t.event) }
+tp.enable
+x = 1
+tp.disable
+kinds",
+    );
+    let symbol = |name: &str| Object::Symbol(Rc::new(name.to_string()));
+    assert_eq!(result, Object::array(vec![symbol("line"), symbol("line")]));
+}
+
+#[test]
+fn event_readers_raise_outside_the_block() {
+    let mut vm = VirtualMachine::new();
+    let error = run(&mut vm, "TracePoint.new(:line) { |t| nil }.lineno")
+        .err()
+        .unwrap();
+    assert!(
+        error
+            .to_string()
+            .contains("TracePoint#lineno is only available inside its block")
+    );
+}
+
+#[test]
+fn trace_point_rejects_unknown_events_and_missing_blocks() {
+    let mut vm = VirtualMachine::new();
+    let error = run(&mut vm, "TracePoint.new(:c_call) { |t| nil }")
+        .err()
+        .unwrap();
+    assert!(
+        error
+            .to_string()
+            .contains("unknown TracePoint event :c_call")
+    );
+
+    let error = run(&mut vm, "TracePoint.new(:call)").err().unwrap();
+    assert!(
+        error
+            .to_string()
+            .contains("TracePoint.new requires a block")
+    );
+}
+
+#[test]
+fn errors_in_a_trace_point_block_propagate() {
+    let mut vm = VirtualMachine::new();
+    let error = run(
+        &mut vm,
+        "TracePoint.new(:line) { |t| raise \"in tracer\" }.enable\nx = 1",
+    )
+    .err()
+    .unwrap();
+    assert!(error.to_string().contains("in tracer"));
+}
+
+#[test]
+fn reserved_words_can_be_symbols() {
+    let mut vm = VirtualMachine::new();
+    let result = run_ok(&mut vm, "[:return, :class, :raise]");
+    let symbol = |name: &str| Object::Symbol(Rc::new(name.to_string()));
+    assert_eq!(
+        result,
+        Object::array(vec![symbol("return"), symbol("class"), symbol("raise")])
+    );
+}
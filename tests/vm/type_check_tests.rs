@@ -0,0 +1,136 @@
+// Tests for call-time checking of type annotations (`check_types`)
+
+use crate::common;
+use metorex::object::Object;
+use metorex::vm::{VirtualMachine, VmOptions};
+
+const DEFINITIONS: &str = "\
+def add(x: Int, y: Int) -> Int
+  x + y
+end
+
+def describe(x: Int?) -> String
+  x.to_s
+end
+
+class Animal
+end
+
+class Dog < Animal
+  def speak(times: Int) -> String
+    \"woof\" + times.to_s
+  end
+
+  def broken() -> Int
+    \"not an int\"
+  end
+end
+
+def pet(a: Animal) -> Animal
+  a
+end
+";
+
+fn checked_vm() -> VirtualMachine {
+    let options = VmOptions {
+        check_types: true,
+        ..VmOptions::default()
+    };
+    VirtualMachine::with_options(options)
+}
+
+fn run(vm: &mut VirtualMachine, source: &str) -> Result<(), String> {
+    common::run(vm, &format!("{}{}", DEFINITIONS, source))
+}
+
+fn eval(source: &str) -> Object {
+    let mut vm = checked_vm();
+    run(&mut vm, &format!("result = {}", source)).unwrap();
+    vm.environment().get("result").unwrap()
+}
+
+fn eval_error(source: &str) -> String {
+    let mut vm = checked_vm();
+    run(&mut vm, source).unwrap_err()
+}
+
+#[test]
+fn test_check_types_is_off_by_default() {
+    assert!(!VmOptions::default().check_types);
+    let mut options = VmOptions::default();
+    options.apply("check_types=on").unwrap();
+    assert!(options.check_types);
+}
+
+#[test]
+fn test_well_typed_calls_succeed() {
+    assert_eq!(eval("add(1, 2)"), Object::Int(3));
+    assert_eq!(eval("describe(nil)"), Object::string("nil"));
+    assert_eq!(eval("Dog.new.speak(2)"), Object::string("woof2"));
+    assert!(matches!(eval("pet(Dog.new)"), Object::Instance(_)));
+}
+
+#[test]
+fn test_argument_mismatch_raises_type_error() {
+    let error = eval_error("add(\"a\", 1)");
+    assert!(
+        error.contains("argument 'x' of 'add' expects Int, got String"),
+        "{}",
+        error
+    );
+    let error = eval_error("pet(3)");
+    assert!(
+        error.contains("argument 'a' of 'pet' expects Animal, got Int"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_method_argument_mismatch_raises_type_error() {
+    let error = eval_error("Dog.new.speak(\"twice\")");
+    assert!(
+        error.contains("argument 'times' of 'speak' expects Int, got String"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_result_mismatch_raises_type_error() {
+    let error = eval_error("Dog.new.broken");
+    assert!(
+        error.contains("'broken' declares result Int but returned String"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_type_errors_can_be_rescued() {
+    let mut vm = checked_vm();
+    let source = "begin\n  add(1.5, 2)\nrescue TypeError => e\n  message = e.message\nend\n";
+    run(&mut vm, source).unwrap();
+    assert_eq!(
+        vm.environment().get("message"),
+        Some(Object::string(
+            "argument 'x' of 'add' expects Int, got Float"
+        ))
+    );
+}
+
+#[test]
+fn test_unknown_annotation_raises_type_error() {
+    let error = eval_error("def f(x: Widget)\n  x\nend\nf(1)\n");
+    assert!(error.contains("Unknown type 'Widget'"), "{}", error);
+}
+
+#[test]
+fn test_annotations_are_ignored_without_check_types() {
+    let mut vm = VirtualMachine::new();
+    run(&mut vm, "result = Dog.new.broken").unwrap();
+    assert_eq!(
+        vm.environment().get("result"),
+        Some(Object::string("not an int"))
+    );
+}
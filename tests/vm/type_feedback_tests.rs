@@ -0,0 +1,146 @@
+use crate::common::run;
+use metorex::object::Object;
+use metorex::vm::{HOT_THRESHOLD, TypeFeedbackStats, VirtualMachine, VmOptions};
+
+fn vm_with_feedback() -> VirtualMachine {
+    let mut vm = VirtualMachine::new();
+    vm.set_type_feedback(true);
+    vm
+}
+
+fn entry(vm: &VirtualMachine, name: &str) -> Object {
+    vm.environment()
+        .get(name)
+        .unwrap_or_else(|| panic!("{} is not defined", name))
+}
+
+const INT_LOOP: &str = r#"
+i = 0
+sum = 0
+while i < 100
+  sum = sum + i % 7
+  i = i + 1
+end
+"#;
+
+#[test]
+fn type_feedback_is_off_by_default() {
+    let mut vm = VirtualMachine::new();
+    assert!(!vm.uses_type_feedback());
+
+    run(&mut vm, INT_LOOP).unwrap();
+    assert_eq!(vm.type_feedback_stats(), TypeFeedbackStats::default());
+}
+
+#[test]
+fn opt_level_two_enables_type_feedback() {
+    let options = VmOptions {
+        optimization_level: 2,
+        ..VmOptions::default()
+    };
+    assert!(VirtualMachine::with_options(options).uses_type_feedback());
+
+    let options = VmOptions {
+        optimization_level: 1,
+        ..VmOptions::default()
+    };
+    assert!(!VirtualMachine::with_options(options).uses_type_feedback());
+}
+
+#[test]
+fn hot_int_operators_take_the_fast_path() {
+    let mut vm = vm_with_feedback();
+    run(&mut vm, INT_LOOP).unwrap();
+
+    assert_eq!(entry(&vm, "sum"), Object::Int(295));
+    let stats = vm.type_feedback_stats();
+    // `i < 100`, `i % 7`, `sum + ...` and `i + 1`
+    assert_eq!(stats.specializations, 4);
+    assert_eq!(stats.deoptimizations, 0);
+    assert!(stats.fast_path_hits > 300, "{:?}", stats);
+}
+
+#[test]
+fn cold_operators_are_not_specialized() {
+    let mut vm = vm_with_feedback();
+    // The condition runs once more than the body
+    let source = format!("i = 0\nwhile i < {}\n  i = i + 1\nend", HOT_THRESHOLD - 2);
+    run(&mut vm, &source).unwrap();
+
+    assert_eq!(vm.type_feedback_stats().specializations, 0);
+}
+
+#[test]
+fn other_operand_types_deoptimize_and_keep_their_semantics() {
+    let mut vm = vm_with_feedback();
+    run(
+        &mut vm,
+        r#"
+def add(a, b)
+  a + b
+end
+i = 0
+while i < 20
+  add(i, 1)
+  i = i + 1
+end
+f = add(1.5, 1)
+s = add("a", "b")
+n = add(2, 3)
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(entry(&vm, "f"), Object::Float(2.5));
+    assert_eq!(entry(&vm, "s"), Object::string("ab"));
+    assert_eq!(entry(&vm, "n"), Object::Int(5));
+    assert_eq!(vm.type_feedback_stats().deoptimizations, 1);
+}
+
+#[test]
+fn specialized_division_matches_the_general_path() {
+    let mut vm = vm_with_feedback();
+    run(
+        &mut vm,
+        r#"
+def div(a, b)
+  a / b
+end
+i = 1
+while i < 20
+  div(i * 2, 2)
+  i = i + 1
+end
+exact = div(9, 3)
+inexact = div(7, 2)
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(entry(&vm, "exact"), Object::Int(3));
+    assert_eq!(entry(&vm, "inexact"), Object::Float(3.5));
+    assert_eq!(vm.type_feedback_stats().deoptimizations, 0);
+}
+
+#[test]
+fn division_by_zero_still_raises_after_specialization() {
+    let mut vm = vm_with_feedback();
+    let err = run(
+        &mut vm,
+        r#"
+def rem(a, b)
+  a % b
+end
+i = 1
+while i < 20
+  rem(10, i)
+  i = i + 1
+end
+rem(10, 0)
+"#,
+    )
+    .unwrap_err();
+
+    assert!(err.contains("Division by zero"), "{}", err);
+    assert_eq!(vm.type_feedback_stats().deoptimizations, 1);
+}
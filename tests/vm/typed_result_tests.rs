@@ -0,0 +1,163 @@
+use crate::common::temp_dir;
+use metorex::error::MetorexError;
+use metorex::object::{ConversionError, FromMetorex, Object, field};
+use metorex::vm::VirtualMachine;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+/// Write `source` to a script in a fresh temp dir
+fn script(name: &str, source: &str) -> PathBuf {
+    let dir = temp_dir(&format!("typed_{}", name));
+    let path = dir.join("config.mx");
+    fs::write(&path, source).unwrap();
+    path
+}
+
+fn eval<T: FromMetorex>(name: &str, source: &str) -> Result<T, MetorexError> {
+    let path = script(name, source);
+    let result = VirtualMachine::new().execute_file_typed::<T>(&path);
+    fs::remove_dir_all(path.parent().unwrap()).ok();
+    result
+}
+
+#[derive(Debug, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+    tags: Vec<String>,
+    timeout: Option<f64>,
+}
+
+impl FromMetorex for Server {
+    fn from_metorex(value: &Object) -> Result<Self, ConversionError> {
+        Ok(Server {
+            host: field(value, "host")?,
+            port: field(value, "port")?,
+            tags: field(value, "tags")?,
+            timeout: field(value, "timeout")?,
+        })
+    }
+}
+
+#[test]
+fn scalars_convert_from_the_final_value() {
+    assert_eq!(eval::<i64>("int", "x = 21\nx * 2").unwrap(), 42);
+    assert_eq!(eval::<f64>("float", "1.5 + 1").unwrap(), 2.5);
+    assert_eq!(eval::<f64>("int_as_float", "3").unwrap(), 3.0);
+    assert!(eval::<bool>("bool", "1 < 2").unwrap());
+    assert_eq!(eval::<String>("string", "\"a\" + \"b\"").unwrap(), "ab");
+    assert_eq!(eval::<Option<i64>>("nil", "nil").unwrap(), None);
+}
+
+#[test]
+fn collections_convert_element_by_element() {
+    assert_eq!(
+        eval::<Vec<i64>>("vec", "[1, 2, 3].map do |n| n * n end").unwrap(),
+        vec![1, 4, 9]
+    );
+    let map =
+        eval::<BTreeMap<String, Vec<String>>>("map", "{\"fruit\" => [\"apple\"], \"veg\" => []}")
+            .unwrap();
+    assert_eq!(map["fruit"], vec!["apple".to_string()]);
+    assert!(map["veg"].is_empty());
+    let counts = eval::<HashMap<String, u32>>("hash", "{\"a\" => 1}").unwrap();
+    assert_eq!(counts["a"], 1);
+}
+
+#[test]
+fn host_types_convert_through_their_fields() {
+    let server = eval::<Server>(
+        "server",
+        "port = 8000 + 80\n{\"host\" => \"localhost\", \"port\" => port, \"tags\" => [\"web\"]}",
+    )
+    .unwrap();
+    assert_eq!(
+        server,
+        Server {
+            host: "localhost".to_string(),
+            port: 8080,
+            tags: vec!["web".to_string()],
+            timeout: None,
+        }
+    );
+}
+
+#[test]
+fn mismatches_name_the_expected_and_found_types() {
+    let error = eval::<i64>("mismatch", "\"42\"").err().unwrap();
+    match error {
+        MetorexError::TypeError {
+            message,
+            expected,
+            found,
+            ..
+        } => {
+            assert!(
+                message.ends_with("config.mx': expected Int, found String"),
+                "{}",
+                message
+            );
+            assert_eq!(expected.as_deref(), Some("Int"));
+            assert_eq!(found.as_deref(), Some("String"));
+        }
+        other => panic!("expected a type error, got {:?}", other),
+    }
+}
+
+#[test]
+fn nested_mismatches_report_where_they_are() {
+    let error = eval::<Server>(
+        "nested",
+        "{\"host\" => \"h\", \"port\" => 80, \"tags\" => [\"a\", 2]}",
+    )
+    .err()
+    .unwrap();
+    assert!(
+        error
+            .to_string()
+            .contains("expected String, found Int at [\"tags\"][1]"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn missing_fields_and_out_of_range_numbers_are_reported() {
+    let missing = eval::<Server>("missing", "{\"host\" => \"h\", \"tags\" => []}")
+        .err()
+        .unwrap();
+    assert!(
+        missing
+            .to_string()
+            .contains("expected Int (u16), found nothing at [\"port\"]"),
+        "{}",
+        missing
+    );
+
+    let range = eval::<u16>("range", "70000").err().unwrap();
+    assert!(
+        range
+            .to_string()
+            .contains("expected Int (u16), found Int 70000"),
+        "{}",
+        range
+    );
+}
+
+#[test]
+fn script_errors_are_returned_unchanged() {
+    let error = eval::<i64>("raises", "raise \"boom\"").err().unwrap();
+    assert!(error.to_string().contains("boom"), "{}", error);
+}
+
+#[test]
+fn conversion_errors_display_their_path() {
+    let error = ConversionError::new("Int", &Object::Nil)
+        .at_index(2)
+        .at_key("ports");
+    assert_eq!(
+        error.to_string(),
+        "expected Int, found Nil at [\"ports\"][2]"
+    );
+}
@@ -0,0 +1,191 @@
+use crate::common::{eval, eval_error, run};
+use metorex::object::Object;
+use metorex::uri::{self, Uri};
+use metorex::vm::VirtualMachine;
+
+fn strings(values: &[&str]) -> Object {
+    Object::array(values.iter().map(|v| Object::string(*v)).collect())
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+#[test]
+fn parse_splits_every_component() {
+    let parsed = Uri::parse("HTTPS://bob:pw@example.com:8443/a/b?q=1#top").unwrap();
+    assert_eq!(parsed.scheme.as_deref(), Some("https"));
+    assert_eq!(parsed.userinfo.as_deref(), Some("bob:pw"));
+    assert_eq!(parsed.host.as_deref(), Some("example.com"));
+    assert_eq!(parsed.port, Some(8443));
+    assert_eq!(parsed.path, "/a/b");
+    assert_eq!(parsed.query.as_deref(), Some("q=1"));
+    assert_eq!(parsed.fragment.as_deref(), Some("top"));
+}
+
+#[test]
+fn parse_accepts_relative_references_and_ipv6_hosts() {
+    let relative = Uri::parse("/search?q=x").unwrap();
+    assert_eq!(relative.scheme, None);
+    assert_eq!(relative.host, None);
+    assert_eq!(relative.path, "/search");
+
+    let ipv6 = Uri::parse("http://[::1]:3000/").unwrap();
+    assert_eq!(ipv6.host.as_deref(), Some("[::1]"));
+    assert_eq!(ipv6.port, Some(3000));
+}
+
+#[test]
+fn parse_rejects_malformed_uris() {
+    assert!(Uri::parse("http://exa mple.com").is_err());
+    assert!(Uri::parse("http://example.com:99999/").is_err());
+    assert!(Uri::parse("http://example.com:+80/").is_err());
+    assert!(Uri::parse("http://[::1/").is_err());
+}
+
+#[test]
+fn display_omits_the_default_port() {
+    let parsed = Uri::parse("http://example.com:80/x?y#z").unwrap();
+    assert_eq!(parsed.to_string(), "http://example.com/x?y#z");
+    assert_eq!(parsed.effective_port(), Some(80));
+    let custom = Uri::parse("http://example.com:8080").unwrap();
+    assert_eq!(custom.to_string(), "http://example.com:8080");
+}
+
+#[test]
+fn uri_parse_exposes_components_to_scripts() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"
+u = URI.parse("https://example.com/docs?page=2#intro")
+scheme = u.scheme
+host = u.host
+port = u.port
+path = u.path
+query = u.query
+fragment = u.fragment
+userinfo = u.userinfo
+text = u.to_s
+"#,
+    )
+    .unwrap();
+    let get = |name: &str| vm.environment().get(name).unwrap();
+    assert_eq!(get("scheme"), Object::string("https"));
+    assert_eq!(get("host"), Object::string("example.com"));
+    assert_eq!(get("port"), Object::Int(443));
+    assert_eq!(get("path"), Object::string("/docs"));
+    assert_eq!(get("query"), Object::string("page=2"));
+    assert_eq!(get("fragment"), Object::string("intro"));
+    assert_eq!(get("userinfo"), Object::Nil);
+    assert_eq!(
+        get("text"),
+        Object::string("https://example.com/docs?page=2#intro")
+    );
+}
+
+#[test]
+fn uri_query_params_decodes_the_query() {
+    let params = eval(r#"URI.parse("/s?q=a+b&x=%2F").query_params"#);
+    let Object::Dict(params) = params else {
+        panic!("expected a Hash, got {:?}", params);
+    };
+    let params = params.borrow();
+    assert_eq!(params.get("q"), Some(&Object::string("a b")));
+    assert_eq!(params.get("x"), Some(&Object::string("/")));
+}
+
+#[test]
+fn uri_parse_reports_bad_uris() {
+    let err = eval_error(r#"URI.parse("http://a b")"#);
+    assert!(err.contains("URI.parse: bad URI"), "{}", err);
+    assert!(eval_error("URI.parse(5)").contains("String"));
+}
+
+// ============================================================================
+// Encoding
+// ============================================================================
+
+#[test]
+fn encode_www_form_accepts_keywords_hashes_and_pairs() {
+    assert_eq!(
+        eval(r#"URI.encode_www_form(q: "a b&c", page: 2, flag: nil)"#),
+        Object::string("q=a+b%26c&page=2&flag")
+    );
+    assert_eq!(
+        eval(r#"URI.encode_www_form([["a", 1], ["b", "x/y"]])"#),
+        Object::string("a=1&b=x%2Fy")
+    );
+    assert!(eval_error(r#"URI.encode_www_form([["a"]])"#).contains("[key, value] pairs"));
+}
+
+#[test]
+fn decode_www_form_returns_pairs_in_order() {
+    assert_eq!(
+        eval(r#"URI.decode_www_form("b=x+y&a=1;c")"#),
+        Object::array(vec![
+            strings(&["b", "x y"]),
+            strings(&["a", "1"]),
+            strings(&["c", ""]),
+        ])
+    );
+}
+
+#[test]
+fn component_encoding_round_trips() {
+    assert_eq!(
+        eval(r#"URI.encode_uri_component("a b/é~")"#),
+        Object::string("a%20b%2F%C3%A9~")
+    );
+    assert_eq!(
+        eval(r#"URI.decode_uri_component("a%20b+%2F")"#),
+        Object::string("a b+/")
+    );
+    assert_eq!(
+        eval(r#"URI.encode_www_form_component("1 + 1 * 2")"#),
+        Object::string("1+%2B+1+*+2")
+    );
+    assert_eq!(
+        eval(r#"URI.decode_www_form_component("1+%2B+1")"#),
+        Object::string("1 + 1")
+    );
+}
+
+#[test]
+fn decoding_rejects_bad_escapes() {
+    assert!(uri::decode_component("%2").is_err());
+    assert!(uri::decode_component("%+1").is_err());
+    assert!(uri::decode_component("%FF").is_err());
+    assert!(eval_error(r#"URI.decode_uri_component("%zz")"#).contains("invalid %-encoding"));
+}
+
+#[test]
+fn malformed_input_raises_a_rescuable_argument_error() {
+    let mut vm = VirtualMachine::new();
+    run(
+        &mut vm,
+        r#"begin
+  URI.parse("http://a b")
+rescue ArgumentError => e
+  parse_message = e.message
+end
+begin
+  URI.decode_www_form_component("%zz")
+rescue ArgumentError => e
+  decode_message = e.message
+end"#,
+    )
+    .unwrap();
+    let parse_message = vm.environment().get("parse_message").unwrap().to_string();
+    assert!(
+        parse_message.contains("URI.parse: bad URI"),
+        "{}",
+        parse_message
+    );
+    let decode_message = vm.environment().get("decode_message").unwrap().to_string();
+    assert!(
+        decode_message.contains("invalid %-encoding"),
+        "{}",
+        decode_message
+    );
+}
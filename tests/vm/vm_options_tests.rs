@@ -0,0 +1,151 @@
+use crate::common::run;
+use metorex::object::Object;
+use metorex::vm::{DEFAULT_MAX_STACK_DEPTH, VirtualMachine, VmOptions};
+
+fn options(opts: &str) -> VmOptions {
+    let mut options = VmOptions::default();
+    options.apply_all(opts).unwrap();
+    options
+}
+
+const RECURSE: &str =
+    "def down(n)\n  if n == 0\n    return 0\n  end\n  return 1 + down(n - 1)\nend\n";
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+#[test]
+fn defaults() {
+    let options = VmOptions::default();
+    assert_eq!(options.max_stack_depth, DEFAULT_MAX_STACK_DEPTH);
+    assert_eq!(options.optimization_level, 0);
+    assert!(!options.warnings);
+    assert_eq!(options.seed, None);
+    assert!(!options.case_insensitive_paths);
+    assert!(options.contracts);
+}
+
+#[test]
+fn apply_all_accepts_spaces_and_commas() {
+    let options = options("stack_depth=64, opt_level=2  warnings=on,seed=9");
+    assert_eq!(options.max_stack_depth, 64);
+    assert_eq!(options.optimization_level, 2);
+    assert!(options.warnings);
+    assert_eq!(options.seed, Some(9));
+}
+
+#[test]
+fn later_settings_override_earlier_ones() {
+    let mut options = options("seed=1 warnings=true");
+    options.apply("seed=none").unwrap();
+    options.apply("warnings=0").unwrap();
+    assert_eq!(options.seed, None);
+    assert!(!options.warnings);
+}
+
+#[test]
+fn invalid_settings_are_rejected() {
+    let mut options = VmOptions::default();
+    for (pair, message) in [
+        ("stack_depth", "expected key=value"),
+        ("stack_depth=0", "positive integer"),
+        ("opt_level=4", "from 0 to 3"),
+        ("warnings=maybe", "true or false"),
+        ("seed=-1", "unsigned integer"),
+        ("turbo=1", "unknown VM option 'turbo'"),
+    ] {
+        let err = options.apply(pair).unwrap_err();
+        assert!(err.contains(message), "{}: {}", pair, err);
+    }
+    assert_eq!(options, VmOptions::default());
+}
+
+#[test]
+fn display_round_trips() {
+    let original = options("stack_depth=100 opt_level=1 seed=5");
+    assert_eq!(
+        original.to_string(),
+        "stack_depth=100 opt_level=1 warnings=false seed=5 ast_cache=false check_types=false contracts=true case_insensitive_paths=false load_path="
+    );
+    assert_eq!(options(&original.to_string()), original);
+}
+
+// ============================================================================
+// Effect on the VM
+// ============================================================================
+
+#[test]
+fn with_options_keeps_the_options() {
+    let vm = VirtualMachine::with_options(options("warnings=on seed=3"));
+    assert!(vm.options().warnings);
+    assert_eq!(vm.options().seed, Some(3));
+}
+
+#[test]
+fn stack_depth_limits_nested_calls() {
+    let mut vm = VirtualMachine::with_options(options("stack_depth=20"));
+    run(&mut vm, &format!("{}x = down(15)", RECURSE)).unwrap();
+    assert_eq!(vm.environment().get("x"), Some(Object::Int(15)));
+
+    let err = run(&mut vm, "y = down(30)").unwrap_err();
+    assert!(
+        err.contains("stack level too deep (more than 20 nested calls)"),
+        "{}",
+        err
+    );
+
+    // The depth unwinds after the error, so shallow calls still work
+    run(&mut vm, "z = down(10)").unwrap();
+    assert_eq!(vm.environment().get("z"), Some(Object::Int(10)));
+}
+
+#[test]
+fn stack_depth_counts_methods_and_blocks() {
+    let mut vm = VirtualMachine::new();
+    vm.set_max_stack_depth(5);
+    let source = "class Counter\n  def down(n)\n    if n == 0\n      return 0\n    end\n    return 1 + self.down(n - 1)\n  end\nend\nc = Counter.new\nc.down(10)";
+    let err = run(&mut vm, source).unwrap_err();
+    assert!(err.contains("stack level too deep"), "{}", err);
+
+    let err = run(
+        &mut vm,
+        "f = lambda do |n|\n  if n == 0\n    0\n  else\n    f.call(n - 1)\n  end\nend\nf.call(10)",
+    )
+    .unwrap_err();
+    assert!(err.contains("stack level too deep"), "{}", err);
+}
+
+#[test]
+fn opt_level_enables_string_interning() {
+    assert!(!VirtualMachine::with_options(options("opt_level=0")).interns_string_literals());
+    assert!(VirtualMachine::with_options(options("opt_level=1")).interns_string_literals());
+}
+
+#[test]
+fn seed_makes_rand_repeatable() {
+    let source = "a = rand\nb = rand(100)\nc = rand(100)";
+    let values = |seed: &str| {
+        let mut vm = VirtualMachine::with_options(options(seed));
+        run(&mut vm, source).unwrap();
+        ["a", "b", "c"].map(|name| vm.environment().get(name).unwrap())
+    };
+
+    let first = values("seed=42");
+    assert_eq!(first, values("seed=42"));
+    assert_ne!(first, values("seed=43"));
+}
+
+#[test]
+fn rand_returns_values_in_range() {
+    let mut vm = VirtualMachine::with_options(options("seed=1"));
+    run(
+        &mut vm,
+        "ok = true\nfor i in 1..200\n  f = rand\n  n = rand(6)\n  if f < 0.0 or f >= 1.0 or n < 0 or n >= 6\n    ok = false\n  end\nend",
+    )
+    .unwrap();
+    assert_eq!(vm.environment().get("ok"), Some(Object::Bool(true)));
+
+    let err = run(&mut vm, "rand(0)").unwrap_err();
+    assert!(err.contains("rand() expects"), "{}", err);
+}
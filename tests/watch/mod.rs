@@ -0,0 +1,3 @@
+// Watch mode tests module
+
+mod watch_tests;
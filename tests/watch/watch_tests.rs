@@ -0,0 +1,103 @@
+// Tests for watch mode: script runs, loaded-file tracking and change detection
+
+use crate::common::temp_dir;
+use metorex::watch::{FileWatcher, run_script};
+use std::fs::{self, File};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// Move a file's modification time forward so the change is always visible
+fn touch_later(path: &PathBuf) {
+    let file = File::options().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::now() + Duration::from_secs(60))
+        .unwrap();
+}
+
+#[test]
+fn test_run_script_reports_loaded_files() {
+    let dir = temp_dir("watch_loaded");
+    let main = dir.join("main.mx");
+    let helper = dir.join("helper.mx");
+    fs::write(&helper, "def helper\n  1\nend\n").unwrap();
+    fs::write(&main, "require_relative \"helper\"\nx = helper()\n").unwrap();
+
+    let run = run_script(&main);
+
+    assert!(run.result.is_ok());
+    assert_eq!(run.files, vec![helper, main]);
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_run_script_failure_still_watches_entry() {
+    let dir = temp_dir("watch_failure");
+    let main = dir.join("main.mx");
+    fs::write(&main, "undefined_name\n").unwrap();
+
+    let run = run_script(&main);
+
+    assert!(run.result.is_err());
+    assert_eq!(run.files, vec![main]);
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_run_script_missing_file() {
+    let dir = temp_dir("watch_missing");
+    let missing = dir.join("nope.mx");
+
+    let run = run_script(&missing);
+
+    assert!(run.result.is_err());
+    assert_eq!(run.files, vec![missing]);
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_file_watcher_detects_modification() {
+    let dir = temp_dir("watch_modified");
+    let a = dir.join("a.mx");
+    let b = dir.join("b.mx");
+    fs::write(&a, "1").unwrap();
+    fs::write(&b, "2").unwrap();
+
+    let watcher = FileWatcher::new(&[a.clone(), b.clone()]);
+    assert!(watcher.changed_files().is_empty());
+
+    touch_later(&b);
+    assert_eq!(watcher.changed_files(), vec![&b]);
+    assert_eq!(watcher.wait_for_change(Duration::from_millis(1)), vec![b]);
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_file_watcher_detects_creation_and_deletion() {
+    let dir = temp_dir("watch_created");
+    let existing = dir.join("existing.mx");
+    let later = dir.join("later.mx");
+    fs::write(&existing, "1").unwrap();
+
+    let watcher = FileWatcher::new(&[existing.clone(), later.clone()]);
+    fs::write(&later, "2").unwrap();
+    fs::remove_file(&existing).unwrap();
+
+    assert_eq!(watcher.changed_files(), vec![&existing, &later]);
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_file_watcher_replaces_watched_set() {
+    let dir = temp_dir("watch_replace");
+    let a = dir.join("a.mx");
+    let b = dir.join("b.mx");
+    fs::write(&a, "1").unwrap();
+    fs::write(&b, "2").unwrap();
+
+    let mut watcher = FileWatcher::new(std::slice::from_ref(&a));
+    watcher.watch(std::slice::from_ref(&b));
+    touch_later(&a);
+
+    assert_eq!(watcher.files(), vec![&b]);
+    assert!(watcher.changed_files().is_empty());
+    fs::remove_dir_all(&dir).ok();
+}